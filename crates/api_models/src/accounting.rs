@@ -0,0 +1,369 @@
+use serde::{Deserialize, Serialize};
+use time::PrimitiveDateTime;
+use utoipa::ToSchema;
+
+use crate::enums;
+
+/// The query parameters for exporting the journal of a period.
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct JournalExportRequest {
+    /// The start of the period for which the journal is exported
+    #[schema(example = "2023-08-01T00:00:00Z")]
+    #[serde(with = "common_utils::custom_serde::iso8601")]
+    pub start_time: PrimitiveDateTime,
+
+    /// The end of the period for which the journal is exported. If not passed, the current time
+    /// is used
+    #[schema(example = "2023-08-31T23:59:59Z")]
+    #[serde(default, with = "common_utils::custom_serde::iso8601::option")]
+    pub end_time: Option<PrimitiveDateTime>,
+
+    /// Only export journal lines in this currency
+    #[schema(value_type = Option<Currency>, example = "USD")]
+    pub currency: Option<enums::Currency>,
+
+    /// The format in which the journal is exported
+    #[serde(default)]
+    pub format: JournalExportFormat,
+}
+
+/// The formats in which a journal can be exported.
+#[derive(
+    Clone, Copy, Debug, Default, Eq, PartialEq, Serialize, Deserialize, strum::Display, ToSchema,
+)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum JournalExportFormat {
+    #[default]
+    Json,
+    Csv,
+}
+
+/// The kind of business event which resulted in a journal entry.
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Eq,
+    PartialEq,
+    Serialize,
+    Deserialize,
+    strum::Display,
+    strum::EnumString,
+    ToSchema,
+)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum JournalEntryType {
+    /// Memo entry recording the amount held on the customer's funds by an authorization
+    Authorization,
+    /// Memo entry releasing the hold of an authorization once it is captured, voided or fails
+    AuthorizationRelease,
+    Sale,
+    Fee,
+    Refund,
+    /// Entry recording the disputed amount withdrawn from the merchant once a dispute is opened
+    Chargeback,
+    /// Entry returning the disputed amount to the merchant once a dispute is won
+    ChargebackReversal,
+    Payout,
+    PayoutFee,
+    PayoutFxMargin,
+}
+
+/// The ledger accounts which are debited or credited by journal lines.
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Eq,
+    PartialEq,
+    Serialize,
+    Deserialize,
+    strum::Display,
+    strum::EnumString,
+    ToSchema,
+)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum LedgerAccount {
+    /// Memo account of the amounts authorized by customers which are yet to be captured
+    AuthorizationHolds,
+    /// Offset of the authorization holds memo account
+    AuthorizationHoldsOffset,
+    /// Funds held by the connector on behalf of the merchant, yet to be settled
+    ConnectorReceivable,
+    /// Income from the sale of goods or services
+    SalesRevenue,
+    /// Surcharges collected from customers
+    FeeIncome,
+    /// Amounts returned to customers
+    Refunds,
+    /// Amounts withdrawn for disputes raised by customers, net of the amounts reinstated
+    Chargebacks,
+    /// Amounts paid out by the merchant
+    PayoutsDisbursed,
+    /// Fees and FX margins charged by connectors for processing payouts
+    ConnectorFees,
+}
+
+/// A single debit or credit line of a journal entry. Each entry has at least one debit and one
+/// credit line, and the amounts of its debit and credit lines always balance.
+#[derive(Clone, Debug, Serialize, ToSchema)]
+pub struct JournalLine {
+    /// The identifier of the journal entry this line belongs to
+    #[schema(example = "lde_3Nf8bWmQpZv1LxR2tKcY")]
+    pub entry_id: String,
+
+    /// The kind of business event which resulted in the journal entry
+    pub entry_type: JournalEntryType,
+
+    /// The identifier of the payment, refund, dispute or payout the entry was generated for
+    #[schema(example = "pay_mbabizu24mvu3mela5njyhpit4")]
+    pub reference_id: String,
+
+    /// The ledger account affected by this line
+    pub account: LedgerAccount,
+
+    /// The amount debited to the account, in the lowest denomination of the currency
+    #[schema(example = 6540)]
+    pub debit: i64,
+
+    /// The amount credited to the account, in the lowest denomination of the currency
+    #[schema(example = 0)]
+    pub credit: i64,
+
+    /// The currency of the amounts
+    #[schema(value_type = Currency, example = "USD")]
+    pub currency: enums::Currency,
+
+    /// The connector through which the funds were moved
+    #[schema(example = "stripe")]
+    pub connector: Option<String>,
+
+    /// The time at which the entry was posted
+    #[schema(example = "2022-09-10T10:11:12Z")]
+    #[serde(with = "common_utils::custom_serde::iso8601")]
+    pub posted_at: PrimitiveDateTime,
+}
+
+#[derive(Clone, Debug, Serialize, ToSchema)]
+pub struct JournalExportResponse {
+    /// The start of the exported period
+    #[serde(with = "common_utils::custom_serde::iso8601")]
+    pub start_time: PrimitiveDateTime,
+
+    /// The end of the exported period
+    #[serde(with = "common_utils::custom_serde::iso8601")]
+    pub end_time: PrimitiveDateTime,
+
+    /// The number of journal lines in the export
+    pub total_count: usize,
+
+    /// The journal lines of the period, ordered by the time at which they were posted
+    pub lines: Vec<JournalLine>,
+}
+
+/// The constraints that are applicable when listing the entries of the ledger of a merchant.
+#[derive(Clone, Debug, Default, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct LedgerEntryListConstraints {
+    /// Only list the entries of this payment, refund, dispute or payout
+    pub reference_id: Option<String>,
+
+    /// Only list the entries which debit or credit this account
+    pub account: Option<LedgerAccount>,
+
+    /// Only list the entries in this currency
+    #[schema(value_type = Option<Currency>, example = "USD")]
+    pub currency: Option<enums::Currency>,
+
+    /// Only list the entries of funds moved through this connector
+    pub connector: Option<String>,
+
+    /// Only list the entries posted at or after this time
+    #[serde(default, with = "common_utils::custom_serde::iso8601::option")]
+    pub start_time: Option<PrimitiveDateTime>,
+
+    /// Only list the entries posted at or before this time
+    #[serde(default, with = "common_utils::custom_serde::iso8601::option")]
+    pub end_time: Option<PrimitiveDateTime>,
+
+    /// The maximum number of entries to include in the response
+    pub limit: Option<i64>,
+}
+
+/// A double-entry posting of the ledger, which moves an amount from the credit account to the
+/// debit account.
+#[derive(Clone, Debug, Serialize, ToSchema)]
+pub struct LedgerEntryResponse {
+    /// The identifier of the ledger entry
+    #[schema(example = "lde_3Nf8bWmQpZv1LxR2tKcY")]
+    pub entry_id: String,
+
+    /// The kind of business event which resulted in the entry
+    pub entry_type: JournalEntryType,
+
+    /// The identifier of the payment, refund, dispute or payout the entry was posted for
+    #[schema(example = "pay_mbabizu24mvu3mela5njyhpit4")]
+    pub reference_id: String,
+
+    /// The account debited by the entry
+    pub debit_account: LedgerAccount,
+
+    /// The account credited by the entry
+    pub credit_account: LedgerAccount,
+
+    /// The amount moved by the entry, in the lowest denomination of the currency
+    #[schema(example = 6540)]
+    pub amount: i64,
+
+    /// The currency of the amount
+    #[schema(value_type = Currency, example = "USD")]
+    pub currency: enums::Currency,
+
+    /// The connector through which the funds were moved
+    #[schema(example = "stripe")]
+    pub connector: Option<String>,
+
+    /// The time at which the entry was posted
+    #[schema(example = "2022-09-10T10:11:12Z")]
+    #[serde(with = "common_utils::custom_serde::iso8601")]
+    pub created: PrimitiveDateTime,
+}
+
+#[derive(Clone, Debug, Serialize, ToSchema)]
+pub struct LedgerEntryListResponse {
+    /// The number of entries included in the list
+    pub count: usize,
+
+    /// The entries of the ledger, most recent first
+    pub data: Vec<LedgerEntryResponse>,
+}
+
+/// The query parameters for retrieving the balances of the ledger accounts of a merchant.
+#[derive(Clone, Debug, Default, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct LedgerBalanceRequest {
+    /// Only retrieve the balances in this currency
+    #[schema(value_type = Option<Currency>, example = "USD")]
+    pub currency: Option<enums::Currency>,
+}
+
+/// The balance of a ledger account in a currency.
+#[derive(Clone, Debug, Serialize, ToSchema)]
+pub struct LedgerAccountBalance {
+    /// The ledger account
+    pub account: LedgerAccount,
+
+    /// The currency of the amounts
+    #[schema(value_type = Currency, example = "USD")]
+    pub currency: enums::Currency,
+
+    /// The total amount debited to the account
+    #[schema(example = 6540)]
+    pub total_debit: i64,
+
+    /// The total amount credited to the account
+    #[schema(example = 1000)]
+    pub total_credit: i64,
+
+    /// The balance of the account, which is the total debit less the total credit
+    #[schema(example = 5540)]
+    pub balance: i64,
+}
+
+#[derive(Clone, Debug, Serialize, ToSchema)]
+pub struct LedgerBalanceResponse {
+    /// The balances of the accounts of the merchant, ordered by account and currency
+    pub balances: Vec<LedgerAccountBalance>,
+}
+
+/// A line of the settlement report of a connector, to be reconciled against the ledger.
+#[derive(Clone, Debug, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct SettlementLine {
+    /// The identifier of the payment, refund, dispute or payout which was settled
+    #[schema(example = "pay_mbabizu24mvu3mela5njyhpit4")]
+    pub reference_id: String,
+
+    /// The kind of business event which was settled
+    pub entry_type: JournalEntryType,
+
+    /// The amount settled by the connector, in the lowest denomination of the currency
+    #[schema(example = 6540)]
+    pub amount: i64,
+}
+
+/// The request body for reconciling the settlement report of a connector against the ledger.
+#[derive(Clone, Debug, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct LedgerReconciliationRequest {
+    /// The connector which reported the settlement
+    #[schema(example = "stripe")]
+    pub connector: String,
+
+    /// The currency of the settlement
+    #[schema(value_type = Currency, example = "USD")]
+    pub currency: enums::Currency,
+
+    /// The start of the period covered by the settlement report
+    #[schema(example = "2023-08-01T00:00:00Z")]
+    #[serde(with = "common_utils::custom_serde::iso8601")]
+    pub start_time: PrimitiveDateTime,
+
+    /// The end of the period covered by the settlement report
+    #[schema(example = "2023-08-31T23:59:59Z")]
+    #[serde(with = "common_utils::custom_serde::iso8601")]
+    pub end_time: PrimitiveDateTime,
+
+    /// The lines of the settlement report
+    pub lines: Vec<SettlementLine>,
+}
+
+/// The kind of difference found between the ledger and a settlement report.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ReconciliationDiscrepancyType {
+    /// The amount settled differs from the amount posted to the ledger
+    AmountMismatch,
+    /// The settlement report has a line for which no entry was posted to the ledger
+    MissingInLedger,
+    /// An entry was posted to the ledger in the period, but was not settled by the connector
+    MissingInSettlement,
+}
+
+#[derive(Clone, Debug, Serialize, ToSchema)]
+pub struct ReconciliationDiscrepancy {
+    /// The identifier of the payment, refund, dispute or payout
+    pub reference_id: String,
+
+    /// The kind of business event
+    pub entry_type: JournalEntryType,
+
+    /// The kind of difference found
+    pub discrepancy_type: ReconciliationDiscrepancyType,
+
+    /// The amount posted to the ledger, if any
+    pub ledger_amount: Option<i64>,
+
+    /// The amount settled by the connector, if any
+    pub settlement_amount: Option<i64>,
+}
+
+#[derive(Clone, Debug, Serialize, ToSchema)]
+pub struct LedgerReconciliationResponse {
+    /// The connector which reported the settlement
+    pub connector: String,
+
+    /// The currency of the settlement
+    #[schema(value_type = Currency, example = "USD")]
+    pub currency: enums::Currency,
+
+    /// The number of settlement lines which match the entries of the ledger
+    pub matched_count: usize,
+
+    /// The differences found between the ledger and the settlement report
+    pub discrepancies: Vec<ReconciliationDiscrepancy>,
+}
@@ -34,6 +34,9 @@ pub struct MerchantAccountCreate {
     /// Webhook related details
     pub webhook_details: Option<WebhookDetails>,
 
+    /// Client certificate to be presented to webhook endpoints which require mutual TLS
+    pub webhook_mtls_details: Option<WebhookMtlsDetails>,
+
     /// The routing algorithm to be used for routing payments to desired connectors
     #[schema(value_type = Option<Object>,example = json!({"type": "single", "data": "stripe"}))]
     pub routing_algorithm: Option<serde_json::Value>,
@@ -93,6 +96,12 @@ pub struct MerchantAccountCreate {
 
     /// The id of the organization to which the merchant belongs to
     pub organization_id: Option<String>,
+
+    /// The storage region in which the customers and payments of the merchant are stored, being
+    /// one of the regions configured for data residency. They are stored in the default storage if
+    /// not provided. The storage region cannot be changed once the merchant account is created
+    #[schema(max_length = 64, example = "eu")]
+    pub storage_region: Option<String>,
 }
 
 #[derive(Clone, Debug, Deserialize, ToSchema)]
@@ -116,6 +125,9 @@ pub struct MerchantAccountUpdate {
     /// Webhook related details
     pub webhook_details: Option<WebhookDetails>,
 
+    /// Client certificate to be presented to webhook endpoints which require mutual TLS
+    pub webhook_mtls_details: Option<WebhookMtlsDetails>,
+
     /// The routing algorithm to be used for routing payments to desired connectors
     #[schema(value_type = Option<Object>,example = json!({"type": "single", "data": "stripe"}))]
     pub routing_algorithm: Option<serde_json::Value>,
@@ -255,6 +267,13 @@ pub struct MerchantAccountResponse {
 
     ///  A boolean value to indicate if the merchant has recon service is enabled or not, by default value is false
     pub is_recon_enabled: bool,
+
+    /// A boolean value to indicate if a client certificate is configured for delivering webhooks over mutual TLS
+    pub webhook_mtls_enabled: bool,
+
+    /// The storage region in which the customers and payments of the merchant are stored
+    #[schema(example = "eu")]
+    pub storage_region: Option<String>,
 }
 
 #[derive(Clone, Debug, Deserialize, ToSchema, Serialize)]
@@ -414,6 +433,39 @@ pub mod payout_routing_algorithm {
 #[serde(tag = "type", content = "data", rename_all = "snake_case")]
 pub enum RoutingAlgorithm {
     Single(api_enums::RoutableConnectors),
+    /// Payments are routed to the first connector, and automatically retried on the next
+    /// connectors when declined with a retryable error
+    Priority(Vec<api_enums::RoutableConnectors>),
+    /// Payments are split across the connectors according to their weights, the payments of a
+    /// customer being always routed to the same connector
+    VolumeSplit(Vec<ConnectorVolumeSplit>),
+    /// Payments are routed to the eligible connector expected to charge the lowest fee for the
+    /// payment, according to the fee schedules of the connectors. Connectors whose fees for the
+    /// payment are not known are only considered after the others, in the given order. Declined
+    /// payments are automatically retried on the next cheapest connectors, as with `Priority`
+    CostBased(Vec<api_enums::RoutableConnectors>),
+}
+
+impl RoutingAlgorithm {
+    /// Returns the connectors to which payments are routed, in the order of priority
+    pub fn get_connectors(&self) -> Vec<api_enums::RoutableConnectors> {
+        match self {
+            Self::Single(connector) => vec![*connector],
+            Self::Priority(connectors) | Self::CostBased(connectors) => connectors.clone(),
+            Self::VolumeSplit(splits) => splits
+                .iter()
+                .filter(|split| split.split > 0)
+                .map(|split| split.connector)
+                .collect(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct ConnectorVolumeSplit {
+    pub connector: api_enums::RoutableConnectors,
+    /// The weight of the connector, relative to the weights of the other connectors of the split
+    pub split: u8,
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -473,6 +525,100 @@ pub struct PrimaryBusinessDetails {
     pub country: api_enums::CountryAlpha2,
     #[schema(example = "food")]
     pub business: String,
+    /// The merchant category code (ISO 18245) sent to the connectors which accept one (Adyen and Worldpay) for payments made under this business, unless overridden in the connector account metadata
+    #[schema(max_length = 4, example = "5411")]
+    pub merchant_category_code: Option<String>,
+    /// The default statement descriptor name used for payments made under this business, unless overridden in the payment
+    #[schema(max_length = 255, example = "Juspay Router")]
+    pub statement_descriptor_name: Option<String>,
+    /// The default statement descriptor suffix used for payments made under this business, unless overridden in the payment
+    #[schema(max_length = 255, example = "Juspay Router")]
+    pub statement_descriptor_suffix: Option<String>,
+    /// The support contact shared with connectors for payments made under this business
+    pub support_contact: Option<SupportContactDetails>,
+    /// Whether the address on file of the customer is attached to payments made under this business, when the billing or shipping address is not passed in the payment
+    #[schema(default = false, example = true)]
+    pub use_customer_address_on_file: Option<bool>,
+    /// The connectors from which session tokens are fetched when a session is created for payments made under this business
+    #[schema(value_type = Option<SessionTokenStrategy>, default = "parallel", example = "sequential")]
+    pub session_token_strategy: Option<api_enums::SessionTokenStrategy>,
+    /// Whether payments made under this business which are awaiting capture are re-authorized before their authorization expires, for connectors supporting re-authorization
+    #[schema(default = false, example = true)]
+    pub auto_reauthorization_enabled: Option<bool>,
+    /// The branding of the pages hosted by hyperswitch for payments made under this business, such as the page redirecting the customer to the connector
+    pub payment_link_config: Option<PaymentLinkConfig>,
+    /// The template of the receipts of the successful payments made under this business. The receipts are branded with the logo and the primary color of the `payment_link_config` of the business
+    pub receipt_config: Option<ReceiptConfig>,
+    /// The payment method types listed first when listing the payment methods of payments made under this business, in the given order. The other payment method types are ordered by how often they are used by the customer, and by how often payments made with them succeed
+    #[schema(value_type = Option<Vec<PaymentMethodType>>, example = json!(["apple_pay", "credit"]))]
+    pub payment_method_ranking: Option<Vec<api_enums::PaymentMethodType>>,
+    /// The webhook endpoints to which the webhooks about the payments made under this business, and about their refunds and disputes, are posted in addition to the webhook endpoint of the merchant
+    pub webhook_endpoints: Option<Vec<BusinessWebhookEndpoint>>,
+}
+
+#[derive(Clone, Debug, Deserialize, ToSchema, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct BusinessWebhookEndpoint {
+    /// The identifier of the webhook endpoint, unique among the webhook endpoints of all the businesses of the merchant
+    #[schema(max_length = 64, example = "fulfilment")]
+    pub endpoint_id: String,
+    /// The url of the webhook endpoint
+    #[schema(value_type = String, example = "www.ekart.com/fulfilment/webhooks")]
+    pub webhook_url: Secret<String>,
+    /// The events posted to the webhook endpoint. All events are posted when not set
+    #[schema(value_type = Option<Vec<EventType>>, example = json!(["payment_succeeded", "refund_succeeded"]))]
+    pub enabled_events: Option<Vec<api_enums::EventType>>,
+    /// The key with which the webhooks posted to the webhook endpoint are signed. The payment response hash key of the merchant is used when not set. The key is stored encrypted and is never returned; the key of an endpoint is retained when the endpoint is updated without a key
+    #[schema(value_type = Option<String>, max_length = 255)]
+    pub webhook_secret: Option<Secret<String>>,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, ToSchema, Serialize, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct PaymentLinkConfig {
+    /// The URL of the logo of the business, displayed at the top of the page. Must be an HTTPS URL
+    #[schema(value_type = Option<String>, max_length = 255, example = "https://example.com/logo.png")]
+    pub logo: Option<url::Url>,
+    /// The color of the accents of the page, such as the loader and the text, as a hexadecimal RGB code
+    #[schema(example = "#006DF9")]
+    pub primary_color: Option<String>,
+    /// The background color of the page, as a hexadecimal RGB code
+    #[schema(example = "#FFFFFF")]
+    pub background_color: Option<String>,
+    /// The locale in which the text of the page is displayed, as a language tag. One of `en`, `de`, `es`, `fr`, `it`, `nl` or `pt`, optionally followed by a region. Defaults to `en`
+    #[schema(example = "fr-FR")]
+    pub locale: Option<String>,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, ToSchema, Serialize, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct ReceiptConfig {
+    /// The title of the receipts. Defaults to `Receipt`
+    #[schema(max_length = 255, example = "Payment Receipt")]
+    pub title: Option<String>,
+    /// The text displayed above the details of the payment
+    #[schema(max_length = 1024, example = "Thank you for your purchase!")]
+    pub header: Option<String>,
+    /// The text displayed below the details of the payment, such as the terms of the business
+    #[schema(max_length = 1024, example = "Returns are accepted within 30 days.")]
+    pub footer: Option<String>,
+    /// Whether the receipt of a payment is emailed to the customer when the payment is captured, in full or in part, if the email address of the customer is known
+    #[schema(default = false, example = true)]
+    pub email_enabled: Option<bool>,
+}
+
+#[derive(Clone, Debug, Deserialize, ToSchema, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct SupportContactDetails {
+    /// The support email address of the business
+    #[schema(value_type = Option<String>, max_length = 255, example = "support@example.com")]
+    pub email: Option<pii::Email>,
+    /// The support phone number of the business
+    #[schema(value_type = Option<String>, max_length = 255, example = "+1-800-555-0100")]
+    pub phone: Option<Secret<String>>,
+    /// The support website of the business
+    #[schema(max_length = 255, example = "https://example.com/support")]
+    pub url: Option<String>,
 }
 
 #[derive(Clone, Debug, Deserialize, ToSchema, Serialize)]
@@ -505,6 +651,34 @@ pub struct WebhookDetails {
     /// If this property is true, a webhook message is posted whenever a payment fails
     #[schema(example = true)]
     pub payment_failed_enabled: Option<bool>,
+
+    /// If this property is true, webhooks are queued instead of being posted once the webhook endpoint is marked degraded, until the endpoint is verified again and the queued webhooks have been delivered
+    #[schema(example = false)]
+    pub auto_pause_enabled: Option<bool>,
+
+    /// If this property is true, webhooks include the IP ranges from which they are sent, so that the webhook endpoint can validate their source
+    #[schema(example = false)]
+    pub source_validation_enabled: Option<bool>,
+
+    /// The number of times a webhook which was not received by the webhook endpoint is retried, at most 10
+    #[schema(example = 5, maximum = 10)]
+    pub max_retries: Option<u8>,
+
+    /// The delay (in seconds) before the first retry of a webhook, between 10 and 3600. The delay is doubled for every following retry.
+    #[schema(example = 60, minimum = 10, maximum = 3600)]
+    pub retry_initial_delay: Option<u32>,
+}
+
+#[derive(Clone, Debug, Deserialize, ToSchema, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct WebhookMtlsDetails {
+    /// Base64 encoded client certificate (in PEM format) presented to the webhook endpoint
+    #[schema(value_type = String)]
+    pub client_certificate: Secret<String>,
+
+    /// Base64 encoded private key (in PKCS#8 PEM format) of the client certificate
+    #[schema(value_type = String)]
+    pub client_certificate_key: Secret<String>,
 }
 
 #[derive(Debug, Serialize, ToSchema)]
@@ -549,6 +723,9 @@ pub struct MerchantConnectorCreate {
     /// Account details of the Connector. You can specify up to 50 keys, with key names up to 40 characters long and values up to 500 characters long. Useful for storing additional, structured information on an object.
     #[schema(value_type = Option<Object>,example = json!({ "auth_type": "HeaderKey","api_key": "Basic MyVerySecretApiKey" }))]
     pub connector_account_details: Option<pii::SecretSerdeValue>,
+    /// Account details of the Connector to be used for test payments, that is payments created with `livemode` set to false, when the same connector account is used for test and live payments. The `connector_account_details` are then used for live payments
+    #[schema(value_type = Option<Object>,example = json!({ "auth_type": "HeaderKey","api_key": "Basic MyVerySecretTestApiKey" }))]
+    pub test_connector_account_details: Option<pii::SecretSerdeValue>,
     /// A boolean value to indicate if the connector is in Test mode. By default, its value is false.
     #[schema(default = false, example = false)]
     pub test_mode: Option<bool>,
@@ -609,6 +786,9 @@ pub struct MerchantConnectorCreate {
         }
     }))]
     pub connector_webhook_details: Option<MerchantConnectorWebhookDetails>,
+
+    /// Client certificate to be presented to the connector, for connectors which require mutual TLS
+    pub connector_mtls_details: Option<ConnectorMtlsDetails>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
@@ -616,6 +796,47 @@ pub struct MerchantConnectorCreate {
 pub struct MerchantConnectorWebhookDetails {
     #[schema(value_type = String, example = "12345678900987654321")]
     pub merchant_secret: Secret<String>,
+    /// An additional secret with which the webhooks of the connector are verified, accepted along with the `merchant_secret` while the secret is being rotated
+    #[schema(value_type = Option<String>, example = "98765432100123456789")]
+    pub additional_secret: Option<Secret<String>>,
+}
+
+/// The request body for rotating the webhook secret of a Merchant Connector
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct MerchantConnectorWebhookSecretRotateRequest {
+    /// The new secret with which the webhooks of the connector are verified
+    #[schema(value_type = String, example = "98765432100123456789")]
+    pub merchant_secret: Secret<String>,
+
+    /// Whether the secret being replaced is still accepted for verifying webhooks, until the next rotation. Rotate the secret again with this set to `false` once the new secret is configured on the connector dashboard
+    #[schema(default = true, example = true)]
+    pub keep_previous_secret: Option<bool>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct MerchantConnectorWebhookSecretRotateResponse {
+    /// The identifier for the Merchant Account
+    #[schema(max_length = 255, example = "y3oqhf46pyzuxjbcn2giaqnb44")]
+    pub merchant_id: String,
+    /// Unique ID of the connector
+    #[schema(example = "mca_5apGeP94tMts6rg3U3kR")]
+    pub merchant_connector_id: String,
+    /// Whether the previous secret is still accepted for verifying webhooks
+    #[schema(example = true)]
+    pub previous_secret_accepted: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ConnectorMtlsDetails {
+    /// Base64 encoded client certificate (in PEM format) presented to the connector
+    #[schema(value_type = String)]
+    pub client_certificate: Secret<String>,
+
+    /// Base64 encoded private key (in PKCS#8 PEM format) of the client certificate
+    #[schema(value_type = String)]
+    pub client_certificate_key: Secret<String>,
 }
 
 /// Response of creating a new Merchant Connector for the merchant account."
@@ -639,6 +860,9 @@ pub struct MerchantConnectorResponse {
     /// Account details of the Connector. You can specify up to 50 keys, with key names up to 40 characters long and values up to 500 characters long. Useful for storing additional, structured information on an object.
     #[schema(value_type = Option<Object>,example = json!({ "auth_type": "HeaderKey","api_key": "Basic MyVerySecretApiKey" }))]
     pub connector_account_details: pii::SecretSerdeValue,
+    /// Account details of the Connector used for test payments, that is payments created with `livemode` set to false, when the same connector account is used for test and live payments. The `connector_account_details` are then used for live payments
+    #[schema(value_type = Option<Object>,example = json!({ "auth_type": "HeaderKey","api_key": "Basic MyVerySecretTestApiKey" }))]
+    pub test_connector_account_details: Option<pii::SecretSerdeValue>,
     /// A boolean value to indicate if the connector is in Test mode. By default, its value is false.
     #[schema(default = false, example = false)]
     pub test_mode: Option<bool>,
@@ -703,6 +927,16 @@ pub struct MerchantConnectorResponse {
         }
     }))]
     pub connector_webhook_details: Option<MerchantConnectorWebhookDetails>,
+
+    /// The time at which the client certificate presented to the connector expires, if a client certificate is configured
+    #[schema(value_type = Option<PrimitiveDateTime>, example = "2024-09-10T10:11:12Z")]
+    #[serde(default, with = "common_utils::custom_serde::iso8601::option")]
+    pub client_certificate_expires_at: Option<time::PrimitiveDateTime>,
+
+    /// The outcome of verifying the account details with the connector, returned when the Merchant Connector is created or updated and its account details were verified. Account details whose verification is `unknown` are accepted, unless the merchant has opted to reject them
+    #[schema(value_type = Option<ConnectorVerificationStatus>, example = "valid")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub verification_status: Option<ConnectorVerificationStatus>,
 }
 
 /// Create a new Merchant Connector for the merchant account. The connector could be a payment processor / facilitator / acquirer or specialized services like Fraud / Accounting etc."
@@ -717,6 +951,10 @@ pub struct MerchantConnectorUpdate {
     #[schema(value_type = Option<Object>,example = json!({ "auth_type": "HeaderKey","api_key": "Basic MyVerySecretApiKey" }))]
     pub connector_account_details: Option<pii::SecretSerdeValue>,
 
+    /// Account details of the Connector to be used for test payments, that is payments created with `livemode` set to false, when the same connector account is used for test and live payments. The `connector_account_details` are then used for live payments
+    #[schema(value_type = Option<Object>,example = json!({ "auth_type": "HeaderKey","api_key": "Basic MyVerySecretTestApiKey" }))]
+    pub test_connector_account_details: Option<pii::SecretSerdeValue>,
+
     /// A boolean value to indicate if the connector is in Test mode. By default, its value is false.
     #[schema(default = false, example = false)]
     pub test_mode: Option<bool>,
@@ -772,6 +1010,9 @@ pub struct MerchantConnectorUpdate {
         }
     }))]
     pub connector_webhook_details: Option<MerchantConnectorWebhookDetails>,
+
+    /// Client certificate to be presented to the connector, for connectors which require mutual TLS. The certificate replaces the one configured earlier, if any.
+    pub connector_mtls_details: Option<ConnectorMtlsDetails>,
 }
 
 ///Details of FrmConfigs are mentioned here... it should be passed in payment connector create api call, and stored in merchant_connector_table
@@ -869,6 +1110,59 @@ pub struct MerchantConnectorDeleteResponse {
     pub deleted: bool,
 }
 
+/// The request body for enabling or disabling a payment method type of a Merchant Connector
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct PaymentMethodTypeToggleRequest {
+    /// The payment method to which the payment method type belongs
+    #[schema(value_type = PaymentMethod, example = "card")]
+    pub payment_method: common_enums::PaymentMethod,
+
+    /// The payment method type to be enabled or disabled
+    #[schema(value_type = PaymentMethodType, example = "credit")]
+    pub payment_method_type: api_enums::PaymentMethodType,
+
+    /// Whether the payment method type is to be enabled for the connector
+    #[schema(example = false)]
+    pub enabled: bool,
+}
+
+/// The outcome of verifying the account details of a Merchant Connector with the connector
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ConnectorVerificationStatus {
+    /// The connector accepted the account details
+    Valid,
+    /// The connector rejected the account details
+    Invalid,
+    /// The account details cannot be verified for the connector
+    NotSupported,
+    /// The connector could not be reached, or failed to process the verification request
+    Unknown,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct MerchantConnectorVerifyResponse {
+    /// The identifier for the Merchant Account
+    #[schema(max_length = 255, example = "y3oqhf46pyzuxjbcn2giaqnb44")]
+    pub merchant_id: String,
+    /// Unique ID of the connector
+    #[schema(example = "mca_5apGeP94tMts6rg3U3kR")]
+    pub merchant_connector_id: String,
+    /// Name of the Connector
+    #[schema(example = "stripe")]
+    pub connector_name: String,
+    /// Whether the connector accepted the account details of the Merchant Connector
+    #[schema(example = "valid")]
+    pub status: ConnectorVerificationStatus,
+    /// The error code returned by the connector, if the account details could not be verified
+    #[schema(example = "E0001")]
+    pub error_code: Option<String>,
+    /// The error message returned by the connector, if the account details could not be verified
+    #[schema(example = "Authentication failed")]
+    pub error_message: Option<String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct ToggleKVResponse {
     /// The identifier for the Merchant Account
@@ -886,6 +1180,166 @@ pub struct ToggleKVRequest {
     pub kv_enabled: bool,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct MerchantKeyStoreResponse {
+    /// The identifier for the Merchant Account
+    #[schema(max_length = 255, example = "y3oqhf46pyzuxjbcn2giaqnb44")]
+    pub merchant_id: String,
+    /// The version of the master key the merchant encryption key is encrypted with
+    #[schema(example = 1)]
+    pub key_version: i32,
+    /// The time at which the merchant key store was created
+    #[schema(value_type = PrimitiveDateTime, example = "2022-09-10T10:11:12Z")]
+    #[serde(with = "common_utils::custom_serde::iso8601")]
+    pub created_at: time::PrimitiveDateTime,
+    /// The time at which the merchant encryption key was last rotated
+    #[schema(value_type = Option<PrimitiveDateTime>, example = "2022-09-10T10:11:12Z")]
+    #[serde(default, with = "common_utils::custom_serde::iso8601::option")]
+    pub rotated_at: Option<time::PrimitiveDateTime>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct MerchantAllowedDomainsUpdateRequest {
+    /// The domains on which return urls and webhook urls of the merchant must be hosted. Subdomains of the listed domains are allowed as well. An empty list removes the restriction
+    #[schema(example = json!(["example.com"]))]
+    pub allowed_domains: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct MerchantAllowedDomainsResponse {
+    /// The identifier for the Merchant Account
+    #[schema(max_length = 255, example = "y3oqhf46pyzuxjbcn2giaqnb44")]
+    pub merchant_id: String,
+    /// The domains on which return urls and webhook urls of the merchant must be hosted, empty if any domain is allowed
+    #[schema(example = json!(["example.com"]))]
+    pub allowed_domains: Vec<String>,
+}
+
+/// A range of card BINs, matched against the first six digits of the card number.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct CardBinRange {
+    /// The first BIN in the range
+    #[schema(min_length = 6, max_length = 6, example = "400000")]
+    pub start: String,
+    /// The last BIN in the range, inclusive
+    #[schema(min_length = 6, max_length = 6, example = "499999")]
+    pub end: String,
+}
+
+/// The cards which must not be routed to a connector, identified by their BIN or by the country
+/// in which they were issued.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ConnectorExclusionRule {
+    /// The connector to which the rule applies
+    #[schema(value_type = RoutableConnectors, example = "stripe")]
+    pub connector: api_enums::RoutableConnectors,
+    /// Cards whose BIN falls in any of these ranges are not routed to the connector
+    pub excluded_bin_ranges: Option<Vec<CardBinRange>>,
+    /// Cards issued in any of these countries are not routed to the connector
+    #[schema(value_type = Option<Vec<CountryAlpha2>>, example = json!(["US"]))]
+    pub excluded_issuer_countries: Option<Vec<api_enums::CountryAlpha2>>,
+    /// Only cards issued in these countries are routed to the connector, for example to use a local acquirer for domestic cards only
+    #[schema(value_type = Option<Vec<CountryAlpha2>>, example = json!(["IN"]))]
+    pub allowed_issuer_countries: Option<Vec<api_enums::CountryAlpha2>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ConnectorExclusionRulesUpdateRequest {
+    /// The rules evaluated when routing card payments of the merchant. An empty list removes all the rules
+    pub rules: Vec<ConnectorExclusionRule>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ConnectorExclusionRulesResponse {
+    /// The identifier for the Merchant Account
+    #[schema(max_length = 255, example = "y3oqhf46pyzuxjbcn2giaqnb44")]
+    pub merchant_id: String,
+    /// The rules evaluated when routing card payments of the merchant
+    pub rules: Vec<ConnectorExclusionRule>,
+}
+
+/// The request body for generating a new webhook signing key pair for a Merchant Account
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct WebhookSigningKeyRotateRequest {
+    /// The algorithm of the new key pair, with which outgoing webhooks are signed from now on
+    #[schema(value_type = WebhookSigningAlgorithm, example = "ed25519")]
+    pub algorithm: api_enums::WebhookSigningAlgorithm,
+
+    /// Whether the public key of the key pair being replaced is still published in the JWKS of the merchant for a grace period, so that webhooks signed with it before the rotation can still be verified
+    #[schema(default = true, example = true)]
+    pub keep_previous_key: Option<bool>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct WebhookSigningKeyResponse {
+    /// The identifier of the key, sent as the `kid` of the signature of outgoing webhooks
+    #[schema(example = "whsk_5apGeP94tMts6rg3U3kR")]
+    pub key_id: String,
+    /// The algorithm with which outgoing webhooks are signed using the key
+    #[schema(value_type = WebhookSigningAlgorithm, example = "ed25519")]
+    pub algorithm: api_enums::WebhookSigningAlgorithm,
+    /// The status of the key
+    #[schema(value_type = WebhookSigningKeyStatus, example = "active")]
+    pub status: api_enums::WebhookSigningKeyStatus,
+    /// The public key, as a JSON Web Key
+    #[schema(value_type = Object, example = json!({"kty": "OKP", "crv": "Ed25519", "x": "11qYAYKxCrfVS_7TyWQHOg7hcvPapiMlrwIaaPcHURo", "kid": "whsk_5apGeP94tMts6rg3U3kR", "alg": "EdDSA", "use": "sig"}))]
+    pub public_key: serde_json::Value,
+    /// The time at which the key was generated
+    #[schema(value_type = PrimitiveDateTime, example = "2022-09-10T10:11:12Z")]
+    #[serde(with = "common_utils::custom_serde::iso8601")]
+    pub created_at: time::PrimitiveDateTime,
+    /// The time after which the public key of a retired key is no longer published
+    #[schema(value_type = Option<PrimitiveDateTime>, example = "2022-09-17T10:11:12Z")]
+    #[serde(default, with = "common_utils::custom_serde::iso8601::option")]
+    pub expires_at: Option<time::PrimitiveDateTime>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct WebhookSigningKeysResponse {
+    /// The identifier for the Merchant Account
+    #[schema(max_length = 255, example = "y3oqhf46pyzuxjbcn2giaqnb44")]
+    pub merchant_id: String,
+    /// The active and retired webhook signing keys of the merchant, empty if outgoing webhooks are not signed with a key pair
+    pub keys: Vec<WebhookSigningKeyResponse>,
+}
+
+/// The public keys with which the signatures of the outgoing webhooks of a merchant are verified
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct WebhookJsonWebKeySet {
+    /// The public keys of the active and retired webhook signing keys of the merchant, as JSON Web Keys
+    #[schema(value_type = Vec<Object>, example = json!([{"kty": "OKP", "crv": "Ed25519", "x": "11qYAYKxCrfVS_7TyWQHOg7hcvPapiMlrwIaaPcHURo", "kid": "whsk_5apGeP94tMts6rg3U3kR", "alg": "EdDSA", "use": "sig"}]))]
+    pub keys: Vec<serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct WebhookEndpointHealthResponse {
+    /// The identifier for the Merchant Account
+    #[schema(max_length = 255, example = "y3oqhf46pyzuxjbcn2giaqnb44")]
+    pub merchant_id: String,
+    /// The status of the webhook endpoint of the merchant
+    #[schema(value_type = WebhookEndpointStatus, example = "healthy")]
+    pub status: api_enums::WebhookEndpointStatus,
+    /// The number of webhooks posted to the endpoint in the current monitoring window
+    #[schema(example = 42)]
+    pub delivery_count: i64,
+    /// The number of webhooks in the current monitoring window which were not received by the endpoint
+    #[schema(example = 3)]
+    pub failure_count: i64,
+    /// The time at which the endpoint was last marked degraded
+    #[schema(example = "2023-08-21T10:11:12Z")]
+    #[serde(with = "common_utils::custom_serde::iso8601::option")]
+    pub degraded_at: Option<time::PrimitiveDateTime>,
+    /// The time at which the endpoint was last verified through a challenge request
+    #[schema(example = "2023-08-21T12:11:12Z")]
+    #[serde(with = "common_utils::custom_serde::iso8601::option")]
+    pub last_verified_at: Option<time::PrimitiveDateTime>,
+}
+
 #[derive(Debug, Clone, Default, Eq, PartialEq, serde::Deserialize, serde::Serialize, ToSchema)]
 pub struct MerchantConnectorDetailsWrap {
     /// Creds Identifier is to uniquely identify the credentials. Do not send any sensitive info in this field. And do not send the string "null".
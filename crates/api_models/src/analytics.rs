@@ -0,0 +1,289 @@
+use serde::{Deserialize, Serialize};
+use time::PrimitiveDateTime;
+use utoipa::ToSchema;
+
+use crate::enums;
+
+/// The query parameters for retrieving the currency exposure of a period.
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct CurrencyExposureRequest {
+    /// The start of the period for which the exposure is summarized
+    #[schema(example = "2023-08-01T00:00:00Z")]
+    #[serde(with = "common_utils::custom_serde::iso8601")]
+    pub start_time: PrimitiveDateTime,
+
+    /// The end of the period for which the exposure is summarized. If not passed, the current
+    /// time is used
+    #[schema(example = "2023-08-31T23:59:59Z")]
+    #[serde(default, with = "common_utils::custom_serde::iso8601::option")]
+    pub end_time: Option<PrimitiveDateTime>,
+}
+
+/// The volumes of payments, refunds, payouts and disputes of the period in a single currency. All
+/// amounts are in the lowest denomination of the currency.
+#[derive(Clone, Debug, Serialize, ToSchema)]
+pub struct CurrencyExposure {
+    /// The currency of the amounts
+    #[schema(value_type = Currency, example = "USD")]
+    pub currency: enums::Currency,
+
+    /// The total amount captured for payments in the period
+    #[schema(example = 125000)]
+    pub gross_volume: i64,
+
+    /// The number of payments captured in the period
+    #[schema(example = 42)]
+    pub payment_count: i64,
+
+    /// The total amount refunded in the period
+    #[schema(example = 6540)]
+    pub refunded_amount: i64,
+
+    /// The number of refunds which succeeded in the period
+    #[schema(example = 3)]
+    pub refund_count: i64,
+
+    /// The total amount of payouts created in the period which are yet to be paid out
+    #[schema(example = 50000)]
+    pub pending_payout_amount: i64,
+
+    /// The number of payouts created in the period which are yet to be paid out
+    #[schema(example = 2)]
+    pub pending_payout_count: i64,
+
+    /// The amount withdrawn for disputes opened in the period, less the amount reinstated for
+    /// disputes won in the period
+    #[schema(example = 1500)]
+    pub net_dispute_impact: i64,
+
+    /// The gross volume, less the refunded amount, the pending payout amount and the net dispute
+    /// impact
+    #[schema(example = 66960)]
+    pub net_exposure: i64,
+}
+
+#[derive(Clone, Debug, Serialize, ToSchema)]
+pub struct CurrencyExposureResponse {
+    /// The start of the summarized period
+    #[serde(with = "common_utils::custom_serde::iso8601")]
+    pub start_time: PrimitiveDateTime,
+
+    /// The end of the summarized period
+    #[serde(with = "common_utils::custom_serde::iso8601")]
+    pub end_time: PrimitiveDateTime,
+
+    /// The exposure in each currency with any payments, refunds, pending payouts or disputes in the
+    /// period, ordered by currency
+    pub currencies: Vec<CurrencyExposure>,
+}
+
+/// The length of the periods into which the payments funnel is split.
+#[derive(
+    Clone, Copy, Debug, Default, Eq, PartialEq, Deserialize, Serialize, strum::Display, ToSchema,
+)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum FunnelGranularity {
+    Hour,
+    #[default]
+    Day,
+    Week,
+    Month,
+}
+
+/// The query parameters for retrieving the payments funnel of a period.
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct PaymentsFunnelRequest {
+    /// The start of the period for which the funnel is computed, payments being placed in the
+    /// period in which they were created
+    #[schema(example = "2023-08-01T00:00:00Z")]
+    #[serde(with = "common_utils::custom_serde::iso8601")]
+    pub start_time: PrimitiveDateTime,
+
+    /// The end of the period for which the funnel is computed. If not passed, the current time is
+    /// used
+    #[schema(example = "2023-08-31T23:59:59Z")]
+    #[serde(default, with = "common_utils::custom_serde::iso8601::option")]
+    pub end_time: Option<PrimitiveDateTime>,
+
+    /// The length of the periods into which the funnel is split, `day` if not passed
+    #[serde(default)]
+    pub granularity: FunnelGranularity,
+
+    /// Only include payments made with this payment method
+    #[schema(value_type = Option<PaymentMethod>, example = "card")]
+    pub payment_method: Option<enums::PaymentMethod>,
+
+    /// Only include payments of this business country
+    #[schema(value_type = Option<CountryAlpha2>, example = "US")]
+    pub business_country: Option<enums::CountryAlpha2>,
+
+    /// Only include payments of this business label
+    #[schema(example = "food")]
+    pub business_label: Option<String>,
+}
+
+/// The number of payments which reached each stage of the funnel. A payment counts towards all
+/// the stages up to the furthest one it reached.
+#[derive(Clone, Copy, Debug, Default, Serialize, ToSchema)]
+pub struct PaymentsFunnelStages {
+    /// The number of payments created
+    #[schema(example = 120)]
+    pub created: i64,
+
+    /// The number of payments confirmed by the customer or the merchant
+    #[schema(example = 100)]
+    pub confirmed: i64,
+
+    /// The number of payments authorized by the connector
+    #[schema(example = 85)]
+    pub authorized: i64,
+
+    /// The number of payments captured, in full or in part
+    #[schema(example = 80)]
+    pub captured: i64,
+
+    /// The number of confirmed payments which are still awaiting authentication of the customer,
+    /// such as 3DS, or whose authentication failed
+    #[schema(example = 9)]
+    pub authentication_dropped: i64,
+}
+
+/// The funnel of the payments made with a payment method which were created in a period.
+#[derive(Clone, Debug, Serialize, ToSchema)]
+pub struct PaymentsFunnelBucket {
+    /// The start of the period
+    #[serde(with = "common_utils::custom_serde::iso8601")]
+    pub period_start: PrimitiveDateTime,
+
+    /// The payment method of the payments, absent for payments which have not been confirmed with
+    /// a payment method
+    #[schema(value_type = Option<PaymentMethod>, example = "card")]
+    pub payment_method: Option<enums::PaymentMethod>,
+
+    /// The number of payments which reached each stage of the funnel
+    pub stages: PaymentsFunnelStages,
+}
+
+#[derive(Clone, Debug, Serialize, ToSchema)]
+pub struct PaymentsFunnelResponse {
+    /// The start of the period for which the funnel is computed
+    #[serde(with = "common_utils::custom_serde::iso8601")]
+    pub start_time: PrimitiveDateTime,
+
+    /// The end of the period for which the funnel is computed
+    #[serde(with = "common_utils::custom_serde::iso8601")]
+    pub end_time: PrimitiveDateTime,
+
+    /// The length of the periods into which the funnel is split
+    pub granularity: FunnelGranularity,
+
+    /// The funnel of all the payments of the period
+    pub total: PaymentsFunnelStages,
+
+    /// The funnel of the payments per period and payment method, ordered by period
+    pub buckets: Vec<PaymentsFunnelBucket>,
+}
+
+/// The query parameters for retrieving the payout analytics of a period.
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct PayoutAnalyticsRequest {
+    /// The start of the period for which the payouts are aggregated, payouts being placed in the
+    /// period in which they were created
+    #[schema(example = "2023-08-01T00:00:00Z")]
+    #[serde(with = "common_utils::custom_serde::iso8601")]
+    pub start_time: PrimitiveDateTime,
+
+    /// The end of the period for which the payouts are aggregated. If not passed, the current time
+    /// is used
+    #[schema(example = "2023-08-31T23:59:59Z")]
+    #[serde(default, with = "common_utils::custom_serde::iso8601::option")]
+    pub end_time: Option<PrimitiveDateTime>,
+}
+
+/// The aggregates of the payouts of the period made through a connector, in a currency and of a
+/// payout type. All amounts are in the lowest denomination of the currency.
+#[derive(Clone, Debug, Serialize, ToSchema)]
+pub struct PayoutAnalyticsBucket {
+    /// The connector through which the payouts were made
+    #[schema(example = "adyen")]
+    pub connector: String,
+
+    /// The currency in which the payouts were made
+    #[schema(value_type = Currency, example = "USD")]
+    pub currency: enums::Currency,
+
+    /// The type of the payouts
+    #[schema(value_type = PayoutType, example = "bank")]
+    pub payout_type: enums::PayoutType,
+
+    /// The number of payouts created
+    #[schema(example = 40)]
+    pub payout_count: i64,
+
+    /// The total amount of the payouts created
+    #[schema(example = 400000)]
+    pub total_amount: i64,
+
+    /// The number of payouts which succeeded
+    #[schema(example = 36)]
+    pub successful_count: i64,
+
+    /// The total amount of the payouts which succeeded
+    #[schema(example = 360000)]
+    pub successful_amount: i64,
+
+    /// The number of payouts which failed or were found ineligible by the connector
+    #[schema(example = 2)]
+    pub failed_count: i64,
+
+    /// The ratio of the payouts which succeeded to those which succeeded or failed, absent when
+    /// none of the payouts succeeded or failed yet
+    #[schema(example = 0.947)]
+    pub success_rate: Option<f64>,
+
+    /// The average duration (in seconds) between the creation of a payout and its success
+    #[schema(example = 5400)]
+    pub average_fulfillment_latency: Option<i64>,
+}
+
+/// The number of payouts of the period which failed with an error through a connector.
+#[derive(Clone, Debug, Serialize, ToSchema)]
+pub struct PayoutFailureReason {
+    /// The connector through which the payouts failed
+    #[schema(example = "adyen")]
+    pub connector: String,
+
+    /// The error code returned by the connector
+    #[schema(example = "invalid_account")]
+    pub error_code: Option<String>,
+
+    /// An error message returned by the connector along with the error code
+    #[schema(example = "The bank account number is invalid")]
+    pub error_message: Option<String>,
+
+    /// The number of payouts which failed with the error
+    #[schema(example = 2)]
+    pub count: i64,
+}
+
+#[derive(Clone, Debug, Serialize, ToSchema)]
+pub struct PayoutAnalyticsResponse {
+    /// The start of the period for which the payouts are aggregated
+    #[serde(with = "common_utils::custom_serde::iso8601")]
+    pub start_time: PrimitiveDateTime,
+
+    /// The end of the period for which the payouts are aggregated
+    #[serde(with = "common_utils::custom_serde::iso8601")]
+    pub end_time: PrimitiveDateTime,
+
+    /// The aggregates of the payouts per connector, currency and payout type, ordered by
+    /// connector, currency and payout type
+    pub buckets: Vec<PayoutAnalyticsBucket>,
+
+    /// The reasons for which payouts failed, ordered by decreasing number of payouts
+    pub failure_reasons: Vec<PayoutFailureReason>,
+}
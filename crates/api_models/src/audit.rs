@@ -0,0 +1,81 @@
+use common_utils::pii;
+use serde::{Deserialize, Serialize};
+use time::PrimitiveDateTime;
+use utoipa::ToSchema;
+
+use crate::enums;
+
+/// The constraints that are applicable when listing the audit events of a merchant.
+#[derive(Clone, Debug, Default, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct AuditEventListConstraints {
+    /// Only list the changes made to this type of entity
+    #[schema(value_type = Option<AuditEntityType>, example = "api_key")]
+    pub entity_type: Option<enums::AuditEntityType>,
+
+    /// Only list the changes made to the entity with this identifier
+    #[schema(example = "dev_N7XJ9SrpkxXvVD9kPJbO")]
+    pub entity_id: Option<String>,
+
+    /// Only list the changes of this kind
+    #[schema(value_type = Option<AuditAction>, example = "update")]
+    pub action: Option<enums::AuditAction>,
+
+    /// Only list the changes made at or after this time
+    #[serde(default, with = "common_utils::custom_serde::iso8601::option")]
+    pub start_time: Option<PrimitiveDateTime>,
+
+    /// Only list the changes made at or before this time
+    #[serde(default, with = "common_utils::custom_serde::iso8601::option")]
+    pub end_time: Option<PrimitiveDateTime>,
+
+    /// The maximum number of events to include in the response
+    pub limit: Option<i64>,
+}
+
+/// A change made to the configuration of a merchant.
+#[derive(Clone, Debug, Serialize, ToSchema)]
+pub struct AuditEventResponse {
+    /// The identifier of the audit event
+    #[schema(example = "aud_3Nf8bWmQpZv1LxR2tKcY")]
+    pub event_id: String,
+
+    /// The type of entity which was changed
+    #[schema(value_type = AuditEntityType, example = "api_key")]
+    pub entity_type: enums::AuditEntityType,
+
+    /// The identifier of the entity which was changed
+    #[schema(example = "dev_N7XJ9SrpkxXvVD9kPJbO")]
+    pub entity_id: String,
+
+    /// The kind of change made to the entity
+    #[schema(value_type = AuditAction, example = "update")]
+    pub action: enums::AuditAction,
+
+    /// The kind of credentials the change was made with
+    #[schema(example = "admin_api_key")]
+    pub actor: String,
+
+    /// The values of the changed fields before the change. Secrets are masked.
+    #[schema(value_type = Option<Object>, example = r#"{ "name": "Production key" }"#)]
+    pub previous_values: Option<pii::SecretSerdeValue>,
+
+    /// The values of the changed fields after the change. Secrets are masked.
+    #[schema(value_type = Option<Object>, example = r#"{ "name": "Checkout key" }"#)]
+    pub updated_values: Option<pii::SecretSerdeValue>,
+
+    /// The time at which the change was made
+    #[schema(value_type = PrimitiveDateTime, example = "2022-09-10T10:11:12Z")]
+    #[serde(with = "common_utils::custom_serde::iso8601")]
+    pub created_at: PrimitiveDateTime,
+}
+
+/// The audit events of a merchant.
+#[derive(Clone, Debug, Serialize, ToSchema)]
+pub struct AuditEventListResponse {
+    /// The number of events included in the list
+    pub count: usize,
+
+    /// The audit events, most recent first
+    pub data: Vec<AuditEventResponse>,
+}
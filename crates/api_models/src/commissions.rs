@@ -0,0 +1,100 @@
+use serde::{Deserialize, Serialize};
+use time::PrimitiveDateTime;
+use utoipa::ToSchema;
+
+use crate::enums;
+
+/// The query parameters for retrieving the commission statement of a merchant for a period.
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct CommissionStatementRequest {
+    /// The start of the period of the statement
+    #[schema(example = "2023-08-01T00:00:00Z")]
+    #[serde(with = "common_utils::custom_serde::iso8601")]
+    pub start_time: PrimitiveDateTime,
+
+    /// The end of the period of the statement. If not passed, the current time is used
+    #[schema(example = "2023-08-31T23:59:59Z")]
+    #[serde(default, with = "common_utils::custom_serde::iso8601::option")]
+    pub end_time: Option<PrimitiveDateTime>,
+}
+
+/// The commission recorded by the platform for a successful payment of a merchant.
+#[derive(Clone, Debug, Serialize, ToSchema)]
+pub struct CommissionResponse {
+    /// The identifier of the commission
+    #[schema(example = "com_mbabizu24mvu3mela5njy")]
+    pub commission_id: String,
+
+    /// The identifier of the payment the commission was recorded for
+    #[schema(example = "pay_mbabizu24mvu3mela5njyhpit4")]
+    pub payment_id: String,
+
+    /// The pricing plan with which the commission was computed
+    #[schema(example = "standard")]
+    pub pricing_plan: String,
+
+    /// The currency of the payment and the commission
+    #[schema(value_type = Currency, example = "USD")]
+    pub currency: enums::Currency,
+
+    /// The amount of the payment, in the lowest denomination of the currency
+    #[schema(example = 6540)]
+    pub payment_amount: i64,
+
+    /// The commission charged on the payment, in the lowest denomination of the currency
+    #[schema(example = 65)]
+    pub commission_amount: i64,
+
+    /// The time at which the commission was recorded
+    #[schema(example = "2022-09-10T10:11:12Z")]
+    #[serde(with = "common_utils::custom_serde::iso8601")]
+    pub created: PrimitiveDateTime,
+}
+
+/// The commissions of a statement in a single currency.
+#[derive(Clone, Debug, Serialize, ToSchema)]
+pub struct CommissionStatementTotal {
+    /// The currency of the amounts
+    #[schema(value_type = Currency, example = "USD")]
+    pub currency: enums::Currency,
+
+    /// The number of payments commission was recorded for
+    pub payment_count: usize,
+
+    /// The total amount of the payments, in the lowest denomination of the currency
+    pub payment_amount: i64,
+
+    /// The total commission charged on the payments, in the lowest denomination of the currency
+    pub commission_amount: i64,
+}
+
+#[derive(Clone, Debug, Serialize, ToSchema)]
+pub struct CommissionStatementResponse {
+    /// The identifier of the merchant the statement was generated for
+    pub merchant_id: String,
+
+    /// The identifier of the platform merchant the merchant belongs to, if any
+    pub parent_merchant_id: Option<String>,
+
+    /// The pricing plan currently assigned to the merchant
+    pub pricing_plan: Option<String>,
+
+    /// The start of the period of the statement
+    #[serde(with = "common_utils::custom_serde::iso8601")]
+    pub start_time: PrimitiveDateTime,
+
+    /// The end of the period of the statement
+    #[serde(with = "common_utils::custom_serde::iso8601")]
+    pub end_time: PrimitiveDateTime,
+
+    /// The commissions of the period summed up per currency
+    pub totals: Vec<CommissionStatementTotal>,
+
+    /// The commissions of the period, ordered by the time at which they were recorded
+    pub commissions: Vec<CommissionResponse>,
+
+    /// Whether the period has more commissions than can be listed in `commissions`. The totals
+    /// always include all the commissions of the period.
+    pub is_truncated: bool,
+}
@@ -1,9 +1,11 @@
+use std::collections::HashMap;
+
 use common_utils::{consts, crypto, custom_serde, pii};
 use masking::Secret;
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 
-use crate::payments;
+use crate::{enums as api_enums, payments};
 
 /// The customer details
 #[derive(Debug, Default, Clone, Deserialize, Serialize, ToSchema)]
@@ -73,6 +75,16 @@ pub struct CustomerResponse {
     /// object.
     #[schema(value_type = Option<Object>,example = json!({ "city": "NY", "unit": "245" }))]
     pub metadata: Option<pii::SecretSerdeValue>,
+    /// The identifier of the default shipping address in the address book of the customer
+    #[schema(max_length = 64, example = "add_5ghsy3ft5tw3ghsif7sf")]
+    pub default_shipping_address_id: Option<String>,
+    /// The identifier of the default billing address in the address book of the customer
+    #[schema(max_length = 64, example = "add_5ghsy3ft5tw3ghsif7sf")]
+    pub default_billing_address_id: Option<String>,
+    /// The identifier of the default payment method of the customer, which is listed first among
+    /// the saved payment methods of the customer
+    #[schema(max_length = 64, example = "pm_y3oqhf46pyzuxjbcn2giaqnb44")]
+    pub default_payment_method_id: Option<String>,
 }
 
 #[derive(Default, Clone, Debug, Deserialize, Serialize)]
@@ -96,6 +108,205 @@ pub struct CustomerDeleteResponse {
     pub payment_methods_deleted: bool,
 }
 
+/// An address to be saved in the address book of a customer
+#[derive(Debug, Default, Clone, Deserialize, Serialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct CustomerAddressRequest {
+    /// The address details
+    #[schema(value_type = AddressDetails)]
+    pub address: payments::AddressDetails,
+    /// The phone number of the recipient at the address
+    #[schema(value_type = Option<PhoneDetails>)]
+    pub phone: Option<payments::PhoneDetails>,
+}
+
+/// The type of address which is defaulted to when an address is omitted in a payment
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum CustomerAddressType {
+    Shipping,
+    Billing,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct CustomerDefaultAddressRequest {
+    /// The type of address for which the address is set as the default
+    #[schema(value_type = CustomerAddressType, example = "shipping")]
+    pub address_type: CustomerAddressType,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct CustomerAddressResponse {
+    /// The identifier for the address
+    #[schema(max_length = 64, example = "add_5ghsy3ft5tw3ghsif7sf")]
+    pub address_id: String,
+    /// The identifier for the customer object
+    #[schema(max_length = 255, example = "cus_y3oqhf46pyzuxjbcn2giaqnb44")]
+    pub customer_id: String,
+    /// The address details
+    #[schema(value_type = AddressDetails)]
+    pub address: payments::AddressDetails,
+    /// The phone number of the recipient at the address
+    #[schema(value_type = PhoneDetails)]
+    pub phone: payments::PhoneDetails,
+    /// Whether the address is the default shipping address of the customer
+    #[schema(example = true)]
+    pub is_default_shipping: bool,
+    /// Whether the address is the default billing address of the customer
+    #[schema(example = false)]
+    pub is_default_billing: bool,
+    /// A timestamp (ISO 8601 code) that determines when the address was created
+    #[schema(value_type = PrimitiveDateTime, example = "2023-01-18T11:04:09.922Z")]
+    #[serde(with = "custom_serde::iso8601")]
+    pub created_at: time::PrimitiveDateTime,
+}
+
+#[derive(Default, Clone, Debug, Deserialize, Serialize)]
+pub struct CustomerAddressId {
+    pub customer_id: String,
+    pub address_id: String,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct CustomerAddressDeleteResponse {
+    /// The identifier for the address
+    #[schema(max_length = 64, example = "add_5ghsy3ft5tw3ghsif7sf")]
+    pub address_id: String,
+    /// The identifier for the customer object
+    #[schema(max_length = 255, example = "cus_y3oqhf46pyzuxjbcn2giaqnb44")]
+    pub customer_id: String,
+    /// Whether the address was deleted or not
+    #[schema(example = true)]
+    pub deleted: bool,
+}
+
+#[derive(Default, Clone, Debug, Deserialize, Serialize)]
+pub struct CustomerPaymentMethodId {
+    pub customer_id: String,
+    pub payment_method_id: String,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct CustomerDefaultPaymentMethodResponse {
+    /// The identifier for the customer object
+    #[schema(max_length = 255, example = "cus_y3oqhf46pyzuxjbcn2giaqnb44")]
+    pub customer_id: String,
+    /// The identifier of the default payment method of the customer
+    #[schema(max_length = 64, example = "pm_y3oqhf46pyzuxjbcn2giaqnb44")]
+    pub default_payment_method_id: String,
+    /// The type of the default payment method
+    #[schema(value_type = PaymentMethod, example = "card")]
+    pub payment_method: api_enums::PaymentMethod,
+}
+
+/// The customers to be created by a batch import
+#[derive(Debug, Clone, Deserialize, Serialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct CustomerImportRequest {
+    /// The customers to be created, up to 10000 in a single import
+    pub customers: Vec<CustomerImportRecord>,
+}
+
+/// A customer to be created by a batch import
+#[derive(Debug, Clone, Deserialize, Serialize, ToSchema)]
+pub struct CustomerImportRecord {
+    /// The details of the customer
+    #[serde(flatten)]
+    pub customer: CustomerRequest,
+    /// The identifiers of the customer at the connectors, keyed by the label of the connector
+    #[schema(value_type = Option<Object>, example = json!({ "stripe_US_default": "cus_OZkbsGZ1x2CyWj" }))]
+    pub connector_customer: Option<HashMap<String, String>>,
+    /// The payment methods previously saved for the customer, to be migrated along with it
+    pub payment_methods: Option<Vec<CustomerImportPaymentMethod>>,
+}
+
+/// A reference to a payment method previously saved for a customer
+#[derive(Debug, Clone, Deserialize, Serialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct CustomerImportPaymentMethod {
+    /// The type of payment method
+    #[schema(value_type = PaymentMethod, example = "card")]
+    pub payment_method: api_enums::PaymentMethod,
+    /// This is a sub-category of payment method.
+    #[schema(value_type = Option<PaymentMethodType>, example = "credit")]
+    pub payment_method_type: Option<api_enums::PaymentMethodType>,
+    /// The name of the bank/ provider issuing the payment method to the end user
+    #[schema(example = "Citibank")]
+    pub payment_method_issuer: Option<String>,
+    /// The card network
+    #[schema(example = "Visa")]
+    pub card_network: Option<String>,
+    /// The expiry month of the card
+    #[schema(value_type = Option<String>, example = "10")]
+    pub card_exp_month: Option<Secret<String>>,
+    /// The expiry year of the card
+    #[schema(value_type = Option<String>, example = "25")]
+    pub card_exp_year: Option<Secret<String>>,
+    /// The reference of the card in the locker. Required for cards, which must have been stored in
+    /// the locker for the same merchant and customer beforehand.
+    #[schema(max_length = 64, example = "card_7skzZxQFNeF8QytnJFkP")]
+    pub locker_id: Option<String>,
+    /// The tokens of the payment method at the connectors, keyed by the name of the connector
+    #[schema(value_type = Option<Object>, example = json!({ "stripe": "pm_1NmXv2CZ2GmBUNXmLfXFvHpU" }))]
+    pub connector_payment_method_tokens: Option<HashMap<String, String>>,
+}
+
+#[derive(Default, Clone, Debug, Deserialize, Serialize)]
+pub struct CustomerImportJobId {
+    pub job_id: String,
+}
+
+/// The status of a batch import of customers
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct CustomerImportResponse {
+    /// The identifier of the import
+    #[schema(max_length = 64, example = "cimp_Ldm2SSY5DIAqGNTHaUbB")]
+    pub job_id: String,
+    /// The status of the import
+    #[schema(value_type = CustomerImportStatus, example = "processing")]
+    pub status: api_enums::CustomerImportStatus,
+    /// The number of customers in the import
+    #[schema(example = 2500)]
+    pub total_count: i32,
+    /// The number of customers processed so far
+    #[schema(example = 1200)]
+    pub processed_count: i32,
+    /// The number of customers created
+    #[schema(example = 1195)]
+    pub succeeded_count: i32,
+    /// The number of customers which could not be created
+    #[schema(example = 5)]
+    pub failed_count: i32,
+    /// The errors of the customers which could not be created
+    pub errors: Vec<CustomerImportError>,
+    /// The time at which the import was created
+    #[schema(value_type = PrimitiveDateTime, example = "2023-09-01T10:11:12Z")]
+    #[serde(with = "custom_serde::iso8601")]
+    pub created_at: time::PrimitiveDateTime,
+    /// The time at which all the customers of the import were processed
+    #[schema(value_type = Option<PrimitiveDateTime>, example = "2023-09-01T10:14:32Z")]
+    #[serde(default, with = "custom_serde::iso8601::option")]
+    pub completed_at: Option<time::PrimitiveDateTime>,
+}
+
+/// The reason why a customer of a batch import could not be created
+#[derive(Debug, Clone, Deserialize, Serialize, ToSchema)]
+pub struct CustomerImportError {
+    /// The position of the customer in the import, starting from 0
+    #[schema(example = 17)]
+    pub row_index: usize,
+    /// The identifier of the customer
+    #[schema(max_length = 255, example = "cus_y3oqhf46pyzuxjbcn2giaqnb44")]
+    pub customer_id: String,
+    /// The code of the error
+    #[schema(example = "IR_16")]
+    pub error_code: String,
+    /// The description of the error
+    #[schema(example = "A customer with the same customer_id already exists")]
+    pub error_message: String,
+}
+
 pub fn generate_customer_id() -> String {
     common_utils::generate_id(consts::ID_LENGTH, "cus")
 }
@@ -2,7 +2,10 @@ use masking::{Deserialize, Serialize};
 use time::PrimitiveDateTime;
 use utoipa::ToSchema;
 
-use super::enums::{DisputeStage, DisputeStatus};
+use super::enums::{
+    CardNetwork, Currency, DisputeStage, DisputeStatus, IntentStatus, PaymentMethod,
+    PaymentMethodType,
+};
 use crate::files;
 
 #[derive(Clone, Debug, Serialize, ToSchema, Eq, PartialEq)]
@@ -43,6 +46,169 @@ pub struct DisputeResponse {
     /// Time at which dispute is received
     #[serde(with = "common_utils::custom_serde::iso8601")]
     pub created_at: PrimitiveDateTime,
+    /// Summary of the disputed payment, included in outgoing webhooks
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub payment_summary: Option<DisputePaymentSummary>,
+    /// The identifier for the customer of the disputed payment, included in outgoing webhooks
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub customer_id: Option<String>,
+    /// Description of the card network reason code of the dispute, included in outgoing webhooks
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason_code_description: Option<String>,
+    /// Normalized category of the card network reason code of the dispute, along with the evidence recommended to challenge it
+    pub reason_code_details: Option<DisputeReasonCodeResponse>,
+}
+
+/// Normalized category of the reasons for which a dispute can be raised, across card networks
+#[derive(
+    Clone, Copy, Debug, Deserialize, Eq, Hash, PartialEq, Serialize, strum::Display, ToSchema,
+)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum DisputeReasonCategory {
+    /// The cardholder claims that they did not authorize the payment
+    Fraud,
+    /// The payment was not authorized, or was processed with a declined or expired authorization
+    Authorization,
+    /// The payment was processed with incorrect details, such as the amount, currency or date
+    ProcessingError,
+    /// The cardholder was charged more than once for the same purchase
+    Duplicate,
+    /// The goods or services paid for were not received
+    ProductNotReceived,
+    /// The goods or services received were defective or not as described
+    ProductUnacceptable,
+    /// A refund promised to the cardholder was not processed
+    CreditNotProcessed,
+    /// The cardholder cancelled the purchase or the recurring payment before it was charged
+    Cancelled,
+    /// The reason does not fall under any other category
+    General,
+}
+
+#[derive(Clone, Debug, Serialize, ToSchema, Eq, PartialEq)]
+pub struct DisputeReasonCodeResponse {
+    /// The card network which defines the reason code
+    pub card_network: CardNetwork,
+    /// The reason code assigned by the card network
+    #[schema(example = "10.4")]
+    pub reason_code: String,
+    /// Description of the reason code
+    pub description: String,
+    /// Normalized category of the reason code
+    pub category: DisputeReasonCategory,
+    /// The evidence recommended to challenge disputes raised with the reason code, in order of relevance
+    pub recommended_evidence: Vec<EvidenceType>,
+}
+
+#[derive(Clone, Debug, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct DisputeReasonCodeListConstraints {
+    /// Only list the reason codes of the card network
+    pub card_network: Option<CardNetwork>,
+    /// Only list the reason codes of the category
+    pub category: Option<DisputeReasonCategory>,
+}
+
+/// Rules for the automatic handling of the disputes raised against the payments of the merchant
+#[derive(Clone, Debug, Default, Deserialize, Serialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct DisputeAutoDefendRules {
+    /// The rules, evaluated in order when a dispute is opened. Only the action of the first rule
+    /// matching the dispute is taken, and disputes matching no rule are left to the merchant.
+    pub rules: Vec<DisputeAutoDefendRule>,
+}
+
+/// A rule taking an action on the disputes matching all of its conditions
+#[derive(Clone, Debug, Deserialize, Serialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct DisputeAutoDefendRule {
+    /// Name of the rule, unique among the rules of the merchant
+    #[schema(example = "accept_small_disputes")]
+    pub name: String,
+    /// Only match disputes for an amount up to this amount, in the lowest denomination of the currency
+    #[schema(example = 1000)]
+    pub max_amount: Option<i64>,
+    /// Only match disputes in this currency
+    #[schema(example = "USD")]
+    pub currency: Option<Currency>,
+    /// Only match disputes raised with one of these reason codes
+    #[schema(example = json!(["10.4", "4837"]))]
+    pub reason_codes: Option<Vec<String>>,
+    /// Only match disputes whose reason code falls under one of these categories
+    pub reason_categories: Option<Vec<DisputeReasonCategory>>,
+    /// Only match disputes raised through one of these connectors
+    #[schema(example = json!(["stripe"]))]
+    pub connectors: Option<Vec<String>>,
+    /// The action taken on the matching disputes
+    pub action: DisputeAutoDefendAction,
+}
+
+/// An action taken automatically on a dispute
+#[derive(Clone, Debug, Deserialize, Serialize, ToSchema)]
+#[serde(tag = "type", rename_all = "snake_case", deny_unknown_fields)]
+pub enum DisputeAutoDefendAction {
+    /// Accept the dispute
+    Accept,
+    /// Challenge the dispute by submitting evidence
+    SubmitEvidence {
+        /// The fields of the evidence submission request, other than `dispute_id`. The
+        /// `{dispute_id}`, `{payment_id}`, `{amount}`, `{currency}`, `{connector}` and
+        /// `{reason_code}` placeholders in the evidence are replaced with the details of the dispute.
+        #[schema(value_type = Object, example = json!({
+            "uncategorized_text": "The payment {payment_id} was authenticated by the cardholder",
+            "receipt": "file_abc123"
+        }))]
+        evidence: serde_json::Map<String, serde_json::Value>,
+    },
+}
+
+#[derive(Clone, Debug, Serialize, ToSchema, Eq, PartialEq)]
+pub struct DisputePaymentSummary {
+    /// The amount of the payment, in the lowest denomination of the currency
+    pub amount: i64,
+    /// The amount captured for the payment
+    pub amount_captured: Option<i64>,
+    /// The three-letter ISO currency code of the payment
+    pub currency: Option<Currency>,
+    /// Status of the payment
+    pub status: IntentStatus,
+    /// Payment method used for the payment
+    pub payment_method: Option<PaymentMethod>,
+    /// Payment method type used for the payment
+    pub payment_method_type: Option<PaymentMethodType>,
+    /// Card network of the card used for the payment
+    pub card_network: Option<CardNetwork>,
+    /// Last four digits of the card used for the payment
+    pub card_last4: Option<String>,
+    /// Transaction id of the payment sent by connector
+    pub connector_transaction_id: Option<String>,
+    /// Time at which the payment was created
+    #[serde(with = "common_utils::custom_serde::iso8601")]
+    pub created_at: PrimitiveDateTime,
+}
+
+/// The funds withdrawn from and reinstated to the merchant over the lifecycle of a dispute
+#[derive(Clone, Debug, Serialize, ToSchema)]
+pub struct DisputeFinancialsResponse {
+    /// The identifier for dispute
+    pub dispute_id: String,
+    /// The identifier for payment
+    pub payment_id: String,
+    /// The three-letter ISO currency code of the amounts
+    #[schema(value_type = Currency, example = "USD")]
+    pub currency: Currency,
+    /// The amount withdrawn from the merchant when the dispute was opened, in the lowest denomination of the currency
+    #[schema(example = 6540)]
+    pub withdrawn_amount: i64,
+    /// The amount reinstated to the merchant when the dispute was won
+    #[schema(example = 0)]
+    pub reinstated_amount: i64,
+    /// The net impact of the dispute on the funds of the merchant, which is the withdrawn amount less the reinstated amount
+    #[schema(example = 6540)]
+    pub net_impact: i64,
+    /// The ledger entries posted for the dispute, most recent first
+    pub entries: Vec<crate::accounting::LedgerEntryResponse>,
 }
 
 #[derive(Clone, Debug, Serialize, ToSchema, Eq, PartialEq)]
@@ -75,7 +241,9 @@ pub struct DisputeResponsePaymentsRetrieve {
     pub created_at: PrimitiveDateTime,
 }
 
-#[derive(Debug, Serialize, strum::Display, Clone)]
+#[derive(
+    Debug, Serialize, Deserialize, strum::Display, Clone, Copy, PartialEq, Eq, Hash, ToSchema,
+)]
 #[serde(rename_all = "snake_case")]
 #[strum(serialize_all = "snake_case")]
 pub enum EvidenceType {
@@ -189,4 +357,16 @@ pub struct SubmitEvidenceRequest {
     pub uncategorized_file: Option<String>,
     /// Any additional evidence statements
     pub uncategorized_text: Option<String>,
+    /// Files submitted as evidence in addition to the files above, for connectors which accept
+    /// several files of an evidence category in a single submission
+    pub evidence_files: Option<Vec<EvidenceFiles>>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct EvidenceFiles {
+    /// Evidence type of the files
+    pub evidence_type: EvidenceType,
+    /// File Ids of the files
+    pub file_ids: Vec<String>,
 }
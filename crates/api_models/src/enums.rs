@@ -254,6 +254,17 @@ pub enum PayoutConnectors {
     Wise,
 }
 
+#[cfg(feature = "payouts")]
+impl PayoutConnectors {
+    /// Whether KYC documents for the beneficiary of a payout can be uploaded to the connector
+    pub fn supports_file_upload(&self) -> bool {
+        match self {
+            Self::Adyen => true,
+            Self::Wise => false,
+        }
+    }
+}
+
 #[cfg(feature = "payouts")]
 impl From<PayoutConnectors> for RoutableConnectors {
     fn from(value: PayoutConnectors) -> Self {
@@ -545,3 +556,123 @@ pub enum RetryAction {
     /// Denotes that the payment is requeued
     Requeue,
 }
+
+/// The way in which card details were captured by a card-present terminal
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Eq,
+    PartialEq,
+    serde::Deserialize,
+    serde::Serialize,
+    strum::Display,
+    strum::EnumString,
+    ToSchema,
+)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum CardPresentEntryMode {
+    /// The chip on the card was read (EMV contact)
+    Chip,
+    /// The card or a device was tapped on the terminal (EMV contactless / NFC)
+    Contactless,
+    /// The magnetic stripe of the card was swiped
+    MagneticStripe,
+    /// The card number was keyed in on the terminal
+    Manual,
+}
+
+/// The connectors from which session tokens are fetched when a session is created for a payment
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    Eq,
+    PartialEq,
+    serde::Deserialize,
+    serde::Serialize,
+    strum::Display,
+    strum::EnumString,
+    ToSchema,
+)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum SessionTokenStrategy {
+    /// Session tokens are fetched from all the eligible connectors at once
+    #[default]
+    Parallel,
+    /// Session tokens are fetched only from the connector the payment is routed to, and from the other eligible connectors when they are requested
+    Sequential,
+}
+
+/// The status of an incremental authorization of a payment
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Eq,
+    PartialEq,
+    serde::Deserialize,
+    serde::Serialize,
+    strum::Display,
+    strum::EnumString,
+    ToSchema,
+)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum AuthorizationStatus {
+    /// The connector authorized the increased amount
+    Success,
+    /// The connector declined the increased amount, the previously authorized amount remains authorized
+    Failure,
+    /// The connector is yet to decide on the increased amount
+    Processing,
+}
+
+/// The algorithm with which outgoing webhooks are signed using the signing key of the merchant
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    Eq,
+    PartialEq,
+    serde::Deserialize,
+    serde::Serialize,
+    strum::Display,
+    strum::EnumString,
+    ToSchema,
+)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum WebhookSigningAlgorithm {
+    /// EdDSA signatures using the Ed25519 curve
+    #[default]
+    Ed25519,
+    /// RSASSA-PKCS1-v1_5 signatures using SHA-256, with a 2048 bit key
+    Rs256,
+}
+
+/// The status of a webhook signing key of a merchant
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Eq,
+    PartialEq,
+    serde::Deserialize,
+    serde::Serialize,
+    strum::Display,
+    strum::EnumString,
+    ToSchema,
+)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum WebhookSigningKeyStatus {
+    /// Outgoing webhooks are signed with the key
+    Active,
+    /// The key was replaced by a newer key, and its public key is published until it expires
+    Retired,
+}
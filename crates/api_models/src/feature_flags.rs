@@ -0,0 +1,66 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+/// The capabilities which can be rolled out to merchants gradually.
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Eq,
+    Hash,
+    PartialEq,
+    Deserialize,
+    Serialize,
+    strum::Display,
+    strum::EnumIter,
+    strum::EnumString,
+)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum FeatureFlag {
+    /// Process the incoming webhooks of the merchant through the webhook queue, when the queue is
+    /// enabled
+    WebhookQueue,
+    /// Include the source validation details in the outgoing webhooks of the merchant, when the
+    /// merchant has enabled them
+    WebhookSourceValidation,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct FeatureFlagUpdateRequest {
+    /// The percentage of merchants, between 0 and 100, for which the flag is enabled unless
+    /// overridden for the merchant
+    pub rollout_percentage: u8,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct MerchantFeatureFlagOverrideRequest {
+    /// Whether the flag is enabled for the merchant, regardless of the rollout percentage
+    pub enabled: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct FeatureFlagResponse {
+    /// The name of the flag
+    pub flag: FeatureFlag,
+
+    /// The percentage of merchants for which the flag is enabled unless overridden for the
+    /// merchant
+    pub rollout_percentage: u8,
+
+    /// Whether the flag is enabled or disabled for specific merchants, regardless of the rollout
+    /// percentage
+    pub merchant_overrides: HashMap<String, bool>,
+
+    /// Whether the flag is using its default rollout, as it has not been configured yet
+    pub is_default: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct FeatureFlagListResponse {
+    /// All the flags, with their rollout
+    pub flags: Vec<FeatureFlagResponse>,
+}
@@ -0,0 +1,157 @@
+use common_utils::pii;
+use serde::{Deserialize, Serialize};
+use time::PrimitiveDateTime;
+use utoipa::ToSchema;
+
+use crate::enums;
+
+/// The request body for issuing a gift card.
+#[derive(Debug, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct GiftCardCreateRequest {
+    /// The currency of the balance of the gift card.
+    #[schema(value_type = Currency, example = "USD")]
+    pub currency: enums::Currency,
+
+    /// The balance to issue the gift card with, in the lowest denomination of the currency.
+    #[schema(example = 5000)]
+    pub amount: i64,
+
+    /// The identifier for the customer the gift card is issued to.
+    #[schema(max_length = 64, example = "cus_y3oqhf46pyzuxjbcn2giaqnb44")]
+    pub customer_id: Option<String>,
+
+    /// The time after which the gift card can no longer be redeemed.
+    #[schema(example = "2024-12-31T23:59:59Z")]
+    #[serde(default, with = "common_utils::custom_serde::iso8601::option")]
+    pub expires_at: Option<PrimitiveDateTime>,
+
+    /// You can specify up to 50 keys, with key names up to 40 characters long and values up to
+    /// 500 characters long. Metadata is useful for storing additional, structured information on
+    /// an object.
+    #[schema(value_type = Option<Object>, example = r#"{ "campaign": "holiday" }"#)]
+    pub metadata: Option<pii::SecretSerdeValue>,
+}
+
+/// The response body for a gift card.
+#[derive(Clone, Debug, Serialize, ToSchema)]
+pub struct GiftCardResponse {
+    /// The identifier for the gift card.
+    #[schema(max_length = 64, example = "gc_4hDjyLsm6Y7BIMC9zPYB")]
+    pub gift_card_id: String,
+
+    /// The identifier for the Merchant Account.
+    #[schema(max_length = 64, example = "y3oqhf46pyzuxjbcn2giaqnb44")]
+    pub merchant_id: String,
+
+    /// The identifier for the customer the gift card is issued to.
+    #[schema(max_length = 64, example = "cus_y3oqhf46pyzuxjbcn2giaqnb44")]
+    pub customer_id: Option<String>,
+
+    /// The status of the gift card.
+    #[schema(value_type = GiftCardStatus, example = "active")]
+    pub status: enums::GiftCardStatus,
+
+    /// The currency of the balance of the gift card.
+    #[schema(value_type = Currency, example = "USD")]
+    pub currency: enums::Currency,
+
+    /// The balance the gift card was issued with.
+    #[schema(example = 5000)]
+    pub initial_balance: i64,
+
+    /// The balance currently available on the gift card.
+    #[schema(example = 3000)]
+    pub balance: i64,
+
+    /// The time after which the gift card can no longer be redeemed.
+    #[schema(example = "2024-12-31T23:59:59Z")]
+    #[serde(with = "common_utils::custom_serde::iso8601::option")]
+    pub expires_at: Option<PrimitiveDateTime>,
+
+    /// Additional, structured information stored against the gift card.
+    #[schema(value_type = Option<Object>, example = r#"{ "campaign": "holiday" }"#)]
+    pub metadata: Option<pii::SecretSerdeValue>,
+
+    /// The time at which the gift card was issued.
+    #[schema(example = "2022-09-10T10:11:12Z")]
+    #[serde(with = "common_utils::custom_serde::iso8601")]
+    pub created: PrimitiveDateTime,
+}
+
+/// The request body for redeeming a part of the balance of a gift card.
+#[derive(Debug, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct GiftCardRedeemRequest {
+    /// The amount to redeem, in the lowest denomination of the currency of the gift card.
+    #[schema(example = 2000)]
+    pub amount: i64,
+
+    /// The identifier for the payment the gift card is redeemed towards. The payment must be in
+    /// the currency of the gift card.
+    #[schema(max_length = 64, example = "pay_mbabizu24mvu3mela5njyhpit4")]
+    pub payment_id: Option<String>,
+
+    /// A unique key identifying the redemption. Retrying a redemption with the same idempotency
+    /// key returns the gift card without redeeming its balance again.
+    #[schema(max_length = 64, example = "order_1234_gift_card")]
+    pub idempotency_key: Option<String>,
+}
+
+/// The request body for reloading the balance of a gift card.
+#[derive(Debug, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct GiftCardReloadRequest {
+    /// The amount to add to the balance, in the lowest denomination of the currency of the gift
+    /// card.
+    #[schema(example = 2500)]
+    pub amount: i64,
+}
+
+/// A change made to the balance of a gift card.
+#[derive(Clone, Debug, Serialize, ToSchema)]
+pub struct GiftCardTransactionResponse {
+    /// The identifier for the transaction.
+    #[schema(max_length = 64, example = "gctxn_4hDjyLsm6Y7BIMC9zPYB")]
+    pub transaction_id: String,
+
+    /// The identifier for the gift card.
+    #[schema(max_length = 64, example = "gc_4hDjyLsm6Y7BIMC9zPYB")]
+    pub gift_card_id: String,
+
+    /// The type of the transaction.
+    #[schema(value_type = GiftCardTransactionType, example = "redeem")]
+    pub transaction_type: enums::GiftCardTransactionType,
+
+    /// The amount by which the balance changed.
+    #[schema(example = 2000)]
+    pub amount: i64,
+
+    /// The balance of the gift card after the transaction.
+    #[schema(example = 3000)]
+    pub balance_after: i64,
+
+    /// The currency of the gift card.
+    #[schema(value_type = Currency, example = "USD")]
+    pub currency: enums::Currency,
+
+    /// The identifier for the payment the gift card was redeemed towards, if any.
+    #[schema(max_length = 64, example = "pay_mbabizu24mvu3mela5njyhpit4")]
+    pub payment_id: Option<String>,
+
+    /// The time at which the transaction was made.
+    #[schema(example = "2022-09-10T10:11:12Z")]
+    #[serde(with = "common_utils::custom_serde::iso8601")]
+    pub created: PrimitiveDateTime,
+}
+
+/// The constraints that are applicable when listing the transactions of a gift card.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct GiftCardTransactionListConstraints {
+    /// The maximum number of transactions to include in the response.
+    pub limit: Option<i64>,
+
+    /// The number of transactions to skip when retrieving the list of transactions.
+    pub skip: Option<i64>,
+}
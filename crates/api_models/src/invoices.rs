@@ -0,0 +1,160 @@
+use common_utils::pii;
+use serde::{Deserialize, Serialize};
+use time::PrimitiveDateTime;
+use url::Url;
+use utoipa::ToSchema;
+
+use crate::enums;
+
+/// A single item billed on an invoice.
+#[derive(Clone, Debug, Deserialize, Serialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct InvoiceLineItem {
+    /// A description of the goods or services billed.
+    #[schema(max_length = 255, example = "Annual subscription")]
+    pub description: String,
+
+    /// The number of units billed.
+    #[schema(example = 2)]
+    pub quantity: u16,
+
+    /// The price of a single unit, in the lowest denomination of the currency of the invoice.
+    #[schema(example = 3270)]
+    pub unit_amount: i64,
+}
+
+/// The request body for creating an invoice.
+#[derive(Debug, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct InvoiceCreateRequest {
+    /// The currency in which the invoice is billed and paid.
+    #[schema(value_type = Currency, example = "USD")]
+    pub currency: enums::Currency,
+
+    /// The items billed on the invoice. The amount of the invoice is the sum of the amounts of
+    /// its line items.
+    #[schema(min_items = 1)]
+    pub line_items: Vec<InvoiceLineItem>,
+
+    /// The identifier for the customer being billed.
+    #[schema(max_length = 64, example = "cus_y3oqhf46pyzuxjbcn2giaqnb44")]
+    pub customer_id: Option<String>,
+
+    /// A description of the invoice.
+    #[schema(max_length = 255, example = "Invoice for August 2023")]
+    pub description: Option<String>,
+
+    /// The time by which the invoice is expected to be paid.
+    #[schema(example = "2023-08-31T23:59:59Z")]
+    #[serde(default, with = "common_utils::custom_serde::iso8601::option")]
+    pub due_date: Option<PrimitiveDateTime>,
+
+    /// You can specify up to 50 keys, with key names up to 40 characters long and values up to
+    /// 500 characters long. Metadata is useful for storing additional, structured information on
+    /// an object.
+    #[schema(value_type = Option<Object>, example = r#"{ "order": "1042" }"#)]
+    pub metadata: Option<pii::SecretSerdeValue>,
+}
+
+/// The response body for an invoice.
+#[derive(Clone, Debug, Serialize, ToSchema)]
+pub struct InvoiceResponse {
+    /// The identifier for the invoice.
+    #[schema(max_length = 64, example = "inv_4hDjyLsm6Y7BIMC9zPYB")]
+    pub invoice_id: String,
+
+    /// The identifier for the Merchant Account.
+    #[schema(max_length = 64, example = "y3oqhf46pyzuxjbcn2giaqnb44")]
+    pub merchant_id: String,
+
+    /// The identifier for the customer being billed.
+    #[schema(max_length = 64, example = "cus_y3oqhf46pyzuxjbcn2giaqnb44")]
+    pub customer_id: Option<String>,
+
+    /// The status of the invoice.
+    #[schema(value_type = InvoiceStatus, example = "partially_paid")]
+    pub status: enums::InvoiceStatus,
+
+    /// The currency in which the invoice is billed and paid.
+    #[schema(value_type = Currency, example = "USD")]
+    pub currency: enums::Currency,
+
+    /// The total amount of the invoice, in the lowest denomination of the currency.
+    #[schema(example = 6540)]
+    pub amount: i64,
+
+    /// The amount paid so far.
+    #[schema(example = 2000)]
+    pub amount_paid: i64,
+
+    /// The amount yet to be paid.
+    #[schema(example = 4540)]
+    pub amount_due: i64,
+
+    /// The items billed on the invoice.
+    pub line_items: Vec<InvoiceLineItem>,
+
+    /// A description of the invoice.
+    #[schema(max_length = 255, example = "Invoice for August 2023")]
+    pub description: Option<String>,
+
+    /// The time by which the invoice is expected to be paid.
+    #[schema(example = "2023-08-31T23:59:59Z")]
+    #[serde(with = "common_utils::custom_serde::iso8601::option")]
+    pub due_date: Option<PrimitiveDateTime>,
+
+    /// The link which can be shared with the customer to pay the invoice.
+    #[schema(
+        example = "https://sandbox.hyperswitch.io/invoices/y3oqhf46pyzuxjbcn2giaqnb44/inv_4hDjyLsm6Y7BIMC9zPYB/pay?client_secret=inv_4hDjyLsm6Y7BIMC9zPYB_secret_a1b2c3"
+    )]
+    pub payment_link: String,
+
+    /// The secret which authorizes payments towards the invoice through its payment link.
+    #[schema(value_type = String, example = "inv_4hDjyLsm6Y7BIMC9zPYB_secret_a1b2c3")]
+    pub client_secret: masking::Secret<String>,
+
+    /// Additional, structured information stored against the invoice.
+    #[schema(value_type = Option<Object>, example = r#"{ "order": "1042" }"#)]
+    pub metadata: Option<pii::SecretSerdeValue>,
+
+    /// The time at which the invoice was created.
+    #[schema(example = "2022-09-10T10:11:12Z")]
+    #[serde(with = "common_utils::custom_serde::iso8601")]
+    pub created: PrimitiveDateTime,
+}
+
+/// The constraints that are applicable when listing the invoices of a merchant.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct InvoiceListConstraints {
+    /// The maximum number of invoices to include in the response.
+    pub limit: Option<i64>,
+
+    /// The number of invoices to skip when retrieving the list of invoices.
+    pub skip: Option<i64>,
+}
+
+/// The query parameters of the payment link of an invoice.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct InvoicePaymentLinkQuery {
+    /// The secret issued with the payment link of the invoice.
+    pub client_secret: String,
+}
+
+/// The request body for paying an invoice through its payment link.
+#[derive(Debug, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct InvoicePaymentRequest {
+    /// The secret issued with the payment link of the invoice.
+    #[schema(value_type = String, example = "inv_4hDjyLsm6Y7BIMC9zPYB_secret_a1b2c3")]
+    pub client_secret: String,
+
+    /// The amount to pay towards the invoice, at most the amount due. If not passed, the entire amount due is paid.
+    #[schema(example = 2000)]
+    pub amount: Option<i64>,
+
+    /// The URL to redirect the customer to after completing the payment.
+    #[schema(value_type = Option<String>, example = "https://hyperswitch.io")]
+    pub return_url: Option<Url>,
+}
@@ -1,18 +1,33 @@
 #![forbid(unsafe_code)]
+pub mod accounting;
 pub mod admin;
+pub mod analytics;
 pub mod api_keys;
+pub mod audit;
 pub mod bank_accounts;
 pub mod cards_info;
+pub mod commissions;
 pub mod customers;
 pub mod disputes;
 pub mod enums;
 pub mod ephemeral_key;
 #[cfg(feature = "errors")]
 pub mod errors;
+pub mod feature_flags;
 pub mod files;
+pub mod gift_cards;
+pub mod invoices;
+pub mod lookup;
 pub mod mandates;
+pub mod payment_links;
+pub mod payment_method_shares;
 pub mod payment_methods;
 pub mod payments;
 pub mod payouts;
 pub mod refunds;
+pub mod reports;
+pub mod routing;
+pub mod runtime_config;
+pub mod subscriptions;
+pub mod terminals;
 pub mod webhooks;
@@ -0,0 +1,68 @@
+use serde::{Deserialize, Serialize};
+use time::PrimitiveDateTime;
+use utoipa::ToSchema;
+
+/// The query parameters for looking up objects by a connector reference.
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ConnectorReferenceLookupRequest {
+    /// The identifier assigned to the payment, refund, dispute or payout by the connector, as
+    /// shown on the connector dashboard
+    #[schema(example = "pi_3NXmYqD5R7gDAGff0KjzuVg4")]
+    pub connector_reference: String,
+
+    /// Only look up objects processed by this connector
+    #[schema(example = "stripe")]
+    pub connector: Option<String>,
+}
+
+/// The kinds of objects which can be looked up by a connector reference.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize, strum::Display, ToSchema)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum LookupObjectType {
+    Payment,
+    Refund,
+    Dispute,
+    Payout,
+}
+
+#[derive(Clone, Debug, Serialize, ToSchema)]
+pub struct ConnectorReferenceLookupResult {
+    /// The kind of object matching the connector reference
+    pub object: LookupObjectType,
+
+    /// The identifier of the matching payment, refund, dispute or payout
+    #[schema(example = "ref_mbabizu24mvu3mela5njyhpit4")]
+    pub id: String,
+
+    /// The identifier of the payment the object belongs to, if any
+    #[schema(example = "pay_mbabizu24mvu3mela5njyhpit4")]
+    pub payment_id: Option<String>,
+
+    /// The connector which processed the object
+    #[schema(example = "stripe")]
+    pub connector: Option<String>,
+
+    /// The status of the object
+    #[schema(example = "success")]
+    pub status: String,
+
+    /// The time at which the object was created
+    #[schema(example = "2022-09-10T10:11:12Z")]
+    #[serde(with = "common_utils::custom_serde::iso8601")]
+    pub created_at: PrimitiveDateTime,
+}
+
+#[derive(Clone, Debug, Serialize, ToSchema)]
+pub struct ConnectorReferenceLookupResponse {
+    /// The connector reference which was looked up
+    #[schema(example = "pi_3NXmYqD5R7gDAGff0KjzuVg4")]
+    pub connector_reference: String,
+
+    /// The number of objects matching the connector reference
+    pub count: usize,
+
+    /// The objects matching the connector reference
+    pub data: Vec<ConnectorReferenceLookupResult>,
+}
@@ -0,0 +1,95 @@
+use serde::{Deserialize, Serialize};
+use time::PrimitiveDateTime;
+use utoipa::ToSchema;
+
+use crate::{admin, enums};
+
+/// The request body for creating a payment link.
+#[derive(Debug, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct PaymentLinkCreateRequest {
+    /// The identifier for the payment paid through the payment link. The payment has to be
+    /// awaiting a payment method.
+    #[schema(max_length = 64, example = "pay_mbabizu24mvu3mela5njyhpit4")]
+    pub payment_id: String,
+
+    /// The duration (in seconds) after which the payment link expires. Defaults to the expiry
+    /// configured for payment links.
+    #[schema(example = 86400)]
+    pub expires_in: Option<u32>,
+
+    /// The branding of the payment link page. Defaults to the branding of the business of the
+    /// payment.
+    pub theme: Option<admin::PaymentLinkConfig>,
+
+    /// The payment method types offered on the payment link page. All the payment method types
+    /// available for the payment are offered when not passed.
+    #[schema(value_type = Option<Vec<PaymentMethodType>>, example = json!(["credit", "google_pay"]))]
+    pub allowed_payment_methods: Option<Vec<enums::PaymentMethodType>>,
+}
+
+/// The response body for a payment link.
+#[derive(Clone, Debug, Serialize, ToSchema)]
+pub struct PaymentLinkResponse {
+    /// The identifier for the payment link.
+    #[schema(max_length = 64, example = "plink_4hDjyLsm6Y7BIMC9zPYB")]
+    pub payment_link_id: String,
+
+    /// The identifier for the Merchant Account.
+    #[schema(max_length = 64, example = "y3oqhf46pyzuxjbcn2giaqnb44")]
+    pub merchant_id: String,
+
+    /// The identifier for the payment paid through the payment link.
+    #[schema(max_length = 64, example = "pay_mbabizu24mvu3mela5njyhpit4")]
+    pub payment_id: String,
+
+    /// The signed URL of the payment link, which can be shared with the customer.
+    #[schema(
+        example = "https://sandbox.hyperswitch.io/payment_links/y3oqhf46pyzuxjbcn2giaqnb44/plink_4hDjyLsm6Y7BIMC9zPYB?signature=5b1e4b8d"
+    )]
+    pub link: String,
+
+    /// The status of the payment link.
+    #[schema(value_type = PaymentLinkStatus, example = "active")]
+    pub status: enums::PaymentLinkStatus,
+
+    /// The branding of the payment link page.
+    pub theme: Option<admin::PaymentLinkConfig>,
+
+    /// The payment method types offered on the payment link page.
+    #[schema(value_type = Option<Vec<PaymentMethodType>>, example = json!(["credit", "google_pay"]))]
+    pub allowed_payment_methods: Option<Vec<enums::PaymentMethodType>>,
+
+    /// The time at which the payment link expires.
+    #[schema(example = "2022-09-10T10:11:12Z")]
+    #[serde(with = "common_utils::custom_serde::iso8601")]
+    pub expires_at: PrimitiveDateTime,
+
+    /// The time at which the payment link was created.
+    #[schema(example = "2022-09-10T10:11:12Z")]
+    #[serde(with = "common_utils::custom_serde::iso8601")]
+    pub created: PrimitiveDateTime,
+}
+
+/// The query parameters of the URL of a payment link.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PaymentLinkQuery {
+    /// The signature of the URL of the payment link.
+    pub signature: String,
+}
+
+/// The configuration with which the SDK is loaded on the payment link page.
+#[derive(Clone, Debug, Serialize)]
+pub struct PaymentLinkSdkConfig {
+    pub publishable_key: String,
+    pub client_secret: String,
+    pub payment_id: String,
+    pub amount: i64,
+    pub currency: enums::Currency,
+    pub return_url: Option<String>,
+    pub theme: admin::PaymentLinkConfig,
+    pub allowed_payment_methods: Option<Vec<enums::PaymentMethodType>>,
+    #[serde(with = "common_utils::custom_serde::iso8601")]
+    pub expires_at: PrimitiveDateTime,
+}
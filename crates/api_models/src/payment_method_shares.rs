@@ -0,0 +1,116 @@
+use serde::{Deserialize, Serialize};
+use time::PrimitiveDateTime;
+use utoipa::ToSchema;
+
+use crate::enums;
+
+/// The request body for sharing a saved payment method with another sub-merchant of the platform.
+#[derive(Debug, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct PaymentMethodShareRequest {
+    /// The identifier for the sub-merchant with which the payment method is shared. The
+    /// sub-merchant has to be under the same platform as the merchant owning the payment method.
+    #[schema(max_length = 64, example = "merchant_1668273825")]
+    pub merchant_id: String,
+
+    /// The consent given by the customer to share the payment method with the sub-merchant.
+    pub consent: PaymentMethodShareConsent,
+
+    /// The maximum number of payments the sub-merchant can make with the payment method. The
+    /// number of payments is not limited when not passed.
+    #[schema(example = 12)]
+    pub max_uses: Option<u32>,
+
+    /// The maximum amount of each payment the sub-merchant can make with the payment method, in
+    /// the lowest denomination of `currency`.
+    #[schema(example = 10000)]
+    pub max_amount: Option<i64>,
+
+    /// The currency of `max_amount`. Payments in other currencies cannot be made with the payment
+    /// method when `max_amount` is passed.
+    #[schema(value_type = Option<Currency>, example = "USD")]
+    pub currency: Option<enums::Currency>,
+
+    /// The time after which the sub-merchant can no longer use the payment method.
+    #[schema(example = "2023-12-31T23:59:59Z")]
+    #[serde(default, with = "common_utils::custom_serde::iso8601::option")]
+    pub expires_at: Option<PrimitiveDateTime>,
+}
+
+/// The consent given by a customer to share their payment method with a sub-merchant.
+#[derive(Clone, Debug, Deserialize, Serialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct PaymentMethodShareConsent {
+    /// The reference of the consent in the records of the merchant.
+    #[schema(max_length = 255, example = "consent_4hDjyLsm6Y7BIMC9zPYB")]
+    pub reference: String,
+
+    /// The time at which the customer gave their consent.
+    #[schema(example = "2023-09-10T10:11:12Z")]
+    #[serde(with = "common_utils::custom_serde::iso8601")]
+    pub consented_at: PrimitiveDateTime,
+
+    /// The IP address from which the customer gave their consent.
+    #[schema(max_length = 64, example = "203.0.113.10")]
+    pub ip_address: Option<String>,
+}
+
+/// The response body for a payment method shared with a sub-merchant.
+#[derive(Clone, Debug, Serialize, ToSchema)]
+pub struct PaymentMethodShareResponse {
+    /// The identifier for the share, passed by the sub-merchant as the
+    /// `shared_payment_method_id` of its payments to pay with the payment method.
+    #[schema(max_length = 64, example = "pms_4hDjyLsm6Y7BIMC9zPYB")]
+    pub share_id: String,
+
+    /// The identifier for the shared payment method.
+    #[schema(max_length = 64, example = "pm_y3oqhf46pyzuxjbcn2giaqnb44")]
+    pub payment_method_id: String,
+
+    /// The identifier for the merchant owning the payment method.
+    #[schema(max_length = 64, example = "y3oqhf46pyzuxjbcn2giaqnb44")]
+    pub owner_merchant_id: String,
+
+    /// The identifier for the sub-merchant with which the payment method is shared.
+    #[schema(max_length = 64, example = "merchant_1668273825")]
+    pub merchant_id: String,
+
+    /// The status of the share.
+    #[schema(value_type = PaymentMethodShareStatus, example = "active")]
+    pub status: enums::PaymentMethodShareStatus,
+
+    /// The consent given by the customer to share the payment method.
+    pub consent: PaymentMethodShareConsent,
+
+    /// The maximum number of payments the sub-merchant can make with the payment method.
+    #[schema(example = 12)]
+    pub max_uses: Option<i32>,
+
+    /// The number of payments the sub-merchant has confirmed with the payment method, not counting
+    /// the payments which failed.
+    #[schema(example = 3)]
+    pub use_count: i32,
+
+    /// The maximum amount of each payment the sub-merchant can make with the payment method.
+    #[schema(example = 10000)]
+    pub max_amount: Option<i64>,
+
+    /// The currency of `max_amount`.
+    #[schema(value_type = Option<Currency>, example = "USD")]
+    pub currency: Option<enums::Currency>,
+
+    /// The time after which the sub-merchant can no longer use the payment method.
+    #[schema(example = "2023-12-31T23:59:59Z")]
+    #[serde(with = "common_utils::custom_serde::iso8601::option")]
+    pub expires_at: Option<PrimitiveDateTime>,
+
+    /// The time at which the share was revoked.
+    #[schema(example = "2023-10-01T10:11:12Z")]
+    #[serde(with = "common_utils::custom_serde::iso8601::option")]
+    pub revoked_at: Option<PrimitiveDateTime>,
+
+    /// The time at which the payment method was shared.
+    #[schema(example = "2023-09-10T10:11:12Z")]
+    #[serde(with = "common_utils::custom_serde::iso8601")]
+    pub created: PrimitiveDateTime,
+}
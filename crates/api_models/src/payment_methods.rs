@@ -142,6 +142,28 @@ pub struct PaymentMethodResponse {
     #[schema(value_type = Option<PrimitiveDateTime>, example = "2023-01-18T11:04:09.922Z")]
     #[serde(default, with = "common_utils::custom_serde::iso8601::option")]
     pub created: Option<time::PrimitiveDateTime>,
+
+    /// The last update of the card reported by the account updater service of a connector
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub account_update: Option<PaymentMethodAccountUpdate>,
+}
+
+/// An update of a saved card reported by the account updater service of a connector, such as
+/// Visa Account Updater or Mastercard Automatic Billing Updater
+#[derive(Debug, serde::Deserialize, serde::Serialize, Clone, ToSchema)]
+pub struct PaymentMethodAccountUpdate {
+    /// The kind of update reported for the card
+    #[schema(example = "expiry_updated")]
+    pub update_type: api_enums::AccountUpdateType,
+
+    /// The connector whose account updater service reported the update
+    #[schema(example = "adyen")]
+    pub source: String,
+
+    /// The time at which the card was updated
+    #[schema(value_type = PrimitiveDateTime, example = "2023-08-25T11:04:09.922Z")]
+    #[serde(with = "common_utils::custom_serde::iso8601")]
+    pub updated_at: time::PrimitiveDateTime,
 }
 
 #[derive(Debug, serde::Deserialize, serde::Serialize, Clone, ToSchema)]
@@ -320,6 +342,15 @@ pub struct RequestPaymentMethodTypes {
     /// Boolean to enable installment / EMI / BNPL payments. Default is true.
     #[schema(default = true, example = false)]
     pub installment_payment_enabled: bool,
+
+    /// Boolean to enable the payment method type for the connector. A disabled payment method type retains its configuration, but is neither listed nor used for payments. Default is true.
+    #[schema(default = true, example = true)]
+    #[serde(default = "default_payment_method_type_enabled")]
+    pub enabled: bool,
+}
+
+fn default_payment_method_type_enabled() -> bool {
+    true
 }
 
 //List Payment Method
@@ -528,6 +559,10 @@ pub struct CustomerPaymentMethod {
     #[schema(example = "7ebf443f-a050-4067-84e5-e6f6d4800aef")]
     pub payment_token: String,
 
+    /// The unique identifier of the saved payment method
+    #[schema(example = "pm_iouuy468iyuowqs")]
+    pub payment_method_id: String,
+
     /// The unique identifier of the customer.
     #[schema(example = "cus_meowerunwiuwiwqw")]
     pub customer_id: String,
@@ -581,6 +616,16 @@ pub struct CustomerPaymentMethod {
     /// Whether this payment method requires CVV to be collected
     #[schema(example = true)]
     pub requires_cvv: bool,
+
+    /// Whether this payment method is the default payment method of the customer
+    #[schema(example = true)]
+    pub is_default: bool,
+
+    /// A timestamp (ISO 8601 code) that determines when the payment method was last used in a
+    /// successful payment
+    #[schema(value_type = Option<PrimitiveDateTime>,example = "2023-01-18T11:04:09.922Z")]
+    #[serde(default, with = "common_utils::custom_serde::iso8601::option")]
+    pub last_used_at: Option<time::PrimitiveDateTime>,
 }
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct PaymentMethodId {
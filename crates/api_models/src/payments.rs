@@ -1,4 +1,7 @@
-use std::num::NonZeroI64;
+use std::{
+    collections::{BTreeMap, HashMap},
+    num::NonZeroI64,
+};
 
 use cards::CardNumber;
 use common_utils::{
@@ -13,7 +16,8 @@ use url::Url;
 use utoipa::ToSchema;
 
 use crate::{
-    admin, disputes, enums as api_enums, ephemeral_key::EphemeralKeyCreateResponse, refunds,
+    admin, customers, disputes, enums as api_enums, ephemeral_key::EphemeralKeyCreateResponse,
+    refunds,
 };
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
@@ -210,6 +214,14 @@ pub struct PaymentsRequest {
     /// The billing address for the payment
     pub billing: Option<Address>,
 
+    /// The identifier of an address in the address book of the customer, used as the shipping address when `shipping` is not provided. Only applicable when creating a payment
+    #[schema(max_length = 64, example = "add_5ghsy3ft5tw3ghsif7sf")]
+    pub shipping_address_id: Option<String>,
+
+    /// The identifier of an address in the address book of the customer, used as the billing address when `billing` is not provided. Only applicable when creating a payment
+    #[schema(max_length = 64, example = "add_5ghsy3ft5tw3ghsif7sf")]
+    pub billing_address_id: Option<String>,
+
     /// For non-card charges, you can use this value as the complete description that appears on your customers’ statements. Must contain at least one letter, maximum 22 characters.
     #[schema(max_length = 255, example = "Hyperswitch Router")]
     pub statement_descriptor_name: Option<String>,
@@ -291,6 +303,57 @@ pub struct PaymentsRequest {
 
     /// additional data that might be required by hyperswitch
     pub feature_metadata: Option<FeatureMetadata>,
+
+    /// The identifier of the invoice to which this payment is applied. The amount of the payment
+    /// must not exceed the amount due on the invoice
+    #[schema(max_length = 64, example = "inv_4hDjyLsm6Y7BIMC9zPYB")]
+    pub invoice_id: Option<String>,
+
+    /// A fee charged by the merchant on top of the order, included in the amount of the payment
+    pub convenience_fee: Option<ConvenienceFee>,
+
+    /// Level 2 and level 3 data of a card payment, passed to connectors which support it so that
+    /// business to business payments qualify for reduced interchange rates
+    pub l2_l3_data: Option<L2L3Data>,
+
+    /// The currency in which the customer is charged, when different from `currency`. The amount
+    /// is converted at the current exchange rate and charged through a connector supporting
+    /// dynamic currency conversion, with the payment settled to the merchant in `currency`. Only
+    /// considered when the payment is created
+    #[schema(value_type = Option<Currency>, example = "EUR")]
+    pub presentment_currency: Option<api_enums::Currency>,
+
+    /// Whether the payment is a live payment or a test payment, which selects the live or test
+    /// account details of connector accounts holding both. If not provided, the connector account
+    /// is used with its own account details. Only considered when the payment is created
+    #[schema(example = true)]
+    pub livemode: Option<bool>,
+
+    /// The token obtained by the customer on solving the CAPTCHA presented through the
+    /// `solve_captcha` next action, to be passed when confirming the payment again
+    #[schema(value_type = Option<String>)]
+    pub captcha_token: Option<Secret<String>>,
+
+    /// The identifier of a saved card shared with the merchant by another sub-merchant of the same
+    /// platform, used as the payment method of the payment. The CVC of the card can be passed in
+    /// `card_cvc`. It must be passed when confirming the payment, which only then counts against the
+    /// maximum number of uses of the share
+    #[schema(max_length = 64, example = "pms_4hDjyLsm6Y7BIMC9zPYB")]
+    pub shared_payment_method_id: Option<String>,
+
+    /// Whether to only simulate the confirmation of the payment. The payment is routed and
+    /// validated as it would be on confirmation, and the request which would be sent to the
+    /// connector is returned redacted, without calling the connector. Only considered when
+    /// confirming the payment through the confirm API, and not available in production
+    #[schema(default = false, example = true)]
+    pub simulate: Option<bool>,
+
+    /// The identifier of a gift card of the merchant whose balance is redeemed towards the payment,
+    /// so that only the remaining amount is charged to the payment method of the payment. Only
+    /// considered when confirming the payment through the confirm API. The amount redeemed is
+    /// returned to the gift card if the payment fails
+    #[schema(max_length = 64, example = "gc_4hDjyLsm6Y7BIMC9zPYB")]
+    pub gift_card_id: Option<String>,
 }
 
 #[derive(
@@ -341,6 +404,36 @@ pub struct PaymentAttemptResponse {
     /// reference to the payment at connector side
     #[schema(value_type = Option<String>, example = "993672945374576J")]
     pub reference_id: Option<String>,
+    /// The attempt which was retried to create this attempt, if any
+    pub previous_attempt_id: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, serde::Serialize, ToSchema)]
+pub struct CaptureResponse {
+    /// Unique identifier for the capture
+    pub capture_id: String,
+    /// The status of the capture
+    #[schema(value_type = CaptureStatus, example = "charged")]
+    pub status: enums::CaptureStatus,
+    /// The capture amount. Amount for the capture in lowest denomination of the currency. (i.e) in cents for USD denomination, in paisa for INR denomination etc.,
+    pub amount: i64,
+    /// The currency of the amount of the capture
+    #[schema(value_type = Option<Currency>, example = "usd")]
+    pub currency: Option<enums::Currency>,
+    /// The connector used for the capture
+    pub connector: Option<String>,
+    /// Unique identifier for the parent attempt on which the capture is made
+    pub authorized_attempt_id: String,
+    /// A unique identifier for the capture provided by the connector
+    pub connector_capture_id: Option<String>,
+    /// Sequence number of the capture among the captures of the payment, starting at 1
+    pub capture_sequence: i16,
+    /// If there was an error while calling the connector the error message is received here
+    pub error_message: Option<String>,
+    /// If there was an error while calling the connectors the code is received here
+    pub error_code: Option<String>,
+    /// If there was an error while calling the connectors the reason is received here
+    pub error_reason: Option<String>,
 }
 
 impl PaymentsRequest {
@@ -380,6 +473,18 @@ impl PaymentsRequest {
             .transpose()
     }
 
+    pub fn get_l2_l3_data_as_value(
+        &self,
+    ) -> common_utils::errors::CustomResult<
+        Option<serde_json::Value>,
+        common_utils::errors::ParsingError,
+    > {
+        self.l2_l3_data
+            .as_ref()
+            .map(Encode::<L2L3Data>::encode_to_value)
+            .transpose()
+    }
+
     pub fn get_order_details_as_value(
         &self,
     ) -> common_utils::errors::CustomResult<
@@ -741,6 +846,22 @@ pub enum PaymentMethodData {
     Upi(UpiData),
     Voucher(VoucherData),
     GiftCard(Box<GiftCardData>),
+    CardPresent(CardPresentData),
+}
+
+#[derive(serde::Deserialize, serde::Serialize, Debug, Clone, ToSchema, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub struct CardPresentData {
+    /// The identifier of the registered terminal on which the card was presented
+    #[schema(max_length = 64, example = "term_4hDjyLsm6Y7BIMC9zPYB")]
+    pub terminal_id: String,
+    /// The way in which the card details were captured by the terminal
+    #[schema(value_type = CardPresentEntryMode, example = "chip")]
+    pub entry_mode: api_enums::CardPresentEntryMode,
+    /// Reference to the encrypted EMV (chip) data read by the terminal, as issued by the processor's terminal SDK
+    pub emv_data_reference: Option<String>,
+    /// Reference to the encrypted track data read from the magnetic stripe, as issued by the processor's terminal SDK
+    pub track_data_reference: Option<String>,
 }
 
 #[derive(serde::Deserialize, serde::Serialize, Debug, Clone, ToSchema, Eq, PartialEq)]
@@ -794,6 +915,7 @@ pub enum AdditionalPaymentData {
     GiftCard {},
     Voucher {},
     CardRedirect {},
+    CardPresent {},
 }
 
 #[derive(Debug, Clone, Eq, PartialEq, serde::Deserialize, serde::Serialize, ToSchema)]
@@ -1362,6 +1484,7 @@ pub enum PaymentMethodDataResponse {
     Voucher,
     GiftCard,
     CardRedirect,
+    CardPresent,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize, ToSchema)]
@@ -1481,6 +1604,188 @@ pub struct PaymentsCaptureRequest {
     pub merchant_connector_details: Option<admin::MerchantConnectorDetailsWrap>,
 }
 
+#[derive(Default, Debug, Clone, serde::Deserialize, serde::Serialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct PaymentsRetryRequest {
+    /// The routing algorithm to be used for the new attempt, this can be used to retry the payment with a different connector
+    #[schema(value_type = Option<RoutingAlgorithm>, example = json!({
+        "type": "single",
+        "data": "adyen"
+    }))]
+    pub routing: Option<serde_json::Value>,
+
+    /// The authentication type to be used for the new attempt, pass `three_ds` to force 3DS. If not provided, the authentication type of the previous attempt is used
+    #[schema(value_type = Option<AuthenticationType>, example = "three_ds")]
+    pub authentication_type: Option<api_enums::AuthenticationType>,
+
+    /// The payment method information to be used for the new attempt
+    pub payment_method_data: Option<PaymentMethodData>,
+
+    /// The payment method that is to be used
+    #[schema(value_type = Option<PaymentMethod>, example = "card")]
+    pub payment_method: Option<api_enums::PaymentMethod>,
+
+    /// Payment Method Type
+    #[schema(value_type = Option<PaymentMethodType>, example = "credit")]
+    pub payment_method_type: Option<api_enums::PaymentMethodType>,
+
+    /// Provide a reference to a stored payment method
+    #[schema(example = "187282ab-40ef-47a9-9206-5099ba31e432")]
+    pub payment_token: Option<String>,
+
+    /// The corrected billing address for the payment, this replaces the billing address of the payment
+    pub billing: Option<Address>,
+
+    /// Additional details required by 3DS 2.0
+    #[schema(value_type = Option<Object>)]
+    pub browser_info: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct PaymentsMetadataUpdateRequest {
+    /// The metadata merged into the metadata of the payment. Keys in the request replace the keys of the same name in the metadata of the payment, and keys set to `null` are removed from it. The request can specify up to 50 keys, with key names up to 40 characters long and values up to 500 characters long
+    #[schema(value_type = Object, example = r#"{ "shipment_reference": "SHP-2023-0042", "invoice_number": "INV-1024" }"#)]
+    pub metadata: pii::SecretSerdeValue,
+}
+
+#[derive(Debug, Clone, serde::Serialize, ToSchema)]
+pub struct PaymentsMetadataUpdateResponse {
+    /// The identifier for the payment
+    #[schema(max_length = 64, example = "pay_mbabizu24mvu3mela5njyhpit4")]
+    pub payment_id: String,
+
+    /// The metadata of the payment after the update
+    #[schema(value_type = Object, example = r#"{ "udf1": "some-value", "shipment_reference": "SHP-2023-0042" }"#)]
+    pub metadata: pii::SecretSerdeValue,
+}
+
+#[derive(Debug, Clone, serde::Serialize, ToSchema)]
+pub struct PaymentMetadataAuditResponse {
+    /// The metadata of the payment before the update
+    #[schema(value_type = Option<Object>, example = r#"{ "udf1": "some-value" }"#)]
+    pub previous_metadata: Option<pii::SecretSerdeValue>,
+
+    /// The metadata of the payment after the update
+    #[schema(value_type = Object, example = r#"{ "udf1": "some-value", "shipment_reference": "SHP-2023-0042" }"#)]
+    pub updated_metadata: pii::SecretSerdeValue,
+
+    /// The time at which the metadata was updated
+    #[schema(value_type = PrimitiveDateTime, example = "2022-09-10T10:11:12Z")]
+    #[serde(with = "common_utils::custom_serde::iso8601")]
+    pub created_at: PrimitiveDateTime,
+}
+
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct PaymentsIncrementalAuthorizationRequest {
+    /// The identifier for the payment
+    #[serde(skip)]
+    pub payment_id: String,
+
+    /// The total amount to be authorized for the payment, in the lowest denomination of its currency. It must be greater than the amount currently authorized
+    #[schema(example = 8000)]
+    pub amount: i64,
+
+    /// The reason for increasing the authorized amount, such as an extended stay or additional charges
+    #[schema(example = "Stay extended by two nights")]
+    pub reason: Option<String>,
+}
+
+#[derive(Debug, Clone, serde::Serialize, ToSchema)]
+pub struct PaymentsIncrementalAuthorizationResponse {
+    /// The identifier for the payment
+    #[schema(max_length = 64, example = "pay_mbabizu24mvu3mela5njyhpit4")]
+    pub payment_id: String,
+
+    /// The identifier for the attempt whose authorization was increased
+    pub attempt_id: String,
+
+    /// The status of the incremental authorization
+    #[schema(value_type = AuthorizationStatus, example = "success")]
+    pub status: api_enums::AuthorizationStatus,
+
+    /// The amount currently authorized for the payment, which remains unchanged if the incremental authorization did not succeed
+    #[schema(example = 8000)]
+    pub authorized_amount: i64,
+
+    /// The authorizations made for the attempt, oldest first
+    pub authorizations: Vec<AuthorizationRecord>,
+}
+
+/// An incremental authorization made for an attempt
+#[derive(Debug, Clone, PartialEq, serde::Deserialize, serde::Serialize, ToSchema)]
+pub struct AuthorizationRecord {
+    /// The amount which was authorized before the incremental authorization
+    #[schema(example = 6540)]
+    pub previous_amount: i64,
+
+    /// The total amount requested to be authorized
+    #[schema(example = 8000)]
+    pub amount: i64,
+
+    /// The status of the incremental authorization
+    #[schema(value_type = AuthorizationStatus, example = "success")]
+    pub status: api_enums::AuthorizationStatus,
+
+    /// The reason provided for the incremental authorization
+    pub reason: Option<String>,
+
+    /// A unique identifier for the incremental authorization provided by the connector
+    pub connector_authorization_id: Option<String>,
+
+    /// If the connector declined the incremental authorization the error code is received here
+    pub error_code: Option<String>,
+
+    /// If the connector declined the incremental authorization the error message is received here
+    pub error_message: Option<String>,
+
+    /// The time at which the incremental authorization was made
+    #[schema(value_type = PrimitiveDateTime, example = "2022-09-10T10:11:12Z")]
+    #[serde(with = "common_utils::custom_serde::iso8601")]
+    pub created_at: PrimitiveDateTime,
+}
+
+#[derive(Debug, Clone, serde::Deserialize)]
+pub struct HostedCheckoutQuery {
+    /// The client secret of the payment, which authorizes the customer to access its checkout page
+    pub client_secret: String,
+}
+
+#[derive(Debug, Clone, serde::Deserialize)]
+pub struct HostedCheckoutSubmitRequest {
+    /// The client secret of the payment being confirmed
+    pub client_secret: String,
+
+    /// The payment method selected by the customer
+    pub payment_method: api_enums::PaymentMethod,
+
+    /// The payment method type selected by the customer
+    pub payment_method_type: api_enums::PaymentMethodType,
+
+    /// The fields filled in by the customer, keyed by the path of the field in the payments request
+    #[serde(flatten)]
+    pub fields: HashMap<String, Secret<String>>,
+}
+
+/// The formats in which the receipt of a payment can be retrieved.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, serde::Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ReceiptFormat {
+    /// An HTML page, which can be displayed to the customer as is
+    #[default]
+    Html,
+    /// A PDF document, which can be downloaded by the customer
+    Pdf,
+}
+
+#[derive(Debug, Clone, Default, serde::Deserialize)]
+pub struct PaymentReceiptQuery {
+    /// The format of the receipt document. Defaults to `html`
+    #[serde(default)]
+    pub format: ReceiptFormat,
+}
+
 #[derive(Default, Clone, Debug, Eq, PartialEq, serde::Serialize)]
 pub struct UrlDetails {
     pub url: String,
@@ -1528,6 +1833,8 @@ pub enum NextActionData {
         display_from_timestamp: i128,
         display_to_timestamp: Option<i128>,
     },
+    /// Contains the site key of the CAPTCHA to be solved by the customer before the payment can be confirmed
+    SolveCaptcha { site_key: String },
 }
 
 #[derive(Clone, Debug, Eq, PartialEq, serde::Serialize, serde::Deserialize, ToSchema)]
@@ -1708,6 +2015,20 @@ pub struct PaymentsResponse {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub attempts: Option<Vec<PaymentAttemptResponse>>,
 
+    /// The customer of the payment, present only if `customer` was expanded in the retrieve request
+    #[schema(value_type = Option<CustomerResponse>)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub customer: Option<customers::CustomerResponse>,
+
+    /// The number of captures made on the payment, present only for payments captured with multiple partial captures
+    #[schema(example = 2)]
+    pub capture_count: Option<i16>,
+
+    /// List of captures made on the payment, present only if `expand_captures` was enabled in the retrieve request or the payment was just captured
+    #[schema(value_type = Option<Vec<CaptureResponse>>)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub captures: Option<Vec<CaptureResponse>>,
+
     /// A unique identifier to link the payment to a mandate, can be use instead of payment_method_data
     #[schema(max_length = 255, example = "mandate_iwer89rnjef349dni3")]
     pub mandate_id: Option<String>,
@@ -1858,6 +2179,87 @@ pub struct PaymentsResponse {
     /// reference to the payment at connector side
     #[schema(value_type = Option<String>, example = "993672945374576J")]
     pub reference_id: Option<String>,
+
+    /// The identifier of the invoice to which this payment is applied
+    #[schema(max_length = 64, example = "inv_4hDjyLsm6Y7BIMC9zPYB")]
+    pub invoice_id: Option<String>,
+
+    /// The convenience fee charged by the merchant, along with the tax charged on it
+    #[schema(value_type = Option<ConvenienceFeeDetails>)]
+    pub convenience_fee: Option<serde_json::Value>, // This is Value because it is fetched from DB and before putting in DB the type is validated
+
+    /// Level 2 and level 3 data of the card payment
+    #[schema(value_type = Option<L2L3Data>)]
+    pub l2_l3_data: Option<serde_json::Value>, // This is Value because it is fetched from DB and before putting in DB the type is validated
+
+    /// The amounts charged to the customer and settled to the merchant, if the payment is charged
+    /// in a currency other than the settlement currency
+    pub currency_conversion: Option<CurrencyConversionDetails>,
+
+    /// The estimated international transaction fee and the cross-border surcharge of the payment,
+    /// if the customer is in a country other than the business country of the payment
+    #[schema(value_type = Option<CrossBorderFeeDetails>)]
+    pub cross_border_fees: Option<serde_json::Value>, // This is Value because it is fetched from DB and before putting in DB the type is validated
+
+    /// Whether the payment is a live payment or a test payment
+    #[schema(example = true)]
+    pub livemode: Option<bool>,
+}
+
+/// The outcome of simulating the confirmation of a payment
+#[derive(Clone, Debug, serde::Serialize, ToSchema)]
+pub struct PaymentsSimulationResponse {
+    /// The identifier for the payment
+    #[schema(max_length = 255, example = "pay_mbabizu24mvu3mela5njyhpit4")]
+    pub payment_id: String,
+
+    /// The connector to which the payment would be routed
+    #[schema(example = "stripe")]
+    pub connector: String,
+
+    /// The authentication type with which the payment would be processed, which decides whether
+    /// 3DS would be performed
+    #[schema(value_type = Option<AuthenticationType>, example = "three_ds")]
+    pub authentication_type: Option<api_enums::AuthenticationType>,
+
+    /// The amount which would be authorized by the connector
+    #[schema(example = 6540)]
+    pub amount: i64,
+
+    /// The surcharge which would be applied to the payment
+    #[schema(example = 100)]
+    pub surcharge_amount: Option<i64>,
+
+    /// The currency of the payment
+    #[schema(value_type = Currency, example = "USD")]
+    pub currency: api_enums::Currency,
+
+    /// The request which would be sent to the connector, with its sensitive values redacted
+    pub connector_request: Option<ConnectorRequestPreview>,
+
+    /// The reason the request to the connector could not be built, if it could not be
+    #[schema(example = "Missing required field: browser_info")]
+    pub connector_request_error: Option<String>,
+}
+
+/// A request to a connector, with its sensitive values redacted
+#[derive(Clone, Debug, serde::Serialize, ToSchema)]
+pub struct ConnectorRequestPreview {
+    /// The HTTP method of the request
+    #[schema(example = "POST")]
+    pub method: String,
+
+    /// The URL of the request, with its query string redacted
+    #[schema(example = "https://api.stripe.com/v1/payment_intents")]
+    pub url: String,
+
+    /// The headers of the request, with the values of sensitive headers redacted
+    #[schema(value_type = Object)]
+    pub headers: BTreeMap<String, String>,
+
+    /// The body of the request, with the values of sensitive fields redacted
+    #[schema(value_type = Option<Object>)]
+    pub body: Option<serde_json::Value>,
 }
 
 #[derive(Clone, Debug, serde::Deserialize, ToSchema)]
@@ -1925,6 +2327,11 @@ pub struct PaymentListResponse {
     pub size: usize,
     // The list of payments response objects
     pub data: Vec<PaymentsResponse>,
+    /// The cursor to pass as `starting_after` to fetch the next page of payments, present when
+    /// the page is full
+    #[schema(example = "pay_fafa124123")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
 }
 
 #[derive(Clone, Debug, serde::Deserialize)]
@@ -1932,7 +2339,13 @@ pub struct PaymentListResponse {
 pub struct PaymentListFilterConstraints {
     /// The identifier for payment
     pub payment_id: Option<String>,
-    /// The starting point within a list of objects, limit on number of object will be some constant for join query
+    /// The maximum number of payments to return, 20 by default and at most 100
+    pub limit: Option<u32>,
+    /// A cursor for use in pagination, the `next_cursor` of the previous page. Payments created
+    /// before the payment with this identifier are returned
+    pub starting_after: Option<String>,
+    /// The starting point within a list of objects. This field will be deprecated soon, use
+    /// `starting_after` instead
     pub offset: Option<i64>,
     /// The time range for which objects are needed. TimeRange has two fields start_time and end_time from which objects can be filtered as per required scenarios (created_at, time less than, greater than etc).
     #[serde(flatten)]
@@ -1945,6 +2358,64 @@ pub struct PaymentListFilterConstraints {
     pub status: Option<Vec<enums::IntentStatus>>,
     /// The list of payment methods to filter payments list
     pub payment_methods: Option<Vec<enums::PaymentMethod>>,
+    /// The list of payment method types to filter payments list
+    pub payment_method_types: Option<Vec<enums::PaymentMethodType>>,
+    /// The range of amounts to filter payments list
+    pub amount: Option<AmountFilter>,
+    /// The email of the customer to filter payments list
+    pub customer_email: Option<Email>,
+    /// The list of status groups to filter payments list, each of which matches several payment
+    /// statuses
+    pub status_groups: Option<Vec<PaymentStatusGroup>>,
+}
+
+/// The range of amounts (in the lowest denomination of the currency) to filter payments by, both
+/// bounds included.
+#[derive(Clone, Copy, Debug, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct AmountFilter {
+    pub start_amount: Option<i64>,
+    pub end_amount: Option<i64>,
+}
+
+/// Groups of payment statuses to filter payments by.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PaymentStatusGroup {
+    /// Payments which succeeded, including the partially captured ones
+    Succeeded,
+    /// Payments which failed
+    Failed,
+    /// Payments which were cancelled
+    Cancelled,
+    /// Payments awaiting the connector, the customer, the merchant or a capture
+    Pending,
+    /// Payments which have not been confirmed with a payment method yet
+    Incomplete,
+}
+
+impl PaymentStatusGroup {
+    /// Returns the payment statuses in the group.
+    pub fn get_statuses(self) -> &'static [enums::IntentStatus] {
+        match self {
+            Self::Succeeded => &[
+                enums::IntentStatus::Succeeded,
+                enums::IntentStatus::PartiallyCaptured,
+            ],
+            Self::Failed => &[enums::IntentStatus::Failed],
+            Self::Cancelled => &[enums::IntentStatus::Cancelled],
+            Self::Pending => &[
+                enums::IntentStatus::Processing,
+                enums::IntentStatus::RequiresCustomerAction,
+                enums::IntentStatus::RequiresMerchantAction,
+                enums::IntentStatus::RequiresCapture,
+            ],
+            Self::Incomplete => &[
+                enums::IntentStatus::RequiresPaymentMethod,
+                enums::IntentStatus::RequiresConfirmation,
+            ],
+        }
+    }
 }
 #[derive(Clone, Debug, serde::Serialize)]
 pub struct PaymentListFilters {
@@ -2042,6 +2513,8 @@ impl From<PaymentsSessionRequest> for PaymentsSessionResponse {
         let client_secret: Secret<String, pii::ClientSecret> = Secret::new(item.client_secret);
         Self {
             session_token: vec![],
+            timed_out_connectors: vec![],
+            deferred_connectors: vec![],
             payment_id: item.payment_id,
             client_secret,
         }
@@ -2090,6 +2563,7 @@ impl From<AdditionalPaymentData> for PaymentMethodDataResponse {
             AdditionalPaymentData::Voucher {} => Self::Voucher,
             AdditionalPaymentData::GiftCard {} => Self::GiftCard,
             AdditionalPaymentData::CardRedirect {} => Self::CardRedirect,
+            AdditionalPaymentData::CardPresent {} => Self::CardPresent,
         }
     }
 }
@@ -2128,6 +2602,8 @@ pub struct PaymentsRetrieveRequest {
     pub merchant_id: Option<String>,
     /// Decider to enable or disable the connector call for retrieve request
     pub force_sync: bool,
+    /// Syncs with the connector even if the payment was synced within the sync cache window
+    pub bypass_sync_cache: Option<bool>,
     /// The parameters passed to a retrieve request
     pub param: Option<String>,
     /// The name of the connector
@@ -2139,6 +2615,36 @@ pub struct PaymentsRetrieveRequest {
     pub client_secret: Option<String>,
     /// If enabled provides list of attempts linked to payment intent
     pub expand_attempts: Option<bool>,
+    /// If enabled provides list of captures linked to the active attempt of the payment intent
+    pub expand_captures: Option<bool>,
+    /// If disabled the list of refunds linked to payment intent is not provided, it is provided by default
+    pub expand_refunds: Option<bool>,
+    /// If disabled the list of disputes linked to payment intent is not provided, it is provided by default
+    pub expand_disputes: Option<bool>,
+    /// If enabled provides the customer of the payment intent
+    pub expand_customer: Option<bool>,
+}
+
+/// The related resources which can be embedded in the response of a payment retrieve request
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Eq,
+    PartialEq,
+    serde::Deserialize,
+    serde::Serialize,
+    strum::Display,
+    strum::EnumString,
+)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum PaymentExpandableResource {
+    Attempts,
+    Captures,
+    Refunds,
+    Disputes,
+    Customer,
 }
 
 #[derive(Debug, Default, Eq, PartialEq, serde::Deserialize, serde::Serialize, Clone, ToSchema)]
@@ -2153,6 +2659,195 @@ pub struct OrderDetailsWithAmount {
     pub amount: i64,
 }
 
+/// How tax applies to a convenience fee
+#[derive(
+    Clone, Copy, Debug, Default, Eq, PartialEq, serde::Deserialize, serde::Serialize, ToSchema,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum ConvenienceFeeTaxTreatment {
+    /// No tax is charged on the fee
+    #[default]
+    Exempt,
+    /// The amount of the fee already includes the tax
+    Inclusive,
+    /// The tax is charged on top of the amount of the fee
+    Exclusive,
+}
+
+/// A fee defined by the merchant for a payment, such as a fee for paying online. Unlike a
+/// surcharge, it is neither computed nor configured by hyperswitch.
+#[derive(Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize, Clone, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ConvenienceFee {
+    /// The amount of the fee, in the lowest denomination of the currency of the payment
+    #[schema(minimum = 1, example = 250)]
+    pub amount: i64,
+
+    /// A description of the fee, used as the name of the fee line in the order details
+    #[schema(max_length = 255, example = "Convenience fee")]
+    pub description: Option<String>,
+
+    /// How tax applies to the fee
+    #[serde(default)]
+    pub tax_treatment: ConvenienceFeeTaxTreatment,
+
+    /// The rate at which the fee is taxed, in basis points. Required unless the fee is exempt from
+    /// tax
+    #[schema(maximum = 10000, example = 1800)]
+    pub tax_rate: Option<u16>,
+}
+
+/// The convenience fee of a payment, along with the tax charged on it
+#[derive(Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize, Clone, ToSchema)]
+pub struct ConvenienceFeeDetails {
+    /// The amount of the fee, excluding tax
+    #[schema(example = 250)]
+    pub amount: i64,
+
+    /// The tax charged on the fee
+    #[schema(example = 45)]
+    pub tax_amount: i64,
+
+    /// The amount charged to the customer for the fee, including tax
+    #[schema(example = 295)]
+    pub total_amount: i64,
+
+    /// A description of the fee
+    #[schema(max_length = 255, example = "Convenience fee")]
+    pub description: Option<String>,
+
+    /// How tax applies to the fee
+    pub tax_treatment: ConvenienceFeeTaxTreatment,
+
+    /// The rate at which the fee is taxed, in basis points
+    #[schema(example = 1800)]
+    pub tax_rate: Option<u16>,
+}
+
+/// The conversion of a payment charged to the customer in a currency other than the settlement
+/// currency of the merchant
+#[derive(Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize, Clone, ToSchema)]
+pub struct CurrencyConversionDetails {
+    /// The amount charged to the customer, in the presentment currency
+    #[schema(example = 6160)]
+    pub presentment_amount: i64,
+
+    /// The currency in which the customer is charged
+    #[schema(value_type = Currency, example = "EUR")]
+    pub presentment_currency: api_enums::Currency,
+
+    /// The amount settled to the merchant, in the settlement currency
+    #[schema(example = 6540)]
+    pub settlement_amount: i64,
+
+    /// The currency in which the payment is settled to the merchant
+    #[schema(value_type = Currency, example = "USD")]
+    pub settlement_currency: api_enums::Currency,
+
+    /// The exchange rate from the settlement currency to the presentment currency at which the
+    /// amount was converted
+    #[schema(example = "0.9419")]
+    pub exchange_rate: String,
+}
+
+/// The fees disclosed on a cross-border payment, being a payment of a customer in a country other
+/// than the business country of the payment
+#[derive(Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize, Clone, ToSchema)]
+pub struct CrossBorderFeeDetails {
+    /// The country of the customer, being the country of the billing address of the payment
+    #[schema(value_type = CountryAlpha2, example = "DE")]
+    pub customer_country: api_enums::CountryAlpha2,
+
+    /// The business country of the payment
+    #[schema(value_type = CountryAlpha2, example = "US")]
+    pub business_country: api_enums::CountryAlpha2,
+
+    /// The international transaction fee the issuer of the card of the customer is estimated to
+    /// charge on top of the amount of the payment, in the lowest denomination of the currency
+    #[schema(example = 196)]
+    pub estimated_international_fee: Option<i64>,
+
+    /// The rate of the estimated international transaction fee, in basis points
+    #[schema(example = 300)]
+    pub estimated_international_fee_rate: Option<u16>,
+
+    /// The cross-border surcharge of the merchant, included in the amount of the payment
+    #[schema(example = 130)]
+    pub surcharge_amount: Option<i64>,
+
+    /// A description of the surcharge
+    #[schema(max_length = 255, example = "Cross-border surcharge")]
+    pub surcharge_description: Option<String>,
+}
+
+/// Level 2 and level 3 data of a card payment. Level 2 data covers the tax and the reference of
+/// the customer, level 3 data the line items of the order
+#[derive(Debug, Default, Eq, PartialEq, serde::Deserialize, serde::Serialize, Clone, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct L2L3Data {
+    /// The reference of the customer for the order, such as the purchase order number
+    #[schema(max_length = 25, example = "PO-2023-0042")]
+    pub customer_reference: Option<String>,
+
+    /// The tax amount of the order, in the lowest denomination of the currency of the payment
+    #[schema(minimum = 0, example = 650)]
+    pub tax_amount: Option<i64>,
+
+    /// The shipping amount of the order, in the lowest denomination of the currency of the payment
+    #[schema(minimum = 0, example = 1000)]
+    pub shipping_amount: Option<i64>,
+
+    /// The line items of the order
+    pub line_items: Option<Vec<L3LineItem>>,
+}
+
+/// A line item of the level 3 data of a card payment
+#[derive(Debug, Default, Eq, PartialEq, serde::Deserialize, serde::Serialize, Clone, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct L3LineItem {
+    /// The code of the product assigned by the merchant, such as the SKU
+    #[schema(max_length = 12, example = "SKU-1234")]
+    pub product_code: Option<String>,
+
+    /// The description of the product
+    #[schema(max_length = 255, example = "Office chair")]
+    pub description: String,
+
+    /// The quantity of the product
+    #[schema(minimum = 1, example = 2)]
+    pub quantity: u16,
+
+    /// The unit in which the quantity of the product is measured
+    #[schema(max_length = 12, example = "EA")]
+    pub unit_of_measure: Option<String>,
+
+    /// The price of a unit of the product, in the lowest denomination of the currency of the
+    /// payment
+    #[schema(minimum = 0, example = 5000)]
+    pub unit_amount: i64,
+
+    /// The tax amount of the line item, in the lowest denomination of the currency of the payment
+    #[schema(minimum = 0, example = 650)]
+    pub tax_amount: Option<i64>,
+
+    /// The discount on the line item, in the lowest denomination of the currency of the payment
+    #[schema(minimum = 0, example = 500)]
+    pub discount_amount: Option<i64>,
+
+    /// The commodity code of the product, as per the classification used by the card networks
+    #[schema(max_length = 12, example = "4111700")]
+    pub commodity_code: Option<String>,
+}
+
+impl L3LineItem {
+    /// The amount of the line item, after discount and excluding tax
+    pub fn get_total_amount(&self) -> i64 {
+        self.unit_amount
+            .saturating_mul(i64::from(self.quantity))
+            .saturating_sub(self.discount_amount.unwrap_or_default())
+    }
+}
+
 #[derive(Debug, Default, Eq, PartialEq, serde::Deserialize, serde::Serialize, Clone, ToSchema)]
 pub struct OrderDetails {
     /// Name of the product that is being purchased
@@ -2183,6 +2878,22 @@ pub struct PaymentsSessionRequest {
     /// Merchant connector details used to make payments.
     #[schema(value_type = Option<MerchantConnectorDetailsWrap>)]
     pub merchant_connector_details: Option<admin::MerchantConnectorDetailsWrap>,
+    /// The connectors from which session tokens are to be fetched. Used to fetch the session tokens of the connectors listed in `deferred_connectors` of a previous session response
+    #[schema(value_type = Option<Vec<Connector>>, example = json!(["stripe"]))]
+    pub connectors: Option<Vec<api_enums::Connector>>,
+}
+
+#[derive(Debug, serde::Deserialize, serde::Serialize, Clone, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct PaymentsSessionRefreshRequest {
+    /// This is a token which expires after 15 minutes, used from the client to authenticate and create sessions from the SDK
+    pub client_secret: String,
+    /// The wallets whose session tokens are to be refreshed. The session tokens of Apple Pay, Google Pay and PayPal are refreshed if not provided
+    #[schema(value_type = Option<Vec<PaymentMethodType>>, example = json!(["apple_pay"]))]
+    pub wallets: Option<Vec<api_enums::PaymentMethodType>>,
+    /// The connectors whose session tokens are to be refreshed. If not provided, the session tokens of the same connectors as when the session tokens were created are refreshed
+    #[schema(value_type = Option<Vec<Connector>>, example = json!(["stripe"]))]
+    pub connectors: Option<Vec<api_enums::Connector>>,
 }
 
 #[derive(Debug, Clone, Eq, PartialEq, serde::Serialize, serde::Deserialize, ToSchema)]
@@ -2321,6 +3032,23 @@ pub struct SessionTokenInfo {
     pub display_name: String,
     pub initiative: String,
     pub initiative_context: String,
+    /// The payment processing certificate of the merchant. When given, the Apple Pay payment
+    /// tokens are decrypted by the router and the decrypted card details are sent to the
+    /// connector, instead of the connector decrypting the payment tokens
+    pub payment_processing_details: Option<ApplePayPaymentProcessingDetails>,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, ToSchema)]
+pub struct ApplePayPaymentProcessingDetails {
+    /// The base64 encoded payment processing certificate (in PEM format) issued by Apple for the
+    /// merchant identifier
+    #[schema(value_type = String)]
+    pub payment_processing_certificate: Secret<String>,
+    /// The base64 encoded private key (in PEM format) of the payment processing certificate,
+    /// required when the certificate is first configured. The key is stored encrypted and never
+    /// returned, and the key configured earlier is retained when it is omitted on update
+    #[schema(value_type = Option<String>)]
+    pub payment_processing_certificate_key: Option<Secret<String>>,
 }
 
 #[derive(Debug, Clone, Eq, PartialEq, serde::Serialize, ToSchema)]
@@ -2522,6 +3250,14 @@ pub struct PaymentsSessionResponse {
     pub client_secret: Secret<String, pii::ClientSecret>,
     /// The list of session token object
     pub session_token: Vec<SessionToken>,
+    /// The list of connectors which did not respond within the time budget, and hence have no session token
+    pub timed_out_connectors: Vec<String>,
+    /// The list of eligible connectors whose session tokens were not fetched, either because the business uses the `sequential` session token strategy or because other connectors were requested. Their session tokens can be fetched by passing them in `connectors`
+    pub deferred_connectors: Vec<String>,
+    /// The time after which the session tokens are to be refreshed, using the session tokens refresh API
+    #[schema(example = "2022-09-10T10:11:12Z")]
+    #[serde(with = "common_utils::custom_serde::iso8601::option")]
+    pub expires_at: Option<PrimitiveDateTime>,
 }
 
 #[derive(Default, Debug, serde::Deserialize, serde::Serialize, Clone, ToSchema)]
@@ -2530,10 +3266,20 @@ pub struct PaymentRetrieveBody {
     pub merchant_id: Option<String>,
     /// Decider to enable or disable the connector call for retrieve request
     pub force_sync: Option<bool>,
+    /// Syncs with the connector even if the payment was synced within the sync cache window
+    pub bypass_sync_cache: Option<bool>,
     /// This is a token which expires after 15 minutes, used from the client to authenticate and create sessions from the SDK
     pub client_secret: Option<String>,
     /// If enabled provides list of attempts linked to payment intent
     pub expand_attempts: Option<bool>,
+    /// If enabled provides list of captures linked to the active attempt of the payment intent
+    pub expand_captures: Option<bool>,
+    /// Comma separated list of the related resources to embed in the response, out of `attempts`, `captures`, `refunds`, `disputes` and `customer`. When provided, refunds and disputes are embedded only if listed
+    #[schema(example = "attempts,refunds,customer")]
+    pub expand: Option<String>,
+    /// Holds the request until the payment reaches a terminal status (`succeeded`, `failed` or `cancelled`) or the given number of seconds elapses, at most 60 seconds
+    #[schema(example = "30s")]
+    pub wait: Option<String>,
 }
 
 #[derive(Default, Debug, serde::Deserialize, serde::Serialize, Clone, ToSchema)]
@@ -2758,3 +3504,128 @@ mod tests {
         )
     }
 }
+
+/// Limits on the number and the amount of the payments confirmed within a time window, which
+/// decline the payments exceeding them before they are authorized
+#[derive(Clone, Debug, Default, serde::Deserialize, serde::Serialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct PaymentVelocityLimits {
+    /// The velocity limits of the merchant, all of which are enforced on every payment
+    pub limits: Vec<PaymentVelocityLimit>,
+}
+
+/// A limit on the payments confirmed within a time window by the same customer, with the same
+/// card or from the same IP address
+#[derive(Clone, Debug, serde::Deserialize, serde::Serialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct PaymentVelocityLimit {
+    /// Name of the limit, unique among the limits of the merchant
+    #[schema(example = "card_daily_count")]
+    pub name: String,
+
+    /// Whose payments are counted against the limit
+    pub scope: VelocityLimitScope,
+
+    /// The sliding time window over which the payments are counted
+    pub window: VelocityLimitWindow,
+
+    /// The maximum number of payments within the window
+    #[schema(example = 5)]
+    pub max_count: Option<u32>,
+
+    /// The maximum total amount of the payments within the window, in the lowest denomination of
+    /// the currency. Only payments in the currency of the limit are counted against it.
+    #[schema(example = 100000)]
+    pub max_amount: Option<i64>,
+
+    /// The currency of the maximum amount, required along with it
+    #[schema(value_type = Option<Currency>, example = "USD")]
+    pub currency: Option<api_enums::Currency>,
+}
+
+/// Whose payments are counted against a velocity limit
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Eq,
+    PartialEq,
+    serde::Deserialize,
+    serde::Serialize,
+    ToSchema,
+    strum::Display,
+)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum VelocityLimitScope {
+    /// The payments of the same customer
+    Customer,
+    /// The payments made with the same card
+    Card,
+    /// The payments made from the same IP address
+    IpAddress,
+}
+
+/// The sliding time window of a velocity limit
+#[derive(Clone, Copy, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum VelocityLimitWindow {
+    /// The last hour
+    Hour,
+    /// The last 24 hours
+    Day,
+}
+
+impl VelocityLimitWindow {
+    /// The length of the window, in seconds
+    pub fn as_secs(self) -> i64 {
+        match self {
+            Self::Hour => 60 * 60,
+            Self::Day => 24 * 60 * 60,
+        }
+    }
+}
+
+/// The cross-border fees disclosed on the payments of customers in a country other than the
+/// business country of the payment
+#[derive(Clone, Debug, Default, serde::Deserialize, serde::Serialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct CrossBorderFeeConfig {
+    /// The rate at which card issuers are estimated to charge international transaction fees, in
+    /// basis points. No international transaction fee is disclosed if not provided
+    #[schema(maximum = 10000, example = 300)]
+    pub estimated_international_fee_rate: Option<u16>,
+
+    /// The surcharges of the merchant on cross-border payments. A payment is charged the first
+    /// surcharge applicable to it, so surcharges specific to countries are to be listed before
+    /// general surcharges
+    #[serde(default)]
+    pub surcharges: Vec<CrossBorderSurcharge>,
+}
+
+/// A surcharge of the merchant on the cross-border payments it applies to, included in the amount
+/// of the payment
+#[derive(Clone, Debug, serde::Deserialize, serde::Serialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct CrossBorderSurcharge {
+    /// The currency of the payments the surcharge applies to, in which the fixed amount is charged
+    #[schema(value_type = Currency, example = "USD")]
+    pub currency: api_enums::Currency,
+
+    /// The countries of the customers the surcharge applies to. Applies to the customers from all
+    /// countries if not provided
+    #[schema(value_type = Option<Vec<CountryAlpha2>>, example = json!(["DE"]))]
+    pub countries: Option<Vec<api_enums::CountryAlpha2>>,
+
+    /// The surcharge as a share of the amount of the payment, in basis points
+    #[schema(maximum = 10000, example = 150)]
+    pub basis_points: u16,
+
+    /// The surcharge per payment, in the lowest denomination of the currency
+    #[schema(example = 30)]
+    pub fixed_amount: i64,
+
+    /// A description of the surcharge, disclosed along with the surcharge in the payment response
+    #[schema(max_length = 255, example = "Cross-border surcharge")]
+    pub description: Option<String>,
+}
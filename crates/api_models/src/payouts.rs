@@ -392,6 +392,17 @@ pub struct PayoutCreateResponse {
     /// If there was an error while calling the connectors the code is received here
     #[schema(value_type = String, example = "E0001")]
     pub error_code: Option<String>,
+
+    /// The fee charged by the connector for the payout, as reported by the connector. The fee is
+    /// in the lowest denomination of the source currency of the payout
+    #[schema(example = 150)]
+    pub connector_fee: Option<i64>,
+
+    /// The margin applied by the connector over the exchange rate when converting the payout
+    /// from its source currency, as reported by the connector. The margin is in the lowest
+    /// denomination of the source currency of the payout
+    #[schema(example = 45)]
+    pub fx_margin: Option<i64>,
 }
 
 #[cfg(feature = "payouts")]
@@ -432,3 +443,75 @@ pub struct PayoutActionRequest {
     )]
     pub payout_id: String,
 }
+
+#[cfg(feature = "payouts")]
+#[derive(Debug, Deserialize, Serialize, Clone, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct PayoutBatchCreateRequest {
+    /// The payouts to be created, at most 100. The payouts are validated before any of them is
+    /// created, and the batch is rejected if any of them is invalid
+    #[schema(min_items = 1, max_items = 100)]
+    pub payouts: Vec<PayoutCreateRequest>,
+
+    /// A unique key identifying the batch. Retrying the creation of a batch with the same
+    /// idempotency key returns the batch created first, without creating its payouts again.
+    #[schema(max_length = 128, example = "payroll_2023_09")]
+    pub idempotency_key: Option<String>,
+}
+
+#[cfg(feature = "payouts")]
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct PayoutBatchResponse {
+    /// The identifier for the batch of payouts
+    #[schema(max_length = 64, example = "pobatch_4hDjyLsm6Y7BIMC9zPYB")]
+    pub batch_id: String,
+
+    /// The identifier for the merchant account
+    #[schema(max_length = 255, example = "merchant_1668273825")]
+    pub merchant_id: String,
+
+    /// The number of payouts in the batch
+    #[schema(example = 2)]
+    pub total_count: usize,
+
+    /// The number of payouts of the batch in each status
+    pub status_counts: Vec<PayoutBatchStatusCount>,
+
+    /// The payouts of the batch, in the order in which they were requested
+    pub payouts: Vec<PayoutBatchItem>,
+
+    /// The time at which the batch was created
+    #[schema(value_type = PrimitiveDateTime, example = "2022-09-10T10:11:12Z")]
+    #[serde(with = "common_utils::custom_serde::iso8601")]
+    pub created: time::PrimitiveDateTime,
+}
+
+#[cfg(feature = "payouts")]
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct PayoutBatchStatusCount {
+    #[schema(value_type = PayoutStatus, example = "success")]
+    pub status: api_enums::PayoutStatus,
+
+    #[schema(example = 1)]
+    pub count: usize,
+}
+
+#[cfg(feature = "payouts")]
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct PayoutBatchItem {
+    /// The identifier for the payout
+    #[schema(example = "payout_mbabizu24mvu3mela5njyhpit4")]
+    pub payout_id: String,
+
+    /// The status of the payout, absent if the payout could not be created
+    #[schema(value_type = Option<PayoutStatus>, example = "success")]
+    pub status: Option<api_enums::PayoutStatus>,
+
+    /// The error code of the payout, if it failed or could not be created
+    #[schema(example = "E0001")]
+    pub error_code: Option<String>,
+
+    /// The error message of the payout, if it failed or could not be created
+    #[schema(example = "Failed while verifying the card")]
+    pub error_message: Option<String>,
+}
@@ -3,6 +3,8 @@ use serde::{Deserialize, Serialize};
 use time::PrimitiveDateTime;
 use utoipa::ToSchema;
 
+#[cfg(feature = "payouts")]
+use crate::payouts;
 use crate::{admin, enums};
 
 #[derive(Default, Debug, ToSchema, Clone, Deserialize)]
@@ -47,11 +49,39 @@ pub struct RefundRequest {
     /// Merchant connector details used to make payments.
     #[schema(value_type = Option<MerchantConnectorDetailsWrap>)]
     pub merchant_connector_details: Option<admin::MerchantConnectorDetailsWrap>,
+
+    /// A reference for the refund, unique across the refunds of the merchant. Retrying a refund request with the same reference returns the refund created by the first request instead of creating another refund
+    #[schema(max_length = 64, example = "order_1042_refund_1")]
+    pub merchant_refund_reference: Option<String>,
+
+    /// The time at which the refund is to be sent to the connector, for example to avoid a maintenance window of the connector. Until then, the refund can be cancelled. If not provided, the refund is processed according to its refund type
+    #[schema(example = "2023-09-01T02:00:00Z")]
+    #[serde(default, with = "common_utils::custom_serde::iso8601::option")]
+    pub schedule_at: Option<PrimitiveDateTime>,
+
+    /// The destination to which the refund is to be paid out, when the amount cannot be credited back to the original payment method (for example, an expired card or a closed account)
+    #[cfg(feature = "payouts")]
+    #[schema(value_type = Option<RefundAlternateDestination>)]
+    pub alternate_destination: Option<RefundAlternateDestination>,
+}
+
+#[cfg(feature = "payouts")]
+#[derive(Debug, ToSchema, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RefundAlternateDestination {
+    /// The bank account to which the refund amount is to be transferred
+    #[schema(value_type = Bank)]
+    pub bank: payouts::Bank,
+
+    /// The billing address of the account holder. If not provided, the billing address of the payment is used
+    #[schema(value_type = Option<Address>)]
+    pub billing: Option<crate::payments::Address>,
 }
 
 #[derive(Default, Debug, Clone, Deserialize)]
 pub struct RefundsRetrieveBody {
     pub force_sync: Option<bool>,
+    pub bypass_sync_cache: Option<bool>,
 }
 
 #[derive(Default, Debug, ToSchema, Clone, Deserialize)]
@@ -68,6 +98,10 @@ pub struct RefundsRetrieveRequest {
     /// (defaults to false)
     pub force_sync: Option<bool>,
 
+    /// Syncs with the connector even if the refund was synced within the sync cache window
+    /// (defaults to false)
+    pub bypass_sync_cache: Option<bool>,
+
     /// Merchant connector details used to make payments.
     pub merchant_connector_details: Option<admin::MerchantConnectorDetailsWrap>,
 }
@@ -84,6 +118,29 @@ pub struct RefundUpdateRequest {
     pub metadata: Option<pii::SecretSerdeValue>,
 }
 
+/// The final status an administrator assigns to a refund stuck pending or in manual review
+#[derive(Debug, Clone, Copy, ToSchema, Deserialize, Serialize, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum RefundResolution {
+    Succeeded,
+    Failed,
+}
+
+#[derive(Debug, ToSchema, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RefundResolveRequest {
+    /// The final status of the refund, as confirmed with the connector
+    #[schema(example = "succeeded")]
+    pub resolution: RefundResolution,
+
+    /// The reason for resolving the refund manually, recorded in the audit trail
+    #[schema(
+        max_length = 255,
+        example = "Confirmed as settled on the connector dashboard"
+    )]
+    pub reason: Option<String>,
+}
+
 #[derive(
     Default, Debug, Clone, Copy, ToSchema, Deserialize, Serialize, Eq, PartialEq, strum::Display,
 )]
@@ -124,6 +181,20 @@ pub struct RefundResponse {
     /// The connector used for the refund and the corresponding payment
     #[schema(example = "stripe")]
     pub connector: String,
+    /// The identifier of the payout created when the refund is made to an alternate destination
+    pub payout_id: Option<String>,
+    /// The reference for the refund provided by the merchant
+    #[schema(example = "order_1042_refund_1")]
+    pub merchant_refund_reference: Option<String>,
+    /// The part of the refund amount which refunds the convenience fee of the payment, in proportion to the amount refunded
+    #[schema(example = 45)]
+    pub convenience_fee_amount: Option<i64>,
+    /// The time at which the refund is scheduled to be sent to the connector
+    #[serde(with = "common_utils::custom_serde::iso8601::option")]
+    pub scheduled_at: Option<PrimitiveDateTime>,
+    /// The amount reported as refunded by the connector, if the connector reports it. The refund is flagged with the `amount_mismatch` status when it differs from the amount requested
+    #[schema(example = 6540)]
+    pub settled_amount: Option<i64>,
 }
 
 #[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize, ToSchema)]
@@ -198,6 +269,10 @@ pub enum RefundStatus {
     #[default]
     Pending,
     Review,
+    Cancelled,
+    AmountMismatch,
+    PendingApproval,
+    Rejected,
 }
 
 impl From<enums::RefundStatus> for RefundStatus {
@@ -207,6 +282,10 @@ impl From<enums::RefundStatus> for RefundStatus {
             enums::RefundStatus::ManualReview => Self::Review,
             enums::RefundStatus::Pending => Self::Pending,
             enums::RefundStatus::Success => Self::Succeeded,
+            enums::RefundStatus::Cancelled => Self::Cancelled,
+            enums::RefundStatus::AmountMismatch => Self::AmountMismatch,
+            enums::RefundStatus::PendingApproval => Self::PendingApproval,
+            enums::RefundStatus::Rejected => Self::Rejected,
         }
     }
 }
@@ -0,0 +1,226 @@
+use common_utils::pii;
+use serde::{Deserialize, Serialize};
+use time::PrimitiveDateTime;
+use utoipa::ToSchema;
+
+use crate::enums;
+
+/// The formats in which a report can be generated.
+#[derive(
+    Clone, Copy, Debug, Default, Eq, PartialEq, Serialize, Deserialize, strum::Display, ToSchema,
+)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum ReportFormat {
+    #[default]
+    Csv,
+    Json,
+}
+
+/// The template used to generate a report, which controls the format of the report and the
+/// columns included in it.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ReportTemplate {
+    /// The format in which the report is generated
+    #[serde(default)]
+    pub format: ReportFormat,
+
+    /// The columns included in the report, in order. If not passed, all the columns available for
+    /// the report type are included.
+    /// For `payments_summary` reports, the available columns are `currency`, `connector`,
+    /// `channel` (`online` or `in_store`), `status`, `payment_count`, `total_amount` and
+    /// `captured_amount`.
+    /// For `disputes` reports, the available columns are `dispute_id`, `payment_id`,
+    /// `connector`, `dispute_stage`, `dispute_status`, `amount`, `currency`, `reason` and
+    /// `created_at`.
+    #[schema(example = json!(["currency", "status", "payment_count", "total_amount"]))]
+    pub columns: Option<Vec<String>>,
+}
+
+/// The filters applied to the records included in a report.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ReportFilters {
+    /// Only include records in these currencies
+    #[schema(value_type = Option<Vec<Currency>>, example = json!(["USD", "EUR"]))]
+    pub currency: Option<Vec<enums::Currency>>,
+
+    /// Only include records processed through these connectors
+    #[schema(example = json!(["stripe", "adyen"]))]
+    pub connector: Option<Vec<String>>,
+}
+
+/// The request body for scheduling a recurring report.
+#[derive(Debug, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ReportScheduleCreateRequest {
+    /// The type of the report
+    #[schema(value_type = ReportType, example = "payments_summary")]
+    pub report_type: enums::ReportType,
+
+    /// How often the report is generated. Each report covers the day or week before it is
+    /// generated.
+    #[schema(value_type = ReportFrequency, example = "daily")]
+    pub frequency: enums::ReportFrequency,
+
+    /// The template used to generate the report
+    pub template: Option<ReportTemplate>,
+
+    /// The filters applied to the records included in the report
+    pub filters: Option<ReportFilters>,
+
+    /// How the merchant is notified once the report has been generated
+    #[schema(value_type = ReportDeliveryMethod, example = "webhook")]
+    pub delivery_method: enums::ReportDeliveryMethod,
+
+    /// The email address to which the report is sent. Required when `delivery_method` is
+    /// `email`.
+    #[schema(value_type = Option<String>, example = "finance@example.com")]
+    pub recipient_email: Option<pii::Email>,
+}
+
+/// The request body for updating a report schedule.
+#[derive(Debug, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ReportScheduleUpdateRequest {
+    /// The template used to generate the report
+    pub template: Option<ReportTemplate>,
+
+    /// The filters applied to the records included in the report
+    pub filters: Option<ReportFilters>,
+
+    /// How the merchant is notified once the report has been generated
+    #[schema(value_type = Option<ReportDeliveryMethod>, example = "email")]
+    pub delivery_method: Option<enums::ReportDeliveryMethod>,
+
+    /// The email address to which the report is sent
+    #[schema(value_type = Option<String>, example = "finance@example.com")]
+    pub recipient_email: Option<pii::Email>,
+
+    /// Pass `false` to pause the generation of the report, and `true` to resume it
+    #[schema(example = false)]
+    pub is_active: Option<bool>,
+}
+
+/// The response body for a report schedule.
+#[derive(Clone, Debug, Serialize, ToSchema)]
+pub struct ReportScheduleResponse {
+    /// The identifier for the report schedule
+    #[schema(max_length = 64, example = "rps_4hDjyLsm6Y7BIMC9zPYB")]
+    pub report_schedule_id: String,
+
+    /// The identifier for the Merchant Account
+    #[schema(max_length = 64, example = "y3oqhf46pyzuxjbcn2giaqnb44")]
+    pub merchant_id: String,
+
+    /// The type of the report
+    #[schema(value_type = ReportType, example = "payments_summary")]
+    pub report_type: enums::ReportType,
+
+    /// How often the report is generated
+    #[schema(value_type = ReportFrequency, example = "daily")]
+    pub frequency: enums::ReportFrequency,
+
+    /// The template used to generate the report
+    pub template: ReportTemplate,
+
+    /// The filters applied to the records included in the report
+    pub filters: Option<ReportFilters>,
+
+    /// How the merchant is notified once the report has been generated
+    #[schema(value_type = ReportDeliveryMethod, example = "webhook")]
+    pub delivery_method: enums::ReportDeliveryMethod,
+
+    /// The email address to which the report is sent
+    #[schema(value_type = Option<String>, example = "finance@example.com")]
+    pub recipient_email: Option<pii::Email>,
+
+    /// Whether the report is being generated
+    #[schema(example = true)]
+    pub is_active: bool,
+
+    /// The time at which the report is next generated
+    #[schema(example = "2023-08-18T00:00:00Z")]
+    #[serde(with = "common_utils::custom_serde::iso8601")]
+    pub next_run_at: PrimitiveDateTime,
+
+    /// The time at which the report was last generated
+    #[schema(example = "2023-08-17T00:00:00Z")]
+    #[serde(with = "common_utils::custom_serde::iso8601::option")]
+    pub last_generated_at: Option<PrimitiveDateTime>,
+
+    /// The identifier for the file containing the last generated report
+    #[schema(example = "file_sTnVrDFGmLHLwH4wZ8ad")]
+    pub last_file_id: Option<String>,
+
+    /// The time at which the report schedule was created
+    #[schema(example = "2022-09-10T10:11:12Z")]
+    #[serde(with = "common_utils::custom_serde::iso8601")]
+    pub created: PrimitiveDateTime,
+}
+
+/// The constraints that are applicable when listing the report schedules of a merchant.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ReportScheduleListConstraints {
+    /// The maximum number of report schedules to include in the response.
+    pub limit: Option<i64>,
+
+    /// The number of report schedules to skip when retrieving the list of report schedules.
+    pub skip: Option<i64>,
+}
+
+/// The response body for deleting a report schedule.
+#[derive(Clone, Debug, Serialize, ToSchema)]
+pub struct ReportScheduleDeleteResponse {
+    /// The identifier for the report schedule
+    #[schema(max_length = 64, example = "rps_4hDjyLsm6Y7BIMC9zPYB")]
+    pub report_schedule_id: String,
+
+    /// Whether the report schedule was deleted
+    #[schema(example = true)]
+    pub deleted: bool,
+}
+
+/// The details of a generated report, sent to the merchant in the `report_generated` webhook.
+#[derive(Clone, Debug, Serialize, ToSchema)]
+pub struct GeneratedReportResponse {
+    /// The identifier for the report schedule which generated the report
+    #[schema(max_length = 64, example = "rps_4hDjyLsm6Y7BIMC9zPYB")]
+    pub report_schedule_id: String,
+
+    /// The type of the report
+    #[schema(value_type = ReportType, example = "payments_summary")]
+    pub report_type: enums::ReportType,
+
+    /// The format in which the report was generated
+    pub format: ReportFormat,
+
+    /// The identifier for the file containing the report
+    #[schema(example = "file_sTnVrDFGmLHLwH4wZ8ad")]
+    pub file_id: String,
+
+    /// The link from which the report can be downloaded using the API key of the merchant
+    #[schema(example = "https://sandbox.hyperswitch.io/files/file_sTnVrDFGmLHLwH4wZ8ad")]
+    pub file_url: String,
+
+    /// The start of the period covered by the report
+    #[schema(example = "2023-08-16T00:00:00Z")]
+    #[serde(with = "common_utils::custom_serde::iso8601")]
+    pub period_start: PrimitiveDateTime,
+
+    /// The end of the period covered by the report
+    #[schema(example = "2023-08-17T00:00:00Z")]
+    #[serde(with = "common_utils::custom_serde::iso8601")]
+    pub period_end: PrimitiveDateTime,
+
+    /// The number of rows in the report
+    #[schema(example = 12)]
+    pub row_count: usize,
+
+    /// Whether the period has more records than can be included in a report, in which case only
+    /// the first records of the period are included
+    #[schema(example = false)]
+    pub is_truncated: bool,
+}
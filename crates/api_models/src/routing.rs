@@ -0,0 +1,114 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::enums as api_enums;
+
+/// The fees charged by the connectors of the merchant, by which cost based routing picks the
+/// cheapest connector for a payment.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ConnectorFeeSchedules {
+    /// The fee schedules of the connectors
+    pub fee_schedules: Vec<ConnectorFeeSchedule>,
+}
+
+/// The fees charged by a connector.
+#[derive(Clone, Debug, Deserialize, Serialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ConnectorFeeSchedule {
+    /// The connector charging the fees
+    #[schema(value_type = Connector, example = "stripe")]
+    pub connector: api_enums::RoutableConnectors,
+
+    /// The fees charged by the connector. A payment is charged the first fee applicable to it, so
+    /// fees specific to card networks or countries are to be listed before general fees
+    pub fees: Vec<ConnectorFee>,
+}
+
+/// A fee charged by a connector for the payments it applies to.
+#[derive(Clone, Debug, Deserialize, Serialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ConnectorFee {
+    /// The currency of the payments the fee applies to, in which the fixed amount is charged
+    #[schema(value_type = Currency, example = "USD")]
+    pub currency: api_enums::Currency,
+
+    /// The card networks of the payments the fee applies to. Applies to the payments of all card
+    /// networks if not provided
+    #[schema(value_type = Option<Vec<CardNetwork>>, example = json!(["Visa"]))]
+    pub card_networks: Option<Vec<api_enums::CardNetwork>>,
+
+    /// The countries of the payments the fee applies to, being the country of the billing address
+    /// of the payment, or else its business country. Applies to the payments from all countries if
+    /// not provided
+    #[schema(value_type = Option<Vec<CountryAlpha2>>, example = json!(["US"]))]
+    pub countries: Option<Vec<api_enums::CountryAlpha2>>,
+
+    /// The fee charged as a share of the amount of the payment, in basis points (hundredths of a
+    /// percent)
+    #[schema(maximum = 10000, example = 290)]
+    pub basis_points: u32,
+
+    /// The fee charged per payment, in the lowest denomination of the currency
+    #[schema(example = 30)]
+    pub fixed_amount: i64,
+}
+
+/// The details of a payment whose routing is previewed.
+#[derive(Clone, Debug, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct RoutingSimulationRequest {
+    /// The amount of the payment, in the lowest denomination of the currency
+    #[schema(example = 6540)]
+    pub amount: i64,
+
+    /// The currency of the payment
+    #[schema(value_type = Currency, example = "USD")]
+    pub currency: api_enums::Currency,
+
+    /// The payment method type of the payment
+    #[schema(value_type = Option<PaymentMethodType>, example = "credit")]
+    pub payment_method_type: Option<api_enums::PaymentMethodType>,
+
+    /// The network of the card the payment is made with
+    #[schema(value_type = Option<CardNetwork>, example = "Visa")]
+    pub card_network: Option<api_enums::CardNetwork>,
+
+    /// The country of the billing address of the payment
+    #[schema(value_type = Option<CountryAlpha2>, example = "US")]
+    pub country: Option<api_enums::CountryAlpha2>,
+
+    /// The customer of the payment, by which the payment is assigned a connector of a volume
+    /// split. A connector is assigned at random if not provided
+    #[schema(max_length = 64, example = "cus_y3oqhf46pyzuxjbcn2giaqnb44")]
+    pub customer_id: Option<String>,
+}
+
+/// The routing decision for a payment.
+#[derive(Clone, Debug, Serialize, ToSchema)]
+pub struct RoutingSimulationResponse {
+    /// The connector to which the payment would be routed
+    #[schema(value_type = Option<Connector>, example = "stripe")]
+    pub connector: Option<api_enums::RoutableConnectors>,
+
+    /// The expected costs of the payment with the connectors of cost based routing, cheapest
+    /// first. Empty for the other routing algorithms
+    pub connector_costs: Vec<ConnectorCost>,
+}
+
+/// The expected cost of a payment with a connector.
+#[derive(Clone, Debug, Serialize, ToSchema)]
+pub struct ConnectorCost {
+    /// The connector
+    #[schema(value_type = Connector, example = "stripe")]
+    pub connector: api_enums::RoutableConnectors,
+
+    /// The fee the connector is expected to charge for the payment, in the lowest denomination of
+    /// the currency. Not provided if the fee schedule of the connector has no fee applicable to
+    /// the payment
+    #[schema(example = 220)]
+    pub expected_cost: Option<i64>,
+
+    /// Whether the connector accepts the payment, considering the amount limits of the connector
+    pub eligible: bool,
+}
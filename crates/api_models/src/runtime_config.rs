@@ -0,0 +1,13 @@
+use serde::Serialize;
+use time::PrimitiveDateTime;
+
+/// The outcome of reloading the settings which can be changed without restarting the application.
+#[derive(Clone, Debug, Serialize)]
+pub struct RuntimeConfigReloadResponse {
+    /// The sections of the configuration whose values changed, empty if none did
+    pub changed_sections: Vec<String>,
+
+    /// The time at which the settings were reloaded
+    #[serde(with = "common_utils::custom_serde::iso8601")]
+    pub reloaded_at: PrimitiveDateTime,
+}
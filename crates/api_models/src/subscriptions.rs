@@ -0,0 +1,190 @@
+use serde::{Deserialize, Serialize};
+use time::PrimitiveDateTime;
+use utoipa::ToSchema;
+
+use crate::enums;
+
+/// The request body for creating a subscription, which bills a customer a fixed amount on every
+/// billing cycle using a mandate of the customer.
+#[derive(Debug, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct SubscriptionCreateRequest {
+    /// The identifier for the customer who is billed
+    #[schema(max_length = 64, example = "cus_y3oqhf46pyzuxjbcn2giaqnb44")]
+    pub customer_id: String,
+
+    /// The identifier for the multi use mandate of the customer against which the payments are
+    /// created
+    #[schema(max_length = 64, example = "man_XzgYtNjMsGuwUX9e3XUn")]
+    pub mandate_id: String,
+
+    /// The amount billed on every billing cycle, in the lowest denomination of the currency
+    #[schema(example = 1999)]
+    pub amount: i64,
+
+    /// The currency of the amount billed
+    #[schema(value_type = Currency, example = "USD")]
+    pub currency: enums::Currency,
+
+    /// The unit of the interval between two billing cycles
+    #[schema(value_type = BillingInterval, example = "month")]
+    pub billing_interval: enums::BillingInterval,
+
+    /// The number of units of `billing_interval` between two billing cycles. Defaults to 1.
+    #[schema(minimum = 1, maximum = 365, example = 1)]
+    pub billing_interval_count: Option<i32>,
+
+    /// The description of the subscription, used as the description of its payments
+    #[schema(max_length = 255, example = "Premium plan")]
+    pub description: Option<String>,
+
+    /// The time of the first billing cycle, which the following billing cycles are anchored to.
+    /// Defaults to the current time.
+    #[schema(example = "2023-10-01T00:00:00Z")]
+    #[serde(default, with = "common_utils::custom_serde::iso8601::option")]
+    pub start_at: Option<PrimitiveDateTime>,
+}
+
+/// The request body for updating a subscription. The changes apply from the next billing cycle.
+#[derive(Debug, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct SubscriptionUpdateRequest {
+    /// The amount billed on every billing cycle, in the lowest denomination of the currency
+    #[schema(example = 2999)]
+    pub amount: Option<i64>,
+
+    /// The identifier for the multi use mandate of the customer against which the payments are
+    /// created
+    #[schema(max_length = 64, example = "man_XzgYtNjMsGuwUX9e3XUn")]
+    pub mandate_id: Option<String>,
+
+    /// The description of the subscription, used as the description of its payments
+    #[schema(max_length = 255, example = "Premium plan")]
+    pub description: Option<String>,
+}
+
+/// The response body for a subscription.
+#[derive(Clone, Debug, Serialize, ToSchema)]
+pub struct SubscriptionResponse {
+    /// The identifier for the subscription
+    #[schema(max_length = 64, example = "sub_4hDjyLsm6Y7BIMC9zPYB")]
+    pub subscription_id: String,
+
+    /// The identifier for the Merchant Account
+    #[schema(max_length = 64, example = "y3oqhf46pyzuxjbcn2giaqnb44")]
+    pub merchant_id: String,
+
+    /// The identifier for the customer who is billed
+    #[schema(max_length = 64, example = "cus_y3oqhf46pyzuxjbcn2giaqnb44")]
+    pub customer_id: String,
+
+    /// The identifier for the mandate against which the payments are created
+    #[schema(max_length = 64, example = "man_XzgYtNjMsGuwUX9e3XUn")]
+    pub mandate_id: String,
+
+    /// The status of the subscription
+    #[schema(value_type = SubscriptionStatus, example = "active")]
+    pub status: enums::SubscriptionStatus,
+
+    /// The amount billed on every billing cycle
+    #[schema(example = 1999)]
+    pub amount: i64,
+
+    /// The currency of the amount billed
+    #[schema(value_type = Currency, example = "USD")]
+    pub currency: enums::Currency,
+
+    /// The unit of the interval between two billing cycles
+    #[schema(value_type = BillingInterval, example = "month")]
+    pub billing_interval: enums::BillingInterval,
+
+    /// The number of units of `billing_interval` between two billing cycles
+    #[schema(example = 1)]
+    pub billing_interval_count: i32,
+
+    /// The description of the subscription
+    #[schema(example = "Premium plan")]
+    pub description: Option<String>,
+
+    /// The number of billing cycles since the start of the subscription, excluding the billing
+    /// cycle currently being billed. Billing cycles skipped while the subscription was paused are
+    /// included.
+    #[schema(example = 3)]
+    pub billing_cycle: i32,
+
+    /// The time at which the customer is next billed. When the subscription is past due, this is
+    /// the time of the next retry.
+    #[schema(example = "2023-11-01T00:00:00Z")]
+    #[serde(with = "common_utils::custom_serde::iso8601")]
+    pub next_billing_at: PrimitiveDateTime,
+
+    /// The number of consecutive failed attempts to bill the current billing cycle
+    #[schema(example = 0)]
+    pub failed_attempt_count: i32,
+
+    /// The identifier for the last payment created for the subscription
+    #[schema(example = "pay_mbabizu24mvu3mela5njyhpit4")]
+    pub last_payment_id: Option<String>,
+
+    /// The time at which the subscription was cancelled
+    #[schema(example = "2023-12-01T00:00:00Z")]
+    #[serde(with = "common_utils::custom_serde::iso8601::option")]
+    pub cancelled_at: Option<PrimitiveDateTime>,
+
+    /// The time at which the subscription was created
+    #[schema(example = "2022-09-10T10:11:12Z")]
+    #[serde(with = "common_utils::custom_serde::iso8601")]
+    pub created: PrimitiveDateTime,
+}
+
+/// The constraints that are applicable when listing the subscriptions of a merchant.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SubscriptionListConstraints {
+    /// Only include the subscriptions of this customer.
+    pub customer_id: Option<String>,
+
+    /// The maximum number of subscriptions to include in the response.
+    pub limit: Option<i64>,
+
+    /// The number of subscriptions to skip when retrieving the list of subscriptions.
+    pub skip: Option<i64>,
+}
+
+/// The action taken on a subscription once all the retries of a billing cycle have failed.
+#[derive(
+    Clone, Copy, Debug, Default, Eq, PartialEq, Serialize, Deserialize, strum::Display, ToSchema,
+)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum SubscriptionDunningAction {
+    /// The subscription is cancelled
+    #[default]
+    Cancel,
+    /// The subscription is paused, and can be resumed by the merchant
+    Pause,
+}
+
+/// The configuration of the retries of failed subscription payments of a merchant.
+#[derive(Clone, Debug, Deserialize, Serialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct SubscriptionDunningConfig {
+    /// The delays in hours after a failed payment at which the payment of the billing cycle is
+    /// retried, each counted from the previous failed attempt. At most 10 retries can be
+    /// configured, each delayed by 1 to 720 hours.
+    #[schema(example = json!([24, 72, 120]))]
+    pub retry_intervals: Vec<u32>,
+
+    /// The action taken on the subscription once all the retries have failed
+    #[serde(default)]
+    pub final_action: SubscriptionDunningAction,
+}
+
+impl Default for SubscriptionDunningConfig {
+    fn default() -> Self {
+        Self {
+            retry_intervals: vec![24, 72, 120],
+            final_action: SubscriptionDunningAction::default(),
+        }
+    }
+}
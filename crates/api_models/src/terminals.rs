@@ -0,0 +1,137 @@
+use common_utils::pii;
+use serde::{Deserialize, Serialize};
+use time::PrimitiveDateTime;
+use utoipa::ToSchema;
+
+/// The request body for registering a card-present terminal.
+#[derive(Debug, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct TerminalCreateRequest {
+    /// The identifier of the merchant connector account which will process payments initiated on
+    /// this terminal.
+    #[schema(max_length = 128, example = "mca_5apGeP94tMts6rg3U3kR")]
+    pub merchant_connector_id: Option<String>,
+
+    /// The identifier assigned to the terminal by the processor.
+    #[schema(max_length = 255, example = "tmr_6A4f8GQyrNbXcNf")]
+    pub connector_terminal_id: Option<String>,
+
+    /// A name to help you identify the terminal.
+    #[schema(max_length = 255, example = "Front desk reader")]
+    pub label: Option<String>,
+
+    /// The physical location of the terminal.
+    #[schema(max_length = 255, example = "Store #42, Bengaluru")]
+    pub location: Option<String>,
+
+    /// The hardware serial number of the terminal.
+    #[schema(max_length = 255, example = "WSC513105011295")]
+    pub serial_number: Option<String>,
+
+    /// You can specify up to 50 keys, with key names up to 40 characters long and values up to
+    /// 500 characters long. Metadata is useful for storing additional, structured information on
+    /// an object.
+    #[schema(value_type = Option<Object>, example = r#"{ "city": "NY", "unit": "245" }"#)]
+    pub metadata: Option<pii::SecretSerdeValue>,
+}
+
+/// The request body for updating a registered terminal.
+#[derive(Debug, Default, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct TerminalUpdateRequest {
+    /// The identifier of the merchant connector account which will process payments initiated on
+    /// this terminal.
+    #[schema(max_length = 128, example = "mca_5apGeP94tMts6rg3U3kR")]
+    pub merchant_connector_id: Option<String>,
+
+    /// The identifier assigned to the terminal by the processor.
+    #[schema(max_length = 255, example = "tmr_6A4f8GQyrNbXcNf")]
+    pub connector_terminal_id: Option<String>,
+
+    /// A name to help you identify the terminal.
+    #[schema(max_length = 255, example = "Front desk reader")]
+    pub label: Option<String>,
+
+    /// The physical location of the terminal.
+    #[schema(max_length = 255, example = "Store #42, Bengaluru")]
+    pub location: Option<String>,
+
+    /// Whether the terminal can be used to accept card-present payments.
+    #[schema(example = true)]
+    pub is_active: Option<bool>,
+
+    /// You can specify up to 50 keys, with key names up to 40 characters long and values up to
+    /// 500 characters long. Metadata is useful for storing additional, structured information on
+    /// an object.
+    #[schema(value_type = Option<Object>, example = r#"{ "city": "NY", "unit": "245" }"#)]
+    pub metadata: Option<pii::SecretSerdeValue>,
+}
+
+/// The response body for a registered terminal.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct TerminalResponse {
+    /// The identifier for the terminal.
+    #[schema(max_length = 64, example = "term_4hDjyLsm6Y7BIMC9zPYB")]
+    pub terminal_id: String,
+
+    /// The identifier for the Merchant Account.
+    #[schema(max_length = 64, example = "y3oqhf46pyzuxjbcn2giaqnb44")]
+    pub merchant_id: String,
+
+    /// The identifier of the merchant connector account which processes payments initiated on
+    /// this terminal.
+    #[schema(max_length = 128, example = "mca_5apGeP94tMts6rg3U3kR")]
+    pub merchant_connector_id: Option<String>,
+
+    /// The identifier assigned to the terminal by the processor.
+    #[schema(max_length = 255, example = "tmr_6A4f8GQyrNbXcNf")]
+    pub connector_terminal_id: Option<String>,
+
+    /// A name to help you identify the terminal.
+    #[schema(max_length = 255, example = "Front desk reader")]
+    pub label: Option<String>,
+
+    /// The physical location of the terminal.
+    #[schema(max_length = 255, example = "Store #42, Bengaluru")]
+    pub location: Option<String>,
+
+    /// The hardware serial number of the terminal.
+    #[schema(max_length = 255, example = "WSC513105011295")]
+    pub serial_number: Option<String>,
+
+    /// Whether the terminal can be used to accept card-present payments.
+    #[schema(example = true)]
+    pub is_active: bool,
+
+    /// Additional, structured information stored against the terminal.
+    #[schema(value_type = Option<Object>, example = r#"{ "city": "NY", "unit": "245" }"#)]
+    pub metadata: Option<pii::SecretSerdeValue>,
+
+    /// The time at which the terminal was registered.
+    #[schema(example = "2022-09-10T10:11:12Z")]
+    #[serde(with = "common_utils::custom_serde::iso8601")]
+    pub created: PrimitiveDateTime,
+}
+
+/// The response body for deleting a registered terminal.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct TerminalDeleteResponse {
+    /// The identifier for the terminal.
+    #[schema(max_length = 64, example = "term_4hDjyLsm6Y7BIMC9zPYB")]
+    pub terminal_id: String,
+
+    /// Indicates whether the terminal was deleted or not.
+    #[schema(example = true)]
+    pub deleted: bool,
+}
+
+/// The constraints that are applicable when listing terminals registered by a merchant.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TerminalListConstraints {
+    /// The maximum number of terminals to include in the response.
+    pub limit: Option<i64>,
+
+    /// The number of terminals to skip when retrieving the list of terminals.
+    pub skip: Option<i64>,
+}
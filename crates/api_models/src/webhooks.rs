@@ -3,7 +3,9 @@ use serde::{Deserialize, Serialize};
 use time::PrimitiveDateTime;
 use utoipa::ToSchema;
 
-use crate::{disputes, enums as api_enums, payments, refunds};
+#[cfg(feature = "payouts")]
+use crate::payouts;
+use crate::{disputes, enums as api_enums, invoices, payment_methods, payments, refunds, reports};
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
@@ -28,6 +30,10 @@ pub enum IncomingWebhookEvent {
     // dispute has been unsuccessfully challenged
     DisputeLost,
     EndpointVerification,
+    PayoutInitiated,
+    PayoutSuccess,
+    PayoutFailure,
+    PayoutReversed,
 }
 
 pub enum WebhookFlow {
@@ -37,6 +43,7 @@ pub enum WebhookFlow {
     Subscription,
     ReturnResponse,
     BankTransfer,
+    Payout,
 }
 
 impl From<IncomingWebhookEvent> for WebhookFlow {
@@ -61,6 +68,10 @@ impl From<IncomingWebhookEvent> for WebhookFlow {
             IncomingWebhookEvent::EndpointVerification => Self::ReturnResponse,
             IncomingWebhookEvent::SourceChargeable
             | IncomingWebhookEvent::SourceTransactionCreated => Self::BankTransfer,
+            IncomingWebhookEvent::PayoutInitiated
+            | IncomingWebhookEvent::PayoutSuccess
+            | IncomingWebhookEvent::PayoutFailure
+            | IncomingWebhookEvent::PayoutReversed => Self::Payout,
         }
     }
 }
@@ -72,9 +83,15 @@ pub enum RefundIdType {
     ConnectorRefundId(String),
 }
 
+pub enum PayoutIdType {
+    PayoutId(String),
+    ConnectorPayoutId(String),
+}
+
 pub enum ObjectReferenceId {
     PaymentId(payments::PaymentIdType),
     RefundId(RefundIdType),
+    PayoutId(PayoutIdType),
 }
 
 pub struct IncomingWebhookDetails {
@@ -100,6 +117,70 @@ pub struct OutgoingWebhook {
 
     /// The time at which webhook was sent
     pub timestamp: PrimitiveDateTime,
+
+    /// The IP ranges from which webhooks are sent, included when enabled in the webhook details of the merchant
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source_validation: Option<WebhookSourceValidation>,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct WebhookSourceValidation {
+    /// The IP ranges, in CIDR notation, from which webhooks are sent
+    #[schema(example = json!(["52.66.0.0/16", "2406:da1a::/32"]))]
+    pub egress_ip_ranges: Vec<String>,
+
+    /// The page documenting how to validate the source of webhooks
+    #[schema(example = "https://docs.hyperswitch.io/webhooks/source-validation")]
+    pub documentation_url: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct WebhookEgressIpRangesUpdateRequest {
+    /// The IP ranges, in CIDR notation, from which webhooks are sent
+    pub egress_ip_ranges: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct WebhookEgressIpRangesResponse {
+    /// The IP ranges, in CIDR notation, from which webhooks are sent
+    pub egress_ip_ranges: Vec<String>,
+
+    /// The page documenting how to validate the source of webhooks
+    pub documentation_url: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct WebhookDeliveryAttempt {
+    /// The business webhook endpoint to which the webhook was posted, not set for the webhook endpoint of the merchant
+    pub endpoint_id: Option<String>,
+
+    /// The number of the attempt to post the webhook to the endpoint, starting from 1 for the first delivery of the webhook
+    pub attempt_number: i32,
+
+    /// Whether the webhook was received by the webhook endpoint
+    pub is_delivered: bool,
+
+    /// The HTTP status code returned by the webhook endpoint, if a response was received
+    pub response_status_code: Option<i32>,
+
+    /// The reason the webhook could not be posted to the webhook endpoint
+    pub error_message: Option<String>,
+
+    /// The time at which the next attempt is scheduled, if the webhook is retried
+    #[serde(with = "custom_serde::iso8601::option")]
+    pub next_retry_at: Option<PrimitiveDateTime>,
+
+    #[serde(with = "custom_serde::iso8601")]
+    pub created_at: PrimitiveDateTime,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct WebhookDeliveryAttemptsResponse {
+    pub event_id: String,
+
+    /// The attempts made to deliver the webhook, oldest first
+    pub attempts: Vec<WebhookDeliveryAttempt>,
 }
 
 #[derive(Debug, Clone, Serialize, ToSchema)]
@@ -111,4 +192,13 @@ pub enum OutgoingWebhookContent {
     RefundDetails(refunds::RefundResponse),
     #[schema(value_type = DisputeResponse)]
     DisputeDetails(Box<disputes::DisputeResponse>),
+    #[schema(value_type = InvoiceResponse)]
+    InvoiceDetails(Box<invoices::InvoiceResponse>),
+    #[schema(value_type = GeneratedReportResponse)]
+    ReportDetails(Box<reports::GeneratedReportResponse>),
+    #[schema(value_type = PaymentMethodResponse)]
+    PaymentMethodDetails(Box<payment_methods::PaymentMethodResponse>),
+    #[cfg(feature = "payouts")]
+    #[schema(value_type = PayoutCreateResponse)]
+    PayoutDetails(Box<payouts::PayoutCreateResponse>),
 }
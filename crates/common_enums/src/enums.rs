@@ -97,6 +97,7 @@ pub enum AuthenticationType {
     strum::Display,
     strum::EnumString,
     Hash,
+    ToSchema,
 )]
 #[router_derive::diesel_enum(storage_type = "pg_enum")]
 #[serde(rename_all = "snake_case")]
@@ -142,6 +143,8 @@ pub enum CaptureMethod {
     ManualMultiple,
     /// The capture can be scheduled to automatically get triggered at a specific date & time
     Scheduled,
+    /// Post the payment authorization, the capture will be executed on the full amount after a merchant configured delay, unless the payment is voided before that
+    AutomaticDelayed,
 }
 
 #[derive(
@@ -750,6 +753,8 @@ pub enum EventType {
     ActionRequired,
     RefundSucceeded,
     RefundFailed,
+    RefundAmountMismatch,
+    RefundManualReview,
     DisputeOpened,
     DisputeExpired,
     DisputeAccepted,
@@ -757,6 +762,16 @@ pub enum EventType {
     DisputeChallenged,
     DisputeWon,
     DisputeLost,
+    InvoicePartiallyPaid,
+    InvoicePaid,
+    InvoiceVoided,
+    ReportGenerated,
+    PaymentMethodExpiring,
+    PaymentMethodUpdated,
+    PayoutProcessing,
+    PayoutSucceeded,
+    PayoutFailed,
+    PayoutReversed,
 }
 
 #[derive(
@@ -1040,6 +1055,14 @@ pub enum RefundStatus {
     Pending,
     Success,
     TransactionFailure,
+    Cancelled,
+    /// The connector reported an amount refunded different from the amount requested
+    AmountMismatch,
+    /// The refund amount is above the approval threshold of the merchant, and the refund is held
+    /// until it is approved
+    PendingApproval,
+    /// The refund was rejected while pending approval, and was never sent to the connector
+    Rejected,
 }
 
 /// The status of the mandate, which indicates whether it can be used to initiate a payment
@@ -1150,6 +1173,388 @@ pub enum DisputeStatus {
     DisputeLost,
 }
 
+#[derive(
+    Clone,
+    Debug,
+    Copy,
+    Default,
+    Eq,
+    Hash,
+    PartialEq,
+    serde::Deserialize,
+    serde::Serialize,
+    strum::Display,
+    strum::EnumString,
+    ToSchema,
+)]
+#[router_derive::diesel_enum(storage_type = "pg_enum")]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum InvoiceStatus {
+    // no payments have been applied to the invoice yet
+    #[default]
+    Open,
+    PartiallyPaid,
+    Paid,
+    // the invoice was cancelled by the merchant and can no longer be paid
+    Void,
+}
+
+#[derive(
+    Clone,
+    Debug,
+    Copy,
+    Default,
+    Eq,
+    Hash,
+    PartialEq,
+    serde::Deserialize,
+    serde::Serialize,
+    strum::Display,
+    strum::EnumString,
+    ToSchema,
+)]
+#[router_derive::diesel_enum(storage_type = "pg_enum")]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum PaymentLinkStatus {
+    // the payment link can be opened by the customer to pay
+    #[default]
+    Active,
+    // the payment link has passed its expiry and can no longer be opened
+    Expired,
+}
+
+#[derive(
+    Clone,
+    Debug,
+    Copy,
+    Default,
+    Eq,
+    Hash,
+    PartialEq,
+    serde::Deserialize,
+    serde::Serialize,
+    strum::Display,
+    strum::EnumString,
+    ToSchema,
+)]
+#[router_derive::diesel_enum(storage_type = "pg_enum")]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum PaymentMethodShareStatus {
+    // the payment method can be used by the sub-merchant it is shared with
+    #[default]
+    Active,
+    // the share has been revoked by the merchant owning the payment method
+    Revoked,
+}
+
+#[derive(
+    Clone,
+    Debug,
+    Copy,
+    Default,
+    Eq,
+    Hash,
+    PartialEq,
+    serde::Deserialize,
+    serde::Serialize,
+    strum::Display,
+    strum::EnumString,
+    ToSchema,
+)]
+#[router_derive::diesel_enum(storage_type = "pg_enum")]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum GiftCardStatus {
+    // the balance of the gift card can be redeemed and reloaded
+    #[default]
+    Active,
+    // the gift card was voided by the merchant, forfeiting its remaining balance
+    Void,
+}
+
+#[derive(
+    Clone,
+    Debug,
+    Copy,
+    Eq,
+    Hash,
+    PartialEq,
+    serde::Deserialize,
+    serde::Serialize,
+    strum::Display,
+    strum::EnumString,
+    ToSchema,
+)]
+#[router_derive::diesel_enum(storage_type = "pg_enum")]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum GiftCardTransactionType {
+    Issue,
+    Redeem,
+    /// Returns the amount redeemed towards a payment which has failed to the gift card
+    RedeemReversal,
+    /// Records the amount redeemed towards a payment which has failed, which was not returned to
+    /// the gift card as the gift card was voided in the meantime
+    RedeemForfeit,
+    Reload,
+    Void,
+}
+
+#[derive(
+    Clone,
+    Debug,
+    Copy,
+    Eq,
+    Hash,
+    PartialEq,
+    serde::Deserialize,
+    serde::Serialize,
+    strum::Display,
+    strum::EnumString,
+    ToSchema,
+)]
+#[router_derive::diesel_enum(storage_type = "pg_enum")]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum AuditEntityType {
+    MerchantAccount,
+    MerchantConnectorAccount,
+    // the routing algorithms and connector exclusion rules of a merchant
+    RoutingConfig,
+    ApiKey,
+    // the webhook details of a merchant
+    WebhookSettings,
+    // the status of a refund resolved by an administrator
+    Refund,
+}
+
+#[derive(
+    Clone,
+    Debug,
+    Copy,
+    Eq,
+    Hash,
+    PartialEq,
+    serde::Deserialize,
+    serde::Serialize,
+    strum::Display,
+    strum::EnumString,
+    ToSchema,
+)]
+#[router_derive::diesel_enum(storage_type = "pg_enum")]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum AuditAction {
+    Create,
+    Update,
+    Delete,
+}
+
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Eq,
+    Hash,
+    PartialEq,
+    serde::Deserialize,
+    serde::Serialize,
+    strum::Display,
+    strum::EnumString,
+    ToSchema,
+)]
+#[router_derive::diesel_enum(storage_type = "text")]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum ReportType {
+    /// Payment counts and amounts of the period, grouped by currency, connector and status
+    PaymentsSummary,
+    /// Disputes received during the period
+    Disputes,
+}
+
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Eq,
+    Hash,
+    PartialEq,
+    serde::Deserialize,
+    serde::Serialize,
+    strum::Display,
+    strum::EnumString,
+    ToSchema,
+)]
+#[router_derive::diesel_enum(storage_type = "text")]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum ReportFrequency {
+    /// Generated every day at midnight UTC, covering the previous day
+    Daily,
+    /// Generated every Monday at midnight UTC, covering the previous week
+    Weekly,
+}
+
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Eq,
+    Hash,
+    PartialEq,
+    serde::Deserialize,
+    serde::Serialize,
+    strum::Display,
+    strum::EnumString,
+    ToSchema,
+)]
+#[router_derive::diesel_enum(storage_type = "text")]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum ReportDeliveryMethod {
+    /// A `report_generated` webhook with a link to the report is sent to the merchant
+    Webhook,
+    /// An email with a link to the report is sent to the recipient of the report
+    Email,
+}
+
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Eq,
+    Hash,
+    PartialEq,
+    serde::Deserialize,
+    serde::Serialize,
+    strum::Display,
+    strum::EnumString,
+    ToSchema,
+)]
+#[router_derive::diesel_enum(storage_type = "text")]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum SubscriptionStatus {
+    /// The customer is billed on every billing cycle
+    Active,
+    /// The last billing attempt failed and is being retried
+    PastDue,
+    /// Billing has been paused, either by the merchant or after the retries were exhausted
+    Paused,
+    /// The subscription has ended and the customer is no longer billed
+    Cancelled,
+}
+
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Eq,
+    Hash,
+    PartialEq,
+    serde::Deserialize,
+    serde::Serialize,
+    strum::Display,
+    strum::EnumString,
+    ToSchema,
+)]
+#[router_derive::diesel_enum(storage_type = "text")]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum BillingInterval {
+    Day,
+    Week,
+    Month,
+    Year,
+}
+
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    Eq,
+    Hash,
+    PartialEq,
+    serde::Deserialize,
+    serde::Serialize,
+    strum::Display,
+    strum::EnumString,
+    ToSchema,
+)]
+#[router_derive::diesel_enum(storage_type = "text")]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum CustomerImportStatus {
+    /// The customers of the import are yet to be processed
+    #[default]
+    Pending,
+    /// The customers of the import are being created
+    Processing,
+    /// All the customers of the import have been processed, some of them may have failed
+    Completed,
+    /// The import was stopped due to an error which is not specific to any of its customers, it
+    /// can be resumed from its last saved position
+    Failed,
+}
+
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Eq,
+    Hash,
+    PartialEq,
+    serde::Deserialize,
+    serde::Serialize,
+    strum::Display,
+    strum::EnumString,
+    ToSchema,
+)]
+#[router_derive::diesel_enum(storage_type = "text")]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum AccountUpdateType {
+    /// The card was renewed by the issuer with a new expiry date
+    ExpiryUpdated,
+    /// The card was replaced by the issuer with a new card number
+    CardReplaced,
+    /// The account of the card was closed by the cardholder or the issuer
+    AccountClosed,
+}
+
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    Eq,
+    Hash,
+    PartialEq,
+    serde::Deserialize,
+    serde::Serialize,
+    strum::Display,
+    strum::EnumString,
+    ToSchema,
+)]
+#[router_derive::diesel_enum(storage_type = "text")]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum WebhookEndpointStatus {
+    /// Webhooks are delivered to the endpoint
+    #[default]
+    Healthy,
+    /// Deliveries to the endpoint have been failing, but webhooks are still delivered to it
+    Degraded,
+    /// Deliveries to the endpoint have been failing, and webhooks are queued until the endpoint
+    /// is verified again and the queued webhooks have been delivered
+    Paused,
+}
+
 #[derive(
     Clone,
     Debug,
@@ -1496,6 +1901,7 @@ pub enum FileUploadProvider {
     Router,
     Stripe,
     Checkout,
+    Adyen,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, strum::Display)]
@@ -1604,6 +2010,7 @@ pub enum PayoutStatus {
     RequiresCreation,
     RequiresPayoutMethodData,
     RequiresFulfillment,
+    Reversed,
 }
 
 #[derive(
@@ -238,6 +238,36 @@ impl VerifySignature for HmacSha512 {
     }
 }
 
+/// Represents the RSASSA-PKCS1-v1_5 signature algorithm with SHA-256
+#[derive(Debug)]
+pub struct RsaSha256;
+
+impl SignMessage for RsaSha256 {
+    /// Signs the message with the RSA private key, which is expected to be DER encoded in the
+    /// PKCS#8 format
+    fn sign_message(
+        &self,
+        secret: &[u8],
+        msg: &[u8],
+    ) -> CustomResult<Vec<u8>, errors::CryptoError> {
+        let key_pair = ring::signature::RsaKeyPair::from_pkcs8(secret)
+            .map_err(|_| errors::CryptoError::MessageSigningFailed)
+            .into_report()
+            .attach_printable("Invalid RSA private key")?;
+        let mut signature = vec![0; key_pair.public_modulus_len()];
+        key_pair
+            .sign(
+                &ring::signature::RSA_PKCS1_SHA256,
+                &ring::rand::SystemRandom::new(),
+                msg,
+                &mut signature,
+            )
+            .map_err(|_| errors::CryptoError::MessageSigningFailed)
+            .into_report()?;
+        Ok(signature)
+    }
+}
+
 /// Represents the GCM-AES-256 algorithm
 #[derive(Debug)]
 pub struct GcmAes256;
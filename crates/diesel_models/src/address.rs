@@ -22,6 +22,7 @@ pub struct AddressNew {
     pub merchant_id: String,
     pub created_at: PrimitiveDateTime,
     pub modified_at: PrimitiveDateTime,
+    pub is_address_book_entry: bool,
 }
 
 #[derive(Clone, Debug, Identifiable, Queryable)]
@@ -44,6 +45,7 @@ pub struct Address {
     pub modified_at: PrimitiveDateTime,
     pub customer_id: String,
     pub merchant_id: String,
+    pub is_address_book_entry: bool,
 }
 
 #[derive(Clone, Debug, AsChangeset, router_derive::DebugAsDisplay)]
@@ -0,0 +1,39 @@
+use common_utils::{custom_serde, pii};
+use diesel::{Identifiable, Insertable, Queryable};
+use serde::{Deserialize, Serialize};
+
+use crate::{enums as storage_enums, schema::audit_event};
+
+#[derive(Clone, Debug, Insertable, router_derive::DebugAsDisplay)]
+#[diesel(table_name = audit_event)]
+pub struct AuditEventNew {
+    pub event_id: String,
+    pub merchant_id: String,
+    pub entity_type: storage_enums::AuditEntityType,
+    pub entity_id: String,
+    pub action: storage_enums::AuditAction,
+    pub actor: String,
+    pub previous_values: Option<pii::SecretSerdeValue>,
+    pub updated_values: Option<pii::SecretSerdeValue>,
+    pub created_at: time::PrimitiveDateTime,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, Identifiable, Queryable)]
+#[diesel(table_name = audit_event, primary_key(event_id))]
+pub struct AuditEvent {
+    #[serde(skip_serializing)]
+    pub id: i32,
+    pub event_id: String,
+    pub merchant_id: String,
+    pub entity_type: storage_enums::AuditEntityType,
+    pub entity_id: String,
+    pub action: storage_enums::AuditAction,
+    /// The kind of credentials the change was made with
+    pub actor: String,
+    /// The values of the changed fields of the entity before the change
+    pub previous_values: Option<pii::SecretSerdeValue>,
+    /// The values of the changed fields of the entity after the change
+    pub updated_values: Option<pii::SecretSerdeValue>,
+    #[serde(with = "custom_serde::iso8601")]
+    pub created_at: time::PrimitiveDateTime,
+}
@@ -0,0 +1,36 @@
+use common_utils::custom_serde;
+use diesel::{Identifiable, Insertable, Queryable};
+use serde::{Deserialize, Serialize};
+
+use crate::{enums as storage_enums, schema::commission};
+
+#[derive(Clone, Debug, Deserialize, Insertable, Serialize, router_derive::DebugAsDisplay)]
+#[diesel(table_name = commission)]
+pub struct CommissionNew {
+    pub commission_id: String,
+    pub merchant_id: String,
+    pub parent_merchant_id: Option<String>,
+    pub payment_id: String,
+    pub pricing_plan: String,
+    pub currency: storage_enums::Currency,
+    pub payment_amount: i64,
+    pub commission_amount: i64,
+    pub created_at: time::PrimitiveDateTime,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, Identifiable, Queryable)]
+#[diesel(table_name = commission, primary_key(commission_id))]
+pub struct Commission {
+    #[serde(skip_serializing)]
+    pub id: i32,
+    pub commission_id: String,
+    pub merchant_id: String,
+    pub parent_merchant_id: Option<String>,
+    pub payment_id: String,
+    pub pricing_plan: String,
+    pub currency: storage_enums::Currency,
+    pub payment_amount: i64,
+    pub commission_amount: i64,
+    #[serde(with = "custom_serde::iso8601")]
+    pub created_at: time::PrimitiveDateTime,
+}
@@ -0,0 +1,115 @@
+use diesel::{AsChangeset, Identifiable, Insertable, Queryable};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    address::AddressNew, customers::CustomerNew, encryption::Encryption, enums as storage_enums,
+    payment_method::PaymentMethodNew, process_tracker::ProcessTrackerNew,
+    schema::customer_import_job,
+};
+
+#[derive(Clone, Debug, Insertable, router_derive::DebugAsDisplay)]
+#[diesel(table_name = customer_import_job)]
+pub struct CustomerImportJobNew {
+    pub job_id: String,
+    pub merchant_id: String,
+    pub status: storage_enums::CustomerImportStatus,
+    pub records: Encryption,
+    pub total_count: i32,
+    pub created_at: time::PrimitiveDateTime,
+    pub modified_at: time::PrimitiveDateTime,
+}
+
+#[derive(Clone, Debug, Identifiable, Queryable)]
+#[diesel(table_name = customer_import_job, primary_key(job_id, merchant_id))]
+pub struct CustomerImportJob {
+    pub id: i32,
+    pub job_id: String,
+    pub merchant_id: String,
+    pub status: storage_enums::CustomerImportStatus,
+    /// The customers to be imported, encrypted with the key of the merchant
+    pub records: Encryption,
+    pub total_count: i32,
+    /// The number of customers processed so far, the import is resumed from this position
+    pub processed_count: i32,
+    pub succeeded_count: i32,
+    pub failed_count: i32,
+    /// The errors of the customers which could not be imported
+    pub errors: Option<serde_json::Value>,
+    pub created_at: time::PrimitiveDateTime,
+    pub modified_at: time::PrimitiveDateTime,
+    pub completed_at: Option<time::PrimitiveDateTime>,
+}
+
+/// The customers of a batch of an import, created in a single transaction along with the progress
+/// of the import
+#[derive(Debug, Default)]
+pub struct CustomerImportBatch {
+    pub addresses: Vec<AddressNew>,
+    pub customers: Vec<CustomerNew>,
+    pub payment_methods: Vec<PaymentMethodNew>,
+    /// The tasks refreshing the saved cards of the customers before their expiry
+    pub processes: Vec<ProcessTrackerNew>,
+}
+
+#[derive(Debug)]
+pub enum CustomerImportJobUpdate {
+    StatusUpdate {
+        status: storage_enums::CustomerImportStatus,
+    },
+    ProgressUpdate {
+        status: storage_enums::CustomerImportStatus,
+        processed_count: i32,
+        succeeded_count: i32,
+        failed_count: i32,
+        errors: Option<serde_json::Value>,
+        completed_at: Option<time::PrimitiveDateTime>,
+    },
+}
+
+#[derive(Clone, Debug, Default, AsChangeset, router_derive::DebugAsDisplay)]
+#[diesel(table_name = customer_import_job)]
+pub struct CustomerImportJobUpdateInternal {
+    status: Option<storage_enums::CustomerImportStatus>,
+    processed_count: Option<i32>,
+    succeeded_count: Option<i32>,
+    failed_count: Option<i32>,
+    errors: Option<serde_json::Value>,
+    completed_at: Option<time::PrimitiveDateTime>,
+    modified_at: Option<time::PrimitiveDateTime>,
+}
+
+impl From<CustomerImportJobUpdate> for CustomerImportJobUpdateInternal {
+    fn from(customer_import_job_update: CustomerImportJobUpdate) -> Self {
+        let modified_at = Some(common_utils::date_time::now());
+        match customer_import_job_update {
+            CustomerImportJobUpdate::StatusUpdate { status } => Self {
+                status: Some(status),
+                modified_at,
+                ..Default::default()
+            },
+            CustomerImportJobUpdate::ProgressUpdate {
+                status,
+                processed_count,
+                succeeded_count,
+                failed_count,
+                errors,
+                completed_at,
+            } => Self {
+                status: Some(status),
+                processed_count: Some(processed_count),
+                succeeded_count: Some(succeeded_count),
+                failed_count: Some(failed_count),
+                errors,
+                completed_at,
+                modified_at,
+            },
+        }
+    }
+}
+
+// Tracking data by process_tracker
+#[derive(Default, Debug, Deserialize, Serialize, Clone)]
+pub struct CustomerImportWorkflow {
+    pub job_id: String,
+    pub merchant_id: String,
+}
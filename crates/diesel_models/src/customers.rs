@@ -18,6 +18,13 @@ pub struct CustomerNew {
     pub connector_customer: Option<serde_json::Value>,
     pub created_at: PrimitiveDateTime,
     pub modified_at: PrimitiveDateTime,
+    pub address_id: Option<String>,
+    pub default_shipping_address_id: Option<String>,
+    pub default_billing_address_id: Option<String>,
+    /// The hash of the normalized email of the customer, by which customers are looked up as the
+    /// email itself is encrypted
+    pub email_hash: Option<String>,
+    pub default_payment_method_id: Option<String>,
 }
 
 #[derive(Clone, Debug, Identifiable, Queryable)]
@@ -35,6 +42,13 @@ pub struct Customer {
     pub metadata: Option<pii::SecretSerdeValue>,
     pub connector_customer: Option<serde_json::Value>,
     pub modified_at: PrimitiveDateTime,
+    pub address_id: Option<String>,
+    pub default_shipping_address_id: Option<String>,
+    pub default_billing_address_id: Option<String>,
+    /// The hash of the normalized email of the customer, by which customers are looked up as the
+    /// email itself is encrypted
+    pub email_hash: Option<String>,
+    pub default_payment_method_id: Option<String>,
 }
 
 #[derive(Clone, Debug, Default, AsChangeset, router_derive::DebugAsDisplay)]
@@ -48,4 +62,9 @@ pub struct CustomerUpdateInternal {
     pub metadata: Option<pii::SecretSerdeValue>,
     pub modified_at: Option<PrimitiveDateTime>,
     pub connector_customer: Option<serde_json::Value>,
+    pub address_id: Option<String>,
+    pub default_shipping_address_id: Option<Option<String>>,
+    pub default_billing_address_id: Option<Option<String>>,
+    pub email_hash: Option<String>,
+    pub default_payment_method_id: Option<Option<String>>,
 }
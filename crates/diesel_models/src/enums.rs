@@ -1,16 +1,20 @@
 #[doc(hidden)]
 pub mod diesel_exports {
     pub use super::{
-        DbAttemptStatus as AttemptStatus, DbAuthenticationType as AuthenticationType,
+        DbAttemptStatus as AttemptStatus, DbAuditAction as AuditAction,
+        DbAuditEntityType as AuditEntityType, DbAuthenticationType as AuthenticationType,
         DbCaptureMethod as CaptureMethod, DbCaptureStatus as CaptureStatus,
         DbConnectorType as ConnectorType, DbCountryAlpha2 as CountryAlpha2, DbCurrency as Currency,
         DbDisputeStage as DisputeStage, DbDisputeStatus as DisputeStatus,
         DbEventClass as EventClass, DbEventObjectType as EventObjectType, DbEventType as EventType,
         DbFraudCheckStatus as FraudCheckStatus, DbFraudCheckType as FraudCheckType,
-        DbFutureUsage as FutureUsage, DbIntentStatus as IntentStatus,
-        DbMandateStatus as MandateStatus, DbMandateType as MandateType,
-        DbMerchantStorageScheme as MerchantStorageScheme,
-        DbPaymentMethodIssuerCode as PaymentMethodIssuerCode, DbPayoutStatus as PayoutStatus,
+        DbFutureUsage as FutureUsage, DbGiftCardStatus as GiftCardStatus,
+        DbGiftCardTransactionType as GiftCardTransactionType, DbIntentStatus as IntentStatus,
+        DbInvoiceStatus as InvoiceStatus, DbMandateStatus as MandateStatus,
+        DbMandateType as MandateType, DbMerchantStorageScheme as MerchantStorageScheme,
+        DbPaymentLinkStatus as PaymentLinkStatus,
+        DbPaymentMethodIssuerCode as PaymentMethodIssuerCode,
+        DbPaymentMethodShareStatus as PaymentMethodShareStatus, DbPayoutStatus as PayoutStatus,
         DbPayoutType as PayoutType, DbProcessTrackerStatus as ProcessTrackerStatus,
         DbRefundStatus as RefundStatus, DbRefundType as RefundType,
     };
@@ -38,6 +42,10 @@ pub enum EventClass {
     Payments,
     Refunds,
     Disputes,
+    Invoices,
+    Reports,
+    PaymentMethods,
+    Payouts,
 }
 
 #[derive(
@@ -58,6 +66,10 @@ pub enum EventObjectType {
     PaymentDetails,
     RefundDetails,
     DisputeDetails,
+    InvoiceDetails,
+    ReportDetails,
+    PaymentMethodDetails,
+    PayoutDetails,
 }
 
 #[derive(
@@ -0,0 +1,109 @@
+use common_utils::{custom_serde, pii};
+use diesel::{AsChangeset, Identifiable, Insertable, Queryable};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    enums as storage_enums,
+    schema::{gift_card, gift_card_transaction},
+};
+
+#[derive(Clone, Debug, Deserialize, Insertable, Serialize, router_derive::DebugAsDisplay)]
+#[diesel(table_name = gift_card)]
+pub struct GiftCardNew {
+    pub gift_card_id: String,
+    pub merchant_id: String,
+    pub customer_id: Option<String>,
+    pub status: storage_enums::GiftCardStatus,
+    pub currency: storage_enums::Currency,
+    pub initial_balance: i64,
+    pub balance: i64,
+    pub expires_at: Option<time::PrimitiveDateTime>,
+    pub metadata: Option<pii::SecretSerdeValue>,
+    pub created_at: time::PrimitiveDateTime,
+    pub modified_at: time::PrimitiveDateTime,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, Identifiable, Queryable)]
+#[diesel(table_name = gift_card, primary_key(gift_card_id, merchant_id))]
+pub struct GiftCard {
+    #[serde(skip_serializing)]
+    pub id: i32,
+    pub gift_card_id: String,
+    pub merchant_id: String,
+    pub customer_id: Option<String>,
+    pub status: storage_enums::GiftCardStatus,
+    pub currency: storage_enums::Currency,
+    pub initial_balance: i64,
+    pub balance: i64,
+    #[serde(default, with = "custom_serde::iso8601::option")]
+    pub expires_at: Option<time::PrimitiveDateTime>,
+    pub metadata: Option<pii::SecretSerdeValue>,
+    #[serde(with = "custom_serde::iso8601")]
+    pub created_at: time::PrimitiveDateTime,
+    #[serde(with = "custom_serde::iso8601")]
+    pub modified_at: time::PrimitiveDateTime,
+}
+
+#[derive(Debug)]
+pub enum GiftCardUpdate {
+    BalanceUpdate { balance: i64 },
+    Void,
+}
+
+#[derive(Clone, Debug, AsChangeset, router_derive::DebugAsDisplay)]
+#[diesel(table_name = gift_card)]
+pub struct GiftCardUpdateInternal {
+    balance: Option<i64>,
+    status: Option<storage_enums::GiftCardStatus>,
+    modified_at: time::PrimitiveDateTime,
+}
+
+impl From<GiftCardUpdate> for GiftCardUpdateInternal {
+    fn from(gift_card_update: GiftCardUpdate) -> Self {
+        match gift_card_update {
+            GiftCardUpdate::BalanceUpdate { balance } => Self {
+                balance: Some(balance),
+                status: None,
+                modified_at: common_utils::date_time::now(),
+            },
+            GiftCardUpdate::Void => Self {
+                balance: Some(0),
+                status: Some(storage_enums::GiftCardStatus::Void),
+                modified_at: common_utils::date_time::now(),
+            },
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Insertable, Serialize, router_derive::DebugAsDisplay)]
+#[diesel(table_name = gift_card_transaction)]
+pub struct GiftCardTransactionNew {
+    pub transaction_id: String,
+    pub gift_card_id: String,
+    pub merchant_id: String,
+    pub transaction_type: storage_enums::GiftCardTransactionType,
+    pub amount: i64,
+    pub balance_after: i64,
+    pub currency: storage_enums::Currency,
+    pub payment_id: Option<String>,
+    pub created_at: time::PrimitiveDateTime,
+    pub idempotency_key: Option<String>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, Identifiable, Queryable)]
+#[diesel(table_name = gift_card_transaction, primary_key(transaction_id))]
+pub struct GiftCardTransaction {
+    #[serde(skip_serializing)]
+    pub id: i32,
+    pub transaction_id: String,
+    pub gift_card_id: String,
+    pub merchant_id: String,
+    pub transaction_type: storage_enums::GiftCardTransactionType,
+    pub amount: i64,
+    pub balance_after: i64,
+    pub currency: storage_enums::Currency,
+    pub payment_id: Option<String>,
+    #[serde(with = "custom_serde::iso8601")]
+    pub created_at: time::PrimitiveDateTime,
+    pub idempotency_key: Option<String>,
+}
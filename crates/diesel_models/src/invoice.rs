@@ -0,0 +1,73 @@
+use common_utils::{custom_serde, pii};
+use diesel::{AsChangeset, Identifiable, Insertable, Queryable};
+use serde::{Deserialize, Serialize};
+
+use crate::{enums as storage_enums, schema::invoice};
+
+#[derive(Clone, Debug, Deserialize, Insertable, Serialize, router_derive::DebugAsDisplay)]
+#[diesel(table_name = invoice)]
+pub struct InvoiceNew {
+    pub invoice_id: String,
+    pub merchant_id: String,
+    pub customer_id: Option<String>,
+    pub status: storage_enums::InvoiceStatus,
+    pub currency: storage_enums::Currency,
+    pub amount: i64,
+    pub amount_paid: i64,
+    pub line_items: serde_json::Value,
+    pub description: Option<String>,
+    pub due_date: Option<time::PrimitiveDateTime>,
+    pub client_secret: String,
+    pub metadata: Option<pii::SecretSerdeValue>,
+    pub created_at: time::PrimitiveDateTime,
+    pub modified_at: time::PrimitiveDateTime,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, Identifiable, Queryable)]
+#[diesel(table_name = invoice, primary_key(invoice_id, merchant_id))]
+pub struct Invoice {
+    #[serde(skip_serializing)]
+    pub id: i32,
+    pub invoice_id: String,
+    pub merchant_id: String,
+    pub customer_id: Option<String>,
+    pub status: storage_enums::InvoiceStatus,
+    pub currency: storage_enums::Currency,
+    pub amount: i64,
+    pub amount_paid: i64,
+    pub line_items: serde_json::Value,
+    pub description: Option<String>,
+    #[serde(default, with = "custom_serde::iso8601::option")]
+    pub due_date: Option<time::PrimitiveDateTime>,
+    pub client_secret: String,
+    pub metadata: Option<pii::SecretSerdeValue>,
+    #[serde(with = "custom_serde::iso8601")]
+    pub created_at: time::PrimitiveDateTime,
+    #[serde(with = "custom_serde::iso8601")]
+    pub modified_at: time::PrimitiveDateTime,
+}
+
+#[derive(Debug)]
+pub enum InvoiceUpdate {
+    StatusUpdate {
+        status: storage_enums::InvoiceStatus,
+    },
+}
+
+#[derive(Clone, Debug, AsChangeset, router_derive::DebugAsDisplay)]
+#[diesel(table_name = invoice)]
+pub struct InvoiceUpdateInternal {
+    status: Option<storage_enums::InvoiceStatus>,
+    modified_at: time::PrimitiveDateTime,
+}
+
+impl From<InvoiceUpdate> for InvoiceUpdateInternal {
+    fn from(invoice_update: InvoiceUpdate) -> Self {
+        match invoice_update {
+            InvoiceUpdate::StatusUpdate { status } => Self {
+                status: Some(status),
+                modified_at: common_utils::date_time::now(),
+            },
+        }
+    }
+}
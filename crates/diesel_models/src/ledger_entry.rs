@@ -0,0 +1,40 @@
+use common_utils::custom_serde;
+use diesel::{Identifiable, Insertable, Queryable};
+use serde::{Deserialize, Serialize};
+
+use crate::{enums as storage_enums, schema::ledger_entry};
+
+#[derive(Clone, Debug, Deserialize, Insertable, Serialize, router_derive::DebugAsDisplay)]
+#[diesel(table_name = ledger_entry)]
+pub struct LedgerEntryNew {
+    pub entry_id: String,
+    pub merchant_id: String,
+    pub entry_type: String,
+    pub reference_id: String,
+    pub debit_account: String,
+    pub credit_account: String,
+    pub amount: i64,
+    pub currency: storage_enums::Currency,
+    pub connector: Option<String>,
+    pub created_at: time::PrimitiveDateTime,
+    pub idempotency_key: String,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, Identifiable, Queryable)]
+#[diesel(table_name = ledger_entry, primary_key(entry_id))]
+pub struct LedgerEntry {
+    #[serde(skip_serializing)]
+    pub id: i32,
+    pub entry_id: String,
+    pub merchant_id: String,
+    pub entry_type: String,
+    pub reference_id: String,
+    pub debit_account: String,
+    pub credit_account: String,
+    pub amount: i64,
+    pub currency: storage_enums::Currency,
+    pub connector: Option<String>,
+    #[serde(with = "custom_serde::iso8601")]
+    pub created_at: time::PrimitiveDateTime,
+    pub idempotency_key: String,
+}
@@ -38,6 +38,12 @@ pub struct MerchantAccount {
     pub payout_routing_algorithm: Option<serde_json::Value>,
     pub organization_id: Option<String>,
     pub is_recon_enabled: bool,
+    pub webhook_mtls_details: Option<Encryption>,
+    pub allowed_domains: Option<Vec<String>>,
+    pub connector_exclusion_rules: Option<serde_json::Value>,
+    pub storage_region: Option<String>,
+    pub webhook_signing_keys: Option<Encryption>,
+    pub business_webhook_secrets: Option<Encryption>,
 }
 
 #[derive(Clone, Debug, Insertable, router_derive::DebugAsDisplay)]
@@ -65,6 +71,12 @@ pub struct MerchantAccountNew {
     pub payout_routing_algorithm: Option<serde_json::Value>,
     pub organization_id: Option<String>,
     pub is_recon_enabled: bool,
+    pub webhook_mtls_details: Option<Encryption>,
+    pub allowed_domains: Option<Vec<String>>,
+    pub connector_exclusion_rules: Option<serde_json::Value>,
+    pub storage_region: Option<String>,
+    pub webhook_signing_keys: Option<Encryption>,
+    pub business_webhook_secrets: Option<Encryption>,
 }
 
 #[derive(Clone, Debug, Default, AsChangeset, router_derive::DebugAsDisplay)]
@@ -91,4 +103,9 @@ pub struct MerchantAccountUpdateInternal {
     pub payout_routing_algorithm: Option<serde_json::Value>,
     pub organization_id: Option<String>,
     pub is_recon_enabled: bool,
+    pub webhook_mtls_details: Option<Encryption>,
+    pub allowed_domains: Option<Vec<String>>,
+    pub connector_exclusion_rules: Option<serde_json::Value>,
+    pub webhook_signing_keys: Option<Encryption>,
+    pub business_webhook_secrets: Option<Encryption>,
 }
@@ -37,6 +37,13 @@ pub struct MerchantConnectorAccount {
     pub created_at: time::PrimitiveDateTime,
     pub modified_at: time::PrimitiveDateTime,
     pub connector_webhook_details: Option<pii::SecretSerdeValue>,
+    pub test_connector_account_details: Option<Encryption>,
+    pub client_certificate: Option<Encryption>,
+    pub client_certificate_key: Option<Encryption>,
+    pub client_certificate_expires_at: Option<time::PrimitiveDateTime>,
+    pub webhook_secret: Option<Encryption>,
+    pub additional_webhook_secret: Option<Encryption>,
+    pub apple_pay_payment_processing_key: Option<Encryption>,
 }
 
 #[derive(Clone, Debug, Insertable, router_derive::DebugAsDisplay)]
@@ -60,6 +67,13 @@ pub struct MerchantConnectorAccountNew {
     pub created_at: time::PrimitiveDateTime,
     pub modified_at: time::PrimitiveDateTime,
     pub connector_webhook_details: Option<pii::SecretSerdeValue>,
+    pub test_connector_account_details: Option<Encryption>,
+    pub client_certificate: Option<Encryption>,
+    pub client_certificate_key: Option<Encryption>,
+    pub client_certificate_expires_at: Option<time::PrimitiveDateTime>,
+    pub webhook_secret: Option<Encryption>,
+    pub additional_webhook_secret: Option<Encryption>,
+    pub apple_pay_payment_processing_key: Option<Encryption>,
 }
 
 #[derive(Clone, Debug, AsChangeset, router_derive::DebugAsDisplay)]
@@ -77,7 +91,14 @@ pub struct MerchantConnectorAccountUpdateInternal {
     #[diesel(deserialize_as = super::OptionalDieselArray<pii::SecretSerdeValue>)]
     pub frm_configs: Option<Vec<Secret<serde_json::Value>>>,
     pub modified_at: Option<time::PrimitiveDateTime>,
-    pub connector_webhook_details: Option<pii::SecretSerdeValue>,
+    pub connector_webhook_details: Option<Option<pii::SecretSerdeValue>>,
+    pub test_connector_account_details: Option<Encryption>,
+    pub client_certificate: Option<Encryption>,
+    pub client_certificate_key: Option<Encryption>,
+    pub client_certificate_expires_at: Option<time::PrimitiveDateTime>,
+    pub webhook_secret: Option<Encryption>,
+    pub additional_webhook_secret: Option<Option<Encryption>>,
+    pub apple_pay_payment_processing_key: Option<Encryption>,
 }
 
 impl MerchantConnectorAccountUpdateInternal {
@@ -99,8 +120,40 @@ impl MerchantConnectorAccountUpdateInternal {
             payment_methods_enabled: self.payment_methods_enabled,
             frm_configs: self.frm_configs,
             modified_at: self.modified_at.unwrap_or(source.modified_at),
+            connector_webhook_details: self
+                .connector_webhook_details
+                .unwrap_or(source.connector_webhook_details),
+            test_connector_account_details: self
+                .test_connector_account_details
+                .or(source.test_connector_account_details),
+            client_certificate: self.client_certificate.or(source.client_certificate),
+            client_certificate_key: self
+                .client_certificate_key
+                .or(source.client_certificate_key),
+            client_certificate_expires_at: self
+                .client_certificate_expires_at
+                .or(source.client_certificate_expires_at),
+            webhook_secret: self.webhook_secret.or(source.webhook_secret),
+            additional_webhook_secret: self
+                .additional_webhook_secret
+                .unwrap_or(source.additional_webhook_secret),
+            apple_pay_payment_processing_key: self
+                .apple_pay_payment_processing_key
+                .or(source.apple_pay_payment_processing_key),
 
             ..source
         }
     }
 }
+
+// Tracking data by process_tracker
+#[derive(Default, Debug, serde::Deserialize, serde::Serialize, Clone)]
+pub struct ConnectorCertificateExpiryWorkflow {
+    pub merchant_id: String,
+    pub merchant_connector_id: String,
+    /// The expiry of the client certificate the reminders are sent for, reminders are not sent
+    /// once the certificate has been rotated
+    pub certificate_expires_at: Option<time::PrimitiveDateTime>,
+    /// Days before the expiry of the client certificate on which reminders are sent
+    pub expiry_reminder_days: Vec<u8>,
+}
@@ -20,6 +20,9 @@ pub struct MerchantKeyStore {
     pub key: Encryption,
     #[serde(with = "custom_serde::iso8601")]
     pub created_at: PrimitiveDateTime,
+    pub key_version: i32,
+    #[serde(default, with = "custom_serde::iso8601::option")]
+    pub rotated_at: Option<PrimitiveDateTime>,
 }
 
 #[derive(
@@ -30,6 +33,7 @@ pub struct MerchantKeyStoreNew {
     pub merchant_id: String,
     pub key: Encryption,
     pub created_at: PrimitiveDateTime,
+    pub key_version: i32,
 }
 
 #[derive(
@@ -37,6 +41,32 @@ pub struct MerchantKeyStoreNew {
 )]
 #[diesel(table_name = merchant_key_store)]
 pub struct MerchantKeyStoreUpdateInternal {
-    pub merchant_id: String,
     pub key: Encryption,
+    pub key_version: i32,
+    pub rotated_at: PrimitiveDateTime,
+}
+
+#[derive(Debug)]
+pub enum MerchantKeyStoreUpdate {
+    KeyRotation {
+        key: Encryption,
+        key_version: i32,
+        rotated_at: PrimitiveDateTime,
+    },
+}
+
+impl From<MerchantKeyStoreUpdate> for MerchantKeyStoreUpdateInternal {
+    fn from(merchant_key_store_update: MerchantKeyStoreUpdate) -> Self {
+        match merchant_key_store_update {
+            MerchantKeyStoreUpdate::KeyRotation {
+                key,
+                key_version,
+                rotated_at,
+            } => Self {
+                key,
+                key_version,
+                rotated_at,
+            },
+        }
+    }
 }
@@ -56,6 +56,10 @@ pub struct PaymentAttempt {
     pub multiple_capture_count: Option<i16>,
     // reference to the payment at connector side
     pub connector_response_reference_id: Option<String>,
+    // the attempt which this attempt retried, if it was created by retrying a failed attempt
+    pub previous_attempt_id: Option<String>,
+    // the incremental authorizations made for this attempt, oldest first
+    pub authorization_history: Option<serde_json::Value>,
 }
 
 #[derive(Clone, Debug, Eq, PartialEq, Queryable, Serialize, Deserialize)]
@@ -114,6 +118,8 @@ pub struct PaymentAttemptNew {
     pub error_reason: Option<String>,
     pub connector_response_reference_id: Option<String>,
     pub multiple_capture_count: Option<i16>,
+    pub previous_attempt_id: Option<String>,
+    pub authorization_history: Option<serde_json::Value>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -205,6 +211,10 @@ pub enum PaymentAttemptUpdate {
         connector_transaction_id: Option<String>,
         connector_response_reference_id: Option<String>,
     },
+    IncrementalAuthorizationUpdate {
+        amount: i64,
+        authorization_history: serde_json::Value,
+    },
 }
 
 #[derive(Clone, Debug, Default, AsChangeset, router_derive::DebugAsDisplay)]
@@ -237,6 +247,7 @@ pub struct PaymentAttemptUpdateInternal {
     capture_method: Option<storage_enums::CaptureMethod>,
     connector_response_reference_id: Option<String>,
     multiple_capture_count: Option<i16>,
+    authorization_history: Option<serde_json::Value>,
 }
 
 impl PaymentAttemptUpdate {
@@ -263,6 +274,10 @@ impl PaymentAttemptUpdate {
             preprocessing_step_id: pa_update
                 .preprocessing_step_id
                 .or(source.preprocessing_step_id),
+            amount_to_capture: pa_update.amount_to_capture.or(source.amount_to_capture),
+            authorization_history: pa_update
+                .authorization_history
+                .or(source.authorization_history),
             ..source
         }
     }
@@ -452,6 +467,16 @@ impl From<PaymentAttemptUpdate> for PaymentAttemptUpdateInternal {
                 multiple_capture_count,
                 ..Default::default()
             },
+            PaymentAttemptUpdate::IncrementalAuthorizationUpdate {
+                amount,
+                authorization_history,
+            } => Self {
+                amount: Some(amount),
+                amount_to_capture: Some(amount),
+                authorization_history: Some(authorization_history),
+                modified_at: Some(common_utils::date_time::now()),
+                ..Default::default()
+            },
         }
     }
 }
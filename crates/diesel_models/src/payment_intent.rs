@@ -42,6 +42,16 @@ pub struct PaymentIntent {
     pub connector_metadata: Option<serde_json::Value>,
     pub feature_metadata: Option<serde_json::Value>,
     pub attempt_count: i16,
+    pub invoice_id: Option<String>,
+    pub convenience_fee: Option<serde_json::Value>,
+    pub livemode: Option<bool>,
+    pub l2_l3_data: Option<serde_json::Value>,
+    pub presentment_amount: Option<i64>,
+    pub presentment_currency: Option<storage_enums::Currency>,
+    pub settlement_amount: Option<i64>,
+    pub settlement_currency: Option<storage_enums::Currency>,
+    pub exchange_rate: Option<String>,
+    pub cross_border_fees: Option<serde_json::Value>,
 }
 
 #[derive(
@@ -90,6 +100,16 @@ pub struct PaymentIntentNew {
     pub connector_metadata: Option<serde_json::Value>,
     pub feature_metadata: Option<serde_json::Value>,
     pub attempt_count: i16,
+    pub invoice_id: Option<String>,
+    pub convenience_fee: Option<serde_json::Value>,
+    pub livemode: Option<bool>,
+    pub l2_l3_data: Option<serde_json::Value>,
+    pub presentment_amount: Option<i64>,
+    pub presentment_currency: Option<storage_enums::Currency>,
+    pub settlement_amount: Option<i64>,
+    pub settlement_currency: Option<storage_enums::Currency>,
+    pub exchange_rate: Option<String>,
+    pub cross_border_fees: Option<serde_json::Value>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -133,6 +153,8 @@ pub enum PaymentIntentUpdate {
         statement_descriptor_suffix: Option<String>,
         order_details: Option<Vec<pii::SecretSerdeValue>>,
         metadata: Option<pii::SecretSerdeValue>,
+        l2_l3_data: Option<serde_json::Value>,
+        cross_border_fees: Option<serde_json::Value>,
     },
     PaymentAttemptAndAttemptCountUpdate {
         active_attempt_id: String,
@@ -143,6 +165,9 @@ pub enum PaymentIntentUpdate {
         active_attempt_id: String,
         attempt_count: i16,
     },
+    IncrementalAuthorizationAmountUpdate {
+        amount: i64,
+    },
 }
 
 #[derive(Clone, Debug, Default, AsChangeset, router_derive::DebugAsDisplay)]
@@ -170,6 +195,8 @@ pub struct PaymentIntentUpdateInternal {
     #[diesel(deserialize_as = super::OptionalDieselArray<pii::SecretSerdeValue>)]
     pub order_details: Option<Vec<pii::SecretSerdeValue>>,
     pub attempt_count: Option<i16>,
+    pub l2_l3_data: Option<serde_json::Value>,
+    pub cross_border_fees: Option<serde_json::Value>,
 }
 
 impl PaymentIntentUpdate {
@@ -195,6 +222,10 @@ impl PaymentIntentUpdate {
                 .or(source.shipping_address_id),
             modified_at: common_utils::date_time::now(),
             order_details: internal_update.order_details.or(source.order_details),
+            l2_l3_data: internal_update.l2_l3_data.or(source.l2_l3_data),
+            cross_border_fees: internal_update
+                .cross_border_fees
+                .or(source.cross_border_fees),
             ..source
         }
     }
@@ -219,6 +250,8 @@ impl From<PaymentIntentUpdate> for PaymentIntentUpdateInternal {
                 statement_descriptor_suffix,
                 order_details,
                 metadata,
+                l2_l3_data,
+                cross_border_fees,
             } => Self {
                 amount: Some(amount),
                 currency: Some(currency),
@@ -236,6 +269,8 @@ impl From<PaymentIntentUpdate> for PaymentIntentUpdateInternal {
                 statement_descriptor_suffix,
                 order_details,
                 metadata,
+                l2_l3_data,
+                cross_border_fees,
                 ..Default::default()
             },
             PaymentIntentUpdate::MetadataUpdate { metadata } => Self {
@@ -309,6 +344,11 @@ impl From<PaymentIntentUpdate> for PaymentIntentUpdateInternal {
                 attempt_count: Some(attempt_count),
                 ..Default::default()
             },
+            PaymentIntentUpdate::IncrementalAuthorizationAmountUpdate { amount } => Self {
+                amount: Some(amount),
+                modified_at: Some(common_utils::date_time::now()),
+                ..Default::default()
+            },
         }
     }
 }
@@ -0,0 +1,63 @@
+use common_utils::custom_serde;
+use diesel::{AsChangeset, Identifiable, Insertable, Queryable};
+use serde::{Deserialize, Serialize};
+
+use crate::{enums as storage_enums, schema::payment_link};
+
+#[derive(Clone, Debug, Deserialize, Insertable, Serialize, router_derive::DebugAsDisplay)]
+#[diesel(table_name = payment_link)]
+pub struct PaymentLinkNew {
+    pub payment_link_id: String,
+    pub merchant_id: String,
+    pub payment_id: String,
+    pub status: storage_enums::PaymentLinkStatus,
+    pub theme: Option<serde_json::Value>,
+    pub allowed_payment_methods: Option<serde_json::Value>,
+    pub expires_at: time::PrimitiveDateTime,
+    pub created_at: time::PrimitiveDateTime,
+    pub modified_at: time::PrimitiveDateTime,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, Identifiable, Queryable)]
+#[diesel(table_name = payment_link, primary_key(payment_link_id, merchant_id))]
+pub struct PaymentLink {
+    #[serde(skip_serializing)]
+    pub id: i32,
+    pub payment_link_id: String,
+    pub merchant_id: String,
+    pub payment_id: String,
+    pub status: storage_enums::PaymentLinkStatus,
+    pub theme: Option<serde_json::Value>,
+    pub allowed_payment_methods: Option<serde_json::Value>,
+    #[serde(with = "custom_serde::iso8601")]
+    pub expires_at: time::PrimitiveDateTime,
+    #[serde(with = "custom_serde::iso8601")]
+    pub created_at: time::PrimitiveDateTime,
+    #[serde(with = "custom_serde::iso8601")]
+    pub modified_at: time::PrimitiveDateTime,
+}
+
+#[derive(Debug)]
+pub enum PaymentLinkUpdate {
+    StatusUpdate {
+        status: storage_enums::PaymentLinkStatus,
+    },
+}
+
+#[derive(Clone, Debug, AsChangeset, router_derive::DebugAsDisplay)]
+#[diesel(table_name = payment_link)]
+pub struct PaymentLinkUpdateInternal {
+    status: Option<storage_enums::PaymentLinkStatus>,
+    modified_at: time::PrimitiveDateTime,
+}
+
+impl From<PaymentLinkUpdate> for PaymentLinkUpdateInternal {
+    fn from(payment_link_update: PaymentLinkUpdate) -> Self {
+        match payment_link_update {
+            PaymentLinkUpdate::StatusUpdate { status } => Self {
+                status: Some(status),
+                modified_at: common_utils::date_time::now(),
+            },
+        }
+    }
+}
@@ -0,0 +1,27 @@
+use common_utils::pii;
+use diesel::{Identifiable, Insertable, Queryable};
+
+use crate::schema::payment_metadata_audit;
+
+#[derive(Clone, Debug, Insertable, router_derive::DebugAsDisplay)]
+#[diesel(table_name = payment_metadata_audit)]
+pub struct PaymentMetadataAuditNew {
+    pub payment_id: String,
+    pub merchant_id: String,
+    pub previous_metadata: Option<pii::SecretSerdeValue>,
+    pub updated_metadata: pii::SecretSerdeValue,
+    pub created_at: time::PrimitiveDateTime,
+}
+
+#[derive(Clone, Debug, Identifiable, Queryable)]
+#[diesel(table_name = payment_metadata_audit)]
+pub struct PaymentMetadataAudit {
+    pub id: i32,
+    pub payment_id: String,
+    pub merchant_id: String,
+    /// The metadata of the payment before it was updated
+    pub previous_metadata: Option<pii::SecretSerdeValue>,
+    /// The metadata of the payment after it was updated
+    pub updated_metadata: pii::SecretSerdeValue,
+    pub created_at: time::PrimitiveDateTime,
+}
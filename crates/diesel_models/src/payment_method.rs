@@ -4,9 +4,9 @@ use masking::Secret;
 use serde::{Deserialize, Serialize};
 use time::PrimitiveDateTime;
 
-use crate::{enums as storage_enums, schema::payment_methods};
+use crate::{encryption::Encryption, enums as storage_enums, schema::payment_methods};
 
-#[derive(Clone, Debug, Eq, PartialEq, Identifiable, Queryable)]
+#[derive(Clone, Debug, Identifiable, Queryable)]
 #[diesel(table_name = payment_methods)]
 pub struct PaymentMethod {
     pub id: i32,
@@ -32,9 +32,26 @@ pub struct PaymentMethod {
     pub payment_method_issuer: Option<String>,
     pub payment_method_issuer_code: Option<storage_enums::PaymentMethodIssuerCode>,
     pub metadata: Option<pii::SecretSerdeValue>,
+    /// The expiry month of the card, encrypted with the key of the merchant
+    pub card_exp_month: Option<Encryption>,
+    /// The expiry year of the card, encrypted with the key of the merchant
+    pub card_exp_year: Option<Encryption>,
+    pub expiry_notified_at: Option<PrimitiveDateTime>,
+    /// The reference of the card in the locker, if it differs from the payment method ID because
+    /// the card was refreshed by an account updater
+    pub locker_id: Option<String>,
+    pub account_update_type: Option<storage_enums::AccountUpdateType>,
+    /// The connector whose account updater service reported the last update of the card
+    pub account_update_source: Option<String>,
+    pub account_updated_at: Option<PrimitiveDateTime>,
+    /// The reference of the network token provisioned for the card with the token service of its
+    /// card network, used in place of the card number in payments
+    pub network_token: Option<serde_json::Value>,
+    /// When the payment method was last used in a successful payment
+    pub last_used_at: Option<PrimitiveDateTime>,
 }
 
-#[derive(Clone, Debug, Eq, PartialEq, Insertable, Queryable, router_derive::DebugAsDisplay)]
+#[derive(Clone, Debug, Insertable, Queryable, router_derive::DebugAsDisplay)]
 #[diesel(table_name = payment_methods)]
 pub struct PaymentMethodNew {
     pub customer_id: String,
@@ -57,6 +74,9 @@ pub struct PaymentMethodNew {
     pub created_at: PrimitiveDateTime,
     pub last_modified: PrimitiveDateTime,
     pub metadata: Option<pii::SecretSerdeValue>,
+    pub card_exp_month: Option<Encryption>,
+    pub card_exp_year: Option<Encryption>,
+    pub locker_id: Option<String>,
 }
 
 impl Default for PaymentMethodNew {
@@ -84,6 +104,9 @@ impl Default for PaymentMethodNew {
             created_at: now,
             last_modified: now,
             metadata: Option::default(),
+            card_exp_month: Option::default(),
+            card_exp_year: Option::default(),
+            locker_id: Option::default(),
         }
     }
 }
@@ -92,31 +115,122 @@ impl Default for PaymentMethodNew {
 pub struct TokenizeCoreWorkflow {
     pub lookup_key: String,
     pub pm: storage_enums::PaymentMethod,
+    /// The merchant whose payment method data is stored, absent for the tasks created before the
+    /// merchant was tracked
+    pub merchant_id: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 pub enum PaymentMethodUpdate {
-    MetadataUpdate { metadata: Option<serde_json::Value> },
+    MetadataUpdate {
+        metadata: Option<serde_json::Value>,
+    },
+    ExpiryNotifiedUpdate {
+        expiry_notified_at: PrimitiveDateTime,
+    },
+    AccountUpdate {
+        locker_id: Option<String>,
+        card_exp_month: Option<Encryption>,
+        card_exp_year: Option<Encryption>,
+        account_update_type: storage_enums::AccountUpdateType,
+        account_update_source: String,
+        account_updated_at: PrimitiveDateTime,
+    },
+    CardExpiryUpdate {
+        card_exp_month: Encryption,
+        card_exp_year: Encryption,
+    },
+    NetworkTokenUpdate {
+        network_token: Option<serde_json::Value>,
+    },
+    LastUsedUpdate {
+        last_used_at: PrimitiveDateTime,
+    },
 }
 
 #[derive(Clone, Debug, Default, AsChangeset, router_derive::DebugAsDisplay)]
 #[diesel(table_name = payment_methods)]
 pub struct PaymentMethodUpdateInternal {
     metadata: Option<serde_json::Value>,
+    card_exp_month: Option<Encryption>,
+    card_exp_year: Option<Encryption>,
+    expiry_notified_at: Option<Option<PrimitiveDateTime>>,
+    locker_id: Option<String>,
+    account_update_type: Option<storage_enums::AccountUpdateType>,
+    account_update_source: Option<String>,
+    account_updated_at: Option<PrimitiveDateTime>,
+    network_token: Option<Option<serde_json::Value>>,
+    last_used_at: Option<PrimitiveDateTime>,
+    last_modified: Option<PrimitiveDateTime>,
 }
 
 impl PaymentMethodUpdateInternal {
     pub fn create_payment_method(self, source: PaymentMethod) -> PaymentMethod {
-        let metadata = self.metadata.map(Secret::new);
-
-        PaymentMethod { metadata, ..source }
+        PaymentMethod {
+            metadata: self.metadata.map(Secret::new).or(source.metadata),
+            card_exp_month: self.card_exp_month.or(source.card_exp_month),
+            card_exp_year: self.card_exp_year.or(source.card_exp_year),
+            expiry_notified_at: self.expiry_notified_at.unwrap_or(source.expiry_notified_at),
+            locker_id: self.locker_id.or(source.locker_id),
+            account_update_type: self.account_update_type.or(source.account_update_type),
+            account_update_source: self.account_update_source.or(source.account_update_source),
+            account_updated_at: self.account_updated_at.or(source.account_updated_at),
+            network_token: self.network_token.unwrap_or(source.network_token),
+            last_used_at: self.last_used_at.or(source.last_used_at),
+            last_modified: self.last_modified.unwrap_or(source.last_modified),
+            ..source
+        }
     }
 }
 
 impl From<PaymentMethodUpdate> for PaymentMethodUpdateInternal {
     fn from(payment_method_update: PaymentMethodUpdate) -> Self {
         match payment_method_update {
-            PaymentMethodUpdate::MetadataUpdate { metadata } => Self { metadata },
+            PaymentMethodUpdate::MetadataUpdate { metadata } => Self {
+                metadata,
+                ..Default::default()
+            },
+            PaymentMethodUpdate::ExpiryNotifiedUpdate { expiry_notified_at } => Self {
+                expiry_notified_at: Some(Some(expiry_notified_at)),
+                last_modified: Some(common_utils::date_time::now()),
+                ..Default::default()
+            },
+            PaymentMethodUpdate::AccountUpdate {
+                locker_id,
+                card_exp_month,
+                card_exp_year,
+                account_update_type,
+                account_update_source,
+                account_updated_at,
+            } => Self {
+                locker_id,
+                card_exp_month,
+                card_exp_year,
+                // A refreshed card has not been notified as expiring yet
+                expiry_notified_at: Some(None),
+                account_update_type: Some(account_update_type),
+                account_update_source: Some(account_update_source),
+                account_updated_at: Some(account_updated_at),
+                last_modified: Some(account_updated_at),
+                ..Default::default()
+            },
+            PaymentMethodUpdate::CardExpiryUpdate {
+                card_exp_month,
+                card_exp_year,
+            } => Self {
+                card_exp_month: Some(card_exp_month),
+                card_exp_year: Some(card_exp_year),
+                ..Default::default()
+            },
+            PaymentMethodUpdate::NetworkTokenUpdate { network_token } => Self {
+                network_token: Some(network_token),
+                last_modified: Some(common_utils::date_time::now()),
+                ..Default::default()
+            },
+            PaymentMethodUpdate::LastUsedUpdate { last_used_at } => Self {
+                last_used_at: Some(last_used_at),
+                ..Default::default()
+            },
         }
     }
 }
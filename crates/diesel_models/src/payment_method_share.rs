@@ -0,0 +1,93 @@
+use common_utils::custom_serde;
+use diesel::{AsChangeset, Identifiable, Insertable, Queryable};
+use serde::{Deserialize, Serialize};
+
+use crate::{enums as storage_enums, schema::payment_method_share};
+
+#[derive(Clone, Debug, Deserialize, Insertable, Serialize, router_derive::DebugAsDisplay)]
+#[diesel(table_name = payment_method_share)]
+pub struct PaymentMethodShareNew {
+    pub share_id: String,
+    pub payment_method_id: String,
+    pub merchant_id: String,
+    pub shared_with_merchant_id: String,
+    pub platform_merchant_id: String,
+    pub status: storage_enums::PaymentMethodShareStatus,
+    pub consent_reference: String,
+    pub consented_at: time::PrimitiveDateTime,
+    pub consent_ip_address: Option<String>,
+    pub max_uses: Option<i32>,
+    pub use_count: i32,
+    pub max_amount: Option<i64>,
+    pub currency: Option<storage_enums::Currency>,
+    pub expires_at: Option<time::PrimitiveDateTime>,
+    pub created_at: time::PrimitiveDateTime,
+    pub modified_at: time::PrimitiveDateTime,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, Identifiable, Queryable)]
+#[diesel(table_name = payment_method_share, primary_key(share_id))]
+pub struct PaymentMethodShare {
+    #[serde(skip_serializing)]
+    pub id: i32,
+    pub share_id: String,
+    pub payment_method_id: String,
+    /// The merchant owning the payment method
+    pub merchant_id: String,
+    /// The sub-merchant with which the payment method is shared
+    pub shared_with_merchant_id: String,
+    /// The platform under which both the merchants are sub-merchants
+    pub platform_merchant_id: String,
+    pub status: storage_enums::PaymentMethodShareStatus,
+    /// The reference of the consent given by the customer to share the payment method
+    pub consent_reference: String,
+    #[serde(with = "custom_serde::iso8601")]
+    pub consented_at: time::PrimitiveDateTime,
+    pub consent_ip_address: Option<String>,
+    pub max_uses: Option<i32>,
+    pub use_count: i32,
+    pub max_amount: Option<i64>,
+    pub currency: Option<storage_enums::Currency>,
+    #[serde(default, with = "custom_serde::iso8601::option")]
+    pub expires_at: Option<time::PrimitiveDateTime>,
+    #[serde(default, with = "custom_serde::iso8601::option")]
+    pub revoked_at: Option<time::PrimitiveDateTime>,
+    #[serde(with = "custom_serde::iso8601")]
+    pub created_at: time::PrimitiveDateTime,
+    #[serde(with = "custom_serde::iso8601")]
+    pub modified_at: time::PrimitiveDateTime,
+}
+
+#[derive(Debug)]
+pub enum PaymentMethodShareUpdate {
+    UsageUpdate { use_count: i32 },
+    Revoke { revoked_at: time::PrimitiveDateTime },
+}
+
+#[derive(Clone, Debug, AsChangeset, router_derive::DebugAsDisplay)]
+#[diesel(table_name = payment_method_share)]
+pub struct PaymentMethodShareUpdateInternal {
+    status: Option<storage_enums::PaymentMethodShareStatus>,
+    use_count: Option<i32>,
+    revoked_at: Option<time::PrimitiveDateTime>,
+    modified_at: time::PrimitiveDateTime,
+}
+
+impl From<PaymentMethodShareUpdate> for PaymentMethodShareUpdateInternal {
+    fn from(payment_method_share_update: PaymentMethodShareUpdate) -> Self {
+        match payment_method_share_update {
+            PaymentMethodShareUpdate::UsageUpdate { use_count } => Self {
+                status: None,
+                use_count: Some(use_count),
+                revoked_at: None,
+                modified_at: common_utils::date_time::now(),
+            },
+            PaymentMethodShareUpdate::Revoke { revoked_at } => Self {
+                status: Some(storage_enums::PaymentMethodShareStatus::Revoked),
+                use_count: None,
+                revoked_at: Some(revoked_at),
+                modified_at: common_utils::date_time::now(),
+            },
+        }
+    }
+}
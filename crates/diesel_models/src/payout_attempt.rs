@@ -26,6 +26,8 @@ pub struct PayoutAttempt {
     pub created_at: PrimitiveDateTime,
     #[serde(with = "common_utils::custom_serde::iso8601")]
     pub last_modified_at: PrimitiveDateTime,
+    pub connector_fee: Option<i64>,
+    pub fx_margin: Option<i64>,
 }
 
 impl Default for PayoutAttempt {
@@ -49,6 +51,8 @@ impl Default for PayoutAttempt {
             business_label: None,
             created_at: now,
             last_modified_at: now,
+            connector_fee: None,
+            fx_margin: None,
         }
     }
 }
@@ -95,6 +99,8 @@ pub enum PayoutAttemptUpdate {
         error_message: Option<String>,
         error_code: Option<String>,
         is_eligible: Option<bool>,
+        connector_fee: Option<i64>,
+        fx_margin: Option<i64>,
         last_modified_at: Option<PrimitiveDateTime>,
     },
     PayoutTokenUpdate {
@@ -120,6 +126,8 @@ pub struct PayoutAttemptUpdateInternal {
     pub business_country: Option<storage_enums::CountryAlpha2>,
     pub business_label: Option<String>,
     pub last_modified_at: Option<PrimitiveDateTime>,
+    pub connector_fee: Option<i64>,
+    pub fx_margin: Option<i64>,
 }
 
 impl From<PayoutAttemptUpdate> for PayoutAttemptUpdateInternal {
@@ -139,6 +147,8 @@ impl From<PayoutAttemptUpdate> for PayoutAttemptUpdateInternal {
                 error_message,
                 error_code,
                 is_eligible,
+                connector_fee,
+                fx_margin,
                 last_modified_at,
             } => Self {
                 connector_payout_id: Some(connector_payout_id),
@@ -146,6 +156,8 @@ impl From<PayoutAttemptUpdate> for PayoutAttemptUpdateInternal {
                 error_message,
                 error_code,
                 is_eligible,
+                connector_fee,
+                fx_margin,
                 last_modified_at,
                 ..Default::default()
             },
@@ -0,0 +1,48 @@
+use diesel::{AsChangeset, Identifiable, Insertable, Queryable};
+
+use crate::schema::payout_batch;
+
+#[derive(Clone, Debug, Insertable, router_derive::DebugAsDisplay)]
+#[diesel(table_name = payout_batch)]
+pub struct PayoutBatchNew {
+    pub batch_id: String,
+    pub merchant_id: String,
+    pub payout_ids: Vec<String>,
+    pub created_at: time::PrimitiveDateTime,
+    pub idempotency_key: Option<String>,
+}
+
+#[derive(Clone, Debug, Identifiable, Queryable)]
+#[diesel(table_name = payout_batch, primary_key(batch_id, merchant_id))]
+pub struct PayoutBatch {
+    pub id: i32,
+    pub batch_id: String,
+    pub merchant_id: String,
+    /// The payouts of the batch, in the order in which they were requested
+    #[diesel(deserialize_as = super::DieselArray<String>)]
+    pub payout_ids: Vec<String>,
+    pub created_at: time::PrimitiveDateTime,
+    /// The key with which the merchant identified the request creating the batch, if any
+    pub idempotency_key: Option<String>,
+    /// The errors of the payouts of the batch which could not be created
+    pub errors: Option<serde_json::Value>,
+}
+
+#[derive(Debug)]
+pub enum PayoutBatchUpdate {
+    ErrorsUpdate { errors: Option<serde_json::Value> },
+}
+
+#[derive(Clone, Debug, Default, AsChangeset, router_derive::DebugAsDisplay)]
+#[diesel(table_name = payout_batch)]
+pub struct PayoutBatchUpdateInternal {
+    errors: Option<serde_json::Value>,
+}
+
+impl From<PayoutBatchUpdate> for PayoutBatchUpdateInternal {
+    fn from(payout_batch_update: PayoutBatchUpdate) -> Self {
+        match payout_batch_update {
+            PayoutBatchUpdate::ErrorsUpdate { errors } => Self { errors },
+        }
+    }
+}
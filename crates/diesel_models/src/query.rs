@@ -1,15 +1,21 @@
 pub mod address;
 pub mod api_keys;
+pub mod audit_event;
 mod capture;
 pub mod cards_info;
+pub mod commission;
 pub mod configs;
 pub mod connector_response;
+pub mod customer_import_job;
 pub mod customers;
 pub mod dispute;
 pub mod events;
 pub mod file;
 pub mod fraud_check;
 pub mod generics;
+pub mod gift_card;
+pub mod invoice;
+pub mod ledger_entry;
 pub mod locker_mock_up;
 pub mod mandate;
 pub mod merchant_account;
@@ -17,9 +23,19 @@ pub mod merchant_connector_account;
 pub mod merchant_key_store;
 pub mod payment_attempt;
 pub mod payment_intent;
+pub mod payment_link;
+pub mod payment_metadata_audit;
 pub mod payment_method;
+pub mod payment_method_share;
 pub mod payout_attempt;
+pub mod payout_batch;
 pub mod payouts;
 pub mod process_tracker;
+pub mod queued_webhook;
 pub mod refund;
+pub mod report_schedule;
 pub mod reverse_lookup;
+pub mod subscription;
+pub mod terminal;
+pub mod webhook_delivery_attempt;
+pub mod webhook_endpoint_health;
@@ -0,0 +1,43 @@
+use diesel::{associations::HasTable, BoolExpressionMethods, ExpressionMethods};
+use router_env::{instrument, tracing};
+
+use super::generics;
+use crate::{
+    commission::{Commission, CommissionNew},
+    schema::commission::dsl,
+    PgPooledConn, StorageResult,
+};
+
+impl CommissionNew {
+    #[instrument(skip(conn))]
+    pub async fn insert(self, conn: &PgPooledConn) -> StorageResult<Commission> {
+        generics::generic_insert(conn, self).await
+    }
+}
+
+impl Commission {
+    /// Lists the commissions of the merchant created in the time range in the order in which they
+    /// were recorded, starting after the commission with the given ID
+    #[instrument(skip(conn))]
+    pub async fn filter_by_merchant_id_created_at_range_after_id(
+        conn: &PgPooledConn,
+        merchant_id: &str,
+        start_time: time::PrimitiveDateTime,
+        end_time: time::PrimitiveDateTime,
+        after_id: i32,
+        limit: i64,
+    ) -> StorageResult<Vec<Self>> {
+        generics::generic_filter::<<Self as HasTable>::Table, _, _, _>(
+            conn,
+            dsl::merchant_id
+                .eq(merchant_id.to_owned())
+                .and(dsl::created_at.ge(start_time))
+                .and(dsl::created_at.le(end_time))
+                .and(dsl::id.gt(after_id)),
+            Some(limit),
+            None,
+            Some(dsl::id.asc()),
+        )
+        .await
+    }
+}
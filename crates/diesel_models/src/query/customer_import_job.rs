@@ -0,0 +1,104 @@
+use diesel::{associations::HasTable, BoolExpressionMethods, ExpressionMethods};
+use router_env::{instrument, tracing};
+
+use super::generics;
+use crate::{
+    customer_import_job::{
+        CustomerImportBatch, CustomerImportJob, CustomerImportJobNew, CustomerImportJobUpdate,
+        CustomerImportJobUpdateInternal,
+    },
+    errors,
+    schema::customer_import_job::dsl,
+    PgPooledConn, StorageResult,
+};
+
+impl CustomerImportJobNew {
+    #[instrument(skip(conn))]
+    pub async fn insert(self, conn: &PgPooledConn) -> StorageResult<CustomerImportJob> {
+        generics::generic_insert(conn, self).await
+    }
+}
+
+impl CustomerImportJob {
+    #[instrument(skip(conn))]
+    pub async fn find_by_merchant_id_job_id(
+        conn: &PgPooledConn,
+        merchant_id: &str,
+        job_id: &str,
+    ) -> StorageResult<Self> {
+        generics::generic_find_one::<<Self as HasTable>::Table, _, _>(
+            conn,
+            dsl::merchant_id
+                .eq(merchant_id.to_owned())
+                .and(dsl::job_id.eq(job_id.to_owned())),
+        )
+        .await
+    }
+
+    #[instrument(skip(conn))]
+    pub async fn update_by_merchant_id_job_id(
+        conn: &PgPooledConn,
+        merchant_id: &str,
+        job_id: &str,
+        customer_import_job_update: CustomerImportJobUpdate,
+    ) -> StorageResult<Self> {
+        match generics::generic_update_with_unique_predicate_get_result::<
+            <Self as HasTable>::Table,
+            _,
+            _,
+            _,
+        >(
+            conn,
+            dsl::merchant_id
+                .eq(merchant_id.to_owned())
+                .and(dsl::job_id.eq(job_id.to_owned())),
+            CustomerImportJobUpdateInternal::from(customer_import_job_update),
+        )
+        .await
+        {
+            Err(error) => {
+                match error.current_context() {
+                    errors::DatabaseError::NotFound => Err(error
+                        .attach_printable("Customer import job with the given ID does not exist")),
+                    _ => Err(error),
+                }
+            }
+            result => result,
+        }
+    }
+
+    /// Creates the customers of a batch of the import and saves the progress of the import in a
+    /// single transaction, so that an interrupted import is resumed right after the last batch
+    /// whose customers were created.
+    #[instrument(skip_all)]
+    pub async fn insert_batch_and_update_by_merchant_id_job_id(
+        conn: &PgPooledConn,
+        merchant_id: String,
+        job_id: String,
+        batch: CustomerImportBatch,
+        customer_import_job_update: CustomerImportJobUpdate,
+    ) -> StorageResult<Self> {
+        generics::generic_transaction(conn, |conn| async move {
+            for address in batch.addresses {
+                address.insert(&conn).await?;
+            }
+            for customer in batch.customers {
+                customer.insert(&conn).await?;
+            }
+            for payment_method in batch.payment_methods {
+                payment_method.insert(&conn).await?;
+            }
+            for process in batch.processes {
+                process.insert_process(&conn).await?;
+            }
+            Self::update_by_merchant_id_job_id(
+                &conn,
+                &merchant_id,
+                &job_id,
+                customer_import_job_update,
+            )
+            .await
+        })
+        .await
+    }
+}
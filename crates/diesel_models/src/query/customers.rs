@@ -85,4 +85,22 @@ impl Customer {
         )
         .await
     }
+
+    /// Lists the customers with an email in the order in which they were created, starting after
+    /// the customer with the given ID
+    #[instrument(skip(conn))]
+    pub async fn find_with_email_after_id(
+        conn: &PgPooledConn,
+        last_id: i32,
+        limit: i64,
+    ) -> StorageResult<Vec<Self>> {
+        generics::generic_filter::<<Self as HasTable>::Table, _, _, _>(
+            conn,
+            dsl::id.gt(last_id).and(dsl::email.is_not_null()),
+            Some(limit),
+            None,
+            Some(dsl::id.asc()),
+        )
+        .await
+    }
 }
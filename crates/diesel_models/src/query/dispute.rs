@@ -70,6 +70,29 @@ impl Dispute {
         .await
     }
 
+    #[instrument(skip(conn))]
+    pub async fn filter_by_merchant_id_connector_dispute_id(
+        conn: &PgPooledConn,
+        merchant_id: &str,
+        connector_dispute_id: &str,
+    ) -> StorageResult<Vec<Self>> {
+        generics::generic_filter::<
+            <Self as HasTable>::Table,
+            _,
+            <<Self as HasTable>::Table as Table>::PrimaryKey,
+            _,
+        >(
+            conn,
+            dsl::merchant_id
+                .eq(merchant_id.to_owned())
+                .and(dsl::connector_dispute_id.eq(connector_dispute_id.to_owned())),
+            None,
+            None,
+            None,
+        )
+        .await
+    }
+
     #[instrument(skip(conn))]
     pub async fn update(self, conn: &PgPooledConn, dispute: DisputeUpdate) -> StorageResult<Self> {
         match generics::generic_update_with_unique_predicate_get_result::<
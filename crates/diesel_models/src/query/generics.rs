@@ -1,6 +1,6 @@
-use std::fmt::Debug;
+use std::{fmt::Debug, future::Future};
 
-use async_bb8_diesel::{AsyncRunQueryDsl, ConnectionError};
+use async_bb8_diesel::{AsyncConnection, AsyncRunQueryDsl, ConnectionError};
 use diesel::{
     associations::HasTable,
     debug_query,
@@ -53,6 +53,40 @@ where
     .attach_printable_lazy(|| format!("Error while inserting {debug_values}"))
 }
 
+/// The error which rolled back a transaction
+#[derive(Debug)]
+enum TransactionError {
+    Connection(DieselError),
+    Query(error_stack::Report<errors::DatabaseError>),
+}
+
+impl From<DieselError> for TransactionError {
+    fn from(error: DieselError) -> Self {
+        Self::Connection(error)
+    }
+}
+
+/// Runs the queries of `queries` in a single transaction, which is rolled back if any of the
+/// queries fails.
+#[instrument(level = "DEBUG", skip_all)]
+pub async fn generic_transaction<F, Fut, R>(conn: &PgPooledConn, queries: F) -> StorageResult<R>
+where
+    F: FnOnce(PgPooledConn) -> Fut + Send,
+    Fut: Future<Output = StorageResult<R>> + Send,
+    R: Send + 'static,
+{
+    conn.transaction_async(
+        |conn| async move { queries(conn).await.map_err(TransactionError::Query) },
+    )
+    .await
+    .map_err(|error| match error {
+        TransactionError::Query(error) => error,
+        TransactionError::Connection(error) => report!(error)
+            .change_context(errors::DatabaseError::Others)
+            .attach_printable("Error while running the transaction"),
+    })
+}
+
 #[instrument(level = "DEBUG", skip_all)]
 pub async fn generic_update<T, V, P>(
     conn: &PgPooledConn,
@@ -0,0 +1,163 @@
+use diesel::{associations::HasTable, BoolExpressionMethods, ExpressionMethods};
+use router_env::{instrument, tracing};
+
+use super::generics;
+use crate::{
+    enums as storage_enums, errors,
+    gift_card::{
+        GiftCard, GiftCardNew, GiftCardTransaction, GiftCardTransactionNew, GiftCardUpdate,
+        GiftCardUpdateInternal,
+    },
+    schema::{gift_card::dsl, gift_card_transaction::dsl as transaction_dsl},
+    PgPooledConn, StorageResult,
+};
+
+impl GiftCardNew {
+    #[instrument(skip(conn))]
+    pub async fn insert(self, conn: &PgPooledConn) -> StorageResult<GiftCard> {
+        generics::generic_insert(conn, self).await
+    }
+
+    /// Inserts the gift card along with the transaction issuing its initial balance, in a single
+    /// database transaction.
+    #[instrument(skip(conn))]
+    pub async fn insert_with_transaction(
+        self,
+        conn: &PgPooledConn,
+        gift_card_transaction: GiftCardTransactionNew,
+    ) -> StorageResult<(GiftCard, GiftCardTransaction)> {
+        generics::generic_transaction(conn, |conn| async move {
+            let gift_card = self.insert(&conn).await?;
+            let gift_card_transaction = gift_card_transaction.insert(&conn).await?;
+            Ok((gift_card, gift_card_transaction))
+        })
+        .await
+    }
+}
+
+impl GiftCard {
+    #[instrument(skip(conn))]
+    pub async fn find_by_merchant_id_gift_card_id(
+        conn: &PgPooledConn,
+        merchant_id: &str,
+        gift_card_id: &str,
+    ) -> StorageResult<Self> {
+        generics::generic_find_one::<<Self as HasTable>::Table, _, _>(
+            conn,
+            dsl::merchant_id
+                .eq(merchant_id.to_owned())
+                .and(dsl::gift_card_id.eq(gift_card_id.to_owned())),
+        )
+        .await
+    }
+
+    /// Updates an active gift card, provided that its balance is still `current_balance`, so that
+    /// concurrent updates of the balance of the gift card do not overwrite each other.
+    #[instrument(skip(conn))]
+    pub async fn update_active_by_merchant_id_gift_card_id_balance(
+        conn: &PgPooledConn,
+        merchant_id: &str,
+        gift_card_id: &str,
+        current_balance: i64,
+        gift_card_update: GiftCardUpdate,
+    ) -> StorageResult<Self> {
+        match generics::generic_update_with_unique_predicate_get_result::<
+            <Self as HasTable>::Table,
+            _,
+            _,
+            _,
+        >(
+            conn,
+            dsl::merchant_id
+                .eq(merchant_id.to_owned())
+                .and(dsl::gift_card_id.eq(gift_card_id.to_owned()))
+                .and(dsl::status.eq(storage_enums::GiftCardStatus::Active))
+                .and(dsl::balance.eq(current_balance)),
+            GiftCardUpdateInternal::from(gift_card_update),
+        )
+        .await
+        {
+            Err(error) => match error.current_context() {
+                errors::DatabaseError::NotFound => Err(error.attach_printable(
+                    "Active gift card with the given ID and balance does not exist",
+                )),
+                _ => Err(error),
+            },
+            result => result,
+        }
+    }
+
+    /// Updates the balance of an active gift card as in
+    /// [`Self::update_active_by_merchant_id_gift_card_id_balance`], and records the change in the
+    /// ledger of the gift card in the same database transaction, so that the balance of the gift
+    /// card and its ledger never diverge.
+    #[instrument(skip(conn))]
+    pub async fn update_balance_with_transaction(
+        conn: &PgPooledConn,
+        merchant_id: String,
+        gift_card_id: String,
+        current_balance: i64,
+        gift_card_update: GiftCardUpdate,
+        gift_card_transaction: GiftCardTransactionNew,
+    ) -> StorageResult<(Self, GiftCardTransaction)> {
+        generics::generic_transaction(conn, |conn| async move {
+            let gift_card = Self::update_active_by_merchant_id_gift_card_id_balance(
+                &conn,
+                &merchant_id,
+                &gift_card_id,
+                current_balance,
+                gift_card_update,
+            )
+            .await?;
+            let gift_card_transaction = gift_card_transaction.insert(&conn).await?;
+            Ok((gift_card, gift_card_transaction))
+        })
+        .await
+    }
+}
+
+impl GiftCardTransactionNew {
+    #[instrument(skip(conn))]
+    pub async fn insert(self, conn: &PgPooledConn) -> StorageResult<GiftCardTransaction> {
+        generics::generic_insert(conn, self).await
+    }
+}
+
+impl GiftCardTransaction {
+    #[instrument(skip(conn))]
+    pub async fn find_by_merchant_id_gift_card_id_idempotency_key(
+        conn: &PgPooledConn,
+        merchant_id: &str,
+        gift_card_id: &str,
+        idempotency_key: &str,
+    ) -> StorageResult<Self> {
+        generics::generic_find_one::<<Self as HasTable>::Table, _, _>(
+            conn,
+            transaction_dsl::merchant_id
+                .eq(merchant_id.to_owned())
+                .and(transaction_dsl::gift_card_id.eq(gift_card_id.to_owned()))
+                .and(transaction_dsl::idempotency_key.eq(idempotency_key.to_owned())),
+        )
+        .await
+    }
+
+    #[instrument(skip(conn))]
+    pub async fn find_by_merchant_id_gift_card_id(
+        conn: &PgPooledConn,
+        merchant_id: &str,
+        gift_card_id: &str,
+        limit: Option<i64>,
+        offset: Option<i64>,
+    ) -> StorageResult<Vec<Self>> {
+        generics::generic_filter::<<Self as HasTable>::Table, _, _, _>(
+            conn,
+            transaction_dsl::merchant_id
+                .eq(merchant_id.to_owned())
+                .and(transaction_dsl::gift_card_id.eq(gift_card_id.to_owned())),
+            limit,
+            offset,
+            Some(transaction_dsl::created_at.desc()),
+        )
+        .await
+    }
+}
@@ -0,0 +1,157 @@
+use diesel::{associations::HasTable, BoolExpressionMethods, ExpressionMethods};
+use router_env::{instrument, tracing};
+
+use super::generics;
+use crate::{
+    enums, errors,
+    invoice::{Invoice, InvoiceNew, InvoiceUpdate, InvoiceUpdateInternal},
+    schema::invoice::dsl,
+    PgPooledConn, StorageResult,
+};
+
+impl InvoiceNew {
+    #[instrument(skip(conn))]
+    pub async fn insert(self, conn: &PgPooledConn) -> StorageResult<Invoice> {
+        generics::generic_insert(conn, self).await
+    }
+}
+
+impl Invoice {
+    #[instrument(skip(conn))]
+    pub async fn find_by_merchant_id_invoice_id(
+        conn: &PgPooledConn,
+        merchant_id: &str,
+        invoice_id: &str,
+    ) -> StorageResult<Self> {
+        generics::generic_find_one::<<Self as HasTable>::Table, _, _>(
+            conn,
+            dsl::merchant_id
+                .eq(merchant_id.to_owned())
+                .and(dsl::invoice_id.eq(invoice_id.to_owned())),
+        )
+        .await
+    }
+
+    #[instrument(skip(conn))]
+    pub async fn find_by_merchant_id(
+        conn: &PgPooledConn,
+        merchant_id: &str,
+        limit: Option<i64>,
+        offset: Option<i64>,
+    ) -> StorageResult<Vec<Self>> {
+        generics::generic_filter::<<Self as HasTable>::Table, _, _, _>(
+            conn,
+            dsl::merchant_id.eq(merchant_id.to_owned()),
+            limit,
+            offset,
+            Some(dsl::created_at.desc()),
+        )
+        .await
+    }
+
+    #[instrument(skip(conn))]
+    pub async fn update_by_merchant_id_invoice_id(
+        conn: &PgPooledConn,
+        merchant_id: &str,
+        invoice_id: &str,
+        invoice_update: InvoiceUpdate,
+    ) -> StorageResult<Self> {
+        match generics::generic_update_with_unique_predicate_get_result::<
+            <Self as HasTable>::Table,
+            _,
+            _,
+            _,
+        >(
+            conn,
+            dsl::merchant_id
+                .eq(merchant_id.to_owned())
+                .and(dsl::invoice_id.eq(invoice_id.to_owned())),
+            InvoiceUpdateInternal::from(invoice_update),
+        )
+        .await
+        {
+            Err(error) => match error.current_context() {
+                errors::DatabaseError::NotFound => {
+                    Err(error.attach_printable("Invoice with the given ID does not exist"))
+                }
+                _ => Err(error),
+            },
+            result => result,
+        }
+    }
+
+    /// Adds the amount of a payment to the amount paid of an invoice in a single statement, so
+    /// that concurrent payments of the invoice are all accounted for, provided that the invoice is
+    /// not overpaid by the payment.
+    #[instrument(skip(conn))]
+    pub async fn add_payment_by_merchant_id_invoice_id(
+        conn: &PgPooledConn,
+        merchant_id: &str,
+        invoice_id: &str,
+        amount: i64,
+    ) -> StorageResult<Self> {
+        match generics::generic_update_with_unique_predicate_get_result::<
+            <Self as HasTable>::Table,
+            _,
+            _,
+            _,
+        >(
+            conn,
+            dsl::merchant_id
+                .eq(merchant_id.to_owned())
+                .and(dsl::invoice_id.eq(invoice_id.to_owned()))
+                .and((dsl::amount_paid + amount).le(dsl::amount)),
+            (
+                dsl::amount_paid.eq(dsl::amount_paid + amount),
+                dsl::modified_at.eq(common_utils::date_time::now()),
+            ),
+        )
+        .await
+        {
+            Err(error) => match error.current_context() {
+                errors::DatabaseError::NotFound => Err(error.attach_printable(
+                    "Invoice with the given ID does not exist or would be overpaid by the payment",
+                )),
+                _ => Err(error),
+            },
+            result => result,
+        }
+    }
+
+    /// Updates the status of an invoice which is not void, provided that its amount paid is still
+    /// `amount_paid`, so that the status set after a payment of the invoice is not overwritten by
+    /// the status set after an earlier payment.
+    #[instrument(skip(conn))]
+    pub async fn update_status_by_merchant_id_invoice_id_amount_paid(
+        conn: &PgPooledConn,
+        merchant_id: &str,
+        invoice_id: &str,
+        amount_paid: i64,
+        status: enums::InvoiceStatus,
+    ) -> StorageResult<Self> {
+        match generics::generic_update_with_unique_predicate_get_result::<
+            <Self as HasTable>::Table,
+            _,
+            _,
+            _,
+        >(
+            conn,
+            dsl::merchant_id
+                .eq(merchant_id.to_owned())
+                .and(dsl::invoice_id.eq(invoice_id.to_owned()))
+                .and(dsl::amount_paid.eq(amount_paid))
+                .and(dsl::status.ne(enums::InvoiceStatus::Void)),
+            InvoiceUpdateInternal::from(InvoiceUpdate::StatusUpdate { status }),
+        )
+        .await
+        {
+            Err(error) => match error.current_context() {
+                errors::DatabaseError::NotFound => Err(error.attach_printable(
+                    "Invoice with the given ID and amount paid does not exist or is void",
+                )),
+                _ => Err(error),
+            },
+            result => result,
+        }
+    }
+}
@@ -0,0 +1,14 @@
+use router_env::{instrument, tracing};
+
+use super::generics;
+use crate::{
+    ledger_entry::{LedgerEntry, LedgerEntryNew},
+    PgPooledConn, StorageResult,
+};
+
+impl LedgerEntryNew {
+    #[instrument(skip(conn))]
+    pub async fn insert(self, conn: &PgPooledConn) -> StorageResult<LedgerEntry> {
+        generics::generic_insert(conn, self).await
+    }
+}
@@ -146,4 +146,24 @@ impl MerchantConnectorAccount {
             .await
         }
     }
+
+    /// Finds the merchant connector accounts which still have plaintext webhook details, ordered
+    /// by ID, after the merchant connector account with the given ID
+    #[instrument(skip(conn))]
+    pub async fn find_with_webhook_details_after_id(
+        conn: &PgPooledConn,
+        last_id: i32,
+        limit: i64,
+    ) -> StorageResult<Vec<Self>> {
+        generics::generic_filter::<<Self as HasTable>::Table, _, _, _>(
+            conn,
+            dsl::id
+                .gt(last_id)
+                .and(dsl::connector_webhook_details.is_not_null()),
+            Some(limit),
+            None,
+            Some(dsl::id.asc()),
+        )
+        .await
+    }
 }
@@ -3,7 +3,10 @@ use router_env::{instrument, tracing};
 
 use super::generics;
 use crate::{
-    merchant_key_store::{MerchantKeyStore, MerchantKeyStoreNew},
+    merchant_key_store::{
+        MerchantKeyStore, MerchantKeyStoreNew, MerchantKeyStoreUpdate,
+        MerchantKeyStoreUpdateInternal,
+    },
     schema::merchant_key_store::dsl,
     PgPooledConn, StorageResult,
 };
@@ -27,4 +30,23 @@ impl MerchantKeyStore {
         )
         .await
     }
+
+    #[instrument(skip(conn))]
+    pub async fn update_by_merchant_id(
+        conn: &PgPooledConn,
+        merchant_id: &str,
+        merchant_key_store_update: MerchantKeyStoreUpdate,
+    ) -> StorageResult<Self> {
+        generics::generic_update_with_unique_predicate_get_result::<
+            <Self as HasTable>::Table,
+            _,
+            _,
+            _,
+        >(
+            conn,
+            dsl::merchant_id.eq(merchant_id.to_owned()),
+            MerchantKeyStoreUpdateInternal::from(merchant_key_store_update),
+        )
+        .await
+    }
 }
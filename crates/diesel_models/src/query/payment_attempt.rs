@@ -273,4 +273,26 @@ impl PaymentAttempt {
 
         Ok(filters)
     }
+
+    /// Returns the payment method type and status of the latest attempts of the merchant created
+    /// after the given time, up to the given number of attempts
+    #[instrument(skip(conn))]
+    pub async fn get_payment_method_type_statuses(
+        conn: &PgPooledConn,
+        merchant_id: &str,
+        created_after: time::PrimitiveDateTime,
+        limit: i64,
+    ) -> StorageResult<Vec<(Option<enums::PaymentMethodType>, enums::AttemptStatus)>> {
+        <Self as HasTable>::table()
+            .filter(dsl::merchant_id.eq(merchant_id.to_owned()))
+            .filter(dsl::created_at.ge(created_after))
+            .order(dsl::created_at.desc())
+            .limit(limit)
+            .select((dsl::payment_method_type, dsl::status))
+            .get_results_async::<(Option<enums::PaymentMethodType>, enums::AttemptStatus)>(conn)
+            .await
+            .into_report()
+            .change_context(DatabaseError::Others)
+            .attach_printable("Error fetching the statuses of the payment attempts")
+    }
 }
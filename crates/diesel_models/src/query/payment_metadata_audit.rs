@@ -0,0 +1,60 @@
+use diesel::{associations::HasTable, BoolExpressionMethods, ExpressionMethods};
+use router_env::{instrument, tracing};
+
+use super::generics;
+use crate::{
+    payment_intent::{PaymentIntent, PaymentIntentUpdate},
+    payment_metadata_audit::{PaymentMetadataAudit, PaymentMetadataAuditNew},
+    schema::payment_metadata_audit::dsl,
+    PgPooledConn, StorageResult,
+};
+
+impl PaymentMetadataAuditNew {
+    #[instrument(skip(conn))]
+    pub async fn insert(self, conn: &PgPooledConn) -> StorageResult<PaymentMetadataAudit> {
+        generics::generic_insert(conn, self).await
+    }
+
+    /// Inserts the audit record along with the update of the payment intent it records, in a
+    /// single database transaction.
+    #[instrument(skip(conn))]
+    pub async fn insert_with_payment_intent_update(
+        self,
+        conn: &PgPooledConn,
+        payment_intent: PaymentIntent,
+        payment_intent_update: PaymentIntentUpdate,
+    ) -> StorageResult<(PaymentIntent, PaymentMetadataAudit)> {
+        generics::generic_transaction(conn, |conn| async move {
+            let payment_metadata_audit = self.insert(&conn).await?;
+            let payment_intent = payment_intent.update(&conn, payment_intent_update).await?;
+            Ok((payment_intent, payment_metadata_audit))
+        })
+        .await
+    }
+}
+
+impl PaymentMetadataAudit {
+    /// Find the changes made to the metadata of a payment, oldest first
+    #[instrument(skip(conn))]
+    pub async fn find_by_merchant_id_payment_id(
+        conn: &PgPooledConn,
+        merchant_id: &str,
+        payment_id: &str,
+    ) -> StorageResult<Vec<Self>> {
+        generics::generic_filter::<<Self as HasTable>::Table, _, _, _>(
+            conn,
+            dsl::merchant_id
+                .eq(merchant_id.to_owned())
+                .and(dsl::payment_id.eq(payment_id.to_owned())),
+            None,
+            None,
+            Some(dsl::created_at.asc()),
+        )
+        .await
+    }
+
+    #[instrument(skip(conn))]
+    pub async fn delete_by_id(conn: &PgPooledConn, id: i32) -> StorageResult<bool> {
+        generics::generic_delete::<<Self as HasTable>::Table, _>(conn, dsl::id.eq(id)).await
+    }
+}
@@ -3,7 +3,7 @@ use router_env::{instrument, tracing};
 
 use super::generics;
 use crate::{
-    errors,
+    enums as storage_enums, errors,
     payment_method::{self, PaymentMethod, PaymentMethodNew},
     schema::payment_methods::dsl,
     PgPooledConn, StorageResult,
@@ -99,6 +99,26 @@ impl PaymentMethod {
         .await
     }
 
+    /// Lists the saved cards in the order in which they were saved, starting after the card with
+    /// the given ID
+    #[instrument(skip(conn))]
+    pub async fn find_cards_after_id(
+        conn: &PgPooledConn,
+        last_id: i32,
+        limit: i64,
+    ) -> StorageResult<Vec<Self>> {
+        generics::generic_filter::<<Self as HasTable>::Table, _, _, _>(
+            conn,
+            dsl::id
+                .gt(last_id)
+                .and(dsl::payment_method.eq(storage_enums::PaymentMethod::Card)),
+            Some(limit),
+            None,
+            Some(dsl::id.asc()),
+        )
+        .await
+    }
+
     pub async fn update_with_payment_method_id(
         self,
         conn: &PgPooledConn,
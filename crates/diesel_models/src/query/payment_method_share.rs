@@ -0,0 +1,146 @@
+use diesel::{associations::HasTable, BoolExpressionMethods, ExpressionMethods};
+use router_env::{instrument, tracing};
+
+use super::generics;
+use crate::{
+    enums as storage_enums, errors,
+    payment_method_share::{
+        PaymentMethodShare, PaymentMethodShareNew, PaymentMethodShareUpdate,
+        PaymentMethodShareUpdateInternal,
+    },
+    schema::payment_method_share::dsl,
+    PgPooledConn, StorageResult,
+};
+
+impl PaymentMethodShareNew {
+    #[instrument(skip(conn))]
+    pub async fn insert(self, conn: &PgPooledConn) -> StorageResult<PaymentMethodShare> {
+        generics::generic_insert(conn, self).await
+    }
+}
+
+impl PaymentMethodShare {
+    #[instrument(skip(conn))]
+    pub async fn find_by_share_id(conn: &PgPooledConn, share_id: &str) -> StorageResult<Self> {
+        generics::generic_find_one::<<Self as HasTable>::Table, _, _>(
+            conn,
+            dsl::share_id.eq(share_id.to_owned()),
+        )
+        .await
+    }
+
+    #[instrument(skip(conn))]
+    pub async fn find_by_merchant_id_payment_method_id(
+        conn: &PgPooledConn,
+        merchant_id: &str,
+        payment_method_id: &str,
+    ) -> StorageResult<Vec<Self>> {
+        generics::generic_filter::<<Self as HasTable>::Table, _, _, _>(
+            conn,
+            dsl::merchant_id
+                .eq(merchant_id.to_owned())
+                .and(dsl::payment_method_id.eq(payment_method_id.to_owned())),
+            None,
+            None,
+            Some(dsl::created_at.desc()),
+        )
+        .await
+    }
+
+    #[instrument(skip(conn))]
+    pub async fn update_by_share_id(
+        conn: &PgPooledConn,
+        share_id: &str,
+        payment_method_share_update: PaymentMethodShareUpdate,
+    ) -> StorageResult<Self> {
+        match generics::generic_update_with_unique_predicate_get_result::<
+            <Self as HasTable>::Table,
+            _,
+            _,
+            _,
+        >(
+            conn,
+            dsl::share_id.eq(share_id.to_owned()),
+            PaymentMethodShareUpdateInternal::from(payment_method_share_update),
+        )
+        .await
+        {
+            Err(error) => {
+                match error.current_context() {
+                    errors::DatabaseError::NotFound => Err(error
+                        .attach_printable("Payment method share with the given ID does not exist")),
+                    _ => Err(error),
+                }
+            }
+            result => result,
+        }
+    }
+
+    /// Updates an active share, provided that it has still been used `current_use_count` times, so
+    /// that concurrent uses of the share are all counted against its maximum number of uses.
+    #[instrument(skip(conn))]
+    pub async fn update_active_by_share_id_use_count(
+        conn: &PgPooledConn,
+        share_id: &str,
+        current_use_count: i32,
+        payment_method_share_update: PaymentMethodShareUpdate,
+    ) -> StorageResult<Self> {
+        match generics::generic_update_with_unique_predicate_get_result::<
+            <Self as HasTable>::Table,
+            _,
+            _,
+            _,
+        >(
+            conn,
+            dsl::share_id
+                .eq(share_id.to_owned())
+                .and(dsl::status.eq(storage_enums::PaymentMethodShareStatus::Active))
+                .and(dsl::use_count.eq(current_use_count)),
+            PaymentMethodShareUpdateInternal::from(payment_method_share_update),
+        )
+        .await
+        {
+            Err(error) => match error.current_context() {
+                errors::DatabaseError::NotFound => Err(error.attach_printable(
+                    "Active payment method share with the given ID and use count does not exist",
+                )),
+                _ => Err(error),
+            },
+            result => result,
+        }
+    }
+
+    /// Removes a use from the use count of a share, for a payment which was counted against the
+    /// maximum number of uses of the share but failed.
+    #[instrument(skip(conn))]
+    pub async fn release_use_by_share_id(
+        conn: &PgPooledConn,
+        share_id: &str,
+    ) -> StorageResult<Self> {
+        match generics::generic_update_with_unique_predicate_get_result::<
+            <Self as HasTable>::Table,
+            _,
+            _,
+            _,
+        >(
+            conn,
+            dsl::share_id
+                .eq(share_id.to_owned())
+                .and(dsl::use_count.gt(0)),
+            (
+                dsl::use_count.eq(dsl::use_count - 1),
+                dsl::modified_at.eq(common_utils::date_time::now()),
+            ),
+        )
+        .await
+        {
+            Err(error) => match error.current_context() {
+                errors::DatabaseError::NotFound => Err(error.attach_printable(
+                    "Used payment method share with the given ID does not exist",
+                )),
+                _ => Err(error),
+            },
+            result => result,
+        }
+    }
+}
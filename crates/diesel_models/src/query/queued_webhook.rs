@@ -0,0 +1,40 @@
+use diesel::{associations::HasTable, ExpressionMethods};
+use router_env::{instrument, tracing};
+
+use super::generics;
+use crate::{
+    queued_webhook::{QueuedWebhook, QueuedWebhookNew},
+    schema::queued_webhook::dsl,
+    PgPooledConn, StorageResult,
+};
+
+impl QueuedWebhookNew {
+    #[instrument(skip(conn))]
+    pub async fn insert(self, conn: &PgPooledConn) -> StorageResult<QueuedWebhook> {
+        generics::generic_insert(conn, self).await
+    }
+}
+
+impl QueuedWebhook {
+    /// Find the webhooks queued for a merchant, oldest first
+    #[instrument(skip(conn))]
+    pub async fn find_by_merchant_id(
+        conn: &PgPooledConn,
+        merchant_id: &str,
+        limit: Option<i64>,
+    ) -> StorageResult<Vec<Self>> {
+        generics::generic_filter::<<Self as HasTable>::Table, _, _, _>(
+            conn,
+            dsl::merchant_id.eq(merchant_id.to_owned()),
+            limit,
+            None,
+            Some(dsl::created_at.asc()),
+        )
+        .await
+    }
+
+    #[instrument(skip(conn))]
+    pub async fn delete_by_id(conn: &PgPooledConn, id: i32) -> StorageResult<bool> {
+        generics::generic_delete::<<Self as HasTable>::Table, _>(conn, dsl::id.eq(id)).await
+    }
+}
@@ -3,7 +3,7 @@ use router_env::{instrument, tracing};
 
 use super::generics;
 use crate::{
-    errors,
+    enums, errors,
     refund::{Refund, RefundNew, RefundUpdate, RefundUpdateInternal},
     schema::refund::dsl,
     PgPooledConn, StorageResult,
@@ -41,6 +41,41 @@ impl Refund {
         }
     }
 
+    /// Updates the refund, provided that its status is still one of `current_statuses`, so that
+    /// concurrent status transitions of the refund do not overwrite each other.
+    #[instrument(skip(conn))]
+    pub async fn update_with_status(
+        self,
+        conn: &PgPooledConn,
+        current_statuses: Vec<enums::RefundStatus>,
+        refund: RefundUpdate,
+    ) -> StorageResult<Self> {
+        match generics::generic_update_with_unique_predicate_get_result::<
+            <Self as HasTable>::Table,
+            _,
+            _,
+            _,
+        >(
+            conn,
+            dsl::refund_id
+                .eq(self.refund_id.to_owned())
+                .and(dsl::merchant_id.eq(self.merchant_id.to_owned()))
+                .and(dsl::refund_status.eq_any(current_statuses)),
+            RefundUpdateInternal::from(refund),
+        )
+        .await
+        {
+            Err(error) => {
+                match error.current_context() {
+                    errors::DatabaseError::NotFound => Err(error
+                        .attach_printable("Refund with the given ID and status does not exist")),
+                    _ => Err(error),
+                }
+            }
+            result => result,
+        }
+    }
+
     // This is required to be changed for KV.
     #[instrument(skip(conn))]
     pub async fn find_by_merchant_id_refund_id(
@@ -57,6 +92,21 @@ impl Refund {
         .await
     }
 
+    #[instrument(skip(conn))]
+    pub async fn find_by_merchant_id_merchant_refund_reference(
+        conn: &PgPooledConn,
+        merchant_id: &str,
+        merchant_refund_reference: &str,
+    ) -> StorageResult<Self> {
+        generics::generic_find_one::<<Self as HasTable>::Table, _, _>(
+            conn,
+            dsl::merchant_id
+                .eq(merchant_id.to_owned())
+                .and(dsl::merchant_refund_reference.eq(merchant_refund_reference.to_owned())),
+        )
+        .await
+    }
+
     #[instrument(skip(conn))]
     pub async fn find_by_merchant_id_connector_refund_id_connector(
         conn: &PgPooledConn,
@@ -134,4 +184,27 @@ impl Refund {
         )
         .await
     }
+
+    #[instrument(skip(conn))]
+    pub async fn filter_by_merchant_id_connector_refund_id(
+        conn: &PgPooledConn,
+        merchant_id: &str,
+        connector_refund_id: &str,
+    ) -> StorageResult<Vec<Self>> {
+        generics::generic_filter::<
+            <Self as HasTable>::Table,
+            _,
+            <<Self as HasTable>::Table as Table>::PrimaryKey,
+            _,
+        >(
+            conn,
+            dsl::merchant_id
+                .eq(merchant_id.to_owned())
+                .and(dsl::connector_refund_id.eq(connector_refund_id.to_owned())),
+            None,
+            None,
+            None,
+        )
+        .await
+    }
 }
@@ -0,0 +1,99 @@
+use diesel::{associations::HasTable, BoolExpressionMethods, ExpressionMethods};
+use router_env::{instrument, tracing};
+
+use super::generics;
+use crate::{
+    errors,
+    report_schedule::{
+        ReportSchedule, ReportScheduleNew, ReportScheduleUpdate, ReportScheduleUpdateInternal,
+    },
+    schema::report_schedule::dsl,
+    PgPooledConn, StorageResult,
+};
+
+impl ReportScheduleNew {
+    #[instrument(skip(conn))]
+    pub async fn insert(self, conn: &PgPooledConn) -> StorageResult<ReportSchedule> {
+        generics::generic_insert(conn, self).await
+    }
+}
+
+impl ReportSchedule {
+    #[instrument(skip(conn))]
+    pub async fn find_by_merchant_id_report_schedule_id(
+        conn: &PgPooledConn,
+        merchant_id: &str,
+        report_schedule_id: &str,
+    ) -> StorageResult<Self> {
+        generics::generic_find_one::<<Self as HasTable>::Table, _, _>(
+            conn,
+            dsl::merchant_id
+                .eq(merchant_id.to_owned())
+                .and(dsl::report_schedule_id.eq(report_schedule_id.to_owned())),
+        )
+        .await
+    }
+
+    #[instrument(skip(conn))]
+    pub async fn find_by_merchant_id(
+        conn: &PgPooledConn,
+        merchant_id: &str,
+        limit: Option<i64>,
+        offset: Option<i64>,
+    ) -> StorageResult<Vec<Self>> {
+        generics::generic_filter::<<Self as HasTable>::Table, _, _, _>(
+            conn,
+            dsl::merchant_id.eq(merchant_id.to_owned()),
+            limit,
+            offset,
+            Some(dsl::created_at.desc()),
+        )
+        .await
+    }
+
+    #[instrument(skip(conn))]
+    pub async fn update_by_merchant_id_report_schedule_id(
+        conn: &PgPooledConn,
+        merchant_id: &str,
+        report_schedule_id: &str,
+        report_schedule_update: ReportScheduleUpdate,
+    ) -> StorageResult<Self> {
+        match generics::generic_update_with_unique_predicate_get_result::<
+            <Self as HasTable>::Table,
+            _,
+            _,
+            _,
+        >(
+            conn,
+            dsl::merchant_id
+                .eq(merchant_id.to_owned())
+                .and(dsl::report_schedule_id.eq(report_schedule_id.to_owned())),
+            ReportScheduleUpdateInternal::from(report_schedule_update),
+        )
+        .await
+        {
+            Err(error) => match error.current_context() {
+                errors::DatabaseError::NotFound => {
+                    Err(error.attach_printable("Report schedule with the given ID does not exist"))
+                }
+                _ => Err(error),
+            },
+            result => result,
+        }
+    }
+
+    #[instrument(skip(conn))]
+    pub async fn delete_by_merchant_id_report_schedule_id(
+        conn: &PgPooledConn,
+        merchant_id: &str,
+        report_schedule_id: &str,
+    ) -> StorageResult<bool> {
+        generics::generic_delete::<<Self as HasTable>::Table, _>(
+            conn,
+            dsl::merchant_id
+                .eq(merchant_id.to_owned())
+                .and(dsl::report_schedule_id.eq(report_schedule_id.to_owned())),
+        )
+        .await
+    }
+}
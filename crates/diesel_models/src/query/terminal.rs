@@ -0,0 +1,97 @@
+use diesel::{associations::HasTable, BoolExpressionMethods, ExpressionMethods};
+use router_env::{instrument, tracing};
+
+use super::generics;
+use crate::{
+    errors,
+    schema::terminal::dsl,
+    terminal::{Terminal, TerminalNew, TerminalUpdate, TerminalUpdateInternal},
+    PgPooledConn, StorageResult,
+};
+
+impl TerminalNew {
+    #[instrument(skip(conn))]
+    pub async fn insert(self, conn: &PgPooledConn) -> StorageResult<Terminal> {
+        generics::generic_insert(conn, self).await
+    }
+}
+
+impl Terminal {
+    #[instrument(skip(conn))]
+    pub async fn find_by_merchant_id_terminal_id(
+        conn: &PgPooledConn,
+        merchant_id: &str,
+        terminal_id: &str,
+    ) -> StorageResult<Self> {
+        generics::generic_find_one::<<Self as HasTable>::Table, _, _>(
+            conn,
+            dsl::merchant_id
+                .eq(merchant_id.to_owned())
+                .and(dsl::terminal_id.eq(terminal_id.to_owned())),
+        )
+        .await
+    }
+
+    #[instrument(skip(conn))]
+    pub async fn find_by_merchant_id(
+        conn: &PgPooledConn,
+        merchant_id: &str,
+        limit: Option<i64>,
+        offset: Option<i64>,
+    ) -> StorageResult<Vec<Self>> {
+        generics::generic_filter::<<Self as HasTable>::Table, _, _, _>(
+            conn,
+            dsl::merchant_id.eq(merchant_id.to_owned()),
+            limit,
+            offset,
+            Some(dsl::created_at.asc()),
+        )
+        .await
+    }
+
+    #[instrument(skip(conn))]
+    pub async fn update_by_merchant_id_terminal_id(
+        conn: &PgPooledConn,
+        merchant_id: &str,
+        terminal_id: &str,
+        terminal_update: TerminalUpdate,
+    ) -> StorageResult<Self> {
+        match generics::generic_update_with_unique_predicate_get_result::<
+            <Self as HasTable>::Table,
+            _,
+            _,
+            _,
+        >(
+            conn,
+            dsl::merchant_id
+                .eq(merchant_id.to_owned())
+                .and(dsl::terminal_id.eq(terminal_id.to_owned())),
+            TerminalUpdateInternal::from(terminal_update),
+        )
+        .await
+        {
+            Err(error) => match error.current_context() {
+                errors::DatabaseError::NotFound => {
+                    Err(error.attach_printable("Terminal with the given ID does not exist"))
+                }
+                _ => Err(error),
+            },
+            result => result,
+        }
+    }
+
+    #[instrument(skip(conn))]
+    pub async fn delete_by_merchant_id_terminal_id(
+        conn: &PgPooledConn,
+        merchant_id: &str,
+        terminal_id: &str,
+    ) -> StorageResult<bool> {
+        generics::generic_delete::<<Self as HasTable>::Table, _>(
+            conn,
+            dsl::merchant_id
+                .eq(merchant_id.to_owned())
+                .and(dsl::terminal_id.eq(terminal_id.to_owned())),
+        )
+        .await
+    }
+}
@@ -0,0 +1,60 @@
+use diesel::{
+    associations::HasTable, BoolExpressionMethods, ExpressionMethods, PgExpressionMethods,
+};
+use router_env::{instrument, tracing};
+
+use super::generics;
+use crate::{
+    schema::webhook_delivery_attempt::dsl,
+    webhook_delivery_attempt::{WebhookDeliveryAttempt, WebhookDeliveryAttemptNew},
+    PgPooledConn, StorageResult,
+};
+
+impl WebhookDeliveryAttemptNew {
+    #[instrument(skip(conn))]
+    pub async fn insert(self, conn: &PgPooledConn) -> StorageResult<WebhookDeliveryAttempt> {
+        generics::generic_insert(conn, self).await
+    }
+}
+
+impl WebhookDeliveryAttempt {
+    /// Find the delivery attempts of an event, oldest first
+    #[instrument(skip(conn))]
+    pub async fn find_by_merchant_id_event_id(
+        conn: &PgPooledConn,
+        merchant_id: &str,
+        event_id: &str,
+    ) -> StorageResult<Vec<Self>> {
+        generics::generic_filter::<<Self as HasTable>::Table, _, _, _>(
+            conn,
+            dsl::merchant_id
+                .eq(merchant_id.to_owned())
+                .and(dsl::event_id.eq(event_id.to_owned())),
+            None,
+            None,
+            Some(dsl::attempt_number.asc()),
+        )
+        .await
+    }
+
+    /// Find an attempt to post an event to a webhook endpoint, the webhook endpoint of the
+    /// merchant when `endpoint_id` is not set
+    #[instrument(skip(conn))]
+    pub async fn find_by_merchant_id_event_id_endpoint_id_attempt_number(
+        conn: &PgPooledConn,
+        merchant_id: &str,
+        event_id: &str,
+        endpoint_id: Option<&str>,
+        attempt_number: i32,
+    ) -> StorageResult<Self> {
+        generics::generic_find_one::<<Self as HasTable>::Table, _, _>(
+            conn,
+            dsl::merchant_id
+                .eq(merchant_id.to_owned())
+                .and(dsl::event_id.eq(event_id.to_owned()))
+                .and(dsl::endpoint_id.is_not_distinct_from(endpoint_id.map(ToOwned::to_owned)))
+                .and(dsl::attempt_number.eq(attempt_number)),
+        )
+        .await
+    }
+}
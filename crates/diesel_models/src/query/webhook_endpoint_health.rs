@@ -0,0 +1,62 @@
+use diesel::{associations::HasTable, ExpressionMethods};
+use router_env::{instrument, tracing};
+
+use super::generics;
+use crate::{
+    errors,
+    schema::webhook_endpoint_health::dsl,
+    webhook_endpoint_health::{
+        WebhookEndpointHealth, WebhookEndpointHealthNew, WebhookEndpointHealthUpdate,
+        WebhookEndpointHealthUpdateInternal,
+    },
+    PgPooledConn, StorageResult,
+};
+
+impl WebhookEndpointHealthNew {
+    #[instrument(skip(conn))]
+    pub async fn insert(self, conn: &PgPooledConn) -> StorageResult<WebhookEndpointHealth> {
+        generics::generic_insert(conn, self).await
+    }
+}
+
+impl WebhookEndpointHealth {
+    #[instrument(skip(conn))]
+    pub async fn find_by_merchant_id(
+        conn: &PgPooledConn,
+        merchant_id: &str,
+    ) -> StorageResult<Self> {
+        generics::generic_find_one::<<Self as HasTable>::Table, _, _>(
+            conn,
+            dsl::merchant_id.eq(merchant_id.to_owned()),
+        )
+        .await
+    }
+
+    #[instrument(skip(conn))]
+    pub async fn update_by_merchant_id(
+        conn: &PgPooledConn,
+        merchant_id: &str,
+        webhook_endpoint_health_update: WebhookEndpointHealthUpdate,
+    ) -> StorageResult<Self> {
+        match generics::generic_update_with_unique_predicate_get_result::<
+            <Self as HasTable>::Table,
+            _,
+            _,
+            _,
+        >(
+            conn,
+            dsl::merchant_id.eq(merchant_id.to_owned()),
+            WebhookEndpointHealthUpdateInternal::from(webhook_endpoint_health_update),
+        )
+        .await
+        {
+            Err(error) => match error.current_context() {
+                errors::DatabaseError::NotFound => Err(error.attach_printable(
+                    "Webhook endpoint health of the given merchant does not exist",
+                )),
+                _ => Err(error),
+            },
+            result => result,
+        }
+    }
+}
@@ -0,0 +1,26 @@
+use diesel::{Identifiable, Insertable, Queryable};
+
+use crate::{encryption::Encryption, schema::queued_webhook};
+
+#[derive(Clone, Debug, Insertable, router_derive::DebugAsDisplay)]
+#[diesel(table_name = queued_webhook)]
+pub struct QueuedWebhookNew {
+    pub event_id: String,
+    pub merchant_id: String,
+    pub request_body: Encryption,
+    pub request_headers: serde_json::Value,
+    pub created_at: time::PrimitiveDateTime,
+}
+
+#[derive(Clone, Debug, Identifiable, Queryable)]
+#[diesel(table_name = queued_webhook)]
+pub struct QueuedWebhook {
+    pub id: i32,
+    pub event_id: String,
+    pub merchant_id: String,
+    /// The body of the webhook request, encrypted with the key of the merchant
+    pub request_body: Encryption,
+    /// The headers carrying the signature of the webhook
+    pub request_headers: serde_json::Value,
+    pub created_at: time::PrimitiveDateTime,
+}
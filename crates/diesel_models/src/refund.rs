@@ -36,6 +36,14 @@ pub struct Refund {
     pub attempt_id: String,
     pub refund_reason: Option<String>,
     pub refund_error_code: Option<String>,
+    pub payout_id: Option<String>,
+    pub merchant_refund_reference: Option<String>,
+    pub convenience_fee_amount: Option<i64>,
+    #[serde(default, with = "common_utils::custom_serde::iso8601::option")]
+    pub scheduled_at: Option<PrimitiveDateTime>,
+    /// The amount reported as refunded by the connector, if the connector reports it
+    #[serde(default)]
+    pub settled_amount: Option<i64>,
 }
 
 #[derive(
@@ -75,6 +83,11 @@ pub struct RefundNew {
     pub description: Option<String>,
     pub attempt_id: String,
     pub refund_reason: Option<String>,
+    pub payout_id: Option<String>,
+    pub merchant_refund_reference: Option<String>,
+    pub convenience_fee_amount: Option<i64>,
+    #[serde(default, with = "common_utils::custom_serde::iso8601::option")]
+    pub scheduled_at: Option<PrimitiveDateTime>,
 }
 
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
@@ -85,6 +98,7 @@ pub enum RefundUpdate {
         sent_to_gateway: bool,
         refund_error_message: Option<String>,
         refund_arn: String,
+        settled_amount: Option<i64>,
     },
     MetadataAndReasonUpdate {
         metadata: Option<pii::SecretSerdeValue>,
@@ -94,12 +108,20 @@ pub enum RefundUpdate {
         connector_refund_id: Option<String>,
         sent_to_gateway: bool,
         refund_status: storage_enums::RefundStatus,
+        settled_amount: Option<i64>,
     },
     ErrorUpdate {
         refund_status: Option<storage_enums::RefundStatus>,
         refund_error_message: Option<String>,
         refund_error_code: Option<String>,
     },
+    PayoutUpdate {
+        payout_id: String,
+        refund_status: storage_enums::RefundStatus,
+        sent_to_gateway: bool,
+        refund_error_message: Option<String>,
+        refund_error_code: Option<String>,
+    },
 }
 
 #[derive(Clone, Debug, Default, AsChangeset, router_derive::DebugAsDisplay)]
@@ -113,6 +135,8 @@ pub struct RefundUpdateInternal {
     metadata: Option<pii::SecretSerdeValue>,
     refund_reason: Option<String>,
     refund_error_code: Option<String>,
+    payout_id: Option<String>,
+    settled_amount: Option<i64>,
 }
 
 impl RefundUpdateInternal {
@@ -126,6 +150,8 @@ impl RefundUpdateInternal {
             metadata: self.metadata,
             refund_reason: self.refund_reason,
             refund_error_code: self.refund_error_code,
+            payout_id: self.payout_id,
+            settled_amount: self.settled_amount.or(source.settled_amount),
             ..source
         }
     }
@@ -140,12 +166,14 @@ impl From<RefundUpdate> for RefundUpdateInternal {
                 sent_to_gateway,
                 refund_error_message,
                 refund_arn,
+                settled_amount,
             } => Self {
                 connector_refund_id: Some(connector_refund_id),
                 refund_status: Some(refund_status),
                 sent_to_gateway: Some(sent_to_gateway),
                 refund_error_message,
                 refund_arn: Some(refund_arn),
+                settled_amount,
                 ..Default::default()
             },
             RefundUpdate::MetadataAndReasonUpdate { metadata, reason } => Self {
@@ -157,10 +185,12 @@ impl From<RefundUpdate> for RefundUpdateInternal {
                 connector_refund_id,
                 sent_to_gateway,
                 refund_status,
+                settled_amount,
             } => Self {
                 connector_refund_id,
                 sent_to_gateway: Some(sent_to_gateway),
                 refund_status: Some(refund_status),
+                settled_amount,
                 ..Default::default()
             },
             RefundUpdate::ErrorUpdate {
@@ -173,6 +203,20 @@ impl From<RefundUpdate> for RefundUpdateInternal {
                 refund_error_code,
                 ..Default::default()
             },
+            RefundUpdate::PayoutUpdate {
+                payout_id,
+                refund_status,
+                sent_to_gateway,
+                refund_error_message,
+                refund_error_code,
+            } => Self {
+                payout_id: Some(payout_id),
+                refund_status: Some(refund_status),
+                sent_to_gateway: Some(sent_to_gateway),
+                refund_error_message,
+                refund_error_code,
+                ..Default::default()
+            },
         }
     }
 }
@@ -191,6 +235,8 @@ impl RefundUpdate {
             refund_arn: pa_update.refund_arn.or(source.refund_arn),
             metadata: pa_update.metadata.or(source.metadata),
             refund_reason: pa_update.refund_reason.or(source.refund_reason),
+            payout_id: pa_update.payout_id.or(source.payout_id),
+            settled_amount: pa_update.settled_amount.or(source.settled_amount),
             ..source
         }
     }
@@ -0,0 +1,121 @@
+use common_utils::{custom_serde, pii};
+use diesel::{AsChangeset, Identifiable, Insertable, Queryable};
+use serde::{Deserialize, Serialize};
+
+use crate::{enums as storage_enums, schema::report_schedule};
+
+#[derive(Clone, Debug, Deserialize, Insertable, Serialize, router_derive::DebugAsDisplay)]
+#[diesel(table_name = report_schedule)]
+pub struct ReportScheduleNew {
+    pub report_schedule_id: String,
+    pub merchant_id: String,
+    pub report_type: storage_enums::ReportType,
+    pub frequency: storage_enums::ReportFrequency,
+    pub template: serde_json::Value,
+    pub filters: Option<serde_json::Value>,
+    pub delivery_method: storage_enums::ReportDeliveryMethod,
+    pub recipient_email: Option<pii::Email>,
+    pub is_active: bool,
+    pub next_run_at: time::PrimitiveDateTime,
+    pub created_at: time::PrimitiveDateTime,
+    pub modified_at: time::PrimitiveDateTime,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, Identifiable, Queryable)]
+#[diesel(table_name = report_schedule, primary_key(report_schedule_id, merchant_id))]
+pub struct ReportSchedule {
+    #[serde(skip_serializing)]
+    pub id: i32,
+    pub report_schedule_id: String,
+    pub merchant_id: String,
+    pub report_type: storage_enums::ReportType,
+    pub frequency: storage_enums::ReportFrequency,
+    pub template: serde_json::Value,
+    pub filters: Option<serde_json::Value>,
+    pub delivery_method: storage_enums::ReportDeliveryMethod,
+    pub recipient_email: Option<pii::Email>,
+    pub is_active: bool,
+    #[serde(with = "custom_serde::iso8601")]
+    pub next_run_at: time::PrimitiveDateTime,
+    #[serde(default, with = "custom_serde::iso8601::option")]
+    pub last_generated_at: Option<time::PrimitiveDateTime>,
+    pub last_file_id: Option<String>,
+    #[serde(with = "custom_serde::iso8601")]
+    pub created_at: time::PrimitiveDateTime,
+    #[serde(with = "custom_serde::iso8601")]
+    pub modified_at: time::PrimitiveDateTime,
+}
+
+#[derive(Debug)]
+pub enum ReportScheduleUpdate {
+    Update {
+        template: Option<serde_json::Value>,
+        filters: Option<serde_json::Value>,
+        delivery_method: Option<storage_enums::ReportDeliveryMethod>,
+        recipient_email: Option<pii::Email>,
+        is_active: Option<bool>,
+        next_run_at: Option<time::PrimitiveDateTime>,
+    },
+    GenerationUpdate {
+        last_generated_at: time::PrimitiveDateTime,
+        last_file_id: String,
+        next_run_at: time::PrimitiveDateTime,
+    },
+}
+
+#[derive(Clone, Debug, Default, AsChangeset, router_derive::DebugAsDisplay)]
+#[diesel(table_name = report_schedule)]
+pub struct ReportScheduleUpdateInternal {
+    template: Option<serde_json::Value>,
+    filters: Option<serde_json::Value>,
+    delivery_method: Option<storage_enums::ReportDeliveryMethod>,
+    recipient_email: Option<pii::Email>,
+    is_active: Option<bool>,
+    next_run_at: Option<time::PrimitiveDateTime>,
+    last_generated_at: Option<time::PrimitiveDateTime>,
+    last_file_id: Option<String>,
+    modified_at: Option<time::PrimitiveDateTime>,
+}
+
+impl From<ReportScheduleUpdate> for ReportScheduleUpdateInternal {
+    fn from(report_schedule_update: ReportScheduleUpdate) -> Self {
+        let modified_at = Some(common_utils::date_time::now());
+        match report_schedule_update {
+            ReportScheduleUpdate::Update {
+                template,
+                filters,
+                delivery_method,
+                recipient_email,
+                is_active,
+                next_run_at,
+            } => Self {
+                template,
+                filters,
+                delivery_method,
+                recipient_email,
+                is_active,
+                next_run_at,
+                modified_at,
+                ..Default::default()
+            },
+            ReportScheduleUpdate::GenerationUpdate {
+                last_generated_at,
+                last_file_id,
+                next_run_at,
+            } => Self {
+                next_run_at: Some(next_run_at),
+                last_generated_at: Some(last_generated_at),
+                last_file_id: Some(last_file_id),
+                modified_at,
+                ..Default::default()
+            },
+        }
+    }
+}
+
+// Tracking data by process_tracker
+#[derive(Default, Debug, Deserialize, Serialize, Clone)]
+pub struct ReportGenerationWorkflow {
+    pub report_schedule_id: String,
+    pub merchant_id: String,
+}
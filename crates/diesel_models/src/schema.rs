@@ -27,6 +27,7 @@ diesel::table! {
         customer_id -> Varchar,
         #[max_length = 64]
         merchant_id -> Varchar,
+        is_address_book_entry -> Bool,
     }
 }
 
@@ -53,6 +54,28 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    use diesel::sql_types::*;
+    use crate::enums::diesel_exports::*;
+
+    audit_event (event_id) {
+        id -> Int4,
+        #[max_length = 64]
+        event_id -> Varchar,
+        #[max_length = 64]
+        merchant_id -> Varchar,
+        entity_type -> AuditEntityType,
+        #[max_length = 64]
+        entity_id -> Varchar,
+        action -> AuditAction,
+        #[max_length = 64]
+        actor -> Varchar,
+        previous_values -> Nullable<Jsonb>,
+        updated_values -> Nullable<Jsonb>,
+        created_at -> Timestamp,
+    }
+}
+
 diesel::table! {
     use diesel::sql_types::*;
     use crate::enums::diesel_exports::*;
@@ -110,6 +133,29 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    use diesel::sql_types::*;
+    use crate::enums::diesel_exports::*;
+
+    commission (commission_id) {
+        id -> Int4,
+        #[max_length = 64]
+        commission_id -> Varchar,
+        #[max_length = 64]
+        merchant_id -> Varchar,
+        #[max_length = 64]
+        parent_merchant_id -> Nullable<Varchar>,
+        #[max_length = 64]
+        payment_id -> Varchar,
+        #[max_length = 64]
+        pricing_plan -> Varchar,
+        currency -> Currency,
+        payment_amount -> Int8,
+        commission_amount -> Int8,
+        created_at -> Timestamp,
+    }
+}
+
 diesel::table! {
     use diesel::sql_types::*;
     use crate::enums::diesel_exports::*;
@@ -145,6 +191,30 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    use diesel::sql_types::*;
+    use crate::enums::diesel_exports::*;
+
+    customer_import_job (job_id, merchant_id) {
+        id -> Int4,
+        #[max_length = 64]
+        job_id -> Varchar,
+        #[max_length = 64]
+        merchant_id -> Varchar,
+        #[max_length = 64]
+        status -> Varchar,
+        records -> Bytea,
+        total_count -> Int4,
+        processed_count -> Int4,
+        succeeded_count -> Int4,
+        failed_count -> Int4,
+        errors -> Nullable<Jsonb>,
+        created_at -> Timestamp,
+        modified_at -> Timestamp,
+        completed_at -> Nullable<Timestamp>,
+    }
+}
+
 diesel::table! {
     use diesel::sql_types::*;
     use crate::enums::diesel_exports::*;
@@ -166,6 +236,16 @@ diesel::table! {
         metadata -> Nullable<Json>,
         connector_customer -> Nullable<Jsonb>,
         modified_at -> Timestamp,
+        #[max_length = 64]
+        address_id -> Nullable<Varchar>,
+        #[max_length = 64]
+        default_shipping_address_id -> Nullable<Varchar>,
+        #[max_length = 64]
+        default_billing_address_id -> Nullable<Varchar>,
+        #[max_length = 64]
+        email_hash -> Nullable<Varchar>,
+        #[max_length = 64]
+        default_payment_method_id -> Nullable<Varchar>,
     }
 }
 
@@ -283,6 +363,109 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    use diesel::sql_types::*;
+    use crate::enums::diesel_exports::*;
+
+    gift_card (gift_card_id, merchant_id) {
+        id -> Int4,
+        #[max_length = 64]
+        gift_card_id -> Varchar,
+        #[max_length = 64]
+        merchant_id -> Varchar,
+        #[max_length = 64]
+        customer_id -> Nullable<Varchar>,
+        status -> GiftCardStatus,
+        currency -> Currency,
+        initial_balance -> Int8,
+        balance -> Int8,
+        expires_at -> Nullable<Timestamp>,
+        metadata -> Nullable<Jsonb>,
+        created_at -> Timestamp,
+        modified_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    use diesel::sql_types::*;
+    use crate::enums::diesel_exports::*;
+
+    gift_card_transaction (transaction_id) {
+        id -> Int4,
+        #[max_length = 64]
+        transaction_id -> Varchar,
+        #[max_length = 64]
+        gift_card_id -> Varchar,
+        #[max_length = 64]
+        merchant_id -> Varchar,
+        transaction_type -> GiftCardTransactionType,
+        amount -> Int8,
+        balance_after -> Int8,
+        currency -> Currency,
+        #[max_length = 64]
+        payment_id -> Nullable<Varchar>,
+        created_at -> Timestamp,
+        #[max_length = 128]
+        idempotency_key -> Nullable<Varchar>,
+    }
+}
+
+diesel::table! {
+    use diesel::sql_types::*;
+    use crate::enums::diesel_exports::*;
+
+    invoice (invoice_id, merchant_id) {
+        id -> Int4,
+        #[max_length = 64]
+        invoice_id -> Varchar,
+        #[max_length = 64]
+        merchant_id -> Varchar,
+        #[max_length = 64]
+        customer_id -> Nullable<Varchar>,
+        status -> InvoiceStatus,
+        currency -> Currency,
+        amount -> Int8,
+        amount_paid -> Int8,
+        line_items -> Jsonb,
+        #[max_length = 255]
+        description -> Nullable<Varchar>,
+        due_date -> Nullable<Timestamp>,
+        #[max_length = 128]
+        client_secret -> Varchar,
+        metadata -> Nullable<Jsonb>,
+        created_at -> Timestamp,
+        modified_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    use diesel::sql_types::*;
+    use crate::enums::diesel_exports::*;
+
+    ledger_entry (entry_id) {
+        id -> Int4,
+        #[max_length = 64]
+        entry_id -> Varchar,
+        #[max_length = 64]
+        merchant_id -> Varchar,
+        #[max_length = 32]
+        entry_type -> Varchar,
+        #[max_length = 64]
+        reference_id -> Varchar,
+        #[max_length = 32]
+        debit_account -> Varchar,
+        #[max_length = 32]
+        credit_account -> Varchar,
+        amount -> Int8,
+        currency -> Currency,
+        #[max_length = 64]
+        connector -> Nullable<Varchar>,
+        created_at -> Timestamp,
+        #[max_length = 128]
+        idempotency_key -> Varchar,
+    }
+}
+
 diesel::table! {
     use diesel::sql_types::*;
     use crate::enums::diesel_exports::*;
@@ -396,6 +579,13 @@ diesel::table! {
         #[max_length = 32]
         organization_id -> Nullable<Varchar>,
         is_recon_enabled -> Bool,
+        webhook_mtls_details -> Nullable<Bytea>,
+        allowed_domains -> Nullable<Array<Nullable<Text>>>,
+        connector_exclusion_rules -> Nullable<Jsonb>,
+        #[max_length = 64]
+        storage_region -> Nullable<Varchar>,
+        webhook_signing_keys -> Nullable<Bytea>,
+        business_webhook_secrets -> Nullable<Bytea>,
     }
 }
 
@@ -428,6 +618,13 @@ diesel::table! {
         created_at -> Timestamp,
         modified_at -> Timestamp,
         connector_webhook_details -> Nullable<Jsonb>,
+        test_connector_account_details -> Nullable<Bytea>,
+        client_certificate -> Nullable<Bytea>,
+        client_certificate_key -> Nullable<Bytea>,
+        client_certificate_expires_at -> Nullable<Timestamp>,
+        webhook_secret -> Nullable<Bytea>,
+        additional_webhook_secret -> Nullable<Bytea>,
+        apple_pay_payment_processing_key -> Nullable<Bytea>,
     }
 }
 
@@ -440,6 +637,8 @@ diesel::table! {
         merchant_id -> Varchar,
         key -> Bytea,
         created_at -> Timestamp,
+        key_version -> Int4,
+        rotated_at -> Nullable<Timestamp>,
     }
 }
 
@@ -502,6 +701,9 @@ diesel::table! {
         multiple_capture_count -> Nullable<Int2>,
         #[max_length = 128]
         connector_response_reference_id -> Nullable<Varchar>,
+        #[max_length = 64]
+        previous_attempt_id -> Nullable<Varchar>,
+        authorization_history -> Nullable<Jsonb>,
     }
 }
 
@@ -553,6 +755,88 @@ diesel::table! {
         connector_metadata -> Nullable<Json>,
         feature_metadata -> Nullable<Json>,
         attempt_count -> Int2,
+        #[max_length = 64]
+        invoice_id -> Nullable<Varchar>,
+        convenience_fee -> Nullable<Jsonb>,
+        livemode -> Nullable<Bool>,
+        l2_l3_data -> Nullable<Jsonb>,
+        presentment_amount -> Nullable<Int8>,
+        presentment_currency -> Nullable<Currency>,
+        settlement_amount -> Nullable<Int8>,
+        settlement_currency -> Nullable<Currency>,
+        #[max_length = 32]
+        exchange_rate -> Nullable<Varchar>,
+        cross_border_fees -> Nullable<Jsonb>,
+    }
+}
+
+diesel::table! {
+    use diesel::sql_types::*;
+    use crate::enums::diesel_exports::*;
+
+    payment_link (payment_link_id, merchant_id) {
+        id -> Int4,
+        #[max_length = 64]
+        payment_link_id -> Varchar,
+        #[max_length = 64]
+        merchant_id -> Varchar,
+        #[max_length = 64]
+        payment_id -> Varchar,
+        status -> PaymentLinkStatus,
+        theme -> Nullable<Jsonb>,
+        allowed_payment_methods -> Nullable<Jsonb>,
+        expires_at -> Timestamp,
+        created_at -> Timestamp,
+        modified_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    use diesel::sql_types::*;
+    use crate::enums::diesel_exports::*;
+
+    payment_metadata_audit (id) {
+        id -> Int4,
+        #[max_length = 64]
+        payment_id -> Varchar,
+        #[max_length = 64]
+        merchant_id -> Varchar,
+        previous_metadata -> Nullable<Jsonb>,
+        updated_metadata -> Jsonb,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    use diesel::sql_types::*;
+    use crate::enums::diesel_exports::*;
+
+    payment_method_share (share_id) {
+        id -> Int4,
+        #[max_length = 64]
+        share_id -> Varchar,
+        #[max_length = 64]
+        payment_method_id -> Varchar,
+        #[max_length = 64]
+        merchant_id -> Varchar,
+        #[max_length = 64]
+        shared_with_merchant_id -> Varchar,
+        #[max_length = 64]
+        platform_merchant_id -> Varchar,
+        status -> PaymentMethodShareStatus,
+        #[max_length = 255]
+        consent_reference -> Varchar,
+        consented_at -> Timestamp,
+        #[max_length = 64]
+        consent_ip_address -> Nullable<Varchar>,
+        max_uses -> Nullable<Int4>,
+        use_count -> Int4,
+        max_amount -> Nullable<Int8>,
+        currency -> Nullable<Currency>,
+        expires_at -> Nullable<Timestamp>,
+        revoked_at -> Nullable<Timestamp>,
+        created_at -> Timestamp,
+        modified_at -> Timestamp,
     }
 }
 
@@ -593,6 +877,18 @@ diesel::table! {
         payment_method_issuer -> Nullable<Varchar>,
         payment_method_issuer_code -> Nullable<PaymentMethodIssuerCode>,
         metadata -> Nullable<Json>,
+        card_exp_month -> Nullable<Bytea>,
+        card_exp_year -> Nullable<Bytea>,
+        expiry_notified_at -> Nullable<Timestamp>,
+        #[max_length = 64]
+        locker_id -> Nullable<Varchar>,
+        #[max_length = 64]
+        account_update_type -> Nullable<Varchar>,
+        #[max_length = 64]
+        account_update_source -> Nullable<Varchar>,
+        account_updated_at -> Nullable<Timestamp>,
+        network_token -> Nullable<Jsonb>,
+        last_used_at -> Nullable<Timestamp>,
     }
 }
 
@@ -627,6 +923,26 @@ diesel::table! {
         business_label -> Nullable<Varchar>,
         created_at -> Timestamp,
         last_modified_at -> Timestamp,
+        connector_fee -> Nullable<Int8>,
+        fx_margin -> Nullable<Int8>,
+    }
+}
+
+diesel::table! {
+    use diesel::sql_types::*;
+    use crate::enums::diesel_exports::*;
+
+    payout_batch (batch_id, merchant_id) {
+        id -> Int4,
+        #[max_length = 64]
+        batch_id -> Varchar,
+        #[max_length = 64]
+        merchant_id -> Varchar,
+        payout_ids -> Array<Nullable<Text>>,
+        created_at -> Timestamp,
+        #[max_length = 128]
+        idempotency_key -> Nullable<Varchar>,
+        errors -> Nullable<Jsonb>,
     }
 }
 
@@ -689,6 +1005,22 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    use diesel::sql_types::*;
+    use crate::enums::diesel_exports::*;
+
+    queued_webhook (id) {
+        id -> Int4,
+        #[max_length = 64]
+        event_id -> Varchar,
+        #[max_length = 64]
+        merchant_id -> Varchar,
+        request_body -> Bytea,
+        request_headers -> Jsonb,
+        created_at -> Timestamp,
+    }
+}
+
 diesel::table! {
     use diesel::sql_types::*;
     use crate::enums::diesel_exports::*;
@@ -730,6 +1062,43 @@ diesel::table! {
         #[max_length = 255]
         refund_reason -> Nullable<Varchar>,
         refund_error_code -> Nullable<Text>,
+        #[max_length = 64]
+        payout_id -> Nullable<Varchar>,
+        #[max_length = 64]
+        merchant_refund_reference -> Nullable<Varchar>,
+        convenience_fee_amount -> Nullable<Int8>,
+        scheduled_at -> Nullable<Timestamp>,
+        settled_amount -> Nullable<Int8>,
+    }
+}
+
+diesel::table! {
+    use diesel::sql_types::*;
+    use crate::enums::diesel_exports::*;
+
+    report_schedule (report_schedule_id, merchant_id) {
+        id -> Int4,
+        #[max_length = 64]
+        report_schedule_id -> Varchar,
+        #[max_length = 64]
+        merchant_id -> Varchar,
+        #[max_length = 64]
+        report_type -> Varchar,
+        #[max_length = 64]
+        frequency -> Varchar,
+        template -> Jsonb,
+        filters -> Nullable<Jsonb>,
+        #[max_length = 64]
+        delivery_method -> Varchar,
+        #[max_length = 255]
+        recipient_email -> Nullable<Varchar>,
+        is_active -> Bool,
+        next_run_at -> Timestamp,
+        last_generated_at -> Nullable<Timestamp>,
+        #[max_length = 64]
+        last_file_id -> Nullable<Varchar>,
+        created_at -> Timestamp,
+        modified_at -> Timestamp,
     }
 }
 
@@ -749,18 +1118,128 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    use diesel::sql_types::*;
+    use crate::enums::diesel_exports::*;
+
+    subscription (subscription_id, merchant_id) {
+        id -> Int4,
+        #[max_length = 64]
+        subscription_id -> Varchar,
+        #[max_length = 64]
+        merchant_id -> Varchar,
+        #[max_length = 64]
+        customer_id -> Varchar,
+        #[max_length = 64]
+        mandate_id -> Varchar,
+        #[max_length = 64]
+        status -> Varchar,
+        amount -> Int8,
+        currency -> Currency,
+        #[max_length = 64]
+        billing_interval -> Varchar,
+        billing_interval_count -> Int4,
+        #[max_length = 255]
+        description -> Nullable<Varchar>,
+        billing_anchor -> Timestamp,
+        billing_cycle -> Int4,
+        next_billing_at -> Timestamp,
+        failed_attempt_count -> Int4,
+        #[max_length = 64]
+        last_payment_id -> Nullable<Varchar>,
+        cancelled_at -> Nullable<Timestamp>,
+        created_at -> Timestamp,
+        modified_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    use diesel::sql_types::*;
+    use crate::enums::diesel_exports::*;
+
+    terminal (terminal_id, merchant_id) {
+        id -> Int4,
+        #[max_length = 64]
+        terminal_id -> Varchar,
+        #[max_length = 64]
+        merchant_id -> Varchar,
+        #[max_length = 128]
+        merchant_connector_id -> Nullable<Varchar>,
+        #[max_length = 255]
+        connector_terminal_id -> Nullable<Varchar>,
+        #[max_length = 255]
+        label -> Nullable<Varchar>,
+        #[max_length = 255]
+        location -> Nullable<Varchar>,
+        #[max_length = 255]
+        serial_number -> Nullable<Varchar>,
+        is_active -> Bool,
+        metadata -> Nullable<Jsonb>,
+        created_at -> Timestamp,
+        modified_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    use diesel::sql_types::*;
+    use crate::enums::diesel_exports::*;
+
+    webhook_delivery_attempt (id) {
+        id -> Int4,
+        #[max_length = 64]
+        event_id -> Varchar,
+        #[max_length = 64]
+        merchant_id -> Varchar,
+        attempt_number -> Int4,
+        is_delivered -> Bool,
+        response_status_code -> Nullable<Int4>,
+        error_message -> Nullable<Text>,
+        request_body -> Nullable<Bytea>,
+        request_headers -> Nullable<Jsonb>,
+        next_retry_at -> Nullable<Timestamp>,
+        created_at -> Timestamp,
+        #[max_length = 64]
+        endpoint_id -> Nullable<Varchar>,
+    }
+}
+
+diesel::table! {
+    use diesel::sql_types::*;
+    use crate::enums::diesel_exports::*;
+
+    webhook_endpoint_health (merchant_id) {
+        #[max_length = 64]
+        merchant_id -> Varchar,
+        #[max_length = 64]
+        status -> Varchar,
+        delivery_count -> Int4,
+        failure_count -> Int4,
+        degraded_at -> Nullable<Timestamp>,
+        last_verified_at -> Nullable<Timestamp>,
+        created_at -> Timestamp,
+        modified_at -> Timestamp,
+    }
+}
+
 diesel::allow_tables_to_appear_in_same_query!(
     address,
     api_keys,
+    audit_event,
     captures,
     cards_info,
+    commission,
     configs,
     connector_response,
+    customer_import_job,
     customers,
     dispute,
     events,
     file_metadata,
     fraud_check,
+    gift_card,
+    gift_card_transaction,
+    invoice,
+    ledger_entry,
     locker_mock_up,
     mandate,
     merchant_account,
@@ -768,10 +1247,20 @@ diesel::allow_tables_to_appear_in_same_query!(
     merchant_key_store,
     payment_attempt,
     payment_intent,
+    payment_link,
+    payment_metadata_audit,
+    payment_method_share,
     payment_methods,
     payout_attempt,
+    payout_batch,
     payouts,
     process_tracker,
+    queued_webhook,
     refund,
+    report_schedule,
     reverse_lookup,
+    subscription,
+    terminal,
+    webhook_delivery_attempt,
+    webhook_endpoint_health,
 );
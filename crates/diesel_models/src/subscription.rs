@@ -0,0 +1,143 @@
+use common_utils::custom_serde;
+use diesel::{AsChangeset, Identifiable, Insertable, Queryable};
+use serde::{Deserialize, Serialize};
+
+use crate::{enums as storage_enums, schema::subscription};
+
+#[derive(Clone, Debug, Deserialize, Insertable, Serialize, router_derive::DebugAsDisplay)]
+#[diesel(table_name = subscription)]
+pub struct SubscriptionNew {
+    pub subscription_id: String,
+    pub merchant_id: String,
+    pub customer_id: String,
+    pub mandate_id: String,
+    pub status: storage_enums::SubscriptionStatus,
+    pub amount: i64,
+    pub currency: storage_enums::Currency,
+    pub billing_interval: storage_enums::BillingInterval,
+    pub billing_interval_count: i32,
+    pub description: Option<String>,
+    pub billing_anchor: time::PrimitiveDateTime,
+    pub billing_cycle: i32,
+    pub next_billing_at: time::PrimitiveDateTime,
+    pub created_at: time::PrimitiveDateTime,
+    pub modified_at: time::PrimitiveDateTime,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, Identifiable, Queryable)]
+#[diesel(table_name = subscription, primary_key(subscription_id, merchant_id))]
+pub struct Subscription {
+    #[serde(skip_serializing)]
+    pub id: i32,
+    pub subscription_id: String,
+    pub merchant_id: String,
+    pub customer_id: String,
+    pub mandate_id: String,
+    pub status: storage_enums::SubscriptionStatus,
+    pub amount: i64,
+    pub currency: storage_enums::Currency,
+    pub billing_interval: storage_enums::BillingInterval,
+    pub billing_interval_count: i32,
+    pub description: Option<String>,
+    #[serde(with = "custom_serde::iso8601")]
+    pub billing_anchor: time::PrimitiveDateTime,
+    pub billing_cycle: i32,
+    #[serde(with = "custom_serde::iso8601")]
+    pub next_billing_at: time::PrimitiveDateTime,
+    pub failed_attempt_count: i32,
+    pub last_payment_id: Option<String>,
+    #[serde(default, with = "custom_serde::iso8601::option")]
+    pub cancelled_at: Option<time::PrimitiveDateTime>,
+    #[serde(with = "custom_serde::iso8601")]
+    pub created_at: time::PrimitiveDateTime,
+    #[serde(with = "custom_serde::iso8601")]
+    pub modified_at: time::PrimitiveDateTime,
+}
+
+#[derive(Debug)]
+pub enum SubscriptionUpdate {
+    Update {
+        amount: Option<i64>,
+        mandate_id: Option<String>,
+        description: Option<String>,
+    },
+    BillingUpdate {
+        status: storage_enums::SubscriptionStatus,
+        billing_cycle: i32,
+        next_billing_at: time::PrimitiveDateTime,
+        failed_attempt_count: i32,
+        last_payment_id: Option<String>,
+        cancelled_at: Option<time::PrimitiveDateTime>,
+    },
+    StatusUpdate {
+        status: storage_enums::SubscriptionStatus,
+        cancelled_at: Option<time::PrimitiveDateTime>,
+    },
+}
+
+#[derive(Clone, Debug, Default, AsChangeset, router_derive::DebugAsDisplay)]
+#[diesel(table_name = subscription)]
+pub struct SubscriptionUpdateInternal {
+    amount: Option<i64>,
+    mandate_id: Option<String>,
+    description: Option<String>,
+    status: Option<storage_enums::SubscriptionStatus>,
+    billing_cycle: Option<i32>,
+    next_billing_at: Option<time::PrimitiveDateTime>,
+    failed_attempt_count: Option<i32>,
+    last_payment_id: Option<String>,
+    cancelled_at: Option<time::PrimitiveDateTime>,
+    modified_at: Option<time::PrimitiveDateTime>,
+}
+
+impl From<SubscriptionUpdate> for SubscriptionUpdateInternal {
+    fn from(subscription_update: SubscriptionUpdate) -> Self {
+        let modified_at = Some(common_utils::date_time::now());
+        match subscription_update {
+            SubscriptionUpdate::Update {
+                amount,
+                mandate_id,
+                description,
+            } => Self {
+                amount,
+                mandate_id,
+                description,
+                modified_at,
+                ..Default::default()
+            },
+            SubscriptionUpdate::BillingUpdate {
+                status,
+                billing_cycle,
+                next_billing_at,
+                failed_attempt_count,
+                last_payment_id,
+                cancelled_at,
+            } => Self {
+                status: Some(status),
+                billing_cycle: Some(billing_cycle),
+                next_billing_at: Some(next_billing_at),
+                failed_attempt_count: Some(failed_attempt_count),
+                last_payment_id,
+                cancelled_at,
+                modified_at,
+                ..Default::default()
+            },
+            SubscriptionUpdate::StatusUpdate {
+                status,
+                cancelled_at,
+            } => Self {
+                status: Some(status),
+                cancelled_at,
+                modified_at,
+                ..Default::default()
+            },
+        }
+    }
+}
+
+// Tracking data by process_tracker
+#[derive(Default, Debug, Deserialize, Serialize, Clone)]
+pub struct SubscriptionBillingWorkflow {
+    pub subscription_id: String,
+    pub merchant_id: String,
+}
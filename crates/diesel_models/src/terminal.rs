@@ -0,0 +1,88 @@
+use common_utils::{custom_serde, pii};
+use diesel::{AsChangeset, Identifiable, Insertable, Queryable};
+use serde::{Deserialize, Serialize};
+
+use crate::schema::terminal;
+
+#[derive(Clone, Debug, Deserialize, Insertable, Serialize, router_derive::DebugAsDisplay)]
+#[diesel(table_name = terminal)]
+pub struct TerminalNew {
+    pub terminal_id: String,
+    pub merchant_id: String,
+    pub merchant_connector_id: Option<String>,
+    pub connector_terminal_id: Option<String>,
+    pub label: Option<String>,
+    pub location: Option<String>,
+    pub serial_number: Option<String>,
+    pub is_active: bool,
+    pub metadata: Option<pii::SecretSerdeValue>,
+    pub created_at: time::PrimitiveDateTime,
+    pub modified_at: time::PrimitiveDateTime,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, Identifiable, Queryable)]
+#[diesel(table_name = terminal, primary_key(terminal_id, merchant_id))]
+pub struct Terminal {
+    #[serde(skip_serializing)]
+    pub id: i32,
+    pub terminal_id: String,
+    pub merchant_id: String,
+    pub merchant_connector_id: Option<String>,
+    pub connector_terminal_id: Option<String>,
+    pub label: Option<String>,
+    pub location: Option<String>,
+    pub serial_number: Option<String>,
+    pub is_active: bool,
+    pub metadata: Option<pii::SecretSerdeValue>,
+    #[serde(with = "custom_serde::iso8601")]
+    pub created_at: time::PrimitiveDateTime,
+    #[serde(with = "custom_serde::iso8601")]
+    pub modified_at: time::PrimitiveDateTime,
+}
+
+#[derive(Debug)]
+pub enum TerminalUpdate {
+    Update {
+        merchant_connector_id: Option<String>,
+        connector_terminal_id: Option<String>,
+        label: Option<String>,
+        location: Option<String>,
+        is_active: Option<bool>,
+        metadata: Option<pii::SecretSerdeValue>,
+    },
+}
+
+#[derive(Clone, Debug, AsChangeset, router_derive::DebugAsDisplay)]
+#[diesel(table_name = terminal)]
+pub struct TerminalUpdateInternal {
+    merchant_connector_id: Option<String>,
+    connector_terminal_id: Option<String>,
+    label: Option<String>,
+    location: Option<String>,
+    is_active: Option<bool>,
+    metadata: Option<pii::SecretSerdeValue>,
+    modified_at: time::PrimitiveDateTime,
+}
+
+impl From<TerminalUpdate> for TerminalUpdateInternal {
+    fn from(terminal_update: TerminalUpdate) -> Self {
+        match terminal_update {
+            TerminalUpdate::Update {
+                merchant_connector_id,
+                connector_terminal_id,
+                label,
+                location,
+                is_active,
+                metadata,
+            } => Self {
+                merchant_connector_id,
+                connector_terminal_id,
+                label,
+                location,
+                is_active,
+                metadata,
+                modified_at: common_utils::date_time::now(),
+            },
+        }
+    }
+}
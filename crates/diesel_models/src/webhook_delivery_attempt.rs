@@ -0,0 +1,57 @@
+use diesel::{Identifiable, Insertable, Queryable};
+use serde::{Deserialize, Serialize};
+
+use crate::{encryption::Encryption, schema::webhook_delivery_attempt};
+
+#[derive(Clone, Debug, Insertable, router_derive::DebugAsDisplay)]
+#[diesel(table_name = webhook_delivery_attempt)]
+pub struct WebhookDeliveryAttemptNew {
+    pub event_id: String,
+    pub merchant_id: String,
+    pub attempt_number: i32,
+    pub is_delivered: bool,
+    pub response_status_code: Option<i32>,
+    pub error_message: Option<String>,
+    pub request_body: Option<Encryption>,
+    pub request_headers: Option<serde_json::Value>,
+    pub next_retry_at: Option<time::PrimitiveDateTime>,
+    pub created_at: time::PrimitiveDateTime,
+    pub endpoint_id: Option<String>,
+}
+
+#[derive(Clone, Debug, Identifiable, Queryable)]
+#[diesel(table_name = webhook_delivery_attempt)]
+pub struct WebhookDeliveryAttempt {
+    pub id: i32,
+    pub event_id: String,
+    pub merchant_id: String,
+    pub attempt_number: i32,
+    pub is_delivered: bool,
+    /// The HTTP status code returned by the webhook endpoint, if a response was received
+    pub response_status_code: Option<i32>,
+    pub error_message: Option<String>,
+    /// The body of the webhook request, encrypted with the key of the merchant. Stored only when
+    /// a retry of the webhook is scheduled.
+    pub request_body: Option<Encryption>,
+    /// The headers carrying the signature of the webhook. Stored only when a retry of the
+    /// webhook is scheduled.
+    pub request_headers: Option<serde_json::Value>,
+    pub next_retry_at: Option<time::PrimitiveDateTime>,
+    pub created_at: time::PrimitiveDateTime,
+    /// The business webhook endpoint to which the webhook was posted, not set for the webhook
+    /// endpoint of the merchant
+    pub endpoint_id: Option<String>,
+}
+
+// Tracking data by process_tracker
+#[derive(Default, Debug, Deserialize, Serialize, Clone)]
+pub struct OutgoingWebhookRetryWorkflow {
+    pub merchant_id: String,
+    pub event_id: String,
+    /// The number of the attempt to be made by the retry
+    pub attempt_number: i32,
+    /// The business webhook endpoint to which the webhook is retried, not set for the webhook
+    /// endpoint of the merchant
+    #[serde(default)]
+    pub endpoint_id: Option<String>,
+}
@@ -0,0 +1,89 @@
+use diesel::{AsChangeset, Identifiable, Insertable, Queryable};
+use serde::{Deserialize, Serialize};
+
+use crate::{enums as storage_enums, schema::webhook_endpoint_health};
+
+#[derive(Clone, Debug, Deserialize, Insertable, Serialize, router_derive::DebugAsDisplay)]
+#[diesel(table_name = webhook_endpoint_health)]
+pub struct WebhookEndpointHealthNew {
+    pub merchant_id: String,
+    pub status: storage_enums::WebhookEndpointStatus,
+    pub delivery_count: i32,
+    pub failure_count: i32,
+    pub degraded_at: Option<time::PrimitiveDateTime>,
+    pub last_verified_at: Option<time::PrimitiveDateTime>,
+    pub created_at: time::PrimitiveDateTime,
+    pub modified_at: time::PrimitiveDateTime,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, Identifiable, Queryable)]
+#[diesel(table_name = webhook_endpoint_health, primary_key(merchant_id))]
+pub struct WebhookEndpointHealth {
+    pub merchant_id: String,
+    pub status: storage_enums::WebhookEndpointStatus,
+    /// The number of deliveries in the window in which the endpoint was last marked degraded
+    pub delivery_count: i32,
+    /// The number of failed deliveries in the window in which the endpoint was last marked
+    /// degraded
+    pub failure_count: i32,
+    pub degraded_at: Option<time::PrimitiveDateTime>,
+    pub last_verified_at: Option<time::PrimitiveDateTime>,
+    pub created_at: time::PrimitiveDateTime,
+    pub modified_at: time::PrimitiveDateTime,
+}
+
+#[derive(Debug)]
+pub enum WebhookEndpointHealthUpdate {
+    Degraded {
+        status: storage_enums::WebhookEndpointStatus,
+        delivery_count: i32,
+        failure_count: i32,
+        degraded_at: time::PrimitiveDateTime,
+    },
+    Verified {
+        last_verified_at: time::PrimitiveDateTime,
+    },
+    Resumed,
+}
+
+#[derive(Clone, Debug, Default, AsChangeset, router_derive::DebugAsDisplay)]
+#[diesel(table_name = webhook_endpoint_health)]
+pub struct WebhookEndpointHealthUpdateInternal {
+    status: Option<storage_enums::WebhookEndpointStatus>,
+    delivery_count: Option<i32>,
+    failure_count: Option<i32>,
+    degraded_at: Option<time::PrimitiveDateTime>,
+    last_verified_at: Option<time::PrimitiveDateTime>,
+    modified_at: Option<time::PrimitiveDateTime>,
+}
+
+impl From<WebhookEndpointHealthUpdate> for WebhookEndpointHealthUpdateInternal {
+    fn from(webhook_endpoint_health_update: WebhookEndpointHealthUpdate) -> Self {
+        let modified_at = Some(common_utils::date_time::now());
+        match webhook_endpoint_health_update {
+            WebhookEndpointHealthUpdate::Degraded {
+                status,
+                delivery_count,
+                failure_count,
+                degraded_at,
+            } => Self {
+                status: Some(status),
+                delivery_count: Some(delivery_count),
+                failure_count: Some(failure_count),
+                degraded_at: Some(degraded_at),
+                modified_at,
+                ..Default::default()
+            },
+            WebhookEndpointHealthUpdate::Verified { last_verified_at } => Self {
+                last_verified_at: Some(last_verified_at),
+                modified_at,
+                ..Default::default()
+            },
+            WebhookEndpointHealthUpdate::Resumed => Self {
+                status: Some(storage_enums::WebhookEndpointStatus::Healthy),
+                modified_at,
+                ..Default::default()
+            },
+        }
+    }
+}
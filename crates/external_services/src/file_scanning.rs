@@ -0,0 +1,238 @@
+//! Interactions with malware scanning engines for files uploaded to the router
+
+use std::time::Duration;
+
+use common_utils::errors::CustomResult;
+use error_stack::{IntoReport, ResultExt};
+use router_env::logger;
+use serde::Deserialize;
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::TcpStream,
+};
+
+/// Custom Result type alias for file scanning operations.
+pub type FileScanningResult<T> = CustomResult<T, FileScanningError>;
+
+/// The outcome of scanning a file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ScanVerdict {
+    /// No threats were found in the file.
+    Clean,
+    /// The scanner flagged the file, along with the name of the detected threat.
+    Infected {
+        /// Name of the threat (signature) reported by the scanner.
+        threat: String,
+    },
+}
+
+/// A trait that defines the methods that must be implemented by a malware scanning engine.
+///
+/// Implementations are invoked on every file received through the files API before it is
+/// stored or forwarded to a connector. The ClamAV (`clamd`) integration is provided out of the
+/// box, other engines (for example, an ICAP server) can be plugged in by implementing this trait.
+#[async_trait::async_trait]
+pub trait FileScanner: Sync + Send + dyn_clone::DynClone {
+    /// Scans the provided file contents and returns the verdict of the scanning engine.
+    async fn scan_file(&self, file: &[u8]) -> FileScanningResult<ScanVerdict>;
+}
+
+dyn_clone::clone_trait_object!(FileScanner);
+
+/// The scanning engine used for scanning uploaded files.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FileScannerKind {
+    /// Files are not scanned.
+    #[default]
+    Disabled,
+    /// Files are scanned by a ClamAV daemon using the `INSTREAM` command.
+    Clamav,
+}
+
+/// Struct that contains the settings required to construct a [`FileScanner`].
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct FileScanningSettings {
+    /// The scanning engine to be used.
+    pub scanner: FileScannerKind,
+
+    /// Host on which the scanning engine is listening.
+    pub host: String,
+
+    /// Port on which the scanning engine is listening.
+    pub port: u16,
+
+    /// Maximum time (in seconds) to wait for the scanning engine to return a verdict.
+    pub timeout: u64,
+
+    /// Size (in bytes) of the chunks in which the file is streamed to the scanning engine.
+    pub chunk_size: usize,
+}
+
+impl Default for FileScanningSettings {
+    fn default() -> Self {
+        Self {
+            scanner: FileScannerKind::Disabled,
+            host: "localhost".into(),
+            port: 3310,
+            timeout: 30,
+            chunk_size: 64 * 1024,
+        }
+    }
+}
+
+impl FileScanningSettings {
+    /// Constructs the file scanner configured in the settings, if any.
+    pub fn get_file_scanner(&self) -> Option<Box<dyn FileScanner>> {
+        match self.scanner {
+            FileScannerKind::Disabled => None,
+            FileScannerKind::Clamav => Some(Box::new(ClamAv::new(self))),
+        }
+    }
+}
+
+/// Client for scanning files using a ClamAV daemon (`clamd`).
+#[derive(Debug, Clone)]
+pub struct ClamAv {
+    address: String,
+    timeout: Duration,
+    chunk_size: usize,
+}
+
+impl ClamAv {
+    /// Constructs a new ClamAV client
+    pub fn new(conf: &FileScanningSettings) -> Self {
+        Self {
+            address: format!("{}:{}", conf.host, conf.port),
+            timeout: Duration::from_secs(conf.timeout),
+            chunk_size: conf.chunk_size.max(1),
+        }
+    }
+
+    async fn stream_file(&self, file: &[u8]) -> FileScanningResult<String> {
+        let mut stream = TcpStream::connect(&self.address)
+            .await
+            .into_report()
+            .change_context(FileScanningError::ConnectionFailure)?;
+
+        stream
+            .write_all(b"zINSTREAM\0")
+            .await
+            .into_report()
+            .change_context(FileScanningError::ScanRequestFailure)?;
+
+        for chunk in file.chunks(self.chunk_size) {
+            let chunk_length = u32::try_from(chunk.len())
+                .into_report()
+                .change_context(FileScanningError::ScanRequestFailure)?;
+            stream
+                .write_all(&chunk_length.to_be_bytes())
+                .await
+                .into_report()
+                .change_context(FileScanningError::ScanRequestFailure)?;
+            stream
+                .write_all(chunk)
+                .await
+                .into_report()
+                .change_context(FileScanningError::ScanRequestFailure)?;
+        }
+
+        // A zero length chunk marks the end of the stream
+        stream
+            .write_all(&[0; 4])
+            .await
+            .into_report()
+            .change_context(FileScanningError::ScanRequestFailure)?;
+
+        let mut response = Vec::new();
+        stream
+            .read_to_end(&mut response)
+            .await
+            .into_report()
+            .change_context(FileScanningError::ScanResponseFailure)?;
+
+        String::from_utf8(response)
+            .into_report()
+            .change_context(FileScanningError::ScanResponseFailure)
+    }
+}
+
+#[async_trait::async_trait]
+impl FileScanner for ClamAv {
+    async fn scan_file(&self, file: &[u8]) -> FileScanningResult<ScanVerdict> {
+        let response = tokio::time::timeout(self.timeout, self.stream_file(file))
+            .await
+            .into_report()
+            .change_context(FileScanningError::Timeout)??;
+
+        parse_clamd_response(&response)
+    }
+}
+
+/// Parses the response returned by `clamd` for an `INSTREAM` command.
+///
+/// The response is of the form `stream: OK`, `stream: <threat> FOUND` or `<reason> ERROR`.
+fn parse_clamd_response(response: &str) -> FileScanningResult<ScanVerdict> {
+    let response = response.trim_end_matches(['\0', '\n']).trim();
+    let result = response
+        .strip_prefix("stream:")
+        .map(str::trim)
+        .unwrap_or(response);
+
+    if result == "OK" {
+        Ok(ScanVerdict::Clean)
+    } else if let Some(threat) = result.strip_suffix("FOUND") {
+        Ok(ScanVerdict::Infected {
+            threat: threat.trim().to_string(),
+        })
+    } else {
+        logger::error!(clamd_response = %response, "Unexpected response from ClamAV");
+        Err(FileScanningError::UnexpectedResponse).into_report()
+    }
+}
+
+/// Errors that could occur while scanning files.
+#[derive(Debug, thiserror::Error)]
+pub enum FileScanningError {
+    /// Unable to connect to the scanning engine.
+    #[error("Failed to connect to the file scanning engine")]
+    ConnectionFailure,
+
+    /// An error occurred when sending the file to the scanning engine.
+    #[error("Failed to send the file to the file scanning engine")]
+    ScanRequestFailure,
+
+    /// An error occurred when reading the verdict from the scanning engine.
+    #[error("Failed to read the response from the file scanning engine")]
+    ScanResponseFailure,
+
+    /// The scanning engine did not return a verdict in time.
+    #[error("Timed out waiting for the file scanning engine")]
+    Timeout,
+
+    /// The scanning engine returned a response that could not be interpreted.
+    #[error("Unexpected response received from the file scanning engine")]
+    UnexpectedResponse,
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+
+    #[test]
+    fn test_clamd_response_parsing() {
+        assert_eq!(
+            parse_clamd_response("stream: OK\0").unwrap(),
+            ScanVerdict::Clean
+        );
+        assert_eq!(
+            parse_clamd_response("stream: Eicar-Signature FOUND\0").unwrap(),
+            ScanVerdict::Infected {
+                threat: "Eicar-Signature".to_string()
+            }
+        );
+        assert!(parse_clamd_response("INSTREAM size limit exceeded. ERROR\0").is_err());
+    }
+}
@@ -6,6 +6,9 @@
 #[cfg(feature = "email")]
 pub mod email;
 
+#[cfg(feature = "file_scanning")]
+pub mod file_scanning;
+
 #[cfg(feature = "kms")]
 pub mod kms;
 
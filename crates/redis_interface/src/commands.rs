@@ -200,6 +200,40 @@ impl super::RedisConnectionPool {
             .change_context(errors::RedisError::SetFailed)
     }
 
+    /// Increments the counter stored at `key`, setting it to expire after `seconds` when it is
+    /// created, and returns the value of the counter after the increment.
+    #[instrument(level = "DEBUG", skip(self))]
+    pub async fn increment_key_with_expiry(
+        &self,
+        key: &str,
+        seconds: i64,
+    ) -> CustomResult<i64, errors::RedisError> {
+        self.increment_key_by_with_expiry(key, 1, seconds).await
+    }
+
+    /// Increments the value stored at `key` by `increment`, setting the expiry of the key when
+    /// the increment creates it.
+    #[instrument(level = "DEBUG", skip(self))]
+    pub async fn increment_key_by_with_expiry(
+        &self,
+        key: &str,
+        increment: i64,
+        seconds: i64,
+    ) -> CustomResult<i64, errors::RedisError> {
+        let value: i64 = self
+            .pool
+            .incr_by(key, increment)
+            .await
+            .into_report()
+            .change_context(errors::RedisError::IncrementFailed)?;
+
+        if value == increment {
+            self.set_expiry(key, seconds).await?;
+        }
+
+        Ok(value)
+    }
+
     #[instrument(level = "DEBUG", skip(self))]
     pub async fn set_expiry(
         &self,
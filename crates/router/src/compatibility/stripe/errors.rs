@@ -49,6 +49,9 @@ pub enum StripeErrorCode {
     #[error(error_type = StripeErrorType::CardError, code = "invalid_card_type", message = "Card data is invalid")]
     InvalidCardType,
 
+    #[error(error_type = StripeErrorType::CardError, code = "card_velocity_exceeded", message = "The payment was declined as it exceeds the {limit} velocity limit of the {scope}")]
+    CardVelocityExceeded { limit: String, scope: String },
+
     #[error(error_type = StripeErrorType::ApiError, code = "refund_failed", message = "refund has failed")]
     RefundFailed, // stripe error code
 
@@ -118,6 +121,9 @@ pub enum StripeErrorCode {
     #[error(error_type = StripeErrorType::InvalidRequestError, code = "token_already_used", message = "Duplicate payout request")]
     DuplicatePayout { payout_id: String },
 
+    #[error(error_type = StripeErrorType::InvalidRequestError, code = "idempotency_key_in_use", message = "The merchant refund reference was already used for a different refund request")]
+    DuplicateMerchantRefundReference { merchant_refund_reference: String },
+
     #[error(error_type = StripeErrorType::InvalidRequestError, code = "parameter_missing", message = "Return url is not available")]
     ReturnUrlUnavailable,
 
@@ -225,6 +231,8 @@ pub enum StripeErrorCode {
     PaymentMethodUnactivated,
     #[error(error_type = StripeErrorType::HyperswitchError, code = "", message = "{entity} expired or invalid")]
     HyperswitchUnprocessableEntity { entity: String },
+    #[error(error_type = StripeErrorType::InvalidRequestError, code = "rate_limit", message = "Too many attempts were made to confirm this payment, try again later")]
+    RateLimit,
     // [#216]: https://github.com/juspay/hyperswitch/issues/216
     // Implement the remaining stripe error codes
 
@@ -463,6 +471,24 @@ impl From<errors::ApiErrorResponse> for StripeErrorCode {
                 connector,
                 status_code,
             },
+            errors::ApiErrorResponse::ConnectorUnderMaintenance { connector } => {
+                Self::ExternalConnectorError {
+                    code: "connector_under_maintenance".to_string(),
+                    message: format!("{connector} is under maintenance, try again later"),
+                    connector,
+                    status_code: 503,
+                }
+            }
+            errors::ApiErrorResponse::ConnectorRequestThrottled { connector } => {
+                Self::ExternalConnectorError {
+                    code: "connector_request_throttled".to_string(),
+                    message: format!(
+                        "Too many requests are being made to {connector}, try again later"
+                    ),
+                    connector,
+                    status_code: 429,
+                }
+            }
             errors::ApiErrorResponse::IncorrectConnectorNameGiven => {
                 Self::IncorrectConnectorNameGiven
             }
@@ -474,6 +500,11 @@ impl From<errors::ApiErrorResponse> for StripeErrorCode {
             errors::ApiErrorResponse::DuplicatePayout { payout_id } => {
                 Self::DuplicatePayout { payout_id }
             }
+            errors::ApiErrorResponse::DuplicateMerchantRefundReference {
+                merchant_refund_reference,
+            } => Self::DuplicateMerchantRefundReference {
+                merchant_refund_reference,
+            },
             errors::ApiErrorResponse::RefundNotFound => Self::RefundNotFound,
             errors::ApiErrorResponse::CustomerNotFound => Self::CustomerNotFound,
             errors::ApiErrorResponse::PaymentNotFound => Self::PaymentNotFound,
@@ -488,6 +519,10 @@ impl From<errors::ApiErrorResponse> for StripeErrorCode {
             errors::ApiErrorResponse::MandateNotFound => Self::MandateNotFound,
             errors::ApiErrorResponse::ApiKeyNotFound => Self::ApiKeyNotFound,
             errors::ApiErrorResponse::PayoutNotFound => Self::PayoutNotFound,
+            errors::ApiErrorResponse::PayoutBatchNotFound { batch_id } => Self::ResourceMissing {
+                object: "payout_batch".to_owned(),
+                id: batch_id,
+            },
             errors::ApiErrorResponse::MandateValidationFailed { reason } => {
                 Self::PaymentIntentMandateInvalid { message: reason }
             }
@@ -535,10 +570,53 @@ impl From<errors::ApiErrorResponse> for StripeErrorCode {
                 object: "dispute".to_owned(),
                 id: dispute_id,
             },
+            errors::ApiErrorResponse::TerminalNotFound { terminal_id } => Self::ResourceMissing {
+                object: "terminal".to_owned(),
+                id: terminal_id,
+            },
+            errors::ApiErrorResponse::InvoiceNotFound { invoice_id } => Self::ResourceMissing {
+                object: "invoice".to_owned(),
+                id: invoice_id,
+            },
+            errors::ApiErrorResponse::GiftCardNotFound { gift_card_id } => Self::ResourceMissing {
+                object: "gift_card".to_owned(),
+                id: gift_card_id,
+            },
+            errors::ApiErrorResponse::ReportScheduleNotFound { report_schedule_id } => {
+                Self::ResourceMissing {
+                    object: "report_schedule".to_owned(),
+                    id: report_schedule_id,
+                }
+            }
+            errors::ApiErrorResponse::CustomerImportJobNotFound { job_id } => {
+                Self::ResourceMissing {
+                    object: "customer_import".to_owned(),
+                    id: job_id,
+                }
+            }
+            errors::ApiErrorResponse::PaymentLinkNotFound { payment_link_id } => {
+                Self::ResourceMissing {
+                    object: "payment_link".to_owned(),
+                    id: payment_link_id,
+                }
+            }
+            errors::ApiErrorResponse::PaymentMethodShareNotFound { share_id } => {
+                Self::ResourceMissing {
+                    object: "payment_method_share".to_owned(),
+                    id: share_id,
+                }
+            }
+            errors::ApiErrorResponse::SubscriptionNotFound { subscription_id } => {
+                Self::ResourceMissing {
+                    object: "subscription".to_owned(),
+                    id: subscription_id,
+                }
+            }
             errors::ApiErrorResponse::DisputeStatusValidationFailed { reason } => {
                 Self::InternalServerError
             }
-            errors::ApiErrorResponse::FileValidationFailed { .. } => Self::FileValidationFailed,
+            errors::ApiErrorResponse::FileValidationFailed { .. }
+            | errors::ApiErrorResponse::FileInfected { .. } => Self::FileValidationFailed,
             errors::ApiErrorResponse::MissingFile => Self::MissingFile,
             errors::ApiErrorResponse::MissingFilePurpose => Self::MissingFilePurpose,
             errors::ApiErrorResponse::MissingFileContentType => Self::MissingFileContentType,
@@ -556,10 +634,52 @@ impl From<errors::ApiErrorResponse> for StripeErrorCode {
             | errors::ApiErrorResponse::WebhookResourceNotFound
             | errors::ApiErrorResponse::WebhookProcessingFailure
             | errors::ApiErrorResponse::WebhookAuthenticationFailed
-            | errors::ApiErrorResponse::WebhookUnprocessableEntity => Self::WebhookProcessingError,
+            | errors::ApiErrorResponse::WebhookUnprocessableEntity
+            | errors::ApiErrorResponse::WebhookQueueFull => Self::WebhookProcessingError,
             errors::ApiErrorResponse::IncorrectPaymentMethodConfiguration => {
                 Self::PaymentMethodUnactivated
             }
+            errors::ApiErrorResponse::ConfirmAttemptsExceeded
+            | errors::ApiErrorResponse::ServerOverloaded => Self::RateLimit,
+            errors::ApiErrorResponse::VelocityLimitExceeded { limit, scope } => {
+                Self::CardVelocityExceeded { limit, scope }
+            }
+            errors::ApiErrorResponse::SchemaValidationFailed { diagnostics } => {
+                Self::InvalidRequestData {
+                    message: diagnostics
+                        .into_iter()
+                        .map(|diagnostic| format!("{}: {}", diagnostic.pointer, diagnostic.message))
+                        .collect::<Vec<_>>()
+                        .join(", "),
+                }
+            }
+            errors::ApiErrorResponse::ApplePayDecryptionFailed => Self::InvalidRequestData {
+                message: "The Apple Pay payment token could not be decrypted".to_string(),
+            },
+            errors::ApiErrorResponse::ConnectorAuthenticationFailed { connector, reason } => {
+                Self::InvalidRequestData {
+                    message: match reason {
+                        Some(reason) => format!(
+                            "The {connector} connector rejected the account details: {reason}"
+                        ),
+                        None => format!("The {connector} connector rejected the account details"),
+                    },
+                }
+            }
+            errors::ApiErrorResponse::ConnectorVerificationInconclusive { connector, reason } => {
+                Self::InvalidRequestData {
+                    message: match reason {
+                        Some(reason) => format!(
+                            "The account details could not be verified with the {connector} \
+                             connector: {reason}"
+                        ),
+                        None => format!(
+                            "The account details could not be verified with the {connector} \
+                             connector"
+                        ),
+                    },
+                }
+            }
         }
     }
 }
@@ -580,8 +700,10 @@ impl actix_web::ResponseError for StripeErrorCode {
             | Self::PaymentIntentPaymentAttemptFailed { .. }
             | Self::ExpiredCard
             | Self::InvalidCardType
+            | Self::CardVelocityExceeded { .. }
             | Self::DuplicateRefundRequest
             | Self::DuplicatePayout { .. }
+            | Self::DuplicateMerchantRefundReference { .. }
             | Self::RefundNotFound
             | Self::CustomerNotFound
             | Self::ConfigNotFound
@@ -632,6 +754,7 @@ impl actix_web::ResponseError for StripeErrorCode {
             | Self::CustomerRedacted
             | Self::WebhookProcessingError => StatusCode::INTERNAL_SERVER_ERROR,
             Self::ReturnUrlUnavailable => StatusCode::SERVICE_UNAVAILABLE,
+            Self::RateLimit => StatusCode::TOO_MANY_REQUESTS,
             Self::ExternalConnectorError { status_code, .. } => {
                 StatusCode::from_u16(*status_code).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR)
             }
@@ -779,6 +779,9 @@ pub enum StripeNextAction {
         display_from_timestamp: i128,
         display_to_timestamp: Option<i128>,
     },
+    SolveCaptcha {
+        site_key: String,
+    },
 }
 
 pub(crate) fn into_stripe_next_action(
@@ -819,6 +822,9 @@ pub(crate) fn into_stripe_next_action(
             display_from_timestamp,
             display_to_timestamp,
         },
+        payments::NextActionData::SolveCaptcha { site_key } => {
+            StripeNextAction::SolveCaptcha { site_key }
+        }
     })
 }
 
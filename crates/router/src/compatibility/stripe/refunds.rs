@@ -107,6 +107,7 @@ pub async fn refund_retrieve(
     let refund_request = refund_types::RefundsRetrieveRequest {
         refund_id: path.into_inner(),
         force_sync: Some(true),
+        bypass_sync_cache: None,
         merchant_connector_details: None,
     };
 
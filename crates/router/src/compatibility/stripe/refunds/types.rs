@@ -38,6 +38,7 @@ pub enum StripeRefundStatus {
     Failed,
     Pending,
     RequiresAction,
+    Canceled,
 }
 
 impl From<StripeCreateRefundRequest> for refunds::RefundRequest {
@@ -69,8 +70,13 @@ impl From<refunds::RefundStatus> for StripeRefundStatus {
         match status {
             refunds::RefundStatus::Succeeded => Self::Succeeded,
             refunds::RefundStatus::Failed => Self::Failed,
-            refunds::RefundStatus::Pending => Self::Pending,
-            refunds::RefundStatus::Review => Self::RequiresAction,
+            refunds::RefundStatus::Pending | refunds::RefundStatus::PendingApproval => {
+                Self::Pending
+            }
+            refunds::RefundStatus::Review | refunds::RefundStatus::AmountMismatch => {
+                Self::RequiresAction
+            }
+            refunds::RefundStatus::Cancelled | refunds::RefundStatus::Rejected => Self::Canceled,
         }
     }
 }
@@ -80,11 +80,16 @@ pub async fn setup_intents_retrieve(
         resource_id: api_types::PaymentIdType::PaymentIntentId(path.to_string()),
         merchant_id: None,
         force_sync: true,
+        bypass_sync_cache: None,
         connector: None,
         param: None,
         merchant_connector_details: None,
         client_secret: query_payload.client_secret.clone(),
         expand_attempts: None,
+        expand_captures: None,
+        expand_refunds: None,
+        expand_disputes: None,
+        expand_customer: None,
     };
 
     let (auth_type, auth_flow) =
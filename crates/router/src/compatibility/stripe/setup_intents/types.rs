@@ -380,6 +380,9 @@ pub enum StripeNextAction {
         display_from_timestamp: i128,
         display_to_timestamp: Option<i128>,
     },
+    SolveCaptcha {
+        site_key: String,
+    },
 }
 
 pub(crate) fn into_stripe_next_action(
@@ -420,6 +423,9 @@ pub(crate) fn into_stripe_next_action(
             display_from_timestamp,
             display_to_timestamp,
         },
+        payments::NextActionData::SolveCaptcha { site_key } => {
+            StripeNextAction::SolveCaptcha { site_key }
+        }
     })
 }
 
@@ -73,6 +73,10 @@ pub enum StripeWebhookObject {
     PaymentIntent(StripePaymentIntentResponse),
     Refund(StripeRefundResponse),
     Dispute(StripeDisputeResponse),
+    Invoice(Box<api_models::invoices::InvoiceResponse>),
+    Report(Box<api_models::reports::GeneratedReportResponse>),
+    PaymentMethod(Box<api_models::payment_methods::PaymentMethodResponse>),
+    Payout(Box<api_models::payouts::PayoutCreateResponse>),
 }
 
 #[derive(Serialize, Debug)]
@@ -135,6 +139,8 @@ fn get_stripe_event_type(event_type: api_models::enums::EventType) -> &'static s
         api_models::enums::EventType::ActionRequired => "action.required",
         api_models::enums::EventType::RefundSucceeded => "refund.succeeded",
         api_models::enums::EventType::RefundFailed => "refund.failed",
+        api_models::enums::EventType::RefundAmountMismatch => "refund.amount_mismatch",
+        api_models::enums::EventType::RefundManualReview => "refund.manual_review",
         api_models::enums::EventType::DisputeOpened => "dispute.failed",
         api_models::enums::EventType::DisputeExpired => "dispute.expired",
         api_models::enums::EventType::DisputeAccepted => "dispute.accepted",
@@ -142,6 +148,18 @@ fn get_stripe_event_type(event_type: api_models::enums::EventType) -> &'static s
         api_models::enums::EventType::DisputeChallenged => "dispute.challenged",
         api_models::enums::EventType::DisputeWon => "dispute.won",
         api_models::enums::EventType::DisputeLost => "dispute.lost",
+        api_models::enums::EventType::InvoicePartiallyPaid => "invoice.partially_paid",
+        api_models::enums::EventType::InvoicePaid => "invoice.paid",
+        api_models::enums::EventType::InvoiceVoided => "invoice.voided",
+        api_models::enums::EventType::ReportGenerated => "reporting.report_run.succeeded",
+        api_models::enums::EventType::PaymentMethodExpiring => "customer.source.expiring",
+        api_models::enums::EventType::PaymentMethodUpdated => {
+            "payment_method.automatically_updated"
+        }
+        api_models::enums::EventType::PayoutProcessing => "payout.processing",
+        api_models::enums::EventType::PayoutSucceeded => "payout.paid",
+        api_models::enums::EventType::PayoutFailed => "payout.failed",
+        api_models::enums::EventType::PayoutReversed => "payout.reversed",
     }
 }
 
@@ -179,6 +197,12 @@ impl From<api::OutgoingWebhookContent> for StripeWebhookObject {
             api::OutgoingWebhookContent::DisputeDetails(dispute) => {
                 Self::Dispute((*dispute).into())
             }
+            api::OutgoingWebhookContent::InvoiceDetails(invoice) => Self::Invoice(invoice),
+            api::OutgoingWebhookContent::ReportDetails(report) => Self::Report(report),
+            api::OutgoingWebhookContent::PaymentMethodDetails(payment_method) => {
+                Self::PaymentMethod(payment_method)
+            }
+            api::OutgoingWebhookContent::PayoutDetails(payout) => Self::Payout(payout),
         }
     }
 }
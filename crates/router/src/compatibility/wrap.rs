@@ -93,6 +93,7 @@ where
             redirection_data.payment_method_data,
             redirection_data.amount,
             redirection_data.currency,
+            redirection_data.payment_link_config.as_ref(),
         )
         .respond_to(request)
         .map_into_boxed_body(),
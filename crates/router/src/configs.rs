@@ -1,5 +1,6 @@
 mod defaults;
 #[cfg(feature = "kms")]
 pub mod kms;
+pub mod runtime;
 pub mod settings;
 mod validations;
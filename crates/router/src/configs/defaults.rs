@@ -69,6 +69,142 @@ impl Default for super::settings::Refund {
         Self {
             max_attempts: 10,
             max_age: 365,
+            max_pending_duration: 0,
+            connector_max_pending_durations: HashMap::new(),
+        }
+    }
+}
+
+impl Default for super::settings::ConfirmProtection {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            window: 3600,
+            client_secret_captcha_threshold: 3,
+            client_secret_max_attempts: 10,
+            card_captcha_threshold: 3,
+            card_max_attempts: 10,
+            captcha: Default::default(),
+        }
+    }
+}
+
+impl Default for super::settings::WebhookQueueSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            high_priority_stream: "WEBHOOK_QUEUE_HIGH_PRIORITY".into(),
+            low_priority_stream: "WEBHOOK_QUEUE_LOW_PRIORITY".into(),
+            consumer_group: "WEBHOOK_QUEUE_GROUP".into(),
+            consumer_name_prefix: "webhook_worker".into(),
+            workers: 8,
+            max_concurrency_per_connector: 4,
+            max_queue_length: 10000,
+            max_retries: 5,
+            retry_interval: 5000,
+            poll_interval: 500,
+        }
+    }
+}
+
+impl Default for super::settings::LoadSheddingSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_concurrent_requests: 1024,
+            low_priority_max_concurrent_requests: 512,
+            critical_latency_threshold: 2000,
+            critical_paths: vec!["/payments".into(), "/refunds".into(), "/webhooks".into()],
+            low_priority_paths: vec![
+                "/payments/list".into(),
+                "/refunds/list".into(),
+                "/disputes/list".into(),
+                "/accounting".into(),
+                "/reports".into(),
+            ],
+            retry_after: 1,
+        }
+    }
+}
+
+impl Default for super::settings::PaymentMethodExpirySettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            notice_days: 30,
+            account_updater_connectors: Vec::new(),
+        }
+    }
+}
+
+impl Default for super::settings::ConnectorCertificateSettings {
+    fn default() -> Self {
+        Self {
+            expiry_reminder_days: vec![30, 7, 1],
+        }
+    }
+}
+
+impl Default for super::settings::PaymentLinkSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            sdk_url: String::new(),
+            signing_key: String::new().into(),
+            // 1 hour
+            default_expiry: 60 * 60,
+            // 7 days
+            max_expiry: 7 * 24 * 60 * 60,
+        }
+    }
+}
+
+impl Default for super::settings::PaymentRetrySettings {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            retry_on_server_errors: false,
+            retryable_error_codes: HashMap::new(),
+        }
+    }
+}
+
+impl Default for super::settings::ApplePayDecryptionSettings {
+    fn default() -> Self {
+        Self {
+            apple_root_ca_certificate: String::new(),
+            // 5 minutes
+            max_signature_age: 5 * 60,
+        }
+    }
+}
+
+impl Default for super::settings::ConnectorVerificationSettings {
+    fn default() -> Self {
+        Self {
+            verify_on_save: true,
+        }
+    }
+}
+
+impl Default for super::settings::WebhookEndpointHealthSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            window: 3600,
+            min_deliveries: 20,
+            failure_rate_threshold: 0.5,
+        }
+    }
+}
+
+impl Default for super::settings::WebhookRetrySettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            default_max_retries: 5,
+            default_initial_delay: 60,
+            max_delay: 24 * 60 * 60,
         }
     }
 }
@@ -850,3 +986,15 @@ impl Default for super::settings::ApiKeys {
         }
     }
 }
+
+impl Default for super::settings::ConnectorRequestSettings {
+    fn default() -> Self {
+        Self {
+            timeout: crate::consts::REQUEST_TIME_OUT,
+            connectors_under_maintenance: HashSet::new(),
+            rate_limits: HashMap::new(),
+            sync_cache_ttl: 0,
+            signing_profiles: HashMap::new(),
+        }
+    }
+}
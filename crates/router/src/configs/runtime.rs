@@ -0,0 +1,160 @@
+//! Settings which can be reloaded while the application is running, without restarting it.
+//!
+//! The reloadable settings are read again from the configuration file and the environment when
+//! the `/runtime_config/reload` admin endpoint is called, or when the configuration file is
+//! modified if `runtime_config.watch_interval` is set. A reload only applies to the instance on
+//! which it is performed, and the other settings keep the values with which the application was
+//! started.
+
+use std::{
+    path::PathBuf,
+    sync::{Arc, PoisonError, RwLock},
+    time::{Duration, SystemTime},
+};
+
+use super::settings;
+use crate::{core::errors::ApplicationResult, env::logger, routes::metrics};
+
+/// The settings which can be reloaded at runtime.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RuntimeSettings {
+    pub connector_requests: settings::ConnectorRequestSettings,
+    pub load_shedding: settings::LoadSheddingSettings,
+    pub confirm_protection: settings::ConfirmProtection,
+    pub schema_validation: settings::SchemaValidationSettings,
+}
+
+impl From<&settings::Settings> for RuntimeSettings {
+    fn from(settings: &settings::Settings) -> Self {
+        Self {
+            connector_requests: settings.connector_requests.clone(),
+            load_shedding: settings.load_shedding.clone(),
+            confirm_protection: settings.confirm_protection.clone(),
+            schema_validation: settings.schema_validation.clone(),
+        }
+    }
+}
+
+impl RuntimeSettings {
+    /// Returns the names of the sections of the configuration which differ between the settings.
+    fn get_changed_sections(&self, other: &Self) -> Vec<&'static str> {
+        [
+            (
+                "connector_requests",
+                self.connector_requests != other.connector_requests,
+            ),
+            ("load_shedding", self.load_shedding != other.load_shedding),
+            (
+                "confirm_protection",
+                self.confirm_protection != other.confirm_protection,
+            ),
+            (
+                "schema_validation",
+                self.schema_validation != other.schema_validation,
+            ),
+        ]
+        .into_iter()
+        .filter_map(|(section, is_changed)| is_changed.then_some(section))
+        .collect()
+    }
+}
+
+/// The reloadable settings currently in use, shared by all the workers of the server.
+#[derive(Debug)]
+pub struct RuntimeConfig {
+    config_path: Option<PathBuf>,
+    settings: RwLock<Arc<RuntimeSettings>>,
+}
+
+impl RuntimeConfig {
+    pub fn new(settings: &settings::Settings) -> Self {
+        Self {
+            config_path: settings.config_path.clone(),
+            settings: RwLock::new(Arc::new(RuntimeSettings::from(settings))),
+        }
+    }
+
+    /// Returns the reloadable settings currently in use.
+    pub fn get(&self) -> Arc<RuntimeSettings> {
+        self.settings
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    /// Reads the configuration again and replaces the reloadable settings with the new values,
+    /// returning the sections of the configuration which changed. The settings in use are left
+    /// unchanged if the new configuration cannot be read or is invalid.
+    pub fn reload(&self) -> ApplicationResult<Vec<&'static str>> {
+        let new_settings = settings::Settings::with_config_path(self.config_path.clone())?;
+        new_settings.validate()?;
+        let new_settings = Arc::new(RuntimeSettings::from(&new_settings));
+
+        let mut settings = self
+            .settings
+            .write()
+            .unwrap_or_else(PoisonError::into_inner);
+        let changed_sections = settings.get_changed_sections(&new_settings);
+        if !changed_sections.is_empty() {
+            logger::info!(
+                ?changed_sections,
+                previous_settings = ?settings,
+                new_settings = ?new_settings,
+                "Reloaded runtime settings"
+            );
+            metrics::RUNTIME_CONFIG_RELOADS.add(&metrics::CONTEXT, 1, &[]);
+        }
+        *settings = new_settings;
+
+        Ok(changed_sections)
+    }
+
+    fn get_config_modified_at(&self) -> Option<SystemTime> {
+        self.config_path
+            .as_ref()
+            .and_then(|config_path| std::fs::metadata(config_path).ok())
+            .and_then(|metadata| metadata.modified().ok())
+    }
+
+    /// Reloads the settings whenever the configuration file is modified, checking its
+    /// modification time at the given interval.
+    pub async fn watch(self: Arc<Self>, interval: Duration) {
+        let mut modified_at = self.get_config_modified_at();
+        let mut interval = tokio::time::interval(interval);
+
+        loop {
+            interval.tick().await;
+            let current_modified_at = self.get_config_modified_at();
+            if current_modified_at == modified_at {
+                continue;
+            }
+            modified_at = current_modified_at;
+
+            if let Err(error) = self.reload() {
+                logger::error!(
+                    ?error,
+                    "Failed to reload runtime settings after the configuration file was modified"
+                );
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_get_changed_sections() {
+        let settings = RuntimeSettings::default();
+        assert!(settings.get_changed_sections(&settings.clone()).is_empty());
+
+        let mut new_settings = settings.clone();
+        new_settings.connector_requests.timeout = 60;
+        new_settings.load_shedding.enabled = true;
+        assert_eq!(
+            settings.get_changed_sections(&new_settings),
+            vec!["connector_requests", "load_shedding"]
+        );
+    }
+}
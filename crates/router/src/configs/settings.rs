@@ -9,6 +9,8 @@ use common_utils::ext_traits::ConfigExt;
 use config::{Environment, File};
 #[cfg(feature = "email")]
 use external_services::email::EmailSettings;
+#[cfg(feature = "file_scanning")]
+use external_services::file_scanning::FileScanningSettings;
 #[cfg(feature = "kms")]
 use external_services::kms;
 use redis_interface::RedisSettings;
@@ -18,6 +20,7 @@ use serde::{de::Error, Deserialize, Deserializer};
 use crate::{
     core::errors::{ApplicationError, ApplicationResult},
     env::{self, logger, Env},
+    services::api::request_signing,
 };
 #[cfg(feature = "kms")]
 pub type Password = kms::KmsValue;
@@ -81,6 +84,8 @@ pub struct Settings {
     pub kms: kms::KmsConfig,
     #[cfg(feature = "s3")]
     pub file_upload_config: FileUploadConfig,
+    #[cfg(feature = "file_scanning")]
+    pub file_scanning: FileScanningSettings,
     pub tokenization: TokenizationConfig,
     pub connector_customer: ConnectorCustomer,
     #[cfg(feature = "dummy_connector")]
@@ -93,6 +98,27 @@ pub struct Settings {
     pub connector_request_reference_id_config: ConnectorRequestReferenceIdConfig,
     #[cfg(feature = "payouts")]
     pub payouts: Payouts,
+    pub confirm_protection: ConfirmProtection,
+    pub webhook_queue: WebhookQueueSettings,
+    pub platform_commission: PlatformCommission,
+    pub load_shedding: LoadSheddingSettings,
+    pub reauthorization: ReauthorizationSettings,
+    pub payment_method_expiry: PaymentMethodExpirySettings,
+    pub network_tokenization: NetworkTokenizationSettings,
+    pub currency_conversion: CurrencyConversionSettings,
+    pub connector_capabilities: ConnectorCapabilities,
+    pub connector_certificates: ConnectorCertificateSettings,
+    pub connector_requests: ConnectorRequestSettings,
+    pub payment_links: PaymentLinkSettings,
+    pub payment_retries: PaymentRetrySettings,
+    pub apple_pay_decryption: ApplePayDecryptionSettings,
+    pub connector_verification: ConnectorVerificationSettings,
+    pub schema_validation: SchemaValidationSettings,
+    pub runtime_config: RuntimeConfigSettings,
+    pub data_residency: DataResidencySettings,
+    /// The configuration file from which the settings were read
+    #[serde(skip)]
+    pub config_path: Option<PathBuf>,
 }
 
 #[derive(Debug, Deserialize, Clone, Default)]
@@ -251,6 +277,25 @@ pub struct BanksVector {
 #[serde(transparent)]
 pub struct ConnectorFilters(pub HashMap<String, PaymentMethodFilters>);
 
+impl ConnectorFilters {
+    /// Returns the filter configured for the payment method type of the connector, falling back
+    /// to the default filters when the connector has no filters configured
+    pub fn get_payment_method_type_filter(
+        &self,
+        connector: &str,
+        payment_method_type: api_models::enums::PaymentMethodType,
+    ) -> Option<&CurrencyCountryFlowFilter> {
+        self.0
+            .get(connector)
+            .or_else(|| self.0.get("default"))
+            .and_then(|filters| {
+                filters.0.get(&PaymentMethodFilterKey::PaymentMethodType(
+                    payment_method_type,
+                ))
+            })
+    }
+}
+
 #[derive(Debug, Deserialize, Clone, Default)]
 #[serde(transparent)]
 pub struct PaymentMethodFilters(pub HashMap<PaymentMethodFilterKey, CurrencyCountryFlowFilter>);
@@ -270,6 +315,20 @@ pub struct CurrencyCountryFlowFilter {
     #[serde(deserialize_with = "string_set_deser")]
     pub country: Option<HashSet<api_models::enums::CountryAlpha2>>,
     pub not_available_flows: Option<NotAvailableFlows>,
+    /// Minimum amount (in the lowest denomination of the currency) accepted by the connector
+    pub min_amount: Option<i64>,
+    /// Maximum amount (in the lowest denomination of the currency) accepted by the connector
+    pub max_amount: Option<i64>,
+}
+
+impl CurrencyCountryFlowFilter {
+    pub fn is_amount_within_limits(&self, amount: i64) -> bool {
+        self.min_amount
+            .map_or(true, |min_amount| amount >= min_amount)
+            && self
+                .max_amount
+                .map_or(true, |max_amount| amount <= max_amount)
+    }
 }
 
 #[derive(Debug, Deserialize, Copy, Clone, Default)]
@@ -358,6 +417,13 @@ pub struct Secrets {
     #[cfg(not(feature = "kms"))]
     pub recon_admin_api_key: String,
     pub master_enc_key: Password,
+    /// The version of `master_enc_key`, which is recorded on every merchant key store encrypted
+    /// with it. It must be incremented whenever a new master encryption key is deployed.
+    pub master_enc_key_version: Option<i32>,
+    /// The master encryption key that was active before `master_enc_key`, whose version is the one
+    /// preceding `master_enc_key_version`. It is only used for decrypting merchant key stores which
+    /// have not yet been rotated to the current master key.
+    pub previous_master_enc_key: Option<Password>,
     #[cfg(feature = "kms")]
     pub kms_encrypted_jwt_secret: kms::KmsValue,
     #[cfg(feature = "kms")]
@@ -380,6 +446,12 @@ pub struct Locker {
 pub struct Refund {
     pub max_attempts: usize,
     pub max_age: i64,
+    /// The duration (in seconds) after which a refund still pending despite its syncs is moved to
+    /// manual review. Set to 0 to leave refunds pending indefinitely.
+    pub max_pending_duration: i64,
+    /// The duration after which refunds still pending are moved to manual review, by connector,
+    /// overriding `max_pending_duration`
+    pub connector_max_pending_durations: HashMap<String, i64>,
 }
 
 #[derive(Debug, Deserialize, Clone)]
@@ -521,6 +593,7 @@ pub struct ConnectorParamsWithFileUploadUrl {
 pub struct ConnectorParamsWithSecondaryBaseUrl {
     pub base_url: String,
     pub secondary_base_url: String,
+    pub third_base_url: String,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -566,6 +639,40 @@ pub struct DrainerSettings {
 #[serde(default)]
 pub struct WebhooksSettings {
     pub outgoing_enabled: bool,
+    pub endpoint_health: WebhookEndpointHealthSettings,
+    pub retry: WebhookRetrySettings,
+    /// The page documenting how merchants can validate the source of webhooks, included in
+    /// webhooks along with the egress IP ranges
+    pub source_validation_documentation_url: Option<String>,
+}
+
+/// Settings for monitoring the failure rate of deliveries to the webhook endpoints of merchants.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct WebhookEndpointHealthSettings {
+    pub enabled: bool,
+    /// The duration (in seconds) of the window in which deliveries to an endpoint are counted
+    pub window: i64,
+    /// The number of deliveries in a window below which an endpoint is not marked degraded
+    pub min_deliveries: i64,
+    /// The fraction of failed deliveries in a window beyond which an endpoint is marked degraded
+    pub failure_rate_threshold: f64,
+}
+
+/// Settings for retrying the deliveries of webhooks which were not received by the webhook
+/// endpoints of merchants. Merchants can override the number of retries and the initial delay in
+/// their webhook details.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct WebhookRetrySettings {
+    pub enabled: bool,
+    /// The number of retries of a webhook, unless configured by the merchant
+    pub default_max_retries: u8,
+    /// The delay (in seconds) before the first retry of a webhook, unless configured by the
+    /// merchant. The delay is doubled for every following retry.
+    pub default_initial_delay: u32,
+    /// The maximum delay (in seconds) between two retries of a webhook
+    pub max_delay: u32,
 }
 
 #[derive(Debug, Deserialize, Clone)]
@@ -602,6 +709,364 @@ pub struct DelayedSessionConfig {
     pub connectors_with_delayed_session_response: HashSet<api_models::enums::Connector>,
 }
 
+/// Limits on the number of attempts made to confirm payments from the client, which protect
+/// merchants against card testing.
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+#[serde(default)]
+pub struct ConfirmProtection {
+    pub enabled: bool,
+    /// The duration (in seconds) over which confirm attempts are counted
+    pub window: i64,
+    /// The number of attempts with the same client secret after which a CAPTCHA has to be solved
+    pub client_secret_captcha_threshold: i64,
+    /// The number of attempts with the same client secret after which confirmation is refused
+    pub client_secret_max_attempts: i64,
+    /// The number of attempts with the same card after which a CAPTCHA has to be solved
+    pub card_captcha_threshold: i64,
+    /// The number of attempts with the same card after which confirmation is refused
+    pub card_max_attempts: i64,
+    pub captcha: CaptchaSettings,
+}
+
+#[derive(Debug, Deserialize, Clone, Default, PartialEq)]
+#[serde(default)]
+pub struct CaptchaSettings {
+    /// The site key with which the CAPTCHA widget is rendered to the customer
+    pub site_key: String,
+    /// The secret key with which CAPTCHA tokens are verified
+    pub secret_key: masking::Secret<String>,
+    /// The endpoint of the CAPTCHA provider at which CAPTCHA tokens are verified
+    pub verify_url: String,
+}
+
+/// Settings of the queue from which incoming webhooks are processed asynchronously.
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct WebhookQueueSettings {
+    pub enabled: bool,
+    /// The stream to which webhooks about payments, refunds and disputes reaching a terminal
+    /// state are added
+    pub high_priority_stream: String,
+    /// The stream to which all other webhooks are added
+    pub low_priority_stream: String,
+    pub consumer_group: String,
+    /// The prefix of the consumer names of the workers, which are followed by the host name of the
+    /// instance and the index of the worker
+    pub consumer_name_prefix: String,
+    /// The number of webhooks processed concurrently by an instance
+    pub workers: usize,
+    /// The number of webhooks of a single connector processed concurrently by an instance
+    pub max_concurrency_per_connector: usize,
+    /// The number of webhooks waiting in a stream, beyond which further webhooks are rejected
+    pub max_queue_length: usize,
+    /// The number of times processing a webhook is retried before it is discarded
+    pub max_retries: u32,
+    /// The delay (in milliseconds) before the first retry of a webhook, doubled for each further
+    /// retry
+    pub retry_interval: u64,
+    /// The duration (in milliseconds) for which workers wait when the queue is empty
+    pub poll_interval: u64,
+}
+
+/// Commission recorded by the platform operator on the payments of the merchants it runs
+/// hyperswitch for.
+#[derive(Debug, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct PlatformCommission {
+    pub enabled: bool,
+    /// The pricing plan applied to merchants who have not been assigned a pricing plan
+    pub default_plan: Option<String>,
+    /// The pricing plans, keyed by their names
+    pub plans: HashMap<String, CommissionPricingPlan>,
+    /// The names of the pricing plans assigned to merchants, keyed by merchant ID
+    pub merchant_plans: HashMap<String, String>,
+}
+
+#[derive(Debug, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct CommissionPricingPlan {
+    /// The share of the payment amount charged as commission, in basis points
+    pub rate: u16,
+    /// The amount charged as commission on every payment, in the lowest denomination of the
+    /// currency of the payment
+    pub fixed_amount: i64,
+}
+
+/// Settings for shedding low priority requests while the server is overloaded, in order to
+/// preserve the latency of critical requests such as payment authorization and capture.
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+#[serde(default)]
+pub struct LoadSheddingSettings {
+    pub enabled: bool,
+    /// The number of requests being processed concurrently beyond which normal priority requests
+    /// are shed
+    pub max_concurrent_requests: usize,
+    /// The number of requests being processed concurrently beyond which low priority requests
+    /// are shed
+    pub low_priority_max_concurrent_requests: usize,
+    /// The average response time (in milliseconds) of critical requests beyond which low priority
+    /// requests are shed
+    pub critical_latency_threshold: u64,
+    /// The path prefixes of critical requests, which are never shed
+    pub critical_paths: Vec<String>,
+    /// The path prefixes of low priority requests, which take precedence over critical paths
+    pub low_priority_paths: Vec<String>,
+    /// The duration (in seconds) after which clients are asked to retry shed requests
+    pub retry_after: u64,
+}
+
+/// Settings of the requests made to connectors, which can be reloaded at runtime.
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+#[serde(default)]
+pub struct ConnectorRequestSettings {
+    /// The timeout (in seconds) of the requests made to connectors
+    pub timeout: u64,
+    /// The connectors under maintenance, to which no payment requests are sent
+    #[serde(deserialize_with = "connector_deser")]
+    pub connectors_under_maintenance: HashSet<api_models::enums::Connector>,
+    /// The rate limits of the requests made to connectors, by connector name
+    pub rate_limits: HashMap<String, ConnectorRateLimit>,
+    /// The duration (in seconds) for which the result of a payment or refund sync with a connector
+    /// is served to further syncs of the same transaction, 0 to always sync with the connector
+    pub sync_cache_ttl: i64,
+    /// The profiles with which the requests made to connectors are signed, by connector name, for
+    /// the connectors which do not sign their requests themselves
+    pub signing_profiles: HashMap<String, ConnectorSigningProfileConfig>,
+}
+
+/// The profile with which the requests made to a connector are signed, with the `api_secret` of
+/// the `SignatureKey` credentials of the merchant connector account as the key.
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct ConnectorSigningProfileConfig {
+    pub algorithm: request_signing::SigningAlgorithm,
+    /// The header carrying the signature
+    pub signature_header: String,
+    #[serde(default)]
+    pub signature_encoding: request_signing::SignatureEncoding,
+    /// The header carrying the timestamp of the request, which is not sent if unset
+    pub timestamp_header: Option<String>,
+    /// The header carrying the nonce of the request, which is not sent if unset
+    pub nonce_header: Option<String>,
+    /// The header carrying the `api_key` of the credentials as the identifier of the signing key,
+    /// which is not sent if unset
+    pub key_id_header: Option<String>,
+    #[serde(default)]
+    pub body_canonicalization: request_signing::BodyCanonicalization,
+}
+
+/// Token bucket rate limit of the requests made to a connector, applied by every instance of the
+/// router on its own.
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct ConnectorRateLimit {
+    /// The number of requests per second which can be made to the connector by an instance
+    pub requests_per_second: u32,
+    /// The number of requests which can be made to the connector in a burst by an instance
+    pub burst: u32,
+    /// The maximum duration (in milliseconds) for which a request is deferred when the rate limit
+    /// is exceeded, after which the request is rejected
+    #[serde(default)]
+    pub max_wait: u64,
+}
+
+/// Settings for reloading the runtime settings without restarting the application.
+#[derive(Debug, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct RuntimeConfigSettings {
+    /// The interval (in seconds) at which the configuration file is checked for changes, with the
+    /// runtime settings being reloaded when it is modified. Set to 0 to only reload the settings
+    /// through the admin API.
+    pub watch_interval: u64,
+}
+
+/// Settings for storing the customers and payments of merchants in the region they are pinned to.
+#[derive(Debug, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct DataResidencySettings {
+    /// The storage of each region, by the name of the region with which merchant accounts are
+    /// created
+    pub regions: HashMap<String, RegionalStorage>,
+}
+
+/// The databases and locker in which the customers, payments and payment methods of the merchants
+/// pinned to a region are stored.
+#[derive(Debug, Deserialize, Clone)]
+pub struct RegionalStorage {
+    pub master_database: Database,
+    #[cfg(feature = "olap")]
+    pub replica_database: Database,
+    pub locker: Locker,
+}
+
+/// Settings for re-authorizing payments awaiting capture whose authorization is nearing its expiry.
+#[derive(Debug, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct ReauthorizationSettings {
+    pub enabled: bool,
+    /// The validity of the authorizations of the connectors supporting re-authorization, keyed by
+    /// connector name
+    pub connectors: HashMap<String, AuthorizationValidity>,
+}
+
+#[derive(Debug, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct AuthorizationValidity {
+    /// The duration (in seconds) for which an authorization made through the connector is valid
+    pub validity: i64,
+    /// The duration (in seconds) before the expiry of an authorization at which it is
+    /// re-authorized
+    pub lead_time: i64,
+}
+
+/// Settings for refreshing saved cards nearing their expiry through the account updater services
+/// of connectors, and notifying merchants of the cards which could not be refreshed.
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct PaymentMethodExpirySettings {
+    pub enabled: bool,
+    /// The number of days before the expiry of a saved card at which it is refreshed, or the
+    /// merchant notified of its expiry
+    pub notice_days: i64,
+    /// The connectors whose account updater services are used to refresh saved cards, in order of
+    /// preference
+    pub account_updater_connectors: Vec<String>,
+}
+
+/// Settings for provisioning network tokens for saved cards with the token services of card
+/// networks, and sending them to connectors in place of card numbers.
+#[derive(Debug, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct NetworkTokenizationSettings {
+    pub enabled: bool,
+    /// Visa Token Service (VTS)
+    pub visa: Option<TokenServiceProviderConfig>,
+    /// Mastercard Digital Enablement Service (MDES)
+    pub mastercard: Option<TokenServiceProviderConfig>,
+    /// The connectors to which network tokens are sent in place of card numbers
+    pub connector_list: HashSet<String>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct TokenServiceProviderConfig {
+    pub base_url: String,
+    pub api_key: masking::Secret<String>,
+    /// The identifier of the token requestor registered with the token service provider
+    pub token_requestor_id: String,
+}
+
+/// Settings for charging customers in a currency other than the settlement currency of the
+/// merchant, with the amount converted at the exchange rate of the configured rate provider and
+/// charged through connectors supporting dynamic currency conversion (DCC).
+#[derive(Debug, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct CurrencyConversionSettings {
+    pub enabled: bool,
+    /// Fixed exchange rates keyed by currency pair in the `<FROM>_<TO>` format, such as `USD_EUR`,
+    /// used when no exchange rate API is configured
+    pub fixed_rates: HashMap<String, String>,
+    /// An exchange rate API, which takes precedence over the fixed exchange rates
+    pub rate_api: Option<FxRateApiConfig>,
+    /// The connectors supporting dynamic currency conversion, through which payments charged in
+    /// the currency of the customer are settled in the currency of the merchant
+    pub dcc_connector_list: HashSet<String>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct FxRateApiConfig {
+    pub base_url: String,
+    pub api_key: Option<masking::Secret<String>>,
+}
+
+/// The capabilities of connectors, against which payments are validated before being sent to
+/// connectors.
+#[derive(Debug, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct ConnectorCapabilities {
+    /// The connectors which only support capturing payments as they are authorized, through which
+    /// payments with a manual or delayed capture are rejected
+    #[serde(deserialize_with = "connector_deser")]
+    pub auto_capture_only_connectors: HashSet<api_models::enums::Connector>,
+}
+
+/// Settings for the client certificates presented to connectors which require mutual TLS.
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct ConnectorCertificateSettings {
+    /// The number of days before the expiry of a client certificate on which the merchant is
+    /// reminded of its expiry, in descending order
+    pub expiry_reminder_days: Vec<u8>,
+}
+
+/// Settings of the payment links, pages hosted for the merchant through which a customer pays a
+/// payment using the SDK.
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct PaymentLinkSettings {
+    pub enabled: bool,
+    /// The URL of the SDK loaded on the payment link pages
+    pub sdk_url: String,
+    /// The key with which the URLs of the payment links are signed
+    pub signing_key: masking::Secret<String>,
+    /// The duration (in seconds) after which a payment link expires, unless set when it is created
+    pub default_expiry: i64,
+    /// The maximum duration (in seconds) after which a payment link can expire
+    pub max_expiry: i64,
+}
+
+/// Settings of the automatic retries of payments declined by a connector with a retryable error,
+/// on the next connectors of the `priority` routing algorithm of the merchant.
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct PaymentRetrySettings {
+    /// The maximum number of automatic retries of a payment which merchants can configure
+    pub max_retries: u8,
+    /// Whether payments are retried when the connector fails to process them, responding with a
+    /// server error. Connectors may have processed a payment for which they responded with a
+    /// server error, which could be charged twice when retried, hence disabled by default
+    pub retry_on_server_errors: bool,
+    /// The error codes with which connectors decline payments that can be retried on another
+    /// connector, by connector name
+    pub retryable_error_codes: HashMap<String, HashSet<String>>,
+}
+
+/// Settings of the decryption of Apple Pay payment tokens by the router, for the merchants which
+/// configured a payment processing certificate on their connector accounts.
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct ApplePayDecryptionSettings {
+    /// The base64 encoded root certificate of Apple (in PEM format), against which the signatures
+    /// of the payment tokens are verified before they are decrypted
+    pub apple_root_ca_certificate: String,
+    /// The maximum time (in seconds) between the signing of a payment token and its decryption,
+    /// beyond which the payment token is rejected as replayed
+    pub max_signature_age: u64,
+}
+
+/// Settings of the verification of the account details of merchant connector accounts with the
+/// connector, when the accounts are created or updated.
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct ConnectorVerificationSettings {
+    /// Whether the account details of enabled connector accounts are verified with the connector
+    /// before the accounts are saved, rejecting the account details refused by the connector
+    pub verify_on_save: bool,
+}
+
+/// Settings of the validation of the bodies of API requests and responses against the OpenAPI
+/// specification embedded in the application. The settings can be reloaded at runtime.
+#[derive(Debug, Deserialize, Clone, Default, PartialEq)]
+#[serde(default)]
+pub struct SchemaValidationSettings {
+    /// Whether the bodies of requests are validated, rejecting the requests which violate the
+    /// specification
+    pub enabled: bool,
+    /// Whether requests with fields which are not described by the specification are rejected,
+    /// instead of the unknown fields being reported in the `x-schema-warnings` response header
+    pub strict: bool,
+    /// Whether the bodies of responses are validated as well, logging their violations of the
+    /// specification
+    pub validate_responses: bool,
+}
+
 #[derive(Debug, Deserialize, Clone, Default)]
 pub struct ConnectorRequestReferenceIdConfig {
     pub merchant_ids_send_payment_id_as_connector_request_id: HashSet<String>,
@@ -645,7 +1110,7 @@ impl Settings {
         let config_path = router_env::Config::config_path(&environment.to_string(), config_path);
 
         let config = router_env::Config::builder(&environment.to_string())?
-            .add_source(File::from(config_path).required(true))
+            .add_source(File::from(config_path.clone()).required(true))
             .add_source(
                 Environment::with_prefix("ROUTER")
                     .try_parsing(true)
@@ -653,15 +1118,20 @@ impl Settings {
                     .list_separator(",")
                     .with_list_parse_key("redis.cluster_urls")
                     .with_list_parse_key("connectors.supported.wallets")
+                    .with_list_parse_key("payment_method_expiry.account_updater_connectors")
+                    .with_list_parse_key("network_tokenization.connector_list")
+                    .with_list_parse_key("currency_conversion.dcc_connector_list")
                     .with_list_parse_key("connector_request_reference_id_config.merchant_ids_send_payment_id_as_connector_request_id"),
             )
             .build()?;
 
-        serde_path_to_error::deserialize(config).map_err(|error| {
+        let mut settings: Self = serde_path_to_error::deserialize(config).map_err(|error| {
             logger::error!(%error, "Unable to deserialize application configuration");
             eprintln!("Unable to deserialize application configuration: {error}");
             ApplicationError::from(error.into_inner())
-        })
+        })?;
+        settings.config_path = Some(config_path);
+        Ok(settings)
     }
 
     pub fn validate(&self) -> ApplicationResult<()> {
@@ -688,6 +1158,7 @@ impl Settings {
         }
         self.secrets.validate()?;
         self.locker.validate()?;
+        self.refund.validate()?;
         self.connectors.validate("connectors")?;
 
         self.scheduler
@@ -703,6 +1174,20 @@ impl Settings {
             .map_err(|error| ApplicationError::InvalidConfigurationValueError(error.into()))?;
         #[cfg(feature = "s3")]
         self.file_upload_config.validate()?;
+        self.confirm_protection.validate()?;
+        self.webhook_queue.validate()?;
+        self.webhooks.endpoint_health.validate()?;
+        self.platform_commission.validate()?;
+        self.load_shedding.validate()?;
+        self.reauthorization.validate()?;
+        self.payment_method_expiry.validate()?;
+        self.network_tokenization.validate()?;
+        self.currency_conversion.validate()?;
+        self.connector_certificates.validate()?;
+        self.connector_requests.validate()?;
+        self.payment_links.validate()?;
+        self.data_residency.validate()?;
+        self.schema_validation.validate()?;
         Ok(())
     }
 }
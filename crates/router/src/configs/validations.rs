@@ -67,6 +67,25 @@ impl super::settings::Locker {
     }
 }
 
+impl super::settings::Refund {
+    pub fn validate(&self) -> Result<(), ApplicationError> {
+        use common_utils::fp_utils::when;
+
+        when(
+            self.max_pending_duration < 0
+                || self
+                    .connector_max_pending_durations
+                    .values()
+                    .any(|duration| *duration < 0),
+            || {
+                Err(ApplicationError::InvalidConfigurationValueError(
+                    "maximum pending durations of refunds must not be negative".into(),
+                ))
+            },
+        )
+    }
+}
+
 impl super::settings::Server {
     pub fn validate(&self) -> Result<(), ApplicationError> {
         common_utils::fp_utils::when(self.host.is_default_or_empty(), || {
@@ -198,3 +217,413 @@ impl super::settings::ApiKeys {
         })
     }
 }
+
+impl super::settings::ConfirmProtection {
+    pub fn validate(&self) -> Result<(), ApplicationError> {
+        use common_utils::fp_utils::when;
+
+        if !self.enabled {
+            return Ok(());
+        }
+
+        when(self.window <= 0, || {
+            Err(ApplicationError::InvalidConfigurationValueError(
+                "confirm protection window must be greater than zero".into(),
+            ))
+        })?;
+
+        when(
+            self.client_secret_captcha_threshold >= self.client_secret_max_attempts
+                || self.card_captcha_threshold >= self.card_max_attempts,
+            || {
+                Err(ApplicationError::InvalidConfigurationValueError(
+                    "confirm protection CAPTCHA thresholds must be lower than the maximum number of attempts".into(),
+                ))
+            },
+        )?;
+
+        when(
+            self.captcha.site_key.is_default_or_empty()
+                || self.captcha.secret_key.is_default_or_empty()
+                || self.captcha.verify_url.is_default_or_empty(),
+            || {
+                Err(ApplicationError::InvalidConfigurationValueError(
+                    "CAPTCHA site key, secret key and verification URL must not be empty when confirm protection is enabled".into(),
+                ))
+            },
+        )
+    }
+}
+
+impl super::settings::WebhookQueueSettings {
+    pub fn validate(&self) -> Result<(), ApplicationError> {
+        use common_utils::fp_utils::when;
+
+        if !self.enabled {
+            return Ok(());
+        }
+
+        when(
+            self.high_priority_stream.is_default_or_empty()
+                || self.low_priority_stream.is_default_or_empty()
+                || self.consumer_group.is_default_or_empty(),
+            || {
+                Err(ApplicationError::InvalidConfigurationValueError(
+                    "webhook queue stream and consumer group names must not be empty".into(),
+                ))
+            },
+        )?;
+
+        when(
+            self.high_priority_stream == self.low_priority_stream,
+            || {
+                Err(ApplicationError::InvalidConfigurationValueError(
+                    "webhook queue high and low priority streams must be different".into(),
+                ))
+            },
+        )?;
+
+        when(
+            self.workers == 0 || self.max_concurrency_per_connector == 0,
+            || {
+                Err(ApplicationError::InvalidConfigurationValueError(
+                    "webhook queue workers and concurrency per connector must be greater than zero"
+                        .into(),
+                ))
+            },
+        )
+    }
+}
+
+impl super::settings::WebhookEndpointHealthSettings {
+    pub fn validate(&self) -> Result<(), ApplicationError> {
+        use common_utils::fp_utils::when;
+
+        if !self.enabled {
+            return Ok(());
+        }
+
+        when(self.window <= 0 || self.min_deliveries <= 0, || {
+            Err(ApplicationError::InvalidConfigurationValueError(
+                "webhook endpoint health window and minimum deliveries must be greater than zero"
+                    .into(),
+            ))
+        })?;
+
+        when(
+            !(self.failure_rate_threshold > 0.0 && self.failure_rate_threshold <= 1.0),
+            || {
+                Err(ApplicationError::InvalidConfigurationValueError(
+                    "webhook endpoint health failure rate threshold must be greater than 0 and at most 1"
+                        .into(),
+                ))
+            },
+        )
+    }
+}
+
+impl super::settings::PlatformCommission {
+    pub fn validate(&self) -> Result<(), ApplicationError> {
+        use common_utils::fp_utils::when;
+
+        if !self.enabled {
+            return Ok(());
+        }
+
+        when(
+            self.plans.values().any(|plan| {
+                i64::from(plan.rate) > crate::consts::BASIS_POINTS_DENOMINATOR
+                    || plan.fixed_amount < 0
+            }),
+            || {
+                Err(ApplicationError::InvalidConfigurationValueError(
+                    "commission pricing plan rates must not exceed 10000 basis points and fixed amounts must not be negative".into(),
+                ))
+            },
+        )?;
+
+        self.default_plan
+            .iter()
+            .chain(self.merchant_plans.values())
+            .try_for_each(|plan| {
+                when(!self.plans.contains_key(plan), || {
+                    Err(ApplicationError::InvalidConfigurationValueError(format!(
+                        "commission pricing plan `{plan}` is not configured"
+                    )))
+                })
+            })
+    }
+}
+
+impl super::settings::LoadSheddingSettings {
+    pub fn validate(&self) -> Result<(), ApplicationError> {
+        use common_utils::fp_utils::when;
+
+        if !self.enabled {
+            return Ok(());
+        }
+
+        when(self.low_priority_max_concurrent_requests == 0, || {
+            Err(ApplicationError::InvalidConfigurationValueError(
+                "load shedding concurrency limits must be greater than zero".into(),
+            ))
+        })?;
+
+        when(
+            self.low_priority_max_concurrent_requests > self.max_concurrent_requests,
+            || {
+                Err(ApplicationError::InvalidConfigurationValueError(
+                    "load shedding low priority concurrency limit must not exceed the concurrency \
+                     limit"
+                        .into(),
+                ))
+            },
+        )
+    }
+}
+
+impl super::settings::ReauthorizationSettings {
+    pub fn validate(&self) -> Result<(), ApplicationError> {
+        use common_utils::fp_utils::when;
+
+        if !self.enabled {
+            return Ok(());
+        }
+
+        self.connectors
+            .iter()
+            .try_for_each(|(connector, authorization_validity)| {
+                when(
+                    authorization_validity.lead_time <= 0
+                        || authorization_validity.lead_time >= authorization_validity.validity,
+                    || {
+                        Err(ApplicationError::InvalidConfigurationValueError(format!(
+                            "re-authorization lead time of connector `{connector}` must be greater \
+                             than zero and lower than the validity of its authorizations"
+                        )))
+                    },
+                )
+            })
+    }
+}
+
+impl super::settings::PaymentMethodExpirySettings {
+    pub fn validate(&self) -> Result<(), ApplicationError> {
+        use common_utils::fp_utils::when;
+
+        when(self.enabled && self.notice_days <= 0, || {
+            Err(ApplicationError::InvalidConfigurationValueError(
+                "payment method expiry notice days must be greater than zero".into(),
+            ))
+        })
+    }
+}
+
+impl super::settings::ConnectorCertificateSettings {
+    pub fn validate(&self) -> Result<(), ApplicationError> {
+        use common_utils::fp_utils::when;
+
+        when(
+            self.expiry_reminder_days
+                .windows(2)
+                .any(|days| days.first() <= days.last()),
+            || {
+                Err(ApplicationError::InvalidConfigurationValueError(
+                    "connector certificate expiry reminder days must be in descending order".into(),
+                ))
+            },
+        )
+    }
+}
+
+impl super::settings::NetworkTokenizationSettings {
+    pub fn validate(&self) -> Result<(), ApplicationError> {
+        use common_utils::fp_utils::when;
+
+        when(
+            self.enabled && self.visa.is_none() && self.mastercard.is_none(),
+            || {
+                Err(ApplicationError::InvalidConfigurationValueError(
+                    "at least one token service provider must be configured for network tokenization"
+                        .into(),
+                ))
+            },
+        )?;
+
+        [&self.visa, &self.mastercard]
+            .into_iter()
+            .flatten()
+            .try_for_each(|provider| {
+                when(provider.base_url.is_default_or_empty(), || {
+                    Err(ApplicationError::InvalidConfigurationValueError(
+                        "token service provider base url must not be empty".into(),
+                    ))
+                })?;
+
+                when(provider.token_requestor_id.is_default_or_empty(), || {
+                    Err(ApplicationError::InvalidConfigurationValueError(
+                        "token service provider token requestor id must not be empty".into(),
+                    ))
+                })
+            })?;
+
+        self.connector_list.iter().try_for_each(|connector| {
+            when(
+                !crate::consts::NETWORK_TOKEN_CONNECTORS.contains(&connector.as_str()),
+                || {
+                    Err(ApplicationError::InvalidConfigurationValueError(format!(
+                        "connector {connector} does not send network tokens in place of card numbers"
+                    )))
+                },
+            )
+        })
+    }
+}
+
+impl super::settings::CurrencyConversionSettings {
+    pub fn validate(&self) -> Result<(), ApplicationError> {
+        use common_utils::fp_utils::when;
+
+        when(
+            self.enabled && self.rate_api.is_none() && self.fixed_rates.is_empty(),
+            || {
+                Err(ApplicationError::InvalidConfigurationValueError(
+                    "an exchange rate API or fixed exchange rates must be configured for currency conversion"
+                        .into(),
+                ))
+            },
+        )?;
+
+        when(
+            self.rate_api
+                .as_ref()
+                .map_or(false, |rate_api| rate_api.base_url.is_default_or_empty()),
+            || {
+                Err(ApplicationError::InvalidConfigurationValueError(
+                    "exchange rate API base url must not be empty".into(),
+                ))
+            },
+        )?;
+
+        when(
+            self.fixed_rates.values().any(|rate| {
+                crate::core::payments::currency_conversion::ExchangeRate::parse(rate).is_none()
+            }),
+            || {
+                Err(ApplicationError::InvalidConfigurationValueError(
+                    "fixed exchange rates must be decimal numbers greater than zero".into(),
+                ))
+            },
+        )
+    }
+}
+
+impl super::settings::ConnectorRequestSettings {
+    pub fn validate(&self) -> Result<(), ApplicationError> {
+        use common_utils::fp_utils::when;
+
+        when(self.timeout == 0, || {
+            Err(ApplicationError::InvalidConfigurationValueError(
+                "connector request timeout must be greater than zero".into(),
+            ))
+        })?;
+
+        when(self.sync_cache_ttl < 0, || {
+            Err(ApplicationError::InvalidConfigurationValueError(
+                "connector sync cache TTL must not be negative".into(),
+            ))
+        })?;
+
+        self.rate_limits
+            .iter()
+            .try_for_each(|(connector, rate_limit)| {
+                when(
+                    rate_limit.requests_per_second == 0 || rate_limit.burst == 0,
+                    || {
+                        Err(ApplicationError::InvalidConfigurationValueError(format!(
+                            "requests_per_second and burst of the rate limit of {connector} must be greater than zero"
+                        )))
+                    },
+                )
+            })?;
+
+        self.signing_profiles
+            .iter()
+            .try_for_each(|(connector, signing_profile)| {
+                when(
+                    signing_profile.signature_header.is_default_or_empty(),
+                    || {
+                        Err(ApplicationError::InvalidConfigurationValueError(format!(
+                        "signature header of the signing profile of {connector} must not be empty"
+                    )))
+                    },
+                )
+            })
+    }
+}
+
+impl super::settings::PaymentLinkSettings {
+    pub fn validate(&self) -> Result<(), ApplicationError> {
+        use common_utils::fp_utils::when;
+
+        if !self.enabled {
+            return Ok(());
+        }
+
+        when(
+            self.sdk_url.is_default_or_empty() || self.signing_key.is_default_or_empty(),
+            || {
+                Err(ApplicationError::InvalidConfigurationValueError(
+                    "SDK URL and signing key must not be empty when payment links are enabled"
+                        .into(),
+                ))
+            },
+        )?;
+
+        when(
+            self.default_expiry <= 0 || self.default_expiry > self.max_expiry,
+            || {
+                Err(ApplicationError::InvalidConfigurationValueError(
+                    "default payment link expiry must be greater than zero and not exceed the maximum expiry".into(),
+                ))
+            },
+        )
+    }
+}
+
+impl super::settings::DataResidencySettings {
+    pub fn validate(&self) -> Result<(), ApplicationError> {
+        use common_utils::fp_utils::when;
+
+        self.regions
+            .iter()
+            .try_for_each(|(region, regional_storage)| {
+                when(region.is_empty() || region.len() > 64, || {
+                    Err(ApplicationError::InvalidConfigurationValueError(
+                        "data residency region names must be between 1 and 64 characters long"
+                            .into(),
+                    ))
+                })?;
+                regional_storage.master_database.validate()?;
+                #[cfg(feature = "olap")]
+                regional_storage.replica_database.validate()?;
+                regional_storage.locker.validate()
+            })
+    }
+}
+
+impl super::settings::SchemaValidationSettings {
+    pub fn validate(&self) -> Result<(), ApplicationError> {
+        use common_utils::fp_utils::when;
+
+        when(
+            self.enabled && !crate::utils::schema_validation::is_specification_available(),
+            || {
+                Err(ApplicationError::InvalidConfigurationValueError(
+                    "schema validation cannot be enabled without a valid OpenAPI specification"
+                        .into(),
+                ))
+            },
+        )
+    }
+}
@@ -9,12 +9,18 @@ use external_services::kms::decrypt::KmsDecrypt;
 #[cfg(not(feature = "kms"))]
 use masking::PeekInterface;
 
-use crate::{configs::settings::Database, errors};
+use crate::{configs::settings::Database, errors, services::RegionalPools};
 
 pub type PgPool = bb8::Pool<async_bb8_diesel::ConnectionManager<PgConnection>>;
 
 pub type PgPooledConn = async_bb8_diesel::Connection<PgConnection>;
 
+tokio::task_local! {
+    /// The storage region of the merchant on whose behalf the current task runs, if the merchant is
+    /// pinned to a region
+    static STORAGE_REGION: Option<String>;
+}
+
 #[derive(Debug)]
 struct TestTransaction;
 
@@ -117,3 +123,134 @@ pub async fn pg_connection_write(
         .into_report()
         .change_context(errors::StorageError::DatabaseConnectionError)
 }
+
+/// Runs the future with the customers and payments it accesses being stored in the given storage
+/// region, or in the default databases if no region is given.
+pub async fn with_storage_region<F>(storage_region: Option<String>, future: F) -> F::Output
+where
+    F: std::future::Future,
+{
+    STORAGE_REGION.scope(storage_region, future).await
+}
+
+/// Returns the storage region of the merchant on whose behalf the current task runs.
+pub fn current_storage_region() -> Option<String> {
+    STORAGE_REGION.try_with(Clone::clone).ok().flatten()
+}
+
+/// Binds the future to the storage region of the current task. Spawned tasks do not inherit the
+/// storage region of the task spawning them, so futures must be bound to the region before being
+/// spawned.
+pub fn in_current_storage_region<F>(future: F) -> impl std::future::Future<Output = F::Output>
+where
+    F: std::future::Future,
+{
+    with_storage_region(current_storage_region(), future)
+}
+
+fn get_regional_pools(
+    store: &crate::services::Store,
+) -> errors::CustomResult<Option<&RegionalPools>, errors::StorageError> {
+    let Some(storage_region) = current_storage_region() else {
+        return Ok(None);
+    };
+
+    // The data of merchants pinned to a region must never be stored outside the region, so a
+    // region without databases is an error rather than a fallback to the default databases
+    store
+        .regional_pools
+        .get(&storage_region)
+        .map(Some)
+        .ok_or(errors::StorageError::DatabaseConnectionError)
+        .into_report()
+        .attach_printable_lazy(|| {
+            format!("No databases configured for the storage region {storage_region}")
+        })
+}
+
+/// Returns a connection for reading customers and payments, from the databases of the storage
+/// region of the current merchant.
+pub async fn pg_regional_connection_read(
+    store: &crate::services::Store,
+) -> errors::CustomResult<
+    PooledConnection<'_, async_bb8_diesel::ConnectionManager<PgConnection>>,
+    errors::StorageError,
+> {
+    let Some(regional_pools) = get_regional_pools(store)? else {
+        return pg_connection_read(store).await;
+    };
+
+    #[cfg(all(feature = "olap", not(feature = "oltp")))]
+    let pool = &regional_pools.replica_pool;
+
+    #[cfg(any(
+        all(not(feature = "olap"), feature = "oltp"),
+        all(feature = "olap", feature = "oltp"),
+        all(not(feature = "olap"), not(feature = "oltp"))
+    ))]
+    let pool = &regional_pools.master_pool;
+
+    pool.get()
+        .await
+        .into_report()
+        .change_context(errors::StorageError::DatabaseConnectionError)
+}
+
+/// Returns a connection for writing customers and payments, to the databases of the storage
+/// region of the current merchant.
+pub async fn pg_regional_connection_write(
+    store: &crate::services::Store,
+) -> errors::CustomResult<
+    PooledConnection<'_, async_bb8_diesel::ConnectionManager<PgConnection>>,
+    errors::StorageError,
+> {
+    let Some(regional_pools) = get_regional_pools(store)? else {
+        return pg_connection_write(store).await;
+    };
+
+    regional_pools
+        .master_pool
+        .get()
+        .await
+        .into_report()
+        .change_context(errors::StorageError::DatabaseConnectionError)
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+
+    #[tokio::test]
+    async fn test_storage_region_scopes() {
+        assert_eq!(current_storage_region(), None);
+
+        let storage_region = with_storage_region(Some("eu".to_string()), async {
+            let outer = current_storage_region();
+            let inner = with_storage_region(None, async { current_storage_region() }).await;
+            (outer, inner, current_storage_region())
+        })
+        .await;
+        assert_eq!(
+            storage_region,
+            (Some("eu".to_string()), None, Some("eu".to_string()))
+        );
+
+        assert_eq!(current_storage_region(), None);
+    }
+
+    #[tokio::test]
+    async fn test_spawned_tasks_storage_region() {
+        let (unbound, bound) = with_storage_region(Some("eu".to_string()), async {
+            let unbound = tokio::spawn(async { current_storage_region() });
+            let bound = tokio::spawn(in_current_storage_region(async {
+                current_storage_region()
+            }));
+            (unbound.await.unwrap(), bound.await.unwrap())
+        })
+        .await;
+
+        assert_eq!(unbound, None);
+        assert_eq!(bound, Some("eu".to_string()));
+    }
+}
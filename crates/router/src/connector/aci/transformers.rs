@@ -370,6 +370,7 @@ impl TryFrom<&types::PaymentsAuthorizeRouterData> for AciPaymentsRequest {
             | api::PaymentMethodData::BankDebit(_)
             | api::PaymentMethodData::BankTransfer(_)
             | api::PaymentMethodData::Reward(_)
+            | api::PaymentMethodData::CardPresent(_)
             | api::PaymentMethodData::GiftCard(_)
             | api::PaymentMethodData::CardRedirect(_)
             | api::PaymentMethodData::Upi(_)
@@ -780,6 +781,7 @@ impl<F> TryFrom<types::RefundsResponseRouterData<F, AciRefundResponse>>
                 refund_status: enums::RefundStatus::from(AciRefundStatus::from_str(
                     &item.response.result.code,
                 )?),
+                settled_amount: None,
             }),
             ..item.data
         })
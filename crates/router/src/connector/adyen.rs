@@ -10,6 +10,7 @@ use ring::hmac;
 use router_env::{instrument, tracing};
 
 use self::transformers as adyen;
+use super::utils as connector_utils;
 use crate::{
     configs::settings,
     consts,
@@ -17,7 +18,6 @@ use crate::{
         self,
         errors::{self, CustomResult},
     },
-    db::StorageInterface,
     headers, logger, routes,
     services::{
         self,
@@ -26,12 +26,14 @@ use crate::{
     types::{
         self,
         api::{self, ConnectorCommon},
-        domain,
         transformers::ForeignFrom,
     },
     utils::{self, crypto, ByteSliceExt, BytesExt, OptionExt},
 };
 
+/// Adyen accepts idempotency keys of up to 64 characters.
+const ADYEN_IDEMPOTENCY_KEY_MAX_LENGTH: usize = 64;
+
 #[derive(Debug, Clone)]
 pub struct Adyen;
 
@@ -80,6 +82,7 @@ impl api::PaymentCapture for Adyen {}
 impl api::PreVerify for Adyen {}
 impl api::ConnectorAccessToken for Adyen {}
 impl api::PaymentToken for Adyen {}
+impl api::PaymentMethodAccountUpdater for Adyen {}
 
 impl
     services::ConnectorIntegration<
@@ -239,6 +242,10 @@ impl
         )];
         let mut api_key = self.get_auth_header(&req.connector_auth_type)?;
         header.append(&mut api_key);
+        header.append(&mut connector_utils::get_idempotency_key_header(
+            req.idempotency_key.as_ref(),
+            ADYEN_IDEMPOTENCY_KEY_MAX_LENGTH,
+        )?);
         Ok(header)
     }
 
@@ -553,6 +560,10 @@ impl
         )];
         let mut api_key = self.get_auth_header(&req.connector_auth_type)?;
         header.append(&mut api_key);
+        header.append(&mut connector_utils::get_idempotency_key_header(
+            req.idempotency_key.as_ref(),
+            ADYEN_IDEMPOTENCY_KEY_MAX_LENGTH,
+        )?);
         Ok(header)
     }
 
@@ -755,6 +766,10 @@ impl
         )];
         let mut api_key = self.get_auth_header(&req.connector_auth_type)?;
         header.append(&mut api_key);
+        header.append(&mut connector_utils::get_idempotency_key_header(
+            req.idempotency_key.as_ref(),
+            ADYEN_IDEMPOTENCY_KEY_MAX_LENGTH,
+        )?);
         Ok(header)
     }
 
@@ -834,6 +849,95 @@ impl
     }
 }
 
+impl
+    services::ConnectorIntegration<
+        api::AccountUpdate,
+        types::AccountUpdaterRequestData,
+        types::AccountUpdaterResponseData,
+    > for Adyen
+{
+    fn get_headers(
+        &self,
+        req: &types::AccountUpdaterRouterData,
+        _connectors: &settings::Connectors,
+    ) -> CustomResult<Vec<(String, request::Maskable<String>)>, errors::ConnectorError> {
+        let mut header = vec![(
+            headers::CONTENT_TYPE.to_string(),
+            types::AccountUpdaterType::get_content_type(self)
+                .to_string()
+                .into(),
+        )];
+        let mut api_key = self.get_auth_header(&req.connector_auth_type)?;
+        header.append(&mut api_key);
+        Ok(header)
+    }
+
+    fn get_url(
+        &self,
+        _req: &types::AccountUpdaterRouterData,
+        connectors: &settings::Connectors,
+    ) -> CustomResult<String, errors::ConnectorError> {
+        // The card is verified with a zero value authorisation, whose response holds the update
+        // reported by the Real-time Account Updater of Adyen
+        Ok(format!("{}{}", self.base_url(connectors), "v68/payments"))
+    }
+
+    fn get_request_body(
+        &self,
+        req: &types::AccountUpdaterRouterData,
+    ) -> CustomResult<Option<types::RequestBody>, errors::ConnectorError> {
+        let connector_req = adyen::AdyenAccountUpdaterRequest::try_from(req)?;
+        let adyen_req = types::RequestBody::log_and_get_request_body(
+            &connector_req,
+            utils::Encode::<adyen::AdyenAccountUpdaterRequest>::encode_to_string_of_json,
+        )
+        .change_context(errors::ConnectorError::RequestEncodingFailed)?;
+        Ok(Some(adyen_req))
+    }
+
+    fn build_request(
+        &self,
+        req: &types::AccountUpdaterRouterData,
+        connectors: &settings::Connectors,
+    ) -> CustomResult<Option<services::Request>, errors::ConnectorError> {
+        Ok(Some(
+            services::RequestBuilder::new()
+                .method(services::Method::Post)
+                .url(&types::AccountUpdaterType::get_url(self, req, connectors)?)
+                .attach_default_headers()
+                .headers(types::AccountUpdaterType::get_headers(
+                    self, req, connectors,
+                )?)
+                .body(types::AccountUpdaterType::get_request_body(self, req)?)
+                .build(),
+        ))
+    }
+
+    fn handle_response(
+        &self,
+        data: &types::AccountUpdaterRouterData,
+        res: types::Response,
+    ) -> CustomResult<types::AccountUpdaterRouterData, errors::ConnectorError> {
+        let response: adyen::AdyenAccountUpdaterResponse = res
+            .response
+            .parse_struct("AdyenAccountUpdaterResponse")
+            .change_context(errors::ConnectorError::ResponseDeserializationFailed)?;
+        types::RouterData::try_from(types::ResponseRouterData {
+            response,
+            data: data.clone(),
+            http_code: res.status_code,
+        })
+        .change_context(errors::ConnectorError::ResponseHandlingFailed)
+    }
+
+    fn get_error_response(
+        &self,
+        res: types::Response,
+    ) -> CustomResult<types::ErrorResponse, errors::ConnectorError> {
+        self.build_error_response(res)
+    }
+}
+
 impl api::Payouts for Adyen {}
 #[cfg(feature = "payouts")]
 impl api::PayoutCancel for Adyen {}
@@ -843,6 +947,8 @@ impl api::PayoutCreate for Adyen {}
 impl api::PayoutEligibility for Adyen {}
 #[cfg(feature = "payouts")]
 impl api::PayoutFulfill for Adyen {}
+#[cfg(feature = "payouts")]
+impl api::PayoutUploadFile for Adyen {}
 
 #[cfg(feature = "payouts")]
 impl services::ConnectorIntegration<api::PoCancel, types::PayoutsData, types::PayoutsResponseData>
@@ -877,6 +983,10 @@ impl services::ConnectorIntegration<api::PoCancel, types::PayoutsData, types::Pa
             auth.review_key.unwrap_or(auth.api_key).into_masked(),
         )];
         header.append(&mut api_key);
+        header.append(&mut connector_utils::get_idempotency_key_header(
+            req.idempotency_key.as_ref(),
+            ADYEN_IDEMPOTENCY_KEY_MAX_LENGTH,
+        )?);
         Ok(header)
     }
 
@@ -962,6 +1072,10 @@ impl services::ConnectorIntegration<api::PoCreate, types::PayoutsData, types::Pa
         )];
         let mut api_key = self.get_auth_header(&req.connector_auth_type)?;
         header.append(&mut api_key);
+        header.append(&mut connector_utils::get_idempotency_key_header(
+            req.idempotency_key.as_ref(),
+            ADYEN_IDEMPOTENCY_KEY_MAX_LENGTH,
+        )?);
         Ok(header)
     }
 
@@ -1151,6 +1265,10 @@ impl services::ConnectorIntegration<api::PoFulfill, types::PayoutsData, types::P
             },
         )];
         header.append(&mut api_key);
+        header.append(&mut connector_utils::get_idempotency_key_header(
+            req.idempotency_key.as_ref(),
+            ADYEN_IDEMPOTENCY_KEY_MAX_LENGTH,
+        )?);
         Ok(header)
     }
 
@@ -1210,6 +1328,160 @@ impl services::ConnectorIntegration<api::PoFulfill, types::PayoutsData, types::P
     }
 }
 
+#[cfg(feature = "payouts")]
+impl
+    services::ConnectorIntegration<
+        api::PoUploadFile,
+        types::UploadFileRequestData,
+        types::UploadFileResponse,
+    > for Adyen
+{
+    fn get_url(
+        &self,
+        _req: &types::PayoutUploadFileRouterData,
+        connectors: &settings::Connectors,
+    ) -> CustomResult<String, errors::ConnectorError> {
+        Ok(format!("{}documents", connectors.adyen.third_base_url))
+    }
+
+    fn get_headers(
+        &self,
+        req: &types::PayoutUploadFileRouterData,
+        _connectors: &settings::Connectors,
+    ) -> CustomResult<Vec<(String, request::Maskable<String>)>, errors::ConnectorError> {
+        let mut header = vec![(
+            headers::CONTENT_TYPE.to_string(),
+            types::PayoutUploadFileType::get_content_type(self)
+                .to_string()
+                .into(),
+        )];
+        let mut api_key = self.get_auth_header(&req.connector_auth_type)?;
+        header.append(&mut api_key);
+        Ok(header)
+    }
+
+    fn get_request_body(
+        &self,
+        req: &types::PayoutUploadFileRouterData,
+    ) -> CustomResult<Option<types::RequestBody>, errors::ConnectorError> {
+        let connector_req = adyen::AdyenDocumentRequest::try_from(req)?;
+        let adyen_req = types::RequestBody::log_and_get_request_body(
+            &connector_req,
+            utils::Encode::<adyen::AdyenDocumentRequest>::encode_to_string_of_json,
+        )
+        .change_context(errors::ConnectorError::RequestEncodingFailed)?;
+        Ok(Some(adyen_req))
+    }
+
+    fn build_request(
+        &self,
+        req: &types::PayoutUploadFileRouterData,
+        connectors: &settings::Connectors,
+    ) -> CustomResult<Option<services::Request>, errors::ConnectorError> {
+        let request = services::RequestBuilder::new()
+            .method(services::Method::Post)
+            .url(&types::PayoutUploadFileType::get_url(
+                self, req, connectors,
+            )?)
+            .attach_default_headers()
+            .headers(types::PayoutUploadFileType::get_headers(
+                self, req, connectors,
+            )?)
+            .body(types::PayoutUploadFileType::get_request_body(self, req)?)
+            .build();
+
+        Ok(Some(request))
+    }
+
+    #[instrument(skip_all)]
+    fn handle_response(
+        &self,
+        data: &types::PayoutUploadFileRouterData,
+        res: types::Response,
+    ) -> CustomResult<types::PayoutUploadFileRouterData, errors::ConnectorError> {
+        let response: adyen::AdyenDocumentResponse = res
+            .response
+            .parse_struct("AdyenDocumentResponse")
+            .change_context(errors::ConnectorError::ResponseDeserializationFailed)?;
+        Ok(types::PayoutUploadFileRouterData {
+            response: Ok(types::UploadFileResponse {
+                provider_file_id: response.id,
+            }),
+            ..data.clone()
+        })
+    }
+
+    fn get_error_response(
+        &self,
+        res: types::Response,
+    ) -> CustomResult<types::ErrorResponse, errors::ConnectorError> {
+        let response: adyen::AdyenProblemDetailsResponse = res
+            .response
+            .parse_struct("AdyenProblemDetailsResponse")
+            .change_context(errors::ConnectorError::ResponseDeserializationFailed)?;
+        Ok(types::ErrorResponse {
+            status_code: res.status_code,
+            code: response.error_code,
+            message: response.title,
+            reason: response.detail,
+        })
+    }
+}
+
+impl api::FileUpload for Adyen {
+    fn validate_file_upload(
+        &self,
+        purpose: api::FilePurpose,
+        file_size: i32,
+        file_type: mime::Mime,
+    ) -> CustomResult<(), errors::ConnectorError> {
+        match purpose {
+            api::FilePurpose::DisputeEvidence => {
+                Err(errors::ConnectorError::FileValidationFailed {
+                    reason: "file uploads for disputes are not supported".to_owned(),
+                })?
+            }
+            api::FilePurpose::PayoutKyc => {
+                let supported_file_types = vec!["image/jpeg", "image/png", "application/pdf"];
+                // 4 Megabytes (MB)
+                if file_size > 4000000 {
+                    Err(errors::ConnectorError::FileValidationFailed {
+                        reason: "file_size exceeded the max file size of 4MB".to_owned(),
+                    })?
+                }
+                if !supported_file_types.contains(&file_type.to_string().as_str()) {
+                    Err(errors::ConnectorError::FileValidationFailed {
+                        reason: "file_type does not match JPEG, JPG, PNG, or PDF format".to_owned(),
+                    })?
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl api::UploadFile for Adyen {}
+
+impl
+    services::ConnectorIntegration<
+        api::Upload,
+        types::UploadFileRequestData,
+        types::UploadFileResponse,
+    > for Adyen
+{
+}
+
+impl api::RetrieveFile for Adyen {}
+
+impl
+    services::ConnectorIntegration<
+        api::Retrieve,
+        types::RetrieveFileRequestData,
+        types::RetrieveFileResponse,
+    > for Adyen
+{
+}
+
 impl api::Refund for Adyen {}
 impl api::RefundExecute for Adyen {}
 impl api::RefundSync for Adyen {}
@@ -1230,6 +1502,10 @@ impl services::ConnectorIntegration<api::Execute, types::RefundsData, types::Ref
         )];
         let mut api_header = self.get_auth_header(&req.connector_auth_type)?;
         header.append(&mut api_header);
+        header.append(&mut connector_utils::get_idempotency_key_header(
+            req.idempotency_key.as_ref(),
+            ADYEN_IDEMPOTENCY_KEY_MAX_LENGTH,
+        )?);
         Ok(header)
     }
 
@@ -1378,28 +1654,17 @@ impl api::IncomingWebhook for Adyen {
         Ok(message.into_bytes())
     }
 
-    async fn verify_webhook_source(
+    fn verify_webhook_source_with_secret(
         &self,
-        db: &dyn StorageInterface,
         request: &api::IncomingWebhookRequestDetails<'_>,
         merchant_id: &str,
-        connector_label: &str,
-        key_store: &domain::MerchantKeyStore,
+        secret: &[u8],
     ) -> CustomResult<bool, errors::ConnectorError> {
         let signature = self
             .get_webhook_source_verification_signature(request)
             .change_context(errors::ConnectorError::WebhookSourceVerificationFailed)?;
-        let secret = self
-            .get_webhook_source_verification_merchant_secret(
-                db,
-                merchant_id,
-                connector_label,
-                key_store,
-            )
-            .await
-            .change_context(errors::ConnectorError::WebhookSourceVerificationFailed)?;
         let message = self
-            .get_webhook_source_verification_message(request, merchant_id, &secret)
+            .get_webhook_source_verification_message(request, merchant_id, secret)
             .change_context(errors::ConnectorError::WebhookSourceVerificationFailed)?;
 
         let raw_key = hex::decode(secret)
@@ -1437,6 +1702,12 @@ impl api::IncomingWebhook for Adyen {
                 ),
             ));
         }
+        #[cfg(feature = "payouts")]
+        if adyen::is_payout_event(&notif.event_code) {
+            return Ok(api_models::webhooks::ObjectReferenceId::PayoutId(
+                api_models::webhooks::PayoutIdType::PayoutId(notif.merchant_reference),
+            ));
+        }
         Err(errors::ConnectorError::WebhookReferenceIdNotFound).into_report()
     }
 
@@ -1468,6 +1739,16 @@ impl api::IncomingWebhook for Adyen {
         Ok(res_json)
     }
 
+    fn get_webhook_refund_settled_amount(
+        &self,
+        request: &api::IncomingWebhookRequestDetails<'_>,
+    ) -> CustomResult<Option<i64>, errors::ConnectorError> {
+        let notif = get_webhook_object_from_body(request.body)
+            .change_context(errors::ConnectorError::WebhookBodyDecodingFailed)?;
+
+        Ok(adyen::is_refund_event(&notif.event_code).then_some(notif.amount.value))
+    }
+
     fn get_webhook_api_response(
         &self,
         _request: &api::IncomingWebhookRequestDetails<'_>,
@@ -1,6 +1,8 @@
 #[cfg(feature = "payouts")]
 use api_models::payouts::PayoutMethodData;
 use api_models::{enums, payments, webhooks};
+#[cfg(feature = "payouts")]
+use base64::Engine;
 use cards::CardNumber;
 use error_stack::ResultExt;
 use masking::PeekInterface;
@@ -129,6 +131,7 @@ pub struct AdyenPaymentRequest<'a> {
     country_code: Option<api_enums::CountryAlpha2>,
     line_items: Option<Vec<LineItem>>,
     channel: Option<Channel>,
+    mcc: Option<String>,
 }
 
 #[derive(Debug, Serialize)]
@@ -2219,6 +2222,7 @@ impl<'a>
             shopper_reference,
             store_payment_method,
             channel: None,
+            mcc: item.get_optional_merchant_category_code(),
         })
     }
 }
@@ -2259,6 +2263,7 @@ impl<'a> TryFrom<(&types::PaymentsAuthorizeRouterData, &api::Card)> for AdyenPay
             shopper_reference,
             store_payment_method,
             channel: None,
+            mcc: item.get_optional_merchant_category_code(),
         })
     }
 }
@@ -2309,6 +2314,7 @@ impl<'a>
             shopper_reference: None,
             store_payment_method: None,
             channel: None,
+            mcc: item.get_optional_merchant_category_code(),
         };
         Ok(request)
     }
@@ -2360,6 +2366,7 @@ impl<'a>
             shopper_reference: None,
             store_payment_method: None,
             channel: None,
+            mcc: item.get_optional_merchant_category_code(),
         };
         Ok(request)
     }
@@ -2407,6 +2414,7 @@ impl<'a>
             shopper_reference: None,
             store_payment_method: None,
             channel: None,
+            mcc: item.get_optional_merchant_category_code(),
         };
         Ok(request)
     }
@@ -2453,6 +2461,7 @@ impl<'a>
             shopper_reference: None,
             store_payment_method: None,
             channel: None,
+            mcc: item.get_optional_merchant_category_code(),
             social_security_number: None,
         };
         Ok(request)
@@ -2507,6 +2516,7 @@ impl<'a>
             shopper_reference,
             store_payment_method,
             channel: None,
+            mcc: item.get_optional_merchant_category_code(),
         })
     }
 }
@@ -2594,6 +2604,7 @@ impl<'a> TryFrom<(&types::PaymentsAuthorizeRouterData, &api::WalletData)>
             shopper_reference,
             store_payment_method,
             channel,
+            mcc: item.get_optional_merchant_category_code(),
         })
     }
 }
@@ -2644,6 +2655,7 @@ impl<'a> TryFrom<(&types::PaymentsAuthorizeRouterData, &api::PayLaterData)>
             shopper_reference,
             store_payment_method,
             channel: None,
+            mcc: item.get_optional_merchant_category_code(),
         })
     }
 }
@@ -2697,6 +2709,7 @@ impl<'a>
             shopper_reference: None,
             store_payment_method: None,
             channel: None,
+            mcc: item.get_optional_merchant_category_code(),
             social_security_number: None,
         })
     }
@@ -3393,6 +3406,7 @@ impl<F> TryFrom<types::RefundsResponseRouterData<F, AdyenRefundResponse>>
                 // through refund notification webhook
                 // For more info: https://docs.adyen.com/online-payments/refund
                 refund_status: storage_enums::RefundStatus::Pending,
+                settled_amount: None,
             }),
             ..item.data
         })
@@ -3409,6 +3423,174 @@ pub struct ErrorResponse {
     pub psp_reference: Option<String>,
 }
 
+/// The currency of the zero value authorisations verifying the cards whose updates are requested,
+/// which is not charged to the customer
+const ACCOUNT_UPDATER_CURRENCY: &str = "EUR";
+
+/// Zero value authorisation of a saved card, requesting the update of the card reported by the
+/// issuer through the Real-time Account Updater
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AdyenAccountUpdaterRequest {
+    amount: Amount,
+    merchant_account: Secret<String>,
+    payment_method: AdyenCard,
+    reference: String,
+    shopper_interaction: AdyenShopperInteraction,
+    recurring_processing_model: AdyenRecurringModel,
+}
+
+impl TryFrom<&types::AccountUpdaterRouterData> for AdyenAccountUpdaterRequest {
+    type Error = Error;
+    fn try_from(item: &types::AccountUpdaterRouterData) -> Result<Self, Self::Error> {
+        let auth_type = AdyenAuthType::try_from(&item.connector_auth_type)?;
+        Ok(Self {
+            amount: Amount {
+                currency: ACCOUNT_UPDATER_CURRENCY.to_string(),
+                value: 0,
+            },
+            merchant_account: auth_type.merchant_account,
+            payment_method: AdyenCard {
+                payment_type: PaymentType::Scheme,
+                number: item.request.card_number.clone(),
+                expiry_month: item.request.card_exp_month.clone(),
+                expiry_year: item.request.card_exp_year.clone(),
+                cvc: None,
+                brand: None,
+                network_payment_reference: None,
+            },
+            reference: item.connector_request_reference_id.clone(),
+            shopper_interaction: AdyenShopperInteraction::ContinuedAuthentication,
+            recurring_processing_model: AdyenRecurringModel::CardOnFile,
+        })
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AdyenAccountUpdaterResponse {
+    result_code: AdyenStatus,
+    refusal_reason: Option<String>,
+    refusal_reason_code: Option<String>,
+    additional_data: Option<AdyenAccountUpdaterAdditionalData>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AdyenAccountUpdaterAdditionalData {
+    /// The update of the card reported by the issuer, such as `CardExpiryChanged`,
+    /// `CardChanged`, `CloseAccount` or `ContactCardAccountHolder`
+    realtime_account_updater_status: Option<String>,
+    /// The expiry date of the card, in the `M/YYYY` format
+    expiry_date: Option<Secret<String>>,
+}
+
+/// Returns the update of the card reported by the Real-time Account Updater. The number of a
+/// replaced card is not returned by Adyen, so a replaced card is reported as not updated, for the
+/// merchant to be notified of the expiry of the saved card.
+fn get_account_update(
+    additional_data: AdyenAccountUpdaterAdditionalData,
+) -> types::AccountUpdaterResponseData {
+    match additional_data.realtime_account_updater_status.as_deref() {
+        Some("CardExpiryChanged") => additional_data
+            .expiry_date
+            .and_then(|expiry_date| {
+                let (card_exp_month, card_exp_year) = expiry_date.peek().split_once('/')?;
+                Some(types::AccountUpdaterResponseData::ExpiryUpdated {
+                    card_exp_month: Secret::new(format!("{:0>2}", card_exp_month.trim())),
+                    card_exp_year: Secret::new(card_exp_year.trim().to_string()),
+                })
+            })
+            .unwrap_or(types::AccountUpdaterResponseData::NoUpdate),
+        Some("CloseAccount") => types::AccountUpdaterResponseData::AccountClosed,
+        _ => types::AccountUpdaterResponseData::NoUpdate,
+    }
+}
+
+impl TryFrom<types::AccountUpdaterResponseRouterData<AdyenAccountUpdaterResponse>>
+    for types::AccountUpdaterRouterData
+{
+    type Error = Error;
+    fn try_from(
+        item: types::AccountUpdaterResponseRouterData<AdyenAccountUpdaterResponse>,
+    ) -> Result<Self, Self::Error> {
+        // A refused authorisation still reports the update of the card, as when the account of
+        // the card was closed
+        let response = match item.response.result_code {
+            AdyenStatus::Authorised | AdyenStatus::Refused => Ok(get_account_update(
+                item.response.additional_data.unwrap_or_default(),
+            )),
+            _ => Err(types::ErrorResponse {
+                code: item
+                    .response
+                    .refusal_reason_code
+                    .unwrap_or_else(|| consts::NO_ERROR_CODE.to_string()),
+                message: item
+                    .response
+                    .refusal_reason
+                    .clone()
+                    .unwrap_or_else(|| consts::NO_ERROR_MESSAGE.to_string()),
+                reason: item.response.refusal_reason,
+                status_code: item.http_code,
+            }),
+        };
+        Ok(Self {
+            response,
+            ..item.data
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+
+    fn get_additional_data(value: serde_json::Value) -> AdyenAccountUpdaterAdditionalData {
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[test]
+    fn test_get_account_update() {
+        let account_update = get_account_update(get_additional_data(serde_json::json!({
+            "realtimeAccountUpdaterStatus": "CardExpiryChanged",
+            "expiryDate": "3/2030",
+        })));
+        assert!(matches!(
+            account_update,
+            types::AccountUpdaterResponseData::ExpiryUpdated {
+                ref card_exp_month,
+                ref card_exp_year,
+            } if card_exp_month.peek() == "03" && card_exp_year.peek() == "2030"
+        ));
+
+        let account_update = get_account_update(get_additional_data(serde_json::json!({
+            "realtimeAccountUpdaterStatus": "CloseAccount",
+        })));
+        assert!(matches!(
+            account_update,
+            types::AccountUpdaterResponseData::AccountClosed
+        ));
+
+        // The number of a replaced card is not returned
+        let account_update = get_account_update(get_additional_data(serde_json::json!({
+            "realtimeAccountUpdaterStatus": "CardChanged",
+        })));
+        assert!(matches!(
+            account_update,
+            types::AccountUpdaterResponseData::NoUpdate
+        ));
+
+        let account_update = get_account_update(get_additional_data(serde_json::json!({
+            "realtimeAccountUpdaterStatus": "CardExpiryChanged",
+        })));
+        assert!(matches!(
+            account_update,
+            types::AccountUpdaterResponseData::NoUpdate
+        ));
+    }
+}
+
 // #[cfg(test)]
 // mod test_adyen_transformers {
 //     use super::*;
@@ -3468,6 +3650,14 @@ pub enum WebhookEventCode {
     SecondChargeback,
     PrearbitrationWon,
     PrearbitrationLost,
+    #[cfg(feature = "payouts")]
+    PayoutThirdparty,
+    #[cfg(feature = "payouts")]
+    PayoutDecline,
+    #[cfg(feature = "payouts")]
+    PayoutExpire,
+    #[cfg(feature = "payouts")]
+    PaidoutReversed,
     #[serde(other)]
     Unknown,
 }
@@ -3485,6 +3675,17 @@ pub fn is_refund_event(event_code: &WebhookEventCode) -> bool {
     )
 }
 
+#[cfg(feature = "payouts")]
+pub fn is_payout_event(event_code: &WebhookEventCode) -> bool {
+    matches!(
+        event_code,
+        WebhookEventCode::PayoutThirdparty
+            | WebhookEventCode::PayoutDecline
+            | WebhookEventCode::PayoutExpire
+            | WebhookEventCode::PaidoutReversed
+    )
+}
+
 pub fn is_chargeback_event(event_code: &WebhookEventCode) -> bool {
     matches!(
         event_code,
@@ -3519,6 +3720,14 @@ impl ForeignFrom<(WebhookEventCode, Option<DisputeStatus>)> for webhooks::Incomi
             }
             (WebhookEventCode::PrearbitrationWon, _) => Self::DisputeWon,
             (WebhookEventCode::PrearbitrationLost, _) => Self::DisputeLost,
+            #[cfg(feature = "payouts")]
+            (WebhookEventCode::PayoutThirdparty, _) => Self::PayoutSuccess,
+            #[cfg(feature = "payouts")]
+            (WebhookEventCode::PayoutDecline, _) | (WebhookEventCode::PayoutExpire, _) => {
+                Self::PayoutFailure
+            }
+            #[cfg(feature = "payouts")]
+            (WebhookEventCode::PaidoutReversed, _) => Self::PayoutReversed,
             (WebhookEventCode::Unknown, _) => Self::EventNotSupported,
         }
     }
@@ -3915,6 +4124,8 @@ impl<F> TryFrom<types::PayoutsResponseRouterData<F, AdyenPayoutResponse>>
                 status,
                 connector_payout_id: response.psp_reference,
                 payout_eligible,
+                connector_fee: None,
+                fx_margin: None,
             }),
             ..item.data
         })
@@ -3934,3 +4145,111 @@ impl ForeignFrom<AdyenStatus> for storage_enums::PayoutStatus {
         }
     }
 }
+
+/// The legal entity in the Legal Entity Management API which the KYC documents of payouts are
+/// uploaded for, configured in the metadata of the merchant connector account
+#[cfg(feature = "payouts")]
+#[derive(Debug, Deserialize)]
+pub struct AdyenPayoutKycMetadata {
+    pub legal_entity_id: Secret<String>,
+}
+
+#[cfg(feature = "payouts")]
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum AdyenDocumentType {
+    IdentityCard,
+    Passport,
+    DriversLicense,
+    ProofOfAddress,
+    BankStatement,
+}
+
+#[cfg(feature = "payouts")]
+impl From<api::KycDocumentType> for AdyenDocumentType {
+    fn from(document_type: api::KycDocumentType) -> Self {
+        match document_type {
+            api::KycDocumentType::IdentityCard => Self::IdentityCard,
+            api::KycDocumentType::Passport => Self::Passport,
+            api::KycDocumentType::DriversLicense => Self::DriversLicense,
+            api::KycDocumentType::ProofOfAddress => Self::ProofOfAddress,
+            api::KycDocumentType::BankStatement => Self::BankStatement,
+        }
+    }
+}
+
+#[cfg(feature = "payouts")]
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum AdyenDocumentOwnerType {
+    LegalEntity,
+}
+
+#[cfg(feature = "payouts")]
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AdyenDocumentOwner {
+    id: Secret<String>,
+    #[serde(rename = "type")]
+    owner_type: AdyenDocumentOwnerType,
+}
+
+#[cfg(feature = "payouts")]
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AdyenDocumentAttachment {
+    content: Secret<String>,
+}
+
+#[cfg(feature = "payouts")]
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AdyenDocumentRequest {
+    #[serde(rename = "type")]
+    document_type: AdyenDocumentType,
+    description: String,
+    attachments: Vec<AdyenDocumentAttachment>,
+    owner: AdyenDocumentOwner,
+}
+
+#[cfg(feature = "payouts")]
+impl TryFrom<&types::PayoutUploadFileRouterData> for AdyenDocumentRequest {
+    type Error = Error;
+    fn try_from(item: &types::PayoutUploadFileRouterData) -> Result<Self, Self::Error> {
+        let metadata: AdyenPayoutKycMetadata =
+            utils::to_connector_meta_from_secret(item.connector_meta_data.clone())?;
+        let document_type =
+            item.request
+                .document_type
+                .ok_or(errors::ConnectorError::MissingRequiredField {
+                    field_name: "document_type",
+                })?;
+        Ok(Self {
+            document_type: document_type.into(),
+            description: format!("KYC document for payout {}", item.request.file_key),
+            attachments: vec![AdyenDocumentAttachment {
+                content: Secret::new(consts::BASE64_ENGINE.encode(&item.request.file)),
+            }],
+            owner: AdyenDocumentOwner {
+                id: metadata.legal_entity_id,
+                owner_type: AdyenDocumentOwnerType::LegalEntity,
+            },
+        })
+    }
+}
+
+#[cfg(feature = "payouts")]
+#[derive(Debug, Deserialize)]
+pub struct AdyenDocumentResponse {
+    pub id: String,
+}
+
+/// Errors of the Legal Entity Management API, which are returned as problem details
+#[cfg(feature = "payouts")]
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AdyenProblemDetailsResponse {
+    pub error_code: String,
+    pub title: String,
+    pub detail: Option<String>,
+}
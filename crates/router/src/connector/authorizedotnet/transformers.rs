@@ -177,12 +177,94 @@ struct TransactionRequest {
     amount: f64,
     currency_code: String,
     payment: PaymentDetails,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    line_items: Option<LineItems>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tax: Option<ExtendedAmount>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    shipping: Option<ExtendedAmount>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    po_number: Option<String>,
     processing_options: Option<ProcessingOptions>,
     #[serde(skip_serializing_if = "Option::is_none")]
     subsequent_auth_information: Option<SubsequentAuthInformation>,
     authorization_indicator_type: Option<AuthorizationIndicator>,
 }
 
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct LineItems {
+    line_item: Vec<LineItem>,
+}
+
+// Level 3 fields follow the basic fields of the line item, in the order of the API documentation
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct LineItem {
+    item_id: String,
+    name: String,
+    description: String,
+    quantity: u16,
+    unit_price: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    unit_of_measure: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tax_amount: Option<f64>,
+    total_amount: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    commodity_code: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    product_code: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    discount_amount: Option<f64>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ExtendedAmount {
+    amount: f64,
+}
+
+// Maximum length of the identifier and the name of a line item
+const LINE_ITEM_ID_AND_NAME_MAX_LENGTH: usize = 31;
+
+fn get_line_items(
+    line_items: &[api_models::payments::L3LineItem],
+    currency: enums::Currency,
+) -> Result<LineItems, error_stack::Report<errors::ConnectorError>> {
+    let to_base_unit = |amount| utils::to_currency_base_unit_asf64(amount, currency);
+    let line_item = line_items
+        .iter()
+        .enumerate()
+        .map(|(index, line_item)| {
+            Ok(LineItem {
+                item_id: line_item
+                    .product_code
+                    .clone()
+                    .unwrap_or_else(|| (index + 1).to_string())
+                    .chars()
+                    .take(LINE_ITEM_ID_AND_NAME_MAX_LENGTH)
+                    .collect(),
+                name: line_item
+                    .description
+                    .chars()
+                    .take(LINE_ITEM_ID_AND_NAME_MAX_LENGTH)
+                    .collect(),
+                description: line_item.description.clone(),
+                quantity: line_item.quantity,
+                unit_price: to_base_unit(line_item.unit_amount)?,
+                unit_of_measure: line_item.unit_of_measure.clone(),
+                tax_amount: line_item.tax_amount.map(to_base_unit).transpose()?,
+                total_amount: to_base_unit(line_item.get_total_amount())?,
+                commodity_code: line_item.commodity_code.clone(),
+                product_code: line_item.product_code.clone(),
+                discount_amount: line_item.discount_amount.map(to_base_unit).transpose()?,
+            })
+        })
+        .collect::<Result<Vec<_>, error_stack::Report<errors::ConnectorError>>>()?;
+    Ok(LineItems { line_item })
+}
+
 #[derive(Debug, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ProcessingOptions {
@@ -275,11 +357,28 @@ impl TryFrom<&types::PaymentsAuthorizeRouterData> for CreateTransactionRequest {
             item.request.capture_method.map(|c| AuthorizationIndicator {
                 authorization_indicator: c.into(),
             });
+        let currency = item.request.currency;
+        let l2_l3_data = item.request.l2_l3_data.as_ref();
+        let get_extended_amount = |amount: Option<i64>| {
+            amount
+                .map(|amount| {
+                    utils::to_currency_base_unit_asf64(amount, currency)
+                        .map(|amount| ExtendedAmount { amount })
+                })
+                .transpose()
+        };
         let transaction_request = TransactionRequest {
             transaction_type: TransactionType::from(item.request.capture_method),
-            amount: utils::to_currency_base_unit_asf64(item.request.amount, item.request.currency)?,
+            amount: utils::to_currency_base_unit_asf64(item.request.amount, currency)?,
             payment: payment_details,
-            currency_code: item.request.currency.to_string(),
+            currency_code: currency.to_string(),
+            line_items: l2_l3_data
+                .and_then(|data| data.line_items.as_deref())
+                .map(|line_items| get_line_items(line_items, currency))
+                .transpose()?,
+            tax: get_extended_amount(l2_l3_data.and_then(|data| data.tax_amount))?,
+            shipping: get_extended_amount(l2_l3_data.and_then(|data| data.shipping_amount))?,
+            po_number: l2_l3_data.and_then(|data| data.customer_reference.clone()),
             processing_options,
             subsequent_auth_information,
             authorization_indicator_type,
@@ -729,6 +828,7 @@ impl<F> TryFrom<types::RefundsResponseRouterData<F, AuthorizedotnetRefundRespons
                 None => Ok(types::RefundsResponseData {
                     connector_refund_id: transaction_response.transaction_id.clone(),
                     refund_status,
+                    settled_amount: None,
                 }),
             },
             ..item.data
@@ -857,6 +957,7 @@ impl TryFrom<types::RefundsResponseRouterData<api::RSync, AuthorizedotnetSyncRes
                     response: Ok(types::RefundsResponseData {
                         connector_refund_id: transaction.transaction_id,
                         refund_status,
+                        settled_amount: None,
                     }),
                     ..item.data
                 })
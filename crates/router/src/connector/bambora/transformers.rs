@@ -504,6 +504,7 @@ impl TryFrom<types::RefundsResponseRouterData<api::Execute, RefundResponse>>
             response: Ok(types::RefundsResponseData {
                 connector_refund_id: item.response.id.to_string(),
                 refund_status,
+                settled_amount: None,
             }),
             ..item.data
         })
@@ -526,6 +527,7 @@ impl TryFrom<types::RefundsResponseRouterData<api::RSync, RefundResponse>>
             response: Ok(types::RefundsResponseData {
                 connector_refund_id: item.response.id.to_string(),
                 refund_status,
+                settled_amount: None,
             }),
             ..item.data
         })
@@ -22,7 +22,6 @@ use crate::{
         errors::{self, CustomResult},
         payments,
     },
-    db::StorageInterface,
     headers, logger,
     services::{
         self,
@@ -32,7 +31,6 @@ use crate::{
     types::{
         self,
         api::{self, ConnectorCommon, ConnectorCommonExt},
-        domain,
         storage::enums,
         ErrorResponse, Response,
     },
@@ -990,13 +988,11 @@ impl api::IncomingWebhook for Bluesnap {
         Ok(msg.into_bytes())
     }
 
-    async fn verify_webhook_source(
+    fn verify_webhook_source_with_secret(
         &self,
-        db: &dyn StorageInterface,
         request: &api::IncomingWebhookRequestDetails<'_>,
         merchant_id: &str,
-        connector_label: &str,
-        key_store: &domain::MerchantKeyStore,
+        secret: &[u8],
     ) -> CustomResult<bool, errors::ConnectorError> {
         let algorithm = self
             .get_webhook_source_verification_algorithm(request)
@@ -1005,15 +1001,7 @@ impl api::IncomingWebhook for Bluesnap {
         let signature = self
             .get_webhook_source_verification_signature(request)
             .change_context(errors::ConnectorError::WebhookSourceVerificationFailed)?;
-        let mut secret = self
-            .get_webhook_source_verification_merchant_secret(
-                db,
-                merchant_id,
-                connector_label,
-                key_store,
-            )
-            .await
-            .change_context(errors::ConnectorError::WebhookSourceVerificationFailed)?;
+        let mut secret = secret.to_vec();
         let mut message = self
             .get_webhook_source_verification_message(request, merchant_id, &secret)
             .change_context(errors::ConnectorError::WebhookSourceVerificationFailed)?;
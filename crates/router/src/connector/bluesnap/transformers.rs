@@ -302,6 +302,7 @@ impl TryFrom<&types::PaymentsAuthorizeRouterData> for BluesnapPaymentsRequest {
             | payments::PaymentMethodData::Upi(_)
             | payments::PaymentMethodData::CardRedirect(_)
             | payments::PaymentMethodData::Voucher(_)
+            | payments::PaymentMethodData::CardPresent(_)
             | payments::PaymentMethodData::GiftCard(_) => {
                 Err(errors::ConnectorError::NotImplemented(
                     utils::get_unimplemented_payment_method_error_message("bluesnap"),
@@ -771,6 +772,7 @@ impl TryFrom<types::RefundsResponseRouterData<api::RSync, BluesnapPaymentsRespon
                 refund_status: enums::RefundStatus::from(
                     item.response.processing_info.processing_status,
                 ),
+                settled_amount: None,
             }),
             ..item.data
         })
@@ -788,6 +790,7 @@ impl TryFrom<types::RefundsResponseRouterData<api::Execute, RefundResponse>>
             response: Ok(types::RefundsResponseData {
                 connector_refund_id: item.response.refund_transaction_id.to_string(),
                 refund_status: enums::RefundStatus::Pending,
+                settled_amount: None,
             }),
             ..item.data
         })
@@ -359,6 +359,7 @@ impl TryFrom<types::RefundsResponseRouterData<api::Execute, RefundResponse>>
             response: Ok(types::RefundsResponseData {
                 connector_refund_id: item.response.charge_id,
                 refund_status: get_refund_status(item.response.refund_status),
+                settled_amount: None,
             }),
             ..item.data
         })
@@ -420,6 +421,7 @@ impl TryFrom<types::RefundsResponseRouterData<api::RSync, BokuRsyncResponse>>
             response: Ok(types::RefundsResponseData {
                 connector_refund_id: item.response.refunds.refund.refund_id,
                 refund_status: get_refund_status(item.response.refunds.refund.refund_status),
+                settled_amount: None,
             }),
             ..item.data
         })
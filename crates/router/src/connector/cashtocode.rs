@@ -10,7 +10,6 @@ use crate::{
     configs::settings,
     connector::utils as conn_utils,
     core::errors::{self, CustomResult},
-    db::StorageInterface,
     headers,
     services::{
         self,
@@ -20,7 +19,6 @@ use crate::{
     types::{
         self,
         api::{self, ConnectorCommon, ConnectorCommonExt},
-        domain,
         storage::{self},
         ErrorResponse, Response,
     },
@@ -318,26 +316,15 @@ impl api::IncomingWebhook for Cashtocode {
         Ok(signature)
     }
 
-    async fn verify_webhook_source(
+    fn verify_webhook_source_with_secret(
         &self,
-        db: &dyn StorageInterface,
         request: &api::IncomingWebhookRequestDetails<'_>,
-        merchant_id: &str,
-        connector_label: &str,
-        key_store: &domain::MerchantKeyStore,
+        _merchant_id: &str,
+        secret: &[u8],
     ) -> CustomResult<bool, errors::ConnectorError> {
         let signature = self
             .get_webhook_source_verification_signature(request)
             .change_context(errors::ConnectorError::WebhookSourceVerificationFailed)?;
-        let secret = self
-            .get_webhook_source_verification_merchant_secret(
-                db,
-                merchant_id,
-                connector_label,
-                key_store,
-            )
-            .await
-            .change_context(errors::ConnectorError::WebhookSourceVerificationFailed)?;
         let secret_auth = String::from_utf8(secret.to_vec())
             .into_report()
             .change_context(errors::ConnectorError::WebhookSourceVerificationFailed)
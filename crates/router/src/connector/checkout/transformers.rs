@@ -101,6 +101,7 @@ impl TryFrom<&types::TokenizationRouterData> for TokenRequest {
             | api_models::payments::PaymentMethodData::Upi(_)
             | api_models::payments::PaymentMethodData::Voucher(_)
             | api_models::payments::PaymentMethodData::CardRedirect(_)
+            | api_models::payments::PaymentMethodData::CardPresent(_)
             | api_models::payments::PaymentMethodData::GiftCard(_) => {
                 Err(errors::ConnectorError::NotImplemented(
                     utils::get_unimplemented_payment_method_error_message("checkout"),
@@ -277,6 +278,7 @@ impl TryFrom<&types::PaymentsAuthorizeRouterData> for PaymentsRequest {
             | api_models::payments::PaymentMethodData::Upi(_)
             | api_models::payments::PaymentMethodData::Voucher(_)
             | api_models::payments::PaymentMethodData::CardRedirect(_)
+            | api_models::payments::PaymentMethodData::CardPresent(_)
             | api_models::payments::PaymentMethodData::GiftCard(_) => {
                 Err(errors::ConnectorError::NotImplemented(
                     utils::get_unimplemented_payment_method_error_message("checkout"),
@@ -562,6 +564,88 @@ impl TryFrom<&types::PaymentsCancelRouterData> for PaymentVoidRequest {
     }
 }
 
+/// Request to extend the validity of an authorization, by incrementing the authorization with a
+/// zero amount
+#[derive(Debug, Serialize)]
+pub struct PaymentReauthorizeRequest {
+    amount: i64,
+    reference: String,
+}
+
+impl TryFrom<&types::PaymentsReauthorizeRouterData> for PaymentReauthorizeRequest {
+    type Error = error_stack::Report<errors::ConnectorError>;
+    fn try_from(item: &types::PaymentsReauthorizeRouterData) -> Result<Self, Self::Error> {
+        Ok(Self {
+            amount: 0,
+            reference: item.connector_request_reference_id.clone(),
+        })
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PaymentReauthorizeResponse {
+    action_id: String,
+    approved: Option<bool>,
+    status: Option<String>,
+    response_code: Option<String>,
+    response_summary: Option<String>,
+}
+
+impl From<&PaymentReauthorizeResponse> for enums::AttemptStatus {
+    fn from(item: &PaymentReauthorizeResponse) -> Self {
+        if item.approved == Some(true) && item.status.as_deref() == Some("Authorized") {
+            Self::Authorized
+        } else {
+            Self::Failure
+        }
+    }
+}
+
+impl TryFrom<types::PaymentsReauthorizeResponseRouterData<PaymentReauthorizeResponse>>
+    for types::PaymentsReauthorizeRouterData
+{
+    type Error = error_stack::Report<errors::ConnectorError>;
+    fn try_from(
+        item: types::PaymentsReauthorizeResponseRouterData<PaymentReauthorizeResponse>,
+    ) -> Result<Self, Self::Error> {
+        let status = enums::AttemptStatus::from(&item.response);
+        let response = if status == enums::AttemptStatus::Failure {
+            Err(types::ErrorResponse {
+                code: item
+                    .response
+                    .response_code
+                    .clone()
+                    .unwrap_or_else(|| consts::NO_ERROR_CODE.to_string()),
+                message: item
+                    .response
+                    .response_summary
+                    .clone()
+                    .unwrap_or_else(|| consts::NO_ERROR_MESSAGE.to_string()),
+                reason: item.response.response_summary,
+                status_code: item.http_code,
+            })
+        } else {
+            // The authorization keeps the identifier of the payment, the identifier of the action
+            // is kept as the reference of the re-authorization
+            Ok(types::PaymentsResponseData::TransactionResponse {
+                resource_id: types::ResponseId::ConnectorTransactionId(
+                    item.data.request.connector_transaction_id.clone(),
+                ),
+                redirection_data: None,
+                mandate_reference: None,
+                connector_metadata: None,
+                network_txn_id: None,
+                connector_response_reference_id: Some(item.response.action_id),
+            })
+        };
+        Ok(Self {
+            response,
+            status,
+            ..item.data
+        })
+    }
+}
+
 #[derive(Debug, Serialize)]
 pub enum CaptureType {
     Final,
@@ -679,6 +763,7 @@ impl TryFrom<types::RefundsResponseRouterData<api::Execute, CheckoutRefundRespon
             response: Ok(types::RefundsResponseData {
                 connector_refund_id: item.response.response.action_id.clone(),
                 refund_status,
+                settled_amount: None,
             }),
             ..item.data
         })
@@ -697,6 +782,7 @@ impl TryFrom<types::RefundsResponseRouterData<api::RSync, CheckoutRefundResponse
             response: Ok(types::RefundsResponseData {
                 connector_refund_id: item.response.response.action_id.clone(),
                 refund_status,
+                settled_amount: None,
             }),
             ..item.data
         })
@@ -769,6 +855,7 @@ impl TryFrom<types::RefundsResponseRouterData<api::Execute, &ActionResponse>>
             response: Ok(types::RefundsResponseData {
                 connector_refund_id: item.response.action_id.clone(),
                 refund_status,
+                settled_amount: None,
             }),
             ..item.data
         })
@@ -787,6 +874,7 @@ impl TryFrom<types::RefundsResponseRouterData<api::RSync, &ActionResponse>>
             response: Ok(types::RefundsResponseData {
                 connector_refund_id: item.response.action_id.clone(),
                 refund_status,
+                settled_amount: None,
             }),
             ..item.data
         })
@@ -1033,3 +1121,30 @@ impl From<String> for utils::ErrorCodeAndMessage {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+
+    #[test]
+    fn test_reauthorize_response_status() {
+        let response: PaymentReauthorizeResponse = serde_json::from_str(
+            r#"{"action_id":"act_y3oqhf46pyzuxjbcn2giaqnb44","amount":0,"currency":"USD","approved":true,"status":"Authorized","response_code":"10000","response_summary":"Approved","expires_on":"2023-10-20T12:00:00Z"}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            enums::AttemptStatus::from(&response),
+            enums::AttemptStatus::Authorized
+        );
+
+        let response: PaymentReauthorizeResponse = serde_json::from_str(
+            r#"{"action_id":"act_y3oqhf46pyzuxjbcn2giaqnb44","amount":0,"currency":"USD","approved":false,"status":"Declined","response_code":"20051","response_summary":"Insufficient Funds"}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            enums::AttemptStatus::from(&response),
+            enums::AttemptStatus::Failure
+        );
+    }
+}
@@ -38,8 +38,31 @@ pub struct CaptureOptions {
 }
 
 #[derive(Default, Debug, Serialize, Eq, PartialEq)]
-pub struct PaymentInformation {
-    card: Card,
+#[serde(untagged)]
+pub enum PaymentInformation {
+    #[default]
+    Card { card: Card },
+    NetworkToken {
+        #[serde(rename = "tokenizedCard")]
+        tokenized_card: TokenizedCard,
+    },
+}
+
+#[derive(Debug, Serialize, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenizedCard {
+    number: cards::CardNumber,
+    expiration_month: Secret<String>,
+    expiration_year: Secret<String>,
+    cryptogram: Secret<String>,
+    transaction_type: TokenizedCardTransactionType,
+}
+
+#[derive(Debug, Serialize, Eq, PartialEq)]
+pub enum TokenizedCardTransactionType {
+    /// A network token stored by the merchant, used in a card-not-present transaction
+    #[serde(rename = "1")]
+    StoredCredential,
 }
 
 #[derive(Default, Debug, Serialize, Eq, PartialEq)]
@@ -56,6 +79,10 @@ pub struct Card {
 pub struct OrderInformationWithBill {
     amount_details: Amount,
     bill_to: BillTo,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    line_items: Option<Vec<LineItem>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    invoice_details: Option<InvoiceDetails>,
 }
 
 #[derive(Default, Debug, Serialize, Eq, PartialEq)]
@@ -69,6 +96,51 @@ pub struct OrderInformation {
 pub struct Amount {
     total_amount: String,
     currency: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tax_amount: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    freight_amount: Option<String>,
+}
+
+#[derive(Default, Debug, Serialize, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct LineItem {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    product_code: Option<String>,
+    product_name: String,
+    quantity: u16,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    unit_of_measure: Option<String>,
+    unit_price: String,
+    total_amount: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tax_amount: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    discount_amount: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    commodity_code: Option<String>,
+}
+
+impl From<&payments::L3LineItem> for LineItem {
+    fn from(line_item: &payments::L3LineItem) -> Self {
+        Self {
+            product_code: line_item.product_code.clone(),
+            product_name: line_item.description.clone(),
+            quantity: line_item.quantity,
+            unit_of_measure: line_item.unit_of_measure.clone(),
+            unit_price: line_item.unit_amount.to_string(),
+            total_amount: line_item.get_total_amount().to_string(),
+            tax_amount: line_item.tax_amount.map(|amount| amount.to_string()),
+            discount_amount: line_item.discount_amount.map(|amount| amount.to_string()),
+            commodity_code: line_item.commodity_code.clone(),
+        }
+    }
+}
+
+#[derive(Default, Debug, Serialize, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct InvoiceDetails {
+    purchase_order_number: String,
 }
 
 #[derive(Default, Debug, Serialize, Eq, PartialEq)]
@@ -125,20 +197,48 @@ impl TryFrom<&types::PaymentsAuthorizeRouterData> for CybersourcePaymentsRequest
                     .ok_or_else(utils::missing_field_err("email"))?;
                 let bill_to = build_bill_to(item.get_billing()?, email, number_with_code)?;
 
+                // Level 2 and level 3 data qualifies business payments for reduced interchange
+                let l2_l3_data = item.request.l2_l3_data.as_ref();
                 let order_information = OrderInformationWithBill {
                     amount_details: Amount {
                         total_amount: item.request.amount.to_string(),
                         currency: item.request.currency.to_string().to_uppercase(),
+                        tax_amount: l2_l3_data
+                            .and_then(|data| data.tax_amount)
+                            .map(|amount| amount.to_string()),
+                        freight_amount: l2_l3_data
+                            .and_then(|data| data.shipping_amount)
+                            .map(|amount| amount.to_string()),
                     },
                     bill_to,
+                    line_items: l2_l3_data
+                        .and_then(|data| data.line_items.as_ref())
+                        .map(|line_items| line_items.iter().map(LineItem::from).collect()),
+                    invoice_details: l2_l3_data
+                        .and_then(|data| data.customer_reference.clone())
+                        .map(|purchase_order_number| InvoiceDetails {
+                            purchase_order_number,
+                        }),
                 };
 
-                let payment_information = PaymentInformation {
-                    card: Card {
-                        number: ccard.card_number,
-                        expiration_month: ccard.card_exp_month,
-                        expiration_year: ccard.card_exp_year,
-                        security_code: ccard.card_cvc,
+                // The network token of the card is sent in place of the card number if available
+                let payment_information = match item.request.network_token_data.clone() {
+                    Some(network_token_data) => PaymentInformation::NetworkToken {
+                        tokenized_card: TokenizedCard {
+                            number: network_token_data.token_number,
+                            expiration_month: network_token_data.token_exp_month,
+                            expiration_year: network_token_data.token_exp_year,
+                            cryptogram: network_token_data.token_cryptogram,
+                            transaction_type: TokenizedCardTransactionType::StoredCredential,
+                        },
+                    },
+                    None => PaymentInformation::Card {
+                        card: Card {
+                            number: ccard.card_number,
+                            expiration_month: ccard.card_exp_month,
+                            expiration_year: ccard.card_exp_year,
+                            security_code: ccard.card_cvc,
+                        },
                     },
                 };
 
@@ -192,6 +292,7 @@ impl TryFrom<&types::RefundExecuteRouterData> for CybersourcePaymentsRequest {
                 amount_details: Amount {
                     total_amount: value.request.refund_amount.to_string(),
                     currency: value.request.currency.to_string(),
+                    ..Default::default()
                 },
                 ..Default::default()
             },
@@ -447,6 +548,7 @@ impl<F> TryFrom<&types::RefundsRouterData<F>> for CybersourceRefundRequest {
                 amount_details: Amount {
                     total_amount: item.request.refund_amount.to_string(),
                     currency: item.request.currency.to_string(),
+                    ..Default::default()
                 },
             },
         })
@@ -465,6 +567,7 @@ impl TryFrom<types::RefundsResponseRouterData<api::Execute, CybersourcePaymentsR
             response: Ok(types::RefundsResponseData {
                 connector_refund_id: item.response.id,
                 refund_status,
+                settled_amount: None,
             }),
             ..item.data
         })
@@ -484,6 +587,7 @@ impl TryFrom<types::RefundsResponseRouterData<api::RSync, CybersourceTransaction
                 refund_status: enums::RefundStatus::from(
                     item.response.application_information.status,
                 ),
+                settled_amount: None,
             }),
             ..item.data
         })
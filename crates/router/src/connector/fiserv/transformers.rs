@@ -518,6 +518,7 @@ impl TryFrom<types::RefundsResponseRouterData<api::Execute, RefundResponse>>
                 refund_status: enums::RefundStatus::from(
                     item.response.gateway_response.transaction_state,
                 ),
+                settled_amount: None,
             }),
             ..item.data
         })
@@ -546,6 +547,7 @@ impl TryFrom<types::RefundsResponseRouterData<api::RSync, FiservSyncResponse>>
                 refund_status: enums::RefundStatus::from(
                     gateway_resp.gateway_response.transaction_state.clone(),
                 ),
+                settled_amount: None,
             }),
             ..item.data
         })
@@ -304,6 +304,7 @@ impl TryFrom<types::RefundsResponseRouterData<api::Execute, GlobalpayPaymentsRes
             response: Ok(types::RefundsResponseData {
                 connector_refund_id: item.response.id,
                 refund_status: enums::RefundStatus::from(item.response.status),
+                settled_amount: None,
             }),
             ..item.data
         })
@@ -321,6 +322,7 @@ impl TryFrom<types::RefundsResponseRouterData<api::RSync, GlobalpayPaymentsRespo
             response: Ok(types::RefundsResponseData {
                 connector_refund_id: item.response.id,
                 refund_status: enums::RefundStatus::from(item.response.status),
+                settled_amount: None,
             }),
             ..item.data
         })
@@ -324,6 +324,7 @@ impl<T> TryFrom<types::RefundsResponseRouterData<T, GlobepayRefundResponse>>
                 response: Ok(types::RefundsResponseData {
                     connector_refund_id: globepay_refund_id,
                     refund_status: enums::RefundStatus::from(globepay_refund_status),
+                    settled_amount: None,
                 }),
                 ..item.data
             })
@@ -472,7 +472,9 @@ impl<F, T>
     ) -> Result<Self, Self::Error> {
         Ok(Self {
             status: storage_enums::AttemptStatus::Pending,
-            payment_method_token: Some(item.response.card_token.clone().expose()),
+            payment_method_token: Some(types::PaymentMethodToken::Token(
+                item.response.card_token.clone().expose(),
+            )),
             response: Ok(types::PaymentsResponseData::TokenizationResponse {
                 token: item.response.card_token.expose(),
             }),
@@ -581,6 +583,7 @@ impl<T> TryFrom<types::RefundsResponseRouterData<T, RefundResponse>>
             response: Ok(types::RefundsResponseData {
                 connector_refund_id: item.response.id,
                 refund_status: enums::RefundStatus::from(item.response.status),
+                settled_amount: None,
             }),
             ..item.data
         })
@@ -618,6 +618,7 @@ impl TryFrom<types::RefundsResponseRouterData<api::Execute, RefundResponse>>
             response: Ok(types::RefundsResponseData {
                 connector_refund_id: item.response.data.refund_id.to_string(),
                 refund_status: enums::RefundStatus::from(refund_stat),
+                settled_amount: None,
             }),
             ..item.data
         })
@@ -641,6 +642,7 @@ impl TryFrom<types::RefundsResponseRouterData<api::RSync, RefundResponse>>
             response: Ok(types::RefundsResponseData {
                 connector_refund_id: item.response.data.refund_id.to_string(),
                 refund_status: enums::RefundStatus::from(refund_status),
+                settled_amount: None,
             }),
             ..item.data
         })
@@ -560,6 +560,7 @@ impl TryFrom<types::RefundsResponseRouterData<api::Execute, StandardResponse>>
             response: Ok(types::RefundsResponseData {
                 connector_refund_id: item.response.transactionid,
                 refund_status,
+                settled_amount: None,
             }),
             ..item.data
         })
@@ -578,6 +579,7 @@ impl TryFrom<types::RefundsResponseRouterData<api::Capture, StandardResponse>>
             response: Ok(types::RefundsResponseData {
                 connector_refund_id: item.response.transactionid,
                 refund_status,
+                settled_amount: None,
             }),
             ..item.data
         })
@@ -624,6 +626,7 @@ impl TryFrom<types::RefundsResponseRouterData<api::RSync, types::Response>>
             response: Ok(types::RefundsResponseData {
                 connector_refund_id: response.transaction.transaction_id,
                 refund_status,
+                settled_amount: None,
             }),
             ..item.data
         })
@@ -541,6 +541,7 @@ impl TryFrom<types::RefundsResponseRouterData<api::Execute, RefundResponse>>
             response: Ok(types::RefundsResponseData {
                 connector_refund_id: item.response.result.transaction.id,
                 refund_status: enums::RefundStatus::from(item.response.result.transaction.status),
+                settled_amount: None,
             }),
             ..item.data
         })
@@ -583,6 +584,7 @@ impl TryFrom<types::RefundsResponseRouterData<api::RSync, RefundSyncResponse>>
             response: Ok(types::RefundsResponseData {
                 connector_refund_id: noon_transaction.id.to_owned(),
                 refund_status: enums::RefundStatus::from(noon_transaction.status.to_owned()),
+                settled_amount: None,
             }),
             ..item.data
         })
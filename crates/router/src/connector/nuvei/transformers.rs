@@ -726,6 +726,7 @@ impl<F>
             | payments::PaymentMethodData::Upi(_)
             | payments::PaymentMethodData::Voucher(_)
             | api_models::payments::PaymentMethodData::CardRedirect(_)
+            | payments::PaymentMethodData::CardPresent(_)
             | payments::PaymentMethodData::GiftCard(_) => {
                 Err(errors::ConnectorError::NotImplemented(
                     utils::get_unimplemented_payment_method_error_message("nuvei"),
@@ -893,6 +894,7 @@ impl TryFrom<(&types::PaymentsCompleteAuthorizeRouterData, String)> for NuveiPay
             | Some(api::PaymentMethodData::BankTransfer(..))
             | Some(api::PaymentMethodData::Crypto(..))
             | Some(api::PaymentMethodData::MandatePayment)
+            | Some(api::PaymentMethodData::CardPresent(..))
             | Some(api::PaymentMethodData::GiftCard(..))
             | Some(api::PaymentMethodData::Voucher(..))
             | Some(api::PaymentMethodData::CardRedirect(..))
@@ -1418,6 +1420,7 @@ fn get_refund_response(
             _ => Ok(types::RefundsResponseData {
                 connector_refund_id: txn_id,
                 refund_status,
+                settled_amount: None,
             }),
         },
     }
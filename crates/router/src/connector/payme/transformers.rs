@@ -1,8 +1,9 @@
-use api_models::payments::PaymentMethodData;
+use api_models::payments::{PaymentMethodData, WalletData};
 use common_utils::pii;
 use error_stack::{IntoReport, ResultExt};
 use masking::{ExposeInterface, Secret};
 use serde::{Deserialize, Serialize};
+use url::Url;
 
 use crate::{
     connector::utils::{
@@ -10,6 +11,7 @@ use crate::{
         PaymentsSyncRequestData, RouterData,
     },
     core::errors,
+    services,
     types::{self, api, storage::enums, MandateReference},
 };
 
@@ -19,7 +21,14 @@ pub struct PayRequest {
     buyer_email: pii::Email,
     payme_sale_id: String,
     #[serde(flatten)]
-    card: PaymeCard,
+    payment_method: PaymePaymentMethod,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(untagged)]
+pub enum PaymePaymentMethod {
+    Card(PaymeCard),
+    ApplePay(PaymeApplePay),
 }
 
 #[derive(Debug, Serialize)]
@@ -60,6 +69,11 @@ pub struct PaymeCard {
     credit_card_number: cards::CardNumber,
 }
 
+#[derive(Debug, Serialize)]
+pub struct PaymeApplePay {
+    apple_pay_token: Secret<String>,
+}
+
 #[derive(Debug, Serialize)]
 pub struct GenerateSaleRequest {
     currency: enums::Currency,
@@ -76,6 +90,8 @@ pub struct GenerateSaleRequest {
 #[derive(Debug, Deserialize)]
 pub struct GenerateSaleResponse {
     payme_sale_id: String,
+    sale_3ds: Option<bool>,
+    sale_url: Option<Url>,
 }
 
 impl<F, T>
@@ -115,11 +131,20 @@ impl<F, T>
     fn try_from(
         item: types::ResponseRouterData<F, PaymePaySaleResponse, T, types::PaymentsResponseData>,
     ) -> Result<Self, Self::Error> {
+        let redirection_data = get_three_ds_redirection_data(
+            item.response.sale_3ds,
+            item.response.redirect_url.clone(),
+        );
+        let status = if redirection_data.is_some() {
+            enums::AttemptStatus::AuthenticationPending
+        } else {
+            enums::AttemptStatus::from(item.response.sale_status)
+        };
         Ok(Self {
-            status: enums::AttemptStatus::from(item.response.sale_status),
+            status,
             response: Ok(types::PaymentsResponseData::TransactionResponse {
                 resource_id: types::ResponseId::ConnectorTransactionId(item.response.payme_sale_id),
-                redirection_data: None,
+                redirection_data,
                 mandate_reference: item.response.buyer_key.map(|buyer_key| MandateReference {
                     connector_mandate_id: Some(buyer_key.expose()),
                     payment_method_id: None,
@@ -183,6 +208,7 @@ pub enum SaleType {
 #[serde(rename_all = "kebab-case")]
 pub enum SalePaymentMethod {
     CreditCard,
+    ApplePay,
 }
 
 impl TryFrom<&types::PaymentsInitRouterData> for GenerateSaleRequest {
@@ -196,13 +222,19 @@ impl TryFrom<&types::PaymentsInitRouterData> for GenerateSaleRequest {
             .ok_or_else(missing_field_err("order_details"))?
             .product_name
             .clone();
+        // The buyer is sent back to the complete authorize url after authenticating on the 3DS hosted page
+        let sale_return_url = if item.is_three_ds() {
+            item.request.get_complete_authorize_url()?
+        } else {
+            item.request.get_return_url()?
+        };
         Ok(Self {
             currency: item.request.currency,
             sale_type,
             sale_price: item.request.amount,
             transaction_id: item.payment_id.clone(),
             product_name,
-            sale_return_url: item.request.get_return_url()?,
+            sale_return_url,
             seller_payme_id,
             sale_callback_url: item.request.get_webhook_url()?,
             sale_payment_method: SalePaymentMethod::try_from(&item.request.payment_method_data)?,
@@ -232,6 +264,7 @@ impl TryFrom<&PaymentMethodData> for SalePaymentMethod {
     fn try_from(item: &PaymentMethodData) -> Result<Self, Self::Error> {
         match item {
             PaymentMethodData::Card(_) => Ok(Self::CreditCard),
+            PaymentMethodData::Wallet(WalletData::ApplePay(_)) => Ok(Self::ApplePay),
             PaymentMethodData::Wallet(_)
             | PaymentMethodData::PayLater(_)
             | PaymentMethodData::BankRedirect(_)
@@ -240,6 +273,7 @@ impl TryFrom<&PaymentMethodData> for SalePaymentMethod {
             | PaymentMethodData::Crypto(_)
             | PaymentMethodData::MandatePayment
             | PaymentMethodData::Reward(_)
+            | PaymentMethodData::CardPresent(_)
             | PaymentMethodData::GiftCard(_)
             | PaymentMethodData::CardRedirect(_)
             | PaymentMethodData::Upi(_)
@@ -273,6 +307,21 @@ impl TryFrom<&types::PaymentsSyncRouterData> for PaymeQuerySaleRequest {
     }
 }
 
+impl TryFrom<&types::PaymentsCompleteAuthorizeRouterData> for PaymeQuerySaleRequest {
+    type Error = error_stack::Report<errors::ConnectorError>;
+    fn try_from(value: &types::PaymentsCompleteAuthorizeRouterData) -> Result<Self, Self::Error> {
+        let seller_payme_id = PaymeAuthType::try_from(&value.connector_auth_type)?.seller_payme_id;
+        Ok(Self {
+            sale_payme_id: value
+                .request
+                .connector_transaction_id
+                .clone()
+                .ok_or(errors::ConnectorError::MissingConnectorTransactionID)?,
+            seller_payme_id,
+        })
+    }
+}
+
 impl TryFrom<&types::RefundSyncRouterData> for PaymeQueryTransactionRequest {
     type Error = error_stack::Report<errors::ConnectorError>;
     fn try_from(value: &types::RefundSyncRouterData) -> Result<Self, Self::Error> {
@@ -314,30 +363,36 @@ impl TryFrom<&types::PaymentsAuthorizeRouterData> for MandateRequest {
 impl TryFrom<&types::PaymentsAuthorizeRouterData> for PayRequest {
     type Error = error_stack::Report<errors::ConnectorError>;
     fn try_from(item: &types::PaymentsAuthorizeRouterData) -> Result<Self, Self::Error> {
-        match item.request.payment_method_data.clone() {
-            api::PaymentMethodData::Card(req_card) => {
-                let card = PaymeCard {
-                    credit_card_cvv: req_card.card_cvc.clone(),
-                    credit_card_exp: req_card
-                        .get_card_expiry_month_year_2_digit_with_delimiter("".to_string()),
-                    credit_card_number: req_card.card_number,
-                };
-                let buyer_email = item.request.get_email()?;
-                let buyer_name = item.get_billing_address()?.get_full_name()?;
-                let payme_sale_id = item.request.related_transaction_id.clone().ok_or(
-                    errors::ConnectorError::MissingConnectorRelatedTransactionID {
-                        id: "payme_sale_id".to_string(),
-                    },
-                )?;
-                Ok(Self {
-                    card,
-                    buyer_email,
-                    buyer_name,
-                    payme_sale_id,
+        let payment_method = match item.request.payment_method_data.clone() {
+            api::PaymentMethodData::Card(req_card) => PaymePaymentMethod::Card(PaymeCard {
+                credit_card_cvv: req_card.card_cvc.clone(),
+                credit_card_exp: req_card
+                    .get_card_expiry_month_year_2_digit_with_delimiter("".to_string()),
+                credit_card_number: req_card.card_number,
+            }),
+            // The encrypted payment token of Apple Pay is decrypted by Payme
+            api::PaymentMethodData::Wallet(WalletData::ApplePay(apple_pay_data)) => {
+                PaymePaymentMethod::ApplePay(PaymeApplePay {
+                    apple_pay_token: Secret::new(apple_pay_data.payment_data),
                 })
             }
-            _ => Err(errors::ConnectorError::NotImplemented("Payment methods".to_string()).into()),
-        }
+            _ => Err(errors::ConnectorError::NotImplemented(
+                "Payment methods".to_string(),
+            ))?,
+        };
+        let buyer_email = item.request.get_email()?;
+        let buyer_name = item.get_billing_address()?.get_full_name()?;
+        let payme_sale_id = item.request.related_transaction_id.clone().ok_or(
+            errors::ConnectorError::MissingConnectorRelatedTransactionID {
+                id: "payme_sale_id".to_string(),
+            },
+        )?;
+        Ok(Self {
+            buyer_email,
+            buyer_name,
+            payme_sale_id,
+            payment_method,
+        })
     }
 }
 
@@ -413,6 +468,21 @@ pub struct PaymePaySaleResponse {
     payme_sale_id: String,
     payme_transaction_id: String,
     buyer_key: Option<Secret<String>>,
+    sale_3ds: Option<bool>,
+    redirect_url: Option<Url>,
+}
+
+// Payme returns the url of its 3DS hosted page when the sale requires the buyer to be authenticated
+fn get_three_ds_redirection_data(
+    sale_3ds: Option<bool>,
+    redirect_url: Option<Url>,
+) -> Option<services::RedirectForm> {
+    match sale_3ds {
+        Some(true) => {
+            redirect_url.map(|url| services::RedirectForm::from((url, services::Method::Get)))
+        }
+        Some(false) | None => None,
+    }
 }
 
 #[derive(Serialize, Deserialize)]
@@ -439,15 +509,22 @@ impl<F>
             types::PaymentsResponseData,
         >,
     ) -> Result<Self, Self::Error> {
+        let redirection_data =
+            get_three_ds_redirection_data(item.response.sale_3ds, item.response.sale_url);
+        let status = if redirection_data.is_some() {
+            enums::AttemptStatus::AuthenticationPending
+        } else {
+            enums::AttemptStatus::Authorizing
+        };
         Ok(Self {
-            status: enums::AttemptStatus::Authorizing,
+            status,
             request: types::PaymentsAuthorizeData {
                 related_transaction_id: Some(item.response.payme_sale_id.clone()),
                 ..item.data.request
             },
             response: Ok(types::PaymentsResponseData::TransactionResponse {
                 resource_id: types::ResponseId::ConnectorTransactionId(item.response.payme_sale_id),
-                redirection_data: None,
+                redirection_data,
                 mandate_reference: None,
                 connector_metadata: None,
                 network_txn_id: None,
@@ -528,6 +605,7 @@ impl TryFrom<types::RefundsResponseRouterData<api::Execute, PaymeRefundResponse>
             response: Ok(types::RefundsResponseData {
                 connector_refund_id: item.response.payme_transaction_id,
                 refund_status: enums::RefundStatus::try_from(item.response.sale_status)?,
+                settled_amount: None,
             }),
             ..item.data
         })
@@ -570,6 +648,7 @@ impl<F, T>
                     pay_sale_response.sale_status.clone(),
                 )?,
                 connector_refund_id: pay_sale_response.payme_transaction_id.clone(),
+                settled_amount: None,
             }),
             ..item.data
         })
@@ -623,6 +702,9 @@ impl From<WebhookEventDataResource> for PaymePaySaleResponse {
             payme_sale_id: value.payme_sale_id,
             payme_transaction_id: value.payme_transaction_id,
             buyer_key: value.buyer_key,
+            // Webhooks are only sent once the buyer has completed the 3DS authentication
+            sale_3ds: None,
+            redirect_url: None,
         }
     }
 }
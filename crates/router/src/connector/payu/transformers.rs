@@ -556,6 +556,7 @@ impl TryFrom<types::RefundsResponseRouterData<api::Execute, RefundResponse>>
             response: Ok(types::RefundsResponseData {
                 connector_refund_id: item.response.refund.refund_id,
                 refund_status,
+                settled_amount: None,
             }),
             ..item.data
         })
@@ -581,6 +582,7 @@ impl TryFrom<types::RefundsResponseRouterData<api::RSync, RefundSyncResponse>>
             response: Ok(types::RefundsResponseData {
                 connector_refund_id: refund.refund_id.clone(),
                 refund_status: enums::RefundStatus::from(refund.status.clone()),
+                settled_amount: None,
             }),
             ..item.data
         })
@@ -396,6 +396,7 @@ impl TryFrom<types::RefundsResponseRouterData<api::Execute, PowertranzBaseRespon
                     true => RefundStatus::Success,
                     false => RefundStatus::Failure,
                 },
+                settled_amount: None,
             }),
             Err,
         );
@@ -14,7 +14,6 @@ use crate::{
     connector::utils as conn_utils,
     consts,
     core::errors::{self, CustomResult},
-    db::StorageInterface,
     headers, logger,
     services::{
         self,
@@ -23,7 +22,7 @@ use crate::{
     types::{
         self,
         api::{self, ConnectorCommon},
-        domain, ErrorResponse,
+        ErrorResponse,
     },
     utils::{self, crypto, ByteSliceExt, BytesExt},
 };
@@ -742,28 +741,17 @@ impl api::IncomingWebhook for Rapyd {
         Ok(to_sign.into_bytes())
     }
 
-    async fn verify_webhook_source(
+    fn verify_webhook_source_with_secret(
         &self,
-        db: &dyn StorageInterface,
         request: &api::IncomingWebhookRequestDetails<'_>,
         merchant_id: &str,
-        connector_label: &str,
-        key_store: &domain::MerchantKeyStore,
+        secret: &[u8],
     ) -> CustomResult<bool, errors::ConnectorError> {
         let signature = self
             .get_webhook_source_verification_signature(request)
             .change_context(errors::ConnectorError::WebhookSourceVerificationFailed)?;
-        let secret = self
-            .get_webhook_source_verification_merchant_secret(
-                db,
-                merchant_id,
-                connector_label,
-                key_store,
-            )
-            .await
-            .change_context(errors::ConnectorError::WebhookSourceVerificationFailed)?;
         let message = self
-            .get_webhook_source_verification_message(request, merchant_id, &secret)
+            .get_webhook_source_verification_message(request, merchant_id, secret)
             .change_context(errors::ConnectorError::WebhookSourceVerificationFailed)?;
 
         let stringify_auth = String::from_utf8(secret.to_vec())
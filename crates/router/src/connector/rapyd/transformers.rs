@@ -343,6 +343,7 @@ impl TryFrom<types::RefundsResponseRouterData<api::Execute, RefundResponse>>
             response: Ok(types::RefundsResponseData {
                 connector_refund_id,
                 refund_status,
+                settled_amount: None,
             }),
             ..item.data
         })
@@ -367,6 +368,7 @@ impl TryFrom<types::RefundsResponseRouterData<api::RSync, RefundResponse>>
             response: Ok(types::RefundsResponseData {
                 connector_refund_id,
                 refund_status,
+                settled_amount: None,
             }),
             ..item.data
         })
@@ -192,6 +192,7 @@ impl TryFrom<&types::TokenizationRouterData> for StaxTokenRequest {
             | api::PaymentMethodData::MandatePayment
             | api::PaymentMethodData::Reward(_)
             | api::PaymentMethodData::Voucher(_)
+            | api::PaymentMethodData::CardPresent(_)
             | api::PaymentMethodData::GiftCard(_)
             | api::PaymentMethodData::CardRedirect(_)
             | api::PaymentMethodData::Upi(_) => Err(errors::ConnectorError::NotImplemented(
@@ -374,6 +375,7 @@ impl TryFrom<types::RefundsResponseRouterData<api::Execute, RefundResponse>>
             response: Ok(types::RefundsResponseData {
                 connector_refund_id: refund_txn.id.clone(),
                 refund_status,
+                settled_amount: None,
             }),
             ..item.data
         })
@@ -395,6 +397,7 @@ impl TryFrom<types::RefundsResponseRouterData<api::RSync, RefundResponse>>
             response: Ok(types::RefundsResponseData {
                 connector_refund_id: item.response.id,
                 refund_status,
+                settled_amount: None,
             }),
             ..item.data
         })
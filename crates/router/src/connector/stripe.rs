@@ -8,7 +8,7 @@ use masking::PeekInterface;
 use router_env::{instrument, tracing};
 
 use self::transformers as stripe;
-use super::utils::RefundsRequestData;
+use super::utils::{self as connector_utils, RefundsRequestData};
 use crate::{
     configs::settings,
     consts,
@@ -28,6 +28,9 @@ use crate::{
     utils::{self, crypto, ByteSliceExt, BytesExt, OptionExt},
 };
 
+/// Stripe accepts idempotency keys of up to 255 characters.
+const STRIPE_IDEMPOTENCY_KEY_MAX_LENGTH: usize = 255;
+
 #[derive(Debug, Clone)]
 pub struct Stripe;
 
@@ -45,6 +48,10 @@ impl ConnectorCommon for Stripe {
         connectors.stripe.base_url.as_ref()
     }
 
+    fn is_currency_conversion_supported(&self) -> bool {
+        true
+    }
+
     fn get_auth_header(
         &self,
         auth_type: &types::ConnectorAuthType,
@@ -65,6 +72,7 @@ impl api::PaymentAuthorize for Stripe {}
 impl api::PaymentSync for Stripe {}
 impl api::PaymentVoid for Stripe {}
 impl api::PaymentCapture for Stripe {}
+impl api::PaymentIncrementalAuthorization for Stripe {}
 impl api::PaymentSession for Stripe {}
 impl api::ConnectorAccessToken for Stripe {}
 
@@ -457,6 +465,10 @@ impl
         )];
         let mut api_key = self.get_auth_header(&req.connector_auth_type)?;
         header.append(&mut api_key);
+        header.append(&mut connector_utils::get_idempotency_key_header(
+            req.idempotency_key.as_ref(),
+            STRIPE_IDEMPOTENCY_KEY_MAX_LENGTH,
+        )?);
         Ok(header)
     }
 
@@ -559,6 +571,128 @@ impl
     }
 }
 
+impl
+    services::ConnectorIntegration<
+        api::IncrementalAuthorization,
+        types::PaymentsIncrementalAuthorizationData,
+        types::PaymentsResponseData,
+    > for Stripe
+{
+    fn get_headers(
+        &self,
+        req: &types::PaymentsIncrementalAuthorizationRouterData,
+        _connectors: &settings::Connectors,
+    ) -> CustomResult<Vec<(String, request::Maskable<String>)>, errors::ConnectorError> {
+        let mut header = vec![(
+            headers::CONTENT_TYPE.to_string(),
+            Self::common_get_content_type(self).to_string().into(),
+        )];
+        let mut api_key = self.get_auth_header(&req.connector_auth_type)?;
+        header.append(&mut api_key);
+        header.append(&mut connector_utils::get_idempotency_key_header(
+            req.idempotency_key.as_ref(),
+            STRIPE_IDEMPOTENCY_KEY_MAX_LENGTH,
+        )?);
+        Ok(header)
+    }
+
+    fn get_content_type(&self) -> &'static str {
+        self.common_get_content_type()
+    }
+
+    fn get_url(
+        &self,
+        req: &types::PaymentsIncrementalAuthorizationRouterData,
+        connectors: &settings::Connectors,
+    ) -> CustomResult<String, errors::ConnectorError> {
+        let id = req.request.connector_transaction_id.as_str();
+
+        Ok(format!(
+            "{}{}/{}/increment_authorization",
+            self.base_url(connectors),
+            "v1/payment_intents",
+            id
+        ))
+    }
+
+    fn get_request_body(
+        &self,
+        req: &types::PaymentsIncrementalAuthorizationRouterData,
+    ) -> CustomResult<Option<types::RequestBody>, errors::ConnectorError> {
+        let connector_request = stripe::IncrementalAuthorizationRequest::try_from(req)?;
+        let stripe_req = types::RequestBody::log_and_get_request_body(
+            &connector_request,
+            utils::Encode::<stripe::IncrementalAuthorizationRequest>::url_encode,
+        )
+        .change_context(errors::ConnectorError::RequestEncodingFailed)?;
+        Ok(Some(stripe_req))
+    }
+
+    fn build_request(
+        &self,
+        req: &types::PaymentsIncrementalAuthorizationRouterData,
+        connectors: &settings::Connectors,
+    ) -> CustomResult<Option<services::Request>, errors::ConnectorError> {
+        Ok(Some(
+            services::RequestBuilder::new()
+                .method(services::Method::Post)
+                .url(&types::PaymentsIncrementalAuthorizationType::get_url(
+                    self, req, connectors,
+                )?)
+                .attach_default_headers()
+                .headers(types::PaymentsIncrementalAuthorizationType::get_headers(
+                    self, req, connectors,
+                )?)
+                .body(types::PaymentsIncrementalAuthorizationType::get_request_body(self, req)?)
+                .build(),
+        ))
+    }
+
+    fn handle_response(
+        &self,
+        data: &types::PaymentsIncrementalAuthorizationRouterData,
+        res: types::Response,
+    ) -> CustomResult<types::PaymentsIncrementalAuthorizationRouterData, errors::ConnectorError>
+    {
+        let response: stripe::IncrementalAuthorizationResponse = res
+            .response
+            .parse_struct("IncrementalAuthorizationResponse")
+            .change_context(errors::ConnectorError::ResponseDeserializationFailed)?;
+        logger::info!(connector_response=?response);
+        types::RouterData::try_from(types::ResponseRouterData {
+            response,
+            data: data.clone(),
+            http_code: res.status_code,
+        })
+        .change_context(errors::ConnectorError::ResponseHandlingFailed)
+    }
+
+    fn get_error_response(
+        &self,
+        res: types::Response,
+    ) -> CustomResult<types::ErrorResponse, errors::ConnectorError> {
+        let response: stripe::ErrorResponse = res
+            .response
+            .parse_struct("ErrorResponse")
+            .change_context(errors::ConnectorError::ResponseDeserializationFailed)?;
+        router_env::logger::info!(error_response=?response);
+
+        Ok(types::ErrorResponse {
+            status_code: res.status_code,
+            code: response
+                .error
+                .code
+                .clone()
+                .unwrap_or_else(|| consts::NO_ERROR_CODE.to_string()),
+            message: response
+                .error
+                .code
+                .unwrap_or_else(|| consts::NO_ERROR_MESSAGE.to_string()),
+            reason: response.error.message,
+        })
+    }
+}
+
 impl
     services::ConnectorIntegration<api::PSync, types::PaymentsSyncData, types::PaymentsResponseData>
     for Stripe
@@ -712,6 +846,10 @@ impl
         )];
         let mut api_key = self.get_auth_header(&req.connector_auth_type)?;
         header.append(&mut api_key);
+        header.append(&mut connector_utils::get_idempotency_key_header(
+            req.idempotency_key.as_ref(),
+            STRIPE_IDEMPOTENCY_KEY_MAX_LENGTH,
+        )?);
         Ok(header)
     }
 
@@ -857,6 +995,10 @@ impl
         )];
         let mut api_key = self.get_auth_header(&req.connector_auth_type)?;
         header.append(&mut api_key);
+        header.append(&mut connector_utils::get_idempotency_key_header(
+            req.idempotency_key.as_ref(),
+            STRIPE_IDEMPOTENCY_KEY_MAX_LENGTH,
+        )?);
         Ok(header)
     }
 
@@ -1098,6 +1240,10 @@ impl services::ConnectorIntegration<api::Execute, types::RefundsData, types::Ref
         )];
         let mut api_key = self.get_auth_header(&req.connector_auth_type)?;
         header.append(&mut api_key);
+        header.append(&mut connector_utils::get_idempotency_key_header(
+            req.idempotency_key.as_ref(),
+            STRIPE_IDEMPOTENCY_KEY_MAX_LENGTH,
+        )?);
         Ok(header)
     }
 
@@ -1307,6 +1453,9 @@ impl api::FileUpload for Stripe {
                     })?
                 }
             }
+            api::FilePurpose::PayoutKyc => Err(errors::ConnectorError::FileValidationFailed {
+                reason: "file uploads for payouts are not supported".to_owned(),
+            })?,
         }
         Ok(())
     }
@@ -1534,6 +1683,10 @@ impl
         )];
         let mut api_key = self.get_auth_header(&req.connector_auth_type)?;
         header.append(&mut api_key);
+        header.append(&mut connector_utils::get_idempotency_key_header(
+            req.idempotency_key.as_ref(),
+            STRIPE_IDEMPOTENCY_KEY_MAX_LENGTH,
+        )?);
         Ok(header)
     }
 
@@ -1839,6 +1992,24 @@ impl api::IncomingWebhook for Stripe {
 
         Ok(details.data.object)
     }
+
+    fn get_webhook_refund_settled_amount(
+        &self,
+        request: &api::IncomingWebhookRequestDetails<'_>,
+    ) -> CustomResult<Option<i64>, errors::ConnectorError> {
+        let details: stripe::WebhookEvent = request
+            .body
+            .parse_struct("WebhookEvent")
+            .change_context(errors::ConnectorError::WebhookBodyDecodingFailed)?;
+
+        Ok(match details.event_data.event_object.object {
+            stripe::WebhookEventObjectType::Refund => {
+                details.event_data.event_object.amount.map(i64::from)
+            }
+            _ => None,
+        })
+    }
+
     fn get_dispute_details(
         &self,
         request: &api::IncomingWebhookRequestDetails<'_>,
@@ -55,12 +55,21 @@ impl From<Option<enums::CaptureMethod>> for StripeCaptureMethod {
                 enums::CaptureMethod::Manual => Self::Manual,
                 enums::CaptureMethod::Automatic => Self::Automatic,
                 enums::CaptureMethod::Scheduled => Self::Manual,
+                enums::CaptureMethod::AutomaticDelayed => Self::Manual,
             },
             None => Self::Automatic,
         }
     }
 }
 
+/// Stripe only allows the authorized amount of card payments to be increased if it was requested
+/// when the payment was authorized
+#[derive(Debug, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StripeRequestIncrementalAuthorization {
+    IfAvailable,
+}
+
 #[derive(Debug, Default, Eq, PartialEq, Serialize)]
 #[serde(rename_all = "lowercase")]
 pub enum Auth3ds {
@@ -118,6 +127,8 @@ pub struct PaymentIntentRequest {
     pub payment_data: Option<StripePaymentMethodData>,
     pub capture_method: StripeCaptureMethod,
     pub payment_method_options: Option<StripePaymentMethodOptions>, // For mandate txns using network_txns_id, needs to be validated
+    #[serde(rename = "payment_method_options[card][request_incremental_authorization]")]
+    pub request_incremental_authorization: Option<StripeRequestIncrementalAuthorization>,
     pub setup_future_usage: Option<enums::FutureUsage>,
     pub off_session: Option<bool>,
     #[serde(rename = "payment_method_types[0]")]
@@ -431,6 +442,7 @@ pub enum StripeWallet {
     ApplepayToken(StripeApplePay),
     GooglepayToken(GooglePayToken),
     ApplepayPayment(ApplepayPayment),
+    ApplePayPredecryptToken(Box<StripeApplePayPredecrypt>),
     WechatpayPayment(WechatpayPayment),
     AlipayPayment(AlipayPayment),
     Cashapp(CashappPayment),
@@ -460,6 +472,38 @@ pub struct ApplepayPayment {
     pub payment_method_types: StripePaymentMethodType,
 }
 
+#[derive(Debug, Eq, PartialEq, Serialize)]
+pub struct StripeApplePayPredecrypt {
+    #[serde(rename = "payment_method_data[card][number]")]
+    pub number: cards::CardNumber,
+    #[serde(rename = "payment_method_data[card][exp_year]")]
+    pub exp_year: Secret<String>,
+    #[serde(rename = "payment_method_data[card][exp_month]")]
+    pub exp_month: Secret<String>,
+    #[serde(rename = "payment_method_data[card][network_token][cryptogram]")]
+    pub cryptogram: Secret<String>,
+    #[serde(rename = "payment_method_data[card][network_token][electronic_commerce_indicator]")]
+    pub eci: Option<String>,
+    #[serde(rename = "payment_method_data[card][network_token][tokenization_method]")]
+    pub tokenization_method: String,
+    #[serde(rename = "payment_method_data[type]")]
+    pub payment_method_types: StripePaymentMethodType,
+}
+
+impl From<types::ApplePayPredecryptData> for StripeApplePayPredecrypt {
+    fn from(decrypted_data: types::ApplePayPredecryptData) -> Self {
+        Self {
+            exp_year: decrypted_data.get_four_digit_expiry_year(),
+            exp_month: decrypted_data.get_expiry_month(),
+            number: decrypted_data.application_primary_account_number,
+            cryptogram: decrypted_data.payment_data.online_payment_cryptogram,
+            eci: decrypted_data.payment_data.eci_indicator,
+            tokenization_method: "apple_pay".to_string(),
+            payment_method_types: StripePaymentMethodType::Card,
+        }
+    }
+}
+
 #[derive(Debug, Eq, PartialEq, Serialize)]
 pub struct AlipayPayment {
     #[serde(rename = "payment_method_data[type]")]
@@ -1440,6 +1484,7 @@ fn create_stripe_payment_method(
         | payments::PaymentMethodData::Upi(_)
         | payments::PaymentMethodData::CardRedirect(_)
         | payments::PaymentMethodData::Voucher(_)
+        | payments::PaymentMethodData::CardPresent(_)
         | payments::PaymentMethodData::GiftCard(_) => Err(errors::ConnectorError::NotImplemented(
             connector_util::get_unimplemented_payment_method_error_message("stripe"),
         )
@@ -1560,17 +1605,27 @@ impl TryFrom<&types::PaymentsAuthorizeRouterData> for PaymentIntentRequest {
         };
 
         payment_data = match item.request.payment_method_data {
-            payments::PaymentMethodData::Wallet(payments::WalletData::ApplePay(_)) => Some(
-                StripePaymentMethodData::Wallet(StripeWallet::ApplepayPayment(ApplepayPayment {
-                    token: Secret::new(
-                        item.payment_method_token
-                            .to_owned()
-                            .get_required_value("payment_token")
-                            .change_context(errors::ConnectorError::RequestEncodingFailed)?,
-                    ),
-                    payment_method_types: StripePaymentMethodType::Card,
-                })),
-            ),
+            payments::PaymentMethodData::Wallet(payments::WalletData::ApplePay(_)) => {
+                let payment_method_token = item
+                    .payment_method_token
+                    .to_owned()
+                    .get_required_value("payment_token")
+                    .change_context(errors::ConnectorError::RequestEncodingFailed)?;
+                let wallet = match payment_method_token {
+                    types::PaymentMethodToken::Token(token) => {
+                        StripeWallet::ApplepayPayment(ApplepayPayment {
+                            token: Secret::new(token),
+                            payment_method_types: StripePaymentMethodType::Card,
+                        })
+                    }
+                    types::PaymentMethodToken::ApplePayDecrypt(decrypted_data) => {
+                        StripeWallet::ApplePayPredecryptToken(Box::new(
+                            StripeApplePayPredecrypt::from(*decrypted_data),
+                        ))
+                    }
+                };
+                Some(StripePaymentMethodData::Wallet(wallet))
+            }
             _ => payment_data,
         };
 
@@ -1637,9 +1692,25 @@ impl TryFrom<&types::PaymentsAuthorizeRouterData> for PaymentIntentRequest {
                     None
                 }
             });
+
+        let capture_method = StripeCaptureMethod::from(item.request.capture_method);
+        // Incremental authorization can only be requested for card payments which are not captured
+        // immediately
+        let request_incremental_authorization = (capture_method == StripeCaptureMethod::Manual
+            && item.payment_method == api_enums::PaymentMethod::Card)
+            .then_some(StripeRequestIncrementalAuthorization::IfAvailable);
+        // Payments converted to the currency of the customer are charged in the presentment
+        // currency, and settled to the merchant in its settlement currency
+        let (amount, currency) = match &item.request.currency_conversion {
+            Some(conversion) => (
+                conversion.presentment_amount,
+                conversion.presentment_currency,
+            ),
+            None => (item.request.amount, item.request.currency),
+        };
         Ok(Self {
-            amount: item.request.amount, //hopefully we don't loose some cents here
-            currency: item.request.currency.to_string(), //we need to copy the value and not transfer ownership
+            amount,                         //hopefully we don't loose some cents here
+            currency: currency.to_string(), //we need to copy the value and not transfer ownership
             statement_descriptor_suffix: item.request.statement_descriptor_suffix.clone(),
             statement_descriptor: item.request.statement_descriptor.clone(),
             metadata_order_id,
@@ -1654,10 +1725,11 @@ impl TryFrom<&types::PaymentsAuthorizeRouterData> for PaymentIntentRequest {
             description: item.description.clone(),
             shipping: shipping_address,
             billing: billing_address,
-            capture_method: StripeCaptureMethod::from(item.request.capture_method),
+            capture_method,
             payment_data,
             mandate: mandate.map(Secret::new),
             payment_method_options,
+            request_incremental_authorization,
             payment_method,
             customer: item.connector_customer.to_owned().map(Secret::new),
             setup_mandate_details,
@@ -2441,7 +2513,13 @@ pub struct RefundRequest {
 impl<F> TryFrom<&types::RefundsRouterData<F>> for RefundRequest {
     type Error = error_stack::Report<errors::ConnectorError>;
     fn try_from(item: &types::RefundsRouterData<F>) -> Result<Self, Self::Error> {
-        let amount = item.request.refund_amount;
+        let amount = item
+            .request
+            .currency_conversion
+            .as_ref()
+            .map_or(item.request.refund_amount, |conversion| {
+                conversion.presentment_amount
+            });
         let metadata_txn_id = "Fetch txn_id from DB".to_string();
         let metadata_txn_uuid = "Fetch txn_id from DB".to_string();
         let payment_intent = item.request.connector_transaction_id.clone();
@@ -2489,6 +2567,23 @@ pub struct RefundResponse {
     pub status: RefundStatus,
 }
 
+/// Returns the amount settled by a refund in the settlement currency of the payment, as refunds of
+/// payments charged in a currency other than their settlement currency are made in the
+/// presentment currency
+fn get_refund_settled_amount(request: &types::RefundsData, amount: i64) -> i64 {
+    match &request.currency_conversion {
+        Some(conversion) if amount == conversion.presentment_amount => conversion.settlement_amount,
+        Some(conversion) if conversion.presentment_amount > 0 => {
+            let presentment_amount = i128::from(conversion.presentment_amount);
+            let settled_amount = (i128::from(amount) * i128::from(conversion.settlement_amount)
+                + presentment_amount / 2)
+                / presentment_amount;
+            i64::try_from(settled_amount).unwrap_or(amount)
+        }
+        _ => amount,
+    }
+}
+
 impl TryFrom<types::RefundsResponseRouterData<api::Execute, RefundResponse>>
     for types::RefundsRouterData<api::Execute>
 {
@@ -2500,6 +2595,10 @@ impl TryFrom<types::RefundsResponseRouterData<api::Execute, RefundResponse>>
             response: Ok(types::RefundsResponseData {
                 connector_refund_id: item.response.id,
                 refund_status: enums::RefundStatus::from(item.response.status),
+                settled_amount: Some(get_refund_settled_amount(
+                    &item.data.request,
+                    item.response.amount,
+                )),
             }),
             ..item.data
         })
@@ -2517,6 +2616,10 @@ impl TryFrom<types::RefundsResponseRouterData<api::RSync, RefundResponse>>
             response: Ok(types::RefundsResponseData {
                 connector_refund_id: item.response.id,
                 refund_status: enums::RefundStatus::from(item.response.status),
+                settled_amount: Some(get_refund_settled_amount(
+                    &item.data.request,
+                    item.response.amount,
+                )),
             }),
             ..item.data
         })
@@ -2664,8 +2767,88 @@ pub struct CaptureRequest {
 impl TryFrom<&types::PaymentsCaptureRouterData> for CaptureRequest {
     type Error = error_stack::Report<errors::ConnectorError>;
     fn try_from(item: &types::PaymentsCaptureRouterData) -> Result<Self, Self::Error> {
+        let amount_to_capture = item
+            .request
+            .currency_conversion
+            .as_ref()
+            .map_or(item.request.amount_to_capture, |conversion| {
+                conversion.presentment_amount
+            });
         Ok(Self {
-            amount_to_capture: Some(item.request.amount_to_capture),
+            amount_to_capture: Some(amount_to_capture),
+        })
+    }
+}
+
+/// Represents the incremental authorization request body for stripe connector.
+#[derive(Debug, Serialize, Clone, Copy)]
+pub struct IncrementalAuthorizationRequest {
+    /// The total amount to be authorized, including the amount authorized so far
+    amount: i64,
+}
+
+impl TryFrom<&types::PaymentsIncrementalAuthorizationRouterData>
+    for IncrementalAuthorizationRequest
+{
+    type Error = error_stack::Report<errors::ConnectorError>;
+    fn try_from(
+        item: &types::PaymentsIncrementalAuthorizationRouterData,
+    ) -> Result<Self, Self::Error> {
+        Ok(Self {
+            amount: item.request.total_amount,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct IncrementalAuthorizationResponse {
+    pub id: String,
+    pub amount: i64,
+    pub amount_capturable: Option<i64>,
+    pub status: StripePaymentStatus,
+}
+
+impl<F>
+    TryFrom<
+        types::ResponseRouterData<
+            F,
+            IncrementalAuthorizationResponse,
+            types::PaymentsIncrementalAuthorizationData,
+            types::PaymentsResponseData,
+        >,
+    >
+    for types::RouterData<
+        F,
+        types::PaymentsIncrementalAuthorizationData,
+        types::PaymentsResponseData,
+    >
+{
+    type Error = error_stack::Report<errors::ConnectorError>;
+    fn try_from(
+        item: types::ResponseRouterData<
+            F,
+            IncrementalAuthorizationResponse,
+            types::PaymentsIncrementalAuthorizationData,
+            types::PaymentsResponseData,
+        >,
+    ) -> Result<Self, Self::Error> {
+        // Declined increases are responded to with an error, the payment intent is returned with
+        // the increased capturable amount once the increase has been authorized
+        let status = if item.response.amount_capturable == Some(item.data.request.total_amount) {
+            api_enums::AuthorizationStatus::Success
+        } else {
+            api_enums::AuthorizationStatus::Failure
+        };
+        Ok(Self {
+            response: Ok(
+                types::PaymentsResponseData::IncrementalAuthorizationResponse {
+                    status,
+                    connector_authorization_id: None,
+                    error_code: None,
+                    error_message: None,
+                },
+            ),
+            ..item.data
         })
     }
 }
@@ -2727,6 +2910,7 @@ impl TryFrom<&types::PaymentsPreProcessingRouterData> for StripeCreditTransferSo
             | Some(payments::PaymentMethodData::Reward(..))
             | Some(payments::PaymentMethodData::MandatePayment)
             | Some(payments::PaymentMethodData::Upi(..))
+            | Some(payments::PaymentMethodData::CardPresent(..))
             | Some(payments::PaymentMethodData::GiftCard(..))
             | Some(payments::PaymentMethodData::CardRedirect(..))
             | Some(payments::PaymentMethodData::Voucher(..))
@@ -3213,6 +3397,7 @@ impl
             api::PaymentMethodData::MandatePayment
             | api::PaymentMethodData::Crypto(_)
             | api::PaymentMethodData::Reward(_)
+            | api::PaymentMethodData::CardPresent(_)
             | api::PaymentMethodData::GiftCard(_)
             | api::PaymentMethodData::Upi(_)
             | api::PaymentMethodData::CardRedirect(_)
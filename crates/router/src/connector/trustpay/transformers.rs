@@ -1306,6 +1306,7 @@ fn handle_cards_refund_response(
     let refund_response_data = types::RefundsResponseData {
         connector_refund_id: response.instance_id,
         refund_status,
+        settled_amount: None,
     };
     Ok((error, refund_response_data))
 }
@@ -1321,6 +1322,7 @@ fn handle_webhooks_refund_response(
             .payment_request_id
             .ok_or(errors::ConnectorError::MissingConnectorRefundID)?,
         refund_status,
+        settled_amount: None,
     };
     Ok((None, refund_response_data))
 }
@@ -1344,6 +1346,7 @@ fn handle_bank_redirects_refund_response(
     let refund_response_data = types::RefundsResponseData {
         connector_refund_id: response.payment_request_id.to_string(),
         refund_status,
+        settled_amount: None,
     };
     (error, refund_response_data)
 }
@@ -1371,6 +1374,7 @@ fn handle_bank_redirects_refund_sync_response(
     let refund_response_data = types::RefundsResponseData {
         connector_refund_id: response.payment_information.references.payment_request_id,
         refund_status,
+        settled_amount: None,
     };
     (error, refund_response_data)
 }
@@ -1390,6 +1394,7 @@ fn handle_bank_redirects_refund_sync_error_response(
     let refund_response_data = types::RefundsResponseData {
         connector_refund_id: "".to_string(),
         refund_status: enums::RefundStatus::Failure,
+        settled_amount: None,
     };
     (error, refund_response_data)
 }
@@ -489,6 +489,7 @@ impl TryFrom<types::RefundsResponseRouterData<api::Execute, RefundResponse>>
             TsysResponseTypes::SuccessResponse(return_response) => Ok(types::RefundsResponseData {
                 connector_refund_id: return_response.transaction_id,
                 refund_status: enums::RefundStatus::from(return_response.status),
+                settled_amount: None,
             }),
             TsysResponseTypes::ErrorResponse(connector_error_response) => {
                 Err(get_error_response(connector_error_response, item.http_code))
@@ -528,6 +529,7 @@ impl TryFrom<types::RefundsResponseRouterData<api::RSync, TsysSyncResponse>>
                 Ok(types::RefundsResponseData {
                     connector_refund_id: search_response.transaction_details.transaction_id.clone(),
                     refund_status: enums::RefundStatus::from(search_response.transaction_details),
+                    settled_amount: None,
                 })
             }
             SearchResponseTypes::ErrorResponse(connector_error_response) => {
@@ -6,6 +6,7 @@ use api_models::{
 };
 use base64::Engine;
 use common_utils::{
+    crypto::{self, GenerateDigest},
     date_time,
     errors::ReportSwitchExt,
     pii::{self, Email, IpAddress},
@@ -19,7 +20,9 @@ use serde::Serializer;
 use crate::{
     consts,
     core::errors::{self, CustomResult},
+    headers,
     pii::PeekInterface,
+    services::request::{self, Mask},
     types::{self, api, transformers::ForeignTryFrom, PaymentsCancelData, ResponseId},
     utils::{OptionExt, ValueExt},
 };
@@ -59,6 +62,7 @@ pub trait RouterData {
     fn get_billing_address(&self) -> Result<&api::AddressDetails, Error>;
     fn get_shipping_address(&self) -> Result<&api::AddressDetails, Error>;
     fn get_connector_meta(&self) -> Result<pii::SecretSerdeValue, Error>;
+    fn get_optional_merchant_category_code(&self) -> Option<String>;
     fn get_session_token(&self) -> Result<String, Error>;
     fn to_connector_meta<T>(&self) -> Result<T, Error>
     where
@@ -125,6 +129,20 @@ impl<Flow, Request, Response> RouterData for types::RouterData<Flow, Request, Re
             .ok_or_else(missing_field_err("connector_meta_data"))
     }
 
+    /// The merchant category code of the connector account, or of the business under which the
+    /// payment is made when the connector account does not set one
+    fn get_optional_merchant_category_code(&self) -> Option<String> {
+        self.connector_meta_data
+            .clone()
+            .map(ExposeInterface::expose)
+            .and_then(|metadata| {
+                metadata
+                    .get("merchant_category_code")
+                    .and_then(serde_json::Value::as_str)
+                    .map(ToString::to_string)
+            })
+    }
+
     fn get_session_token(&self) -> Result<String, Error> {
         self.session_token
             .clone()
@@ -155,9 +173,16 @@ impl<Flow, Request, Response> RouterData for types::RouterData<Flow, Request, Re
             .ok_or_else(missing_field_err("shipping.address"))
     }
     fn get_payment_method_token(&self) -> Result<String, Error> {
-        self.payment_method_token
+        match self
+            .payment_method_token
             .clone()
-            .ok_or_else(missing_field_err("payment_method_token"))
+            .ok_or_else(missing_field_err("payment_method_token"))?
+        {
+            types::PaymentMethodToken::Token(token) => Ok(token),
+            types::PaymentMethodToken::ApplePayDecrypt(_) => {
+                Err(errors::ConnectorError::InvalidWalletToken.into())
+            }
+        }
     }
     fn get_customer_id(&self) -> Result<String, Error> {
         self.customer_id
@@ -873,6 +898,31 @@ fn get_header_field(
         ))?
 }
 
+/// Builds the `Idempotency-Key` header from the idempotency key of the router data.
+/// Keys longer than `max_length` are replaced by their SHA-256 hex digest, so the header value
+/// stays deterministic across retries. The header is masked in the logs of the request.
+pub fn get_idempotency_key_header(
+    idempotency_key: Option<&Secret<String>>,
+    max_length: usize,
+) -> CustomResult<Vec<(String, request::Maskable<String>)>, errors::ConnectorError> {
+    let Some(key) = idempotency_key else {
+        return Ok(Vec::new());
+    };
+    let key = if key.peek().len() > max_length {
+        crypto::Sha256
+            .generate_digest(key.peek().as_bytes())
+            .map(hex::encode)
+            .change_context(errors::ConnectorError::RequestEncodingFailed)
+            .attach_printable("Failed to hash the idempotency key")?
+    } else {
+        key.peek().to_owned()
+    };
+    Ok(vec![(
+        headers::IDEMPOTENCY_KEY.to_string(),
+        key.into_masked(),
+    )])
+}
+
 pub fn to_boolean(string: String) -> bool {
     let str = string.as_str();
     match str {
@@ -966,6 +1016,28 @@ pub fn to_currency_base_unit_asf64(
         .change_context(errors::ConnectorError::RequestEncodingFailed)
 }
 
+/// Converts an amount reported by a connector in the base denomination of the currency (for
+/// example, `10.25` USD) to the lowest denomination of the currency (`1025` cents).
+pub fn to_currency_lower_unit(
+    amount: f64,
+    currency: diesel_models::enums::Currency,
+) -> Result<i64, error_stack::Report<errors::ConnectorError>> {
+    let multiplier = if currency.is_zero_decimal_currency() {
+        1.0
+    } else if currency.is_three_decimal_currency() {
+        1000.0
+    } else {
+        100.0
+    };
+    let amount = (amount * multiplier).round();
+    if !amount.is_finite() {
+        return Err(errors::ConnectorError::ResponseDeserializationFailed.into());
+    }
+    #[allow(clippy::as_conversions)]
+    let amount = amount as i64;
+    Ok(amount)
+}
+
 pub fn str_to_f32<S>(value: &str, serializer: S) -> Result<S::Ok, S::Error>
 where
     S: Serializer,
@@ -1247,3 +1319,65 @@ mod error_code_error_message_tests {
         assert_eq!(error_code_error_message_none, None);
     }
 }
+
+#[cfg(test)]
+mod currency_unit_tests {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+
+    #[test]
+    fn test_to_currency_lower_unit() {
+        assert_eq!(
+            to_currency_lower_unit(10.25, diesel_models::enums::Currency::USD).unwrap(),
+            1025
+        );
+        assert_eq!(
+            to_currency_lower_unit(1025.0, diesel_models::enums::Currency::JPY).unwrap(),
+            1025
+        );
+        assert_eq!(
+            to_currency_lower_unit(1.025, diesel_models::enums::Currency::KWD).unwrap(),
+            1025
+        );
+        // Amounts computed from exchange rates are rounded to the nearest unit
+        assert_eq!(
+            to_currency_lower_unit(0.1 + 0.2, diesel_models::enums::Currency::EUR).unwrap(),
+            30
+        );
+        assert!(to_currency_lower_unit(f64::NAN, diesel_models::enums::Currency::EUR).is_err());
+    }
+}
+
+#[cfg(test)]
+mod idempotency_key_tests {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+
+    #[test]
+    fn test_idempotency_key_header_is_masked() {
+        let idempotency_key = Secret::new("authorize_pay_1_1".to_string());
+
+        let header = get_idempotency_key_header(Some(&idempotency_key), 64).unwrap();
+
+        assert_eq!(
+            header,
+            vec![(
+                headers::IDEMPOTENCY_KEY.to_string(),
+                request::Maskable::new_masked(idempotency_key)
+            )]
+        );
+        assert!(get_idempotency_key_header(None, 64).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_long_idempotency_key_is_hashed() {
+        let idempotency_key = Secret::new("a".repeat(65));
+
+        let header = get_idempotency_key_header(Some(&idempotency_key), 64).unwrap();
+
+        let (_, value) = header.first().unwrap();
+        assert!(matches!(value, request::Maskable::Masked(_)));
+        // The SHA-256 hex digest of the key
+        assert_eq!(value.clone().into_inner().len(), 64);
+    }
+}
@@ -11,7 +11,7 @@ type Error = error_stack::Report<errors::ConnectorError>;
 
 #[cfg(feature = "payouts")]
 use crate::{
-    connector::utils::RouterData,
+    connector::utils::{self as connector_utils, RouterData},
     types::{
         api::payouts,
         storage::enums::{self as storage_enums, PayoutEntityType},
@@ -235,7 +235,7 @@ pub struct WisePayoutResponse {
     quote_uuid: String,
     status: WiseStatus,
     reference: String,
-    rate: f32,
+    rate: f64,
     business: Option<i64>,
     details: WiseTransferDetails,
     has_active_issues: bool,
@@ -274,6 +274,13 @@ pub struct WiseFulfillResponse {
     balance_transaction_id: Option<i64>,
 }
 
+#[cfg(feature = "payouts")]
+#[derive(Debug, Deserialize)]
+pub struct WisePayoutSyncResponse {
+    id: i64,
+    status: WiseStatus,
+}
+
 #[cfg(feature = "payouts")]
 #[derive(Debug, Default, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "UPPERCASE")]
@@ -291,6 +298,18 @@ pub enum WiseStatus {
 
     #[serde(rename = "incoming_payment_waiting")]
     IncomingPaymentWaiting,
+
+    #[serde(rename = "funds_converted")]
+    FundsConverted,
+
+    #[serde(rename = "outgoing_payment_sent")]
+    OutgoingPaymentSent,
+
+    #[serde(rename = "bounced_back")]
+    BouncedBack,
+
+    #[serde(rename = "funds_refunded")]
+    FundsRefunded,
 }
 
 #[cfg(feature = "payouts")]
@@ -414,6 +433,8 @@ impl<F> TryFrom<types::PayoutsResponseRouterData<F, WiseRecipientCreateResponse>
                 status: Some(storage_enums::PayoutStatus::RequiresCreation),
                 connector_payout_id: response.id.to_string(),
                 payout_eligible: None,
+                connector_fee: None,
+                fx_margin: None,
             }),
             ..item.data
         })
@@ -459,6 +480,8 @@ impl<F> TryFrom<types::PayoutsResponseRouterData<F, WisePayoutQuoteResponse>>
                 status: Some(storage_enums::PayoutStatus::RequiresCreation),
                 connector_payout_id: response.id,
                 payout_eligible: None,
+                connector_fee: None,
+                fx_margin: None,
             }),
             ..item.data
         })
@@ -511,6 +534,7 @@ impl<F> TryFrom<types::PayoutsResponseRouterData<F, WisePayoutResponse>>
         item: types::PayoutsResponseRouterData<F, WisePayoutResponse>,
     ) -> Result<Self, Self::Error> {
         let response: WisePayoutResponse = item.response;
+        let connector_fee = get_transfer_fee(&response, item.data.request.source_currency)?;
         let status = match storage_enums::PayoutStatus::foreign_from(response.status) {
             storage_enums::PayoutStatus::Cancelled => storage_enums::PayoutStatus::Cancelled,
             _ => storage_enums::PayoutStatus::RequiresFulfillment,
@@ -521,12 +545,30 @@ impl<F> TryFrom<types::PayoutsResponseRouterData<F, WisePayoutResponse>>
                 status: Some(status),
                 connector_payout_id: response.id.to_string(),
                 payout_eligible: None,
+                connector_fee,
+                // Wise converts at the mid-market rate and includes its conversion charges in the
+                // fee, so it does not report a separate FX margin
+                fx_margin: None,
             }),
             ..item.data
         })
     }
 }
 
+/// Wise deducts its fee from the source value of a transfer and converts the rest at the rate of
+/// the transfer, so the fee is the part of the source value which was not converted.
+#[cfg(feature = "payouts")]
+fn get_transfer_fee(
+    response: &WisePayoutResponse,
+    source_currency: storage_enums::Currency,
+) -> Result<Option<i64>, Error> {
+    if response.rate <= 0.0 {
+        return Ok(None);
+    }
+    let fee = response.source_value - response.target_value / response.rate;
+    connector_utils::to_currency_lower_unit(fee.max(0.0), source_currency).map(Some)
+}
+
 // Payouts fulfill request transform
 #[cfg(feature = "payouts")]
 impl<F> TryFrom<&types::PayoutsRouterData<F>> for WisePayoutFulfillRequest {
@@ -562,6 +604,38 @@ impl<F> TryFrom<types::PayoutsResponseRouterData<F, WiseFulfillResponse>>
                 status: Some(storage_enums::PayoutStatus::foreign_from(response.status)),
                 connector_payout_id: "".to_string(),
                 payout_eligible: None,
+                connector_fee: None,
+                fx_margin: None,
+            }),
+            ..item.data
+        })
+    }
+}
+
+// Payouts transfer sync response
+#[cfg(feature = "payouts")]
+impl<F> TryFrom<types::PayoutsResponseRouterData<F, WisePayoutSyncResponse>>
+    for types::PayoutsRouterData<F>
+{
+    type Error = Error;
+    fn try_from(
+        item: types::PayoutsResponseRouterData<F, WisePayoutSyncResponse>,
+    ) -> Result<Self, Self::Error> {
+        let response: WisePayoutSyncResponse = item.response;
+        // A transfer waits for the incoming payment until it is funded, which is when the payout
+        // is fulfilled
+        let status = match response.status {
+            WiseStatus::IncomingPaymentWaiting => storage_enums::PayoutStatus::RequiresFulfillment,
+            status => storage_enums::PayoutStatus::foreign_from(status),
+        };
+
+        Ok(Self {
+            response: Ok(types::PayoutsResponseData {
+                status: Some(status),
+                connector_payout_id: response.id.to_string(),
+                payout_eligible: None,
+                connector_fee: None,
+                fx_margin: None,
             }),
             ..item.data
         })
@@ -572,12 +646,14 @@ impl<F> TryFrom<types::PayoutsResponseRouterData<F, WiseFulfillResponse>>
 impl ForeignFrom<WiseStatus> for storage_enums::PayoutStatus {
     fn foreign_from(wise_status: WiseStatus) -> Self {
         match wise_status {
-            WiseStatus::Completed => Self::Success,
-            WiseStatus::Rejected => Self::Failed,
+            WiseStatus::Completed | WiseStatus::OutgoingPaymentSent => Self::Success,
+            WiseStatus::Rejected | WiseStatus::FundsRefunded => Self::Failed,
             WiseStatus::Cancelled => Self::Cancelled,
-            WiseStatus::Pending | WiseStatus::Processing | WiseStatus::IncomingPaymentWaiting => {
-                Self::Pending
-            }
+            WiseStatus::BouncedBack => Self::Reversed,
+            WiseStatus::Pending
+            | WiseStatus::Processing
+            | WiseStatus::IncomingPaymentWaiting
+            | WiseStatus::FundsConverted => Self::Pending,
         }
     }
 }
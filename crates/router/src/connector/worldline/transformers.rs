@@ -629,6 +629,7 @@ impl TryFrom<types::RefundsResponseRouterData<api::Execute, RefundResponse>>
             response: Ok(types::RefundsResponseData {
                 connector_refund_id: item.response.id.clone(),
                 refund_status,
+                settled_amount: None,
             }),
             ..item.data
         })
@@ -647,6 +648,7 @@ impl TryFrom<types::RefundsResponseRouterData<api::RSync, RefundResponse>>
             response: Ok(types::RefundsResponseData {
                 connector_refund_id: item.response.id.clone(),
                 refund_status,
+                settled_amount: None,
             }),
             ..item.data
         })
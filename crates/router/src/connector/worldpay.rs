@@ -539,6 +539,7 @@ impl ConnectorIntegration<api::Execute, types::RefundsData, types::RefundsRespon
                     response: Ok(types::RefundsResponseData {
                         connector_refund_id: ResponseIdStr::try_from(response.links)?.id,
                         refund_status: enums::RefundStatus::Success,
+                        settled_amount: None,
                     }),
                     ..data.clone()
                 })
@@ -609,6 +610,7 @@ impl ConnectorIntegration<api::RSync, types::RefundsData, types::RefundsResponse
             response: Ok(types::RefundsResponseData {
                 connector_refund_id: data.request.refund_id.clone(),
                 refund_status: enums::RefundStatus::from(response.last_event),
+                settled_amount: None,
             }),
             ..data.clone()
         })
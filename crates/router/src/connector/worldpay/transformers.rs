@@ -6,7 +6,7 @@ use masking::{PeekInterface, Secret};
 
 use super::{requests::*, response::*};
 use crate::{
-    connector::utils,
+    connector::utils::{self, RouterData},
     consts,
     core::errors,
     types::{self, api},
@@ -91,6 +91,7 @@ fn fetch_payment_instrument(
         | api_models::payments::PaymentMethodData::Upi(_)
         | api_models::payments::PaymentMethodData::Voucher(_)
         | api_models::payments::PaymentMethodData::CardRedirect(_)
+        | api_models::payments::PaymentMethodData::CardPresent(_)
         | api_models::payments::PaymentMethodData::GiftCard(_) => {
             Err(errors::ConnectorError::NotImplemented(
                 utils::get_unimplemented_payment_method_error_message("worldpay"),
@@ -120,6 +121,7 @@ impl TryFrom<&types::PaymentsAuthorizeRouterData> for WorldpayPaymentsRequest {
             },
             merchant: Merchant {
                 entity: item.attempt_id.clone().replace('_', "-"),
+                mcc: item.get_optional_merchant_category_code(),
                 ..Default::default()
             },
             transaction_reference: item.attempt_id.clone(),
@@ -17,7 +17,6 @@ use crate::{
         errors::{self, CustomResult},
         payments,
     },
-    db::StorageInterface,
     headers,
     services::{
         self,
@@ -27,7 +26,7 @@ use crate::{
     types::{
         self,
         api::{self, ConnectorCommon, ConnectorCommonExt},
-        domain, ErrorResponse, Response,
+        ErrorResponse, Response,
     },
     utils::{self, BytesExt},
 };
@@ -553,25 +552,16 @@ impl api::IncomingWebhook for Zen {
         Ok(msg.into_bytes())
     }
 
-    async fn verify_webhook_source(
+    fn verify_webhook_source_with_secret(
         &self,
-        db: &dyn StorageInterface,
         request: &api::IncomingWebhookRequestDetails<'_>,
         merchant_id: &str,
-        connector_label: &str,
-        key_store: &domain::MerchantKeyStore,
+        secret: &[u8],
     ) -> CustomResult<bool, errors::ConnectorError> {
         let algorithm = self.get_webhook_source_verification_algorithm(request)?;
 
         let signature = self.get_webhook_source_verification_signature(request)?;
-        let mut secret = self
-            .get_webhook_source_verification_merchant_secret(
-                db,
-                merchant_id,
-                connector_label,
-                key_store,
-            )
-            .await?;
+        let mut secret = secret.to_vec();
         let mut message =
             self.get_webhook_source_verification_message(request, merchant_id, &secret)?;
         message.append(&mut secret);
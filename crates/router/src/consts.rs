@@ -17,11 +17,141 @@ pub const REQUEST_TIMEOUT_ERROR_MESSAGE: &str = "Connector did not respond in sp
 ///Payment intent fulfillment default timeout (in seconds)
 pub const DEFAULT_FULFILLMENT_TIME: i64 = 15 * 60;
 
+/// Default delay (in seconds) after which payments with `automatic_delayed` capture are captured
+pub const DEFAULT_DELAYED_CAPTURE_DELAY: i64 = 2 * 60 * 60;
+
+/// Default time budget (in milliseconds) given to each connector while creating session tokens
+pub const DEFAULT_SESSION_CALL_TIME_BUDGET: u64 = 3000;
+
+/// Time (in seconds) after which the session tokens of wallets are to be refreshed, as Apple Pay
+/// merchant sessions expire after five minutes
+pub const SESSION_TOKEN_VALIDITY: i64 = 5 * 60;
+
+/// Number of ledger entries read at a time, and maximum number of journal entries exported, while
+/// exporting the journal of a period
+pub const JOURNAL_EXPORT_RECORDS_LIMIT: i64 = 10000;
+
+/// Maximum number of commissions listed in the commission statement of a period, and number of
+/// commissions read at a time to sum up the statement
+pub const COMMISSION_STATEMENT_RECORDS_LIMIT: i64 = 10000;
+
+/// Delay (in seconds) after which the commission of a payment is recorded again, if recording it
+/// along with the payment failed
+pub const COMMISSION_RECORDING_RETRY_DELAY: i64 = 15 * 60;
+
+/// Maximum number of retries of the recording of the commission of a payment
+pub const COMMISSION_RECORDING_MAX_RETRIES: i32 = 10;
+
+/// Default and maximum number of ledger entries included in a list of ledger entries
+pub const DEFAULT_LEDGER_ENTRY_LIST_LIMIT: i64 = 100;
+pub const MAX_LEDGER_ENTRY_LIST_LIMIT: i64 = 1000;
+
+/// Default and maximum number of audit events included in a list of audit events
+pub const DEFAULT_AUDIT_EVENT_LIST_LIMIT: i64 = 100;
+pub const MAX_AUDIT_EVENT_LIST_LIMIT: i64 = 1000;
+
+/// Number of ledger entries read at a time while reconciling a settlement report
+pub const LEDGER_RECONCILIATION_RECORDS_LIMIT: i64 = 10000;
+
+/// Number of payments returned by a page of the payments list with filters, unless a limit is
+/// passed
+pub const PAYMENTS_LIST_DEFAULT_LIMIT: u32 = 20;
+
+/// Maximum number of disputes included in a scheduled report; the payments of a period are
+/// summarized in full
+pub const REPORT_RECORDS_LIMIT: i64 = 10000;
+
+/// Delay (in seconds) after which the generation of a scheduled report is retried, if it failed
+pub const REPORT_GENERATION_RETRY_DELAY: i64 = 60 * 60;
+
+/// Delay (in seconds) after which the billing of a subscription is retried, if it failed with an
+/// error before a payment could be attempted
+pub const SUBSCRIPTION_BILLING_RETRY_DELAY: i64 = 60 * 60;
+
+/// Delay (in seconds) after which a task is retried, if the storage region of its merchant could
+/// not be determined
+pub const STORAGE_REGION_LOOKUP_RETRY_DELAY: i64 = 60;
+
+/// Maximum number of retries of a task whose merchant could not be found, after which the task is
+/// finished
+pub const STORAGE_REGION_LOOKUP_MAX_RETRIES: i32 = 5;
+
+/// Maximum number of retries of a failed subscription payment which a merchant can configure
+pub const SUBSCRIPTION_DUNNING_MAX_RETRIES: usize = 10;
+
+/// Maximum delay (in hours) of a retry of a failed subscription payment
+pub const SUBSCRIPTION_DUNNING_MAX_RETRY_INTERVAL: u32 = 720;
+
+/// Maximum number of keys in a request updating the metadata of a payment
+pub const PAYMENT_METADATA_MAX_KEYS: usize = 50;
+
+/// Maximum length (in characters) of the keys in a request updating the metadata of a payment
+pub const PAYMENT_METADATA_MAX_KEY_LENGTH: usize = 40;
+
+/// Maximum length (in characters) of the values in a request updating the metadata of a payment
+pub const PAYMENT_METADATA_MAX_VALUE_LENGTH: usize = 500;
+
+/// Maximum time (in seconds) for which a payment retrieve request waits for a terminal status
+pub const MAX_PAYMENT_RETRIEVE_WAIT_TIME: u64 = 60;
+
+/// Interval (in seconds) at which a waiting payment retrieve request re-reads the payment
+pub const PAYMENT_RETRIEVE_WAIT_POLL_INTERVAL: u64 = 2;
+
+/// Maximum number of customers in a batch import
+pub const MAX_CUSTOMER_IMPORT_RECORDS: usize = 10000;
+
+/// Maximum size (in bytes) of the body of a batch import of customers
+pub const CUSTOMER_IMPORT_REQUEST_BODY_LIMIT: usize = 8 * 1024 * 1024;
+
+/// Number of customers imported between two updates of the progress of a batch import
+pub const CUSTOMER_IMPORT_PROGRESS_INTERVAL: usize = 100;
+
+/// Maximum number of payouts in a batch of payouts
+pub const MAX_PAYOUT_BATCH_SIZE: usize = 100;
+
+/// Maximum number of payouts of a batch which are created concurrently
+pub const PAYOUT_BATCH_CONCURRENCY: usize = 10;
+
+/// Maximum number of retries of a webhook that a merchant can configure
+pub const MAX_WEBHOOK_RETRIES: u8 = 10;
+
+/// Minimum delay (in seconds) before the first retry of a webhook that a merchant can configure
+pub const MIN_WEBHOOK_RETRY_INITIAL_DELAY: u32 = 10;
+
+/// Maximum delay (in seconds) before the first retry of a webhook that a merchant can configure
+pub const MAX_WEBHOOK_RETRY_INITIAL_DELAY: u32 = 60 * 60;
+
+/// Number of days for which the public key of a retired webhook signing key is still published
+pub const WEBHOOK_SIGNING_KEY_GRACE_PERIOD_DAYS: i64 = 7;
+
+/// Size in bits of the RSA keys with which outgoing webhooks are signed
+pub const WEBHOOK_SIGNING_RSA_KEY_SIZE: u32 = 2048;
+
+/// Number of days of payment attempts considered for the conversion of the payment method types
+/// of a merchant
+pub const PAYMENT_METHOD_CONVERSION_LOOKBACK_DAYS: i64 = 30;
+
+/// Maximum number of the latest payment attempts considered for the conversion of the payment
+/// method types of a merchant
+pub const PAYMENT_METHOD_CONVERSION_SAMPLE_SIZE: i64 = 1000;
+
+/// Time in seconds for which the conversions of the payment method types of a merchant are cached
+pub const PAYMENT_METHOD_CONVERSION_CACHE_TTL: i64 = 15 * 60;
+
+/// Connectors which send network tokens in place of card numbers when network token data is
+/// provided for a payment
+pub const NETWORK_TOKEN_CONNECTORS: [&str; 1] = ["cybersource"];
+
 // String literals
 pub(crate) const NO_ERROR_MESSAGE: &str = "No error message";
 pub(crate) const NO_ERROR_CODE: &str = "No error code";
 pub(crate) const UNSUPPORTED_ERROR_MESSAGE: &str = "Unsupported response type";
 pub(crate) const CONNECTOR_UNAUTHORIZED_ERROR: &str = "Authentication Error from the connector";
+pub(crate) const DEFAULT_CONVENIENCE_FEE_DESCRIPTION: &str = "Convenience fee";
+pub(crate) const DEFAULT_CROSS_BORDER_SURCHARGE_DESCRIPTION: &str = "Cross-border surcharge";
+
+/// Denominator of rates expressed in basis points
+pub(crate) const BASIS_POINTS_DENOMINATOR: i64 = 10000;
 
 // General purpose base64 engines
 pub(crate) const BASE64_ENGINE: base64::engine::GeneralPurpose =
@@ -39,3 +169,9 @@ pub(crate) const APPLEPAY_VALIDATION_URL: &str =
 // Qr Image data source starts with this string
 // The base64 image data will be appended to it to image data source
 pub(crate) const QR_IMAGE_DATA_SOURCE_STRING: &str = "data:image/png;base64";
+
+// Version of the master key, when no version has been configured for it
+pub(crate) const INITIAL_MERCHANT_KEY_VERSION: i32 = 1;
+
+// Roles of the dashboard users allowed to approve or reject refunds held for approval
+pub(crate) const REFUND_APPROVER_ROLES: &[&str] = &["merchant_admin", "refund_approver"];
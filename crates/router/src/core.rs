@@ -1,18 +1,40 @@
+#[cfg(feature = "olap")]
+pub mod accounting;
 pub mod admin;
+#[cfg(feature = "olap")]
+pub mod analytics;
 pub mod api_keys;
+pub mod audit;
 pub mod cache;
 pub mod cards_info;
+pub mod commissions;
 pub mod configs;
 pub mod customers;
 pub mod disputes;
 pub mod errors;
+pub mod event_bus;
+pub mod feature_flags;
 pub mod files;
+pub mod gift_cards;
+pub mod hooks;
+pub mod invoices;
+pub mod ledger;
+#[cfg(feature = "olap")]
+pub mod lookup;
 pub mod mandate;
 pub mod metrics;
+pub mod payment_links;
 pub mod payment_methods;
 pub mod payments;
 #[cfg(feature = "payouts")]
 pub mod payouts;
 pub mod refunds;
+#[cfg(feature = "olap")]
+pub mod reports;
+pub mod runtime_config;
+#[cfg(feature = "olap")]
+pub mod subscriptions;
+pub mod sync_cache;
+pub mod terminals;
 pub mod utils;
 pub mod webhooks;
@@ -0,0 +1,216 @@
+use api_models::accounting::{
+    JournalEntryType, JournalExportFormat, JournalExportRequest, JournalExportResponse,
+    JournalLine, LedgerAccount, LedgerEntryListConstraints,
+};
+use common_utils::date_time;
+use error_stack::{IntoReport, ResultExt};
+use router_env::{instrument, tracing};
+
+use crate::{
+    consts,
+    core::{
+        errors::{self, RouterResponse, RouterResult},
+        ledger,
+    },
+    routes::AppState,
+    services::ApplicationResponse,
+    types::{domain, storage::enums},
+};
+
+/// Column headers of the CSV export, in the order in which the fields of a [`JournalLine`] are
+/// written.
+const CSV_HEADERS: &str =
+    "entry_id,entry_type,reference_id,account,debit,credit,currency,connector,posted_at";
+
+/// Builds the two balancing lines of a journal entry, moving `amount` from the `credit` account
+/// to the `debit` account.
+#[allow(clippy::too_many_arguments)]
+fn journal_entry(
+    entry_id: String,
+    entry_type: JournalEntryType,
+    reference_id: &str,
+    debit: LedgerAccount,
+    credit: LedgerAccount,
+    amount: i64,
+    currency: enums::Currency,
+    connector: Option<String>,
+    posted_at: time::PrimitiveDateTime,
+) -> [JournalLine; 2] {
+    [
+        JournalLine {
+            entry_id: entry_id.clone(),
+            entry_type,
+            reference_id: reference_id.to_owned(),
+            account: debit,
+            debit: amount,
+            credit: 0,
+            currency,
+            connector: connector.clone(),
+            posted_at,
+        },
+        JournalLine {
+            entry_id,
+            entry_type,
+            reference_id: reference_id.to_owned(),
+            account: credit,
+            debit: 0,
+            credit: amount,
+            currency,
+            connector,
+            posted_at,
+        },
+    ]
+}
+
+/// The entries of the ledger which are not exported in the journal, the memo entries of the
+/// authorizations.
+const NON_JOURNAL_ENTRY_TYPES: [JournalEntryType; 2] = [
+    JournalEntryType::Authorization,
+    JournalEntryType::AuthorizationRelease,
+];
+
+/// Returns the journal lines of the entries posted to the ledger of the merchant during the
+/// period, reading the entries a page at a time. At most [`consts::JOURNAL_EXPORT_RECORDS_LIMIT`]
+/// entries are exported, longer periods have to be exported in parts.
+async fn get_ledger_lines(
+    state: &AppState,
+    merchant_account: &domain::MerchantAccount,
+    start_time: time::PrimitiveDateTime,
+    end_time: time::PrimitiveDateTime,
+    currency: Option<enums::Currency>,
+) -> RouterResult<Vec<JournalLine>> {
+    let constraints = LedgerEntryListConstraints {
+        currency,
+        start_time: Some(start_time),
+        end_time: Some(end_time),
+        ..Default::default()
+    };
+
+    let mut lines = Vec::new();
+    let mut entry_count = 0;
+    let mut after_id = 0;
+    loop {
+        let entries = state
+            .store
+            .filter_ledger_entries_by_constraints_after_id(
+                &merchant_account.merchant_id,
+                &constraints,
+                after_id,
+                consts::JOURNAL_EXPORT_RECORDS_LIMIT,
+            )
+            .await
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Failed to retrieve the ledger entries for the journal")?;
+        let is_last_page = i64::try_from(entries.len())
+            .map_or(false, |count| count < consts::JOURNAL_EXPORT_RECORDS_LIMIT);
+
+        for entry in entries {
+            after_id = entry.id;
+            let entry = ledger::get_ledger_entry_response(entry)?;
+            if NON_JOURNAL_ENTRY_TYPES.contains(&entry.entry_type) {
+                continue;
+            }
+            entry_count += 1;
+            if entry_count > consts::JOURNAL_EXPORT_RECORDS_LIMIT {
+                Err(errors::ApiErrorResponse::InvalidRequestData {
+                    message: format!(
+                        "The journal of the period has more than {} entries, export a shorter \
+                         period",
+                        consts::JOURNAL_EXPORT_RECORDS_LIMIT
+                    ),
+                })?
+            }
+            lines.extend(journal_entry(
+                entry.entry_id,
+                entry.entry_type,
+                &entry.reference_id,
+                entry.debit_account,
+                entry.credit_account,
+                entry.amount,
+                entry.currency,
+                entry.connector,
+                entry.created,
+            ));
+        }
+        if is_last_page {
+            break;
+        }
+    }
+    Ok(lines)
+}
+
+pub(crate) fn escape_csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_owned()
+    }
+}
+
+fn journal_to_csv(lines: &[JournalLine]) -> RouterResult<Vec<u8>> {
+    let mut csv = String::from(CSV_HEADERS);
+    csv.push('\n');
+    for line in lines {
+        let posted_at = line
+            .posted_at
+            .assume_utc()
+            .format(&time::format_description::well_known::Rfc3339)
+            .into_report()
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Failed to format the posting time of a journal line")?;
+        let fields = [
+            escape_csv_field(&line.entry_id),
+            line.entry_type.to_string(),
+            escape_csv_field(&line.reference_id),
+            line.account.to_string(),
+            line.debit.to_string(),
+            line.credit.to_string(),
+            line.currency.to_string(),
+            escape_csv_field(line.connector.as_deref().unwrap_or_default()),
+            posted_at,
+        ];
+        csv.push_str(&fields.join(","));
+        csv.push('\n');
+    }
+    Ok(csv.into_bytes())
+}
+
+/// Generates the double-entry journal of the merchant for the requested period. Sales, fees,
+/// refunds, chargebacks and payouts are exported from the entries posted to the ledger when they
+/// happened, so that the journal of a past period does not change.
+#[instrument(skip(state))]
+pub async fn export_journal(
+    state: &AppState,
+    merchant_account: domain::MerchantAccount,
+    req: JournalExportRequest,
+) -> RouterResponse<JournalExportResponse> {
+    let start_time = req.start_time;
+    let end_time = req.end_time.unwrap_or_else(date_time::now);
+    if start_time > end_time {
+        Err(errors::ApiErrorResponse::InvalidRequestData {
+            message: "`start_time` must be earlier than `end_time`".to_string(),
+        })?
+    }
+
+    let mut lines =
+        get_ledger_lines(state, &merchant_account, start_time, end_time, req.currency).await?;
+    // Sorting is stable, so the lines of an entry stay together in the debit, credit order
+    lines.sort_by(|a, b| {
+        a.posted_at
+            .cmp(&b.posted_at)
+            .then_with(|| a.entry_id.cmp(&b.entry_id))
+    });
+
+    match req.format {
+        JournalExportFormat::Json => Ok(ApplicationResponse::Json(JournalExportResponse {
+            start_time,
+            end_time,
+            total_count: lines.len(),
+            lines,
+        })),
+        JournalExportFormat::Csv => Ok(ApplicationResponse::FileData((
+            journal_to_csv(&lines)?,
+            mime::TEXT_CSV,
+        ))),
+    }
+}
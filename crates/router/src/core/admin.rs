@@ -1,22 +1,36 @@
-use api_models::{admin::PrimaryBusinessDetails, enums as api_enums};
+pub mod connector_certificate;
+pub mod connector_verification;
+pub mod webhook_secrets;
+pub mod webhook_signing_keys;
+
+use std::{collections::HashMap, str::FromStr};
+
+use api_models::{
+    admin::{PaymentLinkConfig, PrimaryBusinessDetails},
+    enums as api_enums,
+};
 use common_utils::{
-    crypto::{generate_cryptographically_secure_random_string, OptionalSecretValue},
+    crypto::{generate_cryptographically_secure_random_string, Encryptable, OptionalSecretValue},
     date_time,
-    ext_traits::{Encode, ValueExt},
+    ext_traits::ValueExt,
+    pii,
 };
 use diesel_models::enums;
 use error_stack::{report, FutureExt, ResultExt};
-use masking::{PeekInterface, Secret};
+use masking::{ExposeInterface, PeekInterface, Secret};
+use router_env::logger;
 use uuid::Uuid;
 
 use crate::{
     consts,
     core::{
+        audit,
         errors::{self, RouterResponse, RouterResult, StorageErrorExt},
-        payments::helpers,
+        payments::{apple_pay, connector_exclusion, helpers},
+        utils as core_utils,
     },
     db::StorageInterface,
-    routes::metrics,
+    routes::{metrics, AppState},
     services::{self, api as service_api},
     types::{
         self, api,
@@ -39,9 +53,10 @@ pub fn create_merchant_publishable_key() -> String {
 }
 
 pub async fn create_merchant_account(
-    db: &dyn StorageInterface,
+    state: &AppState,
     req: api::MerchantAccountCreate,
 ) -> RouterResponse<api::MerchantAccountResponse> {
+    let db = &*state.store;
     let master_key = db.get_master_key();
 
     let key = services::generate_aes256_key()
@@ -50,12 +65,19 @@ pub async fn create_merchant_account(
 
     let publishable_key = Some(create_merchant_publishable_key());
 
-    let primary_business_details = utils::Encode::<Vec<PrimaryBusinessDetails>>::encode_to_value(
-        &req.primary_business_details.unwrap_or_default(),
-    )
-    .change_context(errors::ApiErrorResponse::InvalidDataValue {
-        field_name: "primary_business_details",
-    })?;
+    validate_payment_link_configs(req.primary_business_details.as_deref())?;
+    validate_receipt_configs(req.primary_business_details.as_deref())?;
+    validate_payment_method_rankings(req.primary_business_details.as_deref())?;
+    validate_business_webhook_endpoints(req.primary_business_details.as_deref())?;
+
+    let mut primary_business_details = req.primary_business_details.unwrap_or_default();
+    let business_webhook_secrets =
+        take_business_webhook_secrets(&mut primary_business_details, &HashMap::new());
+    let primary_business_details =
+        utils::Encode::<Vec<PrimaryBusinessDetails>>::encode_to_value(&primary_business_details)
+            .change_context(errors::ApiErrorResponse::InvalidDataValue {
+                field_name: "primary_business_details",
+            })?;
 
     let merchant_details: OptionalSecretValue =
         req.merchant_details
@@ -69,6 +91,9 @@ pub async fn create_merchant_account(
             .transpose()?
             .map(Into::into);
 
+    validate_webhook_retry_policy(req.webhook_details.as_ref())?;
+    validate_storage_region(state, req.storage_region.as_deref())?;
+
     let webhook_details =
         req.webhook_details
             .as_ref()
@@ -80,14 +105,10 @@ pub async fn create_merchant_account(
             })
             .transpose()?;
 
+    let webhook_mtls_details = get_webhook_mtls_details_value(req.webhook_mtls_details.as_ref())?;
+
     if let Some(ref routing_algorithm) = req.routing_algorithm {
-        let _: api::RoutingAlgorithm = routing_algorithm
-            .clone()
-            .parse_value("RoutingAlgorithm")
-            .change_context(errors::ApiErrorResponse::InvalidDataValue {
-                field_name: "routing_algorithm",
-            })
-            .attach_printable("Invalid routing algorithm given")?;
+        validate_routing_algorithm(routing_algorithm)?;
     }
 
     let key_store = domain::MerchantKeyStore {
@@ -97,6 +118,8 @@ pub async fn create_merchant_account(
             .change_context(errors::ApiErrorResponse::InternalServerError)
             .attach_printable("Failed to decrypt data from key store")?,
         created_at: date_time::now(),
+        key_version: db.get_master_key_version(),
+        rotated_at: None,
     };
 
     let enable_payment_response_hash = req.enable_payment_response_hash.unwrap_or(true);
@@ -117,6 +140,12 @@ pub async fn create_merchant_account(
     )
     .await?;
 
+    let business_webhook_secrets = if business_webhook_secrets.is_empty() {
+        None
+    } else {
+        Some(encrypt_business_webhook_secrets(business_webhook_secrets, &key).await?)
+    };
+
     let merchant_account = async {
         Ok(domain::MerchantAccount {
             merchant_id: req.merchant_id,
@@ -150,6 +179,14 @@ pub async fn create_merchant_account(
             id: None,
             organization_id: req.organization_id,
             is_recon_enabled: false,
+            webhook_mtls_details: webhook_mtls_details
+                .async_lift(|inner| domain_types::encrypt_optional(inner, &key))
+                .await?,
+            allowed_domains: None,
+            connector_exclusion_rules: None,
+            storage_region: req.storage_region,
+            webhook_signing_keys: None,
+            business_webhook_secrets,
         })
     }
     .await
@@ -159,12 +196,22 @@ pub async fn create_merchant_account(
         .insert_merchant(merchant_account, &key_store)
         .await
         .to_duplicate_response(errors::ApiErrorResponse::DuplicateMerchantAccount)?;
-    Ok(service_api::ApplicationResponse::Json(
-        merchant_account
-            .try_into()
-            .change_context(errors::ApiErrorResponse::InternalServerError)
-            .attach_printable("Failed while generating response")?,
-    ))
+    let response: api::MerchantAccountResponse = merchant_account
+        .try_into()
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed while generating response")?;
+
+    audit::record_audit_event(
+        db,
+        &response.merchant_id,
+        enums::AuditEntityType::MerchantAccount,
+        &response.merchant_id,
+        None,
+        Some(&response),
+    )
+    .await?;
+
+    Ok(service_api::ApplicationResponse::Json(response))
 }
 
 pub async fn get_merchant_account(
@@ -217,28 +264,59 @@ pub async fn merchant_account_update(
     }
 
     if let Some(ref routing_algorithm) = req.routing_algorithm {
-        let _: api::RoutingAlgorithm = routing_algorithm
-            .clone()
-            .parse_value("RoutingAlgorithm")
-            .change_context(errors::ApiErrorResponse::InvalidDataValue {
-                field_name: "routing_algorithm",
-            })
-            .attach_printable("Invalid routing algorithm given")?;
+        validate_routing_algorithm(routing_algorithm)?;
     }
 
-    let primary_business_details = req
-        .primary_business_details
-        .as_ref()
-        .map(|primary_business_details| {
-            utils::Encode::<Vec<PrimaryBusinessDetails>>::encode_to_value(primary_business_details)
-                .change_context(errors::ApiErrorResponse::InvalidDataValue {
-                    field_name: "primary_business_details",
-                })
-        })
-        .transpose()?;
+    let merchant_account = db
+        .find_merchant_account_by_merchant_id(merchant_id, &key_store)
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::MerchantAccountNotFound)?;
+
+    validate_merchant_urls_against_allowed_domains(
+        req.return_url.as_ref().map(url::Url::as_str),
+        req.webhook_details.as_ref(),
+        req.primary_business_details.as_deref(),
+        merchant_account.allowed_domains.as_deref(),
+    )?;
+    validate_webhook_retry_policy(req.webhook_details.as_ref())?;
+    validate_payment_link_configs(req.primary_business_details.as_deref())?;
+    validate_receipt_configs(req.primary_business_details.as_deref())?;
+    validate_payment_method_rankings(req.primary_business_details.as_deref())?;
+    validate_business_webhook_endpoints(req.primary_business_details.as_deref())?;
+
+    // The signing keys of the webhook endpoints which are updated without a key are retained
+    let existing_business_webhook_secrets = get_business_webhook_secrets(&merchant_account)
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Unable to deserialize the business webhook secrets")?;
+
+    let previous_merchant_account: api::MerchantAccountResponse = merchant_account
+        .try_into()
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed while generating response")?;
 
     let key = key_store.key.get_inner().peek();
 
+    let (primary_business_details, business_webhook_secrets) = match req.primary_business_details {
+        Some(mut primary_business_details) => {
+            let business_webhook_secrets = take_business_webhook_secrets(
+                &mut primary_business_details,
+                &existing_business_webhook_secrets,
+            );
+            let primary_business_details =
+                utils::Encode::<Vec<PrimaryBusinessDetails>>::encode_to_value(
+                    &primary_business_details,
+                )
+                .change_context(errors::ApiErrorResponse::InvalidDataValue {
+                    field_name: "primary_business_details",
+                })?;
+            (
+                Some(primary_business_details),
+                Some(encrypt_business_webhook_secrets(business_webhook_secrets, key).await?),
+            )
+        }
+        None => (None, None),
+    };
+
     let updated_merchant_account = storage::MerchantAccountUpdate::Update {
         merchant_name: req
             .merchant_name
@@ -290,29 +368,428 @@ pub async fn merchant_account_update(
         frm_routing_algorithm: req.frm_routing_algorithm,
         intent_fulfillment_time: req.intent_fulfillment_time.map(i64::from),
         payout_routing_algorithm: req.payout_routing_algorithm,
+        webhook_mtls_details: get_webhook_mtls_details_value(req.webhook_mtls_details.as_ref())?
+            .async_lift(|inner| domain_types::encrypt_optional(inner, key))
+            .await
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Unable to encrypt webhook mTLS details")?,
+        business_webhook_secrets,
     };
 
     let response = db
         .update_specific_fields_in_merchant(merchant_id, updated_merchant_account, &key_store)
         .await
         .to_not_found_response(errors::ApiErrorResponse::MerchantAccountNotFound)?;
+    let response: api::MerchantAccountResponse = response
+        .try_into()
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed while generating response")?;
 
-    Ok(service_api::ApplicationResponse::Json(
-        response
-            .try_into()
-            .change_context(errors::ApiErrorResponse::InternalServerError)
-            .attach_printable("Failed while generating response")?,
-    ))
+    audit::record_merchant_account_update(db, &previous_merchant_account, &response).await?;
+
+    Ok(service_api::ApplicationResponse::Json(response))
+}
+
+/// Validates that the return url and the webhook url of the merchant are hosted on the domains in
+/// its allowlist, if it has one.
+fn validate_merchant_urls_against_allowed_domains(
+    return_url: Option<&str>,
+    webhook_details: Option<&api::WebhookDetails>,
+    primary_business_details: Option<&[PrimaryBusinessDetails]>,
+    allowed_domains: Option<&[String]>,
+) -> RouterResult<()> {
+    if let Some(return_url) = return_url {
+        core_utils::validate_url_against_allowed_domains(
+            return_url,
+            "return_url",
+            allowed_domains,
+        )?;
+    }
+
+    if let Some(webhook_url) = webhook_details.and_then(|details| details.webhook_url.as_ref()) {
+        core_utils::validate_url_against_allowed_domains(
+            webhook_url.peek(),
+            "webhook_details.webhook_url",
+            allowed_domains,
+        )?;
+    }
+
+    for endpoint in primary_business_details
+        .unwrap_or_default()
+        .iter()
+        .flat_map(|business_details| {
+            business_details
+                .webhook_endpoints
+                .as_deref()
+                .unwrap_or_default()
+        })
+    {
+        core_utils::validate_url_against_allowed_domains(
+            endpoint.webhook_url.peek(),
+            "primary_business_details.webhook_endpoints.webhook_url",
+            allowed_domains,
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Validates that the storage region of the merchant is one of the regions configured for data
+/// residency.
+fn validate_storage_region(state: &AppState, storage_region: Option<&str>) -> RouterResult<()> {
+    match storage_region {
+        Some(storage_region)
+            if !state
+                .conf
+                .data_residency
+                .regions
+                .contains_key(storage_region) =>
+        {
+            Err(report!(errors::ApiErrorResponse::InvalidRequestData {
+                message: format!("storage_region {storage_region} is not a configured region"),
+            }))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Validates that the retries of webhooks configured by the merchant are within the supported
+/// limits.
+fn validate_webhook_retry_policy(
+    webhook_details: Option<&api::WebhookDetails>,
+) -> RouterResult<()> {
+    let Some(webhook_details) = webhook_details else {
+        return Ok(());
+    };
+
+    if webhook_details.max_retries.map_or(false, |max_retries| {
+        max_retries > consts::MAX_WEBHOOK_RETRIES
+    }) {
+        Err(errors::ApiErrorResponse::InvalidRequestData {
+            message: format!(
+                "webhook_details.max_retries must be at most {}",
+                consts::MAX_WEBHOOK_RETRIES
+            ),
+        })?
+    }
+
+    if webhook_details.retry_initial_delay.map_or(false, |delay| {
+        !(consts::MIN_WEBHOOK_RETRY_INITIAL_DELAY..=consts::MAX_WEBHOOK_RETRY_INITIAL_DELAY)
+            .contains(&delay)
+    }) {
+        Err(errors::ApiErrorResponse::InvalidRequestData {
+            message: format!(
+                "webhook_details.retry_initial_delay must be between {} and {} seconds",
+                consts::MIN_WEBHOOK_RETRY_INITIAL_DELAY,
+                consts::MAX_WEBHOOK_RETRY_INITIAL_DELAY
+            ),
+        })?
+    }
+
+    Ok(())
+}
+
+/// Validates the branding configured for the pages hosted for each of the businesses of the
+/// merchant, as it is rendered on those pages.
+fn validate_payment_link_configs(
+    primary_business_details: Option<&[PrimaryBusinessDetails]>,
+) -> RouterResult<()> {
+    primary_business_details
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|business_details| business_details.payment_link_config.as_ref())
+        .try_for_each(|payment_link_config| {
+            validate_payment_link_config(payment_link_config, "payment_link_config")
+        })
+}
+
+/// Validates the branding of a page hosted for the merchant, `field_name` being the field of the
+/// request holding it.
+pub fn validate_payment_link_config(
+    payment_link_config: &PaymentLinkConfig,
+    field_name: &str,
+) -> RouterResult<()> {
+    let is_hex_color = |color: &str| {
+        color.strip_prefix('#').map_or(false, |hex| {
+            hex.len() == 6 && hex.chars().all(|c| c.is_ascii_hexdigit())
+        })
+    };
+
+    if payment_link_config
+        .logo
+        .as_ref()
+        .map_or(false, |logo| logo.scheme() != "https")
+    {
+        Err(errors::ApiErrorResponse::InvalidRequestData {
+            message: format!("{field_name}.logo must be an HTTPS URL"),
+        })?
+    }
+
+    for (color_field, color) in [
+        ("primary_color", &payment_link_config.primary_color),
+        ("background_color", &payment_link_config.background_color),
+    ] {
+        if color.as_deref().map_or(false, |color| !is_hex_color(color)) {
+            Err(errors::ApiErrorResponse::InvalidRequestData {
+                message: format!(
+                    "{field_name}.{color_field} must be a hexadecimal RGB code, such as #006DF9"
+                ),
+            })?
+        }
+    }
+
+    if payment_link_config
+        .locale
+        .as_deref()
+        .map_or(false, |locale| {
+            service_api::get_redirection_message(locale).is_none()
+        })
+    {
+        Err(errors::ApiErrorResponse::InvalidRequestData {
+            message: format!("{field_name}.locale is not a supported locale"),
+        })?
+    }
+
+    Ok(())
+}
+
+/// Validates the length of the texts of the receipt template configured for each of the
+/// businesses of the merchant.
+fn validate_receipt_configs(
+    primary_business_details: Option<&[PrimaryBusinessDetails]>,
+) -> RouterResult<()> {
+    for receipt_config in primary_business_details
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|business_details| business_details.receipt_config.as_ref())
+    {
+        for (field_name, text, max_length) in [
+            ("title", &receipt_config.title, 255),
+            ("header", &receipt_config.header, 1024),
+            ("footer", &receipt_config.footer, 1024),
+        ] {
+            if text
+                .as_ref()
+                .map_or(false, |text| text.chars().count() > max_length)
+            {
+                Err(errors::ApiErrorResponse::InvalidRequestData {
+                    message: format!(
+                        "receipt_config.{field_name} must be at most {max_length} characters long"
+                    ),
+                })?
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Validates that the payment method types ranked for each of the businesses of the merchant are
+/// ranked only once.
+fn validate_payment_method_rankings(
+    primary_business_details: Option<&[PrimaryBusinessDetails]>,
+) -> RouterResult<()> {
+    for payment_method_ranking in primary_business_details
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|business_details| business_details.payment_method_ranking.as_ref())
+    {
+        let mut ranked_payment_method_types = std::collections::HashSet::new();
+        if let Some(payment_method_type) = payment_method_ranking
+            .iter()
+            .find(|payment_method_type| !ranked_payment_method_types.insert(**payment_method_type))
+        {
+            Err(errors::ApiErrorResponse::InvalidRequestData {
+                message: format!(
+                    "payment_method_ranking contains the payment method type {payment_method_type} more than once"
+                ),
+            })?
+        }
+    }
+
+    Ok(())
+}
+
+/// Validates that the identifiers of the webhook endpoints registered for the businesses of the
+/// merchant are unique among all the businesses.
+fn validate_business_webhook_endpoints(
+    primary_business_details: Option<&[PrimaryBusinessDetails]>,
+) -> RouterResult<()> {
+    let mut endpoint_ids = std::collections::HashSet::new();
+    for endpoint in primary_business_details
+        .unwrap_or_default()
+        .iter()
+        .flat_map(|business_details| {
+            business_details
+                .webhook_endpoints
+                .as_deref()
+                .unwrap_or_default()
+        })
+    {
+        if endpoint.endpoint_id.is_empty() || endpoint.endpoint_id.len() > 64 {
+            Err(errors::ApiErrorResponse::InvalidRequestData {
+                message: "webhook_endpoints.endpoint_id must be between 1 and 64 characters long"
+                    .to_string(),
+            })?
+        }
+        if !endpoint_ids.insert(endpoint.endpoint_id.as_str()) {
+            Err(errors::ApiErrorResponse::InvalidRequestData {
+                message: format!(
+                    "webhook_endpoints contains the endpoint_id {} more than once",
+                    endpoint.endpoint_id
+                ),
+            })?
+        }
+    }
+
+    Ok(())
+}
+
+/// Returns the signing keys of the webhook endpoints registered for the businesses of the
+/// merchant, by the identifier of the endpoint. Keys stored in the business details before the
+/// keys were stored encrypted are used until the business details are next updated.
+pub fn get_business_webhook_secrets(
+    merchant_account: &domain::MerchantAccount,
+) -> errors::CustomResult<HashMap<String, Secret<String>>, errors::ParsingError> {
+    let mut secrets = merchant_account
+        .business_webhook_secrets
+        .clone()
+        .map(|secrets| {
+            secrets
+                .into_inner()
+                .expose()
+                .parse_value::<HashMap<String, Secret<String>>>("BusinessWebhookSecrets")
+        })
+        .transpose()?
+        .unwrap_or_default();
+
+    let primary_business_details: Vec<PrimaryBusinessDetails> = merchant_account
+        .primary_business_details
+        .clone()
+        .parse_value("PrimaryBusinessDetails")?;
+    for endpoint in primary_business_details
+        .into_iter()
+        .flat_map(|business_details| business_details.webhook_endpoints.unwrap_or_default())
+    {
+        if let Some(webhook_secret) = endpoint.webhook_secret {
+            secrets
+                .entry(endpoint.endpoint_id)
+                .or_insert(webhook_secret);
+        }
+    }
+
+    Ok(secrets)
+}
+
+/// Takes the signing keys of the webhook endpoints out of the business details, which are stored
+/// in plain text, so that they are stored encrypted instead. Returns the keys by the identifier of
+/// the endpoint, retaining the key in `existing_secrets` for the endpoints passed without a key.
+fn take_business_webhook_secrets(
+    primary_business_details: &mut [PrimaryBusinessDetails],
+    existing_secrets: &HashMap<String, Secret<String>>,
+) -> HashMap<String, Secret<String>> {
+    let mut secrets = HashMap::new();
+    for endpoint in primary_business_details
+        .iter_mut()
+        .flat_map(|business_details| business_details.webhook_endpoints.iter_mut().flatten())
+    {
+        let webhook_secret = endpoint
+            .webhook_secret
+            .take()
+            .or_else(|| existing_secrets.get(&endpoint.endpoint_id).cloned());
+        if let Some(webhook_secret) = webhook_secret {
+            secrets.insert(endpoint.endpoint_id.clone(), webhook_secret);
+        }
+    }
+    secrets
+}
+
+async fn encrypt_business_webhook_secrets(
+    secrets: HashMap<String, Secret<String>>,
+    key: &[u8],
+) -> RouterResult<Encryptable<Secret<serde_json::Value>>> {
+    let secrets = utils::Encode::<HashMap<String, Secret<String>>>::encode_to_value(&secrets)
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Unable to convert the business webhook secrets to a value")?;
+    domain_types::encrypt(Secret::new(secrets), key)
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Unable to encrypt the business webhook secrets")
+}
+
+/// Validates that the client certificate and key provided for webhooks can be used to establish
+/// a mutual TLS connection, and converts them into a value that can be encrypted and stored.
+fn get_webhook_mtls_details_value(
+    webhook_mtls_details: Option<&api::WebhookMtlsDetails>,
+) -> RouterResult<OptionalSecretValue> {
+    webhook_mtls_details
+        .map(|webhook_mtls_details| {
+            helpers::create_identity_from_certificate_and_key(
+                webhook_mtls_details.client_certificate.peek().clone(),
+                webhook_mtls_details.client_certificate_key.peek().clone(),
+            )
+            .change_context(errors::ApiErrorResponse::InvalidDataValue {
+                field_name: "webhook_mtls_details",
+            })
+            .attach_printable("Invalid client certificate or key provided for webhooks")?;
+
+            utils::Encode::<api::WebhookMtlsDetails>::encode_to_value(webhook_mtls_details)
+                .change_context(errors::ApiErrorResponse::InternalServerError)
+                .attach_printable("Unable to convert webhook mTLS details to a value")
+        })
+        .transpose()
+        .map(|value| value.map(Into::into))
+}
+
+fn validate_routing_algorithm(routing_algorithm: &serde_json::Value) -> RouterResult<()> {
+    let routing_algorithm: api::RoutingAlgorithm = routing_algorithm
+        .clone()
+        .parse_value("RoutingAlgorithm")
+        .change_context(errors::ApiErrorResponse::InvalidDataValue {
+            field_name: "routing_algorithm",
+        })
+        .attach_printable("Invalid routing algorithm given")?;
+
+    utils::when(routing_algorithm.get_connectors().is_empty(), || {
+        Err(report!(errors::ApiErrorResponse::InvalidDataValue {
+            field_name: "routing_algorithm",
+        }))
+        .attach_printable("No connectors given in the routing algorithm")
+    })
 }
 
 pub async fn merchant_account_delete(
     db: &dyn StorageInterface,
     merchant_id: String,
 ) -> RouterResponse<api::MerchantAccountDeleteResponse> {
+    let key_store = db
+        .get_merchant_key_store_by_merchant_id(&merchant_id, &db.get_master_key().to_vec().into())
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::MerchantAccountNotFound)?;
+    let merchant_account: api::MerchantAccountResponse = db
+        .find_merchant_account_by_merchant_id(&merchant_id, &key_store)
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::MerchantAccountNotFound)?
+        .try_into()
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed while generating response")?;
+
     let is_deleted = db
         .delete_merchant_account_by_merchant_id(&merchant_id)
         .await
         .to_not_found_response(errors::ApiErrorResponse::MerchantAccountNotFound)?;
+    if is_deleted {
+        audit::record_audit_event(
+            db,
+            &merchant_id,
+            enums::AuditEntityType::MerchantAccount,
+            &merchant_id,
+            Some(&merchant_account),
+            None,
+        )
+        .await?;
+    }
+
     let response = api::MerchantAccountDeleteResponse {
         merchant_id,
         deleted: is_deleted,
@@ -378,6 +855,7 @@ fn validate_certificate_in_mca_metadata(
                     let api_models::payments::SessionTokenInfo {
                         certificate,
                         certificate_keys,
+                        payment_processing_details,
                         ..
                     } = session_token_data;
 
@@ -385,7 +863,13 @@ fn validate_certificate_in_mca_metadata(
                         .change_context(errors::ApiErrorResponse::InvalidDataValue {
                             field_name: "certificate/certificate key",
                         })
-                        .map(|_identity_result| ())
+                        .and_then(|_identity_result| {
+                            payment_processing_details
+                                .as_ref()
+                                .map(apple_pay::validate_payment_processing_details)
+                                .transpose()
+                        })
+                        .map(|_| ())
                 })
         })
         .transpose()?;
@@ -394,10 +878,11 @@ fn validate_certificate_in_mca_metadata(
 }
 
 pub async fn create_payment_connector(
-    store: &dyn StorageInterface,
+    state: &AppState,
     req: api::MerchantConnectorCreate,
     merchant_id: &String,
 ) -> RouterResponse<api_models::admin::MerchantConnectorResponse> {
+    let store = &*state.store;
     let key_store = store
         .get_merchant_key_store_by_merchant_id(merchant_id, &store.get_master_key().to_vec().into())
         .await
@@ -464,8 +949,44 @@ pub async fn create_payment_connector(
         }
     })?;
 
+    if let (Some(connector_account_details), Some(test_connector_account_details)) = (
+        req.connector_account_details.as_ref(),
+        req.test_connector_account_details.as_ref(),
+    ) {
+        validate_test_connector_account_details(
+            Some(req.connector_name),
+            req.test_mode,
+            connector_account_details.peek(),
+            test_connector_account_details,
+        )?;
+    }
+
     let frm_configs = get_frm_config_as_secret(req.frm_configs);
 
+    let client_certificate = match req.connector_mtls_details {
+        Some(connector_mtls_details) => Some(
+            connector_certificate::encrypt_client_certificate(connector_mtls_details, &key_store)
+                .await?,
+        ),
+        None => None,
+    };
+
+    let webhook_secrets = match req.connector_webhook_details {
+        Some(connector_webhook_details) => Some(
+            webhook_secrets::encrypt_webhook_secrets(connector_webhook_details, &key_store).await?,
+        ),
+        None => None,
+    };
+
+    // The Apple Pay payment processing key is stored encrypted, apart from the metadata
+    let mut metadata = req.metadata;
+    let apple_pay_payment_processing_key =
+        apple_pay::take_payment_processing_certificate_key(&mut metadata)
+            .async_lift(|key| domain_types::encrypt_optional(key, key_store.key.peek()))
+            .await
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Unable to encrypt Apple Pay payment processing key")?;
+
     let merchant_connector_account = domain::MerchantConnectorAccount {
         merchant_id: merchant_id.to_string(),
         connector_type: req.connector_type,
@@ -482,10 +1003,16 @@ pub async fn create_payment_connector(
         .await
         .change_context(errors::ApiErrorResponse::InternalServerError)
         .attach_printable("Unable to encrypt connector account details")?,
+        test_connector_account_details: req
+            .test_connector_account_details
+            .async_lift(|inner| domain_types::encrypt_optional(inner, key_store.key.peek()))
+            .await
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Unable to encrypt test connector account details")?,
         payment_methods_enabled,
         test_mode: req.test_mode,
         disabled: req.disabled,
-        metadata: req.metadata,
+        metadata,
         frm_configs,
         connector_label: connector_label.clone(),
         business_country: req.business_country,
@@ -494,20 +1021,31 @@ pub async fn create_payment_connector(
         created_at: common_utils::date_time::now(),
         modified_at: common_utils::date_time::now(),
         id: None,
-        connector_webhook_details: match req.connector_webhook_details {
-            Some(connector_webhook_details) => {
-                Encode::<api_models::admin::MerchantConnectorWebhookDetails>::encode_to_value(
-                    &connector_webhook_details,
-                )
-                .change_context(errors::ApiErrorResponse::InternalServerError)
-                .attach_printable(format!("Failed to serialize api_models::admin::MerchantConnectorWebhookDetails for Merchant: {}", merchant_id))
-                .map(Some)?
-                .map(masking::Secret::new)
-            }
-            None => None,
-        },
+        // The webhook secrets are stored encrypted instead
+        connector_webhook_details: None,
+        client_certificate_expires_at: client_certificate
+            .as_ref()
+            .map(|client_certificate| client_certificate.expires_at),
+        client_certificate_key: client_certificate
+            .as_ref()
+            .map(|client_certificate| client_certificate.client_certificate_key.clone()),
+        client_certificate: client_certificate
+            .map(|client_certificate| client_certificate.client_certificate),
+        webhook_secret: webhook_secrets
+            .as_ref()
+            .map(|webhook_secrets| webhook_secrets.webhook_secret.clone()),
+        additional_webhook_secret: webhook_secrets
+            .and_then(|webhook_secrets| webhook_secrets.additional_webhook_secret),
+        apple_pay_payment_processing_key,
     };
 
+    let verification_status = connector_verification::validate_account_details(
+        state,
+        &merchant_account,
+        &merchant_connector_account,
+    )
+    .await?;
+
     let mca = store
         .insert_merchant_connector_account(merchant_connector_account, &key_store)
         .await
@@ -526,7 +1064,29 @@ pub async fn create_payment_connector(
         ],
     );
 
-    let mca_response = mca.try_into()?;
+    if mca.client_certificate_expires_at.is_some() {
+        if let Err(error) =
+            connector_certificate::schedule_certificate_expiry_reminders(state, &mca).await
+        {
+            logger::error!(connector_certificate_expiry_reminder_error=?error);
+        }
+    }
+
+    let mut mca_response: api_models::admin::MerchantConnectorResponse = mca.try_into()?;
+
+    audit::record_audit_event(
+        store,
+        merchant_id,
+        enums::AuditEntityType::MerchantConnectorAccount,
+        &mca_response.merchant_connector_id,
+        None,
+        Some(&mca_response),
+    )
+    .await?;
+
+    // The status of the verification is not stored, so it is only returned in the response
+    mca_response.verification_status = verification_status;
+
     Ok(service_api::ApplicationResponse::Json(mca_response))
 }
 
@@ -599,17 +1159,18 @@ pub async fn list_payment_connectors(
 }
 
 pub async fn update_payment_connector(
-    db: &dyn StorageInterface,
+    state: &AppState,
     merchant_id: &str,
     merchant_connector_id: &str,
     req: api_models::admin::MerchantConnectorUpdate,
 ) -> RouterResponse<api_models::admin::MerchantConnectorResponse> {
+    let db = &*state.store;
     let key_store = db
         .get_merchant_key_store_by_merchant_id(merchant_id, &db.get_master_key().to_vec().into())
         .await
         .to_not_found_response(errors::ApiErrorResponse::MerchantAccountNotFound)?;
 
-    let _merchant_account = db
+    let merchant_account = db
         .find_merchant_account_by_merchant_id(merchant_id, &key_store)
         .await
         .to_not_found_response(errors::ApiErrorResponse::MerchantAccountNotFound)?;
@@ -634,15 +1195,103 @@ pub async fn update_payment_connector(
             .collect::<Vec<serde_json::Value>>()
     });
 
+    if let Some(test_connector_account_details) = req.test_connector_account_details.as_ref() {
+        validate_test_connector_account_details(
+            api_enums::Connector::from_str(&mca.connector_name).ok(),
+            mca.test_mode,
+            req.connector_account_details
+                .as_ref()
+                .unwrap_or(mca.connector_account_details.get_inner())
+                .peek(),
+            test_connector_account_details,
+        )?;
+    }
+
     let frm_configs = get_frm_config_as_secret(req.frm_configs);
 
+    // A client certificate provided in the update replaces the one configured earlier
+    let client_certificate = match req.connector_mtls_details {
+        Some(connector_mtls_details) => Some(
+            connector_certificate::encrypt_client_certificate(connector_mtls_details, &key_store)
+                .await?,
+        ),
+        None => None,
+    };
+    let is_client_certificate_rotated = client_certificate.is_some();
+
+    // Webhook details provided in the update replace both the webhook secrets configured earlier
+    let webhook_secrets = match req.connector_webhook_details {
+        Some(connector_webhook_details) => Some(
+            webhook_secrets::encrypt_webhook_secrets(connector_webhook_details, &key_store).await?,
+        ),
+        None => None,
+    };
+    let previous_mca: api_models::admin::MerchantConnectorResponse = mca.clone().try_into()?;
+
+    // An Apple Pay payment processing key provided in the metadata replaces the one configured
+    // earlier, which is retained otherwise
+    req.metadata
+        .clone()
+        .map(
+            |metadata| match mca.apple_pay_payment_processing_key.as_ref() {
+                Some(key) => {
+                    apple_pay::add_payment_processing_certificate_key(metadata, key.get_inner())
+                }
+                None => metadata,
+            },
+        )
+        .map(validate_certificate_in_mca_metadata)
+        .transpose()?;
+    let mut metadata = req.metadata;
+    let apple_pay_payment_processing_key =
+        apple_pay::take_payment_processing_certificate_key(&mut metadata)
+            .async_lift(|key| domain_types::encrypt_optional(key, key_store.key.get_inner().peek()))
+            .await
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Failed while encrypting Apple Pay payment processing key")?;
+
+    let connector_account_details = req
+        .connector_account_details
+        .async_lift(|inner| domain_types::encrypt_optional(inner, key_store.key.get_inner().peek()))
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed while encrypting data")?;
+
+    // The account details are verified again when the credentials used to reach the connector
+    // change
+    let mut verification_status = None;
+    if connector_account_details.is_some() || metadata.is_some() || is_client_certificate_rotated {
+        let mut merchant_connector_account = mca.clone();
+        if let Some(connector_account_details) = connector_account_details.clone() {
+            merchant_connector_account.connector_account_details = connector_account_details;
+        }
+        if let Some(metadata) = metadata.clone() {
+            merchant_connector_account.metadata = Some(metadata);
+        }
+        if let Some(client_certificate) = client_certificate.as_ref() {
+            merchant_connector_account.client_certificate =
+                Some(client_certificate.client_certificate.clone());
+            merchant_connector_account.client_certificate_key =
+                Some(client_certificate.client_certificate_key.clone());
+            merchant_connector_account.client_certificate_expires_at =
+                Some(client_certificate.expires_at);
+        }
+        verification_status = connector_verification::validate_account_details(
+            state,
+            &merchant_account,
+            &merchant_connector_account,
+        )
+        .await?;
+    }
+
     let payment_connector = storage::MerchantConnectorAccountUpdate::Update {
         merchant_id: None,
         connector_type: Some(req.connector_type),
         connector_name: None,
         merchant_connector_id: None,
-        connector_account_details: req
-            .connector_account_details
+        connector_account_details,
+        test_connector_account_details: req
+            .test_connector_account_details
             .async_lift(|inner| {
                 domain_types::encrypt_optional(inner, key_store.key.get_inner().peek())
             })
@@ -652,19 +1301,25 @@ pub async fn update_payment_connector(
         test_mode: mca.test_mode,
         disabled: mca.disabled,
         payment_methods_enabled,
-        metadata: req.metadata,
+        metadata,
         frm_configs,
-        connector_webhook_details: match &req.connector_webhook_details {
-            Some(connector_webhook_details) => {
-                Encode::<api_models::admin::MerchantConnectorWebhookDetails>::encode_to_value(
-                    connector_webhook_details,
-                )
-                .change_context(errors::ApiErrorResponse::InternalServerError)
-                .map(Some)?
-                .map(masking::Secret::new)
-            }
-            None => None,
-        },
+        // The plaintext webhook details of the accounts configured before the webhook secrets
+        // were encrypted are cleared once the secrets are replaced
+        connector_webhook_details: webhook_secrets.as_ref().map(|_| None),
+        client_certificate_expires_at: client_certificate
+            .as_ref()
+            .map(|client_certificate| client_certificate.expires_at),
+        client_certificate_key: client_certificate
+            .as_ref()
+            .map(|client_certificate| client_certificate.client_certificate_key.clone()),
+        client_certificate: client_certificate
+            .map(|client_certificate| client_certificate.client_certificate),
+        webhook_secret: webhook_secrets
+            .as_ref()
+            .map(|webhook_secrets| webhook_secrets.webhook_secret.clone()),
+        additional_webhook_secret: webhook_secrets
+            .map(|webhook_secrets| webhook_secrets.additional_webhook_secret),
+        apple_pay_payment_processing_key,
     };
 
     let updated_mca = db
@@ -675,7 +1330,137 @@ pub async fn update_payment_connector(
             format!("Failed while updating MerchantConnectorAccount: id: {merchant_connector_id}")
         })?;
 
-    let response = updated_mca.try_into()?;
+    if is_client_certificate_rotated {
+        if let Err(error) =
+            connector_certificate::schedule_certificate_expiry_reminders(state, &updated_mca).await
+        {
+            logger::error!(connector_certificate_expiry_reminder_error=?error);
+        }
+    }
+
+    let mut response: api_models::admin::MerchantConnectorResponse = updated_mca.try_into()?;
+
+    audit::record_audit_event(
+        db,
+        merchant_id,
+        enums::AuditEntityType::MerchantConnectorAccount,
+        merchant_connector_id,
+        Some(&previous_mca),
+        Some(&response),
+    )
+    .await?;
+
+    response.verification_status = verification_status;
+
+    Ok(service_api::ApplicationResponse::Json(response))
+}
+
+pub async fn toggle_payment_method_type(
+    db: &dyn StorageInterface,
+    merchant_id: &str,
+    merchant_connector_id: &str,
+    req: api_models::admin::PaymentMethodTypeToggleRequest,
+) -> RouterResponse<api_models::admin::MerchantConnectorResponse> {
+    let key_store = db
+        .get_merchant_key_store_by_merchant_id(merchant_id, &db.get_master_key().to_vec().into())
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::MerchantAccountNotFound)?;
+
+    let mca = db
+        .find_by_merchant_connector_account_merchant_id_merchant_connector_id(
+            merchant_id,
+            merchant_connector_id,
+            &key_store,
+        )
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::MerchantConnectorAccountNotFound {
+            id: merchant_connector_id.to_string(),
+        })?;
+
+    let mut payment_methods_enabled = mca
+        .payment_methods_enabled
+        .clone()
+        .unwrap_or_default()
+        .into_iter()
+        .map(|payment_method| {
+            payment_method.parse_value::<api::PaymentMethodsEnabled>("PaymentMethodsEnabled")
+        })
+        .collect::<Result<Vec<_>, _>>()
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to deserialize payment methods enabled for the connector")?;
+
+    let mut is_payment_method_type_configured = false;
+    for payment_method_type in payment_methods_enabled
+        .iter_mut()
+        .filter(|payment_method| payment_method.payment_method == req.payment_method)
+        .flat_map(|payment_method| payment_method.payment_method_types.iter_mut().flatten())
+        .filter(|payment_method_type| {
+            payment_method_type.payment_method_type == req.payment_method_type
+        })
+    {
+        payment_method_type.enabled = req.enabled;
+        is_payment_method_type_configured = true;
+    }
+
+    utils::when(!is_payment_method_type_configured, || {
+        Err(errors::ApiErrorResponse::InvalidRequestData {
+            message: format!(
+                "payment method type {} of payment method {} is not configured for the connector",
+                req.payment_method_type, req.payment_method
+            ),
+        })
+    })?;
+
+    let payment_methods_enabled = payment_methods_enabled
+        .iter()
+        .map(utils::Encode::<api::PaymentMethodsEnabled>::encode_to_value)
+        .collect::<Result<Vec<_>, _>>()
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to serialize payment methods enabled for the connector")?;
+
+    let previous_mca: api_models::admin::MerchantConnectorResponse = mca.clone().try_into()?;
+    let payment_connector = storage::MerchantConnectorAccountUpdate::Update {
+        merchant_id: None,
+        connector_type: None,
+        connector_name: None,
+        merchant_connector_id: None,
+        connector_account_details: None,
+        test_mode: mca.test_mode,
+        disabled: mca.disabled,
+        payment_methods_enabled: Some(payment_methods_enabled),
+        metadata: None,
+        frm_configs: None,
+        connector_webhook_details: None,
+        test_connector_account_details: None,
+        client_certificate: None,
+        client_certificate_key: None,
+        client_certificate_expires_at: None,
+        webhook_secret: None,
+        additional_webhook_secret: None,
+        apple_pay_payment_processing_key: None,
+    };
+
+    // Updating the merchant connector account also invalidates its cached copy, so that payments
+    // honor the toggle right away
+    let updated_mca = db
+        .update_merchant_connector_account(mca, payment_connector.into(), &key_store)
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable_lazy(|| {
+            format!("Failed while updating MerchantConnectorAccount: id: {merchant_connector_id}")
+        })?;
+
+    let response: api_models::admin::MerchantConnectorResponse = updated_mca.try_into()?;
+
+    audit::record_audit_event(
+        db,
+        merchant_id,
+        enums::AuditEntityType::MerchantConnectorAccount,
+        merchant_connector_id,
+        Some(&previous_mca),
+        Some(&response),
+    )
+    .await?;
 
     Ok(service_api::ApplicationResponse::Json(response))
 }
@@ -695,7 +1480,7 @@ pub async fn delete_payment_connector(
         .await
         .to_not_found_response(errors::ApiErrorResponse::MerchantAccountNotFound)?;
 
-    let _mca = db
+    let mca: api_models::admin::MerchantConnectorResponse = db
         .find_by_merchant_connector_account_merchant_id_merchant_connector_id(
             &merchant_id,
             &merchant_connector_id,
@@ -704,7 +1489,8 @@ pub async fn delete_payment_connector(
         .await
         .to_not_found_response(errors::ApiErrorResponse::MerchantConnectorAccountNotFound {
             id: merchant_connector_id.clone(),
-        })?;
+        })?
+        .try_into()?;
 
     let is_deleted = db
         .delete_merchant_connector_account_by_merchant_id_merchant_connector_id(
@@ -716,6 +1502,18 @@ pub async fn delete_payment_connector(
             id: merchant_connector_id.clone(),
         })?;
 
+    if is_deleted {
+        audit::record_audit_event(
+            db,
+            &merchant_id,
+            enums::AuditEntityType::MerchantConnectorAccount,
+            &merchant_connector_id,
+            Some(&mca),
+            None,
+        )
+        .await?;
+    }
+
     let response = api::MerchantConnectorDeleteResponse {
         merchant_id,
         merchant_connector_id,
@@ -740,6 +1538,15 @@ pub async fn kv_for_merchant(
         .await
         .to_not_found_response(errors::ApiErrorResponse::MerchantAccountNotFound)?;
 
+    // Entries of the key value store are drained to the default storage, whereas the data of
+    // merchants with a storage region must stay in their region
+    if enable && merchant_account.storage_region.is_some() {
+        return Err(report!(errors::ApiErrorResponse::PreconditionFailed {
+            message: "The key value store cannot be enabled for merchants with a storage region"
+                .to_string(),
+        }));
+    }
+
     let updated_merchant_account = match (enable, merchant_account.storage_scheme) {
         (true, enums::MerchantStorageScheme::RedisKv)
         | (false, enums::MerchantStorageScheme::PostgresOnly) => Ok(merchant_account),
@@ -810,6 +1617,256 @@ pub async fn check_merchant_account_kv_status(
     ))
 }
 
+pub async fn retrieve_merchant_key_store(
+    db: &dyn StorageInterface,
+    merchant_id: String,
+) -> RouterResponse<api_models::admin::MerchantKeyStoreResponse> {
+    let key_store = db
+        .get_merchant_key_store_by_merchant_id(&merchant_id, &db.get_master_key().to_vec().into())
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::MerchantAccountNotFound)?;
+
+    Ok(service_api::ApplicationResponse::Json(
+        api_models::admin::MerchantKeyStoreResponse::from(key_store),
+    ))
+}
+
+/// Re-encrypts the merchant encryption key with the current master key and records the version of
+/// the master key on the key store. Key stores still encrypted with the previous master key are
+/// decrypted with it until they are rotated.
+///
+/// Only the master key is rotated: the merchant encryption key itself is kept, so that the data
+/// encrypted with it need not be re-encrypted.
+pub async fn rotate_merchant_key_store(
+    db: &dyn StorageInterface,
+    merchant_id: String,
+) -> RouterResponse<api_models::admin::MerchantKeyStoreResponse> {
+    let master_key = db.get_master_key();
+    let master_key_version = db.get_master_key_version();
+    let key_store = db
+        .get_merchant_key_store_by_merchant_id(&merchant_id, &master_key.to_vec().into())
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::MerchantAccountNotFound)?;
+
+    if key_store.key_version == master_key_version {
+        return Ok(service_api::ApplicationResponse::Json(
+            api_models::admin::MerchantKeyStoreResponse::from(key_store),
+        ));
+    }
+
+    let rotated_key = domain_types::encrypt(key_store.key.into_inner(), master_key)
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to encrypt merchant key with the current master key")?;
+
+    let updated_key_store = db
+        .update_merchant_key_store(
+            &merchant_id,
+            storage::MerchantKeyStoreUpdate::KeyRotation {
+                key: rotated_key.into(),
+                key_version: master_key_version,
+                rotated_at: date_time::now(),
+            },
+            &master_key.to_vec().into(),
+        )
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable_lazy(|| {
+            format!("Failed to rotate merchant key store for merchant_id: {merchant_id}")
+        })?;
+
+    metrics::MERCHANT_KEY_STORE_ROTATED.add(&metrics::CONTEXT, 1, &[]);
+
+    Ok(service_api::ApplicationResponse::Json(
+        api_models::admin::MerchantKeyStoreResponse::from(updated_key_store),
+    ))
+}
+
+pub async fn retrieve_merchant_allowed_domains(
+    db: &dyn StorageInterface,
+    merchant_id: String,
+) -> RouterResponse<api::MerchantAllowedDomainsResponse> {
+    let key_store = db
+        .get_merchant_key_store_by_merchant_id(&merchant_id, &db.get_master_key().to_vec().into())
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::MerchantAccountNotFound)?;
+
+    let merchant_account = db
+        .find_merchant_account_by_merchant_id(&merchant_id, &key_store)
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::MerchantAccountNotFound)?;
+
+    Ok(service_api::ApplicationResponse::Json(
+        api::MerchantAllowedDomainsResponse {
+            merchant_id: merchant_account.merchant_id,
+            allowed_domains: merchant_account.allowed_domains.unwrap_or_default(),
+        },
+    ))
+}
+
+/// Replaces the domains on which return urls and webhook urls of the merchant must be hosted.
+/// The allowlist is rejected if the urls already configured on the merchant account are not
+/// hosted on one of its domains.
+pub async fn update_merchant_allowed_domains(
+    db: &dyn StorageInterface,
+    merchant_id: String,
+    req: api::MerchantAllowedDomainsUpdateRequest,
+) -> RouterResponse<api::MerchantAllowedDomainsResponse> {
+    let key_store = db
+        .get_merchant_key_store_by_merchant_id(&merchant_id, &db.get_master_key().to_vec().into())
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::MerchantAccountNotFound)?;
+
+    let merchant_account = db
+        .find_merchant_account_by_merchant_id(&merchant_id, &key_store)
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::MerchantAccountNotFound)?;
+
+    let mut allowed_domains = req
+        .allowed_domains
+        .iter()
+        .map(String::as_str)
+        .map(normalize_allowed_domain)
+        .collect::<RouterResult<Vec<_>>>()?;
+    allowed_domains.sort();
+    allowed_domains.dedup();
+
+    let webhook_details = merchant_account
+        .webhook_details
+        .clone()
+        .map(|webhook_details| webhook_details.parse_value::<api::WebhookDetails>("WebhookDetails"))
+        .transpose()
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to parse webhook details of the merchant account")?;
+    let primary_business_details = merchant_account
+        .primary_business_details
+        .clone()
+        .parse_value::<Vec<PrimaryBusinessDetails>>("PrimaryBusinessDetails")
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to parse primary business details of the merchant account")?;
+
+    validate_merchant_urls_against_allowed_domains(
+        merchant_account.return_url.as_deref(),
+        webhook_details.as_ref(),
+        Some(primary_business_details.as_slice()),
+        Some(allowed_domains.as_slice()),
+    )?;
+
+    let updated_merchant_account = db
+        .update_merchant(
+            merchant_account,
+            storage::MerchantAccountUpdate::AllowedDomainsUpdate { allowed_domains },
+            &key_store,
+        )
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable_lazy(|| {
+            format!("Failed to update allowed domains for merchant_id: {merchant_id}")
+        })?;
+
+    Ok(service_api::ApplicationResponse::Json(
+        api::MerchantAllowedDomainsResponse {
+            merchant_id: updated_merchant_account.merchant_id,
+            allowed_domains: updated_merchant_account.allowed_domains.unwrap_or_default(),
+        },
+    ))
+}
+
+/// Converts a domain to the form in which it is matched against the hosts of urls, rejecting
+/// values which are not plain hosts, such as urls.
+fn normalize_allowed_domain(domain: &str) -> RouterResult<String> {
+    let domain = domain.trim().trim_end_matches('.');
+    url::Host::parse(domain)
+        .map(|host| host.to_string())
+        .map_err(|_| {
+            report!(errors::ApiErrorResponse::InvalidRequestData {
+                message: format!("`{domain}` is not a valid domain name"),
+            })
+        })
+}
+
+pub async fn retrieve_connector_exclusion_rules(
+    db: &dyn StorageInterface,
+    merchant_id: String,
+) -> RouterResponse<api::ConnectorExclusionRulesResponse> {
+    let key_store = db
+        .get_merchant_key_store_by_merchant_id(&merchant_id, &db.get_master_key().to_vec().into())
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::MerchantAccountNotFound)?;
+
+    let merchant_account = db
+        .find_merchant_account_by_merchant_id(&merchant_id, &key_store)
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::MerchantAccountNotFound)?;
+
+    Ok(service_api::ApplicationResponse::Json(
+        api::ConnectorExclusionRulesResponse {
+            rules: connector_exclusion::get_connector_exclusion_rules(&merchant_account)?,
+            merchant_id: merchant_account.merchant_id,
+        },
+    ))
+}
+
+/// Replaces the rules which exclude cards from being routed to specific connectors, based on
+/// their BIN or the country in which they were issued.
+pub async fn update_connector_exclusion_rules(
+    db: &dyn StorageInterface,
+    merchant_id: String,
+    req: api::ConnectorExclusionRulesUpdateRequest,
+) -> RouterResponse<api::ConnectorExclusionRulesResponse> {
+    connector_exclusion::validate_connector_exclusion_rules(&req.rules)?;
+
+    let key_store = db
+        .get_merchant_key_store_by_merchant_id(&merchant_id, &db.get_master_key().to_vec().into())
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::MerchantAccountNotFound)?;
+
+    let merchant_account = db
+        .find_merchant_account_by_merchant_id(&merchant_id, &key_store)
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::MerchantAccountNotFound)?;
+
+    let previous_rules = api::ConnectorExclusionRulesResponse {
+        rules: connector_exclusion::get_connector_exclusion_rules(&merchant_account)?,
+        merchant_id: merchant_account.merchant_id.clone(),
+    };
+    let connector_exclusion_rules =
+        utils::Encode::<Vec<api::ConnectorExclusionRule>>::encode_to_value(&req.rules)
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Failed to serialize connector exclusion rules")?;
+
+    let updated_merchant_account = db
+        .update_merchant(
+            merchant_account,
+            storage::MerchantAccountUpdate::ConnectorExclusionRulesUpdate {
+                connector_exclusion_rules,
+            },
+            &key_store,
+        )
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable_lazy(|| {
+            format!("Failed to update connector exclusion rules for merchant_id: {merchant_id}")
+        })?;
+
+    let response = api::ConnectorExclusionRulesResponse {
+        rules: connector_exclusion::get_connector_exclusion_rules(&updated_merchant_account)?,
+        merchant_id: updated_merchant_account.merchant_id,
+    };
+
+    audit::record_audit_event(
+        db,
+        &merchant_id,
+        enums::AuditEntityType::RoutingConfig,
+        &merchant_id,
+        Some(&previous_rules),
+        Some(&response),
+    )
+    .await?;
+
+    Ok(service_api::ApplicationResponse::Json(response))
+}
+
 pub fn get_frm_config_as_secret(
     frm_configs: Option<Vec<api_models::admin::FrmConfigs>>,
 ) -> Option<Vec<Secret<serde_json::Value>>> {
@@ -830,6 +1887,51 @@ pub fn get_frm_config_as_secret(
     }
 }
 
+/// Validates the test account details of a connector account, which are used for test payments
+/// while its account details are used for live payments. Using the same account details for both
+/// would send test payments to the live account of the connector.
+fn validate_test_connector_account_details(
+    connector_name: Option<api_enums::Connector>,
+    test_mode: Option<bool>,
+    connector_account_details: &serde_json::Value,
+    test_connector_account_details: &pii::SecretSerdeValue,
+) -> RouterResult<()> {
+    utils::when(test_mode == Some(true), || {
+        Err(report!(errors::ApiErrorResponse::InvalidRequestData {
+            message: "test_connector_account_details cannot be provided for a connector account in test mode".to_string(),
+        }))
+    })?;
+
+    utils::when(
+        test_connector_account_details.peek() == connector_account_details,
+        || {
+            Err(report!(errors::ApiErrorResponse::InvalidRequestData {
+                message:
+                    "test_connector_account_details must differ from connector_account_details"
+                        .to_string(),
+            }))
+        },
+    )?;
+
+    let auth: types::ConnectorAuthType = test_connector_account_details
+        .clone()
+        .parse_value("ConnectorAuthType")
+        .change_context(errors::ApiErrorResponse::InvalidDataFormat {
+            field_name: "test_connector_account_details".to_string(),
+            expected_format: "auth_type and api_key".to_string(),
+        })?;
+
+    connector_name
+        .map(|connector_name| validate_auth_type(connector_name, &auth))
+        .transpose()
+        .change_context(errors::ApiErrorResponse::InvalidRequestData {
+            message: "The auth type of test_connector_account_details is invalid for the connector"
+                .to_string(),
+        })?;
+
+    Ok(())
+}
+
 pub(crate) fn validate_auth_type(
     connector_name: api_models::enums::Connector,
     val: &types::ConnectorAuthType,
@@ -1023,3 +2125,120 @@ pub(crate) fn validate_auth_type(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+
+    #[test]
+    fn test_take_business_webhook_secrets() {
+        let mut primary_business_details: Vec<PrimaryBusinessDetails> =
+            serde_json::from_value(serde_json::json!([{
+                "country": "US",
+                "business": "food",
+                "webhook_endpoints": [
+                    {
+                        "endpoint_id": "fulfilment",
+                        "webhook_url": "https://example.com/fulfilment",
+                        "webhook_secret": "new_secret"
+                    },
+                    {
+                        "endpoint_id": "accounting",
+                        "webhook_url": "https://example.com/accounting"
+                    },
+                    {
+                        "endpoint_id": "analytics",
+                        "webhook_url": "https://example.com/analytics"
+                    }
+                ]
+            }]))
+            .unwrap();
+        let existing_secrets = HashMap::from([
+            (
+                "fulfilment".to_string(),
+                Secret::new("old_secret".to_string()),
+            ),
+            (
+                "accounting".to_string(),
+                Secret::new("kept_secret".to_string()),
+            ),
+            (
+                "removed".to_string(),
+                Secret::new("removed_secret".to_string()),
+            ),
+        ]);
+
+        let secrets =
+            take_business_webhook_secrets(&mut primary_business_details, &existing_secrets);
+        let secrets: HashMap<_, _> = secrets
+            .into_iter()
+            .map(|(endpoint_id, secret)| (endpoint_id, secret.expose()))
+            .collect();
+        assert_eq!(
+            secrets,
+            HashMap::from([
+                ("fulfilment".to_string(), "new_secret".to_string()),
+                ("accounting".to_string(), "kept_secret".to_string()),
+            ])
+        );
+
+        // The keys are not left in the business details, which are stored in plain text
+        let stored_details = utils::Encode::<Vec<PrimaryBusinessDetails>>::encode_to_value(
+            &primary_business_details,
+        )
+        .unwrap()
+        .to_string();
+        assert!(!stored_details.contains("new_secret"));
+        assert!(primary_business_details[0]
+            .webhook_endpoints
+            .iter()
+            .flatten()
+            .all(|endpoint| endpoint.webhook_secret.is_none()));
+    }
+
+    #[tokio::test]
+    async fn test_rotate_merchant_key_store_to_current_master_key() {
+        use crate::db::{MasterKeyInterface, MockDb};
+
+        let previous_master_key = vec![7; 32];
+        let mock_db = MockDb::new(&Default::default())
+            .await
+            .with_previous_master_key(previous_master_key.clone());
+        let master_key = mock_db.get_master_key().to_vec();
+        let merchant_key = services::generate_aes256_key().unwrap().to_vec();
+
+        mock_db
+            .insert_merchant_key_store(
+                domain::MerchantKeyStore {
+                    merchant_id: "merchant1".into(),
+                    key: domain_types::encrypt(merchant_key.clone().into(), &previous_master_key)
+                        .await
+                        .unwrap(),
+                    created_at: date_time::now(),
+                    key_version: 1,
+                    rotated_at: None,
+                },
+                &previous_master_key.clone().into(),
+            )
+            .await
+            .unwrap();
+
+        rotate_merchant_key_store(&mock_db, "merchant1".to_string())
+            .await
+            .unwrap();
+
+        // The merchant key is unchanged, and is now encrypted with the current master key only
+        let key_store = mock_db
+            .get_merchant_key_store_by_merchant_id("merchant1", &master_key.clone().into())
+            .await
+            .unwrap();
+        assert_eq!(key_store.key_version, 2);
+        assert!(key_store.rotated_at.is_some());
+        assert_eq!(key_store.key.get_inner().peek(), &merchant_key);
+        assert!(mock_db
+            .get_merchant_key_store_by_merchant_id("merchant1", &previous_master_key.into())
+            .await
+            .is_err());
+    }
+}
@@ -0,0 +1,304 @@
+//! Client certificates presented to connectors which require mutual TLS.
+//!
+//! The certificate and its private key are stored encrypted with the merchant connector account,
+//! along with the expiry of the certificate. Merchants are reminded of the expiry through the
+//! process tracker, on each of the configured number of days before it. Rotating the certificate
+//! reschedules the reminders for the new certificate.
+
+use common_utils::crypto::Encryptable;
+#[cfg(feature = "email")]
+use common_utils::ext_traits::ValueExt;
+use error_stack::{report, IntoReport, ResultExt};
+use masking::{PeekInterface, Secret};
+use router_env::{instrument, logger, tracing};
+
+use crate::{
+    core::{
+        errors::{self, RouterResult, StorageErrorExt},
+        payments::helpers,
+    },
+    routes::AppState,
+    scheduler::utils as pt_utils,
+    types::{
+        api, domain,
+        domain::types as domain_types,
+        storage::{self, enums, ProcessTrackerExt},
+    },
+    utils,
+};
+
+const CONNECTOR_CERTIFICATE_EXPIRY_RUNNER: &str = "CONNECTOR_CERTIFICATE_EXPIRY_WORKFLOW";
+const CONNECTOR_CERTIFICATE_EXPIRY_TASK: &str = "CONNECTOR_CERTIFICATE_EXPIRY";
+
+/// A client certificate which has been validated, encrypted for storage with the merchant
+/// connector account
+pub struct EncryptedClientCertificate {
+    pub client_certificate: Encryptable<Secret<String>>,
+    pub client_certificate_key: Encryptable<Secret<String>>,
+    pub expires_at: time::PrimitiveDateTime,
+}
+
+/// Validates the client certificate and its private key, and encrypts them for storage. Expired
+/// certificates are rejected.
+pub async fn encrypt_client_certificate(
+    connector_mtls_details: api::ConnectorMtlsDetails,
+    key_store: &domain::MerchantKeyStore,
+) -> RouterResult<EncryptedClientCertificate> {
+    helpers::create_identity_from_certificate_and_key(
+        connector_mtls_details.client_certificate.peek().clone(),
+        connector_mtls_details.client_certificate_key.peek().clone(),
+    )
+    .change_context(errors::ApiErrorResponse::InvalidDataValue {
+        field_name: "connector_mtls_details",
+    })
+    .attach_printable("Invalid client certificate or key provided for the connector")?;
+
+    let client_certificate = connector_mtls_details.client_certificate.peek().clone();
+    let expires_at = helpers::get_certificate_expiry(client_certificate).change_context(
+        errors::ApiErrorResponse::InvalidDataValue {
+            field_name: "connector_mtls_details",
+        },
+    )?;
+
+    utils::when(expires_at <= common_utils::date_time::now(), || {
+        Err(report!(errors::ApiErrorResponse::InvalidRequestData {
+            message: format!("The client certificate for the connector expired at {expires_at}"),
+        }))
+    })?;
+
+    let key = key_store.key.get_inner().peek();
+    Ok(EncryptedClientCertificate {
+        client_certificate: domain_types::encrypt(connector_mtls_details.client_certificate, key)
+            .await
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Unable to encrypt the client certificate")?,
+        client_certificate_key: domain_types::encrypt(
+            connector_mtls_details.client_certificate_key,
+            key,
+        )
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Unable to encrypt the client certificate key")?,
+        expires_at,
+    })
+}
+
+/// Returns the time at which the reminder with the given index is to be sent, the reminders being
+/// sent on each of the expiry reminder days before the expiry of the certificate
+pub fn get_reminder_time(
+    tracking_data: &storage::ConnectorCertificateExpiryWorkflow,
+    reminder_index: usize,
+) -> Option<time::PrimitiveDateTime> {
+    let expiry_reminder_day = tracking_data.expiry_reminder_days.get(reminder_index)?;
+    tracking_data.certificate_expires_at.map(|expires_at| {
+        expires_at.saturating_sub(time::Duration::days(i64::from(*expiry_reminder_day)))
+    })
+}
+
+/// Schedules the reminders of the expiry of the client certificate of the connector account. The
+/// reminders of a certificate the connector account has been rotated from are replaced, and
+/// reminders whose time has already passed are skipped.
+#[instrument(skip_all)]
+pub async fn schedule_certificate_expiry_reminders(
+    state: &AppState,
+    merchant_connector_account: &domain::MerchantConnectorAccount,
+) -> RouterResult<()> {
+    let tracking_data = storage::ConnectorCertificateExpiryWorkflow {
+        merchant_id: merchant_connector_account.merchant_id.clone(),
+        merchant_connector_id: merchant_connector_account.merchant_connector_id.clone(),
+        certificate_expires_at: merchant_connector_account.client_certificate_expires_at,
+        expiry_reminder_days: state
+            .conf
+            .connector_certificates
+            .expiry_reminder_days
+            .clone(),
+    };
+
+    let current_time = common_utils::date_time::now();
+    let Some((reminder_index, schedule_time)) = (0..tracking_data.expiry_reminder_days.len())
+        .filter_map(|reminder_index| {
+            get_reminder_time(&tracking_data, reminder_index)
+                .map(|schedule_time| (reminder_index, schedule_time))
+        })
+        .find(|(_, schedule_time)| *schedule_time > current_time)
+    else {
+        return Ok(());
+    };
+    let retry_count = i32::try_from(reminder_index)
+        .into_report()
+        .change_context(errors::ApiErrorResponse::InternalServerError)?;
+
+    let task_id = pt_utils::get_process_tracker_id(
+        CONNECTOR_CERTIFICATE_EXPIRY_RUNNER,
+        CONNECTOR_CERTIFICATE_EXPIRY_TASK,
+        &tracking_data.merchant_connector_id,
+        &tracking_data.merchant_id,
+    );
+    let existing_task = state
+        .store
+        .find_process_by_id(&task_id)
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to retrieve the connector certificate expiry reminder task")?;
+
+    if existing_task.is_some() {
+        let tracking_data = serde_json::to_value(&tracking_data)
+            .into_report()
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Failed to serialize the connector certificate expiry tracker")?;
+        let process_tracker_update = storage::ProcessTrackerUpdate::Update {
+            name: None,
+            retry_count: Some(retry_count),
+            schedule_time: Some(schedule_time),
+            tracking_data: Some(tracking_data),
+            business_status: Some(String::from("Pending")),
+            status: Some(enums::ProcessTrackerStatus::New),
+            updated_at: Some(current_time),
+        };
+        state
+            .store
+            .process_tracker_update_process_status_by_ids(vec![task_id], process_tracker_update)
+            .await
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Failed to update the connector certificate expiry reminder task")?;
+    } else {
+        let mut process_tracker_entry = <storage::ProcessTracker>::make_process_tracker_new(
+            task_id,
+            CONNECTOR_CERTIFICATE_EXPIRY_TASK,
+            CONNECTOR_CERTIFICATE_EXPIRY_RUNNER,
+            tracking_data,
+            schedule_time,
+        )
+        .change_context(errors::ApiErrorResponse::InternalServerError)?;
+        // The retry count is the index of the reminder to be sent next
+        process_tracker_entry.retry_count = retry_count;
+        state
+            .store
+            .insert_process(process_tracker_entry)
+            .await
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Failed to insert the connector certificate expiry reminder task")?;
+    }
+
+    logger::info!(%schedule_time, "Connector certificate expiry reminder scheduled");
+
+    Ok(())
+}
+
+/// Reminds the merchant of the expiry of the client certificate of the connector account. Returns
+/// `false` if the certificate has been rotated or removed since the reminders were scheduled, in
+/// which case no more reminders are to be sent for it.
+#[instrument(skip_all)]
+pub async fn send_certificate_expiry_reminder(
+    state: &AppState,
+    tracking_data: &storage::ConnectorCertificateExpiryWorkflow,
+    reminder_index: usize,
+) -> RouterResult<bool> {
+    let key_store = state
+        .store
+        .get_merchant_key_store_by_merchant_id(
+            &tracking_data.merchant_id,
+            &state.store.get_master_key().to_vec().into(),
+        )
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::MerchantAccountNotFound)?;
+
+    let merchant_account = state
+        .store
+        .find_merchant_account_by_merchant_id(&tracking_data.merchant_id, &key_store)
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::MerchantAccountNotFound)?;
+
+    let merchant_connector_account = match state
+        .store
+        .find_by_merchant_connector_account_merchant_id_merchant_connector_id(
+            &tracking_data.merchant_id,
+            &tracking_data.merchant_connector_id,
+            &key_store,
+        )
+        .await
+    {
+        Ok(merchant_connector_account) => merchant_connector_account,
+        Err(error) if error.current_context().is_db_not_found() => return Ok(false),
+        Err(error) => Err(error).change_context(errors::ApiErrorResponse::InternalServerError)?,
+    };
+
+    let (Some(expires_at), Some(expiry_reminder_day)) = (
+        merchant_connector_account
+            .client_certificate_expires_at
+            .filter(|expires_at| Some(*expires_at) == tracking_data.certificate_expires_at),
+        tracking_data.expiry_reminder_days.get(reminder_index),
+    ) else {
+        return Ok(false);
+    };
+
+    notify_merchant(
+        state,
+        &merchant_account,
+        &merchant_connector_account,
+        expires_at,
+        *expiry_reminder_day,
+    )
+    .await;
+
+    Ok(true)
+}
+
+#[cfg(feature = "email")]
+async fn notify_merchant(
+    state: &AppState,
+    merchant_account: &domain::MerchantAccount,
+    merchant_connector_account: &domain::MerchantConnectorAccount,
+    expires_at: time::PrimitiveDateTime,
+    expiry_reminder_day: u8,
+) {
+    let primary_email = merchant_account
+        .merchant_details
+        .clone()
+        .parse_value::<api::MerchantDetails>("MerchantDetails")
+        .map_err(|error| logger::error!(merchant_details_parsing_error=?error))
+        .ok()
+        .and_then(|merchant_details| merchant_details.primary_email);
+
+    let Some(primary_email) = primary_email else {
+        logger::warn!("Merchant has no primary email to remind about the connector certificate");
+        return;
+    };
+
+    let result = state
+        .email_client
+        .clone()
+        .send_email(
+            primary_email,
+            "Connector Certificate Expiry Notice".to_string(),
+            format!("Dear Merchant,\n
+The client certificate presented to {} for the connector account {} will expire in {expiry_reminder_day} days, at {expires_at} UTC. To ensure uninterrupted processing through the connector, we kindly request that you update the connector account with a renewed certificate as soon as possible.\n\n
+Thanks,\n
+Team Hyperswitch",
+                merchant_connector_account.connector_name,
+                merchant_connector_account.merchant_connector_id,
+            ),
+        )
+        .await;
+
+    if let Err(error) = result {
+        logger::error!(connector_certificate_expiry_notification_error=?error);
+    }
+}
+
+#[cfg(not(feature = "email"))]
+async fn notify_merchant(
+    _state: &AppState,
+    merchant_account: &domain::MerchantAccount,
+    merchant_connector_account: &domain::MerchantConnectorAccount,
+    expires_at: time::PrimitiveDateTime,
+    expiry_reminder_day: u8,
+) {
+    logger::warn!(
+        merchant_id = %merchant_account.merchant_id,
+        merchant_connector_id = %merchant_connector_account.merchant_connector_id,
+        %expires_at,
+        expiry_reminder_day,
+        "Email notifications are disabled, merchant not reminded about the connector certificate expiry"
+    );
+}
@@ -0,0 +1,424 @@
+//! Verification of the account details of merchant connector accounts.
+//!
+//! The account details are verified by sending a zero amount verification of a test card to the
+//! connector. The test card is not expected to be approved, so the outcome only depends on whether
+//! the connector authenticated the request: a `401 Unauthorized` response means that the account
+//! details were rejected, while a success response or any other client error response from the
+//! connector means that they were accepted. A `403 Forbidden` response does not tell whether the
+//! account details were accepted, as connectors also forbid authenticated requests, such as those
+//! from addresses which are not allowed or for features which are not enabled for the account.
+//!
+//! For the connectors which authenticate requests with access tokens, a new access token is
+//! obtained with the account details being verified, so that the access token cached for the
+//! connector account, obtained with the account details saved earlier, does not decide the
+//! outcome.
+//!
+//! The account details of enabled connector accounts are also verified before the accounts are
+//! created or updated, so that merchants do not discover account details refused by the connector
+//! only when making payments. Account details which could not be verified are accepted, unless
+//! the merchant has opted to reject them using the
+//! `reject_unverified_connector_accounts_{merchant_id}` config. The status of the verification is
+//! returned along with the saved connector account.
+
+use api_models::admin::{ConnectorVerificationStatus, MerchantConnectorVerifyResponse};
+use error_stack::{report, ResultExt};
+use router_env::{instrument, logger, tracing};
+
+use crate::{
+    consts,
+    core::{
+        errors::{self, RouterResponse, RouterResult, StorageErrorExt},
+        payments::{self, access_token},
+        utils as core_utils,
+    },
+    db::StorageInterface,
+    routes::AppState,
+    services::{self, api as service_api},
+    types::{self, api, domain},
+};
+
+/// The outcome of the verification, along with the error returned by the connector, if any
+struct VerificationOutcome {
+    status: ConnectorVerificationStatus,
+    error_code: Option<String>,
+    error_message: Option<String>,
+}
+
+impl VerificationOutcome {
+    fn without_error(status: ConnectorVerificationStatus) -> Self {
+        Self {
+            status,
+            error_code: None,
+            error_message: None,
+        }
+    }
+
+    fn from_error_response(error_response: types::ErrorResponse) -> Self {
+        Self {
+            status: get_verification_status(error_response.status_code),
+            error_code: Some(error_response.code),
+            error_message: Some(error_response.message),
+        }
+    }
+}
+
+/// Returns the status of the verification from the HTTP status code of the error response of the
+/// connector
+fn get_verification_status(status_code: u16) -> ConnectorVerificationStatus {
+    match status_code {
+        401 => ConnectorVerificationStatus::Invalid,
+        403 => ConnectorVerificationStatus::Unknown,
+        400..=499 => ConnectorVerificationStatus::Valid,
+        _ => ConnectorVerificationStatus::Unknown,
+    }
+}
+
+#[instrument(skip_all)]
+pub async fn verify_payment_connector(
+    state: &AppState,
+    merchant_id: String,
+    merchant_connector_id: String,
+) -> RouterResponse<MerchantConnectorVerifyResponse> {
+    let db = &*state.store;
+    let key_store = db
+        .get_merchant_key_store_by_merchant_id(&merchant_id, &db.get_master_key().to_vec().into())
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::MerchantAccountNotFound)?;
+
+    let merchant_account = db
+        .find_merchant_account_by_merchant_id(&merchant_id, &key_store)
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::MerchantAccountNotFound)?;
+
+    let merchant_connector_account = db
+        .find_by_merchant_connector_account_merchant_id_merchant_connector_id(
+            &merchant_id,
+            &merchant_connector_id,
+            &key_store,
+        )
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::MerchantConnectorAccountNotFound {
+            id: merchant_connector_id.clone(),
+        })?;
+
+    let outcome =
+        verify_account_details(state, &merchant_account, &merchant_connector_account).await?;
+
+    logger::info!(
+        merchant_connector_id = %merchant_connector_account.merchant_connector_id,
+        connector_verification_status = ?outcome.status,
+    );
+
+    Ok(service_api::ApplicationResponse::Json(
+        MerchantConnectorVerifyResponse {
+            merchant_id,
+            merchant_connector_id,
+            connector_name: merchant_connector_account.connector_name,
+            status: outcome.status,
+            error_code: outcome.error_code,
+            error_message: outcome.error_message,
+        },
+    ))
+}
+
+/// Returns whether the merchant rejects the account details which could not be verified with
+/// the connector, as configured using the `reject_unverified_connector_accounts_{merchant_id}`
+/// config. Such account details are accepted by default.
+async fn is_unverified_account_rejected(
+    db: &dyn StorageInterface,
+    merchant_id: &str,
+) -> RouterResult<bool> {
+    match db
+        .find_config_by_key_cached(&format!(
+            "reject_unverified_connector_accounts_{merchant_id}"
+        ))
+        .await
+    {
+        Ok(config) => Ok(config.config == "true"),
+        Err(err) if err.current_context().is_db_not_found() => Ok(false),
+        Err(err) => Err(err
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Failed to fetch the connector verification config of the merchant")),
+    }
+}
+
+/// Verifies the account details of a connector account about to be saved, rejecting the account
+/// details refused by the connector along with the error returned by the connector, and the
+/// account details which could not be verified if the merchant rejects them. Disabled connector
+/// accounts are not verified, as they are not used for payments. Returns the status of the
+/// verification, if the account details were verified.
+#[instrument(skip_all)]
+pub async fn validate_account_details(
+    state: &AppState,
+    merchant_account: &domain::MerchantAccount,
+    merchant_connector_account: &domain::MerchantConnectorAccount,
+) -> RouterResult<Option<ConnectorVerificationStatus>> {
+    if !state.conf.connector_verification.verify_on_save
+        || merchant_connector_account.disabled == Some(true)
+    {
+        return Ok(None);
+    }
+
+    let outcome =
+        verify_account_details(state, merchant_account, merchant_connector_account).await?;
+    logger::info!(
+        connector = %merchant_connector_account.connector_name,
+        connector_verification_status = ?outcome.status,
+    );
+
+    let connector = merchant_connector_account.connector_name.clone();
+    let reason = match (outcome.error_code, outcome.error_message) {
+        (Some(error_code), Some(error_message)) => Some(format!("{error_code}: {error_message}")),
+        (error_code, error_message) => error_message.or(error_code),
+    };
+    match outcome.status {
+        ConnectorVerificationStatus::Invalid => Err(report!(
+            errors::ApiErrorResponse::ConnectorAuthenticationFailed { connector, reason }
+        )),
+        ConnectorVerificationStatus::Unknown
+            if is_unverified_account_rejected(&*state.store, &merchant_account.merchant_id)
+                .await? =>
+        {
+            Err(report!(
+                errors::ApiErrorResponse::ConnectorVerificationInconclusive { connector, reason }
+            ))
+        }
+        status => Ok(Some(status)),
+    }
+}
+
+/// Obtains a new access token with the account details being verified. The access token cached for
+/// the connector account is neither used nor replaced. Failing to obtain the access token is
+/// returned as the outcome of the verification.
+async fn get_access_token(
+    state: &AppState,
+    connector_data: &api::ConnectorData,
+    merchant_account: &domain::MerchantAccount,
+    router_data: &types::VerifyRouterData,
+) -> Result<types::AccessToken, VerificationOutcome> {
+    let access_token_request_data =
+        match types::AccessTokenRequestData::try_from(router_data.connector_auth_type.clone()) {
+            Ok(access_token_request_data) => access_token_request_data,
+            Err(error) => {
+                return Err(VerificationOutcome {
+                    status: ConnectorVerificationStatus::Invalid,
+                    error_code: None,
+                    error_message: Some(error.to_string()),
+                })
+            }
+        };
+    let access_token_router_data = payments::helpers::router_data_type_conversion::<
+        _,
+        api::AccessTokenAuth,
+        _,
+        types::AccessTokenRequestData,
+        _,
+        types::AccessToken,
+    >(
+        router_data.clone(),
+        access_token_request_data,
+        Err(types::ErrorResponse::default()),
+    );
+
+    match access_token::refresh_connector_auth(
+        state,
+        connector_data,
+        merchant_account,
+        &access_token_router_data,
+    )
+    .await
+    {
+        Ok(Ok(access_token)) => Ok(access_token),
+        // Timeouts are returned as error responses which do not carry the status code of a
+        // response from the connector
+        Ok(Err(error_response)) if error_response.code == consts::REQUEST_TIMEOUT_ERROR_CODE => {
+            Err(VerificationOutcome {
+                status: ConnectorVerificationStatus::Unknown,
+                error_code: Some(error_response.code),
+                error_message: Some(error_response.message),
+            })
+        }
+        Ok(Err(error_response)) => Err(VerificationOutcome::from_error_response(error_response)),
+        Err(error) => {
+            logger::warn!(connector_verification_access_token_error=?error);
+            Err(VerificationOutcome {
+                status: ConnectorVerificationStatus::Unknown,
+                error_code: None,
+                error_message: Some(error.current_context().to_string()),
+            })
+        }
+    }
+}
+
+async fn verify_account_details(
+    state: &AppState,
+    merchant_account: &domain::MerchantAccount,
+    merchant_connector_account: &domain::MerchantConnectorAccount,
+) -> RouterResult<VerificationOutcome> {
+    let connector_data = api::ConnectorData::get_connector_by_name(
+        &state.conf.connectors,
+        &merchant_connector_account.connector_name,
+        api::GetToken::Connector,
+    )?;
+    let mut router_data =
+        core_utils::construct_connector_verification_router_data(merchant_connector_account)?;
+
+    if connector_data
+        .connector_name
+        .supports_access_token(router_data.payment_method)
+    {
+        match get_access_token(state, &connector_data, merchant_account, &router_data).await {
+            Ok(access_token) => router_data.access_token = Some(access_token),
+            Err(outcome) => return Ok(outcome),
+        }
+    }
+
+    let connector_integration: services::BoxedConnectorIntegration<
+        '_,
+        api::Verify,
+        types::VerifyRequestData,
+        types::PaymentsResponseData,
+    > = connector_data.connector.get_connector_integration();
+
+    let mut request =
+        match connector_integration.build_request(&router_data, &state.conf.connectors) {
+            Ok(Some(request)) => request,
+            Ok(None) => {
+                return Ok(VerificationOutcome::without_error(
+                    ConnectorVerificationStatus::NotSupported,
+                ))
+            }
+            Err(error) => {
+                logger::warn!(connector_verification_request_error=?error);
+                let status = match error.current_context() {
+                    errors::ConnectorError::FailedToObtainAuthType => {
+                        ConnectorVerificationStatus::Invalid
+                    }
+                    errors::ConnectorError::NotImplemented(_)
+                    | errors::ConnectorError::NotSupported { .. }
+                    | errors::ConnectorError::FlowNotSupported { .. } => {
+                        ConnectorVerificationStatus::NotSupported
+                    }
+                    _ => ConnectorVerificationStatus::Unknown,
+                };
+                return Ok(VerificationOutcome {
+                    status,
+                    error_code: None,
+                    error_message: Some(error.current_context().to_string()),
+                });
+            }
+        };
+
+    // Requests which do not carry a certificate of their own present the client certificate of
+    // the connector account, if any
+    if request.certificate.is_none() {
+        request.add_certificate(
+            connector_integration
+                .get_certificate(&router_data)
+                .change_context(errors::ApiErrorResponse::InternalServerError)?,
+        );
+        request.add_certificate_key(
+            connector_integration
+                .get_certificate_key(&router_data)
+                .change_context(errors::ApiErrorResponse::InternalServerError)?,
+        );
+    }
+
+    let response = match services::call_connector_api(state, request).await {
+        Ok(response) => response,
+        Err(error) => {
+            logger::warn!(connector_verification_error=?error);
+            return Ok(VerificationOutcome {
+                status: ConnectorVerificationStatus::Unknown,
+                error_code: None,
+                error_message: Some(error.current_context().to_string()),
+            });
+        }
+    };
+
+    match response {
+        Ok(_) => Ok(VerificationOutcome::without_error(
+            ConnectorVerificationStatus::Valid,
+        )),
+        Err(response) => {
+            let status = get_verification_status(response.status_code);
+            // The error response is only parsed for its code and message, the status of the
+            // verification is decided by the status code of the response alone
+            let error_response = match response.status_code {
+                500..=599 => connector_integration.get_5xx_error_response(response),
+                _ => connector_integration.get_error_response(response),
+            }
+            .map_err(|error| logger::warn!(connector_error_response_parsing_error=?error))
+            .ok();
+            Ok(VerificationOutcome {
+                status,
+                error_code: error_response
+                    .as_ref()
+                    .map(|error_response| error_response.code.clone()),
+                error_message: error_response.map(|error_response| error_response.message),
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+
+    #[test]
+    fn test_verification_status_from_status_code() {
+        assert_eq!(
+            get_verification_status(401),
+            ConnectorVerificationStatus::Invalid
+        );
+        assert_eq!(
+            get_verification_status(403),
+            ConnectorVerificationStatus::Unknown
+        );
+        assert_eq!(
+            get_verification_status(402),
+            ConnectorVerificationStatus::Valid
+        );
+        assert_eq!(
+            get_verification_status(422),
+            ConnectorVerificationStatus::Valid
+        );
+        assert_eq!(
+            get_verification_status(503),
+            ConnectorVerificationStatus::Unknown
+        );
+        assert_eq!(
+            get_verification_status(200),
+            ConnectorVerificationStatus::Unknown
+        );
+    }
+
+    #[tokio::test]
+    async fn test_unverified_account_rejection_config() {
+        use crate::{
+            db::{configs::ConfigInterface, MockDb},
+            types::storage,
+        };
+
+        let db = MockDb::new(&Default::default()).await;
+
+        // Account details which could not be verified are accepted by default
+        assert!(!is_unverified_account_rejected(&db, "merchant_1")
+            .await
+            .unwrap());
+
+        db.insert_config(storage::ConfigNew {
+            key: "reject_unverified_connector_accounts_merchant_1".to_string(),
+            config: "true".to_string(),
+        })
+        .await
+        .unwrap();
+        assert!(is_unverified_account_rejected(&db, "merchant_1")
+            .await
+            .unwrap());
+        assert!(!is_unverified_account_rejected(&db, "merchant_2")
+            .await
+            .unwrap());
+    }
+}
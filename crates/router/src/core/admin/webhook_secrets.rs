@@ -0,0 +1,298 @@
+//! Secrets with which the webhooks of connectors are verified.
+//!
+//! The webhook secret of a merchant connector account is stored encrypted with the account, along
+//! with an additional secret which is accepted while the secret is being rotated. Rotating the
+//! secret keeps the secret being replaced as the additional secret by default, so that webhooks
+//! signed with either secret are verified until the new secret is configured on the connector.
+
+use std::collections::{hash_map::Entry, HashMap};
+
+use common_utils::crypto::Encryptable;
+use error_stack::ResultExt;
+use masking::{PeekInterface, Secret};
+use router_env::{instrument, logger, tracing};
+
+use crate::{
+    core::{
+        audit,
+        errors::{self, RouterResponse, RouterResult, StorageErrorExt},
+    },
+    db::StorageInterface,
+    routes::AppState,
+    services::api as service_api,
+    types::{
+        api,
+        domain::{
+            self,
+            behaviour::ReverseConversion,
+            types::{self as domain_types, AsyncLift},
+        },
+        storage::{self, enums},
+    },
+};
+
+/// The number of merchant connector accounts whose webhook details are encrypted at a time
+const WEBHOOK_DETAILS_BACKFILL_BATCH_SIZE: i64 = 100;
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct ConnectorWebhookDetailsBackfillTrackingData {
+    /// The ID of the last merchant connector account processed by the backfill
+    pub last_id: i32,
+}
+
+/// The webhook secrets of a merchant connector account, encrypted for storage with the account
+pub struct EncryptedWebhookSecrets {
+    pub webhook_secret: Encryptable<Secret<String>>,
+    pub additional_webhook_secret: Option<Encryptable<Secret<String>>>,
+}
+
+/// The number of trailing characters of a webhook secret which are shown in responses, so that
+/// merchants can tell which secret is configured
+const WEBHOOK_SECRET_VISIBLE_SUFFIX_LENGTH: usize = 4;
+
+/// Masks a webhook secret for responses, showing only its last characters. Secrets too short for
+/// the suffix to leave most of them hidden are masked entirely.
+fn mask_webhook_secret(secret: &Secret<String>) -> Secret<String> {
+    let secret = secret.peek();
+    let length = secret.chars().count();
+    let suffix = if length > 2 * WEBHOOK_SECRET_VISIBLE_SUFFIX_LENGTH {
+        secret
+            .chars()
+            .skip(length - WEBHOOK_SECRET_VISIBLE_SUFFIX_LENGTH)
+            .collect()
+    } else {
+        String::new()
+    };
+    Secret::new(format!("****{suffix}"))
+}
+
+/// Masks the webhook secrets of a merchant connector account, which are never returned in
+/// responses
+pub fn mask_webhook_details(
+    webhook_details: api_models::admin::MerchantConnectorWebhookDetails,
+) -> api_models::admin::MerchantConnectorWebhookDetails {
+    api_models::admin::MerchantConnectorWebhookDetails {
+        merchant_secret: mask_webhook_secret(&webhook_details.merchant_secret),
+        additional_secret: webhook_details
+            .additional_secret
+            .as_ref()
+            .map(mask_webhook_secret),
+    }
+}
+
+/// Encrypts the webhook secrets of a merchant connector account for storage
+pub async fn encrypt_webhook_secrets(
+    webhook_details: api_models::admin::MerchantConnectorWebhookDetails,
+    key_store: &domain::MerchantKeyStore,
+) -> RouterResult<EncryptedWebhookSecrets> {
+    let key = key_store.key.get_inner().peek();
+    Ok(EncryptedWebhookSecrets {
+        webhook_secret: domain_types::encrypt(webhook_details.merchant_secret, key)
+            .await
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Unable to encrypt the webhook secret")?,
+        additional_webhook_secret: webhook_details
+            .additional_secret
+            .async_lift(|inner| domain_types::encrypt_optional(inner, key))
+            .await
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Unable to encrypt the additional webhook secret")?,
+    })
+}
+
+/// Replaces the webhook secret of the merchant connector account, keeping the secret being
+/// replaced as the additional secret unless requested otherwise.
+#[instrument(skip_all)]
+pub async fn rotate_webhook_secret(
+    state: &AppState,
+    merchant_id: &str,
+    merchant_connector_id: &str,
+    req: api_models::admin::MerchantConnectorWebhookSecretRotateRequest,
+) -> RouterResponse<api_models::admin::MerchantConnectorWebhookSecretRotateResponse> {
+    let db = &*state.store;
+    let key_store = db
+        .get_merchant_key_store_by_merchant_id(merchant_id, &db.get_master_key().to_vec().into())
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::MerchantAccountNotFound)?;
+
+    let mca = db
+        .find_by_merchant_connector_account_merchant_id_merchant_connector_id(
+            merchant_id,
+            merchant_connector_id,
+            &key_store,
+        )
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::MerchantConnectorAccountNotFound {
+            id: merchant_connector_id.to_string(),
+        })?;
+
+    let previous_webhook_details = mca
+        .get_webhook_details()
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Unable to deserialize connector_webhook_details")?;
+    let additional_secret = if req.keep_previous_secret.unwrap_or(true) {
+        previous_webhook_details.map(|webhook_details| webhook_details.merchant_secret)
+    } else {
+        None
+    };
+    let previous_secret_accepted = additional_secret.is_some();
+
+    let webhook_secrets = encrypt_webhook_secrets(
+        api_models::admin::MerchantConnectorWebhookDetails {
+            merchant_secret: req.merchant_secret,
+            additional_secret,
+        },
+        &key_store,
+    )
+    .await?;
+
+    let previous_mca: api::MerchantConnectorResponse = mca.clone().try_into()?;
+    let updated_mca = db
+        .update_merchant_connector_account(
+            mca,
+            storage::MerchantConnectorAccountUpdate::WebhookSecretsUpdate {
+                webhook_secret: webhook_secrets.webhook_secret,
+                additional_webhook_secret: webhook_secrets.additional_webhook_secret,
+            }
+            .into(),
+            &key_store,
+        )
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable_lazy(|| {
+            format!("Failed while updating MerchantConnectorAccount: id: {merchant_connector_id}")
+        })?;
+    let updated_response: api::MerchantConnectorResponse = updated_mca.try_into()?;
+
+    audit::record_audit_event(
+        db,
+        merchant_id,
+        enums::AuditEntityType::MerchantConnectorAccount,
+        merchant_connector_id,
+        Some(&previous_mca),
+        Some(&updated_response),
+    )
+    .await?;
+
+    Ok(service_api::ApplicationResponse::Json(
+        api_models::admin::MerchantConnectorWebhookSecretRotateResponse {
+            merchant_id: merchant_id.to_string(),
+            merchant_connector_id: merchant_connector_id.to_string(),
+            previous_secret_accepted,
+        },
+    ))
+}
+
+/// Encrypts the plaintext webhook details of a merchant connector account configured before the
+/// webhook secrets were encrypted, clearing the plaintext webhook details.
+async fn backfill_webhook_secrets(
+    db: &dyn StorageInterface,
+    mca: storage::MerchantConnectorAccount,
+    key_store: &domain::MerchantKeyStore,
+) -> RouterResult<()> {
+    let mca = mca
+        .convert(key_store.key.get_inner())
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed while decrypting the merchant connector account")?;
+    let Some(webhook_details) = mca
+        .get_webhook_details()
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Unable to deserialize connector_webhook_details")?
+    else {
+        return Ok(());
+    };
+    let webhook_secrets = encrypt_webhook_secrets(webhook_details, key_store).await?;
+
+    db.update_merchant_connector_account(
+        mca,
+        storage::MerchantConnectorAccountUpdate::WebhookSecretsUpdate {
+            webhook_secret: webhook_secrets.webhook_secret,
+            additional_webhook_secret: webhook_secrets.additional_webhook_secret,
+        }
+        .into(),
+        key_store,
+    )
+    .await
+    .change_context(errors::ApiErrorResponse::InternalServerError)
+    .attach_printable(
+        "Failed while updating the webhook secrets of the merchant connector account",
+    )?;
+    Ok(())
+}
+
+/// Encrypts the plaintext webhook details of the next batch of merchant connector accounts after
+/// the merchant connector account with the given ID. Returns the ID of the last merchant connector
+/// account of the batch, or `None` once every merchant connector account has been processed. A
+/// merchant connector account whose webhook details could not be encrypted is skipped, so that it
+/// does not hold up the backfill of the other accounts.
+pub async fn backfill_connector_webhook_details(
+    state: &AppState,
+    last_id: i32,
+) -> RouterResult<Option<i32>> {
+    let db = &*state.store;
+    let accounts = db
+        .find_merchant_connector_accounts_with_webhook_details_after_id(
+            last_id,
+            WEBHOOK_DETAILS_BACKFILL_BATCH_SIZE,
+        )
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to fetch the merchant connector accounts to backfill")?;
+    let next_last_id = accounts.last().map(|account| account.id);
+
+    // The accounts of a batch mostly belong to a few merchants, so their key stores are fetched
+    // once per batch
+    let mut key_stores = HashMap::new();
+    for account in accounts {
+        let merchant_connector_id = account.merchant_connector_id.clone();
+        let key_store = match key_stores.entry(account.merchant_id.clone()) {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => {
+                match db
+                    .get_merchant_key_store_by_merchant_id(
+                        entry.key(),
+                        &db.get_master_key().to_vec().into(),
+                    )
+                    .await
+                {
+                    Ok(key_store) => entry.insert(key_store),
+                    Err(error) => {
+                        logger::error!(
+                            merchant_connector_id = %merchant_connector_id,
+                            webhook_details_backfill_error=?error
+                        );
+                        continue;
+                    }
+                }
+            }
+        };
+        if let Err(error) = backfill_webhook_secrets(db, account, key_store).await {
+            logger::error!(
+                merchant_connector_id = %merchant_connector_id,
+                webhook_details_backfill_error=?error
+            );
+        }
+    }
+
+    Ok(next_last_id)
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used, clippy::expect_used, clippy::panic)]
+    use super::*;
+
+    #[test]
+    fn test_webhook_secrets_are_masked() {
+        let masked = mask_webhook_details(api_models::admin::MerchantConnectorWebhookDetails {
+            merchant_secret: Secret::new("whsec_0123456789abcdef".to_string()),
+            additional_secret: Some(Secret::new("short".to_string())),
+        });
+        assert_eq!(masked.merchant_secret.peek(), "****cdef");
+        assert_eq!(
+            masked.additional_secret.as_ref().map(PeekInterface::peek),
+            Some(&"****".to_string())
+        );
+    }
+}
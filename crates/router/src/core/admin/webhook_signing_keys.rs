@@ -0,0 +1,495 @@
+//! Key pairs with which the outgoing webhooks of a merchant are signed.
+//!
+//! In addition to the HMAC signature computed with the payment response hash key, merchants can
+//! opt into signing their outgoing webhooks with an Ed25519 or RSA key pair, so that the webhooks
+//! can be verified without sharing a secret. The key pairs are stored encrypted with the merchant
+//! account, and their public keys are published as a JSON Web Key Set. Rotating the key pair
+//! retires the active key, whose public key remains published for a grace period so that webhooks
+//! signed before the rotation can still be verified.
+
+use api_models::enums as api_enums;
+use common_utils::{crypto::Encryptable, date_time, ext_traits::ValueExt};
+use error_stack::{IntoReport, ResultExt};
+use josekit::{
+    jwk::{alg::ed::EdCurve, Jwk},
+    jws::{self, JwsAlgorithm, JwsSigner},
+};
+use masking::{ExposeInterface, PeekInterface, Secret};
+use router_env::{instrument, logger, tracing};
+
+use crate::{
+    cache::{CacheKind, CONFIG_CACHE},
+    consts,
+    core::{
+        audit,
+        errors::{self, RouterResponse, RouterResult, StorageErrorExt},
+    },
+    db, headers,
+    routes::AppState,
+    services::{api as service_api, request::Maskable},
+    types::{
+        domain::{self, types as domain_types},
+        storage::{self, enums},
+    },
+    utils::{self, Encode},
+};
+
+/// A webhook signing key pair of a merchant, as stored encrypted with the merchant account
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub struct WebhookSigningKey {
+    pub key_id: String,
+    pub algorithm: api_enums::WebhookSigningAlgorithm,
+    /// The private key, in PKCS#8 PEM format
+    pub private_key: Secret<String>,
+    /// The public key, as a JSON Web Key
+    pub public_key: serde_json::Value,
+    #[serde(with = "common_utils::custom_serde::iso8601")]
+    pub created_at: time::PrimitiveDateTime,
+    /// The time after which the public key is no longer published, set once the key is retired
+    #[serde(default, with = "common_utils::custom_serde::iso8601::option")]
+    pub expires_at: Option<time::PrimitiveDateTime>,
+}
+
+impl WebhookSigningKey {
+    fn is_active(&self) -> bool {
+        self.expires_at.is_none()
+    }
+
+    fn is_expired(&self, now: time::PrimitiveDateTime) -> bool {
+        self.expires_at
+            .map_or(false, |expires_at| expires_at <= now)
+    }
+}
+
+impl From<WebhookSigningKey> for api_models::admin::WebhookSigningKeyResponse {
+    fn from(key: WebhookSigningKey) -> Self {
+        Self {
+            status: if key.is_active() {
+                api_enums::WebhookSigningKeyStatus::Active
+            } else {
+                api_enums::WebhookSigningKeyStatus::Retired
+            },
+            key_id: key.key_id,
+            algorithm: key.algorithm,
+            public_key: key.public_key,
+            created_at: key.created_at,
+            expires_at: key.expires_at,
+        }
+    }
+}
+
+/// Returns the webhook signing keys of the merchant whose public keys are still published, the
+/// active key first.
+pub fn get_webhook_signing_keys(
+    merchant_account: &domain::MerchantAccount,
+) -> errors::CustomResult<Vec<WebhookSigningKey>, errors::ParsingError> {
+    let now = date_time::now();
+    Ok(merchant_account
+        .webhook_signing_keys
+        .clone()
+        .map(|keys| {
+            keys.into_inner()
+                .expose()
+                .parse_value::<Vec<WebhookSigningKey>>("WebhookSigningKeys")
+        })
+        .transpose()?
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|key| !key.is_expired(now))
+        .collect())
+}
+
+/// Generates a new key pair with which outgoing webhooks are signed
+fn generate_webhook_signing_key(
+    algorithm: api_enums::WebhookSigningAlgorithm,
+) -> RouterResult<WebhookSigningKey> {
+    let (private_key, mut public_key, jws_algorithm) = match algorithm {
+        api_enums::WebhookSigningAlgorithm::Ed25519 => jws::EdDSA
+            .generate_key_pair(EdCurve::Ed25519)
+            .map(|key_pair| {
+                (
+                    key_pair.to_pem_private_key(),
+                    key_pair.to_jwk_public_key(),
+                    jws::EdDSA.name().to_string(),
+                )
+            }),
+        api_enums::WebhookSigningAlgorithm::Rs256 => jws::RS256
+            .generate_key_pair(consts::WEBHOOK_SIGNING_RSA_KEY_SIZE)
+            .map(|key_pair| {
+                (
+                    key_pair.to_pem_private_key(),
+                    key_pair.to_jwk_public_key(),
+                    jws::RS256.name().to_string(),
+                )
+            }),
+    }
+    .into_report()
+    .change_context(errors::ApiErrorResponse::InternalServerError)
+    .attach_printable("Failed to generate the webhook signing key pair")?;
+
+    let key_id = utils::generate_id(consts::ID_LENGTH, "whsk");
+    public_key.set_key_id(&key_id);
+    public_key.set_algorithm(jws_algorithm);
+    public_key.set_key_use("sig");
+
+    let private_key = String::from_utf8(private_key)
+        .into_report()
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("The PEM encoded webhook signing key is not valid UTF-8")?;
+
+    Ok(WebhookSigningKey {
+        key_id,
+        algorithm,
+        private_key: Secret::new(private_key),
+        public_key: jwk_to_value(public_key),
+        created_at: date_time::now(),
+        expires_at: None,
+    })
+}
+
+fn jwk_to_value(jwk: Jwk) -> serde_json::Value {
+    let jwk: &serde_json::Map<String, serde_json::Value> = jwk.as_ref();
+    serde_json::Value::Object(jwk.clone())
+}
+
+fn get_webhook_jwks_cache_key(merchant_id: &str) -> String {
+    format!("webhook_jwks_{merchant_id}")
+}
+
+async fn find_merchant_account(
+    state: &AppState,
+    merchant_id: &str,
+) -> RouterResult<(domain::MerchantAccount, domain::MerchantKeyStore)> {
+    let db = &*state.store;
+    let key_store = db
+        .get_merchant_key_store_by_merchant_id(merchant_id, &db.get_master_key().to_vec().into())
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::MerchantAccountNotFound)?;
+
+    let merchant_account = db
+        .find_merchant_account_by_merchant_id(merchant_id, &key_store)
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::MerchantAccountNotFound)?;
+
+    Ok((merchant_account, key_store))
+}
+
+fn get_webhook_signing_keys_response(
+    merchant_id: &str,
+    keys: Vec<WebhookSigningKey>,
+) -> api_models::admin::WebhookSigningKeysResponse {
+    api_models::admin::WebhookSigningKeysResponse {
+        merchant_id: merchant_id.to_string(),
+        keys: keys.into_iter().map(Into::into).collect(),
+    }
+}
+
+/// Replaces the webhook signing keys of the merchant, recording the change of the published
+/// public keys as a change to the webhook settings of the merchant.
+async fn update_webhook_signing_keys(
+    state: &AppState,
+    merchant_account: domain::MerchantAccount,
+    key_store: &domain::MerchantKeyStore,
+    previous_keys: Vec<WebhookSigningKey>,
+    updated_keys: Vec<WebhookSigningKey>,
+) -> RouterResponse<api_models::admin::WebhookSigningKeysResponse> {
+    let db = &*state.store;
+    let merchant_id = merchant_account.merchant_id.clone();
+
+    let webhook_signing_keys = Encode::<Vec<WebhookSigningKey>>::encode_to_value(&updated_keys)
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to serialize the webhook signing keys")?;
+    let webhook_signing_keys: Encryptable<Secret<serde_json::Value>> = domain_types::encrypt(
+        Secret::new(webhook_signing_keys),
+        key_store.key.get_inner().peek(),
+    )
+    .await
+    .change_context(errors::ApiErrorResponse::InternalServerError)
+    .attach_printable("Unable to encrypt the webhook signing keys")?;
+
+    db.update_merchant(
+        merchant_account,
+        storage::MerchantAccountUpdate::WebhookSigningKeysUpdate {
+            webhook_signing_keys,
+        },
+        key_store,
+    )
+    .await
+    .change_context(errors::ApiErrorResponse::InternalServerError)
+    .attach_printable_lazy(|| {
+        format!("Failed to update webhook signing keys for merchant_id: {merchant_id}")
+    })?;
+
+    // The published key set is cached by every instance of the application
+    let cache_key = get_webhook_jwks_cache_key(&merchant_id);
+    CONFIG_CACHE.remove(&cache_key).await;
+    db::cache::publish_into_redact_channel(db, CacheKind::Config(cache_key.into()))
+        .await
+        .map_err(|error| logger::error!(webhook_jwks_cache_invalidation_error=?error))
+        .ok();
+
+    let previous_response = get_webhook_signing_keys_response(&merchant_id, previous_keys);
+    let updated_response = get_webhook_signing_keys_response(&merchant_id, updated_keys);
+    audit::record_audit_event(
+        db,
+        &merchant_id,
+        enums::AuditEntityType::WebhookSettings,
+        &merchant_id,
+        Some(&previous_response),
+        Some(&updated_response),
+    )
+    .await?;
+
+    Ok(service_api::ApplicationResponse::Json(updated_response))
+}
+
+#[instrument(skip_all)]
+pub async fn retrieve_webhook_signing_keys(
+    state: &AppState,
+    merchant_id: &str,
+) -> RouterResponse<api_models::admin::WebhookSigningKeysResponse> {
+    let (merchant_account, _) = find_merchant_account(state, merchant_id).await?;
+    let keys = get_webhook_signing_keys(&merchant_account)
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Unable to deserialize webhook_signing_keys")?;
+
+    Ok(service_api::ApplicationResponse::Json(
+        get_webhook_signing_keys_response(merchant_id, keys),
+    ))
+}
+
+/// Generates a new key pair with which the outgoing webhooks of the merchant are signed, retiring
+/// the active key pair. The public key of the retired key pair remains published for a grace
+/// period unless requested otherwise.
+#[instrument(skip_all)]
+pub async fn rotate_webhook_signing_key(
+    state: &AppState,
+    merchant_id: &str,
+    req: api_models::admin::WebhookSigningKeyRotateRequest,
+) -> RouterResponse<api_models::admin::WebhookSigningKeysResponse> {
+    let (merchant_account, key_store) = find_merchant_account(state, merchant_id).await?;
+    let previous_keys = get_webhook_signing_keys(&merchant_account)
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Unable to deserialize webhook_signing_keys")?;
+
+    let expires_at =
+        date_time::now() + time::Duration::days(consts::WEBHOOK_SIGNING_KEY_GRACE_PERIOD_DAYS);
+    let retired_keys = if req.keep_previous_key.unwrap_or(true) {
+        previous_keys
+            .iter()
+            .cloned()
+            .map(|key| WebhookSigningKey {
+                expires_at: key.expires_at.or(Some(expires_at)),
+                ..key
+            })
+            .collect()
+    } else {
+        Vec::new()
+    };
+
+    let updated_keys = std::iter::once(generate_webhook_signing_key(req.algorithm)?)
+        .chain(retired_keys)
+        .collect();
+
+    update_webhook_signing_keys(
+        state,
+        merchant_account,
+        &key_store,
+        previous_keys,
+        updated_keys,
+    )
+    .await
+}
+
+/// Stops signing the outgoing webhooks of the merchant with a key pair, removing all of its
+/// webhook signing keys. Webhooks are still signed with the payment response hash key.
+#[instrument(skip_all)]
+pub async fn delete_webhook_signing_keys(
+    state: &AppState,
+    merchant_id: &str,
+) -> RouterResponse<api_models::admin::WebhookSigningKeysResponse> {
+    let (merchant_account, key_store) = find_merchant_account(state, merchant_id).await?;
+    let previous_keys = get_webhook_signing_keys(&merchant_account)
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Unable to deserialize webhook_signing_keys")?;
+
+    update_webhook_signing_keys(
+        state,
+        merchant_account,
+        &key_store,
+        previous_keys,
+        Vec::new(),
+    )
+    .await
+}
+
+/// Returns the public keys with which the signatures of the outgoing webhooks of the merchant are
+/// verified, as a JSON Web Key Set.
+#[instrument(skip_all)]
+pub async fn retrieve_webhook_jwks(
+    state: &AppState,
+    merchant_id: &str,
+) -> RouterResponse<api_models::admin::WebhookJsonWebKeySet> {
+    // The key set is publicly served, it is cached so that its requests do not each decrypt the
+    // merchant account
+    let cache_key = get_webhook_jwks_cache_key(merchant_id);
+    if let Some(jwks) = CONFIG_CACHE.get_val::<api_models::admin::WebhookJsonWebKeySet>(&cache_key)
+    {
+        return Ok(service_api::ApplicationResponse::Json(jwks));
+    }
+
+    let (merchant_account, _) = find_merchant_account(state, merchant_id).await?;
+    let keys = get_webhook_signing_keys(&merchant_account)
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Unable to deserialize webhook_signing_keys")?;
+    let jwks = api_models::admin::WebhookJsonWebKeySet {
+        keys: keys.into_iter().map(|key| key.public_key).collect(),
+    };
+    CONFIG_CACHE.push(cache_key, jwks.clone()).await;
+
+    Ok(service_api::ApplicationResponse::Json(jwks))
+}
+
+/// Signs the body of an outgoing webhook with the active webhook signing key of the merchant, if
+/// any. The signature is a JWS with a detached payload, whose header identifies the key with
+/// which it is verified. Webhooks which cannot be signed must not be sent, so that merchants
+/// verifying the signature never receive an unsigned webhook.
+pub fn get_webhook_signature_header(
+    merchant_account: &domain::MerchantAccount,
+    request_body: &str,
+) -> errors::CustomResult<Option<(String, Maskable<String>)>, errors::WebhooksFlowError> {
+    let signing_key = get_webhook_signing_keys(merchant_account)
+        .change_context(errors::WebhooksFlowError::OutgoingWebhookSigningFailed)
+        .attach_printable("Unable to deserialize webhook_signing_keys")?
+        .into_iter()
+        .find(WebhookSigningKey::is_active);
+
+    signing_key
+        .map(|signing_key| {
+            sign_webhook(&signing_key, request_body).map(|signature| {
+                (
+                    headers::X_WEBHOOK_SIGNATURE_JWS.to_string(),
+                    signature.into(),
+                )
+            })
+        })
+        .transpose()
+}
+
+/// Returns the JWS of the request body signed with the key, with its payload detached
+fn sign_webhook(
+    signing_key: &WebhookSigningKey,
+    request_body: &str,
+) -> errors::CustomResult<String, errors::WebhooksFlowError> {
+    let private_key = signing_key.private_key.peek().as_bytes();
+    let signer: Box<dyn JwsSigner> = match signing_key.algorithm {
+        api_enums::WebhookSigningAlgorithm::Ed25519 => jws::EdDSA
+            .signer_from_pem(private_key)
+            .map(|signer| Box::new(signer) as Box<dyn JwsSigner>),
+        api_enums::WebhookSigningAlgorithm::Rs256 => jws::RS256
+            .signer_from_pem(private_key)
+            .map(|signer| Box::new(signer) as Box<dyn JwsSigner>),
+    }
+    .into_report()
+    .change_context(errors::WebhooksFlowError::OutgoingWebhookSigningFailed)
+    .attach_printable("Failed to load the webhook signing key")?;
+
+    let mut header = jws::JwsHeader::new();
+    header.set_key_id(&signing_key.key_id);
+    let signature = jws::serialize_compact(request_body.as_bytes(), &header, &*signer)
+        .into_report()
+        .change_context(errors::WebhooksFlowError::OutgoingWebhookSigningFailed)
+        .attach_printable("Failed to sign the outgoing webhook")?;
+
+    // The payload is detached from the signature, as it is sent as the body of the webhook
+    let mut parts = signature.splitn(3, '.');
+    let (header, _, signature) = (parts.next(), parts.next(), parts.next());
+    let (Some(header), Some(signature)) = (header, signature) else {
+        return Err(errors::WebhooksFlowError::OutgoingWebhookSigningFailed)
+            .into_report()
+            .attach_printable("The signature of the outgoing webhook is not a compact JWS");
+    };
+
+    Ok(format!("{header}..{signature}"))
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use base64::Engine;
+    use josekit::jws::JwsVerifier;
+
+    use super::*;
+
+    fn verify_webhook(
+        signing_key: &WebhookSigningKey,
+        signature: &str,
+        request_body: &str,
+    ) -> Result<(Vec<u8>, jws::JwsHeader), josekit::JoseError> {
+        let public_key = Jwk::from_map(
+            signing_key
+                .public_key
+                .as_object()
+                .cloned()
+                .unwrap_or_default(),
+        )?;
+        let verifier: Box<dyn JwsVerifier> = match signing_key.algorithm {
+            api_enums::WebhookSigningAlgorithm::Ed25519 => {
+                Box::new(jws::EdDSA.verifier_from_jwk(&public_key)?)
+            }
+            api_enums::WebhookSigningAlgorithm::Rs256 => {
+                Box::new(jws::RS256.verifier_from_jwk(&public_key)?)
+            }
+        };
+
+        // The detached payload is the body of the webhook
+        let payload = base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(request_body);
+        let signature = signature.replacen("..", &format!(".{payload}."), 1);
+        jws::deserialize_compact(signature, &*verifier)
+    }
+
+    fn assert_signature_round_trip(algorithm: api_enums::WebhookSigningAlgorithm) {
+        let signing_key = generate_webhook_signing_key(algorithm).unwrap();
+        let request_body = r#"{"type":"payment_succeeded"}"#;
+
+        let signature = sign_webhook(&signing_key, request_body).unwrap();
+        let (payload, header) = verify_webhook(&signing_key, &signature, request_body).unwrap();
+
+        assert_eq!(payload, request_body.as_bytes());
+        assert_eq!(header.key_id(), Some(signing_key.key_id.as_str()));
+        assert!(verify_webhook(&signing_key, &signature, r#"{"type":"payment_failed"}"#).is_err());
+    }
+
+    #[test]
+    fn test_ed25519_webhook_signature_round_trip() {
+        assert_signature_round_trip(api_enums::WebhookSigningAlgorithm::Ed25519);
+    }
+
+    #[test]
+    fn test_rs256_webhook_signature_round_trip() {
+        assert_signature_round_trip(api_enums::WebhookSigningAlgorithm::Rs256);
+    }
+
+    #[test]
+    fn test_webhook_signature_is_not_verified_with_another_key() {
+        let signing_key =
+            generate_webhook_signing_key(api_enums::WebhookSigningAlgorithm::Ed25519).unwrap();
+        let other_signing_key =
+            generate_webhook_signing_key(api_enums::WebhookSigningAlgorithm::Ed25519).unwrap();
+        let request_body = r#"{"type":"payment_succeeded"}"#;
+
+        let signature = sign_webhook(&signing_key, request_body).unwrap();
+
+        assert!(verify_webhook(&other_signing_key, &signature, request_body).is_err());
+    }
+
+    #[test]
+    fn test_invalid_webhook_signing_key_fails_to_sign() {
+        let signing_key = WebhookSigningKey {
+            private_key: Secret::new("not a PEM encoded key".to_string()),
+            ..generate_webhook_signing_key(api_enums::WebhookSigningAlgorithm::Rs256).unwrap()
+        };
+
+        assert!(sign_webhook(&signing_key, "{}").is_err());
+    }
+}
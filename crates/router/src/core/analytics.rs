@@ -0,0 +1,367 @@
+use std::collections::HashMap;
+
+use api_models::{
+    accounting::JournalEntryType,
+    analytics::{
+        CurrencyExposure, CurrencyExposureRequest, CurrencyExposureResponse, PaymentsFunnelBucket,
+        PaymentsFunnelRequest, PaymentsFunnelResponse, PaymentsFunnelStages, PayoutAnalyticsBucket,
+        PayoutAnalyticsRequest, PayoutAnalyticsResponse, PayoutFailureReason,
+    },
+};
+use common_utils::date_time;
+use error_stack::ResultExt;
+use router_env::{instrument, tracing};
+
+use crate::{
+    core::errors::{self, RouterResponse},
+    routes::AppState,
+    services::ApplicationResponse,
+    types::{
+        domain,
+        storage::{self, enums},
+    },
+};
+
+fn get_exposure(
+    exposures: &mut HashMap<enums::Currency, CurrencyExposure>,
+    currency: enums::Currency,
+) -> &mut CurrencyExposure {
+    exposures
+        .entry(currency)
+        .or_insert_with(|| CurrencyExposure {
+            currency,
+            gross_volume: 0,
+            payment_count: 0,
+            refunded_amount: 0,
+            refund_count: 0,
+            pending_payout_amount: 0,
+            pending_payout_count: 0,
+            net_dispute_impact: 0,
+            net_exposure: 0,
+        })
+}
+
+/// Summarizes the volumes of payments, refunds, pending payouts and disputes of the merchant per
+/// currency for the requested period. The volumes are aggregated by the database, payments and
+/// refunds being placed in the period in which they were captured or succeeded, payouts in the
+/// period in which they were created, and disputes in the period in which their funds were
+/// withdrawn or reinstated according to the ledger.
+#[instrument(skip(state))]
+pub async fn retrieve_currency_exposure(
+    state: &AppState,
+    merchant_account: domain::MerchantAccount,
+    req: CurrencyExposureRequest,
+) -> RouterResponse<CurrencyExposureResponse> {
+    let start_time = req.start_time;
+    let end_time = req.end_time.unwrap_or_else(date_time::now);
+    if start_time > end_time {
+        Err(errors::ApiErrorResponse::InvalidRequestData {
+            message: "`start_time` must be earlier than `end_time`".to_string(),
+        })?
+    }
+
+    let db = &*state.store;
+    let merchant_id = &merchant_account.merchant_id;
+    let (payment_volumes, refund_volumes, payout_volumes, dispute_totals) = futures::try_join!(
+        db.get_captured_payment_volume_by_currency(
+            merchant_id,
+            start_time,
+            end_time,
+            merchant_account.storage_scheme,
+        ),
+        db.get_refunded_volume_by_currency(
+            merchant_id,
+            start_time,
+            end_time,
+            merchant_account.storage_scheme,
+        ),
+        db.get_pending_payout_volume_by_currency(merchant_id, start_time, end_time),
+        db.get_ledger_totals_by_entry_type(
+            merchant_id,
+            vec![
+                JournalEntryType::Chargeback.to_string(),
+                JournalEntryType::ChargebackReversal.to_string(),
+            ],
+            start_time,
+            end_time,
+        ),
+    )
+    .change_context(errors::ApiErrorResponse::InternalServerError)
+    .attach_printable("Failed to aggregate the volumes of the period by currency")?;
+
+    let mut exposures = HashMap::new();
+    for storage::CurrencyVolume {
+        currency,
+        total_amount,
+        count,
+    } in payment_volumes
+    {
+        let exposure = get_exposure(&mut exposures, currency);
+        exposure.gross_volume = total_amount;
+        exposure.payment_count = count;
+    }
+    for storage::CurrencyVolume {
+        currency,
+        total_amount,
+        count,
+    } in refund_volumes
+    {
+        let exposure = get_exposure(&mut exposures, currency);
+        exposure.refunded_amount = total_amount;
+        exposure.refund_count = count;
+    }
+    for storage::CurrencyVolume {
+        currency,
+        total_amount,
+        count,
+    } in payout_volumes
+    {
+        let exposure = get_exposure(&mut exposures, currency);
+        exposure.pending_payout_amount = total_amount;
+        exposure.pending_payout_count = count;
+    }
+    for storage::LedgerEntryTypeTotal {
+        entry_type,
+        currency,
+        total_amount,
+    } in dispute_totals
+    {
+        let exposure = get_exposure(&mut exposures, currency);
+        if entry_type == JournalEntryType::ChargebackReversal.to_string() {
+            exposure.net_dispute_impact = exposure.net_dispute_impact.saturating_sub(total_amount);
+        } else {
+            exposure.net_dispute_impact = exposure.net_dispute_impact.saturating_add(total_amount);
+        }
+    }
+
+    let mut currencies: Vec<CurrencyExposure> = exposures
+        .into_values()
+        .map(|exposure| CurrencyExposure {
+            net_exposure: exposure
+                .gross_volume
+                .saturating_sub(exposure.refunded_amount)
+                .saturating_sub(exposure.pending_payout_amount)
+                .saturating_sub(exposure.net_dispute_impact),
+            ..exposure
+        })
+        .collect();
+    currencies.sort_by_key(|exposure| exposure.currency.to_string());
+
+    Ok(ApplicationResponse::Json(CurrencyExposureResponse {
+        start_time,
+        end_time,
+        currencies,
+    }))
+}
+
+/// The stages of the payments funnel, in the order in which payments go through them.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum FunnelStage {
+    Created,
+    Confirmed,
+    Authorized,
+    Captured,
+}
+
+/// Returns the furthest stage of the funnel reached by a payment whose active attempt is in the
+/// status.
+fn get_funnel_stage(status: enums::AttemptStatus) -> FunnelStage {
+    match status {
+        enums::AttemptStatus::Started
+        | enums::AttemptStatus::PaymentMethodAwaited
+        | enums::AttemptStatus::ConfirmationAwaited => FunnelStage::Created,
+        enums::AttemptStatus::AuthenticationPending
+        | enums::AttemptStatus::AuthenticationFailed
+        | enums::AttemptStatus::AuthenticationSuccessful
+        | enums::AttemptStatus::DeviceDataCollectionPending
+        | enums::AttemptStatus::Authorizing
+        | enums::AttemptStatus::AuthorizationFailed
+        | enums::AttemptStatus::RouterDeclined
+        | enums::AttemptStatus::CodInitiated
+        | enums::AttemptStatus::Pending
+        | enums::AttemptStatus::Unresolved
+        | enums::AttemptStatus::Failure => FunnelStage::Confirmed,
+        enums::AttemptStatus::Authorized
+        | enums::AttemptStatus::VoidInitiated
+        | enums::AttemptStatus::Voided
+        | enums::AttemptStatus::VoidFailed
+        | enums::AttemptStatus::CaptureInitiated
+        | enums::AttemptStatus::CaptureFailed => FunnelStage::Authorized,
+        enums::AttemptStatus::Charged
+        | enums::AttemptStatus::PartialCharged
+        | enums::AttemptStatus::AutoRefunded => FunnelStage::Captured,
+    }
+}
+
+fn add_to_funnel(stages: &mut PaymentsFunnelStages, status: enums::AttemptStatus, count: i64) {
+    let stage = get_funnel_stage(status);
+    stages.created += count;
+    if stage >= FunnelStage::Confirmed {
+        stages.confirmed += count;
+    }
+    if stage >= FunnelStage::Authorized {
+        stages.authorized += count;
+    }
+    if stage >= FunnelStage::Captured {
+        stages.captured += count;
+    }
+    if matches!(
+        status,
+        enums::AttemptStatus::AuthenticationPending | enums::AttemptStatus::AuthenticationFailed
+    ) {
+        stages.authentication_dropped += count;
+    }
+}
+
+/// Computes the number of payments created in the requested period which reached each stage of
+/// the funnel, per period of the requested granularity and payment method. The stage reached by
+/// a payment is derived from the current status of its active attempt, as aggregated by the
+/// database.
+#[instrument(skip(state))]
+pub async fn retrieve_payments_funnel(
+    state: &AppState,
+    merchant_account: domain::MerchantAccount,
+    mut req: PaymentsFunnelRequest,
+) -> RouterResponse<PaymentsFunnelResponse> {
+    let start_time = req.start_time;
+    let end_time = *req.end_time.get_or_insert_with(date_time::now);
+    if start_time > end_time {
+        Err(errors::ApiErrorResponse::InvalidRequestData {
+            message: "`start_time` must be earlier than `end_time`".to_string(),
+        })?
+    }
+
+    let funnel_counts = state
+        .store
+        .get_payment_funnel_counts(
+            &merchant_account.merchant_id,
+            &req,
+            merchant_account.storage_scheme,
+        )
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to aggregate the payments of the period by funnel stage")?;
+
+    let mut total = PaymentsFunnelStages::default();
+    let mut buckets: Vec<PaymentsFunnelBucket> = Vec::new();
+    for storage::FunnelCount {
+        period_start,
+        payment_method,
+        status,
+        count,
+    } in funnel_counts
+    {
+        add_to_funnel(&mut total, status, count);
+
+        // The counts are ordered by period, so the bucket is searched from the latest one
+        let existing_bucket = buckets.iter_mut().rev().find(|bucket| {
+            bucket.period_start == period_start && bucket.payment_method == payment_method
+        });
+        match existing_bucket {
+            Some(bucket) => add_to_funnel(&mut bucket.stages, status, count),
+            None => {
+                let mut stages = PaymentsFunnelStages::default();
+                add_to_funnel(&mut stages, status, count);
+                buckets.push(PaymentsFunnelBucket {
+                    period_start,
+                    payment_method,
+                    stages,
+                });
+            }
+        }
+    }
+
+    Ok(ApplicationResponse::Json(PaymentsFunnelResponse {
+        start_time,
+        end_time,
+        granularity: req.granularity,
+        total,
+        buckets,
+    }))
+}
+
+/// Returns the ratio of the successful payouts to the payouts which either succeeded or failed.
+#[allow(clippy::as_conversions)]
+fn get_success_rate(successful_count: i64, failed_count: i64) -> Option<f64> {
+    let completed_count = successful_count + failed_count;
+    (completed_count > 0).then(|| successful_count as f64 / completed_count as f64)
+}
+
+/// Aggregates the payouts created in the requested period per connector, currency and payout
+/// type, along with the reasons for which they failed, as aggregated by the database.
+#[instrument(skip(state))]
+pub async fn retrieve_payout_analytics(
+    state: &AppState,
+    merchant_account: domain::MerchantAccount,
+    req: PayoutAnalyticsRequest,
+) -> RouterResponse<PayoutAnalyticsResponse> {
+    let start_time = req.start_time;
+    let end_time = req.end_time.unwrap_or_else(date_time::now);
+    if start_time > end_time {
+        Err(errors::ApiErrorResponse::InvalidRequestData {
+            message: "`start_time` must be earlier than `end_time`".to_string(),
+        })?
+    }
+
+    let db = &*state.store;
+    let merchant_id = &merchant_account.merchant_id;
+    let (volumes, failure_counts) = futures::try_join!(
+        db.get_payout_volumes(merchant_id, start_time, end_time),
+        db.get_payout_failure_counts(merchant_id, start_time, end_time),
+    )
+    .change_context(errors::ApiErrorResponse::InternalServerError)
+    .attach_printable("Failed to aggregate the payouts of the period")?;
+
+    let buckets = volumes
+        .into_iter()
+        .map(|volume| PayoutAnalyticsBucket {
+            success_rate: get_success_rate(volume.successful_count, volume.failed_count),
+            connector: volume.connector,
+            currency: volume.currency,
+            payout_type: volume.payout_type,
+            payout_count: volume.payout_count,
+            total_amount: volume.total_amount,
+            successful_count: volume.successful_count,
+            successful_amount: volume.successful_amount,
+            failed_count: volume.failed_count,
+            average_fulfillment_latency: volume.average_fulfillment_latency,
+        })
+        .collect();
+
+    let failure_reasons = failure_counts
+        .into_iter()
+        .map(|failure_count| PayoutFailureReason {
+            connector: failure_count.connector,
+            error_code: failure_count.error_code,
+            error_message: failure_count.error_message,
+            count: failure_count.count,
+        })
+        .collect();
+
+    Ok(ApplicationResponse::Json(PayoutAnalyticsResponse {
+        start_time,
+        end_time,
+        buckets,
+        failure_reasons,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_add_to_funnel() {
+        let mut stages = PaymentsFunnelStages::default();
+        add_to_funnel(&mut stages, enums::AttemptStatus::Charged, 5);
+        add_to_funnel(&mut stages, enums::AttemptStatus::AuthenticationPending, 2);
+        add_to_funnel(&mut stages, enums::AttemptStatus::Authorized, 1);
+        add_to_funnel(&mut stages, enums::AttemptStatus::PaymentMethodAwaited, 3);
+
+        assert_eq!(stages.created, 11);
+        assert_eq!(stages.confirmed, 8);
+        assert_eq!(stages.authorized, 6);
+        assert_eq!(stages.captured, 5);
+        assert_eq!(stages.authentication_dropped, 2);
+    }
+}
@@ -12,7 +12,10 @@ use crate::types::storage::enums;
 use crate::{
     configs::settings,
     consts,
-    core::errors::{self, RouterResponse, StorageErrorExt},
+    core::{
+        audit,
+        errors::{self, RouterResponse, StorageErrorExt},
+    },
     db::StorageInterface,
     routes::{metrics, AppState},
     services::ApplicationResponse,
@@ -178,9 +181,23 @@ pub async fn create_api_key(
     metrics::API_KEY_CREATED.add(
         &metrics::CONTEXT,
         1,
-        &[metrics::request::add_attributes("merchant", merchant_id)],
+        &[metrics::request::add_attributes(
+            "merchant",
+            merchant_id.clone(),
+        )],
     );
 
+    let audited_api_key: api::RetrieveApiKeyResponse = api_key.clone().foreign_into();
+    audit::record_audit_event(
+        store,
+        &merchant_id,
+        storage::enums::AuditEntityType::ApiKey,
+        &api_key.key_id,
+        None,
+        Some(&audited_api_key),
+    )
+    .await?;
+
     // Add process to process_tracker for email reminder, only if expiry is set to future date
     // If the `api_key` is set to expire in less than 7 days, the merchant is not notified about it's expiry
     #[cfg(feature = "email")]
@@ -300,6 +317,14 @@ pub async fn update_api_key(
 ) -> RouterResponse<api::RetrieveApiKeyResponse> {
     let store = &*state.store;
 
+    let previous_api_key: api::RetrieveApiKeyResponse = store
+        .find_api_key_by_merchant_id_key_id_optional(merchant_id, key_id)
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to retrieve API key")?
+        .ok_or(report!(errors::ApiErrorResponse::ApiKeyNotFound))?
+        .foreign_into();
+
     let api_key = store
         .update_api_key(
             merchant_id.to_owned(),
@@ -309,6 +334,17 @@ pub async fn update_api_key(
         .await
         .to_not_found_response(errors::ApiErrorResponse::ApiKeyNotFound)?;
 
+    let updated_api_key: api::RetrieveApiKeyResponse = api_key.clone().foreign_into();
+    audit::record_audit_event(
+        store,
+        merchant_id,
+        storage::enums::AuditEntityType::ApiKey,
+        key_id,
+        Some(&previous_api_key),
+        Some(&updated_api_key),
+    )
+    .await?;
+
     #[cfg(feature = "email")]
     {
         let expiry_reminder_days = state.conf.api_keys.expiry_reminder_days.clone();
@@ -434,6 +470,13 @@ pub async fn revoke_api_key(
     key_id: &str,
 ) -> RouterResponse<api::RevokeApiKeyResponse> {
     let store = &*state.store;
+    let previous_api_key: Option<api::RetrieveApiKeyResponse> = store
+        .find_api_key_by_merchant_id_key_id_optional(merchant_id, key_id)
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to retrieve API key")?
+        .map(ForeignInto::foreign_into);
+
     let revoked = store
         .revoke_api_key(merchant_id, key_id)
         .await
@@ -441,6 +484,18 @@ pub async fn revoke_api_key(
 
     metrics::API_KEY_REVOKED.add(&metrics::CONTEXT, 1, &[]);
 
+    if revoked {
+        audit::record_audit_event(
+            store,
+            merchant_id,
+            storage::enums::AuditEntityType::ApiKey,
+            key_id,
+            previous_api_key.as_ref(),
+            None,
+        )
+        .await?;
+    }
+
     #[cfg(feature = "email")]
     {
         let task_id = generate_task_id_for_api_key_expiry_workflow(key_id);
@@ -0,0 +1,459 @@
+//! Audit trail of the changes made to the configuration of merchants.
+//!
+//! Changes made to merchant accounts, merchant connector accounts, routing configurations, API
+//! keys and webhook settings are recorded as audit events with the values of the changed fields
+//! before and after the change, so that compliance teams can review who changed what and when.
+
+use std::{collections::BTreeSet, future::Future};
+
+use api_models::audit::{AuditEventListConstraints, AuditEventListResponse, AuditEventResponse};
+use common_utils::date_time;
+use error_stack::{IntoReport, ResultExt};
+use masking::Secret;
+use router_env::{instrument, tracing};
+use serde::Serialize;
+
+use crate::{
+    connection, consts,
+    core::errors::{self, CustomResult, RouterResponse, RouterResult},
+    db::StorageInterface,
+    routes::AppState,
+    services::ApplicationResponse,
+    types::{
+        api, domain,
+        storage::{self, enums},
+    },
+    utils,
+};
+
+/// The actor recorded for changes made through the admin APIs, which are authenticated with the
+/// admin API key and hence cannot be attributed to an individual user.
+pub const ADMIN_API_KEY_ACTOR: &str = "admin_api_key";
+
+/// The actor recorded for changes made by dashboard users whose JWT does not identify the user.
+pub const DASHBOARD_USER_ACTOR: &str = "dashboard_user";
+
+/// The actor recorded for changes made by requests whose caller could not be identified.
+pub const UNKNOWN_ACTOR: &str = "unknown";
+
+/// The actor recorded for changes made outside of requests, such as by the scheduler.
+const SYSTEM_ACTOR: &str = "system";
+
+tokio::task_local! {
+    /// The caller of the request being served, to which the changes made during the request are
+    /// attributed.
+    static ACTOR: String;
+}
+
+const MASKED_VALUE: &str = "*** masked ***";
+
+/// Fields whose values are never stored in audit events. Changes to them are still recorded,
+/// with their values masked.
+const SENSITIVE_FIELDS: &[&str] = &[
+    "api_key",
+    "connector_account_details",
+    "test_connector_account_details",
+    "webhook_password",
+    "payment_response_hash_key",
+    "client_certificate_key",
+    "connector_webhook_details",
+    "webhook_secret",
+];
+
+/// Fields of a merchant account which are audited as its routing configuration.
+const ROUTING_CONFIG_FIELDS: &[&str] = &[
+    "routing_algorithm",
+    "frm_routing_algorithm",
+    "payout_routing_algorithm",
+];
+
+/// Fields of a merchant account which are audited as its webhook settings.
+const WEBHOOK_SETTINGS_FIELDS: &[&str] = &["webhook_details"];
+
+type Fields = serde_json::Map<String, serde_json::Value>;
+
+/// The action recorded for a change, along with the values of the changed fields before and
+/// after the change.
+type AuditChanges = (enums::AuditAction, Option<Fields>, Option<Fields>);
+
+/// Runs the future with the changes it makes audited as made by the given actor.
+pub async fn with_actor<F>(actor: String, future: F) -> F::Output
+where
+    F: Future,
+{
+    ACTOR.scope(actor, future).await
+}
+
+fn current_actor() -> String {
+    ACTOR
+        .try_with(Clone::clone)
+        .unwrap_or_else(|_| SYSTEM_ACTOR.to_string())
+}
+
+fn get_fields<T: Serialize>(value: &T) -> RouterResult<Fields> {
+    match serde_json::to_value(value)
+        .into_report()
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to serialize the audited entity")?
+    {
+        serde_json::Value::Object(fields) => Ok(fields),
+        _ => Err(errors::ApiErrorResponse::InternalServerError)
+            .into_report()
+            .attach_printable("The audited entity was not serialized to an object"),
+    }
+}
+
+/// Returns the values before and after the change of the fields which differ between the two
+/// versions of an entity.
+fn get_changed_fields(previous: &Fields, updated: &Fields) -> (Fields, Fields) {
+    let keys: BTreeSet<&String> = previous.keys().chain(updated.keys()).collect();
+    let mut previous_values = Fields::new();
+    let mut updated_values = Fields::new();
+    for key in keys
+        .into_iter()
+        .filter(|key| previous.get(*key) != updated.get(*key))
+    {
+        if let Some(value) = previous.get(key) {
+            previous_values.insert(key.clone(), value.clone());
+        }
+        if let Some(value) = updated.get(key) {
+            updated_values.insert(key.clone(), value.clone());
+        }
+    }
+    (previous_values, updated_values)
+}
+
+/// Removes the given fields from the changed fields, returning them separately.
+fn take_fields(fields: &mut Fields, keys: &[&str]) -> Fields {
+    keys.iter()
+        .filter_map(|key| fields.remove(*key).map(|value| (key.to_string(), value)))
+        .collect()
+}
+
+fn mask_sensitive_fields(value: serde_json::Value) -> serde_json::Value {
+    match value {
+        serde_json::Value::Object(fields) => serde_json::Value::Object(
+            fields
+                .into_iter()
+                .map(|(key, value)| {
+                    let value = if SENSITIVE_FIELDS.contains(&key.as_str()) && !value.is_null() {
+                        serde_json::Value::String(MASKED_VALUE.to_string())
+                    } else {
+                        mask_sensitive_fields(value)
+                    };
+                    (key, value)
+                })
+                .collect(),
+        ),
+        serde_json::Value::Array(values) => {
+            serde_json::Value::Array(values.into_iter().map(mask_sensitive_fields).collect())
+        }
+        value => value,
+    }
+}
+
+/// Returns the storage region of the merchant, in which its audit events are stored. Changes made
+/// through the admin APIs are not made in the storage region of the merchant, so the region is
+/// read from the merchant account when the current task is not bound to one. Merchants which no
+/// longer exist have their audit events stored in the default databases.
+async fn get_storage_region(
+    db: &dyn StorageInterface,
+    merchant_id: &str,
+) -> CustomResult<Option<String>, errors::StorageError> {
+    if let Some(storage_region) = connection::current_storage_region() {
+        return Ok(Some(storage_region));
+    }
+
+    let merchant_account = async {
+        let key_store = db
+            .get_merchant_key_store_by_merchant_id(
+                merchant_id,
+                &db.get_master_key().to_vec().into(),
+            )
+            .await?;
+        db.find_merchant_account_by_merchant_id(merchant_id, &key_store)
+            .await
+    }
+    .await;
+
+    match merchant_account {
+        Ok(merchant_account) => Ok(merchant_account.storage_region),
+        Err(error) if error.current_context().is_db_not_found() => Ok(None),
+        Err(error) => Err(error),
+    }
+}
+
+async fn insert_audit_event(
+    db: &dyn StorageInterface,
+    merchant_id: &str,
+    entity_type: enums::AuditEntityType,
+    entity_id: &str,
+    action: enums::AuditAction,
+    previous_values: Option<Fields>,
+    updated_values: Option<Fields>,
+) -> RouterResult<()> {
+    let to_secret_value =
+        |fields: Fields| Secret::new(mask_sensitive_fields(serde_json::Value::Object(fields)));
+    let audit_event = storage::AuditEventNew {
+        event_id: utils::generate_id(consts::ID_LENGTH, "aud"),
+        merchant_id: merchant_id.to_owned(),
+        entity_type,
+        entity_id: entity_id.to_owned(),
+        action,
+        actor: current_actor(),
+        previous_values: previous_values.map(to_secret_value),
+        updated_values: updated_values.map(to_secret_value),
+        created_at: date_time::now(),
+    };
+
+    // Changes which could not be audited are reported as failed, so that they are not left
+    // unaudited without the caller knowing
+    let storage_region = get_storage_region(db, merchant_id)
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to fetch the storage region of the merchant")?;
+    connection::with_storage_region(storage_region, db.insert_audit_event(audit_event))
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable_lazy(|| {
+            format!("Failed to record the {action} audit event of the {entity_type} {entity_id}")
+        })?;
+    Ok(())
+}
+
+/// Determines the kind of change made to an entity from which of its previous and updated
+/// versions are present. Updates which do not change any field yield no changes.
+fn get_audit_changes<T: Serialize>(
+    previous: Option<&T>,
+    updated: Option<&T>,
+) -> RouterResult<Option<AuditChanges>> {
+    let previous = previous.map(get_fields).transpose()?;
+    let updated = updated.map(get_fields).transpose()?;
+
+    Ok(match (previous, updated) {
+        (None, Some(updated)) => Some((enums::AuditAction::Create, None, Some(updated))),
+        (Some(previous), None) => Some((enums::AuditAction::Delete, Some(previous), None)),
+        (Some(previous), Some(updated)) => {
+            let (previous_values, updated_values) = get_changed_fields(&previous, &updated);
+            (!previous_values.is_empty() || !updated_values.is_empty()).then_some((
+                enums::AuditAction::Update,
+                Some(previous_values),
+                Some(updated_values),
+            ))
+        }
+        (None, None) => None,
+    })
+}
+
+/// Records the creation, update or deletion of an entity, depending on which of its previous and
+/// updated versions are present.
+#[instrument(skip_all)]
+pub async fn record_audit_event<T: Serialize>(
+    db: &dyn StorageInterface,
+    merchant_id: &str,
+    entity_type: enums::AuditEntityType,
+    entity_id: &str,
+    previous: Option<&T>,
+    updated: Option<&T>,
+) -> RouterResult<()> {
+    match get_audit_changes(previous, updated)? {
+        Some((action, previous_values, updated_values)) => {
+            insert_audit_event(
+                db,
+                merchant_id,
+                entity_type,
+                entity_id,
+                action,
+                previous_values,
+                updated_values,
+            )
+            .await
+        }
+        None => Ok(()),
+    }
+}
+
+/// Records an update of a merchant account. Changes to its routing algorithms and webhook
+/// details are recorded as changes to the routing configuration and webhook settings of the
+/// merchant respectively, separately from changes to its other fields.
+#[instrument(skip_all)]
+pub async fn record_merchant_account_update(
+    db: &dyn StorageInterface,
+    previous: &api::MerchantAccountResponse,
+    updated: &api::MerchantAccountResponse,
+) -> RouterResult<()> {
+    let (mut previous_values, mut updated_values) =
+        get_changed_fields(&get_fields(previous)?, &get_fields(updated)?);
+
+    let routing_config_changes = (
+        take_fields(&mut previous_values, ROUTING_CONFIG_FIELDS),
+        take_fields(&mut updated_values, ROUTING_CONFIG_FIELDS),
+    );
+    let webhook_settings_changes = (
+        take_fields(&mut previous_values, WEBHOOK_SETTINGS_FIELDS),
+        take_fields(&mut updated_values, WEBHOOK_SETTINGS_FIELDS),
+    );
+
+    for (entity_type, (previous_values, updated_values)) in [
+        (
+            enums::AuditEntityType::MerchantAccount,
+            (previous_values, updated_values),
+        ),
+        (
+            enums::AuditEntityType::RoutingConfig,
+            routing_config_changes,
+        ),
+        (
+            enums::AuditEntityType::WebhookSettings,
+            webhook_settings_changes,
+        ),
+    ] {
+        if previous_values.is_empty() && updated_values.is_empty() {
+            continue;
+        }
+        insert_audit_event(
+            db,
+            &updated.merchant_id,
+            entity_type,
+            &updated.merchant_id,
+            enums::AuditAction::Update,
+            Some(previous_values),
+            Some(updated_values),
+        )
+        .await?;
+    }
+    Ok(())
+}
+
+#[instrument(skip(state))]
+pub async fn list_audit_events(
+    state: &AppState,
+    merchant_account: domain::MerchantAccount,
+    constraints: AuditEventListConstraints,
+) -> RouterResponse<AuditEventListResponse> {
+    let limit = constraints
+        .limit
+        .unwrap_or(consts::DEFAULT_AUDIT_EVENT_LIST_LIMIT);
+    if !(1..=consts::MAX_AUDIT_EVENT_LIST_LIMIT).contains(&limit) {
+        Err(errors::ApiErrorResponse::InvalidRequestData {
+            message: format!(
+                "`limit` must be between 1 and {}",
+                consts::MAX_AUDIT_EVENT_LIST_LIMIT
+            ),
+        })?
+    }
+
+    let data: Vec<_> = state
+        .store
+        .filter_audit_events_by_constraints(&merchant_account.merchant_id, &constraints, limit)
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to retrieve the audit events of the merchant")?
+        .into_iter()
+        .map(|audit_event| AuditEventResponse {
+            event_id: audit_event.event_id,
+            entity_type: audit_event.entity_type,
+            entity_id: audit_event.entity_id,
+            action: audit_event.action,
+            actor: audit_event.actor,
+            previous_values: audit_event.previous_values,
+            updated_values: audit_event.updated_values,
+            created_at: audit_event.created_at,
+        })
+        .collect();
+
+    Ok(ApplicationResponse::Json(AuditEventListResponse {
+        count: data.len(),
+        data,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used, clippy::expect_used, clippy::panic)]
+    use serde_json::json;
+
+    use super::*;
+
+    fn to_fields(value: serde_json::Value) -> Fields {
+        match value {
+            serde_json::Value::Object(fields) => fields,
+            _ => panic!("not an object"),
+        }
+    }
+
+    #[test]
+    fn test_sensitive_fields_are_masked() {
+        let masked = mask_sensitive_fields(json!({
+            "merchant_id": "merchant_1",
+            "payment_response_hash_key": null,
+            "primary_business_details": [{
+                "country": "US",
+                "webhook_endpoints": [{
+                    "url": "https://example.com",
+                    "webhook_secret": "whsec_123",
+                }],
+            }],
+        }));
+        assert_eq!(
+            masked,
+            json!({
+                "merchant_id": "merchant_1",
+                "payment_response_hash_key": null,
+                "primary_business_details": [{
+                    "country": "US",
+                    "webhook_endpoints": [{
+                        "url": "https://example.com",
+                        "webhook_secret": MASKED_VALUE,
+                    }],
+                }],
+            })
+        );
+    }
+
+    #[test]
+    fn test_audit_changes() {
+        let previous = json!({"name": "old", "enabled": true, "removed": 1});
+        let updated = json!({"name": "new", "enabled": true});
+
+        let (action, previous_values, updated_values) =
+            get_audit_changes(Some(&previous), Some(&updated))
+                .unwrap()
+                .expect("update not audited");
+        assert_eq!(action, enums::AuditAction::Update);
+        assert_eq!(
+            previous_values,
+            Some(to_fields(json!({"name": "old", "removed": 1})))
+        );
+        assert_eq!(updated_values, Some(to_fields(json!({"name": "new"}))));
+
+        assert!(get_audit_changes(Some(&previous), Some(&previous))
+            .unwrap()
+            .is_none());
+        assert!(matches!(
+            get_audit_changes(None, Some(&updated)).unwrap(),
+            Some((enums::AuditAction::Create, None, Some(_)))
+        ));
+        assert!(matches!(
+            get_audit_changes(Some(&previous), None).unwrap(),
+            Some((enums::AuditAction::Delete, Some(_), None))
+        ));
+        assert!(get_audit_changes(Some(&json!("not an object")), None).is_err());
+    }
+
+    #[test]
+    fn test_take_fields() {
+        let mut fields = to_fields(json!({"routing_algorithm": {}, "return_url": "url"}));
+        let routing_fields = take_fields(&mut fields, ROUTING_CONFIG_FIELDS);
+        assert_eq!(routing_fields, to_fields(json!({"routing_algorithm": {}})));
+        assert_eq!(fields, to_fields(json!({"return_url": "url"})));
+    }
+
+    #[tokio::test]
+    async fn test_changes_are_attributed_to_the_actor() {
+        assert_eq!(current_actor(), SYSTEM_ACTOR);
+        let actor = with_actor(ADMIN_API_KEY_ACTOR.to_string(), async { current_actor() }).await;
+        assert_eq!(actor, ADMIN_API_KEY_ACTOR);
+        assert_eq!(current_actor(), SYSTEM_ACTOR);
+    }
+}
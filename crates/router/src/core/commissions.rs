@@ -0,0 +1,342 @@
+use std::collections::BTreeMap;
+
+use api_models::commissions::{
+    CommissionResponse, CommissionStatementRequest, CommissionStatementResponse,
+    CommissionStatementTotal,
+};
+use common_utils::date_time;
+use error_stack::{IntoReport, ResultExt};
+use router_env::{instrument, logger, tracing};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    configs::settings::{CommissionPricingPlan, PlatformCommission},
+    consts,
+    core::errors::{self, RouterResponse, RouterResult, StorageErrorExt},
+    routes::{metrics, AppState},
+    scheduler::{metrics as scheduler_metrics, utils as pt_utils},
+    services::ApplicationResponse,
+    types::{
+        domain,
+        storage::{self, ProcessTrackerExt},
+    },
+    utils::{self, OptionExt},
+};
+
+const COMMISSION_RECORDING_RUNNER: &str = "COMMISSION_RECORDING_WORKFLOW";
+const COMMISSION_RECORDING_TASK: &str = "COMMISSION_RECORDING";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommissionRecordingTrackingData {
+    pub merchant_id: String,
+    pub payment_id: String,
+}
+
+/// Finds the pricing plan assigned to the merchant, falling back to the default pricing plan.
+fn get_pricing_plan<'a>(
+    config: &'a PlatformCommission,
+    merchant_id: &str,
+) -> Option<(&'a String, &'a CommissionPricingPlan)> {
+    let plan_name = config
+        .merchant_plans
+        .get(merchant_id)
+        .or(config.default_plan.as_ref())?;
+    config.plans.get_key_value(plan_name)
+}
+
+/// Computes the commission charged on a payment, with the variable part rounded half up to the
+/// lowest denomination of the currency. The commission never exceeds the amount of the payment.
+fn get_commission_amount(plan: &CommissionPricingPlan, payment_amount: i64) -> i64 {
+    let denominator = i128::from(consts::BASIS_POINTS_DENOMINATOR);
+    let variable_amount =
+        (i128::from(payment_amount) * i128::from(plan.rate) * 2 + denominator) / (denominator * 2);
+
+    i64::try_from(variable_amount)
+        .unwrap_or(i64::MAX)
+        .saturating_add(plan.fixed_amount)
+        .min(payment_amount)
+}
+
+/// Records the commission of the platform on a payment which has succeeded. A payment is only
+/// charged commission once, even if it is reported to have succeeded more than once.
+#[instrument(skip_all)]
+pub async fn record_payment_commission(
+    state: &AppState,
+    merchant_account: &domain::MerchantAccount,
+    payment_intent: &storage::PaymentIntent,
+) -> RouterResult<()> {
+    let config = &state.conf.platform_commission;
+    if !config.enabled {
+        return Ok(());
+    }
+    let (pricing_plan, plan) = match get_pricing_plan(config, &merchant_account.merchant_id) {
+        Some(pricing_plan) => pricing_plan,
+        None => return Ok(()),
+    };
+
+    let payment_amount = payment_intent
+        .amount_captured
+        .unwrap_or(payment_intent.amount);
+    let commission = storage::CommissionNew {
+        commission_id: utils::generate_id(consts::ID_LENGTH, "com"),
+        merchant_id: merchant_account.merchant_id.clone(),
+        parent_merchant_id: merchant_account.parent_merchant_id.clone(),
+        payment_id: payment_intent.payment_id.clone(),
+        pricing_plan: pricing_plan.clone(),
+        currency: payment_intent.currency.get_required_value("currency")?,
+        payment_amount,
+        commission_amount: get_commission_amount(plan, payment_amount),
+        created_at: date_time::now(),
+    };
+
+    match state.store.insert_commission(commission).await {
+        Ok(_) => Ok(()),
+        Err(error) if error.current_context().is_db_unique_violation() => {
+            logger::info!(
+                payment_id = %payment_intent.payment_id,
+                "Commission has already been recorded for the payment"
+            );
+            Ok(())
+        }
+        Err(error) => Err(error)
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Failed to record the commission of the payment"),
+    }
+}
+
+/// Adds a commission to the total of its currency. Totals are keyed by the currency code, so that
+/// they are listed in alphabetical order.
+fn add_commission_to_totals(
+    totals: &mut BTreeMap<String, CommissionStatementTotal>,
+    commission: &storage::Commission,
+) -> RouterResult<()> {
+    let total = totals
+        .entry(commission.currency.to_string())
+        .or_insert_with(|| CommissionStatementTotal {
+            currency: commission.currency,
+            payment_count: 0,
+            payment_amount: 0,
+            commission_amount: 0,
+        });
+    total.payment_count = total
+        .payment_count
+        .checked_add(1)
+        .ok_or(errors::ApiErrorResponse::InternalServerError)
+        .into_report()
+        .attach_printable("Payment count overflowed in the commission statement")?;
+    total.payment_amount = total
+        .payment_amount
+        .checked_add(commission.payment_amount)
+        .ok_or(errors::ApiErrorResponse::InternalServerError)
+        .into_report()
+        .attach_printable("Payment amount overflowed in the commission statement")?;
+    total.commission_amount = total
+        .commission_amount
+        .checked_add(commission.commission_amount)
+        .ok_or(errors::ApiErrorResponse::InternalServerError)
+        .into_report()
+        .attach_printable("Commission amount overflowed in the commission statement")?;
+    Ok(())
+}
+
+/// Schedules the commission of a succeeded payment to be recorded again later, when recording it
+/// along with the payment failed.
+pub async fn add_commission_recording_task(
+    state: &AppState,
+    merchant_account: &domain::MerchantAccount,
+    payment_intent: &storage::PaymentIntent,
+) -> RouterResult<()> {
+    let process_tracker_id = pt_utils::get_process_tracker_id(
+        COMMISSION_RECORDING_RUNNER,
+        COMMISSION_RECORDING_TASK,
+        &payment_intent.payment_id,
+        &merchant_account.merchant_id,
+    );
+    let tracking_data = CommissionRecordingTrackingData {
+        merchant_id: merchant_account.merchant_id.clone(),
+        payment_id: payment_intent.payment_id.clone(),
+    };
+    let schedule_time = date_time::now().saturating_add(time::Duration::seconds(
+        consts::COMMISSION_RECORDING_RETRY_DELAY,
+    ));
+    let process_tracker_entry = <storage::ProcessTracker>::make_process_tracker_new(
+        process_tracker_id,
+        COMMISSION_RECORDING_TASK,
+        COMMISSION_RECORDING_RUNNER,
+        tracking_data,
+        schedule_time,
+    )
+    .change_context(errors::ApiErrorResponse::InternalServerError)
+    .attach_printable("Failed to construct the commission recording task")?;
+
+    state
+        .store
+        .insert_process(process_tracker_entry)
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to schedule the commission recording task")?;
+    scheduler_metrics::TASKS_ADDED_COUNT.add(&metrics::CONTEXT, 1, &[]);
+
+    Ok(())
+}
+
+/// Records the commission of a succeeded payment from the process tracker, for payments whose
+/// commission could not be recorded along with the payment.
+pub async fn record_scheduled_payment_commission(
+    state: &AppState,
+    tracking_data: &CommissionRecordingTrackingData,
+) -> RouterResult<()> {
+    let db = &*state.store;
+    let key_store = db
+        .get_merchant_key_store_by_merchant_id(
+            &tracking_data.merchant_id,
+            &db.get_master_key().to_vec().into(),
+        )
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::MerchantAccountNotFound)?;
+    let merchant_account = db
+        .find_merchant_account_by_merchant_id(&tracking_data.merchant_id, &key_store)
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::MerchantAccountNotFound)?;
+    let payment_intent = db
+        .find_payment_intent_by_payment_id_merchant_id(
+            &tracking_data.payment_id,
+            &merchant_account.merchant_id,
+            merchant_account.storage_scheme,
+        )
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::PaymentNotFound)?;
+
+    record_payment_commission(state, &merchant_account, &payment_intent).await
+}
+
+#[instrument(skip(state))]
+pub async fn retrieve_commission_statement(
+    state: &AppState,
+    merchant_id: String,
+    req: CommissionStatementRequest,
+) -> RouterResponse<CommissionStatementResponse> {
+    let start_time = req.start_time;
+    let end_time = req.end_time.unwrap_or_else(date_time::now);
+    if start_time > end_time {
+        Err(errors::ApiErrorResponse::InvalidRequestData {
+            message: "`start_time` must be earlier than `end_time`".to_string(),
+        })?
+    }
+
+    let db = &*state.store;
+    let key_store = db
+        .get_merchant_key_store_by_merchant_id(&merchant_id, &db.get_master_key().to_vec().into())
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::MerchantAccountNotFound)?;
+    let merchant_account = db
+        .find_merchant_account_by_merchant_id(&merchant_id, &key_store)
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::MerchantAccountNotFound)?;
+
+    // All the commissions of the period are summed up, while only the first ones are listed
+    let mut totals = BTreeMap::new();
+    let mut commissions = Vec::new();
+    let mut is_truncated = false;
+    let mut after_id = 0;
+    loop {
+        let page = db
+            .filter_commissions_by_merchant_id_created_at_range_after_id(
+                &merchant_id,
+                start_time,
+                end_time,
+                after_id,
+                consts::COMMISSION_STATEMENT_RECORDS_LIMIT,
+            )
+            .await
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Failed to retrieve the commissions of the merchant")?;
+        let is_last_page = i64::try_from(page.len()).map_or(false, |count| {
+            count < consts::COMMISSION_STATEMENT_RECORDS_LIMIT
+        });
+
+        for commission in page {
+            after_id = commission.id;
+            add_commission_to_totals(&mut totals, &commission)?;
+            if i64::try_from(commissions.len()).map_or(false, |count| {
+                count < consts::COMMISSION_STATEMENT_RECORDS_LIMIT
+            }) {
+                commissions.push(commission);
+            } else {
+                is_truncated = true;
+            }
+        }
+
+        if is_last_page {
+            break;
+        }
+    }
+    if is_truncated {
+        logger::warn!(
+            "Only the first {} commissions of the period are listed in the statement",
+            consts::COMMISSION_STATEMENT_RECORDS_LIMIT
+        );
+    }
+
+    Ok(ApplicationResponse::Json(CommissionStatementResponse {
+        pricing_plan: get_pricing_plan(&state.conf.platform_commission, &merchant_id)
+            .map(|(pricing_plan, _)| pricing_plan.clone()),
+        merchant_id,
+        parent_merchant_id: merchant_account.parent_merchant_id,
+        start_time,
+        end_time,
+        totals: totals.into_values().collect(),
+        commissions: commissions
+            .into_iter()
+            .map(|commission| CommissionResponse {
+                commission_id: commission.commission_id,
+                payment_id: commission.payment_id,
+                pricing_plan: commission.pricing_plan,
+                currency: commission.currency,
+                payment_amount: commission.payment_amount,
+                commission_amount: commission.commission_amount,
+                created: commission.created_at,
+            })
+            .collect(),
+        is_truncated,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+
+    fn get_commission(payment_amount: i64, commission_amount: i64) -> storage::Commission {
+        storage::Commission {
+            id: 1,
+            commission_id: "com_123".to_string(),
+            merchant_id: "merchant_123".to_string(),
+            parent_merchant_id: None,
+            payment_id: "pay_123".to_string(),
+            pricing_plan: "standard".to_string(),
+            currency: storage::enums::Currency::USD,
+            payment_amount,
+            commission_amount,
+            created_at: date_time::now(),
+        }
+    }
+
+    #[test]
+    fn test_add_commission_to_totals_rejects_overflowing_amounts() {
+        let mut totals = BTreeMap::new();
+        add_commission_to_totals(&mut totals, &get_commission(1000, 30)).unwrap();
+        add_commission_to_totals(&mut totals, &get_commission(500, 15)).unwrap();
+        let total = totals.get("USD").unwrap();
+        assert_eq!(
+            (
+                total.payment_count,
+                total.payment_amount,
+                total.commission_amount
+            ),
+            (2, 1500, 45)
+        );
+
+        assert!(add_commission_to_totals(&mut totals, &get_commission(i64::MAX, 0)).is_err());
+    }
+}
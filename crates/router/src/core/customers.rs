@@ -1,12 +1,19 @@
-use common_utils::crypto::{Encryptable, GcmAes256};
-use error_stack::ResultExt;
+pub mod import;
+
+use std::collections::{hash_map::Entry, HashMap};
+
+use common_utils::{
+    crypto::{Encryptable, GcmAes256},
+    errors::CustomResult,
+};
+use error_stack::{report, ResultExt};
 use masking::ExposeInterface;
-use router_env::{instrument, tracing};
+use router_env::{instrument, logger, tracing};
 
 use crate::{
     consts,
     core::{
-        errors::{self, RouterResponse, StorageErrorExt},
+        errors::{self, RouterResponse, RouterResult, StorageErrorExt},
         payment_methods::cards,
     },
     db::StorageInterface,
@@ -17,115 +24,111 @@ use crate::{
         api::customers,
         domain::{
             self,
+            behaviour::ReverseConversion,
             types::{self, AsyncLift, TypeEncryption},
         },
         storage::{self, enums},
     },
-    utils::generate_id,
+    utils::{self, generate_id},
 };
 
 pub const REDACTED: &str = "Redacted";
 
-#[instrument(skip(db))]
-pub async fn create_customer(
-    db: &dyn StorageInterface,
-    merchant_account: domain::MerchantAccount,
-    key_store: domain::MerchantKeyStore,
-    mut customer_data: customers::CustomerRequest,
-) -> RouterResponse<customers::CustomerResponse> {
-    let customer_id = &customer_data.customer_id;
-    let merchant_id = &merchant_account.merchant_id;
-    customer_data.merchant_id = merchant_id.to_owned();
+/// The number of customers whose email hash is backfilled at a time
+const EMAIL_HASH_BACKFILL_BATCH_SIZE: i64 = 100;
 
-    let key = key_store.key.get_inner().peek();
-    if let Some(addr) = &customer_data.address {
-        let customer_address: api_models::payments::AddressDetails = addr.clone();
-
-        let address = async {
-            Ok(domain::Address {
-                city: customer_address.city,
-                country: customer_address.country,
-                line1: customer_address
-                    .line1
-                    .async_lift(|inner| types::encrypt_optional(inner, key))
-                    .await?,
-                line2: customer_address
-                    .line2
-                    .async_lift(|inner| types::encrypt_optional(inner, key))
-                    .await?,
-                line3: customer_address
-                    .line3
-                    .async_lift(|inner| types::encrypt_optional(inner, key))
-                    .await?,
-                zip: customer_address
-                    .zip
-                    .async_lift(|inner| types::encrypt_optional(inner, key))
-                    .await?,
-                state: customer_address
-                    .state
-                    .async_lift(|inner| types::encrypt_optional(inner, key))
-                    .await?,
-                first_name: customer_address
-                    .first_name
-                    .async_lift(|inner| types::encrypt_optional(inner, key))
-                    .await?,
-                last_name: customer_address
-                    .last_name
-                    .async_lift(|inner| types::encrypt_optional(inner, key))
-                    .await?,
-                phone_number: customer_data
-                    .phone
-                    .clone()
-                    .async_lift(|inner| types::encrypt_optional(inner, key))
-                    .await?,
-                country_code: customer_data.phone_country_code.clone(),
-                customer_id: customer_id.to_string(),
-                merchant_id: merchant_id.to_string(),
-                id: None,
-                address_id: generate_id(consts::ID_LENGTH, "add"),
-                created_at: common_utils::date_time::now(),
-                modified_at: common_utils::date_time::now(),
-            })
-        }
-        .await
-        .change_context(errors::ApiErrorResponse::InternalServerError)
-        .attach_printable("Failed while encrypting address")?;
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct CustomerEmailHashBackfillTrackingData {
+    /// The ID of the last customer processed by the backfill
+    pub last_id: i32,
+}
 
-        db.insert_address(address, &key_store)
-            .await
-            .change_context(errors::ApiErrorResponse::InternalServerError)
-            .attach_printable("Failed while inserting new address")?;
-    }
+/// The customer to be created for a customer request, along with its address, encrypted with the
+/// key of the merchant
+pub struct NewCustomer {
+    pub address: Option<domain::Address>,
+    pub customer: domain::Customer,
+}
+
+/// Constructs the customer and the address to be created for a customer request.
+pub async fn get_new_customer(
+    customer_data: &customers::CustomerRequest,
+    merchant_id: &str,
+    key_store: &domain::MerchantKeyStore,
+) -> RouterResult<NewCustomer> {
+    let customer_id = &customer_data.customer_id;
+    let key = key_store.key.get_inner().peek();
+    let address = match &customer_data.address {
+        Some(addr) => Some(
+            get_customer_address(addr.clone(), customer_data, customer_id, merchant_id, key)
+                .await
+                .change_context(errors::ApiErrorResponse::InternalServerError)
+                .attach_printable("Failed while encrypting address")?,
+        ),
+        None => None,
+    };
 
-    let new_customer = async {
+    let customer = async {
         Ok(domain::Customer {
             customer_id: customer_id.to_string(),
             merchant_id: merchant_id.to_string(),
             name: customer_data
                 .name
+                .clone()
                 .async_lift(|inner| types::encrypt_optional(inner, key))
                 .await?,
             email: customer_data
                 .email
+                .clone()
                 .async_lift(|inner| types::encrypt_optional(inner.map(|inner| inner.expose()), key))
                 .await?,
             phone: customer_data
                 .phone
+                .clone()
                 .async_lift(|inner| types::encrypt_optional(inner, key))
                 .await?,
-            description: customer_data.description,
-            phone_country_code: customer_data.phone_country_code,
-            metadata: customer_data.metadata,
+            description: customer_data.description.clone(),
+            phone_country_code: customer_data.phone_country_code.clone(),
+            metadata: customer_data.metadata.clone(),
             id: None,
             connector_customer: None,
             created_at: common_utils::date_time::now(),
             modified_at: common_utils::date_time::now(),
+            address_id: address.as_ref().map(|address| address.address_id.clone()),
+            default_shipping_address_id: None,
+            default_billing_address_id: None,
+            default_payment_method_id: None,
         })
     }
     .await
     .change_context(errors::ApiErrorResponse::InternalServerError)
     .attach_printable("Failed while encrypting Customer")?;
 
+    Ok(NewCustomer { address, customer })
+}
+
+#[instrument(skip(db))]
+pub async fn create_customer(
+    db: &dyn StorageInterface,
+    merchant_account: domain::MerchantAccount,
+    key_store: domain::MerchantKeyStore,
+    mut customer_data: customers::CustomerRequest,
+) -> RouterResponse<customers::CustomerResponse> {
+    let customer_id = &customer_data.customer_id;
+    let merchant_id = &merchant_account.merchant_id;
+    customer_data.merchant_id = merchant_id.to_owned();
+
+    let NewCustomer {
+        address,
+        customer: new_customer,
+    } = get_new_customer(&customer_data, merchant_id, &key_store).await?;
+    if let Some(address) = address {
+        db.insert_address(address, &key_store)
+            .await
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Failed while inserting new address")?;
+    }
+
     let customer = match db.insert_customer(new_customer, &key_store).await {
         Ok(customer) => customer,
         Err(error) => {
@@ -210,7 +213,7 @@ pub async fn delete_customer(
                         state,
                         &req.customer_id,
                         &merchant_account.merchant_id,
-                        &pm.payment_method_id,
+                        cards::get_locker_id(&pm),
                     )
                     .await?;
                 }
@@ -287,6 +290,7 @@ pub async fn delete_customer(
         phone_country_code: Some(REDACTED.to_string()),
         metadata: None,
         connector_customer: None,
+        address_id: None,
     };
     db.update_customer_by_customer_id_merchant_id(
         req.customer_id.clone(),
@@ -315,73 +319,100 @@ pub async fn update_customer(
     key_store: domain::MerchantKeyStore,
 ) -> RouterResponse<customers::CustomerResponse> {
     //Add this in update call if customer can be updated anywhere else
-    db.find_customer_by_customer_id_merchant_id(
-        &update_customer.customer_id,
-        &merchant_account.merchant_id,
-        &key_store,
-    )
-    .await
-    .to_not_found_response(errors::ApiErrorResponse::CustomerNotFound)?;
-
-    let key = key_store.key.get_inner().peek();
-
-    if let Some(addr) = &update_customer.address {
-        let customer_address: api_models::payments::AddressDetails = addr.clone();
-        let update_address = async {
-            Ok(storage::AddressUpdate::Update {
-                city: customer_address.city,
-                country: customer_address.country,
-                line1: customer_address
-                    .line1
-                    .async_lift(|inner| types::encrypt_optional(inner, key))
-                    .await?,
-                line2: customer_address
-                    .line2
-                    .async_lift(|inner| types::encrypt_optional(inner, key))
-                    .await?,
-                line3: customer_address
-                    .line3
-                    .async_lift(|inner| types::encrypt_optional(inner, key))
-                    .await?,
-                zip: customer_address
-                    .zip
-                    .async_lift(|inner| types::encrypt_optional(inner, key))
-                    .await?,
-                state: customer_address
-                    .state
-                    .async_lift(|inner| types::encrypt_optional(inner, key))
-                    .await?,
-                first_name: customer_address
-                    .first_name
-                    .async_lift(|inner| types::encrypt_optional(inner, key))
-                    .await?,
-                last_name: customer_address
-                    .last_name
-                    .async_lift(|inner| types::encrypt_optional(inner, key))
-                    .await?,
-                phone_number: update_customer
-                    .phone
-                    .clone()
-                    .async_lift(|inner| types::encrypt_optional(inner, key))
-                    .await?,
-                country_code: update_customer.phone_country_code.clone(),
-            })
-        }
-        .await
-        .change_context(errors::ApiErrorResponse::InternalServerError)
-        .attach_printable("Failed while encrypting Address while Update")?;
-        db.update_address_by_merchant_id_customer_id(
+    let customer = db
+        .find_customer_by_customer_id_merchant_id(
             &update_customer.customer_id,
             &merchant_account.merchant_id,
-            update_address,
             &key_store,
         )
         .await
-        .change_context(errors::ApiErrorResponse::InternalServerError)
-        .attach_printable(format!(
-            "Failed while updating address: merchant_id: {}, customer_id: {}",
-            merchant_account.merchant_id, update_customer.customer_id
-        ))?;
+        .to_not_found_response(errors::ApiErrorResponse::CustomerNotFound)?;
+
+    let key = key_store.key.get_inner().peek();
+
+    // Customers created without an address have no address on file, which is linked here
+    let address_id = match (&update_customer.address, customer.address_id) {
+        (Some(addr), None) => {
+            let address = get_customer_address(
+                addr.clone(),
+                &update_customer,
+                &update_customer.customer_id,
+                &merchant_account.merchant_id,
+                key,
+            )
+            .await
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Failed while encrypting address")?;
+
+            let address = db
+                .insert_address(address, &key_store)
+                .await
+                .change_context(errors::ApiErrorResponse::InternalServerError)
+                .attach_printable("Failed while inserting new address")?;
+
+            Some(address.address_id)
+        }
+        (Some(addr), Some(_)) => {
+            let customer_address: api_models::payments::AddressDetails = addr.clone();
+            let update_address = async {
+                Ok(storage::AddressUpdate::Update {
+                    city: customer_address.city,
+                    country: customer_address.country,
+                    line1: customer_address
+                        .line1
+                        .async_lift(|inner| types::encrypt_optional(inner, key))
+                        .await?,
+                    line2: customer_address
+                        .line2
+                        .async_lift(|inner| types::encrypt_optional(inner, key))
+                        .await?,
+                    line3: customer_address
+                        .line3
+                        .async_lift(|inner| types::encrypt_optional(inner, key))
+                        .await?,
+                    zip: customer_address
+                        .zip
+                        .async_lift(|inner| types::encrypt_optional(inner, key))
+                        .await?,
+                    state: customer_address
+                        .state
+                        .async_lift(|inner| types::encrypt_optional(inner, key))
+                        .await?,
+                    first_name: customer_address
+                        .first_name
+                        .async_lift(|inner| types::encrypt_optional(inner, key))
+                        .await?,
+                    last_name: customer_address
+                        .last_name
+                        .async_lift(|inner| types::encrypt_optional(inner, key))
+                        .await?,
+                    phone_number: update_customer
+                        .phone
+                        .clone()
+                        .async_lift(|inner| types::encrypt_optional(inner, key))
+                        .await?,
+                    country_code: update_customer.phone_country_code.clone(),
+                })
+            }
+            .await
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Failed while encrypting Address while Update")?;
+            db.update_address_by_merchant_id_customer_id(
+                &update_customer.customer_id,
+                &merchant_account.merchant_id,
+                update_address,
+                &key_store,
+            )
+            .await
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable(format!(
+                "Failed while updating address: merchant_id: {}, customer_id: {}",
+                merchant_account.merchant_id, update_customer.customer_id
+            ))?;
+
+            None
+        }
+        (None, _) => None,
     };
 
     let response = db
@@ -408,6 +439,7 @@ pub async fn update_customer(
                     metadata: update_customer.metadata,
                     description: update_customer.description,
                     connector_customer: None,
+                    address_id,
                 })
             }
             .await
@@ -424,3 +456,531 @@ pub async fn update_customer(
         customer_update_response,
     ))
 }
+
+async fn get_customer_address(
+    customer_address: api_models::payments::AddressDetails,
+    customer_data: &customers::CustomerRequest,
+    customer_id: &str,
+    merchant_id: &str,
+    key: &[u8],
+) -> CustomResult<domain::Address, common_utils::errors::CryptoError> {
+    Ok(domain::Address {
+        city: customer_address.city,
+        country: customer_address.country,
+        line1: customer_address
+            .line1
+            .async_lift(|inner| types::encrypt_optional(inner, key))
+            .await?,
+        line2: customer_address
+            .line2
+            .async_lift(|inner| types::encrypt_optional(inner, key))
+            .await?,
+        line3: customer_address
+            .line3
+            .async_lift(|inner| types::encrypt_optional(inner, key))
+            .await?,
+        zip: customer_address
+            .zip
+            .async_lift(|inner| types::encrypt_optional(inner, key))
+            .await?,
+        state: customer_address
+            .state
+            .async_lift(|inner| types::encrypt_optional(inner, key))
+            .await?,
+        first_name: customer_address
+            .first_name
+            .async_lift(|inner| types::encrypt_optional(inner, key))
+            .await?,
+        last_name: customer_address
+            .last_name
+            .async_lift(|inner| types::encrypt_optional(inner, key))
+            .await?,
+        phone_number: customer_data
+            .phone
+            .clone()
+            .async_lift(|inner| types::encrypt_optional(inner, key))
+            .await?,
+        country_code: customer_data.phone_country_code.clone(),
+        customer_id: customer_id.to_string(),
+        merchant_id: merchant_id.to_string(),
+        id: None,
+        address_id: generate_id(consts::ID_LENGTH, "add"),
+        created_at: common_utils::date_time::now(),
+        modified_at: common_utils::date_time::now(),
+        is_address_book_entry: false,
+    })
+}
+
+async fn get_address_book_entry(
+    address: &customers::CustomerAddressRequest,
+    customer_id: &str,
+    merchant_id: &str,
+    key: &[u8],
+) -> CustomResult<domain::Address, common_utils::errors::CryptoError> {
+    let address_details = address.address.clone();
+    let phone = address.phone.clone().unwrap_or_default();
+    let now = common_utils::date_time::now();
+    Ok(domain::Address {
+        city: address_details.city,
+        country: address_details.country,
+        line1: address_details
+            .line1
+            .async_lift(|inner| types::encrypt_optional(inner, key))
+            .await?,
+        line2: address_details
+            .line2
+            .async_lift(|inner| types::encrypt_optional(inner, key))
+            .await?,
+        line3: address_details
+            .line3
+            .async_lift(|inner| types::encrypt_optional(inner, key))
+            .await?,
+        zip: address_details
+            .zip
+            .async_lift(|inner| types::encrypt_optional(inner, key))
+            .await?,
+        state: address_details
+            .state
+            .async_lift(|inner| types::encrypt_optional(inner, key))
+            .await?,
+        first_name: address_details
+            .first_name
+            .async_lift(|inner| types::encrypt_optional(inner, key))
+            .await?,
+        last_name: address_details
+            .last_name
+            .async_lift(|inner| types::encrypt_optional(inner, key))
+            .await?,
+        phone_number: phone
+            .number
+            .async_lift(|inner| types::encrypt_optional(inner, key))
+            .await?,
+        country_code: phone.country_code,
+        customer_id: customer_id.to_string(),
+        merchant_id: merchant_id.to_string(),
+        id: None,
+        address_id: generate_id(consts::ID_LENGTH, "add"),
+        created_at: now,
+        modified_at: now,
+        is_address_book_entry: true,
+    })
+}
+
+fn get_customer_address_response(
+    address: &domain::Address,
+    customer: &domain::Customer,
+) -> customers::CustomerAddressResponse {
+    let api_models::payments::Address {
+        address: details,
+        phone,
+    } = address.into();
+    customers::CustomerAddressResponse {
+        address_id: address.address_id.clone(),
+        customer_id: address.customer_id.clone(),
+        address: details.unwrap_or_default(),
+        phone: phone.unwrap_or_default(),
+        is_default_shipping: customer.default_shipping_address_id.as_ref()
+            == Some(&address.address_id),
+        is_default_billing: customer.default_billing_address_id.as_ref()
+            == Some(&address.address_id),
+        created_at: address.created_at,
+    }
+}
+
+/// Find an address in the address book of the customer. Addresses of other customers and the
+/// addresses of payments are reported as not found.
+pub async fn find_address_book_entry(
+    db: &dyn StorageInterface,
+    merchant_id: &str,
+    customer_id: &str,
+    address_id: &str,
+    key_store: &domain::MerchantKeyStore,
+) -> RouterResult<domain::Address> {
+    let address = db
+        .find_address(address_id, key_store)
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::AddressNotFound)?;
+
+    utils::when(
+        !address.is_address_book_entry
+            || address.merchant_id != merchant_id
+            || address.customer_id != customer_id,
+        || Err(report!(errors::ApiErrorResponse::AddressNotFound)),
+    )?;
+
+    Ok(address)
+}
+
+#[instrument(skip(db))]
+pub async fn create_customer_address(
+    db: &dyn StorageInterface,
+    merchant_account: domain::MerchantAccount,
+    key_store: domain::MerchantKeyStore,
+    customer_id: String,
+    req: customers::CustomerAddressRequest,
+) -> RouterResponse<customers::CustomerAddressResponse> {
+    let customer = db
+        .find_customer_by_customer_id_merchant_id(
+            &customer_id,
+            &merchant_account.merchant_id,
+            &key_store,
+        )
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::CustomerNotFound)?;
+
+    let key = key_store.key.get_inner().peek();
+    let address = get_address_book_entry(&req, &customer_id, &merchant_account.merchant_id, key)
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed while encrypting address")?;
+
+    let address = db
+        .insert_address(address, &key_store)
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed while inserting new address")?;
+
+    Ok(services::ApplicationResponse::Json(
+        get_customer_address_response(&address, &customer),
+    ))
+}
+
+#[instrument(skip(db))]
+pub async fn list_customer_addresses(
+    db: &dyn StorageInterface,
+    merchant_account: domain::MerchantAccount,
+    key_store: domain::MerchantKeyStore,
+    req: customers::CustomerId,
+) -> RouterResponse<Vec<customers::CustomerAddressResponse>> {
+    let customer = db
+        .find_customer_by_customer_id_merchant_id(
+            &req.customer_id,
+            &merchant_account.merchant_id,
+            &key_store,
+        )
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::CustomerNotFound)?;
+
+    let addresses = db
+        .find_address_book_by_merchant_id_customer_id(
+            &merchant_account.merchant_id,
+            &req.customer_id,
+            &key_store,
+        )
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed while fetching the address book of the customer")?;
+
+    Ok(services::ApplicationResponse::Json(
+        addresses
+            .iter()
+            .map(|address| get_customer_address_response(address, &customer))
+            .collect(),
+    ))
+}
+
+#[instrument(skip(db))]
+pub async fn update_customer_address(
+    db: &dyn StorageInterface,
+    merchant_account: domain::MerchantAccount,
+    key_store: domain::MerchantKeyStore,
+    address_id: customers::CustomerAddressId,
+    req: customers::CustomerAddressRequest,
+) -> RouterResponse<customers::CustomerAddressResponse> {
+    let customer = db
+        .find_customer_by_customer_id_merchant_id(
+            &address_id.customer_id,
+            &merchant_account.merchant_id,
+            &key_store,
+        )
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::CustomerNotFound)?;
+
+    let address = find_address_book_entry(
+        db,
+        &merchant_account.merchant_id,
+        &address_id.customer_id,
+        &address_id.address_id,
+        &key_store,
+    )
+    .await?;
+
+    let key = key_store.key.get_inner().peek();
+    let updated_address = get_address_book_entry(
+        &req,
+        &address_id.customer_id,
+        &merchant_account.merchant_id,
+        key,
+    )
+    .await
+    .change_context(errors::ApiErrorResponse::InternalServerError)
+    .attach_printable("Failed while encrypting address")?;
+
+    let address = db
+        .update_address(
+            address.address_id,
+            storage::AddressUpdate::Update {
+                city: updated_address.city,
+                country: updated_address.country,
+                line1: updated_address.line1,
+                line2: updated_address.line2,
+                line3: updated_address.line3,
+                state: updated_address.state,
+                zip: updated_address.zip,
+                first_name: updated_address.first_name,
+                last_name: updated_address.last_name,
+                phone_number: updated_address.phone_number,
+                country_code: updated_address.country_code,
+            },
+            &key_store,
+        )
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::AddressNotFound)?;
+
+    Ok(services::ApplicationResponse::Json(
+        get_customer_address_response(&address, &customer),
+    ))
+}
+
+#[instrument(skip(db))]
+pub async fn delete_customer_address(
+    db: &dyn StorageInterface,
+    merchant_account: domain::MerchantAccount,
+    key_store: domain::MerchantKeyStore,
+    address_id: customers::CustomerAddressId,
+) -> RouterResponse<customers::CustomerAddressDeleteResponse> {
+    let customer = db
+        .find_customer_by_customer_id_merchant_id(
+            &address_id.customer_id,
+            &merchant_account.merchant_id,
+            &key_store,
+        )
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::CustomerNotFound)?;
+
+    let address = find_address_book_entry(
+        db,
+        &merchant_account.merchant_id,
+        &address_id.customer_id,
+        &address_id.address_id,
+        &key_store,
+    )
+    .await?;
+
+    // Payments only hold copies of the addresses in the address book, so deleting an address
+    // from the address book only affects the defaults of the customer
+    let is_default_shipping =
+        customer.default_shipping_address_id.as_ref() == Some(&address.address_id);
+    let is_default_billing =
+        customer.default_billing_address_id.as_ref() == Some(&address.address_id);
+    if is_default_shipping || is_default_billing {
+        db.update_customer_by_customer_id_merchant_id(
+            customer.customer_id.clone(),
+            merchant_account.merchant_id.clone(),
+            storage::CustomerUpdate::DefaultAddressUpdate {
+                default_shipping_address_id: is_default_shipping.then_some(None),
+                default_billing_address_id: is_default_billing.then_some(None),
+            },
+            &key_store,
+        )
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::CustomerNotFound)?;
+    }
+
+    let deleted = db
+        .delete_address_by_address_id(&address.address_id)
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::AddressNotFound)?;
+
+    Ok(services::ApplicationResponse::Json(
+        customers::CustomerAddressDeleteResponse {
+            address_id: address.address_id,
+            customer_id: customer.customer_id,
+            deleted,
+        },
+    ))
+}
+
+#[instrument(skip(db))]
+pub async fn set_default_customer_address(
+    db: &dyn StorageInterface,
+    merchant_account: domain::MerchantAccount,
+    key_store: domain::MerchantKeyStore,
+    address_id: customers::CustomerAddressId,
+    req: customers::CustomerDefaultAddressRequest,
+) -> RouterResponse<customers::CustomerAddressResponse> {
+    db.find_customer_by_customer_id_merchant_id(
+        &address_id.customer_id,
+        &merchant_account.merchant_id,
+        &key_store,
+    )
+    .await
+    .to_not_found_response(errors::ApiErrorResponse::CustomerNotFound)?;
+
+    let address = find_address_book_entry(
+        db,
+        &merchant_account.merchant_id,
+        &address_id.customer_id,
+        &address_id.address_id,
+        &key_store,
+    )
+    .await?;
+
+    let customer_update = match req.address_type {
+        customers::CustomerAddressType::Shipping => storage::CustomerUpdate::DefaultAddressUpdate {
+            default_shipping_address_id: Some(Some(address.address_id.clone())),
+            default_billing_address_id: None,
+        },
+        customers::CustomerAddressType::Billing => storage::CustomerUpdate::DefaultAddressUpdate {
+            default_shipping_address_id: None,
+            default_billing_address_id: Some(Some(address.address_id.clone())),
+        },
+    };
+    let customer = db
+        .update_customer_by_customer_id_merchant_id(
+            address_id.customer_id,
+            merchant_account.merchant_id,
+            customer_update,
+            &key_store,
+        )
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::CustomerNotFound)?;
+
+    Ok(services::ApplicationResponse::Json(
+        get_customer_address_response(&address, &customer),
+    ))
+}
+
+#[instrument(skip(db))]
+pub async fn set_default_customer_payment_method(
+    db: &dyn StorageInterface,
+    merchant_account: domain::MerchantAccount,
+    key_store: domain::MerchantKeyStore,
+    payment_method_id: customers::CustomerPaymentMethodId,
+) -> RouterResponse<customers::CustomerDefaultPaymentMethodResponse> {
+    db.find_customer_by_customer_id_merchant_id(
+        &payment_method_id.customer_id,
+        &merchant_account.merchant_id,
+        &key_store,
+    )
+    .await
+    .to_not_found_response(errors::ApiErrorResponse::CustomerNotFound)?;
+
+    let payment_method = db
+        .find_payment_method(&payment_method_id.payment_method_id)
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::PaymentMethodNotFound)?;
+
+    // Payment methods of other customers are reported as not found
+    utils::when(
+        payment_method.merchant_id != merchant_account.merchant_id
+            || payment_method.customer_id != payment_method_id.customer_id,
+        || Err(report!(errors::ApiErrorResponse::PaymentMethodNotFound)),
+    )?;
+
+    let customer = db
+        .update_customer_by_customer_id_merchant_id(
+            payment_method_id.customer_id,
+            merchant_account.merchant_id,
+            storage::CustomerUpdate::DefaultPaymentMethodUpdate {
+                default_payment_method_id: Some(payment_method.payment_method_id.clone()),
+            },
+            &key_store,
+        )
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::CustomerNotFound)?;
+
+    Ok(services::ApplicationResponse::Json(
+        customers::CustomerDefaultPaymentMethodResponse {
+            customer_id: customer.customer_id,
+            default_payment_method_id: payment_method.payment_method_id,
+            payment_method: payment_method.payment_method,
+        },
+    ))
+}
+
+/// Stores the keyed hash of the email of a customer created before the hash of its email was
+/// keyed.
+async fn backfill_email_hash(
+    db: &dyn StorageInterface,
+    customer: storage::Customer,
+    key_store: &domain::MerchantKeyStore,
+) -> RouterResult<()> {
+    let customer_id = customer.customer_id.clone();
+    let merchant_id = customer.merchant_id.clone();
+    let customer = customer
+        .convert(key_store.key.get_inner())
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed while decrypting the customer")?;
+    let Some(email) = customer.email else {
+        return Ok(());
+    };
+    let email_hash =
+        domain::get_email_hash(key_store.key.get_inner().peek(), email.get_inner().peek())
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Failed while hashing the email of the customer")?;
+
+    db.update_customer_by_customer_id_merchant_id(
+        customer_id,
+        merchant_id,
+        storage::CustomerUpdate::EmailHashUpdate { email_hash },
+        key_store,
+    )
+    .await
+    .to_not_found_response(errors::ApiErrorResponse::CustomerNotFound)?;
+    Ok(())
+}
+
+/// Backfills the email hash of the next batch of customers after the customer with the given ID.
+/// Returns the ID of the last customer of the batch, or `None` once every customer has been
+/// processed. A customer whose email hash could not be backfilled is skipped, so that it does not
+/// hold up the backfill of the other customers.
+pub async fn backfill_customer_email_hash(
+    state: &AppState,
+    last_id: i32,
+) -> RouterResult<Option<i32>> {
+    let db = &*state.store;
+    let customers = db
+        .find_customers_with_email_after_id(last_id, EMAIL_HASH_BACKFILL_BATCH_SIZE)
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to fetch the customers to backfill")?;
+    let next_last_id = customers.last().map(|customer| customer.id);
+
+    // The customers of a batch mostly belong to a few merchants, so their key stores are fetched
+    // once per batch
+    let mut key_stores = HashMap::new();
+    for customer in customers {
+        let customer_id = customer.customer_id.clone();
+        let key_store = match key_stores.entry(customer.merchant_id.clone()) {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => {
+                match db
+                    .get_merchant_key_store_by_merchant_id(
+                        entry.key(),
+                        &db.get_master_key().to_vec().into(),
+                    )
+                    .await
+                {
+                    Ok(key_store) => entry.insert(key_store),
+                    Err(error) => {
+                        logger::error!(
+                            customer_id = %customer_id,
+                            email_hash_backfill_error=?error
+                        );
+                        continue;
+                    }
+                }
+            }
+        };
+        if let Err(error) = backfill_email_hash(db, customer, key_store).await {
+            logger::error!(
+                customer_id = %customer_id,
+                email_hash_backfill_error=?error
+            );
+        }
+    }
+
+    Ok(next_last_id)
+}
@@ -0,0 +1,620 @@
+//! Batch import of customers.
+//!
+//! The customers of an import are stored encrypted along with the import and created in the
+//! background by the process tracker, so that merchants migrating from another processor can
+//! submit thousands of customers in a single request. The customers are created in batches, each
+//! batch in a single transaction along with the progress of the import, and an interrupted or
+//! failed import is resumed after the last batch which was created.
+
+use std::collections::{HashMap, HashSet};
+
+use common_utils::{crypto, date_time, ext_traits::AsyncExt};
+use error_stack::{report, IntoReport, ResultExt};
+use masking::{ExposeInterface, PeekInterface, Secret};
+use router_env::{instrument, logger, tracing};
+
+use crate::{
+    consts,
+    core::{
+        customers::{get_new_customer, NewCustomer},
+        errors::{self, RouterResponse, RouterResult, StorageErrorExt},
+        payment_methods::expiry,
+    },
+    db::StorageInterface,
+    routes::{metrics, AppState},
+    scheduler::{metrics as scheduler_metrics, utils as pt_utils},
+    services::ApplicationResponse,
+    types::{
+        api::customers,
+        domain::{self, behaviour::Conversion, types as domain_types},
+        storage::{self, enums, ProcessTrackerExt},
+    },
+    utils::{self, Encode, StringExt, ValueExt},
+};
+
+const CUSTOMER_IMPORT_RUNNER: &str = "CUSTOMER_IMPORT_WORKFLOW";
+const CUSTOMER_IMPORT_TASK: &str = "CUSTOMER_IMPORT";
+
+fn validate_customer_import_request(req: &customers::CustomerImportRequest) -> RouterResult<()> {
+    if req.customers.is_empty() {
+        Err(errors::ApiErrorResponse::InvalidRequestData {
+            message: "The import must contain at least one customer".to_string(),
+        })?
+    }
+    if req.customers.len() > consts::MAX_CUSTOMER_IMPORT_RECORDS {
+        Err(errors::ApiErrorResponse::InvalidRequestData {
+            message: format!(
+                "The import can contain at most {} customers",
+                consts::MAX_CUSTOMER_IMPORT_RECORDS
+            ),
+        })?
+    }
+    Ok(())
+}
+
+fn validate_import_payment_method(
+    payment_method: &customers::CustomerImportPaymentMethod,
+) -> RouterResult<()> {
+    // Saved cards are read from the locker when they are listed, so a card can only be
+    // imported if it has been stored in the locker already
+    if payment_method.payment_method == enums::PaymentMethod::Card
+        && payment_method.locker_id.is_none()
+    {
+        Err(errors::ApiErrorResponse::MissingRequiredField {
+            field_name: "payment_methods.locker_id",
+        })?
+    }
+    Ok(())
+}
+
+fn get_customer_import_response(
+    customer_import_job: storage::CustomerImportJob,
+) -> RouterResult<customers::CustomerImportResponse> {
+    let errors = customer_import_job
+        .errors
+        .map(|errors| errors.parse_value("CustomerImportErrors"))
+        .transpose()
+        .change_context(errors::ApiErrorResponse::InternalServerError)?
+        .unwrap_or_default();
+
+    Ok(customers::CustomerImportResponse {
+        job_id: customer_import_job.job_id,
+        status: customer_import_job.status,
+        total_count: customer_import_job.total_count,
+        processed_count: customer_import_job.processed_count,
+        succeeded_count: customer_import_job.succeeded_count,
+        failed_count: customer_import_job.failed_count,
+        errors,
+        created_at: customer_import_job.created_at,
+        completed_at: customer_import_job.completed_at,
+    })
+}
+
+async fn add_customer_import_task(
+    db: &dyn StorageInterface,
+    customer_import_job: &storage::CustomerImportJob,
+) -> RouterResult<()> {
+    let tracking_data = storage::CustomerImportWorkflow {
+        job_id: customer_import_job.job_id.clone(),
+        merchant_id: customer_import_job.merchant_id.clone(),
+    };
+    let process_tracker_entry = <storage::ProcessTracker>::make_process_tracker_new(
+        pt_utils::get_process_tracker_id(
+            CUSTOMER_IMPORT_RUNNER,
+            CUSTOMER_IMPORT_TASK,
+            &customer_import_job.job_id,
+            &customer_import_job.merchant_id,
+        ),
+        CUSTOMER_IMPORT_TASK,
+        CUSTOMER_IMPORT_RUNNER,
+        tracking_data,
+        date_time::now(),
+    )
+    .change_context(errors::ApiErrorResponse::InternalServerError)?;
+
+    db.insert_process(process_tracker_entry)
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to insert the customer import task to process tracker")?;
+    Ok(())
+}
+
+#[instrument(skip_all)]
+pub async fn create_customer_import(
+    db: &dyn StorageInterface,
+    merchant_account: domain::MerchantAccount,
+    key_store: domain::MerchantKeyStore,
+    req: customers::CustomerImportRequest,
+) -> RouterResponse<customers::CustomerImportResponse> {
+    validate_customer_import_request(&req)?;
+
+    let total_count = i32::try_from(req.customers.len())
+        .into_report()
+        .change_context(errors::ApiErrorResponse::InternalServerError)?;
+    let records =
+        Encode::<Vec<customers::CustomerImportRecord>>::encode_to_string_of_json(&req.customers)
+            .change_context(errors::ApiErrorResponse::InternalServerError)?;
+    let records = domain_types::encrypt(Secret::new(records), key_store.key.get_inner().peek())
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to encrypt the customers of the import")?;
+
+    let now = date_time::now();
+    let customer_import_job = db
+        .insert_customer_import_job(storage::CustomerImportJobNew {
+            job_id: utils::generate_id(consts::ID_LENGTH, "cimp"),
+            merchant_id: merchant_account.merchant_id,
+            status: enums::CustomerImportStatus::Pending,
+            records: records.into(),
+            total_count,
+            created_at: now,
+            modified_at: now,
+        })
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to insert the customer import")?;
+
+    add_customer_import_task(db, &customer_import_job).await?;
+
+    Ok(ApplicationResponse::Json(get_customer_import_response(
+        customer_import_job,
+    )?))
+}
+
+#[instrument(skip(db))]
+pub async fn retrieve_customer_import(
+    db: &dyn StorageInterface,
+    merchant_account: domain::MerchantAccount,
+    req: customers::CustomerImportJobId,
+) -> RouterResponse<customers::CustomerImportResponse> {
+    let customer_import_job = db
+        .find_customer_import_job_by_merchant_id_job_id(&merchant_account.merchant_id, &req.job_id)
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::CustomerImportJobNotFound {
+            job_id: req.job_id,
+        })?;
+
+    Ok(ApplicationResponse::Json(get_customer_import_response(
+        customer_import_job,
+    )?))
+}
+
+/// Resumes a failed import from its last saved position, scheduling its task again.
+#[instrument(skip(db))]
+pub async fn resume_customer_import(
+    db: &dyn StorageInterface,
+    merchant_account: domain::MerchantAccount,
+    req: customers::CustomerImportJobId,
+) -> RouterResponse<customers::CustomerImportResponse> {
+    let customer_import_job = db
+        .find_customer_import_job_by_merchant_id_job_id(&merchant_account.merchant_id, &req.job_id)
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::CustomerImportJobNotFound {
+            job_id: req.job_id.clone(),
+        })?;
+    if customer_import_job.status != enums::CustomerImportStatus::Failed {
+        Err(errors::ApiErrorResponse::PreconditionFailed {
+            message: format!(
+                "Only a failed customer import can be resumed, the customer import '{}' is {}",
+                req.job_id, customer_import_job.status
+            ),
+        })?
+    }
+
+    let process_tracker_id = pt_utils::get_process_tracker_id(
+        CUSTOMER_IMPORT_RUNNER,
+        CUSTOMER_IMPORT_TASK,
+        &customer_import_job.job_id,
+        &customer_import_job.merchant_id,
+    );
+    match db
+        .find_process_by_id(&process_tracker_id)
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)?
+    {
+        Some(process) => {
+            let now = date_time::now();
+            db.update_process_tracker(
+                process,
+                storage::ProcessTrackerUpdate::Update {
+                    name: None,
+                    retry_count: Some(0),
+                    schedule_time: Some(now),
+                    tracking_data: None,
+                    business_status: Some(String::from("Pending")),
+                    status: Some(enums::ProcessTrackerStatus::New),
+                    updated_at: Some(now),
+                },
+            )
+            .await
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Failed to reschedule the customer import task")?;
+        }
+        None => add_customer_import_task(db, &customer_import_job).await?,
+    }
+
+    let customer_import_job = db
+        .update_customer_import_job_by_merchant_id_job_id(
+            &customer_import_job.merchant_id,
+            &customer_import_job.job_id,
+            storage::CustomerImportJobUpdate::StatusUpdate {
+                status: enums::CustomerImportStatus::Pending,
+            },
+        )
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to resume the customer import")?;
+
+    Ok(ApplicationResponse::Json(get_customer_import_response(
+        customer_import_job,
+    )?))
+}
+
+/// Adds a customer of an import to the batch of customers to be created, along with its connector
+/// customers and its saved payment methods.
+async fn add_customer_to_batch(
+    state: &AppState,
+    merchant_account: &domain::MerchantAccount,
+    key_store: &domain::MerchantKeyStore,
+    record: customers::CustomerImportRecord,
+    batch: &mut storage::CustomerImportBatch,
+    batch_customer_ids: &mut HashSet<String>,
+) -> RouterResult<()> {
+    let db = &*state.store;
+    let merchant_id = &merchant_account.merchant_id;
+    let customer_id = record.customer.customer_id.clone();
+    let payment_methods = record.payment_methods.unwrap_or_default();
+    for payment_method in &payment_methods {
+        validate_import_payment_method(payment_method)?;
+    }
+
+    // Existing customers are left untouched, so that importing the same customers again does not
+    // duplicate their payment methods
+    if batch_customer_ids.contains(&customer_id)
+        || db
+            .find_customer_optional_by_customer_id_merchant_id(&customer_id, merchant_id, key_store)
+            .await
+            .change_context(errors::ApiErrorResponse::InternalServerError)?
+            .is_some()
+    {
+        Err(report!(errors::ApiErrorResponse::GenericDuplicateError {
+            message: format!("A customer with the customer_id '{customer_id}' already exists"),
+        }))?
+    }
+
+    let NewCustomer {
+        address,
+        mut customer,
+    } = get_new_customer(&record.customer, merchant_id, key_store).await?;
+    customer.connector_customer = record
+        .connector_customer
+        .filter(|connector_customer| !connector_customer.is_empty())
+        .map(|connector_customer| {
+            Encode::<HashMap<String, String>>::encode_to_value(&connector_customer)
+        })
+        .transpose()
+        .change_context(errors::ApiErrorResponse::InternalServerError)?;
+    let address = address
+        .async_map(|address| address.construct_new())
+        .await
+        .transpose()
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed while encrypting address")?;
+    let email_hash = customer
+        .email
+        .as_ref()
+        .map(|email| {
+            domain::get_email_hash(key_store.key.get_inner().peek(), email.get_inner().peek())
+        })
+        .transpose()
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed while hashing the email of the customer")?;
+    let mut customer = customer
+        .construct_new()
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed while encrypting Customer")?;
+    customer.email_hash = email_hash;
+
+    let mut new_payment_methods = Vec::with_capacity(payment_methods.len());
+    let mut processes = Vec::new();
+    for payment_method in payment_methods {
+        // The tokens of the payment method at the connectors are stored in the metadata of the
+        // payment method, as is done for the payment methods tokenized during payments
+        let metadata = payment_method
+            .connector_payment_method_tokens
+            .map(|tokens| Encode::<HashMap<String, String>>::encode_to_value(&tokens))
+            .transpose()
+            .change_context(errors::ApiErrorResponse::InternalServerError)?
+            .map(Secret::new);
+        let card_expiry = match (
+            payment_method.card_exp_month.clone(),
+            payment_method.card_exp_year.clone(),
+        ) {
+            (Some(card_exp_month), Some(card_exp_year)) => Some(
+                expiry::encrypt_card_expiry(card_exp_month, card_exp_year, key_store)
+                    .await
+                    .change_context(errors::ApiErrorResponse::InternalServerError)
+                    .attach_printable("Failed to encrypt the expiry of an imported card")?,
+            ),
+            _ => None,
+        };
+        let (card_exp_month, card_exp_year) = card_expiry
+            .map(|card_expiry| (card_expiry.card_exp_month, card_expiry.card_exp_year))
+            .unzip();
+
+        let payment_method_id = utils::generate_id(consts::ID_LENGTH, "pm");
+        if payment_method.payment_method == enums::PaymentMethod::Card {
+            processes.extend(expiry::get_expiry_task(
+                state,
+                &payment_method_id,
+                merchant_id,
+                None,
+                payment_method.card_exp_month.as_ref(),
+                payment_method.card_exp_year.as_ref(),
+            )?);
+        }
+        new_payment_methods.push(storage::PaymentMethodNew {
+            customer_id: customer_id.clone(),
+            merchant_id: merchant_id.to_owned(),
+            payment_method_id,
+            payment_method: payment_method.payment_method,
+            payment_method_type: payment_method.payment_method_type,
+            payment_method_issuer: payment_method.payment_method_issuer,
+            scheme: payment_method.card_network,
+            metadata,
+            card_exp_month,
+            card_exp_year,
+            locker_id: payment_method.locker_id,
+            ..storage::PaymentMethodNew::default()
+        });
+    }
+
+    batch.addresses.extend(address);
+    batch.customers.push(customer);
+    batch.payment_methods.extend(new_payment_methods);
+    batch.processes.extend(processes);
+    batch_customer_ids.insert(customer_id);
+    Ok(())
+}
+
+fn get_customer_import_progress_update(
+    status: enums::CustomerImportStatus,
+    processed_count: usize,
+    succeeded_count: i32,
+    import_errors: &[customers::CustomerImportError],
+) -> RouterResult<storage::CustomerImportJobUpdate> {
+    let processed_count = i32::try_from(processed_count)
+        .into_report()
+        .change_context(errors::ApiErrorResponse::InternalServerError)?;
+    let failed_count = i32::try_from(import_errors.len())
+        .into_report()
+        .change_context(errors::ApiErrorResponse::InternalServerError)?;
+    let errors = (!import_errors.is_empty())
+        .then(|| Encode::<Vec<customers::CustomerImportError>>::encode_to_value(&import_errors))
+        .transpose()
+        .change_context(errors::ApiErrorResponse::InternalServerError)?;
+
+    Ok(storage::CustomerImportJobUpdate::ProgressUpdate {
+        status,
+        processed_count,
+        succeeded_count,
+        failed_count,
+        errors,
+        completed_at: (status == enums::CustomerImportStatus::Completed).then(date_time::now),
+    })
+}
+
+/// Creates the customers of an import which have not been processed yet, recording the errors of
+/// the customers which could not be created.
+pub async fn process_customer_import(
+    state: &AppState,
+    tracking_data: &storage::CustomerImportWorkflow,
+) -> RouterResult<()> {
+    let db = &*state.store;
+    let customer_import_job = db
+        .find_customer_import_job_by_merchant_id_job_id(
+            &tracking_data.merchant_id,
+            &tracking_data.job_id,
+        )
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::CustomerImportJobNotFound {
+            job_id: tracking_data.job_id.clone(),
+        })?;
+    // A failed import is resumed from its last saved position
+    if customer_import_job.status == enums::CustomerImportStatus::Completed {
+        return Ok(());
+    }
+
+    let key_store = db
+        .get_merchant_key_store_by_merchant_id(
+            &customer_import_job.merchant_id,
+            &db.get_master_key().to_vec().into(),
+        )
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::MerchantAccountNotFound)?;
+    let merchant_account = db
+        .find_merchant_account_by_merchant_id(&customer_import_job.merchant_id, &key_store)
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::MerchantAccountNotFound)?;
+
+    let records: crypto::OptionalEncryptableSecretString = domain_types::decrypt(
+        Some(customer_import_job.records.clone()),
+        key_store.key.get_inner().peek(),
+    )
+    .await
+    .change_context(errors::ApiErrorResponse::InternalServerError)
+    .attach_printable("Failed to decrypt the customers of the import")?;
+    let records: Vec<customers::CustomerImportRecord> = records
+        .map(|records| records.into_inner().expose())
+        .unwrap_or_default()
+        .parse_struct("Vec<CustomerImportRecord>")
+        .change_context(errors::ApiErrorResponse::InternalServerError)?;
+
+    let mut import_errors: Vec<customers::CustomerImportError> = customer_import_job
+        .errors
+        .clone()
+        .map(|errors| errors.parse_value("CustomerImportErrors"))
+        .transpose()
+        .change_context(errors::ApiErrorResponse::InternalServerError)?
+        .unwrap_or_default();
+    let mut succeeded_count = customer_import_job.succeeded_count;
+    let processed_count = usize::try_from(customer_import_job.processed_count)
+        .into_report()
+        .change_context(errors::ApiErrorResponse::InternalServerError)?;
+
+    db.update_customer_import_job_by_merchant_id_job_id(
+        &customer_import_job.merchant_id,
+        &customer_import_job.job_id,
+        storage::CustomerImportJobUpdate::StatusUpdate {
+            status: enums::CustomerImportStatus::Processing,
+        },
+    )
+    .await
+    .change_context(errors::ApiErrorResponse::InternalServerError)?;
+
+    // The customers of every batch are created in a single transaction along with the progress
+    // of the import, so that the customers of an interrupted batch are neither created twice nor
+    // reported as duplicates when the import is resumed
+    let total_count = records.len();
+    let mut records = records
+        .into_iter()
+        .enumerate()
+        .skip(processed_count)
+        .peekable();
+    while records.peek().is_some() {
+        let mut batch = storage::CustomerImportBatch::default();
+        let mut batch_customer_ids = HashSet::new();
+        let mut processed_count = processed_count;
+        for (row_index, record) in records
+            .by_ref()
+            .take(consts::CUSTOMER_IMPORT_PROGRESS_INTERVAL)
+        {
+            let customer_id = record.customer.customer_id.clone();
+            match add_customer_to_batch(
+                state,
+                &merchant_account,
+                &key_store,
+                record,
+                &mut batch,
+                &mut batch_customer_ids,
+            )
+            .await
+            {
+                Ok(()) => succeeded_count += 1,
+                Err(error) => {
+                    logger::info!(customer_import_error=?error, %row_index, "Failed to import customer");
+                    import_errors.push(customers::CustomerImportError {
+                        row_index,
+                        customer_id,
+                        error_code: error.current_context().error_code(),
+                        error_message: error.current_context().error_message(),
+                    });
+                }
+            }
+            processed_count = row_index + 1;
+        }
+
+        let scheduled_tasks_count = batch.processes.len();
+        db.insert_customer_import_batch_by_merchant_id_job_id(
+            &customer_import_job.merchant_id,
+            &customer_import_job.job_id,
+            batch,
+            get_customer_import_progress_update(
+                enums::CustomerImportStatus::Processing,
+                processed_count,
+                succeeded_count,
+                &import_errors,
+            )?,
+        )
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to create the customers of a batch of the customer import")?;
+        scheduler_metrics::TASKS_ADDED_COUNT.add(
+            &metrics::CONTEXT,
+            u64::try_from(scheduled_tasks_count).unwrap_or(u64::MAX),
+            &[],
+        );
+    }
+
+    db.update_customer_import_job_by_merchant_id_job_id(
+        &customer_import_job.merchant_id,
+        &customer_import_job.job_id,
+        get_customer_import_progress_update(
+            enums::CustomerImportStatus::Completed,
+            total_count,
+            succeeded_count,
+            &import_errors,
+        )?,
+    )
+    .await
+    .change_context(errors::ApiErrorResponse::InternalServerError)
+    .attach_printable("Failed to complete the customer import")?;
+    Ok(())
+}
+
+/// Marks an import as failed, once its task has failed with an error which is not specific to any
+/// of its customers.
+pub async fn fail_customer_import(
+    db: &dyn StorageInterface,
+    tracking_data: &storage::CustomerImportWorkflow,
+) -> RouterResult<()> {
+    db.update_customer_import_job_by_merchant_id_job_id(
+        &tracking_data.merchant_id,
+        &tracking_data.job_id,
+        storage::CustomerImportJobUpdate::StatusUpdate {
+            status: enums::CustomerImportStatus::Failed,
+        },
+    )
+    .await
+    .change_context(errors::ApiErrorResponse::InternalServerError)
+    .attach_printable("Failed to mark the customer import as failed")?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+
+    fn get_record(payment_methods: serde_json::Value) -> customers::CustomerImportRecord {
+        serde_json::from_value(serde_json::json!({
+            "customer_id": "cus_import",
+            "email": "guest@example.com",
+            "payment_methods": payment_methods,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_import_record_is_parsed_with_flattened_customer() {
+        let record = get_record(serde_json::json!([
+            { "payment_method": "card", "locker_id": "card_123" }
+        ]));
+        assert_eq!(record.customer.customer_id, "cus_import");
+        assert!(record.customer.email.is_some());
+        assert_eq!(record.payment_methods.map(|pms| pms.len()), Some(1));
+    }
+
+    #[test]
+    fn test_card_without_locker_id_is_rejected() {
+        let record = get_record(serde_json::json!([{ "payment_method": "card" }]));
+        let payment_methods = record.payment_methods.unwrap();
+        assert!(validate_import_payment_method(&payment_methods[0]).is_err());
+
+        let record = get_record(serde_json::json!([{ "payment_method": "wallet" }]));
+        let payment_methods = record.payment_methods.unwrap();
+        assert!(validate_import_payment_method(&payment_methods[0]).is_ok());
+    }
+
+    #[test]
+    fn test_import_size_is_validated() {
+        let empty_request = customers::CustomerImportRequest { customers: vec![] };
+        assert!(validate_customer_import_request(&empty_request).is_err());
+
+        let request = customers::CustomerImportRequest {
+            customers: vec![get_record(serde_json::Value::Null)],
+        };
+        assert!(validate_customer_import_request(&request).is_ok());
+    }
+}
@@ -1,7 +1,13 @@
-use api_models::{disputes as dispute_models, files as files_api_models};
+use std::str::FromStr;
+
+use api_models::{
+    accounting::JournalEntryType, disputes as dispute_models, files as files_api_models,
+};
 use common_utils::ext_traits::ValueExt;
 use error_stack::{IntoReport, ResultExt};
-use router_env::{instrument, tracing};
+use router_env::{instrument, logger, tracing};
+pub mod auto_defend;
+pub mod reason_codes;
 pub mod transformers;
 
 use super::{
@@ -9,7 +15,7 @@ use super::{
     metrics,
 };
 use crate::{
-    core::{files, payments, utils as core_utils},
+    core::{files, ledger, payments, utils as core_utils},
     routes::AppState,
     services,
     types::{
@@ -36,10 +42,96 @@ pub async fn retrieve_dispute(
         .to_not_found_response(errors::ApiErrorResponse::DisputeNotFound {
             dispute_id: req.dispute_id,
         })?;
-    let dispute_response = api_models::disputes::DisputeResponse::foreign_from(dispute);
+    let card_network = get_disputed_card_network(state, &merchant_account, &dispute).await;
+    let reason_code_details = dispute
+        .connector_reason_code
+        .as_deref()
+        .and_then(|reason_code| {
+            reason_codes::get_reason_code_details(card_network.as_ref(), reason_code)
+        });
+    let dispute_response = api_models::disputes::DisputeResponse {
+        reason_code_details,
+        ..api_models::disputes::DisputeResponse::foreign_from(dispute)
+    };
     Ok(services::ApplicationResponse::Json(dispute_response))
 }
 
+/// Retrieves the amounts withdrawn from and reinstated to the merchant for a dispute, from the
+/// entries posted to the ledger over its lifecycle.
+#[instrument(skip(state))]
+pub async fn retrieve_dispute_financials(
+    state: &AppState,
+    merchant_account: domain::MerchantAccount,
+    req: disputes::DisputeId,
+) -> RouterResponse<dispute_models::DisputeFinancialsResponse> {
+    let dispute = state
+        .store
+        .find_dispute_by_merchant_id_dispute_id(&merchant_account.merchant_id, &req.dispute_id)
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::DisputeNotFound {
+            dispute_id: req.dispute_id,
+        })?;
+    // Dispute currencies are stored as received from the connector
+    let currency = storage_enums::Currency::from_str(&dispute.currency)
+        .into_report()
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable_lazy(|| format!("Invalid currency for dispute {}", dispute.dispute_id))?;
+    let entries =
+        ledger::get_reference_entries(state, &merchant_account.merchant_id, &dispute.dispute_id)
+            .await?;
+
+    let total_amount = |entry_type| {
+        entries
+            .iter()
+            .filter(|entry| entry.entry_type == entry_type)
+            .fold(0_i64, |total, entry| total.saturating_add(entry.amount))
+    };
+    let withdrawn_amount = total_amount(JournalEntryType::Chargeback);
+    let reinstated_amount = total_amount(JournalEntryType::ChargebackReversal);
+
+    Ok(services::ApplicationResponse::Json(
+        dispute_models::DisputeFinancialsResponse {
+            dispute_id: dispute.dispute_id,
+            payment_id: dispute.payment_id,
+            currency,
+            withdrawn_amount,
+            reinstated_amount,
+            net_impact: withdrawn_amount.saturating_sub(reinstated_amount),
+            entries,
+        },
+    ))
+}
+
+/// Returns the card network of the disputed payment, if it was paid with a card. The network is
+/// only used to disambiguate the reason code of the dispute, so failures are only logged.
+async fn get_disputed_card_network(
+    state: &AppState,
+    merchant_account: &domain::MerchantAccount,
+    dispute: &diesel_models::dispute::Dispute,
+) -> Option<storage_enums::CardNetwork> {
+    let payment_attempt = state
+        .store
+        .find_payment_attempt_by_attempt_id_merchant_id(
+            &dispute.attempt_id,
+            &merchant_account.merchant_id,
+            merchant_account.storage_scheme,
+        )
+        .await
+        .map_err(|error| logger::warn!(payment_attempt_error=?error))
+        .ok()?;
+    payment_attempt
+        .payment_method_data
+        .map(|data| data.parse_value("AdditionalPaymentData"))
+        .transpose()
+        .map_err(|error| logger::warn!(additional_payment_data_error=?error))
+        .ok()
+        .flatten()
+        .and_then(|data| match data {
+            api_models::payments::AdditionalPaymentData::Card(card_info) => card_info.card_network,
+            _ => None,
+        })
+}
+
 #[instrument(skip(state))]
 pub async fn retrieve_disputes_list(
     state: &AppState,
@@ -52,13 +144,34 @@ pub async fn retrieve_disputes_list(
         .await
         .to_not_found_response(errors::ApiErrorResponse::InternalServerError)
         .attach_printable("Unable to retrieve disputes")?;
+    // The card networks of the disputed payments are not looked up for lists, so reason codes are
+    // only described when they are defined by a single card network
     let disputes_list = disputes
         .into_iter()
-        .map(api_models::disputes::DisputeResponse::foreign_from)
+        .map(|dispute| {
+            let reason_code_details = dispute
+                .connector_reason_code
+                .as_deref()
+                .and_then(|reason_code| reason_codes::get_reason_code_details(None, reason_code));
+            api_models::disputes::DisputeResponse {
+                reason_code_details,
+                ..api_models::disputes::DisputeResponse::foreign_from(dispute)
+            }
+        })
         .collect();
     Ok(services::ApplicationResponse::Json(disputes_list))
 }
 
+#[instrument(skip(_state))]
+pub async fn list_dispute_reason_codes(
+    _state: &AppState,
+    constraints: api_models::disputes::DisputeReasonCodeListConstraints,
+) -> RouterResponse<Vec<api_models::disputes::DisputeReasonCodeResponse>> {
+    let reason_codes =
+        reason_codes::list_reason_codes(constraints.card_network.as_ref(), constraints.category);
+    Ok(services::ApplicationResponse::Json(reason_codes))
+}
+
 #[instrument(skip(state))]
 pub async fn accept_dispute(
     state: &AppState,
@@ -155,6 +268,12 @@ pub async fn accept_dispute(
         .attach_printable_lazy(|| {
             format!("Unable to update dispute with dispute_id: {dispute_id}")
         })?;
+    // The dispute has already been accepted, so failing to post it to the ledger must not fail
+    // the request
+    ledger::record_dispute_postings(state, &updated_dispute)
+        .await
+        .map_err(|error| logger::error!(ledger_error=?error))
+        .ok();
     let dispute_response = api_models::disputes::DisputeResponse::foreign_from(updated_dispute);
     Ok(services::ApplicationResponse::Json(dispute_response))
 }
@@ -192,10 +311,16 @@ pub async fn submit_evidence(
             })
         },
     )?;
+    let connector_data = api::ConnectorData::get_connector_by_name(
+        &state.conf.connectors,
+        &dispute.connector,
+        api::GetToken::Connector,
+    )?;
     let submit_evidence_request_data = transformers::get_evidence_request_data(
         state,
         &merchant_account,
         &key_store,
+        &connector_data,
         req,
         &dispute,
     )
@@ -216,11 +341,6 @@ pub async fn submit_evidence(
         )
         .await
         .change_context(errors::ApiErrorResponse::PaymentNotFound)?;
-    let connector_data = api::ConnectorData::get_connector_by_name(
-        &state.conf.connectors,
-        &dispute.connector,
-        api::GetToken::Connector,
-    )?;
     let connector_integration: services::BoxedConnectorIntegration<
         '_,
         api::Evidence,
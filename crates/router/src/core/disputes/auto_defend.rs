@@ -0,0 +1,357 @@
+//! Automatic handling of disputes.
+//!
+//! Merchants can configure rules which take an action on the disputes raised against their
+//! payments as soon as the disputes are opened, such as accepting the disputes for amounts not
+//! worth challenging, or challenging friendly fraud disputes with templated evidence. The rules
+//! are evaluated in the incoming dispute webhook flow, before the merchant is notified of the
+//! dispute, so that the webhook sent to the merchant reflects the action taken.
+
+use std::collections::HashSet;
+
+use api_models::disputes::{
+    DisputeAutoDefendAction, DisputeAutoDefendRule, DisputeAutoDefendRules, DisputeReasonCategory,
+    SubmitEvidenceRequest,
+};
+use common_utils::ext_traits::{Encode, StringExt, ValueExt};
+use error_stack::{report, ResultExt};
+use router_env::{instrument, logger, tracing};
+
+use super::{accept_dispute, get_disputed_card_network, reason_codes, submit_evidence};
+use crate::{
+    core::{
+        errors::{self, RouterResponse, RouterResult},
+        metrics,
+    },
+    db::StorageInterface,
+    routes::AppState,
+    services,
+    types::{api::disputes, domain, storage},
+    utils,
+};
+
+fn get_config_key(merchant_id: &str) -> String {
+    format!("dispute_auto_defend_rules_{merchant_id}")
+}
+
+#[instrument(skip(state))]
+pub async fn retrieve_auto_defend_rules(
+    state: &AppState,
+    merchant_account: domain::MerchantAccount,
+) -> RouterResponse<DisputeAutoDefendRules> {
+    let rules = find_auto_defend_rules(&*state.store, &merchant_account.merchant_id)
+        .await?
+        .unwrap_or_default();
+    Ok(services::ApplicationResponse::Json(rules))
+}
+
+#[instrument(skip(state))]
+pub async fn update_auto_defend_rules(
+    state: &AppState,
+    merchant_account: domain::MerchantAccount,
+    req: DisputeAutoDefendRules,
+) -> RouterResponse<DisputeAutoDefendRules> {
+    validate_auto_defend_rules(&req)?;
+
+    let key = get_config_key(&merchant_account.merchant_id);
+    let config = Encode::<DisputeAutoDefendRules>::encode_to_string_of_json(&req)
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to encode the dispute auto-defend rules")?;
+
+    let db = &*state.store;
+    match db.find_config_by_key(&key).await {
+        Ok(_) => db
+            .update_config_cached(
+                &key,
+                storage::ConfigUpdate::Update {
+                    config: Some(config),
+                },
+            )
+            .await
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Failed to update the dispute auto-defend rules")?,
+        Err(error) if error.current_context().is_db_not_found() => db
+            .insert_config(storage::ConfigNew { key, config })
+            .await
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Failed to insert the dispute auto-defend rules")?,
+        Err(error) => Err(error
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Failed to find the dispute auto-defend rules"))?,
+    };
+
+    Ok(services::ApplicationResponse::Json(req))
+}
+
+async fn find_auto_defend_rules(
+    db: &dyn StorageInterface,
+    merchant_id: &str,
+) -> RouterResult<Option<DisputeAutoDefendRules>> {
+    match db
+        .find_config_by_key_cached(&get_config_key(merchant_id))
+        .await
+    {
+        Ok(config) => config
+            .config
+            .parse_struct("DisputeAutoDefendRules")
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Invalid dispute auto-defend rules configured for the merchant")
+            .map(Some),
+        Err(error) if error.current_context().is_db_not_found() => Ok(None),
+        Err(error) => Err(error
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Failed to find the dispute auto-defend rules")),
+    }
+}
+
+fn validate_auto_defend_rules(rules: &DisputeAutoDefendRules) -> RouterResult<()> {
+    let mut names = HashSet::new();
+    for rule in &rules.rules {
+        utils::when(
+            rule.name.is_empty() || !names.insert(rule.name.as_str()),
+            || {
+                Err(report!(errors::ApiErrorResponse::InvalidRequestData {
+                    message: format!(
+                        "The names of the rules must be non-empty and unique, found `{}`",
+                        rule.name
+                    ),
+                }))
+            },
+        )?;
+
+        utils::when(rule.max_amount.map_or(false, |amount| amount < 0), || {
+            Err(report!(errors::ApiErrorResponse::InvalidRequestData {
+                message: format!("The max_amount of the rule `{}` is negative", rule.name),
+            }))
+        })?;
+
+        if let DisputeAutoDefendAction::SubmitEvidence { evidence } = &rule.action {
+            utils::when(evidence.contains_key("dispute_id"), || {
+                Err(report!(errors::ApiErrorResponse::InvalidRequestData {
+                    message: format!(
+                        "The evidence of the rule `{}` cannot contain a dispute_id",
+                        rule.name
+                    ),
+                }))
+            })?;
+
+            get_evidence_request(evidence.clone(), String::new()).change_context(
+                errors::ApiErrorResponse::InvalidRequestData {
+                    message: format!("The evidence of the rule `{}` is invalid", rule.name),
+                },
+            )?;
+        }
+    }
+    Ok(())
+}
+
+/// Takes the action of the first auto-defend rule of the merchant matching a dispute which was
+/// just opened, returning the dispute as updated by the action. Failures are logged and leave the
+/// dispute to the merchant, so that they do not prevent the merchant from being notified of it.
+#[instrument(skip_all)]
+pub async fn apply_auto_defend_rules(
+    state: &AppState,
+    merchant_account: &domain::MerchantAccount,
+    key_store: &domain::MerchantKeyStore,
+    dispute: storage::Dispute,
+) -> storage::Dispute {
+    let rules = match find_auto_defend_rules(&*state.store, &merchant_account.merchant_id).await {
+        Ok(Some(rules)) => rules,
+        Ok(None) => return dispute,
+        Err(error) => {
+            logger::error!(dispute_auto_defend_error=?error);
+            return dispute;
+        }
+    };
+
+    let reason_category = match dispute.connector_reason_code.as_deref() {
+        Some(reason_code) => {
+            let card_network = get_disputed_card_network(state, merchant_account, &dispute).await;
+            reason_codes::get_reason_code_details(card_network.as_ref(), reason_code)
+                .map(|details| details.category)
+        }
+        None => None,
+    };
+
+    let Some(rule) = rules
+        .rules
+        .into_iter()
+        .find(|rule| is_rule_matching(rule, &dispute, reason_category))
+    else {
+        return dispute;
+    };
+
+    let dispute_id = disputes::DisputeId {
+        dispute_id: dispute.dispute_id.clone(),
+    };
+    let (action, result) = match rule.action {
+        DisputeAutoDefendAction::Accept => (
+            "accept",
+            accept_dispute(
+                state,
+                merchant_account.clone(),
+                key_store.clone(),
+                dispute_id,
+            )
+            .await
+            .map(|_| ()),
+        ),
+        DisputeAutoDefendAction::SubmitEvidence { evidence } => {
+            let result = match get_evidence_request(
+                fill_placeholders(evidence, &dispute),
+                dispute.dispute_id.clone(),
+            ) {
+                Ok(evidence_request) => submit_evidence(
+                    state,
+                    merchant_account.clone(),
+                    key_store.clone(),
+                    evidence_request,
+                )
+                .await
+                .map(|_| ()),
+                Err(error) => {
+                    Err(error.change_context(errors::ApiErrorResponse::InternalServerError))
+                }
+            };
+            ("submit_evidence", result)
+        }
+    };
+
+    metrics::DISPUTE_AUTO_DEFEND_ACTION_COUNT.add(
+        &metrics::CONTEXT,
+        1,
+        &[
+            metrics::KeyValue::new("action", action),
+            metrics::KeyValue::new(
+                "outcome",
+                if result.is_ok() {
+                    "succeeded"
+                } else {
+                    "failed"
+                },
+            ),
+        ],
+    );
+
+    match result {
+        Ok(()) => {
+            logger::info!(
+                dispute_id = %dispute.dispute_id,
+                rule = %rule.name,
+                action,
+                "Dispute handled by an auto-defend rule"
+            );
+            state
+                .store
+                .find_dispute_by_merchant_id_dispute_id(
+                    &merchant_account.merchant_id,
+                    &dispute.dispute_id,
+                )
+                .await
+                .map_err(|error| logger::error!(dispute_auto_defend_error=?error))
+                .unwrap_or(dispute)
+        }
+        Err(error) => {
+            logger::error!(rule = %rule.name, action, dispute_auto_defend_error=?error);
+            dispute
+        }
+    }
+}
+
+fn is_rule_matching(
+    rule: &DisputeAutoDefendRule,
+    dispute: &storage::Dispute,
+    reason_category: Option<DisputeReasonCategory>,
+) -> bool {
+    let is_amount_matching = rule.max_amount.map_or(true, |max_amount| {
+        dispute
+            .amount
+            .parse::<i64>()
+            .map_or(false, |amount| amount <= max_amount)
+    });
+    let is_currency_matching = rule.currency.map_or(true, |currency| {
+        currency.to_string().eq_ignore_ascii_case(&dispute.currency)
+    });
+    let is_reason_code_matching = rule.reason_codes.as_ref().map_or(true, |reason_codes| {
+        dispute
+            .connector_reason_code
+            .as_ref()
+            .map_or(false, |reason_code| reason_codes.contains(reason_code))
+    });
+    let is_reason_category_matching =
+        rule.reason_categories
+            .as_ref()
+            .map_or(true, |reason_categories| {
+                reason_category.map_or(false, |reason_category| {
+                    reason_categories.contains(&reason_category)
+                })
+            });
+    let is_connector_matching = rule
+        .connectors
+        .as_ref()
+        .map_or(true, |connectors| connectors.contains(&dispute.connector));
+
+    is_amount_matching
+        && is_currency_matching
+        && is_reason_code_matching
+        && is_reason_category_matching
+        && is_connector_matching
+}
+
+/// Replaces the placeholders in the text of the evidence with the details of the dispute.
+fn fill_placeholders(
+    evidence: serde_json::Map<String, serde_json::Value>,
+    dispute: &storage::Dispute,
+) -> serde_json::Map<String, serde_json::Value> {
+    let placeholders = [
+        ("{dispute_id}", dispute.dispute_id.as_str()),
+        ("{payment_id}", dispute.payment_id.as_str()),
+        ("{amount}", dispute.amount.as_str()),
+        ("{currency}", dispute.currency.as_str()),
+        ("{connector}", dispute.connector.as_str()),
+        (
+            "{reason_code}",
+            dispute.connector_reason_code.as_deref().unwrap_or_default(),
+        ),
+    ];
+
+    fn fill(value: serde_json::Value, placeholders: &[(&str, &str)]) -> serde_json::Value {
+        match value {
+            serde_json::Value::String(text) => serde_json::Value::String(
+                placeholders
+                    .iter()
+                    .fold(text, |text, (placeholder, value)| {
+                        text.replace(placeholder, value)
+                    }),
+            ),
+            serde_json::Value::Array(values) => serde_json::Value::Array(
+                values
+                    .into_iter()
+                    .map(|value| fill(value, placeholders))
+                    .collect(),
+            ),
+            serde_json::Value::Object(values) => serde_json::Value::Object(
+                values
+                    .into_iter()
+                    .map(|(key, value)| (key, fill(value, placeholders)))
+                    .collect(),
+            ),
+            value => value,
+        }
+    }
+
+    evidence
+        .into_iter()
+        .map(|(key, value)| (key, fill(value, &placeholders)))
+        .collect()
+}
+
+fn get_evidence_request(
+    mut evidence: serde_json::Map<String, serde_json::Value>,
+    dispute_id: String,
+) -> common_utils::errors::CustomResult<SubmitEvidenceRequest, common_utils::errors::ParsingError> {
+    evidence.insert(
+        "dispute_id".to_string(),
+        serde_json::Value::String(dispute_id),
+    );
+    serde_json::Value::Object(evidence).parse_value("SubmitEvidenceRequest")
+}
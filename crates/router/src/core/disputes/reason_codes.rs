@@ -0,0 +1,348 @@
+//! Dictionary of the dispute reason codes defined by the card networks.
+//!
+//! Connectors forward the reason code assigned by the card network as-is, so the same dispute
+//! reason is described by a different code for each network. Each code is mapped to a normalized
+//! category, which determines the evidence recommended to challenge the dispute. The dictionary is
+//! exposed through the API and embedded in dispute responses and outgoing dispute webhooks, so that
+//! merchants can triage disputes and prepare their evidence without looking up the codes.
+
+use api_models::disputes::{
+    DisputeReasonCategory as Category, DisputeReasonCodeResponse, EvidenceType,
+};
+
+use crate::types::storage::enums;
+
+/// A reason code, its description and its normalized category.
+type ReasonCode = (&'static str, &'static str, Category);
+
+#[rustfmt::skip]
+const VISA_REASON_CODES: &[ReasonCode] = &[
+    ("10.1", "EMV liability shift counterfeit fraud", Category::Fraud),
+    ("10.2", "EMV liability shift non-counterfeit fraud", Category::Fraud),
+    ("10.3", "Other fraud - card present environment", Category::Fraud),
+    ("10.4", "Other fraud - card absent environment", Category::Fraud),
+    ("10.5", "Visa fraud monitoring program", Category::Fraud),
+    ("11.1", "Card recovery bulletin", Category::Authorization),
+    ("11.2", "Declined authorization", Category::Authorization),
+    ("11.3", "No authorization", Category::Authorization),
+    ("12.1", "Late presentment", Category::ProcessingError),
+    ("12.2", "Incorrect transaction code", Category::ProcessingError),
+    ("12.3", "Incorrect currency", Category::ProcessingError),
+    ("12.4", "Incorrect account number", Category::ProcessingError),
+    ("12.5", "Incorrect amount", Category::ProcessingError),
+    ("12.6.1", "Duplicate processing", Category::Duplicate),
+    ("12.6.2", "Paid by other means", Category::Duplicate),
+    ("12.7", "Invalid data", Category::ProcessingError),
+    ("13.1", "Merchandise or services not received", Category::ProductNotReceived),
+    ("13.2", "Cancelled recurring transaction", Category::Cancelled),
+    ("13.3", "Not as described or defective merchandise or services", Category::ProductUnacceptable),
+    ("13.4", "Counterfeit merchandise", Category::ProductUnacceptable),
+    ("13.5", "Misrepresentation", Category::ProductUnacceptable),
+    ("13.6", "Credit not processed", Category::CreditNotProcessed),
+    ("13.7", "Cancelled merchandise or services", Category::Cancelled),
+    ("13.8", "Original credit transaction not accepted", Category::ProcessingError),
+    ("13.9", "Non-receipt of cash or load transaction value", Category::ProductNotReceived),
+];
+
+#[rustfmt::skip]
+const MASTERCARD_REASON_CODES: &[ReasonCode] = &[
+    ("4807", "Warning bulletin file", Category::Authorization),
+    ("4808", "Authorization-related chargeback", Category::Authorization),
+    ("4812", "Account number not on file", Category::ProcessingError),
+    ("4831", "Transaction amount differs", Category::ProcessingError),
+    ("4834", "Point-of-interaction error", Category::ProcessingError),
+    ("4837", "No cardholder authorization", Category::Fraud),
+    ("4840", "Fraudulent processing of transactions", Category::Fraud),
+    ("4841", "Cancelled recurring or digital goods transaction", Category::Cancelled),
+    ("4842", "Late presentment", Category::ProcessingError),
+    ("4846", "Correct transaction currency code not provided", Category::ProcessingError),
+    ("4849", "Questionable merchant activity", Category::Fraud),
+    ("4853", "Cardholder dispute", Category::General),
+    ("4854", "Cardholder dispute - not elsewhere classified", Category::General),
+    ("4855", "Goods or services not provided", Category::ProductNotReceived),
+    ("4859", "Addendum, no-show or ATM dispute", Category::ProductNotReceived),
+    ("4860", "Credit not processed", Category::CreditNotProcessed),
+    ("4863", "Cardholder does not recognize - potential fraud", Category::Fraud),
+    ("4870", "Chip liability shift", Category::Fraud),
+    ("4871", "Chip or PIN liability shift", Category::Fraud),
+];
+
+#[rustfmt::skip]
+const AMERICAN_EXPRESS_REASON_CODES: &[ReasonCode] = &[
+    ("A01", "Charge amount exceeds authorization amount", Category::Authorization),
+    ("A02", "No valid authorization", Category::Authorization),
+    ("A08", "Authorization approval expired", Category::Authorization),
+    ("C02", "Credit not processed", Category::CreditNotProcessed),
+    ("C04", "Goods or services returned or refused", Category::CreditNotProcessed),
+    ("C05", "Goods or services cancelled", Category::Cancelled),
+    ("C08", "Goods or services not received or only partially received", Category::ProductNotReceived),
+    ("C14", "Paid by other means", Category::Duplicate),
+    ("C18", "No show or cancelled reservation", Category::Cancelled),
+    ("C28", "Cancelled recurring billing", Category::Cancelled),
+    ("C31", "Goods or services not as described", Category::ProductUnacceptable),
+    ("C32", "Goods or services damaged or defective", Category::ProductUnacceptable),
+    ("F10", "Missing imprint", Category::Fraud),
+    ("F14", "Missing signature", Category::Fraud),
+    ("F24", "No cardmember authorization", Category::Fraud),
+    ("F29", "Card not present", Category::Fraud),
+    ("F30", "EMV counterfeit", Category::Fraud),
+    ("F31", "EMV lost, stolen or non-received", Category::Fraud),
+    ("M01", "Chargeback authorization", Category::General),
+    ("P01", "Unassigned card number", Category::ProcessingError),
+    ("P03", "Credit processed as charge", Category::ProcessingError),
+    ("P04", "Charge processed as credit", Category::ProcessingError),
+    ("P05", "Incorrect charge amount", Category::ProcessingError),
+    ("P07", "Late submission", Category::ProcessingError),
+    ("P08", "Duplicate charge", Category::Duplicate),
+    ("P22", "Non-matching card number", Category::ProcessingError),
+    ("P23", "Currency discrepancy", Category::ProcessingError),
+];
+
+#[rustfmt::skip]
+const DISCOVER_REASON_CODES: &[ReasonCode] = &[
+    ("AA", "Does not recognize", Category::Fraud),
+    ("AP", "Cancelled recurring transaction", Category::Cancelled),
+    ("AW", "Altered amount", Category::ProcessingError),
+    ("CD", "Credit posted as card sale", Category::ProcessingError),
+    ("DP", "Duplicate processing", Category::Duplicate),
+    ("EX", "Expired card", Category::Authorization),
+    ("IC", "Illegible sales data", Category::ProcessingError),
+    ("LP", "Late presentation", Category::ProcessingError),
+    ("NA", "No authorization", Category::Authorization),
+    ("PM", "Paid by other means", Category::Duplicate),
+    ("RG", "Non-receipt of goods, services or cash", Category::ProductNotReceived),
+    ("RM", "Quality discrepancy", Category::ProductUnacceptable),
+    ("RN2", "Credit not processed", Category::CreditNotProcessed),
+    ("UA01", "Fraud - card present transaction", Category::Fraud),
+    ("UA02", "Fraud - card not present transaction", Category::Fraud),
+    ("UA05", "Fraud - chip counterfeit transaction", Category::Fraud),
+    ("UA06", "Fraud - chip and PIN transaction", Category::Fraud),
+];
+
+/// The card networks with a dictionary of reason codes, along with their reason codes.
+const CARD_NETWORK_REASON_CODES: &[(enums::CardNetwork, &[ReasonCode])] = &[
+    (enums::CardNetwork::Visa, VISA_REASON_CODES),
+    (enums::CardNetwork::Mastercard, MASTERCARD_REASON_CODES),
+    (
+        enums::CardNetwork::AmericanExpress,
+        AMERICAN_EXPRESS_REASON_CODES,
+    ),
+    (enums::CardNetwork::Discover, DISCOVER_REASON_CODES),
+];
+
+fn get_reason_codes(card_network: &enums::CardNetwork) -> Option<&'static [ReasonCode]> {
+    match card_network {
+        enums::CardNetwork::Visa => Some(VISA_REASON_CODES),
+        enums::CardNetwork::Mastercard | enums::CardNetwork::Maestro => {
+            Some(MASTERCARD_REASON_CODES)
+        }
+        enums::CardNetwork::AmericanExpress => Some(AMERICAN_EXPRESS_REASON_CODES),
+        enums::CardNetwork::Discover | enums::CardNetwork::DinersClub => {
+            Some(DISCOVER_REASON_CODES)
+        }
+        enums::CardNetwork::JCB
+        | enums::CardNetwork::CartesBancaires
+        | enums::CardNetwork::UnionPay
+        | enums::CardNetwork::Interac
+        | enums::CardNetwork::RuPay => None,
+    }
+}
+
+fn find_reason_code(
+    reason_codes: &'static [ReasonCode],
+    reason_code: &str,
+) -> Option<&'static ReasonCode> {
+    reason_codes
+        .iter()
+        .find(|(code, _, _)| code.eq_ignore_ascii_case(reason_code))
+}
+
+/// Returns the evidence recommended to challenge disputes of a category, in order of relevance.
+pub fn get_recommended_evidence(category: Category) -> Vec<EvidenceType> {
+    match category {
+        Category::Fraud => vec![
+            EvidenceType::Receipt,
+            EvidenceType::CustomerSignature,
+            EvidenceType::ShippingDocumentation,
+            EvidenceType::CustomerCommunication,
+        ],
+        Category::Authorization => vec![EvidenceType::Receipt, EvidenceType::UncategorizedFile],
+        Category::ProcessingError => vec![
+            EvidenceType::Receipt,
+            EvidenceType::InvoiceShowingDistinctTransactions,
+        ],
+        Category::Duplicate => vec![
+            EvidenceType::InvoiceShowingDistinctTransactions,
+            EvidenceType::Receipt,
+        ],
+        Category::ProductNotReceived => vec![
+            EvidenceType::ShippingDocumentation,
+            EvidenceType::ServiceDocumentation,
+            EvidenceType::CustomerCommunication,
+        ],
+        Category::ProductUnacceptable => vec![
+            EvidenceType::ServiceDocumentation,
+            EvidenceType::CustomerCommunication,
+            EvidenceType::RefundPolicy,
+            EvidenceType::Receipt,
+        ],
+        Category::CreditNotProcessed => vec![
+            EvidenceType::RefundPolicy,
+            EvidenceType::CancellationPolicy,
+            EvidenceType::CustomerCommunication,
+        ],
+        Category::Cancelled => vec![
+            EvidenceType::CancellationPolicy,
+            EvidenceType::RecurringTransactionAgreement,
+            EvidenceType::CustomerCommunication,
+        ],
+        Category::General => vec![
+            EvidenceType::CustomerCommunication,
+            EvidenceType::Receipt,
+            EvidenceType::UncategorizedFile,
+        ],
+    }
+}
+
+fn to_reason_code_response(
+    card_network: enums::CardNetwork,
+    (code, description, category): &ReasonCode,
+) -> DisputeReasonCodeResponse {
+    DisputeReasonCodeResponse {
+        card_network,
+        reason_code: code.to_string(),
+        description: description.to_string(),
+        category: *category,
+        recommended_evidence: get_recommended_evidence(*category),
+    }
+}
+
+/// Looks up the reason code of a dispute.
+///
+/// If the card network of the disputed payment is not known, the reason code is looked up across
+/// all networks, and it is only returned if the code is defined by a single network.
+fn lookup_reason_code(
+    card_network: Option<&enums::CardNetwork>,
+    reason_code: &str,
+) -> Option<(enums::CardNetwork, &'static ReasonCode)> {
+    let reason_code = reason_code.trim();
+    match card_network {
+        Some(card_network) => get_reason_codes(card_network)
+            .and_then(|reason_codes| find_reason_code(reason_codes, reason_code))
+            .map(|found| (card_network.clone(), found)),
+        None => {
+            let mut found =
+                CARD_NETWORK_REASON_CODES
+                    .iter()
+                    .filter_map(|(card_network, reason_codes)| {
+                        find_reason_code(reason_codes, reason_code)
+                            .map(|found| (card_network.clone(), found))
+                    });
+            match (found.next(), found.next()) {
+                (Some(found), None) => Some(found),
+                _ => None,
+            }
+        }
+    }
+}
+
+/// Returns the description of the reason code of a dispute.
+pub fn get_reason_code_description(
+    card_network: Option<&enums::CardNetwork>,
+    reason_code: &str,
+) -> Option<&'static str> {
+    lookup_reason_code(card_network, reason_code).map(|(_, (_, description, _))| *description)
+}
+
+/// Returns the description, category and recommended evidence of the reason code of a dispute.
+pub fn get_reason_code_details(
+    card_network: Option<&enums::CardNetwork>,
+    reason_code: &str,
+) -> Option<DisputeReasonCodeResponse> {
+    lookup_reason_code(card_network, reason_code)
+        .map(|(card_network, found)| to_reason_code_response(card_network, found))
+}
+
+/// Lists the reason codes of the dictionary, optionally only those of a card network or category.
+pub fn list_reason_codes(
+    card_network: Option<&enums::CardNetwork>,
+    category: Option<Category>,
+) -> Vec<DisputeReasonCodeResponse> {
+    let card_networks: Vec<(enums::CardNetwork, &[ReasonCode])> = match card_network {
+        Some(card_network) => get_reason_codes(card_network)
+            .map(|reason_codes| (card_network.clone(), reason_codes))
+            .into_iter()
+            .collect(),
+        None => CARD_NETWORK_REASON_CODES.to_vec(),
+    };
+    card_networks
+        .into_iter()
+        .flat_map(|(card_network, reason_codes)| {
+            reason_codes
+                .iter()
+                .filter(|(_, _, reason_category)| {
+                    category.map_or(true, |category| category == *reason_category)
+                })
+                .map(move |reason_code| to_reason_code_response(card_network.clone(), reason_code))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+
+    #[test]
+    fn test_reason_code_description_for_card_network() {
+        assert_eq!(
+            get_reason_code_description(Some(&enums::CardNetwork::Visa), "10.4"),
+            Some("Other fraud - card absent environment")
+        );
+        assert_eq!(
+            get_reason_code_description(Some(&enums::CardNetwork::Mastercard), "4837"),
+            Some("No cardholder authorization")
+        );
+        assert_eq!(
+            get_reason_code_description(Some(&enums::CardNetwork::Visa), "4837"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_reason_code_description_without_card_network() {
+        assert_eq!(
+            get_reason_code_description(None, " c08 "),
+            Some("Goods or services not received or only partially received")
+        );
+        assert_eq!(get_reason_code_description(None, "unknown"), None);
+    }
+
+    #[test]
+    fn test_reason_code_details() {
+        let details = get_reason_code_details(Some(&enums::CardNetwork::Maestro), "4855").unwrap();
+        assert_eq!(details.card_network, enums::CardNetwork::Maestro);
+        assert_eq!(details.category, Category::ProductNotReceived);
+        assert_eq!(
+            details.recommended_evidence.first(),
+            Some(&EvidenceType::ShippingDocumentation)
+        );
+
+        let details = get_reason_code_details(None, "13.2").unwrap();
+        assert_eq!(details.card_network, enums::CardNetwork::Visa);
+        assert_eq!(details.category, Category::Cancelled);
+    }
+
+    #[test]
+    fn test_list_reason_codes() {
+        let fraud_codes = list_reason_codes(None, Some(Category::Fraud));
+        assert!(!fraud_codes.is_empty());
+        assert!(fraud_codes
+            .iter()
+            .all(|reason_code| reason_code.category == Category::Fraud));
+
+        let visa_codes = list_reason_codes(Some(&enums::CardNetwork::Visa), None);
+        assert_eq!(visa_codes.len(), VISA_REASON_CODES.len());
+        assert!(list_reason_codes(Some(&enums::CardNetwork::JCB), None).is_empty());
+    }
+}
@@ -1,116 +1,223 @@
+use std::collections::HashSet;
+
 use api_models::disputes::EvidenceType;
 use common_utils::errors::CustomResult;
-use error_stack::ResultExt;
+use error_stack::{report, ResultExt};
 
 use crate::{
-    core::{errors, files::helpers::retrieve_file_and_provider_file_id_from_file_id},
+    core::{errors, files::helpers::retrieve_file_and_provider_file_id_from_file_metadata},
     routes::AppState,
     types::{
         api::{self, DisputeEvidence},
         domain,
         transformers::ForeignFrom,
-        SubmitEvidenceRequestData,
+        EvidenceBundle, EvidenceBundleFile, SubmitEvidenceRequestData,
     },
+    utils,
 };
 
-pub async fn get_evidence_request_data(
+/// Returns the ids of the files submitted as evidence, along with their evidence type. The file
+/// submitted through the field of an evidence category comes before the other files of the
+/// category.
+fn get_evidence_file_ids(
+    evidence_request: &api_models::disputes::SubmitEvidenceRequest,
+) -> Vec<(EvidenceType, String)> {
+    [
+        (
+            EvidenceType::CancellationPolicy,
+            &evidence_request.cancellation_policy,
+        ),
+        (
+            EvidenceType::CustomerCommunication,
+            &evidence_request.customer_communication,
+        ),
+        (
+            EvidenceType::CustomerSignature,
+            &evidence_request.customer_signature,
+        ),
+        (EvidenceType::Receipt, &evidence_request.receipt),
+        (EvidenceType::RefundPolicy, &evidence_request.refund_policy),
+        (
+            EvidenceType::ServiceDocumentation,
+            &evidence_request.service_documentation,
+        ),
+        (
+            EvidenceType::ShippingDocumentation,
+            &evidence_request.shipping_documentation,
+        ),
+        (
+            EvidenceType::InvoiceShowingDistinctTransactions,
+            &evidence_request.invoice_showing_distinct_transactions,
+        ),
+        (
+            EvidenceType::RecurringTransactionAgreement,
+            &evidence_request.recurring_transaction_agreement,
+        ),
+        (
+            EvidenceType::UncategorizedFile,
+            &evidence_request.uncategorized_file,
+        ),
+    ]
+    .into_iter()
+    .filter_map(|(evidence_type, file_id)| file_id.clone().map(|file_id| (evidence_type, file_id)))
+    .chain(
+        evidence_request
+            .evidence_files
+            .iter()
+            .flatten()
+            .flat_map(|evidence_files| {
+                evidence_files
+                    .file_ids
+                    .iter()
+                    .map(move |file_id| (evidence_files.evidence_type, file_id.clone()))
+            }),
+    )
+    .collect()
+}
+
+/// Validates the files submitted as evidence against the limits of the connector, before they
+/// are retrieved.
+fn validate_evidence_files(
+    connector: &api::ConnectorData,
+    evidence_files: &[(EvidenceType, diesel_models::file::FileMetadata)],
+) -> CustomResult<(), errors::ApiErrorResponse> {
+    let mut file_ids = HashSet::new();
+    let mut evidence_types = HashSet::new();
+    for (evidence_type, file_metadata) in evidence_files {
+        utils::when(!file_ids.insert(file_metadata.file_id.as_str()), || {
+            Err(report!(errors::ApiErrorResponse::InvalidRequestData {
+                message: format!(
+                    "The file {} is submitted as evidence more than once",
+                    file_metadata.file_id
+                ),
+            }))
+        })?;
+        utils::when(
+            !evidence_types.insert(*evidence_type)
+                && !connector.connector.supports_evidence_bundle(),
+            || {
+                Err(report!(errors::ApiErrorResponse::InvalidRequestData {
+                    message: format!(
+                        "{} accepts a single file of evidence type {evidence_type}",
+                        connector.connector_name
+                    ),
+                }))
+            },
+        )?;
+    }
+
+    if let Some(max_evidence_bundle_size) = connector.connector.get_max_evidence_bundle_size() {
+        let total_file_size: i64 = evidence_files
+            .iter()
+            .map(|(_, file_metadata)| i64::from(file_metadata.file_size))
+            .sum();
+        utils::when(total_file_size > max_evidence_bundle_size, || {
+            Err(report!(errors::ApiErrorResponse::InvalidRequestData {
+                message: format!(
+                    "The total size of the evidence files, {total_file_size} bytes, exceeds the limit of {max_evidence_bundle_size} bytes of {}",
+                    connector.connector_name
+                ),
+            }))
+        })?;
+    }
+
+    Ok(())
+}
+
+async fn get_evidence_bundle(
     state: &AppState,
     merchant_account: &domain::MerchantAccount,
     key_store: &domain::MerchantKeyStore,
-    evidence_request: api_models::disputes::SubmitEvidenceRequest,
-    dispute: &diesel_models::dispute::Dispute,
-) -> CustomResult<SubmitEvidenceRequestData, errors::ApiErrorResponse> {
-    let (cancellation_policy, cancellation_policy_provider_file_id) =
-        retrieve_file_and_provider_file_id_from_file_id(
-            state,
-            evidence_request.cancellation_policy,
-            merchant_account,
-            key_store,
-            api::FileDataRequired::NotRequired,
-        )
-        .await?;
-    let (customer_communication, customer_communication_provider_file_id) =
-        retrieve_file_and_provider_file_id_from_file_id(
-            state,
-            evidence_request.customer_communication,
-            merchant_account,
-            key_store,
-            api::FileDataRequired::NotRequired,
-        )
-        .await?;
-    let (customer_signature, customer_signature_provider_file_id) =
-        retrieve_file_and_provider_file_id_from_file_id(
+    connector: &api::ConnectorData,
+    evidence_request: &api_models::disputes::SubmitEvidenceRequest,
+) -> CustomResult<EvidenceBundle, errors::ApiErrorResponse> {
+    let mut evidence_files = Vec::new();
+    for (evidence_type, file_id) in get_evidence_file_ids(evidence_request) {
+        let file_metadata = state
+            .store
+            .find_file_metadata_by_merchant_id_file_id(&merchant_account.merchant_id, &file_id)
+            .await
+            .change_context(errors::ApiErrorResponse::FileNotFound)?;
+        evidence_files.push((evidence_type, file_metadata));
+    }
+
+    validate_evidence_files(connector, &evidence_files)?;
+
+    let mut files = Vec::with_capacity(evidence_files.len());
+    for (evidence_type, file_metadata) in evidence_files {
+        let file_id = file_metadata.file_id.clone();
+        let file_name = file_metadata.file_name.clone();
+        let file_type = file_metadata.file_type.clone();
+        let file_size = file_metadata.file_size;
+        let (file_data, provider_file_id) = retrieve_file_and_provider_file_id_from_file_metadata(
             state,
-            evidence_request.customer_signature,
+            file_metadata,
             merchant_account,
             key_store,
             api::FileDataRequired::NotRequired,
         )
         .await?;
-    let (receipt, receipt_provider_file_id) = retrieve_file_and_provider_file_id_from_file_id(
+        files.push(EvidenceBundleFile {
+            evidence_type,
+            file_id,
+            file_name,
+            file_type,
+            file_size,
+            file_data,
+            provider_file_id,
+        });
+    }
+
+    Ok(EvidenceBundle { files })
+}
+
+pub async fn get_evidence_request_data(
+    state: &AppState,
+    merchant_account: &domain::MerchantAccount,
+    key_store: &domain::MerchantKeyStore,
+    connector: &api::ConnectorData,
+    evidence_request: api_models::disputes::SubmitEvidenceRequest,
+    dispute: &diesel_models::dispute::Dispute,
+) -> CustomResult<SubmitEvidenceRequestData, errors::ApiErrorResponse> {
+    let evidence_bundle = get_evidence_bundle(
         state,
-        evidence_request.receipt,
         merchant_account,
         key_store,
-        api::FileDataRequired::NotRequired,
+        connector,
+        &evidence_request,
     )
     .await?;
+
+    // The fields of the evidence categories hold the first file of each category, which is the
+    // only file of the category for connectors not supporting evidence bundles
+    let get_evidence_file = |evidence_type| {
+        evidence_bundle
+            .get_files(evidence_type)
+            .next()
+            .map(|file| (file.file_data.clone(), file.provider_file_id.clone()))
+            .unwrap_or_default()
+    };
+    let (cancellation_policy, cancellation_policy_provider_file_id) =
+        get_evidence_file(EvidenceType::CancellationPolicy);
+    let (customer_communication, customer_communication_provider_file_id) =
+        get_evidence_file(EvidenceType::CustomerCommunication);
+    let (customer_signature, customer_signature_provider_file_id) =
+        get_evidence_file(EvidenceType::CustomerSignature);
+    let (receipt, receipt_provider_file_id) = get_evidence_file(EvidenceType::Receipt);
     let (refund_policy, refund_policy_provider_file_id) =
-        retrieve_file_and_provider_file_id_from_file_id(
-            state,
-            evidence_request.refund_policy,
-            merchant_account,
-            key_store,
-            api::FileDataRequired::NotRequired,
-        )
-        .await?;
+        get_evidence_file(EvidenceType::RefundPolicy);
     let (service_documentation, service_documentation_provider_file_id) =
-        retrieve_file_and_provider_file_id_from_file_id(
-            state,
-            evidence_request.service_documentation,
-            merchant_account,
-            key_store,
-            api::FileDataRequired::NotRequired,
-        )
-        .await?;
+        get_evidence_file(EvidenceType::ServiceDocumentation);
     let (shipping_documentation, shipping_documentation_provider_file_id) =
-        retrieve_file_and_provider_file_id_from_file_id(
-            state,
-            evidence_request.shipping_documentation,
-            merchant_account,
-            key_store,
-            api::FileDataRequired::NotRequired,
-        )
-        .await?;
+        get_evidence_file(EvidenceType::ShippingDocumentation);
     let (
         invoice_showing_distinct_transactions,
         invoice_showing_distinct_transactions_provider_file_id,
-    ) = retrieve_file_and_provider_file_id_from_file_id(
-        state,
-        evidence_request.invoice_showing_distinct_transactions,
-        merchant_account,
-        key_store,
-        api::FileDataRequired::NotRequired,
-    )
-    .await?;
+    ) = get_evidence_file(EvidenceType::InvoiceShowingDistinctTransactions);
     let (recurring_transaction_agreement, recurring_transaction_agreement_provider_file_id) =
-        retrieve_file_and_provider_file_id_from_file_id(
-            state,
-            evidence_request.recurring_transaction_agreement,
-            merchant_account,
-            key_store,
-            api::FileDataRequired::NotRequired,
-        )
-        .await?;
+        get_evidence_file(EvidenceType::RecurringTransactionAgreement);
     let (uncategorized_file, uncategorized_file_provider_file_id) =
-        retrieve_file_and_provider_file_id_from_file_id(
-            state,
-            evidence_request.uncategorized_file,
-            merchant_account,
-            key_store,
-            api::FileDataRequired::NotRequired,
-        )
-        .await?;
+        get_evidence_file(EvidenceType::UncategorizedFile);
     Ok(SubmitEvidenceRequestData {
         dispute_id: dispute.dispute_id.clone(),
         connector_dispute_id: dispute.connector_dispute_id.clone(),
@@ -150,6 +257,7 @@ pub async fn get_evidence_request_data(
         uncategorized_file,
         uncategorized_file_provider_file_id,
         uncategorized_text: evidence_request.uncategorized_text,
+        evidence_bundle,
     })
 }
 
@@ -317,6 +317,10 @@ pub enum ConnectorError {
     InSufficientBalanceInPaymentMethod,
     #[error("Server responded with Request Timeout")]
     RequestTimeoutReceived,
+    #[error("Requests to {connector} exceeded its rate limit")]
+    RequestThrottled { connector: String },
+    #[error("Failed to sign the request sent to the connector")]
+    RequestSigningFailed,
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -347,6 +351,32 @@ pub enum VaultError {
     SavePaymentMethodFailed,
 }
 
+#[derive(Debug, thiserror::Error)]
+pub enum NetworkTokenizationError {
+    #[error("Network tokenization is not supported for the card network")]
+    CardNetworkNotSupported,
+    #[error("Failed to encode token service provider request")]
+    RequestEncodingFailed,
+    #[error("Failed to deserialize token service provider response")]
+    ResponseDeserializationFailed,
+    #[error("Failed to provision the network token")]
+    TokenProvisioningFailed,
+    #[error("Failed to fetch the network token data")]
+    FetchTokenDataFailed,
+    #[error("Failed to delete the network token")]
+    TokenDeletionFailed,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum CurrencyConversionError {
+    #[error("No exchange rate is available for the currency pair")]
+    RateNotAvailable,
+    #[error("Failed to fetch the exchange rate")]
+    RateFetchFailed,
+    #[error("Failed to deserialize exchange rate provider response")]
+    ResponseDeserializationFailed,
+}
+
 #[derive(Debug, thiserror::Error)]
 pub enum KmsError {
     #[error("Failed to base64 decode input data")]
@@ -403,6 +433,8 @@ pub enum ProcessTrackerError {
     EParsingError(error_stack::Report<ParsingError>),
     #[error("Validation Error Received: {0}")]
     EValidationError(error_stack::Report<ValidationError>),
+    #[error("Received Error WebhooksFlowError: {0}")]
+    EWebhooksFlowError(error_stack::Report<WebhooksFlowError>),
     #[error("Type Conversion error")]
     TypeConversionError,
 }
@@ -450,6 +482,11 @@ error_to_process_tracker_error!(
     ProcessTrackerError::EValidationError(error_stack::Report<ValidationError>)
 );
 
+error_to_process_tracker_error!(
+    error_stack::Report<WebhooksFlowError>,
+    ProcessTrackerError::EWebhooksFlowError(error_stack::Report<WebhooksFlowError>)
+);
+
 #[derive(Debug, thiserror::Error)]
 pub enum WebhooksFlowError {
     #[error("Merchant webhook config not found")]
@@ -458,6 +495,10 @@ pub enum WebhooksFlowError {
     MerchantWebhookDetailsNotFound,
     #[error("Merchant does not have a webhook URL configured")]
     MerchantWebhookURLNotConfigured,
+    #[error("Client certificate configured for merchant webhooks is invalid")]
+    MerchantWebhookClientCertificateInvalid,
+    #[error("Mutual TLS handshake with the merchant webhook endpoint failed")]
+    MerchantWebhookMtlsHandshakeFailed,
     #[error("Payments core flow failed")]
     PaymentsCoreFailed,
     #[error("Refunds core flow failed")]
@@ -488,10 +529,22 @@ pub enum WebhooksFlowError {
     DisputeWebhookValidationFailed,
     #[error("Outgoing webhook body encoding failed")]
     OutgoingWebhookEncodingFailed,
+    #[error("Queueing the outgoing webhook for a paused webhook endpoint failed")]
+    OutgoingWebhookQueueingFailed,
+    #[error("Scheduling a retry of the outgoing webhook failed")]
+    OutgoingWebhookRetrySchedulingFailed,
     #[error("Missing required field: {field_name}")]
     MissingRequiredField { field_name: &'static str },
 }
 
+#[derive(Debug, thiserror::Error)]
+pub enum HookError {
+    #[error("Operation rejected by the hook: {reason}")]
+    Rejected { reason: String },
+    #[error("Failed to execute the hook")]
+    ExecutionFailed,
+}
+
 impl ApiClientError {
     pub fn is_upstream_timeout(&self) -> bool {
         self == &Self::RequestTimeoutReceived
@@ -94,6 +94,26 @@ pub enum ApiErrorResponse {
     FileProviderNotSupported { message: String },
     #[error(error_type = ErrorType::InvalidRequestError, code = "IR_23", message = "{entity} expired or invalid")]
     UnprocessableEntity { entity: String },
+    #[error(error_type = ErrorType::InvalidRequestError, code = "IR_24", message = "Too many attempts were made to confirm this payment, try again later")]
+    ConfirmAttemptsExceeded,
+    #[error(error_type = ErrorType::ProcessingError, code = "IR_25", message = "The payment was declined as it exceeds the {limit} velocity limit of the {scope}")]
+    VelocityLimitExceeded { limit: String, scope: String },
+    #[error(error_type = ErrorType::InvalidRequestError, code = "IR_26", message = "The request body does not match the API specification")]
+    SchemaValidationFailed {
+        diagnostics: Vec<crate::utils::schema_validation::SchemaDiagnostic>,
+    },
+    #[error(error_type = ErrorType::InvalidRequestError, code = "IR_27", message = "The Apple Pay payment token could not be decrypted")]
+    ApplePayDecryptionFailed,
+    #[error(error_type = ErrorType::InvalidRequestError, code = "IR_28", message = "The {connector} connector rejected the account details")]
+    ConnectorAuthenticationFailed {
+        connector: String,
+        reason: Option<String>,
+    },
+    #[error(error_type = ErrorType::InvalidRequestError, code = "IR_29", message = "The account details could not be verified with the {connector} connector")]
+    ConnectorVerificationInconclusive {
+        connector: String,
+        reason: Option<String>,
+    },
     #[error(error_type = ErrorType::ConnectorError, code = "CE_00", message = "{code}: {message}", ignore = "status_code")]
     ExternalConnectorError {
         code: String,
@@ -120,6 +140,10 @@ pub enum ApiErrorResponse {
     VerificationFailed { data: Option<serde_json::Value> },
     #[error(error_type = ErrorType::ProcessingError, code = "CE_08", message = "Dispute operation failed while processing with connector. Retry operation")]
     DisputeFailed { data: Option<serde_json::Value> },
+    #[error(error_type = ErrorType::ProcessingError, code = "CE_09", message = "{connector} is under maintenance, try again later")]
+    ConnectorUnderMaintenance { connector: String },
+    #[error(error_type = ErrorType::ProcessingError, code = "CE_10", message = "Too many requests are being made to {connector}, try again later")]
+    ConnectorRequestThrottled { connector: String },
 
     #[error(error_type = ErrorType::ServerNotAvailable, code = "HE_00", message = "Something went wrong")]
     InternalServerError,
@@ -137,6 +161,8 @@ pub enum ApiErrorResponse {
     DuplicatePayment { payment_id: String },
     #[error(error_type = ErrorType::DuplicateRequest, code = "HE_01", message = "The payout with the specified payout_id '{payout_id}' already exists in our records")]
     DuplicatePayout { payout_id: String },
+    #[error(error_type = ErrorType::DuplicateRequest, code = "HE_01", message = "The refund with the specified merchant_refund_reference '{merchant_refund_reference}' was already created for a different payment or amount")]
+    DuplicateMerchantRefundReference { merchant_refund_reference: String },
     #[error(error_type = ErrorType::DuplicateRequest, code = "HE_01", message = "The config with the specified key already exists in our records")]
     DuplicateConfig,
     #[error(error_type = ErrorType::ObjectNotFound, code = "HE_02", message = "Refund does not exist in our records")]
@@ -163,6 +189,24 @@ pub enum ApiErrorResponse {
     ApiKeyNotFound,
     #[error(error_type = ErrorType::ObjectNotFound, code = "HE_02", message = "Payout does not exist in our records")]
     PayoutNotFound,
+    #[error(error_type = ErrorType::ObjectNotFound, code = "HE_02", message = "Payout batch does not exist in our records")]
+    PayoutBatchNotFound { batch_id: String },
+    #[error(error_type = ErrorType::ObjectNotFound, code = "HE_02", message = "Terminal does not exist in our records")]
+    TerminalNotFound { terminal_id: String },
+    #[error(error_type = ErrorType::ObjectNotFound, code = "HE_02", message = "Invoice does not exist in our records")]
+    InvoiceNotFound { invoice_id: String },
+    #[error(error_type = ErrorType::ObjectNotFound, code = "HE_02", message = "Gift card does not exist in our records")]
+    GiftCardNotFound { gift_card_id: String },
+    #[error(error_type = ErrorType::ObjectNotFound, code = "HE_02", message = "Report schedule does not exist in our records")]
+    ReportScheduleNotFound { report_schedule_id: String },
+    #[error(error_type = ErrorType::ObjectNotFound, code = "HE_02", message = "Customer import does not exist in our records")]
+    CustomerImportJobNotFound { job_id: String },
+    #[error(error_type = ErrorType::ObjectNotFound, code = "HE_02", message = "Payment link does not exist in our records")]
+    PaymentLinkNotFound { payment_link_id: String },
+    #[error(error_type = ErrorType::ObjectNotFound, code = "HE_02", message = "Payment method share does not exist in our records")]
+    PaymentMethodShareNotFound { share_id: String },
+    #[error(error_type = ErrorType::ObjectNotFound, code = "HE_02", message = "Subscription does not exist in our records")]
+    SubscriptionNotFound { subscription_id: String },
     #[error(error_type = ErrorType::ValidationError, code = "HE_03", message = "Invalid mandate id passed from connector")]
     MandateSerializationFailed,
     #[error(error_type = ErrorType::ValidationError, code = "HE_03", message = "Unable to parse the mandate identifier passed from connector")]
@@ -197,6 +241,8 @@ pub enum ApiErrorResponse {
     InvalidCardIinLength,
     #[error(error_type = ErrorType::ValidationError, code = "HE_03", message = "File validation failed")]
     FileValidationFailed { reason: String },
+    #[error(error_type = ErrorType::ValidationError, code = "HE_03", message = "The uploaded file was rejected by the malware scanner")]
+    FileInfected { threat: String },
     #[error(error_type = ErrorType::InvalidRequestError, code = "HE_04", message = "File not found / valid in the request")]
     MissingFile,
     #[error(error_type = ErrorType::InvalidRequestError, code = "HE_04", message = "Dispute id not found in the request")]
@@ -221,6 +267,10 @@ pub enum ApiErrorResponse {
     IncorrectPaymentMethodConfiguration,
     #[error(error_type = ErrorType::InvalidRequestError, code = "WE_05", message = "Unable to process the webhook body")]
     WebhookUnprocessableEntity,
+    #[error(error_type = ErrorType::ServerNotAvailable, code = "WE_06", message = "Too many webhooks are waiting to be processed, try again later")]
+    WebhookQueueFull,
+    #[error(error_type = ErrorType::ServerNotAvailable, code = "HE_00", message = "The server is overloaded, try again later")]
+    ServerOverloaded,
 }
 
 #[derive(Clone)]
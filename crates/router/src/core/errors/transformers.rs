@@ -91,6 +91,18 @@ impl ErrorSwitch<api_models::errors::types::ApiErrorResponse> for ApiErrorRespon
                 AER::BadRequest(ApiError::new("IR", 23, message.to_string(), None))
             },
             Self::UnprocessableEntity {entity} => AER::Unprocessable(ApiError::new("IR", 23, format!("{entity} expired or invalid"), None)),
+            Self::ConfirmAttemptsExceeded => AER::TooManyRequests(ApiError::new("IR", 24, "Too many attempts were made to confirm this payment, try again later", None)),
+            Self::VelocityLimitExceeded { limit, scope } => AER::BadRequest(ApiError::new("IR", 25, format!("The payment was declined as it exceeds the {limit} velocity limit of the {scope}"), None)),
+            Self::SchemaValidationFailed { diagnostics } => AER::BadRequest(
+                ApiError::new("IR", 26, "The request body does not match the API specification", Some(Extra { data: Some(serde_json::json!(diagnostics)), ..Default::default() })),
+            ),
+            Self::ApplePayDecryptionFailed => AER::BadRequest(ApiError::new("IR", 27, "The Apple Pay payment token could not be decrypted", None)),
+            Self::ConnectorAuthenticationFailed { connector, reason } => AER::BadRequest(
+                ApiError::new("IR", 28, format!("The {connector} connector rejected the account details"), Some(Extra { connector: Some(connector.clone()), reason: reason.clone(), ..Default::default() })),
+            ),
+            Self::ConnectorVerificationInconclusive { connector, reason } => AER::BadRequest(
+                ApiError::new("IR", 29, format!("The account details could not be verified with the {connector} connector"), Some(Extra { connector: Some(connector.clone()), reason: reason.clone(), ..Default::default() })),
+            ),
             Self::ExternalConnectorError {
                 code,
                 message,
@@ -116,6 +128,12 @@ impl ErrorSwitch<api_models::errors::types::ApiErrorResponse> for ApiErrorRespon
             Self::VerificationFailed { data } => {
                 AER::BadRequest(ApiError::new("CE", 7, "Verification failed while processing with connector. Retry operation", Some(Extra { data: data.clone(), ..Default::default()})))
             },
+            Self::ConnectorUnderMaintenance { connector } => {
+                AER::ConnectorError(ApiError::new("CE", 9, format!("{connector} is under maintenance, try again later"), Some(Extra { connector: Some(connector.clone()), ..Default::default()})), StatusCode::SERVICE_UNAVAILABLE)
+            }
+            Self::ConnectorRequestThrottled { connector } => {
+                AER::ConnectorError(ApiError::new("CE", 10, format!("Too many requests are being made to {connector}, try again later"), Some(Extra { connector: Some(connector.clone()), ..Default::default()})), StatusCode::TOO_MANY_REQUESTS)
+            }
             Self::MandateUpdateFailed | Self::MandateSerializationFailed | Self::MandateDeserializationFailed | Self::InternalServerError => {
                 AER::InternalServerError(ApiError::new("HE", 0, "Something went wrong", None))
             }
@@ -135,6 +153,9 @@ impl ErrorSwitch<api_models::errors::types::ApiErrorResponse> for ApiErrorRespon
             Self::DuplicatePayout { payout_id } => {
                 AER::BadRequest(ApiError::new("HE", 1, format!("The payout with the specified payout_id '{payout_id}' already exists in our records"), None))
             }
+            Self::DuplicateMerchantRefundReference { merchant_refund_reference } => {
+                AER::BadRequest(ApiError::new("HE", 1, format!("The refund with the specified merchant_refund_reference '{merchant_refund_reference}' was already created for a different payment or amount"), None))
+            }
             Self::GenericDuplicateError { message } => {
                 AER::BadRequest(ApiError::new("HE", 1, message, None))
             }
@@ -174,6 +195,12 @@ impl ErrorSwitch<api_models::errors::types::ApiErrorResponse> for ApiErrorRespon
             Self::PayoutNotFound => {
                 AER::NotFound(ApiError::new("HE", 2, "Payout does not exist in our records", None))
             }
+            Self::PayoutBatchNotFound { .. } => AER::NotFound(ApiError::new(
+                "HE",
+                2,
+                "Payout batch does not exist in our records",
+                None,
+            )),
             Self::ReturnUrlUnavailable => AER::NotFound(ApiError::new("HE", 3, "Return URL is not configured and not passed in payments request", None)),
             Self::RefundNotPossible { connector } => {
                 AER::BadRequest(ApiError::new("HE", 3, format!("This refund is not possible through Hyperswitch. Please raise the refund through {connector} dashboard"), None))
@@ -208,6 +235,45 @@ impl ErrorSwitch<api_models::errors::types::ApiErrorResponse> for ApiErrorRespon
             Self::DisputeNotFound { .. } => {
                 AER::NotFound(ApiError::new("HE", 2, "Dispute does not exist in our records", None))
             }
+            Self::TerminalNotFound { .. } => {
+                AER::NotFound(ApiError::new("HE", 2, "Terminal does not exist in our records", None))
+            }
+            Self::InvoiceNotFound { .. } => {
+                AER::NotFound(ApiError::new("HE", 2, "Invoice does not exist in our records", None))
+            }
+            Self::GiftCardNotFound { .. } => {
+                AER::NotFound(ApiError::new("HE", 2, "Gift card does not exist in our records", None))
+            }
+            Self::ReportScheduleNotFound { .. } => AER::NotFound(ApiError::new(
+                "HE",
+                2,
+                "Report schedule does not exist in our records",
+                None,
+            )),
+            Self::CustomerImportJobNotFound { .. } => AER::NotFound(ApiError::new(
+                "HE",
+                2,
+                "Customer import does not exist in our records",
+                None,
+            )),
+            Self::PaymentLinkNotFound { .. } => AER::NotFound(ApiError::new(
+                "HE",
+                2,
+                "Payment link does not exist in our records",
+                None,
+            )),
+            Self::PaymentMethodShareNotFound { .. } => AER::NotFound(ApiError::new(
+                "HE",
+                2,
+                "Payment method share does not exist in our records",
+                None,
+            )),
+            Self::SubscriptionNotFound { .. } => AER::NotFound(ApiError::new(
+                "HE",
+                2,
+                "Subscription does not exist in our records",
+                None,
+            )),
             Self::FileNotFound => {
                 AER::NotFound(ApiError::new("HE", 2, "File does not exist in our records", None))
             }
@@ -220,6 +286,9 @@ impl ErrorSwitch<api_models::errors::types::ApiErrorResponse> for ApiErrorRespon
             Self::FileValidationFailed { reason } => {
                 AER::BadRequest(ApiError::new("HE", 2, format!("File validation failed {reason}"), None))
             }
+            Self::FileInfected { threat } => {
+                AER::BadRequest(ApiError::new("HE", 3, "The uploaded file was rejected by the malware scanner", Some(Extra { reason: Some(format!("Threat detected: {threat}")), ..Default::default() })))
+            }
             Self::MissingFile => {
                 AER::BadRequest(ApiError::new("HE", 2, "File not found in the request", None))
             }
@@ -250,6 +319,12 @@ impl ErrorSwitch<api_models::errors::types::ApiErrorResponse> for ApiErrorRespon
             Self::WebhookUnprocessableEntity => {
                 AER::Unprocessable(ApiError::new("WE", 5, "There was an issue processing the webhook body", None))
             }
+            Self::WebhookQueueFull => {
+                AER::TooManyRequests(ApiError::new("WE", 6, "Too many webhooks are waiting to be processed, try again later", None))
+            }
+            Self::ServerOverloaded => {
+                AER::TooManyRequests(ApiError::new("HE", 0, "The server is overloaded, try again later", None))
+            }
         }
     }
 }
@@ -136,6 +136,9 @@ impl<T> ConnectorErrorExt<T> for error_stack::Result<T, errors::ConnectorError>
                 errors::ConnectorError::InvalidDataFormat { field_name } => {
                     errors::ApiErrorResponse::InvalidDataValue { field_name }
                 },
+                errors::ConnectorError::RequestThrottled { connector } => {
+                    errors::ApiErrorResponse::ConnectorRequestThrottled { connector: connector.to_owned() }
+                },
                 _ => errors::ApiErrorResponse::InternalServerError,
             };
             err.change_context(error)
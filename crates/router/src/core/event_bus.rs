@@ -0,0 +1,55 @@
+//! In-process bus of the events raised by the core flows.
+//!
+//! The bus is shared by all the requests handled by an instance of the application, and allows
+//! requests to wait for changes to resources instead of polling the database in a loop. Events
+//! are only delivered to subscribers within the same instance, so subscribers which need to
+//! observe changes made by other instances must still re-read the resource periodically.
+
+use router_env::logger;
+use tokio::sync::broadcast;
+
+use crate::types::storage::enums;
+
+/// Number of events retained for subscribers which have not received them yet. Subscribers which
+/// lag behind by more events miss the oldest ones.
+const EVENT_BUS_CAPACITY: usize = 1024;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Event {
+    /// The status of a payment intent was updated.
+    PaymentStatusChanged {
+        merchant_id: String,
+        payment_id: String,
+        status: enums::IntentStatus,
+    },
+}
+
+#[derive(Debug)]
+pub struct EventBus {
+    sender: broadcast::Sender<Event>,
+}
+
+impl Default for EventBus {
+    fn default() -> Self {
+        let (sender, _) = broadcast::channel(EVENT_BUS_CAPACITY);
+        Self { sender }
+    }
+}
+
+impl EventBus {
+    /// Delivers the event to all the current subscribers. Events published while there are no
+    /// subscribers are dropped.
+    pub fn publish(&self, event: Event) {
+        if self.sender.receiver_count() == 0 {
+            return;
+        }
+        if let Err(error) = self.sender.send(event) {
+            logger::debug!(event_bus_publish_error=?error);
+        }
+    }
+
+    /// Returns a receiver of the events published after this call.
+    pub fn subscribe(&self) -> broadcast::Receiver<Event> {
+        self.sender.subscribe()
+    }
+}
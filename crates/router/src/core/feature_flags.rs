@@ -0,0 +1,301 @@
+//! Merchant scoped feature flags, gating capabilities which are rolled out gradually.
+//!
+//! The rollout of each flag is managed through the admin API and stored in the configs table. A
+//! flag is enabled for a merchant when it has been overridden as enabled for the merchant, or,
+//! when it has not been overridden, when the merchant falls within the rollout percentage of the
+//! flag. Merchants are assigned to a stable bucket per flag, so that increasing the percentage only
+//! ever enables the flag for more merchants. Flags which have not been configured use their
+//! default rollout.
+
+use std::collections::HashMap;
+
+use api_models::feature_flags::{self as feature_flag_types, FeatureFlag};
+use error_stack::{report, ResultExt};
+use router_env::logger;
+use strum::IntoEnumIterator;
+
+use super::errors::{self, CustomResult, RouterResponse, RouterResult, StorageErrorExt};
+use crate::{
+    db::StorageInterface,
+    routes::AppState,
+    services,
+    types::storage,
+    utils::{self, Encode, StringExt},
+};
+
+const FEATURE_FLAG_KEY_PREFIX: &str = "feature_flag";
+const MAX_ROLLOUT_PERCENTAGE: u8 = 100;
+
+/// The rollout of a flag, as stored in the configs table.
+#[derive(Clone, Debug, Default, PartialEq, serde::Deserialize, serde::Serialize)]
+struct FeatureFlagConfig {
+    rollout_percentage: u8,
+    #[serde(default)]
+    merchant_overrides: HashMap<String, bool>,
+}
+
+fn get_config_key(flag: FeatureFlag) -> String {
+    format!("{FEATURE_FLAG_KEY_PREFIX}_{flag}")
+}
+
+/// The rollout of flags which have not been configured. Flags gating capabilities which were
+/// available before the flag was introduced are enabled for all merchants by default.
+fn get_default_rollout_percentage(flag: FeatureFlag) -> u8 {
+    match flag {
+        FeatureFlag::WebhookQueue | FeatureFlag::WebhookSourceValidation => MAX_ROLLOUT_PERCENTAGE,
+    }
+}
+
+fn get_default_config(flag: FeatureFlag) -> FeatureFlagConfig {
+    FeatureFlagConfig {
+        rollout_percentage: get_default_rollout_percentage(flag),
+        merchant_overrides: HashMap::new(),
+    }
+}
+
+/// Returns the bucket, between 0 and 99, into which the merchant falls for the flag.
+fn get_rollout_bucket(flag: FeatureFlag, merchant_id: &str) -> u32 {
+    crc32fast::hash(format!("{flag}:{merchant_id}").as_bytes()) % u32::from(MAX_ROLLOUT_PERCENTAGE)
+}
+
+fn is_enabled_for_merchant(
+    flag: FeatureFlag,
+    config: &FeatureFlagConfig,
+    merchant_id: &str,
+) -> bool {
+    config
+        .merchant_overrides
+        .get(merchant_id)
+        .copied()
+        .unwrap_or_else(|| {
+            get_rollout_bucket(flag, merchant_id) < u32::from(config.rollout_percentage)
+        })
+}
+
+async fn find_feature_flag_config(
+    db: &dyn StorageInterface,
+    flag: FeatureFlag,
+    use_cache: bool,
+) -> CustomResult<Option<FeatureFlagConfig>, errors::StorageError> {
+    let key = get_config_key(flag);
+    let config = if use_cache {
+        db.find_config_by_key_cached(&key).await
+    } else {
+        db.find_config_by_key(&key).await
+    };
+
+    match config {
+        Ok(config) => config
+            .config
+            .parse_struct("FeatureFlagConfig")
+            .change_context(errors::StorageError::DeserializationFailed)
+            .map(Some),
+        Err(error) if error.current_context().is_db_not_found() => Ok(None),
+        Err(error) => Err(error),
+    }
+}
+
+/// Returns whether the flag is enabled for the merchant. The default rollout of the flag is used
+/// when its configuration cannot be retrieved, so that flows are not failed because of it.
+pub async fn is_feature_enabled(state: &AppState, flag: FeatureFlag, merchant_id: &str) -> bool {
+    let config = find_feature_flag_config(&*state.store, flag, true)
+        .await
+        .map_err(|error| logger::error!(%flag, feature_flag_error=?error))
+        .ok()
+        .flatten()
+        .unwrap_or_else(|| get_default_config(flag));
+
+    is_enabled_for_merchant(flag, &config, merchant_id)
+}
+
+async fn store_feature_flag_config(
+    db: &dyn StorageInterface,
+    flag: FeatureFlag,
+    config: &FeatureFlagConfig,
+    is_existing: bool,
+) -> RouterResult<()> {
+    let key = get_config_key(flag);
+    let config = Encode::<FeatureFlagConfig>::encode_to_string_of_json(config)
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to encode the feature flag config")?;
+
+    if is_existing {
+        db.update_config_cached(
+            &key,
+            storage::ConfigUpdate::Update {
+                config: Some(config),
+            },
+        )
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable_lazy(|| format!("Failed to update the feature flag {flag}"))?;
+    } else {
+        db.insert_config(storage::ConfigNew { key, config })
+            .await
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable_lazy(|| format!("Failed to insert the feature flag {flag}"))?;
+    }
+
+    Ok(())
+}
+
+/// Reads the configuration of the flag bypassing the cache, so that it can be modified.
+async fn find_feature_flag_config_for_update(
+    db: &dyn StorageInterface,
+    flag: FeatureFlag,
+) -> RouterResult<(FeatureFlagConfig, bool)> {
+    let config = find_feature_flag_config(db, flag, false)
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable_lazy(|| format!("Failed to find the feature flag {flag}"))?;
+
+    Ok(match config {
+        Some(config) => (config, true),
+        None => (get_default_config(flag), false),
+    })
+}
+
+fn to_response(
+    flag: FeatureFlag,
+    config: Option<FeatureFlagConfig>,
+) -> feature_flag_types::FeatureFlagResponse {
+    let is_default = config.is_none();
+    let config = config.unwrap_or_else(|| get_default_config(flag));
+    feature_flag_types::FeatureFlagResponse {
+        flag,
+        rollout_percentage: config.rollout_percentage,
+        merchant_overrides: config.merchant_overrides,
+        is_default,
+    }
+}
+
+pub async fn list_feature_flags(
+    state: &AppState,
+) -> RouterResponse<feature_flag_types::FeatureFlagListResponse> {
+    let mut flags = Vec::new();
+    for flag in FeatureFlag::iter() {
+        let config = find_feature_flag_config(&*state.store, flag, false)
+            .await
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable_lazy(|| format!("Failed to find the feature flag {flag}"))?;
+        flags.push(to_response(flag, config));
+    }
+
+    Ok(services::ApplicationResponse::Json(
+        feature_flag_types::FeatureFlagListResponse { flags },
+    ))
+}
+
+pub async fn retrieve_feature_flag(
+    state: &AppState,
+    flag: FeatureFlag,
+) -> RouterResponse<feature_flag_types::FeatureFlagResponse> {
+    let config = find_feature_flag_config(&*state.store, flag, false)
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable_lazy(|| format!("Failed to find the feature flag {flag}"))?;
+
+    Ok(services::ApplicationResponse::Json(to_response(
+        flag, config,
+    )))
+}
+
+pub async fn update_feature_flag(
+    state: &AppState,
+    flag: FeatureFlag,
+    req: feature_flag_types::FeatureFlagUpdateRequest,
+) -> RouterResponse<feature_flag_types::FeatureFlagResponse> {
+    utils::when(req.rollout_percentage > MAX_ROLLOUT_PERCENTAGE, || {
+        Err(report!(errors::ApiErrorResponse::InvalidRequestData {
+            message: format!("`rollout_percentage` must be between 0 and {MAX_ROLLOUT_PERCENTAGE}"),
+        }))
+    })?;
+
+    let db = &*state.store;
+    let (mut config, is_existing) = find_feature_flag_config_for_update(db, flag).await?;
+    config.rollout_percentage = req.rollout_percentage;
+    store_feature_flag_config(db, flag, &config, is_existing).await?;
+
+    logger::info!(
+        %flag,
+        rollout_percentage = config.rollout_percentage,
+        "Updated the rollout of the feature flag"
+    );
+
+    Ok(services::ApplicationResponse::Json(to_response(
+        flag,
+        Some(config),
+    )))
+}
+
+pub async fn update_merchant_feature_flag_override(
+    state: &AppState,
+    flag: FeatureFlag,
+    merchant_id: String,
+    req: feature_flag_types::MerchantFeatureFlagOverrideRequest,
+) -> RouterResponse<feature_flag_types::FeatureFlagResponse> {
+    let db = &*state.store;
+    db.get_merchant_key_store_by_merchant_id(&merchant_id, &db.get_master_key().to_vec().into())
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::MerchantAccountNotFound)?;
+
+    let (mut config, is_existing) = find_feature_flag_config_for_update(db, flag).await?;
+    config.merchant_overrides.insert(merchant_id, req.enabled);
+    store_feature_flag_config(db, flag, &config, is_existing).await?;
+
+    Ok(services::ApplicationResponse::Json(to_response(
+        flag,
+        Some(config),
+    )))
+}
+
+pub async fn delete_merchant_feature_flag_override(
+    state: &AppState,
+    flag: FeatureFlag,
+    merchant_id: String,
+) -> RouterResponse<feature_flag_types::FeatureFlagResponse> {
+    let db = &*state.store;
+    let (mut config, is_existing) = find_feature_flag_config_for_update(db, flag).await?;
+    config.merchant_overrides.remove(&merchant_id).ok_or(
+        errors::ApiErrorResponse::GenericNotFoundError {
+            message: format!("The feature flag {flag} is not overridden for the merchant"),
+        },
+    )?;
+    store_feature_flag_config(db, flag, &config, is_existing).await?;
+
+    Ok(services::ApplicationResponse::Json(to_response(
+        flag,
+        Some(config),
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_enabled_for_merchant() {
+        let flag = FeatureFlag::WebhookQueue;
+        let mut config = FeatureFlagConfig::default();
+        assert!(!is_enabled_for_merchant(flag, &config, "merchant_1"));
+
+        config.rollout_percentage = MAX_ROLLOUT_PERCENTAGE;
+        assert!(is_enabled_for_merchant(flag, &config, "merchant_1"));
+
+        config
+            .merchant_overrides
+            .insert("merchant_1".to_string(), false);
+        assert!(!is_enabled_for_merchant(flag, &config, "merchant_1"));
+        assert!(is_enabled_for_merchant(flag, &config, "merchant_2"));
+    }
+
+    #[test]
+    fn test_rollout_bucket_is_stable() {
+        let bucket = get_rollout_bucket(FeatureFlag::WebhookQueue, "merchant_1");
+        assert!(bucket < u32::from(MAX_ROLLOUT_PERCENTAGE));
+        assert_eq!(
+            bucket,
+            get_rollout_bucket(FeatureFlag::WebhookQueue, "merchant_1")
+        );
+    }
+}
@@ -24,6 +24,9 @@ pub async fn files_create_core(
 ) -> RouterResponse<files::CreateFileResponse> {
     helpers::validate_file_upload(state, merchant_account.clone(), create_file_request.clone())
         .await?;
+    // Scan the file before it is persisted or forwarded to the connector
+    #[cfg(feature = "file_scanning")]
+    helpers::scan_file_for_malware(state, &create_file_request).await?;
     let file_id = common_utils::generate_id(consts::ID_LENGTH, "file");
     #[cfg(feature = "s3")]
     let file_key = format!("{}/{}", merchant_account.merchant_id, file_id);
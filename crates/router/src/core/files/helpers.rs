@@ -1,8 +1,13 @@
+#[cfg(feature = "payouts")]
+use std::str::FromStr;
+
 use actix_multipart::Field;
 use common_utils::errors::CustomResult;
 use error_stack::{IntoReport, ResultExt};
 use futures::TryStreamExt;
 
+#[cfg(feature = "payouts")]
+use crate::{core::payouts::PayoutData, utils::OptionExt};
 use crate::{
     core::{
         errors::{self, StorageErrorExt},
@@ -32,6 +37,7 @@ pub async fn get_file_purpose(field: &mut Field) -> Option<api::FilePurpose> {
     let purpose = read_string(field).await;
     match purpose.as_deref() {
         Some("dispute_evidence") => Some(api::FilePurpose::DisputeEvidence),
+        Some("payout_kyc") => Some(api::FilePurpose::PayoutKyc),
         _ => None,
     }
 }
@@ -67,6 +73,34 @@ pub async fn retrieve_file(
     return files::fs_utils::retrieve_file_from_fs(file_key);
 }
 
+#[cfg(feature = "file_scanning")]
+pub async fn scan_file_for_malware(
+    state: &AppState,
+    create_file_request: &api::CreateFileRequest,
+) -> CustomResult<(), errors::ApiErrorResponse> {
+    use external_services::file_scanning::ScanVerdict;
+
+    let Some(file_scanner) = state.file_scanner.as_ref() else {
+        return Ok(());
+    };
+    let verdict = file_scanner
+        .scan_file(&create_file_request.file)
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed while scanning the uploaded file")?;
+    match verdict {
+        ScanVerdict::Clean => Ok(()),
+        ScanVerdict::Infected { threat } => {
+            router_env::logger::warn!(
+                file_name = ?create_file_request.file_name,
+                %threat,
+                "Uploaded file was flagged by the malware scanner"
+            );
+            Err(errors::ApiErrorResponse::FileInfected { threat }).into_report()
+        }
+    }
+}
+
 pub async fn validate_file_upload(
     state: &AppState,
     merchant_account: domain::MerchantAccount,
@@ -75,8 +109,9 @@ pub async fn validate_file_upload(
     //File Validation based on the purpose of file upload
     match create_file_request.purpose {
         api::FilePurpose::DisputeEvidence => {
-            let dispute_id = &create_file_request
+            let dispute_id = create_file_request
                 .dispute_id
+                .as_ref()
                 .ok_or(errors::ApiErrorResponse::MissingDisputeId)?;
             let dispute = state
                 .store
@@ -85,35 +120,103 @@ pub async fn validate_file_upload(
                 .to_not_found_response(errors::ApiErrorResponse::DisputeNotFound {
                     dispute_id: dispute_id.to_string(),
                 })?;
-            let connector_data = api::ConnectorData::get_connector_by_name(
-                &state.conf.connectors,
-                &dispute.connector,
-                api::GetToken::Connector,
-            )?;
-            let validation = connector_data.connector.validate_file_upload(
-                create_file_request.purpose,
-                create_file_request.file_size,
-                create_file_request.file_type.clone(),
-            );
-            match validation {
-                Ok(()) => Ok(()),
-                Err(err) => match err.current_context() {
-                    errors::ConnectorError::FileValidationFailed { reason } => {
-                        Err(errors::ApiErrorResponse::FileValidationFailed {
-                            reason: reason.to_string(),
-                        }
-                        .into())
-                    }
-                    //We are using parent error and ignoring this
-                    _error => Err(err
-                        .change_context(errors::ApiErrorResponse::InternalServerError)
-                        .attach_printable("File validation failed"))?,
-                },
+            validate_connector_file_upload(state, &dispute.connector, &create_file_request)
+        }
+        #[cfg(feature = "payouts")]
+        api::FilePurpose::PayoutKyc => {
+            let payout_id = create_file_request
+                .payout_id
+                .clone()
+                .get_required_value("payout_id")?;
+            let payout_data = get_payout_data(state, &merchant_account, &payout_id).await?;
+            let connector = &payout_data.payout_attempt.connector;
+            if is_payout_file_upload_supported(connector) {
+                validate_connector_file_upload(state, connector, &create_file_request)
+            } else {
+                Ok(())
             }
         }
+        #[cfg(not(feature = "payouts"))]
+        api::FilePurpose::PayoutKyc => Err(payout_kyc_not_supported()),
     }
 }
 
+/// Validates the size and type of a file against the requirements of the connector it is uploaded
+/// to
+fn validate_connector_file_upload(
+    state: &AppState,
+    connector: &str,
+    create_file_request: &api::CreateFileRequest,
+) -> CustomResult<(), errors::ApiErrorResponse> {
+    let connector_data = api::ConnectorData::get_connector_by_name(
+        &state.conf.connectors,
+        connector,
+        api::GetToken::Connector,
+    )?;
+    let validation = connector_data.connector.validate_file_upload(
+        create_file_request.purpose.clone(),
+        create_file_request.file_size,
+        create_file_request.file_type.clone(),
+    );
+    match validation {
+        Ok(()) => Ok(()),
+        Err(err) => match err.current_context() {
+            errors::ConnectorError::FileValidationFailed { reason } => {
+                Err(errors::ApiErrorResponse::FileValidationFailed {
+                    reason: reason.to_string(),
+                }
+                .into())
+            }
+            //We are using parent error and ignoring this
+            _error => Err(err
+                .change_context(errors::ApiErrorResponse::InternalServerError)
+                .attach_printable("File validation failed"))?,
+        },
+    }
+}
+
+/// Whether the KYC documents of the payouts processed by the connector are uploaded to it
+#[cfg(feature = "payouts")]
+fn is_payout_file_upload_supported(connector: &str) -> bool {
+    api_models::enums::PayoutConnectors::from_str(connector).map_or(false, |payout_connector| {
+        payout_connector.supports_file_upload()
+    })
+}
+
+#[cfg(feature = "payouts")]
+async fn get_payout_data(
+    state: &AppState,
+    merchant_account: &domain::MerchantAccount,
+    payout_id: &str,
+) -> CustomResult<PayoutData, errors::ApiErrorResponse> {
+    let payouts = state
+        .store
+        .find_payout_by_merchant_id_payout_id(&merchant_account.merchant_id, payout_id)
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::PayoutNotFound)?;
+    let payout_attempt = state
+        .store
+        .find_payout_attempt_by_merchant_id_payout_id(&merchant_account.merchant_id, payout_id)
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::PayoutNotFound)?;
+    Ok(PayoutData {
+        billing_address: None,
+        customer_details: None,
+        payouts,
+        payout_attempt,
+        payout_method_data: None,
+        merchant_connector_account: None,
+    })
+}
+
+#[cfg(not(feature = "payouts"))]
+fn payout_kyc_not_supported() -> error_stack::Report<errors::ApiErrorResponse> {
+    errors::ApiErrorResponse::NotSupported {
+        message: "File uploads for payouts".to_owned(),
+    }
+    .into()
+}
+
 pub async fn delete_file_using_file_id(
     state: &AppState,
     file_key: String,
@@ -223,44 +326,61 @@ pub async fn retrieve_file_and_provider_file_id_from_file_id(
                 .find_file_metadata_by_merchant_id_file_id(&merchant_account.merchant_id, &file_key)
                 .await
                 .change_context(errors::ApiErrorResponse::FileNotFound)?;
-            let (provider, provider_file_id) = match (
-                file_metadata_object.file_upload_provider,
-                file_metadata_object.provider_file_id.clone(),
-                file_metadata_object.available,
-            ) {
-                (Some(provider), Some(provider_file_id), true) => (provider, provider_file_id),
-                _ => Err(errors::ApiErrorResponse::FileNotAvailable)
-                    .into_report()
-                    .attach_printable("File not available")?,
+            retrieve_file_and_provider_file_id_from_file_metadata(
+                state,
+                file_metadata_object,
+                merchant_account,
+                key_store,
+                is_connector_file_data_required,
+            )
+            .await
+        }
+    }
+}
+
+pub async fn retrieve_file_and_provider_file_id_from_file_metadata(
+    state: &AppState,
+    file_metadata_object: diesel_models::file::FileMetadata,
+    merchant_account: &domain::MerchantAccount,
+    key_store: &domain::MerchantKeyStore,
+    is_connector_file_data_required: api::FileDataRequired,
+) -> CustomResult<(Option<Vec<u8>>, Option<String>), errors::ApiErrorResponse> {
+    let (provider, provider_file_id) = match (
+        file_metadata_object.file_upload_provider,
+        file_metadata_object.provider_file_id.clone(),
+        file_metadata_object.available,
+    ) {
+        (Some(provider), Some(provider_file_id), true) => (provider, provider_file_id),
+        _ => Err(errors::ApiErrorResponse::FileNotAvailable)
+            .into_report()
+            .attach_printable("File not available")?,
+    };
+    match provider {
+        diesel_models::enums::FileUploadProvider::Router => Ok((
+            Some(
+                retrieve_file(
+                    #[cfg(feature = "s3")]
+                    state,
+                    provider_file_id.clone(),
+                )
+                .await?,
+            ),
+            Some(provider_file_id),
+        )),
+        _ => {
+            let connector_file_data = match is_connector_file_data_required {
+                api::FileDataRequired::Required => Some(
+                    retrieve_file_from_connector(
+                        state,
+                        file_metadata_object,
+                        merchant_account,
+                        key_store,
+                    )
+                    .await?,
+                ),
+                api::FileDataRequired::NotRequired => None,
             };
-            match provider {
-                diesel_models::enums::FileUploadProvider::Router => Ok((
-                    Some(
-                        retrieve_file(
-                            #[cfg(feature = "s3")]
-                            state,
-                            provider_file_id.clone(),
-                        )
-                        .await?,
-                    ),
-                    Some(provider_file_id),
-                )),
-                _ => {
-                    let connector_file_data = match is_connector_file_data_required {
-                        api::FileDataRequired::Required => Some(
-                            retrieve_file_from_connector(
-                                state,
-                                file_metadata_object,
-                                merchant_account,
-                                key_store,
-                            )
-                            .await?,
-                        ),
-                        api::FileDataRequired::NotRequired => None,
-                    };
-                    Ok((connector_file_data, Some(provider_file_id)))
-                }
-            }
+            Ok((connector_file_data, Some(provider_file_id)))
         }
     }
 }
@@ -382,5 +502,118 @@ pub async fn upload_and_get_provider_provider_file_id_connector_label(
                 ))
             }
         }
+        #[cfg(feature = "payouts")]
+        api::FilePurpose::PayoutKyc => {
+            let payout_id = create_file_request
+                .payout_id
+                .clone()
+                .get_required_value("payout_id")?;
+            let payout_data = get_payout_data(state, merchant_account, &payout_id).await?;
+            if is_payout_file_upload_supported(&payout_data.payout_attempt.connector) {
+                upload_file_to_payout_connector(
+                    state,
+                    merchant_account,
+                    key_store,
+                    create_file_request,
+                    file_key,
+                    &payout_data,
+                )
+                .await
+            } else {
+                upload_file(
+                    #[cfg(feature = "s3")]
+                    state,
+                    file_key.clone(),
+                    create_file_request.file.clone(),
+                )
+                .await?;
+                Ok((
+                    file_key,
+                    api_models::enums::FileUploadProvider::Router,
+                    None,
+                ))
+            }
+        }
+        #[cfg(not(feature = "payouts"))]
+        api::FilePurpose::PayoutKyc => Err(payout_kyc_not_supported()),
     }
 }
+
+//Upload a KYC document for the beneficiary of a payout to the connector processing the payout
+#[cfg(feature = "payouts")]
+async fn upload_file_to_payout_connector(
+    state: &AppState,
+    merchant_account: &domain::MerchantAccount,
+    key_store: &domain::MerchantKeyStore,
+    create_file_request: &api::CreateFileRequest,
+    file_key: String,
+    payout_data: &PayoutData,
+) -> CustomResult<
+    (
+        String,
+        api_models::enums::FileUploadProvider,
+        Option<String>,
+    ),
+    errors::ApiErrorResponse,
+> {
+    let payout_attempt = &payout_data.payout_attempt;
+    let connector_data = api::ConnectorData::get_connector_by_name(
+        &state.conf.connectors,
+        &payout_attempt.connector,
+        api::GetToken::Connector,
+    )?;
+    let (business_country, business_label) = payments_helpers::get_business_details(
+        payout_attempt.business_country,
+        payout_attempt.business_label.as_ref(),
+        merchant_account,
+    )?;
+    let connector_label = payments_helpers::get_connector_label(
+        business_country,
+        &business_label,
+        None,
+        &payout_attempt.connector,
+    );
+    let connector_integration: services::BoxedConnectorIntegration<
+        '_,
+        api::PoUploadFile,
+        types::UploadFileRequestData,
+        types::UploadFileResponse,
+    > = connector_data.connector.get_connector_integration();
+    let router_data = utils::construct_payout_upload_file_router_data(
+        state,
+        &payout_attempt.connector,
+        merchant_account,
+        key_store,
+        create_file_request,
+        file_key,
+        payout_data,
+    )
+    .await
+    .change_context(errors::ApiErrorResponse::InternalServerError)
+    .attach_printable("Failed constructing the payout upload file router data")?;
+    let response = services::execute_connector_processing_step(
+        state,
+        connector_integration,
+        &router_data,
+        payments::CallConnectorAction::Trigger,
+        None,
+    )
+    .await
+    .change_context(errors::ApiErrorResponse::InternalServerError)
+    .attach_printable("Failed while calling payout upload file connector api")?;
+    let upload_file_response =
+        response
+            .response
+            .map_err(|err| errors::ApiErrorResponse::ExternalConnectorError {
+                code: err.code,
+                message: err.message,
+                connector: payout_attempt.connector.clone(),
+                status_code: err.status_code,
+                reason: err.reason,
+            })?;
+    Ok((
+        upload_file_response.provider_file_id,
+        api_models::enums::FileUploadProvider::foreign_try_from(&connector_data.connector_name)?,
+        Some(connector_label),
+    ))
+}
@@ -0,0 +1,612 @@
+//! Closed-loop gift cards.
+//!
+//! Gift cards are issued by a merchant with a balance which can only be redeemed with the same
+//! merchant. Every change to the balance of a gift card is recorded as a gift card transaction,
+//! so that the transactions of a gift card form a ledger of its balance.
+//!
+//! A gift card can be redeemed towards a payment when the payment is confirmed, in which case only
+//! the amount which is not covered by the gift card is charged to the payment method of the
+//! payment, and the amount redeemed is returned to the gift card if the payment fails.
+
+use common_utils::date_time;
+use error_stack::{report, IntoReport, ResultExt};
+use router_env::{instrument, tracing};
+
+use crate::{
+    consts,
+    core::{
+        errors::{self, RouterResponse, RouterResult, StorageErrorExt},
+        payments::PaymentData,
+    },
+    routes::AppState,
+    services::ApplicationResponse,
+    types::{
+        api::gift_cards as gift_card_types,
+        domain,
+        storage::{self, enums},
+    },
+    utils,
+};
+
+fn get_gift_card_response(gift_card: storage::GiftCard) -> gift_card_types::GiftCardResponse {
+    gift_card_types::GiftCardResponse {
+        gift_card_id: gift_card.gift_card_id,
+        merchant_id: gift_card.merchant_id,
+        customer_id: gift_card.customer_id,
+        status: gift_card.status,
+        currency: gift_card.currency,
+        initial_balance: gift_card.initial_balance,
+        balance: gift_card.balance,
+        expires_at: gift_card.expires_at,
+        metadata: gift_card.metadata,
+        created: gift_card.created_at,
+    }
+}
+
+fn get_gift_card_transaction_response(
+    transaction: storage::GiftCardTransaction,
+) -> gift_card_types::GiftCardTransactionResponse {
+    gift_card_types::GiftCardTransactionResponse {
+        transaction_id: transaction.transaction_id,
+        gift_card_id: transaction.gift_card_id,
+        transaction_type: transaction.transaction_type,
+        amount: transaction.amount,
+        balance_after: transaction.balance_after,
+        currency: transaction.currency,
+        payment_id: transaction.payment_id,
+        created: transaction.created_at,
+    }
+}
+
+fn validate_amount(amount: i64) -> RouterResult<()> {
+    utils::when(amount <= 0, || {
+        Err(report!(errors::ApiErrorResponse::InvalidRequestData {
+            message: "The amount must be greater than zero".to_string(),
+        }))
+    })
+}
+
+async fn find_gift_card(
+    state: &AppState,
+    merchant_id: &str,
+    gift_card_id: &str,
+) -> RouterResult<storage::GiftCard> {
+    state
+        .store
+        .find_gift_card_by_merchant_id_gift_card_id(merchant_id, gift_card_id)
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::GiftCardNotFound {
+            gift_card_id: gift_card_id.to_owned(),
+        })
+}
+
+/// Ensures that the balance of a gift card can be changed, that is, the gift card is neither
+/// voided nor expired.
+fn validate_gift_card_is_usable(gift_card: &storage::GiftCard) -> RouterResult<()> {
+    utils::when(gift_card.status != enums::GiftCardStatus::Active, || {
+        Err(report!(errors::ApiErrorResponse::PreconditionFailed {
+            message: format!(
+                "Gift card {} cannot be used as it is {}",
+                gift_card.gift_card_id, gift_card.status
+            ),
+        }))
+    })?;
+    utils::when(
+        gift_card
+            .expires_at
+            .map_or(false, |expires_at| expires_at <= date_time::now()),
+        || {
+            Err(report!(errors::ApiErrorResponse::PreconditionFailed {
+                message: format!(
+                    "Gift card {} cannot be used as it has expired",
+                    gift_card.gift_card_id
+                ),
+            }))
+        },
+    )
+}
+
+/// Updates the balance of a gift card and records the change in its ledger, in a single database
+/// transaction. The update only succeeds if the balance of the gift card has not changed since it
+/// was read, so that concurrent redemptions cannot spend the same balance twice.
+#[allow(clippy::too_many_arguments)]
+async fn update_gift_card_balance(
+    state: &AppState,
+    gift_card: &storage::GiftCard,
+    transaction_type: enums::GiftCardTransactionType,
+    amount: i64,
+    balance_after: i64,
+    gift_card_update: storage::GiftCardUpdate,
+    payment_id: Option<String>,
+    idempotency_key: Option<String>,
+) -> RouterResult<(storage::GiftCard, storage::GiftCardTransaction)> {
+    state
+        .store
+        .update_gift_card_balance_with_transaction(
+            &gift_card.merchant_id,
+            &gift_card.gift_card_id,
+            gift_card.balance,
+            gift_card_update,
+            storage::GiftCardTransactionNew {
+                transaction_id: utils::generate_id(consts::ID_LENGTH, "gctxn"),
+                gift_card_id: gift_card.gift_card_id.clone(),
+                merchant_id: gift_card.merchant_id.clone(),
+                transaction_type,
+                amount,
+                balance_after,
+                currency: gift_card.currency,
+                payment_id,
+                created_at: date_time::now(),
+                idempotency_key,
+            },
+        )
+        .await
+        .map_err(|error| {
+            if error.current_context().is_db_not_found() {
+                error.change_context(errors::ApiErrorResponse::PreconditionFailed {
+                    message: format!(
+                        "Gift card {} was updated concurrently or is no longer active, please \
+                         retry",
+                        gift_card.gift_card_id
+                    ),
+                })
+            } else if error.current_context().is_db_unique_violation() {
+                error.change_context(errors::ApiErrorResponse::GenericDuplicateError {
+                    message: format!(
+                        "A transaction with the same idempotency key is being made on gift card \
+                         {}, please retry",
+                        gift_card.gift_card_id
+                    ),
+                })
+            } else {
+                error
+                    .change_context(errors::ApiErrorResponse::InternalServerError)
+                    .attach_printable("Failed to update the balance of the gift card")
+            }
+        })
+}
+
+/// Finds the transaction made on a gift card with an idempotency key, if any.
+async fn find_gift_card_transaction_by_idempotency_key(
+    state: &AppState,
+    gift_card: &storage::GiftCard,
+    idempotency_key: &str,
+) -> RouterResult<Option<storage::GiftCardTransaction>> {
+    match state
+        .store
+        .find_gift_card_transaction_by_merchant_id_gift_card_id_idempotency_key(
+            &gift_card.merchant_id,
+            &gift_card.gift_card_id,
+            idempotency_key,
+        )
+        .await
+    {
+        Ok(transaction) => Ok(Some(transaction)),
+        Err(error) if error.current_context().is_db_not_found() => Ok(None),
+        Err(error) => Err(error)
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Failed to find the gift card transaction by idempotency key"),
+    }
+}
+
+#[instrument(skip(state))]
+pub async fn create_gift_card(
+    state: &AppState,
+    merchant_account: domain::MerchantAccount,
+    key_store: domain::MerchantKeyStore,
+    req: gift_card_types::GiftCardCreateRequest,
+) -> RouterResponse<gift_card_types::GiftCardResponse> {
+    validate_amount(req.amount)?;
+    let now = date_time::now();
+    if let Some(expires_at) = req.expires_at {
+        utils::when(expires_at <= now, || {
+            Err(report!(errors::ApiErrorResponse::InvalidRequestData {
+                message: "`expires_at` must be in the future".to_string(),
+            }))
+        })?;
+    }
+    if let Some(customer_id) = req.customer_id.as_ref() {
+        state
+            .store
+            .find_customer_by_customer_id_merchant_id(
+                customer_id,
+                &merchant_account.merchant_id,
+                &key_store,
+            )
+            .await
+            .to_not_found_response(errors::ApiErrorResponse::CustomerNotFound)?;
+    }
+
+    let gift_card_id = utils::generate_id(consts::ID_LENGTH, "gc");
+    let issue_transaction = storage::GiftCardTransactionNew {
+        transaction_id: utils::generate_id(consts::ID_LENGTH, "gctxn"),
+        gift_card_id: gift_card_id.clone(),
+        merchant_id: merchant_account.merchant_id.clone(),
+        transaction_type: enums::GiftCardTransactionType::Issue,
+        amount: req.amount,
+        balance_after: req.amount,
+        currency: req.currency,
+        payment_id: None,
+        created_at: now,
+        idempotency_key: None,
+    };
+    let (gift_card, _) = state
+        .store
+        .insert_gift_card_with_transaction(
+            storage::GiftCardNew {
+                gift_card_id,
+                merchant_id: merchant_account.merchant_id,
+                customer_id: req.customer_id,
+                status: enums::GiftCardStatus::Active,
+                currency: req.currency,
+                initial_balance: req.amount,
+                balance: req.amount,
+                expires_at: req.expires_at,
+                metadata: req.metadata,
+                created_at: now,
+                modified_at: now,
+            },
+            issue_transaction,
+        )
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to insert gift card")?;
+
+    Ok(ApplicationResponse::Json(get_gift_card_response(gift_card)))
+}
+
+#[instrument(skip(state))]
+pub async fn retrieve_gift_card(
+    state: &AppState,
+    merchant_account: domain::MerchantAccount,
+    gift_card_id: String,
+) -> RouterResponse<gift_card_types::GiftCardResponse> {
+    let gift_card = find_gift_card(state, &merchant_account.merchant_id, &gift_card_id).await?;
+    Ok(ApplicationResponse::Json(get_gift_card_response(gift_card)))
+}
+
+/// Redeems a part of the balance of a gift card, optionally towards a payment of the merchant in
+/// the currency of the gift card. A redemption made with an idempotency key is only made once, and
+/// retrying it returns the gift card without redeeming its balance again.
+#[instrument(skip(state))]
+pub async fn redeem_gift_card(
+    state: &AppState,
+    merchant_account: domain::MerchantAccount,
+    gift_card_id: String,
+    req: gift_card_types::GiftCardRedeemRequest,
+) -> RouterResponse<gift_card_types::GiftCardResponse> {
+    validate_amount(req.amount)?;
+    let gift_card = find_gift_card(state, &merchant_account.merchant_id, &gift_card_id).await?;
+
+    if let Some(idempotency_key) = req.idempotency_key.as_deref() {
+        if let Some(transaction) =
+            find_gift_card_transaction_by_idempotency_key(state, &gift_card, idempotency_key)
+                .await?
+        {
+            utils::when(
+                transaction.transaction_type != enums::GiftCardTransactionType::Redeem
+                    || transaction.amount != req.amount
+                    || transaction.payment_id != req.payment_id,
+                || {
+                    Err(report!(errors::ApiErrorResponse::PreconditionFailed {
+                        message: format!(
+                            "The idempotency key {idempotency_key} has already been used for a \
+                             different transaction on gift card {gift_card_id}"
+                        ),
+                    }))
+                },
+            )?;
+            return Ok(ApplicationResponse::Json(get_gift_card_response(gift_card)));
+        }
+    }
+
+    validate_gift_card_is_usable(&gift_card)?;
+    utils::when(req.amount > gift_card.balance, || {
+        Err(report!(errors::ApiErrorResponse::PreconditionFailed {
+            message: format!(
+                "Gift card {gift_card_id} has insufficient balance, available balance is {}",
+                gift_card.balance
+            ),
+        }))
+    })?;
+
+    if let Some(payment_id) = req.payment_id.as_ref() {
+        let payment_intent = state
+            .store
+            .find_payment_intent_by_payment_id_merchant_id(
+                payment_id,
+                &merchant_account.merchant_id,
+                merchant_account.storage_scheme,
+            )
+            .await
+            .to_not_found_response(errors::ApiErrorResponse::PaymentNotFound)?;
+        validate_gift_card_currency(&gift_card, payment_id, payment_intent.currency)?;
+    }
+
+    let balance = gift_card.balance - req.amount;
+    let (gift_card, _) = update_gift_card_balance(
+        state,
+        &gift_card,
+        enums::GiftCardTransactionType::Redeem,
+        req.amount,
+        balance,
+        storage::GiftCardUpdate::BalanceUpdate { balance },
+        req.payment_id,
+        req.idempotency_key,
+    )
+    .await?;
+
+    Ok(ApplicationResponse::Json(get_gift_card_response(gift_card)))
+}
+
+fn validate_gift_card_currency(
+    gift_card: &storage::GiftCard,
+    payment_id: &str,
+    payment_currency: Option<enums::Currency>,
+) -> RouterResult<()> {
+    utils::when(payment_currency != Some(gift_card.currency), || {
+        Err(report!(errors::ApiErrorResponse::InvalidRequestData {
+            message: format!(
+                "Gift card {} cannot be redeemed towards payment {payment_id} as they are in \
+                 different currencies",
+                gift_card.gift_card_id
+            ),
+        }))
+    })
+}
+
+/// Returns the amount of a payment which is redeemed from a gift card with `balance`. The balance
+/// of the gift card must not cover the whole amount of the payment, as the remaining amount is
+/// charged to the payment method of the payment.
+fn get_payment_redemption_amount(
+    gift_card_id: &str,
+    balance: i64,
+    payment_amount: i64,
+) -> RouterResult<i64> {
+    utils::when(balance <= 0, || {
+        Err(report!(errors::ApiErrorResponse::PreconditionFailed {
+            message: format!("Gift card {gift_card_id} has no balance left"),
+        }))
+    })?;
+    utils::when(balance >= payment_amount, || {
+        Err(report!(errors::ApiErrorResponse::InvalidRequestData {
+            message: format!(
+                "Gift card {gift_card_id} covers the full amount of the payment, redeem it \
+                 towards the payment through the gift card redeem API instead"
+            ),
+        }))
+    })?;
+    Ok(balance)
+}
+
+/// Redeems the balance of a gift card towards a payment which is about to be confirmed with the
+/// connector, so that only the remaining amount of the payment is charged to its payment method.
+/// The redemption is made once per payment attempt, and confirming the same attempt again reuses
+/// it.
+pub async fn redeem_gift_card_for_payment<F: Clone>(
+    state: &AppState,
+    merchant_account: &domain::MerchantAccount,
+    gift_card_id: &str,
+    payment_data: &mut PaymentData<F>,
+) -> RouterResult<storage::GiftCardTransaction> {
+    let gift_card = find_gift_card(state, &merchant_account.merchant_id, gift_card_id).await?;
+    let payment_id = payment_data.payment_intent.payment_id.clone();
+    let payment_amount = payment_data.payment_intent.amount;
+    let idempotency_key = format!("redeem_{}", payment_data.payment_attempt.attempt_id);
+
+    let transaction =
+        match find_gift_card_transaction_by_idempotency_key(state, &gift_card, &idempotency_key)
+            .await?
+        {
+            Some(transaction) => transaction,
+            None => {
+                validate_gift_card_is_usable(&gift_card)?;
+                validate_gift_card_currency(&gift_card, &payment_id, Some(payment_data.currency))?;
+                let amount =
+                    get_payment_redemption_amount(gift_card_id, gift_card.balance, payment_amount)?;
+                let balance = gift_card.balance - amount;
+                let (_, transaction) = update_gift_card_balance(
+                    state,
+                    &gift_card,
+                    enums::GiftCardTransactionType::Redeem,
+                    amount,
+                    balance,
+                    storage::GiftCardUpdate::BalanceUpdate { balance },
+                    Some(payment_id),
+                    Some(idempotency_key),
+                )
+                .await?;
+                transaction
+            }
+        };
+
+    let remaining_amount = payment_amount - transaction.amount;
+    payment_data.amount = remaining_amount.into();
+    payment_data.payment_attempt.amount = remaining_amount;
+    Ok(transaction)
+}
+
+/// Returns the amount redeemed from a gift card towards a payment which has failed to the gift
+/// card, or records its forfeit if the gift card is no longer active. The reversal is made once per
+/// redemption.
+pub async fn reverse_gift_card_redemption(
+    state: &AppState,
+    merchant_account: &domain::MerchantAccount,
+    redemption: storage::GiftCardTransaction,
+) -> RouterResult<()> {
+    let gift_card = find_gift_card(
+        state,
+        &merchant_account.merchant_id,
+        &redemption.gift_card_id,
+    )
+    .await?;
+    let idempotency_key = format!("reversal_{}", redemption.transaction_id);
+    if find_gift_card_transaction_by_idempotency_key(state, &gift_card, &idempotency_key)
+        .await?
+        .is_some()
+    {
+        return Ok(());
+    }
+
+    // Gift cards voided after the redemption forfeit the reversed amount along with their balance,
+    // which is recorded in the ledger of the gift card without changing its balance
+    if gift_card.status != enums::GiftCardStatus::Active {
+        return match state
+            .store
+            .insert_gift_card_transaction(storage::GiftCardTransactionNew {
+                transaction_id: utils::generate_id(consts::ID_LENGTH, "gctxn"),
+                gift_card_id: gift_card.gift_card_id.clone(),
+                merchant_id: gift_card.merchant_id.clone(),
+                transaction_type: enums::GiftCardTransactionType::RedeemForfeit,
+                amount: redemption.amount,
+                balance_after: gift_card.balance,
+                currency: gift_card.currency,
+                payment_id: redemption.payment_id,
+                created_at: date_time::now(),
+                idempotency_key: Some(idempotency_key),
+            })
+            .await
+        {
+            Ok(_) => Ok(()),
+            // The forfeit was recorded concurrently
+            Err(error) if error.current_context().is_db_unique_violation() => Ok(()),
+            Err(error) => Err(error)
+                .change_context(errors::ApiErrorResponse::InternalServerError)
+                .attach_printable("Failed to record the forfeit of the gift card redemption"),
+        };
+    }
+
+    let balance = gift_card
+        .balance
+        .checked_add(redemption.amount)
+        .ok_or(errors::ApiErrorResponse::InternalServerError)
+        .into_report()
+        .attach_printable("The balance of the gift card is too large")?;
+    update_gift_card_balance(
+        state,
+        &gift_card,
+        enums::GiftCardTransactionType::RedeemReversal,
+        redemption.amount,
+        balance,
+        storage::GiftCardUpdate::BalanceUpdate { balance },
+        redemption.payment_id,
+        Some(idempotency_key),
+    )
+    .await?;
+    Ok(())
+}
+
+#[instrument(skip(state))]
+pub async fn reload_gift_card(
+    state: &AppState,
+    merchant_account: domain::MerchantAccount,
+    gift_card_id: String,
+    req: gift_card_types::GiftCardReloadRequest,
+) -> RouterResponse<gift_card_types::GiftCardResponse> {
+    validate_amount(req.amount)?;
+    let gift_card = find_gift_card(state, &merchant_account.merchant_id, &gift_card_id).await?;
+    validate_gift_card_is_usable(&gift_card)?;
+    let balance = gift_card.balance.checked_add(req.amount).ok_or_else(|| {
+        report!(errors::ApiErrorResponse::InvalidRequestData {
+            message: "The balance of the gift card is too large".to_string(),
+        })
+    })?;
+
+    let (gift_card, _) = update_gift_card_balance(
+        state,
+        &gift_card,
+        enums::GiftCardTransactionType::Reload,
+        req.amount,
+        balance,
+        storage::GiftCardUpdate::BalanceUpdate { balance },
+        None,
+        None,
+    )
+    .await?;
+
+    Ok(ApplicationResponse::Json(get_gift_card_response(gift_card)))
+}
+
+/// Voids a gift card, forfeiting its remaining balance, so that it can no longer be used.
+#[instrument(skip(state))]
+pub async fn void_gift_card(
+    state: &AppState,
+    merchant_account: domain::MerchantAccount,
+    gift_card_id: String,
+) -> RouterResponse<gift_card_types::GiftCardResponse> {
+    let gift_card = find_gift_card(state, &merchant_account.merchant_id, &gift_card_id).await?;
+    utils::when(gift_card.status != enums::GiftCardStatus::Active, || {
+        Err(report!(errors::ApiErrorResponse::PreconditionFailed {
+            message: format!(
+                "Gift card {gift_card_id} cannot be voided as it is {}",
+                gift_card.status
+            ),
+        }))
+    })?;
+
+    let (gift_card, _) = update_gift_card_balance(
+        state,
+        &gift_card,
+        enums::GiftCardTransactionType::Void,
+        gift_card.balance,
+        0,
+        storage::GiftCardUpdate::Void,
+        None,
+        None,
+    )
+    .await?;
+
+    Ok(ApplicationResponse::Json(get_gift_card_response(gift_card)))
+}
+
+#[instrument(skip(state))]
+pub async fn list_gift_card_transactions(
+    state: &AppState,
+    merchant_account: domain::MerchantAccount,
+    gift_card_id: String,
+    constraints: gift_card_types::GiftCardTransactionListConstraints,
+) -> RouterResponse<Vec<gift_card_types::GiftCardTransactionResponse>> {
+    let gift_card = find_gift_card(state, &merchant_account.merchant_id, &gift_card_id).await?;
+    let transactions = state
+        .store
+        .list_gift_card_transactions_by_merchant_id_gift_card_id(
+            &gift_card.merchant_id,
+            &gift_card.gift_card_id,
+            constraints.limit,
+            constraints.skip,
+        )
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to list gift card transactions")?
+        .into_iter()
+        .map(get_gift_card_transaction_response)
+        .collect();
+
+    Ok(ApplicationResponse::Json(transactions))
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+
+    #[test]
+    fn test_get_payment_redemption_amount() {
+        assert_eq!(
+            get_payment_redemption_amount("gc_1", 3000, 5000).unwrap(),
+            3000
+        );
+        assert!(matches!(
+            get_payment_redemption_amount("gc_1", 5000, 5000)
+                .unwrap_err()
+                .current_context(),
+            errors::ApiErrorResponse::InvalidRequestData { .. }
+        ));
+        assert!(matches!(
+            get_payment_redemption_amount("gc_1", 0, 5000)
+                .unwrap_err()
+                .current_context(),
+            errors::ApiErrorResponse::PreconditionFailed { .. }
+        ));
+    }
+}
@@ -0,0 +1,203 @@
+//! Extension points for custom business logic in the payment and refund flows.
+//!
+//! Deployments can register in-process plugins implementing [`PaymentHook`] when the application
+//! state is constructed, using [`crate::start_server_with_hooks`] or
+//! [`crate::routes::AppState::with_hooks`]. Hooks are invoked in the order in which they were
+//! registered at the following points:
+//!
+//! - `pre_authorize`: before a payment is confirmed with the connector. The hook can reject the
+//!   payment, or enrich the metadata of the payment.
+//! - `post_authorize`: after the response of the connector for a confirmed payment has been
+//!   recorded. Failures of the hook are logged and do not affect the payment.
+//! - `pre_refund`: before a refund is created. The hook can reject the refund, or enrich the
+//!   metadata of the refund.
+
+use std::sync::Arc;
+
+use common_utils::pii;
+use error_stack::{report, ResultExt};
+use masking::{ExposeInterface, Secret};
+use router_env::logger;
+
+use crate::{
+    core::errors::{self, CustomResult, RouterResult},
+    types::{domain, storage},
+};
+
+/// Additions to the metadata of the payment or refund, returned by the `pre_*` hooks.
+#[derive(Clone, Debug, Default)]
+pub struct HookOutcome {
+    /// Keys to be added to the metadata, overwriting any existing values of the keys.
+    pub metadata: serde_json::Map<String, serde_json::Value>,
+}
+
+/// The payment which is about to be confirmed with the connector.
+pub struct PreAuthorizeContext<'a> {
+    pub merchant_account: &'a domain::MerchantAccount,
+    pub payment_intent: &'a storage::PaymentIntent,
+    pub payment_attempt: &'a storage::PaymentAttempt,
+    pub connector: &'a str,
+}
+
+/// The payment after the response of the connector has been recorded.
+pub struct PostAuthorizeContext<'a> {
+    pub merchant_account: &'a domain::MerchantAccount,
+    pub payment_intent: &'a storage::PaymentIntent,
+    pub payment_attempt: &'a storage::PaymentAttempt,
+}
+
+/// The refund which is about to be created.
+pub struct PreRefundContext<'a> {
+    pub merchant_account: &'a domain::MerchantAccount,
+    pub payment_intent: &'a storage::PaymentIntent,
+    pub payment_attempt: &'a storage::PaymentAttempt,
+    pub refund_amount: i64,
+    pub reason: Option<&'a str>,
+}
+
+/// A plugin invoked at the extension points of the payment and refund flows. All methods have
+/// default implementations which do nothing, so that plugins only implement the extension points
+/// they are interested in.
+#[async_trait::async_trait]
+pub trait PaymentHook: Send + Sync {
+    /// The name of the hook, used when logging its failures.
+    fn name(&self) -> &str;
+
+    async fn pre_authorize(
+        &self,
+        _context: &PreAuthorizeContext<'_>,
+    ) -> CustomResult<HookOutcome, errors::HookError> {
+        Ok(HookOutcome::default())
+    }
+
+    async fn post_authorize(
+        &self,
+        _context: &PostAuthorizeContext<'_>,
+    ) -> CustomResult<(), errors::HookError> {
+        Ok(())
+    }
+
+    async fn pre_refund(
+        &self,
+        _context: &PreRefundContext<'_>,
+    ) -> CustomResult<HookOutcome, errors::HookError> {
+        Ok(HookOutcome::default())
+    }
+}
+
+/// The hooks registered when the application was started.
+#[derive(Clone, Default)]
+pub struct HookRegistry {
+    hooks: Vec<Arc<dyn PaymentHook>>,
+}
+
+impl HookRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers a hook, which is invoked after all the previously registered hooks.
+    pub fn register(mut self, hook: impl PaymentHook + 'static) -> Self {
+        self.hooks.push(Arc::new(hook));
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.hooks.is_empty()
+    }
+}
+
+fn to_api_error(
+    hook: &dyn PaymentHook,
+    error: error_stack::Report<errors::HookError>,
+) -> error_stack::Report<errors::ApiErrorResponse> {
+    let api_error = match error.current_context() {
+        errors::HookError::Rejected { reason } => errors::ApiErrorResponse::PreconditionFailed {
+            message: reason.clone(),
+        },
+        errors::HookError::ExecutionFailed => errors::ApiErrorResponse::InternalServerError,
+    };
+    error
+        .change_context(api_error)
+        .attach_printable(format!("Hook {} failed", hook.name()))
+}
+
+/// Adds the keys returned by a hook to the metadata of a payment or refund.
+fn merge_metadata(
+    metadata: Option<pii::SecretSerdeValue>,
+    outcome: HookOutcome,
+) -> RouterResult<Option<pii::SecretSerdeValue>> {
+    if outcome.metadata.is_empty() {
+        return Ok(metadata);
+    }
+
+    let mut merged = match metadata.map(ExposeInterface::expose) {
+        None | Some(serde_json::Value::Null) => serde_json::Map::new(),
+        Some(serde_json::Value::Object(object)) => object,
+        Some(_) => Err(report!(errors::ApiErrorResponse::InternalServerError))
+            .attach_printable("Unable to add the metadata of a hook to non-object metadata")?,
+    };
+    merged.extend(outcome.metadata);
+    Ok(Some(Secret::new(serde_json::Value::Object(merged))))
+}
+
+/// Invokes the `pre_authorize` hooks, adding the metadata returned by the hooks to the payment.
+pub async fn run_pre_authorize_hooks(
+    hooks: &HookRegistry,
+    merchant_account: &domain::MerchantAccount,
+    payment_intent: &mut storage::PaymentIntent,
+    payment_attempt: &storage::PaymentAttempt,
+    connector: &str,
+) -> RouterResult<()> {
+    for hook in hooks.hooks.iter() {
+        let context = PreAuthorizeContext {
+            merchant_account,
+            payment_intent: &*payment_intent,
+            payment_attempt,
+            connector,
+        };
+        let outcome = hook
+            .pre_authorize(&context)
+            .await
+            .map_err(|error| to_api_error(hook.as_ref(), error))?;
+        payment_intent.metadata = merge_metadata(payment_intent.metadata.take(), outcome)?;
+    }
+    Ok(())
+}
+
+/// Invokes the `post_authorize` hooks. The payment has already been processed by the connector,
+/// so failures of the hooks are only logged.
+pub async fn run_post_authorize_hooks(
+    hooks: &HookRegistry,
+    merchant_account: &domain::MerchantAccount,
+    payment_intent: &storage::PaymentIntent,
+    payment_attempt: &storage::PaymentAttempt,
+) {
+    let context = PostAuthorizeContext {
+        merchant_account,
+        payment_intent,
+        payment_attempt,
+    };
+    for hook in hooks.hooks.iter() {
+        if let Err(error) = hook.post_authorize(&context).await {
+            logger::error!(hook = hook.name(), post_authorize_hook_error=?error);
+        }
+    }
+}
+
+/// Invokes the `pre_refund` hooks, returning the metadata of the refund with the metadata
+/// returned by the hooks added to it.
+pub async fn run_pre_refund_hooks(
+    hooks: &HookRegistry,
+    context: &PreRefundContext<'_>,
+    mut metadata: Option<pii::SecretSerdeValue>,
+) -> RouterResult<Option<pii::SecretSerdeValue>> {
+    for hook in hooks.hooks.iter() {
+        let outcome = hook
+            .pre_refund(context)
+            .await
+            .map_err(|error| to_api_error(hook.as_ref(), error))?;
+        metadata = merge_metadata(metadata, outcome)?;
+    }
+    Ok(metadata)
+}
@@ -0,0 +1,470 @@
+use common_utils::date_time;
+use error_stack::{report, IntoReport, ResultExt};
+use masking::StrongSecret;
+use router_env::{instrument, logger, tracing};
+
+use crate::{
+    consts,
+    core::{
+        errors::{self, RouterResponse, RouterResult, StorageErrorExt},
+        payments, webhooks,
+    },
+    routes::AppState,
+    services::{self, ApplicationResponse},
+    types::{
+        api::{self, invoices as invoice_types},
+        domain,
+        storage::{self, enums},
+    },
+    utils::{self, Encode, ValueExt},
+};
+
+fn get_invoice_payment_link(base_url: &str, invoice: &storage::Invoice) -> String {
+    format!(
+        "{}/invoices/{}/{}/pay?client_secret={}",
+        base_url, invoice.merchant_id, invoice.invoice_id, invoice.client_secret
+    )
+}
+
+fn get_invoice_response(
+    state: &AppState,
+    invoice: storage::Invoice,
+) -> RouterResult<invoice_types::InvoiceResponse> {
+    let payment_link = get_invoice_payment_link(&state.conf.server.base_url, &invoice);
+    let line_items = invoice
+        .line_items
+        .parse_value("Vec<InvoiceLineItem>")
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to deserialize the line items of the invoice")?;
+
+    Ok(invoice_types::InvoiceResponse {
+        invoice_id: invoice.invoice_id,
+        merchant_id: invoice.merchant_id,
+        customer_id: invoice.customer_id,
+        status: invoice.status,
+        currency: invoice.currency,
+        amount: invoice.amount,
+        amount_paid: invoice.amount_paid,
+        amount_due: invoice.amount - invoice.amount_paid,
+        line_items,
+        description: invoice.description,
+        due_date: invoice.due_date,
+        payment_link,
+        client_secret: invoice.client_secret.into(),
+        metadata: invoice.metadata,
+        created: invoice.created_at,
+    })
+}
+
+/// Computes the amount of an invoice from its line items, rejecting line items which would not
+/// add up to a positive amount.
+fn get_invoice_amount(line_items: &[invoice_types::InvoiceLineItem]) -> RouterResult<i64> {
+    utils::when(line_items.is_empty(), || {
+        Err(report!(errors::ApiErrorResponse::InvalidRequestData {
+            message: "An invoice must have at least one line item".to_string(),
+        }))
+    })?;
+
+    line_items.iter().try_fold(0i64, |amount, line_item| {
+        utils::when(
+            line_item.quantity == 0 || line_item.unit_amount <= 0,
+            || {
+                Err(report!(errors::ApiErrorResponse::InvalidRequestData {
+                    message: "The quantity and unit amount of line items must be greater than zero"
+                        .to_string(),
+                }))
+            },
+        )?;
+        line_item
+            .unit_amount
+            .checked_mul(i64::from(line_item.quantity))
+            .and_then(|line_item_amount| amount.checked_add(line_item_amount))
+            .ok_or(errors::ApiErrorResponse::InvalidRequestData {
+                message: "The amount of the invoice is too large".to_string(),
+            })
+            .into_report()
+    })
+}
+
+async fn find_invoice(
+    state: &AppState,
+    merchant_id: &str,
+    invoice_id: &str,
+) -> RouterResult<storage::Invoice> {
+    state
+        .store
+        .find_invoice_by_merchant_id_invoice_id(merchant_id, invoice_id)
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::InvoiceNotFound {
+            invoice_id: invoice_id.to_owned(),
+        })
+}
+
+/// Finds the invoice referenced by a payment link, ensuring that the client secret in the link
+/// was issued for the invoice.
+async fn find_invoice_by_client_secret(
+    state: &AppState,
+    merchant_id: &str,
+    invoice_id: &str,
+    client_secret: &str,
+) -> RouterResult<storage::Invoice> {
+    let invoice = find_invoice(state, merchant_id, invoice_id).await?;
+    // The client secrets are compared in constant time, so that the time taken to reject a
+    // payment link does not reveal how much of its client secret is correct
+    let is_client_secret_valid = StrongSecret::new(invoice.client_secret.clone())
+        == StrongSecret::new(client_secret.to_owned());
+    utils::when(!is_client_secret_valid, || {
+        Err(report!(errors::ApiErrorResponse::ClientSecretInvalid))
+    })?;
+    Ok(invoice)
+}
+
+async fn trigger_invoice_webhook(
+    state: &AppState,
+    merchant_account: &domain::MerchantAccount,
+    event_type: enums::EventType,
+    invoice: storage::Invoice,
+    payment_id: Option<String>,
+) -> RouterResult<()> {
+    let invoice_id = invoice.invoice_id.clone();
+    let invoice_response = get_invoice_response(state, invoice)?;
+
+    webhooks::create_event_and_trigger_outgoing_webhook::<api::OutgoingWebhook>(
+        state.clone(),
+        merchant_account.clone(),
+        event_type,
+        enums::EventClass::Invoices,
+        payment_id,
+        invoice_id,
+        enums::EventObjectType::InvoiceDetails,
+        api::OutgoingWebhookContent::InvoiceDetails(Box::new(invoice_response)),
+    )
+    .await
+}
+
+#[instrument(skip(state))]
+pub async fn create_invoice(
+    state: &AppState,
+    merchant_account: domain::MerchantAccount,
+    key_store: domain::MerchantKeyStore,
+    req: invoice_types::InvoiceCreateRequest,
+) -> RouterResponse<invoice_types::InvoiceResponse> {
+    let amount = get_invoice_amount(&req.line_items)?;
+    let now = date_time::now();
+    if let Some(due_date) = req.due_date {
+        utils::when(due_date < now, || {
+            Err(report!(errors::ApiErrorResponse::InvalidRequestData {
+                message: "`due_date` must not be in the past".to_string(),
+            }))
+        })?;
+    }
+    if let Some(customer_id) = req.customer_id.as_ref() {
+        state
+            .store
+            .find_customer_by_customer_id_merchant_id(
+                customer_id,
+                &merchant_account.merchant_id,
+                &key_store,
+            )
+            .await
+            .to_not_found_response(errors::ApiErrorResponse::CustomerNotFound)?;
+    }
+
+    let line_items =
+        Encode::<Vec<invoice_types::InvoiceLineItem>>::encode_to_value(&req.line_items)
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Failed to serialize the line items of the invoice")?;
+    let invoice_id = utils::generate_id(consts::ID_LENGTH, "inv");
+    let invoice = storage::InvoiceNew {
+        client_secret: utils::generate_id(
+            consts::ID_LENGTH,
+            format!("{invoice_id}_secret").as_str(),
+        ),
+        invoice_id,
+        merchant_id: merchant_account.merchant_id,
+        customer_id: req.customer_id,
+        status: enums::InvoiceStatus::Open,
+        currency: req.currency,
+        amount,
+        amount_paid: 0,
+        line_items,
+        description: req.description,
+        due_date: req.due_date,
+        metadata: req.metadata,
+        created_at: now,
+        modified_at: now,
+    };
+
+    let invoice = state
+        .store
+        .insert_invoice(invoice)
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to insert invoice")?;
+
+    Ok(ApplicationResponse::Json(get_invoice_response(
+        state, invoice,
+    )?))
+}
+
+#[instrument(skip(state))]
+pub async fn retrieve_invoice(
+    state: &AppState,
+    merchant_account: domain::MerchantAccount,
+    invoice_id: String,
+) -> RouterResponse<invoice_types::InvoiceResponse> {
+    let invoice = find_invoice(state, &merchant_account.merchant_id, &invoice_id).await?;
+    Ok(ApplicationResponse::Json(get_invoice_response(
+        state, invoice,
+    )?))
+}
+
+#[instrument(skip(state))]
+pub async fn list_invoices(
+    state: &AppState,
+    merchant_account: domain::MerchantAccount,
+    constraints: invoice_types::InvoiceListConstraints,
+) -> RouterResponse<Vec<invoice_types::InvoiceResponse>> {
+    let invoices = state
+        .store
+        .list_invoices_by_merchant_id(
+            &merchant_account.merchant_id,
+            constraints.limit,
+            constraints.skip,
+        )
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to list invoices")?
+        .into_iter()
+        .map(|invoice| get_invoice_response(state, invoice))
+        .collect::<RouterResult<_>>()?;
+
+    Ok(ApplicationResponse::Json(invoices))
+}
+
+/// Voids an invoice which has not received any payments, so that it can no longer be paid.
+#[instrument(skip(state))]
+pub async fn void_invoice(
+    state: &AppState,
+    merchant_account: domain::MerchantAccount,
+    invoice_id: String,
+) -> RouterResponse<invoice_types::InvoiceResponse> {
+    let invoice = find_invoice(state, &merchant_account.merchant_id, &invoice_id).await?;
+    utils::when(invoice.status != enums::InvoiceStatus::Open, || {
+        Err(report!(errors::ApiErrorResponse::InvalidRequestData {
+            message: format!(
+                "Invoice {invoice_id} cannot be voided as it is {}",
+                invoice.status
+            ),
+        }))
+    })?;
+
+    let invoice = state
+        .store
+        .update_invoice_by_merchant_id_invoice_id(
+            &merchant_account.merchant_id,
+            &invoice_id,
+            storage::InvoiceUpdate::StatusUpdate {
+                status: enums::InvoiceStatus::Void,
+            },
+        )
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::InvoiceNotFound { invoice_id })?;
+
+    trigger_invoice_webhook(
+        state,
+        &merchant_account,
+        enums::EventType::InvoiceVoided,
+        invoice.clone(),
+        None,
+    )
+    .await?;
+
+    Ok(ApplicationResponse::Json(get_invoice_response(
+        state, invoice,
+    )?))
+}
+
+/// Retrieves the invoice referenced by a payment link, so that it can be presented to the
+/// customer before they pay it.
+#[instrument(skip(state))]
+pub async fn retrieve_invoice_for_payment_link(
+    state: &AppState,
+    merchant_account: domain::MerchantAccount,
+    invoice_id: String,
+    query: invoice_types::InvoicePaymentLinkQuery,
+) -> RouterResponse<invoice_types::InvoiceResponse> {
+    let invoice = find_invoice_by_client_secret(
+        state,
+        &merchant_account.merchant_id,
+        &invoice_id,
+        &query.client_secret,
+    )
+    .await?;
+    Ok(ApplicationResponse::Json(get_invoice_response(
+        state, invoice,
+    )?))
+}
+
+/// Creates a payment towards the invoice referenced by a payment link, provided that the invoice
+/// is still open for payments. The payment is created without being confirmed, so that the
+/// customer can confirm it with the payment method of their choice using the client secret of the
+/// payment. The amount due on the invoice is checked again when the payment is confirmed, as other
+/// payments of the invoice could have reduced it since.
+#[instrument(skip(state))]
+pub async fn pay_invoice(
+    state: &AppState,
+    merchant_account: domain::MerchantAccount,
+    key_store: domain::MerchantKeyStore,
+    invoice_id: String,
+    req: invoice_types::InvoicePaymentRequest,
+) -> RouterResponse<api::PaymentsResponse> {
+    let invoice = find_invoice_by_client_secret(
+        state,
+        &merchant_account.merchant_id,
+        &invoice_id,
+        &req.client_secret,
+    )
+    .await?;
+    payments::helpers::validate_invoice_is_payable(&invoice)?;
+    let amount_due = invoice.amount - invoice.amount_paid;
+    let amount = req.amount.unwrap_or(amount_due);
+    utils::when(amount <= 0, || {
+        Err(report!(errors::ApiErrorResponse::InvalidRequestData {
+            message: "The amount of the payment must be greater than zero".to_string(),
+        }))
+    })?;
+    utils::when(amount > amount_due, || {
+        Err(report!(errors::ApiErrorResponse::InvalidRequestData {
+            message: format!(
+                "The amount of the payment must not exceed the amount due on the invoice, {amount_due}"
+            ),
+        }))
+    })?;
+
+    let payment_request = api::PaymentsRequest {
+        amount: Some(amount.into()),
+        currency: Some(invoice.currency),
+        confirm: Some(false),
+        customer_id: invoice.customer_id,
+        description: invoice.description,
+        return_url: req.return_url,
+        invoice_id: Some(invoice.invoice_id),
+        ..Default::default()
+    };
+
+    payments::payments_core::<api::Authorize, api::PaymentsResponse, _, _, _>(
+        state,
+        merchant_account,
+        key_store,
+        payments::PaymentCreate,
+        payment_request,
+        services::AuthFlow::Merchant,
+        payments::CallConnectorAction::Trigger,
+    )
+    .await
+}
+
+/// Applies a payment which has succeeded to the invoice it was made towards, and notifies the
+/// merchant when the invoice is first partially paid or is fully paid.
+///
+/// The amount of the payment is added to the amount paid of the invoice in a single statement, so
+/// that concurrent payments of the invoice are all accounted for. The status of the invoice is
+/// then set from the amount paid after the payment, unless a later payment has been applied to
+/// the invoice in the meantime, which sets the status of the invoice instead.
+#[instrument(skip_all)]
+pub async fn apply_invoice_payment(
+    state: &AppState,
+    merchant_account: &domain::MerchantAccount,
+    payment_intent: &storage::PaymentIntent,
+) -> RouterResult<()> {
+    let invoice_id = match payment_intent.invoice_id.as_deref() {
+        Some(invoice_id) => invoice_id,
+        None => return Ok(()),
+    };
+    let payment_amount = payment_intent
+        .amount_captured
+        .unwrap_or(payment_intent.amount);
+
+    let invoice = match state
+        .store
+        .add_payment_to_invoice(&merchant_account.merchant_id, invoice_id, payment_amount)
+        .await
+    {
+        Ok(invoice) => invoice,
+        Err(error) if error.current_context().is_db_not_found() => {
+            let invoice = find_invoice(state, &merchant_account.merchant_id, invoice_id).await?;
+            // Payments are created for at most the amount due on the invoice, which concurrent
+            // payments of the invoice could have reduced since
+            logger::error!(
+                invoice_id,
+                payment_id = %payment_intent.payment_id,
+                payment_amount,
+                amount_due = invoice.amount - invoice.amount_paid,
+                "Payment succeeded for more than the amount due on the invoice, and was not applied to it"
+            );
+            return Ok(());
+        }
+        Err(error) => Err(error)
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Failed to apply payment to the invoice")?,
+    };
+
+    let status = match invoice.status {
+        // A payment created before the invoice was voided could still succeed, in which case it
+        // is recorded on the invoice without reopening it
+        enums::InvoiceStatus::Void => {
+            logger::warn!(
+                invoice_id,
+                payment_id = %payment_intent.payment_id,
+                "Payment succeeded for a voided invoice"
+            );
+            return Ok(());
+        }
+        _ if invoice.amount_paid >= invoice.amount => enums::InvoiceStatus::Paid,
+        _ => enums::InvoiceStatus::PartiallyPaid,
+    };
+    if invoice.status == status {
+        return Ok(());
+    }
+
+    let updated_invoice = match state
+        .store
+        .update_invoice_status_if_amount_paid(
+            &merchant_account.merchant_id,
+            invoice_id,
+            invoice.amount_paid,
+            status,
+        )
+        .await
+    {
+        Ok(updated_invoice) => updated_invoice,
+        Err(error) if error.current_context().is_db_not_found() => {
+            logger::info!(
+                invoice_id,
+                payment_id = %payment_intent.payment_id,
+                "Invoice status left to a later payment applied to the invoice"
+            );
+            return Ok(());
+        }
+        Err(error) => Err(error)
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Failed to update the status of the invoice")?,
+    };
+
+    let event_type = if status == enums::InvoiceStatus::Paid {
+        enums::EventType::InvoicePaid
+    } else {
+        enums::EventType::InvoicePartiallyPaid
+    };
+    trigger_invoice_webhook(
+        state,
+        merchant_account,
+        event_type,
+        updated_invoice,
+        Some(payment_intent.payment_id.clone()),
+    )
+    .await?;
+
+    Ok(())
+}
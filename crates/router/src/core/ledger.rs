@@ -0,0 +1,806 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    str::FromStr,
+};
+
+use api_models::accounting::{
+    JournalEntryType, LedgerAccount, LedgerAccountBalance, LedgerBalanceRequest,
+    LedgerBalanceResponse, LedgerEntryListConstraints, LedgerEntryListResponse,
+    LedgerEntryResponse, LedgerReconciliationRequest, LedgerReconciliationResponse,
+    ReconciliationDiscrepancy, ReconciliationDiscrepancyType, SettlementLine,
+};
+use common_utils::date_time;
+use error_stack::{IntoReport, ResultExt};
+use router_env::{instrument, logger, tracing};
+
+use crate::{
+    consts,
+    core::errors::{self, RouterResponse, RouterResult},
+    routes::AppState,
+    services::ApplicationResponse,
+    types::{
+        domain,
+        storage::{self, enums},
+    },
+    utils,
+};
+
+/// Posts an entry to the ledger of the merchant, moving `amount` from the `credit` account to the
+/// `debit` account. A business event, identified by the type of the entry and the idempotency key,
+/// is only posted once, even if it is reported more than once.
+#[allow(clippy::too_many_arguments)]
+async fn post_entry(
+    state: &AppState,
+    merchant_id: &str,
+    entry_type: JournalEntryType,
+    reference_id: &str,
+    idempotency_key: &str,
+    debit: LedgerAccount,
+    credit: LedgerAccount,
+    amount: i64,
+    currency: enums::Currency,
+    connector: Option<String>,
+) -> RouterResult<()> {
+    let ledger_entry = storage::LedgerEntryNew {
+        entry_id: utils::generate_id(consts::ID_LENGTH, "lde"),
+        merchant_id: merchant_id.to_owned(),
+        entry_type: entry_type.to_string(),
+        reference_id: reference_id.to_owned(),
+        debit_account: debit.to_string(),
+        credit_account: credit.to_string(),
+        amount,
+        currency,
+        connector,
+        created_at: date_time::now(),
+        idempotency_key: idempotency_key.to_owned(),
+    };
+
+    match state.store.insert_ledger_entry(ledger_entry).await {
+        Ok(_) => Ok(()),
+        Err(error) if error.current_context().is_db_unique_violation() => {
+            logger::info!(
+                %reference_id,
+                %idempotency_key,
+                %entry_type,
+                "Ledger entry has already been posted"
+            );
+            Ok(())
+        }
+        Err(error) => Err(error)
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Failed to post the ledger entry"),
+    }
+}
+
+/// Posts the entries resulting from a change in the status of a payment. Authorizations are
+/// recorded in memo accounts until they are captured, voided or fail, while captures move the
+/// captured amount and the surcharge to the funds receivable from the connector.
+///
+/// The entries of a payment are posted once per payment attempt, so that the authorization of a
+/// payment retried after a failed attempt is posted again. Payments can be captured in several
+/// partial captures, each of which posts the amount captured since the previous capture.
+#[instrument(skip_all)]
+pub async fn record_payment_postings(
+    state: &AppState,
+    merchant_account: &domain::MerchantAccount,
+    previous_intent_status: enums::IntentStatus,
+    payment_intent: &storage::PaymentIntent,
+    payment_attempt: &storage::PaymentAttempt,
+) -> RouterResult<()> {
+    let status = payment_intent.status;
+    // Partially captured payments can be captured again without their status changing
+    if status == previous_intent_status && status != enums::IntentStatus::PartiallyCaptured {
+        return Ok(());
+    }
+    let Some(currency) = payment_intent.currency.or(payment_attempt.currency) else {
+        logger::warn!(
+            payment_id = %payment_intent.payment_id,
+            "Skipping ledger postings of payment without currency"
+        );
+        return Ok(());
+    };
+    let merchant_id = &merchant_account.merchant_id;
+    let payment_id = &payment_intent.payment_id;
+    let attempt_id = &payment_attempt.attempt_id;
+    let connector = &payment_attempt.connector;
+
+    if status == enums::IntentStatus::RequiresCapture {
+        post_authorization(state, merchant_id, payment_id, payment_attempt, currency).await?;
+    }
+    if previous_intent_status == enums::IntentStatus::RequiresCapture {
+        post_authorization_release(state, merchant_id, payment_id, payment_attempt, currency)
+            .await?;
+    }
+    if matches!(
+        status,
+        enums::IntentStatus::Succeeded | enums::IntentStatus::PartiallyCaptured
+    ) {
+        let amount_captured = payment_intent
+            .amount_captured
+            .unwrap_or(payment_attempt.amount);
+        let amount_posted = get_reference_entries(state, merchant_id, payment_id)
+            .await?
+            .into_iter()
+            .filter(|entry| entry.entry_type == JournalEntryType::Sale)
+            .fold(0_i64, |amount_posted, entry| {
+                amount_posted.saturating_add(entry.amount)
+            });
+        let sale_amount = amount_captured.saturating_sub(amount_posted);
+        if sale_amount > 0 {
+            post_entry(
+                state,
+                merchant_id,
+                JournalEntryType::Sale,
+                payment_id,
+                &format!("{attempt_id}_{amount_captured}"),
+                LedgerAccount::ConnectorReceivable,
+                LedgerAccount::SalesRevenue,
+                sale_amount,
+                currency,
+                connector.clone(),
+            )
+            .await?;
+        }
+        if let Some(surcharge_amount) = payment_attempt
+            .surcharge_amount
+            .filter(|surcharge_amount| *surcharge_amount > 0)
+        {
+            post_entry(
+                state,
+                merchant_id,
+                JournalEntryType::Fee,
+                payment_id,
+                attempt_id,
+                LedgerAccount::ConnectorReceivable,
+                LedgerAccount::FeeIncome,
+                surcharge_amount,
+                currency,
+                connector.clone(),
+            )
+            .await?;
+        }
+    }
+    Ok(())
+}
+
+async fn post_authorization(
+    state: &AppState,
+    merchant_id: &str,
+    payment_id: &str,
+    payment_attempt: &storage::PaymentAttempt,
+    currency: enums::Currency,
+) -> RouterResult<()> {
+    post_entry(
+        state,
+        merchant_id,
+        JournalEntryType::Authorization,
+        payment_id,
+        &payment_attempt.attempt_id,
+        LedgerAccount::AuthorizationHolds,
+        LedgerAccount::AuthorizationHoldsOffset,
+        payment_attempt.amount,
+        currency,
+        payment_attempt.connector.clone(),
+    )
+    .await
+}
+
+async fn post_authorization_release(
+    state: &AppState,
+    merchant_id: &str,
+    payment_id: &str,
+    payment_attempt: &storage::PaymentAttempt,
+    currency: enums::Currency,
+) -> RouterResult<()> {
+    post_entry(
+        state,
+        merchant_id,
+        JournalEntryType::AuthorizationRelease,
+        payment_id,
+        &payment_attempt.attempt_id,
+        LedgerAccount::AuthorizationHoldsOffset,
+        LedgerAccount::AuthorizationHolds,
+        payment_attempt.amount,
+        currency,
+        payment_attempt.connector.clone(),
+    )
+    .await
+}
+
+/// Posts the entries of the re-authorization of a payment, which replaces the authorization of
+/// the previously authorized attempt with the authorization of the re-authorized attempt.
+#[instrument(skip_all)]
+pub async fn record_reauthorization_postings(
+    state: &AppState,
+    merchant_account: &domain::MerchantAccount,
+    payment_intent: &storage::PaymentIntent,
+    authorized_attempt: &storage::PaymentAttempt,
+    reauthorized_attempt: &storage::PaymentAttempt,
+) -> RouterResult<()> {
+    let Some(currency) = payment_intent.currency.or(reauthorized_attempt.currency) else {
+        logger::warn!(
+            payment_id = %payment_intent.payment_id,
+            "Skipping ledger postings of payment without currency"
+        );
+        return Ok(());
+    };
+    let merchant_id = &merchant_account.merchant_id;
+    let payment_id = &payment_intent.payment_id;
+
+    post_authorization_release(state, merchant_id, payment_id, authorized_attempt, currency)
+        .await?;
+    post_authorization(
+        state,
+        merchant_id,
+        payment_id,
+        reauthorized_attempt,
+        currency,
+    )
+    .await
+}
+
+/// Returns the amount returned to the customer by a refund, which is the amount reported by the
+/// connector for refunds flagged because of an amount mismatch, or `None` if the refund has not
+/// been carried out.
+fn get_refunded_amount(refund: &storage::Refund) -> Option<i64> {
+    match refund.refund_status {
+        enums::RefundStatus::Success => Some(refund.refund_amount),
+        enums::RefundStatus::AmountMismatch => refund.settled_amount,
+        _ => None,
+    }
+}
+
+/// Posts the entry of a refund which has been carried out by the connector, for the amount
+/// actually refunded.
+#[instrument(skip_all)]
+pub async fn record_refund_posting(state: &AppState, refund: &storage::Refund) -> RouterResult<()> {
+    // Refunds made to an alternate destination are posted as the payouts which carry them out
+    if refund.payout_id.is_some() {
+        return Ok(());
+    }
+    let Some(refunded_amount) = get_refunded_amount(refund) else {
+        return Ok(());
+    };
+    post_entry(
+        state,
+        &refund.merchant_id,
+        JournalEntryType::Refund,
+        &refund.refund_id,
+        &refund.refund_id,
+        LedgerAccount::Refunds,
+        LedgerAccount::ConnectorReceivable,
+        refunded_amount,
+        refund.currency,
+        Some(refund.connector.clone()),
+    )
+    .await
+}
+
+/// Returns whether the disputed amount has been withdrawn from the funds of the merchant. Funds are
+/// withdrawn as soon as a dispute is opened, except for pre-disputes which are only inquiries.
+pub fn is_dispute_amount_withdrawn(dispute: &storage::Dispute) -> bool {
+    dispute.dispute_stage != enums::DisputeStage::PreDispute
+}
+
+/// Returns whether the withdrawn disputed amount has been returned to the merchant, which happens
+/// when the dispute is won or cancelled by the customer.
+pub fn is_dispute_amount_reinstated(dispute: &storage::Dispute) -> bool {
+    is_dispute_amount_withdrawn(dispute)
+        && matches!(
+            dispute.dispute_status,
+            enums::DisputeStatus::DisputeWon | enums::DisputeStatus::DisputeCancelled
+        )
+}
+
+/// Posts the entries of the lifecycle of a dispute: the disputed amount is debited to the
+/// chargebacks account once the dispute is opened, and credited back once the dispute is won.
+#[instrument(skip_all)]
+pub async fn record_dispute_postings(
+    state: &AppState,
+    dispute: &storage::Dispute,
+) -> RouterResult<()> {
+    if !is_dispute_amount_withdrawn(dispute) {
+        return Ok(());
+    }
+    // Dispute amounts and currencies are stored as received from the connector
+    let amount = dispute
+        .amount
+        .parse::<i64>()
+        .into_report()
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable_lazy(|| format!("Invalid amount for dispute {}", dispute.dispute_id))?;
+    let currency = enums::Currency::from_str(&dispute.currency)
+        .into_report()
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable_lazy(|| format!("Invalid currency for dispute {}", dispute.dispute_id))?;
+
+    post_entry(
+        state,
+        &dispute.merchant_id,
+        JournalEntryType::Chargeback,
+        &dispute.dispute_id,
+        &dispute.dispute_id,
+        LedgerAccount::Chargebacks,
+        LedgerAccount::ConnectorReceivable,
+        amount,
+        currency,
+        Some(dispute.connector.clone()),
+    )
+    .await?;
+    if is_dispute_amount_reinstated(dispute) {
+        post_entry(
+            state,
+            &dispute.merchant_id,
+            JournalEntryType::ChargebackReversal,
+            &dispute.dispute_id,
+            &dispute.dispute_id,
+            LedgerAccount::ConnectorReceivable,
+            LedgerAccount::Chargebacks,
+            amount,
+            currency,
+            Some(dispute.connector.clone()),
+        )
+        .await?;
+    }
+    Ok(())
+}
+
+/// Posts the entries of a payout which has succeeded, along with the fees and FX margin charged by
+/// the connector in the source currency of the payout.
+#[instrument(skip_all)]
+pub async fn record_payout_postings(
+    state: &AppState,
+    payouts: &storage::Payouts,
+    payout_attempt: &storage::PayoutAttempt,
+) -> RouterResult<()> {
+    if payout_attempt.status != enums::PayoutStatus::Success {
+        return Ok(());
+    }
+    post_entry(
+        state,
+        &payouts.merchant_id,
+        JournalEntryType::Payout,
+        &payouts.payout_id,
+        &payouts.payout_id,
+        LedgerAccount::PayoutsDisbursed,
+        LedgerAccount::ConnectorReceivable,
+        payouts.amount,
+        payouts.destination_currency,
+        Some(payout_attempt.connector.clone()),
+    )
+    .await?;
+
+    let charges = [
+        (JournalEntryType::PayoutFee, payout_attempt.connector_fee),
+        (JournalEntryType::PayoutFxMargin, payout_attempt.fx_margin),
+    ];
+    for (entry_type, amount) in charges {
+        if let Some(amount) = amount.filter(|amount| *amount > 0) {
+            post_entry(
+                state,
+                &payouts.merchant_id,
+                entry_type,
+                &payouts.payout_id,
+                &payouts.payout_id,
+                LedgerAccount::ConnectorFees,
+                LedgerAccount::ConnectorReceivable,
+                amount,
+                payouts.source_currency,
+                Some(payout_attempt.connector.clone()),
+            )
+            .await?;
+        }
+    }
+    Ok(())
+}
+
+fn parse_ledger_value<T: FromStr>(value: &str, field_name: &str) -> RouterResult<T> {
+    T::from_str(value)
+        .map_err(|_| errors::ApiErrorResponse::InternalServerError)
+        .into_report()
+        .attach_printable_lazy(|| format!("Invalid {field_name} `{value}` in the ledger"))
+}
+
+pub(crate) fn get_ledger_entry_response(
+    entry: storage::LedgerEntry,
+) -> RouterResult<LedgerEntryResponse> {
+    Ok(LedgerEntryResponse {
+        entry_type: parse_ledger_value(&entry.entry_type, "entry type")?,
+        debit_account: parse_ledger_value(&entry.debit_account, "account")?,
+        credit_account: parse_ledger_value(&entry.credit_account, "account")?,
+        entry_id: entry.entry_id,
+        reference_id: entry.reference_id,
+        amount: entry.amount,
+        currency: entry.currency,
+        connector: entry.connector,
+        created: entry.created_at,
+    })
+}
+
+#[instrument(skip(state))]
+pub async fn list_ledger_entries(
+    state: &AppState,
+    merchant_account: domain::MerchantAccount,
+    constraints: LedgerEntryListConstraints,
+) -> RouterResponse<LedgerEntryListResponse> {
+    let limit = constraints
+        .limit
+        .unwrap_or(consts::DEFAULT_LEDGER_ENTRY_LIST_LIMIT);
+    if !(1..=consts::MAX_LEDGER_ENTRY_LIST_LIMIT).contains(&limit) {
+        Err(errors::ApiErrorResponse::InvalidRequestData {
+            message: format!(
+                "`limit` must be between 1 and {}",
+                consts::MAX_LEDGER_ENTRY_LIST_LIMIT
+            ),
+        })?
+    }
+
+    let entries = state
+        .store
+        .filter_ledger_entries_by_constraints(&merchant_account.merchant_id, &constraints, limit)
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to retrieve the ledger entries of the merchant")?;
+    let data = entries
+        .into_iter()
+        .map(get_ledger_entry_response)
+        .collect::<RouterResult<Vec<_>>>()?;
+
+    Ok(ApplicationResponse::Json(LedgerEntryListResponse {
+        count: data.len(),
+        data,
+    }))
+}
+
+/// Retrieves the entries posted for a payment, refund, dispute or payout, most recent first.
+pub async fn get_reference_entries(
+    state: &AppState,
+    merchant_id: &str,
+    reference_id: &str,
+) -> RouterResult<Vec<LedgerEntryResponse>> {
+    let constraints = LedgerEntryListConstraints {
+        reference_id: Some(reference_id.to_owned()),
+        ..Default::default()
+    };
+    state
+        .store
+        .filter_ledger_entries_by_constraints(
+            merchant_id,
+            &constraints,
+            consts::MAX_LEDGER_ENTRY_LIST_LIMIT,
+        )
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable_lazy(|| {
+            format!("Failed to retrieve the ledger entries of {reference_id}")
+        })?
+        .into_iter()
+        .map(get_ledger_entry_response)
+        .collect()
+}
+
+/// Combines the totals debited and credited to each account into the balances of the accounts,
+/// ordered by account and currency.
+fn get_account_balances(
+    debit_totals: Vec<storage::LedgerAccountTotal>,
+    credit_totals: Vec<storage::LedgerAccountTotal>,
+) -> RouterResult<Vec<LedgerAccountBalance>> {
+    let mut balances = BTreeMap::new();
+    for (total, is_debit) in debit_totals
+        .into_iter()
+        .map(|total| (total, true))
+        .chain(credit_totals.into_iter().map(|total| (total, false)))
+    {
+        let account: LedgerAccount = parse_ledger_value(&total.account, "account")?;
+        let balance = balances
+            .entry((total.account, total.currency.to_string()))
+            .or_insert_with(|| LedgerAccountBalance {
+                account,
+                currency: total.currency,
+                total_debit: 0,
+                total_credit: 0,
+                balance: 0,
+            });
+        if is_debit {
+            balance.total_debit = balance.total_debit.saturating_add(total.total_amount);
+        } else {
+            balance.total_credit = balance.total_credit.saturating_add(total.total_amount);
+        }
+        balance.balance = balance.total_debit.saturating_sub(balance.total_credit);
+    }
+    Ok(balances.into_values().collect())
+}
+
+#[instrument(skip(state))]
+pub async fn retrieve_ledger_balances(
+    state: &AppState,
+    merchant_account: domain::MerchantAccount,
+    req: LedgerBalanceRequest,
+) -> RouterResponse<LedgerBalanceResponse> {
+    let merchant_id = &merchant_account.merchant_id;
+    let (debit_totals, credit_totals) = futures::try_join!(
+        state.store.get_ledger_debit_totals_by_account(merchant_id),
+        state.store.get_ledger_credit_totals_by_account(merchant_id),
+    )
+    .change_context(errors::ApiErrorResponse::InternalServerError)
+    .attach_printable("Failed to aggregate the ledger entries of the merchant")?;
+
+    let balances = get_account_balances(debit_totals, credit_totals)?
+        .into_iter()
+        .filter(|balance| {
+            req.currency
+                .map_or(true, |currency| balance.currency == currency)
+        })
+        .collect();
+
+    Ok(ApplicationResponse::Json(LedgerBalanceResponse {
+        balances,
+    }))
+}
+
+/// Compares the amounts posted to the ledger with the amounts settled by the connector, both keyed
+/// by the reference and the type of the entry. Returns the number of matching settlement lines and
+/// the discrepancies, ordered by reference.
+fn reconcile_settlement(
+    mut ledger_amounts: HashMap<(String, JournalEntryType), i64>,
+    lines: Vec<SettlementLine>,
+) -> (usize, Vec<ReconciliationDiscrepancy>) {
+    // A settlement report may list an entry in several lines, such as partial settlements
+    let mut settlement_amounts: HashMap<(String, JournalEntryType), i64> = HashMap::new();
+    for line in lines {
+        let amount = settlement_amounts
+            .entry((line.reference_id, line.entry_type))
+            .or_default();
+        *amount = amount.saturating_add(line.amount);
+    }
+
+    let mut matched_count = 0;
+    let mut discrepancies = Vec::new();
+    for ((reference_id, entry_type), settlement_amount) in settlement_amounts {
+        let ledger_amount = ledger_amounts.remove(&(reference_id.clone(), entry_type));
+        let discrepancy_type = match ledger_amount {
+            Some(ledger_amount) if ledger_amount == settlement_amount => {
+                matched_count += 1;
+                continue;
+            }
+            Some(_) => ReconciliationDiscrepancyType::AmountMismatch,
+            None => ReconciliationDiscrepancyType::MissingInLedger,
+        };
+        discrepancies.push(ReconciliationDiscrepancy {
+            reference_id,
+            entry_type,
+            discrepancy_type,
+            ledger_amount,
+            settlement_amount: Some(settlement_amount),
+        });
+    }
+    discrepancies.extend(ledger_amounts.into_iter().map(
+        |((reference_id, entry_type), ledger_amount)| ReconciliationDiscrepancy {
+            reference_id,
+            entry_type,
+            discrepancy_type: ReconciliationDiscrepancyType::MissingInSettlement,
+            ledger_amount: Some(ledger_amount),
+            settlement_amount: None,
+        },
+    ));
+    discrepancies.sort_by(|a, b| {
+        a.reference_id
+            .cmp(&b.reference_id)
+            .then_with(|| a.entry_type.to_string().cmp(&b.entry_type.to_string()))
+    });
+
+    (matched_count, discrepancies)
+}
+
+/// Reconciles the settlement report of a connector against the entries posted to the ledger in the
+/// period covered by the report. Only the entries which move funds receivable from the connector
+/// are expected to be settled, so authorization memo entries are not reconciled.
+#[instrument(skip(state, req))]
+pub async fn reconcile_ledger(
+    state: &AppState,
+    merchant_account: domain::MerchantAccount,
+    req: LedgerReconciliationRequest,
+) -> RouterResponse<LedgerReconciliationResponse> {
+    if req.start_time > req.end_time {
+        Err(errors::ApiErrorResponse::InvalidRequestData {
+            message: "`start_time` must be earlier than `end_time`".to_string(),
+        })?
+    }
+
+    let constraints = LedgerEntryListConstraints {
+        account: Some(LedgerAccount::ConnectorReceivable),
+        currency: Some(req.currency),
+        connector: Some(req.connector.clone()),
+        start_time: Some(req.start_time),
+        end_time: Some(req.end_time),
+        ..Default::default()
+    };
+    // All the entries of the period are reconciled, reading them a page at a time
+    let mut ledger_amounts = HashMap::new();
+    let mut after_id = 0;
+    loop {
+        let entries = state
+            .store
+            .filter_ledger_entries_by_constraints_after_id(
+                &merchant_account.merchant_id,
+                &constraints,
+                after_id,
+                consts::LEDGER_RECONCILIATION_RECORDS_LIMIT,
+            )
+            .await
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Failed to retrieve the ledger entries to reconcile")?;
+        let is_last_page = i64::try_from(entries.len()).map_or(false, |count| {
+            count < consts::LEDGER_RECONCILIATION_RECORDS_LIMIT
+        });
+
+        for entry in entries {
+            after_id = entry.id;
+            let entry_type = parse_ledger_value(&entry.entry_type, "entry type")?;
+            let amount = ledger_amounts
+                .entry((entry.reference_id, entry_type))
+                .or_insert(0_i64);
+            *amount = amount.saturating_add(entry.amount);
+        }
+        if is_last_page {
+            break;
+        }
+    }
+    let (matched_count, discrepancies) = reconcile_settlement(ledger_amounts, req.lines);
+
+    Ok(ApplicationResponse::Json(LedgerReconciliationResponse {
+        connector: req.connector,
+        currency: req.currency,
+        matched_count,
+        discrepancies,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+    use crate::db::{refund::RefundInterface, MockDb};
+
+    fn settlement_line(
+        reference_id: &str,
+        entry_type: JournalEntryType,
+        amount: i64,
+    ) -> SettlementLine {
+        SettlementLine {
+            reference_id: reference_id.to_string(),
+            entry_type,
+            amount,
+        }
+    }
+
+    #[test]
+    fn test_reconcile_settlement() {
+        let ledger_amounts = HashMap::from([
+            (("pay_1".to_string(), JournalEntryType::Sale), 1000),
+            (("pay_2".to_string(), JournalEntryType::Sale), 2000),
+            (("ref_1".to_string(), JournalEntryType::Refund), 500),
+            (("pay_3".to_string(), JournalEntryType::Sale), 3000),
+        ]);
+        let lines = vec![
+            settlement_line("pay_1", JournalEntryType::Sale, 600),
+            settlement_line("pay_1", JournalEntryType::Sale, 400),
+            settlement_line("pay_2", JournalEntryType::Sale, 1900),
+            settlement_line("ref_1", JournalEntryType::Refund, 500),
+            settlement_line("pay_4", JournalEntryType::Sale, 4000),
+        ];
+
+        let (matched_count, discrepancies) = reconcile_settlement(ledger_amounts, lines);
+        assert_eq!(matched_count, 2);
+        let discrepancies: Vec<_> = discrepancies
+            .into_iter()
+            .map(|discrepancy| {
+                (
+                    discrepancy.reference_id,
+                    discrepancy.discrepancy_type,
+                    discrepancy.ledger_amount,
+                    discrepancy.settlement_amount,
+                )
+            })
+            .collect();
+        assert_eq!(
+            discrepancies,
+            vec![
+                (
+                    "pay_2".to_string(),
+                    ReconciliationDiscrepancyType::AmountMismatch,
+                    Some(2000),
+                    Some(1900)
+                ),
+                (
+                    "pay_3".to_string(),
+                    ReconciliationDiscrepancyType::MissingInSettlement,
+                    Some(3000),
+                    None
+                ),
+                (
+                    "pay_4".to_string(),
+                    ReconciliationDiscrepancyType::MissingInLedger,
+                    None,
+                    Some(4000)
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_get_account_balances() {
+        let total = |account: LedgerAccount, currency, total_amount| storage::LedgerAccountTotal {
+            account: account.to_string(),
+            currency,
+            total_amount,
+        };
+        let debit_totals = vec![
+            total(
+                LedgerAccount::ConnectorReceivable,
+                enums::Currency::USD,
+                5000,
+            ),
+            total(LedgerAccount::Refunds, enums::Currency::USD, 700),
+        ];
+        let credit_totals = vec![
+            total(
+                LedgerAccount::ConnectorReceivable,
+                enums::Currency::USD,
+                700,
+            ),
+            total(LedgerAccount::SalesRevenue, enums::Currency::USD, 5000),
+        ];
+
+        let balances = get_account_balances(debit_totals, credit_totals).unwrap();
+        let balances: Vec<_> = balances
+            .into_iter()
+            .map(|balance| (balance.account, balance.balance))
+            .collect();
+        assert_eq!(
+            balances,
+            vec![
+                (LedgerAccount::ConnectorReceivable, 4300),
+                (LedgerAccount::Refunds, 700),
+                (LedgerAccount::SalesRevenue, -5000),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_get_refunded_amount() {
+        let db = MockDb::new(&Default::default()).await;
+        let refund = db
+            .insert_refund(
+                storage::RefundNew {
+                    refund_id: "ref_1".to_string(),
+                    merchant_id: "merchant_1".to_string(),
+                    refund_status: enums::RefundStatus::Pending,
+                    refund_amount: 1000,
+                    ..Default::default()
+                },
+                enums::MerchantStorageScheme::PostgresOnly,
+            )
+            .await
+            .unwrap();
+        let with_status = |refund_status, settled_amount| storage::Refund {
+            refund_status,
+            settled_amount,
+            ..refund.clone()
+        };
+
+        assert_eq!(get_refunded_amount(&refund), None);
+        assert_eq!(
+            get_refunded_amount(&with_status(enums::RefundStatus::Success, None)),
+            Some(1000)
+        );
+        assert_eq!(
+            get_refunded_amount(&with_status(enums::RefundStatus::AmountMismatch, Some(900))),
+            Some(900)
+        );
+        assert_eq!(
+            get_refunded_amount(&with_status(enums::RefundStatus::Failure, Some(900))),
+            None
+        );
+    }
+}
@@ -0,0 +1,183 @@
+use api_models::lookup::{
+    ConnectorReferenceLookupRequest, ConnectorReferenceLookupResponse,
+    ConnectorReferenceLookupResult, LookupObjectType,
+};
+use error_stack::{IntoReport, ResultExt};
+use router_env::{instrument, tracing};
+
+use crate::{
+    core::errors::{self, RouterResponse, RouterResult},
+    routes::AppState,
+    services::ApplicationResponse,
+    types::domain,
+};
+
+async fn find_payments_by_connector_reference(
+    state: &AppState,
+    merchant_account: &domain::MerchantAccount,
+    connector_reference: &str,
+) -> RouterResult<Vec<ConnectorReferenceLookupResult>> {
+    let payment_attempt = match state
+        .store
+        .find_payment_attempt_by_merchant_id_connector_txn_id(
+            &merchant_account.merchant_id,
+            connector_reference,
+            merchant_account.storage_scheme,
+        )
+        .await
+    {
+        Ok(payment_attempt) => payment_attempt,
+        Err(err) if err.current_context().is_db_not_found() => return Ok(vec![]),
+        Err(err) => Err(err)
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Failed to look up payments by connector reference")?,
+    };
+
+    Ok(vec![ConnectorReferenceLookupResult {
+        object: LookupObjectType::Payment,
+        id: payment_attempt.payment_id.clone(),
+        payment_id: Some(payment_attempt.payment_id),
+        connector: payment_attempt.connector,
+        status: payment_attempt.status.to_string(),
+        created_at: payment_attempt.created_at,
+    }])
+}
+
+async fn find_refunds_by_connector_reference(
+    state: &AppState,
+    merchant_account: &domain::MerchantAccount,
+    connector_reference: &str,
+) -> RouterResult<Vec<ConnectorReferenceLookupResult>> {
+    let refunds = match state
+        .store
+        .find_refunds_by_merchant_id_connector_refund_id(
+            &merchant_account.merchant_id,
+            connector_reference,
+            merchant_account.storage_scheme,
+        )
+        .await
+    {
+        Ok(refunds) => refunds,
+        Err(err) if err.current_context().is_db_not_found() => return Ok(vec![]),
+        Err(err) => Err(err)
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Failed to look up refunds by connector reference")?,
+    };
+
+    Ok(refunds
+        .into_iter()
+        .map(|refund| ConnectorReferenceLookupResult {
+            object: LookupObjectType::Refund,
+            id: refund.refund_id,
+            payment_id: Some(refund.payment_id),
+            connector: Some(refund.connector),
+            status: refund.refund_status.to_string(),
+            created_at: refund.created_at,
+        })
+        .collect())
+}
+
+async fn find_disputes_by_connector_reference(
+    state: &AppState,
+    merchant_account: &domain::MerchantAccount,
+    connector_reference: &str,
+) -> RouterResult<Vec<ConnectorReferenceLookupResult>> {
+    let disputes = state
+        .store
+        .find_disputes_by_merchant_id_connector_dispute_id(
+            &merchant_account.merchant_id,
+            connector_reference,
+        )
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to look up disputes by connector reference")?;
+
+    Ok(disputes
+        .into_iter()
+        .map(|dispute| ConnectorReferenceLookupResult {
+            object: LookupObjectType::Dispute,
+            id: dispute.dispute_id,
+            payment_id: Some(dispute.payment_id),
+            connector: Some(dispute.connector),
+            status: dispute.dispute_status.to_string(),
+            created_at: dispute.created_at,
+        })
+        .collect())
+}
+
+async fn find_payouts_by_connector_reference(
+    state: &AppState,
+    merchant_account: &domain::MerchantAccount,
+    connector_reference: &str,
+) -> RouterResult<Vec<ConnectorReferenceLookupResult>> {
+    let payout_attempts = state
+        .store
+        .find_payout_attempts_by_merchant_id_connector_payout_id(
+            &merchant_account.merchant_id,
+            connector_reference,
+        )
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to look up payouts by connector reference")?;
+
+    Ok(payout_attempts
+        .into_iter()
+        .map(|payout_attempt| ConnectorReferenceLookupResult {
+            object: LookupObjectType::Payout,
+            id: payout_attempt.payout_id,
+            payment_id: None,
+            connector: Some(payout_attempt.connector),
+            status: payout_attempt.status.to_string(),
+            created_at: payout_attempt.created_at,
+        })
+        .collect())
+}
+
+/// Looks up the payments, refunds, disputes and payouts of the merchant which were assigned the
+/// given reference by a connector.
+#[instrument(skip(state))]
+pub async fn lookup_by_connector_reference(
+    state: &AppState,
+    merchant_account: domain::MerchantAccount,
+    req: ConnectorReferenceLookupRequest,
+) -> RouterResponse<ConnectorReferenceLookupResponse> {
+    let connector_reference = req.connector_reference.trim();
+    if connector_reference.is_empty() {
+        Err(errors::ApiErrorResponse::InvalidRequestData {
+            message: "`connector_reference` must not be empty".to_string(),
+        })?
+    }
+
+    let (payments, refunds, disputes, payouts) = futures::try_join!(
+        find_payments_by_connector_reference(state, &merchant_account, connector_reference),
+        find_refunds_by_connector_reference(state, &merchant_account, connector_reference),
+        find_disputes_by_connector_reference(state, &merchant_account, connector_reference),
+        find_payouts_by_connector_reference(state, &merchant_account, connector_reference),
+    )?;
+
+    let data: Vec<_> = payments
+        .into_iter()
+        .chain(refunds)
+        .chain(disputes)
+        .chain(payouts)
+        .filter(|result| {
+            req.connector.as_ref().map_or(true, |connector| {
+                result.connector.as_ref() == Some(connector)
+            })
+        })
+        .collect();
+    if data.is_empty() {
+        Err(errors::ApiErrorResponse::GenericNotFoundError {
+            message: "No objects were found for the connector reference".to_string(),
+        })
+        .into_report()?
+    }
+
+    Ok(ApplicationResponse::Json(
+        ConnectorReferenceLookupResponse {
+            connector_reference: connector_reference.to_owned(),
+            count: data.len(),
+            data,
+        },
+    ))
+}
@@ -32,6 +32,7 @@ counter_metric!(
     ATTACH_EVIDENCE_DISPUTE_STATUS_VALIDATION_FAILURE_METRIC,
     GLOBAL_METER
 );
+counter_metric!(DISPUTE_AUTO_DEFEND_ACTION_COUNT, GLOBAL_METER); // No. of actions taken on disputes by auto-defend rules
 
 counter_metric!(WEBHOOK_INCOMING_COUNT, GLOBAL_METER);
 counter_metric!(WEBHOOK_INCOMING_FILTERED_COUNT, GLOBAL_METER);
@@ -43,3 +44,7 @@ counter_metric!(
     WEBHOOK_EVENT_TYPE_IDENTIFICATION_FAILURE_COUNT,
     GLOBAL_METER
 );
+counter_metric!(WEBHOOK_QUEUED_COUNT, GLOBAL_METER);
+counter_metric!(WEBHOOK_QUEUE_REJECTED_COUNT, GLOBAL_METER);
+counter_metric!(WEBHOOK_QUEUE_RETRIED_COUNT, GLOBAL_METER);
+counter_metric!(WEBHOOK_QUEUE_DISCARDED_COUNT, GLOBAL_METER);
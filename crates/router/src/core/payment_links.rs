@@ -0,0 +1,504 @@
+//! Payment links, pages hosted for the merchant through which a customer pays a payment.
+//!
+//! A payment link is created for a payment awaiting a payment method, and its URL is signed with
+//! the payment link signing key, so that only the links issued by the merchant can be opened. The
+//! page of the link loads the SDK with the client secret of the payment, the branding of the link
+//! and the payment method types allowed for it. Payment links expire after a configurable
+//! duration, upon which a process tracker task marks them as expired.
+
+use api_models::{admin::PaymentLinkConfig, payment_links as payment_link_models};
+use common_utils::crypto::{self, SignMessage, VerifySignature};
+use error_stack::{report, IntoReport, ResultExt};
+use masking::PeekInterface;
+use maud::{html, PreEscaped};
+use router_env::{instrument, logger, tracing};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    consts,
+    core::{
+        admin,
+        errors::{self, RouterResponse, RouterResult, StorageErrorExt},
+        payments::{helpers, hosted_checkout},
+    },
+    routes::{metrics, AppState},
+    scheduler::{metrics as scheduler_metrics, utils as pt_utils},
+    services::ApplicationResponse,
+    types::{
+        domain,
+        storage::{self, enums, ProcessTrackerExt},
+    },
+    utils::{self, OptionExt, ValueExt},
+};
+
+const EXPIRY_RUNNER: &str = "PAYMENT_LINK_EXPIRY_WORKFLOW";
+const EXPIRY_TASK: &str = "PAYMENT_LINK_EXPIRY";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PaymentLinkExpiryTrackingData {
+    pub merchant_id: String,
+    pub payment_link_id: String,
+}
+
+/// Signs the identifiers and the expiry of a payment link, so that the URL of the link cannot be
+/// forged or reused for another link.
+fn get_payment_link_signature_payload(payment_link: &storage::PaymentLink) -> String {
+    format!(
+        "{}/{}/{}",
+        payment_link.merchant_id,
+        payment_link.payment_link_id,
+        payment_link.expires_at.assume_utc().unix_timestamp()
+    )
+}
+
+fn get_payment_link_url(
+    state: &AppState,
+    payment_link: &storage::PaymentLink,
+) -> RouterResult<String> {
+    let signature = crypto::HmacSha256
+        .sign_message(
+            state.conf.payment_links.signing_key.peek().as_bytes(),
+            get_payment_link_signature_payload(payment_link).as_bytes(),
+        )
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to sign the URL of the payment link")?;
+
+    Ok(format!(
+        "{}/payment_links/{}/{}?signature={}",
+        state.conf.server.base_url,
+        payment_link.merchant_id,
+        payment_link.payment_link_id,
+        hex::encode(signature)
+    ))
+}
+
+fn get_payment_link_response(
+    state: &AppState,
+    payment_link: storage::PaymentLink,
+) -> RouterResult<payment_link_models::PaymentLinkResponse> {
+    let link = get_payment_link_url(state, &payment_link)?;
+    let theme = payment_link
+        .theme
+        .map(|theme| theme.parse_value("PaymentLinkConfig"))
+        .transpose()
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to deserialize the theme of the payment link")?;
+    let allowed_payment_methods = payment_link
+        .allowed_payment_methods
+        .map(|allowed_payment_methods| {
+            allowed_payment_methods.parse_value("Vec<PaymentMethodType>")
+        })
+        .transpose()
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable(
+            "Failed to deserialize the allowed payment methods of the payment link",
+        )?;
+
+    Ok(payment_link_models::PaymentLinkResponse {
+        payment_link_id: payment_link.payment_link_id,
+        merchant_id: payment_link.merchant_id,
+        payment_id: payment_link.payment_id,
+        link,
+        status: payment_link.status,
+        theme,
+        allowed_payment_methods,
+        expires_at: payment_link.expires_at,
+        created: payment_link.created_at,
+    })
+}
+
+fn is_awaiting_payment_method(payment_intent: &storage::PaymentIntent) -> bool {
+    matches!(
+        payment_intent.status,
+        enums::IntentStatus::RequiresPaymentMethod | enums::IntentStatus::RequiresConfirmation
+    )
+}
+
+fn validate_payment_link_create_request(
+    state: &AppState,
+    req: &payment_link_models::PaymentLinkCreateRequest,
+) -> RouterResult<()> {
+    let settings = &state.conf.payment_links;
+    utils::when(!settings.enabled, || {
+        Err(report!(errors::ApiErrorResponse::NotSupported {
+            message: "Payment links are not enabled".to_string(),
+        }))
+    })?;
+
+    utils::when(
+        req.expires_in.map_or(false, |expires_in| {
+            expires_in == 0 || i64::from(expires_in) > settings.max_expiry
+        }),
+        || {
+            Err(report!(errors::ApiErrorResponse::InvalidRequestData {
+                message: format!(
+                    "expires_in must be greater than zero and at most {} seconds",
+                    settings.max_expiry
+                ),
+            }))
+        },
+    )?;
+
+    utils::when(
+        req.allowed_payment_methods
+            .as_ref()
+            .map_or(false, |allowed_payment_methods| {
+                allowed_payment_methods.is_empty()
+            }),
+        || {
+            Err(report!(errors::ApiErrorResponse::InvalidRequestData {
+                message: "allowed_payment_methods must not be empty".to_string(),
+            }))
+        },
+    )?;
+
+    req.theme
+        .as_ref()
+        .map(|theme| admin::validate_payment_link_config(theme, "theme"))
+        .transpose()?;
+    Ok(())
+}
+
+/// Schedules the expiry of the payment link at its expiry time.
+async fn add_expiry_task(
+    state: &AppState,
+    payment_link: &storage::PaymentLink,
+) -> RouterResult<()> {
+    let process_tracker_id = pt_utils::get_process_tracker_id(
+        EXPIRY_RUNNER,
+        EXPIRY_TASK,
+        &payment_link.payment_link_id,
+        &payment_link.merchant_id,
+    );
+    let tracking_data = PaymentLinkExpiryTrackingData {
+        merchant_id: payment_link.merchant_id.clone(),
+        payment_link_id: payment_link.payment_link_id.clone(),
+    };
+    let process_tracker_entry = <storage::ProcessTracker>::make_process_tracker_new(
+        process_tracker_id,
+        EXPIRY_TASK,
+        EXPIRY_RUNNER,
+        tracking_data,
+        payment_link.expires_at,
+    )
+    .change_context(errors::ApiErrorResponse::InternalServerError)
+    .attach_printable("Failed to construct the payment link expiry task")?;
+
+    state
+        .store
+        .insert_process(process_tracker_entry)
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to schedule the payment link expiry task")?;
+    scheduler_metrics::TASKS_ADDED_COUNT.add(&metrics::CONTEXT, 1, &[]);
+
+    Ok(())
+}
+
+#[instrument(skip(state))]
+pub async fn create_payment_link(
+    state: &AppState,
+    merchant_account: domain::MerchantAccount,
+    req: payment_link_models::PaymentLinkCreateRequest,
+) -> RouterResponse<payment_link_models::PaymentLinkResponse> {
+    validate_payment_link_create_request(state, &req)?;
+
+    let db = &*state.store;
+    let payment_intent = db
+        .find_payment_intent_by_payment_id_merchant_id(
+            &req.payment_id,
+            &merchant_account.merchant_id,
+            merchant_account.storage_scheme,
+        )
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::PaymentNotFound)?;
+    utils::when(!is_awaiting_payment_method(&payment_intent), || {
+        Err(report!(errors::ApiErrorResponse::PreconditionFailed {
+            message: format!(
+                "Payment links can only be created for payments awaiting a payment method, the payment is {}",
+                payment_intent.status
+            ),
+        }))
+    })?;
+
+    let now = common_utils::date_time::now();
+    let expires_in = req
+        .expires_in
+        .map(i64::from)
+        .unwrap_or(state.conf.payment_links.default_expiry);
+    let theme = req
+        .theme
+        .map(|theme| utils::Encode::<PaymentLinkConfig>::encode_to_value(&theme))
+        .transpose()
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to serialize the theme of the payment link")?;
+    let allowed_payment_methods = req
+        .allowed_payment_methods
+        .map(|allowed_payment_methods| {
+            utils::Encode::<Vec<enums::PaymentMethodType>>::encode_to_value(
+                &allowed_payment_methods,
+            )
+        })
+        .transpose()
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to serialize the allowed payment methods of the payment link")?;
+
+    let payment_link = db
+        .insert_payment_link(storage::PaymentLinkNew {
+            payment_link_id: utils::generate_id(consts::ID_LENGTH, "plink"),
+            merchant_id: merchant_account.merchant_id,
+            payment_id: payment_intent.payment_id,
+            status: enums::PaymentLinkStatus::Active,
+            theme,
+            allowed_payment_methods,
+            expires_at: now.saturating_add(time::Duration::seconds(expires_in)),
+            created_at: now,
+            modified_at: now,
+        })
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to insert the payment link")?;
+
+    add_expiry_task(state, &payment_link).await?;
+
+    Ok(ApplicationResponse::Json(get_payment_link_response(
+        state,
+        payment_link,
+    )?))
+}
+
+#[instrument(skip(state))]
+pub async fn retrieve_payment_link(
+    state: &AppState,
+    merchant_account: domain::MerchantAccount,
+    payment_link_id: String,
+) -> RouterResponse<payment_link_models::PaymentLinkResponse> {
+    let payment_link = state
+        .store
+        .find_payment_link_by_merchant_id_payment_link_id(
+            &merchant_account.merchant_id,
+            &payment_link_id,
+        )
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::PaymentLinkNotFound { payment_link_id })?;
+    Ok(ApplicationResponse::Json(get_payment_link_response(
+        state,
+        payment_link,
+    )?))
+}
+
+fn render_expired_page() -> Vec<u8> {
+    hosted_checkout::render_page(
+        "Payment Link",
+        html! {
+            p.status { "This payment link has expired." }
+        },
+    )
+}
+
+/// Serializes the SDK configuration for embedding in the page, escaping the characters which
+/// could close the script element holding it.
+fn get_sdk_config_json(
+    sdk_config: &payment_link_models::PaymentLinkSdkConfig,
+) -> RouterResult<String> {
+    Ok(serde_json::to_string(sdk_config)
+        .into_report()
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to serialize the SDK configuration of the payment link")?
+        .replace('<', "\\u003c")
+        .replace('>', "\\u003e")
+        .replace('&', "\\u0026"))
+}
+
+fn render_payment_link_page(
+    sdk_url: &str,
+    sdk_config: &payment_link_models::PaymentLinkSdkConfig,
+) -> RouterResult<Vec<u8>> {
+    let sdk_config_json = get_sdk_config_json(sdk_config)?;
+    let theme = &sdk_config.theme;
+
+    Ok(hosted_checkout::render_page(
+        "Payment Link",
+        html! {
+            @if let Some(background_color) = &theme.background_color {
+                style { (format!("body {{ background-color: {background_color}; }}")) }
+            }
+            @if let Some(logo) = &theme.logo {
+                img.logo src=(logo) alt="Logo";
+            }
+            div #payment-element {}
+            script #payment-link-config type="application/json" { (PreEscaped(sdk_config_json)) }
+            script src=(sdk_url) {}
+            script {
+                (PreEscaped(r##"
+                    const config = JSON.parse(document.getElementById("payment-link-config").textContent);
+                    const hyper = Hyper(config.publishable_key);
+                    const widgets = hyper.widgets({
+                        clientSecret: config.client_secret,
+                        locale: config.theme.locale,
+                        appearance: { variables: { colorPrimary: config.theme.primary_color } },
+                    });
+                    widgets
+                        .create("payment", { paymentMethodTypes: config.allowed_payment_methods })
+                        .mount("#payment-element");
+                "##))
+            }
+        },
+    ))
+}
+
+/// Renders the page of a payment link, loading the SDK through which the customer pays the
+/// payment. The signature of the URL of the link is verified before the page is rendered.
+#[instrument(skip(state, merchant_account, query))]
+pub async fn render_payment_link(
+    state: &AppState,
+    merchant_account: domain::MerchantAccount,
+    payment_link_id: String,
+    query: payment_link_models::PaymentLinkQuery,
+) -> RouterResponse<()> {
+    let db = &*state.store;
+    let payment_link = db
+        .find_payment_link_by_merchant_id_payment_link_id(
+            &merchant_account.merchant_id,
+            &payment_link_id,
+        )
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::PaymentLinkNotFound {
+            payment_link_id: payment_link_id.clone(),
+        })?;
+
+    let is_signature_valid = hex::decode(&query.signature)
+        .ok()
+        .map(|signature| {
+            crypto::HmacSha256.verify_signature(
+                state.conf.payment_links.signing_key.peek().as_bytes(),
+                &signature,
+                get_payment_link_signature_payload(&payment_link).as_bytes(),
+            )
+        })
+        .transpose()
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to verify the signature of the payment link")?
+        .unwrap_or(false);
+    utils::when(!is_signature_valid, || {
+        Err(report!(errors::ApiErrorResponse::PaymentLinkNotFound {
+            payment_link_id: payment_link_id.clone(),
+        })
+        .attach_printable("Invalid signature of the payment link"))
+    })?;
+
+    // The expiry task could be yet to run for a link which has just expired
+    if payment_link.status == enums::PaymentLinkStatus::Expired
+        || payment_link.expires_at < common_utils::date_time::now()
+    {
+        return Ok(ApplicationResponse::FileData((
+            render_expired_page(),
+            mime::TEXT_HTML_UTF_8,
+        )));
+    }
+
+    let payment_intent = db
+        .find_payment_intent_by_payment_id_merchant_id(
+            &payment_link.payment_id,
+            &merchant_account.merchant_id,
+            merchant_account.storage_scheme,
+        )
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::PaymentNotFound)?;
+    if !is_awaiting_payment_method(&payment_intent) {
+        return Ok(ApplicationResponse::FileData((
+            hosted_checkout::render_status_page(payment_intent.status),
+            mime::TEXT_HTML_UTF_8,
+        )));
+    }
+
+    let client_secret = payment_intent.client_secret.clone();
+    if let Err(error) = helpers::authenticate_client_secret(
+        client_secret.as_ref(),
+        &payment_intent,
+        merchant_account.intent_fulfillment_time,
+    ) {
+        logger::info!(payment_link_client_secret_error=?error);
+        return Ok(ApplicationResponse::FileData((
+            render_expired_page(),
+            mime::TEXT_HTML_UTF_8,
+        )));
+    }
+
+    let theme = match payment_link.theme {
+        Some(theme) => theme
+            .parse_value("PaymentLinkConfig")
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Failed to deserialize the theme of the payment link")?,
+        None => helpers::get_business_profile_defaults(
+            payment_intent.business_country,
+            &payment_intent.business_label,
+            &merchant_account,
+        )?
+        .and_then(|business_profile_defaults| business_profile_defaults.payment_link_config)
+        .unwrap_or_default(),
+    };
+    let allowed_payment_methods = payment_link
+        .allowed_payment_methods
+        .map(|allowed_payment_methods| {
+            allowed_payment_methods.parse_value("Vec<PaymentMethodType>")
+        })
+        .transpose()
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable(
+            "Failed to deserialize the allowed payment methods of the payment link",
+        )?;
+
+    let sdk_config = payment_link_models::PaymentLinkSdkConfig {
+        publishable_key: merchant_account
+            .publishable_key
+            .get_required_value("publishable_key")
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("The merchant account has no publishable key")?,
+        client_secret: client_secret.get_required_value("client_secret")?,
+        payment_id: payment_intent.payment_id,
+        amount: payment_intent.amount,
+        currency: payment_intent.currency.get_required_value("currency")?,
+        return_url: payment_intent.return_url,
+        theme,
+        allowed_payment_methods,
+        expires_at: payment_link.expires_at,
+    };
+
+    Ok(ApplicationResponse::FileData((
+        render_payment_link_page(&state.conf.payment_links.sdk_url, &sdk_config)?,
+        mime::TEXT_HTML_UTF_8,
+    )))
+}
+
+/// Marks a payment link as expired once its expiry time has passed.
+pub async fn expire_payment_link(
+    state: &AppState,
+    tracking_data: &PaymentLinkExpiryTrackingData,
+) -> RouterResult<()> {
+    let db = &*state.store;
+    let payment_link = db
+        .find_payment_link_by_merchant_id_payment_link_id(
+            &tracking_data.merchant_id,
+            &tracking_data.payment_link_id,
+        )
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::PaymentLinkNotFound {
+            payment_link_id: tracking_data.payment_link_id.clone(),
+        })?;
+    if payment_link.status == enums::PaymentLinkStatus::Expired {
+        return Ok(());
+    }
+
+    db.update_payment_link_by_merchant_id_payment_link_id(
+        &payment_link.merchant_id,
+        &payment_link.payment_link_id,
+        storage::PaymentLinkUpdate::StatusUpdate {
+            status: enums::PaymentLinkStatus::Expired,
+        },
+    )
+    .await
+    .change_context(errors::ApiErrorResponse::InternalServerError)
+    .attach_printable("Failed to expire the payment link")?;
+    Ok(())
+}
@@ -1,3 +1,7 @@
 pub mod cards;
+pub mod expiry;
+pub mod network_tokenization;
+pub mod ordering;
+pub mod sharing;
 pub mod transformers;
 pub mod vault;
@@ -32,6 +32,7 @@ use crate::{
     core::{
         errors::{self, StorageErrorExt},
         payment_methods::{
+            expiry, network_tokenization, ordering,
             transformers::{self as payment_methods},
             vault,
         },
@@ -62,6 +63,31 @@ pub async fn create_payment_method(
     merchant_id: &str,
     pm_metadata: Option<serde_json::Value>,
 ) -> errors::CustomResult<storage::PaymentMethod, errors::StorageError> {
+    // The expiry of the card is stored encrypted with the key of the merchant
+    let card_expiry = match req.card.as_ref() {
+        Some(card) => {
+            let key_store = db
+                .get_merchant_key_store_by_merchant_id(
+                    merchant_id,
+                    &db.get_master_key().to_vec().into(),
+                )
+                .await?;
+            Some(
+                expiry::encrypt_card_expiry(
+                    card.card_exp_month.clone(),
+                    card.card_exp_year.clone(),
+                    &key_store,
+                )
+                .await
+                .change_context(errors::StorageError::EncryptionError)?,
+            )
+        }
+        None => None,
+    };
+    let (card_exp_month, card_exp_year) = card_expiry
+        .map(|card_expiry| (card_expiry.card_exp_month, card_expiry.card_exp_year))
+        .unzip();
+
     let response = db
         .insert_payment_method(storage::PaymentMethodNew {
             customer_id: customer_id.to_string(),
@@ -72,6 +98,8 @@ pub async fn create_payment_method(
             payment_method_issuer: req.payment_method_issuer.clone(),
             scheme: req.card_network.clone(),
             metadata: pm_metadata.map(masking::Secret::new),
+            card_exp_month,
+            card_exp_year,
             ..storage::PaymentMethodNew::default()
         })
         .await?;
@@ -113,6 +141,7 @@ pub async fn add_payment_method(
                 recurring_enabled: false,           //[#219]
                 installment_payment_enabled: false, //[#219]
                 payment_experience: Some(vec![api_models::enums::PaymentExperience::RedirectToUrl]), //[#219]
+                account_update: None,
             };
             Ok((payment_method_response, false))
         }
@@ -121,7 +150,7 @@ pub async fn add_payment_method(
     let (resp, is_duplicate) = response?;
     if !is_duplicate {
         let pm_metadata = resp.metadata.as_ref().map(|data| data.peek());
-        create_payment_method(
+        let payment_method = create_payment_method(
             &*state.store,
             &req,
             &customer_id,
@@ -132,6 +161,18 @@ pub async fn add_payment_method(
         .await
         .change_context(errors::ApiErrorResponse::InternalServerError)
         .attach_printable("Failed to save Payment Method")?;
+        expiry::add_expiry_task_if_required(
+            state,
+            &payment_method,
+            req.card.as_ref().map(|card| &card.card_exp_month),
+            req.card.as_ref().map(|card| &card.card_exp_year),
+        )
+        .await?;
+        network_tokenization::provision_network_token_if_required(
+            state,
+            payment_method,
+            req.card.as_ref(),
+        );
     }
 
     Ok(resp).map(services::ApplicationResponse::Json)
@@ -153,13 +194,9 @@ pub async fn update_customer_payment_method(
         .await
         .to_not_found_response(errors::ApiErrorResponse::PaymentMethodNotFound)?;
     if pm.payment_method == enums::PaymentMethod::Card {
-        delete_card_from_locker(
-            state,
-            &pm.customer_id,
-            &pm.merchant_id,
-            &pm.payment_method_id,
-        )
-        .await?;
+        delete_card_from_locker(state, &pm.customer_id, &pm.merchant_id, get_locker_id(&pm))
+            .await?;
+        network_tokenization::delete_network_token_if_exists(state, &pm);
     };
     let new_pm = api::PaymentMethodCreate {
         payment_method: pm.payment_method,
@@ -279,7 +316,7 @@ pub async fn get_payment_method_from_hs_locker<'a>(
     merchant_id: &str,
     payment_method_reference: &'a str,
 ) -> errors::CustomResult<Secret<String>, errors::VaultError> {
-    let locker = &state.conf.locker;
+    let locker = state.get_locker();
     #[cfg(not(feature = "kms"))]
     let jwekey = &state.conf.jwekey;
     #[cfg(feature = "kms")]
@@ -333,7 +370,7 @@ pub async fn call_to_card_hs(
     customer_id: &str,
     merchant_account: &domain::MerchantAccount,
 ) -> errors::CustomResult<payment_methods::StoreCardRespPayload, errors::VaultError> {
-    let locker = &state.conf.locker;
+    let locker = state.get_locker();
     #[cfg(not(feature = "kms"))]
     let jwekey = &state.conf.jwekey;
     #[cfg(feature = "kms")]
@@ -401,7 +438,7 @@ pub async fn get_card_from_hs_locker<'a>(
     merchant_id: &str,
     card_reference: &'a str,
 ) -> errors::CustomResult<payment_methods::Card, errors::VaultError> {
-    let locker = &state.conf.locker;
+    let locker = state.get_locker();
     #[cfg(not(feature = "kms"))]
     let jwekey = &state.conf.jwekey;
     #[cfg(feature = "kms")]
@@ -454,7 +491,7 @@ pub async fn delete_card_from_hs_locker<'a>(
     merchant_id: &str,
     card_reference: &'a str,
 ) -> errors::RouterResult<payment_methods::DeleteCardResp> {
-    let locker = &state.conf.locker;
+    let locker = state.get_locker();
     #[cfg(not(feature = "kms"))]
     let jwekey = &state.conf.jwekey;
     #[cfg(feature = "kms")]
@@ -1201,6 +1238,15 @@ pub async fn list_payment_methods(
         });
     }
 
+    ordering::get_payment_method_ranking(
+        state,
+        &merchant_account,
+        payment_intent.as_ref(),
+        customer.as_ref(),
+    )
+    .await
+    .order(&mut payment_method_responses);
+
     Ok(services::ApplicationResponse::Json(
         api::PaymentMethodListResponse {
             redirect_url: merchant_account.return_url,
@@ -1244,7 +1290,8 @@ pub async fn filter_payment_methods(
                 .payment_method_types
                 .unwrap_or_default()
             {
-                if filter_recurring_based(&payment_method_type_info, req.recurring_enabled)
+                if payment_method_type_info.enabled
+                    && filter_recurring_based(&payment_method_type_info, req.recurring_enabled)
                     && filter_installment_based(
                         &payment_method_type_info,
                         req.installment_payment_enabled,
@@ -1324,6 +1371,15 @@ pub async fn filter_payment_methods(
                         })
                         .unwrap_or(true);
 
+                    let filter8 = filter_pm_based_on_connector_amount_limits(
+                        config,
+                        &connector,
+                        payment_method_object.payment_method_type,
+                        payment_intent
+                            .map(|payment_intent| payment_intent.amount)
+                            .or(req.amount),
+                    );
+
                     let connector = connector.clone();
 
                     let response_pm_type = ResponsePaymentMethodIntermediate::new(
@@ -1332,7 +1388,15 @@ pub async fn filter_payment_methods(
                         payment_method,
                     );
 
-                    if filter && filter2 && filter3 && filter4 && filter5 && filter6 && filter7 {
+                    if filter
+                        && filter2
+                        && filter3
+                        && filter4
+                        && filter5
+                        && filter6
+                        && filter7
+                        && filter8
+                    {
                         resp.push(response_pm_type);
                     }
                 }
@@ -1606,6 +1670,21 @@ fn filter_amount_based(payment_method: &RequestPaymentMethodTypes, amount: Optio
     min_check && max_check
 }
 
+/// Filters out the payment method type if the amount of the payment is outside the amount limits
+/// of the connector for the payment method type
+fn filter_pm_based_on_connector_amount_limits(
+    config: &settings::ConnectorFilters,
+    connector: &str,
+    payment_method_type: api_enums::PaymentMethodType,
+    amount: Option<i64>,
+) -> bool {
+    amount
+        .zip(config.get_payment_method_type_filter(connector, payment_method_type))
+        .map_or(true, |(amount, filter)| {
+            filter.is_amount_within_limits(amount)
+        })
+}
+
 fn filter_pm_based_on_allowed_types(
     allowed_types: Option<&Vec<api_enums::PaymentMethodType>>,
     payment_method_type: &api_enums::PaymentMethodType,
@@ -1711,48 +1790,70 @@ pub async fn do_list_customer_pm_fetch_customer_if_not_passed(
     }
 }
 
-pub async fn list_customer_payment_method(
-    state: &routes::AppState,
-    merchant_account: domain::MerchantAccount,
-    key_store: domain::MerchantKeyStore,
-    payment_intent: Option<storage::PaymentIntent>,
-    customer_id: &str,
-) -> errors::RouterResponse<api::CustomerPaymentMethodsListResponse> {
-    let db = &*state.store;
-    db.find_customer_by_customer_id_merchant_id(
-        customer_id,
-        &merchant_account.merchant_id,
-        &key_store,
-    )
-    .await
-    .to_not_found_response(errors::ApiErrorResponse::CustomerNotFound)?;
-
+/// Whether the merchant requires the CVV to be entered again when a saved card is used, as
+/// configured using the `{merchant_id}_requires_cvv` config. CVV is required by default. The same
+/// config decides whether saved cards are listed as requiring the CVV, and whether on-session
+/// payments with saved cards are rejected without it.
+pub async fn is_cvv_required_for_saved_card(
+    db: &dyn db::StorageInterface,
+    merchant_id: &str,
+) -> errors::RouterResult<bool> {
     let is_requires_cvv = db
-        .find_config_by_key(format!("{}_requires_cvv", merchant_account.merchant_id).as_str())
+        .find_config_by_key(format!("{merchant_id}_requires_cvv").as_str())
         .await;
 
-    let requires_cvv = match is_requires_cvv {
+    match is_requires_cvv {
         // If an entry is found with the config value as `false`, we set requires_cvv to false
-        Ok(value) => value.config != "false",
+        Ok(value) => Ok(value.config != "false"),
         Err(err) => {
             if err.current_context().is_db_not_found() {
                 // By default, cvv is made required field for all merchants
-                true
+                Ok(true)
             } else {
                 Err(err
                     .change_context(errors::ApiErrorResponse::InternalServerError)
-                    .attach_printable("Failed to fetch merchant_id config for requires_cvv"))?
+                    .attach_printable("Failed to fetch merchant_id config for requires_cvv"))
             }
         }
-    };
+    }
+}
+
+pub async fn list_customer_payment_method(
+    state: &routes::AppState,
+    merchant_account: domain::MerchantAccount,
+    key_store: domain::MerchantKeyStore,
+    payment_intent: Option<storage::PaymentIntent>,
+    customer_id: &str,
+) -> errors::RouterResponse<api::CustomerPaymentMethodsListResponse> {
+    let db = &*state.store;
+    let customer = db
+        .find_customer_by_customer_id_merchant_id(
+            customer_id,
+            &merchant_account.merchant_id,
+            &key_store,
+        )
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::CustomerNotFound)?;
+
+    let requires_cvv = is_cvv_required_for_saved_card(db, &merchant_account.merchant_id).await?;
 
-    let resp = db
+    let mut resp = db
         .find_payment_method_by_customer_id_merchant_id_list(
             customer_id,
             &merchant_account.merchant_id,
         )
         .await
         .to_not_found_response(errors::ApiErrorResponse::PaymentMethodNotFound)?;
+    // List the default payment method first, followed by the most recently used ones
+    let is_default_payment_method = |pm: &storage::PaymentMethod| {
+        customer.default_payment_method_id.as_ref() == Some(&pm.payment_method_id)
+    };
+    resp.sort_by(|a, b| {
+        is_default_payment_method(b)
+            .cmp(&is_default_payment_method(a))
+            .then_with(|| b.last_used_at.cmp(&a.last_used_at))
+            .then_with(|| b.created_at.cmp(&a.created_at))
+    });
     //let mca = query::find_mca_by_merchant_id(conn, &merchant_account.merchant_id)?;
     let mut customer_pms = Vec::new();
     for pm in resp.into_iter() {
@@ -1773,8 +1874,10 @@ pub async fn list_customer_payment_method(
             None
         };
         //Need validation for enabled payment method ,querying MCA
+        let is_default = is_default_payment_method(&pm);
         let pma = api::CustomerPaymentMethod {
             payment_token: parent_payment_method_token.to_owned(),
+            payment_method_id: pm.payment_method_id.clone(),
             customer_id: pm.customer_id,
             payment_method: pm.payment_method,
             payment_method_type: pm.payment_method_type,
@@ -1791,6 +1894,8 @@ pub async fn list_customer_payment_method(
             #[cfg(not(feature = "payouts"))]
             bank_transfer: None,
             requires_cvv,
+            is_default,
+            last_used_at: pm.last_used_at,
         };
         customer_pms.push(pma.to_owned());
 
@@ -1826,6 +1931,29 @@ pub async fn list_customer_payment_method(
             .change_context(errors::ApiErrorResponse::InternalServerError)
             .attach_printable("Failed to add data in redis")?;
 
+        if pm.network_token.is_some() {
+            let key_for_network_token = format!(
+                "pm_token_{}_{}_{}",
+                parent_payment_method_token,
+                pma.payment_method,
+                network_tokenization::NETWORK_TOKEN_KEY_SUFFIX
+            );
+            redis_conn
+                .set_key_with_expiry(
+                    &key_for_network_token,
+                    pm.payment_method_id.clone(),
+                    consts::TOKEN_TTL - time_eslapsed.whole_seconds(),
+                )
+                .await
+                .map_err(|error| {
+                    logger::error!(network_token_kv_error=?error);
+                    errors::StorageError::KVError
+                })
+                .into_report()
+                .change_context(errors::ApiErrorResponse::InternalServerError)
+                .attach_printable("Failed to add data in redis")?;
+        }
+
         if let Some(metadata) = pma.metadata {
             let pm_metadata_vec: payment_methods::PaymentMethodMetadata = metadata
                 .parse_value("PaymentMethodMetadata")
@@ -1869,15 +1997,10 @@ pub async fn get_lookup_key_from_locker(
     payment_token: &str,
     pm: &storage::PaymentMethod,
 ) -> errors::RouterResult<api::CardDetailFromLocker> {
-    let card = get_card_from_locker(
-        state,
-        &pm.customer_id,
-        &pm.merchant_id,
-        &pm.payment_method_id,
-    )
-    .await
-    .change_context(errors::ApiErrorResponse::InternalServerError)
-    .attach_printable("Error getting card from card vault")?;
+    let card = get_card_from_locker(state, &pm.customer_id, &pm.merchant_id, get_locker_id(pm))
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Error getting card from card vault")?;
     let card_detail = payment_methods::get_card_detail(pm, card)
         .change_context(errors::ApiErrorResponse::InternalServerError)
         .attach_printable("Get Card Details Failed")?;
@@ -2090,6 +2213,7 @@ impl BasiliskCardSupport {
             &*state.store,
             &lookup_key,
             enums::PaymentMethod::Card,
+            &pm.merchant_id,
         )
         .await?;
         scheduler_metrics::TOKENIZED_DATA_COUNT.add(&metrics::CONTEXT, 1, &[]);
@@ -2097,6 +2221,62 @@ impl BasiliskCardSupport {
     }
 }
 
+/// Returns the reference of the card of a payment method in the locker.
+pub fn get_locker_id(pm: &storage::PaymentMethod) -> &str {
+    pm.locker_id.as_deref().unwrap_or(&pm.payment_method_id)
+}
+
+fn get_account_update(pm: &storage::PaymentMethod) -> Option<api::PaymentMethodAccountUpdate> {
+    match (
+        pm.account_update_type,
+        pm.account_update_source.clone(),
+        pm.account_updated_at,
+    ) {
+        (Some(update_type), Some(source), Some(updated_at)) => {
+            Some(api::PaymentMethodAccountUpdate {
+                update_type,
+                source,
+                updated_at,
+            })
+        }
+        _ => None,
+    }
+}
+
+pub async fn get_payment_method_response(
+    state: &routes::AppState,
+    pm: storage::PaymentMethod,
+) -> errors::RouterResult<api::PaymentMethodResponse> {
+    let card = if pm.payment_method == enums::PaymentMethod::Card {
+        let card =
+            get_card_from_locker(state, &pm.customer_id, &pm.merchant_id, get_locker_id(&pm))
+                .await
+                .change_context(errors::ApiErrorResponse::InternalServerError)
+                .attach_printable("Error getting card from card vault")?;
+        let card_detail = payment_methods::get_card_detail(&pm, card)
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Failed while getting card details from locker")?;
+        Some(card_detail)
+    } else {
+        None
+    };
+    let account_update = get_account_update(&pm);
+    Ok(api::PaymentMethodResponse {
+        merchant_id: pm.merchant_id,
+        customer_id: Some(pm.customer_id),
+        payment_method_id: pm.payment_method_id,
+        payment_method: pm.payment_method,
+        payment_method_type: pm.payment_method_type,
+        card,
+        metadata: pm.metadata,
+        created: Some(pm.created_at),
+        recurring_enabled: false,           //[#219]
+        installment_payment_enabled: false, //[#219]
+        payment_experience: Some(vec![api_models::enums::PaymentExperience::RedirectToUrl]), //[#219],
+        account_update,
+    })
+}
+
 #[instrument(skip_all)]
 pub async fn retrieve_payment_method(
     state: &routes::AppState,
@@ -2107,44 +2287,16 @@ pub async fn retrieve_payment_method(
         .find_payment_method(&pm.payment_method_id)
         .await
         .to_not_found_response(errors::ApiErrorResponse::PaymentMethodNotFound)?;
-    let card = if pm.payment_method == enums::PaymentMethod::Card {
-        let card = get_card_from_locker(
-            state,
-            &pm.customer_id,
-            &pm.merchant_id,
-            &pm.payment_method_id,
-        )
+    get_payment_method_response(state, pm)
         .await
-        .change_context(errors::ApiErrorResponse::InternalServerError)
-        .attach_printable("Error getting card from card vault")?;
-        let card_detail = payment_methods::get_card_detail(&pm, card)
-            .change_context(errors::ApiErrorResponse::InternalServerError)
-            .attach_printable("Failed while getting card details from locker")?;
-        Some(card_detail)
-    } else {
-        None
-    };
-    Ok(services::ApplicationResponse::Json(
-        api::PaymentMethodResponse {
-            merchant_id: pm.merchant_id,
-            customer_id: Some(pm.customer_id),
-            payment_method_id: pm.payment_method_id,
-            payment_method: pm.payment_method,
-            payment_method_type: pm.payment_method_type,
-            card,
-            metadata: pm.metadata,
-            created: Some(pm.created_at),
-            recurring_enabled: false,           //[#219]
-            installment_payment_enabled: false, //[#219]
-            payment_experience: Some(vec![api_models::enums::PaymentExperience::RedirectToUrl]), //[#219],
-        },
-    ))
+        .map(services::ApplicationResponse::Json)
 }
 
 #[instrument(skip_all)]
 pub async fn delete_payment_method(
     state: &routes::AppState,
     merchant_account: domain::MerchantAccount,
+    key_store: domain::MerchantKeyStore,
     pm_id: api::PaymentMethodId,
 ) -> errors::RouterResponse<api::PaymentMethodDeleteResponse> {
     let db = &*state.store;
@@ -2158,7 +2310,7 @@ pub async fn delete_payment_method(
             state,
             &key.customer_id,
             &key.merchant_id,
-            pm_id.payment_method_id.as_str(),
+            get_locker_id(&key),
         )
         .await?;
 
@@ -2168,6 +2320,7 @@ pub async fn delete_payment_method(
             logger::error!("Error: Deleting Card From Locker!\n{:#?}", response);
             Err(errors::ApiErrorResponse::InternalServerError)?
         }
+        network_tokenization::delete_network_token_if_exists(state, &key);
     }
 
     db.delete_payment_method_by_merchant_id_payment_method_id(
@@ -2177,6 +2330,32 @@ pub async fn delete_payment_method(
     .await
     .to_not_found_response(errors::ApiErrorResponse::PaymentMethodNotFound)?;
 
+    // A deleted payment method no longer is the default payment method of its customer
+    let customer = db
+        .find_customer_optional_by_customer_id_merchant_id(
+            &key.customer_id,
+            &merchant_account.merchant_id,
+            &key_store,
+        )
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed while fetching the customer of the payment method")?;
+    if customer.map_or(false, |customer| {
+        customer.default_payment_method_id.as_ref() == Some(&key.payment_method_id)
+    }) {
+        db.update_customer_by_customer_id_merchant_id(
+            key.customer_id.clone(),
+            merchant_account.merchant_id.clone(),
+            storage::CustomerUpdate::DefaultPaymentMethodUpdate {
+                default_payment_method_id: None,
+            },
+            &key_store,
+        )
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed while unsetting the default payment method of the customer")?;
+    }
+
     Ok(services::ApplicationResponse::Json(
         api::PaymentMethodDeleteResponse {
             payment_method_id: key.payment_method_id,
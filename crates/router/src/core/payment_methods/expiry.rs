@@ -0,0 +1,633 @@
+//! Refreshing of saved cards nearing their expiry.
+//!
+//! When a card is saved along with its expiry date, a process tracker task is scheduled the
+//! configured number of days before the card expires. The task requests the latest details of the
+//! card from the account updater services of the connectors listed in the `payment_method_expiry`
+//! settings, which relay the updates collected by the card networks from the issuers (Visa
+//! Account Updater, Mastercard Automatic Billing Updater). If the card was renewed or replaced, it
+//! is refreshed in the locker, the update is recorded on the payment method along with the
+//! connector which reported it, and a `payment_method_updated` webhook is sent to the merchant.
+//! Otherwise the merchant is sent a `payment_method_expiring` webhook, so that the customer can be
+//! asked for another card.
+//!
+//! The expiry of saved cards is stored encrypted with the key of the merchant. The expiry of the
+//! cards saved before it was stored is fetched from the locker by a backfill task, which also
+//! schedules the refreshing of those cards.
+
+use std::str::FromStr;
+
+use common_utils::date_time;
+use diesel_models::encryption::Encryption;
+use error_stack::ResultExt;
+use masking::{PeekInterface, Secret};
+use router_env::logger;
+use serde::{Deserialize, Serialize};
+use time::PrimitiveDateTime;
+
+use super::{cards, transformers as payment_methods};
+use crate::{
+    core::{
+        errors::{self, CustomResult, RouterResult, StorageErrorExt},
+        payments::CallConnectorAction,
+        utils as core_utils, webhooks,
+    },
+    routes::{metrics, AppState},
+    scheduler::{metrics as scheduler_metrics, utils as pt_utils},
+    services,
+    types::{
+        self, api,
+        domain::{self, types as domain_types},
+        storage::{self, enums, ProcessTrackerExt},
+    },
+};
+
+const EXPIRY_RUNNER: &str = "PAYMENT_METHOD_EXPIRY_WORKFLOW";
+const EXPIRY_TASK: &str = "PAYMENT_METHOD_EXPIRY";
+const EXPIRY_BACKFILL_BATCH_SIZE: i64 = 100;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PaymentMethodExpiryTrackingData {
+    pub merchant_id: String,
+    pub payment_method_id: String,
+    /// When the card was last refreshed before the task was scheduled, absent if the card was
+    /// never refreshed
+    #[serde(default)]
+    pub account_updated_at: Option<PrimitiveDateTime>,
+}
+
+/// The progress of the backfilling of the expiry of the cards saved before their expiry was
+/// stored, which is scheduled by the migration encrypting the expiry of saved cards
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PaymentMethodExpiryBackfillTrackingData {
+    /// The ID of the last payment method processed by the backfill
+    pub last_id: i32,
+}
+
+/// The expiry of a saved card, encrypted with the key of the merchant for storage with the
+/// payment method
+pub struct EncryptedCardExpiry {
+    pub card_exp_month: Encryption,
+    pub card_exp_year: Encryption,
+}
+
+/// Encrypts the expiry of a saved card for storage with the payment method
+pub async fn encrypt_card_expiry(
+    card_exp_month: Secret<String>,
+    card_exp_year: Secret<String>,
+    key_store: &domain::MerchantKeyStore,
+) -> CustomResult<EncryptedCardExpiry, common_utils::errors::CryptoError> {
+    let key = key_store.key.get_inner().peek();
+    Ok(EncryptedCardExpiry {
+        card_exp_month: domain_types::encrypt(card_exp_month, key).await?.into(),
+        card_exp_year: domain_types::encrypt(card_exp_year, key).await?.into(),
+    })
+}
+
+/// Returns the time at which a card expires, which is the end of its expiry month.
+fn get_card_expiry(card_exp_month: &str, card_exp_year: &str) -> Option<PrimitiveDateTime> {
+    let month = time::Month::try_from(card_exp_month.trim().parse::<u8>().ok()?).ok()?;
+    let year = card_exp_year.trim().parse::<i32>().ok()?;
+    let year = if year < 100 { year + 2000 } else { year };
+    let year = if month == time::Month::December {
+        year + 1
+    } else {
+        year
+    };
+    time::Date::from_calendar_date(year, month.next(), 1)
+        .ok()
+        .map(time::Date::midnight)
+}
+
+/// Constructs the task refreshing a saved card shortly before its expiry, if the refreshing of
+/// expiring cards is enabled and the expiry of the card is known.
+pub fn get_expiry_task(
+    state: &AppState,
+    payment_method_id: &str,
+    merchant_id: &str,
+    account_updated_at: Option<PrimitiveDateTime>,
+    card_exp_month: Option<&Secret<String>>,
+    card_exp_year: Option<&Secret<String>>,
+) -> RouterResult<Option<storage::ProcessTrackerNew>> {
+    let expiry_settings = &state.conf.payment_method_expiry;
+    if !expiry_settings.enabled {
+        return Ok(None);
+    }
+
+    let (Some(card_exp_month), Some(card_exp_year)) = (card_exp_month, card_exp_year) else {
+        return Ok(None);
+    };
+    let Some(card_expiry) = get_card_expiry(card_exp_month.peek(), card_exp_year.peek()) else {
+        logger::warn!(
+            payment_method_id = %payment_method_id,
+            "Unable to determine the expiry of the card, skipping the expiry task"
+        );
+        return Ok(None);
+    };
+
+    let schedule_time = std::cmp::max(
+        card_expiry.saturating_sub(time::Duration::days(expiry_settings.notice_days)),
+        date_time::now(),
+    );
+    // The time of the last refresh of the card is part of the task ID, as a task is scheduled
+    // for every expiry of a card which is refreshed
+    let process_tracker_id = pt_utils::get_process_tracker_id(
+        EXPIRY_RUNNER,
+        EXPIRY_TASK,
+        &format!(
+            "{payment_method_id}_{}",
+            account_updated_at.map_or(0, |account_updated_at| account_updated_at
+                .assume_utc()
+                .unix_timestamp())
+        ),
+        merchant_id,
+    );
+    let tracking_data = PaymentMethodExpiryTrackingData {
+        merchant_id: merchant_id.to_owned(),
+        payment_method_id: payment_method_id.to_owned(),
+        account_updated_at,
+    };
+    let process_tracker_entry = <storage::ProcessTracker>::make_process_tracker_new(
+        process_tracker_id,
+        EXPIRY_TASK,
+        EXPIRY_RUNNER,
+        tracking_data,
+        schedule_time,
+    )
+    .change_context(errors::ApiErrorResponse::InternalServerError)
+    .attach_printable("Failed to construct the payment method expiry task")?;
+
+    Ok(Some(process_tracker_entry))
+}
+
+/// Schedules the refreshing of a saved card shortly before its expiry, if the refreshing of
+/// expiring cards is enabled and the expiry of the card is known.
+pub async fn add_expiry_task_if_required(
+    state: &AppState,
+    payment_method: &storage::PaymentMethod,
+    card_exp_month: Option<&Secret<String>>,
+    card_exp_year: Option<&Secret<String>>,
+) -> RouterResult<()> {
+    if payment_method.payment_method != enums::PaymentMethod::Card {
+        return Ok(());
+    }
+    let Some(process_tracker_entry) = get_expiry_task(
+        state,
+        &payment_method.payment_method_id,
+        &payment_method.merchant_id,
+        payment_method.account_updated_at,
+        card_exp_month,
+        card_exp_year,
+    )?
+    else {
+        return Ok(());
+    };
+
+    state
+        .store
+        .insert_process(process_tracker_entry)
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to schedule the payment method expiry task")?;
+    scheduler_metrics::TASKS_ADDED_COUNT.add(&metrics::CONTEXT, 1, &[]);
+
+    Ok(())
+}
+
+/// Requests the latest details of a card from the account updater services of the connectors
+/// of the merchant, in the order of preference of the settings. Returns the response of the first
+/// connector which responded successfully, along with the name of the connector.
+async fn request_account_update(
+    state: &AppState,
+    key_store: &domain::MerchantKeyStore,
+    payment_method: &storage::PaymentMethod,
+    card: &payment_methods::Card,
+) -> RouterResult<Option<(String, types::AccountUpdaterResponseData)>> {
+    let account_updater_connectors = &state.conf.payment_method_expiry.account_updater_connectors;
+    if account_updater_connectors.is_empty() {
+        return Ok(None);
+    }
+
+    let merchant_connector_accounts = state
+        .store
+        .find_merchant_connector_account_by_merchant_id_and_disabled_list(
+            &payment_method.merchant_id,
+            false,
+            key_store,
+        )
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to fetch the connector accounts of the merchant")?;
+
+    for connector_name in account_updater_connectors {
+        let Some(merchant_connector_account) = merchant_connector_accounts
+            .iter()
+            .find(|account| &account.connector_name == connector_name)
+        else {
+            continue;
+        };
+
+        let connector_data = api::ConnectorData::get_connector_by_name(
+            &state.conf.connectors,
+            connector_name,
+            api::GetToken::Connector,
+        )?;
+        let connector_integration: services::BoxedConnectorIntegration<
+            '_,
+            api::AccountUpdate,
+            types::AccountUpdaterRequestData,
+            types::AccountUpdaterResponseData,
+        > = connector_data.connector.get_connector_integration();
+        let router_data = core_utils::construct_account_updater_router_data(
+            merchant_connector_account,
+            payment_method,
+            types::AccountUpdaterRequestData {
+                card_number: card.card_number.clone(),
+                card_exp_month: card.card_exp_month.clone(),
+                card_exp_year: card.card_exp_year.clone(),
+                card_network: payment_method
+                    .scheme
+                    .as_deref()
+                    .and_then(|scheme| enums::CardNetwork::from_str(scheme).ok()),
+            },
+        )?;
+
+        match services::execute_connector_processing_step(
+            state,
+            connector_integration,
+            &router_data,
+            CallConnectorAction::Trigger,
+            None,
+        )
+        .await
+        {
+            Ok(router_data) => match router_data.response {
+                Ok(account_update) => return Ok(Some((connector_name.clone(), account_update))),
+                Err(error) => logger::warn!(
+                    connector = %connector_name,
+                    error_code = %error.code,
+                    error_message = %error.message,
+                    "Account updater request was unsuccessful"
+                ),
+            },
+            Err(error) => {
+                logger::error!(connector = %connector_name, account_updater_error=?error);
+            }
+        }
+    }
+
+    Ok(None)
+}
+
+async fn send_payment_method_webhook(
+    state: &AppState,
+    merchant_account: domain::MerchantAccount,
+    event_type: enums::EventType,
+    payment_method: storage::PaymentMethod,
+) -> RouterResult<()> {
+    // The payment method is updated every time it is refreshed or notified as expiring
+    let new_event = storage::EventNew {
+        event_id: format!(
+            "{}_{event_type}_{}",
+            payment_method.payment_method_id,
+            payment_method.last_modified.assume_utc().unix_timestamp()
+        ),
+        event_type,
+        event_class: enums::EventClass::PaymentMethods,
+        is_webhook_notified: false,
+        intent_reference_id: None,
+        primary_object_id: payment_method.payment_method_id.clone(),
+        primary_object_type: enums::EventObjectType::PaymentMethodDetails,
+    };
+    let event = state
+        .store
+        .insert_event(new_event)
+        .await
+        .change_context(errors::ApiErrorResponse::WebhookProcessingFailure)
+        .attach_printable("Failed to insert event in events table")?;
+
+    if state.conf.webhooks.outgoing_enabled {
+        let payment_method_response =
+            cards::get_payment_method_response(state, payment_method).await?;
+        let outgoing_webhook = api::OutgoingWebhook {
+            merchant_id: merchant_account.merchant_id.clone(),
+            event_id: event.event_id,
+            event_type: event.event_type,
+            content: api::OutgoingWebhookContent::PaymentMethodDetails(Box::new(
+                payment_method_response,
+            )),
+            timestamp: event.created_at,
+            source_validation: None,
+        };
+        // The scheduler does not run on an actix arbiter, so the webhook is sent inline rather
+        // than spawned as in `create_event_and_trigger_outgoing_webhook`
+        webhooks::trigger_webhook_to_merchant::<api::OutgoingWebhook>(
+            merchant_account,
+            outgoing_webhook,
+            state,
+        )
+        .await
+        .change_context(errors::ApiErrorResponse::WebhookProcessingFailure)?;
+    }
+    Ok(())
+}
+
+/// Stores the renewed or replaced card in the locker, records the update on the payment method
+/// and schedules the refreshing of the card before its new expiry. The card previously stored in
+/// the locker is deleted once the payment method refers to the refreshed card.
+#[allow(clippy::too_many_arguments)]
+async fn refresh_card(
+    state: &AppState,
+    merchant_account: &domain::MerchantAccount,
+    key_store: &domain::MerchantKeyStore,
+    payment_method: storage::PaymentMethod,
+    card: payment_methods::Card,
+    card_number: Option<::cards::CardNumber>,
+    card_exp_month: Secret<String>,
+    card_exp_year: Secret<String>,
+    account_update_type: enums::AccountUpdateType,
+    connector_name: String,
+) -> RouterResult<storage::PaymentMethod> {
+    let card_detail = api::CardDetail {
+        card_number: card_number.unwrap_or(card.card_number),
+        card_exp_month,
+        card_exp_year,
+        card_holder_name: card.name_on_card,
+        nick_name: card.nick_name.map(Secret::new),
+    };
+    let stored_card = cards::call_to_card_hs(
+        state,
+        &card_detail,
+        None,
+        &payment_method.customer_id,
+        merchant_account,
+    )
+    .await
+    .change_context(errors::ApiErrorResponse::InternalServerError)
+    .attach_printable("Failed to store the refreshed card in the locker")?;
+
+    let card_expiry = encrypt_card_expiry(
+        card_detail.card_exp_month.clone(),
+        card_detail.card_exp_year.clone(),
+        key_store,
+    )
+    .await
+    .change_context(errors::ApiErrorResponse::InternalServerError)
+    .attach_printable("Failed to encrypt the expiry of the refreshed card")?;
+
+    let previous_locker_id = cards::get_locker_id(&payment_method).to_owned();
+    let payment_method = state
+        .store
+        .update_payment_method(
+            payment_method,
+            storage::PaymentMethodUpdate::AccountUpdate {
+                locker_id: Some(stored_card.card_reference.clone()),
+                card_exp_month: Some(card_expiry.card_exp_month),
+                card_exp_year: Some(card_expiry.card_exp_year),
+                account_update_type,
+                account_update_source: connector_name,
+                account_updated_at: date_time::now(),
+            },
+        )
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::PaymentMethodNotFound)?;
+
+    if previous_locker_id != stored_card.card_reference {
+        if let Err(error) = cards::delete_card_from_locker(
+            state,
+            &payment_method.customer_id,
+            &payment_method.merchant_id,
+            &previous_locker_id,
+        )
+        .await
+        {
+            logger::error!(
+                payment_method_id = %payment_method.payment_method_id,
+                delete_refreshed_card_error=?error
+            );
+        }
+    }
+
+    add_expiry_task_if_required(
+        state,
+        &payment_method,
+        Some(&card_detail.card_exp_month),
+        Some(&card_detail.card_exp_year),
+    )
+    .await?;
+
+    Ok(payment_method)
+}
+
+/// Refreshes a saved card nearing its expiry through the account updater services of the
+/// connectors, notifying the merchant of the update, or of the expiry of the card if it could not
+/// be refreshed.
+pub async fn process_expiring_card(
+    state: &AppState,
+    merchant_account: domain::MerchantAccount,
+    key_store: &domain::MerchantKeyStore,
+    payment_method: storage::PaymentMethod,
+) -> RouterResult<()> {
+    let card = cards::get_card_from_locker(
+        state,
+        &payment_method.customer_id,
+        &payment_method.merchant_id,
+        cards::get_locker_id(&payment_method),
+    )
+    .await?;
+
+    let account_update = request_account_update(state, key_store, &payment_method, &card).await?;
+    let (event_type, payment_method) = match account_update {
+        Some((
+            connector_name,
+            types::AccountUpdaterResponseData::ExpiryUpdated {
+                card_exp_month,
+                card_exp_year,
+            },
+        )) => {
+            let payment_method = refresh_card(
+                state,
+                &merchant_account,
+                key_store,
+                payment_method,
+                card,
+                None,
+                card_exp_month,
+                card_exp_year,
+                enums::AccountUpdateType::ExpiryUpdated,
+                connector_name,
+            )
+            .await?;
+            (enums::EventType::PaymentMethodUpdated, payment_method)
+        }
+        Some((
+            connector_name,
+            types::AccountUpdaterResponseData::CardReplaced {
+                card_number,
+                card_exp_month,
+                card_exp_year,
+            },
+        )) => {
+            let payment_method = refresh_card(
+                state,
+                &merchant_account,
+                key_store,
+                payment_method,
+                card,
+                Some(card_number),
+                card_exp_month,
+                card_exp_year,
+                enums::AccountUpdateType::CardReplaced,
+                connector_name,
+            )
+            .await?;
+            (enums::EventType::PaymentMethodUpdated, payment_method)
+        }
+        Some((connector_name, types::AccountUpdaterResponseData::AccountClosed)) => {
+            let payment_method = state
+                .store
+                .update_payment_method(
+                    payment_method,
+                    storage::PaymentMethodUpdate::AccountUpdate {
+                        locker_id: None,
+                        card_exp_month: None,
+                        card_exp_year: None,
+                        account_update_type: enums::AccountUpdateType::AccountClosed,
+                        account_update_source: connector_name,
+                        account_updated_at: date_time::now(),
+                    },
+                )
+                .await
+                .to_not_found_response(errors::ApiErrorResponse::PaymentMethodNotFound)?;
+            (enums::EventType::PaymentMethodUpdated, payment_method)
+        }
+        Some((_, types::AccountUpdaterResponseData::NoUpdate)) | None => {
+            let payment_method = state
+                .store
+                .update_payment_method(
+                    payment_method,
+                    storage::PaymentMethodUpdate::ExpiryNotifiedUpdate {
+                        expiry_notified_at: date_time::now(),
+                    },
+                )
+                .await
+                .to_not_found_response(errors::ApiErrorResponse::PaymentMethodNotFound)?;
+            (enums::EventType::PaymentMethodExpiring, payment_method)
+        }
+    };
+
+    send_payment_method_webhook(state, merchant_account, event_type, payment_method).await
+}
+
+/// Stores the expiry of a card saved before its expiry was stored, fetching it from the locker,
+/// and schedules the refreshing of the card.
+async fn backfill_payment_method_expiry(
+    state: &AppState,
+    payment_method: storage::PaymentMethod,
+) -> RouterResult<()> {
+    let db = &*state.store;
+    let key_store = db
+        .get_merchant_key_store_by_merchant_id(
+            &payment_method.merchant_id,
+            &db.get_master_key().to_vec().into(),
+        )
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::MerchantAccountNotFound)?;
+    let card = cards::get_card_from_locker(
+        state,
+        &payment_method.customer_id,
+        &payment_method.merchant_id,
+        cards::get_locker_id(&payment_method),
+    )
+    .await?;
+
+    let card_expiry = encrypt_card_expiry(
+        card.card_exp_month.clone(),
+        card.card_exp_year.clone(),
+        &key_store,
+    )
+    .await
+    .change_context(errors::ApiErrorResponse::InternalServerError)
+    .attach_printable("Failed to encrypt the expiry of the saved card")?;
+    let payment_method = db
+        .update_payment_method(
+            payment_method,
+            storage::PaymentMethodUpdate::CardExpiryUpdate {
+                card_exp_month: card_expiry.card_exp_month,
+                card_exp_year: card_expiry.card_exp_year,
+            },
+        )
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::PaymentMethodNotFound)?;
+
+    add_expiry_task_if_required(
+        state,
+        &payment_method,
+        Some(&card.card_exp_month),
+        Some(&card.card_exp_year),
+    )
+    .await
+}
+
+/// Backfills the expiry of the next batch of saved cards after the payment method with the given
+/// ID. Returns the ID of the last payment method of the batch, or `None` once every saved card has
+/// been processed. A card whose expiry could not be backfilled is skipped, so that it does not
+/// hold up the backfill of the other cards.
+pub async fn backfill_card_expiry(state: &AppState, last_id: i32) -> RouterResult<Option<i32>> {
+    let payment_methods = state
+        .store
+        .find_card_payment_methods_after_id(last_id, EXPIRY_BACKFILL_BATCH_SIZE)
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to fetch the saved cards to backfill")?;
+    let next_last_id = payment_methods
+        .last()
+        .map(|payment_method| payment_method.id);
+
+    for payment_method in payment_methods {
+        // The expiry of the cards saved since the migration is already stored, and the cards whose
+        // account was closed are not refreshed
+        if payment_method.card_exp_month.is_some()
+            || payment_method.account_update_type == Some(enums::AccountUpdateType::AccountClosed)
+        {
+            continue;
+        }
+        let payment_method_id = payment_method.payment_method_id.clone();
+        if let Err(error) = backfill_payment_method_expiry(state, payment_method).await {
+            logger::error!(
+                payment_method_id = %payment_method_id,
+                expiry_backfill_error=?error
+            );
+        }
+    }
+
+    Ok(next_last_id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_card_expiry_is_end_of_expiry_month() {
+        let card_expiry = get_card_expiry("03", "30");
+        assert_eq!(
+            card_expiry,
+            time::Date::from_calendar_date(2030, time::Month::April, 1)
+                .ok()
+                .map(time::Date::midnight)
+        );
+        let card_expiry = get_card_expiry("12", "2029");
+        assert_eq!(
+            card_expiry,
+            time::Date::from_calendar_date(2030, time::Month::January, 1)
+                .ok()
+                .map(time::Date::midnight)
+        );
+    }
+
+    #[test]
+    fn test_invalid_card_expiry() {
+        assert_eq!(get_card_expiry("13", "2030"), None);
+        assert_eq!(get_card_expiry("ab", "2030"), None);
+    }
+}
@@ -0,0 +1,763 @@
+//! Network tokenization of saved cards.
+//!
+//! When a card is saved and network tokenization is enabled, a network token is provisioned for
+//! the card with the token service of its card network (Visa Token Service, Mastercard Digital
+//! Enablement Service) in the background, so that saving the card does not wait for the token
+//! service. The card itself remains stored in the locker, while the reference of the network token
+//! is stored in the `network_token` column of the payment method. When the saved card is used for
+//! a payment with a connector which accepts network tokens, the network token is fetched along
+//! with a cryptogram for the transaction and sent to the connector in place of the card number. If
+//! no network token is available or it cannot be fetched, the card number is sent instead. The
+//! network token is deleted with the token service when the saved card is deleted.
+
+use async_trait::async_trait;
+use common_utils::ext_traits::{BytesExt, Encode, ValueExt};
+use error_stack::{IntoReport, ResultExt};
+use masking::{ExposeInterface, PeekInterface, Secret};
+use router_env::{instrument, logger, tracing};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use crate::{
+    configs::settings,
+    connection, consts,
+    core::errors::{self, CustomResult, RouterResult},
+    headers,
+    routes::AppState,
+    services::{self, request::Mask},
+    types::{self, api, storage, storage::enums},
+};
+
+/// The suffix of the key under which the payment method of a saved card token is stored in redis,
+/// if a network token is available for the card
+pub const NETWORK_TOKEN_KEY_SUFFIX: &str = "network_token";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TokenServiceProviderName {
+    Visa,
+    Mastercard,
+}
+
+/// The reference of the network token provisioned for a saved card, stored with the payment method
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct NetworkTokenReference {
+    pub provider: TokenServiceProviderName,
+    pub token_reference: String,
+}
+
+#[async_trait]
+pub trait TokenServiceProvider: Send + Sync {
+    /// Provisions a network token for the card, returning the reference of the network token
+    async fn provision_token(
+        &self,
+        state: &AppState,
+        card: &api::CardDetail,
+    ) -> CustomResult<String, errors::NetworkTokenizationError>;
+
+    /// Fetches the network token along with a cryptogram for a single transaction
+    async fn get_token_data(
+        &self,
+        state: &AppState,
+        token_reference: &str,
+    ) -> CustomResult<types::NetworkTokenData, errors::NetworkTokenizationError>;
+
+    /// Deletes the network token, which can no longer be used for payments
+    async fn delete_token(
+        &self,
+        state: &AppState,
+        token_reference: &str,
+    ) -> CustomResult<(), errors::NetworkTokenizationError>;
+}
+
+/// Returns the token service provider of the card network, if it is configured.
+pub fn get_token_service_provider(
+    settings: &settings::NetworkTokenizationSettings,
+    provider: TokenServiceProviderName,
+) -> Option<Box<dyn TokenServiceProvider + '_>> {
+    match provider {
+        TokenServiceProviderName::Visa => settings.visa.as_ref().map(|config| {
+            Box::new(VisaTokenService { config }) as Box<dyn TokenServiceProvider + '_>
+        }),
+        TokenServiceProviderName::Mastercard => settings.mastercard.as_ref().map(|config| {
+            Box::new(MastercardTokenService { config }) as Box<dyn TokenServiceProvider + '_>
+        }),
+    }
+}
+
+/// Returns the token service provider for the card network of the card number, based on its
+/// issuer identification number.
+fn get_token_service_provider_name(card_number: &str) -> Option<TokenServiceProviderName> {
+    let prefix = |length: usize| card_number.get(..length)?.parse::<u32>().ok();
+
+    if card_number.starts_with('4') {
+        Some(TokenServiceProviderName::Visa)
+    } else if prefix(2).map_or(false, |prefix| (51..=55).contains(&prefix))
+        || prefix(4).map_or(false, |prefix| (2221..=2720).contains(&prefix))
+    {
+        Some(TokenServiceProviderName::Mastercard)
+    } else {
+        None
+    }
+}
+
+/// Provisions a network token for a saved card in the background if network tokenization is
+/// enabled and the token service of the card network is configured, and stores its reference with
+/// the payment method.
+///
+/// The card is saved regardless of whether the network token could be provisioned, in which case
+/// the card number is used for payments.
+pub fn provision_network_token_if_required(
+    state: &AppState,
+    payment_method: storage::PaymentMethod,
+    card: Option<&api::CardDetail>,
+) {
+    let settings = &state.conf.network_tokenization;
+    let Some(card) = card else {
+        return;
+    };
+    if !settings.enabled
+        || payment_method.payment_method != enums::PaymentMethod::Card
+        || payment_method.network_token.is_some()
+    {
+        return;
+    }
+    let Some(provider_name) = get_token_service_provider_name(card.card_number.peek()) else {
+        return;
+    };
+    if get_token_service_provider(settings, provider_name).is_none() {
+        return;
+    }
+
+    let state = state.clone();
+    let card = card.clone();
+    tokio::spawn(connection::in_current_storage_region(async move {
+        let payment_method_id = payment_method.payment_method_id.clone();
+        if let Err(error) =
+            provision_network_token(&state, payment_method, &card, provider_name).await
+        {
+            logger::error!(
+                payment_method_id = %payment_method_id,
+                network_tokenization_error = ?error,
+                "Failed to provision a network token for the card"
+            );
+        }
+    }));
+}
+
+#[instrument(skip_all)]
+async fn provision_network_token(
+    state: &AppState,
+    payment_method: storage::PaymentMethod,
+    card: &api::CardDetail,
+    provider_name: TokenServiceProviderName,
+) -> RouterResult<()> {
+    let provider = get_token_service_provider(&state.conf.network_tokenization, provider_name)
+        .ok_or(errors::ApiErrorResponse::InternalServerError)
+        .into_report()
+        .attach_printable("The token service provider of the card network is not configured")?;
+    let token_reference = provider
+        .provision_token(state, card)
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)?;
+
+    let network_token = Encode::<NetworkTokenReference>::encode_to_value(&NetworkTokenReference {
+        provider: provider_name,
+        token_reference,
+    })
+    .change_context(errors::ApiErrorResponse::InternalServerError)
+    .attach_printable("Failed to encode the network token reference")?;
+
+    state
+        .store
+        .update_payment_method(
+            payment_method,
+            storage::PaymentMethodUpdate::NetworkTokenUpdate {
+                network_token: Some(network_token),
+            },
+        )
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to store the network token reference of the payment method")?;
+
+    Ok(())
+}
+
+/// Deletes the network token of a saved card being deleted with the token service, in the
+/// background. The saved card is deleted regardless of whether the network token could be deleted.
+pub fn delete_network_token_if_exists(state: &AppState, payment_method: &storage::PaymentMethod) {
+    let Some(network_token_reference) = get_network_token_reference(payment_method) else {
+        return;
+    };
+
+    let state = state.clone();
+    let payment_method_id = payment_method.payment_method_id.clone();
+    tokio::spawn(connection::in_current_storage_region(async move {
+        let Some(provider) = get_token_service_provider(
+            &state.conf.network_tokenization,
+            network_token_reference.provider,
+        ) else {
+            logger::error!(
+                payment_method_id = %payment_method_id,
+                "The token service provider of the network token is not configured"
+            );
+            return;
+        };
+        if let Err(error) = provider
+            .delete_token(&state, &network_token_reference.token_reference)
+            .await
+        {
+            logger::error!(
+                payment_method_id = %payment_method_id,
+                network_tokenization_error = ?error,
+                "Failed to delete the network token of the card"
+            );
+        }
+    }));
+}
+
+fn get_network_token_reference(
+    payment_method: &storage::PaymentMethod,
+) -> Option<NetworkTokenReference> {
+    payment_method
+        .network_token
+        .clone()?
+        .parse_value::<NetworkTokenReference>("NetworkTokenReference")
+        .map_err(|error| {
+            logger::error!(
+                payment_method_id = %payment_method.payment_method_id,
+                ?error,
+                "Failed to parse the network token reference of the payment method"
+            )
+        })
+        .ok()
+}
+
+/// Returns whether network tokens are sent in place of card numbers to the connector, which is
+/// only the case for the connectors which send the network token data provided for a payment.
+pub fn is_network_tokenization_enabled_for_connector(state: &AppState, connector: &str) -> bool {
+    let settings = &state.conf.network_tokenization;
+    settings.enabled
+        && settings.connector_list.contains(connector)
+        && consts::NETWORK_TOKEN_CONNECTORS.contains(&connector)
+}
+
+/// Fetches the network token of a saved card along with a cryptogram for the transaction.
+///
+/// Returns `None` if no network token is available for the card or it could not be fetched, in
+/// which case the card number is to be sent to the connector instead.
+#[instrument(skip_all)]
+pub async fn get_network_token_data(
+    state: &AppState,
+    payment_method: &storage::PaymentMethod,
+) -> Option<types::NetworkTokenData> {
+    let network_token_reference = get_network_token_reference(payment_method)?;
+
+    let provider = get_token_service_provider(
+        &state.conf.network_tokenization,
+        network_token_reference.provider,
+    )?;
+
+    provider
+        .get_token_data(state, &network_token_reference.token_reference)
+        .await
+        .map_err(|error| {
+            logger::warn!(
+                payment_method_id = %payment_method.payment_method_id,
+                network_tokenization_error = ?error,
+                "Failed to fetch the network token, falling back to the card number"
+            )
+        })
+        .ok()
+}
+
+/// Fetches the network token of the saved card used for a card payment, if network tokens are
+/// sent to the connector and a network token is available for the card.
+///
+/// Returns `None` if the network token could not be fetched for any reason, in which case the
+/// card number is sent to the connector.
+#[instrument(skip_all)]
+pub async fn get_network_token_data_for_payment(
+    state: &AppState,
+    connector_name: &str,
+    pm_parent_token: Option<&str>,
+) -> Option<types::NetworkTokenData> {
+    let token = pm_parent_token?;
+    if !is_network_tokenization_enabled_for_connector(state, connector_name) {
+        return None;
+    }
+
+    let redis_conn = state
+        .store
+        .get_redis_conn()
+        .map_err(|error| logger::error!(redis_connection_error=?error))
+        .ok()?;
+    let key = format!(
+        "pm_token_{}_{}_{}",
+        token,
+        enums::PaymentMethod::Card,
+        NETWORK_TOKEN_KEY_SUFFIX
+    );
+    let payment_method_id = redis_conn
+        .get_key::<Option<String>>(&key)
+        .await
+        .map_err(|error| logger::error!(network_token_lookup_error=?error))
+        .ok()??;
+
+    let payment_method = state
+        .store
+        .find_payment_method(&payment_method_id)
+        .await
+        .map_err(|error| logger::error!(network_token_lookup_error=?error))
+        .ok()?;
+
+    get_network_token_data(state, &payment_method).await
+}
+
+async fn send_token_service_request<Req>(
+    state: &AppState,
+    config: &settings::TokenServiceProviderConfig,
+    path: &str,
+    request_body: &Req,
+    error: errors::NetworkTokenizationError,
+) -> CustomResult<types::Response, errors::NetworkTokenizationError>
+where
+    Req: Serialize + std::fmt::Debug,
+{
+    let body = Encode::<Req>::encode_to_string_of_json(request_body)
+        .change_context(errors::NetworkTokenizationError::RequestEncodingFailed)?;
+    let url = format!("{}{}", config.base_url, path);
+    let mut request = services::Request::new(services::Method::Post, &url);
+    request.add_header(headers::CONTENT_TYPE, "application/json".into());
+    request.add_header(
+        headers::X_API_KEY,
+        config.api_key.peek().to_owned().into_masked(),
+    );
+    request.set_body(body);
+
+    match services::call_connector_api(state, request).await {
+        Ok(Ok(response)) => Ok(response),
+        Ok(Err(error_response)) => {
+            logger::error!(token_service_error_response = ?error_response);
+            Err(error).into_report()
+        }
+        Err(api_client_error) => Err(api_client_error.change_context(error)),
+    }
+}
+
+async fn call_token_service<Req, Res>(
+    state: &AppState,
+    config: &settings::TokenServiceProviderConfig,
+    path: &str,
+    request_body: &Req,
+    response_type_name: &'static str,
+    error: errors::NetworkTokenizationError,
+) -> CustomResult<Res, errors::NetworkTokenizationError>
+where
+    Req: Serialize + std::fmt::Debug,
+    Res: DeserializeOwned,
+{
+    send_token_service_request(state, config, path, request_body, error)
+        .await?
+        .response
+        .parse_struct(response_type_name)
+        .change_context(errors::NetworkTokenizationError::ResponseDeserializationFailed)
+}
+
+fn get_card_token_data(
+    token_number: String,
+    token_exp_month: String,
+    token_exp_year: String,
+    token_cryptogram: String,
+    eci: Option<String>,
+) -> CustomResult<types::NetworkTokenData, errors::NetworkTokenizationError> {
+    Ok(types::NetworkTokenData {
+        token_number: token_number
+            .parse()
+            .into_report()
+            .change_context(errors::NetworkTokenizationError::ResponseDeserializationFailed)
+            .attach_printable("Invalid network token number")?,
+        token_exp_month: Secret::new(token_exp_month),
+        token_exp_year: Secret::new(token_exp_year),
+        token_cryptogram: Secret::new(token_cryptogram),
+        eci,
+    })
+}
+
+/// Visa Token Service (VTS)
+pub struct VisaTokenService<'a> {
+    config: &'a settings::TokenServiceProviderConfig,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct VisaProvisionTokenRequest {
+    #[serde(rename = "tokenRequestorID")]
+    token_requestor_id: String,
+    payment_instrument: VisaPaymentInstrument,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct VisaPaymentInstrument {
+    account_number: Secret<String>,
+    expiration_date: VisaExpirationDate,
+    name: Option<Secret<String>>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct VisaExpirationDate {
+    month: Secret<String>,
+    year: Secret<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct VisaProvisionTokenResponse {
+    #[serde(rename = "vProvisionedTokenID")]
+    v_provisioned_token_id: String,
+}
+
+#[derive(Debug, Serialize)]
+struct VisaPaymentDataRequest {
+    #[serde(rename = "tokenRequestorID")]
+    token_requestor_id: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct VisaPaymentDataResponse {
+    token_info: VisaTokenInfo,
+    cryptogram_info: VisaCryptogramInfo,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct VisaTokenInfo {
+    token: Secret<String>,
+    expiration_date: VisaExpirationDate,
+}
+
+#[derive(Debug, Deserialize)]
+struct VisaCryptogramInfo {
+    cryptogram: Secret<String>,
+    eci: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct VisaDeleteTokenRequest {
+    update_reason: VisaUpdateReason,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct VisaUpdateReason {
+    reason_code: &'static str,
+    reason_desc: &'static str,
+}
+
+#[async_trait]
+impl TokenServiceProvider for VisaTokenService<'_> {
+    async fn provision_token(
+        &self,
+        state: &AppState,
+        card: &api::CardDetail,
+    ) -> CustomResult<String, errors::NetworkTokenizationError> {
+        let request = VisaProvisionTokenRequest {
+            token_requestor_id: self.config.token_requestor_id.clone(),
+            payment_instrument: VisaPaymentInstrument {
+                account_number: Secret::new(card.card_number.peek().to_owned()),
+                expiration_date: VisaExpirationDate {
+                    month: card.card_exp_month.clone(),
+                    year: card.card_exp_year.clone(),
+                },
+                name: card.card_holder_name.clone(),
+            },
+        };
+        let response: VisaProvisionTokenResponse = call_token_service(
+            state,
+            self.config,
+            "vts/provisionedTokens",
+            &request,
+            "VisaProvisionTokenResponse",
+            errors::NetworkTokenizationError::TokenProvisioningFailed,
+        )
+        .await?;
+        Ok(response.v_provisioned_token_id)
+    }
+
+    async fn get_token_data(
+        &self,
+        state: &AppState,
+        token_reference: &str,
+    ) -> CustomResult<types::NetworkTokenData, errors::NetworkTokenizationError> {
+        let request = VisaPaymentDataRequest {
+            token_requestor_id: self.config.token_requestor_id.clone(),
+        };
+        let response: VisaPaymentDataResponse = call_token_service(
+            state,
+            self.config,
+            &format!("vts/provisionedTokens/{token_reference}/paymentData"),
+            &request,
+            "VisaPaymentDataResponse",
+            errors::NetworkTokenizationError::FetchTokenDataFailed,
+        )
+        .await?;
+        get_card_token_data(
+            response.token_info.token.expose(),
+            response.token_info.expiration_date.month.expose(),
+            response.token_info.expiration_date.year.expose(),
+            response.cryptogram_info.cryptogram.expose(),
+            response.cryptogram_info.eci,
+        )
+    }
+
+    async fn delete_token(
+        &self,
+        state: &AppState,
+        token_reference: &str,
+    ) -> CustomResult<(), errors::NetworkTokenizationError> {
+        let request = VisaDeleteTokenRequest {
+            update_reason: VisaUpdateReason {
+                reason_code: "CUSTOMER_CONFIRMED",
+                reason_desc: "The saved card was deleted",
+            },
+        };
+        send_token_service_request(
+            state,
+            self.config,
+            &format!("vts/provisionedTokens/{token_reference}/delete"),
+            &request,
+            errors::NetworkTokenizationError::TokenDeletionFailed,
+        )
+        .await?;
+        Ok(())
+    }
+}
+
+/// Mastercard Digital Enablement Service (MDES)
+pub struct MastercardTokenService<'a> {
+    config: &'a settings::TokenServiceProviderConfig,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct MastercardTokenizeRequest {
+    token_requestor_id: String,
+    token_type: &'static str,
+    funding_account_info: MastercardFundingAccountInfo,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct MastercardFundingAccountInfo {
+    card_info: MastercardCardInfo,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct MastercardCardInfo {
+    account_number: Secret<String>,
+    expiry_month: Secret<String>,
+    expiry_year: Secret<String>,
+    cardholder_name: Option<Secret<String>>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct MastercardTokenizeResponse {
+    token_unique_reference: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct MastercardTransactRequest {
+    token_requestor_id: String,
+    token_unique_reference: String,
+    dsrp_type: &'static str,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct MastercardTransactResponse {
+    token_number: Secret<String>,
+    expiry_month: Secret<String>,
+    expiry_year: Secret<String>,
+    cryptogram: Secret<String>,
+    eci: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct MastercardDeleteRequest {
+    token_requestor_id: String,
+    token_unique_references: Vec<String>,
+    reason_code: &'static str,
+}
+
+#[async_trait]
+impl TokenServiceProvider for MastercardTokenService<'_> {
+    async fn provision_token(
+        &self,
+        state: &AppState,
+        card: &api::CardDetail,
+    ) -> CustomResult<String, errors::NetworkTokenizationError> {
+        let request = MastercardTokenizeRequest {
+            token_requestor_id: self.config.token_requestor_id.clone(),
+            token_type: "CLOUD",
+            funding_account_info: MastercardFundingAccountInfo {
+                card_info: MastercardCardInfo {
+                    account_number: Secret::new(card.card_number.peek().to_owned()),
+                    expiry_month: card.card_exp_month.clone(),
+                    expiry_year: card.card_exp_year.clone(),
+                    cardholder_name: card.card_holder_name.clone(),
+                },
+            },
+        };
+        let response: MastercardTokenizeResponse = call_token_service(
+            state,
+            self.config,
+            "mdes/digitization/static/1/0/tokenize",
+            &request,
+            "MastercardTokenizeResponse",
+            errors::NetworkTokenizationError::TokenProvisioningFailed,
+        )
+        .await?;
+        Ok(response.token_unique_reference)
+    }
+
+    async fn get_token_data(
+        &self,
+        state: &AppState,
+        token_reference: &str,
+    ) -> CustomResult<types::NetworkTokenData, errors::NetworkTokenizationError> {
+        let request = MastercardTransactRequest {
+            token_requestor_id: self.config.token_requestor_id.clone(),
+            token_unique_reference: token_reference.to_owned(),
+            dsrp_type: "UCAF",
+        };
+        let response: MastercardTransactResponse = call_token_service(
+            state,
+            self.config,
+            "mdes/remotetransaction/static/1/0/transact",
+            &request,
+            "MastercardTransactResponse",
+            errors::NetworkTokenizationError::FetchTokenDataFailed,
+        )
+        .await?;
+        get_card_token_data(
+            response.token_number.expose(),
+            response.expiry_month.expose(),
+            response.expiry_year.expose(),
+            response.cryptogram.expose(),
+            response.eci,
+        )
+    }
+
+    async fn delete_token(
+        &self,
+        state: &AppState,
+        token_reference: &str,
+    ) -> CustomResult<(), errors::NetworkTokenizationError> {
+        let request = MastercardDeleteRequest {
+            token_requestor_id: self.config.token_requestor_id.clone(),
+            token_unique_references: vec![token_reference.to_owned()],
+            reason_code: "CARDHOLDER_CONFIRMED",
+        };
+        send_token_service_request(
+            state,
+            self.config,
+            "mdes/digitization/static/1/0/delete",
+            &request,
+            errors::NetworkTokenizationError::TokenDeletionFailed,
+        )
+        .await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use std::collections::HashSet;
+
+    use super::*;
+
+    fn token_service_provider_config() -> settings::TokenServiceProviderConfig {
+        settings::TokenServiceProviderConfig {
+            base_url: "https://token-service.example.com/".to_string(),
+            api_key: Secret::new("api_key".to_string()),
+            token_requestor_id: "token_requestor_id".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_token_service_provider_name_from_card_number() {
+        assert_eq!(
+            get_token_service_provider_name("4111111111111111"),
+            Some(TokenServiceProviderName::Visa)
+        );
+        assert_eq!(
+            get_token_service_provider_name("5555555555554444"),
+            Some(TokenServiceProviderName::Mastercard)
+        );
+        assert_eq!(
+            get_token_service_provider_name("2223003122003222"),
+            Some(TokenServiceProviderName::Mastercard)
+        );
+        assert_eq!(get_token_service_provider_name("378282246310005"), None);
+        assert_eq!(get_token_service_provider_name("5"), None);
+    }
+
+    #[test]
+    fn test_token_service_provider_is_only_returned_when_configured() {
+        let settings = settings::NetworkTokenizationSettings {
+            enabled: true,
+            visa: Some(token_service_provider_config()),
+            ..Default::default()
+        };
+        assert!(get_token_service_provider(&settings, TokenServiceProviderName::Visa).is_some());
+        assert!(
+            get_token_service_provider(&settings, TokenServiceProviderName::Mastercard).is_none()
+        );
+    }
+
+    #[test]
+    fn test_network_token_reference_round_trip() {
+        let network_token =
+            Encode::<NetworkTokenReference>::encode_to_value(&NetworkTokenReference {
+                provider: TokenServiceProviderName::Mastercard,
+                token_reference: "DWSPMC000000000132d72d4fcb2f4136a0532d3093ff1a45".to_string(),
+            })
+            .unwrap();
+        assert_eq!(network_token["provider"], "mastercard");
+
+        let network_token_reference = network_token
+            .parse_value::<NetworkTokenReference>("NetworkTokenReference")
+            .unwrap();
+        assert_eq!(
+            network_token_reference.provider,
+            TokenServiceProviderName::Mastercard
+        );
+        assert_eq!(
+            network_token_reference.token_reference,
+            "DWSPMC000000000132d72d4fcb2f4136a0532d3093ff1a45"
+        );
+    }
+
+    #[test]
+    fn test_connectors_not_sending_network_tokens_are_rejected() {
+        let settings = settings::NetworkTokenizationSettings {
+            enabled: true,
+            visa: Some(token_service_provider_config()),
+            mastercard: None,
+            connector_list: HashSet::from(["cybersource".to_string()]),
+        };
+        assert!(settings.validate().is_ok());
+
+        let settings = settings::NetworkTokenizationSettings {
+            connector_list: HashSet::from(["cybersource".to_string(), "stripe".to_string()]),
+            ..settings
+        };
+        assert!(settings.validate().is_err());
+    }
+}
@@ -0,0 +1,406 @@
+//! Ordering of the payment methods listed for a payment, so that the payment methods most likely
+//! to be used successfully are presented first.
+//!
+//! Payment method types are ranked by, in order:
+//! 1. the ranking configured for the business of the payment, if any
+//! 2. whether the customer has saved a payment method of the type
+//! 3. the conversion of the type for the merchant, that is the share of the recent payment
+//!    attempts of the merchant made with the type which were authorized
+//!
+//! The conversions of a merchant are cached in Redis for
+//! `PAYMENT_METHOD_CONVERSION_CACHE_TTL` seconds, so that listing payment methods does not query
+//! the recent payment attempts of the merchant every time.
+//!
+//! Payment methods are then ordered by the rank of their best ranked payment method type.
+
+use std::{
+    cmp::Ordering,
+    collections::{HashMap, HashSet},
+};
+
+use api_models::{enums as api_enums, payment_methods::ResponsePaymentMethodsEnabled};
+use redis_interface::errors::RedisError;
+use router_env::{instrument, logger, tracing};
+
+use crate::{
+    consts,
+    core::payments::helpers,
+    routes::AppState,
+    types::{
+        domain,
+        storage::{self, enums},
+    },
+};
+
+/// The number of attempts and of successful attempts made with a payment method type
+#[derive(Clone, Copy, Debug, Default, serde::Serialize, serde::Deserialize)]
+struct Conversion {
+    attempts: u32,
+    successes: u32,
+}
+
+impl Conversion {
+    /// The conversion rate, smoothed towards one half so that payment method types with few
+    /// attempts are neither ranked first nor last on the basis of a handful of payments
+    fn rate(&self) -> f64 {
+        (f64::from(self.successes) + 1.0) / (f64::from(self.attempts) + 2.0)
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct PaymentMethodRanking {
+    configured_ranking: HashMap<api_enums::PaymentMethodType, usize>,
+    customer_payment_method_types: HashSet<api_enums::PaymentMethodType>,
+    conversions: HashMap<api_enums::PaymentMethodType, Conversion>,
+}
+
+impl PaymentMethodRanking {
+    fn compare(
+        &self,
+        a: &api_enums::PaymentMethodType,
+        b: &api_enums::PaymentMethodType,
+    ) -> Ordering {
+        let configured_rank = |payment_method_type: &api_enums::PaymentMethodType| {
+            self.configured_ranking.get(payment_method_type).copied()
+        };
+        let used_by_customer = |payment_method_type: &api_enums::PaymentMethodType| {
+            self.customer_payment_method_types
+                .contains(payment_method_type)
+        };
+        let conversion_rate = |payment_method_type: &api_enums::PaymentMethodType| {
+            self.conversions
+                .get(payment_method_type)
+                .copied()
+                .unwrap_or_default()
+                .rate()
+        };
+
+        match (configured_rank(a), configured_rank(b)) {
+            (Some(a_rank), Some(b_rank)) => a_rank.cmp(&b_rank),
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => used_by_customer(b)
+                .cmp(&used_by_customer(a))
+                .then_with(|| conversion_rate(b).total_cmp(&conversion_rate(a))),
+        }
+    }
+
+    /// Orders the payment method types of each payment method, and the payment methods by their
+    /// first payment method type. The order of equally ranked entries is preserved.
+    pub fn order(&self, payment_methods: &mut [ResponsePaymentMethodsEnabled]) {
+        for payment_method in payment_methods.iter_mut() {
+            payment_method
+                .payment_method_types
+                .sort_by(|a, b| self.compare(&a.payment_method_type, &b.payment_method_type));
+        }
+
+        payment_methods.sort_by(|a, b| {
+            match (
+                a.payment_method_types.first(),
+                b.payment_method_types.first(),
+            ) {
+                (Some(a), Some(b)) => self.compare(&a.payment_method_type, &b.payment_method_type),
+                (Some(_), None) => Ordering::Less,
+                (None, Some(_)) => Ordering::Greater,
+                (None, None) => Ordering::Equal,
+            }
+        });
+    }
+}
+
+fn is_successful_attempt(status: enums::AttemptStatus) -> bool {
+    matches!(
+        status,
+        enums::AttemptStatus::Authorized
+            | enums::AttemptStatus::Charged
+            | enums::AttemptStatus::PartialCharged
+            | enums::AttemptStatus::CaptureInitiated
+            | enums::AttemptStatus::CodInitiated
+    )
+}
+
+fn get_conversions_cache_key(merchant_id: &str) -> String {
+    format!("payment_method_conversions_{merchant_id}")
+}
+
+/// Computes the conversions of the payment method types of the merchant from the recent payment
+/// attempts of the merchant
+async fn compute_conversions(
+    state: &AppState,
+    merchant_account: &domain::MerchantAccount,
+) -> Option<HashMap<api_enums::PaymentMethodType, Conversion>> {
+    let created_after = common_utils::date_time::now().saturating_sub(time::Duration::days(
+        consts::PAYMENT_METHOD_CONVERSION_LOOKBACK_DAYS,
+    ));
+    let statuses = state
+        .store
+        .get_payment_method_type_statuses_by_merchant_id(
+            &merchant_account.merchant_id,
+            created_after,
+            consts::PAYMENT_METHOD_CONVERSION_SAMPLE_SIZE,
+            merchant_account.storage_scheme,
+        )
+        .await
+        .map_err(|error| logger::error!(payment_method_conversion_error=?error))
+        .ok()?;
+
+    Some(
+        statuses
+            .into_iter()
+            .filter_map(|(payment_method_type, status)| {
+                payment_method_type.map(|payment_method_type| (payment_method_type, status))
+            })
+            .fold(
+                HashMap::new(),
+                |mut conversions, (payment_method_type, status)| {
+                    let conversion: &mut Conversion =
+                        conversions.entry(payment_method_type).or_default();
+                    conversion.attempts = conversion.attempts.saturating_add(1);
+                    if is_successful_attempt(status) {
+                        conversion.successes = conversion.successes.saturating_add(1);
+                    }
+                    conversions
+                },
+            ),
+    )
+}
+
+/// Returns the conversions of the payment method types of the merchant, from the cache if they
+/// were computed recently. Caching fails open, and conversions which could not be computed are
+/// left out of the ranking without being cached.
+async fn get_conversions(
+    state: &AppState,
+    merchant_account: &domain::MerchantAccount,
+) -> HashMap<api_enums::PaymentMethodType, Conversion> {
+    let cache_key = get_conversions_cache_key(&merchant_account.merchant_id);
+    let redis_conn = state
+        .store
+        .get_redis_conn()
+        .map_err(|error| logger::error!(redis_connection_error=?error))
+        .ok();
+
+    if let Some(redis_conn) = redis_conn.as_ref() {
+        match redis_conn
+            .get_and_deserialize_key::<HashMap<api_enums::PaymentMethodType, Conversion>>(
+                &cache_key,
+                "PaymentMethodConversions",
+            )
+            .await
+        {
+            Ok(conversions) => return conversions,
+            Err(error) if matches!(error.current_context(), RedisError::NotFound) => {}
+            Err(error) => logger::error!(payment_method_conversion_cache_error=?error),
+        }
+    }
+
+    let Some(conversions) = compute_conversions(state, merchant_account).await else {
+        return HashMap::new();
+    };
+    if let Some(redis_conn) = redis_conn {
+        if let Err(error) = redis_conn
+            .serialize_and_set_key_with_expiry(
+                &cache_key,
+                &conversions,
+                consts::PAYMENT_METHOD_CONVERSION_CACHE_TTL,
+            )
+            .await
+        {
+            logger::error!(payment_method_conversion_cache_error=?error);
+        }
+    }
+    conversions
+}
+
+/// Builds the ranking of the payment method types for the payment. The ranking is only used to
+/// order the listed payment methods, so failures in gathering the data it is built from are
+/// logged and the corresponding criterion is left out.
+#[instrument(skip_all)]
+pub async fn get_payment_method_ranking(
+    state: &AppState,
+    merchant_account: &domain::MerchantAccount,
+    payment_intent: Option<&storage::PaymentIntent>,
+    customer: Option<&domain::Customer>,
+) -> PaymentMethodRanking {
+    let db = &*state.store;
+
+    let configured_ranking = payment_intent
+        .and_then(|payment_intent| {
+            helpers::get_business_profile_defaults(
+                payment_intent.business_country,
+                &payment_intent.business_label,
+                merchant_account,
+            )
+            .map_err(|error| logger::error!(business_profile_error=?error))
+            .ok()
+            .flatten()
+        })
+        .and_then(|business_details| business_details.payment_method_ranking)
+        .unwrap_or_default()
+        .into_iter()
+        .enumerate()
+        .map(|(rank, payment_method_type)| (payment_method_type, rank))
+        .collect();
+
+    let customer_payment_method_types = match customer {
+        Some(customer) => db
+            .find_payment_method_by_customer_id_merchant_id_list(
+                &customer.customer_id,
+                &merchant_account.merchant_id,
+            )
+            .await
+            .map_err(|error| logger::error!(customer_payment_methods_error=?error))
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|payment_method| payment_method.payment_method_type)
+            .collect(),
+        None => HashSet::new(),
+    };
+
+    let conversions = get_conversions(state, merchant_account).await;
+
+    PaymentMethodRanking {
+        configured_ranking,
+        customer_payment_method_types,
+        conversions,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use api_models::payment_methods::ResponsePaymentMethodTypes;
+
+    use super::*;
+
+    fn payment_method(
+        payment_method: api_enums::PaymentMethod,
+        payment_method_types: &[api_enums::PaymentMethodType],
+    ) -> ResponsePaymentMethodsEnabled {
+        ResponsePaymentMethodsEnabled {
+            payment_method,
+            payment_method_types: payment_method_types
+                .iter()
+                .map(|payment_method_type| ResponsePaymentMethodTypes {
+                    payment_method_type: *payment_method_type,
+                    payment_experience: None,
+                    card_networks: None,
+                    bank_names: None,
+                    bank_debits: None,
+                    bank_transfers: None,
+                    required_fields: None,
+                })
+                .collect(),
+        }
+    }
+
+    fn order(
+        ranking: &PaymentMethodRanking,
+        payment_methods: &mut [ResponsePaymentMethodsEnabled],
+    ) -> Vec<api_enums::PaymentMethodType> {
+        ranking.order(payment_methods);
+        payment_methods
+            .iter()
+            .flat_map(|payment_method| payment_method.payment_method_types.iter())
+            .map(|payment_method_type| payment_method_type.payment_method_type)
+            .collect()
+    }
+
+    #[test]
+    fn test_payment_methods_ordering() {
+        let mut payment_methods = vec![
+            payment_method(
+                api_enums::PaymentMethod::Card,
+                &[
+                    api_enums::PaymentMethodType::Credit,
+                    api_enums::PaymentMethodType::Debit,
+                ],
+            ),
+            payment_method(
+                api_enums::PaymentMethod::Wallet,
+                &[
+                    api_enums::PaymentMethodType::ApplePay,
+                    api_enums::PaymentMethodType::GooglePay,
+                ],
+            ),
+        ];
+
+        let ranking = PaymentMethodRanking {
+            conversions: HashMap::from([
+                (
+                    api_enums::PaymentMethodType::Credit,
+                    Conversion {
+                        attempts: 100,
+                        successes: 60,
+                    },
+                ),
+                (
+                    api_enums::PaymentMethodType::Debit,
+                    Conversion {
+                        attempts: 100,
+                        successes: 80,
+                    },
+                ),
+                (
+                    api_enums::PaymentMethodType::GooglePay,
+                    Conversion {
+                        attempts: 100,
+                        successes: 90,
+                    },
+                ),
+            ]),
+            ..Default::default()
+        };
+        assert_eq!(
+            order(&ranking, &mut payment_methods),
+            vec![
+                api_enums::PaymentMethodType::GooglePay,
+                api_enums::PaymentMethodType::ApplePay,
+                api_enums::PaymentMethodType::Debit,
+                api_enums::PaymentMethodType::Credit,
+            ]
+        );
+
+        let ranking = PaymentMethodRanking {
+            customer_payment_method_types: HashSet::from([api_enums::PaymentMethodType::Credit]),
+            ..ranking
+        };
+        assert_eq!(
+            order(&ranking, &mut payment_methods),
+            vec![
+                api_enums::PaymentMethodType::Credit,
+                api_enums::PaymentMethodType::Debit,
+                api_enums::PaymentMethodType::GooglePay,
+                api_enums::PaymentMethodType::ApplePay,
+            ]
+        );
+
+        let ranking = PaymentMethodRanking {
+            configured_ranking: HashMap::from([(api_enums::PaymentMethodType::ApplePay, 0)]),
+            ..ranking
+        };
+        assert_eq!(
+            order(&ranking, &mut payment_methods),
+            vec![
+                api_enums::PaymentMethodType::ApplePay,
+                api_enums::PaymentMethodType::GooglePay,
+                api_enums::PaymentMethodType::Credit,
+                api_enums::PaymentMethodType::Debit,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_conversions_cache_round_trip() {
+        let conversions = HashMap::from([(
+            api_enums::PaymentMethodType::GooglePay,
+            Conversion {
+                attempts: 10,
+                successes: 7,
+            },
+        )]);
+        let cached: HashMap<api_enums::PaymentMethodType, Conversion> =
+            serde_json::from_slice(&serde_json::to_vec(&conversions).unwrap()).unwrap();
+        let conversion = cached[&api_enums::PaymentMethodType::GooglePay];
+        assert_eq!((conversion.attempts, conversion.successes), (10, 7));
+    }
+}
@@ -0,0 +1,636 @@
+//! Sharing of saved cards between the sub-merchants of a platform.
+//!
+//! A sub-merchant can share a card saved with it with another sub-merchant of the same platform,
+//! along with a record of the consent given by the customer to share the card. The sub-merchant
+//! the card is shared with pays with it by passing the identifier of the share as the
+//! `shared_payment_method_id` of its payments, without ever holding the card itself: the card is
+//! read from the vault of the merchant owning it for each payment. Each share is scoped to a
+//! single sub-merchant and can limit the number of payments made with it, the amount of each
+//! payment and the time until which it can be used. The merchant owning the card can revoke the
+//! share at any time.
+
+use api_models::{
+    payment_method_shares::{
+        PaymentMethodShareConsent, PaymentMethodShareRequest, PaymentMethodShareResponse,
+    },
+    payments::{PaymentsRequest, PaymentsResponse},
+};
+use error_stack::{report, ResultExt};
+use router_env::{instrument, logger, tracing};
+
+use super::cards;
+use crate::{
+    consts,
+    core::errors::{self, RouterResponse, RouterResult, StorageErrorExt},
+    routes::AppState,
+    services,
+    types::{
+        api::{self, PaymentIdTypeExt},
+        domain,
+        storage::{self, enums},
+    },
+    utils::{self, OptionExt},
+};
+
+fn get_payment_method_share_response(
+    payment_method_share: storage::PaymentMethodShare,
+) -> PaymentMethodShareResponse {
+    PaymentMethodShareResponse {
+        share_id: payment_method_share.share_id,
+        payment_method_id: payment_method_share.payment_method_id,
+        owner_merchant_id: payment_method_share.merchant_id,
+        merchant_id: payment_method_share.shared_with_merchant_id,
+        status: payment_method_share.status,
+        consent: PaymentMethodShareConsent {
+            reference: payment_method_share.consent_reference,
+            consented_at: payment_method_share.consented_at,
+            ip_address: payment_method_share.consent_ip_address,
+        },
+        max_uses: payment_method_share.max_uses,
+        use_count: payment_method_share.use_count,
+        max_amount: payment_method_share.max_amount,
+        currency: payment_method_share.currency,
+        expires_at: payment_method_share.expires_at,
+        revoked_at: payment_method_share.revoked_at,
+        created: payment_method_share.created_at,
+    }
+}
+
+fn validate_payment_method_share_request(
+    merchant_account: &domain::MerchantAccount,
+    req: &PaymentMethodShareRequest,
+) -> RouterResult<()> {
+    let now = common_utils::date_time::now();
+    let invalid_request = |message: &str| {
+        Err(report!(errors::ApiErrorResponse::InvalidRequestData {
+            message: message.to_string(),
+        }))
+    };
+
+    utils::when(req.merchant_id == merchant_account.merchant_id, || {
+        invalid_request("A payment method cannot be shared with the merchant owning it")
+    })?;
+    utils::when(
+        req.consent.reference.is_empty() || req.consent.reference.len() > 255,
+        || invalid_request("consent.reference must be between 1 and 255 characters long"),
+    )?;
+    utils::when(req.consent.consented_at > now, || {
+        invalid_request("consent.consented_at must not be in the future")
+    })?;
+    utils::when(req.max_uses == Some(0), || {
+        invalid_request("max_uses must be greater than zero")
+    })?;
+    utils::when(req.max_amount.map_or(false, |amount| amount <= 0), || {
+        invalid_request("max_amount must be greater than zero")
+    })?;
+    utils::when(req.max_amount.is_some() != req.currency.is_some(), || {
+        invalid_request("currency must be passed along with max_amount, and only along with it")
+    })?;
+    utils::when(
+        req.expires_at.map_or(false, |expires_at| expires_at <= now),
+        || invalid_request("expires_at must be in the future"),
+    )?;
+    Ok(())
+}
+
+/// Finds a payment method owned by the merchant.
+async fn find_merchant_payment_method(
+    state: &AppState,
+    merchant_account: &domain::MerchantAccount,
+    payment_method_id: &str,
+) -> RouterResult<storage::PaymentMethod> {
+    let payment_method = state
+        .store
+        .find_payment_method(payment_method_id)
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::PaymentMethodNotFound)?;
+    utils::when(
+        payment_method.merchant_id != merchant_account.merchant_id,
+        || Err(report!(errors::ApiErrorResponse::PaymentMethodNotFound)),
+    )?;
+    Ok(payment_method)
+}
+
+#[instrument(skip(state))]
+pub async fn share_payment_method(
+    state: &AppState,
+    merchant_account: domain::MerchantAccount,
+    payment_method_id: String,
+    req: PaymentMethodShareRequest,
+) -> RouterResponse<PaymentMethodShareResponse> {
+    validate_payment_method_share_request(&merchant_account, &req)?;
+
+    let payment_method =
+        find_merchant_payment_method(state, &merchant_account, &payment_method_id).await?;
+    utils::when(
+        payment_method.payment_method != enums::PaymentMethod::Card,
+        || {
+            Err(report!(errors::ApiErrorResponse::NotSupported {
+                message: "Only saved cards can be shared with other sub-merchants".to_string(),
+            }))
+        },
+    )?;
+
+    let platform_merchant_id = merchant_account.parent_merchant_id.clone().ok_or(
+        errors::ApiErrorResponse::PreconditionFailed {
+            message: "Payment methods can only be shared by the sub-merchants of a platform"
+                .to_string(),
+        },
+    )?;
+
+    let db = &*state.store;
+    let not_on_platform = || errors::ApiErrorResponse::InvalidRequestData {
+        message: format!(
+            "The merchant {} is not a sub-merchant of the platform {platform_merchant_id}",
+            req.merchant_id
+        ),
+    };
+    let shared_with_key_store = db
+        .get_merchant_key_store_by_merchant_id(
+            &req.merchant_id,
+            &db.get_master_key().to_vec().into(),
+        )
+        .await
+        .to_not_found_response(not_on_platform())?;
+    let shared_with_merchant_account = db
+        .find_merchant_account_by_merchant_id(&req.merchant_id, &shared_with_key_store)
+        .await
+        .to_not_found_response(not_on_platform())?;
+    utils::when(
+        shared_with_merchant_account.parent_merchant_id.as_ref() != Some(&platform_merchant_id),
+        || Err(report!(not_on_platform())),
+    )?;
+    // The share and the payment method are stored in the storage region of the merchant owning
+    // the payment method, from which the payments of the sub-merchant must be able to read them
+    utils::when(
+        shared_with_merchant_account.storage_region != merchant_account.storage_region,
+        || {
+            Err(report!(errors::ApiErrorResponse::InvalidRequestData {
+                message: "Payment methods can only be shared with merchants pinned to the same \
+                          storage region"
+                    .to_string(),
+            }))
+        },
+    )?;
+
+    let now = common_utils::date_time::now();
+    let max_uses = req.max_uses.map(i32::try_from).transpose().map_err(|_| {
+        errors::ApiErrorResponse::InvalidRequestData {
+            message: "max_uses is too large".to_string(),
+        }
+    })?;
+    let payment_method_share = db
+        .insert_payment_method_share(storage::PaymentMethodShareNew {
+            share_id: utils::generate_id(consts::ID_LENGTH, "pms"),
+            payment_method_id: payment_method.payment_method_id,
+            merchant_id: merchant_account.merchant_id,
+            shared_with_merchant_id: req.merchant_id,
+            platform_merchant_id,
+            status: enums::PaymentMethodShareStatus::Active,
+            consent_reference: req.consent.reference,
+            consented_at: req.consent.consented_at,
+            consent_ip_address: req.consent.ip_address,
+            max_uses,
+            use_count: 0,
+            max_amount: req.max_amount,
+            currency: req.currency,
+            expires_at: req.expires_at,
+            created_at: now,
+            modified_at: now,
+        })
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to insert the payment method share")?;
+
+    Ok(services::ApplicationResponse::Json(
+        get_payment_method_share_response(payment_method_share),
+    ))
+}
+
+#[instrument(skip(state))]
+pub async fn list_payment_method_shares(
+    state: &AppState,
+    merchant_account: domain::MerchantAccount,
+    payment_method_id: String,
+) -> RouterResponse<Vec<PaymentMethodShareResponse>> {
+    let payment_method =
+        find_merchant_payment_method(state, &merchant_account, &payment_method_id).await?;
+    let payment_method_shares = state
+        .store
+        .find_payment_method_shares_by_merchant_id_payment_method_id(
+            &merchant_account.merchant_id,
+            &payment_method.payment_method_id,
+        )
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to list the shares of the payment method")?;
+
+    Ok(services::ApplicationResponse::Json(
+        payment_method_shares
+            .into_iter()
+            .map(get_payment_method_share_response)
+            .collect(),
+    ))
+}
+
+/// Finds a payment method share visible to the merchant, being either the merchant owning the
+/// payment method or the sub-merchant it is shared with.
+async fn find_payment_method_share(
+    state: &AppState,
+    merchant_account: &domain::MerchantAccount,
+    share_id: &str,
+) -> RouterResult<storage::PaymentMethodShare> {
+    let not_found = || errors::ApiErrorResponse::PaymentMethodShareNotFound {
+        share_id: share_id.to_string(),
+    };
+    let payment_method_share = state
+        .store
+        .find_payment_method_share_by_share_id(share_id)
+        .await
+        .to_not_found_response(not_found())?;
+    utils::when(
+        payment_method_share.merchant_id != merchant_account.merchant_id
+            && payment_method_share.shared_with_merchant_id != merchant_account.merchant_id,
+        || Err(report!(not_found())),
+    )?;
+    Ok(payment_method_share)
+}
+
+#[instrument(skip(state))]
+pub async fn retrieve_payment_method_share(
+    state: &AppState,
+    merchant_account: domain::MerchantAccount,
+    share_id: String,
+) -> RouterResponse<PaymentMethodShareResponse> {
+    let payment_method_share =
+        find_payment_method_share(state, &merchant_account, &share_id).await?;
+    Ok(services::ApplicationResponse::Json(
+        get_payment_method_share_response(payment_method_share),
+    ))
+}
+
+/// Revokes a payment method share, after which the sub-merchant can no longer pay with the
+/// payment method. Only the merchant owning the payment method can revoke its shares.
+#[instrument(skip(state))]
+pub async fn revoke_payment_method_share(
+    state: &AppState,
+    merchant_account: domain::MerchantAccount,
+    share_id: String,
+) -> RouterResponse<PaymentMethodShareResponse> {
+    let payment_method_share =
+        find_payment_method_share(state, &merchant_account, &share_id).await?;
+    utils::when(
+        payment_method_share.merchant_id != merchant_account.merchant_id,
+        || {
+            Err(report!(errors::ApiErrorResponse::PreconditionFailed {
+                message: "Only the merchant owning the payment method can revoke its shares"
+                    .to_string(),
+            }))
+        },
+    )?;
+
+    let payment_method_share =
+        if payment_method_share.status == enums::PaymentMethodShareStatus::Revoked {
+            payment_method_share
+        } else {
+            state
+                .store
+                .update_payment_method_share_by_share_id(
+                    &share_id,
+                    storage::PaymentMethodShareUpdate::Revoke {
+                        revoked_at: common_utils::date_time::now(),
+                    },
+                )
+                .await
+                .change_context(errors::ApiErrorResponse::InternalServerError)
+                .attach_printable("Failed to revoke the payment method share")?
+        };
+
+    Ok(services::ApplicationResponse::Json(
+        get_payment_method_share_response(payment_method_share),
+    ))
+}
+
+/// Returns the amount and the currency of the payment, from the request or else from the payment
+/// being updated or confirmed.
+async fn get_payment_amount(
+    state: &AppState,
+    merchant_account: &domain::MerchantAccount,
+    req: &PaymentsRequest,
+) -> RouterResult<(i64, Option<enums::Currency>)> {
+    if let (Some(amount), Some(currency)) = (req.amount, req.currency) {
+        return Ok((amount.into(), Some(currency)));
+    }
+
+    let payment_id = req
+        .payment_id
+        .as_ref()
+        .get_required_value("payment_id")?
+        .get_payment_intent_id()
+        .change_context(errors::ApiErrorResponse::PaymentNotFound)?;
+    let payment_intent = state
+        .store
+        .find_payment_intent_by_payment_id_merchant_id(
+            &payment_id,
+            &merchant_account.merchant_id,
+            merchant_account.storage_scheme,
+        )
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::PaymentNotFound)?;
+    Ok((
+        req.amount.map_or(payment_intent.amount, Into::into),
+        req.currency.or(payment_intent.currency),
+    ))
+}
+
+/// Checks that the share can still be used by the merchant for a payment of the amount and the
+/// currency, when the share limits the amount of the payments.
+fn validate_payment_method_share_usage(
+    payment_method_share: &storage::PaymentMethodShare,
+    merchant_id: &str,
+    payment_amount: Option<(i64, Option<enums::Currency>)>,
+) -> RouterResult<()> {
+    let precondition_failed = |message: &str| {
+        Err(report!(errors::ApiErrorResponse::PreconditionFailed {
+            message: message.to_string(),
+        }))
+    };
+    utils::when(
+        payment_method_share.shared_with_merchant_id != merchant_id,
+        || precondition_failed("The payment method is not shared with the merchant"),
+    )?;
+    utils::when(
+        payment_method_share.status == enums::PaymentMethodShareStatus::Revoked,
+        || precondition_failed("The share of the payment method has been revoked"),
+    )?;
+    utils::when(
+        payment_method_share.expires_at.map_or(false, |expires_at| {
+            expires_at <= common_utils::date_time::now()
+        }),
+        || precondition_failed("The share of the payment method has expired"),
+    )?;
+    utils::when(
+        payment_method_share
+            .max_uses
+            .map_or(false, |max_uses| payment_method_share.use_count >= max_uses),
+        || {
+            precondition_failed(
+                "The share of the payment method has reached its maximum number of uses",
+            )
+        },
+    )?;
+
+    if let (Some(max_amount), Some((amount, currency))) =
+        (payment_method_share.max_amount, payment_amount)
+    {
+        utils::when(
+            currency != payment_method_share.currency || amount > max_amount,
+            || {
+                precondition_failed(
+                    "The payment exceeds the maximum amount of the share of the payment method",
+                )
+            },
+        )?;
+    }
+    Ok(())
+}
+
+/// A use of a payment method share counted against its maximum number of uses, which is released
+/// if the payment fails.
+#[derive(Debug)]
+pub struct PaymentMethodShareUse {
+    share_id: String,
+}
+
+/// Uses the card shared with the merchant through `shared_payment_method_id` as the payment method
+/// of the payment, checking that the share can still be used for the payment.
+///
+/// The share is checked on every request passing it, but the card is only read from the vault and
+/// the payment only counted against the maximum number of uses of the share when the request
+/// confirms the payment. The amount is checked again at confirmation, as the amount of the payment
+/// may have been updated since its creation.
+#[instrument(skip_all)]
+pub async fn use_shared_payment_method(
+    state: &AppState,
+    merchant_account: &domain::MerchantAccount,
+    req: &mut PaymentsRequest,
+) -> RouterResult<Option<PaymentMethodShareUse>> {
+    let Some(share_id) = req.shared_payment_method_id.clone() else {
+        return Ok(None);
+    };
+
+    utils::when(
+        req.payment_method_data.is_some()
+            || req.payment_token.is_some()
+            || req.mandate_id.is_some(),
+        || {
+            Err(report!(errors::ApiErrorResponse::InvalidRequestData {
+                message: "shared_payment_method_id cannot be passed along with \
+                          payment_method_data, payment_token or mandate_id"
+                    .to_string(),
+            }))
+        },
+    )?;
+    // The card stays in the vault of the merchant owning it
+    utils::when(
+        req.setup_future_usage.is_some() || req.mandate_data.is_some(),
+        || {
+            Err(report!(errors::ApiErrorResponse::PreconditionFailed {
+                message: "Shared payment methods cannot be saved or used to set up mandates"
+                    .to_string(),
+            }))
+        },
+    )?;
+
+    let payment_method_share =
+        find_payment_method_share(state, merchant_account, &share_id).await?;
+    let payment_amount = match payment_method_share.max_amount {
+        Some(_) => Some(get_payment_amount(state, merchant_account, req).await?),
+        None => None,
+    };
+    validate_payment_method_share_usage(
+        &payment_method_share,
+        &merchant_account.merchant_id,
+        payment_amount,
+    )?;
+
+    if req.confirm != Some(true) {
+        return Ok(None);
+    }
+
+    let payment_method = state
+        .store
+        .find_payment_method(&payment_method_share.payment_method_id)
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::PaymentMethodNotFound)?;
+    let card = cards::get_card_from_locker(
+        state,
+        &payment_method.customer_id,
+        &payment_method.merchant_id,
+        cards::get_locker_id(&payment_method),
+    )
+    .await?;
+
+    state
+        .store
+        .update_active_payment_method_share_by_share_id_use_count(
+            &payment_method_share.share_id,
+            payment_method_share.use_count,
+            storage::PaymentMethodShareUpdate::UsageUpdate {
+                use_count: payment_method_share.use_count + 1,
+            },
+        )
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::PreconditionFailed {
+            message:
+                "The share of the payment method was used or revoked concurrently, please retry"
+                    .to_string(),
+        })?;
+    logger::info!(
+        share_id = %payment_method_share.share_id,
+        payment_method_id = %payment_method.payment_method_id,
+        owner_merchant_id = %payment_method.merchant_id,
+        "Using a payment method shared by another sub-merchant"
+    );
+
+    req.payment_method_data = Some(api::PaymentMethodData::Card(api::Card {
+        card_number: card.card_number,
+        card_exp_month: card.card_exp_month,
+        card_exp_year: card.card_exp_year,
+        card_holder_name: card.name_on_card.unwrap_or_default(),
+        card_cvc: req.card_cvc.clone().unwrap_or_default(),
+        card_issuer: None,
+        card_network: None,
+        card_type: None,
+        card_issuing_country: None,
+        bank_code: None,
+        nick_name: None,
+    }));
+    req.payment_method = Some(enums::PaymentMethod::Card);
+    req.payment_method_type = req
+        .payment_method_type
+        .or(payment_method.payment_method_type);
+    Ok(Some(PaymentMethodShareUse {
+        share_id: payment_method_share.share_id,
+    }))
+}
+
+/// Whether the payment made with a shared payment method did not go through, in which case it
+/// must not count against the maximum number of uses of the share.
+fn is_payment_failed(response: &RouterResponse<PaymentsResponse>) -> bool {
+    match response {
+        Ok(services::ApplicationResponse::Json(payment)) => {
+            payment.status == enums::IntentStatus::Failed
+        }
+        Ok(_) => false,
+        Err(_) => true,
+    }
+}
+
+/// Releases the use of the payment method share if the payment made with it failed.
+#[instrument(skip_all)]
+pub async fn complete_shared_payment_method_use(
+    state: &AppState,
+    payment_method_share_use: PaymentMethodShareUse,
+    response: &RouterResponse<PaymentsResponse>,
+) {
+    if !is_payment_failed(response) {
+        return;
+    }
+    if let Err(error) = state
+        .store
+        .release_payment_method_share_use_by_share_id(&payment_method_share_use.share_id)
+        .await
+    {
+        logger::error!(
+            share_id = %payment_method_share_use.share_id,
+            payment_method_share_release_error=?error
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+
+    fn get_payment_method_share() -> storage::PaymentMethodShare {
+        let now = common_utils::date_time::now();
+        storage::PaymentMethodShare {
+            id: 1,
+            share_id: "pms_123".to_string(),
+            payment_method_id: "pm_123".to_string(),
+            merchant_id: "merchant_owner".to_string(),
+            shared_with_merchant_id: "merchant_shared".to_string(),
+            platform_merchant_id: "merchant_platform".to_string(),
+            status: enums::PaymentMethodShareStatus::Active,
+            consent_reference: "consent_123".to_string(),
+            consented_at: now,
+            consent_ip_address: None,
+            max_uses: Some(2),
+            use_count: 1,
+            max_amount: Some(1000),
+            currency: Some(enums::Currency::USD),
+            expires_at: None,
+            revoked_at: None,
+            created_at: now,
+            modified_at: now,
+        }
+    }
+
+    #[test]
+    fn test_validate_payment_method_share_usage() {
+        let share = get_payment_method_share();
+        let usd = Some(enums::Currency::USD);
+
+        assert!(validate_payment_method_share_usage(&share, "merchant_shared", None).is_ok());
+        assert!(
+            validate_payment_method_share_usage(&share, "merchant_shared", Some((1000, usd)))
+                .is_ok()
+        );
+        assert!(
+            validate_payment_method_share_usage(&share, "merchant_shared", Some((1001, usd)))
+                .is_err()
+        );
+        assert!(validate_payment_method_share_usage(
+            &share,
+            "merchant_shared",
+            Some((500, Some(enums::Currency::EUR)))
+        )
+        .is_err());
+        assert!(validate_payment_method_share_usage(&share, "merchant_other", None).is_err());
+
+        let used_up = storage::PaymentMethodShare {
+            use_count: 2,
+            ..get_payment_method_share()
+        };
+        assert!(validate_payment_method_share_usage(&used_up, "merchant_shared", None).is_err());
+
+        let revoked = storage::PaymentMethodShare {
+            status: enums::PaymentMethodShareStatus::Revoked,
+            ..get_payment_method_share()
+        };
+        assert!(validate_payment_method_share_usage(&revoked, "merchant_shared", None).is_err());
+    }
+
+    #[test]
+    fn test_is_payment_failed() {
+        let response = |status| -> RouterResponse<PaymentsResponse> {
+            Ok(services::ApplicationResponse::Json(PaymentsResponse {
+                status,
+                ..Default::default()
+            }))
+        };
+
+        assert!(is_payment_failed(&response(enums::IntentStatus::Failed)));
+        assert!(!is_payment_failed(&response(
+            enums::IntentStatus::Succeeded
+        )));
+        assert!(!is_payment_failed(&response(
+            enums::IntentStatus::RequiresCustomerAction
+        )));
+        assert!(is_payment_failed(&Err(report!(
+            errors::ApiErrorResponse::InternalServerError
+        ))));
+    }
+}
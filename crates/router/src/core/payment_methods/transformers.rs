@@ -334,6 +334,7 @@ pub fn mk_add_card_response_hs(
         recurring_enabled: false,           // [#256]
         installment_payment_enabled: false, // #[#256]
         payment_experience: Some(vec![api_models::enums::PaymentExperience::RedirectToUrl]), // [#256]
+        account_update: None,
     }
 }
 
@@ -368,6 +369,7 @@ pub fn mk_add_card_response(
         recurring_enabled: false,           // [#256]
         installment_payment_enabled: false, // [#256] Pending on discussion, and not stored in the card locker
         payment_experience: None,           // [#256]
+        account_update: None,
     }
 }
 
@@ -741,6 +741,7 @@ impl Vault {
         payment_method: &api::PaymentMethodData,
         customer_id: Option<String>,
         _pm: enums::PaymentMethod,
+        _merchant_id: &str,
     ) -> RouterResult<String> {
         let value1 = payment_method
             .get_value1(customer_id.clone())
@@ -828,6 +829,7 @@ impl Vault {
         payment_method: &api::PaymentMethodData,
         customer_id: Option<String>,
         pm: enums::PaymentMethod,
+        merchant_id: &str,
     ) -> RouterResult<String> {
         let value1 = payment_method
             .get_value1(customer_id.clone())
@@ -842,7 +844,7 @@ impl Vault {
         let lookup_key = token_id.unwrap_or_else(|| generate_id_with_default_len("token"));
 
         let lookup_key = create_tokenize(state, value1, Some(value2), lookup_key).await?;
-        add_delete_tokenized_data_task(&*state.store, &lookup_key, pm).await?;
+        add_delete_tokenized_data_task(&*state.store, &lookup_key, pm, merchant_id).await?;
         scheduler_metrics::TOKENIZED_DATA_COUNT.add(&metrics::CONTEXT, 1, &[]);
         Ok(lookup_key)
     }
@@ -970,7 +972,7 @@ pub async fn create_tokenize(
         version: Some(VAULT_VERSION.to_string()),
     };
     let request = payment_methods::mk_crud_locker_request(
-        &state.conf.locker,
+        state.get_locker(),
         "/tokenize",
         create_tokenize_request,
     )
@@ -1046,7 +1048,7 @@ pub async fn get_tokenized_data(
         version: Some("0".to_string()),
     };
     let request = payment_methods::mk_crud_locker_request(
-        &state.conf.locker,
+        state.get_locker(),
         "/tokenize/get",
         create_tokenize_request,
     )
@@ -1125,7 +1127,7 @@ pub async fn delete_tokenized_data(
         version: Some("0".to_string()),
     };
     let request = payment_methods::mk_crud_locker_request(
-        &state.conf.locker,
+        state.get_locker(),
         "/tokenize/delete/token",
         create_tokenize_request,
     )
@@ -1158,12 +1160,14 @@ pub async fn add_delete_tokenized_data_task(
     db: &dyn db::StorageInterface,
     lookup_key: &str,
     pm: enums::PaymentMethod,
+    merchant_id: &str,
 ) -> RouterResult<()> {
     let runner = "DELETE_TOKENIZE_DATA_WORKFLOW";
     let current_time = common_utils::date_time::now();
     let tracking_data = serde_json::to_value(storage::TokenizeCoreWorkflow {
         lookup_key: lookup_key.to_owned(),
         pm,
+        merchant_id: Some(merchant_id.to_owned()),
     })
     .into_report()
     .change_context(errors::ApiErrorResponse::InternalServerError)
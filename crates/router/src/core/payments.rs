@@ -1,10 +1,25 @@
 pub mod access_token;
+pub mod apple_pay;
+pub mod confirm_protection;
+pub mod connector_exclusion;
+pub mod cross_border_fees;
+pub mod currency_conversion;
 pub mod customers;
 pub mod flows;
 pub mod helpers;
+pub mod hosted_checkout;
+pub mod incremental_authorization;
+pub mod metadata;
 pub mod operations;
+pub mod reauthorization;
+pub mod receipts;
+pub mod retry;
+pub mod routing;
+pub mod session_tokens;
+pub mod simulation;
 pub mod tokenization;
 pub mod transformers;
+pub mod velocity_limits;
 
 use std::{collections::HashMap, fmt::Debug, marker::PhantomData, ops::Deref, time::Instant};
 
@@ -13,9 +28,10 @@ use common_utils::pii;
 use diesel_models::ephemeral_key;
 use error_stack::{IntoReport, ResultExt};
 use futures::future::join_all;
-use masking::Secret;
+use masking::{PeekInterface, Secret};
 use router_env::{instrument, tracing};
 use time;
+use tokio::sync::broadcast;
 
 pub use self::operations::{
     PaymentCancel, PaymentCapture, PaymentConfirm, PaymentCreate, PaymentMethodValidate,
@@ -29,7 +45,12 @@ use self::{
 use super::errors::StorageErrorExt;
 use crate::{
     configs::settings::PaymentMethodTypeTokenFilter,
-    core::errors::{self, CustomResult, RouterResponse, RouterResult},
+    consts,
+    core::{
+        commissions,
+        errors::{self, CustomResult, RouterResponse, RouterResult},
+        event_bus, gift_cards, hooks, invoices, ledger, sync_cache,
+    },
     db::StorageInterface,
     logger,
     routes::{metrics, AppState},
@@ -39,7 +60,7 @@ use crate::{
         self, api, domain,
         storage::{self, enums as storage_enums, ProcessTrackerExt},
     },
-    utils::{Encode, OptionExt, ValueExt},
+    utils::{Encode, OptionExt, StringExt, ValueExt},
 };
 
 #[instrument(skip_all, fields(payment_id, merchant_id))]
@@ -89,6 +110,7 @@ where
             auth_flow,
         )
         .await?;
+    let previous_intent_status = payment_data.payment_intent.status;
 
     authenticate_client_secret(
         req.get_client_secret(),
@@ -96,6 +118,24 @@ where
         merchant_account.intent_fulfillment_time,
     )?;
 
+    if state.runtime_conf.get().confirm_protection.enabled
+        && auth_flow == services::AuthFlow::Client
+        && is_operation_confirm(&operation)
+    {
+        confirm_protection::check_confirm_attempts(
+            state,
+            &merchant_account,
+            &mut payment_data,
+            req.get_captcha_token(),
+        )
+        .await?;
+
+        // The payment is confirmed only after the customer has solved the CAPTCHA
+        if payment_data.captcha_site_key.is_some() {
+            return Ok((payment_data, req, None));
+        }
+    }
+
     let (operation, customer) = operation
         .to_domain()?
         .get_or_create_customer_details(
@@ -118,98 +158,369 @@ where
     )
     .await?;
 
-    let schedule_time = match &connector {
-        Some(api::ConnectorCallType::Single(connector_data)) => {
-            if should_add_task_to_process_tracker(&payment_data) {
-                payment_sync::get_sync_process_schedule_time(
-                    &*state.store,
-                    connector_data.connector.id(),
-                    &merchant_account.merchant_id,
-                    0,
-                )
-                .await
-                .into_report()
-                .change_context(errors::ApiErrorResponse::InternalServerError)
-                .attach_printable("Failed while getting process schedule time")?
-            } else {
-                None
-            }
-        }
-        _ => None,
-    };
+    if let Some(api::ConnectorCallType::Single(connector_data)) = &connector {
+        if is_operation_confirm(&operation) {
+            helpers::validate_payment_method_type_enabled(
+                state,
+                &merchant_account,
+                &key_store,
+                connector_data.connector.id(),
+                &payment_data,
+            )
+            .await?;
+
+            helpers::validate_connector_amount_limits(
+                state,
+                connector_data.connector.id(),
+                &payment_data,
+            )?;
 
-    let (mut payment_data, tokenization_action) =
-        get_connector_tokenization_action(state, &operation, payment_data, &validate_result)
+            connector_exclusion::validate_connector_not_excluded(
+                &*state.store,
+                &merchant_account,
+                connector_data.connector.id(),
+                &payment_data,
+            )
             .await?;
 
-    let updated_customer = call_create_connector_customer_if_required(
-        state,
-        &customer,
-        &merchant_account,
-        &key_store,
-        &mut payment_data,
-    )
-    .await?;
+            hooks::run_pre_authorize_hooks(
+                &state.hooks,
+                &merchant_account,
+                &mut payment_data.payment_intent,
+                &payment_data.payment_attempt,
+                connector_data.connector.id(),
+            )
+            .await?;
+        }
+    }
+    // Every payment sent to the connectors for authorization is counted against the velocity
+    // limits, until it fails
+    let velocity_reservation = if is_operation_confirm(&operation) && connector.is_some() {
+        Some(velocity_limits::check_velocity_limits(state, &merchant_account, &payment_data).await?)
+    } else {
+        None
+    };
+    let run_post_authorize_hooks = is_operation_confirm(&operation)
+        && matches!(connector, Some(api::ConnectorCallType::Single(_)));
+    // The velocity reservation and the gift card redemption are released if the payment fails, or
+    // if it cannot be sent to the connector. The gift card redemption is kept if the payment could
+    // have been charged by the connector, in which case the status of the payment is not known.
+    let mut gift_card_redemption = None;
+    let mut is_payment_pending_at_connector = false;
+    let result: RouterResult<PaymentData<F>> = async {
+        let schedule_time = match &connector {
+            Some(api::ConnectorCallType::Single(connector_data)) => {
+                if should_add_task_to_process_tracker(&payment_data) {
+                    payment_sync::get_initial_sync_process_schedule_time(
+                        &*state.store,
+                        connector_data.connector.id(),
+                        &merchant_account.merchant_id,
+                    )
+                    .await
+                    .into_report()
+                    .change_context(errors::ApiErrorResponse::InternalServerError)
+                    .attach_printable("Failed while getting process schedule time")?
+                } else {
+                    None
+                }
+            }
+            _ => None,
+        };
 
-    if let Some(connector_details) = connector {
-        payment_data = match connector_details {
-            api::ConnectorCallType::Single(connector) => {
-                let router_data = call_connector_service(
-                    state,
-                    &merchant_account,
-                    &key_store,
-                    connector,
-                    &operation,
-                    &mut payment_data,
-                    &customer,
-                    call_connector_action,
-                    tokenization_action,
-                    updated_customer,
-                    validate_result.requeue,
-                    schedule_time,
-                )
+        let (mut payment_data, mut tokenization_action) =
+            get_connector_tokenization_action(state, &operation, payment_data, &validate_result)
                 .await?;
 
-                let operation = Box::new(PaymentResponse);
-                let db = &*state.store;
-                operation
-                    .to_post_update_tracker()?
-                    .update_tracker(
-                        db,
-                        &validate_result.payment_id,
-                        payment_data,
-                        router_data,
-                        merchant_account.storage_scheme,
+        let mut updated_customer = call_create_connector_customer_if_required(
+            state,
+            &customer,
+            &merchant_account,
+            &key_store,
+            &mut payment_data,
+        )
+        .await?;
+
+        // The balance of the gift card is redeemed right before the remaining amount of the
+        // payment is charged, and returned to the gift card if the payment fails
+        if let Some(gift_card_id) = req.get_gift_card_id() {
+            if is_operation_confirm(&operation)
+                && matches!(connector, Some(api::ConnectorCallType::Single(_)))
+            {
+                gift_card_redemption = Some(
+                    gift_cards::redeem_gift_card_for_payment(
+                        state,
+                        &merchant_account,
+                        gift_card_id,
+                        &mut payment_data,
                     )
-                    .await?
+                    .await?,
+                );
             }
+        }
 
-            api::ConnectorCallType::Multiple(connectors) => {
-                call_multiple_connectors_service(
-                    state,
-                    &merchant_account,
+        if let Some(connector_details) = connector {
+            payment_data = match connector_details {
+                api::ConnectorCallType::Single(mut connector) => {
+                    // Payments routed through the straight through algorithm of the request are only
+                    // attempted with the connector chosen by the merchant
+                    let mut remaining_retries = if is_operation_confirm(&operation)
+                        && matches!(call_connector_action, CallConnectorAction::Trigger)
+                        && payment_data
+                            .payment_attempt
+                            .straight_through_algorithm
+                            .is_none()
+                    {
+                        retry::get_max_retries(state, &merchant_account.merchant_id).await
+                    } else {
+                        0
+                    };
+                    let mut attempted_connectors = vec![connector.connector_name.to_string()];
+
+                    loop {
+                        is_payment_pending_at_connector = true;
+                        let router_data = call_connector_service(
+                            state,
+                            &merchant_account,
+                            &key_store,
+                            connector.clone(),
+                            &operation,
+                            &mut payment_data,
+                            &customer,
+                            call_connector_action.clone(),
+                            tokenization_action,
+                            updated_customer,
+                            validate_result.requeue,
+                            schedule_time,
+                        )
+                        .await;
+                        if is_operation_status(&operation)
+                            && router_data
+                                .as_ref()
+                                .map_or(true, |router_data| router_data.response.is_err())
+                        {
+                            release_payment_sync_claim(state, &payment_data).await;
+                        }
+                        let router_data = router_data?;
+                        let is_retryable = remaining_retries > 0
+                            && retry::is_retryable_error(state, &connector, &router_data);
+
+                        let response_operation = Box::new(PaymentResponse);
+                        let db = &*state.store;
+                        payment_data = response_operation
+                            .to_post_update_tracker()?
+                            .update_tracker(
+                                db,
+                                &validate_result.payment_id,
+                                payment_data,
+                                router_data,
+                                merchant_account.storage_scheme,
+                            )
+                            .await?;
+
+                        if !is_retryable
+                            || payment_data.payment_attempt.status
+                                != storage_enums::AttemptStatus::Failure
+                        {
+                            break payment_data;
+                        }
+                        // The attempt has failed, so the payment has not been charged until the next
+                        // attempt is sent to the connector
+                        is_payment_pending_at_connector = false;
+                        let Some(next_connector) = retry::get_next_connector(
+                            state,
+                            &merchant_account,
+                            &key_store,
+                            &payment_data,
+                            &attempted_connectors,
+                        )
+                        .await?
+                        else {
+                            break payment_data;
+                        };
+
+                        retry::create_retry_attempt(
+                            state,
+                            &merchant_account,
+                            &mut payment_data,
+                            &next_connector,
+                        )
+                        .await?;
+                        (payment_data, tokenization_action) = get_connector_tokenization_action(
+                            state,
+                            &operation,
+                            payment_data,
+                            &validate_result,
+                        )
+                        .await?;
+                        updated_customer = call_create_connector_customer_if_required(
+                            state,
+                            &customer,
+                            &merchant_account,
+                            &key_store,
+                            &mut payment_data,
+                        )
+                        .await?;
+
+                        remaining_retries -= 1;
+                        attempted_connectors.push(next_connector.connector_name.to_string());
+                        connector = next_connector;
+                    }
+                }
+
+                api::ConnectorCallType::Multiple(connectors) => {
+                    call_multiple_connectors_service(
+                        state,
+                        &merchant_account,
+                        &key_store,
+                        connectors,
+                        &operation,
+                        payment_data,
+                        &customer,
+                    )
+                    .await?
+                }
+            };
+        } else {
+            (_, payment_data) = operation
+                .to_update_tracker()?
+                .update_trackers(
+                    &*state.store,
+                    payment_data.clone(),
+                    customer.clone(),
+                    validate_result.storage_scheme,
+                    updated_customer,
                     &key_store,
-                    connectors,
-                    &operation,
-                    payment_data,
-                    &customer,
+                    None,
                 )
-                .await?
-            }
-        };
-    } else {
-        (_, payment_data) = operation
-            .to_update_tracker()?
-            .update_trackers(
-                &*state.store,
-                payment_data.clone(),
-                customer.clone(),
-                validate_result.storage_scheme,
-                updated_customer,
-                &key_store,
-                None,
+                .await?;
+        }
+
+        Ok(payment_data)
+    }
+    .await;
+
+    let (is_payment_failed, is_payment_charge_known) = match &result {
+        Ok(payment_data) => (
+            payment_data.payment_intent.status == storage_enums::IntentStatus::Failed,
+            true,
+        ),
+        Err(_) => (true, !is_payment_pending_at_connector),
+    };
+    if is_payment_failed {
+        if let Some(velocity_reservation) = velocity_reservation {
+            velocity_reservation.release().await;
+        }
+    }
+    if is_payment_failed && is_payment_charge_known {
+        if let Some(gift_card_redemption) = gift_card_redemption {
+            gift_cards::reverse_gift_card_redemption(
+                state,
+                &merchant_account,
+                gift_card_redemption,
             )
-            .await?;
+            .await
+            .map_err(|error| logger::error!(gift_card_reversal_error=?error))
+            .ok();
+        }
+    }
+    let payment_data = result?;
+
+    if run_post_authorize_hooks {
+        hooks::run_post_authorize_hooks(
+            &state.hooks,
+            &merchant_account,
+            &payment_data.payment_intent,
+            &payment_data.payment_attempt,
+        )
+        .await;
+    }
+
+    if previous_intent_status != storage_enums::IntentStatus::Succeeded
+        && payment_data.payment_intent.status == storage_enums::IntentStatus::Succeeded
+    {
+        // The payment has already succeeded, so failing to apply it to its invoice or to record
+        // the commission of the platform must not fail the payment. A commission which could not
+        // be recorded is recorded later by the process tracker.
+        invoices::apply_invoice_payment(state, &merchant_account, &payment_data.payment_intent)
+            .await
+            .map_err(|error| logger::error!(invoice_payment_error=?error))
+            .ok();
+        if let Err(error) = commissions::record_payment_commission(
+            state,
+            &merchant_account,
+            &payment_data.payment_intent,
+        )
+        .await
+        {
+            logger::error!(commission_error=?error);
+            commissions::add_commission_recording_task(
+                state,
+                &merchant_account,
+                &payment_data.payment_intent,
+            )
+            .await
+            .map_err(|error| logger::error!(commission_recording_task_error=?error))
+            .ok();
+        }
+    }
+
+    // The receipt is emailed once, when the payment is first captured in full or in part. Failing
+    // to email it must not fail the payment.
+    #[cfg(feature = "email")]
+    if !receipts::is_receipt_available(previous_intent_status)
+        && receipts::is_receipt_available(payment_data.payment_intent.status)
+    {
+        receipts::send_receipt_email_if_enabled(
+            state,
+            &merchant_account,
+            &payment_data.payment_intent,
+            &payment_data.payment_attempt,
+            payment_data.email.clone(),
+        )
+        .map_err(|error| logger::error!(receipt_email_error=?error))
+        .ok();
+    }
+
+    if previous_intent_status != storage_enums::IntentStatus::RequiresCapture {
+        // The payment can still be captured before its authorization expires, so failing to
+        // schedule its re-authorization must not fail the payment
+        reauthorization::add_reauthorization_task_if_required(
+            state,
+            &merchant_account,
+            &payment_data.payment_intent,
+            &payment_data.payment_attempt,
+        )
+        .await
+        .map_err(|error| logger::error!(reauthorization_error=?error))
+        .ok();
+    }
+
+    // Partially captured payments can be captured again without their status changing
+    if previous_intent_status != payment_data.payment_intent.status
+        || payment_data.payment_intent.status == storage_enums::IntentStatus::PartiallyCaptured
+    {
+        // The status of the payment has already been updated, so failing to post it to the
+        // ledger must not fail the payment
+        ledger::record_payment_postings(
+            state,
+            &merchant_account,
+            previous_intent_status,
+            &payment_data.payment_intent,
+            &payment_data.payment_attempt,
+        )
+        .await
+        .map_err(|error| logger::error!(ledger_error=?error))
+        .ok();
+    }
+
+    if previous_intent_status != payment_data.payment_intent.status {
+        state
+            .event_bus
+            .publish(event_bus::Event::PaymentStatusChanged {
+                merchant_id: merchant_account.merchant_id.clone(),
+                payment_id: payment_data.payment_intent.payment_id.clone(),
+                status: payment_data.payment_intent.status,
+            });
     }
 
     Ok((payment_data, req, customer))
@@ -264,6 +575,105 @@ where
     )
 }
 
+/// Retrieves the payment, holding the request until the payment reaches a terminal status or the
+/// wait time elapses. Status changes made by this instance are observed over the event bus, and
+/// the payment is also re-read periodically to observe changes made by other instances.
+pub async fn payments_retrieve_until_terminal(
+    state: &AppState,
+    merchant_account: domain::MerchantAccount,
+    key_store: domain::MerchantKeyStore,
+    mut req: api::PaymentsRetrieveRequest,
+    auth_flow: services::AuthFlow,
+    wait_time: std::time::Duration,
+) -> RouterResponse<api::PaymentsResponse> {
+    let deadline = tokio::time::Instant::now() + wait_time;
+    // Subscribe before the first read, so that no status change is missed in between
+    let mut events = state.event_bus.subscribe();
+
+    loop {
+        let response = payments_core::<api::PSync, api::PaymentsResponse, _, _, _>(
+            state,
+            merchant_account.clone(),
+            key_store.clone(),
+            PaymentStatus,
+            req.clone(),
+            auth_flow,
+            CallConnectorAction::Trigger,
+        )
+        .await?;
+
+        let payment_id = match &response {
+            services::ApplicationResponse::Json(payment)
+                if !is_terminal_intent_status(payment.status) =>
+            {
+                payment.payment_id.clone()
+            }
+            _ => return Ok(response),
+        };
+
+        // The connector is synced at most once, later reads return the stored status
+        req.force_sync = false;
+
+        let status_changed = wait_for_payment_status_change(
+            &mut events,
+            &merchant_account.merchant_id,
+            payment_id.as_deref(),
+            deadline,
+        )
+        .await;
+        if !status_changed && tokio::time::Instant::now() >= deadline {
+            return Ok(response);
+        }
+    }
+}
+
+fn is_terminal_intent_status(status: storage_enums::IntentStatus) -> bool {
+    matches!(
+        status,
+        storage_enums::IntentStatus::Succeeded
+            | storage_enums::IntentStatus::Failed
+            | storage_enums::IntentStatus::Cancelled
+    )
+}
+
+/// Waits until a status change of the payment is published, or the poll interval or the deadline
+/// elapses. Returns whether the payment may have changed in the meantime.
+async fn wait_for_payment_status_change(
+    events: &mut broadcast::Receiver<event_bus::Event>,
+    merchant_id: &str,
+    payment_id: Option<&str>,
+    deadline: tokio::time::Instant,
+) -> bool {
+    let poll_deadline = std::cmp::min(
+        deadline,
+        tokio::time::Instant::now()
+            + std::time::Duration::from_secs(consts::PAYMENT_RETRIEVE_WAIT_POLL_INTERVAL),
+    );
+
+    loop {
+        match tokio::time::timeout_at(poll_deadline, events.recv()).await {
+            Ok(Ok(event_bus::Event::PaymentStatusChanged {
+                merchant_id: event_merchant_id,
+                payment_id: event_payment_id,
+                ..
+            })) => {
+                if event_merchant_id == merchant_id
+                    && payment_id.map_or(true, |payment_id| payment_id == event_payment_id)
+                {
+                    return true;
+                }
+            }
+            // Missed events may have included a status change of the payment
+            Ok(Err(broadcast::error::RecvError::Lagged(_))) => return true,
+            Ok(Err(broadcast::error::RecvError::Closed)) => {
+                tokio::time::sleep_until(poll_deadline).await;
+                return false;
+            }
+            Err(_) => return false,
+        }
+    }
+}
+
 fn is_start_pay<Op: Debug>(operation: &Op) -> bool {
     format!("{operation:?}").eq("PaymentStart")
 }
@@ -432,6 +842,7 @@ impl PaymentRedirectFlow for PaymentRedirectCompleteAuthorize {
                         api_models::payments::NextActionData::QrCodeInformation{..} => None,
                         api_models::payments::NextActionData::DisplayVoucherInformation{ .. } => None,
                         api_models::payments::NextActionData::WaitScreenInformation{..} => None,
+                        api_models::payments::NextActionData::SolveCaptcha{..} => None,
                     })
                     .ok_or(errors::ApiErrorResponse::InternalServerError)
                     .into_report()
@@ -478,6 +889,8 @@ impl PaymentRedirectFlow for PaymentRedirectSync {
             merchant_id: req.merchant_id,
             param: req.param,
             force_sync: req.force_sync,
+            // Customers returning from a redirection are served the latest status of the payment
+            bypass_sync_cache: Some(true),
             connector: req.connector,
             merchant_connector_details: req.creds_identifier.map(|creds_id| {
                 api::MerchantConnectorDetailsWrap {
@@ -487,6 +900,10 @@ impl PaymentRedirectFlow for PaymentRedirectSync {
             }),
             client_secret: None,
             expand_attempts: None,
+            expand_captures: None,
+            expand_refunds: None,
+            expand_disputes: None,
+            expand_customer: None,
         };
         payments_core::<api::PSync, api::PaymentsResponse, _, _, _>(
             state,
@@ -547,6 +964,32 @@ where
     dyn api::Connector:
         services::api::ConnectorIntegration<F, RouterDReq, types::PaymentsResponseData>,
 {
+    if state
+        .runtime_conf
+        .get()
+        .connector_requests
+        .connectors_under_maintenance
+        .contains(&connector.connector_name)
+    {
+        Err(errors::ApiErrorResponse::ConnectorUnderMaintenance {
+            connector: connector.connector_name.to_string(),
+        })?
+    }
+
+    currency_conversion::validate_connector_for_currency_conversion(
+        state,
+        &payment_data.payment_intent,
+        &connector,
+    )?;
+
+    if is_operation_confirm(operation) {
+        helpers::validate_capture_method_for_connector(
+            state,
+            payment_data.payment_attempt.capture_method,
+            connector.connector_name,
+        )?;
+    }
+
     let stime_connector = Instant::now();
 
     let mut router_data = payment_data
@@ -569,14 +1012,33 @@ where
         &call_connector_action,
     );
 
-    let pm_token = router_data
-        .add_payment_method_token(state, &connector, &tokenization_action)
-        .await?;
-
-    if let Some(payment_method_token) = pm_token {
-        router_data.payment_method_token = Some(payment_method_token);
+    let apple_pay_predecrypt_data = if is_operation_confirm(operation) {
+        apple_pay::decrypt_payment_token_if_configured(
+            state,
+            payment_data.payment_method_data.as_ref(),
+            router_data.connector_meta_data.as_ref(),
+        )?
+    } else {
+        None
     };
 
+    // The decrypted card details are sent to the connector instead of the Apple Pay payment token,
+    // which is not tokenized with the connector
+    if let Some(apple_pay_predecrypt_data) = apple_pay_predecrypt_data {
+        router_data.payment_method_token = Some(types::PaymentMethodToken::ApplePayDecrypt(
+            Box::new(apple_pay_predecrypt_data),
+        ));
+    } else {
+        let pm_token = router_data
+            .add_payment_method_token(state, &connector, &tokenization_action)
+            .await?;
+
+        if let Some(payment_method_token) = pm_token {
+            router_data.payment_method_token =
+                Some(types::PaymentMethodToken::Token(payment_method_token));
+        };
+    }
+
     (router_data, should_continue_further) = complete_preprocessing_steps_if_required(
         state,
         &connector,
@@ -703,13 +1165,44 @@ where
             None,
         );
 
-        join_handlers.push(res);
+        // Each connector gets its own time budget, so that a slow connector does not hold back
+        // the session tokens of the connectors which have already responded
+        let time_budget = get_session_call_time_budget(
+            &*state.store,
+            &merchant_account.merchant_id,
+            &session_connector_data.connector.connector_name.to_string(),
+        )
+        .await;
+        join_handlers
+            .push(async move { (time_budget, tokio::time::timeout(time_budget, res).await) });
     }
 
     let result = join_all(join_handlers).await;
 
-    for (connector_res, session_connector) in result.into_iter().zip(connectors) {
+    for ((time_budget, connector_res), session_connector) in result.into_iter().zip(connectors) {
         let connector_name = session_connector.connector.connector_name.to_string();
+        let connector_res = match connector_res {
+            Ok(connector_res) => connector_res,
+            Err(_) => {
+                logger::warn!(
+                    "sessions_connector_timeout {} did not respond within {} ms",
+                    connector_name,
+                    time_budget.as_millis()
+                );
+                metrics::SESSION_CALL_TIMEOUT_COUNT.add(
+                    &metrics::CONTEXT,
+                    1,
+                    &[metrics::request::add_attributes(
+                        "connector",
+                        connector_name.clone(),
+                    )],
+                );
+                payment_data
+                    .timed_out_session_connectors
+                    .push(connector_name);
+                continue;
+            }
+        };
         match connector_res {
             Ok(connector_response) => {
                 if let Ok(types::PaymentsResponseData::SessionResponse { session_token, .. }) =
@@ -743,6 +1236,47 @@ where
     Ok(payment_data)
 }
 
+/// Returns the time a connector is given to respond while creating session tokens, after which
+/// the session is returned without the token of the connector. The budget (in milliseconds) can
+/// be configured per merchant and connector using the
+/// `session_call_time_budget_{merchant_id}_{connector_name}` config, falls back to the
+/// `session_call_time_budget_{merchant_id}` config for all connectors of the merchant, and
+/// defaults to [`consts::DEFAULT_SESSION_CALL_TIME_BUDGET`].
+async fn get_session_call_time_budget(
+    db: &dyn StorageInterface,
+    merchant_id: &str,
+    connector_name: &str,
+) -> std::time::Duration {
+    let merchant_key = format!("session_call_time_budget_{merchant_id}");
+    let connector_key = format!("{merchant_key}_{connector_name}");
+
+    let time_budget = match find_session_call_time_budget(db, &connector_key).await {
+        Ok(time_budget) => time_budget,
+        Err(_) => find_session_call_time_budget(db, &merchant_key)
+            .await
+            .unwrap_or_else(|err| {
+                logger::debug!("Using default session call time budget: {:?}", err);
+                consts::DEFAULT_SESSION_CALL_TIME_BUDGET
+            }),
+    };
+
+    std::time::Duration::from_millis(time_budget)
+}
+
+async fn find_session_call_time_budget(
+    db: &dyn StorageInterface,
+    key: &str,
+) -> CustomResult<u64, errors::StorageError> {
+    db.find_config_by_key_cached(key)
+        .await
+        .map(|value| value.config)
+        .and_then(|config| {
+            config
+                .parse_struct::<u64>("SessionCallTimeBudget")
+                .change_context(errors::StorageError::DeserializationFailed)
+        })
+}
+
 pub async fn call_create_connector_customer_if_required<F, Req>(
     state: &AppState,
     customer: &Option<domain::Customer>,
@@ -1097,7 +1631,10 @@ where
     pub refunds: Vec<storage::Refund>,
     pub disputes: Vec<storage::Dispute>,
     pub attempts: Option<Vec<storage::PaymentAttempt>>,
+    pub captures: Option<Vec<storage::Capture>>,
     pub sessions_token: Vec<api::SessionToken>,
+    pub timed_out_session_connectors: Vec<String>,
+    pub deferred_session_connectors: Vec<String>,
     pub card_cvc: Option<Secret<String>>,
     pub email: Option<pii::Email>,
     pub creds_identifier: Option<String>,
@@ -1107,6 +1644,9 @@ where
     pub ephemeral_key: Option<ephemeral_key::EphemeralKey>,
     pub redirect_response: Option<api_models::payments::RedirectResponse>,
     pub frm_message: Option<FrmMessage>,
+    pub captcha_site_key: Option<String>,
+    pub payment_link_config: Option<api_models::admin::PaymentLinkConfig>,
+    pub expand_customer: bool,
 }
 
 #[derive(Clone)]
@@ -1116,6 +1656,13 @@ pub struct MultipleCaptureData {
 }
 
 impl MultipleCaptureData {
+    /// Returns all the captures of the payment, ordered by their sequence
+    fn get_all_captures(&self) -> Vec<storage::Capture> {
+        let mut captures = self.previous_captures.clone();
+        captures.push(self.current_capture.clone());
+        captures.sort_by_key(|capture| capture.capture_sequence);
+        captures
+    }
     fn get_previously_blocked_amount(&self) -> i64 {
         self.previous_captures
             .iter()
@@ -1298,6 +1845,30 @@ pub fn is_operation_confirm<Op: Debug>(operation: &Op) -> bool {
     matches!(format!("{operation:?}").as_str(), "PaymentConfirm")
 }
 
+pub fn is_operation_status<Op: Debug>(operation: &Op) -> bool {
+    matches!(format!("{operation:?}").as_str(), "PaymentStatus")
+}
+
+/// Releases the sync cache key claimed for a payment whose sync with the connector failed, so that
+/// the failure is not served to the syncs of the payment within the sync cache window.
+async fn release_payment_sync_claim<F: Clone>(state: &AppState, payment_data: &PaymentData<F>) {
+    if let (Some(connector), Some(connector_transaction_id)) = (
+        payment_data.payment_attempt.connector.as_ref(),
+        payment_data
+            .payment_attempt
+            .connector_transaction_id
+            .as_ref(),
+    ) {
+        sync_cache::release_sync_claim(
+            state,
+            sync_cache::SyncFlow::PaymentSync,
+            connector,
+            connector_transaction_id,
+        )
+        .await;
+    }
+}
+
 #[cfg(feature = "olap")]
 pub async fn list_payments(
     db: &dyn StorageInterface,
@@ -1361,6 +1932,7 @@ pub async fn list_payments(
         api::PaymentListResponse {
             size: data.len(),
             data,
+            next_cursor: None,
         },
     ))
 }
@@ -1368,19 +1940,43 @@ pub async fn list_payments(
 pub async fn apply_filters_on_payments(
     db: &dyn StorageInterface,
     merchant: domain::MerchantAccount,
+    key_store: domain::MerchantKeyStore,
     constraints: api::PaymentListFilterConstraints,
 ) -> RouterResponse<api::PaymentListResponse> {
     use crate::types::transformers::ForeignFrom;
 
+    helpers::validate_payment_list_filter_request(&constraints)?;
+    let customer_email_hash = constraints
+        .customer_email
+        .as_ref()
+        .map(|customer_email| {
+            domain::get_email_hash(key_store.key.get_inner().peek(), customer_email.peek())
+        })
+        .transpose()
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed while hashing the email of the customer")?;
     let list: Vec<(storage::PaymentIntent, storage::PaymentAttempt)> = db
         .apply_filters_on_payments_list(
             &merchant.merchant_id,
             &constraints,
+            customer_email_hash.as_deref(),
             merchant.storage_scheme,
         )
         .await
         .to_not_found_response(errors::ApiErrorResponse::PaymentNotFound)?;
 
+    // A full page may be followed by more payments, which are fetched starting after its last
+    // payment
+    let limit = constraints
+        .limit
+        .unwrap_or(consts::PAYMENTS_LIST_DEFAULT_LIMIT);
+    let is_page_full = constraints.payment_id.is_none()
+        && list.len() >= usize::try_from(limit).unwrap_or(usize::MAX);
+    let next_cursor = list
+        .last()
+        .filter(|_| is_page_full)
+        .map(|(payment_intent, _)| payment_intent.payment_id.clone());
+
     let data: Vec<api::PaymentsResponse> =
         list.into_iter().map(ForeignFrom::foreign_from).collect();
 
@@ -1388,6 +1984,7 @@ pub async fn apply_filters_on_payments(
         api::PaymentListResponse {
             size: data.len(),
             data,
+            next_cursor,
         },
     ))
 }
@@ -1431,6 +2028,7 @@ pub async fn add_process_sync_task(
 ) -> Result<(), errors::ProcessTrackerError> {
     let tracking_data = api::PaymentsRetrieveRequest {
         force_sync: true,
+        bypass_sync_cache: Some(true),
         merchant_id: Some(payment_attempt.merchant_id.clone()),
         resource_id: api::PaymentIdType::PaymentAttemptId(payment_attempt.attempt_id.clone()),
         ..Default::default()
@@ -1476,6 +2074,36 @@ pub async fn reset_process_sync_task(
     Ok(())
 }
 
+pub async fn add_delayed_capture_task(
+    db: &dyn StorageInterface,
+    payment_attempt: &storage::PaymentAttempt,
+    schedule_time: time::PrimitiveDateTime,
+) -> Result<(), errors::ProcessTrackerError> {
+    let tracking_data = api::PaymentsCaptureRequest {
+        payment_id: Some(payment_attempt.payment_id.clone()),
+        merchant_id: Some(payment_attempt.merchant_id.clone()),
+        ..Default::default()
+    };
+    let runner = "DELAYED_CAPTURE_WORKFLOW";
+    let task = "DELAYED_CAPTURE";
+    let process_tracker_id = pt_utils::get_process_tracker_id(
+        runner,
+        task,
+        &payment_attempt.attempt_id,
+        &payment_attempt.merchant_id,
+    );
+    let process_tracker_entry = <storage::ProcessTracker>::make_process_tracker_new(
+        process_tracker_id,
+        task,
+        runner,
+        tracking_data,
+        schedule_time,
+    )?;
+
+    db.insert_process(process_tracker_entry).await?;
+    Ok(())
+}
+
 pub fn update_straight_through_routing<F>(
     payment_data: &mut PaymentData<F>,
     request_straight_through: serde_json::Value,
@@ -1517,19 +2145,27 @@ where
 
     let connector = if should_call_connector(operation, payment_data) {
         Some(match connector_choice {
-            api::ConnectorChoice::SessionMultiple(session_connectors) => {
-                api::ConnectorCallType::Multiple(session_connectors)
+            api::ConnectorChoice::SessionMultiple {
+                connectors,
+                deferred_connectors,
+            } => {
+                payment_data.deferred_session_connectors = deferred_connectors;
+                api::ConnectorCallType::Multiple(connectors)
             }
 
-            api::ConnectorChoice::StraightThrough(straight_through) => connector_selection(
-                state,
-                merchant_account,
-                payment_data,
-                Some(straight_through),
-            )?,
+            api::ConnectorChoice::StraightThrough(straight_through) => {
+                connector_selection(
+                    state,
+                    merchant_account,
+                    key_store,
+                    payment_data,
+                    Some(straight_through),
+                )
+                .await?
+            }
 
             api::ConnectorChoice::Decide => {
-                connector_selection(state, merchant_account, payment_data, None)?
+                connector_selection(state, merchant_account, key_store, payment_data, None).await?
             }
         })
     } else if let api::ConnectorChoice::StraightThrough(val) = connector_choice {
@@ -1544,9 +2180,10 @@ where
     Ok(connector)
 }
 
-pub fn connector_selection<F>(
+pub async fn connector_selection<F>(
     state: &AppState,
     merchant_account: &domain::MerchantAccount,
+    key_store: &domain::MerchantKeyStore,
     payment_data: &mut PaymentData<F>,
     request_straight_through: Option<serde_json::Value>,
 ) -> RouterResult<api::ConnectorCallType>
@@ -1583,12 +2220,41 @@ where
         .change_context(errors::ApiErrorResponse::InternalServerError)
         .attach_printable("Invalid straight through routing rules format")?;
 
+    let mut ineligible_connectors =
+        connector_exclusion::get_excluded_connectors(&*state.store, merchant_account, payment_data)
+            .await?;
+    if let Ok(routing_algorithm) = routing::get_merchant_routing_algorithm(merchant_account) {
+        let connectors = routing_algorithm.get_connectors();
+        ineligible_connectors.extend(helpers::get_connectors_outside_amount_limits(
+            &state.conf.pm_filters,
+            connectors.clone(),
+            payment_data.payment_attempt.payment_method_type,
+            payment_data.payment_attempt.amount,
+        ));
+        ineligible_connectors.extend(
+            helpers::get_connectors_with_disabled_accounts(
+                state,
+                merchant_account,
+                key_store,
+                connectors,
+                payment_data,
+            )
+            .await?,
+        );
+    }
+
+    let payment_cost_details =
+        routing::PaymentCostDetails::from_payment_data(&*state.store, payment_data).await;
     let decided_connector = decide_connector(
         state,
         merchant_account,
         request_straight_through,
         &mut routing_data,
-    )?;
+        get_routing_key(&payment_data.payment_intent),
+        &payment_cost_details,
+        &ineligible_connectors,
+    )
+    .await?;
 
     let encoded_algorithm = routing_data
         .algorithm
@@ -1603,11 +2269,14 @@ where
     Ok(decided_connector)
 }
 
-pub fn decide_connector(
+pub async fn decide_connector(
     state: &AppState,
     merchant_account: &domain::MerchantAccount,
     request_straight_through: Option<api::StraightThroughAlgorithm>,
     routing_data: &mut storage::RoutingData,
+    routing_key: &str,
+    payment_cost_details: &routing::PaymentCostDetails,
+    ineligible_connectors: &[api_models::enums::RoutableConnectors],
 ) -> RouterResult<api::ConnectorCallType> {
     if let Some(ref connector_name) = routing_data.routed_through {
         let connector_data = api::ConnectorData::get_connector_by_name(
@@ -1656,19 +2325,32 @@ pub fn decide_connector(
         return Ok(api::ConnectorCallType::Single(connector_data));
     }
 
-    let routing_algorithm = merchant_account
-        .routing_algorithm
-        .clone()
-        .get_required_value("RoutingAlgorithm")
-        .change_context(errors::ApiErrorResponse::PreconditionFailed {
-            message: "no routing algorithm has been configured".to_string(),
-        })?
-        .parse_value::<api::RoutingAlgorithm>("RoutingAlgorithm")
-        .change_context(errors::ApiErrorResponse::InternalServerError) // Deserialization failed
-        .attach_printable("Unable to deserialize merchant routing algorithm")?;
+    let configured_routing_algorithm = routing::get_merchant_routing_algorithm(merchant_account)?;
+    let has_configured_connectors = !configured_routing_algorithm.get_connectors().is_empty();
+    let routing_algorithm =
+        routing::remove_ineligible_connectors(configured_routing_algorithm, ineligible_connectors);
 
-    let connector_name = match routing_algorithm {
-        api::RoutingAlgorithm::Single(conn) => conn.to_string(),
+    let routed_connector = match &routing_algorithm {
+        api::RoutingAlgorithm::CostBased(connectors) => {
+            routing::get_cheapest_connector(
+                state,
+                &merchant_account.merchant_id,
+                connectors,
+                payment_cost_details,
+            )
+            .await?
+        }
+        _ => get_routed_connector(&routing_algorithm, routing_key),
+    };
+    let connector_name = match routed_connector {
+        Some(connector) => connector.to_string(),
+        None if has_configured_connectors => Err(errors::ApiErrorResponse::NotSupported {
+            message: "None of the connectors of the routing algorithm can process the payment"
+                .to_string(),
+        })?,
+        None => Err(errors::ApiErrorResponse::PreconditionFailed {
+            message: "no connectors have been configured in the routing algorithm".to_string(),
+        })?,
     };
 
     let connector_data = api::ConnectorData::get_connector_by_name(
@@ -1684,6 +2366,56 @@ pub fn decide_connector(
     Ok(api::ConnectorCallType::Single(connector_data))
 }
 
+/// Returns the key by which the payment is assigned a connector of a volume split, the customer
+/// of the payment so that the payments of a customer are always routed to the same connector
+pub fn get_routing_key(payment_intent: &storage::PaymentIntent) -> &str {
+    payment_intent
+        .customer_id
+        .as_deref()
+        .unwrap_or(&payment_intent.payment_id)
+}
+
+/// Returns the connector the payment is routed to by the routing algorithm of the merchant
+pub fn get_routed_connector(
+    routing_algorithm: &api::RoutingAlgorithm,
+    routing_key: &str,
+) -> Option<api_models::enums::RoutableConnectors> {
+    match routing_algorithm {
+        // The cost of the payment with each connector is only known once its payment method is,
+        // the connectors of cost based routing being listed in the order they are preferred in
+        api::RoutingAlgorithm::Single(_)
+        | api::RoutingAlgorithm::Priority(_)
+        | api::RoutingAlgorithm::CostBased(_) => {
+            routing_algorithm.get_connectors().first().copied()
+        }
+        api::RoutingAlgorithm::VolumeSplit(splits) => {
+            let total_split = splits
+                .iter()
+                .map(|split| u128::from(split.split))
+                .sum::<u128>();
+            if total_split == 0 {
+                return None;
+            }
+
+            // A stable hash of the routing key, so that the key is assigned the same connector
+            // across requests and instances of the application
+            let hash = blake3::hash(routing_key.as_bytes());
+            let mut hash_prefix = [0; 8];
+            hash_prefix.copy_from_slice(&hash.as_bytes()[..8]);
+            let position = u128::from(u64::from_be_bytes(hash_prefix));
+
+            // The hash values are divided into consecutive ranges, one per connector, in
+            // proportion to the weights of the connectors. Changing a weight only moves the
+            // boundaries of the ranges, reassigning the keys between the old and new boundaries
+            let mut range_end = 0;
+            splits.iter().find_map(|split| {
+                range_end += u128::from(split.split);
+                (position * total_split < range_end << 64).then_some(split.connector)
+            })
+        }
+    }
+}
+
 pub fn should_add_task_to_process_tracker<F: Clone>(payment_data: &PaymentData<F>) -> bool {
     let connector = payment_data.payment_attempt.connector.as_deref();
 
@@ -1695,3 +2427,125 @@ pub fn should_add_task_to_process_tracker<F: Clone>(payment_data: &PaymentData<F
         )
     )
 }
+
+#[cfg(test)]
+mod tests {
+    use api_models::{admin::ConnectorVolumeSplit, enums::RoutableConnectors};
+
+    use super::*;
+
+    fn get_volume_split(splits: &[(RoutableConnectors, u8)]) -> api::RoutingAlgorithm {
+        api::RoutingAlgorithm::VolumeSplit(
+            splits
+                .iter()
+                .map(|(connector, split)| ConnectorVolumeSplit {
+                    connector: *connector,
+                    split: *split,
+                })
+                .collect(),
+        )
+    }
+
+    fn get_routing_keys() -> impl Iterator<Item = String> {
+        (0..10_000).map(|index| format!("cus_{index}"))
+    }
+
+    #[test]
+    fn test_routed_connector_is_the_first_connector() {
+        assert_eq!(
+            get_routed_connector(
+                &api::RoutingAlgorithm::Single(RoutableConnectors::Adyen),
+                "cus_1"
+            ),
+            Some(RoutableConnectors::Adyen)
+        );
+        assert_eq!(
+            get_routed_connector(
+                &api::RoutingAlgorithm::Priority(vec![
+                    RoutableConnectors::Stripe,
+                    RoutableConnectors::Adyen,
+                ]),
+                "cus_1"
+            ),
+            Some(RoutableConnectors::Stripe)
+        );
+        assert_eq!(
+            get_routed_connector(
+                &api::RoutingAlgorithm::CostBased(vec![
+                    RoutableConnectors::Adyen,
+                    RoutableConnectors::Stripe,
+                ]),
+                "cus_1"
+            ),
+            Some(RoutableConnectors::Adyen)
+        );
+        assert_eq!(
+            get_routed_connector(&api::RoutingAlgorithm::Priority(Vec::new()), "cus_1"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_volume_split_without_weights_routes_nowhere() {
+        assert_eq!(get_routed_connector(&get_volume_split(&[]), "cus_1"), None);
+        assert_eq!(
+            get_routed_connector(
+                &get_volume_split(&[(RoutableConnectors::Stripe, 0)]),
+                "cus_1"
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn test_volume_split_is_sticky_and_follows_the_weights() {
+        let routing_algorithm = get_volume_split(&[
+            (RoutableConnectors::Stripe, 70),
+            (RoutableConnectors::Checkout, 0),
+            (RoutableConnectors::Adyen, 30),
+        ]);
+
+        let mut stripe_count = 0;
+        for routing_key in get_routing_keys() {
+            let connector = get_routed_connector(&routing_algorithm, &routing_key);
+            assert_eq!(
+                connector,
+                get_routed_connector(&routing_algorithm, &routing_key)
+            );
+            // The connector without weight is never routed to
+            assert!(matches!(
+                connector,
+                Some(RoutableConnectors::Stripe | RoutableConnectors::Adyen)
+            ));
+            if connector == Some(RoutableConnectors::Stripe) {
+                stripe_count += 1;
+            }
+        }
+        assert!((6_800..7_200).contains(&stripe_count));
+    }
+
+    #[test]
+    fn test_volume_split_weight_change_only_reassigns_the_difference() {
+        let previous_routing_algorithm = get_volume_split(&[
+            (RoutableConnectors::Stripe, 70),
+            (RoutableConnectors::Adyen, 30),
+        ]);
+        let routing_algorithm = get_volume_split(&[
+            (RoutableConnectors::Stripe, 60),
+            (RoutableConnectors::Adyen, 40),
+        ]);
+
+        let mut reassigned_count = 0;
+        for routing_key in get_routing_keys() {
+            let previous_connector =
+                get_routed_connector(&previous_routing_algorithm, &routing_key);
+            let connector = get_routed_connector(&routing_algorithm, &routing_key);
+            if previous_connector != connector {
+                // Only the keys of the connector whose weight was lowered are reassigned
+                assert_eq!(previous_connector, Some(RoutableConnectors::Stripe));
+                reassigned_count += 1;
+            }
+        }
+        assert!((800..1_200).contains(&reassigned_count));
+    }
+}
@@ -63,9 +63,15 @@ pub async fn add_access_token<
         .supports_access_token(router_data.payment_method)
     {
         let merchant_id = &merchant_account.merchant_id;
+        // The access tokens obtained with the test and live account details of a connector
+        // account are stored separately
+        let access_token_key = match router_data.test_mode {
+            Some(true) => format!("{}_test", connector.connector.id()),
+            _ => connector.connector.id().to_string(),
+        };
         let store = &*state.store;
         let old_access_token = store
-            .get_access_token(merchant_id, connector.connector.id())
+            .get_access_token(merchant_id, &access_token_key)
             .await
             .change_context(errors::ApiErrorResponse::InternalServerError)
             .attach_printable("DB error when accessing the access token")?;
@@ -109,11 +115,7 @@ pub async fn add_access_token<
                     // This error should not be propagated, we don't want payments to fail once we have
                     // the access token, the next request will create new access token
                     let _ = store
-                        .set_access_token(
-                            merchant_id,
-                            connector.connector.id(),
-                            access_token.clone(),
-                        )
+                        .set_access_token(merchant_id, &access_token_key, access_token.clone())
                         .await
                         .change_context(errors::ApiErrorResponse::InternalServerError)
                         .attach_printable("DB error when setting the access token");
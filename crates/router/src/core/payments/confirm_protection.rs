@@ -0,0 +1,214 @@
+//! Protection against card testing through payment confirmations made from the client.
+//!
+//! Confirm attempts made with the client secret of a payment are counted per payment and per
+//! card. Once either count crosses its CAPTCHA threshold, the confirmation is held back until the
+//! customer solves a CAPTCHA, and once it crosses the maximum number of attempts, further
+//! confirmations are refused until the counting window elapses.
+
+use common_utils::{
+    crypto::{HmacSha256, SignMessage},
+    ext_traits::ByteSliceExt,
+};
+use error_stack::{IntoReport, ResultExt};
+use masking::{PeekInterface, Secret};
+use router_env::{instrument, logger, tracing};
+
+use super::PaymentData;
+use crate::{
+    configs::settings::ConfirmProtection,
+    core::errors::{self, RouterResult},
+    routes::{metrics, AppState},
+    services,
+    types::{self, api, domain},
+    utils::Encode,
+};
+
+const CAPTCHA_VERIFICATION_TIMEOUT_SECS: u64 = 10;
+
+/// The context from which the key of the card fingerprints is derived from the master key
+const CARD_FINGERPRINT_KEY_CONTEXT: &[u8] = b"card_fingerprint";
+
+#[derive(Debug, serde::Serialize)]
+struct CaptchaVerificationRequest {
+    secret: Secret<String>,
+    response: Secret<String>,
+}
+
+#[derive(Debug, serde::Deserialize)]
+struct CaptchaVerificationResponse {
+    success: bool,
+}
+
+/// Increments the confirm attempt counter stored at `key`. Counting fails open, so that an
+/// unavailable Redis does not block payments.
+async fn count_attempt(state: &AppState, key: &str, window: i64) -> Option<i64> {
+    let redis_conn = state
+        .store
+        .get_redis_conn()
+        .map_err(|error| logger::error!(redis_connection_error=?error))
+        .ok()?;
+
+    redis_conn
+        .increment_key_with_expiry(key, window)
+        .await
+        .map_err(|error| logger::error!(confirm_attempt_count_error=?error))
+        .ok()
+}
+
+/// The fingerprint of the card of the payment, keyed with a key derived from the master key so that
+/// the card numbers cannot be recovered from the fingerprints by enumerating them.
+pub(super) fn get_keyed_card_fingerprint(
+    master_key: &[u8],
+    payment_method_data: Option<&api::PaymentMethodData>,
+) -> Option<String> {
+    match payment_method_data? {
+        api::PaymentMethodData::Card(card) => HmacSha256
+            .sign_message(master_key, CARD_FINGERPRINT_KEY_CONTEXT)
+            .and_then(|fingerprint_key| {
+                HmacSha256.sign_message(&fingerprint_key, card.card_number.peek().as_bytes())
+            })
+            .map(hex::encode)
+            .map_err(|error| logger::error!(card_fingerprint_error=?error))
+            .ok(),
+        _ => None,
+    }
+}
+
+#[instrument(skip_all)]
+async fn verify_captcha_token(
+    state: &AppState,
+    config: &ConfirmProtection,
+    captcha_token: &Secret<String>,
+) -> RouterResult<bool> {
+    let request_body = types::RequestBody::log_and_get_request_body(
+        &CaptchaVerificationRequest {
+            secret: config.captcha.secret_key.clone(),
+            response: captcha_token.clone(),
+        },
+        Encode::<CaptchaVerificationRequest>::encode_to_string_of_json,
+    )
+    .change_context(errors::ApiErrorResponse::InternalServerError)
+    .attach_printable("Failed to encode the CAPTCHA verification request")?;
+
+    let request = services::RequestBuilder::new()
+        .method(services::Method::Post)
+        .url(&config.captcha.verify_url)
+        .attach_default_headers()
+        .content_type(services::request::ContentType::FormUrlEncoded)
+        .body(Some(request_body))
+        .build();
+
+    let response =
+        services::api::send_request(state, request, Some(CAPTCHA_VERIFICATION_TIMEOUT_SECS))
+            .await
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Failed to call the CAPTCHA verification endpoint")?
+            .bytes()
+            .await
+            .into_report()
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Failed to read the CAPTCHA verification response")?;
+
+    let verification: CaptchaVerificationResponse = response
+        .parse_struct("CaptchaVerificationResponse")
+        .change_context(errors::ApiErrorResponse::InternalServerError)?;
+
+    Ok(verification.success)
+}
+
+/// Counts a confirm attempt made from the client and decides whether it may proceed.
+///
+/// Returns an error once the maximum number of attempts is exceeded. When a CAPTCHA has to be
+/// solved first, the site key of the CAPTCHA is set on the payment data, and the caller is
+/// expected to return without confirming the payment.
+#[instrument(skip_all)]
+pub async fn check_confirm_attempts<F: Clone>(
+    state: &AppState,
+    merchant_account: &domain::MerchantAccount,
+    payment_data: &mut PaymentData<F>,
+    captcha_token: Option<&Secret<String>>,
+) -> RouterResult<()> {
+    let runtime_settings = state.runtime_conf.get();
+    let config = &runtime_settings.confirm_protection;
+    let merchant_id = &merchant_account.merchant_id;
+
+    let client_secret_attempts = count_attempt(
+        state,
+        &format!(
+            "confirm_attempts_{}_{}",
+            merchant_id, payment_data.payment_intent.payment_id
+        ),
+        config.window,
+    )
+    .await
+    .unwrap_or_default();
+
+    let card_fingerprint = get_keyed_card_fingerprint(
+        state.store.get_master_key(),
+        payment_data.payment_method_data.as_ref(),
+    );
+    let card_attempts = match card_fingerprint {
+        Some(fingerprint) => count_attempt(
+            state,
+            &format!("confirm_attempts_card_{merchant_id}_{fingerprint}"),
+            config.window,
+        )
+        .await
+        .unwrap_or_default(),
+        None => 0,
+    };
+
+    if client_secret_attempts > config.client_secret_max_attempts
+        || card_attempts > config.card_max_attempts
+    {
+        logger::warn!(
+            fraud_signal = "confirm_attempts_exceeded",
+            %merchant_id,
+            payment_id = %payment_data.payment_intent.payment_id,
+            client_secret_attempts,
+            card_attempts
+        );
+        metrics::CONFIRM_ATTEMPTS_EXCEEDED.add(
+            &metrics::CONTEXT,
+            1,
+            &[metrics::request::add_attributes(
+                "merchant_id",
+                merchant_id.to_owned(),
+            )],
+        );
+        return Err(errors::ApiErrorResponse::ConfirmAttemptsExceeded.into());
+    }
+
+    if client_secret_attempts <= config.client_secret_captcha_threshold
+        && card_attempts <= config.card_captcha_threshold
+    {
+        return Ok(());
+    }
+
+    let is_captcha_solved = match captcha_token {
+        Some(captcha_token) => verify_captcha_token(state, config, captcha_token).await?,
+        None => false,
+    };
+
+    if !is_captcha_solved {
+        logger::warn!(
+            fraud_signal = "captcha_required",
+            %merchant_id,
+            payment_id = %payment_data.payment_intent.payment_id,
+            client_secret_attempts,
+            card_attempts,
+            captcha_token_provided = captcha_token.is_some()
+        );
+        metrics::CAPTCHA_CHALLENGE_ISSUED.add(
+            &metrics::CONTEXT,
+            1,
+            &[metrics::request::add_attributes(
+                "merchant_id",
+                merchant_id.to_owned(),
+            )],
+        );
+        payment_data.captcha_site_key = Some(config.captcha.site_key.clone());
+    }
+
+    Ok(())
+}
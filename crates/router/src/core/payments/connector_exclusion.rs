@@ -0,0 +1,342 @@
+//! BIN based exclusion of connectors for card payments.
+//!
+//! Merchants can configure rules which keep cards with specific BINs, or cards issued in specific
+//! countries, from being routed to a connector. The rules are evaluated when the payment is
+//! routed, using the BIN and the issuing country of the card, the latter being looked up from the
+//! card BIN data when the customer has not passed it. The connectors from which the card is
+//! excluded are removed from the connectors of the routing algorithm of the merchant, so that the
+//! payment is routed to one of the remaining connectors. Payments explicitly routed to an excluded
+//! connector are rejected.
+
+use std::str::FromStr;
+
+use api_models::{enums as api_enums, payments::AdditionalPaymentData};
+use error_stack::{report, ResultExt};
+use router_env::logger;
+use strum::IntoEnumIterator;
+
+use super::PaymentData;
+use crate::{
+    core::errors::{self, RouterResult},
+    db::StorageInterface,
+    types::{api, domain},
+    utils::{self, ValueExt},
+};
+
+const CARD_BIN_LENGTH: usize = 6;
+
+#[derive(Debug)]
+struct CardDetails {
+    card_bin: Option<String>,
+    card_issuing_country: Option<api_enums::CountryAlpha2>,
+}
+
+pub fn get_connector_exclusion_rules(
+    merchant_account: &domain::MerchantAccount,
+) -> RouterResult<Vec<api::ConnectorExclusionRule>> {
+    merchant_account
+        .connector_exclusion_rules
+        .clone()
+        .map(|rules| {
+            rules.parse_value::<Vec<api::ConnectorExclusionRule>>("ConnectorExclusionRules")
+        })
+        .transpose()
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to parse connector exclusion rules of the merchant account")
+        .map(Option::unwrap_or_default)
+}
+
+/// Validates that the BIN ranges of the rules consist of six digit BINs, with the start of each
+/// range not after its end.
+pub fn validate_connector_exclusion_rules(
+    rules: &[api::ConnectorExclusionRule],
+) -> RouterResult<()> {
+    let bin_ranges = rules
+        .iter()
+        .flat_map(|rule| rule.excluded_bin_ranges.iter().flatten());
+
+    for bin_range in bin_ranges {
+        for bin in [&bin_range.start, &bin_range.end] {
+            utils::when(
+                bin.len() != CARD_BIN_LENGTH || !bin.chars().all(|c| c.is_ascii_digit()),
+                || {
+                    Err(report!(errors::ApiErrorResponse::InvalidRequestData {
+                        message: format!(
+                            "`{bin}` is not a valid card BIN, BINs must be {CARD_BIN_LENGTH} \
+                             digits long"
+                        ),
+                    }))
+                },
+            )?;
+        }
+
+        utils::when(bin_range.start > bin_range.end, || {
+            Err(report!(errors::ApiErrorResponse::InvalidRequestData {
+                message: format!(
+                    "The start of the BIN range `{}` to `{}` is after its end",
+                    bin_range.start, bin_range.end
+                ),
+            }))
+        })?;
+    }
+
+    Ok(())
+}
+
+/// Converts the issuing country of a card, which could either be a country code or the name of
+/// the country, to its country code.
+fn parse_card_issuing_country(card_issuing_country: &str) -> Option<api_enums::CountryAlpha2> {
+    let normalized_country = card_issuing_country
+        .chars()
+        .filter(char::is_ascii_alphanumeric)
+        .collect::<String>()
+        .to_ascii_uppercase();
+
+    api_enums::CountryAlpha2::from_str(&normalized_country)
+        .ok()
+        .or_else(|| {
+            api_enums::Country::iter()
+                .find(|country| country.to_string().to_ascii_uppercase() == normalized_country)
+                .map(|country| country.to_alpha2())
+        })
+}
+
+async fn get_card_details<F: Clone>(
+    db: &dyn StorageInterface,
+    payment_data: &PaymentData<F>,
+) -> Option<CardDetails> {
+    let (card_bin, card_issuing_country) = match &payment_data.payment_method_data {
+        Some(api::PaymentMethodData::Card(card)) => (
+            Some(card.card_number.clone().get_card_isin()),
+            card.card_issuing_country.clone(),
+        ),
+        Some(_) => return None,
+        None => match payment_data
+            .payment_attempt
+            .payment_method_data
+            .clone()
+            .map(|value| value.parse_value::<AdditionalPaymentData>("AdditionalPaymentData"))
+            .transpose()
+            .map_err(|error| logger::warn!(additional_payment_data_parsing_error=?error))
+            .ok()
+            .flatten()?
+        {
+            AdditionalPaymentData::Card(card_info) => {
+                (card_info.card_isin, card_info.card_issuing_country)
+            }
+            _ => return None,
+        },
+    };
+
+    let card_issuing_country = match (card_issuing_country, &card_bin) {
+        (Some(card_issuing_country), _) => Some(card_issuing_country),
+        (None, Some(card_bin)) => db
+            .get_card_info(card_bin)
+            .await
+            .map_err(|error| logger::warn!(card_info_error=?error))
+            .ok()
+            .flatten()
+            .and_then(|card_info| card_info.card_issuing_country),
+        (None, None) => None,
+    };
+
+    Some(CardDetails {
+        card_bin,
+        card_issuing_country: card_issuing_country
+            .as_deref()
+            .and_then(parse_card_issuing_country),
+    })
+}
+
+/// Returns the reason for which the rule excludes the card, if it does. Cards whose issuing
+/// country is unknown are only excluded by their BIN.
+fn get_exclusion_reason(
+    rule: &api::ConnectorExclusionRule,
+    card_details: &CardDetails,
+) -> Option<String> {
+    if let Some(card_bin) = &card_details.card_bin {
+        let is_bin_excluded = rule.excluded_bin_ranges.iter().flatten().any(|bin_range| {
+            bin_range.start.as_str() <= card_bin.as_str()
+                && card_bin.as_str() <= bin_range.end.as_str()
+        });
+
+        if is_bin_excluded {
+            return Some(format!("Cards with the BIN {card_bin}"));
+        }
+    }
+
+    let card_issuing_country = card_details.card_issuing_country?;
+
+    let is_country_excluded = rule
+        .excluded_issuer_countries
+        .as_ref()
+        .map_or(false, |countries| countries.contains(&card_issuing_country))
+        || rule
+            .allowed_issuer_countries
+            .as_ref()
+            .map_or(false, |countries| {
+                !countries.contains(&card_issuing_country)
+            });
+
+    is_country_excluded.then(|| format!("Cards issued in {card_issuing_country}"))
+}
+
+/// Returns the connectors from which the card is excluded by any of the rules.
+fn get_excluded_connectors_for_card(
+    rules: &[api::ConnectorExclusionRule],
+    card_details: &CardDetails,
+) -> Vec<api_enums::RoutableConnectors> {
+    let mut excluded_connectors = Vec::new();
+    for rule in rules {
+        if !excluded_connectors.contains(&rule.connector)
+            && get_exclusion_reason(rule, card_details).is_some()
+        {
+            excluded_connectors.push(rule.connector);
+        }
+    }
+    excluded_connectors
+}
+
+/// Returns the connectors from which the card used for the payment is excluded by the connector
+/// exclusion rules of the merchant, which the payment must not be routed to.
+pub async fn get_excluded_connectors<F: Clone>(
+    db: &dyn StorageInterface,
+    merchant_account: &domain::MerchantAccount,
+    payment_data: &PaymentData<F>,
+) -> RouterResult<Vec<api_enums::RoutableConnectors>> {
+    let rules = get_connector_exclusion_rules(merchant_account)?;
+    if rules.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let excluded_connectors = match get_card_details(db, payment_data).await {
+        Some(card_details) => get_excluded_connectors_for_card(&rules, &card_details),
+        None => Vec::new(),
+    };
+    if !excluded_connectors.is_empty() {
+        logger::info!(
+            ?excluded_connectors,
+            "Card excluded from connectors by the connector exclusion rules of the merchant"
+        );
+    }
+    Ok(excluded_connectors)
+}
+
+/// Validates that the card used for the payment is not excluded from the connector the payment
+/// is routed through by the connector exclusion rules of the merchant, for the payments which are
+/// explicitly routed to a connector.
+pub async fn validate_connector_not_excluded<F: Clone>(
+    db: &dyn StorageInterface,
+    merchant_account: &domain::MerchantAccount,
+    connector_name: &str,
+    payment_data: &PaymentData<F>,
+) -> RouterResult<()> {
+    let rules = get_connector_exclusion_rules(merchant_account)?
+        .into_iter()
+        .filter(|rule| rule.connector.to_string() == connector_name)
+        .collect::<Vec<_>>();
+
+    if rules.is_empty() {
+        return Ok(());
+    }
+
+    let Some(card_details) = get_card_details(db, payment_data).await else {
+        return Ok(());
+    };
+
+    match rules
+        .iter()
+        .find_map(|rule| get_exclusion_reason(rule, &card_details))
+    {
+        Some(reason) => {
+            logger::info!(
+                connector = connector_name,
+                "Card excluded from connector by the connector exclusion rules of the merchant"
+            );
+            Err(report!(errors::ApiErrorResponse::NotSupported {
+                message: format!("{reason} are not supported by the {connector_name} connector"),
+            }))
+        }
+        None => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_rule(
+        connector: api_enums::RoutableConnectors,
+        excluded_bin_ranges: Option<Vec<(&str, &str)>>,
+        allowed_issuer_countries: Option<Vec<api_enums::CountryAlpha2>>,
+    ) -> api::ConnectorExclusionRule {
+        api::ConnectorExclusionRule {
+            connector,
+            excluded_bin_ranges: excluded_bin_ranges.map(|bin_ranges| {
+                bin_ranges
+                    .into_iter()
+                    .map(|(start, end)| api_models::admin::CardBinRange {
+                        start: start.to_string(),
+                        end: end.to_string(),
+                    })
+                    .collect()
+            }),
+            excluded_issuer_countries: None,
+            allowed_issuer_countries,
+        }
+    }
+
+    #[test]
+    fn test_get_excluded_connectors_for_card() {
+        let rules = vec![
+            get_rule(
+                api_enums::RoutableConnectors::Stripe,
+                Some(vec![("400000", "409999")]),
+                None,
+            ),
+            get_rule(
+                api_enums::RoutableConnectors::Adyen,
+                None,
+                Some(vec![api_enums::CountryAlpha2::IN]),
+            ),
+        ];
+
+        let card_details = CardDetails {
+            card_bin: Some("424242".to_string()),
+            card_issuing_country: Some(api_enums::CountryAlpha2::IN),
+        };
+        assert!(get_excluded_connectors_for_card(&rules, &card_details).is_empty());
+
+        let card_details = CardDetails {
+            card_bin: Some("400012".to_string()),
+            card_issuing_country: Some(api_enums::CountryAlpha2::US),
+        };
+        assert_eq!(
+            get_excluded_connectors_for_card(&rules, &card_details),
+            vec![
+                api_enums::RoutableConnectors::Stripe,
+                api_enums::RoutableConnectors::Adyen
+            ]
+        );
+
+        // Cards whose issuing country is unknown are only excluded by their BIN
+        let card_details = CardDetails {
+            card_bin: Some("510510".to_string()),
+            card_issuing_country: None,
+        };
+        assert!(get_excluded_connectors_for_card(&rules, &card_details).is_empty());
+    }
+
+    #[test]
+    fn test_parse_card_issuing_country() {
+        assert_eq!(
+            parse_card_issuing_country("us"),
+            Some(api_enums::CountryAlpha2::US)
+        );
+        assert_eq!(
+            parse_card_issuing_country("UNITED KINGDOM"),
+            parse_card_issuing_country("United Kingdom")
+        );
+        assert_eq!(parse_card_issuing_country("Atlantis"), None);
+    }
+}
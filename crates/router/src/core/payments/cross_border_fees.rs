@@ -0,0 +1,355 @@
+//! Disclosure of the fees of cross-border payments.
+//!
+//! A payment is cross-border when the country of its billing address differs from its business
+//! country. Merchants configure the rate at which card issuers are estimated to charge
+//! international transaction fees, along with their own surcharges on cross-border payments by
+//! currency and country of the customer. The fees are computed when the payment is created,
+//! updated or confirmed, and disclosed in the payment response. The surcharge is included in the
+//! amount of the payment, whereas the estimated international transaction fee is charged by the
+//! issuer on top of the amount.
+
+use api_models::payments::{CrossBorderFeeConfig, CrossBorderFeeDetails};
+use common_utils::ext_traits::{Encode, StringExt};
+use error_stack::{report, IntoReport, ResultExt};
+use router_env::{instrument, tracing};
+
+use crate::{
+    consts,
+    core::errors::{self, RouterResponse, RouterResult},
+    db::StorageInterface,
+    routes::AppState,
+    services,
+    types::{
+        domain,
+        storage::{self, enums},
+    },
+    utils,
+};
+
+fn get_config_key(merchant_id: &str) -> String {
+    format!("cross_border_fee_config_{merchant_id}")
+}
+
+#[instrument(skip(state))]
+pub async fn retrieve_cross_border_fee_config(
+    state: &AppState,
+    merchant_account: domain::MerchantAccount,
+) -> RouterResponse<CrossBorderFeeConfig> {
+    let config = find_cross_border_fee_config(&*state.store, &merchant_account.merchant_id)
+        .await?
+        .unwrap_or_default();
+    Ok(services::ApplicationResponse::Json(config))
+}
+
+#[instrument(skip(state))]
+pub async fn update_cross_border_fee_config(
+    state: &AppState,
+    merchant_account: domain::MerchantAccount,
+    req: CrossBorderFeeConfig,
+) -> RouterResponse<CrossBorderFeeConfig> {
+    validate_cross_border_fee_config(&req)?;
+
+    let key = get_config_key(&merchant_account.merchant_id);
+    let config = Encode::<CrossBorderFeeConfig>::encode_to_string_of_json(&req)
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to encode the cross-border fee config")?;
+
+    let db = &*state.store;
+    match db.find_config_by_key(&key).await {
+        Ok(_) => db
+            .update_config_cached(
+                &key,
+                storage::ConfigUpdate::Update {
+                    config: Some(config),
+                },
+            )
+            .await
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Failed to update the cross-border fee config")?,
+        Err(error) if error.current_context().is_db_not_found() => db
+            .insert_config(storage::ConfigNew { key, config })
+            .await
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Failed to insert the cross-border fee config")?,
+        Err(error) => Err(error
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Failed to find the cross-border fee config"))?,
+    };
+
+    Ok(services::ApplicationResponse::Json(req))
+}
+
+fn validate_cross_border_fee_config(config: &CrossBorderFeeConfig) -> RouterResult<()> {
+    utils::when(
+        config
+            .estimated_international_fee_rate
+            .map_or(false, |rate| {
+                i64::from(rate) > consts::BASIS_POINTS_DENOMINATOR
+            }),
+        || {
+            Err(report!(errors::ApiErrorResponse::InvalidRequestData {
+                message: "estimated_international_fee_rate must be at most 10000".to_string(),
+            }))
+        },
+    )?;
+
+    for surcharge in &config.surcharges {
+        utils::when(
+            i64::from(surcharge.basis_points) > consts::BASIS_POINTS_DENOMINATOR,
+            || {
+                Err(report!(errors::ApiErrorResponse::InvalidRequestData {
+                    message: "basis_points must be at most 10000".to_string(),
+                }))
+            },
+        )?;
+        utils::when(surcharge.fixed_amount < 0, || {
+            Err(report!(errors::ApiErrorResponse::InvalidRequestData {
+                message: "fixed_amount must not be negative".to_string(),
+            }))
+        })?;
+        utils::when(
+            surcharge
+                .description
+                .as_ref()
+                .map_or(false, |description| description.len() > 255),
+            || {
+                Err(report!(errors::ApiErrorResponse::InvalidRequestData {
+                    message: "description must be at most 255 characters long".to_string(),
+                }))
+            },
+        )?;
+    }
+    Ok(())
+}
+
+async fn find_cross_border_fee_config(
+    db: &dyn StorageInterface,
+    merchant_id: &str,
+) -> RouterResult<Option<CrossBorderFeeConfig>> {
+    match db
+        .find_config_by_key_cached(&get_config_key(merchant_id))
+        .await
+    {
+        Ok(config) => config
+            .config
+            .parse_struct("CrossBorderFeeConfig")
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Invalid cross-border fee config configured for the merchant")
+            .map(Some),
+        Err(error) if error.current_context().is_db_not_found() => Ok(None),
+        Err(error) => Err(error
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Failed to find the cross-border fee config")),
+    }
+}
+
+/// Computes the cross-border fees of a payment, to be stored with the payment intent. Returns
+/// `None` if the payment is not cross-border, its customer country is not known, or the merchant
+/// has configured no fee applicable to it.
+#[instrument(skip(db))]
+pub async fn get_cross_border_fees(
+    db: &dyn StorageInterface,
+    merchant_id: &str,
+    business_country: enums::CountryAlpha2,
+    customer_country: Option<enums::CountryAlpha2>,
+    amount: i64,
+    currency: enums::Currency,
+) -> RouterResult<Option<serde_json::Value>> {
+    let customer_country = match customer_country {
+        Some(country) if country != business_country => country,
+        _ => return Ok(None),
+    };
+    let Some(config) = find_cross_border_fee_config(db, merchant_id).await? else {
+        return Ok(None);
+    };
+
+    get_cross_border_fee_details_for_config(
+        &config,
+        business_country,
+        customer_country,
+        amount,
+        currency,
+    )?
+    .map(|cross_border_fees| {
+        Encode::<CrossBorderFeeDetails>::encode_to_value(&cross_border_fees)
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Failed to convert cross-border fees to value")
+    })
+    .transpose()
+}
+
+/// Computes the cross-border fees configured by the merchant for a payment of a customer in
+/// another country than the business country of the payment.
+fn get_cross_border_fee_details_for_config(
+    config: &CrossBorderFeeConfig,
+    business_country: enums::CountryAlpha2,
+    customer_country: enums::CountryAlpha2,
+    amount: i64,
+    currency: enums::Currency,
+) -> RouterResult<Option<CrossBorderFeeDetails>> {
+    let estimated_international_fee = config
+        .estimated_international_fee_rate
+        .map(|rate| get_basis_points_amount(amount, rate))
+        .transpose()?;
+
+    let surcharge = config.surcharges.iter().find(|surcharge| {
+        surcharge.currency == currency
+            && surcharge
+                .countries
+                .as_ref()
+                .map_or(true, |countries| countries.contains(&customer_country))
+    });
+    // The surcharge is included in the amount, so that it never exceeds the amount
+    let surcharge_amount = surcharge
+        .map(|surcharge| {
+            get_basis_points_amount(amount, surcharge.basis_points)
+                .map(|surcharge_amount| (surcharge_amount + surcharge.fixed_amount).min(amount))
+        })
+        .transpose()?
+        .filter(|surcharge_amount| *surcharge_amount > 0);
+
+    if estimated_international_fee.is_none() && surcharge_amount.is_none() {
+        return Ok(None);
+    }
+
+    Ok(Some(CrossBorderFeeDetails {
+        customer_country,
+        business_country,
+        estimated_international_fee,
+        estimated_international_fee_rate: config.estimated_international_fee_rate,
+        surcharge_amount,
+        surcharge_description: surcharge_amount.and(surcharge.map(|surcharge| {
+            surcharge
+                .description
+                .clone()
+                .unwrap_or_else(|| consts::DEFAULT_CROSS_BORDER_SURCHARGE_DESCRIPTION.to_string())
+        })),
+    }))
+}
+
+/// The share of the amount given in basis points, rounded half up to the lowest denomination of
+/// the currency
+fn get_basis_points_amount(amount: i64, basis_points: u16) -> RouterResult<i64> {
+    let denominator = i128::from(consts::BASIS_POINTS_DENOMINATOR);
+    let share =
+        (i128::from(amount) * i128::from(basis_points) * 2 + denominator) / (denominator * 2);
+    i64::try_from(share)
+        .into_report()
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Cross-border fee overflowed")
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use api_models::payments::CrossBorderSurcharge;
+
+    use super::*;
+
+    #[test]
+    fn test_get_basis_points_amount() {
+        assert_eq!(get_basis_points_amount(10000, 150).unwrap(), 150);
+        // Rounded half up to the lowest denomination
+        assert_eq!(get_basis_points_amount(1050, 100).unwrap(), 11);
+        assert_eq!(get_basis_points_amount(1049, 100).unwrap(), 10);
+        assert_eq!(get_basis_points_amount(12345, 0).unwrap(), 0);
+        assert_eq!(get_basis_points_amount(12345, 10000).unwrap(), 12345);
+        assert_eq!(get_basis_points_amount(i64::MAX, 10000).unwrap(), i64::MAX);
+    }
+
+    fn get_config() -> CrossBorderFeeConfig {
+        CrossBorderFeeConfig {
+            estimated_international_fee_rate: Some(300),
+            surcharges: vec![
+                CrossBorderSurcharge {
+                    currency: enums::Currency::USD,
+                    countries: Some(vec![enums::CountryAlpha2::DE]),
+                    basis_points: 200,
+                    fixed_amount: 50,
+                    description: Some("German card surcharge".to_string()),
+                },
+                CrossBorderSurcharge {
+                    currency: enums::Currency::USD,
+                    countries: None,
+                    basis_points: 100,
+                    fixed_amount: 0,
+                    description: None,
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn test_get_cross_border_fee_details_for_config() {
+        let config = get_config();
+        let us = enums::CountryAlpha2::US;
+
+        let fees = get_cross_border_fee_details_for_config(
+            &config,
+            us,
+            enums::CountryAlpha2::DE,
+            10000,
+            enums::Currency::USD,
+        )
+        .unwrap()
+        .unwrap();
+        assert_eq!(fees.estimated_international_fee, Some(300));
+        // The surcharge specific to the country of the customer is applied first
+        assert_eq!(fees.surcharge_amount, Some(250));
+        assert_eq!(
+            fees.surcharge_description.as_deref(),
+            Some("German card surcharge")
+        );
+
+        let fees = get_cross_border_fee_details_for_config(
+            &config,
+            us,
+            enums::CountryAlpha2::FR,
+            10000,
+            enums::Currency::USD,
+        )
+        .unwrap()
+        .unwrap();
+        assert_eq!(fees.surcharge_amount, Some(100));
+        assert_eq!(
+            fees.surcharge_description.as_deref(),
+            Some(consts::DEFAULT_CROSS_BORDER_SURCHARGE_DESCRIPTION)
+        );
+
+        // No surcharge is configured for the currency
+        let fees = get_cross_border_fee_details_for_config(
+            &config,
+            us,
+            enums::CountryAlpha2::DE,
+            10000,
+            enums::Currency::EUR,
+        )
+        .unwrap()
+        .unwrap();
+        assert_eq!(fees.surcharge_amount, None);
+        assert_eq!(fees.surcharge_description, None);
+
+        // The surcharge never exceeds the amount
+        let fees = get_cross_border_fee_details_for_config(
+            &config,
+            us,
+            enums::CountryAlpha2::DE,
+            20,
+            enums::Currency::USD,
+        )
+        .unwrap()
+        .unwrap();
+        assert_eq!(fees.surcharge_amount, Some(20));
+
+        let no_fees = CrossBorderFeeConfig::default();
+        assert!(get_cross_border_fee_details_for_config(
+            &no_fees,
+            us,
+            enums::CountryAlpha2::DE,
+            10000,
+            enums::Currency::USD,
+        )
+        .unwrap()
+        .is_none());
+    }
+}
@@ -0,0 +1,482 @@
+//! Currency conversion of payments charged in the currency of the customer.
+//!
+//! The amount and currency of a payment are those settled to the merchant. When a payment is
+//! created with a presentment currency other than its currency, the amount is converted at the
+//! exchange rate of the configured rate provider, and both the presentment and the settlement
+//! amounts are stored with the payment intent. Such payments are only sent to connectors which
+//! support dynamic currency conversion (DCC), which charge the customer the presentment amount
+//! while settling the settlement amount to the merchant.
+
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use common_utils::ext_traits::BytesExt;
+use error_stack::{report, IntoReport, ResultExt};
+use masking::PeekInterface;
+use router_env::{instrument, logger, tracing};
+use serde::Deserialize;
+
+use crate::{
+    configs::settings,
+    core::errors::{self, CustomResult, RouterResult},
+    headers,
+    routes::AppState,
+    services::{self, request::Mask},
+    types::{
+        api,
+        storage::{self, enums},
+    },
+};
+
+/// Number of decimal places of exchange rates
+const EXCHANGE_RATE_SCALE: u32 = 8;
+
+/// An exchange rate, as a fixed-point number with `EXCHANGE_RATE_SCALE` decimal places, so that
+/// amounts are converted with integer arithmetic and explicit rounding.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct ExchangeRate(i64);
+
+impl ExchangeRate {
+    /// Parses a positive decimal exchange rate, such as `0.9419` or `1.2e-3`, rounding it half
+    /// up to `EXCHANGE_RATE_SCALE` decimal places.
+    pub fn parse(rate: &str) -> Option<Self> {
+        let rate = rate.trim();
+        let (mantissa, exponent) = match rate.split_once(['e', 'E']) {
+            Some((mantissa, exponent)) => (mantissa, exponent.parse::<i32>().ok()?),
+            None => (rate, 0),
+        };
+        let (integer, fraction) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+        let digits = format!("{integer}{fraction}");
+        if digits.is_empty() || digits.len() > 30 || !digits.bytes().all(|b| b.is_ascii_digit()) {
+            return None;
+        }
+
+        let value = digits.parse::<i128>().ok()?;
+        let fraction_digits = i32::try_from(fraction.len()).ok()?;
+        let scale = i32::try_from(EXCHANGE_RATE_SCALE).ok()? + exponent - fraction_digits;
+        let units = if scale >= 0 {
+            value.checked_mul(10_i128.checked_pow(u32::try_from(scale).ok()?)?)?
+        } else {
+            let divisor = 10_i128.checked_pow(u32::try_from(-scale).ok()?)?;
+            (value + divisor / 2) / divisor
+        };
+
+        i64::try_from(units)
+            .ok()
+            .filter(|units| *units > 0)
+            .map(Self)
+    }
+
+    /// Returns the exchange rate of the opposite currency pair, rounded half up
+    pub fn inverse(self) -> Option<Self> {
+        let one = 10_i128.pow(EXCHANGE_RATE_SCALE);
+        let units = (one * one + i128::from(self.0) / 2) / i128::from(self.0);
+        i64::try_from(units)
+            .ok()
+            .filter(|units| *units > 0)
+            .map(Self)
+    }
+}
+
+impl std::fmt::Display for ExchangeRate {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let one = 10_i64.pow(EXCHANGE_RATE_SCALE);
+        let fraction = format!("{:0width$}", self.0 % one, width = 8);
+        let fraction = fraction.trim_end_matches('0');
+        if fraction.is_empty() {
+            write!(f, "{}", self.0 / one)
+        } else {
+            write!(f, "{}.{fraction}", self.0 / one)
+        }
+    }
+}
+
+#[async_trait]
+pub trait FxRateProvider: Send + Sync {
+    /// Returns the exchange rate from the currency `from` to the currency `to`
+    async fn get_exchange_rate(
+        &self,
+        state: &AppState,
+        from: enums::Currency,
+        to: enums::Currency,
+    ) -> CustomResult<ExchangeRate, errors::CurrencyConversionError>;
+}
+
+/// Returns the configured exchange rate provider, the exchange rate API taking precedence over
+/// the fixed exchange rates.
+pub fn get_fx_rate_provider(
+    settings: &settings::CurrencyConversionSettings,
+) -> Option<Box<dyn FxRateProvider + '_>> {
+    match &settings.rate_api {
+        Some(config) => Some(Box::new(FxRateApi { config })),
+        None if !settings.fixed_rates.is_empty() => Some(Box::new(FixedFxRates {
+            rates: &settings.fixed_rates,
+        })),
+        None => None,
+    }
+}
+
+/// Fixed exchange rates, keyed by currency pair in the `<FROM>_<TO>` format. The inverse of the
+/// rate of the opposite pair is used if a currency pair is not configured.
+pub struct FixedFxRates<'a> {
+    rates: &'a HashMap<String, String>,
+}
+
+#[async_trait]
+impl FxRateProvider for FixedFxRates<'_> {
+    async fn get_exchange_rate(
+        &self,
+        _state: &AppState,
+        from: enums::Currency,
+        to: enums::Currency,
+    ) -> CustomResult<ExchangeRate, errors::CurrencyConversionError> {
+        match self.rates.get(&format!("{from}_{to}")) {
+            Some(rate) => ExchangeRate::parse(rate),
+            None => self
+                .rates
+                .get(&format!("{to}_{from}"))
+                .and_then(|inverse_rate| ExchangeRate::parse(inverse_rate))
+                .and_then(ExchangeRate::inverse),
+        }
+        .ok_or(errors::CurrencyConversionError::RateNotAvailable)
+        .into_report()
+    }
+}
+
+/// An exchange rate API returning the rates of a currency pair from
+/// `{base_url}latest?from=<FROM>&to=<TO>`
+pub struct FxRateApi<'a> {
+    config: &'a settings::FxRateApiConfig,
+}
+
+#[derive(Debug, Deserialize)]
+struct FxRateApiResponse {
+    rates: HashMap<String, serde_json::Number>,
+}
+
+#[async_trait]
+impl FxRateProvider for FxRateApi<'_> {
+    async fn get_exchange_rate(
+        &self,
+        state: &AppState,
+        from: enums::Currency,
+        to: enums::Currency,
+    ) -> CustomResult<ExchangeRate, errors::CurrencyConversionError> {
+        let url = format!("{}latest?from={from}&to={to}", self.config.base_url);
+        let mut request = services::Request::new(services::Method::Get, &url);
+        if let Some(api_key) = &self.config.api_key {
+            request.add_header(headers::X_API_KEY, api_key.peek().to_owned().into_masked());
+        }
+
+        let response: FxRateApiResponse = match services::call_connector_api(state, request).await {
+            Ok(Ok(response)) => response
+                .response
+                .parse_struct("FxRateApiResponse")
+                .change_context(errors::CurrencyConversionError::ResponseDeserializationFailed)?,
+            Ok(Err(error_response)) => {
+                logger::error!(fx_rate_api_error_response = ?error_response);
+                Err(errors::CurrencyConversionError::RateFetchFailed).into_report()?
+            }
+            Err(api_client_error) => {
+                Err(api_client_error
+                    .change_context(errors::CurrencyConversionError::RateFetchFailed))?
+            }
+        };
+
+        response
+            .rates
+            .get(&to.to_string())
+            .and_then(|rate| ExchangeRate::parse(&rate.to_string()))
+            .ok_or(errors::CurrencyConversionError::RateNotAvailable)
+            .into_report()
+    }
+}
+
+/// Returns the number of digits of the minor unit of the currency
+fn get_minor_unit_exponent(currency: enums::Currency) -> i32 {
+    if currency.is_zero_decimal_currency() {
+        0
+    } else if currency.is_three_decimal_currency() {
+        3
+    } else {
+        2
+    }
+}
+
+/// Converts an amount in the minor unit of the currency `from` to the minor unit of the currency
+/// `to` at the exchange rate, rounding half up to the nearest minor unit.
+fn convert_amount(
+    amount: i64,
+    from: enums::Currency,
+    to: enums::Currency,
+    exchange_rate: ExchangeRate,
+) -> Option<i64> {
+    let exponent = get_minor_unit_exponent(to) - get_minor_unit_exponent(from);
+    let mut numerator = i128::from(amount).checked_mul(i128::from(exchange_rate.0))?;
+    let mut denominator = 10_i128.pow(EXCHANGE_RATE_SCALE);
+    let exponent_factor = 10_i128.pow(exponent.unsigned_abs());
+    if exponent >= 0 {
+        numerator = numerator.checked_mul(exponent_factor)?;
+    } else {
+        denominator *= exponent_factor;
+    }
+
+    let converted_amount = numerator.checked_add(denominator / 2)? / denominator;
+    i64::try_from(converted_amount)
+        .ok()
+        .filter(|converted_amount| *converted_amount >= 1)
+}
+
+/// Returns the share of the presentment amount of a converted payment corresponding to an amount
+/// in its settlement currency, such as the amount of a capture or a refund, rounding half up to
+/// the nearest minor unit. The amount is converted at the rate at which the payment was
+/// converted, so that the full settlement amount maps to the full presentment amount.
+pub fn get_presentment_amount(
+    amount: i64,
+    conversion: &api_models::payments::CurrencyConversionDetails,
+) -> Option<i64> {
+    if amount == conversion.settlement_amount {
+        return Some(conversion.presentment_amount);
+    }
+    let settlement_amount = i128::from(conversion.settlement_amount);
+    if settlement_amount <= 0 || amount < 0 {
+        return None;
+    }
+
+    let presentment_amount = i128::from(amount)
+        .checked_mul(i128::from(conversion.presentment_amount))?
+        .checked_add(settlement_amount / 2)?
+        / settlement_amount;
+    i64::try_from(presentment_amount).ok()
+}
+
+/// Converts the amount of a payment to the currency in which the customer is charged, returning
+/// `None` if the payment is charged in its settlement currency.
+#[instrument(skip_all)]
+pub async fn get_currency_conversion(
+    state: &AppState,
+    settlement_amount: i64,
+    settlement_currency: enums::Currency,
+    presentment_currency: Option<enums::Currency>,
+) -> RouterResult<Option<api_models::payments::CurrencyConversionDetails>> {
+    let settings = &state.conf.currency_conversion;
+    let Some(presentment_currency) =
+        presentment_currency.filter(|currency| *currency != settlement_currency)
+    else {
+        return Ok(None);
+    };
+    if !settings.enabled {
+        Err(report!(errors::ApiErrorResponse::NotSupported {
+            message: "Charging payments in a currency other than their settlement currency is not enabled".to_string(),
+        }))?
+    }
+
+    let provider = get_fx_rate_provider(settings)
+        .ok_or(errors::ApiErrorResponse::InternalServerError)
+        .into_report()
+        .attach_printable("No exchange rate provider is configured")?;
+    let exchange_rate = provider
+        .get_exchange_rate(state, settlement_currency, presentment_currency)
+        .await
+        .map_err(|error| {
+            let api_error = match error.current_context() {
+                errors::CurrencyConversionError::RateNotAvailable => {
+                    errors::ApiErrorResponse::NotSupported {
+                        message: format!(
+                            "No exchange rate is available from {settlement_currency} to {presentment_currency}"
+                        ),
+                    }
+                }
+                errors::CurrencyConversionError::RateFetchFailed
+                | errors::CurrencyConversionError::ResponseDeserializationFailed => {
+                    errors::ApiErrorResponse::InternalServerError
+                }
+            };
+            error.change_context(api_error)
+        })?;
+
+    let presentment_amount = convert_amount(
+        settlement_amount,
+        settlement_currency,
+        presentment_currency,
+        exchange_rate,
+    )
+    .ok_or(report!(errors::ApiErrorResponse::InvalidRequestData {
+        message: format!(
+            "The amount of the payment cannot be converted from {settlement_currency} to {presentment_currency}"
+        ),
+    }))?;
+
+    Ok(Some(api_models::payments::CurrencyConversionDetails {
+        presentment_amount,
+        presentment_currency,
+        settlement_amount,
+        settlement_currency,
+        exchange_rate: exchange_rate.to_string(),
+    }))
+}
+
+/// Returns the currency conversion of the payment, if it is charged in a currency other than its
+/// settlement currency.
+pub fn get_currency_conversion_details(
+    payment_intent: &storage::PaymentIntent,
+) -> Option<api_models::payments::CurrencyConversionDetails> {
+    Some(api_models::payments::CurrencyConversionDetails {
+        presentment_amount: payment_intent.presentment_amount?,
+        presentment_currency: payment_intent.presentment_currency?,
+        settlement_amount: payment_intent.settlement_amount?,
+        settlement_currency: payment_intent.settlement_currency?,
+        exchange_rate: payment_intent.exchange_rate.clone()?,
+    })
+}
+
+/// Returns the currency conversion of an amount of a payment charged in a currency other than its
+/// settlement currency, such as the amount of a capture or of a refund, to be sent to the
+/// connector which charged the presentment amount of the payment.
+pub fn get_currency_conversion_for_amount(
+    payment_intent: &storage::PaymentIntent,
+    settlement_amount: i64,
+) -> RouterResult<Option<api_models::payments::CurrencyConversionDetails>> {
+    get_currency_conversion_details(payment_intent)
+        .map(|conversion| {
+            let presentment_amount = get_presentment_amount(settlement_amount, &conversion)
+                .ok_or(errors::ApiErrorResponse::InternalServerError)
+                .into_report()
+                .attach_printable_lazy(|| {
+                    format!(
+                        "Failed to convert the amount {settlement_amount} to the presentment currency"
+                    )
+                })?;
+            Ok(api_models::payments::CurrencyConversionDetails {
+                presentment_amount,
+                settlement_amount,
+                ..conversion
+            })
+        })
+        .transpose()
+}
+
+/// Validates that the connector supports dynamic currency conversion and is enabled for it, if
+/// the payment is charged in a currency other than its settlement currency. Connectors which do
+/// not send the presentment amount would charge the customer the settlement amount instead.
+pub fn validate_connector_for_currency_conversion(
+    state: &AppState,
+    payment_intent: &storage::PaymentIntent,
+    connector: &api::ConnectorData,
+) -> RouterResult<()> {
+    let settings = &state.conf.currency_conversion;
+    let connector_name = connector.connector_name.to_string();
+    if payment_intent.presentment_currency.is_some()
+        && !(connector.connector.is_currency_conversion_supported()
+            && settings.dcc_connector_list.contains(&connector_name))
+    {
+        Err(report!(errors::ApiErrorResponse::NotSupported {
+            message: format!(
+                "Payments charged in a currency other than their settlement currency are not supported by the {connector_name} connector"
+            ),
+        }))?
+    }
+    Ok(())
+}
+
+/// Validates that the amount and currency of a payment charged in a currency other than its
+/// settlement currency are not updated, as the presentment amount was converted from them.
+pub fn validate_amount_update_for_currency_conversion(
+    payment_intent: &storage::PaymentIntent,
+    amount: i64,
+    currency: enums::Currency,
+) -> RouterResult<()> {
+    match get_currency_conversion_details(payment_intent) {
+        Some(conversion)
+            if conversion.settlement_amount != amount
+                || conversion.settlement_currency != currency =>
+        {
+            Err(report!(errors::ApiErrorResponse::PreconditionFailed {
+                message: "The amount and currency of a payment charged in a currency other than its settlement currency cannot be updated".to_string(),
+            }))
+        }
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+
+    #[test]
+    fn test_exchange_rate_parse() {
+        assert_eq!(
+            ExchangeRate::parse("0.9419"),
+            Some(ExchangeRate(94_190_000))
+        );
+        assert_eq!(ExchangeRate::parse("1"), Some(ExchangeRate(100_000_000)));
+        assert_eq!(ExchangeRate::parse("1.2e-3"), Some(ExchangeRate(120_000)));
+        assert_eq!(ExchangeRate::parse("0.000000015"), Some(ExchangeRate(2)));
+        assert_eq!(ExchangeRate::parse("0.000000004"), None);
+        assert_eq!(ExchangeRate::parse("-1"), None);
+        assert_eq!(ExchangeRate::parse("abc"), None);
+        assert_eq!(ExchangeRate::parse("0"), None);
+        assert_eq!(ExchangeRate(94_190_000).to_string(), "0.9419");
+        assert_eq!(ExchangeRate(100_000_000).to_string(), "1");
+    }
+
+    #[test]
+    fn test_exchange_rate_inverse() {
+        let rate = ExchangeRate::parse("0.8").unwrap();
+        assert_eq!(rate.inverse(), Some(ExchangeRate(125_000_000)));
+        // 1 / 3 is rounded to 8 decimal places
+        let rate = ExchangeRate::parse("3").unwrap();
+        assert_eq!(rate.inverse(), Some(ExchangeRate(33_333_333)));
+    }
+
+    #[test]
+    fn test_convert_amount() {
+        let rate = ExchangeRate::parse("0.9419").unwrap();
+        // 65.40 USD at 0.9419 is 61.60026 EUR
+        assert_eq!(
+            convert_amount(6540, enums::Currency::USD, enums::Currency::EUR, rate),
+            Some(6160)
+        );
+        // 0.05 EUR is rounded half up
+        let rate = ExchangeRate::parse("0.5").unwrap();
+        assert_eq!(
+            convert_amount(5, enums::Currency::USD, enums::Currency::EUR, rate),
+            Some(3)
+        );
+        // Zero decimal currencies have no minor unit
+        let rate = ExchangeRate::parse("150.25").unwrap();
+        assert_eq!(
+            convert_amount(1000, enums::Currency::USD, enums::Currency::JPY, rate),
+            Some(1503)
+        );
+        let rate = ExchangeRate::parse("0.0067").unwrap();
+        assert_eq!(
+            convert_amount(1503, enums::Currency::JPY, enums::Currency::USD, rate),
+            Some(1007)
+        );
+        // Amounts converting to less than a minor unit cannot be charged
+        let rate = ExchangeRate::parse("0.001").unwrap();
+        assert_eq!(
+            convert_amount(1, enums::Currency::USD, enums::Currency::EUR, rate),
+            None
+        );
+    }
+
+    #[test]
+    fn test_get_presentment_amount() {
+        let conversion = api_models::payments::CurrencyConversionDetails {
+            presentment_amount: 6160,
+            presentment_currency: enums::Currency::EUR,
+            settlement_amount: 6540,
+            settlement_currency: enums::Currency::USD,
+            exchange_rate: "0.9419".to_string(),
+        };
+        assert_eq!(get_presentment_amount(6540, &conversion), Some(6160));
+        // 3270 * 6160 / 6540 = 3080
+        assert_eq!(get_presentment_amount(3270, &conversion), Some(3080));
+        // 1000 * 6160 / 6540 = 941.896
+        assert_eq!(get_presentment_amount(1000, &conversion), Some(942));
+        assert_eq!(get_presentment_amount(-1, &conversion), None);
+    }
+}
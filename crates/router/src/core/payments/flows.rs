@@ -9,6 +9,7 @@ pub mod verify_flow;
 use async_trait::async_trait;
 
 use crate::{
+    configs::settings,
     connector,
     core::{
         errors::{ConnectorError, CustomResult, RouterResult},
@@ -160,7 +161,6 @@ default_imp_for_complete_authorize!(
     connector::Opayo,
     connector::Opennode,
     connector::Payeezy,
-    connector::Payme,
     connector::Payu,
     connector::Rapyd,
     connector::Square,
@@ -297,7 +297,6 @@ default_imp_for_connector_redirect_response!(
     connector::Opayo,
     connector::Opennode,
     connector::Payeezy,
-    connector::Payme,
     connector::Payu,
     connector::Powertranz,
     connector::Rapyd,
@@ -367,6 +366,65 @@ default_imp_for_connector_request_id!(
     connector::Zen
 );
 
+macro_rules! default_imp_for_card_present {
+    ($($path:ident::$connector:ident),*) => {
+        $(
+            impl api::ConnectorCardPresent for $path::$connector {}
+    )*
+    };
+}
+
+#[cfg(feature = "dummy_connector")]
+impl<const T: u8> api::ConnectorCardPresent for connector::DummyConnector<T> {}
+
+default_imp_for_card_present!(
+    connector::Aci,
+    connector::Adyen,
+    connector::Airwallex,
+    connector::Authorizedotnet,
+    connector::Bambora,
+    connector::Bitpay,
+    connector::Bluesnap,
+    connector::Boku,
+    connector::Braintree,
+    connector::Cashtocode,
+    connector::Checkout,
+    connector::Coinbase,
+    connector::Cryptopay,
+    connector::Cybersource,
+    connector::Dlocal,
+    connector::Fiserv,
+    connector::Forte,
+    connector::Globalpay,
+    connector::Globepay,
+    connector::Iatapay,
+    connector::Klarna,
+    connector::Mollie,
+    connector::Multisafepay,
+    connector::Nexinets,
+    connector::Nmi,
+    connector::Noon,
+    connector::Nuvei,
+    connector::Opayo,
+    connector::Opennode,
+    connector::Payeezy,
+    connector::Payme,
+    connector::Paypal,
+    connector::Payu,
+    connector::Powertranz,
+    connector::Rapyd,
+    connector::Shift4,
+    connector::Square,
+    connector::Stax,
+    connector::Stripe,
+    connector::Trustpay,
+    connector::Tsys,
+    connector::Wise,
+    connector::Worldline,
+    connector::Worldpay,
+    connector::Zen
+);
+
 macro_rules! default_imp_for_accept_dispute {
     ($($path:ident::$connector:ident),*) => {
         $(
@@ -495,7 +553,6 @@ impl<const T: u8>
 
 default_imp_for_file_upload!(
     connector::Aci,
-    connector::Adyen,
     connector::Airwallex,
     connector::Authorizedotnet,
     connector::Bambora,
@@ -759,6 +816,253 @@ default_imp_for_pre_processing_steps!(
     connector::Zen
 );
 
+macro_rules! default_imp_for_reauthorize {
+    ($($path:ident::$connector:ident),*) => {
+        $(
+            impl api::PaymentReauthorize for $path::$connector {}
+            impl
+            services::ConnectorIntegration<
+            api::Reauthorize,
+            types::PaymentsReauthorizeData,
+            types::PaymentsResponseData,
+        > for $path::$connector
+        {}
+    )*
+    };
+}
+
+#[cfg(feature = "dummy_connector")]
+impl<const T: u8> api::PaymentReauthorize for connector::DummyConnector<T> {}
+#[cfg(feature = "dummy_connector")]
+impl<const T: u8>
+    services::ConnectorIntegration<
+        api::Reauthorize,
+        types::PaymentsReauthorizeData,
+        types::PaymentsResponseData,
+    > for connector::DummyConnector<T>
+{
+}
+
+default_imp_for_reauthorize!(
+    connector::Aci,
+    connector::Adyen,
+    connector::Airwallex,
+    connector::Authorizedotnet,
+    connector::Bambora,
+    connector::Bitpay,
+    connector::Bluesnap,
+    connector::Boku,
+    connector::Braintree,
+    connector::Cashtocode,
+    connector::Cryptopay,
+    connector::Cybersource,
+    connector::Coinbase,
+    connector::Dlocal,
+    connector::Fiserv,
+    connector::Forte,
+    connector::Globalpay,
+    connector::Globepay,
+    connector::Iatapay,
+    connector::Klarna,
+    connector::Mollie,
+    connector::Multisafepay,
+    connector::Nexinets,
+    connector::Nmi,
+    connector::Noon,
+    connector::Nuvei,
+    connector::Opayo,
+    connector::Opennode,
+    connector::Payeezy,
+    connector::Payme,
+    connector::Paypal,
+    connector::Payu,
+    connector::Powertranz,
+    connector::Rapyd,
+    connector::Square,
+    connector::Stax,
+    connector::Stripe,
+    connector::Shift4,
+    connector::Trustpay,
+    connector::Tsys,
+    connector::Wise,
+    connector::Worldline,
+    connector::Worldpay,
+    connector::Zen
+);
+
+// Connectors which do not support incremental authorization fail the flow instead of leaving the
+// authorization untouched, so that it is not recorded as declined
+macro_rules! default_imp_for_incremental_authorization {
+    ($($path:ident::$connector:ident),*) => {
+        $(
+            impl api::PaymentIncrementalAuthorization for $path::$connector {}
+            impl
+            services::ConnectorIntegration<
+            api::IncrementalAuthorization,
+            types::PaymentsIncrementalAuthorizationData,
+            types::PaymentsResponseData,
+        > for $path::$connector
+        {
+            fn build_request(
+                &self,
+                req: &types::PaymentsIncrementalAuthorizationRouterData,
+                _connectors: &settings::Connectors,
+            ) -> CustomResult<Option<services::Request>, ConnectorError> {
+                Err(ConnectorError::FlowNotSupported {
+                    flow: "Incremental Authorization".to_string(),
+                    connector: req.connector.clone(),
+                }
+                .into())
+            }
+        }
+    )*
+    };
+}
+
+#[cfg(feature = "dummy_connector")]
+impl<const T: u8> api::PaymentIncrementalAuthorization for connector::DummyConnector<T> {}
+#[cfg(feature = "dummy_connector")]
+impl<const T: u8>
+    services::ConnectorIntegration<
+        api::IncrementalAuthorization,
+        types::PaymentsIncrementalAuthorizationData,
+        types::PaymentsResponseData,
+    > for connector::DummyConnector<T>
+{
+    fn build_request(
+        &self,
+        req: &types::PaymentsIncrementalAuthorizationRouterData,
+        _connectors: &settings::Connectors,
+    ) -> CustomResult<Option<services::Request>, ConnectorError> {
+        Err(ConnectorError::FlowNotSupported {
+            flow: "Incremental Authorization".to_string(),
+            connector: req.connector.clone(),
+        }
+        .into())
+    }
+}
+
+default_imp_for_incremental_authorization!(
+    connector::Aci,
+    connector::Adyen,
+    connector::Airwallex,
+    connector::Authorizedotnet,
+    connector::Bambora,
+    connector::Bitpay,
+    connector::Bluesnap,
+    connector::Boku,
+    connector::Braintree,
+    connector::Cashtocode,
+    connector::Checkout,
+    connector::Cryptopay,
+    connector::Cybersource,
+    connector::Coinbase,
+    connector::Dlocal,
+    connector::Fiserv,
+    connector::Forte,
+    connector::Globalpay,
+    connector::Globepay,
+    connector::Iatapay,
+    connector::Klarna,
+    connector::Mollie,
+    connector::Multisafepay,
+    connector::Nexinets,
+    connector::Nmi,
+    connector::Noon,
+    connector::Nuvei,
+    connector::Opayo,
+    connector::Opennode,
+    connector::Payeezy,
+    connector::Payme,
+    connector::Paypal,
+    connector::Payu,
+    connector::Powertranz,
+    connector::Rapyd,
+    connector::Square,
+    connector::Stax,
+    connector::Shift4,
+    connector::Trustpay,
+    connector::Tsys,
+    connector::Wise,
+    connector::Worldline,
+    connector::Worldpay,
+    connector::Zen
+);
+
+macro_rules! default_imp_for_account_updater {
+    ($($path:ident::$connector:ident),*) => {
+        $(
+            impl api::PaymentMethodAccountUpdater for $path::$connector {}
+            impl
+            services::ConnectorIntegration<
+            api::AccountUpdate,
+            types::AccountUpdaterRequestData,
+            types::AccountUpdaterResponseData,
+        > for $path::$connector
+        {}
+    )*
+    };
+}
+
+#[cfg(feature = "dummy_connector")]
+impl<const T: u8> api::PaymentMethodAccountUpdater for connector::DummyConnector<T> {}
+#[cfg(feature = "dummy_connector")]
+impl<const T: u8>
+    services::ConnectorIntegration<
+        api::AccountUpdate,
+        types::AccountUpdaterRequestData,
+        types::AccountUpdaterResponseData,
+    > for connector::DummyConnector<T>
+{
+}
+
+default_imp_for_account_updater!(
+    connector::Aci,
+    connector::Airwallex,
+    connector::Authorizedotnet,
+    connector::Bambora,
+    connector::Bitpay,
+    connector::Bluesnap,
+    connector::Boku,
+    connector::Braintree,
+    connector::Cashtocode,
+    connector::Checkout,
+    connector::Cryptopay,
+    connector::Cybersource,
+    connector::Coinbase,
+    connector::Dlocal,
+    connector::Fiserv,
+    connector::Forte,
+    connector::Globalpay,
+    connector::Globepay,
+    connector::Iatapay,
+    connector::Klarna,
+    connector::Mollie,
+    connector::Multisafepay,
+    connector::Nexinets,
+    connector::Nmi,
+    connector::Noon,
+    connector::Nuvei,
+    connector::Opayo,
+    connector::Opennode,
+    connector::Payeezy,
+    connector::Payme,
+    connector::Paypal,
+    connector::Payu,
+    connector::Powertranz,
+    connector::Rapyd,
+    connector::Square,
+    connector::Stax,
+    connector::Stripe,
+    connector::Shift4,
+    connector::Trustpay,
+    connector::Tsys,
+    connector::Wise,
+    connector::Worldline,
+    connector::Worldpay,
+    connector::Zen
+);
+
 macro_rules! default_imp_for_payouts {
     ($($path:ident::$connector:ident),*) => {
         $(
@@ -1264,3 +1568,156 @@ default_imp_for_payouts_recipient!(
     connector::Worldpay,
     connector::Zen
 );
+
+#[cfg(feature = "payouts")]
+macro_rules! default_imp_for_payouts_sync {
+    ($($path:ident::$connector:ident),*) => {
+        $(
+            impl api::PayoutSync for $path::$connector {}
+            impl
+            services::ConnectorIntegration<
+            api::PoSync,
+            types::PayoutsData,
+            types::PayoutsResponseData,
+        > for $path::$connector
+        {}
+    )*
+    };
+}
+
+#[cfg(feature = "payouts")]
+#[cfg(feature = "dummy_connector")]
+impl<const T: u8> api::PayoutSync for connector::DummyConnector<T> {}
+#[cfg(feature = "payouts")]
+#[cfg(feature = "dummy_connector")]
+impl<const T: u8>
+    services::ConnectorIntegration<api::PoSync, types::PayoutsData, types::PayoutsResponseData>
+    for connector::DummyConnector<T>
+{
+}
+
+#[cfg(feature = "payouts")]
+default_imp_for_payouts_sync!(
+    connector::Aci,
+    connector::Adyen,
+    connector::Airwallex,
+    connector::Authorizedotnet,
+    connector::Bambora,
+    connector::Bitpay,
+    connector::Bluesnap,
+    connector::Boku,
+    connector::Braintree,
+    connector::Cashtocode,
+    connector::Checkout,
+    connector::Cryptopay,
+    connector::Cybersource,
+    connector::Coinbase,
+    connector::Dlocal,
+    connector::Fiserv,
+    connector::Forte,
+    connector::Globalpay,
+    connector::Globepay,
+    connector::Iatapay,
+    connector::Klarna,
+    connector::Mollie,
+    connector::Multisafepay,
+    connector::Nexinets,
+    connector::Nmi,
+    connector::Noon,
+    connector::Nuvei,
+    connector::Opayo,
+    connector::Opennode,
+    connector::Payeezy,
+    connector::Payme,
+    connector::Paypal,
+    connector::Payu,
+    connector::Powertranz,
+    connector::Rapyd,
+    connector::Square,
+    connector::Stax,
+    connector::Stripe,
+    connector::Shift4,
+    connector::Trustpay,
+    connector::Tsys,
+    connector::Worldline,
+    connector::Worldpay,
+    connector::Zen
+);
+
+#[cfg(feature = "payouts")]
+macro_rules! default_imp_for_payouts_upload_file {
+    ($($path:ident::$connector:ident),*) => {
+        $(
+            impl api::PayoutUploadFile for $path::$connector {}
+            impl
+            services::ConnectorIntegration<
+            api::PoUploadFile,
+            types::UploadFileRequestData,
+            types::UploadFileResponse,
+        > for $path::$connector
+        {}
+    )*
+    };
+}
+
+#[cfg(feature = "payouts")]
+#[cfg(feature = "dummy_connector")]
+impl<const T: u8> api::PayoutUploadFile for connector::DummyConnector<T> {}
+#[cfg(feature = "payouts")]
+#[cfg(feature = "dummy_connector")]
+impl<const T: u8>
+    services::ConnectorIntegration<
+        api::PoUploadFile,
+        types::UploadFileRequestData,
+        types::UploadFileResponse,
+    > for connector::DummyConnector<T>
+{
+}
+
+#[cfg(feature = "payouts")]
+default_imp_for_payouts_upload_file!(
+    connector::Aci,
+    connector::Airwallex,
+    connector::Authorizedotnet,
+    connector::Bambora,
+    connector::Bitpay,
+    connector::Bluesnap,
+    connector::Boku,
+    connector::Braintree,
+    connector::Cashtocode,
+    connector::Checkout,
+    connector::Cryptopay,
+    connector::Cybersource,
+    connector::Coinbase,
+    connector::Dlocal,
+    connector::Fiserv,
+    connector::Forte,
+    connector::Globalpay,
+    connector::Globepay,
+    connector::Iatapay,
+    connector::Klarna,
+    connector::Mollie,
+    connector::Multisafepay,
+    connector::Nexinets,
+    connector::Nmi,
+    connector::Noon,
+    connector::Nuvei,
+    connector::Opayo,
+    connector::Opennode,
+    connector::Payeezy,
+    connector::Payme,
+    connector::Paypal,
+    connector::Payu,
+    connector::Powertranz,
+    connector::Rapyd,
+    connector::Square,
+    connector::Stax,
+    connector::Stripe,
+    connector::Shift4,
+    connector::Trustpay,
+    connector::Tsys,
+    connector::Wise,
+    connector::Worldline,
+    connector::Worldpay,
+    connector::Zen
+);
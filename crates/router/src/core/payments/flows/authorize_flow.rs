@@ -6,6 +6,7 @@ use crate::{
     core::{
         errors::{self, ConnectorErrorExt, RouterResult},
         mandate,
+        payment_methods::network_tokenization,
         payments::{self, access_token, customers, tokenization, transformers, PaymentData},
     },
     logger,
@@ -36,7 +37,10 @@ impl
             types::PaymentsResponseData,
         >,
     > {
-        transformers::construct_payment_router_data::<api::Authorize, types::PaymentsAuthorizeData>(
+        let mut router_data = transformers::construct_payment_router_data::<
+            api::Authorize,
+            types::PaymentsAuthorizeData,
+        >(
             state,
             self.clone(),
             connector_id,
@@ -44,7 +48,22 @@ impl
             key_store,
             customer,
         )
-        .await
+        .await?;
+
+        if matches!(
+            router_data.request.payment_method_data,
+            api::PaymentMethodData::Card(_)
+        ) {
+            router_data.request.network_token_data =
+                network_tokenization::get_network_token_data_for_payment(
+                    state,
+                    connector_id,
+                    self.token.as_deref(),
+                )
+                .await;
+        }
+
+        Ok(router_data)
     }
 }
 #[async_trait]
@@ -58,6 +77,18 @@ impl Feature<api::Authorize, types::PaymentsAuthorizeData> for types::PaymentsAu
         merchant_account: &domain::MerchantAccount,
         connector_request: Option<services::Request>,
     ) -> RouterResult<Self> {
+        if matches!(
+            self.request.payment_method_data,
+            api::PaymentMethodData::CardPresent(_)
+        ) && !connector.connector.is_card_present_supported()
+        {
+            Err(error_stack::report!(
+                errors::ApiErrorResponse::NotSupported {
+                    message: format!("Card present payments via {}", connector.connector_name),
+                }
+            ))?
+        }
+
         let connector_integration: services::BoxedConnectorIntegration<
             '_,
             api::Authorize,
@@ -15,30 +15,35 @@ use time::Duration;
 use uuid::Uuid;
 
 use super::{
+    apple_pay,
     operations::{BoxedOperation, Operation, PaymentResponse},
     CustomerDetails, PaymentData,
 };
 use crate::{
-    configs::settings::{ConnectorRequestReferenceIdConfig, Server},
+    configs::settings::{ConnectorFilters, ConnectorRequestReferenceIdConfig, Server},
     consts,
     core::{
+        customers,
         errors::{self, CustomResult, RouterResult, StorageErrorExt},
         payment_methods::{cards, vault},
-        payments,
+        payments, utils as core_utils,
     },
     db::StorageInterface,
     routes::{metrics, AppState},
     scheduler::metrics as scheduler_metrics,
     services,
     types::{
-        api::{self, admin, enums as api_enums, CustomerAcceptanceExt, MandateValidationFieldsExt},
+        api::{
+            self, admin, enums as api_enums, CustomerAcceptanceExt, MandateValidationFieldsExt,
+            PaymentIdTypeExt,
+        },
         domain::{
             self,
             types::{self, AsyncLift},
         },
         storage::{self, enums as storage_enums, ephemeral_key, CustomerUpdate::Update},
         transformers::ForeignTryFrom,
-        ErrorResponse, RouterData,
+        ConnectorClientCertificate, ErrorResponse, RouterData,
     },
     utils::{
         self,
@@ -74,6 +79,156 @@ pub fn create_identity_from_certificate_and_key(
         .change_context(errors::ApiClientError::CertificateDecodeFailed)
 }
 
+/// Returns the time at which the base64 encoded certificate (in PEM format) expires, that is the
+/// end of its validity period
+pub fn get_certificate_expiry(
+    encoded_certificate: String,
+) -> Result<time::PrimitiveDateTime, error_stack::Report<errors::ApiClientError>> {
+    let decoded_certificate = consts::BASE64_ENGINE
+        .decode(encoded_certificate)
+        .into_report()
+        .change_context(errors::ApiClientError::CertificateDecodeFailed)?;
+
+    let certificate = String::from_utf8(decoded_certificate)
+        .into_report()
+        .change_context(errors::ApiClientError::CertificateDecodeFailed)?;
+
+    certificate
+        .split("-----BEGIN CERTIFICATE-----")
+        .nth(1)
+        .and_then(|certificate| certificate.split("-----END CERTIFICATE-----").next())
+        .map(|certificate| certificate.split_whitespace().collect::<String>())
+        .and_then(|certificate| consts::BASE64_ENGINE.decode(certificate).ok())
+        .and_then(|der_certificate| get_certificate_not_after(&der_certificate))
+        .ok_or(errors::ApiClientError::CertificateDecodeFailed)
+        .into_report()
+        .attach_printable("Failed to read the validity period of the certificate")
+}
+
+const X509_VERSION_TAG: u8 = 0xa0;
+const X509_EXTENSIONS_TAG: u8 = 0xa3;
+const OBJECT_IDENTIFIER_TAG: u8 = 0x06;
+const BOOLEAN_TAG: u8 = 0x01;
+const UTC_TIME_TAG: u8 = 0x17;
+const GENERALIZED_TIME_TAG: u8 = 0x18;
+
+/// Reads the end of the validity period of a DER encoded X.509 certificate
+fn get_certificate_not_after(der_certificate: &[u8]) -> Option<time::PrimitiveDateTime> {
+    // Certificate ::= SEQUENCE { tbsCertificate, signatureAlgorithm, signatureValue }
+    let (_, certificate, _) = split_der_value(der_certificate)?;
+    // TBSCertificate ::= SEQUENCE { [0] version OPTIONAL, serialNumber, signature, issuer,
+    // validity, ... }
+    let (_, tbs_certificate, _) = split_der_value(certificate)?;
+    let (tag, _, rest) = split_der_value(tbs_certificate)?;
+    let rest = if tag == X509_VERSION_TAG {
+        split_der_value(rest)?.2
+    } else {
+        rest
+    };
+    let (_, _, rest) = split_der_value(rest)?;
+    let (_, _, rest) = split_der_value(rest)?;
+    // Validity ::= SEQUENCE { notBefore Time, notAfter Time }
+    let (_, validity, _) = split_der_value(rest)?;
+    let (_, _, validity) = split_der_value(validity)?;
+    let (tag, not_after, _) = split_der_value(validity)?;
+
+    parse_certificate_time(tag, not_after)
+}
+
+/// Returns the value of the extension of a DER encoded X.509 certificate with the DER encoded
+/// object identifier, that is the contents of the `extnValue` octet string of the extension
+pub fn get_certificate_extension<'a>(der_certificate: &'a [u8], oid: &[u8]) -> Option<&'a [u8]> {
+    let (_, certificate, _) = split_der_value(der_certificate)?;
+    let (_, tbs_certificate, _) = split_der_value(certificate)?;
+    // The extensions are the last field of the TBSCertificate, tagged with [3]
+    let mut rest = tbs_certificate;
+    let mut extensions = loop {
+        let (tag, contents, remaining) = split_der_value(rest)?;
+        if tag == X509_EXTENSIONS_TAG {
+            break split_der_value(contents)?.1;
+        }
+        rest = remaining;
+    };
+
+    // Extension ::= SEQUENCE { extnID OBJECT IDENTIFIER, critical BOOLEAN DEFAULT FALSE,
+    // extnValue OCTET STRING }
+    while !extensions.is_empty() {
+        let (_, extension, remaining) = split_der_value(extensions)?;
+        extensions = remaining;
+        let (tag, extension_oid, rest) = split_der_value(extension)?;
+        if tag != OBJECT_IDENTIFIER_TAG || extension_oid != oid {
+            continue;
+        }
+        let (tag, value, rest) = split_der_value(rest)?;
+        let value = if tag == BOOLEAN_TAG {
+            split_der_value(rest)?.1
+        } else {
+            value
+        };
+        return Some(value);
+    }
+
+    None
+}
+
+/// Splits the DER encoded value at the start of the input into its tag, its contents and the
+/// remaining input
+pub fn split_der_value(der: &[u8]) -> Option<(u8, &[u8], &[u8])> {
+    let (&tag, rest) = der.split_first()?;
+    let (&length, rest) = rest.split_first()?;
+    let (length, rest) = if length & 0x80 == 0 {
+        (usize::from(length), rest)
+    } else {
+        // The length is encoded in the following bytes, in big endian order
+        let length_size = usize::from(length & 0x7f);
+        if length_size == 0
+            || length_size > std::mem::size_of::<usize>()
+            || rest.len() < length_size
+        {
+            return None;
+        }
+        let (length, rest) = rest.split_at(length_size);
+        let length = length
+            .iter()
+            .fold(0, |length, byte| (length << 8) | usize::from(*byte));
+        (length, rest)
+    };
+    if rest.len() < length {
+        return None;
+    }
+    let (contents, rest) = rest.split_at(length);
+
+    Some((tag, contents, rest))
+}
+
+/// Parses a `UTCTime` (`YYMMDDHHMMSSZ`) or `GeneralizedTime` (`YYYYMMDDHHMMSSZ`), the formats of
+/// the times in X.509 certificates and in the signing time attribute of PKCS #7 signatures
+pub fn parse_certificate_time(tag: u8, value: &[u8]) -> Option<time::PrimitiveDateTime> {
+    let value = std::str::from_utf8(value).ok()?.strip_suffix('Z')?;
+    if !value.bytes().all(|byte| byte.is_ascii_digit()) {
+        return None;
+    }
+    let (year, rest) = match (tag, value.len()) {
+        // Two digit years from 50 onwards are in the 20th century
+        (UTC_TIME_TAG, 12) => {
+            let year = value.get(..2)?.parse::<i32>().ok()?;
+            (
+                if year < 50 { 2000 + year } else { 1900 + year },
+                value.get(2..)?,
+            )
+        }
+        (GENERALIZED_TIME_TAG, 14) => (value.get(..4)?.parse().ok()?, value.get(4..)?),
+        _ => return None,
+    };
+    let field = |index: usize| rest.get(index..index + 2)?.parse::<u8>().ok();
+    let date =
+        time::Date::from_calendar_date(year, time::Month::try_from(field(0)?).ok()?, field(2)?)
+            .ok()?;
+    let time = time::Time::from_hms(field(4)?, field(6)?, field(8)?).ok()?;
+
+    Some(time::PrimitiveDateTime::new(date, time))
+}
+
 pub fn filter_mca_based_on_business_details(
     merchant_connector_accounts: Vec<domain::MerchantConnectorAccount>,
     payment_intent: Option<&diesel_models::payment_intent::PaymentIntent>,
@@ -197,6 +352,7 @@ pub async fn get_address_for_payment_request(
                                     customer_id: customer_id.to_string(),
                                     merchant_id: merchant_id.to_string(),
                                     address_id: generate_id(consts::ID_LENGTH, "add"),
+                                    is_address_book_entry: false,
                                     city: address_details.city,
                                     country: address_details.country,
                                     line1: address_details
@@ -253,6 +409,166 @@ pub async fn get_address_for_payment_request(
     })
 }
 
+/// Get the address on file of the customer, if the customer exists and has an address on file
+pub async fn get_customer_address_on_file(
+    db: &dyn StorageInterface,
+    customer_id: Option<&String>,
+    merchant_id: &str,
+    merchant_key_store: &domain::MerchantKeyStore,
+) -> RouterResult<Option<domain::Address>> {
+    let Some(customer_id) = customer_id else {
+        return Ok(None);
+    };
+
+    let address_id = db
+        .find_customer_optional_by_customer_id_merchant_id(
+            customer_id,
+            merchant_id,
+            merchant_key_store,
+        )
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed while fetching customer")?
+        .and_then(|customer| customer.address_id);
+
+    match address_id {
+        Some(address_id) => db
+            .find_address(&address_id, merchant_key_store)
+            .await
+            .to_not_found_response(errors::ApiErrorResponse::AddressNotFound)
+            .map(Some),
+        None => Ok(None),
+    }
+}
+
+/// Insert a copy of the address on file or an address book entry of the customer for a payment,
+/// so that updating the address of the payment does not modify the address of the customer
+pub async fn insert_customer_address_for_payment(
+    db: &dyn StorageInterface,
+    customer_address: &domain::Address,
+    merchant_key_store: &domain::MerchantKeyStore,
+) -> RouterResult<domain::Address> {
+    let now = common_utils::date_time::now();
+    db.insert_address(
+        domain::Address {
+            id: None,
+            address_id: generate_id(consts::ID_LENGTH, "add"),
+            created_at: now,
+            modified_at: now,
+            is_address_book_entry: false,
+            ..customer_address.clone()
+        },
+        merchant_key_store,
+    )
+    .await
+    .change_context(errors::ApiErrorResponse::InternalServerError)
+    .attach_printable("Failed while inserting customer address for payment")
+}
+
+/// Get the shipping and billing addresses from the address book of the customer to be used for a
+/// payment. An address omitted in the request is taken from the address referenced by its ID in
+/// the request, or otherwise from the default address of the customer.
+pub async fn get_address_book_entries_for_payment(
+    db: &dyn StorageInterface,
+    request: &api::PaymentsRequest,
+    customer_id: Option<&String>,
+    merchant_id: &str,
+    merchant_key_store: &domain::MerchantKeyStore,
+) -> RouterResult<(Option<domain::Address>, Option<domain::Address>)> {
+    utils::when(
+        request.shipping.is_some() && request.shipping_address_id.is_some(),
+        || {
+            Err(report!(errors::ApiErrorResponse::PreconditionFailed {
+                message: "`shipping` and `shipping_address_id` cannot be provided together"
+                    .to_string(),
+            }))
+        },
+    )?;
+    utils::when(
+        request.billing.is_some() && request.billing_address_id.is_some(),
+        || {
+            Err(report!(errors::ApiErrorResponse::PreconditionFailed {
+                message: "`billing` and `billing_address_id` cannot be provided together"
+                    .to_string(),
+            }))
+        },
+    )?;
+
+    let Some(customer_id) = customer_id else {
+        utils::when(
+            request.shipping_address_id.is_some() || request.billing_address_id.is_some(),
+            || {
+                Err(report!(errors::ApiErrorResponse::MissingRequiredField {
+                    field_name: "customer_id"
+                }))
+            },
+        )?;
+        return Ok((None, None));
+    };
+
+    let requires_default_address = (request.shipping.is_none()
+        && request.shipping_address_id.is_none())
+        || (request.billing.is_none() && request.billing_address_id.is_none());
+    let customer = if requires_default_address {
+        db.find_customer_optional_by_customer_id_merchant_id(
+            customer_id,
+            merchant_id,
+            merchant_key_store,
+        )
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed while fetching customer")?
+    } else {
+        None
+    };
+
+    let shipping_address_id = match request.shipping {
+        Some(_) => None,
+        None => request.shipping_address_id.clone().or_else(|| {
+            customer
+                .as_ref()
+                .and_then(|customer| customer.default_shipping_address_id.clone())
+        }),
+    };
+    let billing_address_id = match request.billing {
+        Some(_) => None,
+        None => request.billing_address_id.clone().or_else(|| {
+            customer
+                .as_ref()
+                .and_then(|customer| customer.default_billing_address_id.clone())
+        }),
+    };
+
+    let shipping_address = shipping_address_id
+        .async_map(|address_id| async move {
+            customers::find_address_book_entry(
+                db,
+                merchant_id,
+                customer_id,
+                &address_id,
+                merchant_key_store,
+            )
+            .await
+        })
+        .await
+        .transpose()?;
+    let billing_address = billing_address_id
+        .async_map(|address_id| async move {
+            customers::find_address_book_entry(
+                db,
+                merchant_id,
+                customer_id,
+                &address_id,
+                merchant_key_store,
+            )
+            .await
+        })
+        .await
+        .transpose()?;
+
+    Ok((shipping_address, billing_address))
+}
+
 pub async fn get_address_by_id(
     db: &dyn StorageInterface,
     address_id: Option<String>,
@@ -923,6 +1239,69 @@ fn validate_options_for_inequality<T: PartialEq>(
     )
 }
 
+// Checks if the return url is on one of the domains allowed by the merchant, so that the
+// redirect completion pages cannot be used to redirect customers to arbitrary sites
+pub fn validate_return_url_domain(
+    return_url: Option<&url::Url>,
+    merchant_account: &domain::MerchantAccount,
+) -> RouterResult<()> {
+    if let Some(return_url) = return_url {
+        core_utils::validate_url_against_allowed_domains(
+            return_url.as_str(),
+            "return_url",
+            merchant_account.allowed_domains.as_deref(),
+        )?;
+    }
+    Ok(())
+}
+
+/// Validates the level 2 and level 3 data of a payment, whose amounts may not be negative and whose
+/// line items must be for at least one unit of the product
+pub fn validate_l2_l3_data(
+    l2_l3_data: Option<&api_models::payments::L2L3Data>,
+) -> RouterResult<()> {
+    let Some(l2_l3_data) = l2_l3_data else {
+        return Ok(());
+    };
+
+    let invalid_data = |message: &str| {
+        Err(report!(errors::ApiErrorResponse::InvalidRequestData {
+            message: format!("l2_l3_data: {message}"),
+        }))
+    };
+
+    if l2_l3_data
+        .customer_reference
+        .as_ref()
+        .map_or(false, |customer_reference| customer_reference.len() > 25)
+    {
+        return invalid_data("customer_reference must not exceed 25 characters");
+    }
+
+    let is_negative = |amount: Option<i64>| amount.map_or(false, |amount| amount < 0);
+
+    if is_negative(l2_l3_data.tax_amount) || is_negative(l2_l3_data.shipping_amount) {
+        return invalid_data("tax_amount and shipping_amount must not be negative");
+    }
+
+    for line_item in l2_l3_data.line_items.iter().flatten() {
+        if line_item.description.trim().is_empty() {
+            return invalid_data("the description of a line item must not be empty");
+        }
+        if line_item.quantity == 0 {
+            return invalid_data("the quantity of a line item must be greater than zero");
+        }
+        if line_item.unit_amount < 0
+            || is_negative(line_item.tax_amount)
+            || is_negative(line_item.discount_amount)
+        {
+            return invalid_data("the amounts of a line item must not be negative");
+        }
+    }
+
+    Ok(())
+}
+
 // Checks if the customer details are passed in both places
 // If so, raise an error
 pub fn validate_customer_details_in_request(
@@ -1078,6 +1457,7 @@ pub async fn create_customer_if_not_exist<'a, F: Clone, R>(
                                 description: None,
                                 connector_customer: None,
                                 metadata: None,
+                                address_id: None,
                             })
                         }
                         .await
@@ -1124,6 +1504,10 @@ pub async fn create_customer_if_not_exist<'a, F: Clone, R>(
                             metadata: None,
                             modified_at: common_utils::date_time::now(),
                             connector_customer: None,
+                            address_id: None,
+                            default_shipping_address_id: None,
+                            default_billing_address_id: None,
+                            default_payment_method_id: None,
                         })
                     }
                     .await
@@ -1239,6 +1623,7 @@ pub async fn make_pm_data<'a, F: Clone, R>(
                             &updated_pm,
                             payment_data.payment_intent.customer_id.to_owned(),
                             enums::PaymentMethod::Card,
+                            &payment_data.payment_intent.merchant_id,
                         )
                         .await?;
                         Some(updated_pm)
@@ -1279,6 +1664,7 @@ pub async fn make_pm_data<'a, F: Clone, R>(
                 pm,
                 payment_data.payment_intent.customer_id.to_owned(),
                 enums::PaymentMethod::Card,
+                &payment_data.payment_intent.merchant_id,
             )
             .await?;
             payment_data.token = Some(token);
@@ -1292,6 +1678,15 @@ pub async fn make_pm_data<'a, F: Clone, R>(
         (pm @ Some(api::PaymentMethodData::Reward(_)), _) => Ok(pm.to_owned()),
         (pm @ Some(api::PaymentMethodData::CardRedirect(_)), _) => Ok(pm.to_owned()),
         (pm @ Some(api::PaymentMethodData::GiftCard(_)), _) => Ok(pm.to_owned()),
+        (pm @ Some(api::PaymentMethodData::CardPresent(card_present_data)), _) => {
+            validate_card_present_terminal(
+                &*state.store,
+                &payment_data.payment_intent.merchant_id,
+                card_present_data,
+            )
+            .await?;
+            Ok(pm.to_owned())
+        }
         (pm_opt @ Some(pm @ api::PaymentMethodData::BankTransfer(_)), _) => {
             let token = vault::Vault::store_payment_method_data_in_locker(
                 state,
@@ -1299,6 +1694,7 @@ pub async fn make_pm_data<'a, F: Clone, R>(
                 pm,
                 payment_data.payment_intent.customer_id.to_owned(),
                 enums::PaymentMethod::BankTransfer,
+                &payment_data.payment_intent.merchant_id,
             )
             .await?;
             payment_data.token = Some(token);
@@ -1311,6 +1707,7 @@ pub async fn make_pm_data<'a, F: Clone, R>(
                 pm,
                 payment_data.payment_intent.customer_id.to_owned(),
                 enums::PaymentMethod::Wallet,
+                &payment_data.payment_intent.merchant_id,
             )
             .await?;
             payment_data.token = Some(token);
@@ -1323,6 +1720,7 @@ pub async fn make_pm_data<'a, F: Clone, R>(
                 pm,
                 payment_data.payment_intent.customer_id.to_owned(),
                 enums::PaymentMethod::BankRedirect,
+                &payment_data.payment_intent.merchant_id,
             )
             .await?;
             payment_data.token = Some(token);
@@ -1351,6 +1749,50 @@ pub(crate) fn validate_capture_method(
     )
 }
 
+/// Returns the capture method to be sent to the connector.
+///
+/// Payments with a delayed automatic capture are authorized as manual capture payments with the
+/// connector, the capture is later triggered by the scheduler.
+pub fn get_connector_capture_method(
+    capture_method: Option<storage_enums::CaptureMethod>,
+) -> Option<storage_enums::CaptureMethod> {
+    match capture_method {
+        Some(storage_enums::CaptureMethod::AutomaticDelayed) => {
+            Some(storage_enums::CaptureMethod::Manual)
+        }
+        capture_method => capture_method,
+    }
+}
+
+/// Validates that the connector supports the capture method of the payment. Payments with a
+/// manual or delayed capture are rejected for connectors which only capture payments as they are
+/// authorized, since such connectors cannot hold the funds until the payment is captured.
+pub fn validate_capture_method_for_connector(
+    state: &AppState,
+    capture_method: Option<storage_enums::CaptureMethod>,
+    connector_name: api_enums::Connector,
+) -> RouterResult<()> {
+    let is_capture_deferred = !matches!(
+        get_connector_capture_method(capture_method),
+        Some(storage_enums::CaptureMethod::Automatic) | None
+    );
+    utils::when(
+        is_capture_deferred
+            && state
+                .conf
+                .connector_capabilities
+                .auto_capture_only_connectors
+                .contains(&connector_name),
+        || {
+            Err(report!(errors::ApiErrorResponse::NotSupported {
+                message: format!(
+                    "The {connector_name} connector only supports capturing payments as they are authorized, create the payment with `capture_method` set to `automatic` or route it through a connector supporting manual capture"
+                ),
+            }))
+        },
+    )
+}
+
 #[instrument(skip_all)]
 pub(crate) fn validate_status(status: storage_enums::IntentStatus) -> RouterResult<()> {
     utils::when(
@@ -1644,6 +2086,41 @@ pub(super) fn validate_payment_list_request(
     Ok(())
 }
 
+#[cfg(feature = "olap")]
+pub(super) fn validate_payment_list_filter_request(
+    req: &api::PaymentListFilterConstraints,
+) -> CustomResult<(), errors::ApiErrorResponse> {
+    utils::when(
+        req.limit.map_or(false, |limit| !(1..=100).contains(&limit)),
+        || {
+            Err(errors::ApiErrorResponse::InvalidRequestData {
+                message: "limit should be in between 1 and 100".to_string(),
+            })
+        },
+    )?;
+    utils::when(
+        req.amount.map_or(false, |amount| {
+            matches!((amount.start_amount, amount.end_amount), (Some(start), Some(end)) if start > end)
+        }),
+        || {
+            Err(errors::ApiErrorResponse::InvalidRequestData {
+                message: "start_amount should not be greater than end_amount".to_string(),
+            })
+        },
+    )?;
+    utils::when(req.offset.map_or(false, |offset| offset < 0), || {
+        Err(errors::ApiErrorResponse::InvalidRequestData {
+            message: "offset should not be negative".to_string(),
+        })
+    })?;
+    utils::when(req.offset.is_some() && req.starting_after.is_some(), || {
+        Err(errors::ApiErrorResponse::InvalidRequestData {
+            message: "offset and starting_after cannot be used together".to_string(),
+        })
+    })?;
+    Ok(())
+}
+
 pub fn get_handle_response_url(
     payment_id: String,
     merchant_account: &domain::MerchantAccount,
@@ -1990,54 +2467,376 @@ pub(crate) fn validate_pm_or_token_given(
     )
 }
 
-// A function to perform database lookup and then verify the client secret
-pub async fn verify_payment_intent_time_and_client_secret(
+/// Ensure that the CVV is passed when a saved card is charged in an on-session payment, unless the
+/// merchant does not require the CVV for saved cards
+pub async fn validate_cvv_for_saved_card(
     db: &dyn StorageInterface,
-    merchant_account: &domain::MerchantAccount,
-    client_secret: Option<String>,
-) -> error_stack::Result<Option<storage::PaymentIntent>, errors::ApiErrorResponse> {
-    client_secret
-        .async_map(|cs| async move {
-            let payment_id = get_payment_id_from_client_secret(&cs)?;
-
-            let payment_intent = db
-                .find_payment_intent_by_payment_id_merchant_id(
-                    &payment_id,
-                    &merchant_account.merchant_id,
-                    merchant_account.storage_scheme,
-                )
-                .await
-                .change_context(errors::ApiErrorResponse::PaymentNotFound)?;
+    merchant_id: &str,
+    request: &api::PaymentsRequest,
+    token: &Option<String>,
+    payment_method: Option<storage_enums::PaymentMethod>,
+    off_session: Option<bool>,
+    mandate_type: &Option<api::MandateTransactionType>,
+) -> RouterResult<()> {
+    let is_saved_card_payment = token.is_some()
+        && request.payment_method_data.is_none()
+        && payment_method == Some(storage_enums::PaymentMethod::Card);
+    let is_on_session = off_session != Some(true) && mandate_type.is_none();
+
+    if is_saved_card_payment
+        && is_on_session
+        && request.card_cvc.is_none()
+        && cards::is_cvv_required_for_saved_card(db, merchant_id).await?
+    {
+        Err(report!(errors::ApiErrorResponse::MissingRequiredField {
+            field_name: "card_cvc"
+        }))
+        .attach_printable("CVV is required for payments with saved cards")?
+    }
 
-            authenticate_client_secret(
-                Some(&cs),
-                &payment_intent,
-                merchant_account.intent_fulfillment_time,
-            )?;
-            Ok(payment_intent)
-        })
-        .await
-        .transpose()
+    Ok(())
 }
 
-fn connector_needs_business_sub_label(connector_name: &str) -> bool {
-    let connectors_list = [api_models::enums::Connector::Cybersource];
-    connectors_list
-        .map(|connector| connector.to_string())
-        .contains(&connector_name.to_string())
+/// Ensure that an invoice is open for payments: it must neither be paid in full nor void, and
+/// must not be past its due date
+pub fn validate_invoice_is_payable(invoice: &storage::Invoice) -> RouterResult<()> {
+    utils::when(
+        !matches!(
+            invoice.status,
+            storage_enums::InvoiceStatus::Open | storage_enums::InvoiceStatus::PartiallyPaid
+        ),
+        || {
+            Err(report!(errors::ApiErrorResponse::PreconditionFailed {
+                message: format!(
+                    "Invoice {} cannot be paid as it is {}",
+                    invoice.invoice_id, invoice.status
+                ),
+            }))
+        },
+    )?;
+    utils::when(
+        invoice
+            .due_date
+            .map_or(false, |due_date| due_date < common_utils::date_time::now()),
+        || {
+            Err(report!(errors::ApiErrorResponse::PreconditionFailed {
+                message: format!(
+                    "Invoice {} cannot be paid as it is past its due date",
+                    invoice.invoice_id
+                ),
+            }))
+        },
+    )
 }
 
-/// Create the connector label
-/// {connector_name}_{country}_{business_label}
-pub fn get_connector_label(
-    business_country: api_models::enums::CountryAlpha2,
-    business_label: &str,
-    business_sub_label: Option<&String>,
-    connector_name: &str,
-) -> String {
-    let mut connector_label = format!("{connector_name}_{business_country}_{business_label}");
-
-    // Business sub label is currently being used only for cybersource
+/// Ensure that a payment applied to an invoice can still be accepted by it: the invoice must be
+/// open for payments, and the payment must be in the currency of the invoice and must not exceed
+/// the amount due
+pub async fn validate_invoice_payment(
+    db: &dyn StorageInterface,
+    merchant_id: &str,
+    invoice_id: &str,
+    amount: i64,
+    currency: storage_enums::Currency,
+) -> RouterResult<()> {
+    let invoice = db
+        .find_invoice_by_merchant_id_invoice_id(merchant_id, invoice_id)
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::InvoiceNotFound {
+            invoice_id: invoice_id.to_owned(),
+        })?;
+
+    validate_invoice_is_payable(&invoice)?;
+    utils::when(invoice.currency != currency, || {
+        Err(report!(errors::ApiErrorResponse::InvalidRequestData {
+            message: format!(
+                "The currency of the payment must match the currency of the invoice, {}",
+                invoice.currency
+            ),
+        }))
+    })?;
+
+    let amount_due = invoice.amount - invoice.amount_paid;
+    utils::when(amount > amount_due, || {
+        Err(report!(errors::ApiErrorResponse::InvalidRequestData {
+            message: format!(
+                "The amount of the payment must not exceed the amount due on the invoice, {amount_due}"
+            ),
+        }))
+    })
+}
+
+/// Compute the tax on the convenience fee of a payment as per its tax treatment. The fee,
+/// including tax, must be covered by the amount of the payment
+pub fn get_convenience_fee_details(
+    convenience_fee: &api_models::payments::ConvenienceFee,
+    payment_amount: i64,
+) -> RouterResult<api_models::payments::ConvenienceFeeDetails> {
+    use api_models::payments::ConvenienceFeeTaxTreatment;
+
+    utils::when(convenience_fee.amount <= 0, || {
+        Err(report!(errors::ApiErrorResponse::InvalidRequestData {
+            message: "The amount of the convenience fee must be greater than zero".to_string(),
+        }))
+    })?;
+
+    let tax_rate = match (convenience_fee.tax_treatment, convenience_fee.tax_rate) {
+        (ConvenienceFeeTaxTreatment::Exempt, _) => 0,
+        (_, Some(tax_rate)) if i64::from(tax_rate) <= consts::BASIS_POINTS_DENOMINATOR => {
+            i64::from(tax_rate)
+        }
+        (_, Some(_)) => {
+            return Err(report!(errors::ApiErrorResponse::InvalidRequestData {
+                message: format!(
+                    "The tax rate of the convenience fee must not exceed {} basis points",
+                    consts::BASIS_POINTS_DENOMINATOR
+                ),
+            }))
+        }
+        (_, None) => {
+            return Err(report!(errors::ApiErrorResponse::MissingRequiredField {
+                field_name: "convenience_fee.tax_rate",
+            }))
+        }
+    };
+
+    // Tax is rounded half up to the lowest denomination of the currency
+    let get_tax_amount = |amount: i64, denominator: i64| {
+        let tax_amount = (i128::from(amount) * i128::from(tax_rate) * 2 + i128::from(denominator))
+            / (i128::from(denominator) * 2);
+        i64::try_from(tax_amount).into_report().change_context(
+            errors::ApiErrorResponse::InvalidRequestData {
+                message: "The amount of the convenience fee is too large".to_string(),
+            },
+        )
+    };
+
+    let (amount, tax_amount) = match convenience_fee.tax_treatment {
+        ConvenienceFeeTaxTreatment::Exempt => (convenience_fee.amount, 0),
+        ConvenienceFeeTaxTreatment::Inclusive => {
+            let tax_amount = get_tax_amount(
+                convenience_fee.amount,
+                consts::BASIS_POINTS_DENOMINATOR + tax_rate,
+            )?;
+            (convenience_fee.amount - tax_amount, tax_amount)
+        }
+        ConvenienceFeeTaxTreatment::Exclusive => (
+            convenience_fee.amount,
+            get_tax_amount(convenience_fee.amount, consts::BASIS_POINTS_DENOMINATOR)?,
+        ),
+    };
+
+    let total_amount = amount.saturating_add(tax_amount);
+    utils::when(total_amount > payment_amount, || {
+        Err(report!(errors::ApiErrorResponse::InvalidRequestData {
+            message: format!(
+                "The convenience fee, including tax, must not exceed the amount of the payment, {payment_amount}"
+            ),
+        }))
+    })?;
+
+    Ok(api_models::payments::ConvenienceFeeDetails {
+        amount,
+        tax_amount,
+        total_amount,
+        description: convenience_fee.description.clone(),
+        tax_treatment: convenience_fee.tax_treatment,
+        tax_rate: convenience_fee.tax_rate,
+    })
+}
+
+/// Get the convenience fee of a payment, along with the tax charged on it
+pub fn get_convenience_fee(
+    payment_intent: &storage::PaymentIntent,
+) -> RouterResult<Option<api_models::payments::ConvenienceFeeDetails>> {
+    payment_intent
+        .convenience_fee
+        .clone()
+        .map(|convenience_fee| {
+            convenience_fee
+                .parse_value::<api_models::payments::ConvenienceFeeDetails>("ConvenienceFeeDetails")
+                .change_context(errors::ApiErrorResponse::InternalServerError)
+                .attach_printable("Unable to parse ConvenienceFeeDetails")
+        })
+        .transpose()
+}
+
+/// Get the level 2 and level 3 data of a payment
+pub fn get_l2_l3_data(
+    payment_intent: &storage::PaymentIntent,
+) -> RouterResult<Option<api_models::payments::L2L3Data>> {
+    payment_intent
+        .l2_l3_data
+        .clone()
+        .map(|l2_l3_data| {
+            l2_l3_data
+                .parse_value::<api_models::payments::L2L3Data>("L2L3Data")
+                .change_context(errors::ApiErrorResponse::InternalServerError)
+                .attach_printable("Unable to parse L2L3Data")
+        })
+        .transpose()
+}
+
+/// Ensure that the amount of a payment still covers its convenience fee, including tax, when the
+/// amount of the payment is updated
+pub fn validate_amount_against_convenience_fee(
+    payment_intent: &storage::PaymentIntent,
+    amount: i64,
+) -> RouterResult<()> {
+    let convenience_fee = get_convenience_fee(payment_intent)?;
+
+    match convenience_fee {
+        Some(convenience_fee) if convenience_fee.total_amount > amount => {
+            Err(report!(errors::ApiErrorResponse::InvalidRequestData {
+                message: format!(
+                    "The amount of the payment must cover the convenience fee, including tax, {}",
+                    convenience_fee.total_amount
+                ),
+            }))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Parses the time for which a payment retrieve request waits for a terminal status of the
+/// payment, given as a number of seconds with an optional `s` suffix, such as `30s`.
+pub fn get_payment_retrieve_wait_time(wait: &str) -> RouterResult<std::time::Duration> {
+    let wait_time = wait
+        .trim()
+        .strip_suffix('s')
+        .unwrap_or(wait.trim())
+        .parse::<u64>()
+        .ok()
+        .filter(|seconds| *seconds <= consts::MAX_PAYMENT_RETRIEVE_WAIT_TIME)
+        .ok_or(report!(errors::ApiErrorResponse::InvalidRequestData {
+            message: format!(
+                "`wait` must be a number of seconds such as `30s`, of at most {}s",
+                consts::MAX_PAYMENT_RETRIEVE_WAIT_TIME
+            ),
+        }))?;
+    Ok(std::time::Duration::from_secs(wait_time))
+}
+
+/// Parse the comma separated list of related resources to be embedded in the response of a
+/// payment retrieve request
+pub fn get_payment_retrieve_expand(
+    expand: &str,
+) -> RouterResult<Vec<api_models::payments::PaymentExpandableResource>> {
+    expand
+        .split(',')
+        .map(str::trim)
+        .filter(|resource| !resource.is_empty())
+        .map(|resource| {
+            resource
+                .parse::<api_models::payments::PaymentExpandableResource>()
+                .into_report()
+                .change_context(errors::ApiErrorResponse::InvalidRequestData {
+                    message: format!(
+                        "`{resource}` can't be expanded, `expand` must be a comma separated list out of `attempts`, `captures`, `refunds`, `disputes` and `customer`"
+                    ),
+                })
+        })
+        .collect()
+}
+
+/// Returns whether the related resource is to be embedded in the response of a payment retrieve
+/// request, `None` when the request does not list the resources to expand
+pub fn is_payment_resource_expanded(
+    expand: Option<&[api_models::payments::PaymentExpandableResource]>,
+    resource: api_models::payments::PaymentExpandableResource,
+) -> Option<bool> {
+    expand.map(|expand| expand.contains(&resource))
+}
+
+/// Get the order details of a payment to be sent to the connector. The convenience fee and the
+/// cross-border surcharge of the payment are added to them as lines of their own, so that the
+/// order details account for the entire amount of the payment
+pub fn get_order_details_for_connector(
+    payment_intent: &storage::PaymentIntent,
+) -> RouterResult<Option<Vec<api_models::payments::OrderDetailsWithAmount>>> {
+    let order_details = payment_intent
+        .order_details
+        .as_ref()
+        .map(|order_details| {
+            order_details
+                .iter()
+                .map(|data| {
+                    data.to_owned()
+                        .parse_value("OrderDetailsWithAmount")
+                        .change_context(errors::ApiErrorResponse::InvalidDataValue {
+                            field_name: "OrderDetailsWithAmount",
+                        })
+                        .attach_printable("Unable to parse OrderDetailsWithAmount")
+                })
+                .collect::<Result<Vec<_>, _>>()
+        })
+        .transpose()?;
+
+    let convenience_fee = get_convenience_fee(payment_intent)?;
+
+    // The cross-border surcharge is not listed, as it is already part of the amount of the order
+    Ok(order_details.map(|mut order_details| {
+        if let Some(convenience_fee) = convenience_fee {
+            order_details.push(api_models::payments::OrderDetailsWithAmount {
+                product_name: convenience_fee
+                    .description
+                    .unwrap_or_else(|| consts::DEFAULT_CONVENIENCE_FEE_DESCRIPTION.to_string()),
+                quantity: 1,
+                amount: convenience_fee.total_amount,
+            });
+        }
+        order_details
+    }))
+}
+
+// A function to perform database lookup and then verify the client secret
+pub async fn verify_payment_intent_time_and_client_secret(
+    db: &dyn StorageInterface,
+    merchant_account: &domain::MerchantAccount,
+    client_secret: Option<String>,
+) -> error_stack::Result<Option<storage::PaymentIntent>, errors::ApiErrorResponse> {
+    client_secret
+        .async_map(|cs| async move {
+            let payment_id = get_payment_id_from_client_secret(&cs)?;
+
+            let payment_intent = db
+                .find_payment_intent_by_payment_id_merchant_id(
+                    &payment_id,
+                    &merchant_account.merchant_id,
+                    merchant_account.storage_scheme,
+                )
+                .await
+                .change_context(errors::ApiErrorResponse::PaymentNotFound)?;
+
+            authenticate_client_secret(
+                Some(&cs),
+                &payment_intent,
+                merchant_account.intent_fulfillment_time,
+            )?;
+            Ok(payment_intent)
+        })
+        .await
+        .transpose()
+}
+
+fn connector_needs_business_sub_label(connector_name: &str) -> bool {
+    let connectors_list = [api_models::enums::Connector::Cybersource];
+    connectors_list
+        .map(|connector| connector.to_string())
+        .contains(&connector_name.to_string())
+}
+
+/// Create the connector label
+/// {connector_name}_{country}_{business_label}
+pub fn get_connector_label(
+    business_country: api_models::enums::CountryAlpha2,
+    business_label: &str,
+    business_sub_label: Option<&String>,
+    connector_name: &str,
+) -> String {
+    let mut connector_label = format!("{connector_name}_{business_country}_{business_label}");
+
+    // Business sub label is currently being used only for cybersource
     // To ensure backwards compatibality, cybersource mca's created before this change
     // will have the business_sub_label value as default.
     //
@@ -2114,6 +2913,83 @@ pub fn get_business_details(
     }
 }
 
+/// Get the defaults configured for the business under which the payment is made, if any
+pub fn get_business_profile_defaults(
+    business_country: api_enums::CountryAlpha2,
+    business_label: &str,
+    merchant_account: &domain::MerchantAccount,
+) -> RouterResult<Option<api_models::admin::PrimaryBusinessDetails>> {
+    let primary_business_details = merchant_account
+        .primary_business_details
+        .clone()
+        .parse_value::<Vec<api_models::admin::PrimaryBusinessDetails>>("PrimaryBusinessDetails")
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("failed to parse primary business details")?;
+
+    Ok(primary_business_details
+        .into_iter()
+        .find(|business_details| {
+            business_details.business == business_label
+                && business_details.country == business_country
+        }))
+}
+
+/// Merge the business level defaults into the connector account metadata so that connectors can
+/// read them from a single place. Keys already present in the connector account metadata take
+/// precedence over the business level defaults.
+pub fn merge_business_profile_defaults_into_metadata(
+    business_profile_defaults: Option<&api_models::admin::PrimaryBusinessDetails>,
+    connector_metadata: Option<masking::Secret<serde_json::Value>>,
+) -> RouterResult<Option<masking::Secret<serde_json::Value>>> {
+    let business_profile_defaults = match business_profile_defaults {
+        Some(business_profile_defaults) => business_profile_defaults,
+        None => return Ok(connector_metadata),
+    };
+
+    let mut merged_metadata = serde_json::Map::new();
+    if let Some(merchant_category_code) = &business_profile_defaults.merchant_category_code {
+        merged_metadata.insert(
+            "merchant_category_code".to_string(),
+            merchant_category_code.clone().into(),
+        );
+    }
+    if let Some(statement_descriptor_name) = &business_profile_defaults.statement_descriptor_name {
+        merged_metadata.insert(
+            "statement_descriptor_name".to_string(),
+            statement_descriptor_name.clone().into(),
+        );
+    }
+    if let Some(statement_descriptor_suffix) =
+        &business_profile_defaults.statement_descriptor_suffix
+    {
+        merged_metadata.insert(
+            "statement_descriptor_suffix".to_string(),
+            statement_descriptor_suffix.clone().into(),
+        );
+    }
+    if let Some(support_contact) = &business_profile_defaults.support_contact {
+        let support_contact =
+            utils::Encode::<api_models::admin::SupportContactDetails>::encode_to_value(
+                support_contact,
+            )
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Failed to encode support contact details")?;
+        merged_metadata.insert("support_contact".to_string(), support_contact);
+    }
+
+    match connector_metadata.map(masking::Secret::expose) {
+        Some(serde_json::Value::Object(connector_metadata)) => {
+            merged_metadata.extend(connector_metadata);
+        }
+        // Metadata which is not an object cannot be merged, and is passed as is
+        Some(connector_metadata) => return Ok(Some(masking::Secret::new(connector_metadata))),
+        None => {}
+    }
+
+    Ok((!merged_metadata.is_empty())
+        .then(|| masking::Secret::new(serde_json::Value::Object(merged_metadata))))
+}
+
 #[inline]
 pub(crate) fn get_payment_id_from_client_secret(cs: &str) -> RouterResult<String> {
     let (payment_id, _) = cs
@@ -2160,6 +3036,16 @@ mod tests {
             connector_metadata: None,
             feature_metadata: None,
             attempt_count: 1,
+            invoice_id: None,
+            convenience_fee: None,
+            livemode: None,
+            l2_l3_data: None,
+            presentment_amount: None,
+            presentment_currency: None,
+            settlement_amount: None,
+            settlement_currency: None,
+            exchange_rate: None,
+            cross_border_fees: None,
         };
         let req_cs = Some("1".to_string());
         let merchant_fulfillment_time = Some(900);
@@ -2204,6 +3090,16 @@ mod tests {
             connector_metadata: None,
             feature_metadata: None,
             attempt_count: 1,
+            invoice_id: None,
+            convenience_fee: None,
+            livemode: None,
+            l2_l3_data: None,
+            presentment_amount: None,
+            presentment_currency: None,
+            settlement_amount: None,
+            settlement_currency: None,
+            exchange_rate: None,
+            cross_border_fees: None,
         };
         let req_cs = Some("1".to_string());
         let merchant_fulfillment_time = Some(10);
@@ -2248,6 +3144,16 @@ mod tests {
             connector_metadata: None,
             feature_metadata: None,
             attempt_count: 1,
+            invoice_id: None,
+            convenience_fee: None,
+            livemode: None,
+            l2_l3_data: None,
+            presentment_amount: None,
+            presentment_currency: None,
+            settlement_amount: None,
+            settlement_currency: None,
+            exchange_rate: None,
+            cross_border_fees: None,
         };
         let req_cs = Some("1".to_string());
         let merchant_fulfillment_time = Some(10);
@@ -2293,6 +3199,39 @@ pub async fn insert_merchant_connector_creds_to_config(
     }
 }
 
+/// Returns whether the payment method type has been disabled in the payment methods enabled for a
+/// merchant connector account.
+fn is_payment_method_type_disabled(
+    payment_methods_enabled: Option<&[serde_json::Value]>,
+    payment_method: Option<storage_enums::PaymentMethod>,
+    payment_method_type: Option<storage_enums::PaymentMethodType>,
+) -> bool {
+    let (Some(payment_method), Some(payment_method_type)) = (payment_method, payment_method_type)
+    else {
+        return false;
+    };
+
+    payment_methods_enabled
+        .into_iter()
+        .flatten()
+        .filter_map(|payment_methods_enabled| {
+            payment_methods_enabled
+                .clone()
+                .parse_value::<api_models::admin::PaymentMethodsEnabled>("PaymentMethodsEnabled")
+                .ok()
+        })
+        .filter(|payment_methods_enabled| payment_methods_enabled.payment_method == payment_method)
+        .flat_map(|payment_methods_enabled| {
+            payment_methods_enabled
+                .payment_method_types
+                .unwrap_or_default()
+        })
+        .any(|payment_method_type_info| {
+            payment_method_type_info.payment_method_type == payment_method_type
+                && !payment_method_type_info.enabled
+        })
+}
+
 #[derive(Clone)]
 pub enum MerchantConnectorAccountType {
     DbVal(domain::MerchantConnectorAccount),
@@ -2302,7 +3241,13 @@ pub enum MerchantConnectorAccountType {
 impl MerchantConnectorAccountType {
     pub fn get_metadata(&self) -> Option<masking::Secret<serde_json::Value>> {
         match self {
-            Self::DbVal(val) => val.metadata.to_owned(),
+            // The Apple Pay payment processing key is stored apart from the metadata, encrypted
+            Self::DbVal(val) => match &val.apple_pay_payment_processing_key {
+                Some(key) => val.metadata.to_owned().map(|metadata| {
+                    apple_pay::add_payment_processing_certificate_key(metadata, key.get_inner())
+                }),
+                None => val.metadata.to_owned(),
+            },
             Self::CacheVal(val) => val.metadata.to_owned(),
         }
     }
@@ -2313,6 +3258,13 @@ impl MerchantConnectorAccountType {
         }
     }
 
+    pub fn get_client_certificate(&self) -> Option<ConnectorClientCertificate> {
+        match self {
+            Self::DbVal(val) => val.get_client_certificate(),
+            Self::CacheVal(_) => None,
+        }
+    }
+
     pub fn is_disabled(&self) -> bool {
         match self {
             Self::DbVal(ref inner) => inner.disabled.unwrap_or(false),
@@ -2322,12 +3274,66 @@ impl MerchantConnectorAccountType {
         }
     }
 
+    pub fn is_payment_method_type_disabled(
+        &self,
+        payment_method: Option<storage_enums::PaymentMethod>,
+        payment_method_type: Option<storage_enums::PaymentMethodType>,
+    ) -> bool {
+        match self {
+            Self::DbVal(val) => is_payment_method_type_disabled(
+                val.payment_methods_enabled.as_deref(),
+                payment_method,
+                payment_method_type,
+            ),
+            Self::CacheVal(_) => false,
+        }
+    }
+
     pub fn is_test_mode_on(&self) -> Option<bool> {
         match self {
             Self::DbVal(val) => val.test_mode,
             Self::CacheVal(_) => None,
         }
     }
+
+    /// Selects the account details to be used for a payment of the given environment. Connector
+    /// accounts holding test account details use them for test payments and their account details
+    /// for live payments, while other connector accounts can only be used for payments of their
+    /// own environment. Account details passed in the request are used as is.
+    pub fn select_environment(self, livemode: Option<bool>) -> RouterResult<Self> {
+        let (mut mca, livemode) = match (self, livemode) {
+            (Self::DbVal(mca), Some(livemode)) => (mca, livemode),
+            (merchant_connector_account, _) => return Ok(merchant_connector_account),
+        };
+
+        let is_test_account = mca.test_mode == Some(true);
+        match (livemode, mca.test_connector_account_details.take()) {
+            (false, Some(test_connector_account_details)) => {
+                mca.connector_account_details = test_connector_account_details;
+                mca.test_mode = Some(true);
+            }
+            (false, None) if !is_test_account => Err(report!(
+                errors::ApiErrorResponse::PreconditionFailed {
+                    message: format!(
+                        "The connector account {} has no account details for test payments",
+                        mca.merchant_connector_id
+                    ),
+                }
+            ))?,
+            (true, _) if is_test_account => Err(report!(
+                errors::ApiErrorResponse::PreconditionFailed {
+                    message: format!(
+                        "The connector account {} cannot be used for live payments as it is in test mode",
+                        mca.merchant_connector_id
+                    ),
+                }
+            ))?,
+            (true, _) => mca.test_mode = Some(false),
+            (false, None) => (),
+        }
+
+        Ok(Self::DbVal(mca))
+    }
 }
 
 pub async fn get_merchant_connector_account(
@@ -2390,6 +3396,170 @@ pub async fn get_merchant_connector_account(
     }
 }
 
+/// Returns the connectors whose merchant connector account for the business details of the payment
+/// is disabled, or has the payment method type of the payment disabled, which the payment must not
+/// be routed to. The accounts are not checked for the payments which carry the credentials of the
+/// connector, as those payments are not processed with the accounts of the merchant.
+pub async fn get_connectors_with_disabled_accounts<F: Clone>(
+    state: &AppState,
+    merchant_account: &domain::MerchantAccount,
+    key_store: &domain::MerchantKeyStore,
+    connectors: Vec<api_enums::RoutableConnectors>,
+    payment_data: &PaymentData<F>,
+) -> RouterResult<Vec<api_enums::RoutableConnectors>> {
+    if payment_data.creds_identifier.is_some() || connectors.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let merchant_connector_accounts = state
+        .store
+        .find_merchant_connector_account_by_merchant_id_and_disabled_list(
+            &merchant_account.merchant_id,
+            true,
+            key_store,
+        )
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to retrieve the merchant connector accounts for routing")?;
+
+    Ok(connectors
+        .into_iter()
+        .filter(|connector| {
+            let connector_name = connector.to_string();
+            let connector_label = get_connector_label(
+                payment_data.payment_intent.business_country,
+                &payment_data.payment_intent.business_label,
+                payment_data.payment_attempt.business_sub_label.as_ref(),
+                &connector_name,
+            );
+            merchant_connector_accounts
+                .iter()
+                .find(|merchant_connector_account| {
+                    merchant_connector_account.connector_label == connector_label
+                })
+                .map_or(false, |merchant_connector_account| {
+                    merchant_connector_account.disabled.unwrap_or(false)
+                        || is_payment_method_type_disabled(
+                            merchant_connector_account
+                                .payment_methods_enabled
+                                .as_deref(),
+                            payment_data.payment_attempt.payment_method,
+                            payment_data.payment_attempt.payment_method_type,
+                        )
+                })
+        })
+        .collect())
+}
+
+/// Validates that the payment method type of the payment has not been disabled for the merchant
+/// connector account the payment is routed through.
+pub async fn validate_payment_method_type_enabled<F: Clone>(
+    state: &AppState,
+    merchant_account: &domain::MerchantAccount,
+    key_store: &domain::MerchantKeyStore,
+    connector_name: &str,
+    payment_data: &PaymentData<F>,
+) -> RouterResult<()> {
+    let connector_label = get_connector_label(
+        payment_data.payment_intent.business_country,
+        &payment_data.payment_intent.business_label,
+        payment_data.payment_attempt.business_sub_label.as_ref(),
+        connector_name,
+    );
+
+    let merchant_connector_account = get_merchant_connector_account(
+        state,
+        merchant_account.merchant_id.as_str(),
+        &connector_label,
+        payment_data.creds_identifier.to_owned(),
+        key_store,
+    )
+    .await?;
+
+    utils::when(
+        merchant_connector_account.is_payment_method_type_disabled(
+            payment_data.payment_attempt.payment_method,
+            payment_data.payment_attempt.payment_method_type,
+        ),
+        || {
+            Err(report!(errors::ApiErrorResponse::NotSupported {
+                message: format!(
+                    "Payment method type {} is disabled for the {connector_name} connector",
+                    payment_data
+                        .payment_attempt
+                        .payment_method_type
+                        .map(|payment_method_type| payment_method_type.to_string())
+                        .unwrap_or_default()
+                ),
+            }))
+        },
+    )
+}
+
+/// Returns the connectors whose amount limits for the payment method type of the payment do not
+/// accept the amount of the payment, which the payment must not be routed to
+pub fn get_connectors_outside_amount_limits(
+    connector_filters: &ConnectorFilters,
+    connectors: Vec<api_enums::RoutableConnectors>,
+    payment_method_type: Option<api_enums::PaymentMethodType>,
+    amount: i64,
+) -> Vec<api_enums::RoutableConnectors> {
+    let Some(payment_method_type) = payment_method_type else {
+        return Vec::new();
+    };
+
+    connectors
+        .into_iter()
+        .filter(|connector| {
+            connector_filters
+                .get_payment_method_type_filter(&connector.to_string(), payment_method_type)
+                .map_or(false, |filter| !filter.is_amount_within_limits(amount))
+        })
+        .collect()
+}
+
+/// Validates the amount of the payment against the amount limits of the connector the payment is
+/// explicitly routed to, since the connectors of the routing algorithm of the merchant which do not
+/// accept the amount are already left out when routing the payment.
+pub fn validate_connector_amount_limits<F: Clone>(
+    state: &AppState,
+    connector_name: &str,
+    payment_data: &PaymentData<F>,
+) -> RouterResult<()> {
+    let amount = payment_data.payment_attempt.amount;
+    let Some((payment_method_type, filter)) = payment_data
+        .payment_attempt
+        .payment_method_type
+        .and_then(|payment_method_type| {
+            state
+                .conf
+                .pm_filters
+                .get_payment_method_type_filter(connector_name, payment_method_type)
+                .map(|filter| (payment_method_type, filter))
+        })
+    else {
+        return Ok(());
+    };
+
+    if let Some(min_amount) = filter.min_amount.filter(|min_amount| amount < *min_amount) {
+        return Err(report!(errors::ApiErrorResponse::InvalidRequestData {
+            message: format!(
+                "amount must be at least {min_amount} for {payment_method_type} payments with the {connector_name} connector"
+            ),
+        }));
+    }
+
+    if let Some(max_amount) = filter.max_amount.filter(|max_amount| amount > *max_amount) {
+        return Err(report!(errors::ApiErrorResponse::InvalidRequestData {
+            message: format!(
+                "amount must be at most {max_amount} for {payment_method_type} payments with the {connector_name} connector"
+            ),
+        }));
+    }
+
+    Ok(())
+}
+
 /// This function replaces the request and response type of routerdata with the
 /// request and response type passed
 /// # Arguments
@@ -2435,6 +3605,8 @@ pub fn router_data_type_conversion<F1, F2, Req1, Req2, Res1, Res2>(
         #[cfg(feature = "payouts")]
         quote_id: None,
         test_mode: router_data.test_mode,
+        idempotency_key: router_data.idempotency_key,
+        connector_client_certificate: router_data.connector_client_certificate,
     }
 }
 
@@ -2524,7 +3696,7 @@ impl AttemptType {
     // Logic to override the fields with data provided in the request should be done after this if required.
     // In case if fields are not overridden by the request then they contain the same data that was in the previous attempt provided it is populated in this function.
     #[inline(always)]
-    fn make_new_payment_attempt(
+    pub fn make_new_payment_attempt(
         payment_method_data: &Option<api_models::payments::PaymentMethodData>,
         old_payment_attempt: storage::PaymentAttempt,
         new_attempt_count: i16,
@@ -2587,6 +3759,9 @@ impl AttemptType {
             error_reason: None,
             multiple_capture_count: None,
             connector_response_reference_id: None,
+            // Links the new attempt to the failed attempt it is retrying
+            previous_attempt_id: Some(old_payment_attempt.attempt_id),
+            authorization_history: None,
         }
     }
 
@@ -2602,15 +3777,16 @@ impl AttemptType {
             Self::SameOld => Ok((fetched_payment_intent, fetched_payment_attempt)),
             Self::New => {
                 let new_attempt_count = fetched_payment_intent.attempt_count + 1;
+                let mut new_payment_attempt = Self::make_new_payment_attempt(
+                    &request.payment_method_data,
+                    fetched_payment_attempt,
+                    new_attempt_count,
+                );
+                // The amount of a gift card redeemed towards the previous attempt has been
+                // returned to the gift card, so the new attempt is made for the full amount
+                new_payment_attempt.amount = fetched_payment_intent.amount;
                 let new_payment_attempt = db
-                    .insert_payment_attempt(
-                        Self::make_new_payment_attempt(
-                            &request.payment_method_data,
-                            fetched_payment_attempt,
-                            new_attempt_count,
-                        ),
-                        storage_scheme,
-                    )
+                    .insert_payment_attempt(new_payment_attempt, storage_scheme)
                     .await
                     .to_duplicate_response(errors::ApiErrorResponse::DuplicatePayment {
                         payment_id: fetched_payment_intent.payment_id.to_owned(),
@@ -2722,9 +3898,178 @@ pub fn is_manual_retry_allowed(
         .map(|payment_status_check| payment_status_check && is_merchant_id_enabled_for_retries)
 }
 
+/// Ensures that a new attempt can be created for the payment, i.e. the payment has failed and
+/// the status of its latest attempt allows it to be retried
+pub async fn validate_payment_for_retry(
+    state: &AppState,
+    merchant_account: &domain::MerchantAccount,
+    request: &api::PaymentsRequest,
+) -> RouterResult<()> {
+    let db = &*state.store;
+    let payment_id = request
+        .payment_id
+        .as_ref()
+        .get_required_value("payment_id")?
+        .get_payment_intent_id()
+        .change_context(errors::ApiErrorResponse::PaymentNotFound)?;
+    let payment_intent = db
+        .find_payment_intent_by_payment_id_merchant_id(
+            &payment_id,
+            &merchant_account.merchant_id,
+            merchant_account.storage_scheme,
+        )
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::PaymentNotFound)?;
+    let payment_attempt = db
+        .find_payment_attempt_by_payment_id_merchant_id_attempt_id(
+            &payment_intent.payment_id,
+            &merchant_account.merchant_id,
+            &payment_intent.active_attempt_id,
+            merchant_account.storage_scheme,
+        )
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::PaymentNotFound)?;
+
+    match is_manual_retry_allowed(
+        &payment_intent.status,
+        &payment_attempt.status,
+        &state.conf.connector_request_reference_id_config,
+        &merchant_account.merchant_id,
+    ) {
+        Some(true) => Ok(()),
+        Some(false) | None => Err(report!(errors::ApiErrorResponse::PreconditionFailed {
+            message: format!(
+                "You cannot retry this payment because it has status {}, and the previous attempt has the status {}",
+                payment_intent.status, payment_attempt.status
+            ),
+        })),
+    }
+}
+
 #[cfg(test)]
 mod test {
     #![allow(clippy::unwrap_used)]
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::configs::settings::{
+        CurrencyCountryFlowFilter, PaymentMethodFilterKey, PaymentMethodFilters,
+    };
+
+    #[test]
+    fn test_is_payment_method_type_disabled() {
+        let payment_methods_enabled = vec![serde_json::json!({
+            "payment_method": "card",
+            "payment_method_types": [
+                {
+                    "payment_method_type": "credit",
+                    "recurring_enabled": true,
+                    "installment_payment_enabled": true,
+                    "enabled": false
+                },
+                {
+                    "payment_method_type": "debit",
+                    "recurring_enabled": true,
+                    "installment_payment_enabled": true
+                }
+            ]
+        })];
+
+        assert!(is_payment_method_type_disabled(
+            Some(&payment_methods_enabled),
+            Some(storage_enums::PaymentMethod::Card),
+            Some(storage_enums::PaymentMethodType::Credit),
+        ));
+        assert!(!is_payment_method_type_disabled(
+            Some(&payment_methods_enabled),
+            Some(storage_enums::PaymentMethod::Card),
+            Some(storage_enums::PaymentMethodType::Debit),
+        ));
+        assert!(!is_payment_method_type_disabled(
+            Some(&payment_methods_enabled),
+            Some(storage_enums::PaymentMethod::Wallet),
+            Some(storage_enums::PaymentMethodType::Credit),
+        ));
+        assert!(!is_payment_method_type_disabled(
+            Some(&payment_methods_enabled),
+            Some(storage_enums::PaymentMethod::Card),
+            None,
+        ));
+        assert!(!is_payment_method_type_disabled(
+            None,
+            Some(storage_enums::PaymentMethod::Card),
+            Some(storage_enums::PaymentMethodType::Credit),
+        ));
+    }
+
+    #[test]
+    fn test_get_connectors_outside_amount_limits() {
+        let connector_filters = ConnectorFilters(HashMap::from([(
+            "stripe".to_string(),
+            PaymentMethodFilters(HashMap::from([(
+                PaymentMethodFilterKey::PaymentMethodType(api_enums::PaymentMethodType::Credit),
+                CurrencyCountryFlowFilter {
+                    min_amount: Some(100),
+                    max_amount: Some(10000),
+                    ..Default::default()
+                },
+            )])),
+        )]));
+        let connectors = vec![
+            api_enums::RoutableConnectors::Stripe,
+            api_enums::RoutableConnectors::Adyen,
+        ];
+
+        assert_eq!(
+            get_connectors_outside_amount_limits(
+                &connector_filters,
+                connectors.clone(),
+                Some(api_enums::PaymentMethodType::Credit),
+                50,
+            ),
+            vec![api_enums::RoutableConnectors::Stripe]
+        );
+        assert_eq!(
+            get_connectors_outside_amount_limits(
+                &connector_filters,
+                connectors.clone(),
+                Some(api_enums::PaymentMethodType::Credit),
+                20000,
+            ),
+            vec![api_enums::RoutableConnectors::Stripe]
+        );
+        assert!(get_connectors_outside_amount_limits(
+            &connector_filters,
+            connectors.clone(),
+            Some(api_enums::PaymentMethodType::Credit),
+            10000,
+        )
+        .is_empty());
+        assert!(get_connectors_outside_amount_limits(
+            &connector_filters,
+            connectors.clone(),
+            Some(api_enums::PaymentMethodType::Debit),
+            50,
+        )
+        .is_empty());
+        assert!(
+            get_connectors_outside_amount_limits(&connector_filters, connectors, None, 50)
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_get_certificate_expiry() {
+        // Base64 encoded self-signed certificate, valid until 2033-09-01T12:30:45Z
+        let encoded_certificate = "LS0tLS1CRUdJTiBDRVJUSUZJQ0FURS0tLS0tCk1JSUJHRENCdjZBREFnRUNBZ0VCTUFvR0NDcUdTTTQ5QkFNQ01CWXhGREFTQmdOVkJBTU1DMmg1Y0dWeWMzZHAKZEdOb01CNFhEVEl6TURrd01UQXdNREF3TUZvWERUTXpNRGt3TVRFeU16QTBOVm93RmpFVU1CSUdBMVVFQXd3TAphSGx3WlhKemQybDBZMmd3V1RBVEJnY3Foa2pPUFFJQkJnZ3Foa2pPUFFNQkJ3TkNBQVRDeDJsQ0JiR2tTOFAvCmRWUXdHcHVZc2pjalQxM0ZvQVZqY2t3cDNRWFpleUxYK2dGRzBLQnFybFVtWWtDTFFKVXJ6c0VxZS9aUTNseEkKUzhhUkhzNHZNQW9HQ0NxR1NNNDlCQU1DQTBnQU1FVUNJQ2xKQy9CQlNDL2NCK3RXMWU3ZW55ekY4QldKK3BOYwpVM1hISXBhN2VjQk1BaUVBMGcyeVVMV1ZieWhJRHBpaXZPNkllWFR6QTI4MFNXTG5BSGtITmhSQzdncz0KLS0tLS1FTkQgQ0VSVElGSUNBVEUtLS0tLQo=";
+
+        assert_eq!(
+            super::get_certificate_expiry(encoded_certificate.to_string()).unwrap(),
+            time::macros::datetime!(2033-09-01 12:30:45)
+        );
+        assert!(super::get_certificate_expiry("bm90IGEgY2VydGlmaWNhdGU=".to_string()).is_err());
+    }
+
     #[test]
     fn test_client_secret_parse() {
         let client_secret1 = "pay_3TgelAms4RQec8xSStjF_secret_fc34taHLw1ekPgNh92qr";
@@ -2745,6 +4090,178 @@ mod test {
             super::get_payment_id_from_client_secret(client_secret3).unwrap()
         );
     }
+
+    #[tokio::test]
+    async fn test_validate_cvv_for_saved_card() {
+        use crate::db::{configs::ConfigInterface, MockDb};
+
+        let db = MockDb::new(&Default::default()).await;
+        let request = super::api::PaymentsRequest::default();
+        let token = Some("token_1".to_string());
+        let payment_method = Some(super::storage_enums::PaymentMethod::Card);
+
+        // CVV is required by default
+        assert!(super::validate_cvv_for_saved_card(
+            &db,
+            "merchant_1",
+            &request,
+            &token,
+            payment_method,
+            None,
+            &None,
+        )
+        .await
+        .is_err());
+
+        // Off-session payments are not made in the presence of the customer
+        assert!(super::validate_cvv_for_saved_card(
+            &db,
+            "merchant_1",
+            &request,
+            &token,
+            payment_method,
+            Some(true),
+            &None,
+        )
+        .await
+        .is_ok());
+
+        db.insert_config(super::storage::ConfigNew {
+            key: "merchant_1_requires_cvv".to_string(),
+            config: "false".to_string(),
+        })
+        .await
+        .unwrap();
+        assert!(super::validate_cvv_for_saved_card(
+            &db,
+            "merchant_1",
+            &request,
+            &token,
+            payment_method,
+            None,
+            &None,
+        )
+        .await
+        .is_ok());
+    }
+
+    #[test]
+    fn test_payment_retrieve_wait_time_parse() {
+        assert_eq!(
+            std::time::Duration::from_secs(30),
+            super::get_payment_retrieve_wait_time("30s").unwrap()
+        );
+        assert_eq!(
+            std::time::Duration::from_secs(5),
+            super::get_payment_retrieve_wait_time("5").unwrap()
+        );
+        assert!(super::get_payment_retrieve_wait_time("61s").is_err());
+        assert!(super::get_payment_retrieve_wait_time("2m").is_err());
+        assert!(super::get_payment_retrieve_wait_time("-1s").is_err());
+    }
+
+    #[test]
+    fn test_payment_retrieve_expand_parse() {
+        use api_models::payments::PaymentExpandableResource;
+
+        assert_eq!(
+            super::get_payment_retrieve_expand("attempts,refunds,customer").unwrap(),
+            vec![
+                PaymentExpandableResource::Attempts,
+                PaymentExpandableResource::Refunds,
+                PaymentExpandableResource::Customer,
+            ]
+        );
+        // Whitespace around the resources and empty entries are ignored
+        assert_eq!(
+            super::get_payment_retrieve_expand(" captures , disputes,,").unwrap(),
+            vec![
+                PaymentExpandableResource::Captures,
+                PaymentExpandableResource::Disputes,
+            ]
+        );
+        assert!(super::get_payment_retrieve_expand("").unwrap().is_empty());
+
+        assert!(super::get_payment_retrieve_expand("attempts,payouts").is_err());
+        // Resources are case sensitive
+        assert!(super::get_payment_retrieve_expand("Attempts").is_err());
+        assert!(super::get_payment_retrieve_expand("attempts refunds").is_err());
+    }
+
+    #[test]
+    fn test_payment_resources_not_listed_in_expand_are_omitted() {
+        use api_models::payments::PaymentExpandableResource;
+
+        let expand = super::get_payment_retrieve_expand("attempts,customer").unwrap();
+
+        assert_eq!(
+            super::is_payment_resource_expanded(Some(&expand), PaymentExpandableResource::Attempts),
+            Some(true)
+        );
+        assert_eq!(
+            super::is_payment_resource_expanded(Some(&expand), PaymentExpandableResource::Refunds),
+            Some(false)
+        );
+        assert_eq!(
+            super::is_payment_resource_expanded(Some(&expand), PaymentExpandableResource::Disputes),
+            Some(false)
+        );
+        // Refunds and disputes are embedded by default when the request does not use `expand`
+        assert_eq!(
+            super::is_payment_resource_expanded(None, PaymentExpandableResource::Refunds),
+            None
+        );
+    }
+
+    fn get_invoice(
+        status: storage_enums::InvoiceStatus,
+        due_date: Option<time::PrimitiveDateTime>,
+    ) -> storage::Invoice {
+        let now = common_utils::date_time::now();
+        storage::Invoice {
+            id: 1,
+            invoice_id: "inv_1".to_string(),
+            merchant_id: "merchant_1".to_string(),
+            customer_id: None,
+            status,
+            currency: storage_enums::Currency::USD,
+            amount: 1000,
+            amount_paid: 0,
+            line_items: serde_json::json!([]),
+            description: None,
+            due_date,
+            client_secret: "inv_1_secret".to_string(),
+            metadata: None,
+            created_at: now,
+            modified_at: now,
+        }
+    }
+
+    #[test]
+    fn test_only_open_invoices_within_their_due_date_are_payable() {
+        let now = common_utils::date_time::now();
+        let tomorrow = now.saturating_add(time::Duration::days(1));
+        let yesterday = now.saturating_sub(time::Duration::days(1));
+
+        for status in [
+            storage_enums::InvoiceStatus::Open,
+            storage_enums::InvoiceStatus::PartiallyPaid,
+        ] {
+            assert!(super::validate_invoice_is_payable(&get_invoice(status, None)).is_ok());
+            assert!(
+                super::validate_invoice_is_payable(&get_invoice(status, Some(tomorrow))).is_ok()
+            );
+            assert!(
+                super::validate_invoice_is_payable(&get_invoice(status, Some(yesterday))).is_err()
+            );
+        }
+        for status in [
+            storage_enums::InvoiceStatus::Paid,
+            storage_enums::InvoiceStatus::Void,
+        ] {
+            assert!(super::validate_invoice_is_payable(&get_invoice(status, None)).is_err());
+        }
+    }
 }
 
 pub async fn get_additional_payment_data(
@@ -2866,9 +4383,36 @@ pub async fn get_additional_payment_data(
         api_models::payments::PaymentMethodData::GiftCard(_) => {
             api_models::payments::AdditionalPaymentData::GiftCard {}
         }
+        api_models::payments::PaymentMethodData::CardPresent(_) => {
+            api_models::payments::AdditionalPaymentData::CardPresent {}
+        }
     }
 }
 
+/// Ensures that the terminal on which the card was presented is registered by the merchant and is
+/// still allowed to accept payments.
+pub async fn validate_card_present_terminal(
+    db: &dyn StorageInterface,
+    merchant_id: &str,
+    card_present_data: &api_models::payments::CardPresentData,
+) -> RouterResult<storage::Terminal> {
+    let terminal = db
+        .find_terminal_by_merchant_id_terminal_id(merchant_id, &card_present_data.terminal_id)
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::TerminalNotFound {
+            terminal_id: card_present_data.terminal_id.clone(),
+        })?;
+
+    utils::when(!terminal.is_active, || {
+        Err(errors::ApiErrorResponse::PreconditionFailed {
+            message: format!("Terminal {} is not active", terminal.terminal_id),
+        })
+        .into_report()
+    })?;
+
+    Ok(terminal)
+}
+
 pub fn validate_customer_access(
     payment_intent: &storage::PaymentIntent,
     auth_flow: services::AuthFlow,
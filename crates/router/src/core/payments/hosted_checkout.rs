@@ -0,0 +1,529 @@
+//! Router hosted checkout for payments which were created without a payment method.
+//!
+//! The checkout page lists the payment methods available for the payment along with the fields
+//! required to collect them, as returned by the payment methods list. The details submitted by the
+//! customer are used to confirm the payment server side, using the client secret of the payment.
+
+use std::collections::HashSet;
+
+use api_models::{
+    enums::FieldType,
+    payment_methods::{RequiredFieldInfo, ResponsePaymentMethodTypes},
+    payments::{HostedCheckoutQuery, HostedCheckoutSubmitRequest, NextActionData},
+};
+use error_stack::{report, IntoReport, ResultExt};
+use masking::PeekInterface;
+use maud::{html, Markup};
+use router_env::{instrument, logger, tracing};
+
+use super::helpers;
+use crate::{
+    core::{
+        errors::{self, RouterResponse, RouterResult},
+        payment_methods::cards,
+        payments,
+    },
+    routes::AppState,
+    services::{self, ApplicationResponse},
+    types::{
+        api::{self, enums as api_enums},
+        domain,
+        storage::{self, enums},
+    },
+    utils,
+};
+
+const CHECKOUT_PAGE_CSS: &str = include_str!("hosted_checkout.css");
+
+const CONFIRM_FAILED_MESSAGE: &str =
+    "The payment could not be completed, please check the details provided and try again.";
+
+/// The card details are collected for every card payment, irrespective of the fields required by
+/// the connectors of the merchant.
+const CARD_FIELDS: [(&str, &str); 5] = [
+    ("payment_method_data.card.card_number", "Card Number"),
+    ("payment_method_data.card.card_exp_month", "Expiry Month"),
+    ("payment_method_data.card.card_exp_year", "Expiry Year"),
+    (
+        "payment_method_data.card.card_holder_name",
+        "Card Holder Name",
+    ),
+    ("payment_method_data.card.card_cvc", "CVC"),
+];
+
+/// A payment method type which can be collected on the checkout page, with the fields which have
+/// to be filled in by the customer.
+struct CheckoutPaymentMethod {
+    payment_method: api_enums::PaymentMethod,
+    payment_method_type: api_enums::PaymentMethodType,
+    fields: Vec<RequiredFieldInfo>,
+}
+
+fn get_checkout_page_url(base_url: &str, merchant_id: &str, payment_id: &str) -> String {
+    format!("{base_url}/payments/{payment_id}/{merchant_id}/checkout")
+}
+
+/// Finds the payment whose checkout page is being accessed, ensuring that the client secret was
+/// issued for the payment and has not expired.
+async fn find_payment_intent(
+    state: &AppState,
+    merchant_account: &domain::MerchantAccount,
+    payment_id: &str,
+    client_secret: &str,
+) -> RouterResult<storage::PaymentIntent> {
+    let payment_intent = helpers::verify_payment_intent_time_and_client_secret(
+        &*state.store,
+        merchant_account,
+        Some(client_secret.to_owned()),
+    )
+    .await?
+    .ok_or(errors::ApiErrorResponse::ClientSecretInvalid)
+    .into_report()?;
+
+    utils::when(payment_intent.payment_id != payment_id, || {
+        Err(report!(errors::ApiErrorResponse::ClientSecretInvalid))
+    })?;
+    Ok(payment_intent)
+}
+
+fn is_awaiting_payment_method(payment_intent: &storage::PaymentIntent) -> bool {
+    matches!(
+        payment_intent.status,
+        enums::IntentStatus::RequiresPaymentMethod | enums::IntentStatus::RequiresConfirmation
+    )
+}
+
+/// Only payment method types whose payment method data can be built from form fields are offered
+/// on the checkout page, which are cards and the types for which the payment method data is
+/// described by the required fields.
+fn get_checkout_payment_method(
+    payment_method: api_enums::PaymentMethod,
+    payment_method_type: ResponsePaymentMethodTypes,
+) -> Option<CheckoutPaymentMethod> {
+    let mut fields: Vec<RequiredFieldInfo> = match payment_method {
+        api_enums::PaymentMethod::Card => CARD_FIELDS
+            .iter()
+            .map(|(required_field, display_name)| RequiredFieldInfo {
+                required_field: (*required_field).to_string(),
+                display_name: (*display_name).to_string(),
+                field_type: FieldType::Text,
+                value: None,
+            })
+            .collect(),
+        _ => Vec::new(),
+    };
+
+    let mut required_fields: Vec<RequiredFieldInfo> = payment_method_type
+        .required_fields
+        .unwrap_or_default()
+        .into_values()
+        .filter(|required_field| {
+            !fields
+                .iter()
+                .any(|field| field.required_field == required_field.required_field)
+        })
+        .collect();
+    required_fields.sort_by(|a, b| a.required_field.cmp(&b.required_field));
+    fields.extend(required_fields);
+
+    fields
+        .iter()
+        .any(|field| field.required_field.starts_with("payment_method_data."))
+        .then_some(CheckoutPaymentMethod {
+            payment_method,
+            payment_method_type: payment_method_type.payment_method_type,
+            fields,
+        })
+}
+
+async fn get_checkout_payment_methods(
+    state: &AppState,
+    merchant_account: domain::MerchantAccount,
+    key_store: domain::MerchantKeyStore,
+    client_secret: &str,
+) -> RouterResult<Vec<CheckoutPaymentMethod>> {
+    let payment_methods_list = match cards::list_payment_methods(
+        state,
+        merchant_account,
+        key_store,
+        api::PaymentMethodListRequest {
+            client_secret: Some(client_secret.to_owned()),
+            ..Default::default()
+        },
+    )
+    .await?
+    {
+        ApplicationResponse::Json(payment_methods_list) => Ok(payment_methods_list),
+        _ => Err(errors::ApiErrorResponse::InternalServerError)
+            .into_report()
+            .attach_printable("Failed to get the payment methods list in json"),
+    }?;
+
+    Ok(payment_methods_list
+        .payment_methods
+        .into_iter()
+        .flat_map(|payment_methods_enabled| {
+            let payment_method = payment_methods_enabled.payment_method;
+            payment_methods_enabled
+                .payment_method_types
+                .into_iter()
+                .filter_map(move |payment_method_type| {
+                    get_checkout_payment_method(payment_method, payment_method_type)
+                })
+        })
+        .collect())
+}
+
+fn render_field(field: &RequiredFieldInfo) -> Markup {
+    let options = match &field.field_type {
+        FieldType::UserCountry { options }
+        | FieldType::UserCurrency { options }
+        | FieldType::DropDown { options } => Some(options),
+        _ => None,
+    };
+
+    html! {
+        label {
+            (field.display_name)
+            @if let Some(options) = options {
+                select name=(field.required_field) required {
+                    @for option in options {
+                        option value=(option) selected[field.value.as_ref() == Some(option)] { (option) }
+                    }
+                }
+            } @else {
+                input type="text" name=(field.required_field) value=[field.value.as_ref()] required;
+            }
+        }
+    }
+}
+
+pub(crate) fn render_page(title: &str, content: Markup) -> Vec<u8> {
+    html! {
+        (maud::DOCTYPE)
+        html {
+            head {
+                meta charset="utf-8";
+                meta name="viewport" content="width=device-width, initial-scale=1";
+                title { (title) }
+                style { (CHECKOUT_PAGE_CSS) }
+            }
+            body {
+                div.container { (content) }
+            }
+        }
+    }
+    .into_string()
+    .into_bytes()
+}
+
+fn render_checkout_page(
+    checkout_url: &str,
+    payment_intent: &storage::PaymentIntent,
+    client_secret: &str,
+    payment_methods: &[CheckoutPaymentMethod],
+    error_message: Option<&str>,
+) -> RouterResult<Vec<u8>> {
+    let currency = payment_intent.currency.unwrap_or_default();
+    let amount = currency
+        .to_currency_base_unit(payment_intent.amount)
+        .into_report()
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to convert the amount of the payment to its base unit")?;
+
+    Ok(render_page(
+        "Checkout",
+        html! {
+            h1 { (amount) " " (currency) }
+            @if let Some(description) = &payment_intent.description {
+                p.description { (description) }
+            }
+            @if let Some(error_message) = error_message {
+                p.error { (error_message) }
+            }
+            @if payment_methods.is_empty() {
+                p { "No payment methods are available for this payment." }
+            }
+            @for payment_method in payment_methods {
+                details {
+                    summary { (payment_method.payment_method_type.to_string().replace('_', " ")) }
+                    form method="post" action=(checkout_url) {
+                        input type="hidden" name="client_secret" value=(client_secret);
+                        input type="hidden" name="payment_method" value=(payment_method.payment_method.to_string());
+                        input type="hidden" name="payment_method_type" value=(payment_method.payment_method_type.to_string());
+                        @for field in &payment_method.fields {
+                            (render_field(field))
+                        }
+                        button type="submit" { "Pay " (amount) " " (currency) }
+                    }
+                }
+            }
+        },
+    ))
+}
+
+pub(crate) fn render_status_page(status: enums::IntentStatus) -> Vec<u8> {
+    let message = match status {
+        enums::IntentStatus::Succeeded
+        | enums::IntentStatus::RequiresCapture
+        | enums::IntentStatus::PartiallyCaptured => "Your payment was successful.",
+        enums::IntentStatus::Processing => "Your payment is being processed.",
+        enums::IntentStatus::Failed => "Your payment has failed.",
+        enums::IntentStatus::Cancelled => "This payment has been cancelled.",
+        enums::IntentStatus::RequiresCustomerAction
+        | enums::IntentStatus::RequiresMerchantAction
+        | enums::IntentStatus::RequiresPaymentMethod
+        | enums::IntentStatus::RequiresConfirmation => "Your payment could not be completed.",
+    };
+
+    render_page(
+        "Checkout",
+        html! {
+            p.status { (message) }
+        },
+    )
+}
+
+/// Renders the checkout page of a payment, through which the customer selects a payment method
+/// and provides its details.
+#[instrument(skip(state, merchant_account, key_store))]
+pub async fn retrieve_checkout_page(
+    state: &AppState,
+    merchant_account: domain::MerchantAccount,
+    key_store: domain::MerchantKeyStore,
+    payment_id: String,
+    query: HostedCheckoutQuery,
+) -> RouterResponse<()> {
+    let payment_intent =
+        find_payment_intent(state, &merchant_account, &payment_id, &query.client_secret).await?;
+
+    let page = if is_awaiting_payment_method(&payment_intent) {
+        let checkout_url = get_checkout_page_url(
+            &state.conf.server.base_url,
+            &merchant_account.merchant_id,
+            &payment_id,
+        );
+        let payment_methods =
+            get_checkout_payment_methods(state, merchant_account, key_store, &query.client_secret)
+                .await?;
+        render_checkout_page(
+            &checkout_url,
+            &payment_intent,
+            &query.client_secret,
+            &payment_methods,
+            None,
+        )?
+    } else {
+        render_status_page(payment_intent.status)
+    };
+
+    Ok(ApplicationResponse::FileData((page, mime::TEXT_HTML_UTF_8)))
+}
+
+/// Builds the confirm request of the payment from the submitted form. Only the fields which the
+/// checkout page collects for the selected payment method type are taken into account, so that
+/// the customer cannot alter any other detail of the payment.
+fn get_confirm_request(
+    payment_id: String,
+    req: HostedCheckoutSubmitRequest,
+    payment_method: &CheckoutPaymentMethod,
+) -> RouterResult<api::PaymentsRequest> {
+    let allowed_fields: HashSet<&str> = payment_method
+        .fields
+        .iter()
+        .map(|field| field.required_field.as_str())
+        .collect();
+
+    let mut request = serde_json::Map::new();
+    for (path, value) in req.fields {
+        if allowed_fields.contains(path.as_str()) && !value.peek().is_empty() {
+            insert_field(&mut request, &path, value.peek().to_owned());
+        }
+    }
+
+    let confirm_request: api::PaymentsRequest =
+        serde_json::from_value(serde_json::Value::Object(request))
+            .into_report()
+            .change_context(errors::ApiErrorResponse::InvalidRequestData {
+                message: "The details provided for the payment method are invalid".to_string(),
+            })?;
+
+    Ok(api::PaymentsRequest {
+        payment_id: Some(api::PaymentIdType::PaymentIntentId(payment_id)),
+        client_secret: Some(req.client_secret),
+        confirm: Some(true),
+        payment_method: Some(req.payment_method),
+        payment_method_type: Some(req.payment_method_type),
+        ..confirm_request
+    })
+}
+
+/// Sets the value of a field of the request identified by its dot separated path, such as
+/// `payment_method_data.card.card_number`.
+fn insert_field(
+    object: &mut serde_json::Map<String, serde_json::Value>,
+    path: &str,
+    value: String,
+) {
+    match path.split_once('.') {
+        Some((key, rest)) => {
+            if let serde_json::Value::Object(inner) = object
+                .entry(key)
+                .or_insert_with(|| serde_json::Value::Object(serde_json::Map::new()))
+            {
+                insert_field(inner, rest, value);
+            }
+        }
+        None => {
+            object.insert(path.to_owned(), serde_json::Value::String(value));
+        }
+    }
+}
+
+/// Confirms the payment with the payment method details submitted on the checkout page. The
+/// customer is redirected to the connector when further authentication is required, or to the
+/// return url of the payment once it has been processed.
+#[instrument(skip(state, merchant_account, key_store, req))]
+pub async fn submit_checkout_page(
+    state: &AppState,
+    merchant_account: domain::MerchantAccount,
+    key_store: domain::MerchantKeyStore,
+    payment_id: String,
+    req: HostedCheckoutSubmitRequest,
+) -> RouterResponse<()> {
+    let payment_intent =
+        find_payment_intent(state, &merchant_account, &payment_id, &req.client_secret).await?;
+    if !is_awaiting_payment_method(&payment_intent) {
+        return Ok(ApplicationResponse::FileData((
+            render_status_page(payment_intent.status),
+            mime::TEXT_HTML_UTF_8,
+        )));
+    }
+
+    let checkout_url = get_checkout_page_url(
+        &state.conf.server.base_url,
+        &merchant_account.merchant_id,
+        &payment_id,
+    );
+    let client_secret = req.client_secret.clone();
+    let payment_methods = get_checkout_payment_methods(
+        state,
+        merchant_account.clone(),
+        key_store.clone(),
+        &client_secret,
+    )
+    .await?;
+    let payment_method = payment_methods
+        .iter()
+        .find(|payment_method| {
+            payment_method.payment_method == req.payment_method
+                && payment_method.payment_method_type == req.payment_method_type
+        })
+        .ok_or(errors::ApiErrorResponse::InvalidRequestData {
+            message: "The selected payment method is not available for this payment".to_string(),
+        })
+        .into_report()?;
+
+    let confirm_response = match get_confirm_request(payment_id.clone(), req, payment_method) {
+        Ok(confirm_request) => {
+            payments::payments_core::<api::Authorize, api::PaymentsResponse, _, _, _>(
+                state,
+                merchant_account.clone(),
+                key_store,
+                payments::PaymentConfirm,
+                confirm_request,
+                services::AuthFlow::Client,
+                payments::CallConnectorAction::Trigger,
+            )
+            .await
+        }
+        Err(error) => Err(error),
+    };
+
+    let payments_response = match confirm_response {
+        Ok(ApplicationResponse::Json(payments_response)) => payments_response,
+        Ok(_) => Err(errors::ApiErrorResponse::InternalServerError)
+            .into_report()
+            .attach_printable("Failed to get the payments response in json")?,
+        // The customer is presented the checkout page again, so that they can correct the
+        // details of the payment method or choose another one
+        Err(error) => {
+            logger::error!(hosted_checkout_confirm_error=?error);
+            return Ok(ApplicationResponse::FileData((
+                render_checkout_page(
+                    &checkout_url,
+                    &payment_intent,
+                    &client_secret,
+                    &payment_methods,
+                    Some(CONFIRM_FAILED_MESSAGE),
+                )?,
+                mime::TEXT_HTML_UTF_8,
+            )));
+        }
+    };
+
+    if let Some(NextActionData::RedirectToUrl { redirect_to_url }) = &payments_response.next_action
+    {
+        return Ok(ApplicationResponse::JsonForRedirection(
+            api::RedirectionResponse {
+                return_url: redirect_to_url.clone(),
+                params: Vec::new(),
+                return_url_with_query_params: redirect_to_url.clone(),
+                http_method: "GET".to_string(),
+                headers: Vec::new(),
+            },
+        ));
+    }
+
+    if payments_response.return_url.is_some() || merchant_account.return_url.is_some() {
+        let connector = payments_response.connector.clone().unwrap_or_default();
+        return Ok(ApplicationResponse::JsonForRedirection(
+            helpers::get_handle_response_url(
+                payment_id,
+                &merchant_account,
+                payments_response,
+                connector,
+            )?,
+        ));
+    }
+
+    Ok(ApplicationResponse::FileData((
+        render_status_page(payments_response.status),
+        mime::TEXT_HTML_UTF_8,
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+
+    #[test]
+    fn test_insert_field_builds_nested_request() {
+        let mut request = serde_json::Map::new();
+        insert_field(
+            &mut request,
+            "payment_method_data.card.card_number",
+            "4242424242424242".to_string(),
+        );
+        insert_field(
+            &mut request,
+            "payment_method_data.card.card_cvc",
+            "123".to_string(),
+        );
+        insert_field(&mut request, "email", "guest@example.com".to_string());
+
+        assert_eq!(
+            serde_json::Value::Object(request),
+            serde_json::json!({
+                "payment_method_data": {
+                    "card": {
+                        "card_number": "4242424242424242",
+                        "card_cvc": "123"
+                    }
+                },
+                "email": "guest@example.com"
+            })
+        );
+    }
+}
@@ -0,0 +1,226 @@
+//! Incremental authorization of payments awaiting capture.
+//!
+//! Merchants such as hotels and car rentals may need to charge more than the amount authorized
+//! when the payment was made, for instance when a stay is extended. An incremental authorization
+//! increases the amount authorized for the active attempt of the payment with its connector,
+//! without creating a new attempt. Every incremental authorization is recorded in the
+//! authorization history of the attempt, and the amount of the payment and of the attempt is only
+//! increased once the connector has authorized the increased amount.
+
+use common_utils::date_time;
+use error_stack::{report, IntoReport, ResultExt};
+use router_env::{instrument, tracing};
+
+use super::CallConnectorAction;
+use crate::{
+    core::{
+        errors::{self, ConnectorErrorExt, RouterResponse, RouterResult, StorageErrorExt},
+        utils as core_utils,
+    },
+    routes::AppState,
+    services,
+    types::{
+        self, api, domain,
+        storage::{self, enums},
+    },
+    utils::{self, Encode, ValueExt},
+};
+
+/// Returns the incremental authorizations made for the attempt, oldest first.
+pub fn get_authorization_history(
+    payment_attempt: &storage::PaymentAttempt,
+) -> RouterResult<Vec<api::AuthorizationRecord>> {
+    payment_attempt
+        .authorization_history
+        .clone()
+        .map(|authorization_history| {
+            authorization_history
+                .parse_value("AuthorizationHistory")
+                .change_context(errors::ApiErrorResponse::InternalServerError)
+                .attach_printable("Failed to parse the authorization history of the attempt")
+        })
+        .transpose()
+        .map(Option::unwrap_or_default)
+}
+
+fn validate_incremental_authorization(
+    payment_intent: &storage::PaymentIntent,
+    payment_attempt: &storage::PaymentAttempt,
+    amount: i64,
+) -> RouterResult<()> {
+    utils::when(
+        payment_intent.status != enums::IntentStatus::RequiresCapture
+            || payment_attempt.status != enums::AttemptStatus::Authorized,
+        || {
+            Err(report!(errors::ApiErrorResponse::PaymentUnexpectedState {
+                current_flow: "incrementally authorized".to_string(),
+                field_name: "status".to_string(),
+                current_value: payment_intent.status.to_string(),
+                states: enums::IntentStatus::RequiresCapture.to_string(),
+            }))
+        },
+    )?;
+
+    utils::when(amount <= payment_attempt.amount, || {
+        Err(report!(errors::ApiErrorResponse::InvalidRequestData {
+            message: format!(
+                "amount must be greater than the authorized amount of {}",
+                payment_attempt.amount
+            ),
+        }))
+    })
+}
+
+/// Increases the amount authorized for a payment awaiting capture to the amount in the request.
+///
+/// A declined incremental authorization does not affect the payment, whose previously authorized
+/// amount remains authorized and can still be captured.
+#[instrument(skip_all)]
+pub async fn incremental_authorize_payment(
+    state: &AppState,
+    merchant_account: domain::MerchantAccount,
+    key_store: domain::MerchantKeyStore,
+    req: api::PaymentsIncrementalAuthorizationRequest,
+) -> RouterResponse<api::PaymentsIncrementalAuthorizationResponse> {
+    let db = &*state.store;
+    let storage_scheme = merchant_account.storage_scheme;
+
+    let payment_intent = db
+        .find_payment_intent_by_payment_id_merchant_id(
+            &req.payment_id,
+            &merchant_account.merchant_id,
+            storage_scheme,
+        )
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::PaymentNotFound)?;
+    let payment_attempt = db
+        .find_payment_attempt_by_payment_id_merchant_id_attempt_id(
+            &payment_intent.payment_id,
+            &merchant_account.merchant_id,
+            &payment_intent.active_attempt_id,
+            storage_scheme,
+        )
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::PaymentNotFound)?;
+
+    validate_incremental_authorization(&payment_intent, &payment_attempt, req.amount)?;
+
+    let mut authorizations = get_authorization_history(&payment_attempt)?;
+    let connector_name = payment_attempt
+        .connector
+        .clone()
+        .ok_or(errors::ApiErrorResponse::InternalServerError)
+        .into_report()
+        .attach_printable("Authorized attempt does not have a connector")?;
+
+    let connector_data = api::ConnectorData::get_connector_by_name(
+        &state.conf.connectors,
+        &connector_name,
+        api::GetToken::Connector,
+    )?;
+    let connector_integration: services::BoxedConnectorIntegration<
+        '_,
+        api::IncrementalAuthorization,
+        types::PaymentsIncrementalAuthorizationData,
+        types::PaymentsResponseData,
+    > = connector_data.connector.get_connector_integration();
+    let router_data = core_utils::construct_incremental_authorization_router_data(
+        state,
+        &payment_intent,
+        &payment_attempt,
+        &merchant_account,
+        &key_store,
+        req.amount,
+        req.reason.clone(),
+        authorizations.len(),
+    )
+    .await?;
+    let router_data = services::execute_connector_processing_step(
+        state,
+        connector_integration,
+        &router_data,
+        CallConnectorAction::Trigger,
+        None,
+    )
+    .await
+    .to_payment_failed_response()
+    .attach_printable("Failed while calling the incremental authorization connector api")?;
+
+    let authorization = match router_data.response {
+        Ok(types::PaymentsResponseData::IncrementalAuthorizationResponse {
+            status,
+            connector_authorization_id,
+            error_code,
+            error_message,
+        }) => api::AuthorizationRecord {
+            previous_amount: payment_attempt.amount,
+            amount: req.amount,
+            status,
+            reason: req.reason,
+            connector_authorization_id,
+            error_code,
+            error_message,
+            created_at: date_time::now(),
+        },
+        Ok(_) => Err(errors::ApiErrorResponse::InternalServerError)
+            .into_report()
+            .attach_printable("Unexpected response for the incremental authorization")?,
+        Err(error) => api::AuthorizationRecord {
+            previous_amount: payment_attempt.amount,
+            amount: req.amount,
+            status: api::enums::AuthorizationStatus::Failure,
+            reason: req.reason,
+            connector_authorization_id: None,
+            error_code: Some(error.code),
+            error_message: Some(error.reason.unwrap_or(error.message)),
+            created_at: date_time::now(),
+        },
+    };
+
+    let status = authorization.status;
+    let authorized_amount = match status {
+        api::enums::AuthorizationStatus::Success => req.amount,
+        api::enums::AuthorizationStatus::Failure | api::enums::AuthorizationStatus::Processing => {
+            payment_attempt.amount
+        }
+    };
+    authorizations.push(authorization);
+    let authorization_history =
+        Encode::<Vec<api::AuthorizationRecord>>::encode_to_value(&authorizations)
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Failed to encode the authorization history of the attempt")?;
+
+    let payment_attempt = db
+        .update_payment_attempt_with_attempt_id(
+            payment_attempt,
+            storage::PaymentAttemptUpdate::IncrementalAuthorizationUpdate {
+                amount: authorized_amount,
+                authorization_history,
+            },
+            storage_scheme,
+        )
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::PaymentNotFound)?;
+
+    if status == api::enums::AuthorizationStatus::Success {
+        db.update_payment_intent(
+            payment_intent,
+            storage::PaymentIntentUpdate::IncrementalAuthorizationAmountUpdate {
+                amount: authorized_amount,
+            },
+            storage_scheme,
+        )
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::PaymentNotFound)?;
+    }
+
+    Ok(services::ApplicationResponse::Json(
+        api::PaymentsIncrementalAuthorizationResponse {
+            payment_id: payment_attempt.payment_id,
+            attempt_id: payment_attempt.attempt_id,
+            status,
+            authorized_amount,
+            authorizations,
+        },
+    ))
+}
@@ -0,0 +1,193 @@
+//! Updates to the metadata of payments, which can be made at any point of the lifecycle of a
+//! payment, including after it has completed, as they do not affect the financial state of the
+//! payment. Every update is recorded, so that the changes made to the metadata of a payment can be
+//! audited.
+
+use common_utils::date_time;
+use error_stack::{report, ResultExt};
+use masking::{ExposeInterface, Secret};
+use router_env::{instrument, tracing};
+
+use crate::{
+    consts,
+    core::errors::{self, RouterResponse, RouterResult, StorageErrorExt},
+    routes::AppState,
+    services,
+    types::{api, domain, storage},
+    utils,
+};
+
+/// Merges the patch into the metadata of the payment. Keys of the patch replace the keys of the
+/// same name, and keys set to `null` are removed. Metadata which is not an object is replaced.
+fn merge_metadata(
+    metadata: Option<serde_json::Value>,
+    patch: serde_json::Value,
+) -> RouterResult<serde_json::Value> {
+    let serde_json::Value::Object(patch) = patch else {
+        return Err(report!(errors::ApiErrorResponse::InvalidDataFormat {
+            field_name: "metadata".to_string(),
+            expected_format: "JSON object".to_string(),
+        }));
+    };
+
+    let mut merged_metadata = match metadata {
+        Some(serde_json::Value::Object(metadata)) => metadata,
+        _ => serde_json::Map::new(),
+    };
+
+    for (key, value) in patch {
+        if value.is_null() {
+            merged_metadata.remove(&key);
+        } else {
+            merged_metadata.insert(key, value);
+        }
+    }
+
+    Ok(serde_json::Value::Object(merged_metadata))
+}
+
+/// Validates the size of the metadata sent in the request. Keys set to `null`, which remove the key
+/// from the metadata of the payment, are not limited in length.
+fn validate_metadata_size(metadata: &serde_json::Value) -> RouterResult<()> {
+    let serde_json::Value::Object(metadata) = metadata else {
+        return Ok(());
+    };
+
+    utils::when(metadata.len() > consts::PAYMENT_METADATA_MAX_KEYS, || {
+        Err(report!(errors::ApiErrorResponse::InvalidRequestData {
+            message: format!(
+                "metadata can have at most {} keys",
+                consts::PAYMENT_METADATA_MAX_KEYS
+            ),
+        }))
+    })?;
+
+    for (key, value) in metadata {
+        utils::when(
+            key.chars().count() > consts::PAYMENT_METADATA_MAX_KEY_LENGTH,
+            || {
+                Err(report!(errors::ApiErrorResponse::InvalidRequestData {
+                    message: format!(
+                        "metadata key `{key}` is longer than {} characters",
+                        consts::PAYMENT_METADATA_MAX_KEY_LENGTH
+                    ),
+                }))
+            },
+        )?;
+
+        let value_length = match value {
+            serde_json::Value::Null => 0,
+            serde_json::Value::String(value) => value.chars().count(),
+            value => value.to_string().chars().count(),
+        };
+
+        utils::when(
+            value_length > consts::PAYMENT_METADATA_MAX_VALUE_LENGTH,
+            || {
+                Err(report!(errors::ApiErrorResponse::InvalidRequestData {
+                    message: format!(
+                        "value of the metadata key `{key}` is longer than {} characters",
+                        consts::PAYMENT_METADATA_MAX_VALUE_LENGTH
+                    ),
+                }))
+            },
+        )?;
+    }
+
+    Ok(())
+}
+
+#[instrument(skip_all)]
+pub async fn update_payment_metadata(
+    state: &AppState,
+    merchant_account: domain::MerchantAccount,
+    payment_id: String,
+    req: api::PaymentsMetadataUpdateRequest,
+) -> RouterResponse<api::PaymentsMetadataUpdateResponse> {
+    let db = &*state.store;
+
+    let payment_intent = db
+        .find_payment_intent_by_payment_id_merchant_id(
+            &payment_id,
+            &merchant_account.merchant_id,
+            merchant_account.storage_scheme,
+        )
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::PaymentNotFound)?;
+
+    let patch = req.metadata.expose();
+    validate_metadata_size(&patch)?;
+
+    let previous_metadata = payment_intent.metadata.clone();
+    let updated_metadata = merge_metadata(
+        previous_metadata.clone().map(ExposeInterface::expose),
+        patch,
+    )?;
+    let updated_metadata = Secret::new(updated_metadata);
+
+    let payment_metadata_audit = storage::PaymentMetadataAuditNew {
+        payment_id: payment_intent.payment_id.clone(),
+        merchant_id: payment_intent.merchant_id.clone(),
+        previous_metadata,
+        updated_metadata: updated_metadata.clone(),
+        created_at: date_time::now(),
+    };
+
+    let (payment_intent, _) = db
+        .update_payment_intent_with_metadata_audit(
+            payment_intent,
+            storage::PaymentIntentUpdate::MetadataUpdate {
+                metadata: updated_metadata.clone(),
+            },
+            payment_metadata_audit,
+            merchant_account.storage_scheme,
+        )
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::PaymentNotFound)
+        .attach_printable("Failed to update the payment metadata")?;
+
+    Ok(services::ApplicationResponse::Json(
+        api::PaymentsMetadataUpdateResponse {
+            payment_id: payment_intent.payment_id,
+            metadata: updated_metadata,
+        },
+    ))
+}
+
+#[instrument(skip_all)]
+pub async fn list_payment_metadata_audits(
+    state: &AppState,
+    merchant_account: domain::MerchantAccount,
+    payment_id: String,
+) -> RouterResponse<Vec<api::PaymentMetadataAuditResponse>> {
+    let db = &*state.store;
+
+    let payment_intent = db
+        .find_payment_intent_by_payment_id_merchant_id(
+            &payment_id,
+            &merchant_account.merchant_id,
+            merchant_account.storage_scheme,
+        )
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::PaymentNotFound)?;
+
+    let audits = db
+        .list_payment_metadata_audits_by_merchant_id_payment_id(
+            &payment_intent.merchant_id,
+            &payment_intent.payment_id,
+        )
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to list the updates of the payment metadata")?;
+
+    Ok(services::ApplicationResponse::Json(
+        audits
+            .into_iter()
+            .map(|audit| api::PaymentMetadataAuditResponse {
+                previous_metadata: audit.previous_metadata,
+                updated_metadata: audit.updated_metadata,
+                created_at: audit.created_at,
+            })
+            .collect(),
+    ))
+}
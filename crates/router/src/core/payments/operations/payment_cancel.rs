@@ -154,8 +154,11 @@ impl<F: Send + Clone> GetTracker<F, PaymentData<F>, api::PaymentsCancelRequest>
                 refunds: vec![],
                 disputes: vec![],
                 attempts: None,
+                captures: None,
                 connector_response,
                 sessions_token: vec![],
+                timed_out_session_connectors: vec![],
+                deferred_session_connectors: vec![],
                 card_cvc: None,
                 creds_identifier,
                 pm_token: None,
@@ -165,6 +168,9 @@ impl<F: Send + Clone> GetTracker<F, PaymentData<F>, api::PaymentsCancelRequest>
                 multiple_capture_data: None,
                 redirect_response: None,
                 frm_message: None,
+                captcha_site_key: None,
+                payment_link_config: None,
+                expand_customer: false,
             },
             None,
         ))
@@ -223,8 +223,11 @@ impl<F: Send + Clone> GetTracker<F, payments::PaymentData<F>, api::PaymentsCaptu
                 refunds: vec![],
                 disputes: vec![],
                 attempts: None,
+                captures: None,
                 connector_response,
                 sessions_token: vec![],
+                timed_out_session_connectors: vec![],
+                deferred_session_connectors: vec![],
                 card_cvc: None,
                 creds_identifier,
                 pm_token: None,
@@ -234,6 +237,9 @@ impl<F: Send + Clone> GetTracker<F, payments::PaymentData<F>, api::PaymentsCaptu
                 multiple_capture_data,
                 redirect_response: None,
                 frm_message: None,
+                captcha_site_key: None,
+                payment_link_config: None,
+                expand_customer: false,
             },
             None,
         ))
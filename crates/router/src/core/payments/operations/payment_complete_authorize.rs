@@ -227,7 +227,10 @@ impl<F: Send + Clone> GetTracker<F, PaymentData<F>, api::PaymentsRequest> for Co
                 refunds: vec![],
                 disputes: vec![],
                 attempts: None,
+                captures: None,
                 sessions_token: vec![],
+                timed_out_session_connectors: vec![],
+                deferred_session_connectors: vec![],
                 card_cvc: request.card_cvc.clone(),
                 creds_identifier: None,
                 pm_token: None,
@@ -237,6 +240,9 @@ impl<F: Send + Clone> GetTracker<F, PaymentData<F>, api::PaymentsRequest> for Co
                 multiple_capture_data: None,
                 redirect_response,
                 frm_message: None,
+                captcha_site_key: None,
+                payment_link_config: None,
+                expand_customer: false,
             },
             Some(CustomerDetails {
                 customer_id: request.customer_id.clone(),
@@ -367,6 +373,8 @@ impl<F: Send + Clone> ValidateRequest<F, api::PaymentsRequest> for CompleteAutho
                 expected_format: "merchant_id from merchant account".to_string(),
             })?;
 
+        helpers::validate_return_url_domain(request.return_url.as_ref(), merchant_account)?;
+
         helpers::validate_payment_method_fields_present(request)?;
 
         let mandate_type =
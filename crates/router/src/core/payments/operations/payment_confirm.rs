@@ -11,7 +11,10 @@ use super::{BoxedOperation, Domain, GetTracker, Operation, UpdateTracker, Valida
 use crate::{
     core::{
         errors::{self, CustomResult, RouterResult, StorageErrorExt},
-        payments::{self, helpers, operations, CustomerDetails, PaymentAddress, PaymentData},
+        payments::{
+            self, cross_border_fees, helpers, operations, CustomerDetails, PaymentAddress,
+            PaymentData,
+        },
         utils as core_utils,
     },
     db::StorageInterface,
@@ -91,6 +94,12 @@ impl<F: Send + Clone> GetTracker<F, PaymentData<F>, api::PaymentsRequest> for Pa
             .attach_printable("Failed to convert order details to value")?
             .or(payment_intent.order_details);
 
+        payment_intent.l2_l3_data = request
+            .get_l2_l3_data_as_value()
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Failed to convert l2_l3_data to value")?
+            .or(payment_intent.l2_l3_data);
+
         let attempt_type =
             helpers::get_attempt_type(&payment_intent, &payment_attempt, request, "confirm")?;
 
@@ -148,6 +157,18 @@ impl<F: Send + Clone> GetTracker<F, PaymentData<F>, api::PaymentsRequest> for Pa
         )?;
 
         payment_attempt.payment_method = payment_method.or(payment_attempt.payment_method);
+
+        helpers::validate_cvv_for_saved_card(
+            db,
+            merchant_id,
+            request,
+            &token,
+            payment_attempt.payment_method,
+            request.off_session.or(payment_intent.off_session),
+            &mandate_type,
+        )
+        .await?;
+
         payment_attempt.browser_info = browser_info;
         payment_attempt.payment_method_type =
             payment_method_type.or(payment_attempt.payment_method_type);
@@ -158,9 +179,26 @@ impl<F: Send + Clone> GetTracker<F, PaymentData<F>, api::PaymentsRequest> for Pa
 
         payment_attempt.capture_method = request.capture_method.or(payment_attempt.capture_method);
 
+        payment_attempt.authentication_type = request
+            .authentication_type
+            .or(payment_attempt.authentication_type);
+
         currency = payment_attempt.currency.get_required_value("currency")?;
         amount = payment_attempt.amount.into();
 
+        // Payments of an invoice are created for at most the amount due on the invoice, which
+        // other payments of the invoice could have reduced since
+        if let Some(invoice_id) = payment_intent.invoice_id.as_ref() {
+            helpers::validate_invoice_payment(
+                db,
+                merchant_id,
+                invoice_id,
+                payment_attempt.amount,
+                currency,
+            )
+            .await?;
+        }
+
         helpers::validate_customer_id_mandatory_cases(
             request.shipping.is_some(),
             request.billing.is_some(),
@@ -202,6 +240,15 @@ impl<F: Send + Clone> GetTracker<F, PaymentData<F>, api::PaymentsRequest> for Pa
 
         payment_intent.shipping_address_id = shipping_address.clone().map(|i| i.address_id);
         payment_intent.billing_address_id = billing_address.clone().map(|i| i.address_id);
+        payment_intent.cross_border_fees = cross_border_fees::get_cross_border_fees(
+            db,
+            merchant_id,
+            payment_intent.business_country,
+            billing_address.as_ref().and_then(|address| address.country),
+            amount.into(),
+            currency,
+        )
+        .await?;
         payment_intent.return_url = request
             .return_url
             .as_ref()
@@ -283,7 +330,10 @@ impl<F: Send + Clone> GetTracker<F, PaymentData<F>, api::PaymentsRequest> for Pa
                 refunds: vec![],
                 disputes: vec![],
                 attempts: None,
+                captures: None,
                 sessions_token: vec![],
+                timed_out_session_connectors: vec![],
+                deferred_session_connectors: vec![],
                 card_cvc: request.card_cvc.clone(),
                 creds_identifier,
                 pm_token: None,
@@ -293,6 +343,9 @@ impl<F: Send + Clone> GetTracker<F, PaymentData<F>, api::PaymentsRequest> for Pa
                 multiple_capture_data: None,
                 redirect_response: None,
                 frm_message: None,
+                captcha_site_key: None,
+                payment_link_config: None,
+                expand_customer: false,
             },
             Some(customer_details),
         ))
@@ -468,12 +521,17 @@ impl<F: Clone> UpdateTracker<F, PaymentData<F>, api::PaymentsRequest> for Paymen
             .take();
         let order_details = payment_data.payment_intent.order_details.clone();
         let metadata = payment_data.payment_intent.metadata.clone();
+        let l2_l3_data = payment_data.payment_intent.l2_l3_data.clone();
+        let cross_border_fees = payment_data.payment_intent.cross_border_fees.clone();
+        // The amount charged by the attempt excludes the amount redeemed from a gift card, while
+        // the payment is still for the full amount
+        let amount = payment_data.payment_intent.amount;
 
         payment_data.payment_intent = db
             .update_payment_intent(
                 payment_data.payment_intent,
                 storage::PaymentIntentUpdate::Update {
-                    amount: payment_data.amount.into(),
+                    amount,
                     currency: payment_data.currency,
                     setup_future_usage,
                     status: intent_status,
@@ -488,6 +546,8 @@ impl<F: Clone> UpdateTracker<F, PaymentData<F>, api::PaymentsRequest> for Paymen
                     statement_descriptor_suffix,
                     order_details,
                     metadata,
+                    l2_l3_data,
+                    cross_border_fees,
                 },
                 storage_scheme,
             )
@@ -537,6 +597,10 @@ impl<F: Send + Clone> ValidateRequest<F, api::PaymentsRequest> for PaymentConfir
                 expected_format: "merchant_id from merchant account".to_string(),
             })?;
 
+        helpers::validate_return_url_domain(request.return_url.as_ref(), merchant_account)?;
+
+        helpers::validate_l2_l3_data(request.l2_l3_data.as_ref())?;
+
         helpers::validate_payment_method_fields_present(request)?;
 
         let mandate_type =
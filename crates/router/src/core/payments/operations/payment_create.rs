@@ -13,7 +13,10 @@ use crate::{
     consts,
     core::{
         errors::{self, CustomResult, RouterResult, StorageErrorExt},
-        payments::{self, helpers, operations, CustomerDetails, PaymentAddress, PaymentData},
+        payments::{
+            self, cross_border_fees, currency_conversion, helpers, operations, CustomerDetails,
+            PaymentAddress, PaymentData,
+        },
         utils as core_utils,
     },
     db::StorageInterface,
@@ -65,6 +68,35 @@ impl<F: Send + Clone> GetTracker<F, PaymentData<F>, api::PaymentsRequest> for Pa
             .get_payment_intent_id()
             .change_context(errors::ApiErrorResponse::PaymentNotFound)?;
 
+        if let Some(invoice_id) = request.invoice_id.as_ref() {
+            helpers::validate_invoice_payment(db, merchant_id, invoice_id, amount.into(), currency)
+                .await?;
+        }
+
+        let convenience_fee = request
+            .convenience_fee
+            .as_ref()
+            .map(|convenience_fee| {
+                helpers::get_convenience_fee_details(convenience_fee, amount.into())
+            })
+            .transpose()?
+            .map(|convenience_fee| {
+                Encode::<api_models::payments::ConvenienceFeeDetails>::encode_to_value(
+                    &convenience_fee,
+                )
+            })
+            .transpose()
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Failed to convert convenience fee to value")?;
+
+        let currency_conversion = currency_conversion::get_currency_conversion(
+            state,
+            amount.into(),
+            currency,
+            request.presentment_currency,
+        )
+        .await?;
+
         let (
             token,
             payment_method,
@@ -82,23 +114,105 @@ impl<F: Send + Clone> GetTracker<F, PaymentData<F>, api::PaymentsRequest> for Pa
 
         let customer_details = helpers::get_customer_details_from_request(request);
 
-        let shipping_address = helpers::get_address_for_payment_request(
-            db,
+        let (business_country, business_label) =
+            Self::get_business_details(request, merchant_account)?;
+        let business_profile_defaults = helpers::get_business_profile_defaults(
+            business_country,
+            &business_label,
+            merchant_account,
+        )?;
+
+        let (shipping_address_book_entry, billing_address_book_entry) =
+            helpers::get_address_book_entries_for_payment(
+                db,
+                request,
+                customer_details.customer_id.as_ref(),
+                merchant_id,
+                merchant_key_store,
+            )
+            .await?;
+
+        // The address on file of the customer is attached to the payment in place of the omitted
+        // billing or shipping address, if the business has opted in
+        let customer_address_on_file = if business_profile_defaults
+            .as_ref()
+            .and_then(|defaults| defaults.use_customer_address_on_file)
+            .unwrap_or(false)
+            && ((request.shipping.is_none() && shipping_address_book_entry.is_none())
+                || (request.billing.is_none() && billing_address_book_entry.is_none()))
+        {
+            helpers::get_customer_address_on_file(
+                db,
+                customer_details.customer_id.as_ref(),
+                merchant_id,
+                merchant_key_store,
+            )
+            .await?
+        } else {
+            None
+        };
+
+        let shipping_address = match (
             request.shipping.as_ref(),
-            None,
-            merchant_id,
-            customer_details.customer_id.as_ref(),
-            merchant_key_store,
-        )
-        .await?;
+            shipping_address_book_entry
+                .as_ref()
+                .or(customer_address_on_file.as_ref()),
+        ) {
+            (None, Some(customer_address)) => Some(
+                helpers::insert_customer_address_for_payment(
+                    db,
+                    customer_address,
+                    merchant_key_store,
+                )
+                .await?,
+            ),
+            (shipping, _) => {
+                helpers::get_address_for_payment_request(
+                    db,
+                    shipping,
+                    None,
+                    merchant_id,
+                    customer_details.customer_id.as_ref(),
+                    merchant_key_store,
+                )
+                .await?
+            }
+        };
 
-        let billing_address = helpers::get_address_for_payment_request(
-            db,
+        let billing_address = match (
             request.billing.as_ref(),
-            None,
+            billing_address_book_entry
+                .as_ref()
+                .or(customer_address_on_file.as_ref()),
+        ) {
+            (None, Some(customer_address)) => Some(
+                helpers::insert_customer_address_for_payment(
+                    db,
+                    customer_address,
+                    merchant_key_store,
+                )
+                .await?,
+            ),
+            (billing, _) => {
+                helpers::get_address_for_payment_request(
+                    db,
+                    billing,
+                    None,
+                    merchant_id,
+                    customer_details.customer_id.as_ref(),
+                    merchant_key_store,
+                )
+                .await?
+            }
+        };
+
+        let cross_border_fees = cross_border_fees::get_cross_border_fees(
+            db,
             merchant_id,
-            customer_details.customer_id.as_ref(),
-            merchant_key_store,
+            business_country,
+            billing_address.as_ref().and_then(|address| address.country),
+            amount.into(),
+            currency,
         )
         .await?;
 
@@ -135,15 +249,34 @@ impl<F: Send + Clone> GetTracker<F, PaymentData<F>, api::PaymentsRequest> for Pa
 
         payment_intent = db
             .insert_payment_intent(
-                Self::make_payment_intent(
-                    &payment_id,
-                    merchant_account,
-                    money,
-                    request,
-                    shipping_address.clone().map(|x| x.address_id),
-                    billing_address.clone().map(|x| x.address_id),
-                    payment_attempt.attempt_id.to_owned(),
-                )?,
+                storage::PaymentIntentNew {
+                    convenience_fee,
+                    cross_border_fees,
+                    presentment_amount: currency_conversion
+                        .as_ref()
+                        .map(|conversion| conversion.presentment_amount),
+                    presentment_currency: currency_conversion
+                        .as_ref()
+                        .map(|conversion| conversion.presentment_currency),
+                    settlement_amount: currency_conversion
+                        .as_ref()
+                        .map(|conversion| conversion.settlement_amount),
+                    settlement_currency: currency_conversion
+                        .as_ref()
+                        .map(|conversion| conversion.settlement_currency),
+                    exchange_rate: currency_conversion.map(|conversion| conversion.exchange_rate),
+                    ..Self::make_payment_intent(
+                        &payment_id,
+                        merchant_account,
+                        money,
+                        request,
+                        shipping_address.clone().map(|x| x.address_id),
+                        billing_address.clone().map(|x| x.address_id),
+                        payment_attempt.attempt_id.to_owned(),
+                        (business_country, business_label),
+                        business_profile_defaults.as_ref(),
+                    )?
+                },
                 storage_scheme,
             )
             .await
@@ -260,9 +393,12 @@ impl<F: Send + Clone> GetTracker<F, PaymentData<F>, api::PaymentsRequest> for Pa
                 refunds: vec![],
                 disputes: vec![],
                 attempts: None,
+                captures: None,
                 force_sync: None,
                 connector_response,
                 sessions_token: vec![],
+                timed_out_session_connectors: vec![],
+                deferred_session_connectors: vec![],
                 card_cvc: request.card_cvc.clone(),
                 creds_identifier,
                 pm_token: None,
@@ -272,6 +408,9 @@ impl<F: Send + Clone> GetTracker<F, PaymentData<F>, api::PaymentsRequest> for Pa
                 multiple_capture_data: None,
                 redirect_response: None,
                 frm_message: None,
+                captcha_site_key: None,
+                payment_link_config: None,
+                expand_customer: false,
             },
             Some(customer_details),
         ))
@@ -443,6 +582,10 @@ impl<F: Send + Clone> ValidateRequest<F, api::PaymentsRequest> for PaymentCreate
         helpers::validate_merchant_id(&merchant_account.merchant_id, request_merchant_id)
             .change_context(errors::ApiErrorResponse::MerchantAccountNotFound)?;
 
+        helpers::validate_return_url_domain(request.return_url.as_ref(), merchant_account)?;
+
+        helpers::validate_l2_l3_data(request.l2_l3_data.as_ref())?;
+
         helpers::validate_request_amount_and_amount_to_capture(
             request.amount,
             request.amount_to_capture,
@@ -568,7 +711,38 @@ impl PaymentCreate {
         })
     }
 
+    fn get_business_details(
+        request: &api::PaymentsRequest,
+        merchant_account: &types::domain::MerchantAccount,
+    ) -> RouterResult<(enums::CountryAlpha2, String)> {
+        match (request.business_country, request.business_label.as_ref()) {
+            (Some(business_country), Some(business_label)) => {
+                helpers::validate_business_details(
+                    business_country,
+                    business_label,
+                    merchant_account,
+                )?;
+
+                Ok((business_country, business_label.clone()))
+            }
+            (None, Some(_)) => Err(errors::ApiErrorResponse::MissingRequiredField {
+                field_name: "business_country",
+            }
+            .into()),
+            (Some(_), None) => Err(errors::ApiErrorResponse::MissingRequiredField {
+                field_name: "business_label",
+            }
+            .into()),
+            (None, None) => helpers::get_business_details(
+                request.business_country,
+                request.business_label.as_ref(),
+                merchant_account,
+            ),
+        }
+    }
+
     #[instrument(skip_all)]
+    #[allow(clippy::too_many_arguments)]
     fn make_payment_intent(
         payment_id: &str,
         merchant_account: &types::domain::MerchantAccount,
@@ -577,6 +751,8 @@ impl PaymentCreate {
         shipping_address_id: Option<String>,
         billing_address_id: Option<String>,
         active_attempt_id: String,
+        (business_country, business_label): (enums::CountryAlpha2, String),
+        business_profile_defaults: Option<&api_models::admin::PrimaryBusinessDetails>,
     ) -> RouterResult<storage::PaymentIntentNew> {
         let created_at @ modified_at @ last_synced = Some(common_utils::date_time::now());
         let status =
@@ -590,29 +766,16 @@ impl PaymentCreate {
             .change_context(errors::ApiErrorResponse::InternalServerError)
             .attach_printable("Failed to convert order details to value")?;
 
-        let (business_country, business_label) =
-            match (request.business_country, request.business_label.as_ref()) {
-                (Some(business_country), Some(business_label)) => {
-                    helpers::validate_business_details(
-                        business_country,
-                        business_label,
-                        merchant_account,
-                    )?;
-
-                    Ok((business_country, business_label.clone()))
-                }
-                (None, Some(_)) => Err(errors::ApiErrorResponse::MissingRequiredField {
-                    field_name: "business_country",
-                }),
-                (Some(_), None) => Err(errors::ApiErrorResponse::MissingRequiredField {
-                    field_name: "business_label",
-                }),
-                (None, None) => Ok(helpers::get_business_details(
-                    request.business_country,
-                    request.business_label.as_ref(),
-                    merchant_account,
-                )?),
-            }?;
+        // Descriptors passed in the payment take precedence over the business level defaults
+        let statement_descriptor_name = request.statement_descriptor_name.clone().or_else(|| {
+            business_profile_defaults
+                .and_then(|defaults| defaults.statement_descriptor_name.clone())
+        });
+        let statement_descriptor_suffix =
+            request.statement_descriptor_suffix.clone().or_else(|| {
+                business_profile_defaults
+                    .and_then(|defaults| defaults.statement_descriptor_suffix.clone())
+            });
 
         let allowed_payment_method_types = request
             .get_allowed_payment_method_types_as_value()
@@ -629,6 +792,11 @@ impl PaymentCreate {
             .change_context(errors::ApiErrorResponse::InternalServerError)
             .attach_printable("Error converting feature_metadata to Value")?;
 
+        let l2_l3_data = request
+            .get_l2_l3_data_as_value()
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Error converting l2_l3_data to Value")?;
+
         Ok(storage::PaymentIntentNew {
             payment_id: payment_id.to_string(),
             merchant_id: merchant_account.merchant_id.to_string(),
@@ -645,8 +813,8 @@ impl PaymentCreate {
             return_url: request.return_url.as_ref().map(|a| a.to_string()),
             shipping_address_id,
             billing_address_id,
-            statement_descriptor_name: request.statement_descriptor_name.clone(),
-            statement_descriptor_suffix: request.statement_descriptor_suffix.clone(),
+            statement_descriptor_name,
+            statement_descriptor_suffix,
             metadata: request.metadata.clone(),
             business_country,
             business_label,
@@ -659,6 +827,16 @@ impl PaymentCreate {
             connector_metadata,
             feature_metadata,
             attempt_count: 1,
+            invoice_id: request.invoice_id.clone(),
+            convenience_fee: None,
+            livemode: request.livemode,
+            l2_l3_data,
+            presentment_amount: None,
+            presentment_currency: None,
+            settlement_amount: None,
+            settlement_currency: None,
+            exchange_rate: None,
+            cross_border_fees: None,
         })
     }
 
@@ -181,7 +181,10 @@ impl<F: Send + Clone> GetTracker<F, PaymentData<F>, api::VerifyRequest> for Paym
                 refunds: vec![],
                 disputes: vec![],
                 attempts: None,
+                captures: None,
                 sessions_token: vec![],
+                timed_out_session_connectors: vec![],
+                deferred_session_connectors: vec![],
                 card_cvc: None,
                 creds_identifier,
                 pm_token: None,
@@ -191,6 +194,9 @@ impl<F: Send + Clone> GetTracker<F, PaymentData<F>, api::VerifyRequest> for Paym
                 multiple_capture_data: None,
                 redirect_response: None,
                 frm_message: None,
+                captcha_site_key: None,
+                payment_link_config: None,
+                expand_customer: false,
             },
             Some(payments::CustomerDetails {
                 customer_id: request.customer_id.clone(),
@@ -364,6 +370,16 @@ impl PaymentMethodValidate {
             off_session: request.off_session,
             active_attempt_id,
             attempt_count: 1,
+            invoice_id: None,
+            convenience_fee: None,
+            livemode: None,
+            l2_l3_data: None,
+            presentment_amount: None,
+            presentment_currency: None,
+            settlement_amount: None,
+            settlement_currency: None,
+            exchange_rate: None,
+            cross_border_fees: None,
             ..Default::default()
         }
     }
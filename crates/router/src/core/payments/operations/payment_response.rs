@@ -2,16 +2,18 @@ use async_trait::async_trait;
 use common_utils::fp_utils;
 use error_stack::ResultExt;
 use router_derive;
+use router_env::logger;
 
 use super::{Operation, PostUpdateTracker};
 use crate::{
     core::{
         errors::{self, RouterResult, StorageErrorExt},
         mandate,
-        payments::PaymentData,
+        payments::{self, PaymentData},
     },
     db::StorageInterface,
     routes::metrics,
+    scheduler::{metrics as scheduler_metrics, workflows::delayed_capture},
     services::RedirectForm,
     types::{
         self, api,
@@ -63,6 +65,8 @@ impl<F: Clone> PostUpdateTracker<F, PaymentData<F>, types::PaymentsAuthorizeData
         )
         .await?;
 
+        add_delayed_capture_task_if_required(db, &payment_data).await;
+
         router_response.map(|_| ()).or_else(|error_response| {
             fp_utils::when(
                 !(200..300).contains(&error_response.status_code)
@@ -283,8 +287,42 @@ impl<F: Clone> PostUpdateTracker<F, PaymentData<F>, types::CompleteAuthorizeData
     where
         F: 'b + Send,
     {
-        payment_response_update_tracker(db, payment_id, payment_data, response, storage_scheme)
-            .await
+        let payment_data =
+            payment_response_update_tracker(db, payment_id, payment_data, response, storage_scheme)
+                .await?;
+
+        add_delayed_capture_task_if_required(db, &payment_data).await;
+
+        Ok(payment_data)
+    }
+}
+
+/// Schedules the capture of payments authorized with the `automatic_delayed` capture method.
+///
+/// Failing to schedule the capture does not fail the payment, as the merchant can still capture
+/// the payment manually.
+async fn add_delayed_capture_task_if_required<F: Clone>(
+    db: &dyn StorageInterface,
+    payment_data: &PaymentData<F>,
+) {
+    let payment_attempt = &payment_data.payment_attempt;
+    if payment_attempt.capture_method != Some(enums::CaptureMethod::AutomaticDelayed)
+        || payment_attempt.status != enums::AttemptStatus::Authorized
+    {
+        return;
+    }
+
+    let schedule_time =
+        delayed_capture::get_delayed_capture_schedule_time(db, &payment_attempt.merchant_id).await;
+    match payments::add_delayed_capture_task(db, payment_attempt, schedule_time).await {
+        Ok(()) => {
+            scheduler_metrics::TASKS_ADDED_COUNT.add(&metrics::CONTEXT, 1, &[]);
+        }
+        Err(error) => logger::error!(
+            ?error,
+            payment_id = %payment_attempt.payment_id,
+            "Failed to schedule delayed capture"
+        ),
     }
 }
 
@@ -475,6 +513,9 @@ async fn payment_response_update_tracker<F: Clone, T: types::Capturable>(
             types::PaymentsResponseData::TokenizationResponse { .. } => (None, None, None),
             types::PaymentsResponseData::ConnectorCustomerResponse { .. } => (None, None, None),
             types::PaymentsResponseData::ThreeDSEnrollmentResponse { .. } => (None, None, None),
+            types::PaymentsResponseData::IncrementalAuthorizationResponse { .. } => {
+                (None, None, None)
+            }
         },
     };
     payment_data.multiple_capture_data = match capture_update
@@ -543,6 +584,7 @@ async fn payment_response_update_tracker<F: Clone, T: types::Capturable>(
         },
     };
 
+    let previous_intent_status = payment_data.payment_intent.status;
     payment_data.payment_intent = db
         .update_payment_intent(
             payment_data.payment_intent,
@@ -552,6 +594,18 @@ async fn payment_response_update_tracker<F: Clone, T: types::Capturable>(
         .await
         .to_not_found_response(errors::ApiErrorResponse::PaymentNotFound)?;
 
+    // Saved payment methods of a customer are ordered by when they were last used
+    if previous_intent_status != payment_data.payment_intent.status
+        && matches!(
+            payment_data.payment_intent.status,
+            enums::IntentStatus::Succeeded | enums::IntentStatus::RequiresCapture
+        )
+    {
+        if let Some(payment_method_id) = &payment_data.payment_attempt.payment_method_id {
+            update_payment_method_last_used_at(db, payment_method_id).await;
+        }
+    }
+
     // When connector requires redirection for mandate creation it can update the connector mandate_id during Psync
     mandate::update_connector_mandate_id(
         db,
@@ -564,6 +618,24 @@ async fn payment_response_update_tracker<F: Clone, T: types::Capturable>(
     Ok(payment_data)
 }
 
+async fn update_payment_method_last_used_at(db: &dyn StorageInterface, payment_method_id: &str) {
+    let result = async {
+        let payment_method = db.find_payment_method(payment_method_id).await?;
+        db.update_payment_method(
+            payment_method,
+            storage::PaymentMethodUpdate::LastUsedUpdate {
+                last_used_at: common_utils::date_time::now(),
+            },
+        )
+        .await
+    }
+    .await;
+
+    if let Err(error) = result {
+        logger::error!(payment_method_last_used_update_error=?error);
+    }
+}
+
 fn get_total_amount_captured<F: Clone, T: types::Capturable>(
     request: T,
     amount_captured: Option<i64>,
@@ -578,6 +650,10 @@ fn get_total_amount_captured<F: Clone, T: types::Capturable>(
         None => {
             //Non multiple capture
             let amount = request.get_capture_amount();
+            // Connectors report the amount captured of payments charged in a currency other than
+            // their settlement currency in the presentment currency
+            let amount_captured = amount_captured
+                .filter(|_| payment_data.payment_intent.presentment_currency.is_none());
             amount_captured.or_else(|| {
                 if router_data_status == enums::AttemptStatus::Charged {
                     amount
@@ -1,6 +1,9 @@
 use std::marker::PhantomData;
 
-use api_models::{admin::PaymentMethodsEnabled, enums::CancelTransaction};
+use api_models::{
+    admin::PaymentMethodsEnabled,
+    enums::{CancelTransaction, SessionTokenStrategy},
+};
 use async_trait::async_trait;
 use common_utils::ext_traits::{AsyncExt, ValueExt};
 use error_stack::ResultExt;
@@ -171,7 +174,10 @@ impl<F: Send + Clone> GetTracker<F, PaymentData<F>, api::PaymentsSessionRequest>
                 refunds: vec![],
                 disputes: vec![],
                 attempts: None,
+                captures: None,
                 sessions_token: vec![],
+                timed_out_session_connectors: vec![],
+                deferred_session_connectors: vec![],
                 connector_response,
                 card_cvc: None,
                 creds_identifier,
@@ -182,6 +188,9 @@ impl<F: Send + Clone> GetTracker<F, PaymentData<F>, api::PaymentsSessionRequest>
                 multiple_capture_data: None,
                 redirect_response: None,
                 frm_message: None,
+                captcha_site_key: None,
+                payment_link_config: None,
+                expand_customer: false,
             },
             Some(customer_details),
         ))
@@ -369,7 +378,9 @@ where
                                     .contains(&payment_method_type.payment_method_type)
                                     || requested_payment_method_types.is_empty();
 
-                                is_invoke_sdk_client && is_sent_in_request
+                                payment_method_type.enabled
+                                    && is_invoke_sdk_client
+                                    && is_sent_in_request
                             })
                             .map(|payment_method_type| {
                                 (
@@ -408,12 +419,97 @@ where
             };
         }
 
-        Ok(api::ConnectorChoice::SessionMultiple(
+        let (connectors, deferred_connectors) = split_session_connectors(
+            merchant_account,
+            request,
+            payment_intent,
             session_connector_data,
-        ))
+        )?;
+
+        Ok(api::ConnectorChoice::SessionMultiple {
+            connectors,
+            deferred_connectors,
+        })
     }
 }
 
+/// Returns the connector the payments of the merchant are routed to, if a routing algorithm has
+/// been configured
+fn get_primary_connector(
+    merchant_account: &domain::MerchantAccount,
+    payment_intent: &storage::payment_intent::PaymentIntent,
+) -> Option<String> {
+    let routing_algorithm = merchant_account
+        .routing_algorithm
+        .clone()?
+        .parse_value::<api::RoutingAlgorithm>("RoutingAlgorithm")
+        .map_err(|error| logger::error!(routing_algorithm_parsing_error=?error))
+        .ok()?;
+
+    payments::get_routed_connector(
+        &routing_algorithm,
+        payments::get_routing_key(payment_intent),
+    )
+    .map(|connector| connector.to_string())
+}
+
+/// Splits the eligible session connectors into the connectors whose session tokens are fetched
+/// now, and the connectors whose session tokens are deferred until they are requested.
+/// Connectors passed in the request are always fetched. Otherwise, with the `sequential` session
+/// token strategy only the session token of the connector the payment is routed to is fetched.
+fn split_session_connectors(
+    merchant_account: &domain::MerchantAccount,
+    request: &api::PaymentsSessionRequest,
+    payment_intent: &storage::payment_intent::PaymentIntent,
+    session_connector_data: Vec<api::SessionConnectorData>,
+) -> RouterResult<(Vec<api::SessionConnectorData>, Vec<String>)> {
+    let connectors_to_fetch = match &request.connectors {
+        Some(requested_connectors) => requested_connectors
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>(),
+        None => {
+            let session_token_strategy = helpers::get_business_profile_defaults(
+                payment_intent.business_country,
+                &payment_intent.business_label,
+                merchant_account,
+            )?
+            .and_then(|business_details| business_details.session_token_strategy)
+            .unwrap_or_default();
+
+            match session_token_strategy {
+                SessionTokenStrategy::Parallel => return Ok((session_connector_data, vec![])),
+                SessionTokenStrategy::Sequential => {
+                    match get_primary_connector(merchant_account, payment_intent) {
+                        Some(primary_connector) => vec![primary_connector],
+                        None => {
+                            logger::warn!(
+                                "No routing algorithm configured, fetching session tokens from all the eligible connectors"
+                            );
+                            return Ok((session_connector_data, vec![]));
+                        }
+                    }
+                }
+            }
+        }
+    };
+
+    let (connectors, deferred_connectors): (Vec<_>, Vec<_>) = session_connector_data
+        .into_iter()
+        .partition(|session_connector| {
+            connectors_to_fetch.contains(&session_connector.connector.connector_name.to_string())
+        });
+
+    let mut deferred_connectors = deferred_connectors
+        .into_iter()
+        .map(|session_connector| session_connector.connector.connector_name.to_string())
+        .collect::<Vec<_>>();
+    deferred_connectors.sort();
+    deferred_connectors.dedup();
+
+    Ok((connectors, deferred_connectors))
+}
+
 impl From<api_models::enums::PaymentMethodType> for api::GetToken {
     fn from(value: api_models::enums::PaymentMethodType) -> Self {
         match value {
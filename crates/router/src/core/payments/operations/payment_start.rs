@@ -121,6 +121,15 @@ impl<F: Send + Clone> GetTracker<F, PaymentData<F>, api::PaymentsStartRequest> f
                     .attach_printable("Database error when finding connector response")
             })?;
 
+        // The page redirecting the customer to the connector is branded as configured for the
+        // business under which the payment is made
+        let payment_link_config = helpers::get_business_profile_defaults(
+            payment_intent.business_country,
+            &payment_intent.business_label,
+            merchant_account,
+        )?
+        .and_then(|business_profile_defaults| business_profile_defaults.payment_link_config);
+
         Ok((
             Box::new(self),
             PaymentData {
@@ -145,7 +154,10 @@ impl<F: Send + Clone> GetTracker<F, PaymentData<F>, api::PaymentsStartRequest> f
                 refunds: vec![],
                 disputes: vec![],
                 attempts: None,
+                captures: None,
                 sessions_token: vec![],
+                timed_out_session_connectors: vec![],
+                deferred_session_connectors: vec![],
                 card_cvc: None,
                 creds_identifier: None,
                 pm_token: None,
@@ -155,6 +167,9 @@ impl<F: Send + Clone> GetTracker<F, PaymentData<F>, api::PaymentsStartRequest> f
                 multiple_capture_data: None,
                 redirect_response: None,
                 frm_message: None,
+                captcha_site_key: None,
+                payment_link_config,
+                expand_customer: false,
             },
             Some(customer_details),
         ))
@@ -12,6 +12,7 @@ use crate::{
     core::{
         errors::{self, CustomResult, RouterResult, StorageErrorExt},
         payments::{helpers, operations, CustomerDetails, PaymentAddress, PaymentData},
+        sync_cache,
     },
     db::StorageInterface,
     routes::AppState,
@@ -173,7 +174,7 @@ impl<F: Send + Clone> GetTracker<F, PaymentData<F>, api::PaymentsRetrieveRequest
         PaymentData<F>,
         Option<CustomerDetails>,
     )> {
-        get_tracker_for_sync(
+        let (operation, mut payment_data, customer_details) = get_tracker_for_sync(
             payment_id,
             &merchant_account.merchant_id,
             key_store,
@@ -182,7 +183,30 @@ impl<F: Send + Clone> GetTracker<F, PaymentData<F>, api::PaymentsRetrieveRequest
             self,
             merchant_account.storage_scheme,
         )
-        .await
+        .await?;
+
+        if let (Some(true), Some(connector), Some(connector_transaction_id)) = (
+            payment_data.force_sync,
+            payment_data.payment_attempt.connector.as_ref(),
+            payment_data
+                .payment_attempt
+                .connector_transaction_id
+                .as_ref(),
+        ) {
+            if !request.bypass_sync_cache.unwrap_or(false) {
+                payment_data.force_sync = Some(
+                    sync_cache::should_sync_with_connector(
+                        state,
+                        sync_cache::SyncFlow::PaymentSync,
+                        connector,
+                        connector_transaction_id,
+                    )
+                    .await,
+                );
+            }
+        }
+
+        Ok((operation, payment_data, customer_details))
     }
 }
 
@@ -238,37 +262,71 @@ async fn get_tracker_for_sync<
     )
     .await?;
 
-    let attempts = match request.expand_attempts {
-        Some(true) => {
-            Some(db
+    // The nested resources of the payment are fetched concurrently, each with a single query
+    let attempts = async {
+        match request.expand_attempts {
+            Some(true) => db
                 .find_attempts_by_merchant_id_payment_id(merchant_id, &payment_id_str, storage_scheme)
                 .await
+                .map(Some)
                 .change_context(errors::ApiErrorResponse::InternalServerError)
                 .attach_printable_lazy(|| {
                     format!("Error while retrieving attempt list for, merchant_id: {merchant_id}, payment_id: {payment_id_str}")
-                })?)
-        },
-        _ => None,
+                }),
+            _ => Ok(None),
+        }
     };
 
-    let refunds = db
-        .find_refund_by_payment_id_merchant_id(&payment_id_str, merchant_id, storage_scheme)
-        .await
-        .change_context(errors::ApiErrorResponse::InternalServerError)
-        .attach_printable_lazy(|| {
-            format!(
-                "Failed while getting refund list for, payment_id: {}, merchant_id: {}",
-                &payment_id_str, merchant_id
-            )
-        })?;
-
-    let disputes = db
-        .find_disputes_by_merchant_id_payment_id(merchant_id, &payment_id_str)
-        .await
-        .change_context(errors::ApiErrorResponse::InternalServerError)
-        .attach_printable_lazy(|| {
-            format!("Error while retrieving dispute list for, merchant_id: {merchant_id}, payment_id: {payment_id_str}")
-        })?;
+    let captures = async {
+        match request.expand_captures {
+            Some(true) => db
+                .find_all_captures_by_merchant_id_payment_id_authorized_attempt_id(
+                    merchant_id,
+                    &payment_id_str,
+                    &payment_attempt.attempt_id,
+                    storage_scheme,
+                )
+                .await
+                .map(Some)
+                .change_context(errors::ApiErrorResponse::InternalServerError)
+                .attach_printable_lazy(|| {
+                    format!("Error while retrieving capture list for, merchant_id: {merchant_id}, payment_id: {payment_id_str}")
+                }),
+            _ => Ok(None),
+        }
+    };
+
+    let refunds = async {
+        match request.expand_refunds {
+            Some(false) => Ok(Vec::new()),
+            _ => db
+                .find_refund_by_payment_id_merchant_id(&payment_id_str, merchant_id, storage_scheme)
+                .await
+                .change_context(errors::ApiErrorResponse::InternalServerError)
+                .attach_printable_lazy(|| {
+                    format!(
+                        "Failed while getting refund list for, payment_id: {}, merchant_id: {}",
+                        &payment_id_str, merchant_id
+                    )
+                }),
+        }
+    };
+
+    let disputes = async {
+        match request.expand_disputes {
+            Some(false) => Ok(Vec::new()),
+            _ => db
+                .find_disputes_by_merchant_id_payment_id(merchant_id, &payment_id_str)
+                .await
+                .change_context(errors::ApiErrorResponse::InternalServerError)
+                .attach_printable_lazy(|| {
+                    format!("Error while retrieving dispute list for, merchant_id: {merchant_id}, payment_id: {payment_id_str}")
+                }),
+        }
+    };
+
+    let (attempts, captures, refunds, disputes) =
+        futures::try_join!(attempts, captures, refunds, disputes)?;
 
     let frm_response = db
         .find_fraud_check_by_payment_id(payment_id_str.to_string(), merchant_id.to_string())
@@ -346,7 +404,10 @@ async fn get_tracker_for_sync<
             refunds,
             disputes,
             attempts,
+            captures,
             sessions_token: vec![],
+            timed_out_session_connectors: vec![],
+            deferred_session_connectors: vec![],
             card_cvc: None,
             creds_identifier,
             pm_token: None,
@@ -356,6 +417,9 @@ async fn get_tracker_for_sync<
             multiple_capture_data: None,
             redirect_response: None,
             frm_message,
+            captcha_site_key: None,
+            payment_link_config: None,
+            expand_customer: request.expand_customer.unwrap_or(false),
         },
         None,
     ))
@@ -11,7 +11,10 @@ use super::{BoxedOperation, Domain, GetTracker, Operation, UpdateTracker, Valida
 use crate::{
     core::{
         errors::{self, CustomResult, RouterResult, StorageErrorExt},
-        payments::{self, helpers, operations, CustomerDetails, PaymentAddress, PaymentData},
+        payments::{
+            self, cross_border_fees, currency_conversion, helpers, operations, CustomerDetails,
+            PaymentAddress, PaymentData,
+        },
         utils as core_utils,
     },
     db::StorageInterface,
@@ -106,6 +109,12 @@ impl<F: Send + Clone> GetTracker<F, PaymentData<F>, api::PaymentsRequest> for Pa
             .attach_printable("Failed to convert order details to value")?
             .or(payment_intent.order_details);
 
+        payment_intent.l2_l3_data = request
+            .get_l2_l3_data_as_value()
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Failed to convert l2_l3_data to value")?
+            .or(payment_intent.l2_l3_data);
+
         payment_attempt = db
             .find_payment_attempt_by_payment_id_merchant_id_attempt_id(
                 payment_intent.payment_id.as_str(),
@@ -130,6 +139,13 @@ impl<F: Send + Clone> GetTracker<F, PaymentData<F>, api::PaymentsRequest> for Pa
             .amount
             .unwrap_or_else(|| payment_attempt.amount.into());
 
+        helpers::validate_amount_against_convenience_fee(&payment_intent, amount.into())?;
+        currency_conversion::validate_amount_update_for_currency_conversion(
+            &payment_intent,
+            amount.into(),
+            currency,
+        )?;
+
         if request.confirm.unwrap_or(false) {
             helpers::validate_customer_id_mandatory_cases(
                 request.shipping.is_some(),
@@ -189,6 +205,15 @@ impl<F: Send + Clone> GetTracker<F, PaymentData<F>, api::PaymentsRequest> for Pa
             .or(payment_intent.feature_metadata);
         payment_intent.metadata = request.metadata.clone().or(payment_intent.metadata);
         Self::populate_payment_intent_with_request(&mut payment_intent, request);
+        payment_intent.cross_border_fees = cross_border_fees::get_cross_border_fees(
+            db,
+            merchant_id,
+            payment_intent.business_country,
+            billing_address.as_ref().and_then(|address| address.country),
+            amount.into(),
+            currency,
+        )
+        .await?;
 
         let token = token.or_else(|| payment_attempt.payment_token.clone());
 
@@ -330,8 +355,11 @@ impl<F: Send + Clone> GetTracker<F, PaymentData<F>, api::PaymentsRequest> for Pa
                 refunds: vec![],
                 disputes: vec![],
                 attempts: None,
+                captures: None,
                 connector_response,
                 sessions_token: vec![],
+                timed_out_session_connectors: vec![],
+                deferred_session_connectors: vec![],
                 card_cvc: request.card_cvc.clone(),
                 creds_identifier,
                 pm_token: None,
@@ -341,6 +369,9 @@ impl<F: Send + Clone> GetTracker<F, PaymentData<F>, api::PaymentsRequest> for Pa
                 multiple_capture_data: None,
                 redirect_response: None,
                 frm_message: None,
+                captcha_site_key: None,
+                payment_link_config: None,
+                expand_customer: false,
             },
             Some(customer_details),
         ))
@@ -517,6 +548,8 @@ impl<F: Clone> UpdateTracker<F, PaymentData<F>, api::PaymentsRequest> for Paymen
             .clone();
         let order_details = payment_data.payment_intent.order_details.clone();
         let metadata = payment_data.payment_intent.metadata.clone();
+        let l2_l3_data = payment_data.payment_intent.l2_l3_data.clone();
+        let cross_border_fees = payment_data.payment_intent.cross_border_fees.clone();
 
         payment_data.payment_intent = db
             .update_payment_intent(
@@ -537,6 +570,8 @@ impl<F: Clone> UpdateTracker<F, PaymentData<F>, api::PaymentsRequest> for Paymen
                     statement_descriptor_suffix,
                     order_details,
                     metadata,
+                    l2_l3_data,
+                    cross_border_fees,
                 },
                 storage_scheme,
             )
@@ -579,6 +614,10 @@ impl<F: Send + Clone> ValidateRequest<F, api::PaymentsRequest> for PaymentUpdate
                 expected_format: "merchant_id from merchant account".to_string(),
             })?;
 
+        helpers::validate_return_url_domain(request.return_url.as_ref(), merchant_account)?;
+
+        helpers::validate_l2_l3_data(request.l2_l3_data.as_ref())?;
+
         helpers::validate_request_amount_and_amount_to_capture(
             request.amount,
             request.amount_to_capture,
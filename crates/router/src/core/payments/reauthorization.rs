@@ -0,0 +1,389 @@
+//! Re-authorization of payments awaiting capture whose authorization is nearing its expiry.
+//!
+//! Authorizations are only valid for a connector specific duration, after which the funds are
+//! released back to the customer. For connectors listed in the `reauthorization` settings, a
+//! process tracker task is scheduled when a payment is authorized, which re-authorizes the
+//! payment shortly before the authorization expires if the business of the payment has opted in
+//! using `auto_reauthorization_enabled`. The re-authorization is recorded as a new attempt linked
+//! to the authorized attempt, which remains the active attempt if the re-authorization fails.
+//!
+//! Checkout.com extends the validity of an authorization through an increment of the
+//! authorization by a zero amount.
+
+use error_stack::{IntoReport, ResultExt};
+use router_env::logger;
+use serde::{Deserialize, Serialize};
+
+use super::{helpers, CallConnectorAction};
+use crate::{
+    configs::settings,
+    core::{
+        errors::{self, ConnectorErrorExt, RouterResult, StorageErrorExt},
+        ledger, utils as core_utils,
+    },
+    db::StorageInterface,
+    routes::{metrics, AppState},
+    scheduler::{metrics as scheduler_metrics, utils as pt_utils},
+    services,
+    types::{
+        self, api, domain,
+        storage::{self, enums, ProcessTrackerExt},
+    },
+};
+
+const REAUTHORIZATION_RUNNER: &str = "PAYMENT_REAUTHORIZATION_WORKFLOW";
+const REAUTHORIZATION_TASK: &str = "PAYMENT_REAUTHORIZATION";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PaymentReauthorizationTrackingData {
+    pub merchant_id: String,
+    pub payment_id: String,
+    pub attempt_id: String,
+}
+
+/// Returns whether the business of the payment has opted in to automatic re-authorization.
+pub fn is_auto_reauthorization_enabled(
+    merchant_account: &domain::MerchantAccount,
+    payment_intent: &storage::PaymentIntent,
+) -> RouterResult<bool> {
+    Ok(helpers::get_business_profile_defaults(
+        payment_intent.business_country,
+        &payment_intent.business_label,
+        merchant_account,
+    )?
+    .and_then(|business_details| business_details.auto_reauthorization_enabled)
+    .unwrap_or(false))
+}
+
+/// Returns the validity of the authorizations of the connector of the attempt, if the connector
+/// supports re-authorization and re-authorization is enabled.
+fn get_authorization_validity<'a>(
+    state: &'a AppState,
+    payment_attempt: &storage::PaymentAttempt,
+) -> Option<&'a settings::AuthorizationValidity> {
+    let reauthorization = &state.conf.reauthorization;
+    if !reauthorization.enabled {
+        return None;
+    }
+
+    payment_attempt
+        .connector
+        .as_ref()
+        .and_then(|connector| reauthorization.connectors.get(connector))
+}
+
+/// Schedules the re-authorization of a payment which has been authorized and is awaiting
+/// capture, if its connector supports re-authorization and its business has opted in.
+pub async fn add_reauthorization_task_if_required(
+    state: &AppState,
+    merchant_account: &domain::MerchantAccount,
+    payment_intent: &storage::PaymentIntent,
+    payment_attempt: &storage::PaymentAttempt,
+) -> RouterResult<()> {
+    if payment_intent.status != enums::IntentStatus::RequiresCapture
+        || payment_attempt.status != enums::AttemptStatus::Authorized
+    {
+        return Ok(());
+    }
+
+    let authorization_validity = match get_authorization_validity(state, payment_attempt) {
+        Some(authorization_validity) => authorization_validity,
+        None => return Ok(()),
+    };
+
+    if !is_auto_reauthorization_enabled(merchant_account, payment_intent)? {
+        return Ok(());
+    }
+
+    let schedule_time = payment_attempt
+        .modified_at
+        .saturating_add(time::Duration::seconds(
+            authorization_validity.validity - authorization_validity.lead_time,
+        ));
+    let tracking_data = PaymentReauthorizationTrackingData {
+        merchant_id: payment_attempt.merchant_id.clone(),
+        payment_id: payment_attempt.payment_id.clone(),
+        attempt_id: payment_attempt.attempt_id.clone(),
+    };
+    let process_tracker_id = pt_utils::get_process_tracker_id(
+        REAUTHORIZATION_RUNNER,
+        REAUTHORIZATION_TASK,
+        &payment_attempt.attempt_id,
+        &payment_attempt.merchant_id,
+    );
+    let process_tracker_entry = <storage::ProcessTracker>::make_process_tracker_new(
+        process_tracker_id,
+        REAUTHORIZATION_TASK,
+        REAUTHORIZATION_RUNNER,
+        tracking_data,
+        schedule_time,
+    )
+    .change_context(errors::ApiErrorResponse::InternalServerError)
+    .attach_printable("Failed to construct the re-authorization task")?;
+
+    state
+        .store
+        .insert_process(process_tracker_entry)
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to schedule the re-authorization task")?;
+    scheduler_metrics::TASKS_ADDED_COUNT.add(&metrics::CONTEXT, 1, &[]);
+
+    Ok(())
+}
+
+/// Re-authorizes the authorized attempt of a payment awaiting capture through a new attempt.
+///
+/// The new attempt becomes the active attempt of the payment if the re-authorization succeeds,
+/// after which its own re-authorization is scheduled. Otherwise the authorized attempt remains
+/// the active attempt, so that it can still be captured until its authorization expires.
+pub async fn reauthorize_payment(
+    state: &AppState,
+    merchant_account: &domain::MerchantAccount,
+    key_store: &domain::MerchantKeyStore,
+    payment_intent: storage::PaymentIntent,
+    authorized_attempt: storage::PaymentAttempt,
+) -> RouterResult<storage::PaymentAttempt> {
+    let db: &dyn StorageInterface = &*state.store;
+    let storage_scheme = merchant_account.storage_scheme;
+    let connector_name = authorized_attempt
+        .connector
+        .clone()
+        .ok_or(errors::ApiErrorResponse::InternalServerError)
+        .into_report()
+        .attach_printable("Authorized attempt does not have a connector")?;
+
+    let new_attempt_count = payment_intent.attempt_count + 1;
+    let new_payment_attempt = storage::PaymentAttemptNew {
+        status: enums::AttemptStatus::Pending,
+        connector: authorized_attempt.connector.clone(),
+        payment_method: authorized_attempt.payment_method,
+        payment_method_id: authorized_attempt.payment_method_id.clone(),
+        payment_method_type: authorized_attempt.payment_method_type,
+        payment_method_data: authorized_attempt.payment_method_data.clone(),
+        payment_token: authorized_attempt.payment_token.clone(),
+        ..helpers::AttemptType::make_new_payment_attempt(
+            &None,
+            authorized_attempt.clone(),
+            new_attempt_count,
+        )
+    };
+    let payment_attempt = db
+        .insert_payment_attempt(new_payment_attempt, storage_scheme)
+        .await
+        .to_duplicate_response(errors::ApiErrorResponse::DuplicatePayment {
+            payment_id: payment_intent.payment_id.clone(),
+        })?;
+
+    let connector_data = api::ConnectorData::get_connector_by_name(
+        &state.conf.connectors,
+        &connector_name,
+        api::GetToken::Connector,
+    )?;
+    let connector_integration: services::BoxedConnectorIntegration<
+        '_,
+        api::Reauthorize,
+        types::PaymentsReauthorizeData,
+        types::PaymentsResponseData,
+    > = connector_data.connector.get_connector_integration();
+    let router_data = core_utils::construct_reauthorize_router_data(
+        state,
+        &payment_intent,
+        &payment_attempt,
+        &authorized_attempt,
+        merchant_account,
+        key_store,
+    )
+    .await?;
+    let router_data = services::execute_connector_processing_step(
+        state,
+        connector_integration,
+        &router_data,
+        CallConnectorAction::Trigger,
+        None,
+    )
+    .await
+    .to_payment_failed_response()
+    .attach_printable("Failed while calling the re-authorize connector api")?;
+
+    let (payment_attempt_update, reauthorized) =
+        get_reauthorization_attempt_update(router_data.status, router_data.response);
+    let active_attempt_id = if reauthorized {
+        payment_attempt.attempt_id.clone()
+    } else {
+        authorized_attempt.attempt_id.clone()
+    };
+
+    let payment_attempt = db
+        .update_payment_attempt_with_attempt_id(
+            payment_attempt,
+            payment_attempt_update,
+            storage_scheme,
+        )
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::PaymentNotFound)?;
+    let payment_intent = db
+        .update_payment_intent(
+            payment_intent,
+            storage::PaymentIntentUpdate::StatusAndAttemptUpdate {
+                status: enums::IntentStatus::RequiresCapture,
+                active_attempt_id,
+                attempt_count: new_attempt_count,
+            },
+            storage_scheme,
+        )
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::PaymentNotFound)?;
+
+    if reauthorized {
+        // The payment has already been re-authorized, so failing to post it to the ledger must
+        // not fail the re-authorization
+        ledger::record_reauthorization_postings(
+            state,
+            merchant_account,
+            &payment_intent,
+            &authorized_attempt,
+            &payment_attempt,
+        )
+        .await
+        .map_err(|error| logger::error!(ledger_error=?error))
+        .ok();
+        add_reauthorization_task_if_required(
+            state,
+            merchant_account,
+            &payment_intent,
+            &payment_attempt,
+        )
+        .await?;
+    } else {
+        logger::warn!(
+            payment_id = %payment_intent.payment_id,
+            attempt_id = %payment_attempt.attempt_id,
+            "Re-authorization failed, retaining the authorized attempt"
+        );
+    }
+
+    Ok(payment_attempt)
+}
+
+/// Returns the update of the re-authorization attempt from the response of the connector, along
+/// with whether the payment has been re-authorized.
+fn get_reauthorization_attempt_update(
+    status: enums::AttemptStatus,
+    response: Result<types::PaymentsResponseData, types::ErrorResponse>,
+) -> (storage::PaymentAttemptUpdate, bool) {
+    match response {
+        Ok(types::PaymentsResponseData::TransactionResponse {
+            resource_id,
+            connector_metadata,
+            connector_response_reference_id,
+            ..
+        }) if status == enums::AttemptStatus::Authorized => (
+            storage::PaymentAttemptUpdate::ResponseUpdate {
+                status: enums::AttemptStatus::Authorized,
+                connector: None,
+                connector_transaction_id: resource_id.get_connector_transaction_id().ok(),
+                authentication_type: None,
+                payment_method_id: None,
+                mandate_id: None,
+                connector_metadata,
+                payment_token: None,
+                error_code: None,
+                error_message: None,
+                error_reason: None,
+                connector_response_reference_id,
+            },
+            true,
+        ),
+        Ok(_) => (
+            storage::PaymentAttemptUpdate::ErrorUpdate {
+                connector: None,
+                status: enums::AttemptStatus::Failure,
+                error_code: None,
+                error_message: Some(Some(format!(
+                    "Re-authorization resulted in status {status}"
+                ))),
+                error_reason: None,
+            },
+            false,
+        ),
+        Err(error) => (
+            storage::PaymentAttemptUpdate::ErrorUpdate {
+                connector: None,
+                status: enums::AttemptStatus::Failure,
+                error_code: Some(Some(error.code)),
+                error_message: Some(Some(error.message)),
+                error_reason: Some(error.reason),
+            },
+            false,
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+
+    fn get_transaction_response() -> types::PaymentsResponseData {
+        types::PaymentsResponseData::TransactionResponse {
+            resource_id: types::ResponseId::ConnectorTransactionId("pay_123".to_string()),
+            redirection_data: None,
+            mandate_reference: None,
+            connector_metadata: None,
+            network_txn_id: None,
+            connector_response_reference_id: Some("act_123".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_get_reauthorization_attempt_update() {
+        let (update, reauthorized) = get_reauthorization_attempt_update(
+            enums::AttemptStatus::Authorized,
+            Ok(get_transaction_response()),
+        );
+        assert!(reauthorized);
+        assert!(matches!(
+            update,
+            storage::PaymentAttemptUpdate::ResponseUpdate {
+                status: enums::AttemptStatus::Authorized,
+                connector_transaction_id: Some(ref connector_transaction_id),
+                ..
+            } if connector_transaction_id == "pay_123"
+        ));
+
+        // A response which does not authorize the payment is a failed re-authorization
+        let (update, reauthorized) = get_reauthorization_attempt_update(
+            enums::AttemptStatus::Pending,
+            Ok(get_transaction_response()),
+        );
+        assert!(!reauthorized);
+        assert!(matches!(
+            update,
+            storage::PaymentAttemptUpdate::ErrorUpdate {
+                status: enums::AttemptStatus::Failure,
+                error_code: None,
+                ..
+            }
+        ));
+
+        let (update, reauthorized) = get_reauthorization_attempt_update(
+            enums::AttemptStatus::Failure,
+            Err(types::ErrorResponse {
+                code: "20051".to_string(),
+                message: "Insufficient Funds".to_string(),
+                reason: None,
+                status_code: 201,
+            }),
+        );
+        assert!(!reauthorized);
+        assert!(matches!(
+            update,
+            storage::PaymentAttemptUpdate::ErrorUpdate {
+                status: enums::AttemptStatus::Failure,
+                error_code: Some(Some(ref error_code)),
+                ..
+            } if error_code == "20051"
+        ));
+    }
+}
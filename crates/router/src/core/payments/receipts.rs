@@ -0,0 +1,624 @@
+//! Receipts of successful payments, generated for the customer.
+//!
+//! The receipt of a payment is rendered on demand from the payment and its successful attempt,
+//! with the receipt template and the branding of the business under which the payment was made,
+//! either as an HTML page or as a PDF document. Receipts are not stored, so that they always
+//! reflect the current template of the business. When enabled for the business, the receipt is
+//! also emailed to the customer once the payment is captured, in full or in part.
+
+use api_models::{
+    admin::{PaymentLinkConfig, PrimaryBusinessDetails},
+    payments::{AdditionalPaymentData, PaymentReceiptQuery, ReceiptFormat},
+};
+#[cfg(feature = "email")]
+use common_utils::pii;
+use error_stack::{report, IntoReport, ResultExt};
+use masking::PeekInterface;
+use maud::html;
+#[cfg(feature = "email")]
+use router_env::logger;
+use router_env::{instrument, tracing};
+
+use super::{helpers, hosted_checkout};
+use crate::{
+    core::errors::{self, RouterResponse, RouterResult, StorageErrorExt},
+    routes::AppState,
+    services::ApplicationResponse,
+    types::{
+        domain,
+        storage::{self, enums},
+    },
+    utils::{OptionExt, ValueExt},
+};
+
+const DEFAULT_RECEIPT_TITLE: &str = "Receipt";
+
+const PDF_PAGE_WIDTH: u32 = 595;
+const PDF_PAGE_HEIGHT: u32 = 842;
+const PDF_MARGIN: u32 = 56;
+const PDF_TITLE_HEIGHT: u32 = 40;
+const PDF_LINE_HEIGHT: u32 = 16;
+const PDF_MAX_LINE_LENGTH: usize = 90;
+
+/// The contents of the receipt of a payment, from which the receipt is rendered in each format.
+struct Receipt {
+    title: String,
+    merchant_name: Option<String>,
+    header: Option<String>,
+    footer: Option<String>,
+    branding: PaymentLinkConfig,
+    details: Vec<(&'static str, String)>,
+    contact_details: Vec<(&'static str, String)>,
+}
+
+/// Returns whether the payment has been captured, in full or in part, so that it has a receipt
+pub fn is_receipt_available(status: enums::IntentStatus) -> bool {
+    matches!(
+        status,
+        enums::IntentStatus::Succeeded | enums::IntentStatus::PartiallyCaptured
+    )
+}
+
+fn format_time(time: time::PrimitiveDateTime) -> String {
+    format!(
+        "{} {:02}:{:02} UTC",
+        time.date(),
+        time.hour(),
+        time.minute()
+    )
+}
+
+/// Describes the payment method of the payment, with the last digits of the card for card
+/// payments.
+fn get_payment_method_description(payment_attempt: &storage::PaymentAttempt) -> Option<String> {
+    let card_description = payment_attempt
+        .payment_method_data
+        .clone()
+        .and_then(|payment_method_data| {
+            payment_method_data
+                .parse_value::<AdditionalPaymentData>("AdditionalPaymentData")
+                .ok()
+        })
+        .and_then(|payment_method_data| match payment_method_data {
+            AdditionalPaymentData::Card(card) => card.last4.map(|last4| {
+                let card_network = card.card_network.map_or_else(
+                    || "Card".to_string(),
+                    |card_network| card_network.to_string(),
+                );
+                format!("{card_network} ending in {last4}")
+            }),
+            _ => None,
+        });
+
+    card_description.or_else(|| {
+        payment_attempt
+            .payment_method_type
+            .map(|payment_method_type| payment_method_type.to_string())
+            .or_else(|| {
+                payment_attempt
+                    .payment_method
+                    .map(|payment_method| payment_method.to_string())
+            })
+            .map(|payment_method| payment_method.replace('_', " "))
+    })
+}
+
+impl Receipt {
+    fn new(
+        merchant_account: &domain::MerchantAccount,
+        payment_intent: &storage::PaymentIntent,
+        payment_attempt: &storage::PaymentAttempt,
+        business_profile_defaults: Option<PrimaryBusinessDetails>,
+    ) -> RouterResult<Self> {
+        let currency = payment_intent.currency.get_required_value("currency")?;
+        let amount = currency
+            .to_currency_base_unit(
+                payment_intent
+                    .amount_captured
+                    .unwrap_or(payment_intent.amount),
+            )
+            .into_report()
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Failed to convert the amount of the receipt")?;
+
+        let mut details = vec![
+            ("Payment ID", payment_intent.payment_id.clone()),
+            ("Date", format_time(payment_attempt.modified_at)),
+            ("Amount paid", format!("{amount} {currency}")),
+        ];
+        if let Some(payment_method) = get_payment_method_description(payment_attempt) {
+            details.push(("Payment method", payment_method));
+        }
+        if let Some(description) = &payment_intent.description {
+            details.push(("Description", description.clone()));
+        }
+        if let Some(statement_descriptor) = &payment_intent.statement_descriptor_name {
+            details.push(("Statement descriptor", statement_descriptor.clone()));
+        }
+
+        let (receipt_config, branding, support_contact) = business_profile_defaults
+            .map(|business_profile_defaults| {
+                (
+                    business_profile_defaults.receipt_config,
+                    business_profile_defaults.payment_link_config,
+                    business_profile_defaults.support_contact,
+                )
+            })
+            .unwrap_or_default();
+        let receipt_config = receipt_config.unwrap_or_default();
+
+        let mut contact_details = Vec::new();
+        if let Some(support_contact) = support_contact {
+            if let Some(email) = support_contact.email {
+                contact_details.push(("Email", email.peek().clone()));
+            }
+            if let Some(phone) = support_contact.phone {
+                contact_details.push(("Phone", phone.peek().clone()));
+            }
+            if let Some(url) = support_contact.url {
+                contact_details.push(("Website", url));
+            }
+        }
+
+        Ok(Self {
+            title: receipt_config
+                .title
+                .unwrap_or_else(|| DEFAULT_RECEIPT_TITLE.to_string()),
+            merchant_name: merchant_account
+                .merchant_name
+                .as_ref()
+                .map(|merchant_name| merchant_name.peek().clone()),
+            header: receipt_config.header,
+            footer: receipt_config.footer,
+            branding: branding.unwrap_or_default(),
+            details,
+            contact_details,
+        })
+    }
+
+    /// The text of the receipt, used for the receipts sent by email and the PDF receipts.
+    fn get_text_lines(&self) -> Vec<String> {
+        let mut lines = Vec::new();
+        if let Some(merchant_name) = &self.merchant_name {
+            lines.extend([merchant_name.clone(), String::new()]);
+        }
+        if let Some(header) = &self.header {
+            lines.extend([header.clone(), String::new()]);
+        }
+        lines.extend(
+            self.details
+                .iter()
+                .map(|(label, value)| format!("{label}: {value}")),
+        );
+        if !self.contact_details.is_empty() {
+            lines.extend([String::new(), "Contact us".to_string()]);
+            lines.extend(
+                self.contact_details
+                    .iter()
+                    .map(|(label, value)| format!("{label}: {value}")),
+            );
+        }
+        if let Some(footer) = &self.footer {
+            lines.extend([String::new(), footer.clone()]);
+        }
+        lines
+    }
+
+    fn render_html(&self) -> Vec<u8> {
+        hosted_checkout::render_page(
+            &self.title,
+            html! {
+                @if let Some(background_color) = &self.branding.background_color {
+                    style { (format!("body {{ background-color: {background_color}; }}")) }
+                }
+                @if let Some(primary_color) = &self.branding.primary_color {
+                    style { (format!("h1, h2 {{ color: {primary_color}; }}")) }
+                }
+                @if let Some(logo) = &self.branding.logo {
+                    img.logo src=(logo) alt="Logo";
+                }
+                h1 { (self.title) }
+                @if let Some(merchant_name) = &self.merchant_name {
+                    p.description { (merchant_name) }
+                }
+                @if let Some(header) = &self.header {
+                    p { (header) }
+                }
+                table.receipt {
+                    @for (label, value) in &self.details {
+                        tr {
+                            th { (label) }
+                            td { (value) }
+                        }
+                    }
+                }
+                @if !self.contact_details.is_empty() {
+                    h2 { "Contact us" }
+                    table.receipt {
+                        @for (label, value) in &self.contact_details {
+                            tr {
+                                th { (label) }
+                                td { (value) }
+                            }
+                        }
+                    }
+                }
+                @if let Some(footer) = &self.footer {
+                    p.description { (footer) }
+                }
+            },
+        )
+    }
+
+    /// Renders the receipt as a PDF document using the standard fonts of PDF readers. The logo
+    /// of the business is not embedded in the document.
+    fn render_pdf(&self) -> Vec<u8> {
+        let title_color = self
+            .branding
+            .primary_color
+            .as_deref()
+            .and_then(get_pdf_color)
+            .unwrap_or_else(|| "0 0 0".to_string());
+        let lines = self
+            .get_text_lines()
+            .iter()
+            .flat_map(|line| wrap_pdf_text(line))
+            .collect::<Vec<_>>();
+        let lines_per_page = usize::try_from(
+            (PDF_PAGE_HEIGHT - 2 * PDF_MARGIN - PDF_TITLE_HEIGHT) / PDF_LINE_HEIGHT,
+        )
+        .unwrap_or(1);
+
+        let page_contents = lines
+            .chunks(lines_per_page)
+            .map(|lines| {
+                let title_top = PDF_PAGE_HEIGHT - PDF_MARGIN;
+                let text_top = title_top - PDF_TITLE_HEIGHT;
+                let text = lines
+                    .iter()
+                    .map(|line| format!("({}) Tj T*\n", escape_pdf_text(line)))
+                    .collect::<String>();
+                format!(
+                    "BT /F2 18 Tf {title_color} rg {PDF_MARGIN} {title_top} Td ({}) Tj ET\n\
+                     BT /F1 11 Tf 0 0 0 rg {PDF_LINE_HEIGHT} TL {PDF_MARGIN} {text_top} Td\n\
+                     {text}ET\n",
+                    escape_pdf_text(&self.title)
+                )
+            })
+            .collect::<Vec<_>>();
+
+        write_pdf(&page_contents)
+    }
+}
+
+/// Converts a hexadecimal RGB code to the color operands of PDF content streams.
+fn get_pdf_color(hex_color: &str) -> Option<String> {
+    let hex_color = hex_color.strip_prefix('#')?;
+    let component = |index: usize| {
+        hex_color
+            .get(index..index + 2)
+            .and_then(|component| u8::from_str_radix(component, 16).ok())
+            .map(|component| f64::from(component) / 255.0)
+    };
+    Some(format!(
+        "{:.3} {:.3} {:.3}",
+        component(0)?,
+        component(2)?,
+        component(4)?
+    ))
+}
+
+/// Escapes text for a string of a PDF content stream. The standard fonts do not cover the
+/// characters outside of printable ASCII without embedding a font, so they are replaced.
+fn escape_pdf_text(text: &str) -> String {
+    text.chars()
+        .fold(String::with_capacity(text.len()), |mut escaped, c| {
+            match c {
+                '\\' | '(' | ')' => {
+                    escaped.push('\\');
+                    escaped.push(c);
+                }
+                ' '..='~' => escaped.push(c),
+                _ => escaped.push('?'),
+            }
+            escaped
+        })
+}
+
+/// Splits text into lines of at most `PDF_MAX_LINE_LENGTH` characters on whitespace, as text is
+/// not wrapped in PDF documents.
+fn wrap_pdf_text(text: &str) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut line = String::new();
+    for word in text.split_whitespace() {
+        if !line.is_empty() && line.chars().count() + word.chars().count() >= PDF_MAX_LINE_LENGTH {
+            lines.push(std::mem::take(&mut line));
+        }
+        if !line.is_empty() {
+            line.push(' ');
+        }
+        line.push_str(word);
+    }
+    lines.push(line);
+    lines
+}
+
+/// Writes a PDF document with a page for each of the content streams. The objects of the
+/// document are the catalog, the page tree and the regular and bold fonts, followed by each page
+/// and its content stream.
+fn write_pdf(page_contents: &[String]) -> Vec<u8> {
+    let page_ids = (0..page_contents.len())
+        .map(|index| 5 + 2 * index)
+        .collect::<Vec<_>>();
+    let mut objects = vec![
+        "<< /Type /Catalog /Pages 2 0 R >>".to_string(),
+        format!(
+            "<< /Type /Pages /Kids [{}] /Count {} >>",
+            page_ids
+                .iter()
+                .map(|page_id| format!("{page_id} 0 R"))
+                .collect::<Vec<_>>()
+                .join(" "),
+            page_ids.len()
+        ),
+        "<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica /Encoding /WinAnsiEncoding >>"
+            .to_string(),
+        "<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica-Bold /Encoding /WinAnsiEncoding >>"
+            .to_string(),
+    ];
+    for (page_id, content) in page_ids.iter().zip(page_contents) {
+        objects.push(format!(
+            "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {PDF_PAGE_WIDTH} {PDF_PAGE_HEIGHT}] \
+             /Resources << /Font << /F1 3 0 R /F2 4 0 R >> >> /Contents {} 0 R >>",
+            page_id + 1
+        ));
+        objects.push(format!(
+            "<< /Length {} >>\nstream\n{content}endstream",
+            content.len()
+        ));
+    }
+
+    let mut pdf = String::from("%PDF-1.4\n");
+    let mut offsets = Vec::with_capacity(objects.len());
+    for (index, object) in objects.iter().enumerate() {
+        offsets.push(pdf.len());
+        pdf.push_str(&format!("{} 0 obj\n{object}\nendobj\n", index + 1));
+    }
+    let xref_offset = pdf.len();
+    pdf.push_str(&format!(
+        "xref\n0 {}\n0000000000 65535 f \n",
+        objects.len() + 1
+    ));
+    for offset in offsets {
+        pdf.push_str(&format!("{offset:010} 00000 n \n"));
+    }
+    pdf.push_str(&format!(
+        "trailer\n<< /Size {} /Root 1 0 R >>\nstartxref\n{xref_offset}\n%%EOF\n",
+        objects.len() + 1
+    ));
+
+    pdf.into_bytes()
+}
+
+/// Retrieves the receipt of a successful payment, in the requested format.
+#[instrument(skip(state, merchant_account))]
+pub async fn retrieve_payment_receipt(
+    state: &AppState,
+    merchant_account: domain::MerchantAccount,
+    payment_id: String,
+    query: PaymentReceiptQuery,
+) -> RouterResponse<()> {
+    let db = &*state.store;
+    let payment_intent = db
+        .find_payment_intent_by_payment_id_merchant_id(
+            &payment_id,
+            &merchant_account.merchant_id,
+            merchant_account.storage_scheme,
+        )
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::PaymentNotFound)?;
+    if !is_receipt_available(payment_intent.status) {
+        return Err(report!(errors::ApiErrorResponse::PreconditionFailed {
+            message: format!(
+                "Receipts are only available for successful payments, the payment is {}",
+                payment_intent.status
+            ),
+        }));
+    }
+
+    let payment_attempt = db
+        .find_payment_attempt_by_payment_id_merchant_id_attempt_id(
+            &payment_intent.payment_id,
+            &merchant_account.merchant_id,
+            &payment_intent.active_attempt_id,
+            merchant_account.storage_scheme,
+        )
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::PaymentNotFound)?;
+    let business_profile_defaults = helpers::get_business_profile_defaults(
+        payment_intent.business_country,
+        &payment_intent.business_label,
+        &merchant_account,
+    )?;
+    let receipt = Receipt::new(
+        &merchant_account,
+        &payment_intent,
+        &payment_attempt,
+        business_profile_defaults,
+    )?;
+
+    Ok(ApplicationResponse::FileData(match query.format {
+        ReceiptFormat::Html => (receipt.render_html(), mime::TEXT_HTML_UTF_8),
+        ReceiptFormat::Pdf => (receipt.render_pdf(), mime::APPLICATION_PDF),
+    }))
+}
+
+/// Emails the receipt of a payment which has just been captured to the customer, when receipts
+/// are emailed for the business under which the payment was made. The email is sent in the
+/// background, so that the payment is not delayed by it.
+#[cfg(feature = "email")]
+pub fn send_receipt_email_if_enabled(
+    state: &AppState,
+    merchant_account: &domain::MerchantAccount,
+    payment_intent: &storage::PaymentIntent,
+    payment_attempt: &storage::PaymentAttempt,
+    customer_email: Option<pii::Email>,
+) -> RouterResult<()> {
+    let business_profile_defaults = helpers::get_business_profile_defaults(
+        payment_intent.business_country,
+        &payment_intent.business_label,
+        merchant_account,
+    )?;
+    let is_email_enabled = business_profile_defaults
+        .as_ref()
+        .and_then(|business_profile_defaults| business_profile_defaults.receipt_config.as_ref())
+        .and_then(|receipt_config| receipt_config.email_enabled)
+        .unwrap_or(false);
+    if !is_email_enabled {
+        return Ok(());
+    }
+    let Some(customer_email) = customer_email else {
+        logger::info!(
+            payment_id = %payment_intent.payment_id,
+            "The customer has no email address, receipt not emailed"
+        );
+        return Ok(());
+    };
+
+    let receipt = Receipt::new(
+        merchant_account,
+        payment_intent,
+        payment_attempt,
+        business_profile_defaults,
+    )?;
+    let subject = match &receipt.merchant_name {
+        Some(merchant_name) => format!("Your receipt from {merchant_name}"),
+        None => "Your payment receipt".to_string(),
+    };
+    let body = receipt.get_text_lines().join("\n");
+    let email_client = state.email_client.clone();
+    let payment_id = payment_intent.payment_id.clone();
+
+    crate::async_spawn!({
+        if let Err(error) = email_client.send_email(customer_email, subject, body).await {
+            logger::error!(%payment_id, receipt_email_error=?error);
+        }
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+
+    fn get_receipt(details: Vec<(&'static str, String)>) -> Receipt {
+        Receipt {
+            title: "Receipt".to_string(),
+            merchant_name: Some("Juspay (India)".to_string()),
+            header: None,
+            footer: Some("Thank you".to_string()),
+            branding: PaymentLinkConfig::default(),
+            details,
+            contact_details: Vec::new(),
+        }
+    }
+
+    /// Checks the cross-reference table of the document, which lists the offset of each object
+    fn assert_valid_pdf(pdf: &str) {
+        assert!(pdf.starts_with("%PDF-1.4\n"));
+        assert!(pdf.ends_with("%%EOF\n"));
+
+        let (_, trailer) = pdf.rsplit_once("startxref\n").unwrap();
+        let xref_offset = trailer.lines().next().unwrap().parse::<usize>().unwrap();
+        let xref = pdf.get(xref_offset..).unwrap();
+        assert!(xref.starts_with("xref\n"));
+
+        let object_count = xref
+            .lines()
+            .nth(1)
+            .and_then(|line| line.strip_prefix("0 "))
+            .unwrap()
+            .parse::<usize>()
+            .unwrap();
+        let offsets = xref.lines().skip(3).take(object_count - 1);
+        for (index, offset) in offsets.enumerate() {
+            let offset = offset.split(' ').next().unwrap().parse::<usize>().unwrap();
+            assert!(pdf
+                .get(offset..)
+                .unwrap()
+                .starts_with(&format!("{} 0 obj\n", index + 1)));
+        }
+        assert!(pdf.contains(&format!("trailer\n<< /Size {object_count} /Root 1 0 R >>")));
+    }
+
+    #[test]
+    fn test_write_pdf() {
+        let page_contents = vec![
+            "BT (first) Tj ET\n".to_string(),
+            "BT (second) Tj ET\n".to_string(),
+        ];
+        let pdf = String::from_utf8(write_pdf(&page_contents)).unwrap();
+
+        assert_valid_pdf(&pdf);
+        assert!(pdf.contains("<< /Type /Pages /Kids [5 0 R 7 0 R] /Count 2 >>"));
+        assert!(pdf.contains("/Contents 6 0 R"));
+        assert!(pdf.contains("/Contents 8 0 R"));
+        assert!(pdf.contains("<< /Length 17 >>\nstream\nBT (first) Tj ET\nendstream"));
+    }
+
+    #[test]
+    fn test_render_pdf_splits_the_receipt_in_pages() {
+        let receipt = get_receipt(vec![("Payment ID", "pay_1".to_string())]);
+        let pdf = String::from_utf8(receipt.render_pdf()).unwrap();
+        assert_valid_pdf(&pdf);
+        assert!(pdf.contains("/Count 1 >>"));
+        assert!(pdf.contains("(Juspay \\(India\\)) Tj T*"));
+        assert!(pdf.contains("(Payment ID: pay_1) Tj T*"));
+
+        let receipt = get_receipt(vec![("Description", "word ".repeat(1000))]);
+        let pdf = String::from_utf8(receipt.render_pdf()).unwrap();
+        assert_valid_pdf(&pdf);
+        assert!(pdf.contains("/Count 2 >>"));
+    }
+
+    #[test]
+    fn test_escape_pdf_text() {
+        assert_eq!(escape_pdf_text("Total (EUR)"), "Total \\(EUR\\)");
+        assert_eq!(escape_pdf_text("C:\\receipts"), "C:\\\\receipts");
+        // Characters outside of printable ASCII are not covered by the standard fonts
+        assert_eq!(escape_pdf_text("Caf\u{e9}\n"), "Caf??");
+    }
+
+    #[test]
+    fn test_wrap_pdf_text() {
+        assert_eq!(
+            wrap_pdf_text("Amount paid: 10.00 USD"),
+            vec!["Amount paid: 10.00 USD"]
+        );
+        assert_eq!(wrap_pdf_text(""), vec![""]);
+
+        let lines = wrap_pdf_text(&"word ".repeat(100));
+        assert!(lines.len() > 1);
+        assert!(lines
+            .iter()
+            .all(|line| line.chars().count() < PDF_MAX_LINE_LENGTH));
+        assert_eq!(lines.join(" "), "word ".repeat(100).trim_end());
+
+        // Words longer than a line are kept whole
+        let word = "a".repeat(PDF_MAX_LINE_LENGTH + 10);
+        assert_eq!(wrap_pdf_text(&word), vec![word]);
+    }
+
+    #[test]
+    fn test_get_pdf_color() {
+        assert_eq!(
+            get_pdf_color("#ff8000"),
+            Some("1.000 0.502 0.000".to_string())
+        );
+        assert_eq!(get_pdf_color("ff8000"), None);
+        assert_eq!(get_pdf_color("#ff80"), None);
+        assert_eq!(get_pdf_color("#gg8000"), None);
+    }
+}
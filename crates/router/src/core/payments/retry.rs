@@ -0,0 +1,338 @@
+//! Automatic retries of payments declined by a connector.
+//!
+//! When the routing algorithm of the merchant is a `priority` list of connectors, a payment
+//! declined by a connector with a retryable error is retried on the next eligible connector of the
+//! list. With `cost_based` routing, the payment is retried on the next cheapest connector. Each retry is made with a new payment attempt linked to the declined attempt, so that the
+//! cascade of connectors the payment went through is kept in the history of its attempts.
+//!
+//! Payments are retried up to the number of times set in the `max_auto_retries_{merchant_id}`
+//! config of the merchant, capped by the `payment_retries.max_retries` setting, and are not
+//! retried when the merchant has not set the config.
+
+use error_stack::ResultExt;
+use router_env::{instrument, logger, tracing};
+
+use super::{connector_exclusion, currency_conversion, helpers, routing, PaymentData};
+use crate::{
+    configs::settings,
+    core::errors::{self, RouterResult, StorageErrorExt},
+    routes::{metrics, AppState},
+    types::{self, api, domain, storage},
+    utils::ValueExt,
+};
+
+fn get_config_key(merchant_id: &str) -> String {
+    format!("max_auto_retries_{merchant_id}")
+}
+
+/// Returns the maximum number of automatic retries of the payments of the merchant, 0 when the
+/// merchant has not enabled automatic retries.
+pub async fn get_max_retries(state: &AppState, merchant_id: &str) -> u8 {
+    let max_retries = match state
+        .store
+        .find_config_by_key_cached(&get_config_key(merchant_id))
+        .await
+    {
+        Ok(config) => config
+            .config
+            .trim()
+            .parse::<u8>()
+            .map_err(|error| logger::error!(max_auto_retries_parsing_error=?error))
+            .unwrap_or_default(),
+        Err(error) if error.current_context().is_db_not_found() => 0,
+        Err(error) => {
+            logger::error!(max_auto_retries_error=?error);
+            0
+        }
+    };
+    max_retries.min(state.conf.payment_retries.max_retries)
+}
+
+/// Returns whether the connector declined the payment with an error with which it can be retried
+/// on another connector.
+pub fn is_retryable_error<F, Req>(
+    state: &AppState,
+    connector: &api::ConnectorData,
+    router_data: &types::RouterData<F, Req, types::PaymentsResponseData>,
+) -> bool {
+    let Err(error) = &router_data.response else {
+        return false;
+    };
+
+    is_retryable_error_response(&state.conf.payment_retries, connector, error)
+}
+
+fn is_retryable_error_response(
+    settings: &settings::PaymentRetrySettings,
+    connector: &api::ConnectorData,
+    error: &types::ErrorResponse,
+) -> bool {
+    (settings.retry_on_server_errors && error.status_code >= 500)
+        || settings
+            .retryable_error_codes
+            .get(&connector.connector_name.to_string())
+            .map_or(false, |error_codes| error_codes.contains(&error.code))
+}
+
+/// Validates that the payment can be retried on the connector, applying the same checks as when
+/// the payment is routed to the connector and confirmed.
+async fn validate_retry_connector<F: Clone>(
+    state: &AppState,
+    merchant_account: &domain::MerchantAccount,
+    key_store: &domain::MerchantKeyStore,
+    connector: &api::ConnectorData,
+    payment_data: &PaymentData<F>,
+) -> RouterResult<()> {
+    let connector_id = connector.connector.id();
+    helpers::validate_payment_method_type_enabled(
+        state,
+        merchant_account,
+        key_store,
+        connector_id,
+        payment_data,
+    )
+    .await?;
+    helpers::validate_connector_amount_limits(state, connector_id, payment_data)?;
+    connector_exclusion::validate_connector_not_excluded(
+        &*state.store,
+        merchant_account,
+        connector_id,
+        payment_data,
+    )
+    .await?;
+    currency_conversion::validate_connector_for_currency_conversion(
+        state,
+        &payment_data.payment_intent,
+        connector,
+    )?;
+    helpers::validate_capture_method_for_connector(
+        state,
+        payment_data.payment_attempt.capture_method,
+        connector.connector_name,
+    )
+}
+
+/// Returns the next connector of the `priority` or `cost_based` routing algorithm of the merchant
+/// on which the payment can be retried, skipping the connectors the payment was already attempted with and the
+/// connectors which are not eligible for the payment.
+#[instrument(skip_all)]
+pub async fn get_next_connector<F: Clone>(
+    state: &AppState,
+    merchant_account: &domain::MerchantAccount,
+    key_store: &domain::MerchantKeyStore,
+    payment_data: &PaymentData<F>,
+    attempted_connectors: &[String],
+) -> RouterResult<Option<api::ConnectorData>> {
+    let Some(routing_algorithm) = merchant_account.routing_algorithm.clone() else {
+        return Ok(None);
+    };
+    let routing_algorithm = routing_algorithm
+        .parse_value::<api::RoutingAlgorithm>("RoutingAlgorithm")
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Unable to deserialize merchant routing algorithm")?;
+    let connectors = match routing_algorithm {
+        api::RoutingAlgorithm::Priority(connectors) => connectors,
+        api::RoutingAlgorithm::CostBased(connectors) => {
+            let payment_cost_details =
+                routing::PaymentCostDetails::from_payment_data(&*state.store, payment_data).await;
+            routing::get_connector_costs(
+                state,
+                &merchant_account.merchant_id,
+                &connectors,
+                &payment_cost_details,
+            )
+            .await?
+            .into_iter()
+            .map(|connector_cost| connector_cost.connector)
+            .collect()
+        }
+        api::RoutingAlgorithm::Single(_) | api::RoutingAlgorithm::VolumeSplit(_) => {
+            return Ok(None)
+        }
+    };
+
+    let runtime_conf = state.runtime_conf.get();
+    for connector_name in connectors.iter().map(ToString::to_string) {
+        if attempted_connectors.contains(&connector_name) {
+            continue;
+        }
+
+        let connector_data = api::ConnectorData::get_connector_by_name(
+            &state.conf.connectors,
+            &connector_name,
+            api::GetToken::Connector,
+        )
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Invalid connector name received in routing algorithm")?;
+
+        if runtime_conf
+            .connector_requests
+            .connectors_under_maintenance
+            .contains(&connector_data.connector_name)
+        {
+            continue;
+        }
+
+        match validate_retry_connector(
+            state,
+            merchant_account,
+            key_store,
+            &connector_data,
+            payment_data,
+        )
+        .await
+        {
+            Ok(()) => return Ok(Some(connector_data)),
+            Err(error) => {
+                logger::info!(ineligible_retry_connector = %connector_name, ?error);
+            }
+        }
+    }
+
+    Ok(None)
+}
+
+/// Replaces the declined attempt of the payment with a new attempt routed to the connector, which
+/// is linked to the declined attempt.
+#[instrument(skip_all)]
+pub async fn create_retry_attempt<F: Clone>(
+    state: &AppState,
+    merchant_account: &domain::MerchantAccount,
+    payment_data: &mut PaymentData<F>,
+    connector: &api::ConnectorData,
+) -> RouterResult<()> {
+    let db = &*state.store;
+    let storage_scheme = merchant_account.storage_scheme;
+    let declined_attempt = payment_data.payment_attempt.clone();
+    let new_attempt_count = payment_data.payment_intent.attempt_count + 1;
+
+    let mut new_payment_attempt = helpers::AttemptType::make_new_payment_attempt(
+        &payment_data.payment_method_data,
+        declined_attempt.clone(),
+        new_attempt_count,
+    );
+    // The payment is retried with the payment method of the declined attempt
+    new_payment_attempt.connector = Some(connector.connector_name.to_string());
+    new_payment_attempt.payment_method = declined_attempt.payment_method;
+    new_payment_attempt.payment_method_type = declined_attempt.payment_method_type;
+    new_payment_attempt.payment_experience = declined_attempt.payment_experience;
+    new_payment_attempt.payment_token = declined_attempt.payment_token.clone();
+    new_payment_attempt.browser_info = declined_attempt.browser_info.clone();
+
+    let new_payment_attempt = db
+        .insert_payment_attempt(new_payment_attempt, storage_scheme)
+        .await
+        .to_duplicate_response(errors::ApiErrorResponse::DuplicatePayment {
+            payment_id: payment_data.payment_intent.payment_id.clone(),
+        })?;
+
+    payment_data.connector_response = helpers::AttemptType::New
+        .get_connector_response(&new_payment_attempt, db, storage_scheme)
+        .await?;
+
+    payment_data.payment_intent = db
+        .update_payment_intent(
+            payment_data.payment_intent.clone(),
+            storage::PaymentIntentUpdate::StatusAndAttemptUpdate {
+                status: helpers::payment_intent_status_fsm(
+                    &payment_data.payment_method_data,
+                    Some(true),
+                ),
+                active_attempt_id: new_payment_attempt.attempt_id.clone(),
+                attempt_count: new_attempt_count,
+            },
+            storage_scheme,
+        )
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::PaymentNotFound)?;
+
+    logger::info!(
+        payment_id = %payment_data.payment_intent.payment_id,
+        declined_attempt_id = %declined_attempt.attempt_id,
+        declined_connector = ?declined_attempt.connector,
+        error_code = ?declined_attempt.error_code,
+        retry_attempt_id = %new_payment_attempt.attempt_id,
+        retry_connector = %connector.connector_name,
+        "Retrying declined payment on the next connector"
+    );
+    metrics::PAYMENT_AUTO_RETRIES.add(
+        &metrics::CONTEXT,
+        1,
+        &[
+            metrics::request::add_attributes(
+                "declined_connector",
+                declined_attempt.connector.clone().unwrap_or_default(),
+            ),
+            metrics::request::add_attributes(
+                "retry_connector",
+                connector.connector_name.to_string(),
+            ),
+        ],
+    );
+
+    // The connector specific data of the declined attempt does not apply to the new connector
+    payment_data.payment_attempt = new_payment_attempt;
+    payment_data.pm_token = None;
+    payment_data.connector_customer_id = None;
+    payment_data.sessions_token.clear();
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+
+    fn get_error_response(code: &str, status_code: u16) -> types::ErrorResponse {
+        types::ErrorResponse {
+            code: code.to_string(),
+            status_code,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_is_retryable_error_response() {
+        let connector = api::ConnectorData::get_connector_by_name(
+            &settings::Connectors::default(),
+            "stripe",
+            api::GetToken::Connector,
+        )
+        .unwrap();
+        let mut settings = settings::PaymentRetrySettings {
+            retryable_error_codes: [(
+                "stripe".to_string(),
+                ["processing_error".to_string()].into_iter().collect(),
+            )]
+            .into_iter()
+            .collect(),
+            ..Default::default()
+        };
+
+        assert!(is_retryable_error_response(
+            &settings,
+            &connector,
+            &get_error_response("processing_error", 402),
+        ));
+        assert!(!is_retryable_error_response(
+            &settings,
+            &connector,
+            &get_error_response("card_declined", 402),
+        ));
+        // Server errors are not retried by default, as the connector may have processed the
+        // payment
+        assert!(!is_retryable_error_response(
+            &settings,
+            &connector,
+            &get_error_response("api_error", 500),
+        ));
+
+        settings.retry_on_server_errors = true;
+        assert!(is_retryable_error_response(
+            &settings,
+            &connector,
+            &get_error_response("api_error", 500),
+        ));
+    }
+}
@@ -0,0 +1,572 @@
+//! Cost based routing of payments, and the preview of routing decisions.
+//!
+//! Merchants store the fee schedules of their connectors, listing the fees charged by each
+//! connector as a share of the amount of the payment along with a fixed amount, by currency, card
+//! network and country. Cost based routing computes the fee each connector of the routing
+//! algorithm is expected to charge for the payment when the payment is routed, and picks the
+//! cheapest connector accepting the payment. The routing decision for a payment can be previewed
+//! without creating the payment.
+
+use std::collections::HashSet;
+
+use api_models::routing::{
+    ConnectorCost, ConnectorFee, ConnectorFeeSchedules, RoutingSimulationRequest,
+    RoutingSimulationResponse,
+};
+use common_utils::ext_traits::{Encode, StringExt, ValueExt};
+use error_stack::{report, ResultExt};
+use router_env::{instrument, logger, tracing};
+
+use super::PaymentData;
+use crate::{
+    consts,
+    core::errors::{self, RouterResponse, RouterResult},
+    db::StorageInterface,
+    routes::AppState,
+    services,
+    types::{
+        api::{self, enums as api_enums},
+        domain, storage,
+    },
+    utils::{self, OptionExt},
+};
+
+/// Basis points making up the whole amount of a payment
+const BASIS_POINTS_PER_UNIT: i64 = 10_000;
+
+fn get_config_key(merchant_id: &str) -> String {
+    format!("routing_fee_schedules_{merchant_id}")
+}
+
+/// Returns the routing algorithm configured by the merchant
+pub fn get_merchant_routing_algorithm(
+    merchant_account: &domain::MerchantAccount,
+) -> RouterResult<api::RoutingAlgorithm> {
+    merchant_account
+        .routing_algorithm
+        .clone()
+        .get_required_value("RoutingAlgorithm")
+        .change_context(errors::ApiErrorResponse::PreconditionFailed {
+            message: "no routing algorithm has been configured".to_string(),
+        })?
+        .parse_value::<api::RoutingAlgorithm>("RoutingAlgorithm")
+        .change_context(errors::ApiErrorResponse::InternalServerError) // Deserialization failed
+        .attach_printable("Unable to deserialize merchant routing algorithm")
+}
+
+/// Removes the connectors the payment must not be routed to from the routing algorithm, so that
+/// the payment is routed to one of the remaining connectors instead
+pub fn remove_ineligible_connectors(
+    routing_algorithm: api::RoutingAlgorithm,
+    ineligible_connectors: &[api_enums::RoutableConnectors],
+) -> api::RoutingAlgorithm {
+    let is_eligible =
+        |connector: &api_enums::RoutableConnectors| !ineligible_connectors.contains(connector);
+
+    match routing_algorithm {
+        api::RoutingAlgorithm::Single(connector) if is_eligible(&connector) => {
+            api::RoutingAlgorithm::Single(connector)
+        }
+        api::RoutingAlgorithm::Single(_) => api::RoutingAlgorithm::Priority(Vec::new()),
+        api::RoutingAlgorithm::Priority(connectors) => {
+            api::RoutingAlgorithm::Priority(connectors.into_iter().filter(is_eligible).collect())
+        }
+        api::RoutingAlgorithm::CostBased(connectors) => {
+            api::RoutingAlgorithm::CostBased(connectors.into_iter().filter(is_eligible).collect())
+        }
+        api::RoutingAlgorithm::VolumeSplit(splits) => api::RoutingAlgorithm::VolumeSplit(
+            splits
+                .into_iter()
+                .filter(|split| is_eligible(&split.connector))
+                .collect(),
+        ),
+    }
+}
+
+#[instrument(skip(state))]
+pub async fn retrieve_fee_schedules(
+    state: &AppState,
+    merchant_account: domain::MerchantAccount,
+) -> RouterResponse<ConnectorFeeSchedules> {
+    let fee_schedules = find_fee_schedules(&*state.store, &merchant_account.merchant_id)
+        .await?
+        .unwrap_or_default();
+    Ok(services::ApplicationResponse::Json(fee_schedules))
+}
+
+#[instrument(skip(state))]
+pub async fn update_fee_schedules(
+    state: &AppState,
+    merchant_account: domain::MerchantAccount,
+    req: ConnectorFeeSchedules,
+) -> RouterResponse<ConnectorFeeSchedules> {
+    validate_fee_schedules(&req)?;
+
+    let key = get_config_key(&merchant_account.merchant_id);
+    let config = Encode::<ConnectorFeeSchedules>::encode_to_string_of_json(&req)
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to encode the connector fee schedules")?;
+
+    let db = &*state.store;
+    match db.find_config_by_key(&key).await {
+        Ok(_) => db
+            .update_config_cached(
+                &key,
+                storage::ConfigUpdate::Update {
+                    config: Some(config),
+                },
+            )
+            .await
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Failed to update the connector fee schedules")?,
+        Err(error) if error.current_context().is_db_not_found() => db
+            .insert_config(storage::ConfigNew { key, config })
+            .await
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Failed to insert the connector fee schedules")?,
+        Err(error) => Err(error
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Failed to find the connector fee schedules"))?,
+    };
+
+    Ok(services::ApplicationResponse::Json(req))
+}
+
+fn validate_fee_schedules(fee_schedules: &ConnectorFeeSchedules) -> RouterResult<()> {
+    let mut connectors = HashSet::new();
+    for fee_schedule in &fee_schedules.fee_schedules {
+        utils::when(!connectors.insert(fee_schedule.connector), || {
+            Err(report!(errors::ApiErrorResponse::InvalidRequestData {
+                message: format!(
+                    "more than one fee schedule given for the {} connector",
+                    fee_schedule.connector
+                ),
+            }))
+        })?;
+
+        for fee in &fee_schedule.fees {
+            utils::when(i64::from(fee.basis_points) > BASIS_POINTS_PER_UNIT, || {
+                Err(report!(errors::ApiErrorResponse::InvalidRequestData {
+                    message: "basis_points must be at most 10000".to_string(),
+                }))
+            })?;
+            utils::when(fee.fixed_amount < 0, || {
+                Err(report!(errors::ApiErrorResponse::InvalidRequestData {
+                    message: "fixed_amount must not be negative".to_string(),
+                }))
+            })?;
+        }
+    }
+    Ok(())
+}
+
+async fn find_fee_schedules(
+    db: &dyn StorageInterface,
+    merchant_id: &str,
+) -> RouterResult<Option<ConnectorFeeSchedules>> {
+    match db
+        .find_config_by_key_cached(&get_config_key(merchant_id))
+        .await
+    {
+        Ok(config) => config
+            .config
+            .parse_struct("ConnectorFeeSchedules")
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Invalid connector fee schedules configured for the merchant")
+            .map(Some),
+        Err(error) if error.current_context().is_db_not_found() => Ok(None),
+        Err(error) => Err(error
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Failed to find the connector fee schedules")),
+    }
+}
+
+/// The details of a payment which determine the fees charged for it by the connectors
+#[derive(Debug)]
+pub struct PaymentCostDetails {
+    pub amount: i64,
+    pub currency: api_enums::Currency,
+    pub payment_method_type: Option<api_enums::PaymentMethodType>,
+    pub card_network: Option<api_enums::CardNetwork>,
+    pub country: Option<api_enums::CountryAlpha2>,
+}
+
+impl PaymentCostDetails {
+    pub async fn from_payment_data<F: Clone>(
+        db: &dyn StorageInterface,
+        payment_data: &PaymentData<F>,
+    ) -> Self {
+        let card_network = match &payment_data.payment_method_data {
+            Some(api::PaymentMethodData::Card(card)) => match card.card_network.clone() {
+                Some(card_network) => Some(card_network),
+                None => get_card_network(db, &card.card_number.clone().get_card_isin()).await,
+            },
+            _ => None,
+        };
+        let billing_country = payment_data
+            .address
+            .billing
+            .as_ref()
+            .and_then(|billing_address| billing_address.address.as_ref())
+            .and_then(|address| address.country);
+
+        Self {
+            amount: payment_data.payment_attempt.amount,
+            currency: payment_data.currency,
+            payment_method_type: payment_data.payment_attempt.payment_method_type,
+            card_network,
+            country: billing_country.or(Some(payment_data.payment_intent.business_country)),
+        }
+    }
+}
+
+/// Looks up the network of the card by its BIN, the network being rarely passed by the customer
+async fn get_card_network(
+    db: &dyn StorageInterface,
+    card_bin: &str,
+) -> Option<api_enums::CardNetwork> {
+    db.get_card_info(card_bin)
+        .await
+        .map_err(|error| logger::warn!(card_info_error=?error))
+        .ok()
+        .flatten()
+        .and_then(|card_info| card_info.card_network)
+}
+
+fn is_fee_applicable(fee: &ConnectorFee, payment: &PaymentCostDetails) -> bool {
+    let is_card_network_applicable = fee.card_networks.as_ref().map_or(true, |card_networks| {
+        payment
+            .card_network
+            .as_ref()
+            .map_or(false, |card_network| card_networks.contains(card_network))
+    });
+    let is_country_applicable = fee.countries.as_ref().map_or(true, |countries| {
+        payment
+            .country
+            .map_or(false, |country| countries.contains(&country))
+    });
+
+    fee.currency == payment.currency && is_card_network_applicable && is_country_applicable
+}
+
+fn get_expected_cost(fee: &ConnectorFee, amount: i64) -> i64 {
+    amount
+        .saturating_mul(i64::from(fee.basis_points))
+        .saturating_div(BASIS_POINTS_PER_UNIT)
+        .saturating_add(fee.fixed_amount)
+}
+
+/// Returns whether the amount of the payment is within the amount limits configured for the
+/// payment method type with the connector
+fn is_within_amount_limits(
+    state: &AppState,
+    connector: api_enums::RoutableConnectors,
+    payment: &PaymentCostDetails,
+) -> bool {
+    let Some(filter) = payment.payment_method_type.and_then(|payment_method_type| {
+        state
+            .conf
+            .pm_filters
+            .get_payment_method_type_filter(&connector.to_string(), payment_method_type)
+    }) else {
+        return true;
+    };
+
+    filter
+        .min_amount
+        .map_or(true, |min_amount| payment.amount >= min_amount)
+        && filter
+            .max_amount
+            .map_or(true, |max_amount| payment.amount <= max_amount)
+}
+
+/// Returns the expected costs of the payment with the connectors, the eligible connectors with
+/// the lowest known costs first. Connectors with the same cost keep their relative order.
+pub async fn get_connector_costs(
+    state: &AppState,
+    merchant_id: &str,
+    connectors: &[api_enums::RoutableConnectors],
+    payment: &PaymentCostDetails,
+) -> RouterResult<Vec<ConnectorCost>> {
+    let fee_schedules = find_fee_schedules(&*state.store, merchant_id)
+        .await?
+        .unwrap_or_default();
+
+    let mut connector_costs: Vec<ConnectorCost> = connectors
+        .iter()
+        .map(|connector| {
+            let expected_cost = fee_schedules
+                .fee_schedules
+                .iter()
+                .find(|fee_schedule| fee_schedule.connector == *connector)
+                .and_then(|fee_schedule| {
+                    fee_schedule
+                        .fees
+                        .iter()
+                        .find(|fee| is_fee_applicable(fee, payment))
+                })
+                .map(|fee| get_expected_cost(fee, payment.amount));
+
+            ConnectorCost {
+                connector: *connector,
+                expected_cost,
+                eligible: is_within_amount_limits(state, *connector, payment),
+            }
+        })
+        .collect();
+
+    connector_costs.sort_by_key(|connector_cost| {
+        (
+            !connector_cost.eligible,
+            connector_cost.expected_cost.is_none(),
+            connector_cost.expected_cost.unwrap_or_default(),
+        )
+    });
+    Ok(connector_costs)
+}
+
+/// Returns the connector the payment is routed to by cost based routing, the cheapest of the
+/// eligible connectors
+pub async fn get_cheapest_connector(
+    state: &AppState,
+    merchant_id: &str,
+    connectors: &[api_enums::RoutableConnectors],
+    payment: &PaymentCostDetails,
+) -> RouterResult<Option<api_enums::RoutableConnectors>> {
+    Ok(get_connector_costs(state, merchant_id, connectors, payment)
+        .await?
+        .first()
+        .map(|connector_cost| connector_cost.connector))
+}
+
+/// Previews the connector a payment with the given details would be routed to by the routing
+/// algorithm of the merchant
+#[instrument(skip(state))]
+pub async fn simulate_routing(
+    state: &AppState,
+    merchant_account: domain::MerchantAccount,
+    req: RoutingSimulationRequest,
+) -> RouterResponse<RoutingSimulationResponse> {
+    let routing_algorithm = get_merchant_routing_algorithm(&merchant_account)?;
+
+    let response = match &routing_algorithm {
+        api::RoutingAlgorithm::CostBased(connectors) => {
+            let payment = PaymentCostDetails {
+                amount: req.amount,
+                currency: req.currency,
+                payment_method_type: req.payment_method_type,
+                card_network: req.card_network,
+                country: req.country,
+            };
+            let connector_costs =
+                get_connector_costs(state, &merchant_account.merchant_id, connectors, &payment)
+                    .await?;
+
+            RoutingSimulationResponse {
+                connector: connector_costs
+                    .first()
+                    .map(|connector_cost| connector_cost.connector),
+                connector_costs,
+            }
+        }
+        _ => {
+            let routing_key = req
+                .customer_id
+                .unwrap_or_else(|| utils::generate_id(consts::ID_LENGTH, "pay"));
+            RoutingSimulationResponse {
+                connector: super::get_routed_connector(&routing_algorithm, &routing_key),
+                connector_costs: Vec::new(),
+            }
+        }
+    };
+
+    Ok(services::ApplicationResponse::Json(response))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_remove_ineligible_connectors() {
+        let ineligible_connectors = [api_enums::RoutableConnectors::Stripe];
+
+        assert_eq!(
+            remove_ineligible_connectors(
+                api::RoutingAlgorithm::Single(api_enums::RoutableConnectors::Adyen),
+                &ineligible_connectors,
+            )
+            .get_connectors(),
+            vec![api_enums::RoutableConnectors::Adyen]
+        );
+        assert!(remove_ineligible_connectors(
+            api::RoutingAlgorithm::Single(api_enums::RoutableConnectors::Stripe),
+            &ineligible_connectors,
+        )
+        .get_connectors()
+        .is_empty());
+
+        assert_eq!(
+            remove_ineligible_connectors(
+                api::RoutingAlgorithm::Priority(vec![
+                    api_enums::RoutableConnectors::Stripe,
+                    api_enums::RoutableConnectors::Adyen,
+                ]),
+                &ineligible_connectors,
+            )
+            .get_connectors(),
+            vec![api_enums::RoutableConnectors::Adyen]
+        );
+
+        assert_eq!(
+            remove_ineligible_connectors(
+                api::RoutingAlgorithm::CostBased(vec![
+                    api_enums::RoutableConnectors::Adyen,
+                    api_enums::RoutableConnectors::Stripe,
+                ]),
+                &ineligible_connectors,
+            )
+            .get_connectors(),
+            vec![api_enums::RoutableConnectors::Adyen]
+        );
+
+        let routing_algorithm = remove_ineligible_connectors(
+            api::RoutingAlgorithm::VolumeSplit(vec![
+                api_models::admin::ConnectorVolumeSplit {
+                    connector: api_enums::RoutableConnectors::Stripe,
+                    split: 70,
+                },
+                api_models::admin::ConnectorVolumeSplit {
+                    connector: api_enums::RoutableConnectors::Adyen,
+                    split: 30,
+                },
+            ]),
+            &ineligible_connectors,
+        );
+        assert_eq!(
+            routing_algorithm.get_connectors(),
+            vec![api_enums::RoutableConnectors::Adyen]
+        );
+        // The payments are all routed to the remaining connectors of the volume split
+        assert_eq!(
+            super::super::get_routed_connector(&routing_algorithm, "cus_1"),
+            Some(api_enums::RoutableConnectors::Adyen)
+        );
+    }
+
+    fn get_payment_cost_details(
+        card_network: Option<api_enums::CardNetwork>,
+        country: Option<api_enums::CountryAlpha2>,
+    ) -> PaymentCostDetails {
+        PaymentCostDetails {
+            amount: 10000,
+            currency: api_enums::Currency::USD,
+            payment_method_type: None,
+            card_network,
+            country,
+        }
+    }
+
+    fn get_connector_fee() -> ConnectorFee {
+        ConnectorFee {
+            currency: api_enums::Currency::USD,
+            card_networks: None,
+            countries: None,
+            basis_points: 290,
+            fixed_amount: 30,
+        }
+    }
+
+    #[test]
+    fn test_fee_is_applicable_to_the_payments_of_its_currency() {
+        let fee = get_connector_fee();
+
+        assert!(is_fee_applicable(
+            &fee,
+            &get_payment_cost_details(None, None)
+        ));
+        assert!(!is_fee_applicable(
+            &fee,
+            &PaymentCostDetails {
+                currency: api_enums::Currency::EUR,
+                ..get_payment_cost_details(None, None)
+            }
+        ));
+    }
+
+    #[test]
+    fn test_fee_is_applicable_to_the_payments_of_its_card_networks() {
+        let fee = ConnectorFee {
+            card_networks: Some(vec![api_enums::CardNetwork::Visa]),
+            ..get_connector_fee()
+        };
+
+        assert!(is_fee_applicable(
+            &fee,
+            &get_payment_cost_details(Some(api_enums::CardNetwork::Visa), None)
+        ));
+        assert!(!is_fee_applicable(
+            &fee,
+            &get_payment_cost_details(Some(api_enums::CardNetwork::Mastercard), None)
+        ));
+        // The fees of a card network do not apply to the payments whose network is not known
+        assert!(!is_fee_applicable(
+            &fee,
+            &get_payment_cost_details(None, None)
+        ));
+    }
+
+    #[test]
+    fn test_fee_is_applicable_to_the_payments_of_its_countries() {
+        let fee = ConnectorFee {
+            countries: Some(vec![api_enums::CountryAlpha2::US]),
+            ..get_connector_fee()
+        };
+
+        assert!(is_fee_applicable(
+            &fee,
+            &get_payment_cost_details(None, Some(api_enums::CountryAlpha2::US))
+        ));
+        assert!(!is_fee_applicable(
+            &fee,
+            &get_payment_cost_details(None, Some(api_enums::CountryAlpha2::GB))
+        ));
+        assert!(!is_fee_applicable(
+            &fee,
+            &get_payment_cost_details(None, None)
+        ));
+    }
+
+    #[test]
+    fn test_expected_cost() {
+        let fee = get_connector_fee();
+
+        // 2.9% of 100.00 plus 0.30
+        assert_eq!(get_expected_cost(&fee, 10000), 320);
+        // The share of the amount is rounded down to the lowest denomination of the currency
+        assert_eq!(get_expected_cost(&fee, 99), 32);
+        assert_eq!(get_expected_cost(&fee, 0), 30);
+        assert_eq!(
+            get_expected_cost(
+                &ConnectorFee {
+                    basis_points: 0,
+                    fixed_amount: 0,
+                    ..fee
+                },
+                10000
+            ),
+            0
+        );
+    }
+
+    #[test]
+    fn test_expected_cost_does_not_overflow() {
+        let fee = ConnectorFee {
+            basis_points: 10000,
+            fixed_amount: i64::MAX,
+            ..get_connector_fee()
+        };
+
+        assert_eq!(get_expected_cost(&fee, i64::MAX), i64::MAX);
+    }
+}
@@ -0,0 +1,100 @@
+//! Refresh of the session tokens of wallets.
+//!
+//! The session tokens of wallets created in the sessions flow expire after a while, Apple Pay
+//! merchant sessions for instance being valid for five minutes only. Customers taking longer to
+//! pay can have the session tokens of a payment regenerated, as long as the payment still awaits
+//! its payment method and the client secret has not expired.
+
+use error_stack::report;
+use router_env::{instrument, tracing};
+
+use super::{helpers, CallConnectorAction, PaymentSession};
+use crate::{
+    core::errors::{self, RouterResponse, StorageErrorExt},
+    routes::AppState,
+    services,
+    types::{
+        api::{self, enums as api_enums},
+        domain,
+        storage::enums,
+    },
+};
+
+/// The wallets whose session tokens are refreshed if the wallets are not specified.
+const REFRESHABLE_WALLETS: [api_enums::PaymentMethodType; 3] = [
+    api_enums::PaymentMethodType::ApplePay,
+    api_enums::PaymentMethodType::GooglePay,
+    api_enums::PaymentMethodType::Paypal,
+];
+
+/// The statuses of the payments whose session tokens can be refreshed.
+const REFRESHABLE_STATUSES: [enums::IntentStatus; 2] = [
+    enums::IntentStatus::RequiresPaymentMethod,
+    enums::IntentStatus::RequiresConfirmation,
+];
+
+#[instrument(skip_all)]
+pub async fn refresh_session_tokens(
+    state: &AppState,
+    merchant_account: domain::MerchantAccount,
+    key_store: domain::MerchantKeyStore,
+    payment_id: String,
+    req: api::PaymentsSessionRefreshRequest,
+) -> RouterResponse<api::PaymentsSessionResponse> {
+    let wallets = match req.wallets {
+        Some(wallets) => {
+            if let Some(wallet) = wallets
+                .iter()
+                .find(|wallet| !REFRESHABLE_WALLETS.contains(*wallet))
+            {
+                return Err(report!(errors::ApiErrorResponse::InvalidRequestData {
+                    message: format!("The session tokens of {wallet} cannot be refreshed"),
+                }));
+            }
+            wallets
+        }
+        None => REFRESHABLE_WALLETS.to_vec(),
+    };
+
+    let payment_intent = state
+        .store
+        .find_payment_intent_by_payment_id_merchant_id(
+            &payment_id,
+            &merchant_account.merchant_id,
+            merchant_account.storage_scheme,
+        )
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::PaymentNotFound)?;
+
+    helpers::authenticate_client_secret(
+        Some(&req.client_secret),
+        &payment_intent,
+        merchant_account.intent_fulfillment_time,
+    )?;
+
+    if !REFRESHABLE_STATUSES.contains(&payment_intent.status) {
+        return Err(report!(errors::ApiErrorResponse::PreconditionFailed {
+            message: format!(
+                "You cannot refresh the session tokens of this payment because it has status {}",
+                payment_intent.status
+            ),
+        }));
+    }
+
+    super::payments_core::<api::Session, api::PaymentsSessionResponse, _, _, _>(
+        state,
+        merchant_account,
+        key_store,
+        PaymentSession,
+        api::PaymentsSessionRequest {
+            payment_id,
+            client_secret: req.client_secret,
+            wallets,
+            merchant_connector_details: None,
+            connectors: req.connectors,
+        },
+        services::AuthFlow::Client,
+        CallConnectorAction::Trigger,
+    )
+    .await
+}
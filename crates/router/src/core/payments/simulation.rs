@@ -0,0 +1,403 @@
+//! Simulated confirmation of payments, to debug integrations without calling the connectors.
+//!
+//! A simulated confirmation validates and routes the payment as its confirmation would, checking
+//! that the connector it is routed to accepts the payment method, amount and customer. The request
+//! which would be sent to the connector is built and returned with its sensitive values redacted,
+//! instead of being sent. Connector calls preceding the payment request, such as fetching access
+//! tokens or tokenizing the payment method, are not made either.
+//!
+//! Simulating a confirmation does not update the status of the payment, nor is it counted against
+//! the velocity limits of the merchant. Simulations are not available in production.
+
+use std::collections::BTreeMap;
+
+use api_models::{
+    enums::RetryAction,
+    payments::{ConnectorRequestPreview, PaymentsSimulationResponse},
+};
+use error_stack::{report, ResultExt};
+use masking::PeekInterface;
+use router_env::{env, instrument, logger, tracing};
+
+use super::{
+    connector_exclusion,
+    flows::ConstructFlowSpecificData,
+    helpers,
+    operations::{BoxedOperation, PaymentConfirm},
+};
+use crate::{
+    core::errors::{self, RouterResponse},
+    routes::AppState,
+    services::{self, api::Authenticate, request},
+    types::{self, api, domain},
+    utils,
+};
+
+const MASKED_VALUE: &str = "*** masked ***";
+
+/// Parts of the names of the fields of connector requests whose values are not redacted. The
+/// values of all other fields are redacted, as connectors name the fields holding card and
+/// customer details differently.
+const NON_SENSITIVE_FIELD_PARTS: &[&str] = &[
+    "amount",
+    "currency",
+    "capture",
+    "intent",
+    "type",
+    "mode",
+    "country",
+    "reference",
+    "three_d",
+    "3ds",
+    "return_url",
+    "webhook_url",
+    "locale",
+    "language",
+    "brand",
+    "network",
+];
+
+/// Parts of the names of fields whose values are always redacted, even if their names also contain
+/// a non-sensitive part, such as `network_token` or `three_ds_cryptogram`.
+const SENSITIVE_FIELD_PARTS: &[&str] = &[
+    "cryptogram",
+    "token",
+    "number",
+    "cvc",
+    "cvv",
+    "secret",
+    "password",
+    "email",
+];
+
+/// Whether the values of the field are not redacted. Only the innermost part of the names of form
+/// fields is considered, so that `card[network_token][cryptogram]` is treated as `cryptogram`.
+fn is_non_sensitive_field(name: &str) -> bool {
+    let name = name
+        .trim_end_matches(']')
+        .rsplit(|c| c == '[' || c == ']')
+        .next()
+        .unwrap_or(name)
+        .to_lowercase();
+    !SENSITIVE_FIELD_PARTS.iter().any(|part| name.contains(part))
+        && NON_SENSITIVE_FIELD_PARTS
+            .iter()
+            .any(|part| name.contains(part))
+}
+
+fn redact_value(value: serde_json::Value, is_sensitive: bool) -> serde_json::Value {
+    match value {
+        serde_json::Value::Object(fields) => serde_json::Value::Object(
+            fields
+                .into_iter()
+                .map(|(name, value)| {
+                    let is_sensitive = !is_non_sensitive_field(&name);
+                    (name, redact_value(value, is_sensitive))
+                })
+                .collect(),
+        ),
+        serde_json::Value::Array(values) => serde_json::Value::Array(
+            values
+                .into_iter()
+                .map(|value| redact_value(value, is_sensitive))
+                .collect(),
+        ),
+        serde_json::Value::String(_) | serde_json::Value::Number(_) if is_sensitive => {
+            serde_json::Value::String(MASKED_VALUE.to_string())
+        }
+        value => value,
+    }
+}
+
+/// Redacts the body of a connector request. Bodies which are neither JSON nor form encoded are
+/// redacted entirely.
+fn redact_body(body: &str, content_type: Option<&request::ContentType>) -> serde_json::Value {
+    let parsed_body = match content_type {
+        Some(request::ContentType::Json) => serde_json::from_str(body).ok(),
+        Some(request::ContentType::FormUrlEncoded) => {
+            serde_urlencoded::from_str::<Vec<(String, String)>>(body)
+                .ok()
+                .map(|fields| {
+                    serde_json::Value::Object(
+                        fields
+                            .into_iter()
+                            .map(|(name, value)| (name, serde_json::Value::String(value)))
+                            .collect(),
+                    )
+                })
+        }
+        Some(request::ContentType::FormData) | None => None,
+    };
+    parsed_body.map_or_else(
+        || serde_json::Value::String(MASKED_VALUE.to_string()),
+        |body| redact_value(body, true),
+    )
+}
+
+fn get_connector_request_preview(connector_request: services::Request) -> ConnectorRequestPreview {
+    let url = match connector_request.url.split_once('?') {
+        Some((path, _)) => format!("{path}?{MASKED_VALUE}"),
+        None => connector_request.url,
+    };
+    let headers: BTreeMap<String, String> = connector_request
+        .headers
+        .into_iter()
+        .map(|(name, value)| {
+            let value = match value {
+                request::Maskable::Masked(_) => MASKED_VALUE.to_string(),
+                request::Maskable::Normal(value) => value,
+            };
+            (name, value)
+        })
+        .collect();
+    let body = connector_request
+        .payload
+        .map(|payload| redact_body(payload.peek(), connector_request.content_type.as_ref()));
+
+    ConnectorRequestPreview {
+        method: connector_request.method.to_string(),
+        url,
+        headers,
+        body,
+    }
+}
+
+/// Simulates the confirmation of a payment, returning the request which would be sent to the
+/// connector to which the payment is routed.
+#[instrument(skip_all)]
+pub async fn simulate_payment_confirm(
+    state: &AppState,
+    merchant_account: domain::MerchantAccount,
+    key_store: domain::MerchantKeyStore,
+    req: api::PaymentsRequest,
+    auth_flow: services::AuthFlow,
+) -> RouterResponse<PaymentsSimulationResponse> {
+    if matches!(env::which(), env::Env::Production) {
+        return Err(report!(errors::ApiErrorResponse::PreconditionFailed {
+            message: "Payments cannot be simulated in production".to_string(),
+        }));
+    }
+
+    // Retrying a payment creates a new attempt, and the connector credentials in the request are
+    // stored, which simulations must not do
+    utils::when(
+        matches!(req.retry_action, Some(RetryAction::ManualRetry)),
+        || {
+            Err(report!(errors::ApiErrorResponse::PreconditionFailed {
+                message: "Manual retries of payments cannot be simulated".to_string(),
+            }))
+        },
+    )?;
+    utils::when(req.merchant_connector_details.is_some(), || {
+        Err(report!(errors::ApiErrorResponse::PreconditionFailed {
+            message: "Payments with merchant connector details cannot be simulated".to_string(),
+        }))
+    })?;
+
+    let db = &*state.store;
+    let operation: BoxedOperation<'_, api::Authorize, api::PaymentsRequest> =
+        Box::new(PaymentConfirm);
+    let (operation, validate_result) = operation
+        .to_validate_request()?
+        .validate_request(&req, &merchant_account)?;
+    // The addresses in the request would be stored along with the payment while fetching its
+    // trackers, so they are only set on the payment data instead
+    let tracker_request = api::PaymentsRequest {
+        shipping: None,
+        billing: None,
+        ..req.clone()
+    };
+    let (operation, mut payment_data, _customer_details) = operation
+        .to_get_tracker()?
+        .get_trackers(
+            state,
+            &validate_result.payment_id,
+            &tracker_request,
+            validate_result.mandate_type.to_owned(),
+            &merchant_account,
+            &key_store,
+            auth_flow,
+        )
+        .await?;
+    helpers::validate_customer_id_mandatory_cases(
+        req.shipping.is_some(),
+        req.billing.is_some(),
+        req.setup_future_usage.is_some(),
+        &payment_data
+            .payment_intent
+            .customer_id
+            .clone()
+            .or_else(|| helpers::get_customer_details_from_request(&req).customer_id),
+    )?;
+    if let Some(shipping) = req.shipping.clone() {
+        payment_data.address.shipping = Some(shipping);
+    }
+    if let Some(billing) = req.billing.clone() {
+        payment_data.address.billing = Some(billing);
+    }
+
+    helpers::authenticate_client_secret(
+        req.get_client_secret(),
+        &payment_data.payment_intent,
+        merchant_account.intent_fulfillment_time,
+    )?;
+
+    // The customer is only looked up, so that simulations do not create customers
+    let customer = match payment_data.payment_intent.customer_id.as_ref() {
+        Some(customer_id) => db
+            .find_customer_optional_by_customer_id_merchant_id(
+                customer_id,
+                &merchant_account.merchant_id,
+                &key_store,
+            )
+            .await
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Failed while fetching the customer")?,
+        None => None,
+    };
+
+    let connector_data = match super::get_connector_choice(
+        &operation,
+        state,
+        &req,
+        &merchant_account,
+        &key_store,
+        &mut payment_data,
+    )
+    .await?
+    {
+        Some(api::ConnectorCallType::Single(connector_data)) => connector_data,
+        Some(api::ConnectorCallType::Multiple(_)) | None => {
+            return Err(report!(errors::ApiErrorResponse::PreconditionFailed {
+                message: "The payment would not be routed to a connector".to_string(),
+            }))
+        }
+    };
+    let connector_name = connector_data.connector.id();
+
+    helpers::validate_payment_method_type_enabled(
+        state,
+        &merchant_account,
+        &key_store,
+        connector_name,
+        &payment_data,
+    )
+    .await?;
+    helpers::validate_connector_amount_limits(state, connector_name, &payment_data)?;
+    connector_exclusion::validate_connector_not_excluded(
+        db,
+        &merchant_account,
+        connector_name,
+        &payment_data,
+    )
+    .await?;
+
+    let router_data: types::PaymentsAuthorizeRouterData = payment_data
+        .construct_router_data(
+            state,
+            connector_name,
+            &merchant_account,
+            &key_store,
+            &customer,
+        )
+        .await?;
+
+    let connector_integration: services::BoxedConnectorIntegration<
+        '_,
+        api::Authorize,
+        types::PaymentsAuthorizeData,
+        types::PaymentsResponseData,
+    > = connector_data.connector.get_connector_integration();
+    let (connector_request, connector_request_error) =
+        match connector_integration.build_request(&router_data, &state.conf.connectors) {
+            Ok(connector_request) => (connector_request.map(get_connector_request_preview), None),
+            Err(error) => {
+                logger::info!(simulated_connector_request_error=?error);
+                (None, Some(error.current_context().to_string()))
+            }
+        };
+
+    Ok(services::ApplicationResponse::Json(
+        PaymentsSimulationResponse {
+            payment_id: payment_data.payment_intent.payment_id,
+            connector: connector_name.to_string(),
+            authentication_type: payment_data.payment_attempt.authentication_type,
+            amount: router_data.request.amount,
+            surcharge_amount: payment_data.payment_attempt.surcharge_amount,
+            currency: payment_data.currency,
+            connector_request,
+            connector_request_error,
+        },
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_is_non_sensitive_field() {
+        assert!(is_non_sensitive_field("amount"));
+        assert!(is_non_sensitive_field(
+            "payment_method_options[card][network]"
+        ));
+        assert!(is_non_sensitive_field("billing_details[address][country]"));
+        assert!(!is_non_sensitive_field(
+            "payment_method_data[card][network_token][cryptogram]"
+        ));
+        assert!(!is_non_sensitive_field("network_token"));
+        assert!(!is_non_sensitive_field("card[number]"));
+        assert!(!is_non_sensitive_field("amount_details[card_holder_name]"));
+    }
+
+    #[test]
+    fn test_redact_form_body() {
+        let body = "amount=1000&currency=usd&payment_method_data%5Bcard%5D%5Bnumber%5D=4242424242424242\
+            &payment_method_data%5Bcard%5D%5Bnetwork_token%5D%5Bcryptogram%5D=AgAAAAAABk4DWZ4C28yUQAAAAAA%3D";
+        assert_eq!(
+            redact_body(body, Some(&request::ContentType::FormUrlEncoded)),
+            json!({
+                "amount": "1000",
+                "currency": "usd",
+                "payment_method_data[card][number]": MASKED_VALUE,
+                "payment_method_data[card][network_token][cryptogram]": MASKED_VALUE,
+            })
+        );
+    }
+
+    #[test]
+    fn test_redact_json_body() {
+        let body = json!({
+            "amount": 1000,
+            "currency": "EUR",
+            "paymentMethod": {
+                "brand": "visa",
+                "number": "4242424242424242",
+                "networkPaymentReference": "ref",
+            },
+            "shopperEmail": "john.doe@example.com",
+            "threeDS2RequestData": {"deviceChannel": "browser"},
+        })
+        .to_string();
+        assert_eq!(
+            redact_body(&body, Some(&request::ContentType::Json)),
+            json!({
+                "amount": 1000,
+                "currency": "EUR",
+                "paymentMethod": {
+                    "brand": "visa",
+                    "number": MASKED_VALUE,
+                    "networkPaymentReference": "ref",
+                },
+                "shopperEmail": MASKED_VALUE,
+                "threeDS2RequestData": {"deviceChannel": MASKED_VALUE},
+            })
+        );
+        assert_eq!(
+            redact_body("--boundary", Some(&request::ContentType::FormData)),
+            json!(MASKED_VALUE)
+        );
+    }
+}
@@ -6,7 +6,9 @@ use super::helpers;
 use crate::{
     core::{
         errors::{self, ConnectorErrorExt, RouterResult},
-        mandate, payment_methods, payments,
+        mandate,
+        payment_methods::{self, network_tokenization},
+        payments,
     },
     logger,
     routes::{metrics, AppState},
@@ -43,11 +45,15 @@ where
                 .unwrap_or(false);
 
             let connector_token = if token_store {
-                let token = resp
+                match resp
                     .payment_method_token
                     .to_owned()
-                    .get_required_value("payment_token")?;
-                Some((connector, token))
+                    .get_required_value("payment_token")?
+                {
+                    types::PaymentMethodToken::Token(token) => Some((connector, token)),
+                    // The card details decrypted from Apple Pay payment tokens are not stored
+                    types::PaymentMethodToken::ApplePayDecrypt(_) => None,
+                }
             } else {
                 None
             };
@@ -96,19 +102,41 @@ where
                                     diesel_models::errors::DatabaseError::NotFound => {
                                         let pm_metadata =
                                             create_payment_method_metadata(None, connector_token)?;
-                                        payment_methods::cards::create_payment_method(
-                                            db,
-                                            &payment_method_create_request,
-                                            &customer.customer_id,
-                                            &locker_response.0.payment_method_id,
-                                            merchant_id,
-                                            pm_metadata,
-                                        )
-                                        .await
-                                        .change_context(
-                                            errors::ApiErrorResponse::InternalServerError,
+                                        let payment_method =
+                                            payment_methods::cards::create_payment_method(
+                                                db,
+                                                &payment_method_create_request,
+                                                &customer.customer_id,
+                                                &locker_response.0.payment_method_id,
+                                                merchant_id,
+                                                pm_metadata,
+                                            )
+                                            .await
+                                            .change_context(
+                                                errors::ApiErrorResponse::InternalServerError,
+                                            )
+                                            .attach_printable(
+                                                "Failed to add payment method in db",
+                                            )?;
+                                        payment_methods::expiry::add_expiry_task_if_required(
+                                            state,
+                                            &payment_method,
+                                            payment_method_create_request
+                                                .card
+                                                .as_ref()
+                                                .map(|card| &card.card_exp_month),
+                                            payment_method_create_request
+                                                .card
+                                                .as_ref()
+                                                .map(|card| &card.card_exp_year),
                                         )
-                                        .attach_printable("Failed to add payment method in db")
+                                        .await?;
+                                        network_tokenization::provision_network_token_if_required(
+                                            state,
+                                            payment_method,
+                                            payment_method_create_request.card.as_ref(),
+                                        );
+                                        Ok(())
                                     }
                                     _ => {
                                         Err(report!(errors::ApiErrorResponse::InternalServerError)
@@ -124,7 +152,7 @@ where
                     };
                 } else {
                     let pm_metadata = create_payment_method_metadata(None, connector_token)?;
-                    payment_methods::cards::create_payment_method(
+                    let payment_method = payment_methods::cards::create_payment_method(
                         db,
                         &payment_method_create_request,
                         &customer.customer_id,
@@ -135,6 +163,24 @@ where
                     .await
                     .change_context(errors::ApiErrorResponse::InternalServerError)
                     .attach_printable("Failed to add payment method in db")?;
+                    payment_methods::expiry::add_expiry_task_if_required(
+                        state,
+                        &payment_method,
+                        payment_method_create_request
+                            .card
+                            .as_ref()
+                            .map(|card| &card.card_exp_month),
+                        payment_method_create_request
+                            .card
+                            .as_ref()
+                            .map(|card| &card.card_exp_year),
+                    )
+                    .await?;
+                    network_tokenization::provision_network_token_if_required(
+                        state,
+                        payment_method,
+                        payment_method_create_request.card.as_ref(),
+                    );
                 };
                 Some(locker_response.0.payment_method_id)
             } else {
@@ -182,6 +228,7 @@ pub async fn save_in_locker(
                 recurring_enabled: false,           //[#219]
                 installment_payment_enabled: false, //[#219]
                 payment_experience: Some(vec![api_models::enums::PaymentExperience::RedirectToUrl]), //[#219]
+                account_update: None,
             };
             Ok((payment_method_response, false))
         }
@@ -9,9 +9,10 @@ use super::{flows::Feature, PaymentAddress, PaymentData};
 use crate::{
     configs::settings::{ConnectorRequestReferenceIdConfig, Server},
     connector::{Nexinets, Paypal},
+    consts,
     core::{
         errors::{self, RouterResponse, RouterResult},
-        payments::{self, helpers},
+        payments::{self, currency_conversion, helpers},
         utils as core_utils,
     },
     routes::{metrics, AppState},
@@ -36,7 +37,7 @@ pub async fn construct_payment_router_data<'a, F, T>(
 where
     T: TryFrom<PaymentAdditionalData<'a, F>>,
     types::RouterData<F, T, types::PaymentsResponseData>: Feature<F, T>,
-    F: Clone,
+    F: Clone + api::ConnectorFlowName,
     error_stack::Report<errors::ApiErrorResponse>:
         From<<T as TryFrom<PaymentAdditionalData<'a, F>>>::Error>,
 {
@@ -55,7 +56,8 @@ where
         payment_data.creds_identifier.to_owned(),
         key_store,
     )
-    .await?;
+    .await?
+    .select_environment(payment_data.payment_intent.livemode)?;
 
     fp_utils::when(merchant_connector_account.is_disabled(), || {
         Err(errors::ApiErrorResponse::MerchantConnectorAccountDisabled)
@@ -98,6 +100,27 @@ where
 
     let customer_id = customer.to_owned().map(|customer| customer.customer_id);
 
+    let business_profile_defaults = helpers::get_business_profile_defaults(
+        payment_data.payment_intent.business_country,
+        &payment_data.payment_intent.business_label,
+        merchant_account,
+    )?;
+    let connector_meta_data = helpers::merge_business_profile_defaults_into_metadata(
+        business_profile_defaults.as_ref(),
+        merchant_connector_account.get_metadata(),
+    )?;
+
+    // Each capture of an attempt with multiple captures is a separate connector call
+    let capture_sequence = payment_data
+        .multiple_capture_data
+        .as_ref()
+        .map(|capture_data| capture_data.current_capture.capture_sequence.to_string());
+    let idempotency_key = core_utils::get_idempotency_key::<F>(
+        &std::iter::once(payment_data.payment_attempt.attempt_id.as_str())
+            .chain(capture_sequence.as_deref())
+            .collect::<Vec<_>>(),
+    );
+
     router_data = types::RouterData {
         flow: PhantomData,
         merchant_id: merchant_account.merchant_id.clone(),
@@ -116,14 +139,14 @@ where
             .payment_attempt
             .authentication_type
             .unwrap_or_default(),
-        connector_meta_data: merchant_connector_account.get_metadata(),
+        connector_meta_data,
         request: T::try_from(additional_data)?,
         response: response.map_or_else(|| Err(types::ErrorResponse::default()), Ok),
         amount_captured: payment_data.payment_intent.amount_captured,
         access_token: None,
         session_token: None,
         reference_id: None,
-        payment_method_token: payment_data.pm_token,
+        payment_method_token: payment_data.pm_token.map(types::PaymentMethodToken::Token),
         connector_customer: payment_data.connector_customer_id,
         recurring_mandate_payment_data: payment_data.recurring_mandate_payment_data,
         connector_request_reference_id: core_utils::get_connector_request_reference_id(
@@ -138,6 +161,8 @@ where
         quote_id: None,
         test_mode,
         payment_method_balance: None,
+        idempotency_key: Some(idempotency_key),
+        connector_client_certificate: merchant_connector_account.get_client_certificate(),
     };
 
     Ok(router_data)
@@ -173,6 +198,10 @@ where
         operation: Op,
         connector_request_reference_id_config: &ConnectorRequestReferenceIdConfig,
     ) -> RouterResponse<Self> {
+        let expanded_customer = customer
+            .clone()
+            .filter(|_| payment_data.expand_customer)
+            .map(|customer| api::CustomerResponse::from(customer).into_inner());
         payments_to_payments_response(
             req,
             payment_data.payment_attempt,
@@ -180,8 +209,14 @@ where
             payment_data.refunds,
             payment_data.disputes,
             payment_data.attempts,
+            payment_data
+                .multiple_capture_data
+                .as_ref()
+                .map(payments::MultipleCaptureData::get_all_captures)
+                .or(payment_data.captures),
             payment_data.payment_method_data,
             customer,
+            expanded_customer,
             auth_flow,
             payment_data.address,
             server,
@@ -190,6 +225,8 @@ where
             payment_data.ephemeral_key,
             payment_data.sessions_token,
             payment_data.frm_message,
+            payment_data.captcha_site_key,
+            payment_data.payment_link_config,
             payment_data.setup_mandate,
             connector_request_reference_id_config,
         )
@@ -213,6 +250,12 @@ where
     ) -> RouterResponse<Self> {
         Ok(services::ApplicationResponse::Json(Self {
             session_token: payment_data.sessions_token,
+            timed_out_connectors: payment_data.timed_out_session_connectors,
+            deferred_connectors: payment_data.deferred_session_connectors,
+            expires_at: Some(
+                common_utils::date_time::now()
+                    .saturating_add(time::Duration::seconds(consts::SESSION_TOKEN_VALIDITY)),
+            ),
             payment_id: payment_data.payment_attempt.payment_id,
             client_secret: payment_data
                 .payment_intent
@@ -284,8 +327,10 @@ pub fn payments_to_payments_response<R, Op>(
     refunds: Vec<storage::Refund>,
     disputes: Vec<storage::Dispute>,
     option_attempts: Option<Vec<storage::PaymentAttempt>>,
+    option_captures: Option<Vec<storage::Capture>>,
     payment_method_data: Option<api::PaymentMethodData>,
     customer: Option<domain::Customer>,
+    expanded_customer: Option<api_models::customers::CustomerResponse>,
     auth_flow: services::AuthFlow,
     address: PaymentAddress,
     server: &Server,
@@ -294,6 +339,8 @@ pub fn payments_to_payments_response<R, Op>(
     ephemeral_key_option: Option<ephemeral_key::EphemeralKey>,
     session_tokens: Vec<api::SessionToken>,
     frm_message: Option<payments::FrmMessage>,
+    captcha_site_key: Option<String>,
+    payment_link_config: Option<api_models::admin::PaymentLinkConfig>,
     mandate_data: Option<api_models::payments::MandateData>,
     connector_request_reference_id_config: &ConnectorRequestReferenceIdConfig,
 ) -> RouterResponse<api::PaymentsResponse>
@@ -332,7 +379,15 @@ where
             .map(ForeignInto::foreign_into)
             .collect()
     });
+    let captures_response = option_captures.map(|captures| {
+        captures
+            .into_iter()
+            .map(ForeignInto::foreign_into)
+            .collect()
+    });
     let merchant_id = payment_attempt.merchant_id.to_owned();
+    let currency_conversion_details =
+        currency_conversion::get_currency_conversion_details(&payment_intent);
     let payment_method_type = payment_attempt
         .payment_method_type
         .as_ref()
@@ -367,6 +422,7 @@ where
                     payment_method_data,
                     amount,
                     currency: currency.to_string(),
+                    payment_link_config,
                 }))
             } else {
                 let mut next_action_response = None;
@@ -431,6 +487,12 @@ where
                     )
                 }
 
+                // The confirmation was held back until the customer solves a CAPTCHA
+                if let Some(site_key) = captcha_site_key {
+                    next_action_response =
+                        Some(api_models::payments::NextActionData::SolveCaptcha { site_key })
+                }
+
                 let mut response: api::PaymentsResponse = Default::default();
                 let routed_through = payment_attempt.connector.clone();
 
@@ -482,6 +544,9 @@ where
                         .set_refunds(refunds_response) // refunds.iter().map(refund_to_refund_response),
                         .set_disputes(disputes_response)
                         .set_attempts(attempts_response)
+                        .set_customer(expanded_customer)
+                        .set_capture_count(payment_attempt.multiple_capture_count)
+                        .set_captures(captures_response)
                         .set_payment_method(
                             payment_attempt.payment_method,
                             auth_flow == services::AuthFlow::Merchant,
@@ -530,6 +595,12 @@ where
                         .set_feature_metadata(payment_intent.feature_metadata)
                         .set_connector_metadata(payment_intent.connector_metadata)
                         .set_reference_id(payment_attempt.connector_response_reference_id)
+                        .set_invoice_id(payment_intent.invoice_id)
+                        .set_convenience_fee(payment_intent.convenience_fee)
+                        .set_livemode(payment_intent.livemode)
+                        .set_currency_conversion(currency_conversion_details)
+                        .set_l2_l3_data(payment_intent.l2_l3_data)
+                        .set_cross_border_fees(payment_intent.cross_border_fees)
                         .to_owned(),
                 )
             }
@@ -549,6 +620,9 @@ where
             refunds: refunds_response,
             disputes: disputes_response,
             attempts: attempts_response,
+            customer: expanded_customer,
+            capture_count: payment_attempt.multiple_capture_count,
+            captures: captures_response,
             payment_method: payment_attempt.payment_method,
             capture_method: payment_attempt.capture_method,
             error_message: payment_attempt
@@ -584,6 +658,12 @@ where
             connector_metadata: payment_intent.connector_metadata,
             allowed_payment_method_types: payment_intent.allowed_payment_method_types,
             reference_id: payment_attempt.connector_response_reference_id,
+            invoice_id: payment_intent.invoice_id,
+            convenience_fee: payment_intent.convenience_fee,
+            livemode: payment_intent.livemode,
+            l2_l3_data: payment_intent.l2_l3_data,
+            currency_conversion: currency_conversion_details,
+            cross_border_fees: payment_intent.cross_border_fees,
             ..Default::default()
         }),
     });
@@ -660,6 +740,7 @@ impl ForeignFrom<(storage::PaymentIntent, storage::PaymentAttempt)> for api::Pay
     fn foreign_from(item: (storage::PaymentIntent, storage::PaymentAttempt)) -> Self {
         let pi = item.0;
         let pa = item.1;
+        let currency_conversion = currency_conversion::get_currency_conversion_details(&pi);
         Self {
             payment_id: Some(pi.payment_id),
             merchant_id: Some(pi.merchant_id),
@@ -676,6 +757,12 @@ impl ForeignFrom<(storage::PaymentIntent, storage::PaymentAttempt)> for api::Pay
             connector: pa.connector,
             payment_method: pa.payment_method,
             payment_method_type: pa.payment_method_type,
+            invoice_id: pi.invoice_id,
+            convenience_fee: pi.convenience_fee,
+            livemode: pi.livemode,
+            currency_conversion,
+            l2_l3_data: pi.l2_l3_data,
+            cross_border_fees: pi.cross_border_fees,
             ..Default::default()
         }
     }
@@ -798,24 +885,9 @@ impl<F: Clone> TryFrom<PaymentAdditionalData<'_, F>> for types::PaymentsAuthoriz
             .transpose()?
             .and_then(|cm| cm.noon.and_then(|noon| noon.order_category));
 
-        let order_details = additional_data
-            .payment_data
-            .payment_intent
-            .order_details
-            .map(|order_details| {
-                order_details
-                    .iter()
-                    .map(|data| {
-                        data.to_owned()
-                            .parse_value("OrderDetailsWithAmount")
-                            .change_context(errors::ApiErrorResponse::InvalidDataValue {
-                                field_name: "OrderDetailsWithAmount",
-                            })
-                            .attach_printable("Unable to parse OrderDetailsWithAmount")
-                    })
-                    .collect::<Result<Vec<_>, _>>()
-            })
-            .transpose()?;
+        let order_details = helpers::get_order_details_for_connector(&payment_data.payment_intent)?;
+
+        let l2_l3_data = helpers::get_l2_l3_data(&payment_data.payment_intent)?;
 
         let complete_authorize_url = Some(helpers::create_complete_authorize_url(
             router_base_url,
@@ -852,7 +924,9 @@ impl<F: Clone> TryFrom<PaymentAdditionalData<'_, F>> for types::PaymentsAuthoriz
             confirm: payment_data.payment_attempt.confirm,
             statement_descriptor_suffix: payment_data.payment_intent.statement_descriptor_suffix,
             statement_descriptor: payment_data.payment_intent.statement_descriptor_name,
-            capture_method: payment_data.payment_attempt.capture_method,
+            capture_method: helpers::get_connector_capture_method(
+                payment_data.payment_attempt.capture_method,
+            ),
             amount: payment_data.amount.into(),
             currency: payment_data.currency,
             browser_info,
@@ -860,6 +934,10 @@ impl<F: Clone> TryFrom<PaymentAdditionalData<'_, F>> for types::PaymentsAuthoriz
             payment_experience: payment_data.payment_attempt.payment_experience,
             order_details,
             order_category,
+            l2_l3_data,
+            currency_conversion: currency_conversion::get_currency_conversion_details(
+                &payment_data.payment_intent,
+            ),
             session_token: None,
             enrolled_for_3ds: true,
             related_transaction_id: None,
@@ -868,6 +946,9 @@ impl<F: Clone> TryFrom<PaymentAdditionalData<'_, F>> for types::PaymentsAuthoriz
             webhook_url,
             complete_authorize_url,
             customer_id: None,
+            // The network token is fetched from the token service provider just before the
+            // authorization, as its cryptogram is valid for a single transaction
+            network_token_data: None,
         })
     }
 }
@@ -886,7 +967,9 @@ impl<F: Clone> TryFrom<PaymentAdditionalData<'_, F>> for types::PaymentsSyncData
                 None => types::ResponseId::NoResponseId,
             },
             encoded_data: payment_data.connector_response.encoded_data,
-            capture_method: payment_data.payment_attempt.capture_method,
+            capture_method: helpers::get_connector_capture_method(
+                payment_data.payment_attempt.capture_method,
+            ),
             connector_meta: payment_data.payment_attempt.connector_metadata,
         })
     }
@@ -934,6 +1017,10 @@ impl<F: Clone> TryFrom<PaymentAdditionalData<'_, F>> for types::PaymentsCaptureD
             .payment_attempt
             .amount_to_capture
             .map_or(payment_data.amount.into(), |capture_amount| capture_amount);
+        let currency_conversion = currency_conversion::get_currency_conversion_for_amount(
+            &payment_data.payment_intent,
+            amount_to_capture,
+        )?;
         Ok(Self {
             amount_to_capture,
             currency: payment_data.currency,
@@ -943,10 +1030,17 @@ impl<F: Clone> TryFrom<PaymentAdditionalData<'_, F>> for types::PaymentsCaptureD
                 .ok_or(errors::ApiErrorResponse::ResourceIdNotFound)?,
             payment_amount: payment_data.amount.into(),
             connector_meta: payment_data.payment_attempt.connector_metadata,
-            capture_method: payment_data
-                .payment_attempt
-                .capture_method
-                .unwrap_or_default(),
+            capture_method: helpers::get_connector_capture_method(
+                payment_data.payment_attempt.capture_method,
+            )
+            .unwrap_or_default(),
+            multiple_capture_data: payment_data.multiple_capture_data.as_ref().map(
+                |multiple_capture_data| types::MultipleCaptureRequestData {
+                    capture_sequence: multiple_capture_data.current_capture.capture_sequence,
+                    capture_reference: multiple_capture_data.current_capture.capture_id.clone(),
+                },
+            ),
+            currency_conversion,
         })
     }
 }
@@ -980,24 +1074,7 @@ impl<F: Clone> TryFrom<PaymentAdditionalData<'_, F>> for types::PaymentsSessionD
     fn try_from(additional_data: PaymentAdditionalData<'_, F>) -> Result<Self, Self::Error> {
         let payment_data = additional_data.payment_data.clone();
 
-        let order_details = additional_data
-            .payment_data
-            .payment_intent
-            .order_details
-            .map(|order_details| {
-                order_details
-                    .iter()
-                    .map(|data| {
-                        data.to_owned()
-                            .parse_value("OrderDetailsWithAmount")
-                            .change_context(errors::ApiErrorResponse::InvalidDataValue {
-                                field_name: "OrderDetailsWithAmount",
-                            })
-                            .attach_printable("Unable to parse OrderDetailsWithAmount")
-                    })
-                    .collect::<Result<Vec<_>, _>>()
-            })
-            .transpose()?;
+        let order_details = helpers::get_order_details_for_connector(&payment_data.payment_intent)?;
 
         Ok(Self {
             amount: payment_data.amount.into(),
@@ -1081,7 +1158,9 @@ impl<F: Clone> TryFrom<PaymentAdditionalData<'_, F>> for types::CompleteAuthoriz
             setup_mandate_details: payment_data.setup_mandate.clone(),
             confirm: payment_data.payment_attempt.confirm,
             statement_descriptor_suffix: payment_data.payment_intent.statement_descriptor_suffix,
-            capture_method: payment_data.payment_attempt.capture_method,
+            capture_method: helpers::get_connector_capture_method(
+                payment_data.payment_attempt.capture_method,
+            ),
             amount: payment_data.amount.into(),
             currency: payment_data.currency,
             browser_info,
@@ -0,0 +1,450 @@
+//! Velocity limits on the payments of a merchant.
+//!
+//! Merchants can configure limits on the number and the total amount of the payments confirmed
+//! within an hour or a day by the same customer, with the same card or from the same IP address.
+//! The limits are enforced before the payment is authorized, and payments exceeding any of them
+//! are declined with a velocity limit error instead of being sent to the connector. Payments are
+//! only counted once authorized: payments which fail at the connector are removed from the counts.
+//! Cards are identified by a fingerprint keyed with a server secret rather than by their numbers.
+//!
+//! The payments are counted in Redis over sliding windows, approximated from the counters of the
+//! current and the previous fixed windows: the counters of the previous window are weighted by the
+//! part of it which still falls within the sliding window. Counting fails open, so that an
+//! unavailable Redis does not block payments.
+
+use std::{collections::HashSet, sync::Arc};
+
+use api_models::payments::{PaymentVelocityLimit, PaymentVelocityLimits, VelocityLimitScope};
+use common_utils::ext_traits::{Encode, StringExt, ValueExt};
+use error_stack::{report, ResultExt};
+use redis_interface::RedisConnectionPool;
+use router_env::{instrument, logger, tracing};
+
+use super::{confirm_protection, PaymentData};
+use crate::{
+    core::errors::{self, CustomResult, RouterResponse, RouterResult},
+    db::StorageInterface,
+    routes::{metrics, AppState},
+    services,
+    types::{self, domain, storage},
+    utils,
+};
+
+fn get_config_key(merchant_id: &str) -> String {
+    format!("payment_velocity_limits_{merchant_id}")
+}
+
+#[instrument(skip(state))]
+pub async fn retrieve_velocity_limits(
+    state: &AppState,
+    merchant_account: domain::MerchantAccount,
+) -> RouterResponse<PaymentVelocityLimits> {
+    let limits = find_velocity_limits(&*state.store, &merchant_account.merchant_id)
+        .await?
+        .unwrap_or_default();
+    Ok(services::ApplicationResponse::Json(limits))
+}
+
+#[instrument(skip(state))]
+pub async fn update_velocity_limits(
+    state: &AppState,
+    merchant_account: domain::MerchantAccount,
+    req: PaymentVelocityLimits,
+) -> RouterResponse<PaymentVelocityLimits> {
+    validate_velocity_limits(&req)?;
+
+    let key = get_config_key(&merchant_account.merchant_id);
+    let config = Encode::<PaymentVelocityLimits>::encode_to_string_of_json(&req)
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to encode the payment velocity limits")?;
+
+    let db = &*state.store;
+    match db.find_config_by_key(&key).await {
+        Ok(_) => db
+            .update_config_cached(
+                &key,
+                storage::ConfigUpdate::Update {
+                    config: Some(config),
+                },
+            )
+            .await
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Failed to update the payment velocity limits")?,
+        Err(error) if error.current_context().is_db_not_found() => db
+            .insert_config(storage::ConfigNew { key, config })
+            .await
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Failed to insert the payment velocity limits")?,
+        Err(error) => Err(error
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Failed to find the payment velocity limits"))?,
+    };
+
+    Ok(services::ApplicationResponse::Json(req))
+}
+
+async fn find_velocity_limits(
+    db: &dyn StorageInterface,
+    merchant_id: &str,
+) -> RouterResult<Option<PaymentVelocityLimits>> {
+    match db
+        .find_config_by_key_cached(&get_config_key(merchant_id))
+        .await
+    {
+        Ok(config) => config
+            .config
+            .parse_struct("PaymentVelocityLimits")
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Invalid payment velocity limits configured for the merchant")
+            .map(Some),
+        Err(error) if error.current_context().is_db_not_found() => Ok(None),
+        Err(error) => Err(error
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Failed to find the payment velocity limits")),
+    }
+}
+
+fn validate_velocity_limits(limits: &PaymentVelocityLimits) -> RouterResult<()> {
+    let mut names = HashSet::new();
+    for limit in &limits.limits {
+        utils::when(
+            limit.name.is_empty() || !names.insert(limit.name.as_str()),
+            || {
+                Err(report!(errors::ApiErrorResponse::InvalidRequestData {
+                    message: format!(
+                        "The names of the limits must be non-empty and unique, found `{}`",
+                        limit.name
+                    ),
+                }))
+            },
+        )?;
+
+        utils::when(
+            limit.max_count.is_none() && limit.max_amount.is_none(),
+            || {
+                Err(report!(errors::ApiErrorResponse::InvalidRequestData {
+                    message: format!(
+                        "The limit `{}` must have a max_count or a max_amount",
+                        limit.name
+                    ),
+                }))
+            },
+        )?;
+
+        utils::when(limit.max_amount.map_or(false, |amount| amount < 0), || {
+            Err(report!(errors::ApiErrorResponse::InvalidRequestData {
+                message: format!("The max_amount of the limit `{}` is negative", limit.name),
+            }))
+        })?;
+
+        utils::when(
+            limit.max_amount.is_some() != limit.currency.is_some(),
+            || {
+                Err(report!(errors::ApiErrorResponse::InvalidRequestData {
+                    message: format!(
+                        "The limit `{}` must have a currency along with its max_amount, and only \
+                         along with it",
+                        limit.name
+                    ),
+                }))
+            },
+        )?;
+    }
+    Ok(())
+}
+
+/// The identifier of the customer, the card or the IP address whose payments are counted against
+/// a limit, if it is known for the payment.
+fn get_scope_identifier<F: Clone>(
+    scope: VelocityLimitScope,
+    payment_data: &PaymentData<F>,
+    master_key: &[u8],
+) -> Option<String> {
+    match scope {
+        VelocityLimitScope::Customer => payment_data.payment_intent.customer_id.clone(),
+        VelocityLimitScope::Card => confirm_protection::get_keyed_card_fingerprint(
+            master_key,
+            payment_data.payment_method_data.as_ref(),
+        ),
+        VelocityLimitScope::IpAddress => payment_data
+            .payment_attempt
+            .browser_info
+            .clone()
+            .map(|browser_info| {
+                browser_info.parse_value::<types::BrowserInformation>("BrowserInformation")
+            })
+            .transpose()
+            .map_err(|error| logger::warn!(browser_info_parsing_error=?error))
+            .ok()
+            .flatten()
+            .and_then(|browser_info| browser_info.ip_address)
+            .map(|ip_address| ip_address.to_string()),
+    }
+}
+
+/// The usage of a limit over its sliding window.
+#[derive(Debug, Default, PartialEq)]
+struct VelocityUsage {
+    count: f64,
+    amount: f64,
+}
+
+/// The weight of the counters of the previous fixed window, which is the part of it still falling
+/// within the sliding window ending now.
+#[allow(clippy::as_conversions)]
+fn get_previous_window_weight(now: i64, window: i64) -> f64 {
+    (window - now % window) as f64 / window as f64
+}
+
+/// The usage of a limit over the sliding window, from the counts and the amounts of the previous
+/// and the current fixed windows.
+#[allow(clippy::as_conversions)]
+fn get_weighted_usage(
+    previous: (i64, i64),
+    current: (i64, i64),
+    previous_window_weight: f64,
+) -> VelocityUsage {
+    VelocityUsage {
+        count: previous.0 as f64 * previous_window_weight + current.0 as f64,
+        amount: previous.1 as f64 * previous_window_weight + current.1 as f64,
+    }
+}
+
+/// Whether the usage of a limit, including the payment being checked, exceeds the limit.
+#[allow(clippy::as_conversions)]
+fn exceeds_limit(limit: &PaymentVelocityLimit, usage: &VelocityUsage, counts_amount: bool) -> bool {
+    let exceeds_count = limit
+        .max_count
+        .map_or(false, |max_count| usage.count > f64::from(max_count));
+    let exceeds_amount = counts_amount
+        && limit
+            .max_amount
+            .map_or(false, |max_amount| usage.amount > max_amount as f64);
+    exceeds_count || exceeds_amount
+}
+
+/// The counters of the payments counted against a limit for a customer, a card or an IP address,
+/// kept for each fixed window.
+struct VelocityCounter {
+    key_prefix: String,
+    window: i64,
+    now: i64,
+}
+
+impl VelocityCounter {
+    fn new(merchant_id: &str, limit: &PaymentVelocityLimit, identifier: &str, now: i64) -> Self {
+        Self {
+            key_prefix: format!("velocity_{merchant_id}_{}_{identifier}", limit.name),
+            window: limit.window.as_secs(),
+            now,
+        }
+    }
+
+    fn get_key(&self, window_index: i64, counter: &str) -> String {
+        format!("{}_{window_index}_{counter}", self.key_prefix)
+    }
+
+    /// Adds a payment of the amount to the counters of the current window, which expire once they
+    /// no longer overlap with the sliding window. A negative count removes a payment previously
+    /// added. Returns the count and the amount of the current window after the increment.
+    async fn increment(
+        &self,
+        redis_conn: &RedisConnectionPool,
+        count: i64,
+        amount: Option<i64>,
+    ) -> CustomResult<(i64, i64), redis_interface::errors::RedisError> {
+        let window_index = self.now / self.window;
+        let expiry = 2 * self.window;
+        let current_count = redis_conn
+            .increment_key_by_with_expiry(&self.get_key(window_index, "count"), count, expiry)
+            .await?;
+        let current_amount = match amount {
+            Some(amount) => {
+                redis_conn
+                    .increment_key_by_with_expiry(
+                        &self.get_key(window_index, "amount"),
+                        count * amount,
+                        expiry,
+                    )
+                    .await?
+            }
+            None => 0,
+        };
+        Ok((current_count, current_amount))
+    }
+
+    /// Returns the count and the amount of the previous window.
+    async fn get_previous_window(
+        &self,
+        redis_conn: &RedisConnectionPool,
+    ) -> CustomResult<(i64, i64), redis_interface::errors::RedisError> {
+        let window_index = self.now / self.window - 1;
+        let count = redis_conn
+            .get_key::<Option<i64>>(&self.get_key(window_index, "count"))
+            .await?
+            .unwrap_or_default();
+        let amount = redis_conn
+            .get_key::<Option<i64>>(&self.get_key(window_index, "amount"))
+            .await?
+            .unwrap_or_default();
+        Ok((count, amount))
+    }
+}
+
+/// A payment counted against the velocity limits of the merchant. The payment is only meant to
+/// count once it is authorized, so the reservation is released when the payment fails.
+#[derive(Default)]
+pub struct VelocityReservation {
+    redis_conn: Option<Arc<RedisConnectionPool>>,
+    counters: Vec<(VelocityCounter, Option<i64>)>,
+}
+
+impl VelocityReservation {
+    /// Removes the payment from the counters of the limits it was counted against.
+    #[instrument(skip_all)]
+    pub async fn release(self) {
+        let Some(redis_conn) = self.redis_conn else {
+            return;
+        };
+        for (counter, amount) in self.counters {
+            if let Err(error) = counter.increment(&redis_conn, -1, amount).await {
+                logger::error!(velocity_limit_release_error=?error);
+            }
+        }
+    }
+}
+
+/// Counts the payment against the velocity limits of the merchant, and declines it if it exceeds
+/// any of them. The payment is counted before the limits are compared, so that concurrent payments
+/// cannot all pass the same limit, and it is removed from the counters when it is declined.
+#[instrument(skip_all)]
+pub async fn check_velocity_limits<F: Clone>(
+    state: &AppState,
+    merchant_account: &domain::MerchantAccount,
+    payment_data: &PaymentData<F>,
+) -> RouterResult<VelocityReservation> {
+    let merchant_id = &merchant_account.merchant_id;
+    let limits = match find_velocity_limits(&*state.store, merchant_id).await? {
+        Some(limits) if !limits.limits.is_empty() => limits.limits,
+        _ => return Ok(VelocityReservation::default()),
+    };
+
+    let redis_conn = match state.store.get_redis_conn() {
+        Ok(redis_conn) => redis_conn,
+        Err(error) => {
+            logger::error!(redis_connection_error=?error);
+            return Ok(VelocityReservation::default());
+        }
+    };
+
+    let amount = payment_data.payment_attempt.amount;
+    let currency = payment_data.payment_attempt.currency;
+    let now = common_utils::date_time::now_unix_timestamp();
+    let master_key = state.store.get_master_key();
+
+    let mut reservation = VelocityReservation {
+        redis_conn: Some(redis_conn.clone()),
+        counters: Vec::with_capacity(limits.len()),
+    };
+    for limit in &limits {
+        let Some(identifier) = get_scope_identifier(limit.scope, payment_data, master_key) else {
+            continue;
+        };
+        let counter = VelocityCounter::new(merchant_id, limit, &identifier, now);
+
+        // Only the payments in the currency of the limit are counted against its amount
+        let counts_amount = limit.currency.is_some() && limit.currency == currency;
+        let counted_amount = counts_amount.then_some(amount);
+        let current = match counter.increment(&redis_conn, 1, counted_amount).await {
+            Ok(current) => current,
+            Err(error) => {
+                logger::error!(velocity_limit_record_error=?error);
+                continue;
+            }
+        };
+        let previous = counter
+            .get_previous_window(&redis_conn)
+            .await
+            .unwrap_or_else(|error| {
+                logger::error!(velocity_limit_usage_error=?error);
+                (0, 0)
+            });
+        reservation.counters.push((counter, counted_amount));
+        let usage = get_weighted_usage(
+            previous,
+            current,
+            get_previous_window_weight(now, limit.window.as_secs()),
+        );
+
+        if exceeds_limit(limit, &usage, counts_amount) {
+            logger::warn!(
+                fraud_signal = "velocity_limit_exceeded",
+                %merchant_id,
+                payment_id = %payment_data.payment_intent.payment_id,
+                limit = %limit.name,
+                scope = %limit.scope,
+                usage = ?usage
+            );
+            metrics::PAYMENT_VELOCITY_LIMIT_EXCEEDED.add(
+                &metrics::CONTEXT,
+                1,
+                &[
+                    metrics::request::add_attributes("merchant_id", merchant_id.to_owned()),
+                    metrics::request::add_attributes("scope", limit.scope.to_string()),
+                ],
+            );
+            reservation.release().await;
+            return Err(report!(errors::ApiErrorResponse::VelocityLimitExceeded {
+                limit: limit.name.clone(),
+                scope: limit.scope.to_string(),
+            }));
+        }
+    }
+    Ok(reservation)
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+
+    fn get_limit(max_count: Option<u32>, max_amount: Option<i64>) -> PaymentVelocityLimit {
+        serde_json::from_value(serde_json::json!({
+            "name": "hourly",
+            "scope": "customer",
+            "window": "hour",
+            "max_count": max_count,
+            "max_amount": max_amount,
+            "currency": max_amount.map(|_| "USD"),
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_get_weighted_usage() {
+        let weight = get_previous_window_weight(3600 * 10 + 900, 3600);
+        assert_eq!(weight, 0.75);
+        assert_eq!(
+            get_weighted_usage((4, 1000), (1, 200), weight),
+            VelocityUsage {
+                count: 4.0,
+                amount: 950.0,
+            }
+        );
+    }
+
+    #[test]
+    fn test_exceeds_limit() {
+        let count_limit = get_limit(Some(3), None);
+        let usage = |count, amount| VelocityUsage { count, amount };
+
+        assert!(!exceeds_limit(&count_limit, &usage(3.0, 0.0), false));
+        assert!(exceeds_limit(&count_limit, &usage(3.5, 0.0), false));
+
+        let amount_limit = get_limit(None, Some(1000));
+        assert!(!exceeds_limit(&amount_limit, &usage(10.0, 1000.0), true));
+        assert!(exceeds_limit(&amount_limit, &usage(1.0, 1000.5), true));
+        // The payments in another currency are not counted against the amount
+        assert!(!exceeds_limit(&amount_limit, &usage(1.0, 5000.0), false));
+    }
+}
@@ -5,16 +5,20 @@ use api_models::enums as api_enums;
 use common_utils::{crypto::Encryptable, ext_traits::ValueExt};
 use diesel_models::enums as storage_enums;
 use error_stack::{report, ResultExt};
-use router_env::{instrument, tracing};
+use futures::{stream, FutureExt, StreamExt};
+use router_env::{instrument, logger, tracing};
 use serde_json::{self};
 
 use super::errors::{ConnectorErrorExt, StorageErrorExt};
 use crate::{
+    consts,
     core::{
         errors::{self, RouterResponse, RouterResult},
+        ledger,
         payments::{self, helpers as payment_helpers},
         utils as core_utils,
     },
+    db::StorageInterface,
     routes::AppState,
     services,
     types::{
@@ -155,6 +159,8 @@ pub async fn payouts_update_core(
     let payout_attempt = payout_data.payout_attempt.to_owned();
     let status = payout_attempt.status;
 
+    validator::validate_return_url(&merchant_account, req.return_url.as_deref())?;
+
     // Verify update feasibility
     if helpers::is_payout_terminal_state(status) || helpers::is_payout_initiated(status) {
         return Err(report!(errors::ApiErrorResponse::InvalidRequestData {
@@ -291,23 +297,16 @@ pub async fn payouts_cancel_core(
     let connector_payout_id = payout_attempt.connector_payout_id.to_owned();
     let status = payout_attempt.status;
 
-    // Verify if cancellation can be triggered
-    if helpers::is_payout_terminal_state(status) {
-        return Err(report!(errors::ApiErrorResponse::InvalidRequestData {
-            message: format!(
-                "Payout {} cannot be cancelled for status {}",
-                payout_attempt.payout_id, status
-            ),
-        }));
-
     // Make local cancellation
-    } else if helpers::is_eligible_for_local_payout_cancellation(status) {
+    if helpers::is_eligible_for_local_payout_cancellation(status) {
         let updated_payout_attempt = storage::PayoutAttemptUpdate::StatusUpdate {
             connector_payout_id: connector_payout_id.to_owned(),
             status: storage_enums::PayoutStatus::Cancelled,
             error_message: Some("Cancelled by user".to_string()),
             error_code: None,
             is_eligible: None,
+            connector_fee: None,
+            fx_margin: None,
             last_modified_at: Some(common_utils::date_time::now()),
         };
         payout_data.payout_attempt = state
@@ -322,7 +321,7 @@ pub async fn payouts_cancel_core(
             .attach_printable("Error updating payout_attempt in db")?;
 
     // Trigger connector's cancellation
-    } else {
+    } else if helpers::is_eligible_for_connector_payout_cancellation(status) {
         // Form connector data
         let connector_data = get_connector_data(
             state,
@@ -342,6 +341,15 @@ pub async fn payouts_cancel_core(
         )
         .await
         .attach_printable("Payout cancellation failed for given Payout request")?;
+
+    // Payouts which have been paid out or have failed cannot be cancelled
+    } else {
+        return Err(report!(errors::ApiErrorResponse::InvalidRequestData {
+            message: format!(
+                "Payout {} cannot be cancelled for status {}",
+                payout_attempt.payout_id, status
+            ),
+        }));
     }
 
     response_handler(
@@ -435,6 +443,370 @@ pub async fn payouts_fulfill_core(
     .await
 }
 
+#[cfg(feature = "payouts")]
+#[instrument(skip_all)]
+pub async fn payouts_sync_core(
+    state: &AppState,
+    merchant_account: domain::MerchantAccount,
+    key_store: domain::MerchantKeyStore,
+    req: payouts::PayoutActionRequest,
+) -> RouterResponse<payouts::PayoutCreateResponse> {
+    let mut payout_data = make_payout_data(
+        state,
+        &merchant_account,
+        &key_store,
+        &payouts::PayoutRequest::PayoutActionRequest(req.to_owned()),
+    )
+    .await?;
+
+    let payout_attempt = payout_data.payout_attempt.to_owned();
+
+    // Payouts which have not been created at the connector have no status to be synced
+    if payout_attempt.connector_payout_id.is_empty() {
+        return Err(report!(errors::ApiErrorResponse::InvalidRequestData {
+            message: format!(
+                "Payout {} cannot be synced for status {}",
+                payout_attempt.payout_id, payout_attempt.status
+            ),
+        }));
+    }
+
+    // Form connector data
+    let connector_data = get_connector_data(
+        state,
+        &merchant_account,
+        Some(payout_attempt.connector),
+        None,
+    )
+    .await?;
+
+    payout_data = sync_payout(
+        state,
+        &merchant_account,
+        &key_store,
+        &payouts::PayoutRequest::PayoutActionRequest(req.to_owned()),
+        &connector_data,
+        &mut payout_data,
+    )
+    .await
+    .attach_printable("Payout sync failed for given Payout request")?;
+
+    response_handler(
+        state,
+        &merchant_account,
+        &payouts::PayoutRequest::PayoutActionRequest(req.to_owned()),
+        &payout_data,
+    )
+    .await
+}
+
+#[cfg(feature = "payouts")]
+#[instrument(skip_all)]
+pub async fn payouts_batch_create_core(
+    state: &AppState,
+    merchant_account: domain::MerchantAccount,
+    key_store: domain::MerchantKeyStore,
+    req: payouts::PayoutBatchCreateRequest,
+) -> RouterResponse<payouts::PayoutBatchResponse> {
+    let db = &*state.store;
+    let mut payout_requests = req.payouts;
+    let idempotency_key = req.idempotency_key;
+
+    // A retried request returns the batch it created first, without creating its payouts again
+    if let Some(idempotency_key) = idempotency_key.as_deref() {
+        if let Some(payout_batch) =
+            find_payout_batch_by_idempotency_key(db, &merchant_account, idempotency_key).await?
+        {
+            return get_payout_batch_response(db, payout_batch)
+                .await
+                .map(services::ApplicationResponse::Json);
+        }
+    }
+
+    utils::when(
+        payout_requests.is_empty() || payout_requests.len() > consts::MAX_PAYOUT_BATCH_SIZE,
+        || {
+            Err(report!(errors::ApiErrorResponse::InvalidRequestData {
+                message: format!(
+                    "A batch must contain between 1 and {} payouts",
+                    consts::MAX_PAYOUT_BATCH_SIZE
+                ),
+            }))
+        },
+    )?;
+
+    // The payout ids are assigned upfront, so that the batch records all of its payouts,
+    // including those which could not be created
+    for payout_request in payout_requests.iter_mut() {
+        payout_request.payout_id = Some(core_utils::get_or_generate_uuid(
+            "payout_id",
+            payout_request.payout_id.as_ref(),
+        )?);
+    }
+    let payout_ids = payout_requests
+        .iter()
+        .filter_map(|payout_request| payout_request.payout_id.clone())
+        .collect::<Vec<_>>();
+    if let Some(duplicate_payout_id) = payout_ids
+        .iter()
+        .enumerate()
+        .find_map(|(index, payout_id)| payout_ids[..index].contains(payout_id).then_some(payout_id))
+    {
+        return Err(report!(errors::ApiErrorResponse::DuplicatePayout {
+            payout_id: duplicate_payout_id.to_owned(),
+        }));
+    }
+
+    // Every payout of the batch is validated before any of them is created
+    let mut validated_payouts = Vec::with_capacity(payout_requests.len());
+    for (index, payout_request) in payout_requests.iter().enumerate() {
+        let connector_data = get_connector_data(
+            state,
+            &merchant_account,
+            payout_request
+                .connector
+                .clone()
+                .and_then(|c| c.first().map(|c| c.to_string())),
+            payout_request.routing.clone(),
+        )
+        .await
+        .attach_printable_lazy(|| format!("Invalid payout at index {index} of the batch"))?;
+        let (payout_id, payout_method_data) =
+            validator::validate_create_request(state, &merchant_account, payout_request)
+                .await
+                .attach_printable_lazy(|| {
+                    format!("Invalid payout at index {index} of the batch")
+                })?;
+        validated_payouts.push((
+            payout_request,
+            payout_id,
+            payout_method_data,
+            connector_data,
+        ));
+    }
+
+    let payout_batch = db
+        .insert_payout_batch(storage::PayoutBatchNew {
+            batch_id: utils::generate_id(consts::ID_LENGTH, "pobatch"),
+            merchant_id: merchant_account.merchant_id.clone(),
+            payout_ids,
+            created_at: common_utils::date_time::now(),
+            idempotency_key,
+        })
+        .await
+        .map_err(|error| {
+            if error.current_context().is_db_unique_violation() {
+                error.change_context(errors::ApiErrorResponse::GenericDuplicateError {
+                    message: "A payout batch with the same idempotency key is being created, \
+                              please retry"
+                        .to_string(),
+                })
+            } else {
+                error
+                    .change_context(errors::ApiErrorResponse::InternalServerError)
+                    .attach_printable("Failed to insert payout batch")
+            }
+        })?;
+
+    let creation_errors = stream::iter(validated_payouts)
+        .map(
+            |(payout_request, payout_id, payout_method_data, connector_data)| {
+                let merchant_account = &merchant_account;
+                let key_store = &key_store;
+                async move {
+                    let mut payout_data = payout_create_db_entries(
+                        state,
+                        merchant_account,
+                        key_store,
+                        payout_request,
+                        &payout_id,
+                        &connector_data.connector_name,
+                        payout_method_data.as_ref(),
+                    )
+                    .await?;
+                    call_connector_payout(
+                        state,
+                        merchant_account,
+                        key_store,
+                        payout_request,
+                        connector_data,
+                        &mut payout_data,
+                    )
+                    .await
+                }
+                .map(|result| result.err().map(|error| (payout_id, error)))
+            },
+        )
+        .buffered(consts::PAYOUT_BATCH_CONCURRENCY)
+        .filter_map(|creation_error| async move { creation_error })
+        .collect::<Vec<_>>()
+        .await;
+
+    let creation_errors = creation_errors
+        .into_iter()
+        .map(|(payout_id, error)| {
+            logger::error!(payout_batch_error=?error, %payout_id, "Failed to create payout of batch");
+            PayoutBatchError {
+                payout_id,
+                error_code: error.current_context().error_code(),
+                error_message: error.current_context().error_message(),
+            }
+        })
+        .collect::<Vec<_>>();
+
+    // The errors are recorded on the batch, as the payouts which could not be created have no
+    // payout attempt to record them on
+    let payout_batch = if creation_errors.is_empty() {
+        payout_batch
+    } else {
+        let errors = utils::Encode::<Vec<PayoutBatchError>>::encode_to_value(&creation_errors)
+            .change_context(errors::ApiErrorResponse::InternalServerError)?;
+        db.update_payout_batch_by_merchant_id_batch_id(
+            &payout_batch.merchant_id,
+            &payout_batch.batch_id,
+            storage::PayoutBatchUpdate::ErrorsUpdate {
+                errors: Some(errors),
+            },
+        )
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to record the errors of the payout batch")?
+    };
+
+    get_payout_batch_response(db, payout_batch)
+        .await
+        .map(services::ApplicationResponse::Json)
+}
+
+#[cfg(feature = "payouts")]
+#[instrument(skip_all)]
+pub async fn payouts_batch_retrieve_core(
+    state: &AppState,
+    merchant_account: domain::MerchantAccount,
+    batch_id: String,
+) -> RouterResponse<payouts::PayoutBatchResponse> {
+    let db = &*state.store;
+    let payout_batch = db
+        .find_payout_batch_by_merchant_id_batch_id(&merchant_account.merchant_id, &batch_id)
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::PayoutBatchNotFound { batch_id })?;
+
+    get_payout_batch_response(db, payout_batch)
+        .await
+        .map(services::ApplicationResponse::Json)
+}
+
+/// The error with which the creation of a payout of a batch failed
+#[cfg(feature = "payouts")]
+#[derive(Debug, serde::Deserialize, serde::Serialize)]
+struct PayoutBatchError {
+    payout_id: String,
+    error_code: String,
+    error_message: String,
+}
+
+/// Finds the payout batch created with an idempotency key, if any.
+#[cfg(feature = "payouts")]
+async fn find_payout_batch_by_idempotency_key(
+    db: &dyn StorageInterface,
+    merchant_account: &domain::MerchantAccount,
+    idempotency_key: &str,
+) -> RouterResult<Option<storage::PayoutBatch>> {
+    match db
+        .find_payout_batch_by_merchant_id_idempotency_key(
+            &merchant_account.merchant_id,
+            idempotency_key,
+        )
+        .await
+    {
+        Ok(payout_batch) => Ok(Some(payout_batch)),
+        Err(error) if error.current_context().is_db_not_found() => Ok(None),
+        Err(error) => Err(error)
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Failed to find the payout batch by idempotency key"),
+    }
+}
+
+/// Aggregates the statuses of the payouts of a batch. Payouts which could not be created have no
+/// status, along with the error with which their creation failed.
+#[cfg(feature = "payouts")]
+async fn get_payout_batch_response(
+    db: &dyn StorageInterface,
+    payout_batch: storage::PayoutBatch,
+) -> RouterResult<payouts::PayoutBatchResponse> {
+    let creation_errors: Vec<PayoutBatchError> = payout_batch
+        .errors
+        .clone()
+        .map(|errors| errors.parse_value("PayoutBatchErrors"))
+        .transpose()
+        .change_context(errors::ApiErrorResponse::InternalServerError)?
+        .unwrap_or_default();
+    let payout_attempts = db
+        .find_payout_attempts_by_merchant_id_payout_ids(
+            &payout_batch.merchant_id,
+            &payout_batch.payout_ids,
+        )
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to fetch the payout attempts of the payout batch")?;
+
+    let mut status_counts: Vec<payouts::PayoutBatchStatusCount> = Vec::new();
+    let payout_items = payout_batch
+        .payout_ids
+        .iter()
+        .map(|payout_id| {
+            let payout_attempt = payout_attempts
+                .iter()
+                .find(|payout_attempt| &payout_attempt.payout_id == payout_id);
+            let creation_error = creation_errors
+                .iter()
+                .find(|creation_error| &creation_error.payout_id == payout_id);
+
+            match payout_attempt {
+                Some(payout_attempt) => {
+                    match status_counts
+                        .iter_mut()
+                        .find(|status_count| status_count.status == payout_attempt.status)
+                    {
+                        Some(status_count) => status_count.count += 1,
+                        None => status_counts.push(payouts::PayoutBatchStatusCount {
+                            status: payout_attempt.status,
+                            count: 1,
+                        }),
+                    }
+                    payouts::PayoutBatchItem {
+                        payout_id: payout_id.to_owned(),
+                        status: Some(payout_attempt.status),
+                        error_code: payout_attempt
+                            .error_code
+                            .clone()
+                            .or_else(|| creation_error.map(|error| error.error_code.clone())),
+                        error_message: payout_attempt
+                            .error_message
+                            .clone()
+                            .or_else(|| creation_error.map(|error| error.error_message.clone())),
+                    }
+                }
+                None => payouts::PayoutBatchItem {
+                    payout_id: payout_id.to_owned(),
+                    status: None,
+                    error_code: creation_error.map(|error| error.error_code.clone()),
+                    error_message: creation_error.map(|error| error.error_message.clone()),
+                },
+            }
+        })
+        .collect::<Vec<_>>();
+
+    Ok(payouts::PayoutBatchResponse {
+        batch_id: payout_batch.batch_id,
+        merchant_id: payout_batch.merchant_id,
+        total_count: payout_items.len(),
+        status_counts,
+        payouts: payout_items,
+        created: payout_batch.created_at,
+    })
+}
+
 // ********************************************** HELPERS **********************************************
 #[cfg(feature = "payouts")]
 pub async fn call_connector_payout(
@@ -699,6 +1071,8 @@ pub async fn check_payout_eligibility(
                     error_code: None,
                     error_message: None,
                     is_eligible: payout_response_data.payout_eligible,
+                    connector_fee: payout_response_data.connector_fee,
+                    fx_margin: payout_response_data.fx_margin,
                     last_modified_at: Some(common_utils::date_time::now()),
                 };
             payout_data.payout_attempt = db
@@ -726,6 +1100,8 @@ pub async fn check_payout_eligibility(
                     error_code: Some(err.code),
                     error_message: Some(err.message),
                     is_eligible: None,
+                    connector_fee: None,
+                    fx_margin: None,
                     last_modified_at: Some(common_utils::date_time::now()),
                 };
             payout_data.payout_attempt = db
@@ -805,6 +1181,8 @@ pub async fn create_payout(
                     error_code: None,
                     error_message: None,
                     is_eligible: payout_response_data.payout_eligible,
+                    connector_fee: payout_response_data.connector_fee,
+                    fx_margin: payout_response_data.fx_margin,
                     last_modified_at: Some(common_utils::date_time::now()),
                 };
             payout_data.payout_attempt = db
@@ -832,6 +1210,8 @@ pub async fn create_payout(
                     error_code: Some(err.code),
                     error_message: Some(err.message),
                     is_eligible: None,
+                    connector_fee: None,
+                    fx_margin: None,
                     last_modified_at: Some(common_utils::date_time::now()),
                 };
             payout_data.payout_attempt = db
@@ -904,6 +1284,8 @@ pub async fn cancel_payout(
                     error_code: None,
                     error_message: None,
                     is_eligible: payout_response_data.payout_eligible,
+                    connector_fee: payout_response_data.connector_fee,
+                    fx_margin: payout_response_data.fx_margin,
                     last_modified_at: Some(common_utils::date_time::now()),
                 };
             payout_data.payout_attempt = db
@@ -917,20 +1299,24 @@ pub async fn cancel_payout(
                 .attach_printable("Error updating payout_attempt in db")?
         }
         Err(err) => {
-            let updated_payouts_create =
+            // The payout has not been cancelled by the connector, so it keeps its status and can
+            // still be fulfilled or cancelled again
+            let updated_payout_attempt =
                 storage::payout_attempt::PayoutAttemptUpdate::StatusUpdate {
-                    connector_payout_id: String::default(),
-                    status: storage_enums::PayoutStatus::Failed,
+                    connector_payout_id: payout_data.payout_attempt.connector_payout_id.to_owned(),
+                    status: payout_data.payout_attempt.status,
                     error_code: Some(err.code),
                     error_message: Some(err.message),
                     is_eligible: None,
+                    connector_fee: None,
+                    fx_margin: None,
                     last_modified_at: Some(common_utils::date_time::now()),
                 };
             payout_data.payout_attempt = db
                 .update_payout_attempt_by_merchant_id_payout_id(
                     merchant_id,
                     payout_id,
-                    updated_payouts_create,
+                    updated_payout_attempt,
                 )
                 .await
                 .change_context(errors::ApiErrorResponse::InternalServerError)
@@ -1009,6 +1395,8 @@ pub async fn fulfill_payout(
                 error_code: None,
                 error_message: None,
                 is_eligible: payout_response_data.payout_eligible,
+                connector_fee: payout_response_data.connector_fee,
+                fx_margin: payout_response_data.fx_margin,
                 last_modified_at: Some(common_utils::date_time::now()),
             };
             payout_data.payout_attempt = db
@@ -1035,6 +1423,8 @@ pub async fn fulfill_payout(
                 error_code: Some(err.code),
                 error_message: Some(err.message),
                 is_eligible: None,
+                connector_fee: None,
+                fx_margin: None,
                 last_modified_at: Some(common_utils::date_time::now()),
             };
             payout_data.payout_attempt = db
@@ -1052,15 +1442,105 @@ pub async fn fulfill_payout(
     Ok(payout_data.clone())
 }
 
+#[cfg(feature = "payouts")]
+pub async fn sync_payout(
+    state: &AppState,
+    merchant_account: &domain::MerchantAccount,
+    key_store: &domain::MerchantKeyStore,
+    req: &payouts::PayoutRequest,
+    connector_data: &api::PayoutConnectorData,
+    payout_data: &mut PayoutData,
+) -> RouterResult<PayoutData> {
+    // 1. Form Router data
+    let router_data = core_utils::construct_payout_router_data(
+        state,
+        &connector_data.connector_name.to_string(),
+        merchant_account,
+        key_store,
+        req,
+        payout_data,
+    )
+    .await?;
+
+    // 2. Fetch connector integration details
+    let connector_integration: services::BoxedConnectorIntegration<
+        '_,
+        api::PoSync,
+        types::PayoutsData,
+        types::PayoutsResponseData,
+    > = connector_data.connector.get_connector_integration();
+
+    // 3. Call connector service
+    let router_data_resp = services::execute_connector_processing_step(
+        state,
+        connector_integration,
+        &router_data,
+        payments::CallConnectorAction::Trigger,
+        None,
+    )
+    .await
+    .to_payout_failed_response()?;
+
+    // 4. Process data returned by the connector
+    let db = &*state.store;
+    let merchant_id = &merchant_account.merchant_id;
+    let payout_attempt = &payout_data.payout_attempt;
+    let payout_id = &payout_attempt.payout_id;
+    // A failed sync says nothing about the payout itself, so it keeps its status and the error is
+    // only recorded
+    let updated_payout_attempt = match router_data_resp.response {
+        Ok(payout_response_data) => storage::payout_attempt::PayoutAttemptUpdate::StatusUpdate {
+            connector_payout_id: payout_attempt.connector_payout_id.to_owned(),
+            status: payout_response_data
+                .status
+                .unwrap_or(payout_attempt.status.to_owned()),
+            error_code: None,
+            error_message: None,
+            is_eligible: payout_response_data.payout_eligible,
+            connector_fee: payout_response_data.connector_fee,
+            fx_margin: payout_response_data.fx_margin,
+            last_modified_at: Some(common_utils::date_time::now()),
+        },
+        Err(err) => storage::payout_attempt::PayoutAttemptUpdate::StatusUpdate {
+            connector_payout_id: payout_attempt.connector_payout_id.to_owned(),
+            status: payout_attempt.status,
+            error_code: Some(err.code),
+            error_message: Some(err.message),
+            is_eligible: None,
+            connector_fee: None,
+            fx_margin: None,
+            last_modified_at: Some(common_utils::date_time::now()),
+        },
+    };
+    payout_data.payout_attempt = db
+        .update_payout_attempt_by_merchant_id_payout_id(
+            merchant_id,
+            payout_id,
+            updated_payout_attempt,
+        )
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Error updating payout_attempt in db")?;
+
+    Ok(payout_data.clone())
+}
+
 #[cfg(feature = "payouts")]
 pub async fn response_handler(
-    _state: &AppState,
+    state: &AppState,
     merchant_account: &domain::MerchantAccount,
     _req: &payouts::PayoutRequest,
     payout_data: &PayoutData,
 ) -> RouterResponse<payouts::PayoutCreateResponse> {
     let payout_attempt = payout_data.payout_attempt.to_owned();
     let payouts = payout_data.payouts.to_owned();
+    // Every payout flow responds through this handler, and payouts are only posted to the ledger
+    // once, so the postings are recorded here. The payout has already been processed, so failing
+    // to post it to the ledger must not fail the request
+    ledger::record_payout_postings(state, &payouts, &payout_attempt)
+        .await
+        .map_err(|error| logger::error!(ledger_error=?error))
+        .ok();
     let billing_address = payout_data.billing_address.to_owned();
     let customer_details = payout_data.customer_details.to_owned();
     let customer_id = payouts.customer_id;
@@ -1117,6 +1597,8 @@ pub async fn response_handler(
         status: payout_attempt.status.to_owned(),
         error_message: payout_attempt.error_message.to_owned(),
         error_code: payout_attempt.error_code,
+        connector_fee: payout_attempt.connector_fee,
+        fx_margin: payout_attempt.fx_margin,
     };
     Ok(services::ApplicationResponse::Json(response))
 }
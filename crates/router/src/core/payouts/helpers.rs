@@ -270,6 +270,10 @@ pub async fn get_or_create_customer_details(
                 id: None,
                 created_at: common_utils::date_time::now(),
                 modified_at: common_utils::date_time::now(),
+                address_id: None,
+                default_shipping_address_id: None,
+                default_billing_address_id: None,
+                default_payment_method_id: None,
             };
 
             Ok(Some(
@@ -429,3 +433,12 @@ pub fn is_eligible_for_local_payout_cancellation(status: api_enums::PayoutStatus
             | api_enums::PayoutStatus::RequiresPayoutMethodData,
     )
 }
+
+/// Payouts which have been created at the connector, but not paid out yet, are cancelled through
+/// the connector.
+pub fn is_eligible_for_connector_payout_cancellation(status: api_enums::PayoutStatus) -> bool {
+    matches!(
+        status,
+        api_enums::PayoutStatus::RequiresFulfillment | api_enums::PayoutStatus::Pending,
+    )
+}
@@ -48,9 +48,26 @@ pub async fn validate_uniqueness_of_payout_id_against_merchant_id(
     }
 }
 
+/// Validates that the return_url, if passed, is on one of the domains allowed by the merchant
+#[cfg(feature = "payouts")]
+pub fn validate_return_url(
+    merchant_account: &domain::MerchantAccount,
+    return_url: Option<&str>,
+) -> RouterResult<()> {
+    if let Some(return_url) = return_url {
+        core_utils::validate_url_against_allowed_domains(
+            return_url,
+            "return_url",
+            merchant_account.allowed_domains.as_deref(),
+        )?;
+    }
+    Ok(())
+}
+
 /// Validates the request on below checks
 /// - merchant_id passed is same as the one in merchant_account table
 /// - payout_id is unique against merchant_id
+/// - return_url is on one of the domains allowed by the merchant
 /// - payout_token provided is legitimate
 #[cfg(feature = "payouts")]
 pub async fn validate_create_request(
@@ -91,6 +108,9 @@ pub async fn validate_create_request(
         None => Ok(()),
     }?;
 
+    // Return URL
+    validate_return_url(merchant_account, req.return_url.as_deref())?;
+
     // Payout token
     let payout_method_data = match req.payout_token.to_owned() {
         Some(payout_token) => {
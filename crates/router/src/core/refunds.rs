@@ -1,15 +1,18 @@
 pub mod validator;
 
-use common_utils::ext_traits::AsyncExt;
+use common_utils::ext_traits::{AsyncExt, StringExt};
 use error_stack::{report, IntoReport, ResultExt};
 use router_env::{instrument, tracing};
 
 use crate::{
-    consts,
+    configs::settings,
+    connection, consts,
     core::{
+        audit,
         errors::{self, ConnectorErrorExt, RouterResponse, RouterResult, StorageErrorExt},
+        hooks, ledger,
         payments::{self, access_token},
-        utils as core_utils,
+        sync_cache, utils as core_utils, webhooks,
     },
     db, logger,
     routes::{metrics, AppState},
@@ -24,6 +27,11 @@ use crate::{
     },
     utils::{self, OptionExt},
 };
+#[cfg(feature = "payouts")]
+use masking::PeekInterface;
+
+#[cfg(feature = "payouts")]
+use crate::{core::payouts as core_payouts, types::api::payouts};
 
 // ********************************************** REFUND EXECUTE **********************************************
 
@@ -83,6 +91,17 @@ pub async fn refund_create_core(
         .await
         .to_not_found_response(errors::ApiErrorResponse::SuccessfulPaymentNotFound)?;
 
+    let hook_context = hooks::PreRefundContext {
+        merchant_account: &merchant_account,
+        payment_intent: &payment_intent,
+        payment_attempt: &payment_attempt,
+        refund_amount: amount,
+        reason: req.reason.as_deref(),
+    };
+    let metadata =
+        hooks::run_pre_refund_hooks(&state.hooks, &hook_context, req.metadata.clone()).await?;
+    let req = refunds::RefundRequest { metadata, ..req };
+
     let creds_identifier = req
         .merchant_connector_details
         .as_ref()
@@ -219,10 +238,15 @@ pub async fn trigger_refund_to_gateway(
             }
             storage::RefundUpdate::Update {
                 connector_refund_id: response.connector_refund_id,
-                refund_status: response.refund_status,
+                refund_status: get_reconciled_refund_status(
+                    refund,
+                    response.refund_status,
+                    response.settled_amount,
+                ),
                 sent_to_gateway: true,
                 refund_error_message: None,
                 refund_arn: "".to_string(),
+                settled_amount: response.settled_amount,
             }
         }
     };
@@ -242,9 +266,322 @@ pub async fn trigger_refund_to_gateway(
                 refund.refund_id
             )
         })?;
+    // The refund has already been processed, so failing to post it to the ledger must not fail
+    // the refund
+    ledger::record_refund_posting(state, &response)
+        .await
+        .map_err(|error| logger::error!(ledger_error=?error))
+        .ok();
+    notify_refund_amount_mismatch(state, merchant_account, refund.refund_status, &response).await;
     Ok(response)
 }
 
+// ********************************************** REFUND TO ALTERNATE DESTINATION **********************************************
+
+/// The fields of a refund recorded in the audit trail when it is redirected to an alternate
+/// destination. Only the last four characters of the bank account number are recorded.
+#[cfg(feature = "payouts")]
+#[derive(Debug, Default, serde::Serialize)]
+struct RefundAlternateDestinationAudit {
+    refund_status: Option<enums::RefundStatus>,
+    payout_id: Option<String>,
+    bank_transfer_type: Option<&'static str>,
+    bank_name: Option<String>,
+    bank_country_code: Option<enums::CountryAlpha2>,
+    bank_account_number_last4: Option<String>,
+}
+
+#[cfg(feature = "payouts")]
+impl RefundAlternateDestinationAudit {
+    fn new(refund: &storage::Refund, bank: &payouts::BankPayout) -> Self {
+        let (bank_transfer_type, bank_name, bank_country_code, bank_account_number) = match bank {
+            payouts::BankPayout::Ach(bank) => (
+                "ach",
+                &bank.bank_name,
+                bank.bank_country_code,
+                &bank.bank_account_number,
+            ),
+            payouts::BankPayout::Bacs(bank) => (
+                "bacs",
+                &bank.bank_name,
+                bank.bank_country_code,
+                &bank.bank_account_number,
+            ),
+            payouts::BankPayout::Sepa(bank) => {
+                ("sepa", &bank.bank_name, bank.bank_country_code, &bank.iban)
+            }
+        };
+        let bank_account_number = bank_account_number.peek();
+        let last4_start = bank_account_number.chars().count().saturating_sub(4);
+
+        Self {
+            refund_status: Some(refund.refund_status),
+            payout_id: None,
+            bank_transfer_type: Some(bank_transfer_type),
+            bank_name: Some(bank_name.clone()),
+            bank_country_code: Some(bank_country_code),
+            bank_account_number_last4: Some(
+                bank_account_number.chars().skip(last4_start).collect(),
+            ),
+        }
+    }
+}
+
+/// Refunds the amount to the original payment method, and redirects the refund to the alternate
+/// destination if the amount could not be credited back to it: either the refunds of the payment
+/// method are not possible with the connector, or the connector failed the refund.
+#[cfg(feature = "payouts")]
+#[allow(clippy::too_many_arguments)]
+#[instrument(skip_all)]
+async fn refund_with_alternate_destination(
+    state: &AppState,
+    refund: &storage::Refund,
+    merchant_account: &domain::MerchantAccount,
+    key_store: &domain::MerchantKeyStore,
+    payment_attempt: &storage::PaymentAttempt,
+    payment_intent: &storage::PaymentIntent,
+    creds_identifier: Option<String>,
+    destination: refunds::RefundAlternateDestination,
+) -> RouterResult<storage::Refund> {
+    let refund = match trigger_refund_to_gateway(
+        state,
+        refund,
+        merchant_account,
+        key_store,
+        payment_attempt,
+        payment_intent,
+        creds_identifier,
+    )
+    .await
+    {
+        Ok(refund) => refund,
+        Err(error)
+            if matches!(
+                error.current_context(),
+                errors::ApiErrorResponse::RefundNotPossible { .. }
+            ) =>
+        {
+            let refund_update = storage::RefundUpdate::ErrorUpdate {
+                refund_status: Some(enums::RefundStatus::Failure),
+                refund_error_message: Some(error.current_context().to_string()),
+                refund_error_code: None,
+            };
+            state
+                .store
+                .update_refund(
+                    refund.to_owned(),
+                    refund_update,
+                    merchant_account.storage_scheme,
+                )
+                .await
+                .to_not_found_response(errors::ApiErrorResponse::InternalServerError)
+                .attach_printable_lazy(|| {
+                    format!(
+                        "Failed while updating refund: refund_id: {}",
+                        refund.refund_id
+                    )
+                })?
+        }
+        Err(error) => return Err(error),
+    };
+
+    if refund.refund_status != enums::RefundStatus::Failure {
+        return Ok(refund);
+    }
+
+    trigger_refund_to_alternate_destination(
+        state,
+        &refund,
+        merchant_account,
+        key_store,
+        payment_intent,
+        destination,
+    )
+    .await
+}
+
+/// Refunds the amount of a failed refund to a destination other than the original payment method,
+/// by creating a payout to the provided bank account and linking it to the refund. The
+/// destination is recorded in the audit trail of the refund.
+#[cfg(feature = "payouts")]
+#[instrument(skip_all)]
+pub async fn trigger_refund_to_alternate_destination(
+    state: &AppState,
+    refund: &storage::Refund,
+    merchant_account: &domain::MerchantAccount,
+    key_store: &domain::MerchantKeyStore,
+    payment_intent: &storage::PaymentIntent,
+    destination: refunds::RefundAlternateDestination,
+) -> RouterResult<storage::Refund> {
+    let db = &*state.store;
+
+    utils::when(refund.refund_status != enums::RefundStatus::Failure, || {
+        Err(report!(errors::ApiErrorResponse::PreconditionFailed {
+            message: format!(
+                "Refund {} can only be sent to an alternate destination once it has failed, but \
+                 it is {}",
+                refund.refund_id, refund.refund_status
+            ),
+        }))
+    })?;
+    let previous = RefundAlternateDestinationAudit {
+        refund_status: Some(refund.refund_status),
+        ..Default::default()
+    };
+    let mut destination_audit = RefundAlternateDestinationAudit::new(refund, &destination.bank);
+
+    let billing = match destination.billing {
+        Some(billing) => Some(billing),
+        None => payments::helpers::get_address_for_payment_request(
+            db,
+            None,
+            payment_intent.billing_address_id.as_deref(),
+            &merchant_account.merchant_id,
+            payment_intent.customer_id.as_ref(),
+            key_store,
+        )
+        .await?
+        .as_ref()
+        .map(api_models::payments::Address::from),
+    };
+
+    let payout_request = payouts::PayoutCreateRequest {
+        merchant_id: Some(merchant_account.merchant_id.clone()),
+        amount: Some(refund.refund_amount.into()),
+        currency: Some(refund.currency),
+        confirm: Some(true),
+        auto_fulfill: Some(true),
+        payout_type: Some(enums::PayoutType::Bank),
+        payout_method_data: Some(payouts::PayoutMethodData::Bank(destination.bank)),
+        billing,
+        customer_id: payment_intent.customer_id.clone(),
+        description: Some(format!(
+            "Refund {} for payment {}",
+            refund.refund_id, refund.payment_id
+        )),
+        metadata: refund.metadata.clone(),
+        ..Default::default()
+    };
+
+    let payout_response = match core_payouts::payouts_create_core(
+        state,
+        merchant_account.clone(),
+        key_store.clone(),
+        payout_request,
+    )
+    .await
+    {
+        Ok(services::ApplicationResponse::Json(payout_response)) => payout_response,
+        Ok(_) => Err(errors::ApiErrorResponse::InternalServerError)
+            .into_report()
+            .attach_printable("Unexpected response received while creating the refund payout")?,
+        Err(error) => {
+            let refund_update = storage::RefundUpdate::ErrorUpdate {
+                refund_status: Some(enums::RefundStatus::Failure),
+                refund_error_message: Some(
+                    "Failed to create a payout to the alternate destination".to_string(),
+                ),
+                refund_error_code: None,
+            };
+            db.update_refund(
+                refund.to_owned(),
+                refund_update,
+                merchant_account.storage_scheme,
+            )
+            .await
+            .to_not_found_response(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable_lazy(|| {
+                format!(
+                    "Failed while updating refund: refund_id: {}",
+                    refund.refund_id
+                )
+            })?;
+            return Err(error);
+        }
+    };
+
+    let refund_update = storage::RefundUpdate::PayoutUpdate {
+        payout_id: payout_response.payout_id,
+        refund_status: payout_response.status.foreign_into(),
+        sent_to_gateway: true,
+        refund_error_message: payout_response.error_message,
+        refund_error_code: payout_response.error_code,
+    };
+
+    let response = db
+        .update_refund(
+            refund.to_owned(),
+            refund_update,
+            merchant_account.storage_scheme,
+        )
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable_lazy(|| {
+            format!(
+                "Failed while updating refund: refund_id: {}",
+                refund.refund_id
+            )
+        })?;
+
+    destination_audit.refund_status = Some(response.refund_status);
+    destination_audit.payout_id = response.payout_id.clone();
+    audit::record_audit_event(
+        db,
+        &merchant_account.merchant_id,
+        enums::AuditEntityType::Refund,
+        &response.refund_id,
+        Some(&previous),
+        Some(&destination_audit),
+    )
+    .await?;
+
+    Ok(response)
+}
+
+/// Updates the status of a refund made to an alternate destination from the status of the
+/// payout linked to it.
+#[cfg(feature = "payouts")]
+#[instrument(skip_all)]
+pub async fn sync_refund_with_payout(
+    state: &AppState,
+    merchant_account: &domain::MerchantAccount,
+    refund: &storage::Refund,
+    payout_id: &str,
+) -> RouterResult<storage::Refund> {
+    let db = &*state.store;
+    let payout_attempt = db
+        .find_payout_attempt_by_merchant_id_payout_id(&merchant_account.merchant_id, payout_id)
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::PayoutNotFound)?;
+
+    let refund_status: enums::RefundStatus = payout_attempt.status.foreign_into();
+    if refund_status == refund.refund_status {
+        return Ok(refund.to_owned());
+    }
+
+    let refund_update = storage::RefundUpdate::PayoutUpdate {
+        payout_id: payout_id.to_string(),
+        refund_status,
+        sent_to_gateway: true,
+        refund_error_message: payout_attempt.error_message,
+        refund_error_code: payout_attempt.error_code,
+    };
+
+    db.update_refund(
+        refund.to_owned(),
+        refund_update,
+        merchant_account.storage_scheme,
+    )
+    .await
+    .to_not_found_response(errors::ApiErrorResponse::InternalServerError)
+    .attach_printable_lazy(|| {
+        format!(
+            "Failed while updating refund: refund_id: {}",
+            refund.refund_id
+        )
+    })
+}
+
 // ********************************************** REFUND SYNC **********************************************
 
 pub async fn refund_response_wrapper<'a, F, Fut, T, Req>(
@@ -288,6 +625,12 @@ pub async fn refund_retrieve_core(
         .await
         .to_not_found_response(errors::ApiErrorResponse::RefundNotFound)?;
 
+    // Refunds made to an alternate destination track the status of the linked payout
+    #[cfg(feature = "payouts")]
+    if let Some(payout_id) = refund.payout_id.clone() {
+        return sync_refund_with_payout(state, &merchant_account, &refund, &payout_id).await;
+    }
+
     let payment_id = refund.payment_id.as_str();
     payment_intent = db
         .find_payment_intent_by_payment_id_merchant_id(
@@ -326,8 +669,23 @@ pub async fn refund_retrieve_core(
         .await
         .transpose()?;
 
-    response = if should_call_refund(&refund, request.force_sync.unwrap_or(false)) {
-        sync_refund_with_gateway(
+    let should_sync_with_connector =
+        should_call_refund(&refund, request.force_sync.unwrap_or(false))
+            && match refund.connector_refund_id.as_ref() {
+                Some(connector_refund_id) if !request.bypass_sync_cache.unwrap_or(false) => {
+                    sync_cache::should_sync_with_connector(
+                        state,
+                        sync_cache::SyncFlow::RefundSync,
+                        &refund.connector,
+                        connector_refund_id,
+                    )
+                    .await
+                }
+                _ => true,
+            };
+
+    response = if should_sync_with_connector {
+        let sync_result = sync_refund_with_gateway(
             state,
             &merchant_account,
             &key_store,
@@ -336,7 +694,19 @@ pub async fn refund_retrieve_core(
             &refund,
             creds_identifier,
         )
-        .await
+        .await;
+        if let (Err(_), Some(connector_refund_id)) =
+            (sync_result.as_ref(), refund.connector_refund_id.as_ref())
+        {
+            sync_cache::release_sync_claim(
+                state,
+                sync_cache::SyncFlow::RefundSync,
+                &refund.connector,
+                connector_refund_id,
+            )
+            .await;
+        }
+        sync_result
     } else {
         Ok(refund)
     }?;
@@ -344,6 +714,37 @@ pub async fn refund_retrieve_core(
     Ok(response)
 }
 
+#[instrument(skip_all)]
+pub async fn refund_retrieve_by_merchant_refund_reference_core(
+    state: &AppState,
+    merchant_account: domain::MerchantAccount,
+    key_store: domain::MerchantKeyStore,
+    (merchant_refund_reference, query): (String, api_models::refunds::RefundsRetrieveBody),
+) -> RouterResult<storage::Refund> {
+    let refund = state
+        .store
+        .find_refund_by_merchant_id_merchant_refund_reference(
+            &merchant_account.merchant_id,
+            &merchant_refund_reference,
+            merchant_account.storage_scheme,
+        )
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::RefundNotFound)?;
+
+    refund_retrieve_core(
+        state,
+        merchant_account,
+        key_store,
+        refunds::RefundsRetrieveRequest {
+            refund_id: refund.refund_id,
+            force_sync: query.force_sync,
+            bypass_sync_cache: query.bypass_sync_cache,
+            merchant_connector_details: None,
+        },
+    )
+    .await
+}
+
 fn should_call_refund(refund: &diesel_models::refund::Refund, force_sync: bool) -> bool {
     // This implies, we cannot perform a refund sync & `the connector_refund_id`
     // doesn't exist
@@ -360,6 +761,47 @@ fn should_call_refund(refund: &diesel_models::refund::Refund, force_sync: bool)
     predicate1 && predicate2
 }
 
+/// Returns the status of a refund reported as succeeded by the connector, flagging the refund for
+/// review when the amount refunded reported by the connector differs from the amount requested,
+/// instead of silently marking it succeeded.
+pub fn get_reconciled_refund_status(
+    refund: &storage::Refund,
+    refund_status: enums::RefundStatus,
+    settled_amount: Option<i64>,
+) -> enums::RefundStatus {
+    match settled_amount {
+        Some(settled_amount)
+            if refund_status == enums::RefundStatus::Success
+                && settled_amount != refund.refund_amount =>
+        {
+            if refund.refund_status != enums::RefundStatus::AmountMismatch {
+                logger::warn!(
+                    refund_id = %refund.refund_id,
+                    requested_amount = refund.refund_amount,
+                    settled_amount,
+                    "Amount refunded by the connector differs from the amount requested"
+                );
+                metrics::REFUND_AMOUNT_MISMATCH.add(
+                    &metrics::CONTEXT,
+                    1,
+                    &[metrics::request::add_attributes(
+                        "connector",
+                        refund.connector.clone(),
+                    )],
+                );
+            }
+            enums::RefundStatus::AmountMismatch
+        }
+        // A later sync or webhook which doesn't report the amount refunded must not clear the flag
+        None if refund_status == enums::RefundStatus::Success
+            && refund.refund_status == enums::RefundStatus::AmountMismatch =>
+        {
+            enums::RefundStatus::AmountMismatch
+        }
+        _ => refund_status,
+    }
+}
+
 #[instrument(skip_all)]
 pub async fn sync_refund_with_gateway(
     state: &AppState,
@@ -427,68 +869,549 @@ pub async fn sync_refund_with_gateway(
         router_data
     };
 
-    let refund_update = match router_data_res.response {
-        Err(error_message) => storage::RefundUpdate::ErrorUpdate {
-            refund_status: None,
-            refund_error_message: error_message.reason.or(Some(error_message.message)),
-            refund_error_code: Some(error_message.code),
-        },
-        Ok(response) => storage::RefundUpdate::Update {
-            connector_refund_id: response.connector_refund_id,
-            refund_status: response.refund_status,
-            sent_to_gateway: true,
-            refund_error_message: None,
-            refund_arn: "".to_string(),
-        },
-    };
+    // The error of the connector is not the status of the refund, so it must not be served to the
+    // syncs within the sync cache window
+    if let (Err(_), Some(connector_refund_id)) = (
+        router_data_res.response.as_ref(),
+        refund.connector_refund_id.as_ref(),
+    ) {
+        sync_cache::release_sync_claim(
+            state,
+            sync_cache::SyncFlow::RefundSync,
+            &refund.connector,
+            connector_refund_id,
+        )
+        .await;
+    }
+
+    let refund_update = match router_data_res.response {
+        Err(error_message) => storage::RefundUpdate::ErrorUpdate {
+            refund_status: None,
+            refund_error_message: error_message.reason.or(Some(error_message.message)),
+            refund_error_code: Some(error_message.code),
+        },
+        Ok(response) => storage::RefundUpdate::Update {
+            connector_refund_id: response.connector_refund_id,
+            refund_status: get_reconciled_refund_status(
+                refund,
+                response.refund_status,
+                response.settled_amount,
+            ),
+            sent_to_gateway: true,
+            refund_error_message: None,
+            refund_arn: "".to_string(),
+            settled_amount: response.settled_amount,
+        },
+    };
+
+    let response = state
+        .store
+        .update_refund(
+            refund.to_owned(),
+            refund_update,
+            merchant_account.storage_scheme,
+        )
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::RefundNotFound)
+        .attach_printable_lazy(|| {
+            format!(
+                "Unable to update refund with refund_id: {}",
+                refund.refund_id
+            )
+        })?;
+    ledger::record_refund_posting(state, &response)
+        .await
+        .map_err(|error| logger::error!(ledger_error=?error))
+        .ok();
+    notify_refund_amount_mismatch(state, merchant_account, refund.refund_status, &response).await;
+    Ok(response)
+}
+
+/// Notifies the merchant of a refund which has just been flagged for review because of an amount
+/// mismatch.
+async fn notify_refund_amount_mismatch(
+    state: &AppState,
+    merchant_account: &domain::MerchantAccount,
+    previous_refund_status: enums::RefundStatus,
+    refund: &storage::Refund,
+) {
+    if refund.refund_status != enums::RefundStatus::AmountMismatch
+        || previous_refund_status == enums::RefundStatus::AmountMismatch
+    {
+        return;
+    }
+
+    trigger_refund_outgoing_webhook(
+        state,
+        merchant_account,
+        enums::EventType::RefundAmountMismatch,
+        refund,
+    )
+    .await;
+}
+
+/// Notifies the merchant of a change in the status of a refund which was not reported by the
+/// connector.
+async fn trigger_refund_outgoing_webhook(
+    state: &AppState,
+    merchant_account: &domain::MerchantAccount,
+    event_type: enums::EventType,
+    refund: &storage::Refund,
+) {
+    let refund_response: api::RefundResponse = refund.clone().foreign_into();
+    if let Err(error) = webhooks::create_event_and_trigger_outgoing_webhook::<api::OutgoingWebhook>(
+        state.clone(),
+        merchant_account.clone(),
+        event_type,
+        enums::EventClass::Refunds,
+        None,
+        refund.refund_id.clone(),
+        enums::EventObjectType::RefundDetails,
+        api::OutgoingWebhookContent::RefundDetails(refund_response),
+    )
+    .await
+    {
+        logger::error!(refund_webhook_error=?error, ?event_type);
+    }
+}
+
+// ********************************************** REFUND UPDATE **********************************************
+
+pub async fn refund_update_core(
+    db: &dyn db::StorageInterface,
+    merchant_account: domain::MerchantAccount,
+    refund_id: &str,
+    req: refunds::RefundUpdateRequest,
+) -> RouterResponse<refunds::RefundResponse> {
+    let refund = db
+        .find_refund_by_merchant_id_refund_id(
+            &merchant_account.merchant_id,
+            refund_id,
+            merchant_account.storage_scheme,
+        )
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::RefundNotFound)?;
+
+    let response = db
+        .update_refund(
+            refund,
+            storage::RefundUpdate::MetadataAndReasonUpdate {
+                metadata: req.metadata,
+                reason: req.reason,
+            },
+            merchant_account.storage_scheme,
+        )
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable_lazy(|| format!("Unable to update refund with refund_id: {refund_id}"))?;
+
+    Ok(services::ApplicationResponse::Json(response.foreign_into()))
+}
+
+// ********************************************** REFUND CANCEL **********************************************
+
+#[instrument(skip_all)]
+pub async fn refund_cancel_core(
+    state: &AppState,
+    merchant_account: domain::MerchantAccount,
+    refund_id: String,
+) -> RouterResponse<refunds::RefundResponse> {
+    let db = &*state.store;
+    let refund = db
+        .find_refund_by_merchant_id_refund_id(
+            &merchant_account.merchant_id,
+            &refund_id,
+            merchant_account.storage_scheme,
+        )
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::RefundNotFound)?;
+
+    // Only scheduled refunds which have not been sent to the connector yet can be cancelled
+    utils::when(
+        refund.scheduled_at.is_none()
+            || refund.sent_to_gateway
+            || refund.refund_status != enums::RefundStatus::Pending,
+        || {
+            Err(report!(errors::ApiErrorResponse::PreconditionFailed {
+                message: format!(
+                    "Only scheduled refunds which have not been processed yet can be cancelled, refund status is {}",
+                    refund.refund_status
+                ),
+            }))
+        },
+    )?;
+
+    let task_id = format!(
+        "REFUND_WORKFLOW_ROUTER_EXECUTE_REFUND_{}",
+        refund.internal_reference_id
+    );
+
+    // The refund is cancelled before its task is finished, so that a task picked up by the
+    // scheduler in the meantime does not send the refund to the connector
+    let response = db
+        .update_refund(
+            refund,
+            storage::RefundUpdate::StatusUpdate {
+                connector_refund_id: None,
+                sent_to_gateway: false,
+                refund_status: enums::RefundStatus::Cancelled,
+                settled_amount: None,
+            },
+            merchant_account.storage_scheme,
+        )
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable_lazy(|| format!("Unable to cancel refund with refund_id: {refund_id}"))?;
+
+    if let Some(refund_process) = db
+        .find_process_by_id(&task_id)
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to find the process id")?
+    {
+        refund_process
+            .finish_with_status(db, "CANCELLED_BY_MERCHANT".to_string())
+            .await
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable_lazy(|| {
+                format!("Failed to finish the refund execute task, refund_id: {refund_id}")
+            })?;
+    }
+
+    Ok(services::ApplicationResponse::Json(response.foreign_into()))
+}
+
+// ********************************************** REFUND APPROVAL **********************************************
+
+/// Returns whether a refund has to be approved before it is sent to the connector. Refunds for an
+/// amount above the threshold configured for the merchant in the
+/// `refund_approval_threshold_{merchant_id}` config (in the minor unit of the currency) are held
+/// for approval, while merchants without the config have their refunds executed right away.
+async fn is_refund_approval_required(
+    db: &dyn db::StorageInterface,
+    merchant_id: &str,
+    refund_amount: i64,
+) -> RouterResult<bool> {
+    match db
+        .find_config_by_key_cached(&format!("refund_approval_threshold_{merchant_id}"))
+        .await
+    {
+        Ok(config) => {
+            let threshold: i64 = config
+                .config
+                .parse_struct("RefundApprovalThreshold")
+                .change_context(errors::ApiErrorResponse::InternalServerError)
+                .attach_printable(
+                    "Invalid refund approval threshold configured for the merchant",
+                )?;
+            Ok(refund_amount > threshold)
+        }
+        Err(err) if err.current_context().is_db_not_found() => Ok(false),
+        Err(err) => Err(err
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Failed to fetch the refund approval threshold of the merchant")),
+    }
+}
+
+async fn find_refund_pending_approval(
+    db: &dyn db::StorageInterface,
+    merchant_account: &domain::MerchantAccount,
+    refund_id: &str,
+) -> RouterResult<storage::Refund> {
+    let refund = db
+        .find_refund_by_merchant_id_refund_id(
+            &merchant_account.merchant_id,
+            refund_id,
+            merchant_account.storage_scheme,
+        )
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::RefundNotFound)?;
+
+    utils::when(
+        refund.refund_status != enums::RefundStatus::PendingApproval,
+        || {
+            Err(report!(errors::ApiErrorResponse::PreconditionFailed {
+                message: format!(
+                    "Only refunds pending approval can be approved or rejected, refund status is {}",
+                    refund.refund_status
+                ),
+            }))
+        },
+    )?;
+
+    Ok(refund)
+}
+
+/// Moves a refund out of the pending approval state, provided that it has not been approved or
+/// rejected concurrently
+async fn transition_refund_pending_approval(
+    db: &dyn db::StorageInterface,
+    storage_scheme: enums::MerchantStorageScheme,
+    refund: storage::Refund,
+    refund_status: enums::RefundStatus,
+) -> RouterResult<storage::Refund> {
+    let refund_id = refund.refund_id.clone();
+    db.update_refund_if_status_in(
+        refund,
+        vec![enums::RefundStatus::PendingApproval],
+        storage::RefundUpdate::StatusUpdate {
+            connector_refund_id: None,
+            sent_to_gateway: false,
+            refund_status,
+            settled_amount: None,
+        },
+        storage_scheme,
+    )
+    .await
+    .map_err(|error| {
+        if error.current_context().is_db_not_found() {
+            error.change_context(errors::ApiErrorResponse::PreconditionFailed {
+                message: "Refund has already been approved or rejected".to_string(),
+            })
+        } else {
+            error.change_context(errors::ApiErrorResponse::InternalServerError)
+        }
+    })
+    .attach_printable_lazy(|| {
+        format!("Unable to move refund with refund_id: {refund_id} to {refund_status}")
+    })
+}
+
+#[instrument(skip_all)]
+pub async fn refund_approve_core(
+    state: &AppState,
+    merchant_account: domain::MerchantAccount,
+    key_store: domain::MerchantKeyStore,
+    refund_id: String,
+) -> RouterResponse<refunds::RefundResponse> {
+    let db = &*state.store;
+    let refund = find_refund_pending_approval(db, &merchant_account, &refund_id).await?;
+
+    let payment_attempt = db
+        .find_payment_attempt_by_connector_transaction_id_payment_id_merchant_id(
+            &refund.connector_transaction_id,
+            &refund.payment_id,
+            &merchant_account.merchant_id,
+            merchant_account.storage_scheme,
+        )
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::PaymentNotFound)?;
+
+    let payment_intent = db
+        .find_payment_intent_by_payment_id_merchant_id(
+            &refund.payment_id,
+            &merchant_account.merchant_id,
+            merchant_account.storage_scheme,
+        )
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::PaymentNotFound)?;
+
+    // The refund leaves the pending approval state before it is executed, so that it is executed
+    // the same way as the refunds which do not require approval. Only the request which moves the
+    // refund out of the pending approval state executes it, concurrent approvals fail here.
+    let refund = transition_refund_pending_approval(
+        db,
+        merchant_account.storage_scheme,
+        refund,
+        enums::RefundStatus::Pending,
+    )
+    .await?;
+
+    let response = if let Some(schedule_at) = refund.scheduled_at {
+        add_refund_execute_task(db, &refund, "REFUND_WORKFLOW_ROUTER", schedule_at)
+            .await
+            .attach_printable_lazy(|| {
+                format!("Failed while scheduling the refund execution, refund_id: {refund_id}")
+            })?;
+        refund
+    } else {
+        let refund_type = refund.refund_type.foreign_into();
+        schedule_refund_execution(
+            state,
+            refund,
+            refund_type,
+            &merchant_account,
+            &key_store,
+            &payment_attempt,
+            &payment_intent,
+            None,
+        )
+        .await?
+    };
+
+    Ok(services::ApplicationResponse::Json(response.foreign_into()))
+}
+
+#[instrument(skip_all)]
+pub async fn refund_reject_core(
+    state: &AppState,
+    merchant_account: domain::MerchantAccount,
+    refund_id: String,
+) -> RouterResponse<refunds::RefundResponse> {
+    let db = &*state.store;
+    let refund = find_refund_pending_approval(db, &merchant_account, &refund_id).await?;
+
+    let response = transition_refund_pending_approval(
+        db,
+        merchant_account.storage_scheme,
+        refund,
+        enums::RefundStatus::Rejected,
+    )
+    .await?;
+
+    Ok(services::ApplicationResponse::Json(response.foreign_into()))
+}
+
+// ********************************************** REFUND RESOLUTION **********************************************
+
+/// The fields of a refund recorded in the audit trail when it is resolved by an administrator
+#[derive(Debug, serde::Serialize)]
+struct RefundResolutionAudit {
+    refund_status: enums::RefundStatus,
+    resolution_reason: Option<String>,
+}
+
+#[instrument(skip_all)]
+pub async fn refund_resolve_core(
+    state: &AppState,
+    merchant_id: String,
+    refund_id: String,
+    req: refunds::RefundResolveRequest,
+) -> RouterResponse<refunds::RefundResponse> {
+    let db = &*state.store;
+    let key_store = db
+        .get_merchant_key_store_by_merchant_id(&merchant_id, &db.get_master_key().to_vec().into())
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::MerchantAccountNotFound)?;
 
-    let response = state
-        .store
-        .update_refund(
-            refund.to_owned(),
-            refund_update,
-            merchant_account.storage_scheme,
-        )
+    let merchant_account = db
+        .find_merchant_account_by_merchant_id(&merchant_id, &key_store)
         .await
-        .to_not_found_response(errors::ApiErrorResponse::RefundNotFound)
-        .attach_printable_lazy(|| {
-            format!(
-                "Unable to update refund with refund_id: {}",
-                refund.refund_id
-            )
-        })?;
-    Ok(response)
-}
+        .to_not_found_response(errors::ApiErrorResponse::MerchantAccountNotFound)?;
 
-// ********************************************** REFUND UPDATE **********************************************
+    // Requests authenticated with the admin API key are not bound to the storage region of a
+    // merchant, whereas the refunds of the merchant are stored in its region
+    connection::with_storage_region(
+        merchant_account.storage_region.clone(),
+        resolve_refund(state, merchant_account, refund_id, req),
+    )
+    .await
+}
 
-pub async fn refund_update_core(
-    db: &dyn db::StorageInterface,
+async fn resolve_refund(
+    state: &AppState,
     merchant_account: domain::MerchantAccount,
-    refund_id: &str,
-    req: refunds::RefundUpdateRequest,
+    refund_id: String,
+    req: refunds::RefundResolveRequest,
 ) -> RouterResponse<refunds::RefundResponse> {
+    let db = &*state.store;
     let refund = db
         .find_refund_by_merchant_id_refund_id(
             &merchant_account.merchant_id,
-            refund_id,
+            &refund_id,
             merchant_account.storage_scheme,
         )
         .await
         .to_not_found_response(errors::ApiErrorResponse::RefundNotFound)?;
 
+    utils::when(
+        !matches!(
+            refund.refund_status,
+            enums::RefundStatus::Pending | enums::RefundStatus::ManualReview
+        ),
+        || {
+            Err(report!(errors::ApiErrorResponse::PreconditionFailed {
+                message: format!(
+                    "Only pending refunds or refunds in manual review can be resolved, refund status is {}",
+                    refund.refund_status
+                ),
+            }))
+        },
+    )?;
+
+    let (refund_status, event_type) = match req.resolution {
+        refunds::RefundResolution::Succeeded => (
+            enums::RefundStatus::Success,
+            enums::EventType::RefundSucceeded,
+        ),
+        refunds::RefundResolution::Failed => {
+            (enums::RefundStatus::Failure, enums::EventType::RefundFailed)
+        }
+    };
+    let previous = RefundResolutionAudit {
+        refund_status: refund.refund_status,
+        resolution_reason: None,
+    };
+    let sent_to_gateway = refund.sent_to_gateway;
+    let current_status = refund.refund_status;
+
+    // The refund sync task may update the refund between its read and its resolution, in which
+    // case the refund is not resolved
     let response = db
-        .update_refund(
+        .update_refund_if_status_in(
             refund,
-            storage::RefundUpdate::MetadataAndReasonUpdate {
-                metadata: req.metadata,
-                reason: req.reason,
+            vec![current_status],
+            storage::RefundUpdate::StatusUpdate {
+                connector_refund_id: None,
+                sent_to_gateway,
+                refund_status,
+                settled_amount: None,
             },
             merchant_account.storage_scheme,
         )
         .await
+        .map_err(|error| {
+            if error.current_context().is_db_not_found() {
+                error.change_context(errors::ApiErrorResponse::PreconditionFailed {
+                    message: "Refund was updated while being resolved, please retry".to_string(),
+                })
+            } else {
+                error.change_context(errors::ApiErrorResponse::InternalServerError)
+            }
+        })
+        .attach_printable_lazy(|| {
+            format!("Unable to resolve refund with refund_id: {refund_id}")
+        })?;
+
+    ledger::record_refund_posting(state, &response)
+        .await
+        .map_err(|error| logger::error!(ledger_error=?error))
+        .ok();
+
+    // The refund is no longer synced with the connector once resolved
+    let task_id = format!(
+        "REFUND_WORKFLOW_ROUTER_SYNC_REFUND_{}",
+        response.internal_reference_id
+    );
+    if let Some(refund_process) = db
+        .find_process_by_id(&task_id)
+        .await
         .change_context(errors::ApiErrorResponse::InternalServerError)
-        .attach_printable_lazy(|| format!("Unable to update refund with refund_id: {refund_id}"))?;
+        .attach_printable("Failed to find the process id")?
+    {
+        refund_process
+            .finish_with_status(db, "RESOLVED_BY_ADMIN".to_string())
+            .await
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable_lazy(|| {
+                format!("Failed to finish the refund sync task, refund_id: {refund_id}")
+            })?;
+    }
+
+    trigger_refund_outgoing_webhook(state, &merchant_account, event_type, &response).await;
+
+    audit::record_audit_event(
+        db,
+        &merchant_account.merchant_id,
+        enums::AuditEntityType::Refund,
+        &response.refund_id,
+        Some(&previous),
+        Some(&RefundResolutionAudit {
+            refund_status: response.refund_status,
+            resolution_reason: req.reason,
+        }),
+    )
+    .await?;
 
     Ok(services::ApplicationResponse::Json(response.foreign_into()))
 }
@@ -513,6 +1436,9 @@ pub async fn validate_and_create_refund(
     // Only for initial dev and testing
     let refund_type = req.refund_type.unwrap_or_default();
 
+    #[cfg(feature = "payouts")]
+    let alternate_destination = req.alternate_destination.clone();
+
     // If Refund Id not passed in request Generate one.
 
     refund_id = core_utils::get_or_generate_id("refund_id", &req.refund_id, "ref")?;
@@ -530,6 +1456,67 @@ pub async fn validate_and_create_refund(
         .attach_printable("invalid merchant_id in request"))
     })?;
 
+    // Scheduled refunds are executed by the scheduler, which neither sends refunds to an
+    // alternate destination nor has access to the connector credentials passed in the request
+    #[cfg(feature = "payouts")]
+    let is_executed_with_request_details =
+        alternate_destination.is_some() || creds_identifier.is_some();
+    #[cfg(not(feature = "payouts"))]
+    let is_executed_with_request_details = creds_identifier.is_some();
+
+    utils::when(
+        req.schedule_at.is_some() && is_executed_with_request_details,
+        || {
+            Err(report!(errors::ApiErrorResponse::PreconditionFailed {
+                message: "schedule_at cannot be used along with alternate_destination or merchant_connector_details".to_string(),
+            }))
+        },
+    )?;
+
+    // Refunds held for approval are executed on approval, which has no access to the details
+    // passed in the request either
+    let requires_approval =
+        is_refund_approval_required(db, &merchant_account.merchant_id, refund_amount).await?;
+
+    utils::when(
+        requires_approval && is_executed_with_request_details,
+        || {
+            Err(report!(errors::ApiErrorResponse::PreconditionFailed {
+                message: "Refunds above the approval threshold cannot be made with alternate_destination or merchant_connector_details".to_string(),
+            }))
+        },
+    )?;
+
+    let merchant_refund_reference = req.merchant_refund_reference.clone();
+    if let Some(reference) = merchant_refund_reference.as_deref() {
+        utils::when(
+            reference.is_empty() || reference.len() > consts::MAX_ID_LENGTH,
+            || {
+                Err(report!(errors::ApiErrorResponse::InvalidDataFormat {
+                    field_name: "merchant_refund_reference".to_string(),
+                    expected_format: format!(
+                        "non-empty string of at most {} characters",
+                        consts::MAX_ID_LENGTH
+                    ),
+                }))
+            },
+        )?;
+
+        // Retries of a refund request return the refund created by the first request
+        if let Some(refund) = validator::validate_merchant_refund_reference(
+            db,
+            &payment_intent.payment_id,
+            &merchant_account.merchant_id,
+            reference,
+            refund_amount,
+            merchant_account.storage_scheme,
+        )
+        .await?
+        {
+            return Ok(refund.foreign_into());
+        }
+    }
+
     let refund = match validator::validate_uniqueness_of_refund_id_against_merchant_id(
         db,
         &payment_intent.payment_id,
@@ -575,6 +1562,21 @@ pub async fn validate_and_create_refund(
                 ),
             })?;
 
+            if let Some(schedule_at) = req.schedule_at.as_ref() {
+                validator::validate_refund_schedule_time(
+                    schedule_at,
+                    &payment_intent.created_at,
+                    state.conf.refund.max_age,
+                )
+                .change_context(errors::ApiErrorResponse::InvalidDataFormat {
+                    field_name: "schedule_at".to_string(),
+                    expected_format: format!(
+                        "a time in the future, not later than {} days after the payment was created",
+                        state.conf.refund.max_age,
+                    ),
+                })?;
+            }
+
             validator::validate_refund_amount(payment_attempt.amount, &all_refunds, refund_amount)
                 .change_context(errors::ApiErrorResponse::RefundAmountExceedsPaymentAmount)?;
 
@@ -584,6 +1586,13 @@ pub async fn validate_and_create_refund(
             )
             .change_context(errors::ApiErrorResponse::MaximumRefundCount)?;
 
+            let convenience_fee_amount = get_prorated_convenience_fee_amount(
+                payment_intent,
+                payment_attempt.amount,
+                &all_refunds,
+                refund_amount,
+            )?;
+
             let connector = payment_attempt
                 .connector
                 .clone()
@@ -605,18 +1614,79 @@ pub async fn validate_and_create_refund(
                 .set_currency(currency)
                 .set_created_at(Some(common_utils::date_time::now()))
                 .set_modified_at(Some(common_utils::date_time::now()))
-                .set_refund_status(enums::RefundStatus::Pending)
+                .set_refund_status(if requires_approval {
+                    enums::RefundStatus::PendingApproval
+                } else {
+                    enums::RefundStatus::Pending
+                })
                 .set_metadata(req.metadata)
                 .set_description(req.reason.clone())
                 .set_attempt_id(payment_attempt.attempt_id.clone())
                 .set_refund_reason(req.reason)
+                .set_merchant_refund_reference(merchant_refund_reference.clone())
+                .set_convenience_fee_amount(convenience_fee_amount)
+                .set_scheduled_at(req.schedule_at)
                 .to_owned();
 
-            refund = db
+            let insert_result = db
                 .insert_refund(refund_create_req, merchant_account.storage_scheme)
-                .await
+                .await;
+
+            // A concurrent request with the same merchant refund reference may have created the
+            // refund in the meantime
+            if let (Err(err), Some(reference)) = (&insert_result, merchant_refund_reference) {
+                if err.current_context().is_db_unique_violation() {
+                    if let Some(refund) = validator::validate_merchant_refund_reference(
+                        db,
+                        &payment_intent.payment_id,
+                        &merchant_account.merchant_id,
+                        &reference,
+                        refund_amount,
+                        merchant_account.storage_scheme,
+                    )
+                    .await?
+                    {
+                        return Ok(refund.foreign_into());
+                    }
+                }
+            }
+
+            refund = insert_result
                 .to_duplicate_response(errors::ApiErrorResponse::DuplicateRefundRequest)?;
 
+            #[cfg(feature = "payouts")]
+            if let Some(destination) = alternate_destination {
+                return refund_with_alternate_destination(
+                    state,
+                    &refund,
+                    merchant_account,
+                    key_store,
+                    payment_attempt,
+                    payment_intent,
+                    creds_identifier,
+                    destination,
+                )
+                .await
+                .map(ForeignInto::foreign_into);
+            }
+
+            // Refunds held for approval are executed once they are approved
+            if refund.refund_status == enums::RefundStatus::PendingApproval {
+                return Ok(refund.foreign_into());
+            }
+
+            if let Some(schedule_at) = refund.scheduled_at {
+                add_refund_execute_task(db, &refund, "REFUND_WORKFLOW_ROUTER", schedule_at)
+                    .await
+                    .attach_printable_lazy(|| {
+                        format!(
+                            "Failed while scheduling the refund execution, refund_id: {}",
+                            refund.refund_id
+                        )
+                    })?;
+                return Ok(refund.foreign_into());
+            }
+
             schedule_refund_execution(
                 state,
                 refund,
@@ -634,6 +1704,53 @@ pub async fn validate_and_create_refund(
     Ok(refund.foreign_into())
 }
 
+/// Get the part of a refund which refunds the convenience fee of the payment. The fee, including
+/// its tax, is refunded in proportion to the amount refunded, and the refund which completes the
+/// refund of the payment refunds whatever remains of the fee, so that no part of the fee is left
+/// behind due to rounding.
+fn get_prorated_convenience_fee_amount(
+    payment_intent: &storage::PaymentIntent,
+    payment_amount: i64,
+    all_refunds: &[storage::Refund],
+    refund_amount: i64,
+) -> RouterResult<Option<i64>> {
+    let convenience_fee = match payments::helpers::get_convenience_fee(payment_intent)? {
+        Some(convenience_fee) => convenience_fee,
+        None => return Ok(None),
+    };
+
+    let (refunded_amount, refunded_convenience_fee_amount) = all_refunds
+        .iter()
+        .filter(|refund| {
+            refund.refund_status != enums::RefundStatus::Failure
+                && refund.refund_status != enums::RefundStatus::TransactionFailure
+                && refund.refund_status != enums::RefundStatus::Cancelled
+                && refund.refund_status != enums::RefundStatus::Rejected
+        })
+        .fold((0, 0), |(amount, convenience_fee_amount), refund| {
+            (
+                amount + refund.refund_amount,
+                convenience_fee_amount + refund.convenience_fee_amount.unwrap_or_default(),
+            )
+        });
+    let remaining_convenience_fee_amount =
+        convenience_fee.total_amount - refunded_convenience_fee_amount;
+
+    let convenience_fee_amount = if refunded_amount + refund_amount >= payment_amount {
+        remaining_convenience_fee_amount
+    } else {
+        let prorated_amount = i128::from(refund_amount) * i128::from(convenience_fee.total_amount)
+            / i128::from(payment_amount);
+        i64::try_from(prorated_amount)
+            .into_report()
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Prorated convenience fee amount out of range")?
+            .min(remaining_convenience_fee_amount)
+    };
+
+    Ok(Some(convenience_fee_amount.max(0)))
+}
+
 // ********************************************** Refund list **********************************************
 
 ///   If payment-id is provided, lists all the refunds associated with that particular payment-id
@@ -708,6 +1825,11 @@ impl ForeignFrom<storage::Refund> for api::RefundResponse {
             created_at: Some(refund.created_at),
             updated_at: Some(refund.updated_at),
             connector: refund.connector,
+            payout_id: refund.payout_id,
+            merchant_refund_reference: refund.merchant_refund_reference,
+            convenience_fee_amount: refund.convenience_fee_amount,
+            scheduled_at: refund.scheduled_at,
+            settled_amount: refund.settled_amount,
         }
     }
 }
@@ -745,7 +1867,7 @@ pub async fn schedule_refund_execution(
                     // Execute the refund task based on refund_type
                     match refund_type {
                         api_models::refunds::RefundType::Scheduled => {
-                            add_refund_execute_task(db, &refund, runner)
+                            add_refund_execute_task(db, &refund, runner, common_utils::date_time::now())
                                 .await
                                 .change_context(errors::ApiErrorResponse::InternalServerError)
                                 .attach_printable_lazy(|| format!("Failed while pushing refund execute task to scheduler, refund_id: {}", refund.refund_id))?;
@@ -823,11 +1945,12 @@ pub async fn sync_refund_with_gateway_workflow(
 
     let response = refund_retrieve_core(
         state,
-        merchant_account,
+        merchant_account.clone(),
         key_store,
         refunds::RefundsRetrieveRequest {
             refund_id: refund_core.refund_internal_reference_id,
             force_sync: Some(true),
+            bypass_sync_cache: Some(true),
             merchant_connector_details: None,
         },
     )
@@ -836,6 +1959,7 @@ pub async fn sync_refund_with_gateway_workflow(
         enums::RefundStatus::Success,
         enums::RefundStatus::Failure,
         enums::RefundStatus::TransactionFailure,
+        enums::RefundStatus::AmountMismatch,
     ];
     match response.refund_status {
         status if terminal_status.contains(&status) => {
@@ -846,19 +1970,120 @@ pub async fn sync_refund_with_gateway_workflow(
                 .await?
         }
         _ => {
-            payment_sync::retry_sync_task(
-                &*state.store,
-                response.connector,
-                response.merchant_id,
-                refund_tracker.to_owned(),
-            )
-            .await?
+            let review_time = get_manual_review_time(&state.conf.refund, &response);
+            if review_time.map_or(false, |review_time| {
+                review_time <= common_utils::date_time::now()
+            }) {
+                move_refund_to_manual_review(state, &merchant_account, response).await?;
+                refund_tracker
+                    .clone()
+                    .finish_with_status(&*state.store, "MOVED_TO_MANUAL_REVIEW".to_string())
+                    .await?
+            } else {
+                let schedule_time = payment_sync::get_sync_process_schedule_time(
+                    &*state.store,
+                    &response.connector,
+                    &response.merchant_id,
+                    refund_tracker.retry_count,
+                )
+                .await?;
+
+                // Refunds which are moved to manual review once pending for too long are synced
+                // one last time at that point, even after the retries of the task are exhausted
+                match schedule_time
+                    .map(|schedule_time| {
+                        review_time
+                            .map_or(schedule_time, |review_time| schedule_time.min(review_time))
+                    })
+                    .or(review_time)
+                {
+                    Some(schedule_time) => {
+                        refund_tracker
+                            .clone()
+                            .retry(&*state.store, schedule_time)
+                            .await?
+                    }
+                    None => {
+                        refund_tracker
+                            .clone()
+                            .finish_with_status(&*state.store, "RETRIES_EXCEEDED".to_string())
+                            .await?
+                    }
+                }
+            }
         }
     }
 
     Ok(())
 }
 
+/// Returns the time at which a pending refund is moved to manual review, when a maximum pending
+/// duration is configured for its connector.
+fn get_manual_review_time(
+    refund_conf: &settings::Refund,
+    refund: &storage::Refund,
+) -> Option<time::PrimitiveDateTime> {
+    let max_pending_duration = refund_conf
+        .connector_max_pending_durations
+        .get(&refund.connector)
+        .copied()
+        .unwrap_or(refund_conf.max_pending_duration);
+
+    (refund.refund_status == enums::RefundStatus::Pending && max_pending_duration > 0)
+        .then(|| refund.created_at + time::Duration::seconds(max_pending_duration))
+}
+
+async fn move_refund_to_manual_review(
+    state: &AppState,
+    merchant_account: &domain::MerchantAccount,
+    refund: storage::Refund,
+) -> RouterResult<()> {
+    let refund_id = refund.refund_id.clone();
+    let sent_to_gateway = refund.sent_to_gateway;
+    // A refund resolved by an admin in the meantime is left as it is
+    let refund = match state
+        .store
+        .update_refund_if_status_in(
+            refund,
+            vec![enums::RefundStatus::Pending],
+            storage::RefundUpdate::StatusUpdate {
+                connector_refund_id: None,
+                sent_to_gateway,
+                refund_status: enums::RefundStatus::ManualReview,
+                settled_amount: None,
+            },
+            merchant_account.storage_scheme,
+        )
+        .await
+    {
+        Ok(refund) => refund,
+        Err(error) if error.current_context().is_db_not_found() => {
+            logger::info!(%refund_id, "Refund is no longer pending, not moved to manual review");
+            return Ok(());
+        }
+        Err(error) => Err(error)
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable_lazy(|| {
+                format!("Unable to move refund to manual review, refund_id: {refund_id}")
+            })?,
+    };
+
+    logger::warn!(
+        %refund_id,
+        connector = %refund.connector,
+        "Refund still pending with the connector, moved to manual review"
+    );
+    trigger_refund_outgoing_webhook(
+        state,
+        merchant_account,
+        enums::EventType::RefundManualReview,
+        &refund,
+    )
+    .await;
+
+    Ok(())
+}
+
 #[instrument(skip_all)]
 pub async fn start_refund_workflow(
     state: &AppState,
@@ -1022,6 +2247,7 @@ pub async fn add_refund_execute_task(
     db: &dyn db::StorageInterface,
     refund: &storage::Refund,
     runner: &str,
+    schedule_time: time::PrimitiveDateTime,
 ) -> RouterResult<storage::ProcessTracker> {
     let task = "EXECUTE_REFUND";
     let current_time = common_utils::date_time::now();
@@ -1035,7 +2261,7 @@ pub async fn add_refund_execute_task(
         tag: vec![String::from("REFUND")],
         runner: Some(String::from(runner)),
         retry_count: 0,
-        schedule_time: Some(common_utils::date_time::now()),
+        schedule_time: Some(schedule_time),
         rule: String::new(),
         tracking_data: refund_workflow_model,
         business_status: String::from("Pending"),
@@ -1103,3 +2329,288 @@ pub async fn retry_refund_sync_task(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+    use crate::db::{refund::RefundInterface, MockDb};
+
+    async fn insert_refund_pending_approval(db: &MockDb) -> storage::Refund {
+        db.insert_refund(
+            storage::RefundNew {
+                refund_id: "ref_1".to_string(),
+                merchant_id: "merchant_1".to_string(),
+                refund_status: enums::RefundStatus::PendingApproval,
+                ..Default::default()
+            },
+            enums::MerchantStorageScheme::PostgresOnly,
+        )
+        .await
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_refund_pending_approval_is_approved_once() {
+        let db = MockDb::new(&Default::default()).await;
+        let refund = insert_refund_pending_approval(&db).await;
+
+        let approved = transition_refund_pending_approval(
+            &db,
+            enums::MerchantStorageScheme::PostgresOnly,
+            refund.clone(),
+            enums::RefundStatus::Pending,
+        )
+        .await
+        .unwrap();
+        assert_eq!(approved.refund_status, enums::RefundStatus::Pending);
+
+        // A concurrent approval, which read the refund while it was pending approval, must not
+        // execute the refund again
+        let error = transition_refund_pending_approval(
+            &db,
+            enums::MerchantStorageScheme::PostgresOnly,
+            refund,
+            enums::RefundStatus::Pending,
+        )
+        .await
+        .unwrap_err();
+        assert!(matches!(
+            error.current_context(),
+            errors::ApiErrorResponse::PreconditionFailed { .. }
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_approved_refund_cannot_be_rejected() {
+        let db = MockDb::new(&Default::default()).await;
+        let refund = insert_refund_pending_approval(&db).await;
+
+        transition_refund_pending_approval(
+            &db,
+            enums::MerchantStorageScheme::PostgresOnly,
+            refund.clone(),
+            enums::RefundStatus::Pending,
+        )
+        .await
+        .unwrap();
+
+        assert!(transition_refund_pending_approval(
+            &db,
+            enums::MerchantStorageScheme::PostgresOnly,
+            refund,
+            enums::RefundStatus::Rejected,
+        )
+        .await
+        .is_err());
+        let refund = db
+            .find_refund_by_merchant_id_refund_id(
+                "merchant_1",
+                "ref_1",
+                enums::MerchantStorageScheme::PostgresOnly,
+            )
+            .await
+            .unwrap();
+        assert_eq!(refund.refund_status, enums::RefundStatus::Pending);
+    }
+
+    #[tokio::test]
+    async fn test_refund_amount_mismatch_is_kept_on_later_sync() {
+        let db = MockDb::new(&Default::default()).await;
+        let refund = db
+            .insert_refund(
+                storage::RefundNew {
+                    refund_id: "ref_1".to_string(),
+                    merchant_id: "merchant_1".to_string(),
+                    refund_status: enums::RefundStatus::Pending,
+                    refund_amount: 1000,
+                    ..Default::default()
+                },
+                enums::MerchantStorageScheme::PostgresOnly,
+            )
+            .await
+            .unwrap();
+
+        let refund_status =
+            get_reconciled_refund_status(&refund, enums::RefundStatus::Success, Some(900));
+        assert_eq!(refund_status, enums::RefundStatus::AmountMismatch);
+        let refund = db
+            .update_refund(
+                refund,
+                storage::RefundUpdate::StatusUpdate {
+                    connector_refund_id: None,
+                    sent_to_gateway: true,
+                    refund_status,
+                    settled_amount: Some(900),
+                },
+                enums::MerchantStorageScheme::PostgresOnly,
+            )
+            .await
+            .unwrap();
+
+        // The connector doesn't report the amount refunded when the refund is synced again
+        let refund_status =
+            get_reconciled_refund_status(&refund, enums::RefundStatus::Success, None);
+        assert_eq!(refund_status, enums::RefundStatus::AmountMismatch);
+        let refund = db
+            .update_refund(
+                refund,
+                storage::RefundUpdate::StatusUpdate {
+                    connector_refund_id: None,
+                    sent_to_gateway: true,
+                    refund_status,
+                    settled_amount: None,
+                },
+                enums::MerchantStorageScheme::PostgresOnly,
+            )
+            .await
+            .unwrap();
+        assert_eq!(refund.refund_status, enums::RefundStatus::AmountMismatch);
+        assert_eq!(refund.settled_amount, Some(900));
+    }
+
+    #[tokio::test]
+    async fn test_refund_settled_for_the_requested_amount_succeeds() {
+        let db = MockDb::new(&Default::default()).await;
+        let refund = db
+            .insert_refund(
+                storage::RefundNew {
+                    refund_id: "ref_1".to_string(),
+                    merchant_id: "merchant_1".to_string(),
+                    refund_status: enums::RefundStatus::Pending,
+                    refund_amount: 1000,
+                    ..Default::default()
+                },
+                enums::MerchantStorageScheme::PostgresOnly,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(
+            get_reconciled_refund_status(&refund, enums::RefundStatus::Success, Some(1000)),
+            enums::RefundStatus::Success
+        );
+        assert_eq!(
+            get_reconciled_refund_status(&refund, enums::RefundStatus::Success, None),
+            enums::RefundStatus::Success
+        );
+        assert_eq!(
+            get_reconciled_refund_status(&refund, enums::RefundStatus::Failure, Some(900)),
+            enums::RefundStatus::Failure
+        );
+    }
+
+    async fn insert_refund_with_status(
+        db: &MockDb,
+        refund_status: enums::RefundStatus,
+    ) -> storage::Refund {
+        db.insert_refund(
+            storage::RefundNew {
+                refund_id: "ref_1".to_string(),
+                merchant_id: "merchant_1".to_string(),
+                connector: "stripe".to_string(),
+                refund_status,
+                ..Default::default()
+            },
+            enums::MerchantStorageScheme::PostgresOnly,
+        )
+        .await
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_manual_review_time_uses_the_max_pending_duration() {
+        let db = MockDb::new(&Default::default()).await;
+        let refund = insert_refund_with_status(&db, enums::RefundStatus::Pending).await;
+        let refund_conf = settings::Refund {
+            max_pending_duration: 3600,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            get_manual_review_time(&refund_conf, &refund),
+            Some(refund.created_at + time::Duration::seconds(3600))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_manual_review_time_uses_the_duration_of_the_connector() {
+        let db = MockDb::new(&Default::default()).await;
+        let refund = insert_refund_with_status(&db, enums::RefundStatus::Pending).await;
+        let refund_conf = settings::Refund {
+            max_pending_duration: 3600,
+            connector_max_pending_durations: [("stripe".to_string(), 60)].into_iter().collect(),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            get_manual_review_time(&refund_conf, &refund),
+            Some(refund.created_at + time::Duration::seconds(60))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_no_manual_review_time_when_disabled() {
+        let db = MockDb::new(&Default::default()).await;
+        let refund = insert_refund_with_status(&db, enums::RefundStatus::Pending).await;
+
+        assert_eq!(get_manual_review_time(&Default::default(), &refund), None);
+
+        // A connector override of 0 disables the manual review for that connector only
+        let refund_conf = settings::Refund {
+            max_pending_duration: 3600,
+            connector_max_pending_durations: [("stripe".to_string(), 0)].into_iter().collect(),
+            ..Default::default()
+        };
+        assert_eq!(get_manual_review_time(&refund_conf, &refund), None);
+    }
+
+    #[tokio::test]
+    async fn test_no_manual_review_time_for_refunds_not_pending() {
+        let db = MockDb::new(&Default::default()).await;
+        let refund = insert_refund_with_status(&db, enums::RefundStatus::ManualReview).await;
+        let refund_conf = settings::Refund {
+            max_pending_duration: 3600,
+            ..Default::default()
+        };
+
+        assert_eq!(get_manual_review_time(&refund_conf, &refund), None);
+    }
+
+    #[tokio::test]
+    async fn test_refund_resolved_concurrently_is_not_overwritten() {
+        let db = MockDb::new(&Default::default()).await;
+        let refund = insert_refund_with_status(&db, enums::RefundStatus::ManualReview).await;
+
+        db.update_refund_if_status_in(
+            refund.clone(),
+            vec![enums::RefundStatus::ManualReview],
+            storage::RefundUpdate::StatusUpdate {
+                connector_refund_id: None,
+                sent_to_gateway: true,
+                refund_status: enums::RefundStatus::Success,
+                settled_amount: None,
+            },
+            enums::MerchantStorageScheme::PostgresOnly,
+        )
+        .await
+        .unwrap();
+
+        // A resolution based on the stale read of the refund must not apply
+        let error = db
+            .update_refund_if_status_in(
+                refund,
+                vec![enums::RefundStatus::ManualReview],
+                storage::RefundUpdate::StatusUpdate {
+                    connector_refund_id: None,
+                    sent_to_gateway: true,
+                    refund_status: enums::RefundStatus::Failure,
+                    settled_amount: None,
+                },
+                enums::MerchantStorageScheme::PostgresOnly,
+            )
+            .await
+            .unwrap_err();
+        assert!(error.current_context().is_db_not_found());
+    }
+}
@@ -27,6 +27,8 @@ pub enum RefundValidationError {
     MaxRefundCountReached,
     #[error("There is already another refund request for this payment attempt")]
     DuplicateRefund,
+    #[error("The refund cannot be scheduled at the requested time")]
+    InvalidScheduleTime,
 }
 
 #[instrument(skip_all)]
@@ -51,6 +53,8 @@ pub fn validate_refund_amount(
         .filter_map(|refund| {
             if refund.refund_status != enums::RefundStatus::Failure
                 && refund.refund_status != enums::RefundStatus::TransactionFailure
+                && refund.refund_status != enums::RefundStatus::Cancelled
+                && refund.refund_status != enums::RefundStatus::Rejected
             {
                 Some(refund.refund_amount)
             } else {
@@ -82,6 +86,23 @@ pub fn validate_payment_order_age(
     )
 }
 
+/// Validates that a refund is scheduled in the future, and before the payment is too old to be
+/// refunded.
+#[instrument(skip_all)]
+pub fn validate_refund_schedule_time(
+    schedule_at: &PrimitiveDateTime,
+    payment_created_at: &PrimitiveDateTime,
+    refund_max_age: i64,
+) -> CustomResult<(), RefundValidationError> {
+    let current_time = common_utils::date_time::now();
+
+    utils::when(
+        *schedule_at <= current_time
+            || (*schedule_at - *payment_created_at).whole_days() > refund_max_age,
+        || Err(report!(RefundValidationError::InvalidScheduleTime)),
+    )
+}
+
 #[instrument(skip_all)]
 pub fn validate_maximum_refund_against_payment_attempt(
     all_refunds: &[storage::Refund],
@@ -127,6 +148,49 @@ pub async fn validate_uniqueness_of_refund_id_against_merchant_id(
     }
 }
 
+/// Finds the refund previously created with the merchant refund reference, if any. A refund
+/// created for the same payment and amount is returned so that the request can be retried
+/// safely, while a refund created for a different payment or amount is a conflict.
+#[instrument(skip(db))]
+pub async fn validate_merchant_refund_reference(
+    db: &dyn StorageInterface,
+    payment_id: &str,
+    merchant_id: &str,
+    merchant_refund_reference: &str,
+    refund_amount: i64,
+    storage_scheme: enums::MerchantStorageScheme,
+) -> RouterResult<Option<storage::Refund>> {
+    let refund = match db
+        .find_refund_by_merchant_id_merchant_refund_reference(
+            merchant_id,
+            merchant_refund_reference,
+            storage_scheme,
+        )
+        .await
+    {
+        Ok(refund) => refund,
+        Err(err) if err.current_context().is_db_not_found() => return Ok(None),
+        Err(err) => {
+            return Err(err
+                .change_context(errors::ApiErrorResponse::InternalServerError)
+                .attach_printable("Failed while finding refund by merchant refund reference"))
+        }
+    };
+
+    utils::when(
+        refund.payment_id != payment_id || refund.refund_amount != refund_amount,
+        || {
+            Err(report!(
+                errors::ApiErrorResponse::DuplicateMerchantRefundReference {
+                    merchant_refund_reference: merchant_refund_reference.to_string(),
+                }
+            ))
+        },
+    )?;
+
+    Ok(Some(refund))
+}
+
 pub fn validate_refund_list(limit: Option<i64>) -> CustomResult<i64, errors::ApiErrorResponse> {
     match limit {
         Some(limit_val) => {
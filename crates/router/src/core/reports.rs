@@ -0,0 +1,1065 @@
+use std::collections::BTreeMap;
+
+use api_models::disputes::DisputeListConstraints;
+use common_utils::date_time;
+use error_stack::{report, IntoReport, ResultExt};
+use router_env::{instrument, logger, tracing};
+
+use crate::{
+    consts,
+    core::{
+        accounting::escape_csv_field,
+        errors::{self, RouterResponse, RouterResult, StorageErrorExt},
+        files::helpers as file_helpers,
+        webhooks,
+    },
+    db::StorageInterface,
+    routes::AppState,
+    scheduler::utils as pt_utils,
+    services::ApplicationResponse,
+    types::{
+        api::{self, reports as report_types},
+        domain,
+        storage::{self, enums, ProcessTrackerExt},
+    },
+    utils::{self, Encode, ValueExt},
+};
+
+const REPORT_GENERATION_RUNNER: &str = "REPORT_GENERATION_WORKFLOW";
+const REPORT_GENERATION_TASK: &str = "REPORT_GENERATION";
+
+/// Columns of a `payments_summary` report, in the order in which they are generated.
+const PAYMENTS_SUMMARY_COLUMNS: [&str; 7] = [
+    "currency",
+    "connector",
+    "channel",
+    "status",
+    "payment_count",
+    "total_amount",
+    "captured_amount",
+];
+
+/// Columns of a `disputes` report, in the order in which they are generated.
+const DISPUTES_COLUMNS: [&str; 9] = [
+    "dispute_id",
+    "payment_id",
+    "connector",
+    "dispute_stage",
+    "dispute_status",
+    "amount",
+    "currency",
+    "reason",
+    "created_at",
+];
+
+fn get_report_columns(report_type: enums::ReportType) -> &'static [&'static str] {
+    match report_type {
+        enums::ReportType::PaymentsSummary => &PAYMENTS_SUMMARY_COLUMNS,
+        enums::ReportType::Disputes => &DISPUTES_COLUMNS,
+    }
+}
+
+fn get_report_period(frequency: enums::ReportFrequency) -> time::Duration {
+    match frequency {
+        enums::ReportFrequency::Daily => time::Duration::days(1),
+        enums::ReportFrequency::Weekly => time::Duration::weeks(1),
+    }
+}
+
+/// Returns the first time after `after` at which a report of the given frequency is generated.
+/// Daily reports are generated at midnight UTC and weekly reports at midnight UTC on Mondays, so
+/// that each report covers whole days.
+fn get_next_run_at(
+    frequency: enums::ReportFrequency,
+    after: time::PrimitiveDateTime,
+) -> time::PrimitiveDateTime {
+    let days = match frequency {
+        enums::ReportFrequency::Daily => 1,
+        enums::ReportFrequency::Weekly => 7 - i64::from(after.weekday().number_days_from_monday()),
+    };
+    after
+        .replace_time(time::Time::MIDNIGHT)
+        .saturating_add(time::Duration::days(days))
+}
+
+fn get_process_tracker_id(report_schedule_id: &str, merchant_id: &str) -> String {
+    pt_utils::get_process_tracker_id(
+        REPORT_GENERATION_RUNNER,
+        REPORT_GENERATION_TASK,
+        report_schedule_id,
+        merchant_id,
+    )
+}
+
+fn validate_template(
+    report_type: enums::ReportType,
+    template: &report_types::ReportTemplate,
+) -> RouterResult<()> {
+    let available_columns = get_report_columns(report_type);
+    if let Some(columns) = &template.columns {
+        utils::when(columns.is_empty(), || {
+            Err(report!(errors::ApiErrorResponse::InvalidRequestData {
+                message: "The template of a report must include at least one column".to_string(),
+            }))
+        })?;
+        if let Some(column) = columns
+            .iter()
+            .find(|column| !available_columns.contains(&column.as_str()))
+        {
+            Err(errors::ApiErrorResponse::InvalidRequestData {
+                message: format!(
+                    "`{column}` is not a column of {report_type} reports, the available columns are: {}",
+                    available_columns.join(", ")
+                ),
+            })?
+        }
+    }
+    Ok(())
+}
+
+fn validate_delivery(
+    delivery_method: enums::ReportDeliveryMethod,
+    recipient_email: Option<&common_utils::pii::Email>,
+) -> RouterResult<()> {
+    match delivery_method {
+        enums::ReportDeliveryMethod::Webhook => Ok(()),
+        #[cfg(feature = "email")]
+        enums::ReportDeliveryMethod::Email => recipient_email
+            .map(|_| ())
+            .ok_or(errors::ApiErrorResponse::MissingRequiredField {
+                field_name: "recipient_email",
+            })
+            .into_report(),
+        #[cfg(not(feature = "email"))]
+        enums::ReportDeliveryMethod::Email => {
+            let _ = recipient_email;
+            Err(errors::ApiErrorResponse::NotSupported {
+                message: "Delivering reports by email".to_string(),
+            })
+            .into_report()
+        }
+    }
+}
+
+fn get_report_schedule_response(
+    report_schedule: storage::ReportSchedule,
+) -> RouterResult<report_types::ReportScheduleResponse> {
+    let template = report_schedule
+        .template
+        .parse_value("ReportTemplate")
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to deserialize the template of the report schedule")?;
+    let filters = report_schedule
+        .filters
+        .map(|filters| filters.parse_value("ReportFilters"))
+        .transpose()
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to deserialize the filters of the report schedule")?;
+
+    Ok(report_types::ReportScheduleResponse {
+        report_schedule_id: report_schedule.report_schedule_id,
+        merchant_id: report_schedule.merchant_id,
+        report_type: report_schedule.report_type,
+        frequency: report_schedule.frequency,
+        template,
+        filters,
+        delivery_method: report_schedule.delivery_method,
+        recipient_email: report_schedule.recipient_email,
+        is_active: report_schedule.is_active,
+        next_run_at: report_schedule.next_run_at,
+        last_generated_at: report_schedule.last_generated_at,
+        last_file_id: report_schedule.last_file_id,
+        created: report_schedule.created_at,
+    })
+}
+
+async fn find_report_schedule(
+    db: &dyn StorageInterface,
+    merchant_id: &str,
+    report_schedule_id: &str,
+) -> RouterResult<storage::ReportSchedule> {
+    db.find_report_schedule_by_merchant_id_report_schedule_id(merchant_id, report_schedule_id)
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::ReportScheduleNotFound {
+            report_schedule_id: report_schedule_id.to_owned(),
+        })
+}
+
+async fn add_report_generation_task(
+    db: &dyn StorageInterface,
+    report_schedule: &storage::ReportSchedule,
+) -> RouterResult<()> {
+    let tracking_data = storage::ReportGenerationWorkflow {
+        report_schedule_id: report_schedule.report_schedule_id.clone(),
+        merchant_id: report_schedule.merchant_id.clone(),
+    };
+    let process_tracker_entry = <storage::ProcessTracker>::make_process_tracker_new(
+        get_process_tracker_id(
+            &report_schedule.report_schedule_id,
+            &report_schedule.merchant_id,
+        ),
+        REPORT_GENERATION_TASK,
+        REPORT_GENERATION_RUNNER,
+        tracking_data,
+        report_schedule.next_run_at,
+    )
+    .change_context(errors::ApiErrorResponse::InternalServerError)?;
+
+    db.insert_process(process_tracker_entry)
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to insert the report generation task to process tracker")?;
+    Ok(())
+}
+
+/// Schedules the generation of a paused report again, by resetting its task in the process
+/// tracker to the next run of the report.
+async fn resume_report_generation_task(
+    db: &dyn StorageInterface,
+    report_schedule: &storage::ReportSchedule,
+) -> RouterResult<()> {
+    let process = db
+        .find_process_by_id(&get_process_tracker_id(
+            &report_schedule.report_schedule_id,
+            &report_schedule.merchant_id,
+        ))
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to retrieve the report generation task from process tracker")?;
+
+    match process {
+        Some(process) => process
+            .reset(db, report_schedule.next_run_at)
+            .await
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Failed to reschedule the report generation task"),
+        None => add_report_generation_task(db, report_schedule).await,
+    }
+}
+
+async fn revoke_report_generation_task(
+    db: &dyn StorageInterface,
+    report_schedule: &storage::ReportSchedule,
+    business_status: &str,
+) -> RouterResult<()> {
+    db.process_tracker_update_process_status_by_ids(
+        vec![get_process_tracker_id(
+            &report_schedule.report_schedule_id,
+            &report_schedule.merchant_id,
+        )],
+        storage::ProcessTrackerUpdate::StatusUpdate {
+            status: enums::ProcessTrackerStatus::Finish,
+            business_status: Some(business_status.to_string()),
+        },
+    )
+    .await
+    .change_context(errors::ApiErrorResponse::InternalServerError)
+    .attach_printable("Failed to revoke the report generation task in process tracker")?;
+    Ok(())
+}
+
+#[instrument(skip(state))]
+pub async fn create_report_schedule(
+    state: &AppState,
+    merchant_account: domain::MerchantAccount,
+    req: report_types::ReportScheduleCreateRequest,
+) -> RouterResponse<report_types::ReportScheduleResponse> {
+    let template = req.template.unwrap_or_default();
+    validate_template(req.report_type, &template)?;
+    validate_delivery(req.delivery_method, req.recipient_email.as_ref())?;
+
+    let now = date_time::now();
+    let report_schedule_new = storage::ReportScheduleNew {
+        report_schedule_id: common_utils::generate_id(consts::ID_LENGTH, "rps"),
+        merchant_id: merchant_account.merchant_id,
+        report_type: req.report_type,
+        frequency: req.frequency,
+        template: Encode::<report_types::ReportTemplate>::encode_to_value(&template)
+            .change_context(errors::ApiErrorResponse::InternalServerError)?,
+        filters: req
+            .filters
+            .as_ref()
+            .map(Encode::<report_types::ReportFilters>::encode_to_value)
+            .transpose()
+            .change_context(errors::ApiErrorResponse::InternalServerError)?,
+        delivery_method: req.delivery_method,
+        recipient_email: req.recipient_email,
+        is_active: true,
+        next_run_at: get_next_run_at(req.frequency, now),
+        created_at: now,
+        modified_at: now,
+    };
+
+    let db = &*state.store;
+    let report_schedule = db
+        .insert_report_schedule(report_schedule_new)
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to insert the report schedule")?;
+    add_report_generation_task(db, &report_schedule).await?;
+
+    Ok(ApplicationResponse::Json(get_report_schedule_response(
+        report_schedule,
+    )?))
+}
+
+#[instrument(skip(state))]
+pub async fn retrieve_report_schedule(
+    state: &AppState,
+    merchant_account: domain::MerchantAccount,
+    report_schedule_id: String,
+) -> RouterResponse<report_types::ReportScheduleResponse> {
+    let report_schedule = find_report_schedule(
+        &*state.store,
+        &merchant_account.merchant_id,
+        &report_schedule_id,
+    )
+    .await?;
+    Ok(ApplicationResponse::Json(get_report_schedule_response(
+        report_schedule,
+    )?))
+}
+
+#[instrument(skip(state))]
+pub async fn list_report_schedules(
+    state: &AppState,
+    merchant_account: domain::MerchantAccount,
+    constraints: report_types::ReportScheduleListConstraints,
+) -> RouterResponse<Vec<report_types::ReportScheduleResponse>> {
+    let report_schedules = state
+        .store
+        .list_report_schedules_by_merchant_id(
+            &merchant_account.merchant_id,
+            constraints.limit,
+            constraints.skip,
+        )
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to list the report schedules of the merchant")?;
+
+    Ok(ApplicationResponse::Json(
+        report_schedules
+            .into_iter()
+            .map(get_report_schedule_response)
+            .collect::<RouterResult<_>>()?,
+    ))
+}
+
+#[instrument(skip(state))]
+pub async fn update_report_schedule(
+    state: &AppState,
+    merchant_account: domain::MerchantAccount,
+    report_schedule_id: String,
+    req: report_types::ReportScheduleUpdateRequest,
+) -> RouterResponse<report_types::ReportScheduleResponse> {
+    let db = &*state.store;
+    let report_schedule =
+        find_report_schedule(db, &merchant_account.merchant_id, &report_schedule_id).await?;
+
+    if let Some(template) = &req.template {
+        validate_template(report_schedule.report_type, template)?;
+    }
+    validate_delivery(
+        req.delivery_method
+            .unwrap_or(report_schedule.delivery_method),
+        req.recipient_email
+            .as_ref()
+            .or(report_schedule.recipient_email.as_ref()),
+    )?;
+
+    let is_resumed = req.is_active == Some(true) && !report_schedule.is_active;
+    let is_paused = req.is_active == Some(false) && report_schedule.is_active;
+    // Reports missed while the schedule was paused are not generated
+    let next_run_at =
+        is_resumed.then(|| get_next_run_at(report_schedule.frequency, date_time::now()));
+
+    let report_schedule_update = storage::ReportScheduleUpdate::Update {
+        template: req
+            .template
+            .as_ref()
+            .map(Encode::<report_types::ReportTemplate>::encode_to_value)
+            .transpose()
+            .change_context(errors::ApiErrorResponse::InternalServerError)?,
+        filters: req
+            .filters
+            .as_ref()
+            .map(Encode::<report_types::ReportFilters>::encode_to_value)
+            .transpose()
+            .change_context(errors::ApiErrorResponse::InternalServerError)?,
+        delivery_method: req.delivery_method,
+        recipient_email: req.recipient_email,
+        is_active: req.is_active,
+        next_run_at,
+    };
+    let report_schedule = db
+        .update_report_schedule_by_merchant_id_report_schedule_id(
+            &merchant_account.merchant_id,
+            &report_schedule_id,
+            report_schedule_update,
+        )
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::ReportScheduleNotFound {
+            report_schedule_id: report_schedule_id.clone(),
+        })?;
+
+    if is_resumed {
+        resume_report_generation_task(db, &report_schedule).await?;
+    } else if is_paused {
+        revoke_report_generation_task(db, &report_schedule, "REPORT_SCHEDULE_PAUSED").await?;
+    }
+
+    Ok(ApplicationResponse::Json(get_report_schedule_response(
+        report_schedule,
+    )?))
+}
+
+#[instrument(skip(state))]
+pub async fn delete_report_schedule(
+    state: &AppState,
+    merchant_account: domain::MerchantAccount,
+    report_schedule_id: String,
+) -> RouterResponse<report_types::ReportScheduleDeleteResponse> {
+    let db = &*state.store;
+    let report_schedule =
+        find_report_schedule(db, &merchant_account.merchant_id, &report_schedule_id).await?;
+
+    let deleted = db
+        .delete_report_schedule_by_merchant_id_report_schedule_id(
+            &merchant_account.merchant_id,
+            &report_schedule_id,
+        )
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::ReportScheduleNotFound {
+            report_schedule_id: report_schedule_id.clone(),
+        })?;
+    revoke_report_generation_task(db, &report_schedule, "REPORT_SCHEDULE_DELETED").await?;
+
+    Ok(ApplicationResponse::Json(
+        report_types::ReportScheduleDeleteResponse {
+            report_schedule_id,
+            deleted,
+        },
+    ))
+}
+
+fn format_time(time: time::PrimitiveDateTime) -> RouterResult<String> {
+    time.assume_utc()
+        .format(&time::format_description::well_known::Rfc3339)
+        .into_report()
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to format a time in the report")
+}
+
+/// The (currency, connector, channel, status) of the payments summed up in a row of a
+/// `payments_summary` report.
+type PaymentsSummaryKey = (String, String, String, String);
+
+/// The channel through which a payment was made: `in_store` for card-present payments made on a
+/// terminal, and `online` for every other payment, so that both are reported together.
+fn get_payment_channel(payment_method_data: Option<&serde_json::Value>) -> &'static str {
+    let is_card_present = payment_method_data
+        .and_then(serde_json::Value::as_object)
+        .map_or(false, |payment_method_data| {
+            payment_method_data.contains_key("card_present")
+        });
+
+    if is_card_present {
+        "in_store"
+    } else {
+        "online"
+    }
+}
+
+/// Counts a payment and adds up its amounts in the summary row of its (currency, connector,
+/// channel, status).
+fn add_payment_to_summary(
+    summary: &mut BTreeMap<PaymentsSummaryKey, (u64, i64, i64)>,
+    key: PaymentsSummaryKey,
+    amount: i64,
+    amount_captured: i64,
+) -> RouterResult<()> {
+    let (payment_count, total_amount, captured_amount) = summary.entry(key).or_insert((0, 0, 0));
+    *payment_count = payment_count
+        .checked_add(1)
+        .ok_or(errors::ApiErrorResponse::InternalServerError)
+        .into_report()
+        .attach_printable("Payment count overflowed in the payments summary")?;
+    *total_amount = total_amount
+        .checked_add(amount)
+        .ok_or(errors::ApiErrorResponse::InternalServerError)
+        .into_report()
+        .attach_printable("Total amount overflowed in the payments summary")?;
+    *captured_amount = captured_amount
+        .checked_add(amount_captured)
+        .ok_or(errors::ApiErrorResponse::InternalServerError)
+        .into_report()
+        .attach_printable("Captured amount overflowed in the payments summary")?;
+    Ok(())
+}
+
+/// Summarizes all the payments created in `[start_time, end_time)`, so that a payment created
+/// exactly at the end of a period is only counted in the following report.
+async fn get_payments_summary_rows(
+    state: &AppState,
+    merchant_account: &domain::MerchantAccount,
+    filters: &report_types::ReportFilters,
+    start_time: time::PrimitiveDateTime,
+    end_time: time::PrimitiveDateTime,
+) -> RouterResult<Vec<Vec<String>>> {
+    const PAGE_LIMIT: u32 = 100;
+
+    // Payments are counted and summed up per (currency, connector, channel, status)
+    let mut summary: BTreeMap<PaymentsSummaryKey, (u64, i64, i64)> = BTreeMap::new();
+    let mut starting_after = None;
+    loop {
+        let constraints = api::PaymentListFilterConstraints {
+            payment_id: None,
+            limit: Some(PAGE_LIMIT),
+            starting_after: starting_after.take(),
+            offset: None,
+            time_range: Some(api::TimeRange {
+                start_time,
+                end_time: Some(end_time),
+            }),
+            connector: filters.connector.clone(),
+            currency: filters.currency.clone(),
+            status: None,
+            payment_methods: None,
+            payment_method_types: None,
+            amount: None,
+            customer_email: None,
+            status_groups: None,
+        };
+        let payments = state
+            .store
+            .apply_filters_on_payments_list(
+                &merchant_account.merchant_id,
+                &constraints,
+                None,
+                merchant_account.storage_scheme,
+            )
+            .await
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Failed to retrieve payments for the report")?;
+        let is_last_page = u32::try_from(payments.len()).map_or(false, |count| count < PAGE_LIMIT);
+        starting_after = payments
+            .last()
+            .map(|(payment_intent, _)| payment_intent.payment_id.clone());
+
+        // The time range of the payments list includes its end, which belongs to the next period
+        for (payment_intent, payment_attempt) in payments
+            .into_iter()
+            .filter(|(payment_intent, _)| payment_intent.created_at < end_time)
+        {
+            let channel =
+                get_payment_channel(payment_attempt.payment_method_data.as_ref()).to_string();
+            let key = (
+                payment_intent
+                    .currency
+                    .map(|currency| currency.to_string())
+                    .unwrap_or_default(),
+                payment_attempt.connector.unwrap_or_default(),
+                channel,
+                payment_intent.status.to_string(),
+            );
+            add_payment_to_summary(
+                &mut summary,
+                key,
+                payment_intent.amount,
+                payment_intent.amount_captured.unwrap_or_default(),
+            )?;
+        }
+
+        if is_last_page || starting_after.is_none() {
+            break;
+        }
+    }
+
+    Ok(summary
+        .into_iter()
+        .map(
+            |(
+                (currency, connector, channel, status),
+                (payment_count, total_amount, captured_amount),
+            )| {
+                vec![
+                    currency,
+                    connector,
+                    channel,
+                    status,
+                    payment_count.to_string(),
+                    total_amount.to_string(),
+                    captured_amount.to_string(),
+                ]
+            },
+        )
+        .collect())
+}
+
+/// Lists the disputes received in `[start_time, end_time)`, along with whether the period has more
+/// disputes than can be included in a report.
+async fn get_disputes_rows(
+    state: &AppState,
+    merchant_account: &domain::MerchantAccount,
+    filters: &report_types::ReportFilters,
+    start_time: time::PrimitiveDateTime,
+    end_time: time::PrimitiveDateTime,
+) -> RouterResult<(Vec<Vec<String>>, bool)> {
+    // One more dispute than can be included is fetched to find out whether the report is truncated
+    let dispute_constraints = DisputeListConstraints {
+        limit: Some(consts::REPORT_RECORDS_LIMIT.saturating_add(1)),
+        dispute_status: None,
+        dispute_stage: None,
+        reason: None,
+        connector: None,
+        received_time: None,
+        received_time_lt: Some(end_time),
+        received_time_gt: None,
+        received_time_lte: None,
+        received_time_gte: Some(start_time),
+    };
+    let disputes = state
+        .store
+        .find_disputes_by_merchant_id(&merchant_account.merchant_id, dispute_constraints)
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to retrieve disputes for the report")?;
+    let is_truncated =
+        i64::try_from(disputes.len()).map_or(true, |count| count > consts::REPORT_RECORDS_LIMIT);
+    if is_truncated {
+        logger::warn!(
+            "Only the first {} disputes of the period are included in the report",
+            consts::REPORT_RECORDS_LIMIT
+        );
+    }
+
+    let rows = disputes
+        .into_iter()
+        .take(usize::try_from(consts::REPORT_RECORDS_LIMIT).unwrap_or(usize::MAX))
+        .filter(|dispute| {
+            filters
+                .connector
+                .as_ref()
+                .map_or(true, |connectors| connectors.contains(&dispute.connector))
+                && filters.currency.as_ref().map_or(true, |currencies| {
+                    currencies
+                        .iter()
+                        .any(|currency| currency.to_string() == dispute.currency)
+                })
+        })
+        .map(|dispute| {
+            Ok(vec![
+                dispute.dispute_id,
+                dispute.payment_id,
+                dispute.connector,
+                dispute.dispute_stage.to_string(),
+                dispute.dispute_status.to_string(),
+                dispute.amount,
+                dispute.currency,
+                dispute.connector_reason.unwrap_or_default(),
+                format_time(dispute.created_at)?,
+            ])
+        })
+        .collect::<RouterResult<_>>()?;
+
+    Ok((rows, is_truncated))
+}
+
+/// Renders the rows of a report in the format and with the columns of its template.
+fn render_report(
+    report_type: enums::ReportType,
+    template: &report_types::ReportTemplate,
+    rows: &[Vec<String>],
+) -> RouterResult<(Vec<u8>, mime::Mime)> {
+    let available_columns = get_report_columns(report_type);
+    let column_indices: Vec<(&str, usize)> = match &template.columns {
+        Some(columns) => columns
+            .iter()
+            .filter_map(|column| {
+                available_columns
+                    .iter()
+                    .position(|available_column| available_column == column)
+                    .map(|index| (column.as_str(), index))
+            })
+            .collect(),
+        None => available_columns
+            .iter()
+            .enumerate()
+            .map(|(index, column)| (*column, index))
+            .collect(),
+    };
+    let get_field = |row: &Vec<String>, index: usize| row.get(index).cloned().unwrap_or_default();
+
+    match template.format {
+        report_types::ReportFormat::Csv => {
+            let mut csv = column_indices
+                .iter()
+                .map(|(column, _)| *column)
+                .collect::<Vec<_>>()
+                .join(",");
+            csv.push('\n');
+            for row in rows {
+                let fields = column_indices
+                    .iter()
+                    .map(|(_, index)| escape_csv_field(&get_field(row, *index)))
+                    .collect::<Vec<_>>();
+                csv.push_str(&fields.join(","));
+                csv.push('\n');
+            }
+            Ok((csv.into_bytes(), mime::TEXT_CSV))
+        }
+        report_types::ReportFormat::Json => {
+            let records = rows
+                .iter()
+                .map(|row| {
+                    column_indices
+                        .iter()
+                        .map(|(column, index)| {
+                            (
+                                (*column).to_string(),
+                                serde_json::Value::String(get_field(row, *index)),
+                            )
+                        })
+                        .collect::<serde_json::Map<_, _>>()
+                })
+                .collect::<Vec<_>>();
+            let report = serde_json::to_vec(&records)
+                .into_report()
+                .change_context(errors::ApiErrorResponse::InternalServerError)
+                .attach_printable("Failed to serialize the report")?;
+            Ok((report, mime::APPLICATION_JSON))
+        }
+    }
+}
+
+/// Stores a generated report in the file storage of the router, so that it can be downloaded
+/// through the files API.
+async fn store_report_file(
+    state: &AppState,
+    merchant_id: &str,
+    file_name: String,
+    report: Vec<u8>,
+    content_type: mime::Mime,
+) -> RouterResult<String> {
+    let file_id = common_utils::generate_id(consts::ID_LENGTH, "file");
+    #[cfg(feature = "s3")]
+    let file_key = format!("{merchant_id}/{file_id}");
+    #[cfg(not(feature = "s3"))]
+    let file_key = format!("{merchant_id}_{file_id}");
+    let file_size = i32::try_from(report.len())
+        .into_report()
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("The report is too large to be stored")?;
+
+    file_helpers::upload_file(
+        #[cfg(feature = "s3")]
+        state,
+        file_key.clone(),
+        report,
+    )
+    .await
+    .attach_printable("Failed to upload the report to the file storage")?;
+
+    let file_new = storage::FileMetadataNew {
+        file_id: file_id.clone(),
+        merchant_id: merchant_id.to_owned(),
+        file_name: Some(file_name),
+        file_size,
+        file_type: content_type.to_string(),
+        provider_file_id: Some(file_key),
+        file_upload_provider: Some(enums::FileUploadProvider::Router),
+        available: true,
+        connector_label: None,
+    };
+    state
+        .store
+        .insert_file_metadata(file_new)
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Unable to insert file_metadata of the report")?;
+    Ok(file_id)
+}
+
+async fn send_report_webhook(
+    state: &AppState,
+    merchant_account: domain::MerchantAccount,
+    generated_report: report_types::GeneratedReportResponse,
+) -> RouterResult<()> {
+    let event_type = enums::EventType::ReportGenerated;
+    let new_event = storage::EventNew {
+        event_id: format!("{}_{event_type}", generated_report.file_id),
+        event_type,
+        event_class: enums::EventClass::Reports,
+        is_webhook_notified: false,
+        intent_reference_id: None,
+        primary_object_id: generated_report.file_id.clone(),
+        primary_object_type: enums::EventObjectType::ReportDetails,
+    };
+    let event = state
+        .store
+        .insert_event(new_event)
+        .await
+        .change_context(errors::ApiErrorResponse::WebhookProcessingFailure)
+        .attach_printable("Failed to insert event in events table")?;
+
+    if state.conf.webhooks.outgoing_enabled {
+        let outgoing_webhook = api::OutgoingWebhook {
+            merchant_id: merchant_account.merchant_id.clone(),
+            event_id: event.event_id,
+            event_type: event.event_type,
+            content: api::OutgoingWebhookContent::ReportDetails(Box::new(generated_report)),
+            timestamp: event.created_at,
+            source_validation: None,
+        };
+        // The scheduler does not run on an actix arbiter, so the webhook is sent inline rather
+        // than spawned as in `create_event_and_trigger_outgoing_webhook`
+        webhooks::trigger_webhook_to_merchant::<api::OutgoingWebhook>(
+            merchant_account,
+            outgoing_webhook,
+            state,
+        )
+        .await
+        .change_context(errors::ApiErrorResponse::WebhookProcessingFailure)?;
+    }
+    Ok(())
+}
+
+#[cfg(feature = "email")]
+async fn send_report_email(
+    state: &AppState,
+    recipient_email: Option<common_utils::pii::Email>,
+    generated_report: &report_types::GeneratedReportResponse,
+) -> RouterResult<()> {
+    let recipient_email =
+        recipient_email.ok_or(errors::ApiErrorResponse::MissingRequiredField {
+            field_name: "recipient_email",
+        })?;
+    let period_start = format_time(generated_report.period_start)?;
+    let period_end = format_time(generated_report.period_end)?;
+
+    state
+        .email_client
+        .clone()
+        .send_email(
+            recipient_email,
+            format!("Your {} report is ready", generated_report.report_type),
+            format!("Dear Merchant,\n
+Your {} report for the period from {period_start} to {period_end} has been generated. It can be downloaded from {} using your API key.\n\n
+Thanks,\n
+Team Hyperswitch", generated_report.report_type, generated_report.file_url),
+        )
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to send the report email")?;
+    Ok(())
+}
+
+/// Generates the report of a schedule for the period ending at its `next_run_at`, stores it and
+/// delivers it to the merchant. Returns the time of the next run of the report, or `None` if the
+/// schedule no longer exists or has been paused.
+#[instrument(skip(state))]
+pub async fn generate_scheduled_report(
+    state: &AppState,
+    tracking_data: &storage::ReportGenerationWorkflow,
+) -> RouterResult<Option<time::PrimitiveDateTime>> {
+    let db = &*state.store;
+    let report_schedule = match db
+        .find_report_schedule_by_merchant_id_report_schedule_id(
+            &tracking_data.merchant_id,
+            &tracking_data.report_schedule_id,
+        )
+        .await
+    {
+        Ok(report_schedule) if report_schedule.is_active => report_schedule,
+        Ok(_) => return Ok(None),
+        Err(error) if error.current_context().is_db_not_found() => return Ok(None),
+        Err(error) => Err(error)
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Failed to retrieve the report schedule")?,
+    };
+
+    let key_store = db
+        .get_merchant_key_store_by_merchant_id(
+            &report_schedule.merchant_id,
+            &db.get_master_key().to_vec().into(),
+        )
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::MerchantAccountNotFound)?;
+    let merchant_account = db
+        .find_merchant_account_by_merchant_id(&report_schedule.merchant_id, &key_store)
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::MerchantAccountNotFound)?;
+
+    let period_end = report_schedule.next_run_at;
+    let period_start = period_end.saturating_sub(get_report_period(report_schedule.frequency));
+    let schedule_response = get_report_schedule_response(report_schedule.clone())?;
+    let filters = schedule_response.filters.unwrap_or_default();
+
+    let (rows, is_truncated) = match report_schedule.report_type {
+        enums::ReportType::PaymentsSummary => (
+            get_payments_summary_rows(state, &merchant_account, &filters, period_start, period_end)
+                .await?,
+            false,
+        ),
+        enums::ReportType::Disputes => {
+            get_disputes_rows(state, &merchant_account, &filters, period_start, period_end).await?
+        }
+    };
+    let (report, content_type) = render_report(
+        report_schedule.report_type,
+        &schedule_response.template,
+        &rows,
+    )?;
+
+    let file_name = format!(
+        "{}_{}.{}",
+        report_schedule.report_type,
+        period_start.date(),
+        schedule_response.template.format
+    );
+    let file_id = store_report_file(
+        state,
+        &merchant_account.merchant_id,
+        file_name,
+        report,
+        content_type,
+    )
+    .await?;
+
+    let next_run_at = period_end.saturating_add(get_report_period(report_schedule.frequency));
+    db.update_report_schedule_by_merchant_id_report_schedule_id(
+        &report_schedule.merchant_id,
+        &report_schedule.report_schedule_id,
+        storage::ReportScheduleUpdate::GenerationUpdate {
+            last_generated_at: date_time::now(),
+            last_file_id: file_id.clone(),
+            next_run_at,
+        },
+    )
+    .await
+    .change_context(errors::ApiErrorResponse::InternalServerError)
+    .attach_printable("Failed to update the report schedule")?;
+
+    let generated_report = report_types::GeneratedReportResponse {
+        report_schedule_id: report_schedule.report_schedule_id,
+        report_type: report_schedule.report_type,
+        format: schedule_response.template.format,
+        file_url: format!("{}/files/{file_id}", state.conf.server.base_url),
+        file_id,
+        period_start,
+        period_end,
+        row_count: rows.len(),
+        is_truncated,
+    };
+
+    // The report has been stored and can be retrieved through the report schedule, so failing to
+    // deliver it does not fail the generation of the report
+    let delivery_result = match report_schedule.delivery_method {
+        enums::ReportDeliveryMethod::Webhook => {
+            send_report_webhook(state, merchant_account, generated_report).await
+        }
+        #[cfg(feature = "email")]
+        enums::ReportDeliveryMethod::Email => {
+            send_report_email(state, report_schedule.recipient_email, &generated_report).await
+        }
+        #[cfg(not(feature = "email"))]
+        enums::ReportDeliveryMethod::Email => {
+            Err(report!(errors::ApiErrorResponse::NotSupported {
+                message: "Delivering reports by email".to_string(),
+            }))
+        }
+    };
+    if let Err(error) = delivery_result {
+        logger::error!(report_delivery_error=?error);
+    }
+
+    Ok(Some(next_run_at))
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use time::macros::datetime;
+
+    use super::*;
+
+    #[test]
+    fn test_next_run_at_is_next_midnight_for_daily_reports() {
+        assert_eq!(
+            get_next_run_at(enums::ReportFrequency::Daily, datetime!(2023-08-17 10:30)),
+            datetime!(2023-08-18 00:00)
+        );
+        assert_eq!(
+            get_next_run_at(enums::ReportFrequency::Daily, datetime!(2023-08-17 00:00)),
+            datetime!(2023-08-18 00:00)
+        );
+    }
+
+    #[test]
+    fn test_next_run_at_is_next_monday_for_weekly_reports() {
+        // 2023-08-17 is a Thursday
+        assert_eq!(
+            get_next_run_at(enums::ReportFrequency::Weekly, datetime!(2023-08-17 10:30)),
+            datetime!(2023-08-21 00:00)
+        );
+        // A report scheduled on a Monday is first generated on the following Monday
+        assert_eq!(
+            get_next_run_at(enums::ReportFrequency::Weekly, datetime!(2023-08-21 00:00)),
+            datetime!(2023-08-28 00:00)
+        );
+    }
+
+    #[test]
+    fn test_render_report_selects_template_columns() {
+        let template = report_types::ReportTemplate {
+            format: report_types::ReportFormat::Csv,
+            columns: Some(vec!["status".to_string(), "payment_count".to_string()]),
+        };
+        let rows = vec![vec![
+            "USD".to_string(),
+            "stripe".to_string(),
+            "online".to_string(),
+            "succeeded".to_string(),
+            "2".to_string(),
+            "2000".to_string(),
+            "2000".to_string(),
+        ]];
+        let (report, content_type) =
+            render_report(enums::ReportType::PaymentsSummary, &template, &rows).unwrap();
+
+        assert_eq!(content_type, mime::TEXT_CSV);
+        assert_eq!(
+            String::from_utf8(report).unwrap(),
+            "status,payment_count\nsucceeded,2\n"
+        );
+    }
+
+    #[test]
+    fn test_add_payment_to_summary_rejects_overflowing_amounts() {
+        let key = (
+            "USD".to_string(),
+            "stripe".to_string(),
+            "online".to_string(),
+            "succeeded".to_string(),
+        );
+        let mut summary = BTreeMap::new();
+        add_payment_to_summary(&mut summary, key.clone(), 1000, 1000).unwrap();
+        add_payment_to_summary(&mut summary, key.clone(), 500, 0).unwrap();
+        assert_eq!(summary.get(&key), Some(&(2, 1500, 1000)));
+
+        assert!(add_payment_to_summary(&mut summary, key, i64::MAX, 0).is_err());
+    }
+
+    #[test]
+    fn test_payment_channel_is_in_store_for_card_present_payments() {
+        let card_present_data =
+            serde_json::to_value(api_models::payments::AdditionalPaymentData::CardPresent {})
+                .unwrap();
+        assert_eq!(get_payment_channel(Some(&card_present_data)), "in_store");
+
+        let wallet_data =
+            serde_json::to_value(api_models::payments::AdditionalPaymentData::Wallet {}).unwrap();
+        assert_eq!(get_payment_channel(Some(&wallet_data)), "online");
+        assert_eq!(get_payment_channel(None), "online");
+    }
+}
@@ -0,0 +1,28 @@
+use api_models::runtime_config::RuntimeConfigReloadResponse;
+use common_utils::date_time;
+use error_stack::report;
+
+use super::errors::{self, RouterResponse};
+use crate::{routes::AppState, services};
+
+/// Reloads the runtime settings of this instance from its configuration, refusing invalid
+/// configurations.
+pub async fn reload_runtime_config(
+    state: &AppState,
+) -> RouterResponse<RuntimeConfigReloadResponse> {
+    let changed_sections = state.runtime_conf.reload().map_err(|error| {
+        report!(errors::ApiErrorResponse::PreconditionFailed {
+            message: format!("The configuration could not be reloaded: {error}"),
+        })
+    })?;
+
+    Ok(services::ApplicationResponse::Json(
+        RuntimeConfigReloadResponse {
+            changed_sections: changed_sections
+                .into_iter()
+                .map(ToString::to_string)
+                .collect(),
+            reloaded_at: date_time::now(),
+        },
+    ))
+}
@@ -0,0 +1,993 @@
+//! Recurring billing of customers.
+//!
+//! A subscription bills a customer a fixed amount on every billing cycle, by creating an
+//! off-session payment against a mandate of the customer. Each subscription has a task in the
+//! process tracker which is rescheduled to the next billing cycle after every billing. Failed
+//! payments are retried according to the dunning configuration of the merchant, after which the
+//! subscription is cancelled or paused.
+
+use common_utils::{date_time, ext_traits::StringExt};
+use error_stack::{report, IntoReport, ResultExt};
+use router_env::{instrument, logger, tracing};
+use time::PrimitiveDateTime;
+
+use crate::{
+    consts,
+    core::{
+        errors::{self, RouterResponse, RouterResult, StorageErrorExt},
+        payments,
+    },
+    db::StorageInterface,
+    routes::AppState,
+    scheduler::utils as pt_utils,
+    services::{self, ApplicationResponse},
+    types::{
+        api::{self, subscriptions as subscription_types},
+        domain,
+        storage::{self, enums, ProcessTrackerExt},
+    },
+    utils::{self, Encode},
+};
+
+const SUBSCRIPTION_BILLING_RUNNER: &str = "SUBSCRIPTION_BILLING_WORKFLOW";
+const SUBSCRIPTION_BILLING_TASK: &str = "SUBSCRIPTION_BILLING";
+
+fn get_dunning_config_key(merchant_id: &str) -> String {
+    format!("subscription_dunning_config_{merchant_id}")
+}
+
+fn get_process_tracker_id(subscription_id: &str, merchant_id: &str) -> String {
+    pt_utils::get_process_tracker_id(
+        SUBSCRIPTION_BILLING_RUNNER,
+        SUBSCRIPTION_BILLING_TASK,
+        subscription_id,
+        merchant_id,
+    )
+}
+
+/// Adds a number of months to a time, moving it to the last day of the resulting month when the
+/// month has fewer days than the day of the time.
+fn add_months(datetime: PrimitiveDateTime, months: i64) -> RouterResult<PrimitiveDateTime> {
+    let date = datetime.date();
+    let total_months = i64::from(date.year()) * 12 + i64::from(u8::from(date.month())) - 1 + months;
+    let year = i32::try_from(total_months.div_euclid(12))
+        .into_report()
+        .change_context(errors::ApiErrorResponse::InternalServerError)?;
+    let month = u8::try_from(total_months.rem_euclid(12) + 1)
+        .into_report()
+        .change_context(errors::ApiErrorResponse::InternalServerError)?;
+    let month = time::Month::try_from(month)
+        .into_report()
+        .change_context(errors::ApiErrorResponse::InternalServerError)?;
+    let day = date.day().min(time::util::days_in_year_month(year, month));
+    let date = time::Date::from_calendar_date(year, month, day)
+        .into_report()
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to compute the billing time of the subscription")?;
+    Ok(PrimitiveDateTime::new(date, datetime.time()))
+}
+
+/// Returns the time at which a billing cycle of a subscription is billed. Billing cycles are
+/// anchored to the first billing cycle, so that billing cycles of monthly subscriptions anchored
+/// to the end of a month are not moved earlier by shorter months.
+fn get_billing_time(
+    subscription: &storage::Subscription,
+    billing_cycle: i32,
+) -> RouterResult<PrimitiveDateTime> {
+    let periods = i64::from(subscription.billing_interval_count) * i64::from(billing_cycle);
+    match subscription.billing_interval {
+        enums::BillingInterval::Day => Ok(subscription
+            .billing_anchor
+            .saturating_add(time::Duration::days(periods))),
+        enums::BillingInterval::Week => Ok(subscription
+            .billing_anchor
+            .saturating_add(time::Duration::weeks(periods))),
+        enums::BillingInterval::Month => add_months(subscription.billing_anchor, periods),
+        enums::BillingInterval::Year => add_months(subscription.billing_anchor, periods * 12),
+    }
+}
+
+/// Returns the first billing cycle of a subscription from the given one which is not billed in
+/// the past, along with the time at which it is billed. Billing cycles which were due while the
+/// subscription was not billed are skipped rather than billed all at once.
+fn get_next_billing_cycle(
+    subscription: &storage::Subscription,
+    mut billing_cycle: i32,
+    now: PrimitiveDateTime,
+) -> RouterResult<(i32, PrimitiveDateTime)> {
+    let mut next_billing_at = get_billing_time(subscription, billing_cycle)?;
+    while next_billing_at < now {
+        billing_cycle += 1;
+        next_billing_at = get_billing_time(subscription, billing_cycle)?;
+    }
+    Ok((billing_cycle, next_billing_at))
+}
+
+fn get_subscription_response(
+    subscription: storage::Subscription,
+) -> subscription_types::SubscriptionResponse {
+    subscription_types::SubscriptionResponse {
+        subscription_id: subscription.subscription_id,
+        merchant_id: subscription.merchant_id,
+        customer_id: subscription.customer_id,
+        mandate_id: subscription.mandate_id,
+        status: subscription.status,
+        amount: subscription.amount,
+        currency: subscription.currency,
+        billing_interval: subscription.billing_interval,
+        billing_interval_count: subscription.billing_interval_count,
+        description: subscription.description,
+        billing_cycle: subscription.billing_cycle,
+        next_billing_at: subscription.next_billing_at,
+        failed_attempt_count: subscription.failed_attempt_count,
+        last_payment_id: subscription.last_payment_id,
+        cancelled_at: subscription.cancelled_at,
+        created: subscription.created_at,
+    }
+}
+
+fn validate_amount(amount: i64) -> RouterResult<()> {
+    utils::when(amount <= 0, || {
+        Err(report!(errors::ApiErrorResponse::InvalidRequestData {
+            message: "The amount of a subscription must be greater than zero".to_string(),
+        }))
+    })
+}
+
+/// Validates that the payments of a subscription can be created against a mandate.
+async fn validate_mandate(
+    db: &dyn StorageInterface,
+    merchant_id: &str,
+    mandate_id: &str,
+    customer_id: &str,
+    currency: enums::Currency,
+) -> RouterResult<()> {
+    let mandate = db
+        .find_mandate_by_merchant_id_mandate_id(merchant_id, mandate_id)
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::MandateNotFound)?;
+
+    utils::when(mandate.customer_id != customer_id, || {
+        Err(report!(errors::ApiErrorResponse::InvalidRequestData {
+            message: format!("The mandate `{mandate_id}` does not belong to the customer"),
+        }))
+    })?;
+    // A single use mandate would be consumed by the first billing cycle of the subscription
+    utils::when(mandate.mandate_type != enums::MandateType::MultiUse, || {
+        Err(report!(errors::ApiErrorResponse::InvalidRequestData {
+            message: format!("The mandate `{mandate_id}` is not a multi use mandate"),
+        }))
+    })?;
+    utils::when(
+        mandate.mandate_status != enums::MandateStatus::Active,
+        || {
+            Err(report!(errors::ApiErrorResponse::PreconditionFailed {
+                message: format!("The mandate `{mandate_id}` is not active"),
+            }))
+        },
+    )?;
+    utils::when(
+        mandate
+            .mandate_currency
+            .map_or(false, |mandate_currency| mandate_currency != currency),
+        || {
+            Err(report!(errors::ApiErrorResponse::InvalidRequestData {
+                message: format!(
+                    "The currency of the mandate `{mandate_id}` does not match the currency of the subscription"
+                ),
+            }))
+        },
+    )
+}
+
+async fn find_subscription(
+    db: &dyn StorageInterface,
+    merchant_id: &str,
+    subscription_id: &str,
+) -> RouterResult<storage::Subscription> {
+    db.find_subscription_by_merchant_id_subscription_id(merchant_id, subscription_id)
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::SubscriptionNotFound {
+            subscription_id: subscription_id.to_owned(),
+        })
+}
+
+async fn update_subscription(
+    db: &dyn StorageInterface,
+    subscription: &storage::Subscription,
+    subscription_update: storage::SubscriptionUpdate,
+) -> RouterResult<storage::Subscription> {
+    db.update_subscription_by_merchant_id_subscription_id(
+        &subscription.merchant_id,
+        &subscription.subscription_id,
+        subscription_update,
+    )
+    .await
+    .to_not_found_response(errors::ApiErrorResponse::SubscriptionNotFound {
+        subscription_id: subscription.subscription_id.clone(),
+    })
+}
+
+async fn add_subscription_billing_task(
+    db: &dyn StorageInterface,
+    subscription: &storage::Subscription,
+) -> RouterResult<()> {
+    let tracking_data = storage::SubscriptionBillingWorkflow {
+        subscription_id: subscription.subscription_id.clone(),
+        merchant_id: subscription.merchant_id.clone(),
+    };
+    let process_tracker_entry = <storage::ProcessTracker>::make_process_tracker_new(
+        get_process_tracker_id(&subscription.subscription_id, &subscription.merchant_id),
+        SUBSCRIPTION_BILLING_TASK,
+        SUBSCRIPTION_BILLING_RUNNER,
+        tracking_data,
+        subscription.next_billing_at,
+    )
+    .change_context(errors::ApiErrorResponse::InternalServerError)?;
+
+    db.insert_process(process_tracker_entry)
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to insert the subscription billing task to process tracker")?;
+    Ok(())
+}
+
+/// Schedules the billing of a resumed subscription again, by resetting its task in the process
+/// tracker to the next billing time of the subscription.
+async fn resume_subscription_billing_task(
+    db: &dyn StorageInterface,
+    subscription: &storage::Subscription,
+) -> RouterResult<()> {
+    let process = db
+        .find_process_by_id(&get_process_tracker_id(
+            &subscription.subscription_id,
+            &subscription.merchant_id,
+        ))
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable(
+            "Failed to retrieve the subscription billing task from process tracker",
+        )?;
+
+    match process {
+        Some(process) => process
+            .reset(db, subscription.next_billing_at)
+            .await
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Failed to reschedule the subscription billing task"),
+        None => add_subscription_billing_task(db, subscription).await,
+    }
+}
+
+async fn revoke_subscription_billing_task(
+    db: &dyn StorageInterface,
+    subscription: &storage::Subscription,
+    business_status: &str,
+) -> RouterResult<()> {
+    db.process_tracker_update_process_status_by_ids(
+        vec![get_process_tracker_id(
+            &subscription.subscription_id,
+            &subscription.merchant_id,
+        )],
+        storage::ProcessTrackerUpdate::StatusUpdate {
+            status: enums::ProcessTrackerStatus::Finish,
+            business_status: Some(business_status.to_string()),
+        },
+    )
+    .await
+    .change_context(errors::ApiErrorResponse::InternalServerError)
+    .attach_printable("Failed to revoke the subscription billing task in process tracker")?;
+    Ok(())
+}
+
+#[instrument(skip(state))]
+pub async fn create_subscription(
+    state: &AppState,
+    merchant_account: domain::MerchantAccount,
+    req: subscription_types::SubscriptionCreateRequest,
+) -> RouterResponse<subscription_types::SubscriptionResponse> {
+    validate_amount(req.amount)?;
+    let billing_interval_count = req.billing_interval_count.unwrap_or(1);
+    utils::when(!(1..=365).contains(&billing_interval_count), || {
+        Err(report!(errors::ApiErrorResponse::InvalidRequestData {
+            message: "The billing_interval_count must be between 1 and 365".to_string(),
+        }))
+    })?;
+
+    let db = &*state.store;
+    validate_mandate(
+        db,
+        &merchant_account.merchant_id,
+        &req.mandate_id,
+        &req.customer_id,
+        req.currency,
+    )
+    .await?;
+
+    let now = date_time::now();
+    let billing_anchor = req.start_at.unwrap_or(now);
+    utils::when(billing_anchor < now, || {
+        Err(report!(errors::ApiErrorResponse::InvalidRequestData {
+            message: "The start_at of a subscription cannot be in the past".to_string(),
+        }))
+    })?;
+
+    let subscription_new = storage::SubscriptionNew {
+        subscription_id: common_utils::generate_id(consts::ID_LENGTH, "sub"),
+        merchant_id: merchant_account.merchant_id,
+        customer_id: req.customer_id,
+        mandate_id: req.mandate_id,
+        status: enums::SubscriptionStatus::Active,
+        amount: req.amount,
+        currency: req.currency,
+        billing_interval: req.billing_interval,
+        billing_interval_count,
+        description: req.description,
+        billing_anchor,
+        billing_cycle: 0,
+        next_billing_at: billing_anchor,
+        created_at: now,
+        modified_at: now,
+    };
+    let subscription = db
+        .insert_subscription(subscription_new)
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to insert the subscription")?;
+    add_subscription_billing_task(db, &subscription).await?;
+
+    Ok(ApplicationResponse::Json(get_subscription_response(
+        subscription,
+    )))
+}
+
+#[instrument(skip(state))]
+pub async fn retrieve_subscription(
+    state: &AppState,
+    merchant_account: domain::MerchantAccount,
+    subscription_id: String,
+) -> RouterResponse<subscription_types::SubscriptionResponse> {
+    let subscription = find_subscription(
+        &*state.store,
+        &merchant_account.merchant_id,
+        &subscription_id,
+    )
+    .await?;
+    Ok(ApplicationResponse::Json(get_subscription_response(
+        subscription,
+    )))
+}
+
+#[instrument(skip(state))]
+pub async fn list_subscriptions(
+    state: &AppState,
+    merchant_account: domain::MerchantAccount,
+    constraints: subscription_types::SubscriptionListConstraints,
+) -> RouterResponse<Vec<subscription_types::SubscriptionResponse>> {
+    let subscriptions = state
+        .store
+        .list_subscriptions_by_merchant_id(
+            &merchant_account.merchant_id,
+            constraints.customer_id.as_deref(),
+            constraints.limit,
+            constraints.skip,
+        )
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to list the subscriptions of the merchant")?;
+
+    Ok(ApplicationResponse::Json(
+        subscriptions
+            .into_iter()
+            .map(get_subscription_response)
+            .collect(),
+    ))
+}
+
+#[instrument(skip(state))]
+pub async fn update_subscription_details(
+    state: &AppState,
+    merchant_account: domain::MerchantAccount,
+    subscription_id: String,
+    req: subscription_types::SubscriptionUpdateRequest,
+) -> RouterResponse<subscription_types::SubscriptionResponse> {
+    let db = &*state.store;
+    let subscription =
+        find_subscription(db, &merchant_account.merchant_id, &subscription_id).await?;
+    utils::when(
+        subscription.status == enums::SubscriptionStatus::Cancelled,
+        || {
+            Err(report!(errors::ApiErrorResponse::PreconditionFailed {
+                message: "A cancelled subscription cannot be updated".to_string(),
+            }))
+        },
+    )?;
+
+    if let Some(amount) = req.amount {
+        validate_amount(amount)?;
+    }
+    if let Some(mandate_id) = &req.mandate_id {
+        validate_mandate(
+            db,
+            &merchant_account.merchant_id,
+            mandate_id,
+            &subscription.customer_id,
+            subscription.currency,
+        )
+        .await?;
+    }
+
+    let subscription = update_subscription(
+        db,
+        &subscription,
+        storage::SubscriptionUpdate::Update {
+            amount: req.amount,
+            mandate_id: req.mandate_id,
+            description: req.description,
+        },
+    )
+    .await?;
+
+    Ok(ApplicationResponse::Json(get_subscription_response(
+        subscription,
+    )))
+}
+
+#[instrument(skip(state))]
+pub async fn cancel_subscription(
+    state: &AppState,
+    merchant_account: domain::MerchantAccount,
+    subscription_id: String,
+) -> RouterResponse<subscription_types::SubscriptionResponse> {
+    let db = &*state.store;
+    let subscription =
+        find_subscription(db, &merchant_account.merchant_id, &subscription_id).await?;
+    utils::when(
+        subscription.status == enums::SubscriptionStatus::Cancelled,
+        || {
+            Err(report!(errors::ApiErrorResponse::PreconditionFailed {
+                message: "The subscription has already been cancelled".to_string(),
+            }))
+        },
+    )?;
+
+    let subscription = update_subscription(
+        db,
+        &subscription,
+        storage::SubscriptionUpdate::StatusUpdate {
+            status: enums::SubscriptionStatus::Cancelled,
+            cancelled_at: Some(date_time::now()),
+        },
+    )
+    .await?;
+    revoke_subscription_billing_task(db, &subscription, "SUBSCRIPTION_CANCELLED").await?;
+
+    Ok(ApplicationResponse::Json(get_subscription_response(
+        subscription,
+    )))
+}
+
+#[instrument(skip(state))]
+pub async fn pause_subscription(
+    state: &AppState,
+    merchant_account: domain::MerchantAccount,
+    subscription_id: String,
+) -> RouterResponse<subscription_types::SubscriptionResponse> {
+    let db = &*state.store;
+    let subscription =
+        find_subscription(db, &merchant_account.merchant_id, &subscription_id).await?;
+    utils::when(
+        !matches!(
+            subscription.status,
+            enums::SubscriptionStatus::Active | enums::SubscriptionStatus::PastDue
+        ),
+        || {
+            Err(report!(errors::ApiErrorResponse::PreconditionFailed {
+                message: format!(
+                    "A subscription with status `{}` cannot be paused",
+                    subscription.status
+                ),
+            }))
+        },
+    )?;
+
+    let subscription = update_subscription(
+        db,
+        &subscription,
+        storage::SubscriptionUpdate::StatusUpdate {
+            status: enums::SubscriptionStatus::Paused,
+            cancelled_at: None,
+        },
+    )
+    .await?;
+    revoke_subscription_billing_task(db, &subscription, "SUBSCRIPTION_PAUSED").await?;
+
+    Ok(ApplicationResponse::Json(get_subscription_response(
+        subscription,
+    )))
+}
+
+/// Resumes the billing of a paused subscription from its next billing cycle. Billing cycles
+/// which were due while the subscription was paused are not billed.
+#[instrument(skip(state))]
+pub async fn resume_subscription(
+    state: &AppState,
+    merchant_account: domain::MerchantAccount,
+    subscription_id: String,
+) -> RouterResponse<subscription_types::SubscriptionResponse> {
+    let db = &*state.store;
+    let subscription =
+        find_subscription(db, &merchant_account.merchant_id, &subscription_id).await?;
+    utils::when(
+        subscription.status != enums::SubscriptionStatus::Paused,
+        || {
+            Err(report!(errors::ApiErrorResponse::PreconditionFailed {
+                message: "Only paused subscriptions can be resumed".to_string(),
+            }))
+        },
+    )?;
+
+    let (billing_cycle, next_billing_at) =
+        get_next_billing_cycle(&subscription, subscription.billing_cycle, date_time::now())?;
+
+    let subscription = db
+        .update_subscription_if_status_in(
+            &subscription.merchant_id,
+            &subscription.subscription_id,
+            vec![enums::SubscriptionStatus::Paused],
+            storage::SubscriptionUpdate::BillingUpdate {
+                status: enums::SubscriptionStatus::Active,
+                billing_cycle,
+                next_billing_at,
+                failed_attempt_count: 0,
+                last_payment_id: None,
+                cancelled_at: None,
+            },
+        )
+        .await
+        // The subscription was cancelled or resumed since it was retrieved
+        .to_not_found_response(errors::ApiErrorResponse::PreconditionFailed {
+            message: "Only paused subscriptions can be resumed".to_string(),
+        })?;
+    resume_subscription_billing_task(db, &subscription).await?;
+
+    Ok(ApplicationResponse::Json(get_subscription_response(
+        subscription,
+    )))
+}
+
+#[instrument(skip(state))]
+pub async fn retrieve_dunning_config(
+    state: &AppState,
+    merchant_account: domain::MerchantAccount,
+) -> RouterResponse<subscription_types::SubscriptionDunningConfig> {
+    let config = find_dunning_config(&*state.store, &merchant_account.merchant_id)
+        .await?
+        .unwrap_or_default();
+    Ok(ApplicationResponse::Json(config))
+}
+
+#[instrument(skip(state))]
+pub async fn update_dunning_config(
+    state: &AppState,
+    merchant_account: domain::MerchantAccount,
+    req: subscription_types::SubscriptionDunningConfig,
+) -> RouterResponse<subscription_types::SubscriptionDunningConfig> {
+    utils::when(
+        req.retry_intervals.len() > consts::SUBSCRIPTION_DUNNING_MAX_RETRIES,
+        || {
+            Err(report!(errors::ApiErrorResponse::InvalidRequestData {
+                message: format!(
+                    "At most {} retries can be configured",
+                    consts::SUBSCRIPTION_DUNNING_MAX_RETRIES
+                ),
+            }))
+        },
+    )?;
+    utils::when(
+        req.retry_intervals
+            .iter()
+            .any(|hours| !(1..=consts::SUBSCRIPTION_DUNNING_MAX_RETRY_INTERVAL).contains(hours)),
+        || {
+            Err(report!(errors::ApiErrorResponse::InvalidRequestData {
+                message: format!(
+                    "The retry intervals must be between 1 and {} hours",
+                    consts::SUBSCRIPTION_DUNNING_MAX_RETRY_INTERVAL
+                ),
+            }))
+        },
+    )?;
+
+    let key = get_dunning_config_key(&merchant_account.merchant_id);
+    let config =
+        Encode::<subscription_types::SubscriptionDunningConfig>::encode_to_string_of_json(&req)
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Failed to encode the subscription dunning configuration")?;
+
+    let db = &*state.store;
+    match db.find_config_by_key(&key).await {
+        Ok(_) => db
+            .update_config_cached(
+                &key,
+                storage::ConfigUpdate::Update {
+                    config: Some(config),
+                },
+            )
+            .await
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Failed to update the subscription dunning configuration")?,
+        Err(error) if error.current_context().is_db_not_found() => db
+            .insert_config(storage::ConfigNew { key, config })
+            .await
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Failed to insert the subscription dunning configuration")?,
+        Err(error) => Err(error
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Failed to find the subscription dunning configuration"))?,
+    };
+
+    Ok(ApplicationResponse::Json(req))
+}
+
+async fn find_dunning_config(
+    db: &dyn StorageInterface,
+    merchant_id: &str,
+) -> RouterResult<Option<subscription_types::SubscriptionDunningConfig>> {
+    match db
+        .find_config_by_key_cached(&get_dunning_config_key(merchant_id))
+        .await
+    {
+        Ok(config) => config
+            .config
+            .parse_struct("SubscriptionDunningConfig")
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Invalid subscription dunning configuration for the merchant")
+            .map(Some),
+        Err(error) if error.current_context().is_db_not_found() => Ok(None),
+        Err(error) => Err(error
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Failed to find the subscription dunning configuration")),
+    }
+}
+
+/// Creates the off-session payment of the current billing cycle of a subscription, returning the
+/// status of the payment, or `None` if the payment could not be created. The identifier of the
+/// payment is derived from the billing cycle and attempt, so that a billing retried by the process
+/// tracker after the payment was created does not charge the customer twice.
+async fn create_subscription_payment(
+    state: &AppState,
+    merchant_account: domain::MerchantAccount,
+    key_store: domain::MerchantKeyStore,
+    subscription: &storage::Subscription,
+    payment_id: &str,
+) -> RouterResult<Option<enums::IntentStatus>> {
+    let payment_request = api::PaymentsRequest {
+        payment_id: Some(api::PaymentIdType::PaymentIntentId(payment_id.to_owned())),
+        amount: Some(subscription.amount.into()),
+        currency: Some(subscription.currency),
+        customer_id: Some(subscription.customer_id.clone()),
+        mandate_id: Some(subscription.mandate_id.clone()),
+        off_session: Some(true),
+        confirm: Some(true),
+        description: subscription.description.clone(),
+        ..Default::default()
+    };
+    let storage_scheme = merchant_account.storage_scheme;
+    let merchant_id = merchant_account.merchant_id.clone();
+
+    match payments::payments_core::<api::Authorize, api::PaymentsResponse, _, _, _>(
+        state,
+        merchant_account,
+        key_store,
+        payments::PaymentCreate,
+        payment_request,
+        services::AuthFlow::Merchant,
+        payments::CallConnectorAction::Trigger,
+    )
+    .await
+    {
+        Ok(ApplicationResponse::Json(payments_response)) => Ok(Some(payments_response.status)),
+        Ok(_) => Err(errors::ApiErrorResponse::InternalServerError)
+            .into_report()
+            .attach_printable("Unexpected response from payments core"),
+        Err(error)
+            if matches!(
+                error.current_context(),
+                errors::ApiErrorResponse::DuplicatePayment { .. }
+            ) =>
+        {
+            let payment_intent = state
+                .store
+                .find_payment_intent_by_payment_id_merchant_id(
+                    payment_id,
+                    &merchant_id,
+                    storage_scheme,
+                )
+                .await
+                .to_not_found_response(errors::ApiErrorResponse::PaymentNotFound)?;
+            Ok(Some(payment_intent.status))
+        }
+        Err(error) => {
+            logger::error!(
+                subscription_id = %subscription.subscription_id,
+                ?error,
+                "Failed to create the subscription payment"
+            );
+            Ok(None)
+        }
+    }
+}
+
+/// Bills the current billing cycle of a subscription, returning the time at which the
+/// subscription is next billed, or `None` if it is no longer billed.
+#[instrument(skip(state))]
+pub async fn bill_subscription(
+    state: &AppState,
+    tracking_data: &storage::SubscriptionBillingWorkflow,
+) -> RouterResult<Option<PrimitiveDateTime>> {
+    let db = &*state.store;
+    let subscription = find_subscription(
+        db,
+        &tracking_data.merchant_id,
+        &tracking_data.subscription_id,
+    )
+    .await?;
+    if !matches!(
+        subscription.status,
+        enums::SubscriptionStatus::Active | enums::SubscriptionStatus::PastDue
+    ) {
+        logger::info!(
+            subscription_id = %subscription.subscription_id,
+            status = %subscription.status,
+            "Skipping the billing of a subscription which is no longer billed"
+        );
+        return Ok(None);
+    }
+
+    let key_store = db
+        .get_merchant_key_store_by_merchant_id(
+            &subscription.merchant_id,
+            &db.get_master_key().to_vec().into(),
+        )
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::MerchantAccountNotFound)?;
+    let merchant_account = db
+        .find_merchant_account_by_merchant_id(&subscription.merchant_id, &key_store)
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::MerchantAccountNotFound)?;
+    let dunning_config = find_dunning_config(db, &subscription.merchant_id)
+        .await?
+        .unwrap_or_default();
+
+    let payment_id = format!(
+        "{}_{}_{}",
+        subscription.subscription_id, subscription.billing_cycle, subscription.failed_attempt_count
+    );
+    let payment_status = create_subscription_payment(
+        state,
+        merchant_account,
+        key_store,
+        &subscription,
+        &payment_id,
+    )
+    .await?;
+    let last_payment_id = payment_status.map(|_| payment_id);
+
+    let now = date_time::now();
+    let subscription_update = match payment_status {
+        Some(
+            enums::IntentStatus::Succeeded
+            | enums::IntentStatus::Processing
+            | enums::IntentStatus::RequiresCapture
+            | enums::IntentStatus::PartiallyCaptured,
+        ) => {
+            let (billing_cycle, next_billing_at) =
+                get_next_billing_cycle(&subscription, subscription.billing_cycle + 1, now)?;
+            storage::SubscriptionUpdate::BillingUpdate {
+                status: enums::SubscriptionStatus::Active,
+                billing_cycle,
+                next_billing_at,
+                failed_attempt_count: 0,
+                last_payment_id,
+                cancelled_at: None,
+            }
+        }
+        _ => {
+            let retry_interval = usize::try_from(subscription.failed_attempt_count)
+                .into_report()
+                .change_context(errors::ApiErrorResponse::InternalServerError)
+                .map(|attempt| dunning_config.retry_intervals.get(attempt).copied())?;
+            let (status, next_billing_at, cancelled_at) = match retry_interval {
+                Some(hours) => (
+                    enums::SubscriptionStatus::PastDue,
+                    now.saturating_add(time::Duration::hours(i64::from(hours))),
+                    None,
+                ),
+                None => match dunning_config.final_action {
+                    subscription_types::SubscriptionDunningAction::Cancel => (
+                        enums::SubscriptionStatus::Cancelled,
+                        subscription.next_billing_at,
+                        Some(now),
+                    ),
+                    subscription_types::SubscriptionDunningAction::Pause => (
+                        enums::SubscriptionStatus::Paused,
+                        subscription.next_billing_at,
+                        None,
+                    ),
+                },
+            };
+            logger::info!(
+                subscription_id = %subscription.subscription_id,
+                ?payment_status,
+                %status,
+                "Subscription payment failed"
+            );
+            storage::SubscriptionUpdate::BillingUpdate {
+                status,
+                billing_cycle: subscription.billing_cycle,
+                next_billing_at,
+                failed_attempt_count: subscription.failed_attempt_count + 1,
+                last_payment_id,
+                cancelled_at,
+            }
+        }
+    };
+
+    // The subscription may have been paused or cancelled by the merchant while it was billed, in
+    // which case its new status is kept and it is no longer billed
+    let subscription = match db
+        .update_subscription_if_status_in(
+            &subscription.merchant_id,
+            &subscription.subscription_id,
+            vec![
+                enums::SubscriptionStatus::Active,
+                enums::SubscriptionStatus::PastDue,
+            ],
+            subscription_update,
+        )
+        .await
+    {
+        Ok(subscription) => subscription,
+        Err(error) if error.current_context().is_db_not_found() => {
+            logger::info!(
+                subscription_id = %subscription.subscription_id,
+                "Subscription status changed while it was billed, not scheduling its next billing"
+            );
+            return Ok(None);
+        }
+        Err(error) => {
+            return Err(error
+                .change_context(errors::ApiErrorResponse::InternalServerError)
+                .attach_printable("Failed to update the subscription after billing it"))
+        }
+    };
+    Ok(matches!(
+        subscription.status,
+        enums::SubscriptionStatus::Active | enums::SubscriptionStatus::PastDue
+    )
+    .then_some(subscription.next_billing_at))
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use time::macros::datetime;
+
+    use super::*;
+
+    fn get_subscription(
+        billing_interval: enums::BillingInterval,
+        billing_interval_count: i32,
+        billing_anchor: PrimitiveDateTime,
+    ) -> storage::Subscription {
+        storage::Subscription {
+            id: 1,
+            subscription_id: "sub_1".to_string(),
+            merchant_id: "merchant_1".to_string(),
+            customer_id: "cus_1".to_string(),
+            mandate_id: "man_1".to_string(),
+            status: enums::SubscriptionStatus::Active,
+            amount: 1000,
+            currency: enums::Currency::USD,
+            billing_interval,
+            billing_interval_count,
+            description: None,
+            billing_anchor,
+            billing_cycle: 0,
+            next_billing_at: billing_anchor,
+            failed_attempt_count: 0,
+            last_payment_id: None,
+            cancelled_at: None,
+            created_at: billing_anchor,
+            modified_at: billing_anchor,
+        }
+    }
+
+    #[test]
+    fn test_add_months_at_end_of_month() {
+        assert_eq!(
+            add_months(datetime!(2023-01-31 10:30), 1).unwrap(),
+            datetime!(2023-02-28 10:30)
+        );
+        assert_eq!(
+            add_months(datetime!(2024-01-31 10:30), 1).unwrap(),
+            datetime!(2024-02-29 10:30)
+        );
+        assert_eq!(
+            add_months(datetime!(2023-03-31 00:00), 1).unwrap(),
+            datetime!(2023-04-30 00:00)
+        );
+        assert_eq!(
+            add_months(datetime!(2023-11-30 00:00), 2).unwrap(),
+            datetime!(2024-01-30 00:00)
+        );
+        assert_eq!(
+            add_months(datetime!(2024-02-29 00:00), 12).unwrap(),
+            datetime!(2025-02-28 00:00)
+        );
+    }
+
+    #[test]
+    fn test_get_billing_time_is_anchored_to_first_billing_cycle() {
+        let subscription = get_subscription(
+            enums::BillingInterval::Month,
+            1,
+            datetime!(2023-01-31 12:00),
+        );
+
+        // Billing on the last day of February does not move the following billing cycles
+        assert_eq!(
+            get_billing_time(&subscription, 1).unwrap(),
+            datetime!(2023-02-28 12:00)
+        );
+        assert_eq!(
+            get_billing_time(&subscription, 2).unwrap(),
+            datetime!(2023-03-31 12:00)
+        );
+        assert_eq!(
+            get_billing_time(&subscription, 3).unwrap(),
+            datetime!(2023-04-30 12:00)
+        );
+
+        let subscription =
+            get_subscription(enums::BillingInterval::Year, 1, datetime!(2024-02-29 12:00));
+        assert_eq!(
+            get_billing_time(&subscription, 1).unwrap(),
+            datetime!(2025-02-28 12:00)
+        );
+        assert_eq!(
+            get_billing_time(&subscription, 4).unwrap(),
+            datetime!(2028-02-29 12:00)
+        );
+
+        let subscription =
+            get_subscription(enums::BillingInterval::Week, 2, datetime!(2023-12-25 12:00));
+        assert_eq!(
+            get_billing_time(&subscription, 1).unwrap(),
+            datetime!(2024-01-08 12:00)
+        );
+    }
+
+    #[test]
+    fn test_get_next_billing_cycle_skips_past_billing_cycles() {
+        let subscription = get_subscription(
+            enums::BillingInterval::Month,
+            1,
+            datetime!(2023-01-31 12:00),
+        );
+
+        assert_eq!(
+            get_next_billing_cycle(&subscription, 1, datetime!(2023-02-01 00:00)).unwrap(),
+            (1, datetime!(2023-02-28 12:00))
+        );
+        assert_eq!(
+            get_next_billing_cycle(&subscription, 1, datetime!(2023-05-15 00:00)).unwrap(),
+            (4, datetime!(2023-05-31 12:00))
+        );
+        // A billing cycle due at the current time is not skipped
+        assert_eq!(
+            get_next_billing_cycle(&subscription, 1, datetime!(2023-02-28 12:00)).unwrap(),
+            (1, datetime!(2023-02-28 12:00))
+        );
+    }
+}
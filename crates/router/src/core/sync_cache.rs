@@ -0,0 +1,125 @@
+//! Caching of the syncs of payments and refunds with connectors.
+//!
+//! Syncing a payment or a refund sends a request to the connector, and clients polling the status
+//! of a transaction with `force_sync` would send the same request to the connector over and over.
+//! The first sync of a transaction claims a key in Redis, keyed on the connector transaction id,
+//! which expires after `connector_requests.sync_cache_ttl` seconds. Further syncs of the
+//! transaction while the key exists are served the result of the first sync, as stored on the
+//! payment or the refund, instead of calling the connector. A sync that fails releases the key,
+//! so that the next sync of the transaction calls the connector again instead of serving the
+//! result of the failed sync.
+
+use redis_interface::SetnxReply;
+use router_env::logger;
+
+use crate::routes::{metrics, AppState};
+
+/// The sync flows whose results are cached.
+#[derive(Debug, Clone, Copy, strum::Display)]
+#[strum(serialize_all = "snake_case")]
+pub enum SyncFlow {
+    PaymentSync,
+    RefundSync,
+}
+
+fn get_cache_key(flow: SyncFlow, connector: &str, connector_transaction_id: &str) -> String {
+    format!("{flow}_{connector}_{connector_transaction_id}")
+}
+
+/// Returns whether the transaction has to be synced with the connector, which is not the case
+/// when it was synced within the sync cache window. Caching fails open, so that an unavailable
+/// Redis does not prevent syncs.
+pub async fn should_sync_with_connector(
+    state: &AppState,
+    flow: SyncFlow,
+    connector: &str,
+    connector_transaction_id: &str,
+) -> bool {
+    let ttl = state.runtime_conf.get().connector_requests.sync_cache_ttl;
+    if ttl == 0 {
+        return true;
+    }
+
+    let redis_conn = match state.store.get_redis_conn() {
+        Ok(redis_conn) => redis_conn,
+        Err(error) => {
+            logger::error!(redis_connection_error=?error);
+            return true;
+        }
+    };
+    let is_cached = match redis_conn
+        .set_key_if_not_exists_with_expiry(
+            &get_cache_key(flow, connector, connector_transaction_id),
+            "true",
+            Some(ttl),
+        )
+        .await
+    {
+        Ok(SetnxReply::KeySet) => false,
+        Ok(SetnxReply::KeyNotSet) => true,
+        Err(error) => {
+            logger::error!(sync_cache_error=?error);
+            return true;
+        }
+    };
+
+    let metric = if is_cached {
+        &metrics::CONNECTOR_SYNC_CACHE_HIT
+    } else {
+        &metrics::CONNECTOR_SYNC_CACHE_MISS
+    };
+    metric.add(
+        &metrics::CONTEXT,
+        1,
+        &[
+            metrics::request::add_attributes("flow", flow.to_string()),
+            metrics::request::add_attributes("connector", connector.to_string()),
+        ],
+    );
+
+    !is_cached
+}
+
+/// Releases the key claimed by [`should_sync_with_connector`] after the sync with the connector
+/// failed, so that the failure is not served to the syncs of the transaction within the sync
+/// cache window.
+pub async fn release_sync_claim(
+    state: &AppState,
+    flow: SyncFlow,
+    connector: &str,
+    connector_transaction_id: &str,
+) {
+    if state.runtime_conf.get().connector_requests.sync_cache_ttl == 0 {
+        return;
+    }
+
+    let redis_conn = match state.store.get_redis_conn() {
+        Ok(redis_conn) => redis_conn,
+        Err(error) => {
+            logger::error!(redis_connection_error=?error);
+            return;
+        }
+    };
+    redis_conn
+        .delete_key(&get_cache_key(flow, connector, connector_transaction_id))
+        .await
+        .map_err(|error| logger::error!(sync_cache_release_error=?error))
+        .ok();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cache_key_is_scoped_to_flow_and_connector() {
+        assert_eq!(
+            get_cache_key(SyncFlow::PaymentSync, "stripe", "pi_123"),
+            "payment_sync_stripe_pi_123"
+        );
+        assert_ne!(
+            get_cache_key(SyncFlow::PaymentSync, "stripe", "re_123"),
+            get_cache_key(SyncFlow::RefundSync, "stripe", "re_123")
+        );
+    }
+}
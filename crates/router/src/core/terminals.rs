@@ -0,0 +1,163 @@
+use common_utils::date_time;
+use error_stack::ResultExt;
+use router_env::{instrument, tracing};
+
+use crate::{
+    consts,
+    core::errors::{self, RouterResponse, RouterResult, StorageErrorExt},
+    routes::AppState,
+    services::ApplicationResponse,
+    types::{api::terminals as api, domain, storage, transformers::ForeignInto},
+    utils,
+};
+
+/// Ensures that the merchant connector account, if provided, belongs to the merchant so that
+/// card-present payments from the terminal can be routed to it.
+async fn validate_merchant_connector_id(
+    state: &AppState,
+    merchant_account: &domain::MerchantAccount,
+    key_store: &domain::MerchantKeyStore,
+    merchant_connector_id: Option<&String>,
+) -> RouterResult<()> {
+    if let Some(merchant_connector_id) = merchant_connector_id {
+        state
+            .store
+            .find_by_merchant_connector_account_merchant_id_merchant_connector_id(
+                &merchant_account.merchant_id,
+                merchant_connector_id,
+                key_store,
+            )
+            .await
+            .to_not_found_response(errors::ApiErrorResponse::MerchantConnectorAccountNotFound {
+                id: merchant_connector_id.to_owned(),
+            })?;
+    }
+    Ok(())
+}
+
+#[instrument(skip(state))]
+pub async fn create_terminal(
+    state: &AppState,
+    merchant_account: domain::MerchantAccount,
+    key_store: domain::MerchantKeyStore,
+    req: api::TerminalCreateRequest,
+) -> RouterResponse<api::TerminalResponse> {
+    validate_merchant_connector_id(
+        state,
+        &merchant_account,
+        &key_store,
+        req.merchant_connector_id.as_ref(),
+    )
+    .await?;
+
+    let now = date_time::now();
+    let terminal = storage::TerminalNew {
+        terminal_id: utils::generate_id(consts::ID_LENGTH, "term"),
+        merchant_id: merchant_account.merchant_id,
+        merchant_connector_id: req.merchant_connector_id,
+        connector_terminal_id: req.connector_terminal_id,
+        label: req.label,
+        location: req.location,
+        serial_number: req.serial_number,
+        is_active: true,
+        metadata: req.metadata,
+        created_at: now,
+        modified_at: now,
+    };
+
+    let terminal = state
+        .store
+        .insert_terminal(terminal)
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to insert terminal")?;
+
+    Ok(ApplicationResponse::Json(terminal.foreign_into()))
+}
+
+#[instrument(skip(state))]
+pub async fn retrieve_terminal(
+    state: &AppState,
+    merchant_account: domain::MerchantAccount,
+    terminal_id: String,
+) -> RouterResponse<api::TerminalResponse> {
+    let terminal = state
+        .store
+        .find_terminal_by_merchant_id_terminal_id(&merchant_account.merchant_id, &terminal_id)
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::TerminalNotFound { terminal_id })?;
+
+    Ok(ApplicationResponse::Json(terminal.foreign_into()))
+}
+
+#[instrument(skip(state))]
+pub async fn update_terminal(
+    state: &AppState,
+    merchant_account: domain::MerchantAccount,
+    key_store: domain::MerchantKeyStore,
+    terminal_id: String,
+    req: api::TerminalUpdateRequest,
+) -> RouterResponse<api::TerminalResponse> {
+    validate_merchant_connector_id(
+        state,
+        &merchant_account,
+        &key_store,
+        req.merchant_connector_id.as_ref(),
+    )
+    .await?;
+
+    let terminal = state
+        .store
+        .update_terminal_by_merchant_id_terminal_id(
+            &merchant_account.merchant_id,
+            &terminal_id,
+            req.foreign_into(),
+        )
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::TerminalNotFound { terminal_id })?;
+
+    Ok(ApplicationResponse::Json(terminal.foreign_into()))
+}
+
+#[instrument(skip(state))]
+pub async fn delete_terminal(
+    state: &AppState,
+    merchant_account: domain::MerchantAccount,
+    terminal_id: String,
+) -> RouterResponse<api::TerminalDeleteResponse> {
+    let deleted = state
+        .store
+        .delete_terminal_by_merchant_id_terminal_id(&merchant_account.merchant_id, &terminal_id)
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::TerminalNotFound {
+            terminal_id: terminal_id.clone(),
+        })?;
+
+    Ok(ApplicationResponse::Json(api::TerminalDeleteResponse {
+        terminal_id,
+        deleted,
+    }))
+}
+
+#[instrument(skip(state))]
+pub async fn list_terminals(
+    state: &AppState,
+    merchant_account: domain::MerchantAccount,
+    constraints: api::TerminalListConstraints,
+) -> RouterResponse<Vec<api::TerminalResponse>> {
+    let terminals = state
+        .store
+        .list_terminals_by_merchant_id(
+            &merchant_account.merchant_id,
+            constraints.limit,
+            constraints.skip,
+        )
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to list terminals")?
+        .into_iter()
+        .map(ForeignInto::foreign_into)
+        .collect();
+
+    Ok(ApplicationResponse::Json(terminals))
+}
@@ -1,10 +1,11 @@
-use std::marker::PhantomData;
+use std::{marker::PhantomData, str::FromStr};
 
 use api_models::enums::{DisputeStage, DisputeStatus};
 use common_utils::errors::CustomResult;
 #[cfg(feature = "payouts")]
 use common_utils::{crypto::Encryptable, pii::Email};
 use error_stack::{IntoReport, ResultExt};
+use masking::{PeekInterface, Secret};
 use router_env::{instrument, tracing};
 use uuid::Uuid;
 
@@ -30,6 +31,14 @@ pub const IRRELEVANT_CONNECTOR_REQUEST_REFERENCE_ID_IN_DISPUTE_FLOW: &str =
     "irrelevant_connector_request_reference_id_in_dispute_flow";
 const IRRELEVANT_PAYMENT_ID_IN_DISPUTE_FLOW: &str = "irrelevant_payment_id_in_dispute_flow";
 const IRRELEVANT_ATTEMPT_ID_IN_DISPUTE_FLOW: &str = "irrelevant_attempt_id_in_dispute_flow";
+const IRRELEVANT_PAYMENT_ID_IN_ACCOUNT_UPDATER_FLOW: &str =
+    "irrelevant_payment_id_in_account_updater_flow";
+const IRRELEVANT_ATTEMPT_ID_IN_ACCOUNT_UPDATER_FLOW: &str =
+    "irrelevant_attempt_id_in_account_updater_flow";
+const IRRELEVANT_PAYMENT_ID_IN_CONNECTOR_VERIFICATION_FLOW: &str =
+    "irrelevant_payment_id_in_connector_verification_flow";
+const IRRELEVANT_ATTEMPT_ID_IN_CONNECTOR_VERIFICATION_FLOW: &str =
+    "irrelevant_attempt_id_in_connector_verification_flow";
 
 #[cfg(feature = "payouts")]
 #[instrument(skip_all)]
@@ -75,7 +84,10 @@ pub async fn construct_payout_router_data<'a, F>(
     key_store: &domain::MerchantKeyStore,
     _request: &api_models::payouts::PayoutRequest,
     payout_data: &mut PayoutData,
-) -> RouterResult<types::PayoutsRouterData<F>> {
+) -> RouterResult<types::PayoutsRouterData<F>>
+where
+    F: types::api::ConnectorFlowName,
+{
     let (business_country, _) = helpers::get_business_details(
         payout_data.payout_attempt.business_country,
         payout_data.payout_attempt.business_label.as_ref(),
@@ -149,6 +161,7 @@ pub async fn construct_payout_router_data<'a, F>(
         address,
         auth_type: enums::AuthenticationType::default(),
         connector_meta_data: merchant_connector_account.get_metadata(),
+        connector_client_certificate: merchant_connector_account.get_client_certificate(),
         amount_captured: None,
         request: types::PayoutsData {
             payout_id: payouts.payout_id.to_owned(),
@@ -182,6 +195,82 @@ pub async fn construct_payout_router_data<'a, F>(
         quote_id: None,
         test_mode,
         payment_method_balance: None,
+        idempotency_key: Some(get_idempotency_key::<F>(&[
+            &payout_attempt.payout_attempt_id
+        ])),
+    };
+
+    Ok(router_data)
+}
+
+#[cfg(feature = "payouts")]
+#[instrument(skip_all)]
+pub async fn construct_payout_upload_file_router_data<'a>(
+    state: &'a AppState,
+    connector_id: &str,
+    merchant_account: &domain::MerchantAccount,
+    key_store: &domain::MerchantKeyStore,
+    create_file_request: &types::api::CreateFileRequest,
+    file_key: String,
+    payout_data: &PayoutData,
+) -> RouterResult<types::PayoutUploadFileRouterData> {
+    let merchant_connector_account = get_mca_for_payout(
+        state,
+        connector_id,
+        merchant_account,
+        key_store,
+        payout_data,
+    )
+    .await?;
+    let connector_auth_type: types::ConnectorAuthType = merchant_connector_account
+        .get_connector_account_details()
+        .parse_value("ConnectorAuthType")
+        .change_context(errors::ApiErrorResponse::InternalServerError)?;
+    let test_mode: Option<bool> = merchant_connector_account.is_test_mode_on();
+    let idempotency_key = get_idempotency_key::<types::api::PoUploadFile>(&[
+        &payout_data.payout_attempt.payout_attempt_id,
+        &file_key,
+    ]);
+    let router_data = types::RouterData {
+        flow: PhantomData,
+        merchant_id: merchant_account.merchant_id.to_owned(),
+        customer_id: Some(payout_data.payouts.customer_id.to_owned()),
+        connector_customer: None,
+        connector: connector_id.to_string(),
+        payment_id: "".to_string(),
+        attempt_id: "".to_string(),
+        status: enums::AttemptStatus::Failure,
+        payment_method: enums::PaymentMethod::default(),
+        connector_auth_type,
+        description: None,
+        return_url: payout_data.payouts.return_url.to_owned(),
+        payment_method_id: None,
+        address: PaymentAddress::default(),
+        auth_type: enums::AuthenticationType::default(),
+        connector_meta_data: merchant_connector_account.get_metadata(),
+        connector_client_certificate: merchant_connector_account.get_client_certificate(),
+        amount_captured: None,
+        request: types::UploadFileRequestData {
+            file_key,
+            file: create_file_request.file.clone(),
+            file_type: create_file_request.file_type.clone(),
+            file_size: create_file_request.file_size,
+            document_type: create_file_request.document_type,
+        },
+        response: Err(types::ErrorResponse::default()),
+        access_token: None,
+        session_token: None,
+        reference_id: Some(payout_data.payout_attempt.connector_payout_id.to_owned()),
+        payment_method_token: None,
+        recurring_mandate_payment_data: None,
+        preprocessing_id: None,
+        connector_request_reference_id: IRRELEVANT_CONNECTOR_REQUEST_REFERENCE_ID_IN_DISPUTE_FLOW
+            .to_string(),
+        payout_method_data: payout_data.payout_method_data.to_owned(),
+        quote_id: None,
+        test_mode,
+        payment_method_balance: None,
+        idempotency_key: Some(idempotency_key),
     };
 
     Ok(router_data)
@@ -199,7 +288,10 @@ pub async fn construct_refund_router_data<'a, F>(
     payment_attempt: &storage::PaymentAttempt,
     refund: &'a storage::Refund,
     creds_identifier: Option<String>,
-) -> RouterResult<types::RefundsRouterData<F>> {
+) -> RouterResult<types::RefundsRouterData<F>>
+where
+    F: types::api::ConnectorFlowName,
+{
     let connector_label = helpers::get_connector_label(
         payment_intent.business_country,
         &payment_intent.business_label,
@@ -214,7 +306,8 @@ pub async fn construct_refund_router_data<'a, F>(
         creds_identifier,
         key_store,
     )
-    .await?;
+    .await?
+    .select_environment(payment_intent.livemode)?;
 
     let auth_type: types::ConnectorAuthType = merchant_connector_account
         .get_connector_account_details()
@@ -236,6 +329,11 @@ pub async fn construct_refund_router_data<'a, F>(
         &connector_id.to_string(),
     ));
     let test_mode: Option<bool> = merchant_connector_account.is_test_mode_on();
+    let currency_conversion =
+        super::payments::currency_conversion::get_currency_conversion_for_amount(
+            payment_intent,
+            refund.refund_amount,
+        )?;
 
     let router_data = types::RouterData {
         flow: PhantomData,
@@ -254,6 +352,7 @@ pub async fn construct_refund_router_data<'a, F>(
         address: PaymentAddress::default(),
         auth_type: payment_attempt.authentication_type.unwrap_or_default(),
         connector_meta_data: merchant_connector_account.get_metadata(),
+        connector_client_certificate: merchant_connector_account.get_client_certificate(),
         amount_captured: payment_intent.amount_captured,
         request: types::RefundsData {
             refund_id: refund.refund_id.clone(),
@@ -265,11 +364,13 @@ pub async fn construct_refund_router_data<'a, F>(
             connector_metadata: payment_attempt.connector_metadata.clone(),
             reason: refund.refund_reason.clone(),
             connector_refund_id: refund.connector_refund_id.clone(),
+            currency_conversion,
         },
 
         response: Ok(types::RefundsResponseData {
             connector_refund_id: refund.connector_refund_id.clone().unwrap_or_default(),
             refund_status: refund.refund_status,
+            settled_amount: None,
         }),
         access_token: None,
         session_token: None,
@@ -289,6 +390,7 @@ pub async fn construct_refund_router_data<'a, F>(
         quote_id: None,
         test_mode,
         payment_method_balance: None,
+        idempotency_key: Some(get_idempotency_key::<F>(&[&refund.internal_reference_id])),
     };
 
     Ok(router_data)
@@ -340,6 +442,41 @@ pub fn validate_uuid(uuid: String, key: &str) -> Result<String, errors::ApiError
     }
 }
 
+fn is_host_allowed(host: &str, allowed_domains: &[String]) -> bool {
+    let host = host.trim_end_matches('.').to_lowercase();
+    allowed_domains.iter().any(|domain| {
+        host == *domain
+            || host
+                .strip_suffix(domain.as_str())
+                .map_or(false, |subdomain| subdomain.ends_with('.'))
+    })
+}
+
+/// Validates that the host of `url` is one of the domains in the allowlist of the merchant, or a
+/// subdomain of one of them. Merchants without an allowlist accept URLs on any domain.
+pub fn validate_url_against_allowed_domains(
+    url: &str,
+    field_name: &'static str,
+    allowed_domains: Option<&[String]>,
+) -> Result<(), errors::ApiErrorResponse> {
+    let allowed_domains = match allowed_domains {
+        Some(allowed_domains) if !allowed_domains.is_empty() => allowed_domains,
+        _ => return Ok(()),
+    };
+
+    let url = url::Url::parse(url)
+        .map_err(|_| errors::ApiErrorResponse::InvalidDataValue { field_name })?;
+
+    match url.host_str() {
+        Some(host) if is_host_allowed(host, allowed_domains) => Ok(()),
+        _ => Err(errors::ApiErrorResponse::InvalidRequestData {
+            message: format!(
+                "The domain of `{field_name}` is not in the list of allowed domains of the merchant"
+            ),
+        }),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -368,6 +505,46 @@ mod tests {
         let generated_id = generate_id(consts::ID_LENGTH, "ref");
         assert_eq!(generated_id.len(), consts::ID_LENGTH + 4)
     }
+
+    #[test]
+    fn validate_url_against_allowed_domains_matches_domain_and_subdomains() {
+        let allowed_domains = vec!["example.com".to_string()];
+
+        for url in [
+            "https://example.com/return",
+            "https://shop.example.com/return",
+            "https://EXAMPLE.com./return",
+        ] {
+            let result = validate_url_against_allowed_domains(
+                url,
+                "return_url",
+                Some(allowed_domains.as_slice()),
+            );
+            assert!(result.is_ok(), "{url} should be allowed");
+        }
+
+        for url in [
+            "https://example.org/return",
+            "https://evilexample.com/return",
+            "https://example.com.evil.org/return",
+            "www.example.com/return",
+        ] {
+            let result = validate_url_against_allowed_domains(
+                url,
+                "return_url",
+                Some(allowed_domains.as_slice()),
+            );
+            assert!(result.is_err(), "{url} should not be allowed");
+        }
+    }
+
+    #[test]
+    fn validate_url_against_allowed_domains_without_allowlist() {
+        let url = "https://example.org/return";
+
+        assert!(validate_url_against_allowed_domains(url, "return_url", None).is_ok());
+        assert!(validate_url_against_allowed_domains(url, "return_url", Some(&[])).is_ok());
+    }
 }
 
 // Dispute Stage can move linearly from PreDispute -> Dispute -> PreArbitration
@@ -457,7 +634,8 @@ pub async fn construct_accept_dispute_router_data<'a>(
         None,
         key_store,
     )
-    .await?;
+    .await?
+    .select_environment(payment_intent.livemode)?;
     let test_mode: Option<bool> = merchant_connector_account.is_test_mode_on();
     let auth_type: types::ConnectorAuthType = merchant_connector_account
         .get_connector_account_details()
@@ -481,6 +659,7 @@ pub async fn construct_accept_dispute_router_data<'a>(
         address: PaymentAddress::default(),
         auth_type: payment_attempt.authentication_type.unwrap_or_default(),
         connector_meta_data: merchant_connector_account.get_metadata(),
+        connector_client_certificate: merchant_connector_account.get_client_certificate(),
         amount_captured: payment_intent.amount_captured,
         request: types::AcceptDisputeRequestData {
             dispute_id: dispute.dispute_id.clone(),
@@ -506,6 +685,9 @@ pub async fn construct_accept_dispute_router_data<'a>(
         quote_id: None,
         test_mode,
         payment_method_balance: None,
+        idempotency_key: Some(get_idempotency_key::<types::api::Accept>(&[
+            &dispute.dispute_id
+        ])),
     };
     Ok(router_data)
 }
@@ -534,7 +716,8 @@ pub async fn construct_submit_evidence_router_data<'a>(
         None,
         key_store,
     )
-    .await?;
+    .await?
+    .select_environment(payment_intent.livemode)?;
     let test_mode: Option<bool> = merchant_connector_account.is_test_mode_on();
     let auth_type: types::ConnectorAuthType = merchant_connector_account
         .get_connector_account_details()
@@ -558,6 +741,7 @@ pub async fn construct_submit_evidence_router_data<'a>(
         address: PaymentAddress::default(),
         auth_type: payment_attempt.authentication_type.unwrap_or_default(),
         connector_meta_data: merchant_connector_account.get_metadata(),
+        connector_client_certificate: merchant_connector_account.get_client_certificate(),
         amount_captured: payment_intent.amount_captured,
         request: submit_evidence_request_data,
         response: Err(types::ErrorResponse::default()),
@@ -570,6 +754,14 @@ pub async fn construct_submit_evidence_router_data<'a>(
         recurring_mandate_payment_data: None,
         preprocessing_id: None,
         payment_method_balance: None,
+        idempotency_key: Some(get_idempotency_key::<types::api::Evidence>(&[
+            &dispute.dispute_id,
+            &dispute
+                .modified_at
+                .assume_utc()
+                .unix_timestamp()
+                .to_string(),
+        ])),
         connector_request_reference_id: get_connector_request_reference_id(
             &state.conf,
             &merchant_account.merchant_id,
@@ -604,7 +796,8 @@ pub async fn construct_upload_file_router_data<'a>(
         None,
         key_store,
     )
-    .await?;
+    .await?
+    .select_environment(payment_intent.livemode)?;
     let test_mode: Option<bool> = merchant_connector_account.is_test_mode_on();
     let auth_type: types::ConnectorAuthType = merchant_connector_account
         .get_connector_account_details()
@@ -613,6 +806,7 @@ pub async fn construct_upload_file_router_data<'a>(
     let payment_method = payment_attempt
         .payment_method
         .get_required_value("payment_method_type")?;
+    let idempotency_key = get_idempotency_key::<types::api::Upload>(&[&file_key]);
     let router_data = types::RouterData {
         flow: PhantomData,
         merchant_id: merchant_account.merchant_id.clone(),
@@ -628,12 +822,14 @@ pub async fn construct_upload_file_router_data<'a>(
         address: PaymentAddress::default(),
         auth_type: payment_attempt.authentication_type.unwrap_or_default(),
         connector_meta_data: merchant_connector_account.get_metadata(),
+        connector_client_certificate: merchant_connector_account.get_client_certificate(),
         amount_captured: payment_intent.amount_captured,
         request: types::UploadFileRequestData {
             file_key,
             file: create_file_request.file.clone(),
             file_type: create_file_request.file_type.clone(),
             file_size: create_file_request.file_size,
+            document_type: create_file_request.document_type,
         },
         response: Err(types::ErrorResponse::default()),
         access_token: None,
@@ -645,6 +841,7 @@ pub async fn construct_upload_file_router_data<'a>(
         recurring_mandate_payment_data: None,
         preprocessing_id: None,
         payment_method_balance: None,
+        idempotency_key: Some(idempotency_key),
         connector_request_reference_id: get_connector_request_reference_id(
             &state.conf,
             &merchant_account.merchant_id,
@@ -683,7 +880,8 @@ pub async fn construct_defend_dispute_router_data<'a>(
         None,
         key_store,
     )
-    .await?;
+    .await?
+    .select_environment(payment_intent.livemode)?;
     let test_mode: Option<bool> = merchant_connector_account.is_test_mode_on();
     let auth_type: types::ConnectorAuthType = merchant_connector_account
         .get_connector_account_details()
@@ -707,6 +905,7 @@ pub async fn construct_defend_dispute_router_data<'a>(
         address: PaymentAddress::default(),
         auth_type: payment_attempt.authentication_type.unwrap_or_default(),
         connector_meta_data: merchant_connector_account.get_metadata(),
+        connector_client_certificate: merchant_connector_account.get_client_certificate(),
         amount_captured: payment_intent.amount_captured,
         request: types::DefendDisputeRequestData {
             dispute_id: dispute.dispute_id.clone(),
@@ -722,6 +921,100 @@ pub async fn construct_defend_dispute_router_data<'a>(
         recurring_mandate_payment_data: None,
         preprocessing_id: None,
         payment_method_balance: None,
+        idempotency_key: Some(get_idempotency_key::<types::api::Defend>(&[
+            &dispute.dispute_id
+        ])),
+        connector_request_reference_id: get_connector_request_reference_id(
+            &state.conf,
+            &merchant_account.merchant_id,
+            payment_attempt,
+        ),
+        #[cfg(feature = "payouts")]
+        payout_method_data: None,
+        #[cfg(feature = "payouts")]
+        quote_id: None,
+        test_mode,
+    };
+    Ok(router_data)
+}
+
+#[instrument(skip_all)]
+pub async fn construct_reauthorize_router_data<'a>(
+    state: &'a AppState,
+    payment_intent: &'a storage::PaymentIntent,
+    payment_attempt: &storage::PaymentAttempt,
+    authorized_attempt: &storage::PaymentAttempt,
+    merchant_account: &domain::MerchantAccount,
+    key_store: &domain::MerchantKeyStore,
+) -> RouterResult<types::PaymentsReauthorizeRouterData> {
+    let connector_id = authorized_attempt
+        .connector
+        .as_deref()
+        .get_required_value("connector")?;
+    let connector_label = helpers::get_connector_label(
+        payment_intent.business_country,
+        &payment_intent.business_label,
+        authorized_attempt.business_sub_label.as_ref(),
+        connector_id,
+    );
+    let merchant_connector_account = helpers::get_merchant_connector_account(
+        state,
+        merchant_account.merchant_id.as_str(),
+        &connector_label,
+        None,
+        key_store,
+    )
+    .await?
+    .select_environment(payment_intent.livemode)?;
+    let test_mode: Option<bool> = merchant_connector_account.is_test_mode_on();
+    let auth_type: types::ConnectorAuthType = merchant_connector_account
+        .get_connector_account_details()
+        .parse_value("ConnectorAuthType")
+        .change_context(errors::ApiErrorResponse::InternalServerError)?;
+    let payment_method = authorized_attempt
+        .payment_method
+        .get_required_value("payment_method_type")?;
+    let connector_transaction_id = authorized_attempt
+        .connector_transaction_id
+        .clone()
+        .get_required_value("connector_transaction_id")?;
+    let currency = authorized_attempt.currency.get_required_value("currency")?;
+    let router_data = types::RouterData {
+        flow: PhantomData,
+        merchant_id: merchant_account.merchant_id.clone(),
+        connector: connector_id.to_string(),
+        payment_id: payment_attempt.payment_id.clone(),
+        attempt_id: payment_attempt.attempt_id.clone(),
+        status: payment_attempt.status,
+        payment_method,
+        connector_auth_type: auth_type,
+        description: payment_intent.description.clone(),
+        return_url: payment_intent.return_url.clone(),
+        payment_method_id: authorized_attempt.payment_method_id.clone(),
+        address: PaymentAddress::default(),
+        auth_type: authorized_attempt.authentication_type.unwrap_or_default(),
+        connector_meta_data: merchant_connector_account.get_metadata(),
+        connector_client_certificate: merchant_connector_account.get_client_certificate(),
+        amount_captured: payment_intent.amount_captured,
+        request: types::PaymentsReauthorizeData {
+            amount: authorized_attempt.amount,
+            currency,
+            connector_transaction_id,
+            connector_meta: authorized_attempt.connector_metadata.clone(),
+        },
+        response: Err(ErrorResponse::get_not_implemented()),
+        access_token: None,
+        session_token: None,
+        reference_id: None,
+        payment_method_token: None,
+        customer_id: payment_intent.customer_id.clone(),
+        connector_customer: None,
+        recurring_mandate_payment_data: None,
+        preprocessing_id: None,
+        payment_method_balance: None,
+        idempotency_key: Some(get_idempotency_key::<types::api::Reauthorize>(&[
+            &payment_attempt.attempt_id,
+        ])),
         connector_request_reference_id: get_connector_request_reference_id(
             &state.conf,
             &merchant_account.merchant_id,
@@ -736,6 +1029,240 @@ pub async fn construct_defend_dispute_router_data<'a>(
     Ok(router_data)
 }
 
+#[instrument(skip_all)]
+pub async fn construct_incremental_authorization_router_data<'a>(
+    state: &'a AppState,
+    payment_intent: &'a storage::PaymentIntent,
+    payment_attempt: &storage::PaymentAttempt,
+    merchant_account: &domain::MerchantAccount,
+    key_store: &domain::MerchantKeyStore,
+    total_amount: i64,
+    reason: Option<String>,
+    authorization_sequence: usize,
+) -> RouterResult<types::PaymentsIncrementalAuthorizationRouterData> {
+    let connector_id = payment_attempt
+        .connector
+        .as_deref()
+        .get_required_value("connector")?;
+    let connector_label = helpers::get_connector_label(
+        payment_intent.business_country,
+        &payment_intent.business_label,
+        payment_attempt.business_sub_label.as_ref(),
+        connector_id,
+    );
+    let merchant_connector_account = helpers::get_merchant_connector_account(
+        state,
+        merchant_account.merchant_id.as_str(),
+        &connector_label,
+        None,
+        key_store,
+    )
+    .await?
+    .select_environment(payment_intent.livemode)?;
+    let test_mode: Option<bool> = merchant_connector_account.is_test_mode_on();
+    let auth_type: types::ConnectorAuthType = merchant_connector_account
+        .get_connector_account_details()
+        .parse_value("ConnectorAuthType")
+        .change_context(errors::ApiErrorResponse::InternalServerError)?;
+    let payment_method = payment_attempt
+        .payment_method
+        .get_required_value("payment_method_type")?;
+    let connector_transaction_id = payment_attempt
+        .connector_transaction_id
+        .clone()
+        .get_required_value("connector_transaction_id")?;
+    let currency = payment_attempt.currency.get_required_value("currency")?;
+    let router_data = types::RouterData {
+        flow: PhantomData,
+        merchant_id: merchant_account.merchant_id.clone(),
+        connector: connector_id.to_string(),
+        payment_id: payment_attempt.payment_id.clone(),
+        attempt_id: payment_attempt.attempt_id.clone(),
+        status: payment_attempt.status,
+        payment_method,
+        connector_auth_type: auth_type,
+        description: payment_intent.description.clone(),
+        return_url: payment_intent.return_url.clone(),
+        payment_method_id: payment_attempt.payment_method_id.clone(),
+        address: PaymentAddress::default(),
+        auth_type: payment_attempt.authentication_type.unwrap_or_default(),
+        connector_meta_data: merchant_connector_account.get_metadata(),
+        connector_client_certificate: merchant_connector_account.get_client_certificate(),
+        amount_captured: payment_intent.amount_captured,
+        request: types::PaymentsIncrementalAuthorizationData {
+            total_amount,
+            additional_amount: total_amount - payment_attempt.amount,
+            currency,
+            reason,
+            connector_transaction_id,
+            connector_meta: payment_attempt.connector_metadata.clone(),
+        },
+        response: Err(ErrorResponse::get_not_implemented()),
+        access_token: None,
+        session_token: None,
+        reference_id: None,
+        payment_method_token: None,
+        customer_id: payment_intent.customer_id.clone(),
+        connector_customer: None,
+        recurring_mandate_payment_data: None,
+        preprocessing_id: None,
+        payment_method_balance: None,
+        idempotency_key: Some(get_idempotency_key::<types::api::IncrementalAuthorization>(
+            &[
+                &payment_attempt.attempt_id,
+                &authorization_sequence.to_string(),
+            ],
+        )),
+        connector_request_reference_id: get_connector_request_reference_id(
+            &state.conf,
+            &merchant_account.merchant_id,
+            payment_attempt,
+        ),
+        #[cfg(feature = "payouts")]
+        payout_method_data: None,
+        #[cfg(feature = "payouts")]
+        quote_id: None,
+        test_mode,
+    };
+    Ok(router_data)
+}
+
+#[instrument(skip_all)]
+pub fn construct_account_updater_router_data(
+    merchant_connector_account: &domain::MerchantConnectorAccount,
+    payment_method: &storage::PaymentMethod,
+    request: types::AccountUpdaterRequestData,
+) -> RouterResult<types::AccountUpdaterRouterData> {
+    let auth_type: types::ConnectorAuthType = merchant_connector_account
+        .connector_account_details
+        .peek()
+        .clone()
+        .parse_value("ConnectorAuthType")
+        .change_context(errors::ApiErrorResponse::InternalServerError)?;
+    let router_data = types::RouterData {
+        flow: PhantomData,
+        merchant_id: payment_method.merchant_id.clone(),
+        connector: merchant_connector_account.connector_name.clone(),
+        payment_id: IRRELEVANT_PAYMENT_ID_IN_ACCOUNT_UPDATER_FLOW.to_string(),
+        attempt_id: IRRELEVANT_ATTEMPT_ID_IN_ACCOUNT_UPDATER_FLOW.to_string(),
+        status: enums::AttemptStatus::default(),
+        payment_method: payment_method.payment_method,
+        connector_auth_type: auth_type,
+        description: None,
+        return_url: None,
+        payment_method_id: Some(payment_method.payment_method_id.clone()),
+        address: PaymentAddress::default(),
+        auth_type: enums::AuthenticationType::default(),
+        connector_meta_data: merchant_connector_account.metadata.clone(),
+        connector_client_certificate: merchant_connector_account.get_client_certificate(),
+        amount_captured: None,
+        request,
+        response: Err(ErrorResponse::get_not_implemented()),
+        access_token: None,
+        session_token: None,
+        reference_id: None,
+        payment_method_token: None,
+        customer_id: Some(payment_method.customer_id.clone()),
+        connector_customer: None,
+        recurring_mandate_payment_data: None,
+        preprocessing_id: None,
+        payment_method_balance: None,
+        // Account updates run periodically and every run must reach the connector
+        idempotency_key: None,
+        connector_request_reference_id: payment_method.payment_method_id.clone(),
+        #[cfg(feature = "payouts")]
+        payout_method_data: None,
+        #[cfg(feature = "payouts")]
+        quote_id: None,
+        test_mode: merchant_connector_account.test_mode,
+    };
+    Ok(router_data)
+}
+
+/// Constructs the router data for verifying the account details of a merchant connector account,
+/// through a zero amount verification of a test card
+#[instrument(skip_all)]
+pub fn construct_connector_verification_router_data(
+    merchant_connector_account: &domain::MerchantConnectorAccount,
+) -> RouterResult<types::VerifyRouterData> {
+    let auth_type: types::ConnectorAuthType = merchant_connector_account
+        .connector_account_details
+        .peek()
+        .clone()
+        .parse_value("ConnectorAuthType")
+        .change_context(errors::ApiErrorResponse::InternalServerError)?;
+    let card = api_models::payments::Card {
+        card_number: cards::CardNumber::from_str("4111111111111111")
+            .into_report()
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Failed to form a sample card number")?,
+        card_exp_month: "12".to_string().into(),
+        card_exp_year: (common_utils::date_time::now().year() + 5)
+            .to_string()
+            .into(),
+        card_holder_name: "John Doe".to_string().into(),
+        card_cvc: "123".to_string().into(),
+        card_issuer: None,
+        card_network: None,
+        card_type: None,
+        card_issuing_country: None,
+        bank_code: None,
+        nick_name: None,
+    };
+    let router_data = types::RouterData {
+        flow: PhantomData,
+        merchant_id: merchant_connector_account.merchant_id.clone(),
+        connector: merchant_connector_account.connector_name.clone(),
+        payment_id: IRRELEVANT_PAYMENT_ID_IN_CONNECTOR_VERIFICATION_FLOW.to_string(),
+        attempt_id: IRRELEVANT_ATTEMPT_ID_IN_CONNECTOR_VERIFICATION_FLOW.to_string(),
+        status: enums::AttemptStatus::default(),
+        payment_method: enums::PaymentMethod::Card,
+        connector_auth_type: auth_type,
+        description: None,
+        return_url: None,
+        payment_method_id: None,
+        address: PaymentAddress::default(),
+        auth_type: enums::AuthenticationType::NoThreeDs,
+        connector_meta_data: merchant_connector_account.metadata.clone(),
+        connector_client_certificate: merchant_connector_account.get_client_certificate(),
+        amount_captured: None,
+        request: types::VerifyRequestData {
+            currency: enums::Currency::USD,
+            payment_method_data: api_models::payments::PaymentMethodData::Card(card),
+            confirm: true,
+            statement_descriptor_suffix: None,
+            mandate_id: None,
+            setup_future_usage: None,
+            off_session: None,
+            setup_mandate_details: None,
+            router_return_url: None,
+            browser_info: None,
+            email: None,
+            return_url: None,
+            payment_method_type: Some(enums::PaymentMethodType::Credit),
+        },
+        response: Err(ErrorResponse::get_not_implemented()),
+        access_token: None,
+        session_token: None,
+        reference_id: None,
+        payment_method_token: None,
+        customer_id: None,
+        connector_customer: None,
+        recurring_mandate_payment_data: None,
+        preprocessing_id: None,
+        payment_method_balance: None,
+        // Every verification of the account details must reach the connector
+        idempotency_key: None,
+        connector_request_reference_id: generate_id(consts::ID_LENGTH, "verify"),
+        #[cfg(feature = "payouts")]
+        payout_method_data: None,
+        #[cfg(feature = "payouts")]
+        quote_id: None,
+        test_mode: merchant_connector_account.test_mode,
+    };
+    Ok(router_data)
+}
+
 #[instrument(skip_all)]
 pub async fn construct_retrieve_file_router_data<'a>(
     state: &'a AppState,
@@ -780,6 +1307,7 @@ pub async fn construct_retrieve_file_router_data<'a>(
         address: PaymentAddress::default(),
         auth_type: diesel_models::enums::AuthenticationType::default(),
         connector_meta_data: merchant_connector_account.get_metadata(),
+        connector_client_certificate: merchant_connector_account.get_client_certificate(),
         amount_captured: None,
         request: types::RetrieveFileRequestData {
             provider_file_id: file_metadata
@@ -797,6 +1325,9 @@ pub async fn construct_retrieve_file_router_data<'a>(
         recurring_mandate_payment_data: None,
         preprocessing_id: None,
         payment_method_balance: None,
+        idempotency_key: Some(get_idempotency_key::<types::api::Retrieve>(&[
+            &file_metadata.file_id,
+        ])),
         connector_request_reference_id: IRRELEVANT_CONNECTOR_REQUEST_REFERENCE_ID_IN_DISPUTE_FLOW
             .to_string(),
         #[cfg(feature = "payouts")]
@@ -832,3 +1363,17 @@ pub fn get_connector_request_reference_id(
         payment_attempt.attempt_id.clone()
     }
 }
+
+/// Derives the idempotency key sent to the connector for a flow.
+/// The key only depends on the flow and the given reference ids, so a retried call produces
+/// the same key and is deduplicated by the connector.
+pub fn get_idempotency_key<F: types::api::ConnectorFlowName>(
+    reference_ids: &[&str],
+) -> Secret<String> {
+    Secret::new(
+        std::iter::once(F::FLOW_NAME)
+            .chain(reference_ids.iter().copied())
+            .collect::<Vec<_>>()
+            .join("_"),
+    )
+}
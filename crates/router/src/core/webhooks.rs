@@ -1,19 +1,29 @@
+pub mod egress_ip_ranges;
+pub mod endpoint_health;
+mod fan_out;
+pub mod queue;
+pub mod retry;
 pub mod types;
 pub mod utils;
 
+use api_models::feature_flags::FeatureFlag;
 use common_utils::errors::ReportSwitchExt;
 use error_stack::{report, IntoReport, ResultExt};
 use masking::ExposeInterface;
 use router_env::{instrument, tracing};
 
+#[cfg(feature = "payouts")]
+use super::payouts;
 use super::{errors::StorageErrorExt, metrics};
 use crate::{
-    consts,
+    connection, consts,
     core::{
+        admin::webhook_signing_keys,
+        disputes,
         errors::{self, ConnectorErrorExt, CustomResult, RouterResponse},
-        payments, refunds,
+        feature_flags, ledger, payments, refunds,
     },
-    logger,
+    headers, logger,
     routes::AppState,
     services,
     types::{
@@ -51,11 +61,16 @@ pub async fn payments_incoming_webhook_flow<W: types::OutgoingWebhookType>(
                     resource_id: id,
                     merchant_id: Some(merchant_account.merchant_id.clone()),
                     force_sync: true,
+                    bypass_sync_cache: Some(true),
                     connector: None,
                     param: None,
                     merchant_connector_details: None,
                     client_secret: None,
                     expand_attempts: None,
+                    expand_captures: None,
+                    expand_refunds: None,
+                    expand_disputes: None,
+                    expand_customer: None,
                 },
                 services::AuthFlow::Merchant,
                 consume_or_trigger_flow,
@@ -114,6 +129,8 @@ pub async fn refunds_incoming_webhook_flow<W: types::OutgoingWebhookType>(
     webhook_details: api::IncomingWebhookDetails,
     connector_name: &str,
     source_verified: bool,
+    connector: &(dyn api::Connector + Sync),
+    request_details: &api::IncomingWebhookRequestDetails<'_>,
     event_type: api_models::webhooks::IncomingWebhookEvent,
 ) -> CustomResult<(), errors::ApiErrorResponse> {
     let db = &*state.store;
@@ -147,16 +164,26 @@ pub async fn refunds_incoming_webhook_flow<W: types::OutgoingWebhookType>(
     let refund_id = refund.refund_id.to_owned();
     //if source verified then update refund status else trigger refund sync
     let updated_refund = if source_verified {
+        let settled_amount = connector
+            .get_webhook_refund_settled_amount(request_details)
+            .switch()
+            .attach_printable("Could not find the amount refunded in incoming webhook body")?;
+        let refund_status = event_type
+            .foreign_try_into()
+            .into_report()
+            .change_context(errors::ApiErrorResponse::WebhookProcessingFailure)
+            .attach_printable("failed refund status mapping from event type")?;
         let refund_update = storage::RefundUpdate::StatusUpdate {
             connector_refund_id: None,
             sent_to_gateway: true,
-            refund_status: event_type
-                .foreign_try_into()
-                .into_report()
-                .change_context(errors::ApiErrorResponse::WebhookProcessingFailure)
-                .attach_printable("failed refund status mapping from event type")?,
+            refund_status: refunds::get_reconciled_refund_status(
+                &refund,
+                refund_status,
+                settled_amount,
+            ),
+            settled_amount,
         };
-        state
+        let updated_refund = state
             .store
             .update_refund(
                 refund.to_owned(),
@@ -170,7 +197,12 @@ pub async fn refunds_incoming_webhook_flow<W: types::OutgoingWebhookType>(
                     "Failed while updating refund: refund_id: {}",
                     refund_id.to_owned()
                 )
-            })?
+            })?;
+        ledger::record_refund_posting(&state, &updated_refund)
+            .await
+            .map_err(|error| logger::error!(ledger_error=?error))
+            .ok();
+        updated_refund
     } else {
         refunds::refund_retrieve_core(
             &state,
@@ -179,6 +211,7 @@ pub async fn refunds_incoming_webhook_flow<W: types::OutgoingWebhookType>(
             api_models::refunds::RefundsRetrieveRequest {
                 refund_id: refund_id.to_owned(),
                 force_sync: Some(true),
+                bypass_sync_cache: Some(true),
                 merchant_connector_details: None,
             },
         )
@@ -323,10 +356,75 @@ pub async fn get_or_update_dispute_object(
     }
 }
 
+/// Builds the dispute sent in outgoing webhooks, which includes a summary of the disputed payment
+/// and the description of the reason code, so that merchants can triage the dispute without
+/// retrieving the payment.
+async fn get_dispute_webhook_response(
+    state: &AppState,
+    merchant_account: &domain::MerchantAccount,
+    payment_attempt: &storage::PaymentAttempt,
+    dispute: storage::Dispute,
+) -> CustomResult<api_models::disputes::DisputeResponse, errors::ApiErrorResponse> {
+    let payment_intent = state
+        .store
+        .find_payment_intent_by_payment_id_merchant_id(
+            &payment_attempt.payment_id,
+            &merchant_account.merchant_id,
+            merchant_account.storage_scheme,
+        )
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::WebhookResourceNotFound)?;
+    let card_info = payment_attempt
+        .payment_method_data
+        .clone()
+        .map(|data| data.parse_value("AdditionalPaymentData"))
+        .transpose()
+        .map_err(|error| logger::warn!(additional_payment_data_error=?error))
+        .ok()
+        .flatten()
+        .and_then(|data| match data {
+            api_models::payments::AdditionalPaymentData::Card(card_info) => Some(card_info),
+            _ => None,
+        });
+    let card_network = card_info
+        .as_ref()
+        .and_then(|card_info| card_info.card_network.clone());
+    let reason_code_details = dispute
+        .connector_reason_code
+        .as_deref()
+        .and_then(|reason_code| {
+            disputes::reason_codes::get_reason_code_details(card_network.as_ref(), reason_code)
+        });
+    let reason_code_description = reason_code_details
+        .as_ref()
+        .map(|details| details.description.clone());
+    let payment_summary = api_models::disputes::DisputePaymentSummary {
+        amount: payment_intent.amount,
+        amount_captured: payment_intent.amount_captured,
+        currency: payment_intent.currency,
+        status: payment_intent.status,
+        payment_method: payment_attempt.payment_method,
+        payment_method_type: payment_attempt.payment_method_type,
+        card_network,
+        card_last4: card_info.and_then(|card_info| card_info.last4),
+        connector_transaction_id: payment_attempt.connector_transaction_id.clone(),
+        created_at: payment_intent.created_at,
+    };
+
+    Ok(api_models::disputes::DisputeResponse {
+        payment_summary: Some(payment_summary),
+        customer_id: payment_intent.customer_id,
+        reason_code_description,
+        reason_code_details,
+        ..dispute.foreign_into()
+    })
+}
+
 #[instrument(skip_all)]
 pub async fn disputes_incoming_webhook_flow<W: types::OutgoingWebhookType>(
     state: AppState,
     merchant_account: domain::MerchantAccount,
+    key_store: domain::MerchantKeyStore,
     webhook_details: api::IncomingWebhookDetails,
     source_verified: bool,
     connector: &(dyn api::Connector + Sync),
@@ -351,6 +449,9 @@ pub async fn disputes_incoming_webhook_flow<W: types::OutgoingWebhookType>(
             )
             .await
             .to_not_found_response(errors::ApiErrorResponse::WebhookResourceNotFound)?;
+        let is_newly_opened = option_dispute.as_ref().map_or(true, |dispute| {
+            dispute.dispute_status != enums::DisputeStatus::DisputeOpened
+        });
         let dispute_object = get_or_update_dispute_object(
             state.clone(),
             option_dispute,
@@ -361,7 +462,35 @@ pub async fn disputes_incoming_webhook_flow<W: types::OutgoingWebhookType>(
             connector.id(),
         )
         .await?;
-        let disputes_response = Box::new(dispute_object.clone().foreign_into());
+        ledger::record_dispute_postings(&state, &dispute_object)
+            .await
+            .map_err(|error| logger::error!(ledger_error=?error))
+            .ok();
+        // The auto-defend rules of the merchant act on disputes as soon as they are opened, before
+        // the merchant is notified of them
+        let dispute_object = if is_newly_opened
+            && dispute_object.dispute_stage == enums::DisputeStage::Dispute
+            && dispute_object.dispute_status == enums::DisputeStatus::DisputeOpened
+        {
+            disputes::auto_defend::apply_auto_defend_rules(
+                &state,
+                &merchant_account,
+                &key_store,
+                dispute_object,
+            )
+            .await
+        } else {
+            dispute_object
+        };
+        let disputes_response = Box::new(
+            get_dispute_webhook_response(
+                &state,
+                &merchant_account,
+                &payment_attempt,
+                dispute_object.clone(),
+            )
+            .await?,
+        );
         let event_type: enums::EventType = dispute_object
             .dispute_status
             .foreign_try_into()
@@ -462,6 +591,116 @@ async fn bank_transfer_webhook_flow<W: types::OutgoingWebhookType>(
     Ok(())
 }
 
+#[cfg(feature = "payouts")]
+#[instrument(skip_all)]
+pub async fn payouts_incoming_webhook_flow<W: types::OutgoingWebhookType>(
+    state: AppState,
+    merchant_account: domain::MerchantAccount,
+    key_store: domain::MerchantKeyStore,
+    webhook_details: api::IncomingWebhookDetails,
+    connector_name: &str,
+    source_verified: bool,
+    event_type: api_models::webhooks::IncomingWebhookEvent,
+) -> CustomResult<(), errors::ApiErrorResponse> {
+    // Payouts cannot be synced with the connector, so only verified webhooks are processed
+    if !source_verified {
+        Err(errors::ApiErrorResponse::WebhookAuthenticationFailed)
+            .into_report()
+            .attach_printable("Could not verify the source of the payout webhook")?;
+    }
+
+    let db = &*state.store;
+    let merchant_id = &merchant_account.merchant_id;
+    let payout_attempt = match webhook_details.object_reference_id {
+        api::ObjectReferenceId::PayoutId(api::PayoutIdType::PayoutId(id)) => db
+            .find_payout_attempt_by_merchant_id_payout_id(merchant_id, &id)
+            .await
+            .to_not_found_response(errors::ApiErrorResponse::WebhookResourceNotFound)?,
+        api::ObjectReferenceId::PayoutId(api::PayoutIdType::ConnectorPayoutId(id)) => db
+            .find_payout_attempts_by_merchant_id_connector_payout_id(merchant_id, &id)
+            .await
+            .change_context(errors::ApiErrorResponse::WebhookProcessingFailure)
+            .attach_printable("Failed to find the payout attempts of the connector payout id")?
+            .into_iter()
+            .find(|payout_attempt| payout_attempt.connector == connector_name)
+            .ok_or(errors::ApiErrorResponse::WebhookResourceNotFound)
+            .into_report()?,
+        _ => Err(errors::ApiErrorResponse::WebhookProcessingFailure)
+            .into_report()
+            .attach_printable("received a non-payout id when processing payout webhooks")?,
+    };
+    let payout_id = payout_attempt.payout_id.to_owned();
+
+    let status: enums::PayoutStatus = event_type
+        .foreign_try_into()
+        .into_report()
+        .change_context(errors::ApiErrorResponse::WebhookProcessingFailure)
+        .attach_printable("failed payout status mapping from event type")?;
+
+    if payout_attempt.status != status {
+        let payout_attempt_update = storage::PayoutAttemptUpdate::StatusUpdate {
+            connector_payout_id: payout_attempt.connector_payout_id.to_owned(),
+            status,
+            error_message: None,
+            error_code: None,
+            is_eligible: None,
+            connector_fee: None,
+            fx_margin: None,
+            last_modified_at: Some(common_utils::date_time::now()),
+        };
+        db.update_payout_attempt_by_merchant_id_payout_id(
+            merchant_id,
+            &payout_id,
+            payout_attempt_update,
+        )
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::WebhookResourceNotFound)
+        .attach_printable_lazy(|| {
+            format!("Failed while updating payout: payout_id: {payout_id}")
+        })?;
+    }
+
+    let payouts_response = payouts::payouts_retrieve_core(
+        &state,
+        merchant_account.clone(),
+        key_store,
+        api_models::payouts::PayoutRetrieveRequest {
+            payout_id: payout_id.to_owned(),
+            force_sync: None,
+        },
+    )
+    .await?;
+
+    match payouts_response {
+        services::ApplicationResponse::Json(payouts_response) => {
+            let event_type: enums::EventType = payouts_response
+                .status
+                .foreign_try_into()
+                .into_report()
+                .change_context(errors::ApiErrorResponse::WebhookProcessingFailure)
+                .attach_printable("payout status to event type mapping failed")?;
+
+            create_event_and_trigger_outgoing_webhook::<W>(
+                state,
+                merchant_account,
+                event_type,
+                enums::EventClass::Payouts,
+                None,
+                payout_id,
+                enums::EventObjectType::PayoutDetails,
+                api::OutgoingWebhookContent::PayoutDetails(Box::new(payouts_response)),
+            )
+            .await?;
+        }
+
+        _ => Err(errors::ApiErrorResponse::WebhookProcessingFailure)
+            .into_report()
+            .attach_printable("received non-json response from payouts core")?,
+    }
+
+    Ok(())
+}
+
 #[allow(clippy::too_many_arguments)]
 #[instrument(skip_all)]
 pub async fn create_event_and_trigger_outgoing_webhook<W: types::OutgoingWebhookType>(
@@ -514,16 +753,17 @@ pub async fn create_event_and_trigger_outgoing_webhook<W: types::OutgoingWebhook
             event_type: event.event_type,
             content,
             timestamp: event.created_at,
+            source_validation: None,
         };
 
-        arbiter.spawn(async move {
+        arbiter.spawn(connection::in_current_storage_region(async move {
             let result =
                 trigger_webhook_to_merchant::<W>(merchant_account, outgoing_webhook, &state).await;
 
             if let Err(e) = result {
                 logger::error!(?e);
             }
-        });
+        }));
     }
 
     Ok(())
@@ -531,27 +771,32 @@ pub async fn create_event_and_trigger_outgoing_webhook<W: types::OutgoingWebhook
 
 pub async fn trigger_webhook_to_merchant<W: types::OutgoingWebhookType>(
     merchant_account: domain::MerchantAccount,
-    webhook: api::OutgoingWebhook,
+    mut webhook: api::OutgoingWebhook,
     state: &AppState,
 ) -> CustomResult<(), errors::WebhooksFlowError> {
-    let webhook_details_json = merchant_account
-        .webhook_details
-        .get_required_value("webhook_details")
-        .change_context(errors::WebhooksFlowError::MerchantWebhookDetailsNotFound)?;
+    let webhook_details = get_webhook_details(&merchant_account)?;
 
-    let webhook_details: api::WebhookDetails =
-        webhook_details_json
-            .parse_value("WebhookDetails")
-            .change_context(errors::WebhooksFlowError::MerchantWebhookDetailsNotFound)?;
-
-    let webhook_url = webhook_details
-        .webhook_url
-        .get_required_value("webhook_url")
-        .change_context(errors::WebhooksFlowError::MerchantWebhookURLNotConfigured)
-        .map(ExposeInterface::expose)?;
+    if feature_flags::is_feature_enabled(
+        state,
+        FeatureFlag::WebhookSourceValidation,
+        &merchant_account.merchant_id,
+    )
+    .await
+    {
+        webhook.source_validation =
+            egress_ip_ranges::get_webhook_source_validation(state, &webhook_details).await;
+    }
 
     let outgoing_webhook_event_id = webhook.event_id.clone();
 
+    let business_webhook_endpoints = fan_out::get_business_webhook_endpoints(
+        state,
+        &merchant_account,
+        &webhook.content,
+        webhook.event_type,
+    )
+    .await;
+
     let transformed_outgoing_webhook = W::from(webhook);
 
     let outgoing_webhooks_signature = transformed_outgoing_webhook
@@ -564,23 +809,209 @@ pub async fn trigger_webhook_to_merchant<W: types::OutgoingWebhookType>(
     .change_context(errors::WebhooksFlowError::OutgoingWebhookEncodingFailed)
     .attach_printable("There was an issue when encoding the outgoing webhook body")?;
 
+    let mut signature_headers = Vec::new();
+
+    if let Some(signature) = outgoing_webhooks_signature {
+        W::add_webhook_header(&mut signature_headers, signature)
+    }
+
+    // The deliveries to the webhook endpoints of the business are independent of each other and of
+    // the delivery to the webhook endpoint of the merchant
+    let has_business_webhook_endpoints = !business_webhook_endpoints.is_empty();
+    let business_deliveries =
+        futures::future::join_all(business_webhook_endpoints.into_iter().map(|endpoint| {
+            fan_out::send_webhook_to_business_endpoint(
+                state,
+                &merchant_account,
+                &webhook_details,
+                &transformed_outgoing_webhook,
+                outgoing_webhook_event_id.clone(),
+                transformed_outgoing_webhook_string.clone(),
+                endpoint,
+            )
+        }));
+
+    // Merchants relying only on the webhook endpoints of their businesses need not configure a
+    // webhook endpoint of their own
+    let merchant_delivery = async {
+        if webhook_details.webhook_url.is_none() && has_business_webhook_endpoints {
+            return Ok(());
+        }
+
+        if endpoint_health::is_endpoint_paused(state, &merchant_account.merchant_id).await {
+            return endpoint_health::queue_webhook(
+                state,
+                &merchant_account.merchant_id,
+                outgoing_webhook_event_id.clone(),
+                transformed_outgoing_webhook_string.clone(),
+                signature_headers,
+            )
+            .await;
+        }
+
+        send_webhook_to_merchant(
+            state,
+            &merchant_account,
+            &webhook_details,
+            WebhookDelivery {
+                event_id: outgoing_webhook_event_id.clone(),
+                request_body: transformed_outgoing_webhook_string.clone(),
+                signature_headers,
+                attempt_number: 1,
+                retry_on_failure: true,
+                endpoint: None,
+            },
+        )
+        .await
+    };
+
+    let (merchant_delivery_result, business_delivery_results) =
+        futures::join!(merchant_delivery, business_deliveries);
+
+    for business_delivery_result in business_delivery_results {
+        if let Err(error) = business_delivery_result {
+            logger::error!(business_webhook_delivery_error=?error);
+        }
+    }
+
+    merchant_delivery_result
+}
+
+fn get_webhook_details(
+    merchant_account: &domain::MerchantAccount,
+) -> CustomResult<api::WebhookDetails, errors::WebhooksFlowError> {
+    merchant_account
+        .webhook_details
+        .clone()
+        .get_required_value("webhook_details")
+        .change_context(errors::WebhooksFlowError::MerchantWebhookDetailsNotFound)?
+        .parse_value("WebhookDetails")
+        .change_context(errors::WebhooksFlowError::MerchantWebhookDetailsNotFound)
+}
+
+/// Builds the request posting `request_body` to the webhook endpoint of the merchant, or to the
+/// given webhook endpoint of a business, presenting the client certificate of the merchant if
+/// mutual TLS is enabled. Returns whether mutual TLS is enabled along with the request.
+fn build_webhook_request(
+    merchant_account: &domain::MerchantAccount,
+    webhook_details: &api::WebhookDetails,
+    business_webhook_endpoint: Option<&api_models::admin::BusinessWebhookEndpoint>,
+    request_body: String,
+    signature_headers: Vec<(String, services::request::Maskable<String>)>,
+) -> CustomResult<(services::Request, bool), errors::WebhooksFlowError> {
+    let webhook_url = business_webhook_endpoint
+        .map(|endpoint| endpoint.webhook_url.clone())
+        .or_else(|| webhook_details.webhook_url.clone())
+        .get_required_value("webhook_url")
+        .change_context(errors::WebhooksFlowError::MerchantWebhookURLNotConfigured)
+        .map(ExposeInterface::expose)?;
+
+    let webhook_mtls_details: Option<api::WebhookMtlsDetails> = merchant_account
+        .webhook_mtls_details
+        .clone()
+        .map(|details| {
+            details
+                .into_inner()
+                .expose()
+                .parse_value("WebhookMtlsDetails")
+        })
+        .transpose()
+        .change_context(errors::WebhooksFlowError::MerchantWebhookClientCertificateInvalid)?;
+    let is_mtls_enabled = webhook_mtls_details.is_some();
+    let (client_certificate, client_certificate_key) = webhook_mtls_details
+        .map(|details| {
+            (
+                Some(details.client_certificate.expose()),
+                Some(details.client_certificate_key.expose()),
+            )
+        })
+        .unwrap_or_default();
+
     let mut header = vec![(
         reqwest::header::CONTENT_TYPE.to_string(),
         "application/json".into(),
     )];
-
-    if let Some(signature) = outgoing_webhooks_signature {
-        W::add_webhook_header(&mut header, signature)
-    }
+    header.extend(signature_headers);
 
     let request = services::RequestBuilder::new()
         .method(services::Method::Post)
         .url(&webhook_url)
         .attach_default_headers()
         .headers(header)
-        .body(Some(transformed_outgoing_webhook_string))
+        .body(Some(request_body))
+        .add_certificate(client_certificate)
+        .add_certificate_key(client_certificate_key)
         .build();
 
+    Ok((request, is_mtls_enabled))
+}
+
+/// An outgoing webhook to be posted to the endpoint of a merchant
+struct WebhookDelivery {
+    event_id: String,
+    request_body: String,
+    signature_headers: Vec<(String, services::request::Maskable<String>)>,
+    /// The number of the attempt, starting from 1 for the first delivery of the webhook
+    attempt_number: i32,
+    /// Whether a retry is scheduled if the webhook is not received
+    retry_on_failure: bool,
+    /// The webhook endpoint of a business to which the webhook is posted, instead of the webhook
+    /// endpoint of the merchant
+    endpoint: Option<api_models::admin::BusinessWebhookEndpoint>,
+}
+
+/// Posts a webhook to the endpoint of the merchant, marks its event as notified once the
+/// endpoint has received it, and records the outcome towards the health of the endpoint and in
+/// the delivery attempts of the webhook.
+///
+/// Every attempt is signed with the webhook signing key of the merchant which is active at the
+/// time of the attempt. A webhook which cannot be signed is not posted, and its attempt is
+/// recorded as failed so that it is retried.
+async fn send_webhook_to_merchant(
+    state: &AppState,
+    merchant_account: &domain::MerchantAccount,
+    webhook_details: &api::WebhookDetails,
+    delivery: WebhookDelivery,
+) -> CustomResult<(), errors::WebhooksFlowError> {
+    let signature_header = match webhook_signing_keys::get_webhook_signature_header(
+        merchant_account,
+        &delivery.request_body,
+    ) {
+        Ok(signature_header) => signature_header,
+        Err(error) => {
+            retry::record_delivery_attempt(
+                state,
+                merchant_account,
+                webhook_details,
+                &delivery,
+                retry::DeliveryOutcome {
+                    is_delivered: false,
+                    response_status_code: None,
+                    error_message: Some(error.current_context().to_string()),
+                },
+            )
+            .await;
+            return Err(error);
+        }
+    };
+    // The signature of a previous attempt, stored with the attempt, is replaced by the signature
+    // of this attempt
+    let signature_headers = delivery
+        .signature_headers
+        .iter()
+        .filter(|(name, _)| name.as_str() != headers::X_WEBHOOK_SIGNATURE_JWS)
+        .cloned()
+        .chain(signature_header)
+        .collect();
+
+    let (request, is_mtls_enabled) = build_webhook_request(
+        merchant_account,
+        webhook_details,
+        delivery.endpoint.as_ref(),
+        delivery.request_body.clone(),
+        signature_headers,
+    )?;
+
     let response =
         services::api::send_request(state, request, Some(OUTGOING_WEBHOOK_TIMEOUT_SECS)).await;
 
@@ -596,11 +1027,62 @@ pub async fn trigger_webhook_to_merchant<W: types::OutgoingWebhookType>(
 
     match response {
         Err(e) => {
-            // [#217]: Schedule webhook for retry.
-            Err(e).change_context(errors::WebhooksFlowError::CallToMerchantFailed)?;
+            let error = match e.current_context() {
+                errors::ApiClientError::CertificateDecodeFailed
+                | errors::ApiClientError::ClientConstructionFailed
+                    if is_mtls_enabled =>
+                {
+                    errors::WebhooksFlowError::MerchantWebhookClientCertificateInvalid
+                }
+                errors::ApiClientError::RequestNotSent(_) if is_mtls_enabled => {
+                    errors::WebhooksFlowError::MerchantWebhookMtlsHandshakeFailed
+                }
+                _ => errors::WebhooksFlowError::CallToMerchantFailed,
+            };
+            // Only the health of the webhook endpoint of the merchant is tracked
+            if delivery.endpoint.is_none() {
+                endpoint_health::record_delivery(state, merchant_account, webhook_details, false)
+                    .await;
+            }
+            retry::record_delivery_attempt(
+                state,
+                merchant_account,
+                webhook_details,
+                &delivery,
+                retry::DeliveryOutcome {
+                    is_delivered: false,
+                    response_status_code: None,
+                    error_message: Some(e.current_context().to_string()),
+                },
+            )
+            .await;
+            Err(e).change_context(error)?;
         }
         Ok(res) => {
-            if res.status().is_success() {
+            let is_received = res.status().is_success();
+            if delivery.endpoint.is_none() {
+                endpoint_health::record_delivery(
+                    state,
+                    merchant_account,
+                    webhook_details,
+                    is_received,
+                )
+                .await;
+            }
+            retry::record_delivery_attempt(
+                state,
+                merchant_account,
+                webhook_details,
+                &delivery,
+                retry::DeliveryOutcome {
+                    is_delivered: is_received,
+                    response_status_code: Some(res.status().as_u16()),
+                    error_message: None,
+                },
+            )
+            .await;
+
+            if is_received {
                 metrics::WEBHOOK_OUTGOING_RECEIVED_COUNT.add(
                     &metrics::CONTEXT,
                     1,
@@ -614,7 +1096,7 @@ pub async fn trigger_webhook_to_merchant<W: types::OutgoingWebhookType>(
                 };
                 state
                     .store
-                    .update_event(outgoing_webhook_event_id, update_event)
+                    .update_event(delivery.event_id, update_event)
                     .await
                     .change_context(errors::WebhooksFlowError::WebhookEventUpdationFailed)?;
             } else {
@@ -626,7 +1108,6 @@ pub async fn trigger_webhook_to_merchant<W: types::OutgoingWebhookType>(
                         merchant_account.merchant_id.clone(),
                     )],
                 );
-                // [#217]: Schedule webhook for retry.
                 Err(errors::WebhooksFlowError::NotReceivedByMerchant).into_report()?;
             }
         }
@@ -653,6 +1134,44 @@ pub async fn webhooks_core<W: types::OutgoingWebhookType>(
         )],
     );
 
+    let request_details = api::IncomingWebhookRequestDetails {
+        method: req.method().clone(),
+        headers: req.headers(),
+        query_params: req.query_string().to_string(),
+        body: &body,
+    };
+
+    let enqueue = state.conf.webhook_queue.enabled
+        && feature_flags::is_feature_enabled(
+            state,
+            FeatureFlag::WebhookQueue,
+            &merchant_account.merchant_id,
+        )
+        .await;
+
+    incoming_webhooks_core::<W>(
+        state,
+        request_details,
+        merchant_account,
+        key_store,
+        connector_name,
+        enqueue,
+    )
+    .await
+}
+
+/// Processes an incoming webhook. When `enqueue` is set, webhooks which have to be processed
+/// further and whose source is verified are only added to the webhook queue, to be processed by
+/// the webhook workers.
+#[instrument(skip_all)]
+pub async fn incoming_webhooks_core<W: types::OutgoingWebhookType>(
+    state: &AppState,
+    request_details: api::IncomingWebhookRequestDetails<'_>,
+    merchant_account: domain::MerchantAccount,
+    key_store: domain::MerchantKeyStore,
+    connector_name: &str,
+    enqueue: bool,
+) -> RouterResponse<serde_json::Value> {
     let connector = api::ConnectorData::get_connector_by_name(
         &state.conf.connectors,
         connector_name,
@@ -664,12 +1183,7 @@ pub async fn webhooks_core<W: types::OutgoingWebhookType>(
     .attach_printable("Failed construction of ConnectorData")?;
 
     let connector = connector.connector;
-    let mut request_details = api::IncomingWebhookRequestDetails {
-        method: req.method().clone(),
-        headers: req.headers(),
-        query_params: req.query_string().to_string(),
-        body: &body,
-    };
+    let raw_body = request_details.body;
 
     let decoded_body = connector
         .decode_webhook_body(
@@ -681,7 +1195,10 @@ pub async fn webhooks_core<W: types::OutgoingWebhookType>(
         .switch()
         .attach_printable("There was an error in incoming webhook body decoding")?;
 
-    request_details.body = &decoded_body;
+    let request_details = api::IncomingWebhookRequestDetails {
+        body: &decoded_body,
+        ..request_details
+    };
 
     let event_type = match connector
         .get_webhook_event_type(&request_details)
@@ -725,7 +1242,10 @@ pub async fn webhooks_core<W: types::OutgoingWebhookType>(
     logger::info!(event_type=?event_type);
 
     let flow_type: api::WebhookFlow = event_type.to_owned().into();
-    if process_webhook_further && !matches!(flow_type, api::WebhookFlow::ReturnResponse) {
+    let process_webhook_further =
+        process_webhook_further && !matches!(flow_type, api::WebhookFlow::ReturnResponse);
+
+    let source_verified = if process_webhook_further {
         let source_verified = connector
             .verify_webhook_source(
                 &*state.store,
@@ -748,8 +1268,37 @@ pub async fn webhooks_core<W: types::OutgoingWebhookType>(
                 )],
             );
         }
-
         logger::info!(source_verified=?source_verified);
+        source_verified
+    } else {
+        false
+    };
+
+    // Only webhooks whose source is verified are queued, so that the queue cannot be filled with
+    // forged webhooks. The others are processed right away, by the flows which do not trust the
+    // content of unverified webhooks.
+    if enqueue && process_webhook_further && source_verified {
+        queue::enqueue_incoming_webhook(
+            state,
+            &merchant_account.merchant_id,
+            connector_name,
+            &api::IncomingWebhookRequestDetails {
+                method: request_details.method.clone(),
+                headers: request_details.headers,
+                query_params: request_details.query_params.clone(),
+                body: raw_body,
+            },
+            &event_type,
+        )
+        .await?;
+
+        return connector
+            .get_webhook_api_response(&request_details)
+            .switch()
+            .attach_printable("Could not get incoming webhook api response from connector");
+    }
+
+    if process_webhook_further {
         let object_ref_id = connector
             .get_webhook_object_reference_id(&request_details)
             .switch()
@@ -787,6 +1336,8 @@ pub async fn webhooks_core<W: types::OutgoingWebhookType>(
                 webhook_details,
                 connector_name,
                 source_verified,
+                *connector,
+                &request_details,
                 event_type,
             )
             .await
@@ -795,6 +1346,7 @@ pub async fn webhooks_core<W: types::OutgoingWebhookType>(
             api::WebhookFlow::Dispute => disputes_incoming_webhook_flow::<W>(
                 state.clone(),
                 merchant_account,
+                key_store,
                 webhook_details,
                 source_verified,
                 *connector,
@@ -814,6 +1366,19 @@ pub async fn webhooks_core<W: types::OutgoingWebhookType>(
             .await
             .attach_printable("Incoming bank-transfer webhook flow failed")?,
 
+            #[cfg(feature = "payouts")]
+            api::WebhookFlow::Payout => payouts_incoming_webhook_flow::<W>(
+                state.clone(),
+                merchant_account,
+                key_store,
+                webhook_details,
+                connector_name,
+                source_verified,
+                event_type,
+            )
+            .await
+            .attach_printable("Incoming webhook flow for payouts failed")?,
+
             api::WebhookFlow::ReturnResponse => {}
 
             _ => Err(errors::ApiErrorResponse::InternalServerError)
@@ -0,0 +1,150 @@
+//! Static IP ranges from which outgoing webhooks are sent.
+//!
+//! The ranges are managed through the admin API and stored in the configs table, from where
+//! merchants can retrieve them in order to firewall their webhook endpoints. Merchants who have
+//! enabled `source_validation_enabled` additionally receive the ranges in every webhook.
+
+use std::net::IpAddr;
+
+use api_models::webhooks as webhook_types;
+use error_stack::{report, ResultExt};
+use router_env::logger;
+
+use crate::{
+    core::errors::{self, CustomResult, RouterResponse, RouterResult},
+    routes::AppState,
+    services,
+    types::{api, storage},
+    utils::{self, Encode, StringExt},
+};
+
+const WEBHOOK_EGRESS_IP_RANGES_KEY: &str = "webhook_egress_ip_ranges";
+
+/// Validates that the IP range is an IPv4 or IPv6 address followed by a prefix length no longer
+/// than the address.
+fn validate_ip_range(ip_range: &str) -> RouterResult<()> {
+    let is_valid = ip_range
+        .split_once('/')
+        .and_then(|(address, prefix_length)| {
+            let address = address.parse::<IpAddr>().ok()?;
+            let prefix_length = prefix_length.parse::<u8>().ok()?;
+            let max_prefix_length = if address.is_ipv4() { 32 } else { 128 };
+            Some(prefix_length <= max_prefix_length)
+        })
+        .unwrap_or(false);
+
+    utils::when(!is_valid, || {
+        Err(report!(errors::ApiErrorResponse::InvalidRequestData {
+            message: format!("`{ip_range}` is not a valid IP range in CIDR notation"),
+        }))
+    })
+}
+
+async fn get_egress_ip_ranges(state: &AppState) -> CustomResult<Vec<String>, errors::StorageError> {
+    match state
+        .store
+        .find_config_by_key_cached(WEBHOOK_EGRESS_IP_RANGES_KEY)
+        .await
+    {
+        Ok(config) => config
+            .config
+            .parse_struct("WebhookEgressIpRanges")
+            .change_context(errors::StorageError::DeserializationFailed),
+        Err(error) if error.current_context().is_db_not_found() => Ok(Vec::new()),
+        Err(error) => Err(error),
+    }
+}
+
+/// Returns the source validation details to be included in the webhooks of a merchant, if the
+/// merchant has enabled them. Webhooks are still sent when the ranges cannot be retrieved.
+pub async fn get_webhook_source_validation(
+    state: &AppState,
+    webhook_details: &api::WebhookDetails,
+) -> Option<webhook_types::WebhookSourceValidation> {
+    if !webhook_details.source_validation_enabled.unwrap_or(false) {
+        return None;
+    }
+
+    get_egress_ip_ranges(state)
+        .await
+        .map_err(|error| logger::error!(webhook_egress_ip_ranges_error=?error))
+        .ok()
+        .map(|egress_ip_ranges| webhook_types::WebhookSourceValidation {
+            egress_ip_ranges,
+            documentation_url: state
+                .conf
+                .webhooks
+                .source_validation_documentation_url
+                .clone(),
+        })
+}
+
+pub async fn retrieve_webhook_egress_ip_ranges(
+    state: &AppState,
+) -> RouterResponse<webhook_types::WebhookEgressIpRangesResponse> {
+    let egress_ip_ranges = get_egress_ip_ranges(state)
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to retrieve the webhook egress IP ranges")?;
+
+    Ok(services::ApplicationResponse::Json(
+        webhook_types::WebhookEgressIpRangesResponse {
+            egress_ip_ranges,
+            documentation_url: state
+                .conf
+                .webhooks
+                .source_validation_documentation_url
+                .clone(),
+        },
+    ))
+}
+
+pub async fn update_webhook_egress_ip_ranges(
+    state: &AppState,
+    req: webhook_types::WebhookEgressIpRangesUpdateRequest,
+) -> RouterResponse<webhook_types::WebhookEgressIpRangesResponse> {
+    req.egress_ip_ranges
+        .iter()
+        .map(String::as_str)
+        .try_for_each(validate_ip_range)?;
+
+    let config = Encode::<Vec<String>>::encode_to_string_of_json(&req.egress_ip_ranges)
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to encode the webhook egress IP ranges")?;
+
+    let db = &*state.store;
+    match db.find_config_by_key(WEBHOOK_EGRESS_IP_RANGES_KEY).await {
+        Ok(_) => db
+            .update_config_cached(
+                WEBHOOK_EGRESS_IP_RANGES_KEY,
+                storage::ConfigUpdate::Update {
+                    config: Some(config),
+                },
+            )
+            .await
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Failed to update the webhook egress IP ranges")?,
+        Err(error) if error.current_context().is_db_not_found() => db
+            .insert_config(storage::ConfigNew {
+                key: WEBHOOK_EGRESS_IP_RANGES_KEY.to_string(),
+                config,
+            })
+            .await
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Failed to insert the webhook egress IP ranges")?,
+        Err(error) => Err(error
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Failed to find the webhook egress IP ranges"))?,
+    };
+
+    Ok(services::ApplicationResponse::Json(
+        webhook_types::WebhookEgressIpRangesResponse {
+            egress_ip_ranges: req.egress_ip_ranges,
+            documentation_url: state
+                .conf
+                .webhooks
+                .source_validation_documentation_url
+                .clone(),
+        },
+    ))
+}
@@ -0,0 +1,650 @@
+//! Health monitoring of the webhook endpoints of merchants.
+//!
+//! Deliveries of outgoing webhooks are counted per merchant in fixed windows. Once the fraction of
+//! failed deliveries in a window crosses the configured threshold, the endpoint is marked degraded
+//! and the merchant is notified by email. Merchants who have enabled `auto_pause_enabled` have the
+//! endpoint paused instead, and their webhooks are queued until the endpoint is verified again
+//! through a challenge request, after which the queued webhooks are delivered oldest first. The
+//! endpoint is only marked healthy once all the queued webhooks have been delivered, so that new
+//! webhooks are not delivered ahead of the queued ones.
+
+use common_utils::{
+    crypto::{self, SignMessage},
+    ext_traits::ValueExt,
+};
+use error_stack::{IntoReport, ResultExt};
+use masking::{ExposeInterface, PeekInterface, Secret};
+use router_env::{instrument, logger, tracing};
+
+use super::{
+    build_webhook_request, get_webhook_details, send_webhook_to_merchant, WebhookDelivery,
+};
+use crate::{
+    connection,
+    core::errors::{self, CustomResult, RouterResponse, StorageErrorExt},
+    headers,
+    routes::AppState,
+    services::{self, request::Maskable},
+    types::{
+        api, domain,
+        domain::types as domain_types,
+        storage::{self, enums},
+    },
+    utils::{generate_id, Encode},
+};
+
+/// The number of queued webhooks fetched at a time while delivering them
+const QUEUED_WEBHOOKS_BATCH_SIZE: i64 = 100;
+const CHALLENGE_LENGTH: usize = 32;
+
+/// The challenge posted to the webhook endpoint of a merchant when it is verified. The endpoint
+/// is expected to respond with the value of `challenge` as the response body.
+#[derive(Debug, serde::Serialize)]
+struct WebhookEndpointChallenge {
+    merchant_id: String,
+    challenge: String,
+}
+
+fn get_window_keys(merchant_id: &str, window: i64) -> (String, String) {
+    let window_index = common_utils::date_time::now().assume_utc().unix_timestamp() / window;
+    (
+        format!("WEBHOOK_DELIVERIES_{merchant_id}_{window_index}"),
+        format!("WEBHOOK_DELIVERY_FAILURES_{merchant_id}_{window_index}"),
+    )
+}
+
+async fn get_delivery_count(state: &AppState, key: &str) -> i64 {
+    let Ok(redis_conn) = state
+        .store
+        .get_redis_conn()
+        .map_err(|error| logger::error!(redis_connection_error=?error))
+    else {
+        return 0;
+    };
+
+    redis_conn
+        .get_key::<Option<i64>>(key)
+        .await
+        .map_err(|error| logger::error!(webhook_delivery_count_error=?error))
+        .ok()
+        .flatten()
+        .unwrap_or_default()
+}
+
+/// Returns the number of deliveries and failed deliveries to the endpoint of a merchant in the
+/// current window.
+async fn get_delivery_counts(state: &AppState, merchant_id: &str) -> (i64, i64) {
+    let (deliveries_key, failures_key) =
+        get_window_keys(merchant_id, state.conf.webhooks.endpoint_health.window);
+
+    (
+        get_delivery_count(state, &deliveries_key).await,
+        get_delivery_count(state, &failures_key).await,
+    )
+}
+
+/// Increments the delivery counter stored at `key`. Counting fails open, so that an unavailable
+/// Redis does not hold back webhooks.
+async fn count_delivery(state: &AppState, key: &str) -> Option<i64> {
+    let redis_conn = state
+        .store
+        .get_redis_conn()
+        .map_err(|error| logger::error!(redis_connection_error=?error))
+        .ok()?;
+
+    redis_conn
+        .increment_key_with_expiry(key, state.conf.webhooks.endpoint_health.window)
+        .await
+        .map_err(|error| logger::error!(webhook_delivery_count_error=?error))
+        .ok()
+}
+
+async fn find_endpoint_health(
+    state: &AppState,
+    merchant_id: &str,
+) -> CustomResult<Option<storage::WebhookEndpointHealth>, errors::StorageError> {
+    match state
+        .store
+        .find_webhook_endpoint_health_by_merchant_id(merchant_id)
+        .await
+    {
+        Ok(endpoint_health) => Ok(Some(endpoint_health)),
+        Err(error) if error.current_context().is_db_not_found() => Ok(None),
+        Err(error) => Err(error),
+    }
+}
+
+/// Whether deliveries to the endpoint of the merchant are paused. Fails open, so that webhooks
+/// are delivered if the health of the endpoint cannot be read.
+pub async fn is_endpoint_paused(state: &AppState, merchant_id: &str) -> bool {
+    if !state.conf.webhooks.endpoint_health.enabled {
+        return false;
+    }
+
+    find_endpoint_health(state, merchant_id)
+        .await
+        .map_err(|error| logger::error!(webhook_endpoint_health_error=?error))
+        .ok()
+        .flatten()
+        .map_or(false, |endpoint_health| {
+            endpoint_health.status == enums::WebhookEndpointStatus::Paused
+        })
+}
+
+/// Records the outcome of a delivery to the endpoint of a merchant, and marks the endpoint
+/// degraded once the failure rate in the current window crosses the configured threshold.
+#[instrument(skip_all)]
+pub async fn record_delivery(
+    state: &AppState,
+    merchant_account: &domain::MerchantAccount,
+    webhook_details: &api::WebhookDetails,
+    is_received: bool,
+) {
+    let config = &state.conf.webhooks.endpoint_health;
+    if !config.enabled {
+        return;
+    }
+
+    let (deliveries_key, failures_key) =
+        get_window_keys(&merchant_account.merchant_id, config.window);
+
+    let Some(delivery_count) = count_delivery(state, &deliveries_key).await else {
+        return;
+    };
+    if is_received {
+        return;
+    }
+    let Some(failure_count) = count_delivery(state, &failures_key).await else {
+        return;
+    };
+
+    #[allow(clippy::as_conversions)]
+    let failure_rate = failure_count as f64 / delivery_count as f64;
+    if delivery_count < config.min_deliveries || failure_rate < config.failure_rate_threshold {
+        return;
+    }
+
+    if let Err(error) = mark_endpoint_degraded(
+        state,
+        merchant_account,
+        webhook_details,
+        delivery_count,
+        failure_count,
+    )
+    .await
+    {
+        logger::error!(webhook_endpoint_degradation_error=?error);
+    }
+}
+
+async fn mark_endpoint_degraded(
+    state: &AppState,
+    merchant_account: &domain::MerchantAccount,
+    webhook_details: &api::WebhookDetails,
+    delivery_count: i64,
+    failure_count: i64,
+) -> CustomResult<(), errors::StorageError> {
+    let merchant_id = &merchant_account.merchant_id;
+    let endpoint_health = find_endpoint_health(state, merchant_id).await?;
+
+    if endpoint_health.as_ref().map_or(false, |endpoint_health| {
+        endpoint_health.status != enums::WebhookEndpointStatus::Healthy
+    }) {
+        return Ok(());
+    }
+
+    let status = if webhook_details.auto_pause_enabled.unwrap_or(false) {
+        enums::WebhookEndpointStatus::Paused
+    } else {
+        enums::WebhookEndpointStatus::Degraded
+    };
+    let delivery_count = i32::try_from(delivery_count).unwrap_or(i32::MAX);
+    let failure_count = i32::try_from(failure_count).unwrap_or(i32::MAX);
+    let now = common_utils::date_time::now();
+
+    match endpoint_health {
+        Some(_) => {
+            state
+                .store
+                .update_webhook_endpoint_health_by_merchant_id(
+                    merchant_id,
+                    storage::WebhookEndpointHealthUpdate::Degraded {
+                        status,
+                        delivery_count,
+                        failure_count,
+                        degraded_at: now,
+                    },
+                )
+                .await?;
+        }
+        None => {
+            state
+                .store
+                .insert_webhook_endpoint_health(storage::WebhookEndpointHealthNew {
+                    merchant_id: merchant_id.to_owned(),
+                    status,
+                    delivery_count,
+                    failure_count,
+                    degraded_at: Some(now),
+                    last_verified_at: None,
+                    created_at: now,
+                    modified_at: now,
+                })
+                .await?;
+        }
+    }
+
+    logger::warn!(
+        merchant_id = %merchant_id,
+        ?status,
+        delivery_count,
+        failure_count,
+        "Webhook endpoint marked degraded"
+    );
+    notify_merchant(
+        state,
+        merchant_account,
+        status,
+        delivery_count,
+        failure_count,
+    )
+    .await;
+
+    Ok(())
+}
+
+#[cfg(feature = "email")]
+async fn notify_merchant(
+    state: &AppState,
+    merchant_account: &domain::MerchantAccount,
+    status: enums::WebhookEndpointStatus,
+    delivery_count: i32,
+    failure_count: i32,
+) {
+    let primary_email = merchant_account
+        .merchant_details
+        .clone()
+        .parse_value::<api::MerchantDetails>("MerchantDetails")
+        .map_err(|error| logger::error!(merchant_details_parsing_error=?error))
+        .ok()
+        .and_then(|merchant_details| merchant_details.primary_email);
+
+    let Some(primary_email) = primary_email else {
+        logger::warn!("Merchant has no primary email to notify about the webhook endpoint");
+        return;
+    };
+
+    let consequence = match status {
+        enums::WebhookEndpointStatus::Paused => "Deliveries to the endpoint have been paused, and webhooks will be queued until the endpoint is verified again.",
+        _ => "Webhooks will continue to be posted to the endpoint. Please verify the endpoint once it has been fixed.",
+    };
+
+    let result = state
+        .email_client
+        .clone()
+        .send_email(
+            primary_email,
+            "Your webhook endpoint is failing".to_string(),
+            format!("Dear Merchant,\n
+{failure_count} of the last {delivery_count} webhooks posted to your webhook endpoint were not received. {consequence}\n\n
+Thanks,\n
+Team Hyperswitch"),
+        )
+        .await;
+
+    if let Err(error) = result {
+        logger::error!(webhook_endpoint_notification_error=?error);
+    }
+}
+
+#[cfg(not(feature = "email"))]
+async fn notify_merchant(
+    _state: &AppState,
+    merchant_account: &domain::MerchantAccount,
+    status: enums::WebhookEndpointStatus,
+    delivery_count: i32,
+    failure_count: i32,
+) {
+    logger::warn!(
+        merchant_id = %merchant_account.merchant_id,
+        ?status,
+        delivery_count,
+        failure_count,
+        "Email notifications are disabled, merchant not notified about the webhook endpoint"
+    );
+}
+
+async fn get_merchant_key_store(
+    state: &AppState,
+    merchant_id: &str,
+) -> CustomResult<domain::MerchantKeyStore, errors::StorageError> {
+    state
+        .store
+        .get_merchant_key_store_by_merchant_id(
+            merchant_id,
+            &state.store.get_master_key().to_vec().into(),
+        )
+        .await
+}
+
+/// Queues a webhook to be delivered once the paused endpoint of the merchant is verified again.
+/// The body of the webhook is stored encrypted with the key of the merchant.
+#[instrument(skip_all)]
+pub async fn queue_webhook(
+    state: &AppState,
+    merchant_id: &str,
+    event_id: String,
+    request_body: String,
+    signature_headers: Vec<(String, Maskable<String>)>,
+) -> CustomResult<(), errors::WebhooksFlowError> {
+    let key_store = get_merchant_key_store(state, merchant_id)
+        .await
+        .change_context(errors::WebhooksFlowError::OutgoingWebhookQueueingFailed)?;
+
+    let request_body =
+        domain_types::encrypt(Secret::new(request_body), key_store.key.get_inner().peek())
+            .await
+            .change_context(errors::WebhooksFlowError::OutgoingWebhookQueueingFailed)
+            .attach_printable("Failed to encrypt the body of the queued webhook")?;
+
+    let request_headers = Encode::<Vec<(String, String)>>::encode_to_value(
+        &signature_headers
+            .into_iter()
+            .map(|(name, value)| (name, value.into_inner()))
+            .collect::<Vec<_>>(),
+    )
+    .change_context(errors::WebhooksFlowError::OutgoingWebhookQueueingFailed)?;
+
+    state
+        .store
+        .insert_queued_webhook(storage::QueuedWebhookNew {
+            event_id,
+            merchant_id: merchant_id.to_owned(),
+            request_body: request_body.into(),
+            request_headers,
+            created_at: common_utils::date_time::now(),
+        })
+        .await
+        .change_context(errors::WebhooksFlowError::OutgoingWebhookQueueingFailed)?;
+
+    logger::info!("Webhook endpoint is paused, webhook queued");
+
+    Ok(())
+}
+
+/// Delivers the webhooks queued for a merchant, oldest first. Delivery stops at the first webhook
+/// which is not received, leaving it and the webhooks after it queued.
+#[instrument(skip_all)]
+async fn deliver_queued_webhooks(
+    state: &AppState,
+    merchant_account: &domain::MerchantAccount,
+    key_store: &domain::MerchantKeyStore,
+) -> CustomResult<(), errors::WebhooksFlowError> {
+    let webhook_details = get_webhook_details(merchant_account)?;
+
+    loop {
+        let queued_webhooks = state
+            .store
+            .list_queued_webhooks_by_merchant_id(
+                &merchant_account.merchant_id,
+                Some(QUEUED_WEBHOOKS_BATCH_SIZE),
+            )
+            .await
+            .change_context(errors::WebhooksFlowError::OutgoingWebhookQueueingFailed)?;
+
+        if queued_webhooks.is_empty() {
+            return Ok(());
+        }
+
+        for queued_webhook in queued_webhooks {
+            let request_body: crypto::OptionalEncryptableSecretString = domain_types::decrypt(
+                Some(queued_webhook.request_body),
+                key_store.key.get_inner().peek(),
+            )
+            .await
+            .change_context(errors::WebhooksFlowError::OutgoingWebhookQueueingFailed)
+            .attach_printable("Failed to decrypt the body of the queued webhook")?
+            .map(|request_body| request_body.into_inner().expose())
+            .unwrap_or_default();
+
+            let signature_headers = queued_webhook
+                .request_headers
+                .parse_value::<Vec<(String, String)>>("QueuedWebhookHeaders")
+                .change_context(errors::WebhooksFlowError::OutgoingWebhookQueueingFailed)?
+                .into_iter()
+                .map(|(name, value)| (name, value.into()))
+                .collect();
+
+            // The queued webhook is retried by delivering the queue again, so no retry is scheduled
+            send_webhook_to_merchant(
+                state,
+                merchant_account,
+                &webhook_details,
+                WebhookDelivery {
+                    event_id: queued_webhook.event_id,
+                    request_body,
+                    signature_headers,
+                    attempt_number: 1,
+                    retry_on_failure: false,
+                    endpoint: None,
+                },
+            )
+            .await?;
+
+            state
+                .store
+                .delete_queued_webhook_by_id(queued_webhook.id)
+                .await
+                .change_context(errors::WebhooksFlowError::OutgoingWebhookQueueingFailed)?;
+        }
+    }
+}
+
+/// Delivers the webhooks queued while the endpoint of the merchant was paused, and marks the
+/// endpoint healthy once they have all been delivered. If the delivery fails, the endpoint stays
+/// paused, and the remaining webhooks are delivered when the endpoint is verified again.
+#[instrument(skip_all)]
+async fn resume_endpoint(
+    state: &AppState,
+    merchant_account: &domain::MerchantAccount,
+    key_store: &domain::MerchantKeyStore,
+) -> CustomResult<(), errors::WebhooksFlowError> {
+    deliver_queued_webhooks(state, merchant_account, key_store).await?;
+
+    state
+        .store
+        .update_webhook_endpoint_health_by_merchant_id(
+            &merchant_account.merchant_id,
+            storage::WebhookEndpointHealthUpdate::Resumed,
+        )
+        .await
+        .change_context(errors::WebhooksFlowError::OutgoingWebhookQueueingFailed)
+        .attach_printable("Failed to mark the webhook endpoint healthy")?;
+
+    // Webhooks could have been queued after the queue was drained, but before the endpoint was
+    // marked healthy
+    deliver_queued_webhooks(state, merchant_account, key_store).await
+}
+
+fn get_webhook_endpoint_health_response(
+    merchant_id: String,
+    endpoint_health: Option<storage::WebhookEndpointHealth>,
+    (delivery_count, failure_count): (i64, i64),
+) -> api::WebhookEndpointHealthResponse {
+    api::WebhookEndpointHealthResponse {
+        merchant_id,
+        status: endpoint_health
+            .as_ref()
+            .map(|endpoint_health| endpoint_health.status)
+            .unwrap_or_default(),
+        delivery_count,
+        failure_count,
+        degraded_at: endpoint_health
+            .as_ref()
+            .and_then(|endpoint_health| endpoint_health.degraded_at),
+        last_verified_at: endpoint_health
+            .and_then(|endpoint_health| endpoint_health.last_verified_at),
+    }
+}
+
+pub async fn retrieve_webhook_endpoint_health(
+    state: &AppState,
+    merchant_id: String,
+) -> RouterResponse<api::WebhookEndpointHealthResponse> {
+    let endpoint_health = find_endpoint_health(state, &merchant_id)
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)?;
+    let delivery_counts = get_delivery_counts(state, &merchant_id).await;
+
+    Ok(services::ApplicationResponse::Json(
+        get_webhook_endpoint_health_response(merchant_id, endpoint_health, delivery_counts),
+    ))
+}
+
+/// Verifies the webhook endpoint of a merchant by posting a challenge to it, which the endpoint
+/// must echo back in the response body. Once verified, the webhooks queued while it was paused are
+/// delivered in the background, after which the endpoint is marked healthy.
+#[instrument(skip(state))]
+pub async fn verify_webhook_endpoint(
+    state: &AppState,
+    merchant_id: String,
+) -> RouterResponse<api::WebhookEndpointHealthResponse> {
+    let key_store = get_merchant_key_store(state, &merchant_id)
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::MerchantAccountNotFound)?;
+
+    let merchant_account = state
+        .store
+        .find_merchant_account_by_merchant_id(&merchant_id, &key_store)
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::MerchantAccountNotFound)?;
+
+    let webhook_details = get_webhook_details(&merchant_account).change_context(
+        errors::ApiErrorResponse::PreconditionFailed {
+            message: "webhook_details are not configured in the merchant account".to_string(),
+        },
+    )?;
+
+    let challenge = generate_id(CHALLENGE_LENGTH, "whc");
+    let request_body =
+        Encode::<WebhookEndpointChallenge>::encode_to_string_of_json(&WebhookEndpointChallenge {
+            merchant_id: merchant_id.clone(),
+            challenge: challenge.clone(),
+        })
+        .change_context(errors::ApiErrorResponse::InternalServerError)?;
+
+    let signature = merchant_account
+        .payment_response_hash_key
+        .as_ref()
+        .map(|key| crypto::HmacSha512.sign_message(key.as_bytes(), request_body.as_bytes()))
+        .transpose()
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to sign the webhook endpoint challenge")?
+        .map(hex::encode);
+    let signature_headers = signature
+        .map(|signature| {
+            vec![(
+                headers::X_WEBHOOK_SIGNATURE.to_string(),
+                Maskable::from(signature),
+            )]
+        })
+        .unwrap_or_default();
+
+    let (request, _) = build_webhook_request(
+        &merchant_account,
+        &webhook_details,
+        None,
+        request_body,
+        signature_headers,
+    )
+    .change_context(errors::ApiErrorResponse::PreconditionFailed {
+        message: "The webhook endpoint of the merchant is not configured correctly".to_string(),
+    })?;
+
+    let response =
+        services::api::send_request(state, request, Some(super::OUTGOING_WEBHOOK_TIMEOUT_SECS))
+            .await
+            .change_context(errors::ApiErrorResponse::PreconditionFailed {
+                message: "The webhook endpoint could not be reached".to_string(),
+            })?;
+
+    let is_challenge_echoed = response.status().is_success()
+        && response
+            .text()
+            .await
+            .into_report()
+            .change_context(errors::ApiErrorResponse::PreconditionFailed {
+                message: "The response of the webhook endpoint could not be read".to_string(),
+            })?
+            .trim()
+            == challenge;
+
+    if !is_challenge_echoed {
+        Err(errors::ApiErrorResponse::PreconditionFailed {
+            message: "The webhook endpoint did not respond with the challenge".to_string(),
+        })
+        .into_report()?;
+    }
+
+    let now = common_utils::date_time::now();
+    let endpoint_health = match find_endpoint_health(state, &merchant_id)
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)?
+    {
+        Some(_) => {
+            state
+                .store
+                .update_webhook_endpoint_health_by_merchant_id(
+                    &merchant_id,
+                    storage::WebhookEndpointHealthUpdate::Verified {
+                        last_verified_at: now,
+                    },
+                )
+                .await
+        }
+        None => {
+            state
+                .store
+                .insert_webhook_endpoint_health(storage::WebhookEndpointHealthNew {
+                    merchant_id: merchant_id.clone(),
+                    status: enums::WebhookEndpointStatus::Healthy,
+                    delivery_count: 0,
+                    failure_count: 0,
+                    degraded_at: None,
+                    last_verified_at: Some(now),
+                    created_at: now,
+                    modified_at: now,
+                })
+                .await
+        }
+    }
+    .change_context(errors::ApiErrorResponse::InternalServerError)
+    .attach_printable("Failed to update the health of the webhook endpoint")?;
+
+    // Failures before the verification no longer count towards the health of the endpoint
+    if let Ok(redis_conn) = state.store.get_redis_conn() {
+        let (_, failures_key) =
+            get_window_keys(&merchant_id, state.conf.webhooks.endpoint_health.window);
+        if let Err(error) = redis_conn.delete_key(&failures_key).await {
+            logger::error!(webhook_delivery_count_error=?error);
+        }
+    }
+
+    match actix::Arbiter::try_current() {
+        Some(arbiter) => {
+            let state = state.clone();
+            arbiter.spawn(connection::in_current_storage_region(async move {
+                if let Err(error) = resume_endpoint(&state, &merchant_account, &key_store).await {
+                    logger::error!(queued_webhooks_delivery_error=?error);
+                }
+            }));
+        }
+        None => logger::error!("Arbiter retrieval failure, queued webhooks not delivered"),
+    }
+
+    let delivery_counts = get_delivery_counts(state, &merchant_id).await;
+
+    Ok(services::ApplicationResponse::Json(
+        get_webhook_endpoint_health_response(merchant_id, Some(endpoint_health), delivery_counts),
+    ))
+}
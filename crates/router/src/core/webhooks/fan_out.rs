@@ -0,0 +1,145 @@
+//! Fan-out of outgoing webhooks to the webhook endpoints registered for the businesses of a
+//! merchant.
+//!
+//! A business can register webhook endpoints in addition to the webhook endpoint of the merchant,
+//! each with its own filter of the events posted to it and its own signing key. Webhooks about the
+//! payments made under the business, and about their refunds and disputes, are posted to each of
+//! the endpoints accepting the event independently, and their delivery attempts and retries are
+//! tracked separately for every endpoint.
+
+use api_models::admin::{BusinessWebhookEndpoint, PrimaryBusinessDetails};
+use common_utils::ext_traits::ValueExt;
+use error_stack::ResultExt;
+use masking::ExposeInterface;
+use router_env::{instrument, logger, tracing};
+
+use super::{send_webhook_to_merchant, types, WebhookDelivery};
+use crate::{
+    core::{admin, errors, payments::helpers},
+    routes::AppState,
+    types::{api, domain, storage::enums},
+};
+
+/// Returns the webhook endpoints of the business to which the object of the webhook belongs which
+/// accept the event. Failures are logged, so that they do not affect the delivery of the webhook
+/// to the webhook endpoint of the merchant.
+#[instrument(skip_all)]
+pub(super) async fn get_business_webhook_endpoints(
+    state: &AppState,
+    merchant_account: &domain::MerchantAccount,
+    content: &api::OutgoingWebhookContent,
+    event_type: enums::EventType,
+) -> Vec<BusinessWebhookEndpoint> {
+    let payment_id = match content {
+        api::OutgoingWebhookContent::PaymentDetails(payment) => {
+            return get_webhook_endpoints_of_business(
+                merchant_account,
+                payment.business_country,
+                &payment.business_label,
+                event_type,
+            );
+        }
+        api::OutgoingWebhookContent::RefundDetails(refund) => &refund.payment_id,
+        api::OutgoingWebhookContent::DisputeDetails(dispute) => &dispute.payment_id,
+        _ => return Vec::new(),
+    };
+
+    match state
+        .store
+        .find_payment_intent_by_payment_id_merchant_id(
+            payment_id,
+            &merchant_account.merchant_id,
+            merchant_account.storage_scheme,
+        )
+        .await
+    {
+        Ok(payment_intent) => get_webhook_endpoints_of_business(
+            merchant_account,
+            payment_intent.business_country,
+            &payment_intent.business_label,
+            event_type,
+        ),
+        Err(error) => {
+            logger::error!(business_webhook_endpoints_error=?error);
+            Vec::new()
+        }
+    }
+}
+
+fn get_webhook_endpoints_of_business(
+    merchant_account: &domain::MerchantAccount,
+    business_country: enums::CountryAlpha2,
+    business_label: &str,
+    event_type: enums::EventType,
+) -> Vec<BusinessWebhookEndpoint> {
+    helpers::get_business_profile_defaults(business_country, business_label, merchant_account)
+        .map_err(|error| logger::error!(business_webhook_endpoints_error=?error))
+        .ok()
+        .flatten()
+        .and_then(|business_details| business_details.webhook_endpoints)
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|endpoint| {
+            endpoint
+                .enabled_events
+                .as_ref()
+                .map_or(true, |enabled_events| enabled_events.contains(&event_type))
+        })
+        .collect()
+}
+
+/// Finds a webhook endpoint among the webhook endpoints registered for the businesses of the
+/// merchant.
+pub(super) fn find_business_webhook_endpoint(
+    merchant_account: &domain::MerchantAccount,
+    endpoint_id: &str,
+) -> errors::CustomResult<Option<BusinessWebhookEndpoint>, errors::WebhooksFlowError> {
+    let primary_business_details = merchant_account
+        .primary_business_details
+        .clone()
+        .parse_value::<Vec<PrimaryBusinessDetails>>("PrimaryBusinessDetails")
+        .change_context(errors::WebhooksFlowError::MerchantConfigNotFound)?;
+
+    Ok(primary_business_details
+        .into_iter()
+        .flat_map(|business_details| business_details.webhook_endpoints.unwrap_or_default())
+        .find(|endpoint| endpoint.endpoint_id == endpoint_id))
+}
+
+/// Posts a webhook to a webhook endpoint of a business, signing it with the key of the endpoint,
+/// and with the webhook signing key pair of the merchant if it has one.
+pub(super) async fn send_webhook_to_business_endpoint<W: types::OutgoingWebhookType>(
+    state: &AppState,
+    merchant_account: &domain::MerchantAccount,
+    webhook_details: &api::WebhookDetails,
+    webhook: &W,
+    event_id: String,
+    request_body: String,
+    endpoint: BusinessWebhookEndpoint,
+) -> errors::CustomResult<(), errors::WebhooksFlowError> {
+    let signing_key = admin::get_business_webhook_secrets(merchant_account)
+        .change_context(errors::WebhooksFlowError::MerchantConfigNotFound)?
+        .remove(&endpoint.endpoint_id)
+        .map(ExposeInterface::expose)
+        .or_else(|| merchant_account.payment_response_hash_key.clone());
+
+    let mut signature_headers = Vec::new();
+    if let Some(signature) = webhook.get_outgoing_webhooks_signature(signing_key)? {
+        W::add_webhook_header(&mut signature_headers, signature)
+    }
+
+    send_webhook_to_merchant(
+        state,
+        merchant_account,
+        webhook_details,
+        WebhookDelivery {
+            event_id,
+            request_body,
+            signature_headers,
+            attempt_number: 1,
+            retry_on_failure: true,
+            endpoint: Some(endpoint),
+        },
+    )
+    .await
+}
@@ -0,0 +1,553 @@
+//! Asynchronous processing of incoming webhooks.
+//!
+//! Incoming webhooks which have to be processed further are added to one of two Redis streams,
+//! depending on their priority, and acknowledged to the connector right away. A pool of workers
+//! reads the webhooks from the streams, always preferring the high priority stream, and processes
+//! them with a bounded number of webhooks of the same connector being processed at a time.
+//! Entries are removed from the streams only after they are processed, so that webhooks picked up
+//! by a worker which stopped midway are processed again once it is restarted. Webhooks whose
+//! processing fails are added back to their stream after an exponentially growing delay, without
+//! holding up the worker in the meantime.
+
+use std::{collections::HashMap, str::FromStr, sync::Arc, time::Duration};
+
+use actix_web::http::header::{HeaderMap, HeaderName, HeaderValue};
+use base64::Engine;
+use common_utils::ext_traits::StringExt;
+use error_stack::{IntoReport, ResultExt};
+use redis_interface::{RedisConnectionPool, RedisEntryId};
+use router_env::{instrument, logger, tracing};
+use tokio::sync::Semaphore;
+
+use super::MERCHANT_ID;
+use crate::{
+    configs::settings::WebhookQueueSettings,
+    connection, consts,
+    core::{
+        errors::{self, RouterResult},
+        metrics,
+    },
+    routes::AppState,
+    types::api,
+    utils::{self, Encode},
+};
+
+const WEBHOOK_FIELD: &str = "webhook";
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum WebhookPriority {
+    High,
+    Low,
+}
+
+impl WebhookPriority {
+    /// Webhooks which inform about payments, refunds, disputes and payouts reaching a terminal
+    /// state are processed before informational webhooks.
+    fn from_event_type(event_type: &api::IncomingWebhookEvent) -> Self {
+        match event_type {
+            api::IncomingWebhookEvent::PaymentIntentSuccess
+            | api::IncomingWebhookEvent::PaymentIntentFailure
+            | api::IncomingWebhookEvent::RefundSuccess
+            | api::IncomingWebhookEvent::RefundFailure
+            | api::IncomingWebhookEvent::DisputeAccepted
+            | api::IncomingWebhookEvent::DisputeCancelled
+            | api::IncomingWebhookEvent::DisputeExpired
+            | api::IncomingWebhookEvent::DisputeWon
+            | api::IncomingWebhookEvent::DisputeLost
+            | api::IncomingWebhookEvent::PayoutSuccess
+            | api::IncomingWebhookEvent::PayoutFailure
+            | api::IncomingWebhookEvent::PayoutReversed => Self::High,
+            api::IncomingWebhookEvent::PaymentIntentProcessing
+            | api::IncomingWebhookEvent::PaymentIntentPartiallyFunded
+            | api::IncomingWebhookEvent::PaymentActionRequired
+            | api::IncomingWebhookEvent::EventNotSupported
+            | api::IncomingWebhookEvent::SourceChargeable
+            | api::IncomingWebhookEvent::SourceTransactionCreated
+            | api::IncomingWebhookEvent::DisputeOpened
+            | api::IncomingWebhookEvent::DisputeChallenged
+            | api::IncomingWebhookEvent::EndpointVerification
+            | api::IncomingWebhookEvent::PayoutInitiated => Self::Low,
+        }
+    }
+
+    fn stream_name(self, config: &WebhookQueueSettings) -> &str {
+        match self {
+            Self::High => &config.high_priority_stream,
+            Self::Low => &config.low_priority_stream,
+        }
+    }
+}
+
+/// An incoming webhook as received from the connector, along with the number of times
+/// processing it was attempted.
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+struct QueuedWebhook {
+    merchant_id: String,
+    connector_name: String,
+    method: String,
+    headers: Vec<(String, String)>,
+    query_params: String,
+    /// The base64 encoded body of the webhook
+    body: String,
+    attempts: u32,
+}
+
+fn get_redis_conn(state: &AppState) -> RouterResult<Arc<RedisConnectionPool>> {
+    state
+        .store
+        .get_redis_conn()
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to get redis connection")
+}
+
+async fn append_to_stream(
+    redis_conn: &RedisConnectionPool,
+    stream: &str,
+    webhook: &QueuedWebhook,
+) -> RouterResult<()> {
+    let webhook = Encode::<QueuedWebhook>::encode_to_string_of_json(webhook)
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to serialize the queued webhook")?;
+
+    redis_conn
+        .stream_append_entry(
+            stream,
+            &RedisEntryId::AutoGeneratedID,
+            vec![(WEBHOOK_FIELD, webhook)],
+        )
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to add the webhook to the webhook queue")
+}
+
+/// Adds an incoming webhook to the webhook queue. Webhooks are rejected once the stream they
+/// belong to is full, so that the connector retries delivering them later.
+#[instrument(skip_all)]
+pub async fn enqueue_incoming_webhook(
+    state: &AppState,
+    merchant_id: &str,
+    connector_name: &str,
+    request_details: &api::IncomingWebhookRequestDetails<'_>,
+    event_type: &api::IncomingWebhookEvent,
+) -> RouterResult<()> {
+    let config = &state.conf.webhook_queue;
+    let priority = WebhookPriority::from_event_type(event_type);
+    let stream = priority.stream_name(config);
+    let redis_conn = get_redis_conn(state)?;
+
+    let queue_length = redis_conn
+        .stream_get_length(stream)
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to get the length of the webhook queue")?;
+
+    if queue_length >= config.max_queue_length {
+        logger::warn!(%stream, queue_length, "Webhook queue is full, rejecting webhook");
+        metrics::WEBHOOK_QUEUE_REJECTED_COUNT.add(
+            &metrics::CONTEXT,
+            1,
+            &[metrics::KeyValue::new(
+                "connector",
+                connector_name.to_owned(),
+            )],
+        );
+        return Err(errors::ApiErrorResponse::WebhookQueueFull.into());
+    }
+
+    let headers = request_details
+        .headers
+        .iter()
+        .filter_map(|(name, value)| {
+            value
+                .to_str()
+                .ok()
+                .map(|value| (name.to_string(), value.to_owned()))
+        })
+        .collect();
+
+    let webhook = QueuedWebhook {
+        merchant_id: merchant_id.to_owned(),
+        connector_name: connector_name.to_owned(),
+        method: request_details.method.to_string(),
+        headers,
+        query_params: request_details.query_params.clone(),
+        body: consts::BASE64_ENGINE.encode(request_details.body),
+        attempts: 0,
+    };
+
+    append_to_stream(&redis_conn, stream, &webhook).await?;
+
+    logger::info!(%stream, ?priority, "Webhook added to the webhook queue");
+    metrics::WEBHOOK_QUEUED_COUNT.add(
+        &metrics::CONTEXT,
+        1,
+        &[
+            metrics::KeyValue::new(MERCHANT_ID, merchant_id.to_owned()),
+            metrics::KeyValue::new("connector", connector_name.to_owned()),
+        ],
+    );
+
+    Ok(())
+}
+
+async fn process_queued_webhook(state: &AppState, webhook: &QueuedWebhook) -> RouterResult<()> {
+    let db = &*state.store;
+    let key_store = db
+        .get_merchant_key_store_by_merchant_id(
+            &webhook.merchant_id,
+            &db.get_master_key().to_vec().into(),
+        )
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to fetch merchant key store for the queued webhook")?;
+
+    let merchant_account = db
+        .find_merchant_account_by_merchant_id(&webhook.merchant_id, &key_store)
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to fetch merchant account for the queued webhook")?;
+
+    let method = actix_web::http::Method::from_str(&webhook.method)
+        .into_report()
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Invalid method in the queued webhook")?;
+
+    let mut headers = HeaderMap::new();
+    for (name, value) in &webhook.headers {
+        if let (Ok(name), Ok(value)) = (HeaderName::from_str(name), HeaderValue::from_str(value)) {
+            headers.append(name, value);
+        }
+    }
+
+    let body = consts::BASE64_ENGINE
+        .decode(&webhook.body)
+        .into_report()
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to decode the body of the queued webhook")?;
+
+    let request_details = api::IncomingWebhookRequestDetails {
+        method,
+        headers: &headers,
+        query_params: webhook.query_params.clone(),
+        body: &body,
+    };
+
+    let storage_region = merchant_account.storage_region.clone();
+    connection::with_storage_region(
+        storage_region,
+        super::incoming_webhooks_core::<api_models::webhooks::OutgoingWebhook>(
+            state,
+            request_details,
+            merchant_account,
+            key_store,
+            &webhook.connector_name,
+            false,
+        ),
+    )
+    .await
+    .map(|_| ())
+}
+
+/// Limits the number of webhooks of each connector processed at the same time.
+#[derive(Clone)]
+struct ConnectorConcurrencyLimiter {
+    permits: usize,
+    semaphores: Arc<std::sync::Mutex<HashMap<String, Arc<Semaphore>>>>,
+}
+
+impl ConnectorConcurrencyLimiter {
+    fn new(permits: usize) -> Self {
+        Self {
+            permits,
+            semaphores: Arc::new(std::sync::Mutex::new(HashMap::new())),
+        }
+    }
+
+    fn get_semaphore(&self, connector_name: &str) -> Arc<Semaphore> {
+        let mut semaphores = self
+            .semaphores
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+        Arc::clone(
+            semaphores
+                .entry(connector_name.to_owned())
+                .or_insert_with(|| Arc::new(Semaphore::new(self.permits))),
+        )
+    }
+}
+
+/// Reads a single entry from the stream. When `pending_after` is passed, the entry is read from
+/// the entries after it which were read by the consumer earlier but never acknowledged.
+async fn read_entry(
+    redis_conn: &RedisConnectionPool,
+    config: &WebhookQueueSettings,
+    stream: &str,
+    consumer_name: &str,
+    pending_after: Option<&str>,
+) -> Option<(String, Option<String>)> {
+    let entry_id = pending_after.map_or(RedisEntryId::UndeliveredEntryID, get_entry_id);
+
+    redis_conn
+        .stream_read_with_options(
+            stream,
+            entry_id,
+            Some(1),
+            None,
+            Some((&config.consumer_group, consumer_name)),
+        )
+        .await
+        .map_err(|error| logger::debug!(webhook_queue_read_error=?error))
+        .ok()?
+        .remove(stream)?
+        .into_iter()
+        .next()
+        .map(|(entry_id, mut fields)| (entry_id, fields.remove(WEBHOOK_FIELD).flatten()))
+}
+
+/// The delay before the retry of a webhook whose processing failed `attempts` times, doubled for
+/// each further attempt.
+fn get_retry_delay(retry_interval: u64, attempts: u32) -> Duration {
+    Duration::from_millis(
+        retry_interval.saturating_mul(2_u64.saturating_pow(attempts.saturating_sub(1))),
+    )
+}
+
+/// Parses the identifier of a stream entry, of the form `<milliseconds>-<sequence number>`.
+fn get_entry_id(entry_id: &str) -> RedisEntryId {
+    let (milliseconds, sequence_number) = entry_id.split_once('-').unwrap_or((entry_id, "0"));
+    RedisEntryId::UserSpecifiedID {
+        milliseconds: milliseconds.to_owned(),
+        sequence_number: sequence_number.to_owned(),
+    }
+}
+
+/// Acknowledges an entry and removes it from the stream.
+async fn remove_entry(
+    redis_conn: &RedisConnectionPool,
+    consumer_group: &str,
+    stream: &str,
+    entry_id: String,
+) {
+    if let Err(error) = redis_conn
+        .stream_acknowledge_entries(stream, consumer_group, vec![entry_id.clone()])
+        .await
+    {
+        logger::error!(webhook_queue_acknowledge_error=?error);
+    }
+    if let Err(error) = redis_conn
+        .stream_delete_entries(stream, vec![entry_id])
+        .await
+    {
+        logger::error!(webhook_queue_delete_error=?error);
+    }
+}
+
+/// Adds a webhook whose processing failed back to its stream once the retry delay has elapsed, and
+/// only then removes its original entry. The original entry stays pending in the meantime, so that
+/// the webhook is still retried if the instance stops before.
+#[instrument(skip_all, fields(stream, entry_id))]
+async fn requeue_after_delay(
+    redis_conn: Arc<RedisConnectionPool>,
+    consumer_group: String,
+    stream: String,
+    entry_id: String,
+    webhook: QueuedWebhook,
+    delay: Duration,
+) {
+    tracing::Span::current().record("stream", stream.as_str());
+    tracing::Span::current().record("entry_id", entry_id.as_str());
+
+    tokio::time::sleep(delay).await;
+    if let Err(error) = append_to_stream(&redis_conn, &stream, &webhook).await {
+        // The entry is left unacknowledged so that it is retried once the worker is restarted
+        logger::error!(webhook_requeue_error=?error);
+        return;
+    }
+    remove_entry(&redis_conn, &consumer_group, &stream, entry_id).await;
+}
+
+#[instrument(skip_all, fields(stream, entry_id))]
+async fn handle_entry(
+    state: &AppState,
+    redis_conn: &Arc<RedisConnectionPool>,
+    limiter: &ConnectorConcurrencyLimiter,
+    stream: &str,
+    entry_id: String,
+    webhook: Option<String>,
+) {
+    let config = &state.conf.webhook_queue;
+    tracing::Span::current().record("stream", stream);
+    tracing::Span::current().record("entry_id", entry_id.as_str());
+
+    match webhook.map(|webhook| webhook.parse_struct::<QueuedWebhook>("QueuedWebhook")) {
+        Some(Ok(mut webhook)) => {
+            let semaphore = limiter.get_semaphore(&webhook.connector_name);
+            let result = match semaphore.acquire().await {
+                Ok(_permit) => process_queued_webhook(state, &webhook).await,
+                Err(error) => Err(error)
+                    .into_report()
+                    .change_context(errors::ApiErrorResponse::InternalServerError),
+            };
+
+            if let Err(error) = result {
+                logger::error!(queued_webhook_error=?error, attempts = webhook.attempts);
+                let connector = metrics::KeyValue::new("connector", webhook.connector_name.clone());
+
+                if webhook.attempts < config.max_retries {
+                    webhook.attempts += 1;
+                    metrics::WEBHOOK_QUEUE_RETRIED_COUNT.add(&metrics::CONTEXT, 1, &[connector]);
+                    let delay = get_retry_delay(config.retry_interval, webhook.attempts);
+                    tokio::spawn(requeue_after_delay(
+                        Arc::clone(redis_conn),
+                        config.consumer_group.clone(),
+                        stream.to_owned(),
+                        entry_id,
+                        webhook,
+                        delay,
+                    ));
+                    return;
+                }
+
+                metrics::WEBHOOK_QUEUE_DISCARDED_COUNT.add(&metrics::CONTEXT, 1, &[connector]);
+                logger::error!(
+                    merchant_id = %webhook.merchant_id,
+                    connector = %webhook.connector_name,
+                    "Discarding webhook after exhausting all retries"
+                );
+            }
+        }
+        Some(Err(error)) => logger::error!(queued_webhook_parsing_error=?error),
+        None => logger::error!("Webhook queue entry does not contain a webhook"),
+    }
+
+    remove_entry(redis_conn, &config.consumer_group, stream, entry_id).await;
+}
+
+async fn run_worker(state: AppState, consumer_name: String, limiter: ConnectorConcurrencyLimiter) {
+    let config = state.conf.webhook_queue.clone();
+    let redis_conn = match get_redis_conn(&state) {
+        Ok(redis_conn) => redis_conn,
+        Err(error) => {
+            logger::error!(webhook_worker_error=?error, "Failed to start webhook worker");
+            return;
+        }
+    };
+    let streams = [
+        config.high_priority_stream.as_str(),
+        config.low_priority_stream.as_str(),
+    ];
+
+    // Process the webhooks left unacknowledged by an earlier run of this worker first, moving past
+    // each of them so that an entry which cannot be removed from its stream is not read again
+    let mut pending_after = streams.map(|_| "0-0".to_owned());
+    let mut pending = true;
+
+    loop {
+        let mut entry = None;
+        for (stream, after) in streams.iter().zip(pending_after.iter_mut()) {
+            if let Some((entry_id, webhook)) = read_entry(
+                &redis_conn,
+                &config,
+                stream,
+                &consumer_name,
+                pending.then_some(after.as_str()),
+            )
+            .await
+            {
+                if pending {
+                    *after = entry_id.clone();
+                }
+                entry = Some((*stream, entry_id, webhook));
+                break;
+            }
+        }
+
+        match entry {
+            Some((stream, entry_id, webhook)) => {
+                handle_entry(&state, &redis_conn, &limiter, stream, entry_id, webhook).await
+            }
+            None if pending => pending = false,
+            None => tokio::time::sleep(Duration::from_millis(config.poll_interval)).await,
+        }
+    }
+}
+
+/// The name identifying the instance among the consumers of the webhook queue: the host name of
+/// the instance, so that an instance restarted on the same host resumes the webhooks left pending,
+/// or a random identifier when the host name is not known.
+fn get_instance_name() -> String {
+    std::env::var("HOSTNAME")
+        .ok()
+        .filter(|hostname| !hostname.is_empty())
+        .unwrap_or_else(|| utils::generate_id(consts::ID_LENGTH, "instance"))
+}
+
+/// Starts the workers which process the webhooks added to the webhook queue.
+pub async fn start_webhook_workers(state: AppState) {
+    let config = &state.conf.webhook_queue;
+
+    let redis_conn = match get_redis_conn(&state) {
+        Ok(redis_conn) => redis_conn,
+        Err(error) => {
+            logger::error!(webhook_worker_error=?error, "Failed to start webhook workers");
+            return;
+        }
+    };
+
+    for stream in [&config.high_priority_stream, &config.low_priority_stream] {
+        if redis_conn
+            .consumer_group_create(stream, &config.consumer_group, &RedisEntryId::AfterLastID)
+            .await
+            .is_err()
+        {
+            logger::info!(%stream, "Webhook queue consumer group already exists");
+        }
+    }
+
+    let limiter = ConnectorConcurrencyLimiter::new(config.max_concurrency_per_connector);
+    let instance_name = get_instance_name();
+    for worker in 0..config.workers {
+        tokio::spawn(run_worker(
+            state.clone(),
+            format!("{}_{instance_name}_{worker}", config.consumer_name_prefix),
+            limiter.clone(),
+        ));
+    }
+    logger::info!(workers = config.workers, "Started webhook workers");
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+
+    #[test]
+    fn test_get_retry_delay() {
+        assert_eq!(get_retry_delay(5000, 1), Duration::from_millis(5000));
+        assert_eq!(get_retry_delay(5000, 2), Duration::from_millis(10000));
+        assert_eq!(get_retry_delay(5000, 5), Duration::from_millis(80000));
+        assert_eq!(
+            get_retry_delay(u64::MAX, 3),
+            Duration::from_millis(u64::MAX)
+        );
+    }
+
+    #[test]
+    fn test_get_entry_id() {
+        assert!(matches!(
+            get_entry_id("1695976800000-3"),
+            RedisEntryId::UserSpecifiedID {
+                milliseconds,
+                sequence_number,
+            } if milliseconds == "1695976800000" && sequence_number == "3"
+        ));
+        assert!(matches!(
+            get_entry_id("0-0"),
+            RedisEntryId::UserSpecifiedID {
+                milliseconds,
+                sequence_number,
+            } if milliseconds == "0" && sequence_number == "0"
+        ));
+    }
+}
@@ -0,0 +1,391 @@
+//! Retries of outgoing webhooks which were not received by the webhook endpoint.
+//!
+//! Every delivery of a webhook is recorded as an attempt. When an attempt fails, a retry is
+//! scheduled through the process tracker after a delay which doubles with every retry, until the
+//! number of retries configured by the merchant is exhausted. The body of the webhook is stored
+//! encrypted with the attempt which schedules the retry, so that the retry posts the same body.
+//! The attempts and retries of the deliveries to the webhook endpoints of the businesses of the
+//! merchant are tracked separately for every endpoint.
+
+use common_utils::{crypto, ext_traits::ValueExt};
+use error_stack::{IntoReport, ResultExt};
+use masking::{ExposeInterface, PeekInterface, Secret};
+use router_env::{instrument, logger, tracing};
+
+use super::{
+    endpoint_health, fan_out, get_webhook_details, send_webhook_to_merchant, WebhookDelivery,
+};
+use crate::{
+    core::errors::{self, CustomResult, RouterResponse},
+    routes::AppState,
+    scheduler::utils as pt_utils,
+    services,
+    types::{
+        api, domain,
+        domain::types as domain_types,
+        storage::{self, ProcessTrackerExt},
+    },
+    utils::Encode,
+};
+
+const OUTGOING_WEBHOOK_RETRY_RUNNER: &str = "OUTGOING_WEBHOOK_RETRY_WORKFLOW";
+const OUTGOING_WEBHOOK_RETRY_TASK: &str = "OUTGOING_WEBHOOK_RETRY";
+
+/// The outcome of an attempt to post a webhook to the endpoint of a merchant
+pub(super) struct DeliveryOutcome {
+    pub is_delivered: bool,
+    pub response_status_code: Option<u16>,
+    pub error_message: Option<String>,
+}
+
+/// Returns the delay (in seconds) before the given retry of a webhook, starting from 1 for the
+/// first retry. The delay doubles with every retry, up to `max_delay`.
+fn get_retry_delay(initial_delay: u32, retry_number: i32, max_delay: u32) -> u32 {
+    let exponent = u32::try_from(retry_number.saturating_sub(1)).unwrap_or_default();
+    2_u32
+        .checked_pow(exponent)
+        .and_then(|multiplier| initial_delay.checked_mul(multiplier))
+        .map_or(max_delay, |delay| delay.min(max_delay))
+}
+
+/// Returns the time at which the next attempt of a webhook is to be made, if a retry of the
+/// webhook is to be scheduled after the given attempt.
+fn get_next_retry_time(
+    state: &AppState,
+    webhook_details: &api::WebhookDetails,
+    attempt_number: i32,
+) -> Option<time::PrimitiveDateTime> {
+    let config = &state.conf.webhooks.retry;
+    if !config.enabled {
+        return None;
+    }
+
+    let max_retries = webhook_details
+        .max_retries
+        .unwrap_or(config.default_max_retries);
+    // The first attempt is not a retry, so the attempt numbered `n` is followed by retry `n`
+    if attempt_number > i32::from(max_retries) {
+        return None;
+    }
+
+    let initial_delay = webhook_details
+        .retry_initial_delay
+        .unwrap_or(config.default_initial_delay);
+    let delay = get_retry_delay(initial_delay, attempt_number, config.max_delay);
+
+    Some(common_utils::date_time::now().saturating_add(time::Duration::seconds(delay.into())))
+}
+
+/// Records an attempt to post a webhook to the endpoint of a merchant, and schedules a retry of
+/// the webhook if it was not received. Failures are logged, so that they do not affect the
+/// delivery of the webhook.
+#[instrument(skip_all)]
+pub(super) async fn record_delivery_attempt(
+    state: &AppState,
+    merchant_account: &domain::MerchantAccount,
+    webhook_details: &api::WebhookDetails,
+    delivery: &WebhookDelivery,
+    outcome: DeliveryOutcome,
+) {
+    if let Err(error) =
+        insert_delivery_attempt(state, merchant_account, webhook_details, delivery, outcome).await
+    {
+        logger::error!(webhook_delivery_attempt_error=?error);
+    }
+}
+
+async fn insert_delivery_attempt(
+    state: &AppState,
+    merchant_account: &domain::MerchantAccount,
+    webhook_details: &api::WebhookDetails,
+    delivery: &WebhookDelivery,
+    outcome: DeliveryOutcome,
+) -> CustomResult<(), errors::WebhooksFlowError> {
+    let next_retry_at = if !outcome.is_delivered && delivery.retry_on_failure {
+        get_next_retry_time(state, webhook_details, delivery.attempt_number)
+    } else {
+        None
+    };
+
+    let endpoint_id = delivery
+        .endpoint
+        .as_ref()
+        .map(|endpoint| endpoint.endpoint_id.clone());
+
+    let (request_body, request_headers) = match next_retry_at {
+        Some(_) => {
+            let key_store = state
+                .store
+                .get_merchant_key_store_by_merchant_id(
+                    &merchant_account.merchant_id,
+                    &state.store.get_master_key().to_vec().into(),
+                )
+                .await
+                .change_context(errors::WebhooksFlowError::OutgoingWebhookRetrySchedulingFailed)?;
+
+            let request_body = domain_types::encrypt(
+                Secret::new(delivery.request_body.clone()),
+                key_store.key.get_inner().peek(),
+            )
+            .await
+            .change_context(errors::WebhooksFlowError::OutgoingWebhookRetrySchedulingFailed)
+            .attach_printable("Failed to encrypt the body of the webhook")?;
+
+            let request_headers = Encode::<Vec<(String, String)>>::encode_to_value(
+                &delivery
+                    .signature_headers
+                    .iter()
+                    .map(|(name, value)| (name.clone(), value.clone().into_inner()))
+                    .collect::<Vec<_>>(),
+            )
+            .change_context(errors::WebhooksFlowError::OutgoingWebhookRetrySchedulingFailed)?;
+
+            (Some(request_body.into()), Some(request_headers))
+        }
+        None => (None, None),
+    };
+
+    state
+        .store
+        .insert_webhook_delivery_attempt(storage::WebhookDeliveryAttemptNew {
+            event_id: delivery.event_id.clone(),
+            merchant_id: merchant_account.merchant_id.clone(),
+            attempt_number: delivery.attempt_number,
+            is_delivered: outcome.is_delivered,
+            response_status_code: outcome.response_status_code.map(i32::from),
+            error_message: outcome.error_message,
+            request_body,
+            request_headers,
+            next_retry_at,
+            created_at: common_utils::date_time::now(),
+            endpoint_id: endpoint_id.clone(),
+        })
+        .await
+        .change_context(errors::WebhooksFlowError::OutgoingWebhookRetrySchedulingFailed)?;
+
+    if let Some(next_retry_at) = next_retry_at {
+        add_outgoing_webhook_retry_task(
+            state,
+            &merchant_account.merchant_id,
+            &delivery.event_id,
+            endpoint_id,
+            delivery.attempt_number + 1,
+            next_retry_at,
+        )
+        .await?;
+    }
+
+    Ok(())
+}
+
+async fn add_outgoing_webhook_retry_task(
+    state: &AppState,
+    merchant_id: &str,
+    event_id: &str,
+    endpoint_id: Option<String>,
+    attempt_number: i32,
+    schedule_time: time::PrimitiveDateTime,
+) -> CustomResult<(), errors::WebhooksFlowError> {
+    let task_reference = match &endpoint_id {
+        Some(endpoint_id) => format!("{event_id}_{endpoint_id}_{attempt_number}"),
+        None => format!("{event_id}_{attempt_number}"),
+    };
+    let tracking_data = storage::OutgoingWebhookRetryWorkflow {
+        merchant_id: merchant_id.to_owned(),
+        event_id: event_id.to_owned(),
+        attempt_number,
+        endpoint_id,
+    };
+    let process_tracker_entry = <storage::ProcessTracker>::make_process_tracker_new(
+        pt_utils::get_process_tracker_id(
+            OUTGOING_WEBHOOK_RETRY_RUNNER,
+            OUTGOING_WEBHOOK_RETRY_TASK,
+            &task_reference,
+            merchant_id,
+        ),
+        OUTGOING_WEBHOOK_RETRY_TASK,
+        OUTGOING_WEBHOOK_RETRY_RUNNER,
+        tracking_data,
+        schedule_time,
+    )
+    .change_context(errors::WebhooksFlowError::OutgoingWebhookRetrySchedulingFailed)?;
+
+    state
+        .store
+        .insert_process(process_tracker_entry)
+        .await
+        .change_context(errors::WebhooksFlowError::OutgoingWebhookRetrySchedulingFailed)
+        .attach_printable("Failed to insert the outgoing webhook retry task to process tracker")?;
+
+    logger::info!(attempt_number, %schedule_time, "Outgoing webhook retry scheduled");
+
+    Ok(())
+}
+
+/// Makes a scheduled attempt to deliver a webhook, posting the body stored with the previous
+/// attempt. If the endpoint of the merchant has been paused since, the webhook is queued instead.
+/// Retries to a webhook endpoint of a business which has been removed since are dropped.
+#[instrument(skip_all)]
+pub async fn retry_webhook_delivery(
+    state: &AppState,
+    tracking_data: &storage::OutgoingWebhookRetryWorkflow,
+) -> CustomResult<(), errors::WebhooksFlowError> {
+    let key_store = state
+        .store
+        .get_merchant_key_store_by_merchant_id(
+            &tracking_data.merchant_id,
+            &state.store.get_master_key().to_vec().into(),
+        )
+        .await
+        .change_context(errors::WebhooksFlowError::MerchantConfigNotFound)?;
+
+    let merchant_account = state
+        .store
+        .find_merchant_account_by_merchant_id(&tracking_data.merchant_id, &key_store)
+        .await
+        .change_context(errors::WebhooksFlowError::MerchantConfigNotFound)?;
+
+    let webhook_details = get_webhook_details(&merchant_account)?;
+
+    let previous_attempt = state
+        .store
+        .find_webhook_delivery_attempt_by_merchant_id_event_id_endpoint_id_attempt_number(
+            &tracking_data.merchant_id,
+            &tracking_data.event_id,
+            tracking_data.endpoint_id.as_deref(),
+            tracking_data.attempt_number - 1,
+        )
+        .await
+        .change_context(errors::WebhooksFlowError::ResourceNotFound)
+        .attach_printable("Failed to find the attempt which scheduled the retry")?;
+
+    let request_body: crypto::OptionalEncryptableSecretString = domain_types::decrypt(
+        previous_attempt.request_body,
+        key_store.key.get_inner().peek(),
+    )
+    .await
+    .change_context(errors::WebhooksFlowError::OutgoingWebhookEncodingFailed)
+    .attach_printable("Failed to decrypt the body of the webhook")?;
+    let request_body = request_body
+        .map(|request_body| request_body.into_inner().expose())
+        .ok_or(errors::WebhooksFlowError::MissingRequiredField {
+            field_name: "request_body",
+        })
+        .into_report()?;
+
+    let signature_headers = previous_attempt
+        .request_headers
+        .map(|request_headers| {
+            request_headers.parse_value::<Vec<(String, String)>>("WebhookDeliveryAttemptHeaders")
+        })
+        .transpose()
+        .change_context(errors::WebhooksFlowError::OutgoingWebhookEncodingFailed)?
+        .unwrap_or_default()
+        .into_iter()
+        .map(|(name, value)| (name, value.into()))
+        .collect();
+
+    let endpoint = match &tracking_data.endpoint_id {
+        Some(endpoint_id) => {
+            match fan_out::find_business_webhook_endpoint(&merchant_account, endpoint_id)? {
+                Some(endpoint) => Some(endpoint),
+                None => {
+                    logger::info!(endpoint_id, "Webhook endpoint of the business not found");
+                    return Ok(());
+                }
+            }
+        }
+        None => None,
+    };
+
+    if endpoint.is_none()
+        && endpoint_health::is_endpoint_paused(state, &tracking_data.merchant_id).await
+    {
+        return endpoint_health::queue_webhook(
+            state,
+            &tracking_data.merchant_id,
+            tracking_data.event_id.clone(),
+            request_body,
+            signature_headers,
+        )
+        .await;
+    }
+
+    // A webhook which is not received is retried again by the task scheduled while recording the
+    // attempt, so the failure does not fail this task
+    if let Err(error) = send_webhook_to_merchant(
+        state,
+        &merchant_account,
+        &webhook_details,
+        WebhookDelivery {
+            event_id: tracking_data.event_id.clone(),
+            request_body,
+            signature_headers,
+            attempt_number: tracking_data.attempt_number,
+            retry_on_failure: true,
+            endpoint,
+        },
+    )
+    .await
+    {
+        logger::warn!(outgoing_webhook_retry_error=?error);
+    }
+
+    Ok(())
+}
+
+pub async fn list_webhook_delivery_attempts(
+    state: &AppState,
+    merchant_account: domain::MerchantAccount,
+    event_id: String,
+) -> RouterResponse<api_models::webhooks::WebhookDeliveryAttemptsResponse> {
+    let delivery_attempts = state
+        .store
+        .find_webhook_delivery_attempts_by_merchant_id_event_id(
+            &merchant_account.merchant_id,
+            &event_id,
+        )
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)?;
+
+    if delivery_attempts.is_empty() {
+        Err(errors::ApiErrorResponse::GenericNotFoundError {
+            message: "No webhook delivery attempts found for the given event".to_string(),
+        })
+        .into_report()?
+    }
+
+    Ok(services::ApplicationResponse::Json(
+        api_models::webhooks::WebhookDeliveryAttemptsResponse {
+            event_id,
+            attempts: delivery_attempts
+                .into_iter()
+                .map(|attempt| api_models::webhooks::WebhookDeliveryAttempt {
+                    endpoint_id: attempt.endpoint_id,
+                    attempt_number: attempt.attempt_number,
+                    is_delivered: attempt.is_delivered,
+                    response_status_code: attempt.response_status_code,
+                    error_message: attempt.error_message,
+                    next_retry_at: attempt.next_retry_at,
+                    created_at: attempt.created_at,
+                })
+                .collect(),
+        },
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+
+    #[test]
+    fn test_retry_delay_doubles_up_to_max_delay() {
+        let delays = (1..=6)
+            .map(|retry_number| get_retry_delay(60, retry_number, 1200))
+            .collect::<Vec<_>>();
+
+        assert_eq!(delays, vec![60, 120, 240, 480, 960, 1200]);
+        assert_eq!(get_retry_delay(60, 40, 86400), 86400);
+    }
+}
@@ -1,16 +1,22 @@
 pub mod address;
 pub mod api_keys;
+pub mod audit_event;
 pub mod cache;
 pub mod capture;
 pub mod cards_info;
+pub mod commission;
 pub mod configs;
 pub mod connector_response;
+pub mod customer_import_job;
 pub mod customers;
 pub mod dispute;
 pub mod ephemeral_key;
 pub mod events;
 pub mod file;
 pub mod fraud_check;
+pub mod gift_card;
+pub mod invoice;
+pub mod ledger_entry;
 pub mod locker_mock_up;
 pub mod mandate;
 pub mod merchant_account;
@@ -18,19 +24,30 @@ pub mod merchant_connector_account;
 pub mod merchant_key_store;
 pub mod payment_attempt;
 pub mod payment_intent;
+pub mod payment_link;
+pub mod payment_metadata_audit;
 pub mod payment_method;
+pub mod payment_method_share;
 pub mod payout_attempt;
+pub mod payout_batch;
 pub mod payouts;
 pub mod process_tracker;
 pub mod queue;
+pub mod queued_webhook;
 pub mod refund;
+pub mod report_schedule;
 pub mod reverse_lookup;
+pub mod subscription;
+pub mod terminal;
+pub mod webhook_delivery_attempt;
+pub mod webhook_endpoint_health;
 
 use std::sync::Arc;
 
 use futures::lock::Mutex;
 
 use crate::{
+    consts,
     services::{self, Store},
     types::storage,
 };
@@ -49,15 +66,20 @@ pub trait StorageInterface:
     + dyn_clone::DynClone
     + address::AddressInterface
     + api_keys::ApiKeyInterface
+    + audit_event::AuditEventInterface
     + configs::ConfigInterface
     + capture::CaptureInterface
     + connector_response::ConnectorResponseInterface
+    + customer_import_job::CustomerImportJobInterface
     + customers::CustomerInterface
     + dispute::DisputeInterface
     + ephemeral_key::EphemeralKeyInterface
     + events::EventInterface
     + file::FileMetadataInterface
     + fraud_check::FraudCheckInterface
+    + gift_card::GiftCardInterface
+    + invoice::InvoiceInterface
+    + ledger_entry::LedgerEntryInterface
     + locker_mock_up::LockerMockUpInterface
     + mandate::MandateInterface
     + merchant_account::MerchantAccountInterface
@@ -65,14 +87,25 @@ pub trait StorageInterface:
     + merchant_connector_account::MerchantConnectorAccountInterface
     + payment_attempt::PaymentAttemptInterface
     + payment_intent::PaymentIntentInterface
+    + payment_link::PaymentLinkInterface
+    + payment_metadata_audit::PaymentMetadataAuditInterface
     + payment_method::PaymentMethodInterface
+    + payment_method_share::PaymentMethodShareInterface
     + payout_attempt::PayoutAttemptInterface
+    + payout_batch::PayoutBatchInterface
     + payouts::PayoutsInterface
     + process_tracker::ProcessTrackerInterface
     + queue::QueueInterface
+    + queued_webhook::QueuedWebhookInterface
     + refund::RefundInterface
+    + report_schedule::ReportScheduleInterface
     + reverse_lookup::ReverseLookupInterface
+    + subscription::SubscriptionInterface
+    + terminal::TerminalInterface
+    + webhook_delivery_attempt::WebhookDeliveryAttemptInterface
+    + webhook_endpoint_health::WebhookEndpointHealthInterface
     + cards_info::CardsInfoInterface
+    + commission::CommissionInterface
     + merchant_key_store::MerchantKeyStoreInterface
     + MasterKeyInterface
     + services::RedisConnInterface
@@ -82,12 +115,35 @@ pub trait StorageInterface:
 
 pub trait MasterKeyInterface {
     fn get_master_key(&self) -> &[u8];
+    fn get_master_key_version(&self) -> i32;
+    fn get_previous_master_key(&self) -> Option<&[u8]>;
+
+    /// Returns the master key of the given version, if it is either the current master key or the
+    /// previous one.
+    fn get_master_key_by_version(&self, key_version: i32) -> Option<&[u8]> {
+        let master_key_version = self.get_master_key_version();
+        if key_version == master_key_version {
+            Some(self.get_master_key())
+        } else if key_version == master_key_version - 1 {
+            self.get_previous_master_key()
+        } else {
+            None
+        }
+    }
 }
 
 impl MasterKeyInterface for Store {
     fn get_master_key(&self) -> &[u8] {
         &self.master_key
     }
+
+    fn get_master_key_version(&self) -> i32 {
+        self.master_key_version
+    }
+
+    fn get_previous_master_key(&self) -> Option<&[u8]> {
+        self.previous_master_key.as_deref()
+    }
 }
 
 /// Default dummy key for MockDb
@@ -98,6 +154,14 @@ impl MasterKeyInterface for MockDb {
             25, 26, 27, 28, 29, 30, 31, 32,
         ]
     }
+
+    fn get_master_key_version(&self) -> i32 {
+        self.master_key_version
+    }
+
+    fn get_previous_master_key(&self) -> Option<&[u8]> {
+        self.previous_master_key.as_deref()
+    }
 }
 
 #[async_trait::async_trait]
@@ -126,6 +190,8 @@ pub struct MockDb {
     mandates: Arc<Mutex<Vec<storage::Mandate>>>,
     captures: Arc<Mutex<Vec<storage::Capture>>>,
     merchant_key_store: Arc<Mutex<Vec<storage::MerchantKeyStore>>>,
+    master_key_version: i32,
+    previous_master_key: Option<Vec<u8>>,
 }
 
 impl MockDb {
@@ -152,8 +218,19 @@ impl MockDb {
             mandates: Default::default(),
             captures: Default::default(),
             merchant_key_store: Default::default(),
+            master_key_version: consts::INITIAL_MERCHANT_KEY_VERSION,
+            previous_master_key: None,
         }
     }
+
+    /// Makes the master key of the mock database the successor of the given master key, as if
+    /// the master key had been rotated
+    #[cfg(test)]
+    pub fn with_previous_master_key(mut self, previous_master_key: Vec<u8>) -> Self {
+        self.master_key_version = consts::INITIAL_MERCHANT_KEY_VERSION + 1;
+        self.previous_master_key = Some(previous_master_key);
+        self
+    }
 }
 
 #[async_trait::async_trait]
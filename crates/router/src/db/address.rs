@@ -46,6 +46,18 @@ where
         address: storage::AddressUpdate,
         key_store: &domain::MerchantKeyStore,
     ) -> CustomResult<Vec<domain::Address>, errors::StorageError>;
+
+    async fn find_address_book_by_merchant_id_customer_id(
+        &self,
+        merchant_id: &str,
+        customer_id: &str,
+        key_store: &domain::MerchantKeyStore,
+    ) -> CustomResult<Vec<domain::Address>, errors::StorageError>;
+
+    async fn delete_address_by_address_id(
+        &self,
+        address_id: &str,
+    ) -> CustomResult<bool, errors::StorageError>;
 }
 
 #[async_trait::async_trait]
@@ -55,7 +67,7 @@ impl AddressInterface for Store {
         address_id: &str,
         key_store: &domain::MerchantKeyStore,
     ) -> CustomResult<domain::Address, errors::StorageError> {
-        let conn = connection::pg_connection_read(self).await?;
+        let conn = connection::pg_regional_connection_read(self).await?;
         storage::Address::find_by_address_id(&conn, address_id)
             .await
             .map_err(Into::into)
@@ -75,7 +87,7 @@ impl AddressInterface for Store {
         address: storage::AddressUpdate,
         key_store: &domain::MerchantKeyStore,
     ) -> CustomResult<domain::Address, errors::StorageError> {
-        let conn = connection::pg_connection_write(self).await?;
+        let conn = connection::pg_regional_connection_write(self).await?;
         storage::Address::update_by_address_id(&conn, address_id, address.into())
             .await
             .map_err(Into::into)
@@ -94,7 +106,7 @@ impl AddressInterface for Store {
         address: domain::Address,
         key_store: &domain::MerchantKeyStore,
     ) -> CustomResult<domain::Address, errors::StorageError> {
-        let conn = connection::pg_connection_write(self).await?;
+        let conn = connection::pg_regional_connection_write(self).await?;
         address
             .construct_new()
             .await
@@ -119,7 +131,7 @@ impl AddressInterface for Store {
         address: storage::AddressUpdate,
         key_store: &domain::MerchantKeyStore,
     ) -> CustomResult<Vec<domain::Address>, errors::StorageError> {
-        let conn = connection::pg_connection_write(self).await?;
+        let conn = connection::pg_regional_connection_write(self).await?;
         storage::Address::update_by_merchant_id_customer_id(
             &conn,
             customer_id,
@@ -143,6 +155,47 @@ impl AddressInterface for Store {
         })
         .await
     }
+
+    async fn find_address_book_by_merchant_id_customer_id(
+        &self,
+        merchant_id: &str,
+        customer_id: &str,
+        key_store: &domain::MerchantKeyStore,
+    ) -> CustomResult<Vec<domain::Address>, errors::StorageError> {
+        let conn = connection::pg_regional_connection_read(self).await?;
+        storage::Address::find_address_book_by_merchant_id_customer_id(
+            &conn,
+            merchant_id,
+            customer_id,
+        )
+        .await
+        .map_err(Into::into)
+        .into_report()
+        .async_and_then(|addresses| async {
+            let mut output = Vec::with_capacity(addresses.len());
+            for address in addresses.into_iter() {
+                output.push(
+                    address
+                        .convert(key_store.key.get_inner())
+                        .await
+                        .change_context(errors::StorageError::DecryptionError)?,
+                )
+            }
+            Ok(output)
+        })
+        .await
+    }
+
+    async fn delete_address_by_address_id(
+        &self,
+        address_id: &str,
+    ) -> CustomResult<bool, errors::StorageError> {
+        let conn = connection::pg_regional_connection_write(self).await?;
+        storage::Address::delete_by_address_id(&conn, address_id)
+            .await
+            .map_err(Into::into)
+            .into_report()
+    }
 }
 
 #[async_trait::async_trait]
@@ -253,4 +306,55 @@ impl AddressInterface for MockDb {
             }
         }
     }
+
+    async fn find_address_book_by_merchant_id_customer_id(
+        &self,
+        merchant_id: &str,
+        customer_id: &str,
+        key_store: &domain::MerchantKeyStore,
+    ) -> CustomResult<Vec<domain::Address>, errors::StorageError> {
+        let addresses = self
+            .addresses
+            .lock()
+            .await
+            .iter()
+            .filter(|address| {
+                address.merchant_id == merchant_id
+                    && address.customer_id == customer_id
+                    && address.is_address_book_entry
+            })
+            .cloned()
+            .collect::<Vec<_>>();
+
+        let mut output = Vec::with_capacity(addresses.len());
+        for address in addresses.into_iter() {
+            output.push(
+                address
+                    .convert(key_store.key.get_inner())
+                    .await
+                    .change_context(errors::StorageError::DecryptionError)?,
+            )
+        }
+        Ok(output)
+    }
+
+    async fn delete_address_by_address_id(
+        &self,
+        address_id: &str,
+    ) -> CustomResult<bool, errors::StorageError> {
+        let mut addresses = self.addresses.lock().await;
+        match addresses
+            .iter()
+            .position(|address| address.address_id == address_id)
+        {
+            Some(index) => {
+                addresses.remove(index);
+                Ok(true)
+            }
+            None => Err(errors::StorageError::ValueNotFound(
+                "cannot find address to delete".to_string(),
+            )
+            .into()),
+        }
+    }
 }
@@ -0,0 +1,72 @@
+use error_stack::IntoReport;
+
+use super::{MockDb, Store};
+use crate::{
+    connection,
+    core::errors::{self, CustomResult},
+    types::storage::{self, AuditEventDbExt},
+};
+
+#[async_trait::async_trait]
+pub trait AuditEventInterface {
+    async fn insert_audit_event(
+        &self,
+        audit_event: storage::AuditEventNew,
+    ) -> CustomResult<storage::AuditEvent, errors::StorageError>;
+
+    async fn filter_audit_events_by_constraints(
+        &self,
+        merchant_id: &str,
+        constraints: &api_models::audit::AuditEventListConstraints,
+        limit: i64,
+    ) -> CustomResult<Vec<storage::AuditEvent>, errors::StorageError>;
+}
+
+#[async_trait::async_trait]
+impl AuditEventInterface for Store {
+    async fn insert_audit_event(
+        &self,
+        audit_event: storage::AuditEventNew,
+    ) -> CustomResult<storage::AuditEvent, errors::StorageError> {
+        let conn = connection::pg_regional_connection_write(self).await?;
+        audit_event
+            .insert(&conn)
+            .await
+            .map_err(Into::into)
+            .into_report()
+    }
+
+    async fn filter_audit_events_by_constraints(
+        &self,
+        merchant_id: &str,
+        constraints: &api_models::audit::AuditEventListConstraints,
+        limit: i64,
+    ) -> CustomResult<Vec<storage::AuditEvent>, errors::StorageError> {
+        let conn = connection::pg_regional_connection_read(self).await?;
+        storage::AuditEvent::filter_by_constraints(&conn, merchant_id, constraints, limit)
+            .await
+            .map_err(Into::into)
+            .into_report()
+    }
+}
+
+#[async_trait::async_trait]
+impl AuditEventInterface for MockDb {
+    async fn insert_audit_event(
+        &self,
+        _audit_event: storage::AuditEventNew,
+    ) -> CustomResult<storage::AuditEvent, errors::StorageError> {
+        // TODO: Implement function for `MockDb`
+        Err(errors::StorageError::MockDbError)?
+    }
+
+    async fn filter_audit_events_by_constraints(
+        &self,
+        _merchant_id: &str,
+        _constraints: &api_models::audit::AuditEventListConstraints,
+        _limit: i64,
+    ) -> CustomResult<Vec<storage::AuditEvent>, errors::StorageError> {
+        // TODO: Implement function for `MockDb`
+        Err(errors::StorageError::MockDbError)?
+    }
+}
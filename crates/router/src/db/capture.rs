@@ -48,7 +48,7 @@ mod storage {
             _storage_scheme: enums::MerchantStorageScheme,
         ) -> CustomResult<Capture, errors::StorageError> {
             let db_call = || async {
-                let conn = connection::pg_connection_write(self).await?;
+                let conn = connection::pg_regional_connection_write(self).await?;
                 capture
                     .insert(&conn)
                     .await
@@ -65,7 +65,7 @@ mod storage {
             _storage_scheme: enums::MerchantStorageScheme,
         ) -> CustomResult<Capture, errors::StorageError> {
             let db_call = || async {
-                let conn = connection::pg_connection_write(self).await?;
+                let conn = connection::pg_regional_connection_write(self).await?;
                 this.update_with_capture_id(&conn, capture)
                     .await
                     .map_err(Into::into)
@@ -82,7 +82,7 @@ mod storage {
             _storage_scheme: enums::MerchantStorageScheme,
         ) -> CustomResult<Vec<Capture>, errors::StorageError> {
             let db_call = || async {
-                let conn = connection::pg_connection_read(self).await?;
+                let conn = connection::pg_regional_connection_read(self).await?;
                 Capture::find_all_by_merchant_id_payment_id_authorized_attempt_id(
                     merchant_id,
                     payment_id,
@@ -118,7 +118,7 @@ mod storage {
             _storage_scheme: enums::MerchantStorageScheme,
         ) -> CustomResult<Capture, errors::StorageError> {
             let db_call = || async {
-                let conn = connection::pg_connection_write(self).await?;
+                let conn = connection::pg_regional_connection_write(self).await?;
                 capture
                     .insert(&conn)
                     .await
@@ -135,7 +135,7 @@ mod storage {
             _storage_scheme: enums::MerchantStorageScheme,
         ) -> CustomResult<Capture, errors::StorageError> {
             let db_call = || async {
-                let conn = connection::pg_connection_write(self).await?;
+                let conn = connection::pg_regional_connection_write(self).await?;
                 this.update_with_capture_id(&conn, capture)
                     .await
                     .map_err(Into::into)
@@ -152,7 +152,7 @@ mod storage {
             _storage_scheme: enums::MerchantStorageScheme,
         ) -> CustomResult<Vec<Capture>, errors::StorageError> {
             let db_call = || async {
-                let conn = connection::pg_connection_read(self).await?;
+                let conn = connection::pg_regional_connection_read(self).await?;
                 Capture::find_all_by_merchant_id_payment_id_authorized_attempt_id(
                     merchant_id,
                     payment_id,
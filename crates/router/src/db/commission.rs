@@ -0,0 +1,85 @@
+use error_stack::IntoReport;
+
+use super::{MockDb, Store};
+use crate::{
+    connection,
+    core::errors::{self, CustomResult},
+    types::storage,
+};
+
+#[async_trait::async_trait]
+pub trait CommissionInterface {
+    async fn insert_commission(
+        &self,
+        commission: storage::CommissionNew,
+    ) -> CustomResult<storage::Commission, errors::StorageError>;
+
+    async fn filter_commissions_by_merchant_id_created_at_range_after_id(
+        &self,
+        merchant_id: &str,
+        start_time: time::PrimitiveDateTime,
+        end_time: time::PrimitiveDateTime,
+        after_id: i32,
+        limit: i64,
+    ) -> CustomResult<Vec<storage::Commission>, errors::StorageError>;
+}
+
+#[async_trait::async_trait]
+impl CommissionInterface for Store {
+    async fn insert_commission(
+        &self,
+        commission: storage::CommissionNew,
+    ) -> CustomResult<storage::Commission, errors::StorageError> {
+        let conn = connection::pg_connection_write(self).await?;
+        commission
+            .insert(&conn)
+            .await
+            .map_err(Into::into)
+            .into_report()
+    }
+
+    async fn filter_commissions_by_merchant_id_created_at_range_after_id(
+        &self,
+        merchant_id: &str,
+        start_time: time::PrimitiveDateTime,
+        end_time: time::PrimitiveDateTime,
+        after_id: i32,
+        limit: i64,
+    ) -> CustomResult<Vec<storage::Commission>, errors::StorageError> {
+        let conn = connection::pg_connection_read(self).await?;
+        storage::Commission::filter_by_merchant_id_created_at_range_after_id(
+            &conn,
+            merchant_id,
+            start_time,
+            end_time,
+            after_id,
+            limit,
+        )
+        .await
+        .map_err(Into::into)
+        .into_report()
+    }
+}
+
+#[async_trait::async_trait]
+impl CommissionInterface for MockDb {
+    async fn insert_commission(
+        &self,
+        _commission: storage::CommissionNew,
+    ) -> CustomResult<storage::Commission, errors::StorageError> {
+        // TODO: Implement function for `MockDb`
+        Err(errors::StorageError::MockDbError)?
+    }
+
+    async fn filter_commissions_by_merchant_id_created_at_range_after_id(
+        &self,
+        _merchant_id: &str,
+        _start_time: time::PrimitiveDateTime,
+        _end_time: time::PrimitiveDateTime,
+        _after_id: i32,
+        _limit: i64,
+    ) -> CustomResult<Vec<storage::Commission>, errors::StorageError> {
+        // TODO: Implement function for `MockDb`
+        Err(errors::StorageError::MockDbError)?
+    }
+}
@@ -0,0 +1,143 @@
+use error_stack::IntoReport;
+
+use super::{MockDb, Store};
+use crate::{
+    connection,
+    core::errors::{self, CustomResult},
+    types::storage,
+};
+
+#[async_trait::async_trait]
+pub trait CustomerImportJobInterface {
+    async fn insert_customer_import_job(
+        &self,
+        customer_import_job: storage::CustomerImportJobNew,
+    ) -> CustomResult<storage::CustomerImportJob, errors::StorageError>;
+
+    async fn find_customer_import_job_by_merchant_id_job_id(
+        &self,
+        merchant_id: &str,
+        job_id: &str,
+    ) -> CustomResult<storage::CustomerImportJob, errors::StorageError>;
+
+    async fn update_customer_import_job_by_merchant_id_job_id(
+        &self,
+        merchant_id: &str,
+        job_id: &str,
+        customer_import_job_update: storage::CustomerImportJobUpdate,
+    ) -> CustomResult<storage::CustomerImportJob, errors::StorageError>;
+
+    async fn insert_customer_import_batch_by_merchant_id_job_id(
+        &self,
+        merchant_id: &str,
+        job_id: &str,
+        customer_import_batch: storage::CustomerImportBatch,
+        customer_import_job_update: storage::CustomerImportJobUpdate,
+    ) -> CustomResult<storage::CustomerImportJob, errors::StorageError>;
+}
+
+#[async_trait::async_trait]
+impl CustomerImportJobInterface for Store {
+    async fn insert_customer_import_job(
+        &self,
+        customer_import_job: storage::CustomerImportJobNew,
+    ) -> CustomResult<storage::CustomerImportJob, errors::StorageError> {
+        let conn = connection::pg_regional_connection_write(self).await?;
+        customer_import_job
+            .insert(&conn)
+            .await
+            .map_err(Into::into)
+            .into_report()
+    }
+
+    async fn find_customer_import_job_by_merchant_id_job_id(
+        &self,
+        merchant_id: &str,
+        job_id: &str,
+    ) -> CustomResult<storage::CustomerImportJob, errors::StorageError> {
+        let conn = connection::pg_regional_connection_read(self).await?;
+        storage::CustomerImportJob::find_by_merchant_id_job_id(&conn, merchant_id, job_id)
+            .await
+            .map_err(Into::into)
+            .into_report()
+    }
+
+    async fn update_customer_import_job_by_merchant_id_job_id(
+        &self,
+        merchant_id: &str,
+        job_id: &str,
+        customer_import_job_update: storage::CustomerImportJobUpdate,
+    ) -> CustomResult<storage::CustomerImportJob, errors::StorageError> {
+        let conn = connection::pg_regional_connection_write(self).await?;
+        storage::CustomerImportJob::update_by_merchant_id_job_id(
+            &conn,
+            merchant_id,
+            job_id,
+            customer_import_job_update,
+        )
+        .await
+        .map_err(Into::into)
+        .into_report()
+    }
+
+    async fn insert_customer_import_batch_by_merchant_id_job_id(
+        &self,
+        merchant_id: &str,
+        job_id: &str,
+        customer_import_batch: storage::CustomerImportBatch,
+        customer_import_job_update: storage::CustomerImportJobUpdate,
+    ) -> CustomResult<storage::CustomerImportJob, errors::StorageError> {
+        let conn = connection::pg_regional_connection_write(self).await?;
+        storage::CustomerImportJob::insert_batch_and_update_by_merchant_id_job_id(
+            &conn,
+            merchant_id.to_owned(),
+            job_id.to_owned(),
+            customer_import_batch,
+            customer_import_job_update,
+        )
+        .await
+        .map_err(Into::into)
+        .into_report()
+    }
+}
+
+#[async_trait::async_trait]
+impl CustomerImportJobInterface for MockDb {
+    async fn insert_customer_import_job(
+        &self,
+        _customer_import_job: storage::CustomerImportJobNew,
+    ) -> CustomResult<storage::CustomerImportJob, errors::StorageError> {
+        // TODO: Implement function for `MockDb`
+        Err(errors::StorageError::MockDbError)?
+    }
+
+    async fn find_customer_import_job_by_merchant_id_job_id(
+        &self,
+        _merchant_id: &str,
+        _job_id: &str,
+    ) -> CustomResult<storage::CustomerImportJob, errors::StorageError> {
+        // TODO: Implement function for `MockDb`
+        Err(errors::StorageError::MockDbError)?
+    }
+
+    async fn update_customer_import_job_by_merchant_id_job_id(
+        &self,
+        _merchant_id: &str,
+        _job_id: &str,
+        _customer_import_job_update: storage::CustomerImportJobUpdate,
+    ) -> CustomResult<storage::CustomerImportJob, errors::StorageError> {
+        // TODO: Implement function for `MockDb`
+        Err(errors::StorageError::MockDbError)?
+    }
+
+    async fn insert_customer_import_batch_by_merchant_id_job_id(
+        &self,
+        _merchant_id: &str,
+        _job_id: &str,
+        _customer_import_batch: storage::CustomerImportBatch,
+        _customer_import_job_update: storage::CustomerImportJobUpdate,
+    ) -> CustomResult<storage::CustomerImportJob, errors::StorageError> {
+        // TODO: Implement function for `MockDb`
+        Err(errors::StorageError::MockDbError)?
+    }
+}
@@ -56,6 +56,12 @@ where
         customer_data: domain::Customer,
         key_store: &domain::MerchantKeyStore,
     ) -> CustomResult<domain::Customer, errors::StorageError>;
+
+    async fn find_customers_with_email_after_id(
+        &self,
+        last_id: i32,
+        limit: i64,
+    ) -> CustomResult<Vec<storage::Customer>, errors::StorageError>;
 }
 
 #[async_trait::async_trait]
@@ -66,7 +72,7 @@ impl CustomerInterface for Store {
         merchant_id: &str,
         key_store: &domain::MerchantKeyStore,
     ) -> CustomResult<Option<domain::Customer>, errors::StorageError> {
-        let conn = connection::pg_connection_read(self).await?;
+        let conn = connection::pg_regional_connection_read(self).await?;
         let maybe_customer: Option<domain::Customer> =
             storage::Customer::find_optional_by_customer_id_merchant_id(
                 &conn,
@@ -102,12 +108,17 @@ impl CustomerInterface for Store {
         customer: storage::CustomerUpdate,
         key_store: &domain::MerchantKeyStore,
     ) -> CustomResult<domain::Customer, errors::StorageError> {
-        let conn = connection::pg_connection_write(self).await?;
+        let conn = connection::pg_regional_connection_write(self).await?;
+        let email_hash = customer
+            .get_email_hash(key_store.key.get_inner().peek())
+            .change_context(errors::StorageError::EncryptionError)?;
+        let mut customer_update = storage::CustomerUpdateInternal::from(customer);
+        customer_update.email_hash = email_hash.or(customer_update.email_hash);
         storage::Customer::update_by_customer_id_merchant_id(
             &conn,
             customer_id,
             merchant_id,
-            customer.into(),
+            customer_update,
         )
         .await
         .map_err(Into::into)
@@ -126,7 +137,7 @@ impl CustomerInterface for Store {
         merchant_id: &str,
         key_store: &domain::MerchantKeyStore,
     ) -> CustomResult<domain::Customer, errors::StorageError> {
-        let conn = connection::pg_connection_read(self).await?;
+        let conn = connection::pg_regional_connection_read(self).await?;
         let customer: domain::Customer =
             storage::Customer::find_by_customer_id_merchant_id(&conn, customer_id, merchant_id)
                 .await
@@ -151,11 +162,21 @@ impl CustomerInterface for Store {
         customer_data: domain::Customer,
         key_store: &domain::MerchantKeyStore,
     ) -> CustomResult<domain::Customer, errors::StorageError> {
-        let conn = connection::pg_connection_write(self).await?;
-        customer_data
+        let conn = connection::pg_regional_connection_write(self).await?;
+        let email_hash = customer_data
+            .email
+            .as_ref()
+            .map(|email| {
+                domain::get_email_hash(key_store.key.get_inner().peek(), email.get_inner().peek())
+            })
+            .transpose()
+            .change_context(errors::StorageError::EncryptionError)?;
+        let mut customer_new = customer_data
             .construct_new()
             .await
-            .change_context(errors::StorageError::EncryptionError)?
+            .change_context(errors::StorageError::EncryptionError)?;
+        customer_new.email_hash = email_hash;
+        customer_new
             .insert(&conn)
             .await
             .map_err(Into::into)
@@ -173,12 +194,24 @@ impl CustomerInterface for Store {
         customer_id: &str,
         merchant_id: &str,
     ) -> CustomResult<bool, errors::StorageError> {
-        let conn = connection::pg_connection_write(self).await?;
+        let conn = connection::pg_regional_connection_write(self).await?;
         storage::Customer::delete_by_customer_id_merchant_id(&conn, customer_id, merchant_id)
             .await
             .map_err(Into::into)
             .into_report()
     }
+
+    async fn find_customers_with_email_after_id(
+        &self,
+        last_id: i32,
+        limit: i64,
+    ) -> CustomResult<Vec<storage::Customer>, errors::StorageError> {
+        let conn = connection::pg_regional_connection_read(self).await?;
+        storage::Customer::find_with_email_after_id(&conn, last_id, limit)
+            .await
+            .map_err(Into::into)
+            .into_report()
+    }
 }
 
 #[async_trait::async_trait]
@@ -256,4 +289,20 @@ impl CustomerInterface for MockDb {
         // [#172]: Implement function for `MockDb`
         Err(errors::StorageError::MockDbError)?
     }
+
+    async fn find_customers_with_email_after_id(
+        &self,
+        last_id: i32,
+        limit: i64,
+    ) -> CustomResult<Vec<storage::Customer>, errors::StorageError> {
+        let customers = self.customers.lock().await;
+        let mut customers_found: Vec<storage::Customer> = customers
+            .iter()
+            .filter(|customer| customer.id > last_id && customer.email.is_some())
+            .cloned()
+            .collect();
+        customers_found.sort_by_key(|customer| customer.id);
+        customers_found.truncate(usize::try_from(limit).unwrap_or(0));
+        Ok(customers_found)
+    }
 }
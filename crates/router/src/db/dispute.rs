@@ -39,6 +39,12 @@ pub trait DisputeInterface {
         payment_id: &str,
     ) -> CustomResult<Vec<storage::Dispute>, errors::StorageError>;
 
+    async fn find_disputes_by_merchant_id_connector_dispute_id(
+        &self,
+        merchant_id: &str,
+        connector_dispute_id: &str,
+    ) -> CustomResult<Vec<storage::Dispute>, errors::StorageError>;
+
     async fn update_dispute(
         &self,
         this: storage::Dispute,
@@ -52,7 +58,7 @@ impl DisputeInterface for Store {
         &self,
         dispute: storage::DisputeNew,
     ) -> CustomResult<storage::Dispute, errors::StorageError> {
-        let conn = connection::pg_connection_write(self).await?;
+        let conn = connection::pg_regional_connection_write(self).await?;
         dispute
             .insert(&conn)
             .await
@@ -66,7 +72,7 @@ impl DisputeInterface for Store {
         payment_id: &str,
         connector_dispute_id: &str,
     ) -> CustomResult<Option<storage::Dispute>, errors::StorageError> {
-        let conn = connection::pg_connection_read(self).await?;
+        let conn = connection::pg_regional_connection_read(self).await?;
         storage::Dispute::find_by_merchant_id_payment_id_connector_dispute_id(
             &conn,
             merchant_id,
@@ -83,7 +89,7 @@ impl DisputeInterface for Store {
         merchant_id: &str,
         dispute_id: &str,
     ) -> CustomResult<storage::Dispute, errors::StorageError> {
-        let conn = connection::pg_connection_read(self).await?;
+        let conn = connection::pg_regional_connection_read(self).await?;
         storage::Dispute::find_by_merchant_id_dispute_id(&conn, merchant_id, dispute_id)
             .await
             .map_err(Into::into)
@@ -95,7 +101,7 @@ impl DisputeInterface for Store {
         merchant_id: &str,
         dispute_constraints: api_models::disputes::DisputeListConstraints,
     ) -> CustomResult<Vec<storage::Dispute>, errors::StorageError> {
-        let conn = connection::pg_connection_read(self).await?;
+        let conn = connection::pg_regional_connection_read(self).await?;
         storage::Dispute::filter_by_constraints(&conn, merchant_id, dispute_constraints)
             .await
             .map_err(Into::into)
@@ -107,19 +113,35 @@ impl DisputeInterface for Store {
         merchant_id: &str,
         payment_id: &str,
     ) -> CustomResult<Vec<storage::Dispute>, errors::StorageError> {
-        let conn = connection::pg_connection_read(self).await?;
+        let conn = connection::pg_regional_connection_read(self).await?;
         storage::Dispute::find_by_merchant_id_payment_id(&conn, merchant_id, payment_id)
             .await
             .map_err(Into::into)
             .into_report()
     }
 
+    async fn find_disputes_by_merchant_id_connector_dispute_id(
+        &self,
+        merchant_id: &str,
+        connector_dispute_id: &str,
+    ) -> CustomResult<Vec<storage::Dispute>, errors::StorageError> {
+        let conn = connection::pg_regional_connection_read(self).await?;
+        storage::Dispute::filter_by_merchant_id_connector_dispute_id(
+            &conn,
+            merchant_id,
+            connector_dispute_id,
+        )
+        .await
+        .map_err(Into::into)
+        .into_report()
+    }
+
     async fn update_dispute(
         &self,
         this: storage::Dispute,
         dispute: storage::DisputeUpdate,
     ) -> CustomResult<storage::Dispute, errors::StorageError> {
-        let conn = connection::pg_connection_write(self).await?;
+        let conn = connection::pg_regional_connection_write(self).await?;
         this.update(&conn, dispute)
             .await
             .map_err(Into::into)
@@ -293,6 +315,22 @@ impl DisputeInterface for MockDb {
             .collect())
     }
 
+    async fn find_disputes_by_merchant_id_connector_dispute_id(
+        &self,
+        merchant_id: &str,
+        connector_dispute_id: &str,
+    ) -> CustomResult<Vec<storage::Dispute>, errors::StorageError> {
+        let locked_disputes = self.disputes.lock().await;
+
+        Ok(locked_disputes
+            .iter()
+            .filter(|d| {
+                d.merchant_id == merchant_id && d.connector_dispute_id == connector_dispute_id
+            })
+            .cloned()
+            .collect())
+    }
+
     async fn update_dispute(
         &self,
         this: storage::Dispute,
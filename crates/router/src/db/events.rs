@@ -26,7 +26,7 @@ impl EventInterface for Store {
         &self,
         event: storage::EventNew,
     ) -> CustomResult<storage::Event, errors::StorageError> {
-        let conn = connection::pg_connection_write(self).await?;
+        let conn = connection::pg_regional_connection_write(self).await?;
         event.insert(&conn).await.map_err(Into::into).into_report()
     }
     async fn update_event(
@@ -34,7 +34,7 @@ impl EventInterface for Store {
         event_id: String,
         event: storage::EventUpdate,
     ) -> CustomResult<storage::Event, errors::StorageError> {
-        let conn = connection::pg_connection_write(self).await?;
+        let conn = connection::pg_regional_connection_write(self).await?;
         storage::Event::update(&conn, &event_id, event)
             .await
             .map_err(Into::into)
@@ -39,7 +39,7 @@ impl FileMetadataInterface for Store {
         &self,
         file: storage::FileMetadataNew,
     ) -> CustomResult<storage::FileMetadata, errors::StorageError> {
-        let conn = connection::pg_connection_write(self).await?;
+        let conn = connection::pg_regional_connection_write(self).await?;
         file.insert(&conn).await.map_err(Into::into).into_report()
     }
 
@@ -48,7 +48,7 @@ impl FileMetadataInterface for Store {
         merchant_id: &str,
         file_id: &str,
     ) -> CustomResult<storage::FileMetadata, errors::StorageError> {
-        let conn = connection::pg_connection_read(self).await?;
+        let conn = connection::pg_regional_connection_read(self).await?;
         storage::FileMetadata::find_by_merchant_id_file_id(&conn, merchant_id, file_id)
             .await
             .map_err(Into::into)
@@ -60,7 +60,7 @@ impl FileMetadataInterface for Store {
         merchant_id: &str,
         file_id: &str,
     ) -> CustomResult<bool, errors::StorageError> {
-        let conn = connection::pg_connection_write(self).await?;
+        let conn = connection::pg_regional_connection_write(self).await?;
         storage::FileMetadata::delete_by_merchant_id_file_id(&conn, merchant_id, file_id)
             .await
             .map_err(Into::into)
@@ -72,7 +72,7 @@ impl FileMetadataInterface for Store {
         this: storage::FileMetadata,
         file_metadata: storage::FileMetadataUpdate,
     ) -> CustomResult<storage::FileMetadata, errors::StorageError> {
-        let conn = connection::pg_connection_write(self).await?;
+        let conn = connection::pg_regional_connection_write(self).await?;
         this.update(&conn, file_metadata)
             .await
             .map_err(Into::into)
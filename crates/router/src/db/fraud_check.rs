@@ -34,7 +34,7 @@ impl FraudCheckInterface for Store {
         &self,
         new: storage::FraudCheckNew,
     ) -> CustomResult<FraudCheck, errors::StorageError> {
-        let conn = connection::pg_connection_write(self).await?;
+        let conn = connection::pg_regional_connection_write(self).await?;
         new.insert(&conn).await.map_err(Into::into).into_report()
     }
     async fn update_fraud_check_response_with_attempt_id(
@@ -42,7 +42,7 @@ impl FraudCheckInterface for Store {
         this: FraudCheck,
         fraud_check: FraudCheckUpdate,
     ) -> CustomResult<FraudCheck, errors::StorageError> {
-        let conn = connection::pg_connection_write(self).await?;
+        let conn = connection::pg_regional_connection_write(self).await?;
         this.update_with_attempt_id(&conn, fraud_check)
             .await
             .map_err(Into::into)
@@ -53,7 +53,7 @@ impl FraudCheckInterface for Store {
         payment_id: String,
         merchant_id: String,
     ) -> CustomResult<FraudCheck, errors::StorageError> {
-        let conn = connection::pg_connection_write(self).await?;
+        let conn = connection::pg_regional_connection_write(self).await?;
         FraudCheck::get_with_payment_id(&conn, payment_id, merchant_id)
             .await
             .map_err(Into::into)
@@ -0,0 +1,214 @@
+use error_stack::IntoReport;
+
+use super::{MockDb, Store};
+use crate::{
+    connection,
+    core::errors::{self, CustomResult},
+    types::storage,
+};
+
+#[async_trait::async_trait]
+pub trait GiftCardInterface {
+    async fn insert_gift_card_with_transaction(
+        &self,
+        gift_card: storage::GiftCardNew,
+        gift_card_transaction: storage::GiftCardTransactionNew,
+    ) -> CustomResult<(storage::GiftCard, storage::GiftCardTransaction), errors::StorageError>;
+
+    async fn find_gift_card_by_merchant_id_gift_card_id(
+        &self,
+        merchant_id: &str,
+        gift_card_id: &str,
+    ) -> CustomResult<storage::GiftCard, errors::StorageError>;
+
+    async fn update_gift_card_balance_with_transaction(
+        &self,
+        merchant_id: &str,
+        gift_card_id: &str,
+        current_balance: i64,
+        gift_card_update: storage::GiftCardUpdate,
+        gift_card_transaction: storage::GiftCardTransactionNew,
+    ) -> CustomResult<(storage::GiftCard, storage::GiftCardTransaction), errors::StorageError>;
+
+    async fn insert_gift_card_transaction(
+        &self,
+        gift_card_transaction: storage::GiftCardTransactionNew,
+    ) -> CustomResult<storage::GiftCardTransaction, errors::StorageError>;
+
+    async fn find_gift_card_transaction_by_merchant_id_gift_card_id_idempotency_key(
+        &self,
+        merchant_id: &str,
+        gift_card_id: &str,
+        idempotency_key: &str,
+    ) -> CustomResult<storage::GiftCardTransaction, errors::StorageError>;
+
+    async fn list_gift_card_transactions_by_merchant_id_gift_card_id(
+        &self,
+        merchant_id: &str,
+        gift_card_id: &str,
+        limit: Option<i64>,
+        offset: Option<i64>,
+    ) -> CustomResult<Vec<storage::GiftCardTransaction>, errors::StorageError>;
+}
+
+#[async_trait::async_trait]
+impl GiftCardInterface for Store {
+    async fn insert_gift_card_with_transaction(
+        &self,
+        gift_card: storage::GiftCardNew,
+        gift_card_transaction: storage::GiftCardTransactionNew,
+    ) -> CustomResult<(storage::GiftCard, storage::GiftCardTransaction), errors::StorageError> {
+        let conn = connection::pg_regional_connection_write(self).await?;
+        gift_card
+            .insert_with_transaction(&conn, gift_card_transaction)
+            .await
+            .map_err(Into::into)
+            .into_report()
+    }
+
+    async fn find_gift_card_by_merchant_id_gift_card_id(
+        &self,
+        merchant_id: &str,
+        gift_card_id: &str,
+    ) -> CustomResult<storage::GiftCard, errors::StorageError> {
+        let conn = connection::pg_regional_connection_read(self).await?;
+        storage::GiftCard::find_by_merchant_id_gift_card_id(&conn, merchant_id, gift_card_id)
+            .await
+            .map_err(Into::into)
+            .into_report()
+    }
+
+    async fn update_gift_card_balance_with_transaction(
+        &self,
+        merchant_id: &str,
+        gift_card_id: &str,
+        current_balance: i64,
+        gift_card_update: storage::GiftCardUpdate,
+        gift_card_transaction: storage::GiftCardTransactionNew,
+    ) -> CustomResult<(storage::GiftCard, storage::GiftCardTransaction), errors::StorageError> {
+        let conn = connection::pg_regional_connection_write(self).await?;
+        storage::GiftCard::update_balance_with_transaction(
+            &conn,
+            merchant_id.to_owned(),
+            gift_card_id.to_owned(),
+            current_balance,
+            gift_card_update,
+            gift_card_transaction,
+        )
+        .await
+        .map_err(Into::into)
+        .into_report()
+    }
+
+    async fn insert_gift_card_transaction(
+        &self,
+        gift_card_transaction: storage::GiftCardTransactionNew,
+    ) -> CustomResult<storage::GiftCardTransaction, errors::StorageError> {
+        let conn = connection::pg_regional_connection_write(self).await?;
+        gift_card_transaction
+            .insert(&conn)
+            .await
+            .map_err(Into::into)
+            .into_report()
+    }
+
+    async fn find_gift_card_transaction_by_merchant_id_gift_card_id_idempotency_key(
+        &self,
+        merchant_id: &str,
+        gift_card_id: &str,
+        idempotency_key: &str,
+    ) -> CustomResult<storage::GiftCardTransaction, errors::StorageError> {
+        let conn = connection::pg_regional_connection_read(self).await?;
+        storage::GiftCardTransaction::find_by_merchant_id_gift_card_id_idempotency_key(
+            &conn,
+            merchant_id,
+            gift_card_id,
+            idempotency_key,
+        )
+        .await
+        .map_err(Into::into)
+        .into_report()
+    }
+
+    async fn list_gift_card_transactions_by_merchant_id_gift_card_id(
+        &self,
+        merchant_id: &str,
+        gift_card_id: &str,
+        limit: Option<i64>,
+        offset: Option<i64>,
+    ) -> CustomResult<Vec<storage::GiftCardTransaction>, errors::StorageError> {
+        let conn = connection::pg_regional_connection_read(self).await?;
+        storage::GiftCardTransaction::find_by_merchant_id_gift_card_id(
+            &conn,
+            merchant_id,
+            gift_card_id,
+            limit,
+            offset,
+        )
+        .await
+        .map_err(Into::into)
+        .into_report()
+    }
+}
+
+#[async_trait::async_trait]
+impl GiftCardInterface for MockDb {
+    async fn insert_gift_card_with_transaction(
+        &self,
+        _gift_card: storage::GiftCardNew,
+        _gift_card_transaction: storage::GiftCardTransactionNew,
+    ) -> CustomResult<(storage::GiftCard, storage::GiftCardTransaction), errors::StorageError> {
+        // TODO: Implement function for `MockDb`
+        Err(errors::StorageError::MockDbError)?
+    }
+
+    async fn find_gift_card_by_merchant_id_gift_card_id(
+        &self,
+        _merchant_id: &str,
+        _gift_card_id: &str,
+    ) -> CustomResult<storage::GiftCard, errors::StorageError> {
+        // TODO: Implement function for `MockDb`
+        Err(errors::StorageError::MockDbError)?
+    }
+
+    async fn update_gift_card_balance_with_transaction(
+        &self,
+        _merchant_id: &str,
+        _gift_card_id: &str,
+        _current_balance: i64,
+        _gift_card_update: storage::GiftCardUpdate,
+        _gift_card_transaction: storage::GiftCardTransactionNew,
+    ) -> CustomResult<(storage::GiftCard, storage::GiftCardTransaction), errors::StorageError> {
+        // TODO: Implement function for `MockDb`
+        Err(errors::StorageError::MockDbError)?
+    }
+
+    async fn insert_gift_card_transaction(
+        &self,
+        _gift_card_transaction: storage::GiftCardTransactionNew,
+    ) -> CustomResult<storage::GiftCardTransaction, errors::StorageError> {
+        // TODO: Implement function for `MockDb`
+        Err(errors::StorageError::MockDbError)?
+    }
+
+    async fn find_gift_card_transaction_by_merchant_id_gift_card_id_idempotency_key(
+        &self,
+        _merchant_id: &str,
+        _gift_card_id: &str,
+        _idempotency_key: &str,
+    ) -> CustomResult<storage::GiftCardTransaction, errors::StorageError> {
+        // TODO: Implement function for `MockDb`
+        Err(errors::StorageError::MockDbError)?
+    }
+
+    async fn list_gift_card_transactions_by_merchant_id_gift_card_id(
+        &self,
+        _merchant_id: &str,
+        _gift_card_id: &str,
+        _limit: Option<i64>,
+        _offset: Option<i64>,
+    ) -> CustomResult<Vec<storage::GiftCardTransaction>, errors::StorageError> {
+        // TODO: Implement function for `MockDb`
+        Err(errors::StorageError::MockDbError)?
+    }
+}
@@ -0,0 +1,208 @@
+use error_stack::IntoReport;
+
+use super::{MockDb, Store};
+use crate::{
+    connection,
+    core::errors::{self, CustomResult},
+    types::storage,
+};
+
+#[async_trait::async_trait]
+pub trait InvoiceInterface {
+    async fn insert_invoice(
+        &self,
+        invoice: storage::InvoiceNew,
+    ) -> CustomResult<storage::Invoice, errors::StorageError>;
+
+    async fn find_invoice_by_merchant_id_invoice_id(
+        &self,
+        merchant_id: &str,
+        invoice_id: &str,
+    ) -> CustomResult<storage::Invoice, errors::StorageError>;
+
+    async fn list_invoices_by_merchant_id(
+        &self,
+        merchant_id: &str,
+        limit: Option<i64>,
+        offset: Option<i64>,
+    ) -> CustomResult<Vec<storage::Invoice>, errors::StorageError>;
+
+    async fn update_invoice_by_merchant_id_invoice_id(
+        &self,
+        merchant_id: &str,
+        invoice_id: &str,
+        invoice_update: storage::InvoiceUpdate,
+    ) -> CustomResult<storage::Invoice, errors::StorageError>;
+
+    async fn add_payment_to_invoice(
+        &self,
+        merchant_id: &str,
+        invoice_id: &str,
+        amount: i64,
+    ) -> CustomResult<storage::Invoice, errors::StorageError>;
+
+    async fn update_invoice_status_if_amount_paid(
+        &self,
+        merchant_id: &str,
+        invoice_id: &str,
+        amount_paid: i64,
+        status: storage::enums::InvoiceStatus,
+    ) -> CustomResult<storage::Invoice, errors::StorageError>;
+}
+
+#[async_trait::async_trait]
+impl InvoiceInterface for Store {
+    async fn insert_invoice(
+        &self,
+        invoice: storage::InvoiceNew,
+    ) -> CustomResult<storage::Invoice, errors::StorageError> {
+        let conn = connection::pg_regional_connection_write(self).await?;
+        invoice
+            .insert(&conn)
+            .await
+            .map_err(Into::into)
+            .into_report()
+    }
+
+    async fn find_invoice_by_merchant_id_invoice_id(
+        &self,
+        merchant_id: &str,
+        invoice_id: &str,
+    ) -> CustomResult<storage::Invoice, errors::StorageError> {
+        let conn = connection::pg_regional_connection_read(self).await?;
+        storage::Invoice::find_by_merchant_id_invoice_id(&conn, merchant_id, invoice_id)
+            .await
+            .map_err(Into::into)
+            .into_report()
+    }
+
+    async fn list_invoices_by_merchant_id(
+        &self,
+        merchant_id: &str,
+        limit: Option<i64>,
+        offset: Option<i64>,
+    ) -> CustomResult<Vec<storage::Invoice>, errors::StorageError> {
+        let conn = connection::pg_regional_connection_read(self).await?;
+        storage::Invoice::find_by_merchant_id(&conn, merchant_id, limit, offset)
+            .await
+            .map_err(Into::into)
+            .into_report()
+    }
+
+    async fn update_invoice_by_merchant_id_invoice_id(
+        &self,
+        merchant_id: &str,
+        invoice_id: &str,
+        invoice_update: storage::InvoiceUpdate,
+    ) -> CustomResult<storage::Invoice, errors::StorageError> {
+        let conn = connection::pg_regional_connection_write(self).await?;
+        storage::Invoice::update_by_merchant_id_invoice_id(
+            &conn,
+            merchant_id,
+            invoice_id,
+            invoice_update,
+        )
+        .await
+        .map_err(Into::into)
+        .into_report()
+    }
+
+    async fn add_payment_to_invoice(
+        &self,
+        merchant_id: &str,
+        invoice_id: &str,
+        amount: i64,
+    ) -> CustomResult<storage::Invoice, errors::StorageError> {
+        let conn = connection::pg_regional_connection_write(self).await?;
+        storage::Invoice::add_payment_by_merchant_id_invoice_id(
+            &conn,
+            merchant_id,
+            invoice_id,
+            amount,
+        )
+        .await
+        .map_err(Into::into)
+        .into_report()
+    }
+
+    async fn update_invoice_status_if_amount_paid(
+        &self,
+        merchant_id: &str,
+        invoice_id: &str,
+        amount_paid: i64,
+        status: storage::enums::InvoiceStatus,
+    ) -> CustomResult<storage::Invoice, errors::StorageError> {
+        let conn = connection::pg_regional_connection_write(self).await?;
+        storage::Invoice::update_status_by_merchant_id_invoice_id_amount_paid(
+            &conn,
+            merchant_id,
+            invoice_id,
+            amount_paid,
+            status,
+        )
+        .await
+        .map_err(Into::into)
+        .into_report()
+    }
+}
+
+#[async_trait::async_trait]
+impl InvoiceInterface for MockDb {
+    async fn insert_invoice(
+        &self,
+        _invoice: storage::InvoiceNew,
+    ) -> CustomResult<storage::Invoice, errors::StorageError> {
+        // TODO: Implement function for `MockDb`
+        Err(errors::StorageError::MockDbError)?
+    }
+
+    async fn find_invoice_by_merchant_id_invoice_id(
+        &self,
+        _merchant_id: &str,
+        _invoice_id: &str,
+    ) -> CustomResult<storage::Invoice, errors::StorageError> {
+        // TODO: Implement function for `MockDb`
+        Err(errors::StorageError::MockDbError)?
+    }
+
+    async fn list_invoices_by_merchant_id(
+        &self,
+        _merchant_id: &str,
+        _limit: Option<i64>,
+        _offset: Option<i64>,
+    ) -> CustomResult<Vec<storage::Invoice>, errors::StorageError> {
+        // TODO: Implement function for `MockDb`
+        Err(errors::StorageError::MockDbError)?
+    }
+
+    async fn update_invoice_by_merchant_id_invoice_id(
+        &self,
+        _merchant_id: &str,
+        _invoice_id: &str,
+        _invoice_update: storage::InvoiceUpdate,
+    ) -> CustomResult<storage::Invoice, errors::StorageError> {
+        // TODO: Implement function for `MockDb`
+        Err(errors::StorageError::MockDbError)?
+    }
+
+    async fn add_payment_to_invoice(
+        &self,
+        _merchant_id: &str,
+        _invoice_id: &str,
+        _amount: i64,
+    ) -> CustomResult<storage::Invoice, errors::StorageError> {
+        // TODO: Implement function for `MockDb`
+        Err(errors::StorageError::MockDbError)?
+    }
+
+    async fn update_invoice_status_if_amount_paid(
+        &self,
+        _merchant_id: &str,
+        _invoice_id: &str,
+        _amount_paid: i64,
+        _status: storage::enums::InvoiceStatus,
+    ) -> CustomResult<storage::Invoice, errors::StorageError> {
+        // TODO: Implement function for `MockDb`
+        Err(errors::StorageError::MockDbError)?
+    }
+}
@@ -0,0 +1,198 @@
+use error_stack::IntoReport;
+
+use super::{MockDb, Store};
+use crate::{
+    connection,
+    core::errors::{self, CustomResult},
+    types::storage::{self, LedgerEntryDbExt},
+};
+
+#[async_trait::async_trait]
+pub trait LedgerEntryInterface {
+    async fn insert_ledger_entry(
+        &self,
+        ledger_entry: storage::LedgerEntryNew,
+    ) -> CustomResult<storage::LedgerEntry, errors::StorageError>;
+
+    async fn filter_ledger_entries_by_constraints(
+        &self,
+        merchant_id: &str,
+        constraints: &api_models::accounting::LedgerEntryListConstraints,
+        limit: i64,
+    ) -> CustomResult<Vec<storage::LedgerEntry>, errors::StorageError>;
+
+    async fn filter_ledger_entries_by_constraints_after_id(
+        &self,
+        merchant_id: &str,
+        constraints: &api_models::accounting::LedgerEntryListConstraints,
+        after_id: i32,
+        limit: i64,
+    ) -> CustomResult<Vec<storage::LedgerEntry>, errors::StorageError>;
+
+    async fn get_ledger_debit_totals_by_account(
+        &self,
+        merchant_id: &str,
+    ) -> CustomResult<Vec<storage::LedgerAccountTotal>, errors::StorageError>;
+
+    async fn get_ledger_credit_totals_by_account(
+        &self,
+        merchant_id: &str,
+    ) -> CustomResult<Vec<storage::LedgerAccountTotal>, errors::StorageError>;
+
+    async fn get_ledger_totals_by_entry_type(
+        &self,
+        merchant_id: &str,
+        entry_types: Vec<String>,
+        start_time: time::PrimitiveDateTime,
+        end_time: time::PrimitiveDateTime,
+    ) -> CustomResult<Vec<storage::LedgerEntryTypeTotal>, errors::StorageError>;
+}
+
+#[async_trait::async_trait]
+impl LedgerEntryInterface for Store {
+    async fn insert_ledger_entry(
+        &self,
+        ledger_entry: storage::LedgerEntryNew,
+    ) -> CustomResult<storage::LedgerEntry, errors::StorageError> {
+        let conn = connection::pg_regional_connection_write(self).await?;
+        ledger_entry
+            .insert(&conn)
+            .await
+            .map_err(Into::into)
+            .into_report()
+    }
+
+    async fn filter_ledger_entries_by_constraints(
+        &self,
+        merchant_id: &str,
+        constraints: &api_models::accounting::LedgerEntryListConstraints,
+        limit: i64,
+    ) -> CustomResult<Vec<storage::LedgerEntry>, errors::StorageError> {
+        let conn = connection::pg_regional_connection_read(self).await?;
+        storage::LedgerEntry::filter_by_constraints(&conn, merchant_id, constraints, limit)
+            .await
+            .map_err(Into::into)
+            .into_report()
+    }
+
+    async fn filter_ledger_entries_by_constraints_after_id(
+        &self,
+        merchant_id: &str,
+        constraints: &api_models::accounting::LedgerEntryListConstraints,
+        after_id: i32,
+        limit: i64,
+    ) -> CustomResult<Vec<storage::LedgerEntry>, errors::StorageError> {
+        let conn = connection::pg_regional_connection_read(self).await?;
+        storage::LedgerEntry::filter_by_constraints_after_id(
+            &conn,
+            merchant_id,
+            constraints,
+            after_id,
+            limit,
+        )
+        .await
+        .map_err(Into::into)
+        .into_report()
+    }
+
+    async fn get_ledger_debit_totals_by_account(
+        &self,
+        merchant_id: &str,
+    ) -> CustomResult<Vec<storage::LedgerAccountTotal>, errors::StorageError> {
+        let conn = connection::pg_regional_connection_read(self).await?;
+        storage::LedgerEntry::get_debit_totals_by_account(&conn, merchant_id)
+            .await
+            .map_err(Into::into)
+            .into_report()
+    }
+
+    async fn get_ledger_credit_totals_by_account(
+        &self,
+        merchant_id: &str,
+    ) -> CustomResult<Vec<storage::LedgerAccountTotal>, errors::StorageError> {
+        let conn = connection::pg_regional_connection_read(self).await?;
+        storage::LedgerEntry::get_credit_totals_by_account(&conn, merchant_id)
+            .await
+            .map_err(Into::into)
+            .into_report()
+    }
+
+    async fn get_ledger_totals_by_entry_type(
+        &self,
+        merchant_id: &str,
+        entry_types: Vec<String>,
+        start_time: time::PrimitiveDateTime,
+        end_time: time::PrimitiveDateTime,
+    ) -> CustomResult<Vec<storage::LedgerEntryTypeTotal>, errors::StorageError> {
+        let conn = connection::pg_regional_connection_read(self).await?;
+        storage::LedgerEntry::get_totals_by_entry_type(
+            &conn,
+            merchant_id,
+            entry_types,
+            start_time,
+            end_time,
+        )
+        .await
+        .map_err(Into::into)
+        .into_report()
+    }
+}
+
+#[async_trait::async_trait]
+impl LedgerEntryInterface for MockDb {
+    async fn insert_ledger_entry(
+        &self,
+        _ledger_entry: storage::LedgerEntryNew,
+    ) -> CustomResult<storage::LedgerEntry, errors::StorageError> {
+        // TODO: Implement function for `MockDb`
+        Err(errors::StorageError::MockDbError)?
+    }
+
+    async fn filter_ledger_entries_by_constraints(
+        &self,
+        _merchant_id: &str,
+        _constraints: &api_models::accounting::LedgerEntryListConstraints,
+        _limit: i64,
+    ) -> CustomResult<Vec<storage::LedgerEntry>, errors::StorageError> {
+        // TODO: Implement function for `MockDb`
+        Err(errors::StorageError::MockDbError)?
+    }
+
+    async fn filter_ledger_entries_by_constraints_after_id(
+        &self,
+        _merchant_id: &str,
+        _constraints: &api_models::accounting::LedgerEntryListConstraints,
+        _after_id: i32,
+        _limit: i64,
+    ) -> CustomResult<Vec<storage::LedgerEntry>, errors::StorageError> {
+        // TODO: Implement function for `MockDb`
+        Err(errors::StorageError::MockDbError)?
+    }
+
+    async fn get_ledger_debit_totals_by_account(
+        &self,
+        _merchant_id: &str,
+    ) -> CustomResult<Vec<storage::LedgerAccountTotal>, errors::StorageError> {
+        // TODO: Implement function for `MockDb`
+        Err(errors::StorageError::MockDbError)?
+    }
+
+    async fn get_ledger_credit_totals_by_account(
+        &self,
+        _merchant_id: &str,
+    ) -> CustomResult<Vec<storage::LedgerAccountTotal>, errors::StorageError> {
+        // TODO: Implement function for `MockDb`
+        Err(errors::StorageError::MockDbError)?
+    }
+
+    async fn get_ledger_totals_by_entry_type(
+        &self,
+        _merchant_id: &str,
+        _entry_types: Vec<String>,
+        _start_time: time::PrimitiveDateTime,
+        _end_time: time::PrimitiveDateTime,
+    ) -> CustomResult<Vec<storage::LedgerEntryTypeTotal>, errors::StorageError> {
+        // TODO: Implement function for `MockDb`
+        Err(errors::StorageError::MockDbError)?
+    }
+}
@@ -161,6 +161,12 @@ where
         merchant_id: &str,
         merchant_connector_id: &str,
     ) -> CustomResult<bool, errors::StorageError>;
+
+    async fn find_merchant_connector_accounts_with_webhook_details_after_id(
+        &self,
+        last_id: i32,
+        limit: i64,
+    ) -> CustomResult<Vec<storage::MerchantConnectorAccount>, errors::StorageError>;
 }
 
 #[async_trait::async_trait]
@@ -418,6 +424,18 @@ impl MerchantConnectorAccountInterface for Store {
             delete_call().await
         }
     }
+
+    async fn find_merchant_connector_accounts_with_webhook_details_after_id(
+        &self,
+        last_id: i32,
+        limit: i64,
+    ) -> CustomResult<Vec<storage::MerchantConnectorAccount>, errors::StorageError> {
+        let conn = connection::pg_connection_read(self).await?;
+        storage::MerchantConnectorAccount::find_with_webhook_details_after_id(&conn, last_id, limit)
+            .await
+            .map_err(Into::into)
+            .into_report()
+    }
 }
 
 #[async_trait::async_trait]
@@ -560,6 +578,13 @@ impl MerchantConnectorAccountInterface for MockDb {
             created_at: common_utils::date_time::now(),
             modified_at: common_utils::date_time::now(),
             connector_webhook_details: t.connector_webhook_details,
+            test_connector_account_details: t.test_connector_account_details.map(Into::into),
+            client_certificate: t.client_certificate.map(Into::into),
+            client_certificate_key: t.client_certificate_key.map(Into::into),
+            client_certificate_expires_at: t.client_certificate_expires_at,
+            webhook_secret: t.webhook_secret.map(Into::into),
+            additional_webhook_secret: t.additional_webhook_secret.map(Into::into),
+            apple_pay_payment_processing_key: t.apple_pay_payment_processing_key.map(Into::into),
         };
         accounts.push(account.clone());
         account
@@ -659,6 +684,22 @@ impl MerchantConnectorAccountInterface for MockDb {
             }
         }
     }
+
+    async fn find_merchant_connector_accounts_with_webhook_details_after_id(
+        &self,
+        last_id: i32,
+        limit: i64,
+    ) -> CustomResult<Vec<storage::MerchantConnectorAccount>, errors::StorageError> {
+        let accounts = self.merchant_connector_accounts.lock().await;
+        let mut accounts_found: Vec<storage::MerchantConnectorAccount> = accounts
+            .iter()
+            .filter(|account| account.id > last_id && account.connector_webhook_details.is_some())
+            .cloned()
+            .collect();
+        accounts_found.sort_by_key(|account| account.id);
+        accounts_found.truncate(usize::try_from(limit).unwrap_or(0));
+        Ok(accounts_found)
+    }
 }
 
 #[cfg(test)]
@@ -710,6 +751,8 @@ mod merchant_connector_account_cache_tests {
                 .await
                 .unwrap(),
                 created_at: datetime!(2023-02-01 0:00),
+                key_version: 1,
+                rotated_at: None,
             },
             &master_key.to_vec().into(),
         )
@@ -745,6 +788,13 @@ mod merchant_connector_account_cache_tests {
             created_at: date_time::now(),
             modified_at: date_time::now(),
             connector_webhook_details: None,
+            test_connector_account_details: None,
+            client_certificate: None,
+            client_certificate_key: None,
+            client_certificate_expires_at: None,
+            webhook_secret: None,
+            additional_webhook_secret: None,
+            apple_pay_payment_processing_key: None,
         };
 
         db.insert_merchant_connector_account(mca, &merchant_key)
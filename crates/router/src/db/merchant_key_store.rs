@@ -1,16 +1,19 @@
 use error_stack::{IntoReport, ResultExt};
-use masking::Secret;
+use masking::{PeekInterface, Secret};
 
 #[cfg(feature = "accounts_cache")]
-use crate::cache::ACCOUNTS_CACHE;
+use crate::cache::{self, ACCOUNTS_CACHE};
 use crate::{
     connection,
     core::errors::{self, CustomResult},
-    db::MockDb,
+    db::{MasterKeyInterface, MockDb},
     services::Store,
-    types::domain::{
-        self,
-        behaviour::{Conversion, ReverseConversion},
+    types::{
+        domain::{
+            self,
+            behaviour::{Conversion, ReverseConversion},
+        },
+        storage,
     },
 };
 
@@ -27,6 +30,43 @@ pub trait MerchantKeyStoreInterface {
         merchant_id: &str,
         key: &Secret<Vec<u8>>,
     ) -> CustomResult<domain::MerchantKeyStore, errors::StorageError>;
+
+    async fn update_merchant_key_store(
+        &self,
+        merchant_id: &str,
+        merchant_key_store_update: storage::MerchantKeyStoreUpdate,
+        key: &Secret<Vec<u8>>,
+    ) -> CustomResult<domain::MerchantKeyStore, errors::StorageError>;
+}
+
+/// Decrypts the merchant key store using the provided key. If the provided key is the current
+/// master key, the master key of the version recorded on the key store is used instead, since the
+/// key store may not have been rotated to the current master key yet.
+async fn convert_with_master_key_version(
+    store: &(impl MasterKeyInterface + Sync),
+    merchant_key_store: storage::MerchantKeyStore,
+    key: &Secret<Vec<u8>>,
+) -> CustomResult<domain::MerchantKeyStore, errors::StorageError> {
+    let key = if key.peek().as_slice() == store.get_master_key() {
+        store
+            .get_master_key_by_version(merchant_key_store.key_version)
+            .map(|master_key| master_key.to_vec().into())
+            .ok_or(errors::StorageError::DecryptionError)
+            .into_report()
+            .attach_printable_lazy(|| {
+                format!(
+                    "No master key of version {} is configured",
+                    merchant_key_store.key_version
+                )
+            })?
+    } else {
+        key.clone()
+    };
+
+    merchant_key_store
+        .convert(&key)
+        .await
+        .change_context(errors::StorageError::DecryptionError)
 }
 
 #[async_trait::async_trait]
@@ -68,26 +108,58 @@ impl MerchantKeyStoreInterface for Store {
         };
         #[cfg(not(feature = "accounts_cache"))]
         {
-            fetch_func()
-                .await?
-                .convert(key)
-                .await
-                .change_context(errors::StorageError::DecryptionError)
+            convert_with_master_key_version(self, fetch_func().await?, key).await
         }
 
         #[cfg(feature = "accounts_cache")]
         {
             let key_store_cache_key = format!("merchant_key_store_{}", merchant_id);
-            super::cache::get_or_populate_in_memory(
+            let merchant_key_store = super::cache::get_or_populate_in_memory(
                 self,
                 &key_store_cache_key,
                 fetch_func,
                 &ACCOUNTS_CACHE,
             )
-            .await?
+            .await?;
+            convert_with_master_key_version(self, merchant_key_store, key).await
+        }
+    }
+
+    async fn update_merchant_key_store(
+        &self,
+        merchant_id: &str,
+        merchant_key_store_update: storage::MerchantKeyStoreUpdate,
+        key: &Secret<Vec<u8>>,
+    ) -> CustomResult<domain::MerchantKeyStore, errors::StorageError> {
+        let update_func = || async {
+            let conn = connection::pg_connection_write(self).await?;
+            diesel_models::merchant_key_store::MerchantKeyStore::update_by_merchant_id(
+                &conn,
+                merchant_id,
+                merchant_key_store_update,
+            )
+            .await
+            .map_err(Into::into)
+            .into_report()?
             .convert(key)
             .await
             .change_context(errors::StorageError::DecryptionError)
+        };
+
+        #[cfg(not(feature = "accounts_cache"))]
+        {
+            update_func().await
+        }
+
+        #[cfg(feature = "accounts_cache")]
+        {
+            let key_store_cache_key = format!("merchant_key_store_{}", merchant_id);
+            super::cache::publish_and_redact(
+                self,
+                cache::CacheKind::Accounts(key_store_cache_key.into()),
+                update_func,
+            )
+            .await
         }
     }
 }
@@ -127,7 +199,8 @@ impl MerchantKeyStoreInterface for MockDb {
         merchant_id: &str,
         key: &Secret<Vec<u8>>,
     ) -> CustomResult<domain::MerchantKeyStore, errors::StorageError> {
-        self.merchant_key_store
+        let merchant_key_store = self
+            .merchant_key_store
             .lock()
             .await
             .iter()
@@ -135,15 +208,44 @@ impl MerchantKeyStoreInterface for MockDb {
             .cloned()
             .ok_or(errors::StorageError::ValueNotFound(String::from(
                 "merchant_key_store",
-            )))?
-            .convert(key)
-            .await
-            .change_context(errors::StorageError::DecryptionError)
+            )))?;
+
+        convert_with_master_key_version(self, merchant_key_store, key).await
+    }
+
+    async fn update_merchant_key_store(
+        &self,
+        merchant_id: &str,
+        merchant_key_store_update: storage::MerchantKeyStoreUpdate,
+        key: &Secret<Vec<u8>>,
+    ) -> CustomResult<domain::MerchantKeyStore, errors::StorageError> {
+        let mut locked_merchant_key_store = self.merchant_key_store.lock().await;
+        let merchant_key_store = locked_merchant_key_store
+            .iter_mut()
+            .find(|merchant_key| merchant_key.merchant_id == merchant_id)
+            .ok_or(errors::StorageError::ValueNotFound(String::from(
+                "merchant_key_store",
+            )))?;
+
+        match merchant_key_store_update {
+            storage::MerchantKeyStoreUpdate::KeyRotation {
+                key: rotated_key,
+                key_version,
+                rotated_at,
+            } => {
+                merchant_key_store.key = rotated_key;
+                merchant_key_store.key_version = key_version;
+                merchant_key_store.rotated_at = Some(rotated_at);
+            }
+        }
+
+        convert_with_master_key_version(self, merchant_key_store.clone(), key).await
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use masking::PeekInterface;
     use time::macros::datetime;
 
     use crate::{
@@ -170,6 +272,8 @@ mod tests {
                     .await
                     .unwrap(),
                     created_at: datetime!(2023-02-01 0:00),
+                    key_version: 1,
+                    rotated_at: None,
                 },
                 &master_key.to_vec().into(),
             )
@@ -195,6 +299,8 @@ mod tests {
                     .await
                     .unwrap(),
                     created_at: datetime!(2023-02-01 0:00),
+                    key_version: 1,
+                    rotated_at: None,
                 },
                 &master_key.to_vec().into(),
             )
@@ -211,4 +317,63 @@ mod tests {
             .await;
         assert!(find_merchant_key_with_incorrect_master_key_result.is_err());
     }
+
+    #[allow(clippy::unwrap_used)]
+    #[tokio::test]
+    async fn test_mock_db_merchant_key_store_decrypted_with_master_key_of_its_version() {
+        let previous_master_key = vec![7; 32];
+        let mock_db = MockDb::new(&Default::default())
+            .await
+            .with_previous_master_key(previous_master_key.clone());
+        let master_key = mock_db.get_master_key();
+        let merchant_key = services::generate_aes256_key().unwrap().to_vec();
+
+        for (merchant_id, key_version, key) in [
+            ("merchant1", 1, previous_master_key.as_slice()),
+            ("merchant2", 2, master_key),
+        ] {
+            mock_db
+                .insert_merchant_key_store(
+                    domain::MerchantKeyStore {
+                        merchant_id: merchant_id.into(),
+                        key: domain_types::encrypt(merchant_key.clone().into(), key)
+                            .await
+                            .unwrap(),
+                        created_at: datetime!(2023-02-01 0:00),
+                        key_version,
+                        rotated_at: None,
+                    },
+                    &key.to_vec().into(),
+                )
+                .await
+                .unwrap();
+
+            let found_merchant_key = mock_db
+                .get_merchant_key_store_by_merchant_id(merchant_id, &master_key.to_vec().into())
+                .await
+                .unwrap();
+            assert_eq!(found_merchant_key.key.get_inner().peek(), &merchant_key);
+        }
+
+        // Key stores encrypted with a master key which is no longer configured cannot be decrypted
+        mock_db
+            .insert_merchant_key_store(
+                domain::MerchantKeyStore {
+                    merchant_id: "merchant3".into(),
+                    key: domain_types::encrypt(merchant_key.clone().into(), &[9; 32])
+                        .await
+                        .unwrap(),
+                    created_at: datetime!(2023-02-01 0:00),
+                    key_version: 0,
+                    rotated_at: None,
+                },
+                &vec![9; 32].into(),
+            )
+            .await
+            .unwrap();
+        assert!(mock_db
+            .get_merchant_key_store_by_merchant_id("merchant3", &master_key.to_vec().into())
+            .await
+            .is_err());
+    }
 }
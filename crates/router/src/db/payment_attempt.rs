@@ -76,6 +76,17 @@ pub trait PaymentAttemptInterface {
         merchant_id: &str,
         storage_scheme: enums::MerchantStorageScheme,
     ) -> CustomResult<diesel_models::payment_attempt::PaymentListFilters, errors::StorageError>;
+
+    async fn get_payment_method_type_statuses_by_merchant_id(
+        &self,
+        merchant_id: &str,
+        created_after: time::PrimitiveDateTime,
+        limit: i64,
+        storage_scheme: enums::MerchantStorageScheme,
+    ) -> CustomResult<
+        Vec<(Option<enums::PaymentMethodType>, enums::AttemptStatus)>,
+        errors::StorageError,
+    >;
 }
 
 #[cfg(not(feature = "kv_store"))]
@@ -97,7 +108,7 @@ mod storage {
             payment_attempt: PaymentAttemptNew,
             _storage_scheme: enums::MerchantStorageScheme,
         ) -> CustomResult<PaymentAttempt, errors::StorageError> {
-            let conn = connection::pg_connection_write(self).await?;
+            let conn = connection::pg_regional_connection_write(self).await?;
             payment_attempt
                 .insert(&conn)
                 .await
@@ -111,7 +122,7 @@ mod storage {
             payment_attempt: PaymentAttemptUpdate,
             _storage_scheme: enums::MerchantStorageScheme,
         ) -> CustomResult<PaymentAttempt, errors::StorageError> {
-            let conn = connection::pg_connection_write(&self).await?;
+            let conn = connection::pg_regional_connection_write(&self).await?;
             this.update_with_attempt_id(&conn, payment_attempt)
                 .await
                 .map_err(Into::into)
@@ -125,7 +136,7 @@ mod storage {
             merchant_id: &str,
             _storage_scheme: enums::MerchantStorageScheme,
         ) -> CustomResult<PaymentAttempt, errors::StorageError> {
-            let conn = connection::pg_connection_read(self).await?;
+            let conn = connection::pg_regional_connection_read(self).await?;
             PaymentAttempt::find_by_connector_transaction_id_payment_id_merchant_id(
                 &conn,
                 connector_transaction_id,
@@ -143,7 +154,7 @@ mod storage {
             merchant_id: &str,
             _storage_scheme: enums::MerchantStorageScheme,
         ) -> CustomResult<PaymentAttempt, errors::StorageError> {
-            let conn = connection::pg_connection_read(self).await?;
+            let conn = connection::pg_regional_connection_read(self).await?;
             PaymentAttempt::find_last_successful_attempt_by_payment_id_merchant_id(
                 &conn,
                 payment_id,
@@ -160,7 +171,7 @@ mod storage {
             connector_txn_id: &str,
             _storage_scheme: enums::MerchantStorageScheme,
         ) -> CustomResult<PaymentAttempt, errors::StorageError> {
-            let conn = connection::pg_connection_read(self).await?;
+            let conn = connection::pg_regional_connection_read(self).await?;
             PaymentAttempt::find_by_merchant_id_connector_txn_id(
                 &conn,
                 merchant_id,
@@ -178,7 +189,7 @@ mod storage {
             attempt_id: &str,
             _storage_scheme: enums::MerchantStorageScheme,
         ) -> CustomResult<PaymentAttempt, errors::StorageError> {
-            let conn = connection::pg_connection_read(self).await?;
+            let conn = connection::pg_regional_connection_read(self).await?;
 
             PaymentAttempt::find_by_payment_id_merchant_id_attempt_id(
                 &conn,
@@ -198,20 +209,42 @@ mod storage {
             _storage_scheme: enums::MerchantStorageScheme,
         ) -> CustomResult<diesel_models::payment_attempt::PaymentListFilters, errors::StorageError>
         {
-            let conn = connection::pg_connection_read(self).await?;
+            let conn = connection::pg_regional_connection_read(self).await?;
             PaymentAttempt::get_filters_for_payments(&conn, pi, merchant_id)
                 .await
                 .map_err(Into::into)
                 .into_report()
         }
 
+        async fn get_payment_method_type_statuses_by_merchant_id(
+            &self,
+            merchant_id: &str,
+            created_after: time::PrimitiveDateTime,
+            limit: i64,
+            _storage_scheme: enums::MerchantStorageScheme,
+        ) -> CustomResult<
+            Vec<(Option<enums::PaymentMethodType>, enums::AttemptStatus)>,
+            errors::StorageError,
+        > {
+            let conn = connection::pg_regional_connection_read(self).await?;
+            PaymentAttempt::get_payment_method_type_statuses(
+                &conn,
+                merchant_id,
+                created_after,
+                limit,
+            )
+            .await
+            .map_err(Into::into)
+            .into_report()
+        }
+
         async fn find_payment_attempt_by_preprocessing_id_merchant_id(
             &self,
             preprocessing_id: &str,
             merchant_id: &str,
             _storage_scheme: enums::MerchantStorageScheme,
         ) -> CustomResult<PaymentAttempt, errors::StorageError> {
-            let conn = connection::pg_connection_read(self).await?;
+            let conn = connection::pg_regional_connection_read(self).await?;
 
             PaymentAttempt::find_by_merchant_id_preprocessing_id(
                 &conn,
@@ -229,7 +262,7 @@ mod storage {
             payment_id: &str,
             _storage_scheme: enums::MerchantStorageScheme,
         ) -> CustomResult<Vec<PaymentAttempt>, errors::StorageError> {
-            let conn = connection::pg_connection_read(self).await?;
+            let conn = connection::pg_regional_connection_read(self).await?;
             PaymentAttempt::find_by_merchant_id_payment_id(&conn, merchant_id, payment_id)
                 .await
                 .map_err(Into::into)
@@ -242,7 +275,7 @@ mod storage {
             attempt_id: &str,
             _storage_scheme: enums::MerchantStorageScheme,
         ) -> CustomResult<PaymentAttempt, errors::StorageError> {
-            let conn = connection::pg_connection_read(self).await?;
+            let conn = connection::pg_regional_connection_read(self).await?;
 
             PaymentAttempt::find_by_merchant_id_attempt_id(&conn, merchant_id, attempt_id)
                 .await
@@ -275,6 +308,33 @@ impl PaymentAttemptInterface for MockDb {
         Err(errors::StorageError::MockDbError)?
     }
 
+    async fn get_payment_method_type_statuses_by_merchant_id(
+        &self,
+        merchant_id: &str,
+        created_after: time::PrimitiveDateTime,
+        limit: i64,
+        _storage_scheme: enums::MerchantStorageScheme,
+    ) -> CustomResult<
+        Vec<(Option<enums::PaymentMethodType>, enums::AttemptStatus)>,
+        errors::StorageError,
+    > {
+        let payment_attempts = self.payment_attempts.lock().await;
+        let mut payment_attempts = payment_attempts
+            .iter()
+            .filter(|payment_attempt| {
+                payment_attempt.merchant_id == merchant_id
+                    && payment_attempt.created_at >= created_after
+            })
+            .collect::<Vec<_>>();
+        payment_attempts.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+
+        Ok(payment_attempts
+            .into_iter()
+            .take(usize::try_from(limit).unwrap_or_default())
+            .map(|payment_attempt| (payment_attempt.payment_method_type, payment_attempt.status))
+            .collect())
+    }
+
     async fn find_payment_attempt_by_attempt_id_merchant_id(
         &self,
         _attempt_id: &str,
@@ -367,6 +427,8 @@ impl PaymentAttemptInterface for MockDb {
             error_reason: payment_attempt.error_reason,
             multiple_capture_count: payment_attempt.multiple_capture_count,
             connector_response_reference_id: None,
+            previous_attempt_id: payment_attempt.previous_attempt_id,
+            authorization_history: payment_attempt.authorization_history,
         };
         payment_attempts.push(payment_attempt.clone());
         Ok(payment_attempt)
@@ -450,7 +512,7 @@ mod storage {
         ) -> CustomResult<PaymentAttempt, errors::StorageError> {
             match storage_scheme {
                 enums::MerchantStorageScheme::PostgresOnly => {
-                    let conn = connection::pg_connection_write(self).await?;
+                    let conn = connection::pg_regional_connection_write(self).await?;
                     payment_attempt
                         .insert(&conn)
                         .await
@@ -507,6 +569,8 @@ mod storage {
                         error_reason: payment_attempt.error_reason.clone(),
                         multiple_capture_count: payment_attempt.multiple_capture_count,
                         connector_response_reference_id: None,
+                        previous_attempt_id: payment_attempt.previous_attempt_id.clone(),
+                        authorization_history: payment_attempt.authorization_history.clone(),
                     };
 
                     let field = format!("pa_{}", created_attempt.attempt_id);
@@ -522,7 +586,7 @@ mod storage {
                         })
                         .into_report(),
                         Ok(HsetnxReply::KeySet) => {
-                            let conn = connection::pg_connection_write(self).await?;
+                            let conn = connection::pg_regional_connection_write(self).await?;
 
                             //Reverse lookup for attempt_id
                             ReverseLookupNew {
@@ -568,7 +632,7 @@ mod storage {
         ) -> CustomResult<PaymentAttempt, errors::StorageError> {
             match storage_scheme {
                 enums::MerchantStorageScheme::PostgresOnly => {
-                    let conn = connection::pg_connection_write(self).await?;
+                    let conn = connection::pg_regional_connection_write(self).await?;
                     this.update_with_attempt_id(&conn, payment_attempt)
                         .await
                         .map_err(Into::into)
@@ -678,7 +742,7 @@ mod storage {
             storage_scheme: enums::MerchantStorageScheme,
         ) -> CustomResult<PaymentAttempt, errors::StorageError> {
             let database_call = || async {
-                let conn = connection::pg_connection_read(self).await?;
+                let conn = connection::pg_regional_connection_read(self).await?;
                 PaymentAttempt::find_by_connector_transaction_id_payment_id_merchant_id(
                     &conn,
                     connector_transaction_id,
@@ -714,7 +778,7 @@ mod storage {
             merchant_id: &str,
             _storage_scheme: enums::MerchantStorageScheme,
         ) -> CustomResult<PaymentAttempt, errors::StorageError> {
-            let conn = connection::pg_connection_read(self).await?;
+            let conn = connection::pg_regional_connection_read(self).await?;
             PaymentAttempt::find_last_successful_attempt_by_payment_id_merchant_id(
                 &conn,
                 payment_id,
@@ -732,7 +796,7 @@ mod storage {
             storage_scheme: enums::MerchantStorageScheme,
         ) -> CustomResult<PaymentAttempt, errors::StorageError> {
             let database_call = || async {
-                let conn = connection::pg_connection_read(self).await?;
+                let conn = connection::pg_regional_connection_read(self).await?;
                 PaymentAttempt::find_by_merchant_id_connector_txn_id(
                     &conn,
                     merchant_id,
@@ -768,7 +832,7 @@ mod storage {
             storage_scheme: enums::MerchantStorageScheme,
         ) -> CustomResult<PaymentAttempt, errors::StorageError> {
             let database_call = || async {
-                let conn = connection::pg_connection_read(self).await?;
+                let conn = connection::pg_regional_connection_read(self).await?;
                 PaymentAttempt::find_by_merchant_id_attempt_id(&conn, merchant_id, attempt_id)
                     .await
                     .map_err(Into::into)
@@ -799,7 +863,7 @@ mod storage {
             storage_scheme: enums::MerchantStorageScheme,
         ) -> CustomResult<PaymentAttempt, errors::StorageError> {
             let database_call = || async {
-                let conn = connection::pg_connection_read(self).await?;
+                let conn = connection::pg_regional_connection_read(self).await?;
                 PaymentAttempt::find_by_merchant_id_preprocessing_id(
                     &conn,
                     merchant_id,
@@ -835,7 +899,7 @@ mod storage {
             storage_scheme: enums::MerchantStorageScheme,
         ) -> CustomResult<PaymentAttempt, errors::StorageError> {
             let database_call = || async {
-                let conn = connection::pg_connection_read(self).await?;
+                let conn = connection::pg_regional_connection_read(self).await?;
                 PaymentAttempt::find_by_payment_id_merchant_id_attempt_id(
                     &conn,
                     payment_id,
@@ -872,7 +936,7 @@ mod storage {
         ) -> CustomResult<Vec<PaymentAttempt>, errors::StorageError> {
             match storage_scheme {
                 enums::MerchantStorageScheme::PostgresOnly => {
-                    let conn = connection::pg_connection_read(self).await?;
+                    let conn = connection::pg_regional_connection_read(self).await?;
                     PaymentAttempt::find_by_merchant_id_payment_id(&conn, merchant_id, payment_id)
                         .await
                         .map_err(Into::into)
@@ -900,12 +964,36 @@ mod storage {
             _storage_scheme: enums::MerchantStorageScheme,
         ) -> CustomResult<diesel_models::payment_attempt::PaymentListFilters, errors::StorageError>
         {
-            let conn = connection::pg_connection_read(self).await?;
+            let conn = connection::pg_regional_connection_read(self).await?;
             PaymentAttempt::get_filters_for_payments(&conn, pi, merchant_id)
                 .await
                 .map_err(Into::into)
                 .into_report()
         }
+
+        async fn get_payment_method_type_statuses_by_merchant_id(
+            &self,
+            merchant_id: &str,
+            created_after: time::PrimitiveDateTime,
+            limit: i64,
+            _storage_scheme: enums::MerchantStorageScheme,
+        ) -> CustomResult<
+            Vec<(Option<enums::PaymentMethodType>, enums::AttemptStatus)>,
+            errors::StorageError,
+        > {
+            // Attempts which are yet to be drained from the KV store are left out, which does
+            // not matter for the statistics these statuses are used for
+            let conn = connection::pg_regional_connection_read(self).await?;
+            PaymentAttempt::get_payment_method_type_statuses(
+                &conn,
+                merchant_id,
+                created_after,
+                limit,
+            )
+            .await
+            .map_err(Into::into)
+            .into_report()
+        }
     }
 
     #[inline]
@@ -916,7 +1004,7 @@ mod storage {
         updated_attempt_attempt_id: &str,
         connector_transaction_id: &str,
     ) -> CustomResult<ReverseLookup, errors::StorageError> {
-        let conn = connection::pg_connection_write(store).await?;
+        let conn = connection::pg_regional_connection_write(store).await?;
         let field = format!("pa_{}", updated_attempt_attempt_id);
         ReverseLookupNew {
             lookup_id: format!("{}_{}", merchant_id, connector_transaction_id),
@@ -938,7 +1026,7 @@ mod storage {
         updated_attempt_attempt_id: &str,
         preprocessing_id: &str,
     ) -> CustomResult<ReverseLookup, errors::StorageError> {
-        let conn = connection::pg_connection_write(store).await?;
+        let conn = connection::pg_regional_connection_write(store).await?;
         let field = format!("pa_{}", updated_attempt_attempt_id);
         ReverseLookupNew {
             lookup_id: format!("{}_{}", merchant_id, preprocessing_id),
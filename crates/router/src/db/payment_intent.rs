@@ -49,8 +49,26 @@ pub trait PaymentIntentInterface {
         &self,
         merchant_id: &str,
         constraints: &api::PaymentListFilterConstraints,
+        customer_email_hash: Option<&str>,
         storage_scheme: enums::MerchantStorageScheme,
     ) -> CustomResult<Vec<(types::PaymentIntent, types::PaymentAttempt)>, errors::StorageError>;
+
+    #[cfg(feature = "olap")]
+    async fn get_captured_payment_volume_by_currency(
+        &self,
+        merchant_id: &str,
+        start_time: time::PrimitiveDateTime,
+        end_time: time::PrimitiveDateTime,
+        storage_scheme: enums::MerchantStorageScheme,
+    ) -> CustomResult<Vec<types::CurrencyVolume>, errors::StorageError>;
+
+    #[cfg(feature = "olap")]
+    async fn get_payment_funnel_counts(
+        &self,
+        merchant_id: &str,
+        constraints: &api_models::analytics::PaymentsFunnelRequest,
+        storage_scheme: enums::MerchantStorageScheme,
+    ) -> CustomResult<Vec<types::FunnelCount>, errors::StorageError>;
 }
 
 #[cfg(feature = "kv_store")]
@@ -61,7 +79,10 @@ mod storage {
 
     use super::PaymentIntentInterface;
     #[cfg(feature = "olap")]
-    use crate::types::api;
+    use crate::types::{
+        api,
+        storage::{CurrencyVolume, FunnelCount},
+    };
     use crate::{
         connection,
         core::errors::{self, CustomResult},
@@ -79,7 +100,7 @@ mod storage {
         ) -> CustomResult<PaymentIntent, errors::StorageError> {
             match storage_scheme {
                 enums::MerchantStorageScheme::PostgresOnly => {
-                    let conn = connection::pg_connection_write(self).await?;
+                    let conn = connection::pg_regional_connection_write(self).await?;
                     new.insert(&conn).await.map_err(Into::into).into_report()
                 }
 
@@ -116,6 +137,16 @@ mod storage {
                         connector_metadata: new.connector_metadata.clone(),
                         feature_metadata: new.feature_metadata.clone(),
                         attempt_count: new.attempt_count,
+                        invoice_id: new.invoice_id.clone(),
+                        convenience_fee: new.convenience_fee.clone(),
+                        livemode: new.livemode,
+                        l2_l3_data: new.l2_l3_data.clone(),
+                        presentment_amount: new.presentment_amount,
+                        presentment_currency: new.presentment_currency,
+                        settlement_amount: new.settlement_amount,
+                        settlement_currency: new.settlement_currency,
+                        exchange_rate: new.exchange_rate.clone(),
+                        cross_border_fees: new.cross_border_fees.clone(),
                     };
 
                     match self
@@ -159,7 +190,7 @@ mod storage {
         ) -> CustomResult<PaymentIntent, errors::StorageError> {
             match storage_scheme {
                 enums::MerchantStorageScheme::PostgresOnly => {
-                    let conn = connection::pg_connection_write(self).await?;
+                    let conn = connection::pg_regional_connection_write(self).await?;
                     this.update(&conn, payment_intent)
                         .await
                         .map_err(Into::into)
@@ -215,7 +246,7 @@ mod storage {
             storage_scheme: enums::MerchantStorageScheme,
         ) -> CustomResult<PaymentIntent, errors::StorageError> {
             let database_call = || async {
-                let conn = connection::pg_connection_read(self).await?;
+                let conn = connection::pg_regional_connection_read(self).await?;
                 PaymentIntent::find_by_payment_id_merchant_id(&conn, payment_id, merchant_id)
                     .await
                     .map_err(Into::into)
@@ -246,7 +277,7 @@ mod storage {
         ) -> CustomResult<Vec<PaymentIntent>, errors::StorageError> {
             match storage_scheme {
                 enums::MerchantStorageScheme::PostgresOnly => {
-                    let conn = connection::pg_connection_read(self).await?;
+                    let conn = connection::pg_regional_connection_read(self).await?;
                     PaymentIntent::filter_by_constraints(&conn, merchant_id, pc)
                         .await
                         .map_err(Into::into)
@@ -265,7 +296,7 @@ mod storage {
         ) -> CustomResult<Vec<PaymentIntent>, errors::StorageError> {
             match storage_scheme {
                 enums::MerchantStorageScheme::PostgresOnly => {
-                    let conn = connection::pg_connection_read(self).await?;
+                    let conn = connection::pg_regional_connection_read(self).await?;
                     PaymentIntent::filter_by_time_constraints(&conn, merchant_id, time_range)
                         .await
                         .map_err(Into::into)
@@ -281,12 +312,64 @@ mod storage {
             &self,
             merchant_id: &str,
             constraints: &api::PaymentListFilterConstraints,
+            customer_email_hash: Option<&str>,
             storage_scheme: enums::MerchantStorageScheme,
         ) -> CustomResult<Vec<(PaymentIntent, PaymentAttempt)>, errors::StorageError> {
             match storage_scheme {
                 enums::MerchantStorageScheme::PostgresOnly => {
-                    let conn = connection::pg_connection_read(self).await?;
-                    PaymentIntent::apply_filters_on_payments(&conn, merchant_id, constraints)
+                    let conn = connection::pg_regional_connection_read(self).await?;
+                    PaymentIntent::apply_filters_on_payments(
+                        &conn,
+                        merchant_id,
+                        constraints,
+                        customer_email_hash,
+                    )
+                    .await
+                    .map_err(Into::into)
+                    .into_report()
+                }
+
+                enums::MerchantStorageScheme::RedisKv => Err(errors::StorageError::KVError.into()),
+            }
+        }
+
+        #[cfg(feature = "olap")]
+        async fn get_captured_payment_volume_by_currency(
+            &self,
+            merchant_id: &str,
+            start_time: time::PrimitiveDateTime,
+            end_time: time::PrimitiveDateTime,
+            storage_scheme: enums::MerchantStorageScheme,
+        ) -> CustomResult<Vec<CurrencyVolume>, errors::StorageError> {
+            match storage_scheme {
+                enums::MerchantStorageScheme::PostgresOnly => {
+                    let conn = connection::pg_regional_connection_read(self).await?;
+                    PaymentIntent::get_captured_volume_by_currency(
+                        &conn,
+                        merchant_id,
+                        start_time,
+                        end_time,
+                    )
+                    .await
+                    .map_err(Into::into)
+                    .into_report()
+                }
+
+                enums::MerchantStorageScheme::RedisKv => Err(errors::StorageError::KVError.into()),
+            }
+        }
+
+        #[cfg(feature = "olap")]
+        async fn get_payment_funnel_counts(
+            &self,
+            merchant_id: &str,
+            constraints: &api_models::analytics::PaymentsFunnelRequest,
+            storage_scheme: enums::MerchantStorageScheme,
+        ) -> CustomResult<Vec<FunnelCount>, errors::StorageError> {
+            match storage_scheme {
+                enums::MerchantStorageScheme::PostgresOnly => {
+                    let conn = connection::pg_regional_connection_read(self).await?;
+                    PaymentIntent::get_funnel_counts(&conn, merchant_id, constraints)
                         .await
                         .map_err(Into::into)
                         .into_report()
@@ -304,7 +387,10 @@ mod storage {
 
     use super::PaymentIntentInterface;
     #[cfg(feature = "olap")]
-    use crate::types::api;
+    use crate::types::{
+        api,
+        storage::{CurrencyVolume, FunnelCount},
+    };
     use crate::{
         connection,
         core::errors::{self, CustomResult},
@@ -319,7 +405,7 @@ mod storage {
             new: PaymentIntentNew,
             _storage_scheme: enums::MerchantStorageScheme,
         ) -> CustomResult<PaymentIntent, errors::StorageError> {
-            let conn = connection::pg_connection_write(self).await?;
+            let conn = connection::pg_regional_connection_write(self).await?;
             new.insert(&conn).await.map_err(Into::into).into_report()
         }
 
@@ -329,7 +415,7 @@ mod storage {
             payment_intent: PaymentIntentUpdate,
             _storage_scheme: enums::MerchantStorageScheme,
         ) -> CustomResult<PaymentIntent, errors::StorageError> {
-            let conn = connection::pg_connection_write(self).await?;
+            let conn = connection::pg_regional_connection_write(self).await?;
             this.update(&conn, payment_intent)
                 .await
                 .map_err(Into::into)
@@ -342,7 +428,7 @@ mod storage {
             merchant_id: &str,
             _storage_scheme: enums::MerchantStorageScheme,
         ) -> CustomResult<PaymentIntent, errors::StorageError> {
-            let conn = connection::pg_connection_read(self).await?;
+            let conn = connection::pg_regional_connection_read(self).await?;
             PaymentIntent::find_by_payment_id_merchant_id(&conn, payment_id, merchant_id)
                 .await
                 .map_err(Into::into)
@@ -356,7 +442,7 @@ mod storage {
             pc: &api::PaymentListConstraints,
             _storage_scheme: enums::MerchantStorageScheme,
         ) -> CustomResult<Vec<PaymentIntent>, errors::StorageError> {
-            let conn = connection::pg_connection_read(self).await?;
+            let conn = connection::pg_regional_connection_read(self).await?;
             PaymentIntent::filter_by_constraints(&conn, merchant_id, pc)
                 .await
                 .map_err(Into::into)
@@ -369,7 +455,7 @@ mod storage {
             time_range: &api::TimeRange,
             _storage_scheme: enums::MerchantStorageScheme,
         ) -> CustomResult<Vec<PaymentIntent>, errors::StorageError> {
-            let conn = connection::pg_connection_read(self).await?;
+            let conn = connection::pg_regional_connection_read(self).await?;
             PaymentIntent::filter_by_time_constraints(&conn, merchant_id, time_range)
                 .await
                 .map_err(Into::into)
@@ -381,10 +467,45 @@ mod storage {
             &self,
             merchant_id: &str,
             constraints: &api::PaymentListFilterConstraints,
+            customer_email_hash: Option<&str>,
             _storage_scheme: enums::MerchantStorageScheme,
         ) -> CustomResult<Vec<(PaymentIntent, PaymentAttempt)>, errors::StorageError> {
-            let conn = connection::pg_connection_read(self).await?;
-            PaymentIntent::apply_filters_on_payments(&conn, merchant_id, constraints)
+            let conn = connection::pg_regional_connection_read(self).await?;
+            PaymentIntent::apply_filters_on_payments(
+                &conn,
+                merchant_id,
+                constraints,
+                customer_email_hash,
+            )
+            .await
+            .map_err(Into::into)
+            .into_report()
+        }
+
+        #[cfg(feature = "olap")]
+        async fn get_captured_payment_volume_by_currency(
+            &self,
+            merchant_id: &str,
+            start_time: time::PrimitiveDateTime,
+            end_time: time::PrimitiveDateTime,
+            _storage_scheme: enums::MerchantStorageScheme,
+        ) -> CustomResult<Vec<CurrencyVolume>, errors::StorageError> {
+            let conn = connection::pg_regional_connection_read(self).await?;
+            PaymentIntent::get_captured_volume_by_currency(&conn, merchant_id, start_time, end_time)
+                .await
+                .map_err(Into::into)
+                .into_report()
+        }
+
+        #[cfg(feature = "olap")]
+        async fn get_payment_funnel_counts(
+            &self,
+            merchant_id: &str,
+            constraints: &api_models::analytics::PaymentsFunnelRequest,
+            _storage_scheme: enums::MerchantStorageScheme,
+        ) -> CustomResult<Vec<FunnelCount>, errors::StorageError> {
+            let conn = connection::pg_regional_connection_read(self).await?;
+            PaymentIntent::get_funnel_counts(&conn, merchant_id, constraints)
                 .await
                 .map_err(Into::into)
                 .into_report()
@@ -419,12 +540,35 @@ impl PaymentIntentInterface for MockDb {
         &self,
         _merchant_id: &str,
         _constraints: &api::PaymentListFilterConstraints,
+        _customer_email_hash: Option<&str>,
         _storage_scheme: enums::MerchantStorageScheme,
     ) -> CustomResult<Vec<(types::PaymentIntent, types::PaymentAttempt)>, errors::StorageError>
     {
         // [#172]: Implement function for `MockDb`
         Err(errors::StorageError::MockDbError)?
     }
+    #[cfg(feature = "olap")]
+    async fn get_captured_payment_volume_by_currency(
+        &self,
+        _merchant_id: &str,
+        _start_time: time::PrimitiveDateTime,
+        _end_time: time::PrimitiveDateTime,
+        _storage_scheme: enums::MerchantStorageScheme,
+    ) -> CustomResult<Vec<types::CurrencyVolume>, errors::StorageError> {
+        // [#172]: Implement function for `MockDb`
+        Err(errors::StorageError::MockDbError)?
+    }
+
+    #[cfg(feature = "olap")]
+    async fn get_payment_funnel_counts(
+        &self,
+        _merchant_id: &str,
+        _constraints: &api_models::analytics::PaymentsFunnelRequest,
+        _storage_scheme: enums::MerchantStorageScheme,
+    ) -> CustomResult<Vec<types::FunnelCount>, errors::StorageError> {
+        // [#172]: Implement function for `MockDb`
+        Err(errors::StorageError::MockDbError)?
+    }
 
     #[allow(clippy::panic)]
     async fn insert_payment_intent(
@@ -466,6 +610,16 @@ impl PaymentIntentInterface for MockDb {
             connector_metadata: new.connector_metadata,
             feature_metadata: new.feature_metadata,
             attempt_count: new.attempt_count,
+            invoice_id: new.invoice_id,
+            convenience_fee: new.convenience_fee,
+            livemode: new.livemode,
+            l2_l3_data: new.l2_l3_data,
+            presentment_amount: new.presentment_amount,
+            presentment_currency: new.presentment_currency,
+            settlement_amount: new.settlement_amount,
+            settlement_currency: new.settlement_currency,
+            exchange_rate: new.exchange_rate,
+            cross_border_fees: new.cross_border_fees,
         };
         payment_intents.push(payment_intent.clone());
         Ok(payment_intent)
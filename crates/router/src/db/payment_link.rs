@@ -0,0 +1,108 @@
+use error_stack::IntoReport;
+
+use super::{MockDb, Store};
+use crate::{
+    connection,
+    core::errors::{self, CustomResult},
+    types::storage,
+};
+
+#[async_trait::async_trait]
+pub trait PaymentLinkInterface {
+    async fn insert_payment_link(
+        &self,
+        payment_link: storage::PaymentLinkNew,
+    ) -> CustomResult<storage::PaymentLink, errors::StorageError>;
+
+    async fn find_payment_link_by_merchant_id_payment_link_id(
+        &self,
+        merchant_id: &str,
+        payment_link_id: &str,
+    ) -> CustomResult<storage::PaymentLink, errors::StorageError>;
+
+    async fn update_payment_link_by_merchant_id_payment_link_id(
+        &self,
+        merchant_id: &str,
+        payment_link_id: &str,
+        payment_link_update: storage::PaymentLinkUpdate,
+    ) -> CustomResult<storage::PaymentLink, errors::StorageError>;
+}
+
+#[async_trait::async_trait]
+impl PaymentLinkInterface for Store {
+    async fn insert_payment_link(
+        &self,
+        payment_link: storage::PaymentLinkNew,
+    ) -> CustomResult<storage::PaymentLink, errors::StorageError> {
+        let conn = connection::pg_regional_connection_write(self).await?;
+        payment_link
+            .insert(&conn)
+            .await
+            .map_err(Into::into)
+            .into_report()
+    }
+
+    async fn find_payment_link_by_merchant_id_payment_link_id(
+        &self,
+        merchant_id: &str,
+        payment_link_id: &str,
+    ) -> CustomResult<storage::PaymentLink, errors::StorageError> {
+        let conn = connection::pg_regional_connection_read(self).await?;
+        storage::PaymentLink::find_by_merchant_id_payment_link_id(
+            &conn,
+            merchant_id,
+            payment_link_id,
+        )
+        .await
+        .map_err(Into::into)
+        .into_report()
+    }
+
+    async fn update_payment_link_by_merchant_id_payment_link_id(
+        &self,
+        merchant_id: &str,
+        payment_link_id: &str,
+        payment_link_update: storage::PaymentLinkUpdate,
+    ) -> CustomResult<storage::PaymentLink, errors::StorageError> {
+        let conn = connection::pg_regional_connection_write(self).await?;
+        storage::PaymentLink::update_by_merchant_id_payment_link_id(
+            &conn,
+            merchant_id,
+            payment_link_id,
+            payment_link_update,
+        )
+        .await
+        .map_err(Into::into)
+        .into_report()
+    }
+}
+
+#[async_trait::async_trait]
+impl PaymentLinkInterface for MockDb {
+    async fn insert_payment_link(
+        &self,
+        _payment_link: storage::PaymentLinkNew,
+    ) -> CustomResult<storage::PaymentLink, errors::StorageError> {
+        // TODO: Implement function for `MockDb`
+        Err(errors::StorageError::MockDbError)?
+    }
+
+    async fn find_payment_link_by_merchant_id_payment_link_id(
+        &self,
+        _merchant_id: &str,
+        _payment_link_id: &str,
+    ) -> CustomResult<storage::PaymentLink, errors::StorageError> {
+        // TODO: Implement function for `MockDb`
+        Err(errors::StorageError::MockDbError)?
+    }
+
+    async fn update_payment_link_by_merchant_id_payment_link_id(
+        &self,
+        _merchant_id: &str,
+        _payment_link_id: &str,
+        _payment_link_update: storage::PaymentLinkUpdate,
+    ) -> CustomResult<storage::PaymentLink, errors::StorageError> {
+        // TODO: Implement function for `MockDb`
+        Err(errors::StorageError::MockDbError)?
+    }
+}
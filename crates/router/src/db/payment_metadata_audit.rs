@@ -0,0 +1,146 @@
+use error_stack::IntoReport;
+use router_env::logger;
+
+use super::{payment_intent::PaymentIntentInterface, MockDb, Store};
+use crate::{
+    connection,
+    core::errors::{self, CustomResult},
+    types::storage::{self, enums},
+};
+
+#[async_trait::async_trait]
+pub trait PaymentMetadataAuditInterface {
+    async fn insert_payment_metadata_audit(
+        &self,
+        payment_metadata_audit: storage::PaymentMetadataAuditNew,
+    ) -> CustomResult<storage::PaymentMetadataAudit, errors::StorageError>;
+
+    /// Updates the payment intent and records the update in the audit of its metadata, so that an
+    /// update is never made without being recorded.
+    async fn update_payment_intent_with_metadata_audit(
+        &self,
+        payment_intent: storage::PaymentIntent,
+        payment_intent_update: storage::PaymentIntentUpdate,
+        payment_metadata_audit: storage::PaymentMetadataAuditNew,
+        storage_scheme: enums::MerchantStorageScheme,
+    ) -> CustomResult<(storage::PaymentIntent, storage::PaymentMetadataAudit), errors::StorageError>;
+
+    async fn list_payment_metadata_audits_by_merchant_id_payment_id(
+        &self,
+        merchant_id: &str,
+        payment_id: &str,
+    ) -> CustomResult<Vec<storage::PaymentMetadataAudit>, errors::StorageError>;
+}
+
+#[async_trait::async_trait]
+impl PaymentMetadataAuditInterface for Store {
+    async fn insert_payment_metadata_audit(
+        &self,
+        payment_metadata_audit: storage::PaymentMetadataAuditNew,
+    ) -> CustomResult<storage::PaymentMetadataAudit, errors::StorageError> {
+        let conn = connection::pg_regional_connection_write(self).await?;
+        payment_metadata_audit
+            .insert(&conn)
+            .await
+            .map_err(Into::into)
+            .into_report()
+    }
+
+    async fn update_payment_intent_with_metadata_audit(
+        &self,
+        payment_intent: storage::PaymentIntent,
+        payment_intent_update: storage::PaymentIntentUpdate,
+        payment_metadata_audit: storage::PaymentMetadataAuditNew,
+        storage_scheme: enums::MerchantStorageScheme,
+    ) -> CustomResult<(storage::PaymentIntent, storage::PaymentMetadataAudit), errors::StorageError>
+    {
+        let conn = connection::pg_regional_connection_write(self).await?;
+        match storage_scheme {
+            enums::MerchantStorageScheme::PostgresOnly => payment_metadata_audit
+                .insert_with_payment_intent_update(&conn, payment_intent, payment_intent_update)
+                .await
+                .map_err(Into::into)
+                .into_report(),
+
+            // The payment intent of KV merchants is not updated in the database, so the audit
+            // record is inserted first and removed again if the payment intent can't be updated.
+            enums::MerchantStorageScheme::RedisKv => {
+                let payment_metadata_audit = payment_metadata_audit
+                    .insert(&conn)
+                    .await
+                    .map_err(Into::<errors::StorageError>::into)
+                    .into_report()?;
+
+                match self
+                    .update_payment_intent(payment_intent, payment_intent_update, storage_scheme)
+                    .await
+                {
+                    Ok(payment_intent) => Ok((payment_intent, payment_metadata_audit)),
+                    Err(error) => {
+                        storage::PaymentMetadataAudit::delete_by_id(
+                            &conn,
+                            payment_metadata_audit.id,
+                        )
+                        .await
+                        .map_err(|delete_error| {
+                            logger::error!(
+                                ?delete_error,
+                                "Failed to remove the audit record of a failed update of the payment metadata"
+                            )
+                        })
+                        .ok();
+                        Err(error)
+                    }
+                }
+            }
+        }
+    }
+
+    async fn list_payment_metadata_audits_by_merchant_id_payment_id(
+        &self,
+        merchant_id: &str,
+        payment_id: &str,
+    ) -> CustomResult<Vec<storage::PaymentMetadataAudit>, errors::StorageError> {
+        let conn = connection::pg_regional_connection_read(self).await?;
+        storage::PaymentMetadataAudit::find_by_merchant_id_payment_id(
+            &conn,
+            merchant_id,
+            payment_id,
+        )
+        .await
+        .map_err(Into::into)
+        .into_report()
+    }
+}
+
+#[async_trait::async_trait]
+impl PaymentMetadataAuditInterface for MockDb {
+    async fn insert_payment_metadata_audit(
+        &self,
+        _payment_metadata_audit: storage::PaymentMetadataAuditNew,
+    ) -> CustomResult<storage::PaymentMetadataAudit, errors::StorageError> {
+        // TODO: Implement function for `MockDb`
+        Err(errors::StorageError::MockDbError)?
+    }
+
+    async fn update_payment_intent_with_metadata_audit(
+        &self,
+        _payment_intent: storage::PaymentIntent,
+        _payment_intent_update: storage::PaymentIntentUpdate,
+        _payment_metadata_audit: storage::PaymentMetadataAuditNew,
+        _storage_scheme: enums::MerchantStorageScheme,
+    ) -> CustomResult<(storage::PaymentIntent, storage::PaymentMetadataAudit), errors::StorageError>
+    {
+        // TODO: Implement function for `MockDb`
+        Err(errors::StorageError::MockDbError)?
+    }
+
+    async fn list_payment_metadata_audits_by_merchant_id_payment_id(
+        &self,
+        _merchant_id: &str,
+        _payment_id: &str,
+    ) -> CustomResult<Vec<storage::PaymentMetadataAudit>, errors::StorageError> {
+        // TODO: Implement function for `MockDb`
+        Err(errors::StorageError::MockDbError)?
+    }
+}
@@ -5,7 +5,7 @@ use super::{MockDb, Store};
 use crate::{
     connection,
     core::errors::{self, CustomResult},
-    types::storage,
+    types::storage::{self, enums},
 };
 
 #[async_trait::async_trait]
@@ -21,6 +21,12 @@ pub trait PaymentMethodInterface {
         merchant_id: &str,
     ) -> CustomResult<Vec<storage::PaymentMethod>, errors::StorageError>;
 
+    async fn find_card_payment_methods_after_id(
+        &self,
+        last_id: i32,
+        limit: i64,
+    ) -> CustomResult<Vec<storage::PaymentMethod>, errors::StorageError>;
+
     async fn insert_payment_method(
         &self,
         payment_method_new: storage::PaymentMethodNew,
@@ -45,7 +51,7 @@ impl PaymentMethodInterface for Store {
         &self,
         payment_method_id: &str,
     ) -> CustomResult<storage::PaymentMethod, errors::StorageError> {
-        let conn = connection::pg_connection_read(self).await?;
+        let conn = connection::pg_regional_connection_read(self).await?;
         storage::PaymentMethod::find_by_payment_method_id(&conn, payment_method_id)
             .await
             .map_err(Into::into)
@@ -56,7 +62,7 @@ impl PaymentMethodInterface for Store {
         &self,
         payment_method_new: storage::PaymentMethodNew,
     ) -> CustomResult<storage::PaymentMethod, errors::StorageError> {
-        let conn = connection::pg_connection_write(self).await?;
+        let conn = connection::pg_regional_connection_write(self).await?;
         payment_method_new
             .insert(&conn)
             .await
@@ -69,7 +75,7 @@ impl PaymentMethodInterface for Store {
         payment_method: storage::PaymentMethod,
         payment_method_update: storage::PaymentMethodUpdate,
     ) -> CustomResult<storage::PaymentMethod, errors::StorageError> {
-        let conn = connection::pg_connection_write(self).await?;
+        let conn = connection::pg_regional_connection_write(self).await?;
         payment_method
             .update_with_payment_method_id(&conn, payment_method_update)
             .await
@@ -82,19 +88,31 @@ impl PaymentMethodInterface for Store {
         customer_id: &str,
         merchant_id: &str,
     ) -> CustomResult<Vec<storage::PaymentMethod>, errors::StorageError> {
-        let conn = connection::pg_connection_read(self).await?;
+        let conn = connection::pg_regional_connection_read(self).await?;
         storage::PaymentMethod::find_by_customer_id_merchant_id(&conn, customer_id, merchant_id)
             .await
             .map_err(Into::into)
             .into_report()
     }
 
+    async fn find_card_payment_methods_after_id(
+        &self,
+        last_id: i32,
+        limit: i64,
+    ) -> CustomResult<Vec<storage::PaymentMethod>, errors::StorageError> {
+        let conn = connection::pg_regional_connection_read(self).await?;
+        storage::PaymentMethod::find_cards_after_id(&conn, last_id, limit)
+            .await
+            .map_err(Into::into)
+            .into_report()
+    }
+
     async fn delete_payment_method_by_merchant_id_payment_method_id(
         &self,
         merchant_id: &str,
         payment_method_id: &str,
     ) -> CustomResult<storage::PaymentMethod, errors::StorageError> {
-        let conn = connection::pg_connection_write(self).await?;
+        let conn = connection::pg_regional_connection_write(self).await?;
         storage::PaymentMethod::delete_by_merchant_id_payment_method_id(
             &conn,
             merchant_id,
@@ -156,6 +174,15 @@ impl PaymentMethodInterface for MockDb {
             payment_method_issuer: payment_method_new.payment_method_issuer,
             payment_method_issuer_code: payment_method_new.payment_method_issuer_code,
             metadata: payment_method_new.metadata,
+            card_exp_month: payment_method_new.card_exp_month,
+            card_exp_year: payment_method_new.card_exp_year,
+            expiry_notified_at: None,
+            locker_id: payment_method_new.locker_id,
+            account_update_type: None,
+            account_update_source: None,
+            account_updated_at: None,
+            network_token: None,
+            last_used_at: None,
         };
         payment_methods.push(payment_method.clone());
         Ok(payment_method)
@@ -183,6 +210,22 @@ impl PaymentMethodInterface for MockDb {
         }
     }
 
+    async fn find_card_payment_methods_after_id(
+        &self,
+        last_id: i32,
+        limit: i64,
+    ) -> CustomResult<Vec<storage::PaymentMethod>, errors::StorageError> {
+        let payment_methods = self.payment_methods.lock().await;
+        let mut payment_methods_found: Vec<storage::PaymentMethod> = payment_methods
+            .iter()
+            .filter(|pm| pm.id > last_id && pm.payment_method == enums::PaymentMethod::Card)
+            .cloned()
+            .collect();
+        payment_methods_found.sort_by_key(|pm| pm.id);
+        payment_methods_found.truncate(usize::try_from(limit).unwrap_or(0));
+        Ok(payment_methods_found)
+    }
+
     async fn delete_payment_method_by_merchant_id_payment_method_id(
         &self,
         merchant_id: &str,
@@ -0,0 +1,187 @@
+use error_stack::IntoReport;
+
+use super::{MockDb, Store};
+use crate::{
+    connection,
+    core::errors::{self, CustomResult},
+    types::storage,
+};
+
+#[async_trait::async_trait]
+pub trait PaymentMethodShareInterface {
+    async fn insert_payment_method_share(
+        &self,
+        payment_method_share: storage::PaymentMethodShareNew,
+    ) -> CustomResult<storage::PaymentMethodShare, errors::StorageError>;
+
+    async fn find_payment_method_share_by_share_id(
+        &self,
+        share_id: &str,
+    ) -> CustomResult<storage::PaymentMethodShare, errors::StorageError>;
+
+    async fn find_payment_method_shares_by_merchant_id_payment_method_id(
+        &self,
+        merchant_id: &str,
+        payment_method_id: &str,
+    ) -> CustomResult<Vec<storage::PaymentMethodShare>, errors::StorageError>;
+
+    async fn update_payment_method_share_by_share_id(
+        &self,
+        share_id: &str,
+        payment_method_share_update: storage::PaymentMethodShareUpdate,
+    ) -> CustomResult<storage::PaymentMethodShare, errors::StorageError>;
+
+    async fn update_active_payment_method_share_by_share_id_use_count(
+        &self,
+        share_id: &str,
+        current_use_count: i32,
+        payment_method_share_update: storage::PaymentMethodShareUpdate,
+    ) -> CustomResult<storage::PaymentMethodShare, errors::StorageError>;
+
+    async fn release_payment_method_share_use_by_share_id(
+        &self,
+        share_id: &str,
+    ) -> CustomResult<storage::PaymentMethodShare, errors::StorageError>;
+}
+
+#[async_trait::async_trait]
+impl PaymentMethodShareInterface for Store {
+    async fn insert_payment_method_share(
+        &self,
+        payment_method_share: storage::PaymentMethodShareNew,
+    ) -> CustomResult<storage::PaymentMethodShare, errors::StorageError> {
+        let conn = connection::pg_regional_connection_write(self).await?;
+        payment_method_share
+            .insert(&conn)
+            .await
+            .map_err(Into::into)
+            .into_report()
+    }
+
+    async fn find_payment_method_share_by_share_id(
+        &self,
+        share_id: &str,
+    ) -> CustomResult<storage::PaymentMethodShare, errors::StorageError> {
+        let conn = connection::pg_regional_connection_read(self).await?;
+        storage::PaymentMethodShare::find_by_share_id(&conn, share_id)
+            .await
+            .map_err(Into::into)
+            .into_report()
+    }
+
+    async fn find_payment_method_shares_by_merchant_id_payment_method_id(
+        &self,
+        merchant_id: &str,
+        payment_method_id: &str,
+    ) -> CustomResult<Vec<storage::PaymentMethodShare>, errors::StorageError> {
+        let conn = connection::pg_regional_connection_read(self).await?;
+        storage::PaymentMethodShare::find_by_merchant_id_payment_method_id(
+            &conn,
+            merchant_id,
+            payment_method_id,
+        )
+        .await
+        .map_err(Into::into)
+        .into_report()
+    }
+
+    async fn update_payment_method_share_by_share_id(
+        &self,
+        share_id: &str,
+        payment_method_share_update: storage::PaymentMethodShareUpdate,
+    ) -> CustomResult<storage::PaymentMethodShare, errors::StorageError> {
+        let conn = connection::pg_regional_connection_write(self).await?;
+        storage::PaymentMethodShare::update_by_share_id(
+            &conn,
+            share_id,
+            payment_method_share_update,
+        )
+        .await
+        .map_err(Into::into)
+        .into_report()
+    }
+
+    async fn update_active_payment_method_share_by_share_id_use_count(
+        &self,
+        share_id: &str,
+        current_use_count: i32,
+        payment_method_share_update: storage::PaymentMethodShareUpdate,
+    ) -> CustomResult<storage::PaymentMethodShare, errors::StorageError> {
+        let conn = connection::pg_regional_connection_write(self).await?;
+        storage::PaymentMethodShare::update_active_by_share_id_use_count(
+            &conn,
+            share_id,
+            current_use_count,
+            payment_method_share_update,
+        )
+        .await
+        .map_err(Into::into)
+        .into_report()
+    }
+
+    async fn release_payment_method_share_use_by_share_id(
+        &self,
+        share_id: &str,
+    ) -> CustomResult<storage::PaymentMethodShare, errors::StorageError> {
+        let conn = connection::pg_regional_connection_write(self).await?;
+        storage::PaymentMethodShare::release_use_by_share_id(&conn, share_id)
+            .await
+            .map_err(Into::into)
+            .into_report()
+    }
+}
+
+#[async_trait::async_trait]
+impl PaymentMethodShareInterface for MockDb {
+    async fn insert_payment_method_share(
+        &self,
+        _payment_method_share: storage::PaymentMethodShareNew,
+    ) -> CustomResult<storage::PaymentMethodShare, errors::StorageError> {
+        // TODO: Implement function for `MockDb`
+        Err(errors::StorageError::MockDbError)?
+    }
+
+    async fn find_payment_method_share_by_share_id(
+        &self,
+        _share_id: &str,
+    ) -> CustomResult<storage::PaymentMethodShare, errors::StorageError> {
+        // TODO: Implement function for `MockDb`
+        Err(errors::StorageError::MockDbError)?
+    }
+
+    async fn find_payment_method_shares_by_merchant_id_payment_method_id(
+        &self,
+        _merchant_id: &str,
+        _payment_method_id: &str,
+    ) -> CustomResult<Vec<storage::PaymentMethodShare>, errors::StorageError> {
+        // TODO: Implement function for `MockDb`
+        Err(errors::StorageError::MockDbError)?
+    }
+
+    async fn update_payment_method_share_by_share_id(
+        &self,
+        _share_id: &str,
+        _payment_method_share_update: storage::PaymentMethodShareUpdate,
+    ) -> CustomResult<storage::PaymentMethodShare, errors::StorageError> {
+        // TODO: Implement function for `MockDb`
+        Err(errors::StorageError::MockDbError)?
+    }
+
+    async fn update_active_payment_method_share_by_share_id_use_count(
+        &self,
+        _share_id: &str,
+        _current_use_count: i32,
+        _payment_method_share_update: storage::PaymentMethodShareUpdate,
+    ) -> CustomResult<storage::PaymentMethodShare, errors::StorageError> {
+        // TODO: Implement function for `MockDb`
+        Err(errors::StorageError::MockDbError)?
+    }
+
+    async fn release_payment_method_share_use_by_share_id(
+        &self,
+        _share_id: &str,
+    ) -> CustomResult<storage::PaymentMethodShare, errors::StorageError> {
+        // TODO: Implement function for `MockDb`
+        Err(errors::StorageError::MockDbError)?
+    }
+}
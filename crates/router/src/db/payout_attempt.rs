@@ -26,6 +26,18 @@ pub trait PayoutAttemptInterface {
         &self,
         _payout: storage::PayoutAttemptNew,
     ) -> CustomResult<storage::PayoutAttempt, errors::StorageError>;
+
+    async fn find_payout_attempts_by_merchant_id_connector_payout_id(
+        &self,
+        _merchant_id: &str,
+        _connector_payout_id: &str,
+    ) -> CustomResult<Vec<storage::PayoutAttempt>, errors::StorageError>;
+
+    async fn find_payout_attempts_by_merchant_id_payout_ids(
+        &self,
+        _merchant_id: &str,
+        _payout_ids: &[String],
+    ) -> CustomResult<Vec<storage::PayoutAttempt>, errors::StorageError>;
 }
 
 #[async_trait::async_trait]
@@ -35,7 +47,7 @@ impl PayoutAttemptInterface for Store {
         merchant_id: &str,
         payout_id: &str,
     ) -> CustomResult<storage::PayoutAttempt, errors::StorageError> {
-        let conn = connection::pg_connection_read(self).await?;
+        let conn = connection::pg_regional_connection_read(self).await?;
         storage::PayoutAttempt::find_by_merchant_id_payout_id(&conn, merchant_id, payout_id)
             .await
             .map_err(Into::into)
@@ -48,7 +60,7 @@ impl PayoutAttemptInterface for Store {
         payout_id: &str,
         payout: storage::PayoutAttemptUpdate,
     ) -> CustomResult<storage::PayoutAttempt, errors::StorageError> {
-        let conn = connection::pg_connection_write(self).await?;
+        let conn = connection::pg_regional_connection_write(self).await?;
         storage::PayoutAttempt::update_by_merchant_id_payout_id(
             &conn,
             merchant_id,
@@ -64,9 +76,37 @@ impl PayoutAttemptInterface for Store {
         &self,
         payout: storage::PayoutAttemptNew,
     ) -> CustomResult<storage::PayoutAttempt, errors::StorageError> {
-        let conn = connection::pg_connection_write(self).await?;
+        let conn = connection::pg_regional_connection_write(self).await?;
         payout.insert(&conn).await.map_err(Into::into).into_report()
     }
+
+    async fn find_payout_attempts_by_merchant_id_connector_payout_id(
+        &self,
+        merchant_id: &str,
+        connector_payout_id: &str,
+    ) -> CustomResult<Vec<storage::PayoutAttempt>, errors::StorageError> {
+        let conn = connection::pg_regional_connection_read(self).await?;
+        storage::PayoutAttempt::filter_by_merchant_id_connector_payout_id(
+            &conn,
+            merchant_id,
+            connector_payout_id,
+        )
+        .await
+        .map_err(Into::into)
+        .into_report()
+    }
+
+    async fn find_payout_attempts_by_merchant_id_payout_ids(
+        &self,
+        merchant_id: &str,
+        payout_ids: &[String],
+    ) -> CustomResult<Vec<storage::PayoutAttempt>, errors::StorageError> {
+        let conn = connection::pg_regional_connection_read(self).await?;
+        storage::PayoutAttempt::filter_by_merchant_id_payout_ids(&conn, merchant_id, payout_ids)
+            .await
+            .map_err(Into::into)
+            .into_report()
+    }
 }
 
 #[async_trait::async_trait]
@@ -97,4 +137,22 @@ impl PayoutAttemptInterface for MockDb {
         // TODO: Implement function for `MockDb`
         Err(errors::StorageError::MockDbError)?
     }
+
+    async fn find_payout_attempts_by_merchant_id_connector_payout_id(
+        &self,
+        _merchant_id: &str,
+        _connector_payout_id: &str,
+    ) -> CustomResult<Vec<storage::PayoutAttempt>, errors::StorageError> {
+        // TODO: Implement function for `MockDb`
+        Err(errors::StorageError::MockDbError)?
+    }
+
+    async fn find_payout_attempts_by_merchant_id_payout_ids(
+        &self,
+        _merchant_id: &str,
+        _payout_ids: &[String],
+    ) -> CustomResult<Vec<storage::PayoutAttempt>, errors::StorageError> {
+        // TODO: Implement function for `MockDb`
+        Err(errors::StorageError::MockDbError)?
+    }
 }
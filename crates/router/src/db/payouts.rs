@@ -4,7 +4,7 @@ use super::{MockDb, Store};
 use crate::{
     connection,
     core::errors::{self, CustomResult},
-    types::storage,
+    types::storage::{self, PayoutsDbExt},
 };
 
 #[async_trait::async_trait]
@@ -26,6 +26,27 @@ pub trait PayoutsInterface {
         &self,
         _payout: storage::PayoutsNew,
     ) -> CustomResult<storage::Payouts, errors::StorageError>;
+
+    async fn get_pending_payout_volume_by_currency(
+        &self,
+        _merchant_id: &str,
+        _start_time: time::PrimitiveDateTime,
+        _end_time: time::PrimitiveDateTime,
+    ) -> CustomResult<Vec<storage::CurrencyVolume>, errors::StorageError>;
+
+    async fn get_payout_volumes(
+        &self,
+        _merchant_id: &str,
+        _start_time: time::PrimitiveDateTime,
+        _end_time: time::PrimitiveDateTime,
+    ) -> CustomResult<Vec<storage::PayoutVolume>, errors::StorageError>;
+
+    async fn get_payout_failure_counts(
+        &self,
+        _merchant_id: &str,
+        _start_time: time::PrimitiveDateTime,
+        _end_time: time::PrimitiveDateTime,
+    ) -> CustomResult<Vec<storage::PayoutFailureCount>, errors::StorageError>;
 }
 
 #[async_trait::async_trait]
@@ -35,7 +56,7 @@ impl PayoutsInterface for Store {
         merchant_id: &str,
         payout_id: &str,
     ) -> CustomResult<storage::Payouts, errors::StorageError> {
-        let conn = connection::pg_connection_read(self).await?;
+        let conn = connection::pg_regional_connection_read(self).await?;
         storage::Payouts::find_by_merchant_id_payout_id(&conn, merchant_id, payout_id)
             .await
             .map_err(Into::into)
@@ -48,7 +69,7 @@ impl PayoutsInterface for Store {
         payout_id: &str,
         payout: storage::PayoutsUpdate,
     ) -> CustomResult<storage::Payouts, errors::StorageError> {
-        let conn = connection::pg_connection_write(self).await?;
+        let conn = connection::pg_regional_connection_write(self).await?;
         storage::Payouts::update_by_merchant_id_payout_id(&conn, merchant_id, payout_id, payout)
             .await
             .map_err(Into::into)
@@ -59,9 +80,48 @@ impl PayoutsInterface for Store {
         &self,
         payout: storage::PayoutsNew,
     ) -> CustomResult<storage::Payouts, errors::StorageError> {
-        let conn = connection::pg_connection_write(self).await?;
+        let conn = connection::pg_regional_connection_write(self).await?;
         payout.insert(&conn).await.map_err(Into::into).into_report()
     }
+
+    async fn get_pending_payout_volume_by_currency(
+        &self,
+        merchant_id: &str,
+        start_time: time::PrimitiveDateTime,
+        end_time: time::PrimitiveDateTime,
+    ) -> CustomResult<Vec<storage::CurrencyVolume>, errors::StorageError> {
+        let conn = connection::pg_regional_connection_read(self).await?;
+        storage::Payouts::get_pending_volume_by_currency(&conn, merchant_id, start_time, end_time)
+            .await
+            .map_err(Into::into)
+            .into_report()
+    }
+
+    async fn get_payout_volumes(
+        &self,
+        merchant_id: &str,
+        start_time: time::PrimitiveDateTime,
+        end_time: time::PrimitiveDateTime,
+    ) -> CustomResult<Vec<storage::PayoutVolume>, errors::StorageError> {
+        let conn = connection::pg_regional_connection_read(self).await?;
+        storage::Payouts::get_volumes(&conn, merchant_id, start_time, end_time)
+            .await
+            .map_err(Into::into)
+            .into_report()
+    }
+
+    async fn get_payout_failure_counts(
+        &self,
+        merchant_id: &str,
+        start_time: time::PrimitiveDateTime,
+        end_time: time::PrimitiveDateTime,
+    ) -> CustomResult<Vec<storage::PayoutFailureCount>, errors::StorageError> {
+        let conn = connection::pg_regional_connection_read(self).await?;
+        storage::Payouts::get_failure_counts(&conn, merchant_id, start_time, end_time)
+            .await
+            .map_err(Into::into)
+            .into_report()
+    }
 }
 
 #[async_trait::async_trait]
@@ -92,4 +152,34 @@ impl PayoutsInterface for MockDb {
         // TODO: Implement function for `MockDb`
         Err(errors::StorageError::MockDbError)?
     }
+
+    async fn get_pending_payout_volume_by_currency(
+        &self,
+        _merchant_id: &str,
+        _start_time: time::PrimitiveDateTime,
+        _end_time: time::PrimitiveDateTime,
+    ) -> CustomResult<Vec<storage::CurrencyVolume>, errors::StorageError> {
+        // TODO: Implement function for `MockDb`
+        Err(errors::StorageError::MockDbError)?
+    }
+
+    async fn get_payout_volumes(
+        &self,
+        _merchant_id: &str,
+        _start_time: time::PrimitiveDateTime,
+        _end_time: time::PrimitiveDateTime,
+    ) -> CustomResult<Vec<storage::PayoutVolume>, errors::StorageError> {
+        // TODO: Implement function for `MockDb`
+        Err(errors::StorageError::MockDbError)?
+    }
+
+    async fn get_payout_failure_counts(
+        &self,
+        _merchant_id: &str,
+        _start_time: time::PrimitiveDateTime,
+        _end_time: time::PrimitiveDateTime,
+    ) -> CustomResult<Vec<storage::PayoutFailureCount>, errors::StorageError> {
+        // TODO: Implement function for `MockDb`
+        Err(errors::StorageError::MockDbError)?
+    }
 }
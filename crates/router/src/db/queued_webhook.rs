@@ -0,0 +1,93 @@
+use error_stack::IntoReport;
+
+use super::{MockDb, Store};
+use crate::{
+    connection,
+    core::errors::{self, CustomResult},
+    types::storage,
+};
+
+#[async_trait::async_trait]
+pub trait QueuedWebhookInterface {
+    async fn insert_queued_webhook(
+        &self,
+        queued_webhook: storage::QueuedWebhookNew,
+    ) -> CustomResult<storage::QueuedWebhook, errors::StorageError>;
+
+    async fn list_queued_webhooks_by_merchant_id(
+        &self,
+        merchant_id: &str,
+        limit: Option<i64>,
+    ) -> CustomResult<Vec<storage::QueuedWebhook>, errors::StorageError>;
+
+    async fn delete_queued_webhook_by_id(
+        &self,
+        id: i32,
+    ) -> CustomResult<bool, errors::StorageError>;
+}
+
+#[async_trait::async_trait]
+impl QueuedWebhookInterface for Store {
+    async fn insert_queued_webhook(
+        &self,
+        queued_webhook: storage::QueuedWebhookNew,
+    ) -> CustomResult<storage::QueuedWebhook, errors::StorageError> {
+        let conn = connection::pg_regional_connection_write(self).await?;
+        queued_webhook
+            .insert(&conn)
+            .await
+            .map_err(Into::into)
+            .into_report()
+    }
+
+    async fn list_queued_webhooks_by_merchant_id(
+        &self,
+        merchant_id: &str,
+        limit: Option<i64>,
+    ) -> CustomResult<Vec<storage::QueuedWebhook>, errors::StorageError> {
+        let conn = connection::pg_regional_connection_read(self).await?;
+        storage::QueuedWebhook::find_by_merchant_id(&conn, merchant_id, limit)
+            .await
+            .map_err(Into::into)
+            .into_report()
+    }
+
+    async fn delete_queued_webhook_by_id(
+        &self,
+        id: i32,
+    ) -> CustomResult<bool, errors::StorageError> {
+        let conn = connection::pg_regional_connection_write(self).await?;
+        storage::QueuedWebhook::delete_by_id(&conn, id)
+            .await
+            .map_err(Into::into)
+            .into_report()
+    }
+}
+
+#[async_trait::async_trait]
+impl QueuedWebhookInterface for MockDb {
+    async fn insert_queued_webhook(
+        &self,
+        _queued_webhook: storage::QueuedWebhookNew,
+    ) -> CustomResult<storage::QueuedWebhook, errors::StorageError> {
+        // TODO: Implement function for `MockDb`
+        Err(errors::StorageError::MockDbError)?
+    }
+
+    async fn list_queued_webhooks_by_merchant_id(
+        &self,
+        _merchant_id: &str,
+        _limit: Option<i64>,
+    ) -> CustomResult<Vec<storage::QueuedWebhook>, errors::StorageError> {
+        // TODO: Implement function for `MockDb`
+        Err(errors::StorageError::MockDbError)?
+    }
+
+    async fn delete_queued_webhook_by_id(
+        &self,
+        _id: i32,
+    ) -> CustomResult<bool, errors::StorageError> {
+        // TODO: Implement function for `MockDb`
+        Err(errors::StorageError::MockDbError)?
+    }
+}
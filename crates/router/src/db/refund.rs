@@ -35,6 +35,13 @@ pub trait RefundInterface {
         storage_scheme: enums::MerchantStorageScheme,
     ) -> CustomResult<storage_types::Refund, errors::StorageError>;
 
+    async fn find_refund_by_merchant_id_merchant_refund_reference(
+        &self,
+        merchant_id: &str,
+        merchant_refund_reference: &str,
+        storage_scheme: enums::MerchantStorageScheme,
+    ) -> CustomResult<storage_types::Refund, errors::StorageError>;
+
     async fn find_refund_by_merchant_id_connector_refund_id_connector(
         &self,
         merchant_id: &str,
@@ -50,6 +57,16 @@ pub trait RefundInterface {
         storage_scheme: enums::MerchantStorageScheme,
     ) -> CustomResult<storage_types::Refund, errors::StorageError>;
 
+    /// Updates the refund only if its status is still one of `current_statuses`, fails with a not
+    /// found error otherwise.
+    async fn update_refund_if_status_in(
+        &self,
+        this: storage_types::Refund,
+        current_statuses: Vec<enums::RefundStatus>,
+        refund: storage_types::RefundUpdate,
+        storage_scheme: enums::MerchantStorageScheme,
+    ) -> CustomResult<storage_types::Refund, errors::StorageError>;
+
     async fn find_refund_by_merchant_id_connector_transaction_id(
         &self,
         merchant_id: &str,
@@ -73,6 +90,14 @@ pub trait RefundInterface {
         offset: i64,
     ) -> CustomResult<Vec<diesel_models::refund::Refund>, errors::StorageError>;
 
+    #[cfg(feature = "olap")]
+    async fn find_refunds_by_merchant_id_connector_refund_id(
+        &self,
+        merchant_id: &str,
+        connector_refund_id: &str,
+        storage_scheme: enums::MerchantStorageScheme,
+    ) -> CustomResult<Vec<storage_types::Refund>, errors::StorageError>;
+
     #[cfg(feature = "olap")]
     async fn filter_refund_by_meta_constraints(
         &self,
@@ -80,6 +105,15 @@ pub trait RefundInterface {
         refund_details: &api_models::refunds::TimeRange,
         storage_scheme: enums::MerchantStorageScheme,
     ) -> CustomResult<api_models::refunds::RefundListMetaData, errors::StorageError>;
+
+    #[cfg(feature = "olap")]
+    async fn get_refunded_volume_by_currency(
+        &self,
+        merchant_id: &str,
+        start_time: time::PrimitiveDateTime,
+        end_time: time::PrimitiveDateTime,
+        storage_scheme: enums::MerchantStorageScheme,
+    ) -> CustomResult<Vec<storage_types::CurrencyVolume>, errors::StorageError>;
 }
 
 #[cfg(not(feature = "kv_store"))]
@@ -102,7 +136,7 @@ mod storage {
             merchant_id: &str,
             _storage_scheme: enums::MerchantStorageScheme,
         ) -> CustomResult<storage_types::Refund, errors::StorageError> {
-            let conn = connection::pg_connection_read(self).await?;
+            let conn = connection::pg_regional_connection_read(self).await?;
             storage_types::Refund::find_by_internal_reference_id_merchant_id(
                 &conn,
                 internal_reference_id,
@@ -118,7 +152,7 @@ mod storage {
             new: storage_types::RefundNew,
             _storage_scheme: enums::MerchantStorageScheme,
         ) -> CustomResult<storage_types::Refund, errors::StorageError> {
-            let conn = connection::pg_connection_write(self).await?;
+            let conn = connection::pg_regional_connection_write(self).await?;
             new.insert(&conn).await.map_err(Into::into).into_report()
         }
 
@@ -128,7 +162,7 @@ mod storage {
             connector_transaction_id: &str,
             _storage_scheme: enums::MerchantStorageScheme,
         ) -> CustomResult<Vec<storage_types::Refund>, errors::StorageError> {
-            let conn = connection::pg_connection_read(self).await?;
+            let conn = connection::pg_regional_connection_read(self).await?;
             storage_types::Refund::find_by_merchant_id_connector_transaction_id(
                 &conn,
                 merchant_id,
@@ -145,26 +179,57 @@ mod storage {
             refund: storage_types::RefundUpdate,
             _storage_scheme: enums::MerchantStorageScheme,
         ) -> CustomResult<storage_types::Refund, errors::StorageError> {
-            let conn = connection::pg_connection_write(self).await?;
+            let conn = connection::pg_regional_connection_write(self).await?;
             this.update(&conn, refund)
                 .await
                 .map_err(Into::into)
                 .into_report()
         }
 
+        async fn update_refund_if_status_in(
+            &self,
+            this: storage_types::Refund,
+            current_statuses: Vec<enums::RefundStatus>,
+            refund: storage_types::RefundUpdate,
+            _storage_scheme: enums::MerchantStorageScheme,
+        ) -> CustomResult<storage_types::Refund, errors::StorageError> {
+            let conn = connection::pg_regional_connection_write(self).await?;
+            this.update_with_status(&conn, current_statuses, refund)
+                .await
+                .map_err(Into::into)
+                .into_report()
+        }
+
         async fn find_refund_by_merchant_id_refund_id(
             &self,
             merchant_id: &str,
             refund_id: &str,
             _storage_scheme: enums::MerchantStorageScheme,
         ) -> CustomResult<storage_types::Refund, errors::StorageError> {
-            let conn = connection::pg_connection_read(self).await?;
+            let conn = connection::pg_regional_connection_read(self).await?;
             storage_types::Refund::find_by_merchant_id_refund_id(&conn, merchant_id, refund_id)
                 .await
                 .map_err(Into::into)
                 .into_report()
         }
 
+        async fn find_refund_by_merchant_id_merchant_refund_reference(
+            &self,
+            merchant_id: &str,
+            merchant_refund_reference: &str,
+            _storage_scheme: enums::MerchantStorageScheme,
+        ) -> CustomResult<storage_types::Refund, errors::StorageError> {
+            let conn = connection::pg_regional_connection_read(self).await?;
+            storage_types::Refund::find_by_merchant_id_merchant_refund_reference(
+                &conn,
+                merchant_id,
+                merchant_refund_reference,
+            )
+            .await
+            .map_err(Into::into)
+            .into_report()
+        }
+
         async fn find_refund_by_merchant_id_connector_refund_id_connector(
             &self,
             merchant_id: &str,
@@ -172,7 +237,7 @@ mod storage {
             connector: &str,
             _storage_scheme: enums::MerchantStorageScheme,
         ) -> CustomResult<storage_types::Refund, errors::StorageError> {
-            let conn = connection::pg_connection_read(self).await?;
+            let conn = connection::pg_regional_connection_read(self).await?;
             storage_types::Refund::find_by_merchant_id_connector_refund_id_connector(
                 &conn,
                 merchant_id,
@@ -190,7 +255,7 @@ mod storage {
             merchant_id: &str,
             _storage_scheme: enums::MerchantStorageScheme,
         ) -> CustomResult<Vec<storage_types::Refund>, errors::StorageError> {
-            let conn = connection::pg_connection_read(self).await?;
+            let conn = connection::pg_regional_connection_read(self).await?;
             storage_types::Refund::find_by_payment_id_merchant_id(&conn, payment_id, merchant_id)
                 .await
                 .map_err(Into::into)
@@ -206,7 +271,7 @@ mod storage {
             limit: i64,
             offset: i64,
         ) -> CustomResult<Vec<diesel_models::refund::Refund>, errors::StorageError> {
-            let conn = connection::pg_connection_read(self).await?;
+            let conn = connection::pg_regional_connection_read(self).await?;
             <diesel_models::refund::Refund as storage_types::RefundDbExt>::filter_by_constraints(
                 &conn,
                 merchant_id,
@@ -219,6 +284,24 @@ mod storage {
             .into_report()
         }
 
+        #[cfg(feature = "olap")]
+        async fn find_refunds_by_merchant_id_connector_refund_id(
+            &self,
+            merchant_id: &str,
+            connector_refund_id: &str,
+            _storage_scheme: enums::MerchantStorageScheme,
+        ) -> CustomResult<Vec<storage_types::Refund>, errors::StorageError> {
+            let conn = connection::pg_regional_connection_read(self).await?;
+            storage_types::Refund::filter_by_merchant_id_connector_refund_id(
+                &conn,
+                merchant_id,
+                connector_refund_id,
+            )
+            .await
+            .map_err(Into::into)
+            .into_report()
+        }
+
         #[cfg(feature = "olap")]
         async fn filter_refund_by_meta_constraints(
             &self,
@@ -226,7 +309,7 @@ mod storage {
             refund_details: &api_models::refunds::TimeRange,
             _storage_scheme: enums::MerchantStorageScheme,
         ) -> CustomResult<api_models::refunds::RefundListMetaData, errors::StorageError> {
-            let conn = connection::pg_connection_read(self).await?;
+            let conn = connection::pg_regional_connection_read(self).await?;
             <diesel_models::refund::Refund as storage_types::RefundDbExt>::filter_by_meta_constraints(
                 &conn,
                 merchant_id,
@@ -236,6 +319,26 @@ mod storage {
             .map_err(Into::into)
             .into_report()
         }
+
+        #[cfg(feature = "olap")]
+        async fn get_refunded_volume_by_currency(
+            &self,
+            merchant_id: &str,
+            start_time: time::PrimitiveDateTime,
+            end_time: time::PrimitiveDateTime,
+            _storage_scheme: enums::MerchantStorageScheme,
+        ) -> CustomResult<Vec<storage_types::CurrencyVolume>, errors::StorageError> {
+            let conn = connection::pg_regional_connection_read(self).await?;
+            <diesel_models::refund::Refund as storage_types::RefundDbExt>::get_refunded_volume_by_currency(
+                &conn,
+                merchant_id,
+                start_time,
+                end_time,
+            )
+            .await
+            .map_err(Into::into)
+            .into_report()
+        }
     }
 }
 
@@ -243,7 +346,7 @@ mod storage {
 mod storage {
     use common_utils::date_time;
     use error_stack::{IntoReport, ResultExt};
-    use redis_interface::HsetnxReply;
+    use redis_interface::{HsetnxReply, SetnxReply};
 
     use super::RefundInterface;
     use crate::{
@@ -255,6 +358,10 @@ mod storage {
         types::storage::{self as storage_types, enums, kv},
         utils::{self, db_utils, storage_partitioning::PartitionKey},
     };
+    /// Time after which the lock serializing the status transitions of a refund is released, should
+    /// the router fail to release it
+    const REFUND_STATUS_LOCK_TTL_SECONDS: i64 = 30;
+
     #[async_trait::async_trait]
     impl RefundInterface for Store {
         async fn find_refund_by_internal_reference_id_merchant_id(
@@ -264,7 +371,7 @@ mod storage {
             storage_scheme: enums::MerchantStorageScheme,
         ) -> CustomResult<storage_types::Refund, errors::StorageError> {
             let database_call = || async {
-                let conn = connection::pg_connection_read(self).await?;
+                let conn = connection::pg_regional_connection_read(self).await?;
                 storage_types::Refund::find_by_internal_reference_id_merchant_id(
                     &conn,
                     internal_reference_id,
@@ -299,7 +406,7 @@ mod storage {
         ) -> CustomResult<storage_types::Refund, errors::StorageError> {
             match storage_scheme {
                 enums::MerchantStorageScheme::PostgresOnly => {
-                    let conn = connection::pg_connection_write(self).await?;
+                    let conn = connection::pg_regional_connection_write(self).await?;
                     new.insert(&conn).await.map_err(Into::into).into_report()
                 }
                 enums::MerchantStorageScheme::RedisKv => {
@@ -331,6 +438,11 @@ mod storage {
                         updated_at: new.created_at.unwrap_or_else(date_time::now),
                         description: new.description.clone(),
                         refund_reason: new.refund_reason.clone(),
+                        payout_id: new.payout_id.clone(),
+                        merchant_refund_reference: new.merchant_refund_reference.clone(),
+                        convenience_fee_amount: new.convenience_fee_amount,
+                        scheduled_at: new.scheduled_at,
+                        settled_amount: None,
                     };
 
                     let field = format!(
@@ -349,7 +461,7 @@ mod storage {
                         })
                         .into_report(),
                         Ok(HsetnxReply::KeySet) => {
-                            let conn = connection::pg_connection_write(self).await?;
+                            let conn = connection::pg_regional_connection_write(self).await?;
 
                             let mut reverse_lookups = vec![
                                 storage_types::ReverseLookupNew {
@@ -384,6 +496,19 @@ mod storage {
                                         connector_refund_id,
                                         created_refund.connector
                                     ),
+                                    pk_id: key.clone(),
+                                    source: "refund".to_string(),
+                                })
+                            };
+                            if let Some(merchant_refund_reference) =
+                                created_refund.merchant_refund_reference.as_ref()
+                            {
+                                reverse_lookups.push(storage_types::ReverseLookupNew {
+                                    sk_id: field.clone(),
+                                    lookup_id: format!(
+                                        "{}_merchant_refund_reference_{}",
+                                        created_refund.merchant_id, merchant_refund_reference
+                                    ),
                                     pk_id: key,
                                     source: "refund".to_string(),
                                 })
@@ -422,7 +547,7 @@ mod storage {
         ) -> CustomResult<Vec<storage_types::Refund>, errors::StorageError> {
             match storage_scheme {
                 enums::MerchantStorageScheme::PostgresOnly => {
-                    let conn = connection::pg_connection_read(self).await?;
+                    let conn = connection::pg_regional_connection_read(self).await?;
                     storage_types::Refund::find_by_merchant_id_connector_transaction_id(
                         &conn,
                         merchant_id,
@@ -462,7 +587,7 @@ mod storage {
         ) -> CustomResult<storage_types::Refund, errors::StorageError> {
             match storage_scheme {
                 enums::MerchantStorageScheme::PostgresOnly => {
-                    let conn = connection::pg_connection_write(self).await?;
+                    let conn = connection::pg_regional_connection_write(self).await?;
                     this.update(&conn, refund)
                         .await
                         .map_err(Into::into)
@@ -511,6 +636,76 @@ mod storage {
             }
         }
 
+        async fn update_refund_if_status_in(
+            &self,
+            this: storage_types::Refund,
+            current_statuses: Vec<enums::RefundStatus>,
+            refund: storage_types::RefundUpdate,
+            storage_scheme: enums::MerchantStorageScheme,
+        ) -> CustomResult<storage_types::Refund, errors::StorageError> {
+            match storage_scheme {
+                enums::MerchantStorageScheme::PostgresOnly => {
+                    let conn = connection::pg_regional_connection_write(self).await?;
+                    this.update_with_status(&conn, current_statuses, refund)
+                        .await
+                        .map_err(Into::into)
+                        .into_report()
+                }
+                enums::MerchantStorageScheme::RedisKv => {
+                    // Redis has no conditional update of a hash field, the status transitions of
+                    // the refund are serialized using a lock on the refund instead
+                    let lock_key =
+                        format!("refund_status_lock_{}_{}", this.merchant_id, this.refund_id);
+                    let redis_conn = self
+                        .redis_conn()
+                        .map_err(Into::<errors::StorageError>::into)?;
+                    match redis_conn
+                        .set_key_if_not_exists_with_expiry(
+                            &lock_key,
+                            "true",
+                            Some(REFUND_STATUS_LOCK_TTL_SECONDS),
+                        )
+                        .await
+                        .change_context(errors::StorageError::KVError)?
+                    {
+                        SetnxReply::KeySet => (),
+                        SetnxReply::KeyNotSet => {
+                            return Err(errors::StorageError::ValueNotFound(
+                                "Refund status is being updated concurrently".to_string(),
+                            )
+                            .into())
+                        }
+                    }
+
+                    let result = async {
+                        let current = self
+                            .find_refund_by_merchant_id_refund_id(
+                                &this.merchant_id,
+                                &this.refund_id,
+                                storage_scheme,
+                            )
+                            .await?;
+                        if !current_statuses.contains(&current.refund_status) {
+                            return Err(errors::StorageError::ValueNotFound(
+                                "Refund with the given ID and status does not exist".to_string(),
+                            )
+                            .into());
+                        }
+                        self.update_refund(current, refund, storage_scheme).await
+                    }
+                    .await;
+
+                    redis_conn
+                        .delete_key(&lock_key)
+                        .await
+                        .map_err(|error| logger::error!(refund_status_lock_error=?error))
+                        .ok();
+
+                    result
+                }
+            }
+        }
+
         async fn find_refund_by_merchant_id_refund_id(
             &self,
             merchant_id: &str,
@@ -518,7 +713,7 @@ mod storage {
             storage_scheme: enums::MerchantStorageScheme,
         ) -> CustomResult<storage_types::Refund, errors::StorageError> {
             let database_call = || async {
-                let conn = connection::pg_connection_read(self).await?;
+                let conn = connection::pg_regional_connection_read(self).await?;
                 storage_types::Refund::find_by_merchant_id_refund_id(&conn, merchant_id, refund_id)
                     .await
                     .map_err(Into::into)
@@ -542,6 +737,43 @@ mod storage {
             }
         }
 
+        async fn find_refund_by_merchant_id_merchant_refund_reference(
+            &self,
+            merchant_id: &str,
+            merchant_refund_reference: &str,
+            storage_scheme: enums::MerchantStorageScheme,
+        ) -> CustomResult<storage_types::Refund, errors::StorageError> {
+            let database_call = || async {
+                let conn = connection::pg_regional_connection_read(self).await?;
+                storage_types::Refund::find_by_merchant_id_merchant_refund_reference(
+                    &conn,
+                    merchant_id,
+                    merchant_refund_reference,
+                )
+                .await
+                .map_err(Into::into)
+                .into_report()
+            };
+            match storage_scheme {
+                enums::MerchantStorageScheme::PostgresOnly => database_call().await,
+                enums::MerchantStorageScheme::RedisKv => {
+                    let lookup_id = format!(
+                        "{merchant_id}_merchant_refund_reference_{merchant_refund_reference}"
+                    );
+                    let lookup = self.get_lookup_by_lookup_id(&lookup_id).await?;
+
+                    let key = &lookup.pk_id;
+                    db_utils::try_redis_get_else_try_database_get(
+                        self.redis_conn()
+                            .map_err(Into::<errors::StorageError>::into)?
+                            .get_hash_field_and_deserialize(key, &lookup.sk_id, "Refund"),
+                        database_call,
+                    )
+                    .await
+                }
+            }
+        }
+
         async fn find_refund_by_merchant_id_connector_refund_id_connector(
             &self,
             merchant_id: &str,
@@ -550,7 +782,7 @@ mod storage {
             storage_scheme: enums::MerchantStorageScheme,
         ) -> CustomResult<storage_types::Refund, errors::StorageError> {
             let database_call = || async {
-                let conn = connection::pg_connection_read(self).await?;
+                let conn = connection::pg_regional_connection_read(self).await?;
                 storage_types::Refund::find_by_merchant_id_connector_refund_id_connector(
                     &conn,
                     merchant_id,
@@ -587,7 +819,7 @@ mod storage {
         ) -> CustomResult<Vec<storage_types::Refund>, errors::StorageError> {
             match storage_scheme {
                 enums::MerchantStorageScheme::PostgresOnly => {
-                    let conn = connection::pg_connection_read(self).await?;
+                    let conn = connection::pg_regional_connection_read(self).await?;
                     storage_types::Refund::find_by_payment_id_merchant_id(
                         &conn,
                         payment_id,
@@ -623,7 +855,7 @@ mod storage {
         ) -> CustomResult<Vec<diesel_models::refund::Refund>, errors::StorageError> {
             match storage_scheme {
                 enums::MerchantStorageScheme::PostgresOnly => {
-                    let conn = connection::pg_connection_read(self).await?;
+                    let conn = connection::pg_regional_connection_read(self).await?;
                     <diesel_models::refund::Refund as storage_types::RefundDbExt>::filter_by_constraints(&conn, merchant_id, refund_details, limit, offset)
                         .await
                         .map_err(Into::into)
@@ -634,6 +866,26 @@ mod storage {
             }
         }
 
+        #[cfg(feature = "olap")]
+        async fn find_refunds_by_merchant_id_connector_refund_id(
+            &self,
+            merchant_id: &str,
+            connector_refund_id: &str,
+            _storage_scheme: enums::MerchantStorageScheme,
+        ) -> CustomResult<Vec<storage_types::Refund>, errors::StorageError> {
+            // Refunds in Redis are only indexed by the connector refund ID together with the
+            // connector, so they are looked up in the database, to which they are drained
+            let conn = connection::pg_regional_connection_read(self).await?;
+            storage_types::Refund::filter_by_merchant_id_connector_refund_id(
+                &conn,
+                merchant_id,
+                connector_refund_id,
+            )
+            .await
+            .map_err(Into::into)
+            .into_report()
+        }
+
         #[cfg(feature = "olap")]
         async fn filter_refund_by_meta_constraints(
             &self,
@@ -643,7 +895,7 @@ mod storage {
         ) -> CustomResult<api_models::refunds::RefundListMetaData, errors::StorageError> {
             match storage_scheme {
                 enums::MerchantStorageScheme::PostgresOnly => {
-                    let conn = connection::pg_connection_read(self).await?;
+                    let conn = connection::pg_regional_connection_read(self).await?;
                     <diesel_models::refund::Refund as storage_types::RefundDbExt>::filter_by_meta_constraints(&conn, merchant_id, refund_details)
                         .await
                         .map_err(Into::into)
@@ -653,6 +905,27 @@ mod storage {
                 enums::MerchantStorageScheme::RedisKv => Err(errors::StorageError::KVError.into()),
             }
         }
+
+        #[cfg(feature = "olap")]
+        async fn get_refunded_volume_by_currency(
+            &self,
+            merchant_id: &str,
+            start_time: time::PrimitiveDateTime,
+            end_time: time::PrimitiveDateTime,
+            storage_scheme: enums::MerchantStorageScheme,
+        ) -> CustomResult<Vec<storage_types::CurrencyVolume>, errors::StorageError> {
+            match storage_scheme {
+                enums::MerchantStorageScheme::PostgresOnly => {
+                    let conn = connection::pg_regional_connection_read(self).await?;
+                    <diesel_models::refund::Refund as storage_types::RefundDbExt>::get_refunded_volume_by_currency(&conn, merchant_id, start_time, end_time)
+                        .await
+                        .map_err(Into::into)
+                        .into_report()
+                }
+
+                enums::MerchantStorageScheme::RedisKv => Err(errors::StorageError::KVError.into()),
+            }
+        }
     }
 }
 
@@ -711,6 +984,11 @@ impl RefundInterface for MockDb {
             updated_at: current_time,
             description: new.description,
             refund_reason: new.refund_reason.clone(),
+            payout_id: new.payout_id,
+            merchant_refund_reference: new.merchant_refund_reference,
+            convenience_fee_amount: new.convenience_fee_amount,
+            scheduled_at: new.scheduled_at,
+            settled_amount: None,
         };
         refunds.push(refund.clone());
         Ok(refund)
@@ -755,6 +1033,34 @@ impl RefundInterface for MockDb {
             })
     }
 
+    async fn update_refund_if_status_in(
+        &self,
+        this: storage_types::Refund,
+        current_statuses: Vec<enums::RefundStatus>,
+        refund: storage_types::RefundUpdate,
+        _storage_scheme: enums::MerchantStorageScheme,
+    ) -> CustomResult<storage_types::Refund, errors::StorageError> {
+        self.refunds
+            .lock()
+            .await
+            .iter_mut()
+            .find(|refund| {
+                this.refund_id == refund.refund_id
+                    && current_statuses.contains(&refund.refund_status)
+            })
+            .map(|r| {
+                let refund_updated = RefundUpdateInternal::from(refund).create_refund(r.clone());
+                *r = refund_updated.clone();
+                refund_updated
+            })
+            .ok_or_else(|| {
+                errors::StorageError::ValueNotFound(
+                    "cannot find refund with the given status to update".to_string(),
+                )
+                .into()
+            })
+    }
+
     async fn find_refund_by_merchant_id_refund_id(
         &self,
         merchant_id: &str,
@@ -772,6 +1078,27 @@ impl RefundInterface for MockDb {
             })
     }
 
+    async fn find_refund_by_merchant_id_merchant_refund_reference(
+        &self,
+        merchant_id: &str,
+        merchant_refund_reference: &str,
+        _storage_scheme: enums::MerchantStorageScheme,
+    ) -> CustomResult<storage_types::Refund, errors::StorageError> {
+        let refunds = self.refunds.lock().await;
+
+        refunds
+            .iter()
+            .find(|refund| {
+                refund.merchant_id == merchant_id
+                    && refund.merchant_refund_reference.as_deref()
+                        == Some(merchant_refund_reference)
+            })
+            .cloned()
+            .ok_or_else(|| {
+                errors::StorageError::DatabaseError(DatabaseError::NotFound.into()).into()
+            })
+    }
+
     async fn find_refund_by_merchant_id_connector_refund_id_connector(
         &self,
         merchant_id: &str,
@@ -830,6 +1157,24 @@ impl RefundInterface for MockDb {
             .collect::<Vec<_>>())
     }
 
+    #[cfg(feature = "olap")]
+    async fn find_refunds_by_merchant_id_connector_refund_id(
+        &self,
+        merchant_id: &str,
+        connector_refund_id: &str,
+        _storage_scheme: enums::MerchantStorageScheme,
+    ) -> CustomResult<Vec<storage_types::Refund>, errors::StorageError> {
+        let refunds = self.refunds.lock().await;
+        Ok(refunds
+            .iter()
+            .filter(|refund| {
+                refund.merchant_id == merchant_id
+                    && refund.connector_refund_id.as_deref() == Some(connector_refund_id)
+            })
+            .cloned()
+            .collect())
+    }
+
     #[cfg(feature = "olap")]
     async fn filter_refund_by_meta_constraints(
         &self,
@@ -876,4 +1221,40 @@ impl RefundInterface for MockDb {
 
         Ok(refund_meta_data)
     }
+
+    #[cfg(feature = "olap")]
+    async fn get_refunded_volume_by_currency(
+        &self,
+        merchant_id: &str,
+        start_time: time::PrimitiveDateTime,
+        end_time: time::PrimitiveDateTime,
+        _storage_scheme: enums::MerchantStorageScheme,
+    ) -> CustomResult<Vec<storage_types::CurrencyVolume>, errors::StorageError> {
+        let refunds = self.refunds.lock().await;
+
+        let mut volumes: Vec<storage_types::CurrencyVolume> = Vec::new();
+        for refund in refunds.iter().filter(|refund| {
+            refund.merchant_id == merchant_id
+                && refund.refund_status == enums::RefundStatus::Success
+                && refund.updated_at >= start_time
+                && refund.updated_at <= end_time
+        }) {
+            match volumes
+                .iter_mut()
+                .find(|volume| volume.currency == refund.currency)
+            {
+                Some(volume) => {
+                    volume.total_amount += refund.refund_amount;
+                    volume.count += 1;
+                }
+                None => volumes.push(storage_types::CurrencyVolume {
+                    currency: refund.currency,
+                    total_amount: refund.refund_amount,
+                    count: 1,
+                }),
+            }
+        }
+
+        Ok(volumes)
+    }
 }
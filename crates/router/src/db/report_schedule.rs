@@ -0,0 +1,169 @@
+use error_stack::IntoReport;
+
+use super::{MockDb, Store};
+use crate::{
+    connection,
+    core::errors::{self, CustomResult},
+    types::storage,
+};
+
+#[async_trait::async_trait]
+pub trait ReportScheduleInterface {
+    async fn insert_report_schedule(
+        &self,
+        report_schedule: storage::ReportScheduleNew,
+    ) -> CustomResult<storage::ReportSchedule, errors::StorageError>;
+
+    async fn find_report_schedule_by_merchant_id_report_schedule_id(
+        &self,
+        merchant_id: &str,
+        report_schedule_id: &str,
+    ) -> CustomResult<storage::ReportSchedule, errors::StorageError>;
+
+    async fn list_report_schedules_by_merchant_id(
+        &self,
+        merchant_id: &str,
+        limit: Option<i64>,
+        offset: Option<i64>,
+    ) -> CustomResult<Vec<storage::ReportSchedule>, errors::StorageError>;
+
+    async fn update_report_schedule_by_merchant_id_report_schedule_id(
+        &self,
+        merchant_id: &str,
+        report_schedule_id: &str,
+        report_schedule_update: storage::ReportScheduleUpdate,
+    ) -> CustomResult<storage::ReportSchedule, errors::StorageError>;
+
+    async fn delete_report_schedule_by_merchant_id_report_schedule_id(
+        &self,
+        merchant_id: &str,
+        report_schedule_id: &str,
+    ) -> CustomResult<bool, errors::StorageError>;
+}
+
+#[async_trait::async_trait]
+impl ReportScheduleInterface for Store {
+    async fn insert_report_schedule(
+        &self,
+        report_schedule: storage::ReportScheduleNew,
+    ) -> CustomResult<storage::ReportSchedule, errors::StorageError> {
+        let conn = connection::pg_connection_write(self).await?;
+        report_schedule
+            .insert(&conn)
+            .await
+            .map_err(Into::into)
+            .into_report()
+    }
+
+    async fn find_report_schedule_by_merchant_id_report_schedule_id(
+        &self,
+        merchant_id: &str,
+        report_schedule_id: &str,
+    ) -> CustomResult<storage::ReportSchedule, errors::StorageError> {
+        let conn = connection::pg_connection_read(self).await?;
+        storage::ReportSchedule::find_by_merchant_id_report_schedule_id(
+            &conn,
+            merchant_id,
+            report_schedule_id,
+        )
+        .await
+        .map_err(Into::into)
+        .into_report()
+    }
+
+    async fn list_report_schedules_by_merchant_id(
+        &self,
+        merchant_id: &str,
+        limit: Option<i64>,
+        offset: Option<i64>,
+    ) -> CustomResult<Vec<storage::ReportSchedule>, errors::StorageError> {
+        let conn = connection::pg_connection_read(self).await?;
+        storage::ReportSchedule::find_by_merchant_id(&conn, merchant_id, limit, offset)
+            .await
+            .map_err(Into::into)
+            .into_report()
+    }
+
+    async fn update_report_schedule_by_merchant_id_report_schedule_id(
+        &self,
+        merchant_id: &str,
+        report_schedule_id: &str,
+        report_schedule_update: storage::ReportScheduleUpdate,
+    ) -> CustomResult<storage::ReportSchedule, errors::StorageError> {
+        let conn = connection::pg_connection_write(self).await?;
+        storage::ReportSchedule::update_by_merchant_id_report_schedule_id(
+            &conn,
+            merchant_id,
+            report_schedule_id,
+            report_schedule_update,
+        )
+        .await
+        .map_err(Into::into)
+        .into_report()
+    }
+
+    async fn delete_report_schedule_by_merchant_id_report_schedule_id(
+        &self,
+        merchant_id: &str,
+        report_schedule_id: &str,
+    ) -> CustomResult<bool, errors::StorageError> {
+        let conn = connection::pg_connection_write(self).await?;
+        storage::ReportSchedule::delete_by_merchant_id_report_schedule_id(
+            &conn,
+            merchant_id,
+            report_schedule_id,
+        )
+        .await
+        .map_err(Into::into)
+        .into_report()
+    }
+}
+
+#[async_trait::async_trait]
+impl ReportScheduleInterface for MockDb {
+    async fn insert_report_schedule(
+        &self,
+        _report_schedule: storage::ReportScheduleNew,
+    ) -> CustomResult<storage::ReportSchedule, errors::StorageError> {
+        // TODO: Implement function for `MockDb`
+        Err(errors::StorageError::MockDbError)?
+    }
+
+    async fn find_report_schedule_by_merchant_id_report_schedule_id(
+        &self,
+        _merchant_id: &str,
+        _report_schedule_id: &str,
+    ) -> CustomResult<storage::ReportSchedule, errors::StorageError> {
+        // TODO: Implement function for `MockDb`
+        Err(errors::StorageError::MockDbError)?
+    }
+
+    async fn list_report_schedules_by_merchant_id(
+        &self,
+        _merchant_id: &str,
+        _limit: Option<i64>,
+        _offset: Option<i64>,
+    ) -> CustomResult<Vec<storage::ReportSchedule>, errors::StorageError> {
+        // TODO: Implement function for `MockDb`
+        Err(errors::StorageError::MockDbError)?
+    }
+
+    async fn update_report_schedule_by_merchant_id_report_schedule_id(
+        &self,
+        _merchant_id: &str,
+        _report_schedule_id: &str,
+        _report_schedule_update: storage::ReportScheduleUpdate,
+    ) -> CustomResult<storage::ReportSchedule, errors::StorageError> {
+        // TODO: Implement function for `MockDb`
+        Err(errors::StorageError::MockDbError)?
+    }
+
+    async fn delete_report_schedule_by_merchant_id_report_schedule_id(
+        &self,
+        _merchant_id: &str,
+        _report_schedule_id: &str,
+    ) -> CustomResult<bool, errors::StorageError> {
+        // TODO: Implement function for `MockDb`
+        Err(errors::StorageError::MockDbError)?
+    }
+}
@@ -0,0 +1,193 @@
+use error_stack::IntoReport;
+
+use super::{MockDb, Store};
+use crate::{
+    connection,
+    core::errors::{self, CustomResult},
+    types::storage,
+};
+
+#[async_trait::async_trait]
+pub trait SubscriptionInterface {
+    async fn insert_subscription(
+        &self,
+        subscription: storage::SubscriptionNew,
+    ) -> CustomResult<storage::Subscription, errors::StorageError>;
+
+    async fn find_subscription_by_merchant_id_subscription_id(
+        &self,
+        merchant_id: &str,
+        subscription_id: &str,
+    ) -> CustomResult<storage::Subscription, errors::StorageError>;
+
+    async fn list_subscriptions_by_merchant_id(
+        &self,
+        merchant_id: &str,
+        customer_id: Option<&str>,
+        limit: Option<i64>,
+        offset: Option<i64>,
+    ) -> CustomResult<Vec<storage::Subscription>, errors::StorageError>;
+
+    async fn update_subscription_by_merchant_id_subscription_id(
+        &self,
+        merchant_id: &str,
+        subscription_id: &str,
+        subscription_update: storage::SubscriptionUpdate,
+    ) -> CustomResult<storage::Subscription, errors::StorageError>;
+
+    async fn update_subscription_if_status_in(
+        &self,
+        merchant_id: &str,
+        subscription_id: &str,
+        current_statuses: Vec<storage::enums::SubscriptionStatus>,
+        subscription_update: storage::SubscriptionUpdate,
+    ) -> CustomResult<storage::Subscription, errors::StorageError>;
+}
+
+#[async_trait::async_trait]
+impl SubscriptionInterface for Store {
+    async fn insert_subscription(
+        &self,
+        subscription: storage::SubscriptionNew,
+    ) -> CustomResult<storage::Subscription, errors::StorageError> {
+        let conn = connection::pg_regional_connection_write(self).await?;
+        subscription
+            .insert(&conn)
+            .await
+            .map_err(Into::into)
+            .into_report()
+    }
+
+    async fn find_subscription_by_merchant_id_subscription_id(
+        &self,
+        merchant_id: &str,
+        subscription_id: &str,
+    ) -> CustomResult<storage::Subscription, errors::StorageError> {
+        let conn = connection::pg_regional_connection_read(self).await?;
+        storage::Subscription::find_by_merchant_id_subscription_id(
+            &conn,
+            merchant_id,
+            subscription_id,
+        )
+        .await
+        .map_err(Into::into)
+        .into_report()
+    }
+
+    async fn list_subscriptions_by_merchant_id(
+        &self,
+        merchant_id: &str,
+        customer_id: Option<&str>,
+        limit: Option<i64>,
+        offset: Option<i64>,
+    ) -> CustomResult<Vec<storage::Subscription>, errors::StorageError> {
+        let conn = connection::pg_regional_connection_read(self).await?;
+        match customer_id {
+            Some(customer_id) => {
+                storage::Subscription::find_by_merchant_id_customer_id(
+                    &conn,
+                    merchant_id,
+                    customer_id,
+                    limit,
+                    offset,
+                )
+                .await
+            }
+            None => {
+                storage::Subscription::find_by_merchant_id(&conn, merchant_id, limit, offset).await
+            }
+        }
+        .map_err(Into::into)
+        .into_report()
+    }
+
+    async fn update_subscription_by_merchant_id_subscription_id(
+        &self,
+        merchant_id: &str,
+        subscription_id: &str,
+        subscription_update: storage::SubscriptionUpdate,
+    ) -> CustomResult<storage::Subscription, errors::StorageError> {
+        let conn = connection::pg_regional_connection_write(self).await?;
+        storage::Subscription::update_by_merchant_id_subscription_id(
+            &conn,
+            merchant_id,
+            subscription_id,
+            subscription_update,
+        )
+        .await
+        .map_err(Into::into)
+        .into_report()
+    }
+
+    async fn update_subscription_if_status_in(
+        &self,
+        merchant_id: &str,
+        subscription_id: &str,
+        current_statuses: Vec<storage::enums::SubscriptionStatus>,
+        subscription_update: storage::SubscriptionUpdate,
+    ) -> CustomResult<storage::Subscription, errors::StorageError> {
+        let conn = connection::pg_regional_connection_write(self).await?;
+        storage::Subscription::update_by_merchant_id_subscription_id_status(
+            &conn,
+            merchant_id,
+            subscription_id,
+            current_statuses,
+            subscription_update,
+        )
+        .await
+        .map_err(Into::into)
+        .into_report()
+    }
+}
+
+#[async_trait::async_trait]
+impl SubscriptionInterface for MockDb {
+    async fn insert_subscription(
+        &self,
+        _subscription: storage::SubscriptionNew,
+    ) -> CustomResult<storage::Subscription, errors::StorageError> {
+        // TODO: Implement function for `MockDb`
+        Err(errors::StorageError::MockDbError)?
+    }
+
+    async fn find_subscription_by_merchant_id_subscription_id(
+        &self,
+        _merchant_id: &str,
+        _subscription_id: &str,
+    ) -> CustomResult<storage::Subscription, errors::StorageError> {
+        // TODO: Implement function for `MockDb`
+        Err(errors::StorageError::MockDbError)?
+    }
+
+    async fn list_subscriptions_by_merchant_id(
+        &self,
+        _merchant_id: &str,
+        _customer_id: Option<&str>,
+        _limit: Option<i64>,
+        _offset: Option<i64>,
+    ) -> CustomResult<Vec<storage::Subscription>, errors::StorageError> {
+        // TODO: Implement function for `MockDb`
+        Err(errors::StorageError::MockDbError)?
+    }
+
+    async fn update_subscription_by_merchant_id_subscription_id(
+        &self,
+        _merchant_id: &str,
+        _subscription_id: &str,
+        _subscription_update: storage::SubscriptionUpdate,
+    ) -> CustomResult<storage::Subscription, errors::StorageError> {
+        // TODO: Implement function for `MockDb`
+        Err(errors::StorageError::MockDbError)?
+    }
+
+    async fn update_subscription_if_status_in(
+        &self,
+        _merchant_id: &str,
+        _subscription_id: &str,
+        _current_statuses: Vec<storage::enums::SubscriptionStatus>,
+        _subscription_update: storage::SubscriptionUpdate,
+    ) -> CustomResult<storage::Subscription, errors::StorageError> {
+        // TODO: Implement function for `MockDb`
+        Err(errors::StorageError::MockDbError)?
+    }
+}
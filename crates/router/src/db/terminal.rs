@@ -0,0 +1,161 @@
+use error_stack::IntoReport;
+
+use super::{MockDb, Store};
+use crate::{
+    connection,
+    core::errors::{self, CustomResult},
+    types::storage,
+};
+
+#[async_trait::async_trait]
+pub trait TerminalInterface {
+    async fn insert_terminal(
+        &self,
+        terminal: storage::TerminalNew,
+    ) -> CustomResult<storage::Terminal, errors::StorageError>;
+
+    async fn find_terminal_by_merchant_id_terminal_id(
+        &self,
+        merchant_id: &str,
+        terminal_id: &str,
+    ) -> CustomResult<storage::Terminal, errors::StorageError>;
+
+    async fn list_terminals_by_merchant_id(
+        &self,
+        merchant_id: &str,
+        limit: Option<i64>,
+        offset: Option<i64>,
+    ) -> CustomResult<Vec<storage::Terminal>, errors::StorageError>;
+
+    async fn update_terminal_by_merchant_id_terminal_id(
+        &self,
+        merchant_id: &str,
+        terminal_id: &str,
+        terminal_update: storage::TerminalUpdate,
+    ) -> CustomResult<storage::Terminal, errors::StorageError>;
+
+    async fn delete_terminal_by_merchant_id_terminal_id(
+        &self,
+        merchant_id: &str,
+        terminal_id: &str,
+    ) -> CustomResult<bool, errors::StorageError>;
+}
+
+#[async_trait::async_trait]
+impl TerminalInterface for Store {
+    async fn insert_terminal(
+        &self,
+        terminal: storage::TerminalNew,
+    ) -> CustomResult<storage::Terminal, errors::StorageError> {
+        let conn = connection::pg_connection_write(self).await?;
+        terminal
+            .insert(&conn)
+            .await
+            .map_err(Into::into)
+            .into_report()
+    }
+
+    async fn find_terminal_by_merchant_id_terminal_id(
+        &self,
+        merchant_id: &str,
+        terminal_id: &str,
+    ) -> CustomResult<storage::Terminal, errors::StorageError> {
+        let conn = connection::pg_connection_read(self).await?;
+        storage::Terminal::find_by_merchant_id_terminal_id(&conn, merchant_id, terminal_id)
+            .await
+            .map_err(Into::into)
+            .into_report()
+    }
+
+    async fn list_terminals_by_merchant_id(
+        &self,
+        merchant_id: &str,
+        limit: Option<i64>,
+        offset: Option<i64>,
+    ) -> CustomResult<Vec<storage::Terminal>, errors::StorageError> {
+        let conn = connection::pg_connection_read(self).await?;
+        storage::Terminal::find_by_merchant_id(&conn, merchant_id, limit, offset)
+            .await
+            .map_err(Into::into)
+            .into_report()
+    }
+
+    async fn update_terminal_by_merchant_id_terminal_id(
+        &self,
+        merchant_id: &str,
+        terminal_id: &str,
+        terminal_update: storage::TerminalUpdate,
+    ) -> CustomResult<storage::Terminal, errors::StorageError> {
+        let conn = connection::pg_connection_write(self).await?;
+        storage::Terminal::update_by_merchant_id_terminal_id(
+            &conn,
+            merchant_id,
+            terminal_id,
+            terminal_update,
+        )
+        .await
+        .map_err(Into::into)
+        .into_report()
+    }
+
+    async fn delete_terminal_by_merchant_id_terminal_id(
+        &self,
+        merchant_id: &str,
+        terminal_id: &str,
+    ) -> CustomResult<bool, errors::StorageError> {
+        let conn = connection::pg_connection_write(self).await?;
+        storage::Terminal::delete_by_merchant_id_terminal_id(&conn, merchant_id, terminal_id)
+            .await
+            .map_err(Into::into)
+            .into_report()
+    }
+}
+
+#[async_trait::async_trait]
+impl TerminalInterface for MockDb {
+    async fn insert_terminal(
+        &self,
+        _terminal: storage::TerminalNew,
+    ) -> CustomResult<storage::Terminal, errors::StorageError> {
+        // TODO: Implement function for `MockDb`
+        Err(errors::StorageError::MockDbError)?
+    }
+
+    async fn find_terminal_by_merchant_id_terminal_id(
+        &self,
+        _merchant_id: &str,
+        _terminal_id: &str,
+    ) -> CustomResult<storage::Terminal, errors::StorageError> {
+        // TODO: Implement function for `MockDb`
+        Err(errors::StorageError::MockDbError)?
+    }
+
+    async fn list_terminals_by_merchant_id(
+        &self,
+        _merchant_id: &str,
+        _limit: Option<i64>,
+        _offset: Option<i64>,
+    ) -> CustomResult<Vec<storage::Terminal>, errors::StorageError> {
+        // TODO: Implement function for `MockDb`
+        Err(errors::StorageError::MockDbError)?
+    }
+
+    async fn update_terminal_by_merchant_id_terminal_id(
+        &self,
+        _merchant_id: &str,
+        _terminal_id: &str,
+        _terminal_update: storage::TerminalUpdate,
+    ) -> CustomResult<storage::Terminal, errors::StorageError> {
+        // TODO: Implement function for `MockDb`
+        Err(errors::StorageError::MockDbError)?
+    }
+
+    async fn delete_terminal_by_merchant_id_terminal_id(
+        &self,
+        _merchant_id: &str,
+        _terminal_id: &str,
+    ) -> CustomResult<bool, errors::StorageError> {
+        // TODO: Implement function for `MockDb`
+        Err(errors::StorageError::MockDbError)?
+    }
+}
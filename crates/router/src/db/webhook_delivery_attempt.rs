@@ -0,0 +1,108 @@
+use error_stack::IntoReport;
+
+use super::{MockDb, Store};
+use crate::{
+    connection,
+    core::errors::{self, CustomResult},
+    types::storage,
+};
+
+#[async_trait::async_trait]
+pub trait WebhookDeliveryAttemptInterface {
+    async fn insert_webhook_delivery_attempt(
+        &self,
+        delivery_attempt: storage::WebhookDeliveryAttemptNew,
+    ) -> CustomResult<storage::WebhookDeliveryAttempt, errors::StorageError>;
+
+    async fn find_webhook_delivery_attempts_by_merchant_id_event_id(
+        &self,
+        merchant_id: &str,
+        event_id: &str,
+    ) -> CustomResult<Vec<storage::WebhookDeliveryAttempt>, errors::StorageError>;
+
+    async fn find_webhook_delivery_attempt_by_merchant_id_event_id_endpoint_id_attempt_number(
+        &self,
+        merchant_id: &str,
+        event_id: &str,
+        endpoint_id: Option<&str>,
+        attempt_number: i32,
+    ) -> CustomResult<storage::WebhookDeliveryAttempt, errors::StorageError>;
+}
+
+#[async_trait::async_trait]
+impl WebhookDeliveryAttemptInterface for Store {
+    async fn insert_webhook_delivery_attempt(
+        &self,
+        delivery_attempt: storage::WebhookDeliveryAttemptNew,
+    ) -> CustomResult<storage::WebhookDeliveryAttempt, errors::StorageError> {
+        let conn = connection::pg_connection_write(self).await?;
+        delivery_attempt
+            .insert(&conn)
+            .await
+            .map_err(Into::into)
+            .into_report()
+    }
+
+    async fn find_webhook_delivery_attempts_by_merchant_id_event_id(
+        &self,
+        merchant_id: &str,
+        event_id: &str,
+    ) -> CustomResult<Vec<storage::WebhookDeliveryAttempt>, errors::StorageError> {
+        let conn = connection::pg_connection_read(self).await?;
+        storage::WebhookDeliveryAttempt::find_by_merchant_id_event_id(&conn, merchant_id, event_id)
+            .await
+            .map_err(Into::into)
+            .into_report()
+    }
+
+    async fn find_webhook_delivery_attempt_by_merchant_id_event_id_endpoint_id_attempt_number(
+        &self,
+        merchant_id: &str,
+        event_id: &str,
+        endpoint_id: Option<&str>,
+        attempt_number: i32,
+    ) -> CustomResult<storage::WebhookDeliveryAttempt, errors::StorageError> {
+        let conn = connection::pg_connection_read(self).await?;
+        storage::WebhookDeliveryAttempt::find_by_merchant_id_event_id_endpoint_id_attempt_number(
+            &conn,
+            merchant_id,
+            event_id,
+            endpoint_id,
+            attempt_number,
+        )
+        .await
+        .map_err(Into::into)
+        .into_report()
+    }
+}
+
+#[async_trait::async_trait]
+impl WebhookDeliveryAttemptInterface for MockDb {
+    async fn insert_webhook_delivery_attempt(
+        &self,
+        _delivery_attempt: storage::WebhookDeliveryAttemptNew,
+    ) -> CustomResult<storage::WebhookDeliveryAttempt, errors::StorageError> {
+        // TODO: Implement function for `MockDb`
+        Err(errors::StorageError::MockDbError)?
+    }
+
+    async fn find_webhook_delivery_attempts_by_merchant_id_event_id(
+        &self,
+        _merchant_id: &str,
+        _event_id: &str,
+    ) -> CustomResult<Vec<storage::WebhookDeliveryAttempt>, errors::StorageError> {
+        // TODO: Implement function for `MockDb`
+        Err(errors::StorageError::MockDbError)?
+    }
+
+    async fn find_webhook_delivery_attempt_by_merchant_id_event_id_endpoint_id_attempt_number(
+        &self,
+        _merchant_id: &str,
+        _event_id: &str,
+        _endpoint_id: Option<&str>,
+        _attempt_number: i32,
+    ) -> CustomResult<storage::WebhookDeliveryAttempt, errors::StorageError> {
+        // TODO: Implement function for `MockDb`
+        Err(errors::StorageError::MockDbError)?
+    }
+}
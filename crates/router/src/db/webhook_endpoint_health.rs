@@ -0,0 +1,97 @@
+use error_stack::IntoReport;
+
+use super::{MockDb, Store};
+use crate::{
+    connection,
+    core::errors::{self, CustomResult},
+    types::storage,
+};
+
+#[async_trait::async_trait]
+pub trait WebhookEndpointHealthInterface {
+    async fn insert_webhook_endpoint_health(
+        &self,
+        webhook_endpoint_health: storage::WebhookEndpointHealthNew,
+    ) -> CustomResult<storage::WebhookEndpointHealth, errors::StorageError>;
+
+    async fn find_webhook_endpoint_health_by_merchant_id(
+        &self,
+        merchant_id: &str,
+    ) -> CustomResult<storage::WebhookEndpointHealth, errors::StorageError>;
+
+    async fn update_webhook_endpoint_health_by_merchant_id(
+        &self,
+        merchant_id: &str,
+        webhook_endpoint_health_update: storage::WebhookEndpointHealthUpdate,
+    ) -> CustomResult<storage::WebhookEndpointHealth, errors::StorageError>;
+}
+
+#[async_trait::async_trait]
+impl WebhookEndpointHealthInterface for Store {
+    async fn insert_webhook_endpoint_health(
+        &self,
+        webhook_endpoint_health: storage::WebhookEndpointHealthNew,
+    ) -> CustomResult<storage::WebhookEndpointHealth, errors::StorageError> {
+        let conn = connection::pg_connection_write(self).await?;
+        webhook_endpoint_health
+            .insert(&conn)
+            .await
+            .map_err(Into::into)
+            .into_report()
+    }
+
+    async fn find_webhook_endpoint_health_by_merchant_id(
+        &self,
+        merchant_id: &str,
+    ) -> CustomResult<storage::WebhookEndpointHealth, errors::StorageError> {
+        let conn = connection::pg_connection_read(self).await?;
+        storage::WebhookEndpointHealth::find_by_merchant_id(&conn, merchant_id)
+            .await
+            .map_err(Into::into)
+            .into_report()
+    }
+
+    async fn update_webhook_endpoint_health_by_merchant_id(
+        &self,
+        merchant_id: &str,
+        webhook_endpoint_health_update: storage::WebhookEndpointHealthUpdate,
+    ) -> CustomResult<storage::WebhookEndpointHealth, errors::StorageError> {
+        let conn = connection::pg_connection_write(self).await?;
+        storage::WebhookEndpointHealth::update_by_merchant_id(
+            &conn,
+            merchant_id,
+            webhook_endpoint_health_update,
+        )
+        .await
+        .map_err(Into::into)
+        .into_report()
+    }
+}
+
+#[async_trait::async_trait]
+impl WebhookEndpointHealthInterface for MockDb {
+    async fn insert_webhook_endpoint_health(
+        &self,
+        _webhook_endpoint_health: storage::WebhookEndpointHealthNew,
+    ) -> CustomResult<storage::WebhookEndpointHealth, errors::StorageError> {
+        // TODO: Implement function for `MockDb`
+        Err(errors::StorageError::MockDbError)?
+    }
+
+    async fn find_webhook_endpoint_health_by_merchant_id(
+        &self,
+        _merchant_id: &str,
+    ) -> CustomResult<storage::WebhookEndpointHealth, errors::StorageError> {
+        // TODO: Implement function for `MockDb`
+        Err(errors::StorageError::MockDbError)?
+    }
+
+    async fn update_webhook_endpoint_health_by_merchant_id(
+        &self,
+        _merchant_id: &str,
+        _webhook_endpoint_health_update: storage::WebhookEndpointHealthUpdate,
+    ) -> CustomResult<storage::WebhookEndpointHealth, errors::StorageError> {
+        // TODO: Implement function for `MockDb`
+        Err(errors::StorageError::MockDbError)?
+    }
+}
@@ -50,6 +50,7 @@ pub mod headers {
     pub const AUTHORIZATION: &str = "Authorization";
     pub const CONTENT_TYPE: &str = "Content-Type";
     pub const DATE: &str = "Date";
+    pub const IDEMPOTENCY_KEY: &str = "Idempotency-Key";
     pub const NONCE: &str = "nonce";
     pub const TIMESTAMP: &str = "Timestamp";
     pub const TOKEN: &str = "token";
@@ -64,6 +65,7 @@ pub mod headers {
     pub const X_ACCEPT_VERSION: &str = "X-Accept-Version";
     pub const X_DATE: &str = "X-Date";
     pub const X_WEBHOOK_SIGNATURE: &str = "X-Webhook-Signature-512";
+    pub const X_WEBHOOK_SIGNATURE_JWS: &str = "X-Webhook-Signature-JWS";
 
     pub const STRIPE_COMPATIBLE_WEBHOOK_SIGNATURE: &str = "Stripe-Signature";
 }
@@ -88,7 +90,11 @@ pub fn mk_app(
         InitError = (),
     >,
 > {
-    let mut server_app = get_application_builder(request_body_limit);
+    let mut server_app = get_application_builder(
+        request_body_limit,
+        state.load_shedder.clone(),
+        state.runtime_conf.clone(),
+    );
 
     #[cfg(feature = "openapi")]
     {
@@ -112,6 +118,8 @@ pub fn mk_app(
             .service(routes::Customers::server(state.clone()))
             .service(routes::Configs::server(state.clone()))
             .service(routes::Refunds::server(state.clone()))
+            .service(routes::Invoices::server(state.clone()))
+            .service(routes::PaymentLinks::server(state.clone()))
             .service(routes::MerchantConnectorAccount::server(state.clone()))
             .service(routes::Mandates::server(state.clone()));
     }
@@ -121,6 +129,7 @@ pub fn mk_app(
         server_app = server_app
             .service(routes::PaymentMethods::server(state.clone()))
             .service(routes::EphemeralKey::server(state.clone()))
+            .service(routes::GiftCards::server(state.clone()))
             .service(routes::Webhooks::server(state.clone()));
     }
 
@@ -130,6 +139,15 @@ pub fn mk_app(
             .service(routes::MerchantAccount::server(state.clone()))
             .service(routes::ApiKeys::server(state.clone()))
             .service(routes::Files::server(state.clone()))
+            .service(routes::Terminals::server(state.clone()))
+            .service(routes::Accounting::server(state.clone()))
+            .service(routes::Audit::server(state.clone()))
+            .service(routes::Analytics::server(state.clone()))
+            .service(routes::Reports::server(state.clone()))
+            .service(routes::Routing::server(state.clone()))
+            .service(routes::Subscriptions::server(state.clone()))
+            .service(routes::Lookup::server(state.clone()))
+            .service(routes::FeatureFlags::server(state.clone()))
             .service(routes::Disputes::server(state.clone()));
     }
 
@@ -144,22 +162,46 @@ pub fn mk_app(
     }
     server_app = server_app.service(routes::Cards::server(state.clone()));
     server_app = server_app.service(routes::Cache::server(state.clone()));
+    server_app = server_app.service(routes::Runtime::server(state.clone()));
     server_app = server_app.service(routes::Health::server(state));
 
     server_app
 }
 
 /// Starts the server
+pub async fn start_server(conf: settings::Settings) -> ApplicationResult<Server> {
+    start_server_with_hooks(conf, core::hooks::HookRegistry::default()).await
+}
+
+/// Starts the server, invoking the provided hooks at the extension points of the payment and
+/// refund flows
 ///
 /// # Panics
 ///
 ///  Unwrap used because without the value we can't start the server
 #[allow(clippy::expect_used, clippy::unwrap_used)]
-pub async fn start_server(conf: settings::Settings) -> ApplicationResult<Server> {
+pub async fn start_server_with_hooks(
+    conf: settings::Settings,
+    hooks: core::hooks::HookRegistry,
+) -> ApplicationResult<Server> {
     logger::debug!(startup_config=?conf);
     let server = conf.server.clone();
     let (tx, rx) = oneshot::channel();
-    let state = routes::AppState::new(conf, tx).await;
+    let state = routes::AppState::new(conf, tx).await.with_hooks(hooks);
+    #[cfg(feature = "oltp")]
+    if state.conf.webhook_queue.enabled {
+        tokio::spawn(core::webhooks::queue::start_webhook_workers(state.clone()));
+    }
+    if state.conf.runtime_config.watch_interval > 0 {
+        tokio::spawn(
+            state
+                .runtime_conf
+                .clone()
+                .watch(std::time::Duration::from_secs(
+                    state.conf.runtime_config.watch_interval,
+                )),
+        );
+    }
     let request_body_limit = server.request_body_limit;
     let server = actix_web::HttpServer::new(move || mk_app(state.clone(), request_body_limit))
         .bind((server.host.as_str(), server.port))?
@@ -202,6 +244,8 @@ impl Stop for mpsc::Sender<()> {
 
 pub fn get_application_builder(
     request_body_limit: usize,
+    load_shedder: std::sync::Arc<middleware::LoadShedder>,
+    runtime_conf: std::sync::Arc<configs::runtime::RuntimeConfig>,
 ) -> actix_web::App<
     impl ServiceFactory<
         ServiceRequest,
@@ -218,6 +262,10 @@ pub fn get_application_builder(
 
     actix_web::App::new()
         .app_data(json_cfg)
+        .wrap(middleware::SchemaValidation {
+            runtime_conf,
+            request_body_limit,
+        })
         .wrap(ErrorHandlers::new().handler(
             StatusCode::NOT_FOUND,
             errors::error_handlers::custom_error_handlers,
@@ -226,6 +274,7 @@ pub fn get_application_builder(
             StatusCode::METHOD_NOT_ALLOWED,
             errors::error_handlers::custom_error_handlers,
         ))
+        .wrap(middleware::LoadShedding(load_shedder))
         .wrap(middleware::default_response_headers())
         .wrap(middleware::RequestId)
         .wrap(cors::cors())
@@ -48,7 +48,9 @@ macro_rules! newtype {
 #[macro_export]
 macro_rules! async_spawn {
     ($t:block) => {
-        tokio::spawn(async move { $t });
+        tokio::spawn($crate::connection::in_current_storage_region(
+            async move { $t },
+        ));
     };
 }
 
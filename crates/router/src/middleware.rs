@@ -70,3 +70,431 @@ pub fn default_response_headers() -> actix_web::middleware::DefaultHeaders {
         .add((header::STRICT_TRANSPORT_SECURITY, "max-age=31536000"))
         .add((header::VIA, "HyperSwitch"))
 }
+
+/// Priority classes of requests, which determine the load at which requests are shed.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, strum::Display)]
+#[strum(serialize_all = "snake_case")]
+pub enum RequestPriority {
+    /// Requests which are never shed, such as payment authorization and capture
+    Critical,
+    /// Requests which are shed once the server reaches its concurrency limit
+    Normal,
+    /// Requests which are shed first, such as lists and analytics
+    Low,
+}
+
+/// Load on the server, shared by all the workers of the server.
+#[derive(Debug)]
+pub struct LoadShedder {
+    runtime_conf: std::sync::Arc<crate::configs::runtime::RuntimeConfig>,
+    in_flight_requests: std::sync::atomic::AtomicUsize,
+    /// Exponentially weighted moving average of the response time of critical requests, in
+    /// milliseconds
+    critical_latency: std::sync::atomic::AtomicU64,
+}
+
+impl LoadShedder {
+    /// Weight of the previous average when updating the average response time of critical
+    /// requests, out of `CRITICAL_LATENCY_WEIGHT + 1`
+    const CRITICAL_LATENCY_WEIGHT: u64 = 7;
+
+    pub fn new(runtime_conf: std::sync::Arc<crate::configs::runtime::RuntimeConfig>) -> Self {
+        Self {
+            runtime_conf,
+            in_flight_requests: std::sync::atomic::AtomicUsize::new(0),
+            critical_latency: std::sync::atomic::AtomicU64::new(0),
+        }
+    }
+
+    pub fn get_request_priority(
+        settings: &crate::configs::settings::LoadSheddingSettings,
+        path: &str,
+    ) -> RequestPriority {
+        let matches_path = |prefixes: &[String]| {
+            prefixes
+                .iter()
+                .any(|prefix| path.starts_with(prefix.as_str()))
+        };
+
+        if matches_path(&settings.low_priority_paths) {
+            RequestPriority::Low
+        } else if matches_path(&settings.critical_paths) {
+            RequestPriority::Critical
+        } else {
+            RequestPriority::Normal
+        }
+    }
+
+    /// Returns the reason for which a request with the given priority has to be shed, if it does.
+    fn get_shed_reason(
+        &self,
+        settings: &crate::configs::settings::LoadSheddingSettings,
+        priority: RequestPriority,
+    ) -> Option<&'static str> {
+        let in_flight_requests = self
+            .in_flight_requests
+            .load(std::sync::atomic::Ordering::Relaxed);
+
+        match priority {
+            RequestPriority::Critical => None,
+            RequestPriority::Normal => (in_flight_requests >= settings.max_concurrent_requests)
+                .then_some("concurrency_limit"),
+            RequestPriority::Low => {
+                if in_flight_requests >= settings.low_priority_max_concurrent_requests {
+                    Some("concurrency_limit")
+                } else if self
+                    .critical_latency
+                    .load(std::sync::atomic::Ordering::Relaxed)
+                    > settings.critical_latency_threshold
+                {
+                    Some("critical_latency")
+                } else {
+                    None
+                }
+            }
+        }
+    }
+
+    fn record_critical_latency(&self, latency: std::time::Duration) {
+        let latency = u64::try_from(latency.as_millis()).unwrap_or(u64::MAX);
+        let _ = self.critical_latency.fetch_update(
+            std::sync::atomic::Ordering::Relaxed,
+            std::sync::atomic::Ordering::Relaxed,
+            |average| {
+                Some(
+                    average
+                        .saturating_mul(Self::CRITICAL_LATENCY_WEIGHT)
+                        .saturating_add(latency)
+                        / (Self::CRITICAL_LATENCY_WEIGHT + 1),
+                )
+            },
+        );
+    }
+}
+
+/// Request being processed, counted towards the load on the server until it is dropped.
+struct InFlightRequest(std::sync::Arc<LoadShedder>);
+
+impl InFlightRequest {
+    fn new(load_shedder: std::sync::Arc<LoadShedder>) -> Self {
+        load_shedder
+            .in_flight_requests
+            .fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+        Self(load_shedder)
+    }
+}
+
+impl Drop for InFlightRequest {
+    fn drop(&mut self) {
+        self.0
+            .in_flight_requests
+            .fetch_sub(1, std::sync::atomic::Ordering::Relaxed);
+    }
+}
+
+/// Middleware to shed low priority requests while the server is overloaded, preserving the
+/// latency of critical requests.
+pub struct LoadShedding(pub std::sync::Arc<LoadShedder>);
+
+impl<S, B> actix_web::dev::Transform<S, actix_web::dev::ServiceRequest> for LoadShedding
+where
+    S: actix_web::dev::Service<
+        actix_web::dev::ServiceRequest,
+        Response = actix_web::dev::ServiceResponse<B>,
+        Error = actix_web::Error,
+    >,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = actix_web::dev::ServiceResponse<actix_web::body::EitherBody<B>>;
+    type Error = actix_web::Error;
+    type Transform = LoadSheddingMiddleware<S>;
+    type InitError = ();
+    type Future = std::future::Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        std::future::ready(Ok(LoadSheddingMiddleware {
+            service,
+            load_shedder: self.0.clone(),
+        }))
+    }
+}
+
+pub struct LoadSheddingMiddleware<S> {
+    service: S,
+    load_shedder: std::sync::Arc<LoadShedder>,
+}
+
+impl<S, B> actix_web::dev::Service<actix_web::dev::ServiceRequest> for LoadSheddingMiddleware<S>
+where
+    S: actix_web::dev::Service<
+        actix_web::dev::ServiceRequest,
+        Response = actix_web::dev::ServiceResponse<B>,
+        Error = actix_web::Error,
+    >,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = actix_web::dev::ServiceResponse<actix_web::body::EitherBody<B>>;
+    type Error = actix_web::Error;
+    type Future = futures::future::LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    actix_web::dev::forward_ready!(service);
+
+    fn call(&self, req: actix_web::dev::ServiceRequest) -> Self::Future {
+        let load_shedder = self.load_shedder.clone();
+        let runtime_settings = load_shedder.runtime_conf.get();
+        let settings = &runtime_settings.load_shedding;
+
+        if !settings.enabled {
+            let response_fut = self.service.call(req);
+            return Box::pin(async move { Ok(response_fut.await?.map_into_left_body()) });
+        }
+
+        let priority = LoadShedder::get_request_priority(settings, req.path());
+
+        if let Some(reason) = load_shedder.get_shed_reason(settings, priority) {
+            router_env::logger::warn!(
+                %priority,
+                reason,
+                path = req.path(),
+                "Shedding request as the server is overloaded"
+            );
+            crate::routes::metrics::LOAD_SHED_REQUESTS.add(
+                &crate::routes::metrics::CONTEXT,
+                1,
+                &[
+                    crate::routes::metrics::request::add_attributes(
+                        "priority",
+                        priority.to_string(),
+                    ),
+                    crate::routes::metrics::request::add_attributes("reason", reason),
+                ],
+            );
+
+            let mut response = actix_web::HttpResponse::from_error(
+                crate::core::errors::ApiErrorResponse::ServerOverloaded,
+            );
+            if let Ok(retry_after) = http::HeaderValue::from_str(&settings.retry_after.to_string())
+            {
+                response
+                    .headers_mut()
+                    .insert(http::header::RETRY_AFTER, retry_after);
+            }
+            return Box::pin(async move { Ok(req.into_response(response).map_into_right_body()) });
+        }
+
+        let in_flight_request = InFlightRequest::new(load_shedder.clone());
+        let start_instant = std::time::Instant::now();
+        let response_fut = self.service.call(req);
+
+        Box::pin(async move {
+            let response = response_fut.await;
+            drop(in_flight_request);
+            if priority == RequestPriority::Critical {
+                load_shedder.record_critical_latency(start_instant.elapsed());
+            }
+
+            Ok(response?.map_into_left_body())
+        })
+    }
+}
+
+/// Middleware to validate the JSON bodies of requests, and optionally of responses, against the
+/// OpenAPI specification of the API.
+///
+/// Requests violating the specification are rejected with the JSON pointers to the offending
+/// fields. Fields unknown to the specification are reported in the `x-schema-warnings` response
+/// header, and cause the request to be rejected as well in strict mode. Violations of the
+/// specification by responses are only logged.
+pub struct SchemaValidation {
+    pub runtime_conf: std::sync::Arc<crate::configs::runtime::RuntimeConfig>,
+    /// The maximum size of the bodies of requests, in bytes
+    pub request_body_limit: usize,
+}
+
+impl<S, B> actix_web::dev::Transform<S, actix_web::dev::ServiceRequest> for SchemaValidation
+where
+    S: actix_web::dev::Service<
+            actix_web::dev::ServiceRequest,
+            Response = actix_web::dev::ServiceResponse<B>,
+            Error = actix_web::Error,
+        > + 'static,
+    S::Future: 'static,
+    B: actix_web::body::MessageBody + 'static,
+{
+    type Response = actix_web::dev::ServiceResponse<actix_web::body::EitherBody<B>>;
+    type Error = actix_web::Error;
+    type Transform = SchemaValidationMiddleware<S>;
+    type InitError = ();
+    type Future = std::future::Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        std::future::ready(Ok(SchemaValidationMiddleware {
+            service: std::rc::Rc::new(service),
+            runtime_conf: self.runtime_conf.clone(),
+            request_body_limit: self.request_body_limit,
+        }))
+    }
+}
+
+pub struct SchemaValidationMiddleware<S> {
+    service: std::rc::Rc<S>,
+    runtime_conf: std::sync::Arc<crate::configs::runtime::RuntimeConfig>,
+    request_body_limit: usize,
+}
+
+impl<S> SchemaValidationMiddleware<S> {
+    fn is_json(headers: &actix_web::http::header::HeaderMap) -> bool {
+        headers
+            .get(http::header::CONTENT_TYPE)
+            .and_then(|content_type| content_type.to_str().ok())
+            .map_or(false, |content_type| {
+                content_type.starts_with(mime::APPLICATION_JSON.essence_str())
+            })
+    }
+
+    fn record_failure(path: &str, body: &'static str) {
+        crate::routes::metrics::SCHEMA_VALIDATION_FAILURES.add(
+            &crate::routes::metrics::CONTEXT,
+            1,
+            &[
+                crate::routes::metrics::request::add_attributes("path", path.to_owned()),
+                crate::routes::metrics::request::add_attributes("body", body),
+            ],
+        );
+    }
+}
+
+impl<S, B> actix_web::dev::Service<actix_web::dev::ServiceRequest> for SchemaValidationMiddleware<S>
+where
+    S: actix_web::dev::Service<
+            actix_web::dev::ServiceRequest,
+            Response = actix_web::dev::ServiceResponse<B>,
+            Error = actix_web::Error,
+        > + 'static,
+    S::Future: 'static,
+    B: actix_web::body::MessageBody + 'static,
+{
+    type Response = actix_web::dev::ServiceResponse<actix_web::body::EitherBody<B>>;
+    type Error = actix_web::Error;
+    type Future = futures::future::LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    actix_web::dev::forward_ready!(service);
+
+    fn call(&self, req: actix_web::dev::ServiceRequest) -> Self::Future {
+        use futures::StreamExt;
+
+        use crate::utils::schema_validation;
+
+        let settings = self.runtime_conf.get().schema_validation.clone();
+        let operation = settings
+            .enabled
+            .then(|| schema_validation::Operation::find(req.method(), req.path()))
+            .flatten();
+
+        let Some(operation) = operation else {
+            let response_fut = self.service.call(req);
+            return Box::pin(async move { Ok(response_fut.await?.map_into_left_body()) });
+        };
+
+        let service = self.service.clone();
+        let request_body_limit = self.request_body_limit;
+
+        Box::pin(async move {
+            let mut req = req;
+            let path = req.path().to_owned();
+            let mut unknown_fields = Vec::new();
+
+            if let Some(schema) = operation
+                .get_request_schema()
+                .filter(|_| Self::is_json(req.headers()))
+            {
+                let mut payload = req.take_payload();
+                let mut body = actix_web::web::BytesMut::new();
+                while let Some(chunk) = payload.next().await {
+                    let chunk = chunk?;
+                    if body.len() + chunk.len() > request_body_limit {
+                        return Err(actix_web::error::PayloadError::Overflow.into());
+                    }
+                    body.extend_from_slice(&chunk);
+                }
+                let body = body.freeze();
+
+                // Bodies which are not valid JSON are left to be rejected when deserialized
+                if let Ok(value) = serde_json::from_slice::<serde_json::Value>(&body) {
+                    let report = schema_validation::validate_body(schema, &value);
+                    if !report.errors.is_empty()
+                        || (settings.strict && !report.unknown_fields.is_empty())
+                    {
+                        let diagnostics = report.into_diagnostics();
+                        router_env::logger::warn!(
+                            ?diagnostics,
+                            %path,
+                            "Rejecting request body violating the API specification"
+                        );
+                        Self::record_failure(&path, "request");
+                        let response = actix_web::HttpResponse::from_error(
+                            crate::core::errors::ApiErrorResponse::SchemaValidationFailed {
+                                diagnostics,
+                            },
+                        );
+                        return Ok(req.into_response(response).map_into_right_body());
+                    }
+                    unknown_fields = report.unknown_fields;
+                }
+
+                req.set_payload(actix_web::dev::Payload::from(body));
+            }
+
+            let mut response = service.call(req).await?;
+
+            if !unknown_fields.is_empty() {
+                router_env::logger::warn!(?unknown_fields, %path, "Request body has unknown fields");
+                if let Ok(warnings) = http::HeaderValue::from_str(&unknown_fields.join(", ")) {
+                    response.headers_mut().insert(
+                        http::header::HeaderName::from_static("x-schema-warnings"),
+                        warnings,
+                    );
+                }
+            }
+
+            let response_schema = settings
+                .validate_responses
+                .then(|| operation.get_response_schema(response.status()))
+                .flatten()
+                .filter(|_| Self::is_json(response.headers()));
+            let Some(response_schema) = response_schema else {
+                return Ok(response.map_into_left_body());
+            };
+
+            let (req, res) = response.into_parts();
+            let (res, body) = res.into_parts();
+            let body = actix_web::body::to_bytes(body).await.map_err(|error| {
+                actix_web::error::ErrorInternalServerError(
+                    Into::<Box<dyn std::error::Error>>::into(error),
+                )
+            })?;
+
+            match serde_json::from_slice::<serde_json::Value>(&body) {
+                Ok(value) => {
+                    let report = schema_validation::validate_body(response_schema, &value);
+                    if !report.errors.is_empty() || !report.unknown_fields.is_empty() {
+                        router_env::logger::warn!(
+                            diagnostics = ?report.into_diagnostics(),
+                            %path,
+                            "Response body violates the API specification"
+                        );
+                        Self::record_failure(&path, "response");
+                    }
+                }
+                Err(error) => {
+                    router_env::logger::warn!(?error, %path, "Response body is not valid JSON");
+                }
+            }
+
+            let res = res.set_body(body).map_into_boxed_body();
+            Ok(actix_web::dev::ServiceResponse::new(req, res).map_into_right_body())
+        })
+    }
+}
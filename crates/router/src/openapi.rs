@@ -59,13 +59,28 @@ Never share your secret api keys. Keep them guarded and secure.
         (name = "Customers", description = "Create and manage customers"),
         (name = "Payment Methods", description = "Create and manage payment methods of customers"),
         (name = "Disputes", description = "Manage disputes"),
+        (name = "Terminals", description = "Register and manage card-present terminals"),
+        (name = "Accounting", description = "Export accounting journals and query the ledger"),
+        (name = "Audit", description = "Review the changes made to the configuration of the merchant"),
+        (name = "Analytics", description = "Summarize payment and payout volumes, and the conversion of payments"),
+        (name = "Lookup", description = "Look up objects by connector references"),
+        (name = "Invoices", description = "Create invoices and collect payments towards them"),
+        (name = "Payment Links", description = "Create payment links through which customers pay payments"),
+        (name = "Gift Cards", description = "Issue and manage closed-loop gift cards"),
+        (name = "Reports", description = "Schedule recurring reports"),
+        (name = "Routing", description = "Configure the fees of connectors and preview routing decisions"),
+        (name = "Subscriptions", description = "Bill customers on a recurring basis against their mandates"),
         // (name = "API Key", description = "Create and manage API Keys"),
         (name = "Payouts", description = "Create and manage payouts"),
     ),
     paths(
         crate::routes::refunds::refunds_create,
         crate::routes::refunds::refunds_retrieve,
+        crate::routes::refunds::refunds_retrieve_by_merchant_refund_reference,
         crate::routes::refunds::refunds_update,
+        crate::routes::refunds::refunds_cancel,
+        crate::routes::refunds::refunds_approve,
+        crate::routes::refunds::refunds_reject,
         crate::routes::refunds::refunds_list,
         // Commenting this out as these are admin apis and not to be used by the merchant
         // crate::routes::admin::merchant_account_create,
@@ -76,7 +91,14 @@ Never share your secret api keys. Keep them guarded and secure.
         // crate::routes::admin::payment_connector_retrieve,
         // crate::routes::admin::payment_connector_list,
         // crate::routes::admin::payment_connector_update,
+        // crate::routes::admin::payment_connector_toggle_payment_method_type,
+        // crate::routes::admin::payment_connector_verify,
+        // crate::routes::admin::payment_connector_webhook_secret_rotate,
         // crate::routes::admin::payment_connector_delete,
+        // crate::routes::admin::webhook_signing_keys_retrieve,
+        // crate::routes::admin::webhook_signing_key_rotate,
+        // crate::routes::admin::webhook_signing_keys_delete,
+        crate::routes::admin::webhook_jwks_retrieve,
         crate::routes::mandates::get_mandate,
         crate::routes::mandates::revoke_mandate,
         crate::routes::payments::payments_create,
@@ -84,8 +106,18 @@ Never share your secret api keys. Keep them guarded and secure.
         crate::routes::payments::payments_retrieve,
         crate::routes::payments::payments_update,
         crate::routes::payments::payments_confirm,
+        crate::routes::payments::payments_retry,
+        crate::routes::payments::payments_metadata_update,
+        crate::routes::payments::payments_metadata_audit_list,
+        crate::routes::payments::payments_receipt_retrieve,
+        crate::routes::payments::payments_velocity_limits_retrieve,
+        crate::routes::payments::payments_velocity_limits_update,
+        crate::routes::payments::payments_cross_border_fee_config_retrieve,
+        crate::routes::payments::payments_cross_border_fee_config_update,
         crate::routes::payments::payments_capture,
+        crate::routes::payments::payments_incremental_authorization,
         crate::routes::payments::payments_connector_session,
+        crate::routes::payments::payments_session_tokens_refresh,
     // crate::routes::payments::payments_redirect_response,
         crate::routes::payments::payments_cancel,
         crate::routes::payments::payments_list,
@@ -96,10 +128,23 @@ Never share your secret api keys. Keep them guarded and secure.
         crate::routes::payment_methods::payment_method_retrieve_api,
         crate::routes::payment_methods::payment_method_update_api,
         crate::routes::payment_methods::payment_method_delete_api,
+        crate::routes::payment_methods::payment_method_share_api,
+        crate::routes::payment_methods::payment_method_shares_list_api,
+        crate::routes::payment_methods::payment_method_share_retrieve_api,
+        crate::routes::payment_methods::payment_method_share_revoke_api,
         crate::routes::customers::customers_create,
         crate::routes::customers::customers_retrieve,
         crate::routes::customers::customers_update,
         crate::routes::customers::customers_delete,
+        crate::routes::customers::customer_addresses_create,
+        crate::routes::customers::customer_addresses_list,
+        crate::routes::customers::customer_addresses_update,
+        crate::routes::customers::customer_addresses_delete,
+        crate::routes::customers::customer_addresses_set_default,
+        crate::routes::customers::customer_payment_methods_set_default,
+        crate::routes::customers::customers_import,
+        crate::routes::customers::customers_import_retrieve,
+        crate::routes::customers::customers_import_resume,
         // crate::routes::api_keys::api_key_create,
         // crate::routes::api_keys::api_key_retrieve,
         // crate::routes::api_keys::api_key_update,
@@ -107,11 +152,63 @@ Never share your secret api keys. Keep them guarded and secure.
         // crate::routes::api_keys::api_key_list,
         crate::routes::disputes::retrieve_disputes_list,
         crate::routes::disputes::retrieve_dispute,
+        crate::routes::disputes::retrieve_dispute_financials,
+        crate::routes::disputes::list_dispute_reason_codes,
+        crate::routes::disputes::retrieve_dispute_auto_defend_rules,
+        crate::routes::disputes::update_dispute_auto_defend_rules,
+        crate::routes::terminals::terminals_create,
+        crate::routes::terminals::terminals_retrieve,
+        crate::routes::terminals::terminals_update,
+        crate::routes::terminals::terminals_delete,
+        crate::routes::terminals::terminals_list,
+        crate::routes::accounting::export_journal,
+        crate::routes::accounting::list_ledger_entries,
+        crate::routes::accounting::retrieve_ledger_balances,
+        crate::routes::accounting::reconcile_ledger,
+        crate::routes::audit::list_audit_events,
+        crate::routes::analytics::retrieve_currency_exposure,
+        crate::routes::analytics::retrieve_payments_funnel,
+        crate::routes::analytics::retrieve_payout_analytics,
+        crate::routes::lookup::lookup_by_connector_reference,
+        crate::routes::routing::routing_fee_schedules_retrieve,
+        crate::routes::routing::routing_fee_schedules_update,
+        crate::routes::routing::routing_simulate,
+        crate::routes::invoices::invoices_create,
+        crate::routes::invoices::invoices_retrieve,
+        crate::routes::invoices::invoices_list,
+        crate::routes::invoices::invoices_void,
+        crate::routes::invoices::invoices_payment_link_retrieve,
+        crate::routes::invoices::invoices_pay,
+        crate::routes::payment_links::payment_links_create,
+        crate::routes::payment_links::payment_links_retrieve,
+        crate::routes::gift_cards::gift_cards_create,
+        crate::routes::gift_cards::gift_cards_retrieve,
+        crate::routes::gift_cards::gift_cards_redeem,
+        crate::routes::gift_cards::gift_cards_reload,
+        crate::routes::gift_cards::gift_cards_void,
+        crate::routes::gift_cards::gift_cards_transactions_list,
+        crate::routes::reports::report_schedules_create,
+        crate::routes::reports::report_schedules_retrieve,
+        crate::routes::reports::report_schedules_update,
+        crate::routes::reports::report_schedules_delete,
+        crate::routes::reports::report_schedules_list,
+        crate::routes::subscriptions::subscriptions_create,
+        crate::routes::subscriptions::subscriptions_retrieve,
+        crate::routes::subscriptions::subscriptions_update,
+        crate::routes::subscriptions::subscriptions_cancel,
+        crate::routes::subscriptions::subscriptions_pause,
+        crate::routes::subscriptions::subscriptions_resume,
+        crate::routes::subscriptions::subscriptions_list,
+        crate::routes::subscriptions::subscriptions_dunning_config_retrieve,
+        crate::routes::subscriptions::subscriptions_dunning_config_update,
         crate::routes::payouts::payouts_create,
         crate::routes::payouts::payouts_cancel,
         crate::routes::payouts::payouts_fulfill,
+        crate::routes::payouts::payouts_sync,
         crate::routes::payouts::payouts_retrieve,
         crate::routes::payouts::payouts_update,
+        crate::routes::payouts::payouts_batch_create,
+        crate::routes::payouts::payouts_batch_retrieve,
     ),
     components(schemas(
         crate::types::api::refunds::RefundRequest,
@@ -119,6 +216,8 @@ Never share your secret api keys. Keep them guarded and secure.
         crate::types::api::refunds::RefundResponse,
         crate::types::api::refunds::RefundStatus,
         crate::types::api::refunds::RefundUpdateRequest,
+        crate::types::api::refunds::RefundResolveRequest,
+        crate::types::api::refunds::RefundResolution,
         crate::types::api::admin::MerchantAccountCreate,
         crate::types::api::admin::MerchantAccountUpdate,
         crate::types::api::admin::MerchantAccountDeleteResponse,
@@ -136,7 +235,19 @@ Never share your secret api keys. Keep them guarded and secure.
         crate::types::api::payment_methods::PaymentMethodUpdate,
         crate::types::api::payment_methods::CardDetailFromLocker,
         crate::types::api::payment_methods::CardDetail,
+        crate::types::api::payment_methods::PaymentMethodAccountUpdate,
         api_models::customers::CustomerResponse,
+        api_models::customers::CustomerAddressRequest,
+        api_models::customers::CustomerAddressResponse,
+        api_models::customers::CustomerAddressDeleteResponse,
+        api_models::customers::CustomerAddressType,
+        api_models::customers::CustomerDefaultAddressRequest,
+        api_models::customers::CustomerDefaultPaymentMethodResponse,
+        api_models::customers::CustomerImportRequest,
+        api_models::customers::CustomerImportRecord,
+        api_models::customers::CustomerImportPaymentMethod,
+        api_models::customers::CustomerImportResponse,
+        api_models::customers::CustomerImportError,
         api_models::admin::AcceptedCountries,
         api_models::admin::AcceptedCurrencies,
         api_models::enums::RoutingAlgorithm,
@@ -147,6 +258,7 @@ Never share your secret api keys. Keep them guarded and secure.
         api_models::enums::Currency,
         api_models::enums::IntentStatus,
         api_models::enums::CaptureMethod,
+        api_models::enums::CaptureStatus,
         api_models::enums::FutureUsage,
         api_models::enums::AuthenticationType,
         api_models::enums::Connector,
@@ -158,6 +270,20 @@ Never share your secret api keys. Keep them guarded and secure.
         api_models::enums::CardNetwork,
         api_models::enums::DisputeStage,
         api_models::enums::DisputeStatus,
+        api_models::enums::InvoiceStatus,
+        api_models::enums::PaymentLinkStatus,
+        api_models::enums::PaymentMethodShareStatus,
+        api_models::enums::GiftCardStatus,
+        api_models::enums::GiftCardTransactionType,
+        api_models::enums::AuditEntityType,
+        api_models::enums::AuditAction,
+        api_models::enums::ReportType,
+        api_models::enums::CustomerImportStatus,
+        api_models::enums::ReportFrequency,
+        api_models::enums::ReportDeliveryMethod,
+        api_models::enums::SubscriptionStatus,
+        api_models::enums::BillingInterval,
+        api_models::enums::AccountUpdateType,
         api_models::enums::CountryAlpha2,
         api_models::enums::FieldType,
         api_models::enums::FrmAction,
@@ -166,7 +292,14 @@ Never share your secret api keys. Keep them guarded and secure.
         api_models::enums::AttemptStatus,
         api_models::admin::MerchantConnectorCreate,
         api_models::admin::MerchantConnectorUpdate,
+        api_models::admin::PaymentMethodTypeToggleRequest,
+        api_models::admin::ConnectorVerificationStatus,
+        api_models::admin::MerchantConnectorVerifyResponse,
         api_models::admin::PrimaryBusinessDetails,
+        api_models::admin::BusinessWebhookEndpoint,
+        api_models::admin::SupportContactDetails,
+        api_models::admin::PaymentLinkConfig,
+        api_models::admin::ReceiptConfig,
         api_models::admin::FrmConfigs,
         api_models::admin::FrmPaymentMethod,
         api_models::admin::FrmPaymentMethodType,
@@ -174,8 +307,23 @@ Never share your secret api keys. Keep them guarded and secure.
         api_models::admin::MerchantConnectorDetailsWrap,
         api_models::admin::MerchantConnectorDetails,
         api_models::admin::MerchantConnectorWebhookDetails,
+        api_models::admin::MerchantConnectorWebhookSecretRotateRequest,
+        api_models::admin::MerchantConnectorWebhookSecretRotateResponse,
+        api_models::admin::ConnectorMtlsDetails,
+        api_models::admin::WebhookSigningKeyRotateRequest,
+        api_models::admin::WebhookSigningKeyResponse,
+        api_models::admin::WebhookSigningKeysResponse,
+        api_models::admin::WebhookJsonWebKeySet,
         api_models::disputes::DisputeResponse,
+        api_models::disputes::DisputePaymentSummary,
+        api_models::disputes::DisputeFinancialsResponse,
         api_models::disputes::DisputeResponsePaymentsRetrieve,
+        api_models::disputes::DisputeReasonCategory,
+        api_models::disputes::DisputeReasonCodeResponse,
+        api_models::disputes::DisputeAutoDefendRules,
+        api_models::disputes::DisputeAutoDefendRule,
+        api_models::disputes::DisputeAutoDefendAction,
+        api_models::disputes::EvidenceType,
         api_models::payments::AddressDetails,
         api_models::payments::BankDebitData,
         api_models::payments::AliPayQr,
@@ -210,11 +358,18 @@ Never share your secret api keys. Keep them guarded and secure.
         api_models::payments::FeatureMetadata,
         api_models::payments::ApplepayConnectorMetadataRequest,
         api_models::payments::SessionTokenInfo,
+        api_models::payments::ApplePayPaymentProcessingDetails,
         api_models::payments::SwishQrData,
         api_models::payments::AirwallexData,
         api_models::payments::NoonData,
         api_models::payments::OrderDetails,
         api_models::payments::OrderDetailsWithAmount,
+        api_models::payments::ConvenienceFee,
+        api_models::payments::ConvenienceFeeDetails,
+        api_models::payments::CurrencyConversionDetails,
+        api_models::payments::ConvenienceFeeTaxTreatment,
+        api_models::payments::L2L3Data,
+        api_models::payments::L3LineItem,
         api_models::payments::NextActionType,
         api_models::payments::WalletData,
         api_models::payments::NextActionData,
@@ -232,12 +387,30 @@ Never share your secret api keys. Keep them guarded and secure.
         api_models::payments::PaymentsRequest,
         api_models::payments::PaymentsCreateRequest,
         api_models::payments::PaymentsResponse,
+        api_models::payments::PaymentsSimulationResponse,
+        api_models::payments::ConnectorRequestPreview,
         api_models::payments::PaymentsStartRequest,
         api_models::payments::PaymentRetrieveBody,
         api_models::payments::PaymentsRetrieveRequest,
         api_models::payments::PaymentIdType,
         api_models::payments::PaymentsCaptureRequest,
+        api_models::payments::PaymentsIncrementalAuthorizationRequest,
+        api_models::payments::PaymentsIncrementalAuthorizationResponse,
+        api_models::payments::AuthorizationRecord,
+        api_models::payments::PaymentsRetryRequest,
+        api_models::payments::PaymentsMetadataUpdateRequest,
+        api_models::payments::PaymentsMetadataUpdateResponse,
+        api_models::payments::PaymentVelocityLimits,
+        api_models::payments::PaymentVelocityLimit,
+        api_models::payments::VelocityLimitScope,
+        api_models::payments::VelocityLimitWindow,
+        api_models::payments::CrossBorderFeeConfig,
+        api_models::payments::CrossBorderSurcharge,
+        api_models::payments::CrossBorderFeeDetails,
+        api_models::payments::PaymentMetadataAuditResponse,
+        api_models::payments::ReceiptFormat,
         api_models::payments::PaymentsSessionRequest,
+        api_models::payments::PaymentsSessionRefreshRequest,
         api_models::payments::PaymentsSessionResponse,
         api_models::payments::SessionToken,
         api_models::payments::ApplePaySessionResponse,
@@ -292,6 +465,7 @@ Never share your secret api keys. Keep them guarded and secure.
         api_models::payments::BacsBankTransferInstructions,
         api_models::payments::RedirectResponse,
         api_models::payments::PaymentAttemptResponse,
+        api_models::payments::CaptureResponse,
         api_models::payment_methods::RequiredFieldInfo,
         api_models::refunds::RefundListRequest,
         api_models::refunds::RefundListResponse,
@@ -303,6 +477,77 @@ Never share your secret api keys. Keep them guarded and secure.
         api_models::payments::CustomerDetails,
         api_models::payments::GiftCardData,
         api_models::payments::GiftCardDetails,
+        api_models::payments::CardPresentData,
+        api_models::enums::CardPresentEntryMode,
+        api_models::enums::SessionTokenStrategy,
+        api_models::enums::AuthorizationStatus,
+        api_models::enums::WebhookSigningAlgorithm,
+        api_models::enums::WebhookSigningKeyStatus,
+        api_models::terminals::TerminalCreateRequest,
+        api_models::terminals::TerminalUpdateRequest,
+        api_models::terminals::TerminalResponse,
+        api_models::terminals::TerminalDeleteResponse,
+        api_models::accounting::JournalExportFormat,
+        api_models::accounting::JournalEntryType,
+        api_models::accounting::LedgerAccount,
+        api_models::accounting::JournalLine,
+        api_models::accounting::JournalExportResponse,
+        api_models::accounting::LedgerEntryResponse,
+        api_models::accounting::LedgerEntryListResponse,
+        api_models::accounting::LedgerAccountBalance,
+        api_models::accounting::LedgerBalanceResponse,
+        api_models::accounting::SettlementLine,
+        api_models::accounting::LedgerReconciliationRequest,
+        api_models::accounting::ReconciliationDiscrepancyType,
+        api_models::accounting::ReconciliationDiscrepancy,
+        api_models::accounting::LedgerReconciliationResponse,
+        api_models::analytics::CurrencyExposure,
+        api_models::analytics::CurrencyExposureResponse,
+        api_models::analytics::FunnelGranularity,
+        api_models::analytics::PaymentsFunnelStages,
+        api_models::analytics::PaymentsFunnelBucket,
+        api_models::analytics::PaymentsFunnelResponse,
+        api_models::analytics::PayoutAnalyticsBucket,
+        api_models::analytics::PayoutFailureReason,
+        api_models::analytics::PayoutAnalyticsResponse,
+        api_models::lookup::LookupObjectType,
+        api_models::lookup::ConnectorReferenceLookupResult,
+        api_models::lookup::ConnectorReferenceLookupResponse,
+        api_models::routing::ConnectorFeeSchedules,
+        api_models::routing::ConnectorFeeSchedule,
+        api_models::routing::ConnectorFee,
+        api_models::routing::RoutingSimulationRequest,
+        api_models::routing::RoutingSimulationResponse,
+        api_models::routing::ConnectorCost,
+        api_models::invoices::InvoiceLineItem,
+        api_models::invoices::InvoiceCreateRequest,
+        api_models::invoices::InvoiceResponse,
+        api_models::invoices::InvoicePaymentRequest,
+        api_models::payment_links::PaymentLinkCreateRequest,
+        api_models::payment_links::PaymentLinkResponse,
+        api_models::payment_method_shares::PaymentMethodShareRequest,
+        api_models::payment_method_shares::PaymentMethodShareConsent,
+        api_models::payment_method_shares::PaymentMethodShareResponse,
+        api_models::gift_cards::GiftCardCreateRequest,
+        api_models::gift_cards::GiftCardResponse,
+        api_models::gift_cards::GiftCardRedeemRequest,
+        api_models::gift_cards::GiftCardReloadRequest,
+        api_models::gift_cards::GiftCardTransactionResponse,
+        api_models::audit::AuditEventResponse,
+        api_models::audit::AuditEventListResponse,
+        api_models::reports::ReportFormat,
+        api_models::reports::ReportTemplate,
+        api_models::reports::ReportFilters,
+        api_models::reports::ReportScheduleCreateRequest,
+        api_models::reports::ReportScheduleUpdateRequest,
+        api_models::reports::ReportScheduleResponse,
+        api_models::reports::ReportScheduleDeleteResponse,
+        api_models::reports::GeneratedReportResponse,
+        api_models::subscriptions::SubscriptionCreateRequest,
+        api_models::subscriptions::SubscriptionUpdateRequest,
+        api_models::subscriptions::SubscriptionResponse,
+        api_models::subscriptions::SubscriptionDunningAction,
+        api_models::subscriptions::SubscriptionDunningConfig,
         api_models::payouts::PayoutCreateRequest,
         api_models::payments::Address,
         api_models::payouts::Card,
@@ -313,20 +558,27 @@ Never share your secret api keys. Keep them guarded and secure.
         api_models::payouts::PayoutRetrieveBody,
         api_models::payouts::PayoutRetrieveRequest,
         api_models::payouts::PayoutActionRequest,
+        api_models::payouts::PayoutBatchCreateRequest,
+        api_models::payouts::PayoutBatchResponse,
+        api_models::payouts::PayoutBatchStatusCount,
+        api_models::payouts::PayoutBatchItem,
         api_models::payouts::PayoutRequest,
         api_models::payouts::PayoutMethodData,
         api_models::payouts::Bank,
+        api_models::refunds::RefundAlternateDestination,
         api_models::enums::PayoutEntityType,
         api_models::enums::PayoutStatus,
         api_models::enums::PayoutType,
         api_models::payments::FrmMessage,
         api_models::webhooks::OutgoingWebhook,
+        api_models::webhooks::WebhookSourceValidation,
         api_models::webhooks::OutgoingWebhookContent,
         api_models::enums::EventType,
         crate::types::api::admin::MerchantAccountResponse,
         crate::types::api::admin::MerchantConnectorId,
         crate::types::api::admin::MerchantDetails,
         crate::types::api::admin::WebhookDetails,
+        crate::types::api::admin::WebhookMtlsDetails,
         crate::types::api::api_keys::ApiKeyExpiration,
         crate::types::api::api_keys::CreateApiKeyRequest,
         crate::types::api::api_keys::CreateApiKeyResponse,
@@ -378,6 +630,15 @@ impl utoipa::Modify for SecurityAddon {
                         to a single customer object for a short period of time."
                     ))),
                 ),
+                (
+                    "jwt_key",
+                    SecurityScheme::ApiKey(ApiKey::Header(ApiKeyValue::with_description(
+                        "authorization",
+                        "JWTs are issued to the users of the HyperSwitch dashboard, and allow \
+                        them to perform actions which cannot be performed with API keys, such \
+                        as approving refunds."
+                    ))),
+                ),
             ]);
         }
     }
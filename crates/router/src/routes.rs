@@ -1,6 +1,12 @@
+#[cfg(feature = "olap")]
+pub mod accounting;
 pub mod admin;
+#[cfg(feature = "olap")]
+pub mod analytics;
 pub mod api_keys;
 pub mod app;
+#[cfg(feature = "olap")]
+pub mod audit;
 pub mod cache;
 pub mod cards_info;
 pub mod configs;
@@ -9,15 +15,30 @@ pub mod disputes;
 #[cfg(feature = "dummy_connector")]
 pub mod dummy_connector;
 pub mod ephemeral_key;
+#[cfg(feature = "olap")]
+pub mod feature_flags;
 pub mod files;
+pub mod gift_cards;
 pub mod health;
+pub mod invoices;
+#[cfg(feature = "olap")]
+pub mod lookup;
 pub mod mandates;
 pub mod metrics;
+pub mod payment_links;
 pub mod payment_methods;
 pub mod payments;
 #[cfg(feature = "payouts")]
 pub mod payouts;
 pub mod refunds;
+#[cfg(feature = "olap")]
+pub mod reports;
+#[cfg(feature = "olap")]
+pub mod routing;
+pub mod runtime_config;
+#[cfg(feature = "olap")]
+pub mod subscriptions;
+pub mod terminals;
 pub mod webhooks;
 
 #[cfg(feature = "dummy_connector")]
@@ -25,9 +46,10 @@ pub use self::app::DummyConnector;
 #[cfg(feature = "payouts")]
 pub use self::app::Payouts;
 pub use self::app::{
-    ApiKeys, AppState, Cache, Cards, Configs, Customers, Disputes, EphemeralKey, Files, Health,
-    Mandates, MerchantAccount, MerchantConnectorAccount, PaymentMethods, Payments, Refunds,
-    Webhooks,
+    Accounting, Analytics, ApiKeys, AppState, Audit, Cache, Cards, Configs, Customers, Disputes,
+    EphemeralKey, FeatureFlags, Files, GiftCards, Health, Invoices, Lookup, Mandates,
+    MerchantAccount, MerchantConnectorAccount, PaymentLinks, PaymentMethods, Payments, Refunds,
+    Reports, Routing, Runtime, Subscriptions, Terminals, Webhooks,
 };
 #[cfg(feature = "stripe")]
 pub use super::compatibility::stripe::StripeApis;
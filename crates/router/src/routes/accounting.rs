@@ -0,0 +1,162 @@
+use actix_web::{web, HttpRequest, HttpResponse};
+use api_models::accounting as accounting_models;
+use router_env::{instrument, tracing, Flow};
+
+use super::app::AppState;
+use crate::{
+    core::{accounting, ledger},
+    services::{api, authentication as auth},
+};
+
+/// Accounting - Export Journal
+///
+/// Export the double-entry journal lines of sales, fees, refunds, chargebacks and payouts for a period, to be imported into ledger or ERP systems
+#[utoipa::path(
+    get,
+    path = "/accounting/journal",
+    params(
+        ("start_time" = PrimitiveDateTime, Query, description = "The start of the period for which the journal is exported"),
+        ("end_time" = Option<PrimitiveDateTime>, Query, description = "The end of the period for which the journal is exported"),
+        ("currency" = Option<Currency>, Query, description = "Only export journal lines in this currency"),
+        ("format" = Option<JournalExportFormat>, Query, description = "The format in which the journal is exported"),
+    ),
+    responses(
+        (status = 200, description = "The journal was exported successfully", body = JournalExportResponse),
+        (status = 400, description = "Invalid period, or more entries in the period than can be exported at once"),
+        (status = 401, description = "Unauthorized request")
+    ),
+    tag = "Accounting",
+    operation_id = "Export Journal",
+    security(("api_key" = []))
+)]
+#[instrument(skip_all, fields(flow = ?Flow::AccountingJournalExport))]
+pub async fn export_journal(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    payload: web::Query<accounting_models::JournalExportRequest>,
+) -> HttpResponse {
+    let flow = Flow::AccountingJournalExport;
+    let payload = payload.into_inner();
+    api::server_wrap(
+        flow,
+        state.get_ref(),
+        &req,
+        payload,
+        |state, auth, req| accounting::export_journal(state, auth.merchant_account, req),
+        auth::auth_type(&auth::ApiKeyAuth, &auth::JWTAuth, req.headers()),
+    )
+    .await
+}
+
+/// Accounting - List Ledger Entries
+///
+/// List the double-entry postings recorded in the ledger for authorizations, captures, refunds, chargebacks, fees and payouts
+#[utoipa::path(
+    get,
+    path = "/accounting/ledger/entries",
+    params(
+        ("reference_id" = Option<String>, Query, description = "Only list the entries of this payment, refund, dispute or payout"),
+        ("account" = Option<LedgerAccount>, Query, description = "Only list the entries which debit or credit this account"),
+        ("currency" = Option<Currency>, Query, description = "Only list the entries in this currency"),
+        ("connector" = Option<String>, Query, description = "Only list the entries of funds moved through this connector"),
+        ("start_time" = Option<PrimitiveDateTime>, Query, description = "Only list the entries posted at or after this time"),
+        ("end_time" = Option<PrimitiveDateTime>, Query, description = "Only list the entries posted at or before this time"),
+        ("limit" = Option<i64>, Query, description = "The maximum number of entries to include in the response"),
+    ),
+    responses(
+        (status = 200, description = "The ledger entries were retrieved successfully", body = LedgerEntryListResponse),
+        (status = 400, description = "Invalid constraints"),
+        (status = 401, description = "Unauthorized request")
+    ),
+    tag = "Accounting",
+    operation_id = "List Ledger Entries",
+    security(("api_key" = []))
+)]
+#[instrument(skip_all, fields(flow = ?Flow::LedgerEntriesList))]
+pub async fn list_ledger_entries(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    payload: web::Query<accounting_models::LedgerEntryListConstraints>,
+) -> HttpResponse {
+    let flow = Flow::LedgerEntriesList;
+    let payload = payload.into_inner();
+    api::server_wrap(
+        flow,
+        state.get_ref(),
+        &req,
+        payload,
+        |state, auth, req| ledger::list_ledger_entries(state, auth.merchant_account, req),
+        auth::auth_type(&auth::ApiKeyAuth, &auth::JWTAuth, req.headers()),
+    )
+    .await
+}
+
+/// Accounting - Retrieve Ledger Balances
+///
+/// Retrieve the balances of the ledger accounts of the merchant, per currency
+#[utoipa::path(
+    get,
+    path = "/accounting/ledger/balances",
+    params(
+        ("currency" = Option<Currency>, Query, description = "Only retrieve the balances in this currency"),
+    ),
+    responses(
+        (status = 200, description = "The ledger balances were retrieved successfully", body = LedgerBalanceResponse),
+        (status = 401, description = "Unauthorized request")
+    ),
+    tag = "Accounting",
+    operation_id = "Retrieve Ledger Balances",
+    security(("api_key" = []))
+)]
+#[instrument(skip_all, fields(flow = ?Flow::LedgerBalancesRetrieve))]
+pub async fn retrieve_ledger_balances(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    payload: web::Query<accounting_models::LedgerBalanceRequest>,
+) -> HttpResponse {
+    let flow = Flow::LedgerBalancesRetrieve;
+    let payload = payload.into_inner();
+    api::server_wrap(
+        flow,
+        state.get_ref(),
+        &req,
+        payload,
+        |state, auth, req| ledger::retrieve_ledger_balances(state, auth.merchant_account, req),
+        auth::auth_type(&auth::ApiKeyAuth, &auth::JWTAuth, req.headers()),
+    )
+    .await
+}
+
+/// Accounting - Reconcile Ledger
+///
+/// Reconcile the settlement report of a connector against the entries posted to the ledger in the period covered by the report
+#[utoipa::path(
+    post,
+    path = "/accounting/ledger/reconcile",
+    request_body = LedgerReconciliationRequest,
+    responses(
+        (status = 200, description = "The settlement report was reconciled successfully", body = LedgerReconciliationResponse),
+        (status = 400, description = "Invalid period, or more entries in the period than can be exported at once"),
+        (status = 401, description = "Unauthorized request")
+    ),
+    tag = "Accounting",
+    operation_id = "Reconcile Ledger",
+    security(("api_key" = []))
+)]
+#[instrument(skip_all, fields(flow = ?Flow::LedgerReconcile))]
+pub async fn reconcile_ledger(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    json_payload: web::Json<accounting_models::LedgerReconciliationRequest>,
+) -> HttpResponse {
+    let flow = Flow::LedgerReconcile;
+    api::server_wrap(
+        flow,
+        state.get_ref(),
+        &req,
+        json_payload.into_inner(),
+        |state, auth, req| ledger::reconcile_ledger(state, auth.merchant_account, req),
+        auth::auth_type(&auth::ApiKeyAuth, &auth::JWTAuth, req.headers()),
+    )
+    .await
+}
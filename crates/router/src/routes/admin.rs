@@ -3,9 +3,9 @@ use router_env::{instrument, tracing, Flow};
 
 use super::app::AppState;
 use crate::{
-    core::admin::*,
+    core::{admin::*, commissions, refunds, webhooks::endpoint_health},
     services::{api, authentication as auth},
-    types::api::admin,
+    types::api::{admin, refunds as refund_types},
 };
 
 /// Merchant Account - Create
@@ -35,7 +35,7 @@ pub async fn merchant_account_create(
         state.get_ref(),
         &req,
         json_payload.into_inner(),
-        |state, _, req| create_merchant_account(&*state.store, req),
+        |state, _, req| create_merchant_account(state, req),
         &auth::AdminApiAuth,
     )
     .await
@@ -180,7 +180,7 @@ pub async fn payment_connector_create(
         state.get_ref(),
         &req,
         json_payload.into_inner(),
-        |state, _, req| create_payment_connector(&*state.store, req, &merchant_id),
+        |state, _, req| create_payment_connector(state, req, &merchant_id),
         &auth::AdminApiAuth,
     )
     .await
@@ -300,13 +300,148 @@ pub async fn payment_connector_update(
     let flow = Flow::MerchantConnectorsUpdate;
     let (merchant_id, merchant_connector_id) = path.into_inner();
 
+    api::server_wrap(
+        flow,
+        state.get_ref(),
+        &req,
+        json_payload.into_inner(),
+        |state, _, req| update_payment_connector(state, &merchant_id, &merchant_connector_id, req),
+        &auth::AdminApiAuth,
+    )
+    .await
+}
+
+/// Merchant Connector - Toggle Payment Method Type
+///
+/// To enable or disable a payment method type of an existing Merchant Connector, without changing the rest of its configuration
+#[utoipa::path(
+    post,
+    path = "/accounts/{account_id}/connectors/{connector_id}/payment_methods/toggle",
+    request_body = PaymentMethodTypeToggleRequest,
+    params(
+        ("account_id" = String, Path, description = "The unique identifier for the merchant account"),
+        ("connector_id" = i32, Path, description = "The unique identifier for the Merchant Connector")
+    ),
+    responses(
+        (status = 200, description = "Merchant Connector Updated", body = MerchantConnectorResponse),
+        (status = 400, description = "Payment method type is not configured for the Merchant Connector"),
+        (status = 404, description = "Merchant Connector does not exist in records"),
+        (status = 401, description = "Unauthorized request")
+    ),
+   tag = "Merchant Connector Account",
+   operation_id = "Toggle a Payment Method Type of a Merchant Connector",
+   security(("admin_api_key" = []))
+)]
+#[instrument(skip_all, fields(flow = ?Flow::MerchantConnectorsUpdate))]
+pub async fn payment_connector_toggle_payment_method_type(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<(String, String)>,
+    json_payload: web::Json<api_models::admin::PaymentMethodTypeToggleRequest>,
+) -> HttpResponse {
+    let flow = Flow::MerchantConnectorsUpdate;
+    let (merchant_id, merchant_connector_id) = path.into_inner();
+
+    api::server_wrap(
+        flow,
+        state.get_ref(),
+        &req,
+        json_payload.into_inner(),
+        |state, _, req| {
+            toggle_payment_method_type(&*state.store, &merchant_id, &merchant_connector_id, req)
+        },
+        &auth::AdminApiAuth,
+    )
+    .await
+}
+
+/// Merchant Connector - Verify
+///
+/// Verify the account details of an existing Merchant Connector with the connector, to make sure that they are accepted before processing payments through it
+#[utoipa::path(
+    post,
+    path = "/accounts/{account_id}/connectors/{connector_id}/verify",
+    params(
+        ("account_id" = String, Path, description = "The unique identifier for the merchant account"),
+        ("connector_id" = i32, Path, description = "The unique identifier for the Merchant Connector")
+    ),
+    responses(
+        (status = 200, description = "Merchant Connector account details verified", body = MerchantConnectorVerifyResponse),
+        (status = 404, description = "Merchant Connector does not exist in records"),
+        (status = 401, description = "Unauthorized request")
+    ),
+    tag = "Merchant Connector Account",
+    operation_id = "Verify a Merchant Connector",
+    security(("admin_api_key" = []))
+)]
+#[instrument(skip_all, fields(flow = ?Flow::MerchantConnectorsVerify))]
+pub async fn payment_connector_verify(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<(String, String)>,
+) -> HttpResponse {
+    let flow = Flow::MerchantConnectorsVerify;
+    let (merchant_id, merchant_connector_id) = path.into_inner();
+    let payload = web::Json(admin::MerchantConnectorId {
+        merchant_id,
+        merchant_connector_id,
+    })
+    .into_inner();
+
+    api::server_wrap(
+        flow,
+        state.get_ref(),
+        &req,
+        payload,
+        |state, _, req| {
+            connector_verification::verify_payment_connector(
+                state,
+                req.merchant_id,
+                req.merchant_connector_id,
+            )
+        },
+        &auth::AdminApiAuth,
+    )
+    .await
+}
+
+/// Merchant Connector - Rotate Webhook Secret
+///
+/// Rotate the secret with which the webhooks of an existing Merchant Connector are verified, without rejecting the webhooks signed with the previous secret until the new secret is configured on the connector
+#[utoipa::path(
+    post,
+    path = "/accounts/{account_id}/connectors/{connector_id}/webhook_secret/rotate",
+    request_body = MerchantConnectorWebhookSecretRotateRequest,
+    params(
+        ("account_id" = String, Path, description = "The unique identifier for the merchant account"),
+        ("connector_id" = i32, Path, description = "The unique identifier for the Merchant Connector")
+    ),
+    responses(
+        (status = 200, description = "Merchant Connector webhook secret rotated", body = MerchantConnectorWebhookSecretRotateResponse),
+        (status = 404, description = "Merchant Connector does not exist in records"),
+        (status = 401, description = "Unauthorized request")
+    ),
+    tag = "Merchant Connector Account",
+    operation_id = "Rotate the Webhook Secret of a Merchant Connector",
+    security(("admin_api_key" = []))
+)]
+#[instrument(skip_all, fields(flow = ?Flow::MerchantConnectorsWebhookSecretRotate))]
+pub async fn payment_connector_webhook_secret_rotate(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<(String, String)>,
+    json_payload: web::Json<api_models::admin::MerchantConnectorWebhookSecretRotateRequest>,
+) -> HttpResponse {
+    let flow = Flow::MerchantConnectorsWebhookSecretRotate;
+    let (merchant_id, merchant_connector_id) = path.into_inner();
+
     api::server_wrap(
         flow,
         state.get_ref(),
         &req,
         json_payload.into_inner(),
         |state, _, req| {
-            update_payment_connector(&*state.store, &merchant_id, &merchant_connector_id, req)
+            webhook_secrets::rotate_webhook_secret(state, &merchant_id, &merchant_connector_id, req)
         },
         &auth::AdminApiAuth,
     )
@@ -408,3 +543,394 @@ pub async fn merchant_account_kv_status(
     )
     .await
 }
+
+/// Merchant Account - Key Store Retrieve
+///
+/// Retrieve the version and rotation details of the merchant encryption key
+#[instrument(skip_all, fields(flow = ?Flow::MerchantKeyStoreRetrieve))]
+pub async fn merchant_key_store_retrieve(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<String>,
+) -> HttpResponse {
+    let flow = Flow::MerchantKeyStoreRetrieve;
+    let merchant_id = path.into_inner();
+
+    api::server_wrap(
+        flow,
+        state.get_ref(),
+        &req,
+        merchant_id,
+        |state, _, req| retrieve_merchant_key_store(&*state.store, req),
+        &auth::AdminApiAuth,
+    )
+    .await
+}
+
+/// Merchant Account - Key Store Rotate
+///
+/// Rotate the merchant encryption key to the current master key
+#[instrument(skip_all, fields(flow = ?Flow::MerchantKeyStoreRotate))]
+pub async fn merchant_key_store_rotate(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<String>,
+) -> HttpResponse {
+    let flow = Flow::MerchantKeyStoreRotate;
+    let merchant_id = path.into_inner();
+
+    api::server_wrap(
+        flow,
+        state.get_ref(),
+        &req,
+        merchant_id,
+        |state, _, req| rotate_merchant_key_store(&*state.store, req),
+        &auth::AdminApiAuth,
+    )
+    .await
+}
+
+/// Merchant Account - Allowed Domains Retrieve
+///
+/// Retrieve the domains on which return urls and webhook urls of the merchant must be hosted
+#[instrument(skip_all, fields(flow = ?Flow::MerchantAllowedDomainsRetrieve))]
+pub async fn merchant_allowed_domains_retrieve(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<String>,
+) -> HttpResponse {
+    let flow = Flow::MerchantAllowedDomainsRetrieve;
+    let merchant_id = path.into_inner();
+
+    api::server_wrap(
+        flow,
+        state.get_ref(),
+        &req,
+        merchant_id,
+        |state, _, req| retrieve_merchant_allowed_domains(&*state.store, req),
+        &auth::AdminApiAuth,
+    )
+    .await
+}
+
+/// Merchant Account - Allowed Domains Update
+///
+/// Replace the domains on which return urls and webhook urls of the merchant must be hosted
+#[instrument(skip_all, fields(flow = ?Flow::MerchantAllowedDomainsUpdate))]
+pub async fn merchant_allowed_domains_update(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<String>,
+    json_payload: web::Json<admin::MerchantAllowedDomainsUpdateRequest>,
+) -> HttpResponse {
+    let flow = Flow::MerchantAllowedDomainsUpdate;
+    let merchant_id = path.into_inner();
+
+    api::server_wrap(
+        flow,
+        state.get_ref(),
+        &req,
+        (merchant_id, json_payload.into_inner()),
+        |state, _, (merchant_id, payload)| {
+            update_merchant_allowed_domains(&*state.store, merchant_id, payload)
+        },
+        &auth::AdminApiAuth,
+    )
+    .await
+}
+
+/// Merchant Account - Commission Statement
+///
+/// Retrieve the commissions recorded by the platform for the payments of a merchant in a period
+#[instrument(skip_all, fields(flow = ?Flow::MerchantCommissionStatementRetrieve))]
+pub async fn merchant_commission_statement_retrieve(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<String>,
+    query_payload: web::Query<api_models::commissions::CommissionStatementRequest>,
+) -> HttpResponse {
+    let flow = Flow::MerchantCommissionStatementRetrieve;
+    let merchant_id = path.into_inner();
+
+    api::server_wrap(
+        flow,
+        state.get_ref(),
+        &req,
+        (merchant_id, query_payload.into_inner()),
+        |state, _, (merchant_id, payload)| {
+            commissions::retrieve_commission_statement(state, merchant_id, payload)
+        },
+        &auth::AdminApiAuth,
+    )
+    .await
+}
+
+/// Merchant Account - Webhook Endpoint Health Retrieve
+///
+/// Retrieve the health of the webhook endpoint of a merchant
+#[instrument(skip_all, fields(flow = ?Flow::WebhookEndpointHealthRetrieve))]
+pub async fn webhook_endpoint_health_retrieve(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<String>,
+) -> HttpResponse {
+    let flow = Flow::WebhookEndpointHealthRetrieve;
+    let merchant_id = path.into_inner();
+
+    api::server_wrap(
+        flow,
+        state.get_ref(),
+        &req,
+        merchant_id,
+        |state, _, merchant_id| {
+            endpoint_health::retrieve_webhook_endpoint_health(state, merchant_id)
+        },
+        &auth::AdminApiAuth,
+    )
+    .await
+}
+
+/// Merchant Account - Webhook Endpoint Verify
+///
+/// Verify the webhook endpoint of a merchant through a challenge request, resuming deliveries to
+/// it if they were paused
+#[instrument(skip_all, fields(flow = ?Flow::WebhookEndpointVerify))]
+pub async fn webhook_endpoint_verify(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<String>,
+) -> HttpResponse {
+    let flow = Flow::WebhookEndpointVerify;
+    let merchant_id = path.into_inner();
+
+    api::server_wrap(
+        flow,
+        state.get_ref(),
+        &req,
+        merchant_id,
+        |state, _, merchant_id| endpoint_health::verify_webhook_endpoint(state, merchant_id),
+        &auth::AdminApiAuth,
+    )
+    .await
+}
+
+/// Merchant Account - Webhook Signing Keys Retrieve
+///
+/// Retrieve the key pairs with which the outgoing webhooks of a merchant are signed
+#[utoipa::path(
+    get,
+    path = "/accounts/{account_id}/webhook_signing_keys",
+    params (("account_id" = String, Path, description = "The unique identifier for the merchant account")),
+    responses(
+        (status = 200, description = "Webhook signing keys retrieved", body = WebhookSigningKeysResponse),
+        (status = 404, description = "Merchant account not found")
+    ),
+    tag = "Merchant Account",
+    operation_id = "Retrieve the Webhook Signing Keys of a Merchant Account",
+    security(("admin_api_key" = []))
+)]
+#[instrument(skip_all, fields(flow = ?Flow::WebhookSigningKeysRetrieve))]
+pub async fn webhook_signing_keys_retrieve(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<String>,
+) -> HttpResponse {
+    let flow = Flow::WebhookSigningKeysRetrieve;
+    let merchant_id = path.into_inner();
+
+    api::server_wrap(
+        flow,
+        state.get_ref(),
+        &req,
+        merchant_id,
+        |state, _, merchant_id| {
+            webhook_signing_keys::retrieve_webhook_signing_keys(state, &merchant_id)
+        },
+        &auth::AdminApiAuth,
+    )
+    .await
+}
+
+/// Merchant Account - Webhook Signing Key Rotate
+///
+/// Generate a new Ed25519 or RSA key pair with which the outgoing webhooks of a merchant are signed, retiring the active key pair. The public key of the retired key pair remains published for a grace period, so that webhooks signed before the rotation can still be verified
+#[utoipa::path(
+    post,
+    path = "/accounts/{account_id}/webhook_signing_keys",
+    request_body = WebhookSigningKeyRotateRequest,
+    params (("account_id" = String, Path, description = "The unique identifier for the merchant account")),
+    responses(
+        (status = 200, description = "Webhook signing key rotated", body = WebhookSigningKeysResponse),
+        (status = 404, description = "Merchant account not found")
+    ),
+    tag = "Merchant Account",
+    operation_id = "Rotate the Webhook Signing Key of a Merchant Account",
+    security(("admin_api_key" = []))
+)]
+#[instrument(skip_all, fields(flow = ?Flow::WebhookSigningKeyRotate))]
+pub async fn webhook_signing_key_rotate(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<String>,
+    json_payload: web::Json<api_models::admin::WebhookSigningKeyRotateRequest>,
+) -> HttpResponse {
+    let flow = Flow::WebhookSigningKeyRotate;
+    let merchant_id = path.into_inner();
+
+    api::server_wrap(
+        flow,
+        state.get_ref(),
+        &req,
+        json_payload.into_inner(),
+        |state, _, req| webhook_signing_keys::rotate_webhook_signing_key(state, &merchant_id, req),
+        &auth::AdminApiAuth,
+    )
+    .await
+}
+
+/// Merchant Account - Webhook Signing Keys Delete
+///
+/// Stop signing the outgoing webhooks of a merchant with a key pair, removing all of its webhook signing keys. Webhooks are still signed with the payment response hash key
+#[utoipa::path(
+    delete,
+    path = "/accounts/{account_id}/webhook_signing_keys",
+    params (("account_id" = String, Path, description = "The unique identifier for the merchant account")),
+    responses(
+        (status = 200, description = "Webhook signing keys deleted", body = WebhookSigningKeysResponse),
+        (status = 404, description = "Merchant account not found")
+    ),
+    tag = "Merchant Account",
+    operation_id = "Delete the Webhook Signing Keys of a Merchant Account",
+    security(("admin_api_key" = []))
+)]
+#[instrument(skip_all, fields(flow = ?Flow::WebhookSigningKeysDelete))]
+pub async fn webhook_signing_keys_delete(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<String>,
+) -> HttpResponse {
+    let flow = Flow::WebhookSigningKeysDelete;
+    let merchant_id = path.into_inner();
+
+    api::server_wrap(
+        flow,
+        state.get_ref(),
+        &req,
+        merchant_id,
+        |state, _, merchant_id| {
+            webhook_signing_keys::delete_webhook_signing_keys(state, &merchant_id)
+        },
+        &auth::AdminApiAuth,
+    )
+    .await
+}
+
+/// Merchant Account - Webhook JWKS Retrieve
+///
+/// Retrieve the public keys with which the signatures of the outgoing webhooks of a merchant are verified, as a JSON Web Key Set. The signature is sent in the `X-Webhook-Signature-JWS` header of the webhook as a JWS with a detached payload, whose `kid` identifies the key with which it is verified
+#[utoipa::path(
+    get,
+    path = "/accounts/{account_id}/webhook_signing_keys/jwks",
+    params (("account_id" = String, Path, description = "The unique identifier for the merchant account")),
+    responses(
+        (status = 200, description = "Webhook JWKS retrieved", body = WebhookJsonWebKeySet),
+        (status = 404, description = "Merchant account not found")
+    ),
+    tag = "Merchant Account",
+    operation_id = "Retrieve the Webhook JWKS of a Merchant Account"
+)]
+#[instrument(skip_all, fields(flow = ?Flow::WebhookJwksRetrieve))]
+pub async fn webhook_jwks_retrieve(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<String>,
+) -> HttpResponse {
+    let flow = Flow::WebhookJwksRetrieve;
+    let merchant_id = path.into_inner();
+
+    api::server_wrap(
+        flow,
+        state.get_ref(),
+        &req,
+        merchant_id,
+        |state, _, merchant_id| webhook_signing_keys::retrieve_webhook_jwks(state, &merchant_id),
+        &auth::NoAuth,
+    )
+    .await
+}
+
+/// Merchant Account - Connector Exclusion Rules Retrieve
+///
+/// Retrieve the rules which exclude cards from being routed to specific connectors
+#[instrument(skip_all, fields(flow = ?Flow::ConnectorExclusionRulesRetrieve))]
+pub async fn connector_exclusion_rules_retrieve(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<String>,
+) -> HttpResponse {
+    let flow = Flow::ConnectorExclusionRulesRetrieve;
+    let merchant_id = path.into_inner();
+
+    api::server_wrap(
+        flow,
+        state.get_ref(),
+        &req,
+        merchant_id,
+        |state, _, req| retrieve_connector_exclusion_rules(&*state.store, req),
+        &auth::AdminApiAuth,
+    )
+    .await
+}
+
+/// Merchant Account - Connector Exclusion Rules Update
+///
+/// Replace the rules which exclude cards from being routed to specific connectors, based on their BIN or issuing country
+#[instrument(skip_all, fields(flow = ?Flow::ConnectorExclusionRulesUpdate))]
+pub async fn connector_exclusion_rules_update(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<String>,
+    json_payload: web::Json<admin::ConnectorExclusionRulesUpdateRequest>,
+) -> HttpResponse {
+    let flow = Flow::ConnectorExclusionRulesUpdate;
+    let merchant_id = path.into_inner();
+
+    api::server_wrap(
+        flow,
+        state.get_ref(),
+        &req,
+        (merchant_id, json_payload.into_inner()),
+        |state, _, (merchant_id, payload)| {
+            update_connector_exclusion_rules(&*state.store, merchant_id, payload)
+        },
+        &auth::AdminApiAuth,
+    )
+    .await
+}
+
+/// Merchant Account - Refund Resolve
+///
+/// Resolve a refund stuck pending or in manual review as succeeded or failed, once its final status
+/// has been confirmed with the connector
+#[instrument(skip_all, fields(flow = ?Flow::RefundsResolve))]
+pub async fn refunds_resolve(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<(String, String)>,
+    json_payload: web::Json<refund_types::RefundResolveRequest>,
+) -> HttpResponse {
+    let flow = Flow::RefundsResolve;
+    let (merchant_id, refund_id) = path.into_inner();
+
+    api::server_wrap(
+        flow,
+        state.get_ref(),
+        &req,
+        (merchant_id, refund_id, json_payload.into_inner()),
+        |state, _, (merchant_id, refund_id, payload)| {
+            refunds::refund_resolve_core(state, merchant_id, refund_id, payload)
+        },
+        &auth::AdminApiAuth,
+    )
+    .await
+}
@@ -0,0 +1,127 @@
+use actix_web::{web, HttpRequest, HttpResponse};
+use api_models::analytics as analytics_models;
+use router_env::{instrument, tracing, Flow};
+
+use super::app::AppState;
+use crate::{
+    core::analytics,
+    services::{api, authentication as auth},
+};
+
+/// Analytics - Retrieve Currency Exposure
+///
+/// Retrieve the gross volumes, refunds and pending payouts of a period per currency, to monitor the exposure of the merchant to each currency
+#[utoipa::path(
+    get,
+    path = "/analytics/currency_exposure",
+    params(
+        ("start_time" = PrimitiveDateTime, Query, description = "The start of the period for which the exposure is summarized"),
+        ("end_time" = Option<PrimitiveDateTime>, Query, description = "The end of the period for which the exposure is summarized"),
+    ),
+    responses(
+        (status = 200, description = "The currency exposure was retrieved successfully", body = CurrencyExposureResponse),
+        (status = 400, description = "Invalid period"),
+        (status = 401, description = "Unauthorized request")
+    ),
+    tag = "Analytics",
+    operation_id = "Retrieve Currency Exposure",
+    security(("api_key" = []))
+)]
+#[instrument(skip_all, fields(flow = ?Flow::CurrencyExposureRetrieve))]
+pub async fn retrieve_currency_exposure(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    payload: web::Query<analytics_models::CurrencyExposureRequest>,
+) -> HttpResponse {
+    let flow = Flow::CurrencyExposureRetrieve;
+    let payload = payload.into_inner();
+    api::server_wrap(
+        flow,
+        state.get_ref(),
+        &req,
+        payload,
+        |state, auth, req| analytics::retrieve_currency_exposure(state, auth.merchant_account, req),
+        auth::auth_type(&auth::ApiKeyAuth, &auth::JWTAuth, req.headers()),
+    )
+    .await
+}
+
+/// Analytics - Retrieve Payments Funnel
+///
+/// Retrieve the number of payments of a period which were created, confirmed, authorized and captured, per period and payment method, to see where payments drop off
+#[utoipa::path(
+    get,
+    path = "/analytics/funnel",
+    params(
+        ("start_time" = PrimitiveDateTime, Query, description = "The start of the period for which the funnel is computed"),
+        ("end_time" = Option<PrimitiveDateTime>, Query, description = "The end of the period for which the funnel is computed"),
+        ("granularity" = Option<FunnelGranularity>, Query, description = "The length of the periods into which the funnel is split"),
+        ("payment_method" = Option<PaymentMethod>, Query, description = "Only include payments made with this payment method"),
+        ("business_country" = Option<CountryAlpha2>, Query, description = "Only include payments of this business country"),
+        ("business_label" = Option<String>, Query, description = "Only include payments of this business label"),
+    ),
+    responses(
+        (status = 200, description = "The payments funnel was retrieved successfully", body = PaymentsFunnelResponse),
+        (status = 400, description = "Invalid period"),
+        (status = 401, description = "Unauthorized request")
+    ),
+    tag = "Analytics",
+    operation_id = "Retrieve Payments Funnel",
+    security(("api_key" = []))
+)]
+#[instrument(skip_all, fields(flow = ?Flow::PaymentsFunnelRetrieve))]
+pub async fn retrieve_payments_funnel(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    payload: web::Query<analytics_models::PaymentsFunnelRequest>,
+) -> HttpResponse {
+    let flow = Flow::PaymentsFunnelRetrieve;
+    let payload = payload.into_inner();
+    api::server_wrap(
+        flow,
+        state.get_ref(),
+        &req,
+        payload,
+        |state, auth, req| analytics::retrieve_payments_funnel(state, auth.merchant_account, req),
+        auth::auth_type(&auth::ApiKeyAuth, &auth::JWTAuth, req.headers()),
+    )
+    .await
+}
+
+/// Analytics - Retrieve Payout Analytics
+///
+/// Retrieve the volumes, success rates and average fulfillment latencies of the payouts of a period per connector, currency and payout type, along with the reasons for which payouts failed
+#[utoipa::path(
+    get,
+    path = "/analytics/payouts",
+    params(
+        ("start_time" = PrimitiveDateTime, Query, description = "The start of the period for which the payouts are aggregated"),
+        ("end_time" = Option<PrimitiveDateTime>, Query, description = "The end of the period for which the payouts are aggregated"),
+    ),
+    responses(
+        (status = 200, description = "The payout analytics were retrieved successfully", body = PayoutAnalyticsResponse),
+        (status = 400, description = "Invalid period"),
+        (status = 401, description = "Unauthorized request")
+    ),
+    tag = "Analytics",
+    operation_id = "Retrieve Payout Analytics",
+    security(("api_key" = []))
+)]
+#[instrument(skip_all, fields(flow = ?Flow::PayoutAnalyticsRetrieve))]
+pub async fn retrieve_payout_analytics(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    payload: web::Query<analytics_models::PayoutAnalyticsRequest>,
+) -> HttpResponse {
+    let flow = Flow::PayoutAnalyticsRetrieve;
+    let payload = payload.into_inner();
+    api::server_wrap(
+        flow,
+        state.get_ref(),
+        &req,
+        payload,
+        |state, auth, req| analytics::retrieve_payout_analytics(state, auth.merchant_account, req),
+        auth::auth_type(&auth::ApiKeyAuth, &auth::JWTAuth, req.headers()),
+    )
+    .await
+}
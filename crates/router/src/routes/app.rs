@@ -1,6 +1,10 @@
+use std::sync::Arc;
+
 use actix_web::{web, Scope};
 #[cfg(feature = "email")]
 use external_services::email::{AwsSes, EmailClient};
+#[cfg(feature = "file_scanning")]
+use external_services::file_scanning::FileScanner;
 #[cfg(feature = "kms")]
 use external_services::kms::{self, decrypt::KmsDecrypt};
 use tokio::sync::oneshot;
@@ -10,17 +14,25 @@ use super::dummy_connector::*;
 #[cfg(feature = "payouts")]
 use super::payouts::*;
 #[cfg(feature = "olap")]
-use super::{admin::*, api_keys::*, disputes::*, files::*};
-use super::{cache::*, health::*};
+use super::{
+    accounting::*, admin::*, analytics::*, api_keys::*, audit::*, disputes::*, feature_flags::*,
+    files::*, lookup::*, reports::*, routing::*, subscriptions::*, terminals::*,
+};
+use super::{cache::*, health::*, runtime_config::*};
 #[cfg(any(feature = "olap", feature = "oltp"))]
-use super::{configs::*, customers::*, mandates::*, payments::*, refunds::*};
+use super::{
+    configs::*, customers::*, invoices::*, mandates::*, payment_links::*, payments::*, refunds::*,
+};
 #[cfg(feature = "oltp")]
-use super::{ephemeral_key::*, payment_methods::*, webhooks::*};
+use super::{ephemeral_key::*, gift_cards::*, payment_methods::*, webhooks::*};
 use crate::{
-    configs::settings,
+    configs::{runtime, settings},
+    connection,
+    core::{event_bus::EventBus, hooks::HookRegistry},
     db::{MockDb, StorageImpl, StorageInterface},
+    middleware::LoadShedder,
     routes::cards_info::card_iin_info,
-    services::Store,
+    services::{api::rate_limiter::ConnectorRateLimiter, Store},
 };
 
 #[derive(Clone)]
@@ -30,8 +42,15 @@ pub struct AppState {
     pub conf: settings::Settings,
     #[cfg(feature = "email")]
     pub email_client: Box<dyn EmailClient>,
+    #[cfg(feature = "file_scanning")]
+    pub file_scanner: Option<Box<dyn FileScanner>>,
     #[cfg(feature = "kms")]
     pub kms_secrets: settings::ActiveKmsSecrets,
+    pub runtime_conf: Arc<runtime::RuntimeConfig>,
+    pub load_shedder: Arc<LoadShedder>,
+    pub connector_rate_limiter: Arc<ConnectorRateLimiter>,
+    pub hooks: Arc<HookRegistry>,
+    pub event_bus: Arc<EventBus>,
 }
 
 pub trait AppStateInfo {
@@ -86,20 +105,48 @@ impl AppState {
         #[cfg(feature = "email")]
         #[allow(clippy::expect_used)]
         let email_client = Box::new(AwsSes::new(&conf.email).await);
+
+        #[cfg(feature = "file_scanning")]
+        let file_scanner = conf.file_scanning.get_file_scanner();
+        let runtime_conf = Arc::new(runtime::RuntimeConfig::new(&conf));
+        let load_shedder = Arc::new(LoadShedder::new(runtime_conf.clone()));
         Self {
             flow_name: String::from("default"),
             store,
             conf,
             #[cfg(feature = "email")]
             email_client,
+            #[cfg(feature = "file_scanning")]
+            file_scanner,
             #[cfg(feature = "kms")]
             kms_secrets,
+            runtime_conf,
+            load_shedder,
+            connector_rate_limiter: Arc::new(ConnectorRateLimiter::default()),
+            hooks: Arc::new(HookRegistry::default()),
+            event_bus: Arc::new(EventBus::default()),
         }
     }
 
+    /// Replaces the hooks invoked at the extension points of the payment and refund flows.
+    pub fn with_hooks(mut self, hooks: HookRegistry) -> Self {
+        self.hooks = Arc::new(hooks);
+        self
+    }
+
     pub async fn new(conf: settings::Settings, shut_down_signal: oneshot::Sender<()>) -> Self {
         Self::with_storage(conf, StorageImpl::Postgresql, shut_down_signal).await
     }
+
+    /// Returns the locker in which the payment methods of the current merchant are stored, being
+    /// the locker of the storage region of the merchant if the merchant is pinned to a region.
+    pub fn get_locker(&self) -> &settings::Locker {
+        connection::current_storage_region()
+            .and_then(|storage_region| self.conf.data_residency.regions.get(&storage_region))
+            .map_or(&self.conf.locker, |regional_storage| {
+                &regional_storage.locker
+            })
+    }
 }
 
 pub struct Health;
@@ -180,6 +227,16 @@ impl Payments {
                     web::resource("/sync")
                         .route(web::post().to(payments_retrieve_with_gateway_creds)),
                 )
+                .service(
+                    web::resource("/velocity_limits")
+                        .route(web::get().to(payments_velocity_limits_retrieve))
+                        .route(web::post().to(payments_velocity_limits_update)),
+                )
+                .service(
+                    web::resource("/cross_border_fees")
+                        .route(web::get().to(payments_cross_border_fee_config_retrieve))
+                        .route(web::post().to(payments_cross_border_fee_config_update)),
+                )
                 .service(
                     web::resource("/{payment_id}")
                         .route(web::get().to(payments_retrieve))
@@ -188,12 +245,40 @@ impl Payments {
                 .service(
                     web::resource("/{payment_id}/confirm").route(web::post().to(payments_confirm)),
                 )
+                .service(
+                    web::resource("/{payment_id}/retry").route(web::post().to(payments_retry)),
+                )
+                .service(
+                    web::resource("/{payment_id}/session_tokens/refresh")
+                        .route(web::post().to(payments_session_tokens_refresh)),
+                )
+                .service(
+                    web::resource("/{payment_id}/metadata")
+                        .route(web::patch().to(payments_metadata_update)),
+                )
+                .service(
+                    web::resource("/{payment_id}/metadata/audit")
+                        .route(web::get().to(payments_metadata_audit_list)),
+                )
+                .service(
+                    web::resource("/{payment_id}/receipt")
+                        .route(web::get().to(payments_receipt_retrieve)),
+                )
                 .service(
                     web::resource("/{payment_id}/cancel").route(web::post().to(payments_cancel)),
                 )
                 .service(
                     web::resource("/{payment_id}/capture").route(web::post().to(payments_capture)),
                 )
+                .service(
+                    web::resource("/{payment_id}/incremental_authorization")
+                        .route(web::post().to(payments_incremental_authorization)),
+                )
+                .service(
+                    web::resource("/{payment_id}/{merchant_id}/checkout")
+                        .route(web::get().to(payments_hosted_checkout))
+                        .route(web::post().to(payments_hosted_checkout_submit)),
+                )
                 .service(
                     web::resource("/redirect/{payment_id}/{merchant_id}/{attempt_id}")
                         .route(web::get().to(payments_start)),
@@ -238,6 +323,28 @@ impl Customers {
         {
             route = route
                 .service(web::resource("").route(web::post().to(customers_create)))
+                // The batch import routes are registered before the customer routes, as their
+                // paths would otherwise be matched as customer IDs
+                .service(
+                    web::resource("/batch")
+                        .app_data(
+                            web::JsonConfig::default()
+                                .limit(crate::consts::CUSTOMER_IMPORT_REQUEST_BODY_LIMIT)
+                                .content_type_required(true)
+                                .error_handler(
+                                    crate::utils::error_parser::custom_json_error_handler,
+                                ),
+                        )
+                        .route(web::post().to(customers_import)),
+                )
+                .service(
+                    web::resource("/batch/{job_id}")
+                        .route(web::get().to(customers_import_retrieve)),
+                )
+                .service(
+                    web::resource("/batch/{job_id}/resume")
+                        .route(web::post().to(customers_import_resume)),
+                )
                 .service(
                     web::resource("/payment_methods")
                         .route(web::get().to(list_customer_payment_method_api_client)),
@@ -246,6 +353,24 @@ impl Customers {
                     web::resource("/{customer_id}/payment_methods")
                         .route(web::get().to(list_customer_payment_method_api)),
                 )
+                .service(
+                    web::resource("/{customer_id}/payment_methods/{payment_method_id}/default")
+                        .route(web::post().to(customer_payment_methods_set_default)),
+                )
+                .service(
+                    web::resource("/{customer_id}/addresses")
+                        .route(web::get().to(customer_addresses_list))
+                        .route(web::post().to(customer_addresses_create)),
+                )
+                .service(
+                    web::resource("/{customer_id}/addresses/{address_id}")
+                        .route(web::post().to(customer_addresses_update))
+                        .route(web::delete().to(customer_addresses_delete)),
+                )
+                .service(
+                    web::resource("/{customer_id}/addresses/{address_id}/default")
+                        .route(web::post().to(customer_addresses_set_default)),
+                )
                 .service(
                     web::resource("/{customer_id}")
                         .route(web::get().to(customers_retrieve))
@@ -275,11 +400,18 @@ impl Refunds {
             route = route
                 .service(web::resource("").route(web::post().to(refunds_create)))
                 .service(web::resource("/sync").route(web::post().to(refunds_retrieve_with_body)))
+                .service(
+                    web::resource("/reference/{merchant_refund_reference}")
+                        .route(web::get().to(refunds_retrieve_by_merchant_refund_reference)),
+                )
                 .service(
                     web::resource("/{id}")
                         .route(web::get().to(refunds_retrieve))
                         .route(web::post().to(refunds_update)),
-                );
+                )
+                .service(web::resource("/{id}/cancel").route(web::post().to(refunds_cancel)))
+                .service(web::resource("/{id}/approve").route(web::post().to(refunds_approve)))
+                .service(web::resource("/{id}/reject").route(web::post().to(refunds_reject)));
         }
         route
     }
@@ -294,8 +426,13 @@ impl Payouts {
         let route = web::scope("/payouts").app_data(web::Data::new(state));
         route
             .service(web::resource("/create").route(web::post().to(payouts_create)))
+            .service(web::resource("/batch").route(web::post().to(payouts_batch_create)))
+            .service(
+                web::resource("/batch/{batch_id}").route(web::get().to(payouts_batch_retrieve)),
+            )
             .service(web::resource("/{payout_id}/cancel").route(web::post().to(payouts_cancel)))
             .service(web::resource("/{payout_id}/fulfill").route(web::post().to(payouts_fulfill)))
+            .service(web::resource("/{payout_id}/sync").route(web::post().to(payouts_sync)))
             .service(
                 web::resource("/{payout_id}")
                     .route(web::get().to(payouts_retrieve))
@@ -316,12 +453,25 @@ impl PaymentMethods {
                     .route(web::post().to(create_payment_method_api))
                     .route(web::get().to(list_payment_method_api)), // TODO : added for sdk compatibility for now, need to deprecate this later
             )
+            .service(
+                web::resource("/shares/{share_id}")
+                    .route(web::get().to(payment_method_share_retrieve_api)),
+            )
+            .service(
+                web::resource("/shares/{share_id}/revoke")
+                    .route(web::post().to(payment_method_share_revoke_api)),
+            )
             .service(
                 web::resource("/{payment_method_id}")
                     .route(web::get().to(payment_method_retrieve_api))
                     .route(web::post().to(payment_method_update_api))
                     .route(web::delete().to(payment_method_delete_api)),
             )
+            .service(
+                web::resource("/{payment_method_id}/shares")
+                    .route(web::post().to(payment_method_share_api))
+                    .route(web::get().to(payment_method_shares_list_api)),
+            )
     }
 }
 
@@ -338,6 +488,49 @@ impl MerchantAccount {
                     .route(web::post().to(merchant_account_toggle_kv))
                     .route(web::get().to(merchant_account_kv_status)),
             )
+            .service(
+                web::resource("/{id}/key_store").route(web::get().to(merchant_key_store_retrieve)),
+            )
+            .service(
+                web::resource("/{id}/key_store/rotate")
+                    .route(web::post().to(merchant_key_store_rotate)),
+            )
+            .service(
+                web::resource("/{id}/allowed_domains")
+                    .route(web::get().to(merchant_allowed_domains_retrieve))
+                    .route(web::post().to(merchant_allowed_domains_update)),
+            )
+            .service(
+                web::resource("/{id}/connector_exclusion_rules")
+                    .route(web::get().to(connector_exclusion_rules_retrieve))
+                    .route(web::post().to(connector_exclusion_rules_update)),
+            )
+            .service(
+                web::resource("/{id}/commissions/statement")
+                    .route(web::get().to(merchant_commission_statement_retrieve)),
+            )
+            .service(
+                web::resource("/{id}/webhook_endpoint_health")
+                    .route(web::get().to(webhook_endpoint_health_retrieve)),
+            )
+            .service(
+                web::resource("/{id}/webhook_endpoint_health/verify")
+                    .route(web::post().to(webhook_endpoint_verify)),
+            )
+            .service(
+                web::resource("/{id}/webhook_signing_keys")
+                    .route(web::get().to(webhook_signing_keys_retrieve))
+                    .route(web::post().to(webhook_signing_key_rotate))
+                    .route(web::delete().to(webhook_signing_keys_delete)),
+            )
+            .service(
+                web::resource("/{id}/webhook_signing_keys/jwks")
+                    .route(web::get().to(webhook_jwks_retrieve)),
+            )
+            .service(
+                web::resource("/{id}/refunds/{refund_id}/resolve")
+                    .route(web::post().to(refunds_resolve)),
+            )
             .service(
                 web::resource("/{id}")
                     .route(web::get().to(retrieve_merchant_account))
@@ -369,6 +562,22 @@ impl MerchantConnectorAccount {
                         .route(web::get().to(payment_connector_retrieve))
                         .route(web::post().to(payment_connector_update))
                         .route(web::delete().to(payment_connector_delete)),
+                )
+                .service(
+                    web::resource(
+                        "/{merchant_id}/connectors/{merchant_connector_id}/payment_methods/toggle",
+                    )
+                    .route(web::post().to(payment_connector_toggle_payment_method_type)),
+                )
+                .service(
+                    web::resource("/{merchant_id}/connectors/{merchant_connector_id}/verify")
+                        .route(web::post().to(payment_connector_verify)),
+                )
+                .service(
+                    web::resource(
+                        "/{merchant_id}/connectors/{merchant_connector_id}/webhook_secret/rotate",
+                    )
+                    .route(web::post().to(payment_connector_webhook_secret_rotate)),
                 );
         }
         #[cfg(feature = "oltp")]
@@ -424,6 +633,15 @@ impl Webhooks {
 
         web::scope("/webhooks")
             .app_data(web::Data::new(config))
+            .service(
+                web::resource("/egress_ip_ranges")
+                    .route(web::get().to(webhook_egress_ip_ranges_retrieve))
+                    .route(web::post().to(webhook_egress_ip_ranges_update)),
+            )
+            .service(
+                web::resource("/{event_id}/attempts")
+                    .route(web::get().to(webhook_delivery_attempts_list)),
+            )
             .service(
                 web::resource("/{merchant_id}/{connector_name}")
                     .route(
@@ -479,6 +697,12 @@ impl Disputes {
         web::scope("/disputes")
             .app_data(web::Data::new(state))
             .service(web::resource("/list").route(web::get().to(retrieve_disputes_list)))
+            .service(web::resource("/reason_codes").route(web::get().to(list_dispute_reason_codes)))
+            .service(
+                web::resource("/auto_defend_rules")
+                    .route(web::get().to(retrieve_dispute_auto_defend_rules))
+                    .route(web::post().to(update_dispute_auto_defend_rules)),
+            )
             .service(web::resource("/accept/{dispute_id}").route(web::post().to(accept_dispute)))
             .service(
                 web::resource("/evidence")
@@ -489,6 +713,10 @@ impl Disputes {
                 web::resource("/evidence/{dispute_id}")
                     .route(web::get().to(retrieve_dispute_evidence)),
             )
+            .service(
+                web::resource("/{dispute_id}/financials")
+                    .route(web::get().to(retrieve_dispute_financials)),
+            )
             .service(web::resource("/{dispute_id}").route(web::get().to(retrieve_dispute)))
     }
 }
@@ -519,6 +747,246 @@ impl Files {
     }
 }
 
+pub struct Terminals;
+
+#[cfg(feature = "olap")]
+impl Terminals {
+    pub fn server(state: AppState) -> Scope {
+        web::scope("/terminals")
+            .app_data(web::Data::new(state))
+            .service(web::resource("").route(web::post().to(terminals_create)))
+            .service(web::resource("/list").route(web::get().to(terminals_list)))
+            .service(
+                web::resource("/{terminal_id}")
+                    .route(web::get().to(terminals_retrieve))
+                    .route(web::post().to(terminals_update))
+                    .route(web::delete().to(terminals_delete)),
+            )
+    }
+}
+
+pub struct Accounting;
+
+#[cfg(feature = "olap")]
+impl Accounting {
+    pub fn server(state: AppState) -> Scope {
+        web::scope("/accounting")
+            .app_data(web::Data::new(state))
+            .service(web::resource("/journal").route(web::get().to(export_journal)))
+            .service(web::resource("/ledger/entries").route(web::get().to(list_ledger_entries)))
+            .service(
+                web::resource("/ledger/balances").route(web::get().to(retrieve_ledger_balances)),
+            )
+            .service(web::resource("/ledger/reconcile").route(web::post().to(reconcile_ledger)))
+    }
+}
+
+pub struct Audit;
+
+#[cfg(feature = "olap")]
+impl Audit {
+    pub fn server(state: AppState) -> Scope {
+        web::scope("/audit")
+            .app_data(web::Data::new(state))
+            .service(web::resource("/events").route(web::get().to(list_audit_events)))
+    }
+}
+
+pub struct Analytics;
+
+#[cfg(feature = "olap")]
+impl Analytics {
+    pub fn server(state: AppState) -> Scope {
+        web::scope("/analytics")
+            .app_data(web::Data::new(state))
+            .service(
+                web::resource("/currency_exposure")
+                    .route(web::get().to(retrieve_currency_exposure)),
+            )
+            .service(web::resource("/funnel").route(web::get().to(retrieve_payments_funnel)))
+            .service(web::resource("/payouts").route(web::get().to(retrieve_payout_analytics)))
+    }
+}
+
+pub struct Reports;
+
+#[cfg(feature = "olap")]
+impl Reports {
+    pub fn server(state: AppState) -> Scope {
+        web::scope("/reports")
+            .app_data(web::Data::new(state))
+            .service(web::resource("/schedules").route(web::post().to(report_schedules_create)))
+            .service(web::resource("/schedules/list").route(web::get().to(report_schedules_list)))
+            .service(
+                web::resource("/schedules/{report_schedule_id}")
+                    .route(web::get().to(report_schedules_retrieve))
+                    .route(web::post().to(report_schedules_update))
+                    .route(web::delete().to(report_schedules_delete)),
+            )
+    }
+}
+
+pub struct Routing;
+
+#[cfg(feature = "olap")]
+impl Routing {
+    pub fn server(state: AppState) -> Scope {
+        web::scope("/routing")
+            .app_data(web::Data::new(state))
+            .service(
+                web::resource("/fee_schedules")
+                    .route(web::get().to(routing_fee_schedules_retrieve))
+                    .route(web::post().to(routing_fee_schedules_update)),
+            )
+            .service(web::resource("/simulate").route(web::post().to(routing_simulate)))
+    }
+}
+
+pub struct Subscriptions;
+
+#[cfg(feature = "olap")]
+impl Subscriptions {
+    pub fn server(state: AppState) -> Scope {
+        web::scope("/subscriptions")
+            .app_data(web::Data::new(state))
+            .service(web::resource("").route(web::post().to(subscriptions_create)))
+            .service(web::resource("/list").route(web::get().to(subscriptions_list)))
+            .service(
+                web::resource("/dunning_config")
+                    .route(web::get().to(subscriptions_dunning_config_retrieve))
+                    .route(web::post().to(subscriptions_dunning_config_update)),
+            )
+            .service(
+                web::resource("/{subscription_id}")
+                    .route(web::get().to(subscriptions_retrieve))
+                    .route(web::post().to(subscriptions_update)),
+            )
+            .service(
+                web::resource("/{subscription_id}/cancel")
+                    .route(web::post().to(subscriptions_cancel)),
+            )
+            .service(
+                web::resource("/{subscription_id}/pause")
+                    .route(web::post().to(subscriptions_pause)),
+            )
+            .service(
+                web::resource("/{subscription_id}/resume")
+                    .route(web::post().to(subscriptions_resume)),
+            )
+    }
+}
+
+pub struct Lookup;
+
+#[cfg(feature = "olap")]
+impl Lookup {
+    pub fn server(state: AppState) -> Scope {
+        web::scope("/lookup")
+            .app_data(web::Data::new(state))
+            .service(web::resource("").route(web::get().to(lookup_by_connector_reference)))
+    }
+}
+
+pub struct FeatureFlags;
+
+#[cfg(feature = "olap")]
+impl FeatureFlags {
+    pub fn server(state: AppState) -> Scope {
+        web::scope("/feature_flags")
+            .app_data(web::Data::new(state))
+            .service(web::resource("").route(web::get().to(list_feature_flags)))
+            .service(
+                web::resource("/{flag}")
+                    .route(web::get().to(retrieve_feature_flag))
+                    .route(web::post().to(update_feature_flag)),
+            )
+            .service(
+                web::resource("/{flag}/merchants/{merchant_id}")
+                    .route(web::put().to(update_merchant_feature_flag_override))
+                    .route(web::delete().to(delete_merchant_feature_flag_override)),
+            )
+    }
+}
+
+pub struct Invoices;
+
+#[cfg(any(feature = "olap", feature = "oltp"))]
+impl Invoices {
+    pub fn server(state: AppState) -> Scope {
+        let mut route = web::scope("/invoices").app_data(web::Data::new(state));
+
+        #[cfg(feature = "olap")]
+        {
+            route = route
+                .service(web::resource("").route(web::post().to(invoices_create)))
+                .service(web::resource("/list").route(web::get().to(invoices_list)))
+                .service(web::resource("/{invoice_id}").route(web::get().to(invoices_retrieve)))
+                .service(web::resource("/{invoice_id}/void").route(web::post().to(invoices_void)));
+        }
+
+        #[cfg(feature = "oltp")]
+        {
+            route = route.service(
+                web::resource("/{merchant_id}/{invoice_id}/pay")
+                    .route(web::get().to(invoices_payment_link_retrieve))
+                    .route(web::post().to(invoices_pay)),
+            );
+        }
+        route
+    }
+}
+
+pub struct PaymentLinks;
+
+#[cfg(any(feature = "olap", feature = "oltp"))]
+impl PaymentLinks {
+    pub fn server(state: AppState) -> Scope {
+        let mut route = web::scope("/payment_links").app_data(web::Data::new(state));
+
+        #[cfg(feature = "olap")]
+        {
+            route = route
+                .service(web::resource("").route(web::post().to(payment_links_create)))
+                .service(
+                    web::resource("/{payment_link_id}")
+                        .route(web::get().to(payment_links_retrieve)),
+                );
+        }
+
+        #[cfg(feature = "oltp")]
+        {
+            route = route.service(
+                web::resource("/{merchant_id}/{payment_link_id}")
+                    .route(web::get().to(payment_links_render)),
+            );
+        }
+        route
+    }
+}
+
+pub struct GiftCards;
+
+#[cfg(feature = "oltp")]
+impl GiftCards {
+    pub fn server(state: AppState) -> Scope {
+        web::scope("/gift_cards")
+            .app_data(web::Data::new(state))
+            .service(web::resource("").route(web::post().to(gift_cards_create)))
+            .service(web::resource("/{gift_card_id}").route(web::get().to(gift_cards_retrieve)))
+            .service(
+                web::resource("/{gift_card_id}/redeem").route(web::post().to(gift_cards_redeem)),
+            )
+            .service(
+                web::resource("/{gift_card_id}/reload").route(web::post().to(gift_cards_reload)),
+            )
+            .service(web::resource("/{gift_card_id}/void").route(web::post().to(gift_cards_void)))
+            .service(
+                web::resource("/{gift_card_id}/transactions")
+                    .route(web::get().to(gift_cards_transactions_list)),
+            )
+    }
+}
+
 pub struct Cache;
 
 impl Cache {
@@ -528,3 +996,13 @@ impl Cache {
             .service(web::resource("/invalidate/{key}").route(web::post().to(invalidate)))
     }
 }
+
+pub struct Runtime;
+
+impl Runtime {
+    pub fn server(state: AppState) -> Scope {
+        web::scope("/runtime_config")
+            .app_data(web::Data::new(state))
+            .service(web::resource("/reload").route(web::post().to(reload_runtime_config)))
+    }
+}
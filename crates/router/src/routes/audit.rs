@@ -0,0 +1,52 @@
+use actix_web::{web, HttpRequest, HttpResponse};
+use api_models::audit as audit_models;
+use router_env::{instrument, tracing, Flow};
+
+use super::app::AppState;
+use crate::{
+    core::audit,
+    services::{api, authentication as auth},
+};
+
+/// Audit - List Events
+///
+/// List the changes made to the merchant account, its connector accounts, routing configuration,
+/// API keys and webhook settings, most recent first
+#[utoipa::path(
+    get,
+    path = "/audit/events",
+    params(
+        ("entity_type" = Option<AuditEntityType>, Query, description = "Only list the changes made to this type of entity"),
+        ("entity_id" = Option<String>, Query, description = "Only list the changes made to the entity with this identifier"),
+        ("action" = Option<AuditAction>, Query, description = "Only list the changes of this kind"),
+        ("start_time" = Option<PrimitiveDateTime>, Query, description = "Only list the changes made at or after this time"),
+        ("end_time" = Option<PrimitiveDateTime>, Query, description = "Only list the changes made at or before this time"),
+        ("limit" = Option<i64>, Query, description = "The maximum number of events to include in the response"),
+    ),
+    responses(
+        (status = 200, description = "The audit events were retrieved successfully", body = AuditEventListResponse),
+        (status = 400, description = "Invalid constraints"),
+        (status = 401, description = "Unauthorized request")
+    ),
+    tag = "Audit",
+    operation_id = "List Audit Events",
+    security(("api_key" = []))
+)]
+#[instrument(skip_all, fields(flow = ?Flow::AuditEventsList))]
+pub async fn list_audit_events(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    payload: web::Query<audit_models::AuditEventListConstraints>,
+) -> HttpResponse {
+    let flow = Flow::AuditEventsList;
+    let payload = payload.into_inner();
+    api::server_wrap(
+        flow,
+        state.get_ref(),
+        &req,
+        payload,
+        |state, auth, req| audit::list_audit_events(state, auth.merchant_account, req),
+        auth::auth_type(&auth::ApiKeyAuth, &auth::JWTAuth, req.headers()),
+    )
+    .await
+}
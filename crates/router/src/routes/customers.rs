@@ -165,6 +165,125 @@ pub async fn customers_delete(
     .await
 }
 
+/// Customers - Batch Import
+///
+/// Import a batch of customers, optionally along with their payment methods saved at a previous processor. The customers are created asynchronously, and the progress of the import can be followed with the returned import ID.
+#[utoipa::path(
+    post,
+    path = "/customers/batch",
+    request_body = CustomerImportRequest,
+    responses(
+        (status = 200, description = "Customer import created", body = CustomerImportResponse),
+        (status = 400, description = "Invalid data")
+    ),
+    tag = "Customers",
+    operation_id = "Import Customers",
+    security(("api_key" = []))
+)]
+#[instrument(skip_all, fields(flow = ?Flow::CustomersImport))]
+pub async fn customers_import(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    json_payload: web::Json<customers::CustomerImportRequest>,
+) -> HttpResponse {
+    let flow = Flow::CustomersImport;
+    api::server_wrap(
+        flow,
+        state.get_ref(),
+        &req,
+        json_payload.into_inner(),
+        |state, auth, req| {
+            import::create_customer_import(
+                &*state.store,
+                auth.merchant_account,
+                auth.key_store,
+                req,
+            )
+        },
+        &auth::ApiKeyAuth,
+    )
+    .await
+}
+
+/// Customers - Retrieve Batch Import
+///
+/// Retrieve the status of a batch import of customers, along with the errors of the customers which could not be created.
+#[utoipa::path(
+    get,
+    path = "/customers/batch/{job_id}",
+    params (("job_id" = String, Path, description = "The identifier of the customer import")),
+    responses(
+        (status = 200, description = "Customer import retrieved", body = CustomerImportResponse),
+        (status = 404, description = "Customer import was not found")
+    ),
+    tag = "Customers",
+    operation_id = "Retrieve a Customer Import",
+    security(("api_key" = []))
+)]
+#[instrument(skip_all, fields(flow = ?Flow::CustomersImportRetrieve))]
+pub async fn customers_import_retrieve(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<String>,
+) -> HttpResponse {
+    let flow = Flow::CustomersImportRetrieve;
+    let payload = customers::CustomerImportJobId {
+        job_id: path.into_inner(),
+    };
+
+    api::server_wrap(
+        flow,
+        state.get_ref(),
+        &req,
+        payload,
+        |state, auth, req| {
+            import::retrieve_customer_import(&*state.store, auth.merchant_account, req)
+        },
+        &auth::ApiKeyAuth,
+    )
+    .await
+}
+
+/// Customers - Resume Batch Import
+///
+/// Resume a failed batch import of customers from the last batch of customers which was created.
+#[utoipa::path(
+    post,
+    path = "/customers/batch/{job_id}/resume",
+    params (("job_id" = String, Path, description = "The identifier of the customer import")),
+    responses(
+        (status = 200, description = "Customer import resumed", body = CustomerImportResponse),
+        (status = 404, description = "Customer import was not found"),
+        (status = 412, description = "Customer import has not failed")
+    ),
+    tag = "Customers",
+    operation_id = "Resume a Customer Import",
+    security(("api_key" = []))
+)]
+#[instrument(skip_all, fields(flow = ?Flow::CustomersImportResume))]
+pub async fn customers_import_resume(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<String>,
+) -> HttpResponse {
+    let flow = Flow::CustomersImportResume;
+    let payload = customers::CustomerImportJobId {
+        job_id: path.into_inner(),
+    };
+
+    api::server_wrap(
+        flow,
+        state.get_ref(),
+        &req,
+        payload,
+        |state, auth, req| {
+            import::resume_customer_import(&*state.store, auth.merchant_account, req)
+        },
+        &auth::ApiKeyAuth,
+    )
+    .await
+}
+
 #[instrument(skip_all, fields(flow = ?Flow::CustomersGetMandates))]
 pub async fn get_customer_mandates(
     state: web::Data<AppState>,
@@ -188,3 +307,280 @@ pub async fn get_customer_mandates(
     )
     .await
 }
+
+/// Customer Addresses - Create
+///
+/// Add an address to the address book of a customer.
+#[utoipa::path(
+    post,
+    path = "/customers/{customer_id}/addresses",
+    request_body = CustomerAddressRequest,
+    params (("customer_id" = String, Path, description = "The unique identifier for the Customer")),
+    responses(
+        (status = 200, description = "Address Created", body = CustomerAddressResponse),
+        (status = 404, description = "Customer was not found")
+    ),
+    tag = "Customers",
+    operation_id = "Create a Customer Address",
+    security(("api_key" = []))
+)]
+#[instrument(skip_all, fields(flow = ?Flow::CustomerAddressesCreate))]
+pub async fn customer_addresses_create(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<String>,
+    json_payload: web::Json<customers::CustomerAddressRequest>,
+) -> HttpResponse {
+    let flow = Flow::CustomerAddressesCreate;
+    let customer_id = path.into_inner();
+    api::server_wrap(
+        flow,
+        state.get_ref(),
+        &req,
+        json_payload.into_inner(),
+        |state, auth, req| {
+            create_customer_address(
+                &*state.store,
+                auth.merchant_account,
+                auth.key_store,
+                customer_id.clone(),
+                req,
+            )
+        },
+        &auth::ApiKeyAuth,
+    )
+    .await
+}
+
+/// Customer Addresses - List
+///
+/// List the addresses in the address book of a customer.
+#[utoipa::path(
+    get,
+    path = "/customers/{customer_id}/addresses",
+    params (("customer_id" = String, Path, description = "The unique identifier for the Customer")),
+    responses(
+        (status = 200, description = "Addresses Retrieved", body = Vec<CustomerAddressResponse>),
+        (status = 404, description = "Customer was not found")
+    ),
+    tag = "Customers",
+    operation_id = "List Customer Addresses",
+    security(("api_key" = []))
+)]
+#[instrument(skip_all, fields(flow = ?Flow::CustomerAddressesList))]
+pub async fn customer_addresses_list(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<String>,
+) -> HttpResponse {
+    let flow = Flow::CustomerAddressesList;
+    let payload = customers::CustomerId {
+        customer_id: path.into_inner(),
+    };
+    api::server_wrap(
+        flow,
+        state.get_ref(),
+        &req,
+        payload,
+        |state, auth, req| {
+            list_customer_addresses(&*state.store, auth.merchant_account, auth.key_store, req)
+        },
+        &auth::ApiKeyAuth,
+    )
+    .await
+}
+
+/// Customer Addresses - Update
+///
+/// Replace an address in the address book of a customer.
+#[utoipa::path(
+    post,
+    path = "/customers/{customer_id}/addresses/{address_id}",
+    request_body = CustomerAddressRequest,
+    params (
+        ("customer_id" = String, Path, description = "The unique identifier for the Customer"),
+        ("address_id" = String, Path, description = "The unique identifier for the Address")
+    ),
+    responses(
+        (status = 200, description = "Address was Updated", body = CustomerAddressResponse),
+        (status = 404, description = "Address was not found")
+    ),
+    tag = "Customers",
+    operation_id = "Update a Customer Address",
+    security(("api_key" = []))
+)]
+#[instrument(skip_all, fields(flow = ?Flow::CustomerAddressesUpdate))]
+pub async fn customer_addresses_update(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<(String, String)>,
+    json_payload: web::Json<customers::CustomerAddressRequest>,
+) -> HttpResponse {
+    let flow = Flow::CustomerAddressesUpdate;
+    let (customer_id, address_id) = path.into_inner();
+    let address_id = customers::CustomerAddressId {
+        customer_id,
+        address_id,
+    };
+    api::server_wrap(
+        flow,
+        state.get_ref(),
+        &req,
+        json_payload.into_inner(),
+        |state, auth, req| {
+            update_customer_address(
+                &*state.store,
+                auth.merchant_account,
+                auth.key_store,
+                address_id.clone(),
+                req,
+            )
+        },
+        &auth::ApiKeyAuth,
+    )
+    .await
+}
+
+/// Customer Addresses - Delete
+///
+/// Delete an address from the address book of a customer.
+#[utoipa::path(
+    delete,
+    path = "/customers/{customer_id}/addresses/{address_id}",
+    params (
+        ("customer_id" = String, Path, description = "The unique identifier for the Customer"),
+        ("address_id" = String, Path, description = "The unique identifier for the Address")
+    ),
+    responses(
+        (status = 200, description = "Address was Deleted", body = CustomerAddressDeleteResponse),
+        (status = 404, description = "Address was not found")
+    ),
+    tag = "Customers",
+    operation_id = "Delete a Customer Address",
+    security(("api_key" = []))
+)]
+#[instrument(skip_all, fields(flow = ?Flow::CustomerAddressesDelete))]
+pub async fn customer_addresses_delete(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<(String, String)>,
+) -> HttpResponse {
+    let flow = Flow::CustomerAddressesDelete;
+    let (customer_id, address_id) = path.into_inner();
+    let payload = customers::CustomerAddressId {
+        customer_id,
+        address_id,
+    };
+    api::server_wrap(
+        flow,
+        state.get_ref(),
+        &req,
+        payload,
+        |state, auth, req| {
+            delete_customer_address(&*state.store, auth.merchant_account, auth.key_store, req)
+        },
+        &auth::ApiKeyAuth,
+    )
+    .await
+}
+
+/// Customer Addresses - Set Default
+///
+/// Set an address in the address book of a customer as the default shipping or billing address,
+/// which is used for payments of the customer created without the address.
+#[utoipa::path(
+    post,
+    path = "/customers/{customer_id}/addresses/{address_id}/default",
+    request_body = CustomerDefaultAddressRequest,
+    params (
+        ("customer_id" = String, Path, description = "The unique identifier for the Customer"),
+        ("address_id" = String, Path, description = "The unique identifier for the Address")
+    ),
+    responses(
+        (status = 200, description = "Default Address was Set", body = CustomerAddressResponse),
+        (status = 404, description = "Address was not found")
+    ),
+    tag = "Customers",
+    operation_id = "Set the Default Customer Address",
+    security(("api_key" = []))
+)]
+#[instrument(skip_all, fields(flow = ?Flow::CustomerAddressesSetDefault))]
+pub async fn customer_addresses_set_default(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<(String, String)>,
+    json_payload: web::Json<customers::CustomerDefaultAddressRequest>,
+) -> HttpResponse {
+    let flow = Flow::CustomerAddressesSetDefault;
+    let (customer_id, address_id) = path.into_inner();
+    let address_id = customers::CustomerAddressId {
+        customer_id,
+        address_id,
+    };
+    api::server_wrap(
+        flow,
+        state.get_ref(),
+        &req,
+        json_payload.into_inner(),
+        |state, auth, req| {
+            set_default_customer_address(
+                &*state.store,
+                auth.merchant_account,
+                auth.key_store,
+                address_id.clone(),
+                req,
+            )
+        },
+        &auth::ApiKeyAuth,
+    )
+    .await
+}
+
+/// Customer Payment Methods - Set Default
+///
+/// Set a saved payment method of a customer as the default payment method, which is listed first
+/// among the saved payment methods of the customer.
+#[utoipa::path(
+    post,
+    path = "/customers/{customer_id}/payment_methods/{payment_method_id}/default",
+    params (
+        ("customer_id" = String, Path, description = "The unique identifier for the Customer"),
+        ("payment_method_id" = String, Path, description = "The unique identifier for the Payment Method")
+    ),
+    responses(
+        (status = 200, description = "Default Payment Method was Set", body = CustomerDefaultPaymentMethodResponse),
+        (status = 404, description = "Payment Method was not found")
+    ),
+    tag = "Customers",
+    operation_id = "Set the Default Customer Payment Method",
+    security(("api_key" = []))
+)]
+#[instrument(skip_all, fields(flow = ?Flow::CustomerPaymentMethodsSetDefault))]
+pub async fn customer_payment_methods_set_default(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<(String, String)>,
+) -> HttpResponse {
+    let flow = Flow::CustomerPaymentMethodsSetDefault;
+    let (customer_id, payment_method_id) = path.into_inner();
+    let payment_method_id = customers::CustomerPaymentMethodId {
+        customer_id,
+        payment_method_id,
+    };
+    api::server_wrap(
+        flow,
+        state.get_ref(),
+        &req,
+        payment_method_id,
+        |state, auth, req| {
+            set_default_customer_payment_method(
+                &*state.store,
+                auth.merchant_account,
+                auth.key_store,
+                req,
+            )
+        },
+        &auth::ApiKeyAuth,
+    )
+    .await
+}
@@ -47,6 +47,44 @@ pub async fn retrieve_dispute(
     .await
 }
 
+/// Disputes - Retrieve Dispute Financials
+///
+/// Retrieves the amounts withdrawn from and reinstated to the merchant over the lifecycle of a dispute, along with the ledger entries posted for it
+#[utoipa::path(
+    get,
+    path = "/disputes/{dispute_id}/financials",
+    params(
+        ("dispute_id" = String, Path, description = "The identifier for dispute")
+    ),
+    responses(
+        (status = 200, description = "The dispute financials were retrieved successfully", body = DisputeFinancialsResponse),
+        (status = 404, description = "Dispute does not exist in our records")
+    ),
+    tag = "Disputes",
+    operation_id = "Retrieve Dispute Financials",
+    security(("api_key" = []))
+)]
+#[instrument(skip_all, fields(flow = ?Flow::DisputeFinancialsRetrieve))]
+pub async fn retrieve_dispute_financials(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<String>,
+) -> HttpResponse {
+    let flow = Flow::DisputeFinancialsRetrieve;
+    let dispute_id = dispute_types::DisputeId {
+        dispute_id: path.into_inner(),
+    };
+    api::server_wrap(
+        flow,
+        state.get_ref(),
+        &req,
+        dispute_id,
+        |state, auth, req| disputes::retrieve_dispute_financials(state, auth.merchant_account, req),
+        auth::auth_type(&auth::ApiKeyAuth, &auth::JWTAuth, req.headers()),
+    )
+    .await
+}
+
 /// Disputes - List Disputes
 #[utoipa::path(
     get,
@@ -90,6 +128,111 @@ pub async fn retrieve_disputes_list(
     .await
 }
 
+/// Disputes - List Reason Codes
+///
+/// Lists the dispute reason codes of the card networks, along with their normalized category and the evidence recommended to challenge disputes raised with them
+#[utoipa::path(
+    get,
+    path = "/disputes/reason_codes",
+    params(
+        ("card_network" = Option<CardNetwork>, Query, description = "Only list the reason codes of the card network"),
+        ("category" = Option<DisputeReasonCategory>, Query, description = "Only list the reason codes of the category"),
+    ),
+    responses(
+        (status = 200, description = "The reason codes were listed successfully", body = Vec<DisputeReasonCodeResponse>),
+        (status = 401, description = "Unauthorized request")
+    ),
+    tag = "Disputes",
+    operation_id = "List Dispute Reason Codes",
+    security(("api_key" = []))
+)]
+#[instrument(skip_all, fields(flow = ?Flow::DisputesReasonCodesList))]
+pub async fn list_dispute_reason_codes(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    payload: web::Query<dispute_models::DisputeReasonCodeListConstraints>,
+) -> HttpResponse {
+    let flow = Flow::DisputesReasonCodesList;
+    let payload = payload.into_inner();
+    api::server_wrap(
+        flow,
+        state.get_ref(),
+        &req,
+        payload,
+        |state, _, req| disputes::list_dispute_reason_codes(state, req),
+        auth::auth_type(&auth::ApiKeyAuth, &auth::JWTAuth, req.headers()),
+    )
+    .await
+}
+
+/// Disputes - Retrieve Auto-Defend Rules
+///
+/// Retrieves the rules taking an action automatically on the disputes raised against the payments of the merchant
+#[utoipa::path(
+    get,
+    path = "/disputes/auto_defend_rules",
+    responses(
+        (status = 200, description = "The auto-defend rules were retrieved successfully", body = DisputeAutoDefendRules),
+        (status = 401, description = "Unauthorized request")
+    ),
+    tag = "Disputes",
+    operation_id = "Retrieve Dispute Auto-Defend Rules",
+    security(("api_key" = []))
+)]
+#[instrument(skip_all, fields(flow = ?Flow::DisputesAutoDefendRulesRetrieve))]
+pub async fn retrieve_dispute_auto_defend_rules(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+) -> HttpResponse {
+    let flow = Flow::DisputesAutoDefendRulesRetrieve;
+    api::server_wrap(
+        flow,
+        state.get_ref(),
+        &req,
+        (),
+        |state, auth, _| {
+            disputes::auto_defend::retrieve_auto_defend_rules(state, auth.merchant_account)
+        },
+        auth::auth_type(&auth::ApiKeyAuth, &auth::JWTAuth, req.headers()),
+    )
+    .await
+}
+
+/// Disputes - Update Auto-Defend Rules
+///
+/// Replaces the rules taking an action automatically on the disputes raised against the payments of the merchant. The rules are evaluated in order when a dispute is opened, before the merchant is notified of it, and the action of the first rule matching the dispute is taken
+#[utoipa::path(
+    post,
+    path = "/disputes/auto_defend_rules",
+    request_body = DisputeAutoDefendRules,
+    responses(
+        (status = 200, description = "The auto-defend rules were updated successfully", body = DisputeAutoDefendRules),
+        (status = 400, description = "Invalid auto-defend rules")
+    ),
+    tag = "Disputes",
+    operation_id = "Update Dispute Auto-Defend Rules",
+    security(("api_key" = []))
+)]
+#[instrument(skip_all, fields(flow = ?Flow::DisputesAutoDefendRulesUpdate))]
+pub async fn update_dispute_auto_defend_rules(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    json_payload: web::Json<dispute_models::DisputeAutoDefendRules>,
+) -> HttpResponse {
+    let flow = Flow::DisputesAutoDefendRulesUpdate;
+    api::server_wrap(
+        flow,
+        state.get_ref(),
+        &req,
+        json_payload.into_inner(),
+        |state, auth, req| {
+            disputes::auto_defend::update_auto_defend_rules(state, auth.merchant_account, req)
+        },
+        auth::auth_type(&auth::ApiKeyAuth, &auth::JWTAuth, req.headers()),
+    )
+    .await
+}
+
 /// Disputes - Accept Dispute
 #[utoipa::path(
     get,
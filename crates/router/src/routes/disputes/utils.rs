@@ -94,6 +94,8 @@ pub async fn get_attach_evidence_request(
         file_type,
         purpose: files::FilePurpose::DisputeEvidence,
         dispute_id,
+        payout_id: None,
+        document_type: None,
     };
     Ok(disputes::AttachEvidenceRequest {
         evidence_type,
@@ -0,0 +1,131 @@
+use actix_web::{web, HttpRequest, HttpResponse};
+use api_models::feature_flags::{self as feature_flag_types, FeatureFlag};
+use router_env::{instrument, tracing, Flow};
+
+use super::app::AppState;
+use crate::{
+    core::feature_flags,
+    services::{api, authentication as auth},
+};
+
+/// Feature Flags - List
+///
+/// List all the feature flags, along with their rollout
+#[instrument(skip_all, fields(flow = ?Flow::FeatureFlagsList))]
+pub async fn list_feature_flags(state: web::Data<AppState>, req: HttpRequest) -> HttpResponse {
+    let flow = Flow::FeatureFlagsList;
+
+    api::server_wrap(
+        flow,
+        state.get_ref(),
+        &req,
+        (),
+        |state, _, _| feature_flags::list_feature_flags(state),
+        &auth::AdminApiAuth,
+    )
+    .await
+}
+
+/// Feature Flags - Retrieve
+///
+/// Retrieve the rollout of a feature flag
+#[instrument(skip_all, fields(flow = ?Flow::FeatureFlagRetrieve))]
+pub async fn retrieve_feature_flag(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<FeatureFlag>,
+) -> HttpResponse {
+    let flow = Flow::FeatureFlagRetrieve;
+    let flag = path.into_inner();
+
+    api::server_wrap(
+        flow,
+        state.get_ref(),
+        &req,
+        flag,
+        |state, _, flag| feature_flags::retrieve_feature_flag(state, flag),
+        &auth::AdminApiAuth,
+    )
+    .await
+}
+
+/// Feature Flags - Update
+///
+/// Update the percentage of merchants for which a feature flag is enabled
+#[instrument(skip_all, fields(flow = ?Flow::FeatureFlagUpdate))]
+pub async fn update_feature_flag(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<FeatureFlag>,
+    json_payload: web::Json<feature_flag_types::FeatureFlagUpdateRequest>,
+) -> HttpResponse {
+    let flow = Flow::FeatureFlagUpdate;
+    let flag = path.into_inner();
+
+    api::server_wrap(
+        flow,
+        state.get_ref(),
+        &req,
+        json_payload.into_inner(),
+        |state, _, payload| feature_flags::update_feature_flag(state, flag, payload),
+        &auth::AdminApiAuth,
+    )
+    .await
+}
+
+/// Feature Flags - Update Merchant Override
+///
+/// Enable or disable a feature flag for a merchant, regardless of the rollout of the flag
+#[instrument(skip_all, fields(flow = ?Flow::MerchantFeatureFlagOverrideUpdate))]
+pub async fn update_merchant_feature_flag_override(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<(FeatureFlag, String)>,
+    json_payload: web::Json<feature_flag_types::MerchantFeatureFlagOverrideRequest>,
+) -> HttpResponse {
+    let flow = Flow::MerchantFeatureFlagOverrideUpdate;
+    let (flag, merchant_id) = path.into_inner();
+
+    api::server_wrap(
+        flow,
+        state.get_ref(),
+        &req,
+        json_payload.into_inner(),
+        |state, _, payload| {
+            feature_flags::update_merchant_feature_flag_override(
+                state,
+                flag,
+                merchant_id.clone(),
+                payload,
+            )
+        },
+        &auth::AdminApiAuth,
+    )
+    .await
+}
+
+/// Feature Flags - Delete Merchant Override
+///
+/// Remove the override of a feature flag for a merchant, so that the rollout of the flag applies
+/// to the merchant again
+#[instrument(skip_all, fields(flow = ?Flow::MerchantFeatureFlagOverrideDelete))]
+pub async fn delete_merchant_feature_flag_override(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<(FeatureFlag, String)>,
+) -> HttpResponse {
+    let flow = Flow::MerchantFeatureFlagOverrideDelete;
+    let (flag, merchant_id) = path.into_inner();
+
+    api::server_wrap(
+        flow,
+        state.get_ref(),
+        &req,
+        merchant_id,
+        |state, _, merchant_id| {
+            feature_flags::delete_merchant_feature_flag_override(state, flag, merchant_id)
+        },
+        &auth::AdminApiAuth,
+    )
+    .await
+}
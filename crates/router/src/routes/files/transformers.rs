@@ -15,6 +15,8 @@ pub async fn get_create_file_request(
 ) -> CustomResult<CreateFileRequest, errors::ApiErrorResponse> {
     let mut option_purpose: Option<files::FilePurpose> = None;
     let mut dispute_id: Option<String> = None;
+    let mut payout_id: Option<String> = None;
+    let mut document_type: Option<String> = None;
 
     let mut file_name: Option<String> = None;
     let mut file_content: Option<Vec<Bytes>> = None;
@@ -46,11 +48,24 @@ pub async fn get_create_file_request(
             Some("dispute_id") => {
                 dispute_id = helpers::read_string(&mut field).await;
             }
+            Some("payout_id") => {
+                payout_id = helpers::read_string(&mut field).await;
+            }
+            Some("document_type") => {
+                document_type = helpers::read_string(&mut field).await;
+            }
             // Can ignore other params
             _ => (),
         }
     }
     let purpose = option_purpose.get_required_value("purpose")?;
+    let document_type = document_type
+        .map(|document_type| document_type.parse::<files::KycDocumentType>())
+        .transpose()
+        .into_report()
+        .change_context(errors::ApiErrorResponse::InvalidDataValue {
+            field_name: "document_type",
+        })?;
     let file = match file_content {
         Some(valid_file_content) => valid_file_content.concat().to_vec(),
         None => Err(errors::ApiErrorResponse::MissingFile)
@@ -85,5 +100,7 @@ pub async fn get_create_file_request(
         file_type,
         purpose,
         dispute_id,
+        payout_id,
+        document_type,
     })
 }
@@ -0,0 +1,240 @@
+use actix_web::{web, HttpRequest, Responder};
+use router_env::{instrument, tracing, Flow};
+
+use super::app::AppState;
+use crate::{
+    core::gift_cards,
+    services::{api, authentication as auth},
+    types::api::gift_cards as gift_card_types,
+};
+
+/// Gift Cards - Create
+///
+/// Issue a gift card with a balance which can be redeemed with your merchant account.
+#[utoipa::path(
+    post,
+    path = "/gift_cards",
+    request_body = GiftCardCreateRequest,
+    responses(
+        (status = 200, description = "Gift card issued", body = GiftCardResponse),
+        (status = 400, description = "Invalid data")
+    ),
+    tag = "Gift Cards",
+    operation_id = "Create a Gift Card",
+    security(("api_key" = []))
+)]
+#[instrument(skip_all, fields(flow = ?Flow::GiftCardsCreate))]
+pub async fn gift_cards_create(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    json_payload: web::Json<gift_card_types::GiftCardCreateRequest>,
+) -> impl Responder {
+    let flow = Flow::GiftCardsCreate;
+    api::server_wrap(
+        flow,
+        state.get_ref(),
+        &req,
+        json_payload.into_inner(),
+        |state, auth, req| {
+            gift_cards::create_gift_card(state, auth.merchant_account, auth.key_store, req)
+        },
+        &auth::ApiKeyAuth,
+    )
+    .await
+}
+
+/// Gift Cards - Retrieve
+///
+/// Retrieve a gift card, along with its available balance.
+#[utoipa::path(
+    get,
+    path = "/gift_cards/{gift_card_id}",
+    params(("gift_card_id" = String, Path, description = "The identifier for the gift card")),
+    responses(
+        (status = 200, description = "Gift card retrieved", body = GiftCardResponse),
+        (status = 404, description = "Gift card not found")
+    ),
+    tag = "Gift Cards",
+    operation_id = "Retrieve a Gift Card",
+    security(("api_key" = []))
+)]
+#[instrument(skip_all, fields(flow = ?Flow::GiftCardsRetrieve))]
+pub async fn gift_cards_retrieve(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<String>,
+) -> impl Responder {
+    let flow = Flow::GiftCardsRetrieve;
+    api::server_wrap(
+        flow,
+        state.get_ref(),
+        &req,
+        path.into_inner(),
+        |state, auth, gift_card_id| {
+            gift_cards::retrieve_gift_card(state, auth.merchant_account, gift_card_id)
+        },
+        auth::auth_type(&auth::ApiKeyAuth, &auth::JWTAuth, req.headers()),
+    )
+    .await
+}
+
+/// Gift Cards - Redeem
+///
+/// Redeem a part of the balance of a gift card, optionally towards a payment in the currency of
+/// the gift card.
+#[utoipa::path(
+    post,
+    path = "/gift_cards/{gift_card_id}/redeem",
+    params(("gift_card_id" = String, Path, description = "The identifier for the gift card")),
+    request_body = GiftCardRedeemRequest,
+    responses(
+        (status = 200, description = "Gift card redeemed", body = GiftCardResponse),
+        (status = 400, description = "Gift card cannot be redeemed"),
+        (status = 404, description = "Gift card not found")
+    ),
+    tag = "Gift Cards",
+    operation_id = "Redeem a Gift Card",
+    security(("api_key" = []))
+)]
+#[instrument(skip_all, fields(flow = ?Flow::GiftCardsRedeem))]
+pub async fn gift_cards_redeem(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<String>,
+    json_payload: web::Json<gift_card_types::GiftCardRedeemRequest>,
+) -> impl Responder {
+    let flow = Flow::GiftCardsRedeem;
+    let gift_card_id = path.into_inner();
+    api::server_wrap(
+        flow,
+        state.get_ref(),
+        &req,
+        json_payload.into_inner(),
+        |state, auth, req| {
+            gift_cards::redeem_gift_card(state, auth.merchant_account, gift_card_id.clone(), req)
+        },
+        &auth::ApiKeyAuth,
+    )
+    .await
+}
+
+/// Gift Cards - Reload
+///
+/// Add to the balance of an active gift card.
+#[utoipa::path(
+    post,
+    path = "/gift_cards/{gift_card_id}/reload",
+    params(("gift_card_id" = String, Path, description = "The identifier for the gift card")),
+    request_body = GiftCardReloadRequest,
+    responses(
+        (status = 200, description = "Gift card reloaded", body = GiftCardResponse),
+        (status = 400, description = "Gift card cannot be reloaded"),
+        (status = 404, description = "Gift card not found")
+    ),
+    tag = "Gift Cards",
+    operation_id = "Reload a Gift Card",
+    security(("api_key" = []))
+)]
+#[instrument(skip_all, fields(flow = ?Flow::GiftCardsReload))]
+pub async fn gift_cards_reload(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<String>,
+    json_payload: web::Json<gift_card_types::GiftCardReloadRequest>,
+) -> impl Responder {
+    let flow = Flow::GiftCardsReload;
+    let gift_card_id = path.into_inner();
+    api::server_wrap(
+        flow,
+        state.get_ref(),
+        &req,
+        json_payload.into_inner(),
+        |state, auth, req| {
+            gift_cards::reload_gift_card(state, auth.merchant_account, gift_card_id.clone(), req)
+        },
+        &auth::ApiKeyAuth,
+    )
+    .await
+}
+
+/// Gift Cards - Void
+///
+/// Void a gift card, forfeiting its remaining balance, so that it can no longer be used.
+#[utoipa::path(
+    post,
+    path = "/gift_cards/{gift_card_id}/void",
+    params(("gift_card_id" = String, Path, description = "The identifier for the gift card")),
+    responses(
+        (status = 200, description = "Gift card voided", body = GiftCardResponse),
+        (status = 400, description = "Gift card cannot be voided"),
+        (status = 404, description = "Gift card not found")
+    ),
+    tag = "Gift Cards",
+    operation_id = "Void a Gift Card",
+    security(("api_key" = []))
+)]
+#[instrument(skip_all, fields(flow = ?Flow::GiftCardsVoid))]
+pub async fn gift_cards_void(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<String>,
+) -> impl Responder {
+    let flow = Flow::GiftCardsVoid;
+    api::server_wrap(
+        flow,
+        state.get_ref(),
+        &req,
+        path.into_inner(),
+        |state, auth, gift_card_id| {
+            gift_cards::void_gift_card(state, auth.merchant_account, gift_card_id)
+        },
+        &auth::ApiKeyAuth,
+    )
+    .await
+}
+
+/// Gift Cards - List Transactions
+///
+/// List the transactions which changed the balance of a gift card, most recent first.
+#[utoipa::path(
+    get,
+    path = "/gift_cards/{gift_card_id}/transactions",
+    params(
+        ("gift_card_id" = String, Path, description = "The identifier for the gift card"),
+        ("limit" = Option<i64>, Query, description = "The maximum number of transactions to include in the response"),
+        ("skip" = Option<i64>, Query, description = "The number of transactions to skip when retrieving the list of transactions"),
+    ),
+    responses(
+        (status = 200, description = "List of gift card transactions retrieved successfully", body = Vec<GiftCardTransactionResponse>),
+        (status = 404, description = "Gift card not found")
+    ),
+    tag = "Gift Cards",
+    operation_id = "List all Transactions of a Gift Card",
+    security(("api_key" = []))
+)]
+#[instrument(skip_all, fields(flow = ?Flow::GiftCardsTransactionsList))]
+pub async fn gift_cards_transactions_list(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<String>,
+    query: web::Query<gift_card_types::GiftCardTransactionListConstraints>,
+) -> impl Responder {
+    let flow = Flow::GiftCardsTransactionsList;
+    let gift_card_id = path.into_inner();
+    api::server_wrap(
+        flow,
+        state.get_ref(),
+        &req,
+        query.into_inner(),
+        |state, auth, constraints| {
+            gift_cards::list_gift_card_transactions(
+                state,
+                auth.merchant_account,
+                gift_card_id.clone(),
+                constraints,
+            )
+        },
+        auth::auth_type(&auth::ApiKeyAuth, &auth::JWTAuth, req.headers()),
+    )
+    .await
+}
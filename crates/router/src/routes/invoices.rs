@@ -0,0 +1,244 @@
+use actix_web::{web, HttpRequest, Responder};
+use router_env::{instrument, tracing, Flow};
+
+use super::app::AppState;
+use crate::{
+    core::invoices,
+    services::{api, authentication as auth},
+    types::api::invoices as invoice_types,
+};
+
+/// Invoices - Create
+///
+/// Create an invoice for a customer. The invoice can be paid in one or more payments through its
+/// payment link.
+#[utoipa::path(
+    post,
+    path = "/invoices",
+    request_body = InvoiceCreateRequest,
+    responses(
+        (status = 200, description = "Invoice created", body = InvoiceResponse),
+        (status = 400, description = "Invalid data")
+    ),
+    tag = "Invoices",
+    operation_id = "Create an Invoice",
+    security(("api_key" = []))
+)]
+#[instrument(skip_all, fields(flow = ?Flow::InvoicesCreate))]
+pub async fn invoices_create(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    json_payload: web::Json<invoice_types::InvoiceCreateRequest>,
+) -> impl Responder {
+    let flow = Flow::InvoicesCreate;
+    api::server_wrap(
+        flow,
+        state.get_ref(),
+        &req,
+        json_payload.into_inner(),
+        |state, auth, req| {
+            invoices::create_invoice(state, auth.merchant_account, auth.key_store, req)
+        },
+        &auth::ApiKeyAuth,
+    )
+    .await
+}
+
+/// Invoices - Retrieve
+///
+/// Retrieve an invoice, along with the amount paid towards it so far.
+#[utoipa::path(
+    get,
+    path = "/invoices/{invoice_id}",
+    params(("invoice_id" = String, Path, description = "The identifier for the invoice")),
+    responses(
+        (status = 200, description = "Invoice retrieved", body = InvoiceResponse),
+        (status = 404, description = "Invoice not found")
+    ),
+    tag = "Invoices",
+    operation_id = "Retrieve an Invoice",
+    security(("api_key" = []))
+)]
+#[instrument(skip_all, fields(flow = ?Flow::InvoicesRetrieve))]
+pub async fn invoices_retrieve(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<String>,
+) -> impl Responder {
+    let flow = Flow::InvoicesRetrieve;
+    api::server_wrap(
+        flow,
+        state.get_ref(),
+        &req,
+        path.into_inner(),
+        |state, auth, invoice_id| {
+            invoices::retrieve_invoice(state, auth.merchant_account, invoice_id)
+        },
+        auth::auth_type(&auth::ApiKeyAuth, &auth::JWTAuth, req.headers()),
+    )
+    .await
+}
+
+/// Invoices - List
+///
+/// List the invoices created by your merchant account, most recent first.
+#[utoipa::path(
+    get,
+    path = "/invoices/list",
+    params(
+        ("limit" = Option<i64>, Query, description = "The maximum number of invoices to include in the response"),
+        ("skip" = Option<i64>, Query, description = "The number of invoices to skip when retrieving the list of invoices"),
+    ),
+    responses(
+        (status = 200, description = "List of invoices retrieved successfully", body = Vec<InvoiceResponse>),
+    ),
+    tag = "Invoices",
+    operation_id = "List all Invoices",
+    security(("api_key" = []))
+)]
+#[instrument(skip_all, fields(flow = ?Flow::InvoicesList))]
+pub async fn invoices_list(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    query: web::Query<invoice_types::InvoiceListConstraints>,
+) -> impl Responder {
+    let flow = Flow::InvoicesList;
+    api::server_wrap(
+        flow,
+        state.get_ref(),
+        &req,
+        query.into_inner(),
+        |state, auth, constraints| {
+            invoices::list_invoices(state, auth.merchant_account, constraints)
+        },
+        auth::auth_type(&auth::ApiKeyAuth, &auth::JWTAuth, req.headers()),
+    )
+    .await
+}
+
+/// Invoices - Void
+///
+/// Void an invoice which has not been paid yet, so that it can no longer be paid.
+#[utoipa::path(
+    post,
+    path = "/invoices/{invoice_id}/void",
+    params(("invoice_id" = String, Path, description = "The identifier for the invoice")),
+    responses(
+        (status = 200, description = "Invoice voided", body = InvoiceResponse),
+        (status = 400, description = "Invoice cannot be voided"),
+        (status = 404, description = "Invoice not found")
+    ),
+    tag = "Invoices",
+    operation_id = "Void an Invoice",
+    security(("api_key" = []))
+)]
+#[instrument(skip_all, fields(flow = ?Flow::InvoicesVoid))]
+pub async fn invoices_void(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<String>,
+) -> impl Responder {
+    let flow = Flow::InvoicesVoid;
+    api::server_wrap(
+        flow,
+        state.get_ref(),
+        &req,
+        path.into_inner(),
+        |state, auth, invoice_id| invoices::void_invoice(state, auth.merchant_account, invoice_id),
+        &auth::ApiKeyAuth,
+    )
+    .await
+}
+
+/// Invoices - Retrieve Payment Link
+///
+/// Retrieve the invoice referenced by a payment link, to present it to the customer.
+#[utoipa::path(
+    get,
+    path = "/invoices/{merchant_id}/{invoice_id}/pay",
+    params(
+        ("merchant_id" = String, Path, description = "The identifier for the Merchant Account"),
+        ("invoice_id" = String, Path, description = "The identifier for the invoice"),
+        ("client_secret" = String, Query, description = "The secret issued with the payment link of the invoice"),
+    ),
+    responses(
+        (status = 200, description = "Invoice retrieved", body = InvoiceResponse),
+        (status = 404, description = "Invoice not found")
+    ),
+    tag = "Invoices",
+    operation_id = "Retrieve an Invoice through its Payment Link",
+)]
+#[instrument(skip_all, fields(flow = ?Flow::InvoicesPaymentLinkRetrieve))]
+pub async fn invoices_payment_link_retrieve(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<(String, String)>,
+    query: web::Query<invoice_types::InvoicePaymentLinkQuery>,
+) -> impl Responder {
+    let flow = Flow::InvoicesPaymentLinkRetrieve;
+    let (merchant_id, invoice_id) = path.into_inner();
+    api::server_wrap(
+        flow,
+        state.get_ref(),
+        &req,
+        query.into_inner(),
+        |state, auth, query| {
+            invoices::retrieve_invoice_for_payment_link(
+                state,
+                auth.merchant_account,
+                invoice_id.clone(),
+                query,
+            )
+        },
+        &auth::MerchantIdAuth(merchant_id),
+    )
+    .await
+}
+
+/// Invoices - Pay
+///
+/// Create a payment towards an invoice through its payment link. The payment can be for the
+/// entire amount due or a part of it, and has to be confirmed using its client secret.
+#[utoipa::path(
+    post,
+    path = "/invoices/{merchant_id}/{invoice_id}/pay",
+    params(
+        ("merchant_id" = String, Path, description = "The identifier for the Merchant Account"),
+        ("invoice_id" = String, Path, description = "The identifier for the invoice"),
+    ),
+    request_body = InvoicePaymentRequest,
+    responses(
+        (status = 200, description = "Payment created", body = PaymentsResponse),
+        (status = 400, description = "Invalid data"),
+        (status = 404, description = "Invoice not found")
+    ),
+    tag = "Invoices",
+    operation_id = "Pay an Invoice",
+)]
+#[instrument(skip_all, fields(flow = ?Flow::InvoicesPay))]
+pub async fn invoices_pay(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<(String, String)>,
+    json_payload: web::Json<invoice_types::InvoicePaymentRequest>,
+) -> impl Responder {
+    let flow = Flow::InvoicesPay;
+    let (merchant_id, invoice_id) = path.into_inner();
+    api::server_wrap(
+        flow,
+        state.get_ref(),
+        &req,
+        json_payload.into_inner(),
+        |state, auth, req| {
+            invoices::pay_invoice(
+                state,
+                auth.merchant_account,
+                auth.key_store,
+                invoice_id.clone(),
+                req,
+            )
+        },
+        &auth::MerchantIdAuth(merchant_id),
+    )
+    .await
+}
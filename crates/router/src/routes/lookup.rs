@@ -0,0 +1,48 @@
+use actix_web::{web, HttpRequest, HttpResponse};
+use api_models::lookup as lookup_models;
+use router_env::{instrument, tracing, Flow};
+
+use super::app::AppState;
+use crate::{
+    core::lookup,
+    services::{api, authentication as auth},
+};
+
+/// Lookup - Connector Reference
+///
+/// Find the payments, refunds, disputes and payouts which were assigned a reference by the connector, such as the identifier shown on the connector dashboard
+#[utoipa::path(
+    get,
+    path = "/lookup",
+    params(
+        ("connector_reference" = String, Query, description = "The identifier assigned to the object by the connector"),
+        ("connector" = Option<String>, Query, description = "Only look up objects processed by this connector"),
+    ),
+    responses(
+        (status = 200, description = "The lookup was performed successfully", body = ConnectorReferenceLookupResponse),
+        (status = 400, description = "Missing connector reference"),
+        (status = 401, description = "Unauthorized request"),
+        (status = 404, description = "No objects were found for the connector reference")
+    ),
+    tag = "Lookup",
+    operation_id = "Lookup by Connector Reference",
+    security(("api_key" = []))
+)]
+#[instrument(skip_all, fields(flow = ?Flow::ConnectorReferenceLookup))]
+pub async fn lookup_by_connector_reference(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    payload: web::Query<lookup_models::ConnectorReferenceLookupRequest>,
+) -> HttpResponse {
+    let flow = Flow::ConnectorReferenceLookup;
+    let payload = payload.into_inner();
+    api::server_wrap(
+        flow,
+        state.get_ref(),
+        &req,
+        payload,
+        |state, auth, req| lookup::lookup_by_connector_reference(state, auth.merchant_account, req),
+        auth::auth_type(&auth::ApiKeyAuth, &auth::JWTAuth, req.headers()),
+    )
+    .await
+}
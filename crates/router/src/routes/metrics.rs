@@ -12,6 +12,9 @@ counter_metric!(AWS_KMS_FAILURES, GLOBAL_METER); // No. of AWS KMS API failures
 counter_metric!(REQUESTS_RECEIVED, GLOBAL_METER);
 counter_metric!(REQUEST_STATUS, GLOBAL_METER);
 histogram_metric!(REQUEST_TIME, GLOBAL_METER);
+counter_metric!(LOAD_SHED_REQUESTS, GLOBAL_METER); // No. of requests shed while overloaded
+counter_metric!(RUNTIME_CONFIG_RELOADS, GLOBAL_METER); // No. of reloads of the runtime settings which changed them
+counter_metric!(SCHEMA_VALIDATION_FAILURES, GLOBAL_METER); // No. of request and response bodies violating the OpenAPI specification
 
 // Operation Level Metrics
 counter_metric!(PAYMENT_OPS_COUNT, GLOBAL_METER);
@@ -21,6 +24,7 @@ counter_metric!(SUCCESSFUL_PAYMENT, GLOBAL_METER);
 
 counter_metric!(REFUND_COUNT, GLOBAL_METER);
 counter_metric!(SUCCESSFUL_REFUND, GLOBAL_METER);
+counter_metric!(REFUND_AMOUNT_MISMATCH, GLOBAL_METER); // No. of refunds flagged for an amount refunded different from the amount requested
 
 counter_metric!(PAYMENT_CANCEL_COUNT, GLOBAL_METER);
 counter_metric!(SUCCESSFUL_CANCEL, GLOBAL_METER);
@@ -46,13 +50,19 @@ counter_metric!(API_KEY_REVOKED, GLOBAL_METER);
 
 counter_metric!(MCA_CREATE, GLOBAL_METER);
 
+counter_metric!(MERCHANT_KEY_STORE_ROTATED, GLOBAL_METER);
+
 // Flow Specific Metrics
 
 counter_metric!(ACCESS_TOKEN_CREATION, GLOBAL_METER);
 histogram_metric!(CONNECTOR_REQUEST_TIME, GLOBAL_METER);
 counter_metric!(SESSION_TOKEN_CREATED, GLOBAL_METER);
+counter_metric!(SESSION_CALL_TIMEOUT_COUNT, GLOBAL_METER); // Attributes needed
 
 counter_metric!(CONNECTOR_CALL_COUNT, GLOBAL_METER); // Attributes needed
+counter_metric!(CONNECTOR_REQUESTS_THROTTLED, GLOBAL_METER); // No. of connector requests deferred or rejected by the rate limiter
+counter_metric!(CONNECTOR_SYNC_CACHE_HIT, GLOBAL_METER); // No. of syncs served the result of a recent sync
+counter_metric!(CONNECTOR_SYNC_CACHE_MISS, GLOBAL_METER); // No. of syncs made with the connector
 
 counter_metric!(THREE_DS_PAYMENT_COUNT, GLOBAL_METER);
 counter_metric!(THREE_DS_DOWNGRADE_COUNT, GLOBAL_METER);
@@ -66,6 +76,10 @@ counter_metric!(CONNECTOR_PAYMENT_METHOD_TOKENIZATION, GLOBAL_METER);
 counter_metric!(PREPROCESSING_STEPS_COUNT, GLOBAL_METER);
 counter_metric!(CONNECTOR_CUSTOMER_CREATE, GLOBAL_METER);
 counter_metric!(REDIRECTION_TRIGGERED, GLOBAL_METER);
+counter_metric!(CONFIRM_ATTEMPTS_EXCEEDED, GLOBAL_METER);
+counter_metric!(CAPTCHA_CHALLENGE_ISSUED, GLOBAL_METER);
+counter_metric!(PAYMENT_VELOCITY_LIMIT_EXCEEDED, GLOBAL_METER);
+counter_metric!(PAYMENT_AUTO_RETRIES, GLOBAL_METER);
 
 // Connector Level Metric
 counter_metric!(REQUEST_BUILD_FAILURE, GLOBAL_METER);
@@ -0,0 +1,123 @@
+use actix_web::{web, HttpRequest, Responder};
+use router_env::{instrument, tracing, Flow};
+
+use super::app::AppState;
+use crate::{
+    core::payment_links,
+    services::{api, authentication as auth},
+    types::api::payment_links as payment_link_types,
+};
+
+/// Payment Links - Create
+///
+/// Create a payment link for a payment awaiting a payment method. The payment link is a signed
+/// URL of a page hosted for the merchant, through which the customer pays the payment.
+#[utoipa::path(
+    post,
+    path = "/payment_links",
+    request_body = PaymentLinkCreateRequest,
+    responses(
+        (status = 200, description = "Payment link created", body = PaymentLinkResponse),
+        (status = 400, description = "Invalid data")
+    ),
+    tag = "Payment Links",
+    operation_id = "Create a Payment Link",
+    security(("api_key" = []))
+)]
+#[instrument(skip_all, fields(flow = ?Flow::PaymentLinksCreate))]
+pub async fn payment_links_create(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    json_payload: web::Json<payment_link_types::PaymentLinkCreateRequest>,
+) -> impl Responder {
+    let flow = Flow::PaymentLinksCreate;
+    api::server_wrap(
+        flow,
+        state.get_ref(),
+        &req,
+        json_payload.into_inner(),
+        |state, auth, req| payment_links::create_payment_link(state, auth.merchant_account, req),
+        &auth::ApiKeyAuth,
+    )
+    .await
+}
+
+/// Payment Links - Retrieve
+///
+/// Retrieve a payment link, along with its signed URL.
+#[utoipa::path(
+    get,
+    path = "/payment_links/{payment_link_id}",
+    params(("payment_link_id" = String, Path, description = "The identifier for the payment link")),
+    responses(
+        (status = 200, description = "Payment link retrieved", body = PaymentLinkResponse),
+        (status = 404, description = "Payment link not found")
+    ),
+    tag = "Payment Links",
+    operation_id = "Retrieve a Payment Link",
+    security(("api_key" = []))
+)]
+#[instrument(skip_all, fields(flow = ?Flow::PaymentLinksRetrieve))]
+pub async fn payment_links_retrieve(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<String>,
+) -> impl Responder {
+    let flow = Flow::PaymentLinksRetrieve;
+    api::server_wrap(
+        flow,
+        state.get_ref(),
+        &req,
+        path.into_inner(),
+        |state, auth, payment_link_id| {
+            payment_links::retrieve_payment_link(state, auth.merchant_account, payment_link_id)
+        },
+        auth::auth_type(&auth::ApiKeyAuth, &auth::JWTAuth, req.headers()),
+    )
+    .await
+}
+
+/// Payment Links - Render
+///
+/// Renders the page of a payment link, which loads the SDK for the customer to pay the payment
+// #[utoipa::path(
+//     get,
+//     path = "/payment_links/{merchant_id}/{payment_link_id}",
+//     params(
+//         ("merchant_id" = String, Path, description = "The identifier for the Merchant Account"),
+//         ("payment_link_id" = String, Path, description = "The identifier for the payment link"),
+//         ("signature" = String, Query, description = "The signature of the URL of the payment link")
+//     ),
+//     responses(
+//         (status = 200, description = "The page of the payment link"),
+//         (status = 404, description = "Payment link not found")
+//     ),
+//     tag = "Payment Links",
+//     operation_id = "Render a Payment Link"
+// )]
+#[instrument(skip_all, fields(flow = ?Flow::PaymentLinksRender))]
+pub async fn payment_links_render(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<(String, String)>,
+    query: web::Query<payment_link_types::PaymentLinkQuery>,
+) -> impl Responder {
+    let flow = Flow::PaymentLinksRender;
+    let (merchant_id, payment_link_id) = path.into_inner();
+    api::server_wrap(
+        flow,
+        state.get_ref(),
+        &req,
+        query.into_inner(),
+        |state, auth, query| {
+            payment_links::render_payment_link(
+                state,
+                auth.merchant_account,
+                payment_link_id.clone(),
+                query,
+            )
+        },
+        &auth::MerchantIdAuth(merchant_id),
+    )
+    .await
+}
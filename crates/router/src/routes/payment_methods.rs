@@ -3,9 +3,12 @@ use router_env::{instrument, tracing, Flow};
 
 use super::app::AppState;
 use crate::{
-    core::payment_methods::cards,
+    core::payment_methods::{cards, sharing},
     services::{api, authentication as auth},
-    types::api::payment_methods::{self, PaymentMethodId},
+    types::api::{
+        payment_method_shares,
+        payment_methods::{self, PaymentMethodId},
+    },
 };
 
 /// PaymentMethods - Create
@@ -324,7 +327,173 @@ pub async fn payment_method_delete_api(
         state.get_ref(),
         &req,
         pm,
-        |state, auth, req| cards::delete_payment_method(state, auth.merchant_account, req),
+        |state, auth, req| {
+            cards::delete_payment_method(state, auth.merchant_account, auth.key_store, req)
+        },
+        &auth::ApiKeyAuth,
+    )
+    .await
+}
+
+/// Payment Method - Share
+///
+/// Share a saved card with another sub-merchant of the same platform, along with the consent given
+/// by the customer to share it. The sub-merchant pays with the card by passing the identifier of
+/// the share as the `shared_payment_method_id` of its payments
+#[utoipa::path(
+    post,
+    path = "/payment_methods/{method_id}/shares",
+    params (
+        ("method_id" = String, Path, description = "The unique identifier for the Payment Method"),
+    ),
+    request_body = PaymentMethodShareRequest,
+    responses(
+        (status = 200, description = "Payment Method shared", body = PaymentMethodShareResponse),
+        (status = 400, description = "Invalid data"),
+        (status = 404, description = "Payment Method does not exist in records")
+    ),
+    tag = "Payment Methods",
+    operation_id = "Share a Payment method",
+    security(("api_key" = []))
+)]
+#[instrument(skip_all, fields(flow = ?Flow::PaymentMethodSharesCreate))]
+pub async fn payment_method_share_api(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<String>,
+    json_payload: web::Json<payment_method_shares::PaymentMethodShareRequest>,
+) -> HttpResponse {
+    let flow = Flow::PaymentMethodSharesCreate;
+    let payment_method_id = path.into_inner();
+
+    api::server_wrap(
+        flow,
+        state.get_ref(),
+        &req,
+        json_payload.into_inner(),
+        |state, auth, payload| {
+            sharing::share_payment_method(
+                state,
+                auth.merchant_account,
+                payment_method_id.clone(),
+                payload,
+            )
+        },
+        &auth::ApiKeyAuth,
+    )
+    .await
+}
+
+/// Payment Method - List Shares
+///
+/// List the sub-merchants with which a saved card has been shared, most recent first
+#[utoipa::path(
+    get,
+    path = "/payment_methods/{method_id}/shares",
+    params (
+        ("method_id" = String, Path, description = "The unique identifier for the Payment Method"),
+    ),
+    responses(
+        (status = 200, description = "Payment Method shares retrieved", body = Vec<PaymentMethodShareResponse>),
+        (status = 404, description = "Payment Method does not exist in records")
+    ),
+    tag = "Payment Methods",
+    operation_id = "List the shares of a Payment method",
+    security(("api_key" = []))
+)]
+#[instrument(skip_all, fields(flow = ?Flow::PaymentMethodSharesList))]
+pub async fn payment_method_shares_list_api(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<String>,
+) -> HttpResponse {
+    let flow = Flow::PaymentMethodSharesList;
+
+    api::server_wrap(
+        flow,
+        state.get_ref(),
+        &req,
+        path.into_inner(),
+        |state, auth, payment_method_id| {
+            sharing::list_payment_method_shares(state, auth.merchant_account, payment_method_id)
+        },
+        &auth::ApiKeyAuth,
+    )
+    .await
+}
+
+/// Payment Method - Retrieve Share
+///
+/// Retrieve a share of a saved card, either by the merchant owning the card or by the
+/// sub-merchant it is shared with
+#[utoipa::path(
+    get,
+    path = "/payment_methods/shares/{share_id}",
+    params (
+        ("share_id" = String, Path, description = "The unique identifier for the share"),
+    ),
+    responses(
+        (status = 200, description = "Payment Method share retrieved", body = PaymentMethodShareResponse),
+        (status = 404, description = "Payment Method share does not exist in records")
+    ),
+    tag = "Payment Methods",
+    operation_id = "Retrieve a Payment method share",
+    security(("api_key" = []))
+)]
+#[instrument(skip_all, fields(flow = ?Flow::PaymentMethodSharesRetrieve))]
+pub async fn payment_method_share_retrieve_api(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<String>,
+) -> HttpResponse {
+    let flow = Flow::PaymentMethodSharesRetrieve;
+
+    api::server_wrap(
+        flow,
+        state.get_ref(),
+        &req,
+        path.into_inner(),
+        |state, auth, share_id| {
+            sharing::retrieve_payment_method_share(state, auth.merchant_account, share_id)
+        },
+        &auth::ApiKeyAuth,
+    )
+    .await
+}
+
+/// Payment Method - Revoke Share
+///
+/// Revoke a share of a saved card, after which the sub-merchant can no longer pay with the card
+#[utoipa::path(
+    post,
+    path = "/payment_methods/shares/{share_id}/revoke",
+    params (
+        ("share_id" = String, Path, description = "The unique identifier for the share"),
+    ),
+    responses(
+        (status = 200, description = "Payment Method share revoked", body = PaymentMethodShareResponse),
+        (status = 404, description = "Payment Method share does not exist in records")
+    ),
+    tag = "Payment Methods",
+    operation_id = "Revoke a Payment method share",
+    security(("api_key" = []))
+)]
+#[instrument(skip_all, fields(flow = ?Flow::PaymentMethodSharesRevoke))]
+pub async fn payment_method_share_revoke_api(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<String>,
+) -> HttpResponse {
+    let flow = Flow::PaymentMethodSharesRevoke;
+
+    api::server_wrap(
+        flow,
+        state.get_ref(),
+        &req,
+        path.into_inner(),
+        |state, auth, share_id| {
+            sharing::revoke_payment_method_share(state, auth.merchant_account, share_id)
+        },
         &auth::ApiKeyAuth,
     )
     .await
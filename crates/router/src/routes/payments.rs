@@ -8,6 +8,7 @@ use crate::{
     self as app,
     core::{
         errors::http_not_implemented,
+        payment_methods::sharing,
         payments::{self, PaymentRedirectFlow},
     },
     openapi::examples::{
@@ -172,7 +173,9 @@ pub async fn payments_start(
     get,
     path = "/payments/{payment_id}",
     params(
-        ("payment_id" = String, Path, description = "The identifier for payment")
+        ("payment_id" = String, Path, description = "The identifier for payment"),
+        ("wait" = Option<String>, Query, description = "Holds the request until the payment reaches a terminal status or the given number of seconds elapses, for example `30s`"),
+        ("expand" = Option<String>, Query, description = "Comma separated list of the related resources to embed in the response, out of `attempts`, `captures`, `refunds`, `disputes` and `customer`, for example `attempts,refunds,customer`")
     ),
     request_body=PaymentRetrieveBody,
     responses(
@@ -192,14 +195,43 @@ pub async fn payments_retrieve(
     json_payload: web::Query<payment_types::PaymentRetrieveBody>,
 ) -> impl Responder {
     let flow = Flow::PaymentsRetrieve;
+    let expand = match json_payload
+        .expand
+        .as_deref()
+        .map(payments::helpers::get_payment_retrieve_expand)
+        .transpose()
+    {
+        Ok(expand) => expand,
+        Err(err) => return api::log_and_return_error_response(err),
+    };
+    let is_expanded =
+        |resource| payments::helpers::is_payment_resource_expanded(expand.as_deref(), resource);
     let payload = payment_types::PaymentsRetrieveRequest {
         resource_id: payment_types::PaymentIdType::PaymentIntentId(path.to_string()),
         merchant_id: json_payload.merchant_id.clone(),
         force_sync: json_payload.force_sync.unwrap_or(false),
+        bypass_sync_cache: json_payload.bypass_sync_cache,
         client_secret: json_payload.client_secret.clone(),
-        expand_attempts: json_payload.expand_attempts,
+        expand_attempts: json_payload.expand_attempts.or(is_expanded(
+            payment_types::PaymentExpandableResource::Attempts,
+        )),
+        expand_captures: json_payload.expand_captures.or(is_expanded(
+            payment_types::PaymentExpandableResource::Captures,
+        )),
+        expand_refunds: is_expanded(payment_types::PaymentExpandableResource::Refunds),
+        expand_disputes: is_expanded(payment_types::PaymentExpandableResource::Disputes),
+        expand_customer: is_expanded(payment_types::PaymentExpandableResource::Customer),
         ..Default::default()
     };
+    let wait_time = match json_payload
+        .wait
+        .as_deref()
+        .map(payments::helpers::get_payment_retrieve_wait_time)
+        .transpose()
+    {
+        Ok(wait_time) => wait_time,
+        Err(err) => return api::log_and_return_error_response(err),
+    };
     let (auth_type, auth_flow) =
         match auth::check_client_secret_and_get_auth(req.headers(), &payload) {
             Ok(auth) => auth,
@@ -211,16 +243,38 @@ pub async fn payments_retrieve(
         state.get_ref(),
         &req,
         payload,
-        |state, auth, req| {
-            payments::payments_core::<api_types::PSync, payment_types::PaymentsResponse, _, _, _>(
-                state,
-                auth.merchant_account,
-                auth.key_store,
-                payments::PaymentStatus,
-                req,
-                auth_flow,
-                payments::CallConnectorAction::Trigger,
-            )
+        |state, auth, req| async move {
+            match wait_time {
+                Some(wait_time) => {
+                    payments::payments_retrieve_until_terminal(
+                        state,
+                        auth.merchant_account,
+                        auth.key_store,
+                        req,
+                        auth_flow,
+                        wait_time,
+                    )
+                    .await
+                }
+                None => {
+                    payments::payments_core::<
+                        api_types::PSync,
+                        payment_types::PaymentsResponse,
+                        _,
+                        _,
+                        _,
+                    >(
+                        state,
+                        auth.merchant_account,
+                        auth.key_store,
+                        payments::PaymentStatus,
+                        req,
+                        auth_flow,
+                        payments::CallConnectorAction::Trigger,
+                    )
+                    .await
+                }
+            }
         },
         &*auth_type,
     )
@@ -348,7 +402,7 @@ pub async fn payments_update(
 
 /// Payments - Confirm
 ///
-/// This API is to confirm the payment request and forward payment to the payment processor. This API provides more granular control upon when the API is forwarded to the payment processor. Alternatively you can confirm the payment within the Payments Create API
+/// This API is to confirm the payment request and forward payment to the payment processor. This API provides more granular control upon when the API is forwarded to the payment processor. Alternatively you can confirm the payment within the Payments Create API. Outside production, the confirmation can be simulated by passing `simulate`, which returns the redacted request that would be sent to the payment processor instead of forwarding the payment
 #[utoipa::path(
     post,
     path = "/payments/{payment_id}/confirm",
@@ -392,6 +446,26 @@ pub async fn payments_confirm(
             Err(e) => return api::log_and_return_error_response(e),
         };
 
+    if payload.simulate.unwrap_or(false) {
+        return api::server_wrap(
+            Flow::PaymentsConfirmSimulate,
+            state.get_ref(),
+            &req,
+            payload,
+            |state, auth, req| {
+                payments::simulation::simulate_payment_confirm(
+                    state,
+                    auth.merchant_account,
+                    auth.key_store,
+                    req,
+                    auth_flow,
+                )
+            },
+            &*auth_type,
+        )
+        .await;
+    }
+
     api::server_wrap(
         flow,
         state.get_ref(),
@@ -412,6 +486,462 @@ pub async fn payments_confirm(
     .await
 }
 
+/// Payments - Retry
+///
+/// To create a new attempt for a failed payment. The connector, authentication type, payment method and billing address of the previous attempt can be changed for the new attempt
+#[utoipa::path(
+    post,
+    path = "/payments/{payment_id}/retry",
+    params(
+        ("payment_id" = String, Path, description = "The identifier for payment")
+    ),
+    request_body=PaymentsRetryRequest,
+    responses(
+        (status = 200, description = "Payment retried", body = PaymentsResponse),
+        (status = 400, description = "Missing mandatory fields")
+    ),
+    tag = "Payments",
+    operation_id = "Retry a Payment",
+    security(("api_key" = []))
+)]
+#[instrument(skip_all, fields(flow = ?Flow::PaymentsRetry))]
+// #[post("/{payment_id}/retry")]
+pub async fn payments_retry(
+    state: web::Data<app::AppState>,
+    req: actix_web::HttpRequest,
+    json_payload: web::Json<payment_types::PaymentsRetryRequest>,
+    path: web::Path<String>,
+) -> impl Responder {
+    let flow = Flow::PaymentsRetry;
+    let retry_payload = json_payload.into_inner();
+    let payment_id = path.into_inner();
+
+    // The retry is confirmed as a manual retry, which creates a new attempt for the same payment
+    let mut payload = payment_types::PaymentsRequest {
+        payment_id: Some(payment_types::PaymentIdType::PaymentIntentId(payment_id)),
+        confirm: Some(true),
+        retry_action: Some(api_enums::RetryAction::ManualRetry),
+        routing: retry_payload.routing,
+        authentication_type: retry_payload.authentication_type,
+        payment_method_data: retry_payload.payment_method_data,
+        payment_method: retry_payload.payment_method,
+        payment_method_type: retry_payload.payment_method_type,
+        payment_token: retry_payload.payment_token,
+        billing: retry_payload.billing,
+        browser_info: retry_payload.browser_info,
+        ..Default::default()
+    };
+
+    if let Err(err) = helpers::populate_ip_into_browser_info(&req, &mut payload) {
+        return api::log_and_return_error_response(err);
+    }
+
+    api::server_wrap(
+        flow,
+        state.get_ref(),
+        &req,
+        payload,
+        |state, auth, req| async move {
+            payments::helpers::validate_payment_for_retry(state, &auth.merchant_account, &req)
+                .await?;
+
+            authorize_verify_select(
+                payments::PaymentConfirm,
+                state,
+                auth.merchant_account,
+                auth.key_store,
+                req,
+                api::AuthFlow::Merchant,
+            )
+            .await
+        },
+        &auth::ApiKeyAuth,
+    )
+    .await
+}
+
+/// Payments - Metadata Update
+///
+/// To merge metadata into a payment, such as the reference of the shipment of the order or the invoice number, at any point of its lifecycle including after it has completed. The financial state of the payment is not affected, and every update is recorded for audit
+#[utoipa::path(
+    patch,
+    path = "/payments/{payment_id}/metadata",
+    params(
+        ("payment_id" = String, Path, description = "The identifier for payment")
+    ),
+    request_body=PaymentsMetadataUpdateRequest,
+    responses(
+        (status = 200, description = "Payment metadata updated", body = PaymentsMetadataUpdateResponse),
+        (status = 400, description = "Invalid metadata"),
+        (status = 404, description = "Payment not found")
+    ),
+    tag = "Payments",
+    operation_id = "Update the Metadata of a Payment",
+    security(("api_key" = []))
+)]
+#[instrument(skip_all, fields(flow = ?Flow::PaymentsMetadataUpdate))]
+// #[patch("/{payment_id}/metadata")]
+pub async fn payments_metadata_update(
+    state: web::Data<app::AppState>,
+    req: actix_web::HttpRequest,
+    json_payload: web::Json<payment_types::PaymentsMetadataUpdateRequest>,
+    path: web::Path<String>,
+) -> impl Responder {
+    let flow = Flow::PaymentsMetadataUpdate;
+    let payment_id = path.into_inner();
+
+    api::server_wrap(
+        flow,
+        state.get_ref(),
+        &req,
+        (payment_id, json_payload.into_inner()),
+        |state, auth, (payment_id, req)| {
+            payments::metadata::update_payment_metadata(
+                state,
+                auth.merchant_account,
+                payment_id,
+                req,
+            )
+        },
+        &auth::ApiKeyAuth,
+    )
+    .await
+}
+
+/// Payments - Metadata Audit List
+///
+/// To list the updates made to the metadata of a payment, oldest first
+#[utoipa::path(
+    get,
+    path = "/payments/{payment_id}/metadata/audit",
+    params(
+        ("payment_id" = String, Path, description = "The identifier for payment")
+    ),
+    responses(
+        (status = 200, description = "The updates made to the payment metadata", body = Vec<PaymentMetadataAuditResponse>),
+        (status = 404, description = "Payment not found")
+    ),
+    tag = "Payments",
+    operation_id = "List the Metadata Updates of a Payment",
+    security(("api_key" = []))
+)]
+#[instrument(skip_all, fields(flow = ?Flow::PaymentsMetadataAuditList))]
+// #[get("/{payment_id}/metadata/audit")]
+pub async fn payments_metadata_audit_list(
+    state: web::Data<app::AppState>,
+    req: actix_web::HttpRequest,
+    path: web::Path<String>,
+) -> impl Responder {
+    let flow = Flow::PaymentsMetadataAuditList;
+    let payment_id = path.into_inner();
+
+    api::server_wrap(
+        flow,
+        state.get_ref(),
+        &req,
+        payment_id,
+        |state, auth, payment_id| {
+            payments::metadata::list_payment_metadata_audits(
+                state,
+                auth.merchant_account,
+                payment_id,
+            )
+        },
+        &auth::ApiKeyAuth,
+    )
+    .await
+}
+
+/// Payments - Receipt
+///
+/// Retrieves the receipt of a successful payment, as an HTML page or a PDF document, with the receipt template and the branding of the business under which the payment was made
+#[utoipa::path(
+    get,
+    path = "/payments/{payment_id}/receipt",
+    params(
+        ("payment_id" = String, Path, description = "The identifier for payment"),
+        ("format" = Option<ReceiptFormat>, Query, description = "The format of the receipt document, defaults to `html`")
+    ),
+    responses(
+        (status = 200, description = "The receipt of the payment"),
+        (status = 400, description = "The payment has not succeeded"),
+        (status = 404, description = "Payment not found")
+    ),
+    tag = "Payments",
+    operation_id = "Retrieve the Receipt of a Payment",
+    security(("api_key" = []))
+)]
+#[instrument(skip_all, fields(flow = ?Flow::PaymentsReceiptRetrieve))]
+// #[get("/{payment_id}/receipt")]
+pub async fn payments_receipt_retrieve(
+    state: web::Data<app::AppState>,
+    req: actix_web::HttpRequest,
+    path: web::Path<String>,
+    query_payload: web::Query<payment_types::PaymentReceiptQuery>,
+) -> impl Responder {
+    let flow = Flow::PaymentsReceiptRetrieve;
+    let payment_id = path.into_inner();
+
+    api::server_wrap(
+        flow,
+        state.get_ref(),
+        &req,
+        query_payload.into_inner(),
+        |state, auth, req| {
+            payments::receipts::retrieve_payment_receipt(
+                state,
+                auth.merchant_account,
+                payment_id.clone(),
+                req,
+            )
+        },
+        auth::auth_type(&auth::ApiKeyAuth, &auth::JWTAuth, req.headers()),
+    )
+    .await
+}
+
+/// Payments - Retrieve Velocity Limits
+///
+/// Retrieves the limits on the number and the amount of the payments confirmed within a time window by the same customer, with the same card or from the same IP address
+#[utoipa::path(
+    get,
+    path = "/payments/velocity_limits",
+    responses(
+        (status = 200, description = "The velocity limits were retrieved successfully", body = PaymentVelocityLimits),
+        (status = 401, description = "Unauthorized request")
+    ),
+    tag = "Payments",
+    operation_id = "Retrieve Payment Velocity Limits",
+    security(("api_key" = []))
+)]
+#[instrument(skip_all, fields(flow = ?Flow::PaymentsVelocityLimitsRetrieve))]
+// #[get("/velocity_limits")]
+pub async fn payments_velocity_limits_retrieve(
+    state: web::Data<app::AppState>,
+    req: actix_web::HttpRequest,
+) -> impl Responder {
+    let flow = Flow::PaymentsVelocityLimitsRetrieve;
+    api::server_wrap(
+        flow,
+        state.get_ref(),
+        &req,
+        (),
+        |state, auth, _| {
+            payments::velocity_limits::retrieve_velocity_limits(state, auth.merchant_account)
+        },
+        auth::auth_type(&auth::ApiKeyAuth, &auth::JWTAuth, req.headers()),
+    )
+    .await
+}
+
+/// Payments - Update Velocity Limits
+///
+/// Replaces the limits on the number and the amount of the payments confirmed within a time window by the same customer, with the same card or from the same IP address. Payments exceeding any of the limits are declined before they are authorized
+#[utoipa::path(
+    post,
+    path = "/payments/velocity_limits",
+    request_body = PaymentVelocityLimits,
+    responses(
+        (status = 200, description = "The velocity limits were updated successfully", body = PaymentVelocityLimits),
+        (status = 400, description = "Invalid velocity limits")
+    ),
+    tag = "Payments",
+    operation_id = "Update Payment Velocity Limits",
+    security(("api_key" = []))
+)]
+#[instrument(skip_all, fields(flow = ?Flow::PaymentsVelocityLimitsUpdate))]
+// #[post("/velocity_limits")]
+pub async fn payments_velocity_limits_update(
+    state: web::Data<app::AppState>,
+    req: actix_web::HttpRequest,
+    json_payload: web::Json<payment_types::PaymentVelocityLimits>,
+) -> impl Responder {
+    let flow = Flow::PaymentsVelocityLimitsUpdate;
+    api::server_wrap(
+        flow,
+        state.get_ref(),
+        &req,
+        json_payload.into_inner(),
+        |state, auth, req| {
+            payments::velocity_limits::update_velocity_limits(state, auth.merchant_account, req)
+        },
+        auth::auth_type(&auth::ApiKeyAuth, &auth::JWTAuth, req.headers()),
+    )
+    .await
+}
+
+/// Payments - Retrieve Cross-Border Fee Config
+///
+/// Retrieves the estimated international transaction fee rate and the surcharges disclosed on the payments of customers in a country other than the business country of the payment
+#[utoipa::path(
+    get,
+    path = "/payments/cross_border_fees",
+    responses(
+        (status = 200, description = "The cross-border fee config was retrieved successfully", body = CrossBorderFeeConfig),
+        (status = 401, description = "Unauthorized request")
+    ),
+    tag = "Payments",
+    operation_id = "Retrieve Cross-Border Fee Config",
+    security(("api_key" = []))
+)]
+#[instrument(skip_all, fields(flow = ?Flow::PaymentsCrossBorderFeeConfigRetrieve))]
+// #[get("/cross_border_fees")]
+pub async fn payments_cross_border_fee_config_retrieve(
+    state: web::Data<app::AppState>,
+    req: actix_web::HttpRequest,
+) -> impl Responder {
+    let flow = Flow::PaymentsCrossBorderFeeConfigRetrieve;
+    api::server_wrap(
+        flow,
+        state.get_ref(),
+        &req,
+        (),
+        |state, auth, _| {
+            payments::cross_border_fees::retrieve_cross_border_fee_config(
+                state,
+                auth.merchant_account,
+            )
+        },
+        auth::auth_type(&auth::ApiKeyAuth, &auth::JWTAuth, req.headers()),
+    )
+    .await
+}
+
+/// Payments - Update Cross-Border Fee Config
+///
+/// Replaces the estimated international transaction fee rate and the surcharges disclosed on cross-border payments. The fees are computed when a payment is created, updated or confirmed, and the surcharge is included in the amount of the payment
+#[utoipa::path(
+    post,
+    path = "/payments/cross_border_fees",
+    request_body = CrossBorderFeeConfig,
+    responses(
+        (status = 200, description = "The cross-border fee config was updated successfully", body = CrossBorderFeeConfig),
+        (status = 400, description = "Invalid cross-border fee config")
+    ),
+    tag = "Payments",
+    operation_id = "Update Cross-Border Fee Config",
+    security(("api_key" = []))
+)]
+#[instrument(skip_all, fields(flow = ?Flow::PaymentsCrossBorderFeeConfigUpdate))]
+// #[post("/cross_border_fees")]
+pub async fn payments_cross_border_fee_config_update(
+    state: web::Data<app::AppState>,
+    req: actix_web::HttpRequest,
+    json_payload: web::Json<payment_types::CrossBorderFeeConfig>,
+) -> impl Responder {
+    let flow = Flow::PaymentsCrossBorderFeeConfigUpdate;
+    api::server_wrap(
+        flow,
+        state.get_ref(),
+        &req,
+        json_payload.into_inner(),
+        |state, auth, req| {
+            payments::cross_border_fees::update_cross_border_fee_config(
+                state,
+                auth.merchant_account,
+                req,
+            )
+        },
+        auth::auth_type(&auth::ApiKeyAuth, &auth::JWTAuth, req.headers()),
+    )
+    .await
+}
+
+/// Payments - Hosted Checkout
+///
+/// Renders the checkout page of a payment created without a payment method, where the customer selects a payment method and provides its details
+// #[utoipa::path(
+//     get,
+//     path = "/payments/{payment_id}/{merchant_id}/checkout",
+//     params(
+//         ("payment_id" = String, Path, description = "The identifier for payment"),
+//         ("merchant_id" = String, Path, description = "The identifier for merchant"),
+//         ("client_secret" = String, Query, description = "The client secret of the payment")
+//     ),
+//     responses(
+//         (status = 200, description = "The checkout page of the payment"),
+//         (status = 400, description = "Invalid client secret")
+//     ),
+//     tag = "Payments",
+//     operation_id = "Retrieve the Hosted Checkout Page"
+// )]
+#[instrument(skip_all, fields(flow = ?Flow::PaymentsHostedCheckout))]
+// #[get("/{payment_id}/{merchant_id}/checkout")]
+pub async fn payments_hosted_checkout(
+    state: web::Data<app::AppState>,
+    req: actix_web::HttpRequest,
+    query_payload: web::Query<payment_types::HostedCheckoutQuery>,
+    path: web::Path<(String, String)>,
+) -> impl Responder {
+    let flow = Flow::PaymentsHostedCheckout;
+    let (payment_id, merchant_id) = path.into_inner();
+
+    api::server_wrap(
+        flow,
+        state.get_ref(),
+        &req,
+        query_payload.into_inner(),
+        |state, auth, req| {
+            payments::hosted_checkout::retrieve_checkout_page(
+                state,
+                auth.merchant_account,
+                auth.key_store,
+                payment_id.clone(),
+                req,
+            )
+        },
+        &auth::MerchantIdAuth(merchant_id),
+    )
+    .await
+}
+
+/// Payments - Hosted Checkout Submit
+///
+/// Confirms a payment with the payment method details submitted on its checkout page
+// #[utoipa::path(
+//     post,
+//     path = "/payments/{payment_id}/{merchant_id}/checkout",
+//     params(
+//         ("payment_id" = String, Path, description = "The identifier for payment"),
+//         ("merchant_id" = String, Path, description = "The identifier for merchant")
+//     ),
+//     responses(
+//         (status = 200, description = "The status of the payment"),
+//         (status = 302, description = "Redirects to the authentication page or the return url of the payment")
+//     ),
+//     tag = "Payments",
+//     operation_id = "Submit the Hosted Checkout Page"
+// )]
+#[instrument(skip_all, fields(flow = ?Flow::PaymentsHostedCheckoutSubmit))]
+// #[post("/{payment_id}/{merchant_id}/checkout")]
+pub async fn payments_hosted_checkout_submit(
+    state: web::Data<app::AppState>,
+    req: actix_web::HttpRequest,
+    form_payload: web::Form<payment_types::HostedCheckoutSubmitRequest>,
+    path: web::Path<(String, String)>,
+) -> impl Responder {
+    let flow = Flow::PaymentsHostedCheckoutSubmit;
+    let (payment_id, merchant_id) = path.into_inner();
+
+    api::server_wrap(
+        flow,
+        state.get_ref(),
+        &req,
+        form_payload.into_inner(),
+        |state, auth, req| {
+            payments::hosted_checkout::submit_checkout_page(
+                state,
+                auth.merchant_account,
+                auth.key_store,
+                payment_id.clone(),
+                req,
+            )
+        },
+        &auth::MerchantIdAuth(merchant_id),
+    )
+    .await
+}
+
 /// Payments - Capture
 ///
 /// To capture the funds for an uncaptured payment
@@ -465,6 +995,57 @@ pub async fn payments_capture(
     .await
 }
 
+/// Payments - Incremental Authorization
+///
+/// To increase the amount authorized for an uncaptured payment, such as when a hotel stay or a car rental is extended. The previously authorized amount remains authorized if the connector declines the increased amount
+#[utoipa::path(
+    post,
+    path = "/payments/{payment_id}/incremental_authorization",
+    params(
+        ("payment_id" = String, Path, description = "The identifier for payment")
+    ),
+    request_body=PaymentsIncrementalAuthorizationRequest,
+    responses(
+        (status = 200, description = "Incremental authorization processed", body = PaymentsIncrementalAuthorizationResponse),
+        (status = 400, description = "Invalid amount or payment not awaiting capture"),
+        (status = 404, description = "Payment not found")
+    ),
+    tag = "Payments",
+    operation_id = "Increment the Authorized Amount of a Payment",
+    security(("api_key" = []))
+)]
+#[instrument(skip_all, fields(flow = ?Flow::PaymentsIncrementalAuthorization))]
+// #[post("/{payment_id}/incremental_authorization")]
+pub async fn payments_incremental_authorization(
+    state: web::Data<app::AppState>,
+    req: actix_web::HttpRequest,
+    json_payload: web::Json<payment_types::PaymentsIncrementalAuthorizationRequest>,
+    path: web::Path<String>,
+) -> impl Responder {
+    let flow = Flow::PaymentsIncrementalAuthorization;
+    let payload = payment_types::PaymentsIncrementalAuthorizationRequest {
+        payment_id: path.into_inner(),
+        ..json_payload.into_inner()
+    };
+
+    api::server_wrap(
+        flow,
+        state.get_ref(),
+        &req,
+        payload,
+        |state, auth, req| {
+            payments::incremental_authorization::incremental_authorize_payment(
+                state,
+                auth.merchant_account,
+                auth.key_store,
+                req,
+            )
+        },
+        &auth::ApiKeyAuth,
+    )
+    .await
+}
+
 /// Payments - Session token
 ///
 /// To create the session object or to get session token for wallets
@@ -516,6 +1097,53 @@ pub async fn payments_connector_session(
     .await
 }
 
+/// Payments - Session token refresh
+///
+/// To regenerate the session tokens of wallets for a payment still awaiting its payment method, once the previous session tokens have expired
+#[utoipa::path(
+    post,
+    path = "/payments/{payment_id}/session_tokens/refresh",
+    params(
+        ("payment_id" = String, Path, description = "The identifier for payment")
+    ),
+    request_body=PaymentsSessionRefreshRequest,
+    responses(
+        (status = 200, description = "Session tokens were regenerated", body = PaymentsSessionResponse),
+        (status = 400, description = "Missing mandatory fields")
+    ),
+    tag = "Payments",
+    operation_id = "Refresh Session tokens for a Payment",
+    security(("publishable_key" = []))
+)]
+#[instrument(skip_all, fields(flow = ?Flow::PaymentsSessionTokenRefresh))]
+pub async fn payments_session_tokens_refresh(
+    state: web::Data<app::AppState>,
+    req: actix_web::HttpRequest,
+    json_payload: web::Json<payment_types::PaymentsSessionRefreshRequest>,
+    path: web::Path<String>,
+) -> impl Responder {
+    let flow = Flow::PaymentsSessionTokenRefresh;
+    let payment_id = path.into_inner();
+
+    api::server_wrap(
+        flow,
+        state.get_ref(),
+        &req,
+        json_payload.into_inner(),
+        |state, auth, req| {
+            payments::session_tokens::refresh_session_tokens(
+                state,
+                auth.merchant_account,
+                auth.key_store,
+                payment_id.clone(),
+                req,
+            )
+        },
+        &auth::PublishableKeyAuth,
+    )
+    .await
+}
+
 // /// Payments - Redirect response
 // ///
 // /// To get the payment response for redirect flows
@@ -776,7 +1404,12 @@ pub async fn payments_list_by_filter(
         &req,
         payload,
         |state, auth, req| {
-            payments::apply_filters_on_payments(&*state.store, auth.merchant_account, req)
+            payments::apply_filters_on_payments(
+                &*state.store,
+                auth.merchant_account,
+                auth.key_store,
+                req,
+            )
         },
         &auth::ApiKeyAuth,
     )
@@ -810,7 +1443,7 @@ async fn authorize_verify_select<Op>(
     state: &app::AppState,
     merchant_account: domain::MerchantAccount,
     key_store: domain::MerchantKeyStore,
-    req: api_models::payments::PaymentsRequest,
+    mut req: api_models::payments::PaymentsRequest,
     auth_flow: api::AuthFlow,
 ) -> app::core::errors::RouterResponse<api_models::payments::PaymentsResponse>
 where
@@ -826,7 +1459,10 @@ where
     // the operation are flow agnostic, and the flow is only required in the post_update_tracker
     // Thus the flow can be generated just before calling the connector instead of explicitly passing it here.
 
-    match req.amount.as_ref() {
+    let payment_method_share_use =
+        sharing::use_shared_payment_method(state, &merchant_account, &mut req).await?;
+
+    let response = match req.amount.as_ref() {
         Some(api_types::Amount::Value(_)) | None => payments::payments_core::<
             api_types::Authorize,
             payment_types::PaymentsResponse,
@@ -856,5 +1492,11 @@ where
             )
             .await
         }
+    };
+
+    if let Some(payment_method_share_use) = payment_method_share_use {
+        sharing::complete_shared_payment_method_use(state, payment_method_share_use, &response)
+            .await;
     }
+    response
 }
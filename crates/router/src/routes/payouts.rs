@@ -197,6 +197,120 @@ pub async fn payouts_fulfill(
     .await
 }
 
+/// Payouts - Sync
+///
+/// Sync the status of a payout with the payout connector
+#[cfg(feature = "payouts")]
+#[utoipa::path(
+    post,
+    path = "/payouts/{payout_id}/sync",
+    params(
+        ("payout_id" = String, Path, description = "The identifier for payout")
+    ),
+    responses(
+        (status = 200, description = "Payout synced", body = PayoutCreateResponse),
+        (status = 400, description = "Payout has not been created at the connector"),
+        (status = 404, description = "Payout does not exist in our records")
+    ),
+    tag = "Payouts",
+    operation_id = "Sync a Payout",
+    security(("api_key" = []))
+)]
+#[instrument(skip_all, fields(flow = ?Flow::PayoutsSync))]
+pub async fn payouts_sync(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<String>,
+) -> HttpResponse {
+    let flow = Flow::PayoutsSync;
+    let payload = payout_types::PayoutActionRequest {
+        payout_id: path.into_inner(),
+    };
+
+    api::server_wrap(
+        flow,
+        state.get_ref(),
+        &req,
+        payload,
+        |state, auth, req| payouts_sync_core(state, auth.merchant_account, auth.key_store, req),
+        &auth::ApiKeyAuth,
+    )
+    .await
+}
+
+/// Payouts - Batch Create
+///
+/// Create a batch of payouts. The payouts are validated before any of them is created, and are
+/// then created concurrently
+#[cfg(feature = "payouts")]
+#[utoipa::path(
+    post,
+    path = "/payouts/batch",
+    request_body=PayoutBatchCreateRequest,
+    responses(
+        (status = 200, description = "Payout batch created", body = PayoutBatchResponse),
+        (status = 400, description = "Invalid payout in the batch, or a batch with the same idempotency key is being created")
+    ),
+    tag = "Payouts",
+    operation_id = "Create a Payout Batch",
+    security(("api_key" = []))
+)]
+#[instrument(skip_all, fields(flow = ?Flow::PayoutsBatchCreate))]
+pub async fn payouts_batch_create(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    json_payload: web::Json<payout_types::PayoutBatchCreateRequest>,
+) -> HttpResponse {
+    let flow = Flow::PayoutsBatchCreate;
+    api::server_wrap(
+        flow,
+        state.get_ref(),
+        &req,
+        json_payload.into_inner(),
+        |state, auth, req| {
+            payouts_batch_create_core(state, auth.merchant_account, auth.key_store, req)
+        },
+        &auth::ApiKeyAuth,
+    )
+    .await
+}
+
+/// Payouts - Batch Retrieve
+///
+/// Retrieve a batch of payouts, along with the statuses of its payouts
+#[cfg(feature = "payouts")]
+#[utoipa::path(
+    get,
+    path = "/payouts/batch/{batch_id}",
+    params(
+        ("batch_id" = String, Path, description = "The identifier for the payout batch")
+    ),
+    responses(
+        (status = 200, description = "Payout batch retrieved", body = PayoutBatchResponse),
+        (status = 404, description = "Payout batch does not exist in our records")
+    ),
+    tag = "Payouts",
+    operation_id = "Retrieve a Payout Batch",
+    security(("api_key" = []))
+)]
+#[instrument(skip_all, fields(flow = ?Flow::PayoutsBatchRetrieve))]
+pub async fn payouts_batch_retrieve(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<String>,
+) -> HttpResponse {
+    let flow = Flow::PayoutsBatchRetrieve;
+    api::server_wrap(
+        flow,
+        state.get_ref(),
+        &req,
+        path.into_inner(),
+        |state, auth, batch_id| payouts_batch_retrieve_core(state, auth.merchant_account, batch_id),
+        &auth::ApiKeyAuth,
+    )
+    .await
+}
+
 #[instrument(skip_all, fields(flow = ?Flow::PayoutsAccounts))]
 // #[get("/accounts")]
 pub async fn payouts_accounts() -> impl Responder {
@@ -3,6 +3,7 @@ use router_env::{instrument, tracing, Flow};
 
 use super::app::AppState;
 use crate::{
+    consts,
     core::refunds::*,
     services::{api, authentication as auth},
     types::api::refunds,
@@ -70,6 +71,7 @@ pub async fn refunds_retrieve(
     let refund_request = refunds::RefundsRetrieveRequest {
         refund_id: path.into_inner(),
         force_sync: query_params.force_sync,
+        bypass_sync_cache: query_params.bypass_sync_cache,
         merchant_connector_details: None,
     };
     let flow = Flow::RefundsRetrieve;
@@ -93,6 +95,51 @@ pub async fn refunds_retrieve(
     .await
 }
 
+/// Refunds - Retrieve by Merchant Refund Reference
+///
+/// To retrieve the properties of a Refund using the reference provided by the merchant when creating the refund
+#[utoipa::path(
+    get,
+    path = "/refunds/reference/{merchant_refund_reference}",
+    params(
+        ("merchant_refund_reference" = String, Path, description = "The reference for the refund provided by the merchant")
+    ),
+    responses(
+        (status = 200, description = "Refund retrieved", body = RefundResponse),
+        (status = 404, description = "Refund does not exist in our records")
+    ),
+    tag = "Refunds",
+    operation_id = "Retrieve a Refund by Merchant Refund Reference",
+    security(("api_key" = []))
+)]
+#[instrument(skip_all, fields(flow = ?Flow::RefundsRetrieve))]
+// #[get("/reference/{merchant_refund_reference}")]
+pub async fn refunds_retrieve_by_merchant_refund_reference(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<String>,
+    query_params: web::Query<api_models::refunds::RefundsRetrieveBody>,
+) -> HttpResponse {
+    let flow = Flow::RefundsRetrieve;
+    api::server_wrap(
+        flow,
+        state.get_ref(),
+        &req,
+        (path.into_inner(), query_params.into_inner()),
+        |state, auth, request| {
+            refund_response_wrapper(
+                state,
+                auth.merchant_account,
+                auth.key_store,
+                request,
+                refund_retrieve_by_merchant_refund_reference_core,
+            )
+        },
+        &auth::ApiKeyAuth,
+    )
+    .await
+}
+
 /// Refunds - Retrieve (POST)
 ///
 /// To retrieve the properties of a Refund. This may be used to get the status of a previously initiated payment or next action for an ongoing payment
@@ -175,6 +222,128 @@ pub async fn refunds_update(
     .await
 }
 
+/// Refunds - Cancel
+///
+/// To cancel a scheduled Refund before it is sent to the connector
+#[utoipa::path(
+    post,
+    path = "/refunds/{refund_id}/cancel",
+    params(
+        ("refund_id" = String, Path, description = "The identifier for refund")
+    ),
+    responses(
+        (status = 200, description = "Refund cancelled", body = RefundResponse),
+        (status = 400, description = "Refund is not scheduled or has already been processed"),
+        (status = 404, description = "Refund does not exist in our records")
+    ),
+    tag = "Refunds",
+    operation_id = "Cancel a Refund",
+    security(("api_key" = []))
+)]
+#[instrument(skip_all, fields(flow = ?Flow::RefundsCancel))]
+// #[post("/{id}/cancel")]
+pub async fn refunds_cancel(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<String>,
+) -> HttpResponse {
+    let flow = Flow::RefundsCancel;
+    let refund_id = path.into_inner();
+    api::server_wrap(
+        flow,
+        state.get_ref(),
+        &req,
+        refund_id,
+        |state, auth, refund_id| refund_cancel_core(state, auth.merchant_account, refund_id),
+        &auth::ApiKeyAuth,
+    )
+    .await
+}
+
+/// Refunds - Approve
+///
+/// To approve a Refund held for approval, sending it to the connector. Refunds can only be approved
+/// by users of the dashboard holding an approver role, and not with the API keys used to create
+/// them.
+#[utoipa::path(
+    post,
+    path = "/refunds/{refund_id}/approve",
+    params(
+        ("refund_id" = String, Path, description = "The identifier for refund")
+    ),
+    responses(
+        (status = 200, description = "Refund approved", body = RefundResponse),
+        (status = 400, description = "Refund is not pending approval"),
+        (status = 403, description = "User is not allowed to approve or reject refunds"),
+        (status = 404, description = "Refund does not exist in our records")
+    ),
+    tag = "Refunds",
+    operation_id = "Approve a Refund",
+    security(("jwt_key" = []))
+)]
+#[instrument(skip_all, fields(flow = ?Flow::RefundsApprove))]
+// #[post("/{id}/approve")]
+pub async fn refunds_approve(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<String>,
+) -> HttpResponse {
+    let flow = Flow::RefundsApprove;
+    let refund_id = path.into_inner();
+    api::server_wrap(
+        flow,
+        state.get_ref(),
+        &req,
+        refund_id,
+        |state, auth, refund_id| {
+            refund_approve_core(state, auth.merchant_account, auth.key_store, refund_id)
+        },
+        &auth::JWTAuthWithRoles(consts::REFUND_APPROVER_ROLES),
+    )
+    .await
+}
+
+/// Refunds - Reject
+///
+/// To reject a Refund held for approval, so that it is never sent to the connector. Refunds can
+/// only be rejected by users of the dashboard holding an approver role, and not with the API keys
+/// used to create them.
+#[utoipa::path(
+    post,
+    path = "/refunds/{refund_id}/reject",
+    params(
+        ("refund_id" = String, Path, description = "The identifier for refund")
+    ),
+    responses(
+        (status = 200, description = "Refund rejected", body = RefundResponse),
+        (status = 400, description = "Refund is not pending approval"),
+        (status = 403, description = "User is not allowed to approve or reject refunds"),
+        (status = 404, description = "Refund does not exist in our records")
+    ),
+    tag = "Refunds",
+    operation_id = "Reject a Refund",
+    security(("jwt_key" = []))
+)]
+#[instrument(skip_all, fields(flow = ?Flow::RefundsReject))]
+// #[post("/{id}/reject")]
+pub async fn refunds_reject(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<String>,
+) -> HttpResponse {
+    let flow = Flow::RefundsReject;
+    let refund_id = path.into_inner();
+    api::server_wrap(
+        flow,
+        state.get_ref(),
+        &req,
+        refund_id,
+        |state, auth, refund_id| refund_reject_core(state, auth.merchant_account, refund_id),
+        &auth::JWTAuthWithRoles(consts::REFUND_APPROVER_ROLES),
+    )
+    .await
+}
+
 /// Refunds - List
 ///
 /// To list the refunds associated with a payment_id or with the merchant, if payment_id is not provided
@@ -0,0 +1,193 @@
+use actix_web::{web, HttpRequest, Responder};
+use router_env::{instrument, tracing, Flow};
+
+use super::app::AppState;
+use crate::{
+    core::reports,
+    services::{api, authentication as auth},
+    types::api::reports as report_types,
+};
+
+/// Reports - Create Schedule
+///
+/// Schedule a report to be generated daily or weekly, and delivered via webhook or email.
+#[utoipa::path(
+    post,
+    path = "/reports/schedules",
+    request_body = ReportScheduleCreateRequest,
+    responses(
+        (status = 200, description = "Report scheduled", body = ReportScheduleResponse),
+        (status = 400, description = "Invalid data")
+    ),
+    tag = "Reports",
+    operation_id = "Create a Report Schedule",
+    security(("api_key" = []))
+)]
+#[instrument(skip_all, fields(flow = ?Flow::ReportSchedulesCreate))]
+pub async fn report_schedules_create(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    json_payload: web::Json<report_types::ReportScheduleCreateRequest>,
+) -> impl Responder {
+    let flow = Flow::ReportSchedulesCreate;
+    api::server_wrap(
+        flow,
+        state.get_ref(),
+        &req,
+        json_payload.into_inner(),
+        |state, auth, req| reports::create_report_schedule(state, auth.merchant_account, req),
+        &auth::ApiKeyAuth,
+    )
+    .await
+}
+
+/// Reports - Retrieve Schedule
+///
+/// Retrieve a report schedule, along with the file of the last generated report.
+#[utoipa::path(
+    get,
+    path = "/reports/schedules/{report_schedule_id}",
+    params(("report_schedule_id" = String, Path, description = "The identifier for the report schedule")),
+    responses(
+        (status = 200, description = "Report schedule retrieved", body = ReportScheduleResponse),
+        (status = 404, description = "Report schedule not found")
+    ),
+    tag = "Reports",
+    operation_id = "Retrieve a Report Schedule",
+    security(("api_key" = []))
+)]
+#[instrument(skip_all, fields(flow = ?Flow::ReportSchedulesRetrieve))]
+pub async fn report_schedules_retrieve(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<String>,
+) -> impl Responder {
+    let flow = Flow::ReportSchedulesRetrieve;
+    api::server_wrap(
+        flow,
+        state.get_ref(),
+        &req,
+        path.into_inner(),
+        |state, auth, report_schedule_id| {
+            reports::retrieve_report_schedule(state, auth.merchant_account, report_schedule_id)
+        },
+        auth::auth_type(&auth::ApiKeyAuth, &auth::JWTAuth, req.headers()),
+    )
+    .await
+}
+
+/// Reports - Update Schedule
+///
+/// Update the template, filters or delivery of a report schedule, or pause and resume it.
+#[utoipa::path(
+    post,
+    path = "/reports/schedules/{report_schedule_id}",
+    params(("report_schedule_id" = String, Path, description = "The identifier for the report schedule")),
+    request_body = ReportScheduleUpdateRequest,
+    responses(
+        (status = 200, description = "Report schedule updated", body = ReportScheduleResponse),
+        (status = 400, description = "Invalid data"),
+        (status = 404, description = "Report schedule not found")
+    ),
+    tag = "Reports",
+    operation_id = "Update a Report Schedule",
+    security(("api_key" = []))
+)]
+#[instrument(skip_all, fields(flow = ?Flow::ReportSchedulesUpdate))]
+pub async fn report_schedules_update(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<String>,
+    json_payload: web::Json<report_types::ReportScheduleUpdateRequest>,
+) -> impl Responder {
+    let flow = Flow::ReportSchedulesUpdate;
+    let report_schedule_id = path.into_inner();
+    api::server_wrap(
+        flow,
+        state.get_ref(),
+        &req,
+        json_payload.into_inner(),
+        |state, auth, req| {
+            reports::update_report_schedule(
+                state,
+                auth.merchant_account,
+                report_schedule_id.clone(),
+                req,
+            )
+        },
+        &auth::ApiKeyAuth,
+    )
+    .await
+}
+
+/// Reports - Delete Schedule
+///
+/// Delete a report schedule. Reports which have already been generated remain available.
+#[utoipa::path(
+    delete,
+    path = "/reports/schedules/{report_schedule_id}",
+    params(("report_schedule_id" = String, Path, description = "The identifier for the report schedule")),
+    responses(
+        (status = 200, description = "Report schedule deleted", body = ReportScheduleDeleteResponse),
+        (status = 404, description = "Report schedule not found")
+    ),
+    tag = "Reports",
+    operation_id = "Delete a Report Schedule",
+    security(("api_key" = []))
+)]
+#[instrument(skip_all, fields(flow = ?Flow::ReportSchedulesDelete))]
+pub async fn report_schedules_delete(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<String>,
+) -> impl Responder {
+    let flow = Flow::ReportSchedulesDelete;
+    api::server_wrap(
+        flow,
+        state.get_ref(),
+        &req,
+        path.into_inner(),
+        |state, auth, report_schedule_id| {
+            reports::delete_report_schedule(state, auth.merchant_account, report_schedule_id)
+        },
+        &auth::ApiKeyAuth,
+    )
+    .await
+}
+
+/// Reports - List Schedules
+///
+/// List the report schedules of your merchant account.
+#[utoipa::path(
+    get,
+    path = "/reports/schedules/list",
+    params(
+        ("limit" = Option<i64>, Query, description = "The maximum number of report schedules to include in the response"),
+        ("skip" = Option<i64>, Query, description = "The number of report schedules to skip when retrieving the list of report schedules"),
+    ),
+    responses(
+        (status = 200, description = "List of report schedules retrieved successfully", body = Vec<ReportScheduleResponse>),
+    ),
+    tag = "Reports",
+    operation_id = "List all Report Schedules",
+    security(("api_key" = []))
+)]
+#[instrument(skip_all, fields(flow = ?Flow::ReportSchedulesList))]
+pub async fn report_schedules_list(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    query: web::Query<report_types::ReportScheduleListConstraints>,
+) -> impl Responder {
+    let flow = Flow::ReportSchedulesList;
+    api::server_wrap(
+        flow,
+        state.get_ref(),
+        &req,
+        query.into_inner(),
+        |state, auth, constraints| {
+            reports::list_report_schedules(state, auth.merchant_account, constraints)
+        },
+        auth::auth_type(&auth::ApiKeyAuth, &auth::JWTAuth, req.headers()),
+    )
+    .await
+}
@@ -0,0 +1,106 @@
+use actix_web::{web, HttpRequest, HttpResponse};
+use api_models::routing as routing_models;
+use router_env::{instrument, tracing, Flow};
+
+use super::app::AppState;
+use crate::{
+    core::payments::routing,
+    services::{api, authentication as auth},
+};
+
+/// Routing - Retrieve Fee Schedules
+///
+/// Retrieves the fees charged by the connectors of the merchant, by which cost based routing picks the cheapest connector for a payment
+#[utoipa::path(
+    get,
+    path = "/routing/fee_schedules",
+    responses(
+        (status = 200, description = "The fee schedules were retrieved successfully", body = ConnectorFeeSchedules),
+        (status = 401, description = "Unauthorized request")
+    ),
+    tag = "Routing",
+    operation_id = "Retrieve Connector Fee Schedules",
+    security(("api_key" = []))
+)]
+#[instrument(skip_all, fields(flow = ?Flow::RoutingFeeSchedulesRetrieve))]
+pub async fn routing_fee_schedules_retrieve(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+) -> HttpResponse {
+    let flow = Flow::RoutingFeeSchedulesRetrieve;
+    api::server_wrap(
+        flow,
+        state.get_ref(),
+        &req,
+        (),
+        |state, auth, _| routing::retrieve_fee_schedules(state, auth.merchant_account),
+        auth::auth_type(&auth::ApiKeyAuth, &auth::JWTAuth, req.headers()),
+    )
+    .await
+}
+
+/// Routing - Update Fee Schedules
+///
+/// Replaces the fees charged by the connectors of the merchant. A payment is charged the first fee of the connector applicable to it, by currency, card network and country
+#[utoipa::path(
+    post,
+    path = "/routing/fee_schedules",
+    request_body = ConnectorFeeSchedules,
+    responses(
+        (status = 200, description = "The fee schedules were updated successfully", body = ConnectorFeeSchedules),
+        (status = 400, description = "Invalid fee schedules")
+    ),
+    tag = "Routing",
+    operation_id = "Update Connector Fee Schedules",
+    security(("api_key" = []))
+)]
+#[instrument(skip_all, fields(flow = ?Flow::RoutingFeeSchedulesUpdate))]
+pub async fn routing_fee_schedules_update(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    json_payload: web::Json<routing_models::ConnectorFeeSchedules>,
+) -> HttpResponse {
+    let flow = Flow::RoutingFeeSchedulesUpdate;
+    api::server_wrap(
+        flow,
+        state.get_ref(),
+        &req,
+        json_payload.into_inner(),
+        |state, auth, req| routing::update_fee_schedules(state, auth.merchant_account, req),
+        auth::auth_type(&auth::ApiKeyAuth, &auth::JWTAuth, req.headers()),
+    )
+    .await
+}
+
+/// Routing - Simulate
+///
+/// Previews the connector a payment with the given details would be routed to by the routing algorithm of the merchant, along with the expected cost of the payment with each connector of cost based routing
+#[utoipa::path(
+    post,
+    path = "/routing/simulate",
+    request_body = RoutingSimulationRequest,
+    responses(
+        (status = 200, description = "The routing decision was previewed successfully", body = RoutingSimulationResponse),
+        (status = 412, description = "No routing algorithm has been configured")
+    ),
+    tag = "Routing",
+    operation_id = "Simulate Routing",
+    security(("api_key" = []))
+)]
+#[instrument(skip_all, fields(flow = ?Flow::RoutingSimulate))]
+pub async fn routing_simulate(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    json_payload: web::Json<routing_models::RoutingSimulationRequest>,
+) -> HttpResponse {
+    let flow = Flow::RoutingSimulate;
+    api::server_wrap(
+        flow,
+        state.get_ref(),
+        &req,
+        json_payload.into_inner(),
+        |state, auth, req| routing::simulate_routing(state, auth.merchant_account, req),
+        auth::auth_type(&auth::ApiKeyAuth, &auth::JWTAuth, req.headers()),
+    )
+    .await
+}
@@ -0,0 +1,23 @@
+use actix_web::{web, HttpRequest, Responder};
+use router_env::{instrument, tracing, Flow};
+
+use super::AppState;
+use crate::{
+    core::runtime_config,
+    services::{api, authentication as auth},
+};
+
+#[instrument(skip_all, fields(flow = ?Flow::RuntimeConfigReload))]
+pub async fn reload_runtime_config(state: web::Data<AppState>, req: HttpRequest) -> impl Responder {
+    let flow = Flow::RuntimeConfigReload;
+
+    api::server_wrap(
+        flow,
+        state.get_ref(),
+        &req,
+        (),
+        |state, _, _| runtime_config::reload_runtime_config(state),
+        &auth::AdminApiAuth,
+    )
+    .await
+}
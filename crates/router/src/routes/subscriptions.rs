@@ -0,0 +1,331 @@
+use actix_web::{web, HttpRequest, Responder};
+use router_env::{instrument, tracing, Flow};
+
+use super::app::AppState;
+use crate::{
+    core::subscriptions,
+    services::{api, authentication as auth},
+    types::api::subscriptions as subscription_types,
+};
+
+/// Subscriptions - Create
+///
+/// Create a subscription which bills a customer a fixed amount on every billing cycle, by creating off-session payments against a mandate of the customer. The first billing cycle is billed at `start_at`, or right away if it is not passed.
+#[utoipa::path(
+    post,
+    path = "/subscriptions",
+    request_body = SubscriptionCreateRequest,
+    responses(
+        (status = 200, description = "Subscription created", body = SubscriptionResponse),
+        (status = 400, description = "Invalid data")
+    ),
+    tag = "Subscriptions",
+    operation_id = "Create a Subscription",
+    security(("api_key" = []))
+)]
+#[instrument(skip_all, fields(flow = ?Flow::SubscriptionsCreate))]
+pub async fn subscriptions_create(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    json_payload: web::Json<subscription_types::SubscriptionCreateRequest>,
+) -> impl Responder {
+    let flow = Flow::SubscriptionsCreate;
+    api::server_wrap(
+        flow,
+        state.get_ref(),
+        &req,
+        json_payload.into_inner(),
+        |state, auth, req| subscriptions::create_subscription(state, auth.merchant_account, req),
+        &auth::ApiKeyAuth,
+    )
+    .await
+}
+
+/// Subscriptions - Retrieve
+///
+/// Retrieve a subscription, along with its next billing time and the last payment created for it.
+#[utoipa::path(
+    get,
+    path = "/subscriptions/{subscription_id}",
+    params(("subscription_id" = String, Path, description = "The identifier for the subscription")),
+    responses(
+        (status = 200, description = "Subscription retrieved", body = SubscriptionResponse),
+        (status = 404, description = "Subscription not found")
+    ),
+    tag = "Subscriptions",
+    operation_id = "Retrieve a Subscription",
+    security(("api_key" = []))
+)]
+#[instrument(skip_all, fields(flow = ?Flow::SubscriptionsRetrieve))]
+pub async fn subscriptions_retrieve(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<String>,
+) -> impl Responder {
+    let flow = Flow::SubscriptionsRetrieve;
+    api::server_wrap(
+        flow,
+        state.get_ref(),
+        &req,
+        path.into_inner(),
+        |state, auth, subscription_id| {
+            subscriptions::retrieve_subscription(state, auth.merchant_account, subscription_id)
+        },
+        auth::auth_type(&auth::ApiKeyAuth, &auth::JWTAuth, req.headers()),
+    )
+    .await
+}
+
+/// Subscriptions - Update
+///
+/// Update the amount, mandate or description of a subscription. The changes apply from the next billing cycle.
+#[utoipa::path(
+    post,
+    path = "/subscriptions/{subscription_id}",
+    params(("subscription_id" = String, Path, description = "The identifier for the subscription")),
+    request_body = SubscriptionUpdateRequest,
+    responses(
+        (status = 200, description = "Subscription updated", body = SubscriptionResponse),
+        (status = 400, description = "Invalid data"),
+        (status = 404, description = "Subscription not found")
+    ),
+    tag = "Subscriptions",
+    operation_id = "Update a Subscription",
+    security(("api_key" = []))
+)]
+#[instrument(skip_all, fields(flow = ?Flow::SubscriptionsUpdate))]
+pub async fn subscriptions_update(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<String>,
+    json_payload: web::Json<subscription_types::SubscriptionUpdateRequest>,
+) -> impl Responder {
+    let flow = Flow::SubscriptionsUpdate;
+    let subscription_id = path.into_inner();
+    api::server_wrap(
+        flow,
+        state.get_ref(),
+        &req,
+        json_payload.into_inner(),
+        |state, auth, req| {
+            subscriptions::update_subscription_details(
+                state,
+                auth.merchant_account,
+                subscription_id.clone(),
+                req,
+            )
+        },
+        &auth::ApiKeyAuth,
+    )
+    .await
+}
+
+/// Subscriptions - Cancel
+///
+/// Cancel a subscription, after which the customer is no longer billed.
+#[utoipa::path(
+    post,
+    path = "/subscriptions/{subscription_id}/cancel",
+    params(("subscription_id" = String, Path, description = "The identifier for the subscription")),
+    responses(
+        (status = 200, description = "Subscription cancelled", body = SubscriptionResponse),
+        (status = 404, description = "Subscription not found"),
+        (status = 400, description = "The subscription has already been cancelled")
+    ),
+    tag = "Subscriptions",
+    operation_id = "Cancel a Subscription",
+    security(("api_key" = []))
+)]
+#[instrument(skip_all, fields(flow = ?Flow::SubscriptionsCancel))]
+pub async fn subscriptions_cancel(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<String>,
+) -> impl Responder {
+    let flow = Flow::SubscriptionsCancel;
+    api::server_wrap(
+        flow,
+        state.get_ref(),
+        &req,
+        path.into_inner(),
+        |state, auth, subscription_id| {
+            subscriptions::cancel_subscription(state, auth.merchant_account, subscription_id)
+        },
+        &auth::ApiKeyAuth,
+    )
+    .await
+}
+
+/// Subscriptions - Pause
+///
+/// Pause the billing of an active or past due subscription.
+#[utoipa::path(
+    post,
+    path = "/subscriptions/{subscription_id}/pause",
+    params(("subscription_id" = String, Path, description = "The identifier for the subscription")),
+    responses(
+        (status = 200, description = "Subscription paused", body = SubscriptionResponse),
+        (status = 404, description = "Subscription not found"),
+        (status = 400, description = "The subscription is not active")
+    ),
+    tag = "Subscriptions",
+    operation_id = "Pause a Subscription",
+    security(("api_key" = []))
+)]
+#[instrument(skip_all, fields(flow = ?Flow::SubscriptionsPause))]
+pub async fn subscriptions_pause(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<String>,
+) -> impl Responder {
+    let flow = Flow::SubscriptionsPause;
+    api::server_wrap(
+        flow,
+        state.get_ref(),
+        &req,
+        path.into_inner(),
+        |state, auth, subscription_id| {
+            subscriptions::pause_subscription(state, auth.merchant_account, subscription_id)
+        },
+        &auth::ApiKeyAuth,
+    )
+    .await
+}
+
+/// Subscriptions - Resume
+///
+/// Resume the billing of a paused subscription from its next billing cycle. Billing cycles which were due while the subscription was paused are not billed.
+#[utoipa::path(
+    post,
+    path = "/subscriptions/{subscription_id}/resume",
+    params(("subscription_id" = String, Path, description = "The identifier for the subscription")),
+    responses(
+        (status = 200, description = "Subscription resumed", body = SubscriptionResponse),
+        (status = 404, description = "Subscription not found"),
+        (status = 400, description = "The subscription is not paused")
+    ),
+    tag = "Subscriptions",
+    operation_id = "Resume a Subscription",
+    security(("api_key" = []))
+)]
+#[instrument(skip_all, fields(flow = ?Flow::SubscriptionsResume))]
+pub async fn subscriptions_resume(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<String>,
+) -> impl Responder {
+    let flow = Flow::SubscriptionsResume;
+    api::server_wrap(
+        flow,
+        state.get_ref(),
+        &req,
+        path.into_inner(),
+        |state, auth, subscription_id| {
+            subscriptions::resume_subscription(state, auth.merchant_account, subscription_id)
+        },
+        &auth::ApiKeyAuth,
+    )
+    .await
+}
+
+/// Subscriptions - List
+///
+/// List the subscriptions of your merchant account.
+#[utoipa::path(
+    get,
+    path = "/subscriptions/list",
+    params(
+        ("customer_id" = Option<String>, Query, description = "Only include the subscriptions of this customer"),
+        ("limit" = Option<i64>, Query, description = "The maximum number of subscriptions to include in the response"),
+        ("skip" = Option<i64>, Query, description = "The number of subscriptions to skip when retrieving the list of subscriptions"),
+    ),
+    responses(
+        (status = 200, description = "List of subscriptions retrieved successfully", body = Vec<SubscriptionResponse>),
+    ),
+    tag = "Subscriptions",
+    operation_id = "List all Subscriptions",
+    security(("api_key" = []))
+)]
+#[instrument(skip_all, fields(flow = ?Flow::SubscriptionsList))]
+pub async fn subscriptions_list(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    query: web::Query<subscription_types::SubscriptionListConstraints>,
+) -> impl Responder {
+    let flow = Flow::SubscriptionsList;
+    api::server_wrap(
+        flow,
+        state.get_ref(),
+        &req,
+        query.into_inner(),
+        |state, auth, constraints| {
+            subscriptions::list_subscriptions(state, auth.merchant_account, constraints)
+        },
+        auth::auth_type(&auth::ApiKeyAuth, &auth::JWTAuth, req.headers()),
+    )
+    .await
+}
+
+/// Subscriptions - Retrieve Dunning Configuration
+///
+/// Retrieve the configuration of the retries of failed subscription payments of your merchant account
+#[utoipa::path(
+    get,
+    path = "/subscriptions/dunning_config",
+    responses(
+        (status = 200, description = "The dunning configuration was retrieved successfully", body = SubscriptionDunningConfig),
+        (status = 401, description = "Unauthorized request")
+    ),
+    tag = "Subscriptions",
+    operation_id = "Retrieve the Subscription Dunning Configuration",
+    security(("api_key" = []))
+)]
+#[instrument(skip_all, fields(flow = ?Flow::SubscriptionsDunningConfigRetrieve))]
+pub async fn subscriptions_dunning_config_retrieve(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+) -> impl Responder {
+    let flow = Flow::SubscriptionsDunningConfigRetrieve;
+    api::server_wrap(
+        flow,
+        state.get_ref(),
+        &req,
+        (),
+        |state, auth, _| subscriptions::retrieve_dunning_config(state, auth.merchant_account),
+        auth::auth_type(&auth::ApiKeyAuth, &auth::JWTAuth, req.headers()),
+    )
+    .await
+}
+
+/// Subscriptions - Update Dunning Configuration
+///
+/// Replace the configuration of the retries of failed subscription payments of your merchant account. A failed payment is retried after each of the configured delays in turn, after which the subscription is cancelled or paused according to the final action
+#[utoipa::path(
+    post,
+    path = "/subscriptions/dunning_config",
+    request_body = SubscriptionDunningConfig,
+    responses(
+        (status = 200, description = "The dunning configuration was updated successfully", body = SubscriptionDunningConfig),
+        (status = 400, description = "Invalid dunning configuration")
+    ),
+    tag = "Subscriptions",
+    operation_id = "Update the Subscription Dunning Configuration",
+    security(("api_key" = []))
+)]
+#[instrument(skip_all, fields(flow = ?Flow::SubscriptionsDunningConfigUpdate))]
+pub async fn subscriptions_dunning_config_update(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    json_payload: web::Json<subscription_types::SubscriptionDunningConfig>,
+) -> impl Responder {
+    let flow = Flow::SubscriptionsDunningConfigUpdate;
+    api::server_wrap(
+        flow,
+        state.get_ref(),
+        &req,
+        json_payload.into_inner(),
+        |state, auth, req| subscriptions::update_dunning_config(state, auth.merchant_account, req),
+        auth::auth_type(&auth::ApiKeyAuth, &auth::JWTAuth, req.headers()),
+    )
+    .await
+}
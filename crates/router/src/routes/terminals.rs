@@ -0,0 +1,195 @@
+use actix_web::{web, HttpRequest, Responder};
+use router_env::{instrument, tracing, Flow};
+
+use super::app::AppState;
+use crate::{
+    core::terminals,
+    services::{api, authentication as auth},
+    types::api::terminals as terminal_types,
+};
+
+/// Terminals - Create
+///
+/// Register a card-present terminal against your merchant account.
+#[utoipa::path(
+    post,
+    path = "/terminals",
+    request_body = TerminalCreateRequest,
+    responses(
+        (status = 200, description = "Terminal registered", body = TerminalResponse),
+        (status = 400, description = "Invalid data")
+    ),
+    tag = "Terminals",
+    operation_id = "Register a Terminal",
+    security(("api_key" = []))
+)]
+#[instrument(skip_all, fields(flow = ?Flow::TerminalsCreate))]
+pub async fn terminals_create(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    json_payload: web::Json<terminal_types::TerminalCreateRequest>,
+) -> impl Responder {
+    let flow = Flow::TerminalsCreate;
+    api::server_wrap(
+        flow,
+        state.get_ref(),
+        &req,
+        json_payload.into_inner(),
+        |state, auth, req| {
+            terminals::create_terminal(state, auth.merchant_account, auth.key_store, req)
+        },
+        &auth::ApiKeyAuth,
+    )
+    .await
+}
+
+/// Terminals - Retrieve
+///
+/// Retrieve a registered terminal.
+#[utoipa::path(
+    get,
+    path = "/terminals/{terminal_id}",
+    params(("terminal_id" = String, Path, description = "The identifier for the terminal")),
+    responses(
+        (status = 200, description = "Terminal retrieved", body = TerminalResponse),
+        (status = 404, description = "Terminal not found")
+    ),
+    tag = "Terminals",
+    operation_id = "Retrieve a Terminal",
+    security(("api_key" = []))
+)]
+#[instrument(skip_all, fields(flow = ?Flow::TerminalsRetrieve))]
+pub async fn terminals_retrieve(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<String>,
+) -> impl Responder {
+    let flow = Flow::TerminalsRetrieve;
+    api::server_wrap(
+        flow,
+        state.get_ref(),
+        &req,
+        path.into_inner(),
+        |state, auth, terminal_id| {
+            terminals::retrieve_terminal(state, auth.merchant_account, terminal_id)
+        },
+        &auth::ApiKeyAuth,
+    )
+    .await
+}
+
+/// Terminals - Update
+///
+/// Update a registered terminal, or deactivate it to stop accepting payments on it.
+#[utoipa::path(
+    post,
+    path = "/terminals/{terminal_id}",
+    params(("terminal_id" = String, Path, description = "The identifier for the terminal")),
+    request_body = TerminalUpdateRequest,
+    responses(
+        (status = 200, description = "Terminal updated", body = TerminalResponse),
+        (status = 404, description = "Terminal not found")
+    ),
+    tag = "Terminals",
+    operation_id = "Update a Terminal",
+    security(("api_key" = []))
+)]
+#[instrument(skip_all, fields(flow = ?Flow::TerminalsUpdate))]
+pub async fn terminals_update(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<String>,
+    json_payload: web::Json<terminal_types::TerminalUpdateRequest>,
+) -> impl Responder {
+    let flow = Flow::TerminalsUpdate;
+    let terminal_id = path.into_inner();
+    api::server_wrap(
+        flow,
+        state.get_ref(),
+        &req,
+        json_payload.into_inner(),
+        |state, auth, req| {
+            terminals::update_terminal(
+                state,
+                auth.merchant_account,
+                auth.key_store,
+                terminal_id.clone(),
+                req,
+            )
+        },
+        &auth::ApiKeyAuth,
+    )
+    .await
+}
+
+/// Terminals - Delete
+///
+/// Delete a registered terminal.
+#[utoipa::path(
+    delete,
+    path = "/terminals/{terminal_id}",
+    params(("terminal_id" = String, Path, description = "The identifier for the terminal")),
+    responses(
+        (status = 200, description = "Terminal deleted", body = TerminalDeleteResponse),
+        (status = 404, description = "Terminal not found")
+    ),
+    tag = "Terminals",
+    operation_id = "Delete a Terminal",
+    security(("api_key" = []))
+)]
+#[instrument(skip_all, fields(flow = ?Flow::TerminalsDelete))]
+pub async fn terminals_delete(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<String>,
+) -> impl Responder {
+    let flow = Flow::TerminalsDelete;
+    api::server_wrap(
+        flow,
+        state.get_ref(),
+        &req,
+        path.into_inner(),
+        |state, auth, terminal_id| {
+            terminals::delete_terminal(state, auth.merchant_account, terminal_id)
+        },
+        &auth::ApiKeyAuth,
+    )
+    .await
+}
+
+/// Terminals - List
+///
+/// List the terminals registered against your merchant account.
+#[utoipa::path(
+    get,
+    path = "/terminals/list",
+    params(
+        ("limit" = Option<i64>, Query, description = "The maximum number of terminals to include in the response"),
+        ("skip" = Option<i64>, Query, description = "The number of terminals to skip when retrieving the list of terminals"),
+    ),
+    responses(
+        (status = 200, description = "List of terminals retrieved successfully", body = Vec<TerminalResponse>),
+    ),
+    tag = "Terminals",
+    operation_id = "List all Terminals",
+    security(("api_key" = []))
+)]
+#[instrument(skip_all, fields(flow = ?Flow::TerminalsList))]
+pub async fn terminals_list(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    query: web::Query<terminal_types::TerminalListConstraints>,
+) -> impl Responder {
+    let flow = Flow::TerminalsList;
+    api::server_wrap(
+        flow,
+        state.get_ref(),
+        &req,
+        query.into_inner(),
+        |state, auth, constraints| {
+            terminals::list_terminals(state, auth.merchant_account, constraints)
+        },
+        &auth::ApiKeyAuth,
+    )
+    .await
+}
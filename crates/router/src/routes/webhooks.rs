@@ -1,9 +1,9 @@
-use actix_web::{web, HttpRequest, Responder};
+use actix_web::{web, HttpRequest, HttpResponse, Responder};
 use router_env::{instrument, tracing, Flow};
 
 use super::app::AppState;
 use crate::{
-    core::webhooks::{self, types},
+    core::webhooks::{self, egress_ip_ranges, retry, types},
     services::{api, authentication as auth},
 };
 
@@ -36,3 +36,73 @@ pub async fn receive_incoming_webhook<W: types::OutgoingWebhookType>(
     )
     .await
 }
+
+/// Webhooks - Egress IP Ranges Retrieve
+///
+/// Retrieve the IP ranges from which webhooks are sent, so that webhook endpoints can be
+/// firewalled
+#[instrument(skip_all, fields(flow = ?Flow::WebhookEgressIpRangesRetrieve))]
+pub async fn webhook_egress_ip_ranges_retrieve(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+) -> HttpResponse {
+    let flow = Flow::WebhookEgressIpRangesRetrieve;
+
+    api::server_wrap(
+        flow,
+        state.get_ref(),
+        &req,
+        (),
+        |state, _, _| egress_ip_ranges::retrieve_webhook_egress_ip_ranges(state),
+        auth::auth_type(&auth::ApiKeyAuth, &auth::JWTAuth, req.headers()),
+    )
+    .await
+}
+
+/// Webhooks - Egress IP Ranges Update
+///
+/// Update the IP ranges from which webhooks are sent
+#[instrument(skip_all, fields(flow = ?Flow::WebhookEgressIpRangesUpdate))]
+pub async fn webhook_egress_ip_ranges_update(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    json_payload: web::Json<api_models::webhooks::WebhookEgressIpRangesUpdateRequest>,
+) -> HttpResponse {
+    let flow = Flow::WebhookEgressIpRangesUpdate;
+
+    api::server_wrap(
+        flow,
+        state.get_ref(),
+        &req,
+        json_payload.into_inner(),
+        |state, _, payload| egress_ip_ranges::update_webhook_egress_ip_ranges(state, payload),
+        &auth::AdminApiAuth,
+    )
+    .await
+}
+
+/// Webhooks - Delivery Attempts List
+///
+/// List the attempts made to deliver the webhook of an event, so that failed deliveries can be
+/// debugged
+#[instrument(skip_all, fields(flow = ?Flow::WebhookDeliveryAttemptsList))]
+pub async fn webhook_delivery_attempts_list(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<String>,
+) -> HttpResponse {
+    let flow = Flow::WebhookDeliveryAttemptsList;
+    let event_id = path.into_inner();
+
+    api::server_wrap(
+        flow,
+        state.get_ref(),
+        &req,
+        event_id,
+        |state, auth, event_id| {
+            retry::list_webhook_delivery_attempts(state, auth.merchant_account, event_id)
+        },
+        auth::auth_type(&auth::ApiKeyAuth, &auth::JWTAuth, req.headers()),
+    )
+    .await
+}
@@ -20,6 +20,7 @@ use super::{
 };
 use crate::{
     configs::settings,
+    connection, consts,
     core::errors::{self, CustomResult},
     db::StorageInterface,
     logger,
@@ -202,7 +203,66 @@ pub async fn start_workflow(
     runner: Box<dyn ProcessTrackerWorkflow>,
 ) {
     tracing::Span::current().record("workflow_id", Uuid::new_v4().to_string());
-    run_executor(&state, process, runner).await
+    let storage_region = match get_storage_region(
+        &*state.store,
+        &state.conf.data_residency,
+        &process.tracking_data,
+    )
+    .await
+    {
+        Ok(storage_region) => storage_region,
+        Err(error) => {
+            // Running the task outside the storage region of its merchant would not find the data
+            // of the merchant, or worse store it outside the region, so the task is retried later
+            logger::error!(?error, pt.id = %process.id, "Failed to fetch the merchant of the task");
+            return retry_storage_region_lookup(&state, process).await;
+        }
+    };
+    connection::with_storage_region(storage_region, run_executor(&state, process, runner)).await
+}
+
+/// Returns the storage region of the merchant of the task, for the tasks whose tracking data
+/// identifies their merchant, so that the task accesses the customers and payments of the merchant
+/// in the databases of the region.
+async fn get_storage_region(
+    db: &dyn StorageInterface,
+    data_residency: &settings::DataResidencySettings,
+    tracking_data: &serde_json::Value,
+) -> CustomResult<Option<String>, errors::StorageError> {
+    if data_residency.regions.is_empty() {
+        return Ok(None);
+    }
+    let Some(merchant_id) = tracking_data
+        .get("merchant_id")
+        .and_then(serde_json::Value::as_str)
+    else {
+        return Ok(None);
+    };
+
+    let key_store = db
+        .get_merchant_key_store_by_merchant_id(merchant_id, &db.get_master_key().to_vec().into())
+        .await?;
+    let merchant_account = db
+        .find_merchant_account_by_merchant_id(merchant_id, &key_store)
+        .await?;
+    Ok(merchant_account.storage_region)
+}
+
+async fn retry_storage_region_lookup(state: &AppState, process: storage::ProcessTracker) {
+    let result = if process.retry_count < consts::STORAGE_REGION_LOOKUP_MAX_RETRIES {
+        let schedule_time = common_utils::date_time::now().saturating_add(time::Duration::seconds(
+            consts::STORAGE_REGION_LOOKUP_RETRY_DELAY,
+        ));
+        process.retry(&*state.store, schedule_time).await
+    } else {
+        process
+            .finish_with_status(&*state.store, "STORAGE_REGION_NOT_FOUND".to_string())
+            .await
+    };
+    if let Err(error) = result {
+        logger::error!(%error, "Failed while performing database operation: storage region lookup");
+    }
+    metrics::TASK_PROCESSED.add(&metrics::CONTEXT, 1, &[]);
 }
 
 pub async fn run_executor(
@@ -249,3 +309,52 @@ pub async fn consumer_error_handler<E: fmt::Display + fmt::Debug>(
     .change_context(errors::ProcessTrackerError::ProcessUpdateFailed)?;
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use serde_json::json;
+
+    use super::*;
+    use crate::db::MockDb;
+
+    fn get_data_residency() -> settings::DataResidencySettings {
+        settings::DataResidencySettings {
+            regions: [(
+                "eu".to_string(),
+                settings::RegionalStorage {
+                    master_database: Default::default(),
+                    #[cfg(feature = "olap")]
+                    replica_database: Default::default(),
+                    locker: Default::default(),
+                },
+            )]
+            .into_iter()
+            .collect(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_get_storage_region() {
+        let db = MockDb::new(&Default::default()).await;
+        let tracking_data = json!({"merchant_id": "merchant_1", "payment_id": "pay_1"});
+
+        // The merchant is not looked up when no storage region is configured
+        let storage_region = get_storage_region(&db, &Default::default(), &tracking_data).await;
+        assert_eq!(storage_region.unwrap(), None);
+
+        // Tasks which do not identify their merchant run in the default databases
+        let storage_region = get_storage_region(
+            &db,
+            &get_data_residency(),
+            &json!({"lookup_key": "token_1", "merchant_id": null}),
+        )
+        .await;
+        assert_eq!(storage_region.unwrap(), None);
+
+        // A merchant which cannot be found is an error, so that the task is retried rather than
+        // run outside the storage region of the merchant
+        let storage_region = get_storage_region(&db, &get_data_residency(), &tracking_data).await;
+        assert!(storage_region.is_err());
+    }
+}
@@ -17,3 +17,4 @@ counter_metric!(TASK_FINISHED, PT_METER); // Tasks finished
 counter_metric!(TASK_RETRIED, PT_METER); // Tasks added for retries
 counter_metric!(TOKENIZED_DATA_COUNT, PT_METER); // Tokenized data added
 counter_metric!(RETRIED_DELETE_DATA_COUNT, PT_METER); // Tokenized data retried
+counter_metric!(WEBHOOK_FALLBACK_SYNC_COUNT, PT_METER); // Payments synced as the connector webhook did not arrive within the fallback window
@@ -11,8 +11,23 @@ use crate::{
 #[cfg(feature = "email")]
 pub mod api_key_expiry;
 
+pub mod commission_recording;
+pub mod connector_certificate_expiry;
+pub mod connector_webhook_details_backfill;
+pub mod customer_email_hash_backfill;
+pub mod customer_import;
+pub mod delayed_capture;
+pub mod outgoing_webhook_retry;
+pub mod payment_link_expiry;
+pub mod payment_method_expiry;
+pub mod payment_method_expiry_backfill;
+pub mod payment_reauthorization;
 pub mod payment_sync;
 pub mod refund_router;
+#[cfg(feature = "olap")]
+pub mod report_generation;
+#[cfg(feature = "olap")]
+pub mod subscription_billing;
 pub mod tokenized_data;
 
 macro_rules! runners {
@@ -57,7 +72,20 @@ runners! {
     #[cfg(all())] PaymentsSyncWorkflow,
     #[cfg(all())] RefundWorkflowRouter,
     #[cfg(all())] DeleteTokenizeDataWorkflow,
-    #[cfg(feature = "email")] ApiKeyExpiryWorkflow
+    #[cfg(all())] DelayedCaptureWorkflow,
+    #[cfg(all())] PaymentReauthorizationWorkflow,
+    #[cfg(all())] PaymentMethodExpiryWorkflow,
+    #[cfg(all())] PaymentMethodExpiryBackfillWorkflow,
+    #[cfg(all())] CustomerImportWorkflow,
+    #[cfg(all())] CustomerEmailHashBackfillWorkflow,
+    #[cfg(all())] OutgoingWebhookRetryWorkflow,
+    #[cfg(all())] ConnectorCertificateExpiryWorkflow,
+    #[cfg(all())] ConnectorWebhookDetailsBackfillWorkflow,
+    #[cfg(all())] PaymentLinkExpiryWorkflow,
+    #[cfg(all())] CommissionRecordingWorkflow,
+    #[cfg(feature = "email")] ApiKeyExpiryWorkflow,
+    #[cfg(feature = "olap")] ReportGenerationWorkflow,
+    #[cfg(feature = "olap")] SubscriptionBillingWorkflow
 }
 
 pub type WorkflowSelectorFn =
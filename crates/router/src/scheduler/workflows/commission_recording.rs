@@ -0,0 +1,56 @@
+use router_env::logger;
+
+use super::{CommissionRecordingWorkflow, ProcessTrackerWorkflow};
+use crate::{
+    consts,
+    core::commissions,
+    db::StorageInterface,
+    errors,
+    routes::AppState,
+    types::storage::{self, ProcessTrackerExt},
+    utils::ValueExt,
+};
+
+#[async_trait::async_trait]
+impl ProcessTrackerWorkflow for CommissionRecordingWorkflow {
+    async fn execute_workflow<'a>(
+        &'a self,
+        state: &'a AppState,
+        process: storage::ProcessTracker,
+    ) -> Result<(), errors::ProcessTrackerError> {
+        let db: &dyn StorageInterface = &*state.store;
+        let tracking_data: commissions::CommissionRecordingTrackingData = process
+            .tracking_data
+            .clone()
+            .parse_value("CommissionRecordingTrackingData")?;
+
+        commissions::record_scheduled_payment_commission(state, &tracking_data).await?;
+
+        let id = process.id.clone();
+        process
+            .finish_with_status(db, format!("COMPLETED_BY_PT_{id}"))
+            .await?;
+        Ok(())
+    }
+
+    async fn error_handler<'a>(
+        &'a self,
+        state: &'a AppState,
+        process: storage::ProcessTracker,
+        error: errors::ProcessTrackerError,
+    ) -> errors::CustomResult<(), errors::ProcessTrackerError> {
+        logger::error!(pt.id = %process.id, ?error, "Failed to record the commission of a payment");
+        let db: &dyn StorageInterface = &*state.store;
+        if process.retry_count >= consts::COMMISSION_RECORDING_MAX_RETRIES {
+            return process
+                .finish_with_status(db, "RETRIES_EXCEEDED".to_string())
+                .await
+                .map_err(Into::into);
+        }
+
+        let schedule_time = common_utils::date_time::now().saturating_add(time::Duration::seconds(
+            consts::COMMISSION_RECORDING_RETRY_DELAY,
+        ));
+        process.retry(db, schedule_time).await.map_err(Into::into)
+    }
+}
@@ -0,0 +1,76 @@
+use super::{ConnectorCertificateExpiryWorkflow, ProcessTrackerWorkflow};
+use crate::{
+    core::admin::connector_certificate,
+    db::StorageInterface,
+    errors,
+    routes::AppState,
+    scheduler::consumer,
+    types::storage::{self, enums, ProcessTrackerExt},
+    utils::ValueExt,
+};
+
+#[async_trait::async_trait]
+impl ProcessTrackerWorkflow for ConnectorCertificateExpiryWorkflow {
+    async fn execute_workflow<'a>(
+        &'a self,
+        state: &'a AppState,
+        process: storage::ProcessTracker,
+    ) -> Result<(), errors::ProcessTrackerError> {
+        let db: &dyn StorageInterface = &*state.store;
+        let tracking_data: storage::ConnectorCertificateExpiryWorkflow = process
+            .tracking_data
+            .clone()
+            .parse_value("ConnectorCertificateExpiryWorkflow")?;
+
+        // The retry count is the index of the reminder to be sent
+        let reminder_index = usize::try_from(process.retry_count)
+            .map_err(|_| errors::ProcessTrackerError::TypeConversionError)?;
+        let is_certificate_current = connector_certificate::send_certificate_expiry_reminder(
+            state,
+            &tracking_data,
+            reminder_index,
+        )
+        .await?;
+
+        let next_reminder_time = connector_certificate::get_reminder_time(
+            &tracking_data,
+            reminder_index.saturating_add(1),
+        )
+        .filter(|_| is_certificate_current);
+
+        match next_reminder_time {
+            Some(schedule_time) => {
+                let process_tracker_update = storage::ProcessTrackerUpdate::Update {
+                    name: None,
+                    retry_count: Some(process.retry_count + 1),
+                    schedule_time: Some(schedule_time),
+                    tracking_data: None,
+                    business_status: None,
+                    status: Some(enums::ProcessTrackerStatus::New),
+                    updated_at: Some(common_utils::date_time::now()),
+                };
+                db.process_tracker_update_process_status_by_ids(
+                    vec![process.id.clone()],
+                    process_tracker_update,
+                )
+                .await?;
+            }
+            None => {
+                let id = process.id.clone();
+                process
+                    .finish_with_status(db, format!("COMPLETED_BY_PT_{id}"))
+                    .await?;
+            }
+        }
+        Ok(())
+    }
+
+    async fn error_handler<'a>(
+        &'a self,
+        state: &'a AppState,
+        process: storage::ProcessTracker,
+        error: errors::ProcessTrackerError,
+    ) -> errors::CustomResult<(), errors::ProcessTrackerError> {
+        consumer::consumer_error_handler(state, process, error).await
+    }
+}
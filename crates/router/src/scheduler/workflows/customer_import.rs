@@ -0,0 +1,55 @@
+use router_env::logger;
+
+use super::{CustomerImportWorkflow, ProcessTrackerWorkflow};
+use crate::{
+    core::customers::import,
+    db::StorageInterface,
+    errors,
+    routes::AppState,
+    scheduler::consumer,
+    types::storage::{self, ProcessTrackerExt},
+    utils::ValueExt,
+};
+
+#[async_trait::async_trait]
+impl ProcessTrackerWorkflow for CustomerImportWorkflow {
+    async fn execute_workflow<'a>(
+        &'a self,
+        state: &'a AppState,
+        process: storage::ProcessTracker,
+    ) -> Result<(), errors::ProcessTrackerError> {
+        let db: &dyn StorageInterface = &*state.store;
+        let tracking_data: storage::CustomerImportWorkflow = process
+            .tracking_data
+            .clone()
+            .parse_value("CustomerImportWorkflow")?;
+
+        import::process_customer_import(state, &tracking_data).await?;
+
+        let id = process.id.clone();
+        process
+            .finish_with_status(db, format!("COMPLETED_BY_PT_{id}"))
+            .await?;
+        Ok(())
+    }
+
+    async fn error_handler<'a>(
+        &'a self,
+        state: &'a AppState,
+        process: storage::ProcessTracker,
+        error: errors::ProcessTrackerError,
+    ) -> errors::CustomResult<(), errors::ProcessTrackerError> {
+        // The import is marked as failed so that the merchant does not wait for it indefinitely
+        if let Ok(tracking_data) = process
+            .tracking_data
+            .clone()
+            .parse_value::<storage::CustomerImportWorkflow>("CustomerImportWorkflow")
+        {
+            import::fail_customer_import(&*state.store, &tracking_data)
+                .await
+                .map_err(|error| logger::error!(customer_import_failure_error=?error))
+                .ok();
+        }
+        consumer::consumer_error_handler(state, process, error).await
+    }
+}
@@ -0,0 +1,128 @@
+use common_utils::ext_traits::StringExt;
+use error_stack::ResultExt;
+use router_env::logger;
+
+use super::{DelayedCaptureWorkflow, ProcessTrackerWorkflow};
+use crate::{
+    consts,
+    core::payments::{self as payment_flows, operations},
+    db::StorageInterface,
+    errors,
+    routes::AppState,
+    scheduler::consumer,
+    services,
+    types::{
+        api,
+        storage::{self, enums, ProcessTrackerExt},
+    },
+    utils::{OptionExt, ValueExt},
+};
+
+#[async_trait::async_trait]
+impl ProcessTrackerWorkflow for DelayedCaptureWorkflow {
+    async fn execute_workflow<'a>(
+        &'a self,
+        state: &'a AppState,
+        process: storage::ProcessTracker,
+    ) -> Result<(), errors::ProcessTrackerError> {
+        let db: &dyn StorageInterface = &*state.store;
+        let tracking_data: api::PaymentsCaptureRequest = process
+            .tracking_data
+            .clone()
+            .parse_value("PaymentsCaptureRequest")?;
+
+        let merchant_id = tracking_data
+            .merchant_id
+            .as_ref()
+            .get_required_value("merchant_id")?;
+        let payment_id = tracking_data
+            .payment_id
+            .as_ref()
+            .get_required_value("payment_id")?;
+
+        let key_store = db
+            .get_merchant_key_store_by_merchant_id(
+                merchant_id,
+                &db.get_master_key().to_vec().into(),
+            )
+            .await?;
+
+        let merchant_account = db
+            .find_merchant_account_by_merchant_id(merchant_id, &key_store)
+            .await?;
+
+        let payment_intent = db
+            .find_payment_intent_by_payment_id_merchant_id(
+                payment_id,
+                merchant_id,
+                merchant_account.storage_scheme,
+            )
+            .await?;
+
+        // The payment could have been voided or captured by the merchant before the delay elapsed
+        if payment_intent.status != enums::IntentStatus::RequiresCapture {
+            logger::info!(
+                payment_id = %payment_id,
+                status = %payment_intent.status,
+                "Skipping delayed capture as the payment is no longer capturable"
+            );
+            return Ok(process
+                .finish_with_status(db, "CAPTURE_SKIPPED".to_string())
+                .await?);
+        }
+
+        payment_flows::payments_operation_core::<api::Capture, _, _, _>(
+            state,
+            merchant_account,
+            key_store,
+            operations::PaymentCapture,
+            tracking_data,
+            payment_flows::CallConnectorAction::Trigger,
+            services::AuthFlow::Merchant,
+        )
+        .await?;
+
+        let id = process.id.clone();
+        process
+            .finish_with_status(db, format!("COMPLETED_BY_PT_{id}"))
+            .await?;
+        Ok(())
+    }
+
+    async fn error_handler<'a>(
+        &'a self,
+        state: &'a AppState,
+        process: storage::ProcessTracker,
+        error: errors::ProcessTrackerError,
+    ) -> errors::CustomResult<(), errors::ProcessTrackerError> {
+        consumer::consumer_error_handler(state, process, error).await
+    }
+}
+
+/// Returns the time at which a payment authorized with `automatic_delayed` capture method should
+/// be captured. The delay (in seconds) can be configured per merchant using the
+/// `delayed_capture_delay_{merchant_id}` config, and defaults to
+/// [`consts::DEFAULT_DELAYED_CAPTURE_DELAY`].
+pub async fn get_delayed_capture_schedule_time(
+    db: &dyn StorageInterface,
+    merchant_id: &str,
+) -> time::PrimitiveDateTime {
+    let delay: common_utils::errors::CustomResult<i64, errors::StorageError> = db
+        .find_config_by_key_cached(&format!("delayed_capture_delay_{merchant_id}"))
+        .await
+        .map(|value| value.config)
+        .and_then(|config| {
+            config
+                .parse_struct("DelayedCaptureDelay")
+                .change_context(errors::StorageError::DeserializationFailed)
+        });
+    let delay = match delay {
+        Ok(delay) => delay,
+        Err(err) => {
+            logger::debug!("Using default delayed capture delay: {}", err);
+            consts::DEFAULT_DELAYED_CAPTURE_DELAY
+        }
+    };
+
+    common_utils::date_time::now().saturating_add(time::Duration::seconds(delay))
+}
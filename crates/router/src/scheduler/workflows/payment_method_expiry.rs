@@ -0,0 +1,82 @@
+use router_env::logger;
+
+use super::{PaymentMethodExpiryWorkflow, ProcessTrackerWorkflow};
+use crate::{
+    core::payment_methods::expiry,
+    db::StorageInterface,
+    errors,
+    routes::AppState,
+    scheduler::consumer,
+    types::storage::{self, ProcessTrackerExt},
+    utils::ValueExt,
+};
+
+#[async_trait::async_trait]
+impl ProcessTrackerWorkflow for PaymentMethodExpiryWorkflow {
+    async fn execute_workflow<'a>(
+        &'a self,
+        state: &'a AppState,
+        process: storage::ProcessTracker,
+    ) -> Result<(), errors::ProcessTrackerError> {
+        let db: &dyn StorageInterface = &*state.store;
+        let tracking_data: expiry::PaymentMethodExpiryTrackingData = process
+            .tracking_data
+            .clone()
+            .parse_value("PaymentMethodExpiryTrackingData")?;
+
+        let payment_method = match db
+            .find_payment_method(&tracking_data.payment_method_id)
+            .await
+        {
+            Ok(payment_method) => payment_method,
+            Err(error) if error.current_context().is_db_not_found() => {
+                return Ok(process
+                    .finish_with_status(db, "PAYMENT_METHOD_DELETED".to_string())
+                    .await?);
+            }
+            Err(error) => return Err(error.into()),
+        };
+
+        // The card could have been refreshed since the task was scheduled, in which case a task
+        // has been scheduled for its new expiry
+        if !state.conf.payment_method_expiry.enabled
+            || payment_method.account_updated_at != tracking_data.account_updated_at
+        {
+            logger::info!(
+                payment_method_id = %tracking_data.payment_method_id,
+                "Skipping the expiry of the payment method as its card has been refreshed"
+            );
+            return Ok(process
+                .finish_with_status(db, "EXPIRY_SKIPPED".to_string())
+                .await?);
+        }
+
+        let key_store = db
+            .get_merchant_key_store_by_merchant_id(
+                &tracking_data.merchant_id,
+                &db.get_master_key().to_vec().into(),
+            )
+            .await?;
+
+        let merchant_account = db
+            .find_merchant_account_by_merchant_id(&tracking_data.merchant_id, &key_store)
+            .await?;
+
+        expiry::process_expiring_card(state, merchant_account, &key_store, payment_method).await?;
+
+        let id = process.id.clone();
+        process
+            .finish_with_status(db, format!("COMPLETED_BY_PT_{id}"))
+            .await?;
+        Ok(())
+    }
+
+    async fn error_handler<'a>(
+        &'a self,
+        state: &'a AppState,
+        process: storage::ProcessTracker,
+        error: errors::ProcessTrackerError,
+    ) -> errors::CustomResult<(), errors::ProcessTrackerError> {
+        consumer::consumer_error_handler(state, process, error).await
+    }
+}
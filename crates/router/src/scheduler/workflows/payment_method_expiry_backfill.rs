@@ -0,0 +1,67 @@
+use super::{PaymentMethodExpiryBackfillWorkflow, ProcessTrackerWorkflow};
+use crate::{
+    core::payment_methods::expiry,
+    db::StorageInterface,
+    errors,
+    routes::AppState,
+    scheduler::consumer,
+    types::storage::{self, enums, ProcessTrackerExt},
+    utils::ValueExt,
+};
+
+#[async_trait::async_trait]
+impl ProcessTrackerWorkflow for PaymentMethodExpiryBackfillWorkflow {
+    async fn execute_workflow<'a>(
+        &'a self,
+        state: &'a AppState,
+        process: storage::ProcessTracker,
+    ) -> Result<(), errors::ProcessTrackerError> {
+        let db: &dyn StorageInterface = &*state.store;
+        let tracking_data: expiry::PaymentMethodExpiryBackfillTrackingData = process
+            .tracking_data
+            .clone()
+            .parse_value("PaymentMethodExpiryBackfillTrackingData")?;
+
+        match expiry::backfill_card_expiry(state, tracking_data.last_id).await? {
+            // The task is scheduled again right away with the progress of the backfill, so that
+            // the backfill resumes after the last batch if it is interrupted
+            Some(last_id) => {
+                let tracking_data =
+                    serde_json::to_value(expiry::PaymentMethodExpiryBackfillTrackingData {
+                        last_id,
+                    })
+                    .map_err(|_| errors::ProcessTrackerError::SerializationFailed)?;
+                let process_tracker_update = storage::ProcessTrackerUpdate::Update {
+                    name: None,
+                    retry_count: Some(0),
+                    schedule_time: Some(common_utils::date_time::now()),
+                    tracking_data: Some(tracking_data),
+                    business_status: None,
+                    status: Some(enums::ProcessTrackerStatus::New),
+                    updated_at: Some(common_utils::date_time::now()),
+                };
+                db.process_tracker_update_process_status_by_ids(
+                    vec![process.id.clone()],
+                    process_tracker_update,
+                )
+                .await?;
+            }
+            None => {
+                let id = process.id.clone();
+                process
+                    .finish_with_status(db, format!("COMPLETED_BY_PT_{id}"))
+                    .await?;
+            }
+        }
+        Ok(())
+    }
+
+    async fn error_handler<'a>(
+        &'a self,
+        state: &'a AppState,
+        process: storage::ProcessTracker,
+        error: errors::ProcessTrackerError,
+    ) -> errors::CustomResult<(), errors::ProcessTrackerError> {
+        consumer::consumer_error_handler(state, process, error).await
+    }
+}
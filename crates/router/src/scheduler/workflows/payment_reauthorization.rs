@@ -0,0 +1,97 @@
+use router_env::logger;
+
+use super::{PaymentReauthorizationWorkflow, ProcessTrackerWorkflow};
+use crate::{
+    core::payments::reauthorization,
+    db::StorageInterface,
+    errors,
+    routes::AppState,
+    scheduler::consumer,
+    types::storage::{self, enums, ProcessTrackerExt},
+    utils::ValueExt,
+};
+
+#[async_trait::async_trait]
+impl ProcessTrackerWorkflow for PaymentReauthorizationWorkflow {
+    async fn execute_workflow<'a>(
+        &'a self,
+        state: &'a AppState,
+        process: storage::ProcessTracker,
+    ) -> Result<(), errors::ProcessTrackerError> {
+        let db: &dyn StorageInterface = &*state.store;
+        let tracking_data: reauthorization::PaymentReauthorizationTrackingData = process
+            .tracking_data
+            .clone()
+            .parse_value("PaymentReauthorizationTrackingData")?;
+
+        let key_store = db
+            .get_merchant_key_store_by_merchant_id(
+                &tracking_data.merchant_id,
+                &db.get_master_key().to_vec().into(),
+            )
+            .await?;
+
+        let merchant_account = db
+            .find_merchant_account_by_merchant_id(&tracking_data.merchant_id, &key_store)
+            .await?;
+
+        let payment_intent = db
+            .find_payment_intent_by_payment_id_merchant_id(
+                &tracking_data.payment_id,
+                &tracking_data.merchant_id,
+                merchant_account.storage_scheme,
+            )
+            .await?;
+
+        // The payment could have been captured or voided, or the business could have opted out,
+        // since the re-authorization was scheduled
+        if payment_intent.status != enums::IntentStatus::RequiresCapture
+            || payment_intent.active_attempt_id != tracking_data.attempt_id
+            || !reauthorization::is_auto_reauthorization_enabled(
+                &merchant_account,
+                &payment_intent,
+            )?
+        {
+            logger::info!(
+                payment_id = %tracking_data.payment_id,
+                status = %payment_intent.status,
+                "Skipping re-authorization as the payment no longer requires it"
+            );
+            return Ok(process
+                .finish_with_status(db, "REAUTHORIZATION_SKIPPED".to_string())
+                .await?);
+        }
+
+        let authorized_attempt = db
+            .find_payment_attempt_by_attempt_id_merchant_id(
+                &tracking_data.attempt_id,
+                &tracking_data.merchant_id,
+                merchant_account.storage_scheme,
+            )
+            .await?;
+
+        reauthorization::reauthorize_payment(
+            state,
+            &merchant_account,
+            &key_store,
+            payment_intent,
+            authorized_attempt,
+        )
+        .await?;
+
+        let id = process.id.clone();
+        process
+            .finish_with_status(db, format!("COMPLETED_BY_PT_{id}"))
+            .await?;
+        Ok(())
+    }
+
+    async fn error_handler<'a>(
+        &'a self,
+        state: &'a AppState,
+        process: storage::ProcessTracker,
+        error: errors::ProcessTrackerError,
+    ) -> errors::CustomResult<(), errors::ProcessTrackerError> {
+        consumer::consumer_error_handler(state, process, error).await
+    }
+}
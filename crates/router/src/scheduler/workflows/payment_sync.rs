@@ -1,6 +1,6 @@
 use common_utils::ext_traits::StringExt;
 use error_stack::ResultExt;
-use router_env::logger;
+use router_env::{logger, opentelemetry::KeyValue};
 
 use super::{PaymentsSyncWorkflow, ProcessTrackerWorkflow};
 use crate::{
@@ -8,15 +8,25 @@ use crate::{
     db::StorageInterface,
     errors,
     routes::AppState,
-    scheduler::{consumer, process_data, utils},
+    scheduler::{consumer, metrics, process_data, utils},
     services,
     types::{
-        api,
+        api, domain,
         storage::{self, enums, ProcessTrackerExt},
     },
     utils::{OptionExt, ValueExt},
 };
 
+const TERMINAL_ATTEMPT_STATUSES: [enums::AttemptStatus; 7] = [
+    enums::AttemptStatus::RouterDeclined,
+    enums::AttemptStatus::Charged,
+    enums::AttemptStatus::AutoRefunded,
+    enums::AttemptStatus::Voided,
+    enums::AttemptStatus::VoidFailed,
+    enums::AttemptStatus::CaptureFailed,
+    enums::AttemptStatus::Failure,
+];
+
 #[async_trait::async_trait]
 impl ProcessTrackerWorkflow for PaymentsSyncWorkflow {
     async fn execute_workflow<'a>(
@@ -50,6 +60,12 @@ impl ProcessTrackerWorkflow for PaymentsSyncWorkflow {
             )
             .await?;
 
+        // The first sync for connectors with a webhook fallback window runs only once the window
+        // has elapsed, so a payment which is still pending at this point has missed its webhook
+        if process.retry_count == 0 {
+            record_missed_webhook(db, &tracking_data, &merchant_account).await;
+        }
+
         let (payment_data, _, _) = payment_flows::payments_operation_core::<api::PSync, _, _, _>(
             state,
             merchant_account.clone(),
@@ -61,17 +77,8 @@ impl ProcessTrackerWorkflow for PaymentsSyncWorkflow {
         )
         .await?;
 
-        let terminal_status = vec![
-            enums::AttemptStatus::RouterDeclined,
-            enums::AttemptStatus::Charged,
-            enums::AttemptStatus::AutoRefunded,
-            enums::AttemptStatus::Voided,
-            enums::AttemptStatus::VoidFailed,
-            enums::AttemptStatus::CaptureFailed,
-            enums::AttemptStatus::Failure,
-        ];
         match &payment_data.payment_attempt.status {
-            status if terminal_status.contains(status) => {
+            status if TERMINAL_ATTEMPT_STATUSES.contains(status) => {
                 let id = process.id.clone();
                 process
                     .finish_with_status(db, format!("COMPLETED_BY_PT_{id}"))
@@ -135,6 +142,87 @@ pub async fn get_sync_process_schedule_time(
     Ok(utils::get_time_from_delta(time_delta))
 }
 
+/// Returns the window (in seconds) within which a webhook is expected from the connector for an
+/// async payment, as configured using the `webhook_fallback_window_{connector}` config.
+pub async fn get_webhook_fallback_window(
+    db: &dyn StorageInterface,
+    connector: &str,
+) -> Option<i64> {
+    db.find_config_by_key_cached(&format!("webhook_fallback_window_{connector}"))
+        .await
+        .map(|value| value.config)
+        .and_then(|config| {
+            config
+                .parse_struct("WebhookFallbackWindow")
+                .change_context(errors::StorageError::DeserializationFailed)
+        })
+        .map_err(|err| logger::debug!("No webhook fallback window configured: {}", err))
+        .ok()
+}
+
+/// Returns the time at which the first sync task of a payment should run. For connectors with a
+/// webhook fallback window, polling is deferred until the window elapses so that it only acts as
+/// a fallback for webhooks which do not arrive.
+pub async fn get_initial_sync_process_schedule_time(
+    db: &dyn StorageInterface,
+    connector: &str,
+    merchant_id: &str,
+) -> Result<Option<time::PrimitiveDateTime>, errors::ProcessTrackerError> {
+    match get_webhook_fallback_window(db, connector).await {
+        Some(window) => Ok(Some(
+            common_utils::date_time::now().saturating_add(time::Duration::seconds(window)),
+        )),
+        None => get_sync_process_schedule_time(db, connector, merchant_id, 0).await,
+    }
+}
+
+async fn record_missed_webhook(
+    db: &dyn StorageInterface,
+    tracking_data: &api::PaymentsRetrieveRequest,
+    merchant_account: &domain::MerchantAccount,
+) {
+    let attempt_id = match &tracking_data.resource_id {
+        api::PaymentIdType::PaymentAttemptId(attempt_id) => attempt_id,
+        _ => return,
+    };
+    let payment_attempt = match db
+        .find_payment_attempt_by_attempt_id_merchant_id(
+            attempt_id,
+            &merchant_account.merchant_id,
+            merchant_account.storage_scheme,
+        )
+        .await
+    {
+        Ok(payment_attempt) => payment_attempt,
+        Err(err) => {
+            logger::error!(
+                ?err,
+                "Failed to fetch payment attempt for webhook health check"
+            );
+            return;
+        }
+    };
+    let connector = match payment_attempt.connector {
+        Some(connector) => connector,
+        None => return,
+    };
+
+    if !TERMINAL_ATTEMPT_STATUSES.contains(&payment_attempt.status)
+        && get_webhook_fallback_window(db, &connector).await.is_some()
+    {
+        logger::warn!(
+            %connector,
+            attempt_id = %payment_attempt.attempt_id,
+            "No webhook received within the fallback window, falling back to payments sync"
+        );
+        metrics::WEBHOOK_FALLBACK_SYNC_COUNT.add(
+            &metrics::CONTEXT,
+            1,
+            &[KeyValue::new("connector", connector)],
+        );
+    }
+}
+
 pub async fn retry_sync_task(
     db: &dyn StorageInterface,
     connector: String,
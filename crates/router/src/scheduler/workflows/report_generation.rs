@@ -0,0 +1,56 @@
+use router_env::logger;
+
+use super::{ProcessTrackerWorkflow, ReportGenerationWorkflow};
+use crate::{
+    consts,
+    core::reports,
+    db::StorageInterface,
+    errors,
+    routes::AppState,
+    types::storage::{self, ProcessTrackerExt},
+    utils::ValueExt,
+};
+
+#[async_trait::async_trait]
+impl ProcessTrackerWorkflow for ReportGenerationWorkflow {
+    async fn execute_workflow<'a>(
+        &'a self,
+        state: &'a AppState,
+        process: storage::ProcessTracker,
+    ) -> Result<(), errors::ProcessTrackerError> {
+        let db: &dyn StorageInterface = &*state.store;
+        let tracking_data: storage::ReportGenerationWorkflow = process
+            .tracking_data
+            .clone()
+            .parse_value("ReportGenerationWorkflow")?;
+
+        match reports::generate_scheduled_report(state, &tracking_data).await? {
+            // The same task is rescheduled for the next run of the report
+            Some(next_run_at) => process.reset(db, next_run_at).await?,
+            None => {
+                process
+                    .finish_with_status(db, "REPORT_SCHEDULE_INACTIVE".to_string())
+                    .await?
+            }
+        }
+        Ok(())
+    }
+
+    async fn error_handler<'a>(
+        &'a self,
+        state: &'a AppState,
+        process: storage::ProcessTracker,
+        error: errors::ProcessTrackerError,
+    ) -> errors::CustomResult<(), errors::ProcessTrackerError> {
+        logger::error!(pt.id = %process.id, ?error, "Failed to generate scheduled report");
+        // A failed report is retried later instead of finishing the task, as that would stop the
+        // generation of all the following reports of the schedule
+        let schedule_time = common_utils::date_time::now().saturating_add(time::Duration::seconds(
+            consts::REPORT_GENERATION_RETRY_DELAY,
+        ));
+        process
+            .retry(&*state.store, schedule_time)
+            .await
+            .map_err(Into::into)
+    }
+}
@@ -0,0 +1,56 @@
+use router_env::logger;
+
+use super::{ProcessTrackerWorkflow, SubscriptionBillingWorkflow};
+use crate::{
+    consts,
+    core::subscriptions,
+    db::StorageInterface,
+    errors,
+    routes::AppState,
+    types::storage::{self, ProcessTrackerExt},
+    utils::ValueExt,
+};
+
+#[async_trait::async_trait]
+impl ProcessTrackerWorkflow for SubscriptionBillingWorkflow {
+    async fn execute_workflow<'a>(
+        &'a self,
+        state: &'a AppState,
+        process: storage::ProcessTracker,
+    ) -> Result<(), errors::ProcessTrackerError> {
+        let db: &dyn StorageInterface = &*state.store;
+        let tracking_data: storage::SubscriptionBillingWorkflow = process
+            .tracking_data
+            .clone()
+            .parse_value("SubscriptionBillingWorkflow")?;
+
+        match subscriptions::bill_subscription(state, &tracking_data).await? {
+            // The same task is rescheduled for the next billing cycle or retry of the subscription
+            Some(next_billing_at) => process.reset(db, next_billing_at).await?,
+            None => {
+                process
+                    .finish_with_status(db, "SUBSCRIPTION_INACTIVE".to_string())
+                    .await?
+            }
+        }
+        Ok(())
+    }
+
+    async fn error_handler<'a>(
+        &'a self,
+        state: &'a AppState,
+        process: storage::ProcessTracker,
+        error: errors::ProcessTrackerError,
+    ) -> errors::CustomResult<(), errors::ProcessTrackerError> {
+        logger::error!(pt.id = %process.id, ?error, "Failed to bill subscription");
+        // Finishing the task would stop the billing of the subscription altogether, so the billing
+        // is retried later instead
+        let schedule_time = common_utils::date_time::now().saturating_add(time::Duration::seconds(
+            consts::SUBSCRIPTION_BILLING_RETRY_DELAY,
+        ));
+        process
+            .retry(&*state.store, schedule_time)
+            .await
+            .map_err(Into::into)
+    }
+}
@@ -3,7 +3,10 @@ pub mod authentication;
 pub mod encryption;
 pub mod logger;
 
-use std::sync::{atomic, Arc};
+use std::{
+    collections::HashMap,
+    sync::{atomic, Arc},
+};
 
 use error_stack::{IntoReport, ResultExt};
 #[cfg(feature = "kms")]
@@ -136,6 +139,19 @@ pub struct Store {
     #[cfg(feature = "kv_store")]
     pub(crate) config: StoreConfig,
     pub master_key: Vec<u8>,
+    pub master_key_version: i32,
+    pub previous_master_key: Option<Vec<u8>>,
+    /// The connection pools of the databases of the storage regions, by region
+    pub regional_pools: HashMap<String, RegionalPools>,
+}
+
+/// The connection pools of the databases of a storage region, in which the customers and payments
+/// of the merchants pinned to the region are stored
+#[derive(Clone)]
+pub struct RegionalPools {
+    pub master_pool: PgPool,
+    #[cfg(feature = "olap")]
+    pub replica_pool: PgPool,
 }
 
 #[cfg(feature = "kv_store")]
@@ -178,6 +194,35 @@ impl Store {
         )
         .await;
 
+        let previous_master_enc_key = get_previous_master_enc_key(
+            config,
+            #[cfg(feature = "kms")]
+            kms_client,
+        )
+        .await;
+
+        let mut regional_pools = HashMap::new();
+        for (region, regional_storage) in &config.data_residency.regions {
+            let pools = RegionalPools {
+                master_pool: diesel_make_pg_pool(
+                    &regional_storage.master_database,
+                    test_transaction,
+                    #[cfg(feature = "kms")]
+                    kms_client,
+                )
+                .await,
+                #[cfg(feature = "olap")]
+                replica_pool: diesel_make_pg_pool(
+                    &regional_storage.replica_database,
+                    test_transaction,
+                    #[cfg(feature = "kms")]
+                    kms_client,
+                )
+                .await,
+            };
+            regional_pools.insert(region.clone(), pools);
+        }
+
         Self {
             master_pool: diesel_make_pg_pool(
                 &config.master_database,
@@ -201,6 +246,12 @@ impl Store {
                 drainer_num_partitions: config.drainer.num_partitions,
             },
             master_key: master_enc_key,
+            master_key_version: config
+                .secrets
+                .master_enc_key_version
+                .unwrap_or(consts::INITIAL_MERCHANT_KEY_VERSION),
+            previous_master_key: previous_master_enc_key,
+            regional_pools,
         }
     }
 
@@ -272,6 +323,30 @@ async fn get_master_enc_key(
     master_enc_key
 }
 
+#[allow(clippy::expect_used)]
+async fn get_previous_master_enc_key(
+    conf: &crate::configs::settings::Settings,
+    #[cfg(feature = "kms")] kms_client: &kms::KmsClient,
+) -> Option<Vec<u8>> {
+    let previous_master_enc_key = conf.secrets.previous_master_enc_key.as_ref()?;
+
+    #[cfg(feature = "kms")]
+    let previous_master_enc_key = hex::decode(
+        previous_master_enc_key
+            .clone()
+            .decrypt_inner(kms_client)
+            .await
+            .expect("Failed to decrypt previous master enc key"),
+    )
+    .expect("Failed to decode from hex");
+
+    #[cfg(not(feature = "kms"))]
+    let previous_master_enc_key =
+        hex::decode(previous_master_enc_key.peek()).expect("Failed to decode from hex");
+
+    Some(previous_master_enc_key)
+}
+
 #[inline]
 pub fn generate_aes256_key() -> errors::CustomResult<[u8; 32], common_utils::errors::CryptoError> {
     use ring::rand::SecureRandom;
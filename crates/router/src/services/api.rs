@@ -1,5 +1,7 @@
 mod client;
+pub mod rate_limiter;
 pub mod request;
+pub mod request_signing;
 
 use std::{
     collections::HashMap,
@@ -21,8 +23,9 @@ use self::request::{ContentType, HeaderExt, RequestBuilderExt};
 pub use self::request::{Method, Request, RequestBuilder};
 use crate::{
     configs::settings::Connectors,
-    consts,
+    connection, consts,
     core::{
+        audit,
         errors::{self, CustomResult},
         payments,
     },
@@ -171,21 +174,84 @@ pub trait ConnectorIntegration<T, Req, Resp>: ConnectorIntegrationAny<T, Req, Re
         })
     }
 
+    /// The client certificate presented to the connector, defaults to the client certificate
+    /// configured on the merchant connector account
     fn get_certificate(
         &self,
-        _req: &types::RouterData<T, Req, Resp>,
+        req: &types::RouterData<T, Req, Resp>,
     ) -> CustomResult<Option<String>, errors::ConnectorError> {
-        Ok(None)
+        Ok(req
+            .connector_client_certificate
+            .as_ref()
+            .map(|client_certificate| client_certificate.certificate.peek().clone()))
     }
 
     fn get_certificate_key(
         &self,
-        _req: &types::RouterData<T, Req, Resp>,
+        req: &types::RouterData<T, Req, Resp>,
     ) -> CustomResult<Option<String>, errors::ConnectorError> {
+        Ok(req
+            .connector_client_certificate
+            .as_ref()
+            .map(|client_certificate| client_certificate.certificate_key.peek().clone()))
+    }
+
+    /// The profile with which the requests sent to the connector are signed, for connectors which
+    /// require signed requests
+    fn get_request_signing_profile(
+        &self,
+        _req: &types::RouterData<T, Req, Resp>,
+    ) -> CustomResult<Option<request_signing::RequestSigningProfile>, errors::ConnectorError> {
         Ok(None)
     }
 }
 
+/// Waits for the rate limit of the connector, if any, to allow a request to be sent to it.
+async fn apply_connector_rate_limit(
+    state: &AppState,
+    connector: &str,
+) -> CustomResult<(), errors::ConnectorError> {
+    let runtime_settings = state.runtime_conf.get();
+    let Some(rate_limit) = runtime_settings
+        .connector_requests
+        .rate_limits
+        .get(connector)
+    else {
+        return Ok(());
+    };
+
+    let decision = state.connector_rate_limiter.acquire(connector, rate_limit);
+    let outcome = match decision {
+        rate_limiter::RateLimitDecision::Allowed => return Ok(()),
+        rate_limiter::RateLimitDecision::Deferred(_) => "deferred",
+        rate_limiter::RateLimitDecision::Rejected => "rejected",
+    };
+    metrics::CONNECTOR_REQUESTS_THROTTLED.add(
+        &metrics::CONTEXT,
+        1,
+        &[
+            metrics::request::add_attributes("connector", connector.to_string()),
+            metrics::request::add_attributes("outcome", outcome),
+        ],
+    );
+
+    match decision {
+        rate_limiter::RateLimitDecision::Deferred(wait) => {
+            logger::debug!(
+                connector,
+                ?wait,
+                "Deferring request exceeding the rate limit"
+            );
+            tokio::time::sleep(wait).await;
+            Ok(())
+        }
+        _ => Err(errors::ConnectorError::RequestThrottled {
+            connector: connector.to_string(),
+        })
+        .into_report(),
+    }
+}
+
 /// Handle the flow by interacting with connector module
 /// `connector_request` is applicable only in case if the `CallConnectorAction` is `Trigger`
 /// In other cases, It will be created if required, even if it is not passed
@@ -277,8 +343,42 @@ where
                     error
                 })?);
 
+            // Requests which do not carry a certificate of their own present the client
+            // certificate of the connector account, if any
+            let connector_request = match connector_request {
+                Some(mut request) if request.certificate.is_none() => {
+                    request.add_certificate(connector_integration.get_certificate(req)?);
+                    request.add_certificate_key(connector_integration.get_certificate_key(req)?);
+                    Some(request)
+                }
+                connector_request => connector_request,
+            };
+
             match connector_request {
-                Some(request) => {
+                Some(mut request) => {
+                    apply_connector_rate_limit(state, &req.connector).await?;
+                    // Requests are signed once they can be sent, so that their timestamp is not
+                    // outdated by the rate limit
+                    let signing_profile =
+                        match connector_integration.get_request_signing_profile(req)? {
+                            Some(signing_profile) => Some(signing_profile),
+                            None => state
+                                .runtime_conf
+                                .get()
+                                .connector_requests
+                                .signing_profiles
+                                .get(&req.connector)
+                                .map(|config| {
+                                    request_signing::RequestSigningProfile::from_config(
+                                        config,
+                                        &req.connector_auth_type,
+                                    )
+                                })
+                                .transpose()?,
+                        };
+                    if let Some(signing_profile) = signing_profile {
+                        request_signing::sign_request(&mut request, &signing_profile)?;
+                    }
                     logger::debug!(connector_request=?request);
                     let response = call_connector_api(state, request).await;
                     logger::debug!(connector_response=?response);
@@ -368,9 +468,9 @@ pub async fn send_request(
     let url = &request.url;
     #[cfg(feature = "dummy_connector")]
     let should_bypass_proxy = url.starts_with(&state.conf.connectors.dummyconnector.base_url)
-        || client::proxy_bypass_urls(&state.conf.locker).contains(url);
+        || client::proxy_bypass_urls(state.get_locker()).contains(url);
     #[cfg(not(feature = "dummy_connector"))]
-    let should_bypass_proxy = client::proxy_bypass_urls(&state.conf.locker).contains(url);
+    let should_bypass_proxy = client::proxy_bypass_urls(state.get_locker()).contains(url);
     let client = client::create_client(
         &state.conf.proxy,
         should_bypass_proxy,
@@ -425,7 +525,7 @@ pub async fn send_request(
     }
     .add_headers(headers)
     .timeout(Duration::from_secs(
-        option_timeout_secs.unwrap_or(crate::consts::REQUEST_TIME_OUT),
+        option_timeout_secs.unwrap_or(state.runtime_conf.get().connector_requests.timeout),
     ))
     .send()
     .await
@@ -536,6 +636,7 @@ pub struct RedirectionFormData {
     pub payment_method_data: Option<api::PaymentMethodData>,
     pub amount: String,
     pub currency: String,
+    pub payment_link_config: Option<api_models::admin::PaymentLinkConfig>,
 }
 
 #[derive(Debug, Eq, PartialEq)]
@@ -617,7 +718,17 @@ where
     let merchant_id = auth_out.get_merchant_id().unwrap_or("").to_string();
     tracing::Span::current().record("merchant_id", &merchant_id);
 
-    let output = func(state, auth_out, payload).await.switch();
+    // The customers and payments of merchants pinned to a storage region are stored in the
+    // databases of the region
+    let storage_region = auth_out.get_storage_region().map(ToOwned::to_owned);
+    // The changes made to merchants during the request are audited as made by the caller
+    let actor = auth::get_actor(request.headers(), state).await;
+    let output = audit::with_actor(
+        actor,
+        connection::with_storage_region(storage_region, func(state, auth_out, payload)),
+    )
+    .await
+    .switch();
 
     let status_code = match output.as_ref() {
         Ok(res) => metrics::request::track_response_status_code(res),
@@ -701,6 +812,7 @@ where
             redirection_data.payment_method_data,
             redirection_data.amount,
             redirection_data.currency,
+            redirection_data.payment_link_config.as_ref(),
         )
         .respond_to(request)
         .map_into_boxed_body(),
@@ -819,12 +931,28 @@ pub trait Authenticate {
     fn get_client_secret(&self) -> Option<&String> {
         None
     }
+
+    fn get_captcha_token(&self) -> Option<&masking::Secret<String>> {
+        None
+    }
+
+    fn get_gift_card_id(&self) -> Option<&str> {
+        None
+    }
 }
 
 impl Authenticate for api_models::payments::PaymentsRequest {
     fn get_client_secret(&self) -> Option<&String> {
         self.client_secret.as_ref()
     }
+
+    fn get_captcha_token(&self) -> Option<&masking::Secret<String>> {
+        self.captcha_token.as_ref()
+    }
+
+    fn get_gift_card_id(&self) -> Option<&str> {
+        self.gift_card_id.as_deref()
+    }
 }
 
 impl Authenticate for api_models::payment_methods::PaymentMethodListRequest {
@@ -844,14 +972,88 @@ impl Authenticate for api_models::payments::PaymentsCancelRequest {}
 impl Authenticate for api_models::payments::PaymentsCaptureRequest {}
 impl Authenticate for api_models::payments::PaymentsStartRequest {}
 
+/// The message displayed while the customer is redirected to the connector, in each of the
+/// supported languages
+const REDIRECTION_MESSAGES: [(&str, &str); 7] = [
+    ("en", "Please wait while we process your payment..."),
+    (
+        "de",
+        "Bitte warten Sie, während wir Ihre Zahlung bearbeiten...",
+    ),
+    ("es", "Espere mientras procesamos su pago..."),
+    (
+        "fr",
+        "Veuillez patienter pendant le traitement de votre paiement...",
+    ),
+    ("it", "Attendere mentre elaboriamo il pagamento..."),
+    ("nl", "Een ogenblik geduld, we verwerken uw betaling..."),
+    ("pt", "Aguarde enquanto processamos o seu pagamento..."),
+];
+
+/// Returns the message displayed while the customer is redirected to the connector in the
+/// language of the locale, or `None` if the language is not supported.
+pub fn get_redirection_message(locale: &str) -> Option<&'static str> {
+    let language = locale
+        .split(|c| c == '-' || c == '_')
+        .next()
+        .unwrap_or(locale);
+    REDIRECTION_MESSAGES
+        .iter()
+        .find(|(supported_language, _)| supported_language.eq_ignore_ascii_case(language))
+        .map(|(_, message)| *message)
+}
+
+/// The branding applied to the page redirecting the customer to the connector, as configured for
+/// the business under which the payment is made
+struct RedirectionPageBranding {
+    style: maud::Markup,
+    body_style: String,
+    logo: maud::Markup,
+    message: &'static str,
+}
+
+impl RedirectionPageBranding {
+    fn new(payment_link_config: Option<&api_models::admin::PaymentLinkConfig>) -> Self {
+        let config = payment_link_config.cloned().unwrap_or_default();
+        let message = config
+            .locale
+            .as_deref()
+            .and_then(get_redirection_message)
+            .unwrap_or(REDIRECTION_MESSAGES[0].1);
+        let background_color = config.background_color.as_deref().unwrap_or("#ffffff");
+
+        Self {
+            style: maud::html! {
+                @if let Some(primary_color) = &config.primary_color {
+                    style { (format!("#loader1 svg path {{ fill: {primary_color}; }} h3 {{ color: {primary_color}; }}")) }
+                }
+            },
+            body_style: format!(
+                "background-color: {background_color}; padding: 20px; font-family: Arial, Helvetica, Sans-Serif;"
+            ),
+            logo: maud::html! {
+                @if let Some(logo) = &config.logo {
+                    div style="text-align: center;" {
+                        img src=(logo.as_str()) alt="" style="max-height: 60px; max-width: 200px;";
+                    }
+                }
+            },
+            message,
+        }
+    }
+}
+
 pub fn build_redirection_form(
     form: &RedirectForm,
     payment_method_data: Option<api_models::payments::PaymentMethodData>,
     amount: String,
     currency: String,
+    payment_link_config: Option<&api_models::admin::PaymentLinkConfig>,
 ) -> maud::Markup {
     use maud::PreEscaped;
 
+    let branding = RedirectionPageBranding::new(payment_link_config);
+
     match form {
         RedirectForm::Form {
             endpoint,
@@ -879,9 +1081,12 @@ pub fn build_redirection_form(
                     }
                 </style>
                 "##))
+                (branding.style)
             }
 
-            body style="background-color: #ffffff; padding: 20px; font-family: Arial, Helvetica, Sans-Serif;" {
+            body style=(branding.body_style) {
+
+                (branding.logo)
 
                 div id="loader1" class="lottie" style="height: 150px; display: block; position: relative; margin-left: auto; margin-right: auto;" { "" }
 
@@ -901,7 +1106,7 @@ pub fn build_redirection_form(
                 "#))
 
 
-                h3 style="text-align: center;" { "Please wait while we process your payment..." }
+                h3 style="text-align: center;" { (branding.message) }
                     form action=(PreEscaped(endpoint)) method=(method.to_string()) #payment_form {
                         @for (field, value) in form_fields {
                         input type="hidden" name=(field) value=(value);
@@ -937,8 +1142,11 @@ pub fn build_redirection_form(
                 head {
                     meta name="viewport" content="width=device-width, initial-scale=1";
                     (PreEscaped(r#"<script src="https://sandpay.bluesnap.com/web-sdk/5/bluesnap.js"></script>"#))
+                    (branding.style)
                 }
-                    body style="background-color: #ffffff; padding: 20px; font-family: Arial, Helvetica, Sans-Serif;" {
+                    body style=(branding.body_style) {
+
+                        (branding.logo)
 
                         div id="loader1" class="lottie" style="height: 150px; display: block; position: relative; margin-top: 150px; margin-left: auto; margin-right: auto;" { "" }
 
@@ -958,7 +1166,7 @@ pub fn build_redirection_form(
                         "#))
 
 
-                        h3 style="text-align: center;" { "Please wait while we process your payment..." }
+                        h3 style="text-align: center;" { (branding.message) }
                     }
 
                 (PreEscaped(format!("<script>
@@ -991,4 +1199,17 @@ mod tests {
     fn test_mime_essence() {
         assert_eq!(mime::APPLICATION_JSON.essence_str(), "application/json");
     }
+
+    #[test]
+    fn test_get_redirection_message() {
+        assert_eq!(
+            super::get_redirection_message("fr-CA"),
+            Some("Veuillez patienter pendant le traitement de votre paiement...")
+        );
+        assert_eq!(
+            super::get_redirection_message("PT_br"),
+            Some("Aguarde enquanto processamos o seu pagamento...")
+        );
+        assert_eq!(super::get_redirection_message("xx"), None);
+    }
 }
@@ -0,0 +1,179 @@
+//! Rate limiting of the requests sent to connectors.
+//!
+//! Every connector with a rate limit configured in `connector_requests.rate_limits` has a token
+//! bucket which holds up to `burst` tokens and is refilled at `requests_per_second` tokens per
+//! second. Every request sent to the connector takes a token from its bucket. When the bucket is
+//! empty, the request reserves the next token to be refilled and is deferred until then, so that
+//! bursts of requests are spread out instead of tripping the throttling of the connector. Requests
+//! which would have to be deferred for longer than `max_wait` are rejected instead.
+//!
+//! The buckets are held in the memory of the router process, and are shared by the workers of the
+//! process only. Every instance of the router allows the configured rate on its own, so the rate
+//! limits configured have to be the rate limits of the connectors divided by the number of
+//! instances of the router.
+
+use std::{
+    collections::HashMap,
+    sync::{Mutex, PoisonError},
+    time::{Duration, Instant},
+};
+
+use crate::configs::settings::ConnectorRateLimit;
+
+/// The decision of the rate limiter for a request to a connector.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RateLimitDecision {
+    /// The request can be sent immediately
+    Allowed,
+    /// The request has to be deferred for the given duration before being sent
+    Deferred(Duration),
+    /// The request would have to be deferred for longer than allowed
+    Rejected,
+}
+
+#[derive(Debug)]
+struct TokenBucket {
+    /// The number of tokens available, negative when tokens are reserved by deferred requests
+    tokens: f64,
+    last_refill: Instant,
+}
+
+/// Token buckets of the connectors, shared by the workers of the router process.
+#[derive(Debug, Default)]
+pub struct ConnectorRateLimiter {
+    buckets: Mutex<HashMap<String, TokenBucket>>,
+}
+
+impl ConnectorRateLimiter {
+    /// Takes a token from the bucket of the connector for a request, returning whether the request
+    /// can be sent immediately or has to be deferred.
+    pub fn acquire(&self, connector: &str, rate_limit: &ConnectorRateLimit) -> RateLimitDecision {
+        self.acquire_at(connector, rate_limit, Instant::now())
+    }
+
+    fn acquire_at(
+        &self,
+        connector: &str,
+        rate_limit: &ConnectorRateLimit,
+        now: Instant,
+    ) -> RateLimitDecision {
+        let refill_rate = f64::from(rate_limit.requests_per_second);
+        let capacity = f64::from(rate_limit.burst);
+
+        let mut buckets = self.buckets.lock().unwrap_or_else(PoisonError::into_inner);
+        let bucket = buckets
+            .entry(connector.to_owned())
+            .or_insert_with(|| TokenBucket {
+                tokens: capacity,
+                last_refill: now,
+            });
+
+        let elapsed = now.saturating_duration_since(bucket.last_refill);
+        bucket.tokens = (bucket.tokens + elapsed.as_secs_f64() * refill_rate).min(capacity);
+        bucket.last_refill = now;
+
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            return RateLimitDecision::Allowed;
+        }
+
+        // The request waits for the refilling of the token it reserves, after the tokens already
+        // reserved by the deferred requests ahead of it
+        let wait = Duration::from_secs_f64((1.0 - bucket.tokens) / refill_rate);
+        if wait > Duration::from_millis(rate_limit.max_wait) {
+            RateLimitDecision::Rejected
+        } else {
+            bucket.tokens -= 1.0;
+            RateLimitDecision::Deferred(wait)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_rate_limit(max_wait: u64) -> ConnectorRateLimit {
+        ConnectorRateLimit {
+            requests_per_second: 10,
+            burst: 2,
+            max_wait,
+        }
+    }
+
+    #[test]
+    fn test_requests_are_allowed_up_to_the_burst() {
+        let rate_limiter = ConnectorRateLimiter::default();
+        let rate_limit = get_rate_limit(0);
+        let now = Instant::now();
+
+        assert_eq!(
+            rate_limiter.acquire_at("stripe", &rate_limit, now),
+            RateLimitDecision::Allowed
+        );
+        assert_eq!(
+            rate_limiter.acquire_at("stripe", &rate_limit, now),
+            RateLimitDecision::Allowed
+        );
+        assert_eq!(
+            rate_limiter.acquire_at("stripe", &rate_limit, now),
+            RateLimitDecision::Rejected
+        );
+        // The buckets of the connectors are independent
+        assert_eq!(
+            rate_limiter.acquire_at("adyen", &rate_limit, now),
+            RateLimitDecision::Allowed
+        );
+    }
+
+    #[test]
+    fn test_requests_exceeding_the_rate_limit_are_deferred_in_order() {
+        let rate_limiter = ConnectorRateLimiter::default();
+        let rate_limit = get_rate_limit(250);
+        let now = Instant::now();
+
+        rate_limiter.acquire_at("stripe", &rate_limit, now);
+        rate_limiter.acquire_at("stripe", &rate_limit, now);
+
+        // Every deferred request waits for the refill of its own token, after the tokens reserved
+        // by the requests ahead of it
+        assert!(matches!(
+            rate_limiter.acquire_at("stripe", &rate_limit, now),
+            RateLimitDecision::Deferred(wait) if wait.as_millis() == 100
+        ));
+        assert!(matches!(
+            rate_limiter.acquire_at("stripe", &rate_limit, now),
+            RateLimitDecision::Deferred(wait) if wait.as_millis() == 200
+        ));
+
+        // The request would have to wait for the 300ms refill of the third reserved token
+        assert_eq!(
+            rate_limiter.acquire_at("stripe", &rate_limit, now),
+            RateLimitDecision::Rejected
+        );
+    }
+
+    #[test]
+    fn test_bucket_is_refilled_up_to_the_burst() {
+        let rate_limiter = ConnectorRateLimiter::default();
+        let rate_limit = get_rate_limit(0);
+        let now = Instant::now();
+
+        rate_limiter.acquire_at("stripe", &rate_limit, now);
+        rate_limiter.acquire_at("stripe", &rate_limit, now);
+
+        let later = now + Duration::from_secs(10);
+        assert_eq!(
+            rate_limiter.acquire_at("stripe", &rate_limit, later),
+            RateLimitDecision::Allowed
+        );
+        assert_eq!(
+            rate_limiter.acquire_at("stripe", &rate_limit, later),
+            RateLimitDecision::Allowed
+        );
+        assert_eq!(
+            rate_limiter.acquire_at("stripe", &rate_limit, later),
+            RateLimitDecision::Rejected
+        );
+    }
+}
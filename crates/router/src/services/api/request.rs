@@ -159,7 +159,7 @@ impl Request {
     }
 
     pub fn add_certificate_key(&mut self, certificate_key: Option<String>) {
-        self.certificate = certificate_key;
+        self.certificate_key = certificate_key;
     }
 
     pub fn set_form_data(&mut self, form_data: reqwest::multipart::Form) {
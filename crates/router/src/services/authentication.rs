@@ -11,7 +11,7 @@ use masking::{PeekInterface, StrongSecret};
 use crate::{
     configs::settings,
     core::{
-        api_keys,
+        api_keys, audit,
         errors::{self, utils::StorageErrorExt, RouterResult},
     },
     db::StorageInterface,
@@ -28,18 +28,27 @@ pub struct AuthenticationData {
 
 pub trait AuthInfo {
     fn get_merchant_id(&self) -> Option<&str>;
+    fn get_storage_region(&self) -> Option<&str>;
 }
 
 impl AuthInfo for () {
     fn get_merchant_id(&self) -> Option<&str> {
         None
     }
+
+    fn get_storage_region(&self) -> Option<&str> {
+        None
+    }
 }
 
 impl AuthInfo for AuthenticationData {
     fn get_merchant_id(&self) -> Option<&str> {
         Some(&self.merchant_account.merchant_id)
     }
+
+    fn get_storage_region(&self) -> Option<&str> {
+        self.merchant_account.storage_region.as_deref()
+    }
 }
 
 #[async_trait]
@@ -350,6 +359,64 @@ where
     }
 }
 
+/// Authenticates dashboard users whose JWT carries one of the given roles, for the operations
+/// which are restricted to some of the users of the merchant.
+#[derive(Debug)]
+pub(crate) struct JWTAuthWithRoles(pub &'static [&'static str]);
+
+#[derive(serde::Deserialize)]
+struct JwtAuthPayloadFetchMerchantAccountWithRole {
+    merchant_id: String,
+    role: Option<String>,
+}
+
+#[async_trait]
+impl<A> AuthenticateAndFetch<AuthenticationData, A> for JWTAuthWithRoles
+where
+    A: AppStateInfo + Sync,
+{
+    async fn authenticate_and_fetch(
+        &self,
+        request_headers: &HeaderMap,
+        state: &A,
+    ) -> RouterResult<AuthenticationData> {
+        let mut token = get_jwt(request_headers)?;
+        token = strip_jwt_token(token)?;
+        let payload =
+            decode_jwt::<JwtAuthPayloadFetchMerchantAccountWithRole>(token, state).await?;
+
+        let is_role_allowed = payload
+            .role
+            .as_deref()
+            .map_or(false, |role| self.0.contains(&role));
+        if !is_role_allowed {
+            return Err(report!(errors::ApiErrorResponse::AccessForbidden))
+                .attach_printable("The role of the user is not allowed to perform the operation");
+        }
+
+        let key_store = state
+            .store()
+            .get_merchant_key_store_by_merchant_id(
+                &payload.merchant_id,
+                &state.store().get_master_key().to_vec().into(),
+            )
+            .await
+            .change_context(errors::ApiErrorResponse::InvalidJwtToken)
+            .attach_printable("Failed to fetch merchant key store for the merchant id")?;
+
+        let merchant = state
+            .store()
+            .find_merchant_account_by_merchant_id(&payload.merchant_id, &key_store)
+            .await
+            .change_context(errors::ApiErrorResponse::InvalidJwtToken)?;
+
+        Ok(AuthenticationData {
+            merchant_account: merchant,
+            key_store,
+        })
+    }
+}
+
 pub trait ClientSecretFetch {
     fn get_client_secret(&self) -> Option<&String>;
 }
@@ -448,6 +515,50 @@ pub async fn is_ephemeral_auth<A: AppStateInfo + Sync>(
     Ok(Box::new(MerchantIdAuth(ephemeral_key.merchant_id)))
 }
 
+#[derive(serde::Deserialize)]
+struct JwtAuthPayloadFetchUser {
+    user_id: Option<String>,
+}
+
+/// Identifies who made an authenticated request, for the audit trail of the changes made to
+/// merchants: the dashboard user for requests authenticated with a JWT, the admin API key, or the
+/// API key identified by its prefix.
+pub async fn get_actor<A: AppStateInfo>(request_headers: &HeaderMap, state: &A) -> String {
+    if is_jwt_auth(request_headers) {
+        let user_id = match get_jwt(request_headers).and_then(strip_jwt_token) {
+            Ok(token) => decode_jwt::<JwtAuthPayloadFetchUser>(token, state)
+                .await
+                .ok()
+                .and_then(|payload| payload.user_id),
+            Err(_) => None,
+        };
+        return user_id.map_or_else(
+            || audit::DASHBOARD_USER_ACTOR.to_string(),
+            |user_id| format!("user_{user_id}"),
+        );
+    }
+
+    let Ok(api_key) = get_api_key(request_headers).map(str::trim) else {
+        return audit::UNKNOWN_ACTOR.to_string();
+    };
+    let conf = state.conf();
+    let is_admin_api_key = get_admin_api_key(
+        &conf.secrets,
+        #[cfg(feature = "kms")]
+        kms::get_kms_client(&conf.kms).await,
+    )
+    .await
+    .map_or(false, |admin_api_key| api_key == admin_api_key.peek());
+    if is_admin_api_key {
+        audit::ADMIN_API_KEY_ACTOR.to_string()
+    } else {
+        format!(
+            "api_key_{}",
+            api_keys::PlaintextApiKey::from(api_key).prefix()
+        )
+    }
+}
+
 pub fn is_jwt_auth(headers: &HeaderMap) -> bool {
     headers.get(crate::headers::AUTHORIZATION).is_some()
 }
@@ -46,6 +46,13 @@ pub type PaymentsSyncRouterData = RouterData<api::PSync, PaymentsSyncData, Payme
 pub type PaymentsCaptureRouterData =
     RouterData<api::Capture, PaymentsCaptureData, PaymentsResponseData>;
 pub type PaymentsCancelRouterData = RouterData<api::Void, PaymentsCancelData, PaymentsResponseData>;
+pub type PaymentsReauthorizeRouterData =
+    RouterData<api::Reauthorize, PaymentsReauthorizeData, PaymentsResponseData>;
+pub type PaymentsIncrementalAuthorizationRouterData = RouterData<
+    api::IncrementalAuthorization,
+    PaymentsIncrementalAuthorizationData,
+    PaymentsResponseData,
+>;
 pub type PaymentsSessionRouterData =
     RouterData<api::Session, PaymentsSessionData, PaymentsResponseData>;
 pub type RefundsRouterData<F> = RouterData<F, RefundsData, RefundsResponseData>;
@@ -71,6 +78,8 @@ pub type PaymentsSessionResponseRouterData<R> =
     ResponseRouterData<api::Session, R, PaymentsSessionData, PaymentsResponseData>;
 pub type PaymentsInitResponseRouterData<R> =
     ResponseRouterData<api::InitPayment, R, PaymentsAuthorizeData, PaymentsResponseData>;
+pub type PaymentsReauthorizeResponseRouterData<R> =
+    ResponseRouterData<api::Reauthorize, R, PaymentsReauthorizeData, PaymentsResponseData>;
 pub type PaymentsCaptureResponseRouterData<R> =
     ResponseRouterData<api::Capture, R, PaymentsCaptureData, PaymentsResponseData>;
 pub type TokenizationResponseRouterData<R> = ResponseRouterData<
@@ -123,6 +132,16 @@ pub type PaymentsSessionType =
     dyn services::ConnectorIntegration<api::Session, PaymentsSessionData, PaymentsResponseData>;
 pub type PaymentsVoidType =
     dyn services::ConnectorIntegration<api::Void, PaymentsCancelData, PaymentsResponseData>;
+pub type PaymentsReauthorizeType = dyn services::ConnectorIntegration<
+    api::Reauthorize,
+    PaymentsReauthorizeData,
+    PaymentsResponseData,
+>;
+pub type PaymentsIncrementalAuthorizationType = dyn services::ConnectorIntegration<
+    api::IncrementalAuthorization,
+    PaymentsIncrementalAuthorizationData,
+    PaymentsResponseData,
+>;
 pub type TokenizationType = dyn services::ConnectorIntegration<
     api::PaymentMethodToken,
     PaymentMethodTokenizationData,
@@ -158,6 +177,9 @@ pub type PayoutRecipientType =
 #[cfg(feature = "payouts")]
 pub type PayoutQuoteType =
     dyn services::ConnectorIntegration<api::PoQuote, PayoutsData, PayoutsResponseData>;
+#[cfg(feature = "payouts")]
+pub type PayoutSyncType =
+    dyn services::ConnectorIntegration<api::PoSync, PayoutsData, PayoutsResponseData>;
 
 pub type RefreshTokenType =
     dyn services::ConnectorIntegration<api::AccessTokenAuth, AccessTokenRequestData, AccessToken>;
@@ -177,6 +199,13 @@ pub type SubmitEvidenceType = dyn services::ConnectorIntegration<
 pub type UploadFileType =
     dyn services::ConnectorIntegration<api::Upload, UploadFileRequestData, UploadFileResponse>;
 
+#[cfg(feature = "payouts")]
+pub type PayoutUploadFileType = dyn services::ConnectorIntegration<
+    api::PoUploadFile,
+    UploadFileRequestData,
+    UploadFileResponse,
+>;
+
 pub type RetrieveFileType = dyn services::ConnectorIntegration<
     api::Retrieve,
     RetrieveFileRequestData,
@@ -189,6 +218,12 @@ pub type DefendDisputeType = dyn services::ConnectorIntegration<
     DefendDisputeResponse,
 >;
 
+pub type AccountUpdaterType = dyn services::ConnectorIntegration<
+    api::AccountUpdate,
+    AccountUpdaterRequestData,
+    AccountUpdaterResponseData,
+>;
+
 pub type VerifyRouterData = RouterData<api::Verify, VerifyRequestData, PaymentsResponseData>;
 
 pub type AcceptDisputeRouterData =
@@ -205,9 +240,22 @@ pub type RetrieveFileRouterData =
 pub type DefendDisputeRouterData =
     RouterData<api::Defend, DefendDisputeRequestData, DefendDisputeResponse>;
 
+pub type AccountUpdaterRouterData =
+    RouterData<api::AccountUpdate, AccountUpdaterRequestData, AccountUpdaterResponseData>;
+pub type AccountUpdaterResponseRouterData<R> = ResponseRouterData<
+    api::AccountUpdate,
+    R,
+    AccountUpdaterRequestData,
+    AccountUpdaterResponseData,
+>;
+
 #[cfg(feature = "payouts")]
 pub type PayoutsRouterData<F> = RouterData<F, PayoutsData, PayoutsResponseData>;
 
+#[cfg(feature = "payouts")]
+pub type PayoutUploadFileRouterData =
+    RouterData<api::PoUploadFile, UploadFileRequestData, UploadFileResponse>;
+
 #[cfg(feature = "payouts")]
 pub type PayoutsResponseRouterData<F, R> =
     ResponseRouterData<F, R, PayoutsData, PayoutsResponseData>;
@@ -233,7 +281,7 @@ pub struct RouterData<Flow, Request, Response> {
     pub access_token: Option<AccessToken>,
     pub session_token: Option<String>,
     pub reference_id: Option<String>,
-    pub payment_method_token: Option<String>,
+    pub payment_method_token: Option<PaymentMethodToken>,
     pub recurring_mandate_payment_data: Option<RecurringMandatePaymentData>,
     pub preprocessing_id: Option<String>,
     /// This is the balance amount for gift cards or voucher
@@ -259,6 +307,78 @@ pub struct RouterData<Flow, Request, Response> {
     pub quote_id: Option<String>,
 
     pub test_mode: Option<bool>,
+
+    /// Deterministic key identifying this connector call, sent as the connector's
+    /// idempotency header so that retries of the same flow are not processed twice
+    pub idempotency_key: Option<Secret<String>>,
+
+    /// Client certificate configured on the merchant connector account, presented to connectors
+    /// which require mutual TLS
+    pub connector_client_certificate: Option<ConnectorClientCertificate>,
+}
+
+/// The payment method of the payment as sent to the connector, in place of the payment method data
+/// of the payment.
+#[derive(Debug, Clone)]
+pub enum PaymentMethodToken {
+    /// A token of the payment method issued by the connector
+    Token(String),
+    /// The card details decrypted by the router from the Apple Pay payment token of the payment
+    ApplePayDecrypt(Box<ApplePayPredecryptData>),
+}
+
+/// The payment data of an Apple Pay payment token, once decrypted.
+#[derive(Debug, Clone, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApplePayPredecryptData {
+    /// The device specific account number of the card
+    pub application_primary_account_number: cards::CardNumber,
+    /// The expiry date of the card, in the `YYMMDD` format
+    pub application_expiration_date: String,
+    /// The ISO 4217 numeric code of the currency of the transaction
+    pub currency_code: String,
+    pub transaction_amount: i64,
+    pub device_manufacturer_identifier: Secret<String>,
+    /// The type of the cryptogram, either `3DSecure` or `EMV`
+    pub payment_data_type: String,
+    pub payment_data: ApplePayCryptogramData,
+}
+
+#[derive(Debug, Clone, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApplePayCryptogramData {
+    pub online_payment_cryptogram: Secret<String>,
+    pub eci_indicator: Option<String>,
+}
+
+impl ApplePayPredecryptData {
+    /// Returns the two digit expiry month of the card
+    pub fn get_expiry_month(&self) -> Secret<String> {
+        Secret::new(
+            self.application_expiration_date
+                .get(2..4)
+                .unwrap_or_default()
+                .to_string(),
+        )
+    }
+
+    /// Returns the four digit expiry year of the card
+    pub fn get_four_digit_expiry_year(&self) -> Secret<String> {
+        Secret::new(format!(
+            "20{}",
+            self.application_expiration_date
+                .get(..2)
+                .unwrap_or_default()
+        ))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ConnectorClientCertificate {
+    /// Base64 encoded client certificate, in PEM format
+    pub certificate: Secret<String>,
+    /// Base64 encoded private key of the client certificate, in PKCS#8 PEM format
+    pub certificate_key: Secret<String>,
 }
 
 #[derive(Debug, Clone)]
@@ -287,6 +407,8 @@ pub struct PayoutsResponseData {
     pub status: Option<storage_enums::PayoutStatus>,
     pub connector_payout_id: String,
     pub payout_eligible: Option<bool>,
+    pub connector_fee: Option<i64>,
+    pub fx_margin: Option<i64>,
 }
 
 #[derive(Clone, Debug, Default)]
@@ -316,12 +438,31 @@ pub struct PaymentsAuthorizeData {
     pub browser_info: Option<BrowserInformation>,
     pub order_details: Option<Vec<api_models::payments::OrderDetailsWithAmount>>,
     pub order_category: Option<String>,
+    pub l2_l3_data: Option<api_models::payments::L2L3Data>,
     pub session_token: Option<String>,
     pub enrolled_for_3ds: bool,
     pub related_transaction_id: Option<String>,
     pub payment_experience: Option<storage_enums::PaymentExperience>,
     pub payment_method_type: Option<storage_enums::PaymentMethodType>,
     pub customer_id: Option<String>,
+    /// The network token of the card, to be sent in place of the card number by connectors which
+    /// support network tokens
+    pub network_token_data: Option<NetworkTokenData>,
+    /// The amounts charged to the customer and settled to the merchant, if the payment is charged
+    /// in a currency other than its settlement currency, to be sent by connectors which support
+    /// dynamic currency conversion
+    pub currency_conversion: Option<api_models::payments::CurrencyConversionDetails>,
+}
+
+/// A network token provisioned for a card by the token service of its card network, along with a
+/// cryptogram for a single transaction
+#[derive(Debug, Clone)]
+pub struct NetworkTokenData {
+    pub token_number: cards::CardNumber,
+    pub token_exp_month: Secret<String>,
+    pub token_exp_year: Secret<String>,
+    pub token_cryptogram: Secret<String>,
+    pub eci: Option<String>,
 }
 
 #[derive(Debug, Clone, Default)]
@@ -332,6 +473,18 @@ pub struct PaymentsCaptureData {
     pub payment_amount: i64,
     pub capture_method: storage_enums::CaptureMethod,
     pub connector_meta: Option<serde_json::Value>,
+    pub multiple_capture_data: Option<MultipleCaptureRequestData>,
+    /// The amount to capture in the presentment currency, if the payment is charged in a currency
+    /// other than its settlement currency
+    pub currency_conversion: Option<api_models::payments::CurrencyConversionDetails>,
+}
+
+#[derive(Debug, Clone)]
+pub struct MultipleCaptureRequestData {
+    /// The position of the capture among the captures of the authorized payment, starting at 1
+    pub capture_sequence: i16,
+    /// The identifier of the capture, to be sent to the connector as the reference of the capture
+    pub capture_reference: String,
 }
 
 #[derive(Debug, Clone)]
@@ -411,6 +564,31 @@ pub struct PaymentsCancelData {
     pub connector_meta: Option<serde_json::Value>,
 }
 
+/// Request to extend an authorization nearing its expiry, by authorizing the amount of the
+/// payment again with the connector.
+#[derive(Debug, Clone)]
+pub struct PaymentsReauthorizeData {
+    pub amount: i64,
+    pub currency: storage_enums::Currency,
+    /// The connector transaction ID of the authorization being extended
+    pub connector_transaction_id: String,
+    pub connector_meta: Option<serde_json::Value>,
+}
+
+/// Request to increase the amount authorized for a payment awaiting capture.
+#[derive(Debug, Clone)]
+pub struct PaymentsIncrementalAuthorizationData {
+    /// The total amount to be authorized, including the amount authorized so far
+    pub total_amount: i64,
+    /// The amount by which the authorized amount is increased
+    pub additional_amount: i64,
+    pub currency: storage_enums::Currency,
+    pub reason: Option<String>,
+    /// The connector transaction ID of the authorization being increased
+    pub connector_transaction_id: String,
+    pub connector_meta: Option<serde_json::Value>,
+}
+
 #[derive(Debug, Clone)]
 pub struct PaymentsSessionData {
     pub amount: i64,
@@ -528,6 +706,12 @@ pub enum PaymentsResponseData {
         session_token: Option<api::SessionToken>,
         connector_response_reference_id: Option<String>,
     },
+    IncrementalAuthorizationResponse {
+        status: api::enums::AuthorizationStatus,
+        connector_authorization_id: Option<String>,
+        error_code: Option<String>,
+        error_message: Option<String>,
+    },
 }
 
 #[derive(Debug, Clone)]
@@ -574,6 +758,9 @@ pub struct RefundsData {
     pub refund_amount: i64,
     /// Arbitrary metadata required for refund
     pub connector_metadata: Option<serde_json::Value>,
+    /// The amount to be refunded in the presentment currency, if the payment is charged in a
+    /// currency other than its settlement currency
+    pub currency_conversion: Option<api_models::payments::CurrencyConversionDetails>,
 }
 
 #[derive(Clone, Debug, Default, serde::Serialize, serde::Deserialize)]
@@ -594,7 +781,8 @@ pub struct BrowserInformation {
 pub struct RefundsResponseData {
     pub connector_refund_id: String,
     pub refund_status: storage_enums::RefundStatus,
-    // pub amount_received: Option<i32>, // Calculation for amount received not in place yet
+    /// The amount reported as refunded by the connector, if the connector reports it
+    pub settled_amount: Option<i64>,
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -655,6 +843,44 @@ pub struct SubmitEvidenceRequestData {
     pub uncategorized_file: Option<Vec<u8>>,
     pub uncategorized_file_provider_file_id: Option<String>,
     pub uncategorized_text: Option<String>,
+    pub evidence_bundle: EvidenceBundle,
+}
+
+/// All the files submitted as evidence of a dispute, for connectors which accept several files of
+/// an evidence category in a single submission. The files of each category are in the order in
+/// which they were submitted.
+#[derive(Default, Debug, Clone)]
+pub struct EvidenceBundle {
+    pub files: Vec<EvidenceBundleFile>,
+}
+
+#[derive(Debug, Clone)]
+pub struct EvidenceBundleFile {
+    pub evidence_type: api_models::disputes::EvidenceType,
+    pub file_id: String,
+    pub file_name: Option<String>,
+    pub file_type: String,
+    pub file_size: i32,
+    pub file_data: Option<Vec<u8>>,
+    pub provider_file_id: Option<String>,
+}
+
+impl EvidenceBundle {
+    pub fn get_files(
+        &self,
+        evidence_type: api_models::disputes::EvidenceType,
+    ) -> impl Iterator<Item = &EvidenceBundleFile> {
+        self.files
+            .iter()
+            .filter(move |file| file.evidence_type == evidence_type)
+    }
+
+    pub fn get_total_file_size(&self) -> i64 {
+        self.files
+            .iter()
+            .map(|file| i64::from(file.file_size))
+            .sum()
+    }
 }
 
 #[derive(Default, Clone, Debug)]
@@ -681,6 +907,7 @@ pub struct UploadFileRequestData {
     pub file: Vec<u8>,
     pub file_type: mime::Mime,
     pub file_size: i32,
+    pub document_type: Option<api::KycDocumentType>,
 }
 
 #[derive(Default, Clone, Debug)]
@@ -698,6 +925,36 @@ pub struct RetrieveFileResponse {
     pub file_data: Vec<u8>,
 }
 
+/// Request to the account updater service of a connector for the latest details of a saved card,
+/// which the card networks collect from the issuers (Visa Account Updater, Mastercard Automatic
+/// Billing Updater).
+#[derive(Clone, Debug)]
+pub struct AccountUpdaterRequestData {
+    pub card_number: cards::CardNumber,
+    pub card_exp_month: Secret<String>,
+    pub card_exp_year: Secret<String>,
+    pub card_network: Option<storage_enums::CardNetwork>,
+}
+
+#[derive(Clone, Debug)]
+pub enum AccountUpdaterResponseData {
+    /// The issuer has not reported any update of the card
+    NoUpdate,
+    /// The card was renewed by the issuer with a new expiry date
+    ExpiryUpdated {
+        card_exp_month: Secret<String>,
+        card_exp_year: Secret<String>,
+    },
+    /// The card was replaced by the issuer with a new card number
+    CardReplaced {
+        card_number: cards::CardNumber,
+        card_exp_month: Secret<String>,
+        card_exp_year: Secret<String>,
+    },
+    /// The account of the card was closed
+    AccountClosed,
+}
+
 #[derive(Debug, Clone, Default, serde::Deserialize, serde::Serialize)]
 pub struct ConnectorResponse {
     pub merchant_id: String,
@@ -865,12 +1122,15 @@ impl From<&VerifyRouterData> for PaymentsAuthorizeData {
             browser_info: data.request.browser_info.clone(),
             order_details: None,
             order_category: None,
+            l2_l3_data: None,
             session_token: None,
             enrolled_for_3ds: true,
             related_transaction_id: None,
             payment_experience: None,
             payment_method_type: None,
             customer_id: None,
+            network_token_data: None,
+            currency_conversion: None,
         }
     }
 }
@@ -914,6 +1174,9 @@ impl<F1, F2, T1, T2> From<(&RouterData<F1, T1, PaymentsResponseData>, T2)>
             quote_id: data.quote_id.clone(),
             test_mode: data.test_mode,
             payment_method_balance: data.payment_method_balance.clone(),
+            // The derived request is a separate connector call and must not reuse the key
+            idempotency_key: None,
+            connector_client_certificate: data.connector_client_certificate.clone(),
         }
     }
 }
@@ -985,6 +1248,8 @@ impl<F1, F2>
             quote_id: data.quote_id.clone(),
             test_mode: data.test_mode,
             payment_method_balance: None,
+            idempotency_key: None,
+            connector_client_certificate: data.connector_client_certificate.clone(),
         }
     }
 }
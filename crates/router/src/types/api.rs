@@ -6,11 +6,18 @@ pub mod disputes;
 pub mod enums;
 pub mod ephemeral_key;
 pub mod files;
+pub mod gift_cards;
+pub mod invoices;
 pub mod mandates;
+pub mod payment_links;
+pub mod payment_method_shares;
 pub mod payment_methods;
 pub mod payments;
 pub mod payouts;
 pub mod refunds;
+pub mod reports;
+pub mod subscriptions;
+pub mod terminals;
 pub mod webhooks;
 
 use std::{fmt::Debug, str::FromStr};
@@ -33,6 +40,53 @@ use crate::{
 #[derive(Clone, Debug)]
 pub struct AccessTokenAuth;
 
+/// Stable name of a connector flow, used to derive identifiers that must not change
+/// across deployments, such as connector idempotency keys.
+pub trait ConnectorFlowName {
+    const FLOW_NAME: &'static str;
+}
+
+macro_rules! impl_connector_flow_name {
+    ($($flow:ty => $name:literal),* $(,)?) => {
+        $(
+            impl ConnectorFlowName for $flow {
+                const FLOW_NAME: &'static str = $name;
+            }
+        )*
+    };
+}
+
+impl_connector_flow_name!(
+    Authorize => "authorize",
+    CompleteAuthorize => "complete_authorize",
+    Capture => "capture",
+    PSync => "psync",
+    Void => "void",
+    Session => "session",
+    Verify => "verify",
+    Reauthorize => "reauthorize",
+    IncrementalAuthorization => "incremental_authorization",
+    Execute => "refund_execute",
+    RSync => "refund_sync",
+    Accept => "dispute_accept",
+    Evidence => "dispute_evidence",
+    Defend => "dispute_defend",
+    Upload => "file_upload",
+    Retrieve => "file_retrieve",
+);
+
+#[cfg(feature = "payouts")]
+impl_connector_flow_name!(
+    PoCancel => "payout_cancel",
+    PoCreate => "payout_create",
+    PoEligibility => "payout_eligibility",
+    PoFulfill => "payout_fulfill",
+    PoQuote => "payout_quote",
+    PoRecipient => "payout_recipient",
+    PoSync => "payout_sync",
+    PoUploadFile => "payout_upload_file",
+);
+
 pub trait ConnectorAccessToken:
     ConnectorIntegration<AccessTokenAuth, types::AccessTokenRequestData, types::AccessToken>
 {
@@ -47,6 +101,13 @@ pub trait ConnectorTransactionId: ConnectorCommon + Sync {
     }
 }
 
+pub trait ConnectorCardPresent: ConnectorCommon + Sync {
+    /// Whether the connector can process payments for cards presented on a registered terminal.
+    fn is_card_present_supported(&self) -> bool {
+        false
+    }
+}
+
 pub trait ConnectorCommon {
     /// Name of the connector (in lowercase).
     fn id(&self) -> &'static str;
@@ -71,6 +132,12 @@ pub trait ConnectorCommon {
     /// The base URL for interacting with the connector's API.
     fn base_url<'a>(&self, connectors: &'a Connectors) -> &'a str;
 
+    /// Whether the connector charges payments converted to the currency of the customer in the
+    /// presentment currency, as sent in `currency_conversion` of the request data.
+    fn is_currency_conversion_supported(&self) -> bool {
+        false
+    }
+
     /// common error response for a connector if it is same in all case
     fn build_error_response(
         &self,
@@ -112,7 +179,9 @@ pub trait Connector:
     + Dispute
     + FileUpload
     + ConnectorTransactionId
+    + ConnectorCardPresent
     + Payouts
+    + PaymentMethodAccountUpdater
 {
 }
 
@@ -131,7 +200,9 @@ impl<
             + Dispute
             + FileUpload
             + ConnectorTransactionId
-            + Payouts,
+            + ConnectorCardPresent
+            + Payouts
+            + PaymentMethodAccountUpdater,
     > Connector for T
 {
 }
@@ -178,7 +249,11 @@ pub struct SessionConnectorData {
 }
 
 pub enum ConnectorChoice {
-    SessionMultiple(Vec<SessionConnectorData>),
+    SessionMultiple {
+        connectors: Vec<SessionConnectorData>,
+        /// Eligible connectors whose session tokens are only fetched when requested
+        deferred_connectors: Vec<String>,
+    },
     StraightThrough(serde_json::Value),
     Decide,
 }
@@ -1,10 +1,14 @@
 pub use api_models::admin::{
-    payout_routing_algorithm, MerchantAccountCreate, MerchantAccountDeleteResponse,
-    MerchantAccountResponse, MerchantAccountUpdate, MerchantConnectorCreate,
-    MerchantConnectorDeleteResponse, MerchantConnectorDetails, MerchantConnectorDetailsWrap,
-    MerchantConnectorId, MerchantConnectorResponse, MerchantDetails, MerchantId,
+    payout_routing_algorithm, CardBinRange, ConnectorExclusionRule,
+    ConnectorExclusionRulesResponse, ConnectorExclusionRulesUpdateRequest, ConnectorMtlsDetails,
+    ConnectorVolumeSplit, MerchantAccountCreate, MerchantAccountDeleteResponse,
+    MerchantAccountResponse, MerchantAccountUpdate, MerchantAllowedDomainsResponse,
+    MerchantAllowedDomainsUpdateRequest, MerchantConnectorCreate, MerchantConnectorDeleteResponse,
+    MerchantConnectorDetails, MerchantConnectorDetailsWrap, MerchantConnectorId,
+    MerchantConnectorResponse, MerchantDetails, MerchantId, MerchantKeyStoreResponse,
     PaymentMethodsEnabled, PayoutRoutingAlgorithm, PayoutStraightThroughAlgorithm,
     RoutingAlgorithm, StraightThroughAlgorithm, ToggleKVRequest, ToggleKVResponse, WebhookDetails,
+    WebhookEndpointHealthResponse, WebhookMtlsDetails,
 };
 use common_utils::ext_traits::ValueExt;
 
@@ -13,9 +17,17 @@ use crate::{core::errors, types::domain};
 impl TryFrom<domain::MerchantAccount> for MerchantAccountResponse {
     type Error = error_stack::Report<errors::ParsingError>;
     fn try_from(item: domain::MerchantAccount) -> Result<Self, Self::Error> {
-        let primary_business_details: Vec<api_models::admin::PrimaryBusinessDetails> = item
+        let mut primary_business_details: Vec<api_models::admin::PrimaryBusinessDetails> = item
             .primary_business_details
             .parse_value("primary_business_details")?;
+        // The signing keys of the business webhook endpoints are never returned, including the
+        // keys stored in the business details before they were stored encrypted
+        for endpoint in primary_business_details
+            .iter_mut()
+            .flat_map(|business_details| business_details.webhook_endpoints.iter_mut().flatten())
+        {
+            endpoint.webhook_secret = None;
+        }
 
         Ok(Self {
             merchant_id: item.merchant_id,
@@ -38,6 +50,19 @@ impl TryFrom<domain::MerchantAccount> for MerchantAccountResponse {
             payout_routing_algorithm: item.payout_routing_algorithm,
             organization_id: item.organization_id,
             is_recon_enabled: item.is_recon_enabled,
+            webhook_mtls_enabled: item.webhook_mtls_details.is_some(),
+            storage_region: item.storage_region,
         })
     }
 }
+
+impl From<domain::MerchantKeyStore> for MerchantKeyStoreResponse {
+    fn from(item: domain::MerchantKeyStore) -> Self {
+        Self {
+            merchant_id: item.merchant_id,
+            key_version: item.key_version,
+            created_at: item.created_at,
+            rotated_at: item.rotated_at,
+        }
+    }
+}
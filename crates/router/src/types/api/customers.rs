@@ -1,5 +1,11 @@
 use api_models::customers;
-pub use api_models::customers::{CustomerDeleteResponse, CustomerId, CustomerRequest};
+pub use api_models::customers::{
+    CustomerAddressDeleteResponse, CustomerAddressId, CustomerAddressRequest,
+    CustomerAddressResponse, CustomerAddressType, CustomerDefaultAddressRequest,
+    CustomerDefaultPaymentMethodResponse, CustomerDeleteResponse, CustomerId, CustomerImportError,
+    CustomerImportJobId, CustomerImportPaymentMethod, CustomerImportRecord, CustomerImportRequest,
+    CustomerImportResponse, CustomerPaymentMethodId, CustomerRequest,
+};
 use serde::Serialize;
 
 use crate::{core::errors::RouterResult, newtype, types::domain};
@@ -25,6 +31,9 @@ impl From<domain::Customer> for CustomerResponse {
             created_at: cust.created_at,
             metadata: cust.metadata,
             address: None,
+            default_shipping_address_id: cust.default_shipping_address_id,
+            default_billing_address_id: cust.default_billing_address_id,
+            default_payment_method_id: cust.default_payment_method_id,
         }
         .into()
     }
@@ -25,6 +25,7 @@ impl ForeignTryFrom<FileUploadProvider> for types::Connector {
         match item {
             FileUploadProvider::Stripe => Ok(Self::Stripe),
             FileUploadProvider::Checkout => Ok(Self::Checkout),
+            FileUploadProvider::Adyen => Ok(Self::Adyen),
             FileUploadProvider::Router => Err(errors::ApiErrorResponse::NotSupported {
                 message: "File upload provider is not a connector".to_owned(),
             }
@@ -39,6 +40,7 @@ impl ForeignTryFrom<&types::Connector> for FileUploadProvider {
         match *item {
             types::Connector::Stripe => Ok(Self::Stripe),
             types::Connector::Checkout => Ok(Self::Checkout),
+            types::Connector::Adyen => Ok(Self::Adyen),
             _ => Err(errors::ApiErrorResponse::NotSupported {
                 message: "Connector not supported as file provider".to_owned(),
             }
@@ -55,6 +57,8 @@ pub struct CreateFileRequest {
     pub file_type: mime::Mime,
     pub purpose: FilePurpose,
     pub dispute_id: Option<String>,
+    pub payout_id: Option<String>,
+    pub document_type: Option<KycDocumentType>,
 }
 
 #[derive(Debug, serde::Deserialize, strum::Display, Clone)]
@@ -62,6 +66,19 @@ pub struct CreateFileRequest {
 #[strum(serialize_all = "snake_case")]
 pub enum FilePurpose {
     DisputeEvidence,
+    PayoutKyc,
+}
+
+/// The kind of identity document uploaded with the `payout_kyc` purpose
+#[derive(Debug, Clone, Copy, serde::Deserialize, strum::Display, strum::EnumString)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum KycDocumentType {
+    IdentityCard,
+    Passport,
+    DriversLicense,
+    ProofOfAddress,
+    BankStatement,
 }
 
 #[derive(Debug, Clone)]
@@ -96,4 +113,16 @@ pub trait FileUpload: ConnectorCommon + Sync + UploadFile + RetrieveFile {
         }
         .into())
     }
+
+    /// Whether several files of an evidence category can be submitted as evidence of a dispute in
+    /// a single submission, through the evidence bundle of the request
+    fn supports_evidence_bundle(&self) -> bool {
+        false
+    }
+
+    /// The maximum total size, in bytes, of the files submitted as evidence of a dispute in a
+    /// single submission
+    fn get_max_evidence_bundle_size(&self) -> Option<i64> {
+        None
+    }
 }
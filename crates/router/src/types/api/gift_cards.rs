@@ -0,0 +1,4 @@
+pub use api_models::gift_cards::{
+    GiftCardCreateRequest, GiftCardRedeemRequest, GiftCardReloadRequest, GiftCardResponse,
+    GiftCardTransactionListConstraints, GiftCardTransactionResponse,
+};
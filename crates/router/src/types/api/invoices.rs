@@ -0,0 +1,4 @@
+pub use api_models::invoices::{
+    InvoiceCreateRequest, InvoiceLineItem, InvoiceListConstraints, InvoicePaymentLinkQuery,
+    InvoicePaymentRequest, InvoiceResponse,
+};
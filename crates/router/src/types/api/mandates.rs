@@ -40,7 +40,7 @@ impl MandateResponseExt for MandateResponse {
                 state,
                 &payment_method.customer_id,
                 &payment_method.merchant_id,
-                &payment_method.payment_method_id,
+                payment_methods::cards::get_locker_id(&payment_method),
             )
             .await?;
             let card_detail = payment_methods::transformers::get_card_detail(&payment_method, card)
@@ -0,0 +1,3 @@
+pub use api_models::payment_links::{
+    PaymentLinkCreateRequest, PaymentLinkQuery, PaymentLinkResponse, PaymentLinkSdkConfig,
+};
@@ -2,16 +2,18 @@ use api_models::enums as api_enums;
 pub use api_models::payment_methods::{
     CardDetail, CardDetailFromLocker, CustomerPaymentMethod, CustomerPaymentMethodsListResponse,
     DeleteTokenizeByDateRequest, DeleteTokenizeByTokenRequest, GetTokenizePayloadRequest,
-    GetTokenizePayloadResponse, PaymentMethodCreate, PaymentMethodDeleteResponse, PaymentMethodId,
-    PaymentMethodList, PaymentMethodListRequest, PaymentMethodListResponse, PaymentMethodResponse,
-    PaymentMethodUpdate, TokenizePayloadEncrypted, TokenizePayloadRequest, TokenizedCardValue1,
-    TokenizedCardValue2, TokenizedWalletValue1, TokenizedWalletValue2,
+    GetTokenizePayloadResponse, PaymentMethodAccountUpdate, PaymentMethodCreate,
+    PaymentMethodDeleteResponse, PaymentMethodId, PaymentMethodList, PaymentMethodListRequest,
+    PaymentMethodListResponse, PaymentMethodResponse, PaymentMethodUpdate,
+    TokenizePayloadEncrypted, TokenizePayloadRequest, TokenizedCardValue1, TokenizedCardValue2,
+    TokenizedWalletValue1, TokenizedWalletValue2,
 };
 use error_stack::report;
 
 use crate::{
     core::errors::{self, RouterResult},
-    types::transformers::ForeignFrom,
+    services,
+    types::{self, transformers::ForeignFrom},
 };
 
 pub(crate) trait PaymentMethodCreateExt {
@@ -35,3 +37,15 @@ impl PaymentMethodCreateExt for PaymentMethodCreate {
         Ok(())
     }
 }
+
+#[derive(Debug, Clone)]
+pub struct AccountUpdate;
+
+pub trait PaymentMethodAccountUpdater:
+    services::ConnectorIntegration<
+    AccountUpdate,
+    types::AccountUpdaterRequestData,
+    types::AccountUpdaterResponseData,
+>
+{
+}
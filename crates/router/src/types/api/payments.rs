@@ -1,14 +1,20 @@
 pub use api_models::payments::{
-    AcceptanceType, Address, AddressDetails, Amount, AuthenticationForStartResponse, Card,
-    CryptoData, CustomerAcceptance, MandateData, MandateTransactionType, MandateType,
-    MandateValidationFields, NextActionType, OnlineMandate, PayLaterData, PaymentIdType,
-    PaymentListConstraints, PaymentListFilterConstraints, PaymentListFilters, PaymentListResponse,
-    PaymentMethodData, PaymentMethodDataResponse, PaymentOp, PaymentRetrieveBody,
-    PaymentRetrieveBodyWithCredentials, PaymentsCancelRequest, PaymentsCaptureRequest,
-    PaymentsRedirectRequest, PaymentsRedirectionResponse, PaymentsRequest, PaymentsResponse,
-    PaymentsResponseForm, PaymentsRetrieveRequest, PaymentsSessionRequest, PaymentsSessionResponse,
-    PaymentsStartRequest, PgRedirectResponse, PhoneDetails, RedirectionResponse, SessionToken,
-    TimeRange, UrlDetails, VerifyRequest, VerifyResponse, WalletData,
+    AcceptanceType, Address, AddressDetails, Amount, AuthenticationForStartResponse,
+    AuthorizationRecord, Card, CrossBorderFeeConfig, CryptoData, CustomerAcceptance,
+    HostedCheckoutQuery, HostedCheckoutSubmitRequest, MandateData, MandateTransactionType,
+    MandateType, MandateValidationFields, NextActionType, OnlineMandate, PayLaterData,
+    PaymentExpandableResource, PaymentIdType, PaymentListConstraints, PaymentListFilterConstraints,
+    PaymentListFilters, PaymentListResponse, PaymentMetadataAuditResponse, PaymentMethodData,
+    PaymentMethodDataResponse, PaymentOp, PaymentReceiptQuery, PaymentRetrieveBody,
+    PaymentRetrieveBodyWithCredentials, PaymentVelocityLimits, PaymentsCancelRequest,
+    PaymentsCaptureRequest, PaymentsIncrementalAuthorizationRequest,
+    PaymentsIncrementalAuthorizationResponse, PaymentsMetadataUpdateRequest,
+    PaymentsMetadataUpdateResponse, PaymentsRedirectRequest, PaymentsRedirectionResponse,
+    PaymentsRequest, PaymentsResponse, PaymentsResponseForm, PaymentsRetrieveRequest,
+    PaymentsRetryRequest, PaymentsSessionRefreshRequest, PaymentsSessionRequest,
+    PaymentsSessionResponse, PaymentsStartRequest, PgRedirectResponse, PhoneDetails,
+    RedirectionResponse, SessionToken, TimeRange, UrlDetails, VerifyRequest, VerifyResponse,
+    WalletData,
 };
 use error_stack::{IntoReport, ResultExt};
 use masking::PeekInterface;
@@ -99,6 +105,12 @@ pub struct Verify;
 #[derive(Debug, Clone)]
 pub struct PreProcessing;
 
+#[derive(Debug, Clone)]
+pub struct Reauthorize;
+
+#[derive(Debug, Clone)]
+pub struct IncrementalAuthorization;
+
 pub trait PaymentIdTypeExt {
     fn get_payment_intent_id(&self) -> errors::CustomResult<String, errors::ValidationError>;
 }
@@ -208,6 +220,20 @@ pub trait PaymentsPreProcessing:
 {
 }
 
+pub trait PaymentReauthorize:
+    api::ConnectorIntegration<Reauthorize, types::PaymentsReauthorizeData, types::PaymentsResponseData>
+{
+}
+
+pub trait PaymentIncrementalAuthorization:
+    api::ConnectorIntegration<
+    IncrementalAuthorization,
+    types::PaymentsIncrementalAuthorizationData,
+    types::PaymentsResponseData,
+>
+{
+}
+
 pub trait Payment:
     api_types::ConnectorCommon
     + PaymentAuthorize
@@ -219,6 +245,8 @@ pub trait Payment:
     + PaymentSession
     + PaymentToken
     + PaymentsPreProcessing
+    + PaymentReauthorize
+    + PaymentIncrementalAuthorization
     + ConnectorCustomer
 {
 }
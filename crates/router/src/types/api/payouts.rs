@@ -1,5 +1,6 @@
 pub use api_models::payouts::{
     AchBankTransfer, BacsBankTransfer, Bank as BankPayout, Card as CardPayout, PayoutActionRequest,
+    PayoutBatchCreateRequest, PayoutBatchItem, PayoutBatchResponse, PayoutBatchStatusCount,
     PayoutCreateRequest, PayoutCreateResponse, PayoutMethodData, PayoutRequest, PayoutRetrieveBody,
     PayoutRetrieveRequest, SepaBankTransfer,
 };
@@ -33,6 +34,14 @@ pub struct PoQuote;
 #[derive(Debug, Clone)]
 pub struct PoRecipient;
 
+#[cfg(feature = "payouts")]
+#[derive(Debug, Clone)]
+pub struct PoSync;
+
+#[cfg(feature = "payouts")]
+#[derive(Debug, Clone)]
+pub struct PoUploadFile;
+
 #[cfg(feature = "payouts")]
 pub trait PayoutCancel:
     api::ConnectorIntegration<PoCancel, types::PayoutsData, types::PayoutsResponseData>
@@ -69,6 +78,18 @@ pub trait PayoutRecipient:
 {
 }
 
+#[cfg(feature = "payouts")]
+pub trait PayoutSync:
+    api::ConnectorIntegration<PoSync, types::PayoutsData, types::PayoutsResponseData>
+{
+}
+
+#[cfg(feature = "payouts")]
+pub trait PayoutUploadFile:
+    api::ConnectorIntegration<PoUploadFile, types::UploadFileRequestData, types::UploadFileResponse>
+{
+}
+
 #[cfg(feature = "payouts")]
 pub trait Payouts:
     ConnectorCommon
@@ -78,6 +99,8 @@ pub trait Payouts:
     + PayoutFulfill
     + PayoutQuote
     + PayoutRecipient
+    + PayoutSync
+    + PayoutUploadFile
 {
 }
 #[cfg(not(feature = "payouts"))]
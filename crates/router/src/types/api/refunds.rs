@@ -1,6 +1,6 @@
 pub use api_models::refunds::{
-    RefundRequest, RefundResponse, RefundStatus, RefundType, RefundUpdateRequest,
-    RefundsRetrieveRequest,
+    RefundRequest, RefundResolution, RefundResolveRequest, RefundResponse, RefundStatus,
+    RefundType, RefundUpdateRequest, RefundsRetrieveRequest,
 };
 
 use super::ConnectorCommon;
@@ -17,6 +17,10 @@ impl ForeignFrom<storage_enums::RefundStatus> for RefundStatus {
             storage_enums::RefundStatus::ManualReview => Self::Review,
             storage_enums::RefundStatus::Pending => Self::Pending,
             storage_enums::RefundStatus::Success => Self::Succeeded,
+            storage_enums::RefundStatus::Cancelled => Self::Cancelled,
+            storage_enums::RefundStatus::AmountMismatch => Self::AmountMismatch,
+            storage_enums::RefundStatus::PendingApproval => Self::PendingApproval,
+            storage_enums::RefundStatus::Rejected => Self::Rejected,
         }
     }
 }
@@ -0,0 +1,5 @@
+pub use api_models::reports::{
+    GeneratedReportResponse, ReportFilters, ReportFormat, ReportScheduleCreateRequest,
+    ReportScheduleDeleteResponse, ReportScheduleListConstraints, ReportScheduleResponse,
+    ReportScheduleUpdateRequest, ReportTemplate,
+};
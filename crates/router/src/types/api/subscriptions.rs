@@ -0,0 +1,4 @@
+pub use api_models::subscriptions::{
+    SubscriptionCreateRequest, SubscriptionDunningAction, SubscriptionDunningConfig,
+    SubscriptionListConstraints, SubscriptionResponse, SubscriptionUpdateRequest,
+};
@@ -0,0 +1,4 @@
+pub use api_models::terminals::{
+    TerminalCreateRequest, TerminalDeleteResponse, TerminalListConstraints, TerminalResponse,
+    TerminalUpdateRequest,
+};
@@ -1,10 +1,8 @@
-use api_models::admin::MerchantConnectorWebhookDetails;
 pub use api_models::webhooks::{
     IncomingWebhookDetails, IncomingWebhookEvent, MerchantWebhookConfig, ObjectReferenceId,
-    OutgoingWebhook, OutgoingWebhookContent, WebhookFlow,
+    OutgoingWebhook, OutgoingWebhookContent, PayoutIdType, WebhookFlow,
 };
-use common_utils::ext_traits::ValueExt;
-use error_stack::ResultExt;
+use error_stack::{IntoReport, ResultExt};
 use masking::ExposeInterface;
 
 use super::ConnectorCommon;
@@ -75,13 +73,16 @@ pub trait IncomingWebhook: ConnectorCommon + Sync {
         Ok(Box::new(crypto::NoAlgorithm))
     }
 
-    async fn get_webhook_source_verification_merchant_secret(
+    /// Returns the secrets with which the webhooks of the connector are verified, the webhook
+    /// secret of the merchant connector account followed by its additional secret, accepted while
+    /// the secret is being rotated
+    async fn get_webhook_source_verification_merchant_secrets(
         &self,
         db: &dyn StorageInterface,
         merchant_id: &str,
         connector_name: &str,
         key_store: &domain::MerchantKeyStore,
-    ) -> CustomResult<Vec<u8>, errors::ConnectorError> {
+    ) -> CustomResult<Vec<Vec<u8>>, errors::ConnectorError> {
         let debug_suffix = format!(
             "For merchant_id: {}, and connector_name: {}",
             merchant_id, connector_name
@@ -95,22 +96,23 @@ pub trait IncomingWebhook: ConnectorCommon + Sync {
             )
             .await;
 
-        let merchant_secret = match merchant_connector_account_result {
-            Ok(mca) => match mca.connector_webhook_details {
-                Some(merchant_connector_webhook_details) => merchant_connector_webhook_details
-                    .parse_value::<MerchantConnectorWebhookDetails>(
-                        "MerchantConnectorWebhookDetails",
+        let merchant_secrets = match merchant_connector_account_result {
+            Ok(mca) => match mca
+                .get_webhook_details()
+                .change_context_lazy(|| errors::ConnectorError::WebhookSourceVerificationFailed)
+                .attach_printable_lazy(|| {
+                    format!(
+                        "Deserializing MerchantConnectorWebhookDetails failed {}",
+                        debug_suffix
                     )
-                    .change_context_lazy(|| errors::ConnectorError::WebhookSourceVerificationFailed)
-                    .attach_printable_lazy(|| {
-                        format!(
-                            "Deserializing MerchantConnectorWebhookDetails failed {}",
-                            debug_suffix
-                        )
-                    })?
-                    .merchant_secret
-                    .expose(),
-                None => default_secret,
+                })? {
+                Some(merchant_connector_webhook_details) => {
+                    std::iter::once(merchant_connector_webhook_details.merchant_secret)
+                        .chain(merchant_connector_webhook_details.additional_secret)
+                        .map(ExposeInterface::expose)
+                        .collect::<Vec<_>>()
+                }
+                None => vec![default_secret],
             },
             Err(err) => {
                 logger::error!(
@@ -118,7 +120,7 @@ pub trait IncomingWebhook: ConnectorCommon + Sync {
                     debug_suffix
                 );
                 logger::error!("DB error = {:?}", err);
-                default_secret
+                vec![default_secret]
             }
         };
 
@@ -126,7 +128,30 @@ pub trait IncomingWebhook: ConnectorCommon + Sync {
 
         //If merchant has not set the secret for webhook source verification, "default_secret" is returned.
         //So it will fail during verification step and goes to psync flow.
-        Ok(merchant_secret.into_bytes())
+        Ok(merchant_secrets
+            .into_iter()
+            .map(String::into_bytes)
+            .collect())
+    }
+
+    async fn get_webhook_source_verification_merchant_secret(
+        &self,
+        db: &dyn StorageInterface,
+        merchant_id: &str,
+        connector_name: &str,
+        key_store: &domain::MerchantKeyStore,
+    ) -> CustomResult<Vec<u8>, errors::ConnectorError> {
+        self.get_webhook_source_verification_merchant_secrets(
+            db,
+            merchant_id,
+            connector_name,
+            key_store,
+        )
+        .await?
+        .into_iter()
+        .next()
+        .ok_or(errors::ConnectorError::WebhookSourceVerificationFailed)
+        .into_report()
     }
 
     fn get_webhook_source_verification_signature(
@@ -145,13 +170,13 @@ pub trait IncomingWebhook: ConnectorCommon + Sync {
         Ok(Vec::new())
     }
 
-    async fn verify_webhook_source(
+    /// Verifies the signature of the webhook with one of the webhook secrets of the merchant
+    /// connector account
+    fn verify_webhook_source_with_secret(
         &self,
-        db: &dyn StorageInterface,
         request: &IncomingWebhookRequestDetails<'_>,
         merchant_id: &str,
-        connector_label: &str,
-        key_store: &domain::MerchantKeyStore,
+        secret: &[u8],
     ) -> CustomResult<bool, errors::ConnectorError> {
         let algorithm = self
             .get_webhook_source_verification_algorithm(request)
@@ -160,8 +185,26 @@ pub trait IncomingWebhook: ConnectorCommon + Sync {
         let signature = self
             .get_webhook_source_verification_signature(request)
             .change_context(errors::ConnectorError::WebhookSourceVerificationFailed)?;
-        let secret = self
-            .get_webhook_source_verification_merchant_secret(
+        let message = self
+            .get_webhook_source_verification_message(request, merchant_id, secret)
+            .change_context(errors::ConnectorError::WebhookSourceVerificationFailed)?;
+        algorithm
+            .verify_signature(secret, &signature, &message)
+            .change_context(errors::ConnectorError::WebhookSourceVerificationFailed)
+    }
+
+    /// Verifies the webhook with each of the webhook secrets of the merchant connector account,
+    /// so that webhooks signed with the secret being replaced are accepted during its rotation
+    async fn verify_webhook_source(
+        &self,
+        db: &dyn StorageInterface,
+        request: &IncomingWebhookRequestDetails<'_>,
+        merchant_id: &str,
+        connector_label: &str,
+        key_store: &domain::MerchantKeyStore,
+    ) -> CustomResult<bool, errors::ConnectorError> {
+        let secrets = self
+            .get_webhook_source_verification_merchant_secrets(
                 db,
                 merchant_id,
                 connector_label,
@@ -169,12 +212,19 @@ pub trait IncomingWebhook: ConnectorCommon + Sync {
             )
             .await
             .change_context(errors::ConnectorError::WebhookSourceVerificationFailed)?;
-        let message = self
-            .get_webhook_source_verification_message(request, merchant_id, &secret)
-            .change_context(errors::ConnectorError::WebhookSourceVerificationFailed)?;
-        algorithm
-            .verify_signature(&secret, &signature, &message)
-            .change_context(errors::ConnectorError::WebhookSourceVerificationFailed)
+
+        for (index, secret) in secrets.iter().enumerate() {
+            if self.verify_webhook_source_with_secret(request, merchant_id, secret)? {
+                if index > 0 {
+                    logger::info!(
+                        connector = connector_label,
+                        "Webhook source verified with the additional webhook secret"
+                    );
+                }
+                return Ok(true);
+            }
+        }
+        Ok(false)
     }
 
     fn get_webhook_object_reference_id(
@@ -200,6 +250,15 @@ pub trait IncomingWebhook: ConnectorCommon + Sync {
         Ok(services::api::ApplicationResponse::StatusOk)
     }
 
+    /// Returns the amount reported as refunded by the connector in a refund webhook, if the
+    /// connector reports it
+    fn get_webhook_refund_settled_amount(
+        &self,
+        _request: &IncomingWebhookRequestDetails<'_>,
+    ) -> CustomResult<Option<i64>, errors::ConnectorError> {
+        Ok(None)
+    }
+
     fn get_dispute_details(
         &self,
         _request: &IncomingWebhookRequestDetails<'_>,
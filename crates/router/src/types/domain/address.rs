@@ -38,6 +38,8 @@ pub struct Address {
     pub modified_at: PrimitiveDateTime,
     pub customer_id: String,
     pub merchant_id: String,
+    #[serde(skip_serializing)]
+    pub is_address_book_entry: bool,
 }
 
 #[async_trait]
@@ -66,6 +68,7 @@ impl behaviour::Conversion for Address {
             modified_at: self.modified_at,
             customer_id: self.customer_id,
             merchant_id: self.merchant_id,
+            is_address_book_entry: self.is_address_book_entry,
         })
     }
 
@@ -93,6 +96,7 @@ impl behaviour::Conversion for Address {
                 modified_at: other.modified_at,
                 customer_id: other.customer_id,
                 merchant_id: other.merchant_id,
+                is_address_book_entry: other.is_address_book_entry,
             })
         }
         .await
@@ -123,6 +127,7 @@ impl behaviour::Conversion for Address {
             country_code: self.country_code,
             customer_id: self.customer_id,
             merchant_id: self.merchant_id,
+            is_address_book_entry: self.is_address_book_entry,
             created_at: now,
             modified_at: now,
         })
@@ -1,4 +1,9 @@
-use common_utils::{crypto, date_time, pii};
+use common_utils::{
+    crypto::{self, HmacSha256, SignMessage},
+    date_time,
+    errors::CryptoError,
+    pii,
+};
 use diesel_models::{customers::CustomerUpdateInternal, encryption::Encryption};
 use error_stack::ResultExt;
 use masking::{PeekInterface, Secret};
@@ -21,6 +26,28 @@ pub struct Customer {
     pub metadata: Option<pii::SecretSerdeValue>,
     pub modified_at: PrimitiveDateTime,
     pub connector_customer: Option<serde_json::Value>,
+    pub address_id: Option<String>,
+    pub default_shipping_address_id: Option<String>,
+    pub default_billing_address_id: Option<String>,
+    pub default_payment_method_id: Option<String>,
+}
+
+/// The context from which the key of the hashes of the emails of the customers of a merchant is
+/// derived, so that the key is not used for anything else.
+const EMAIL_HASH_KEY_CONTEXT: &[u8] = b"customer_email_hash";
+
+/// Returns the hash of the email of a customer, which is stored along with the encrypted email so
+/// that customers and their payments can be looked up by email. The hash is keyed with a key
+/// derived from the key of the merchant, so that the emails cannot be recovered from the hashes by
+/// enumerating them.
+pub fn get_email_hash(key: &[u8], email: &str) -> CustomResult<String, CryptoError> {
+    let normalized_email = email.trim().to_lowercase();
+    HmacSha256
+        .sign_message(key, EMAIL_HASH_KEY_CONTEXT)
+        .and_then(|email_hash_key| {
+            HmacSha256.sign_message(&email_hash_key, normalized_email.as_bytes())
+        })
+        .map(hex::encode)
 }
 
 #[async_trait::async_trait]
@@ -43,6 +70,13 @@ impl super::behaviour::Conversion for Customer {
             metadata: self.metadata,
             modified_at: self.modified_at,
             connector_customer: self.connector_customer,
+            address_id: self.address_id,
+            default_shipping_address_id: self.default_shipping_address_id,
+            default_billing_address_id: self.default_billing_address_id,
+            // The hash of the email is keyed with the key of the merchant, so it is computed by the
+            // storage layer
+            email_hash: None,
+            default_payment_method_id: self.default_payment_method_id,
         })
     }
 
@@ -69,6 +103,10 @@ impl super::behaviour::Conversion for Customer {
                 metadata: item.metadata,
                 modified_at: item.modified_at,
                 connector_customer: item.connector_customer,
+                address_id: item.address_id,
+                default_shipping_address_id: item.default_shipping_address_id,
+                default_billing_address_id: item.default_billing_address_id,
+                default_payment_method_id: item.default_payment_method_id,
             })
         }
         .await
@@ -91,6 +129,11 @@ impl super::behaviour::Conversion for Customer {
             created_at: now,
             modified_at: now,
             connector_customer: self.connector_customer,
+            address_id: self.address_id,
+            default_shipping_address_id: self.default_shipping_address_id,
+            default_billing_address_id: self.default_billing_address_id,
+            email_hash: None,
+            default_payment_method_id: self.default_payment_method_id,
         })
     }
 }
@@ -105,10 +148,33 @@ pub enum CustomerUpdate {
         phone_country_code: Option<String>,
         metadata: Option<pii::SecretSerdeValue>,
         connector_customer: Option<serde_json::Value>,
+        address_id: Option<String>,
     },
     ConnectorCustomer {
         connector_customer: Option<serde_json::Value>,
     },
+    DefaultAddressUpdate {
+        default_shipping_address_id: Option<Option<String>>,
+        default_billing_address_id: Option<Option<String>>,
+    },
+    DefaultPaymentMethodUpdate {
+        default_payment_method_id: Option<String>,
+    },
+    EmailHashUpdate {
+        email_hash: String,
+    },
+}
+
+impl CustomerUpdate {
+    /// Returns the hash of the email the customer is updated with, if any.
+    pub fn get_email_hash(&self, key: &[u8]) -> CustomResult<Option<String>, CryptoError> {
+        match self {
+            Self::Update {
+                email: Some(email), ..
+            } => get_email_hash(key, email.get_inner().peek()).map(Some),
+            _ => Ok(None),
+        }
+    }
 }
 
 impl From<CustomerUpdate> for CustomerUpdateInternal {
@@ -122,6 +188,7 @@ impl From<CustomerUpdate> for CustomerUpdateInternal {
                 phone_country_code,
                 metadata,
                 connector_customer,
+                address_id,
             } => Self {
                 name: name.map(Encryption::from),
                 email: email.map(Encryption::from),
@@ -130,13 +197,54 @@ impl From<CustomerUpdate> for CustomerUpdateInternal {
                 phone_country_code,
                 metadata,
                 connector_customer,
+                address_id,
                 modified_at: Some(date_time::now()),
+                ..Default::default()
             },
             CustomerUpdate::ConnectorCustomer { connector_customer } => Self {
                 connector_customer,
                 modified_at: Some(common_utils::date_time::now()),
                 ..Default::default()
             },
+            CustomerUpdate::DefaultAddressUpdate {
+                default_shipping_address_id,
+                default_billing_address_id,
+            } => Self {
+                default_shipping_address_id,
+                default_billing_address_id,
+                modified_at: Some(date_time::now()),
+                ..Default::default()
+            },
+            CustomerUpdate::DefaultPaymentMethodUpdate {
+                default_payment_method_id,
+            } => Self {
+                default_payment_method_id: Some(default_payment_method_id),
+                modified_at: Some(date_time::now()),
+                ..Default::default()
+            },
+            CustomerUpdate::EmailHashUpdate { email_hash } => Self {
+                email_hash: Some(email_hash),
+                ..Default::default()
+            },
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+
+    #[test]
+    fn test_email_hash_is_keyed_and_normalized() {
+        let email_hash = get_email_hash(b"merchant_key", "john.doe@example.com").unwrap();
+        assert_eq!(
+            get_email_hash(b"merchant_key", " John.Doe@Example.com ").unwrap(),
+            email_hash
+        );
+        assert_ne!(
+            get_email_hash(b"other_merchant_key", "john.doe@example.com").unwrap(),
+            email_hash
+        );
+    }
+}
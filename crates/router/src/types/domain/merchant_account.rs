@@ -1,5 +1,5 @@
 use common_utils::{
-    crypto::{OptionalEncryptableName, OptionalEncryptableValue},
+    crypto::{Encryptable, OptionalEncryptableName, OptionalEncryptableValue},
     date_time, pii,
 };
 use diesel_models::{
@@ -39,6 +39,12 @@ pub struct MerchantAccount {
     pub payout_routing_algorithm: Option<serde_json::Value>,
     pub organization_id: Option<String>,
     pub is_recon_enabled: bool,
+    pub webhook_mtls_details: OptionalEncryptableValue,
+    pub allowed_domains: Option<Vec<String>>,
+    pub connector_exclusion_rules: Option<serde_json::Value>,
+    pub storage_region: Option<String>,
+    pub webhook_signing_keys: OptionalEncryptableValue,
+    pub business_webhook_secrets: OptionalEncryptableValue,
 }
 
 #[allow(clippy::large_enum_variant)]
@@ -62,6 +68,8 @@ pub enum MerchantAccountUpdate {
         intent_fulfillment_time: Option<i64>,
         frm_routing_algorithm: Option<serde_json::Value>,
         payout_routing_algorithm: Option<serde_json::Value>,
+        webhook_mtls_details: OptionalEncryptableValue,
+        business_webhook_secrets: OptionalEncryptableValue,
     },
     StorageSchemeUpdate {
         storage_scheme: enums::MerchantStorageScheme,
@@ -69,6 +77,15 @@ pub enum MerchantAccountUpdate {
     ReconUpdate {
         is_recon_enabled: bool,
     },
+    AllowedDomainsUpdate {
+        allowed_domains: Vec<String>,
+    },
+    ConnectorExclusionRulesUpdate {
+        connector_exclusion_rules: serde_json::Value,
+    },
+    WebhookSigningKeysUpdate {
+        webhook_signing_keys: Encryptable<Secret<serde_json::Value>>,
+    },
 }
 
 impl From<MerchantAccountUpdate> for MerchantAccountUpdateInternal {
@@ -92,6 +109,8 @@ impl From<MerchantAccountUpdate> for MerchantAccountUpdateInternal {
                 intent_fulfillment_time,
                 frm_routing_algorithm,
                 payout_routing_algorithm,
+                webhook_mtls_details,
+                business_webhook_secrets,
             } => Self {
                 merchant_name: merchant_name.map(Encryption::from),
                 merchant_details: merchant_details.map(Encryption::from),
@@ -111,6 +130,8 @@ impl From<MerchantAccountUpdate> for MerchantAccountUpdateInternal {
                 modified_at: Some(date_time::now()),
                 intent_fulfillment_time,
                 payout_routing_algorithm,
+                webhook_mtls_details: webhook_mtls_details.map(Encryption::from),
+                business_webhook_secrets: business_webhook_secrets.map(Encryption::from),
                 ..Default::default()
             },
             MerchantAccountUpdate::StorageSchemeUpdate { storage_scheme } => Self {
@@ -122,6 +143,25 @@ impl From<MerchantAccountUpdate> for MerchantAccountUpdateInternal {
                 is_recon_enabled,
                 ..Default::default()
             },
+            MerchantAccountUpdate::AllowedDomainsUpdate { allowed_domains } => Self {
+                allowed_domains: Some(allowed_domains),
+                modified_at: Some(date_time::now()),
+                ..Default::default()
+            },
+            MerchantAccountUpdate::ConnectorExclusionRulesUpdate {
+                connector_exclusion_rules,
+            } => Self {
+                connector_exclusion_rules: Some(connector_exclusion_rules),
+                modified_at: Some(date_time::now()),
+                ..Default::default()
+            },
+            MerchantAccountUpdate::WebhookSigningKeysUpdate {
+                webhook_signing_keys,
+            } => Self {
+                webhook_signing_keys: Some(webhook_signing_keys.into()),
+                modified_at: Some(date_time::now()),
+                ..Default::default()
+            },
         }
     }
 }
@@ -158,6 +198,12 @@ impl super::behaviour::Conversion for MerchantAccount {
             payout_routing_algorithm: self.payout_routing_algorithm,
             organization_id: self.organization_id,
             is_recon_enabled: self.is_recon_enabled,
+            webhook_mtls_details: self.webhook_mtls_details.map(Encryption::from),
+            allowed_domains: self.allowed_domains,
+            connector_exclusion_rules: self.connector_exclusion_rules,
+            storage_region: self.storage_region,
+            webhook_signing_keys: self.webhook_signing_keys.map(Encryption::from),
+            business_webhook_secrets: self.business_webhook_secrets.map(Encryption::from),
         })
     }
 
@@ -200,6 +246,21 @@ impl super::behaviour::Conversion for MerchantAccount {
                 payout_routing_algorithm: item.payout_routing_algorithm,
                 organization_id: item.organization_id,
                 is_recon_enabled: item.is_recon_enabled,
+                webhook_mtls_details: item
+                    .webhook_mtls_details
+                    .async_lift(|inner| types::decrypt(inner, key.peek()))
+                    .await?,
+                allowed_domains: item.allowed_domains,
+                connector_exclusion_rules: item.connector_exclusion_rules,
+                storage_region: item.storage_region,
+                webhook_signing_keys: item
+                    .webhook_signing_keys
+                    .async_lift(|inner| types::decrypt(inner, key.peek()))
+                    .await?,
+                business_webhook_secrets: item
+                    .business_webhook_secrets
+                    .async_lift(|inner| types::decrypt(inner, key.peek()))
+                    .await?,
             })
         }
         .await
@@ -233,6 +294,12 @@ impl super::behaviour::Conversion for MerchantAccount {
             payout_routing_algorithm: self.payout_routing_algorithm,
             organization_id: self.organization_id,
             is_recon_enabled: self.is_recon_enabled,
+            webhook_mtls_details: self.webhook_mtls_details.map(Encryption::from),
+            allowed_domains: self.allowed_domains,
+            connector_exclusion_rules: self.connector_exclusion_rules,
+            storage_region: self.storage_region,
+            webhook_signing_keys: self.webhook_signing_keys.map(Encryption::from),
+            business_webhook_secrets: self.business_webhook_secrets.map(Encryption::from),
         })
     }
 }
@@ -1,7 +1,8 @@
 use common_utils::{
     crypto::{Encryptable, GcmAes256},
     date_time,
-    errors::{CustomResult, ValidationError},
+    errors::{CustomResult, ParsingError, ValidationError},
+    ext_traits::ValueExt,
     pii,
 };
 use diesel_models::{
@@ -11,7 +12,10 @@ use diesel_models::{
 use error_stack::ResultExt;
 use masking::{PeekInterface, Secret};
 
-use super::{behaviour, types::TypeEncryption};
+use super::{
+    behaviour,
+    types::{self, TypeEncryption},
+};
 #[derive(Clone, Debug)]
 pub struct MerchantConnectorAccount {
     pub id: Option<i32>,
@@ -32,6 +36,54 @@ pub struct MerchantConnectorAccount {
     pub created_at: time::PrimitiveDateTime,
     pub modified_at: time::PrimitiveDateTime,
     pub connector_webhook_details: Option<pii::SecretSerdeValue>,
+    pub test_connector_account_details: Option<Encryptable<Secret<serde_json::Value>>>,
+    pub client_certificate: Option<Encryptable<Secret<String>>>,
+    pub client_certificate_key: Option<Encryptable<Secret<String>>>,
+    pub client_certificate_expires_at: Option<time::PrimitiveDateTime>,
+    pub webhook_secret: Option<Encryptable<Secret<String>>>,
+    pub additional_webhook_secret: Option<Encryptable<Secret<String>>>,
+    /// The private key of the Apple Pay payment processing certificate configured in the metadata,
+    /// stored encrypted apart from the metadata
+    pub apple_pay_payment_processing_key: Option<Encryptable<Secret<String>>>,
+}
+
+impl MerchantConnectorAccount {
+    /// The webhook secrets of the connector account, falling back to the secret of the connector
+    /// accounts created before the webhook secrets were stored encrypted
+    pub fn get_webhook_details(
+        &self,
+    ) -> CustomResult<Option<api_models::admin::MerchantConnectorWebhookDetails>, ParsingError>
+    {
+        match &self.webhook_secret {
+            Some(webhook_secret) => Ok(Some(api_models::admin::MerchantConnectorWebhookDetails {
+                merchant_secret: webhook_secret.clone().into_inner(),
+                additional_secret: self
+                    .additional_webhook_secret
+                    .clone()
+                    .map(Encryptable::into_inner),
+            })),
+            None => self
+                .connector_webhook_details
+                .clone()
+                .map(|webhook_details| {
+                    webhook_details.parse_value("MerchantConnectorWebhookDetails")
+                })
+                .transpose(),
+        }
+    }
+
+    /// The client certificate to be presented to the connector, if one is configured
+    pub fn get_client_certificate(&self) -> Option<crate::types::ConnectorClientCertificate> {
+        self.client_certificate
+            .clone()
+            .zip(self.client_certificate_key.clone())
+            .map(
+                |(certificate, certificate_key)| crate::types::ConnectorClientCertificate {
+                    certificate: certificate.into_inner(),
+                    certificate_key: certificate_key.into_inner(),
+                },
+            )
+    }
 }
 
 #[derive(Debug)]
@@ -47,7 +99,20 @@ pub enum MerchantConnectorAccountUpdate {
         payment_methods_enabled: Option<Vec<serde_json::Value>>,
         metadata: Option<pii::SecretSerdeValue>,
         frm_configs: Option<Vec<Secret<serde_json::Value>>>,
-        connector_webhook_details: Option<pii::SecretSerdeValue>,
+        connector_webhook_details: Option<Option<pii::SecretSerdeValue>>,
+        test_connector_account_details: Option<Encryptable<Secret<serde_json::Value>>>,
+        client_certificate: Option<Encryptable<Secret<String>>>,
+        client_certificate_key: Option<Encryptable<Secret<String>>>,
+        client_certificate_expires_at: Option<time::PrimitiveDateTime>,
+        webhook_secret: Option<Encryptable<Secret<String>>>,
+        additional_webhook_secret: Option<Option<Encryptable<Secret<String>>>>,
+        apple_pay_payment_processing_key: Option<Encryptable<Secret<String>>>,
+    },
+    /// Replaces the webhook secrets, clearing the plaintext webhook details which the accounts
+    /// configured before the secrets were encrypted were stored with
+    WebhookSecretsUpdate {
+        webhook_secret: Encryptable<Secret<String>>,
+        additional_webhook_secret: Option<Encryptable<Secret<String>>>,
     },
 }
 
@@ -79,6 +144,17 @@ impl behaviour::Conversion for MerchantConnectorAccount {
                 created_at: self.created_at,
                 modified_at: self.modified_at,
                 connector_webhook_details: self.connector_webhook_details,
+                test_connector_account_details: self
+                    .test_connector_account_details
+                    .map(Encryption::from),
+                client_certificate: self.client_certificate.map(Encryption::from),
+                client_certificate_key: self.client_certificate_key.map(Encryption::from),
+                client_certificate_expires_at: self.client_certificate_expires_at,
+                webhook_secret: self.webhook_secret.map(Encryption::from),
+                additional_webhook_secret: self.additional_webhook_secret.map(Encryption::from),
+                apple_pay_payment_processing_key: self
+                    .apple_pay_payment_processing_key
+                    .map(Encryption::from),
             },
         )
     }
@@ -115,6 +191,43 @@ impl behaviour::Conversion for MerchantConnectorAccount {
             created_at: other.created_at,
             modified_at: other.modified_at,
             connector_webhook_details: other.connector_webhook_details,
+            test_connector_account_details: types::decrypt(
+                other.test_connector_account_details,
+                key.peek(),
+            )
+            .await
+            .change_context(ValidationError::InvalidValue {
+                message: "Failed while decrypting test connector account details".to_string(),
+            })?,
+            client_certificate: types::decrypt(other.client_certificate, key.peek())
+                .await
+                .change_context(ValidationError::InvalidValue {
+                    message: "Failed while decrypting client certificate".to_string(),
+                })?,
+            client_certificate_key: types::decrypt(other.client_certificate_key, key.peek())
+                .await
+                .change_context(ValidationError::InvalidValue {
+                    message: "Failed while decrypting client certificate key".to_string(),
+                })?,
+            client_certificate_expires_at: other.client_certificate_expires_at,
+            webhook_secret: types::decrypt(other.webhook_secret, key.peek())
+                .await
+                .change_context(ValidationError::InvalidValue {
+                    message: "Failed while decrypting webhook secret".to_string(),
+                })?,
+            additional_webhook_secret: types::decrypt(other.additional_webhook_secret, key.peek())
+                .await
+                .change_context(ValidationError::InvalidValue {
+                    message: "Failed while decrypting additional webhook secret".to_string(),
+                })?,
+            apple_pay_payment_processing_key: types::decrypt(
+                other.apple_pay_payment_processing_key,
+                key.peek(),
+            )
+            .await
+            .change_context(ValidationError::InvalidValue {
+                message: "Failed while decrypting Apple Pay payment processing key".to_string(),
+            })?,
         })
     }
 
@@ -138,6 +251,17 @@ impl behaviour::Conversion for MerchantConnectorAccount {
             created_at: now,
             modified_at: now,
             connector_webhook_details: self.connector_webhook_details,
+            test_connector_account_details: self
+                .test_connector_account_details
+                .map(Encryption::from),
+            client_certificate: self.client_certificate.map(Encryption::from),
+            client_certificate_key: self.client_certificate_key.map(Encryption::from),
+            client_certificate_expires_at: self.client_certificate_expires_at,
+            webhook_secret: self.webhook_secret.map(Encryption::from),
+            additional_webhook_secret: self.additional_webhook_secret.map(Encryption::from),
+            apple_pay_payment_processing_key: self
+                .apple_pay_payment_processing_key
+                .map(Encryption::from),
         })
     }
 }
@@ -157,6 +281,13 @@ impl From<MerchantConnectorAccountUpdate> for MerchantConnectorAccountUpdateInte
                 metadata,
                 frm_configs,
                 connector_webhook_details,
+                test_connector_account_details,
+                client_certificate,
+                client_certificate_key,
+                client_certificate_expires_at,
+                webhook_secret,
+                additional_webhook_secret,
+                apple_pay_payment_processing_key,
             } => Self {
                 merchant_id,
                 connector_type,
@@ -170,6 +301,41 @@ impl From<MerchantConnectorAccountUpdate> for MerchantConnectorAccountUpdateInte
                 frm_configs,
                 modified_at: Some(common_utils::date_time::now()),
                 connector_webhook_details,
+                test_connector_account_details: test_connector_account_details
+                    .map(Encryption::from),
+                client_certificate: client_certificate.map(Encryption::from),
+                client_certificate_key: client_certificate_key.map(Encryption::from),
+                client_certificate_expires_at,
+                webhook_secret: webhook_secret.map(Encryption::from),
+                additional_webhook_secret: additional_webhook_secret.map(
+                    |additional_webhook_secret| additional_webhook_secret.map(Encryption::from),
+                ),
+                apple_pay_payment_processing_key: apple_pay_payment_processing_key
+                    .map(Encryption::from),
+            },
+            MerchantConnectorAccountUpdate::WebhookSecretsUpdate {
+                webhook_secret,
+                additional_webhook_secret,
+            } => Self {
+                merchant_id: None,
+                connector_type: None,
+                connector_name: None,
+                connector_account_details: None,
+                test_mode: None,
+                disabled: None,
+                merchant_connector_id: None,
+                payment_methods_enabled: None,
+                metadata: None,
+                frm_configs: None,
+                modified_at: Some(common_utils::date_time::now()),
+                connector_webhook_details: Some(None),
+                test_connector_account_details: None,
+                client_certificate: None,
+                client_certificate_key: None,
+                client_certificate_expires_at: None,
+                webhook_secret: Some(webhook_secret.into()),
+                additional_webhook_secret: Some(additional_webhook_secret.map(Encryption::from)),
+                apple_pay_payment_processing_key: None,
             },
         }
     }
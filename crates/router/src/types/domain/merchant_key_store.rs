@@ -17,6 +17,9 @@ pub struct MerchantKeyStore {
     pub key: Encryptable<Secret<Vec<u8>>>,
     #[serde(with = "custom_serde::iso8601")]
     pub created_at: PrimitiveDateTime,
+    pub key_version: i32,
+    #[serde(with = "custom_serde::iso8601::option")]
+    pub rotated_at: Option<PrimitiveDateTime>,
 }
 
 #[async_trait::async_trait]
@@ -28,6 +31,8 @@ impl super::behaviour::Conversion for MerchantKeyStore {
             key: self.key.into(),
             merchant_id: self.merchant_id,
             created_at: self.created_at,
+            key_version: self.key_version,
+            rotated_at: self.rotated_at,
         })
     }
 
@@ -46,6 +51,8 @@ impl super::behaviour::Conversion for MerchantKeyStore {
                 })?,
             merchant_id: item.merchant_id,
             created_at: item.created_at,
+            key_version: item.key_version,
+            rotated_at: item.rotated_at,
         })
     }
 
@@ -54,6 +61,7 @@ impl super::behaviour::Conversion for MerchantKeyStore {
             merchant_id: self.merchant_id,
             key: self.key.into(),
             created_at: date_time::now(),
+            key_version: self.key_version,
         })
     }
 }
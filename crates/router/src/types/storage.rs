@@ -1,17 +1,24 @@
 pub mod address;
+pub mod analytics;
 pub mod api_keys;
+pub mod audit_event;
 pub mod capture;
 pub mod cards_info;
+pub mod commission;
 pub mod configs;
 pub mod connector_response;
+pub mod customer_import_job;
 pub mod customers;
 pub mod dispute;
 pub mod enums;
 pub mod ephemeral_key;
 pub mod events;
 pub mod file;
+pub mod gift_card;
+pub mod invoice;
 #[cfg(feature = "kv_store")]
 pub mod kv;
+pub mod ledger_entry;
 pub mod locker_mock_up;
 pub mod mandate;
 pub mod merchant_account;
@@ -19,18 +26,32 @@ pub mod merchant_connector_account;
 pub mod merchant_key_store;
 pub mod payment_attempt;
 pub mod payment_intent;
+pub mod payment_link;
+pub mod payment_metadata_audit;
 pub mod payment_method;
+pub mod payment_method_share;
 pub mod payout_attempt;
+pub mod payout_batch;
 pub mod payouts;
 pub mod process_tracker;
 mod query;
+pub mod queued_webhook;
 pub mod refund;
+pub mod report_schedule;
 pub mod reverse_lookup;
+pub mod subscription;
+pub mod terminal;
+pub mod webhook_delivery_attempt;
+pub mod webhook_endpoint_health;
 
 pub use self::{
-    address::*, api_keys::*, capture::*, cards_info::*, configs::*, connector_response::*,
-    customers::*, dispute::*, ephemeral_key::*, events::*, file::*, locker_mock_up::*, mandate::*,
-    merchant_account::*, merchant_connector_account::*, merchant_key_store::*, payment_attempt::*,
-    payment_intent::*, payment_method::*, payout_attempt::*, payouts::*, process_tracker::*,
-    refund::*, reverse_lookup::*,
+    address::*, analytics::*, api_keys::*, audit_event::*, capture::*, cards_info::*,
+    commission::*, configs::*, connector_response::*, customer_import_job::*, customers::*,
+    dispute::*, ephemeral_key::*, events::*, file::*, gift_card::*, invoice::*, ledger_entry::*,
+    locker_mock_up::*, mandate::*, merchant_account::*, merchant_connector_account::*,
+    merchant_key_store::*, payment_attempt::*, payment_intent::*, payment_link::*,
+    payment_metadata_audit::*, payment_method::*, payment_method_share::*, payout_attempt::*,
+    payout_batch::*, payouts::*, process_tracker::*, queued_webhook::*, refund::*,
+    report_schedule::*, reverse_lookup::*, subscription::*, terminal::*,
+    webhook_delivery_attempt::*, webhook_endpoint_health::*,
 };
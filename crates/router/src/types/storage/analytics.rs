@@ -0,0 +1,53 @@
+use diesel::Queryable;
+use diesel_models::enums::{AttemptStatus, Currency, PaymentMethod, PayoutType};
+use time::PrimitiveDateTime;
+
+/// The total amount and number of records of a currency, aggregated by the database.
+#[derive(Clone, Debug, Queryable)]
+pub struct CurrencyVolume {
+    pub currency: Currency,
+    pub total_amount: i64,
+    pub count: i64,
+}
+
+/// The number of payments created in a period with a payment method, whose active attempt is in
+/// a status, aggregated by the database.
+#[derive(Clone, Debug, Queryable)]
+pub struct FunnelCount {
+    pub period_start: PrimitiveDateTime,
+    pub payment_method: Option<PaymentMethod>,
+    pub status: AttemptStatus,
+    pub count: i64,
+}
+
+/// Sums an amount expression as a `BIGINT`, since `SUM` over a `BIGINT` column results in a
+/// `NUMERIC` in PostgreSQL.
+pub(crate) fn sum_amount(amount: &str) -> String {
+    format!("CAST(COALESCE(SUM({amount}), 0) AS BIGINT)")
+}
+
+/// The aggregates of the payouts created in a period through a connector, in a currency and of a
+/// payout type, aggregated by the database.
+#[derive(Clone, Debug, Queryable)]
+pub struct PayoutVolume {
+    pub connector: String,
+    pub currency: Currency,
+    pub payout_type: PayoutType,
+    pub payout_count: i64,
+    pub total_amount: i64,
+    pub successful_count: i64,
+    pub successful_amount: i64,
+    pub failed_count: i64,
+    /// The average duration (in seconds) between the creation of the payouts and their success
+    pub average_fulfillment_latency: Option<i64>,
+}
+
+/// The number of payouts created in a period which failed with an error through a connector,
+/// aggregated by the database.
+#[derive(Clone, Debug, Queryable)]
+pub struct PayoutFailureCount {
+    pub connector: String,
+    pub error_code: Option<String>,
+    pub error_message: Option<String>,
+    pub count: i64,
+}
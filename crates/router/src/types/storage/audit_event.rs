@@ -0,0 +1,59 @@
+use async_bb8_diesel::AsyncRunQueryDsl;
+use common_utils::errors::CustomResult;
+use diesel::{associations::HasTable, debug_query, pg::Pg, ExpressionMethods, QueryDsl};
+pub use diesel_models::audit_event::{AuditEvent, AuditEventNew};
+use diesel_models::{errors, schema::audit_event::dsl};
+use error_stack::{IntoReport, ResultExt};
+
+use crate::{connection::PgPooledConn, logger};
+
+#[async_trait::async_trait]
+pub trait AuditEventDbExt: Sized {
+    async fn filter_by_constraints(
+        conn: &PgPooledConn,
+        merchant_id: &str,
+        constraints: &api_models::audit::AuditEventListConstraints,
+        limit: i64,
+    ) -> CustomResult<Vec<Self>, errors::DatabaseError>;
+}
+
+#[async_trait::async_trait]
+impl AuditEventDbExt for AuditEvent {
+    async fn filter_by_constraints(
+        conn: &PgPooledConn,
+        merchant_id: &str,
+        constraints: &api_models::audit::AuditEventListConstraints,
+        limit: i64,
+    ) -> CustomResult<Vec<Self>, errors::DatabaseError> {
+        let mut filter = <Self as HasTable>::table()
+            .filter(dsl::merchant_id.eq(merchant_id.to_owned()))
+            .order(dsl::created_at.desc())
+            .limit(limit)
+            .into_boxed();
+
+        if let Some(entity_type) = constraints.entity_type {
+            filter = filter.filter(dsl::entity_type.eq(entity_type));
+        }
+        if let Some(entity_id) = &constraints.entity_id {
+            filter = filter.filter(dsl::entity_id.eq(entity_id.to_owned()));
+        }
+        if let Some(action) = constraints.action {
+            filter = filter.filter(dsl::action.eq(action));
+        }
+        if let Some(start_time) = constraints.start_time {
+            filter = filter.filter(dsl::created_at.ge(start_time));
+        }
+        if let Some(end_time) = constraints.end_time {
+            filter = filter.filter(dsl::created_at.le(end_time));
+        }
+
+        logger::debug!(query = %debug_query::<Pg, _>(&filter).to_string());
+
+        filter
+            .get_results_async(conn)
+            .await
+            .into_report()
+            .change_context(errors::DatabaseError::NotFound)
+            .attach_printable_lazy(|| "Error filtering audit events by predicate")
+    }
+}
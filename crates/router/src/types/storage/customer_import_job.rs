@@ -0,0 +1,4 @@
+pub use diesel_models::customer_import_job::{
+    CustomerImportBatch, CustomerImportJob, CustomerImportJobNew, CustomerImportJobUpdate,
+    CustomerImportJobUpdateInternal, CustomerImportWorkflow,
+};
@@ -0,0 +1,4 @@
+pub use diesel_models::gift_card::{
+    GiftCard, GiftCardNew, GiftCardTransaction, GiftCardTransactionNew, GiftCardUpdate,
+    GiftCardUpdateInternal,
+};
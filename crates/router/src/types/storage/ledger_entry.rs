@@ -0,0 +1,213 @@
+use async_bb8_diesel::AsyncRunQueryDsl;
+use common_utils::errors::CustomResult;
+use diesel::{
+    associations::HasTable, debug_query, dsl::sql, pg::Pg, sql_types::BigInt,
+    BoolExpressionMethods, ExpressionMethods, QueryDsl, Queryable,
+};
+pub use diesel_models::ledger_entry::{LedgerEntry, LedgerEntryNew};
+use diesel_models::{
+    enums::Currency,
+    errors,
+    schema::ledger_entry::{self, dsl},
+};
+use error_stack::{IntoReport, ResultExt};
+
+use super::analytics;
+use crate::{connection::PgPooledConn, logger};
+
+/// The total amount debited or credited to a ledger account in a currency, aggregated by the
+/// database.
+#[derive(Clone, Debug, Queryable)]
+pub struct LedgerAccountTotal {
+    pub account: String,
+    pub currency: Currency,
+    pub total_amount: i64,
+}
+
+/// The total amount of the entries of a type posted in a currency, aggregated by the database.
+#[derive(Clone, Debug, Queryable)]
+pub struct LedgerEntryTypeTotal {
+    pub entry_type: String,
+    pub currency: Currency,
+    pub total_amount: i64,
+}
+
+#[async_trait::async_trait]
+pub trait LedgerEntryDbExt: Sized {
+    async fn filter_by_constraints(
+        conn: &PgPooledConn,
+        merchant_id: &str,
+        constraints: &api_models::accounting::LedgerEntryListConstraints,
+        limit: i64,
+    ) -> CustomResult<Vec<Self>, errors::DatabaseError>;
+
+    async fn filter_by_constraints_after_id(
+        conn: &PgPooledConn,
+        merchant_id: &str,
+        constraints: &api_models::accounting::LedgerEntryListConstraints,
+        after_id: i32,
+        limit: i64,
+    ) -> CustomResult<Vec<Self>, errors::DatabaseError>;
+
+    async fn get_debit_totals_by_account(
+        conn: &PgPooledConn,
+        merchant_id: &str,
+    ) -> CustomResult<Vec<LedgerAccountTotal>, errors::DatabaseError>;
+
+    async fn get_credit_totals_by_account(
+        conn: &PgPooledConn,
+        merchant_id: &str,
+    ) -> CustomResult<Vec<LedgerAccountTotal>, errors::DatabaseError>;
+
+    async fn get_totals_by_entry_type(
+        conn: &PgPooledConn,
+        merchant_id: &str,
+        entry_types: Vec<String>,
+        start_time: time::PrimitiveDateTime,
+        end_time: time::PrimitiveDateTime,
+    ) -> CustomResult<Vec<LedgerEntryTypeTotal>, errors::DatabaseError>;
+}
+
+fn get_constraints_filter(
+    merchant_id: &str,
+    constraints: &api_models::accounting::LedgerEntryListConstraints,
+) -> ledger_entry::BoxedQuery<'static, Pg> {
+    let mut filter = <LedgerEntry as HasTable>::table()
+        .filter(dsl::merchant_id.eq(merchant_id.to_owned()))
+        .into_boxed();
+
+    if let Some(reference_id) = &constraints.reference_id {
+        filter = filter.filter(dsl::reference_id.eq(reference_id.to_owned()));
+    }
+    if let Some(account) = constraints.account {
+        filter = filter.filter(
+            dsl::debit_account
+                .eq(account.to_string())
+                .or(dsl::credit_account.eq(account.to_string())),
+        );
+    }
+    if let Some(currency) = constraints.currency {
+        filter = filter.filter(dsl::currency.eq(currency));
+    }
+    if let Some(connector) = &constraints.connector {
+        filter = filter.filter(dsl::connector.eq(connector.to_owned()));
+    }
+    if let Some(start_time) = constraints.start_time {
+        filter = filter.filter(dsl::created_at.ge(start_time));
+    }
+    if let Some(end_time) = constraints.end_time {
+        filter = filter.filter(dsl::created_at.le(end_time));
+    }
+
+    filter
+}
+
+#[async_trait::async_trait]
+impl LedgerEntryDbExt for LedgerEntry {
+    async fn filter_by_constraints(
+        conn: &PgPooledConn,
+        merchant_id: &str,
+        constraints: &api_models::accounting::LedgerEntryListConstraints,
+        limit: i64,
+    ) -> CustomResult<Vec<Self>, errors::DatabaseError> {
+        let filter = get_constraints_filter(merchant_id, constraints)
+            .order(dsl::created_at.desc())
+            .limit(limit);
+
+        logger::debug!(query = %debug_query::<Pg, _>(&filter).to_string());
+
+        filter
+            .get_results_async(conn)
+            .await
+            .into_report()
+            .change_context(errors::DatabaseError::NotFound)
+            .attach_printable_lazy(|| "Error filtering ledger entries by predicate")
+    }
+
+    /// Filters the entries in the order they were posted, starting after the entry with the
+    /// `after_id` row ID, so that all the entries matching the constraints can be read in pages
+    /// without the entries posted in the meantime shifting the pages.
+    async fn filter_by_constraints_after_id(
+        conn: &PgPooledConn,
+        merchant_id: &str,
+        constraints: &api_models::accounting::LedgerEntryListConstraints,
+        after_id: i32,
+        limit: i64,
+    ) -> CustomResult<Vec<Self>, errors::DatabaseError> {
+        let filter = get_constraints_filter(merchant_id, constraints)
+            .filter(dsl::id.gt(after_id))
+            .order(dsl::id.asc())
+            .limit(limit);
+
+        logger::debug!(query = %debug_query::<Pg, _>(&filter).to_string());
+
+        filter
+            .get_results_async(conn)
+            .await
+            .into_report()
+            .change_context(errors::DatabaseError::NotFound)
+            .attach_printable_lazy(|| "Error filtering ledger entries by predicate")
+    }
+
+    async fn get_debit_totals_by_account(
+        conn: &PgPooledConn,
+        merchant_id: &str,
+    ) -> CustomResult<Vec<LedgerAccountTotal>, errors::DatabaseError> {
+        <Self as HasTable>::table()
+            .filter(dsl::merchant_id.eq(merchant_id.to_owned()))
+            .group_by((dsl::debit_account, dsl::currency))
+            .select((
+                dsl::debit_account,
+                dsl::currency,
+                sql::<BigInt>(&analytics::sum_amount("amount")),
+            ))
+            .get_results_async(conn)
+            .await
+            .into_report()
+            .change_context(errors::DatabaseError::Others)
+            .attach_printable("Error aggregating ledger entries by debit account")
+    }
+
+    async fn get_credit_totals_by_account(
+        conn: &PgPooledConn,
+        merchant_id: &str,
+    ) -> CustomResult<Vec<LedgerAccountTotal>, errors::DatabaseError> {
+        <Self as HasTable>::table()
+            .filter(dsl::merchant_id.eq(merchant_id.to_owned()))
+            .group_by((dsl::credit_account, dsl::currency))
+            .select((
+                dsl::credit_account,
+                dsl::currency,
+                sql::<BigInt>(&analytics::sum_amount("amount")),
+            ))
+            .get_results_async(conn)
+            .await
+            .into_report()
+            .change_context(errors::DatabaseError::Others)
+            .attach_printable("Error aggregating ledger entries by credit account")
+    }
+
+    async fn get_totals_by_entry_type(
+        conn: &PgPooledConn,
+        merchant_id: &str,
+        entry_types: Vec<String>,
+        start_time: time::PrimitiveDateTime,
+        end_time: time::PrimitiveDateTime,
+    ) -> CustomResult<Vec<LedgerEntryTypeTotal>, errors::DatabaseError> {
+        <Self as HasTable>::table()
+            .filter(dsl::merchant_id.eq(merchant_id.to_owned()))
+            .filter(dsl::entry_type.eq_any(entry_types))
+            .filter(dsl::created_at.between(start_time, end_time))
+            .group_by((dsl::entry_type, dsl::currency))
+            .select((
+                dsl::entry_type,
+                dsl::currency,
+                sql::<BigInt>(&analytics::sum_amount("amount")),
+            ))
+            .get_results_async(conn)
+            .await
+            .into_report()
+            .change_context(errors::DatabaseError::Others)
+            .attach_printable("Error aggregating ledger entries by entry type")
+    }
+}
@@ -1,5 +1,13 @@
 use async_bb8_diesel::AsyncRunQueryDsl;
-use diesel::{associations::HasTable, debug_query, pg::Pg, ExpressionMethods, JoinOnDsl, QueryDsl};
+use diesel::{
+    associations::HasTable,
+    debug_query,
+    dsl::{count_star, sql},
+    pg::Pg,
+    sql_types::{BigInt, Timestamp},
+    BoolExpressionMethods, ExpressionMethods, JoinOnDsl, NullableExpressionMethods, QueryDsl,
+};
+use diesel_models::enums::AttemptStatus;
 pub use diesel_models::{
     errors,
     payment_attempt::PaymentAttempt,
@@ -7,6 +15,7 @@ pub use diesel_models::{
         PaymentIntent, PaymentIntentNew, PaymentIntentUpdate, PaymentIntentUpdateInternal,
     },
     schema::{
+        customers,
         payment_attempt::{self, dsl as dsl1},
         payment_intent::dsl,
     },
@@ -14,9 +23,8 @@ pub use diesel_models::{
 use error_stack::{IntoReport, ResultExt};
 use router_env::{instrument, tracing};
 
-use crate::{connection::PgPooledConn, core::errors::CustomResult, types::api};
-
-const JOIN_LIMIT: i64 = 20;
+use super::analytics::{self, CurrencyVolume, FunnelCount};
+use crate::{connection::PgPooledConn, consts, core::errors::CustomResult, types::api};
 
 #[cfg(feature = "kv_store")]
 impl crate::utils::storage_partitioning::KvStorePartition for PaymentIntent {}
@@ -39,7 +47,21 @@ pub trait PaymentIntentDbExt: Sized {
         conn: &PgPooledConn,
         merchant_id: &str,
         constraints: &api::PaymentListFilterConstraints,
+        customer_email_hash: Option<&str>,
     ) -> CustomResult<Vec<(PaymentIntent, PaymentAttempt)>, errors::DatabaseError>;
+
+    async fn get_captured_volume_by_currency(
+        conn: &PgPooledConn,
+        merchant_id: &str,
+        start_time: time::PrimitiveDateTime,
+        end_time: time::PrimitiveDateTime,
+    ) -> CustomResult<Vec<CurrencyVolume>, errors::DatabaseError>;
+
+    async fn get_funnel_counts(
+        conn: &PgPooledConn,
+        merchant_id: &str,
+        constraints: &api_models::analytics::PaymentsFunnelRequest,
+    ) -> CustomResult<Vec<FunnelCount>, errors::DatabaseError>;
 }
 
 #[async_trait::async_trait]
@@ -140,22 +162,36 @@ impl PaymentIntentDbExt for PaymentIntent {
         conn: &PgPooledConn,
         merchant_id: &str,
         constraints: &api::PaymentListFilterConstraints,
+        customer_email_hash: Option<&str>,
     ) -> CustomResult<Vec<(Self, PaymentAttempt)>, errors::DatabaseError> {
-        let offset = constraints.offset.unwrap_or_default();
+        let limit = constraints
+            .limit
+            .unwrap_or(consts::PAYMENTS_LIST_DEFAULT_LIMIT);
+        // Payments are paginated by their creation time and then their id, which are both
+        // covered by an index along with the merchant
         let mut filter = Self::table()
             .inner_join(payment_attempt::table.on(dsl1::attempt_id.eq(dsl::active_attempt_id)))
             .filter(dsl::merchant_id.eq(merchant_id.to_owned()))
-            .order(dsl::created_at.desc())
+            .order((dsl::created_at.desc(), dsl::id.desc()))
+            .limit(i64::from(limit))
             .into_boxed();
 
-        match &constraints.payment_id {
-            Some(payment_id) => {
-                filter = filter.filter(dsl::payment_id.eq(payment_id.to_owned()));
-            }
-            None => {
-                filter = filter.limit(JOIN_LIMIT).offset(offset);
-            }
-        };
+        if let Some(payment_id) = &constraints.payment_id {
+            filter = filter.filter(dsl::payment_id.eq(payment_id.to_owned()));
+        }
+
+        if let Some(starting_after) = &constraints.starting_after {
+            let cursor =
+                Self::find_by_payment_id_merchant_id(conn, starting_after, merchant_id).await?;
+            filter = filter.filter(
+                dsl::created_at.lt(cursor.created_at).or(dsl::created_at
+                    .eq(cursor.created_at)
+                    .and(dsl::id.lt(cursor.id))),
+            );
+        } else if let Some(offset) = constraints.offset {
+            // Kept for the clients which still paginate by offset
+            filter = filter.offset(offset);
+        }
 
         if let Some(time_range) = constraints.time_range {
             filter = filter.filter(dsl::created_at.ge(time_range.start_time));
@@ -180,6 +216,38 @@ impl PaymentIntentDbExt for PaymentIntent {
             filter = filter.filter(dsl1::payment_method.eq_any(payment_method));
         }
 
+        if let Some(payment_method_types) = constraints.payment_method_types.clone() {
+            filter = filter.filter(dsl1::payment_method_type.eq_any(payment_method_types));
+        }
+
+        if let Some(status_groups) = &constraints.status_groups {
+            let statuses: Vec<_> = status_groups
+                .iter()
+                .flat_map(|status_group| status_group.get_statuses())
+                .copied()
+                .collect();
+            filter = filter.filter(dsl::status.eq_any(statuses));
+        }
+
+        if let Some(amount) = constraints.amount {
+            if let Some(start_amount) = amount.start_amount {
+                filter = filter.filter(dsl::amount.ge(start_amount));
+            }
+            if let Some(end_amount) = amount.end_amount {
+                filter = filter.filter(dsl::amount.le(end_amount));
+            }
+        }
+
+        if let Some(customer_email_hash) = customer_email_hash {
+            // The emails of customers are encrypted, so customers are looked up by the hash of
+            // their email instead
+            let customer_ids = customers::table
+                .filter(customers::merchant_id.eq(merchant_id.to_owned()))
+                .filter(customers::email_hash.eq(customer_email_hash.to_owned()))
+                .select(customers::customer_id.nullable());
+            filter = filter.filter(dsl::customer_id.eq_any(customer_ids));
+        }
+
         crate::logger::debug!(filter = %debug_query::<Pg, _>(&filter).to_string());
         filter
             .get_results_async(conn)
@@ -188,4 +256,95 @@ impl PaymentIntentDbExt for PaymentIntent {
             .change_context(errors::DatabaseError::Others)
             .attach_printable("Error filtering payment records")
     }
+
+    #[instrument(skip(conn))]
+    async fn get_captured_volume_by_currency(
+        conn: &PgPooledConn,
+        merchant_id: &str,
+        start_time: time::PrimitiveDateTime,
+        end_time: time::PrimitiveDateTime,
+    ) -> CustomResult<Vec<CurrencyVolume>, errors::DatabaseError> {
+        // The attempt is last modified when its capture is recorded, so that is the time used to
+        // place the payment in a period
+        let query = Self::table()
+            .inner_join(payment_attempt::table.on(dsl1::attempt_id.eq(dsl::active_attempt_id)))
+            .filter(dsl::merchant_id.eq(merchant_id.to_owned()))
+            .filter(dsl::currency.is_not_null())
+            .filter(
+                dsl1::status.eq_any(vec![AttemptStatus::Charged, AttemptStatus::PartialCharged]),
+            )
+            .filter(dsl1::modified_at.ge(start_time))
+            .filter(dsl1::modified_at.le(end_time))
+            .group_by(dsl::currency)
+            .select((
+                dsl::currency.assume_not_null(),
+                sql::<BigInt>(&analytics::sum_amount(
+                    "COALESCE(payment_intent.amount_captured, payment_attempt.amount)",
+                )),
+                count_star(),
+            ));
+
+        crate::logger::debug!(query = %debug_query::<Pg, _>(&query).to_string());
+        query
+            .get_results_async(conn)
+            .await
+            .into_report()
+            .change_context(errors::DatabaseError::Others)
+            .attach_printable("Error aggregating captured payments by currency")
+    }
+
+    #[instrument(skip(conn))]
+    async fn get_funnel_counts(
+        conn: &PgPooledConn,
+        merchant_id: &str,
+        constraints: &api_models::analytics::PaymentsFunnelRequest,
+    ) -> CustomResult<Vec<FunnelCount>, errors::DatabaseError> {
+        // The granularity is one of a fixed set of units, so it is safe to interpolate
+        let period_start = format!(
+            "date_trunc('{}', payment_intent.created_at)",
+            constraints.granularity
+        );
+
+        let mut query = Self::table()
+            .inner_join(payment_attempt::table.on(dsl1::attempt_id.eq(dsl::active_attempt_id)))
+            .filter(dsl::merchant_id.eq(merchant_id.to_owned()))
+            .filter(dsl1::merchant_id.eq(merchant_id.to_owned()))
+            .filter(dsl::created_at.ge(constraints.start_time))
+            .into_boxed();
+
+        if let Some(end_time) = constraints.end_time {
+            query = query.filter(dsl::created_at.le(end_time));
+        }
+        if let Some(payment_method) = constraints.payment_method {
+            query = query.filter(dsl1::payment_method.eq(payment_method));
+        }
+        if let Some(business_country) = constraints.business_country {
+            query = query.filter(dsl::business_country.eq(business_country));
+        }
+        if let Some(business_label) = &constraints.business_label {
+            query = query.filter(dsl::business_label.eq(business_label.to_owned()));
+        }
+
+        let query = query
+            .group_by((
+                sql::<Timestamp>(&period_start),
+                dsl1::payment_method,
+                dsl1::status,
+            ))
+            .select((
+                sql::<Timestamp>(&period_start),
+                dsl1::payment_method,
+                dsl1::status,
+                count_star(),
+            ))
+            .order(sql::<Timestamp>(&period_start));
+
+        crate::logger::debug!(query = %debug_query::<Pg, _>(&query).to_string());
+        query
+            .get_results_async(conn)
+            .await
+            .into_report()
+            .change_context(errors::DatabaseError::Others)
+            .attach_printable("Error aggregating payments by funnel stage")
+    }
 }
@@ -0,0 +1 @@
+pub use diesel_models::payment_metadata_audit::{PaymentMetadataAudit, PaymentMetadataAuditNew};
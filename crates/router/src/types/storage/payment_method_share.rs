@@ -0,0 +1,4 @@
+pub use diesel_models::payment_method_share::{
+    PaymentMethodShare, PaymentMethodShareNew, PaymentMethodShareUpdate,
+    PaymentMethodShareUpdateInternal,
+};
@@ -1 +1,167 @@
+use async_bb8_diesel::AsyncRunQueryDsl;
+use diesel::{
+    debug_query,
+    dsl::{count_star, sql},
+    pg::Pg,
+    sql_types::{BigInt, Nullable},
+    ExpressionMethods, JoinOnDsl, QueryDsl,
+};
 pub use diesel_models::payouts::{Payouts, PayoutsNew, PayoutsUpdate, PayoutsUpdateInternal};
+use diesel_models::{
+    enums::PayoutStatus,
+    errors,
+    schema::{
+        payout_attempt::{self, dsl as dsl1},
+        payouts::{self, dsl},
+    },
+};
+use error_stack::{IntoReport, ResultExt};
+use router_env::{instrument, tracing};
+
+use super::analytics::{self, CurrencyVolume, PayoutFailureCount, PayoutVolume};
+use crate::{connection::PgPooledConn, core::errors::CustomResult};
+
+#[async_trait::async_trait]
+pub trait PayoutsDbExt: Sized {
+    async fn get_pending_volume_by_currency(
+        conn: &PgPooledConn,
+        merchant_id: &str,
+        start_time: time::PrimitiveDateTime,
+        end_time: time::PrimitiveDateTime,
+    ) -> CustomResult<Vec<CurrencyVolume>, errors::DatabaseError>;
+
+    async fn get_volumes(
+        conn: &PgPooledConn,
+        merchant_id: &str,
+        start_time: time::PrimitiveDateTime,
+        end_time: time::PrimitiveDateTime,
+    ) -> CustomResult<Vec<PayoutVolume>, errors::DatabaseError>;
+
+    async fn get_failure_counts(
+        conn: &PgPooledConn,
+        merchant_id: &str,
+        start_time: time::PrimitiveDateTime,
+        end_time: time::PrimitiveDateTime,
+    ) -> CustomResult<Vec<PayoutFailureCount>, errors::DatabaseError>;
+}
+
+/// The condition on the status of the payout attempt of payouts which failed.
+const FAILED_CONDITION: &str = "payout_attempt.status IN ('failed', 'ineligible')";
+
+/// The condition on the status of the payout attempt of payouts which succeeded.
+const SUCCESSFUL_CONDITION: &str = "payout_attempt.status = 'success'";
+
+#[async_trait::async_trait]
+impl PayoutsDbExt for Payouts {
+    #[instrument(skip(conn))]
+    async fn get_pending_volume_by_currency(
+        conn: &PgPooledConn,
+        merchant_id: &str,
+        start_time: time::PrimitiveDateTime,
+        end_time: time::PrimitiveDateTime,
+    ) -> CustomResult<Vec<CurrencyVolume>, errors::DatabaseError> {
+        let query = payouts::table
+            .inner_join(payout_attempt::table.on(dsl1::payout_id.eq(dsl::payout_id)))
+            .filter(dsl::merchant_id.eq(merchant_id.to_owned()))
+            .filter(dsl1::merchant_id.eq(merchant_id.to_owned()))
+            .filter(dsl1::status.eq_any(vec![
+                PayoutStatus::RequiresCreation,
+                PayoutStatus::RequiresPayoutMethodData,
+                PayoutStatus::RequiresFulfillment,
+                PayoutStatus::Pending,
+            ]))
+            .filter(dsl::created_at.ge(start_time))
+            .filter(dsl::created_at.le(end_time))
+            .group_by(dsl::destination_currency)
+            .select((
+                dsl::destination_currency,
+                sql::<BigInt>(&analytics::sum_amount("payouts.amount")),
+                count_star(),
+            ));
+
+        crate::logger::debug!(query = %debug_query::<Pg, _>(&query).to_string());
+        query
+            .get_results_async(conn)
+            .await
+            .into_report()
+            .change_context(errors::DatabaseError::Others)
+            .attach_printable("Error aggregating pending payouts by currency")
+    }
+
+    #[instrument(skip(conn))]
+    async fn get_volumes(
+        conn: &PgPooledConn,
+        merchant_id: &str,
+        start_time: time::PrimitiveDateTime,
+        end_time: time::PrimitiveDateTime,
+    ) -> CustomResult<Vec<PayoutVolume>, errors::DatabaseError> {
+        // A payout is fulfilled when its attempt succeeds, which is when the attempt is last
+        // modified
+        let query = payouts::table
+            .inner_join(payout_attempt::table.on(dsl1::payout_id.eq(dsl::payout_id)))
+            .filter(dsl::merchant_id.eq(merchant_id.to_owned()))
+            .filter(dsl1::merchant_id.eq(merchant_id.to_owned()))
+            .filter(dsl::created_at.ge(start_time))
+            .filter(dsl::created_at.le(end_time))
+            .group_by((dsl1::connector, dsl::destination_currency, dsl::payout_type))
+            .select((
+                dsl1::connector,
+                dsl::destination_currency,
+                dsl::payout_type,
+                count_star(),
+                sql::<BigInt>(&analytics::sum_amount("payouts.amount")),
+                sql::<BigInt>(&format!("COUNT(*) FILTER (WHERE {SUCCESSFUL_CONDITION})")),
+                sql::<BigInt>(&format!(
+                    "CAST(COALESCE(SUM(payouts.amount) FILTER (WHERE {SUCCESSFUL_CONDITION}), 0) \
+                     AS BIGINT)"
+                )),
+                sql::<BigInt>(&format!("COUNT(*) FILTER (WHERE {FAILED_CONDITION})")),
+                sql::<Nullable<BigInt>>(&format!(
+                    "CAST(AVG(EXTRACT(EPOCH FROM payout_attempt.last_modified_at - payouts.created_at)) \
+                     FILTER (WHERE {SUCCESSFUL_CONDITION}) AS BIGINT)"
+                )),
+            ))
+            .order((dsl1::connector, dsl::destination_currency, dsl::payout_type));
+
+        crate::logger::debug!(query = %debug_query::<Pg, _>(&query).to_string());
+        query
+            .get_results_async(conn)
+            .await
+            .into_report()
+            .change_context(errors::DatabaseError::Others)
+            .attach_printable("Error aggregating payouts by connector, currency and payout type")
+    }
+
+    #[instrument(skip(conn))]
+    async fn get_failure_counts(
+        conn: &PgPooledConn,
+        merchant_id: &str,
+        start_time: time::PrimitiveDateTime,
+        end_time: time::PrimitiveDateTime,
+    ) -> CustomResult<Vec<PayoutFailureCount>, errors::DatabaseError> {
+        // The messages returned along with an error code may vary, so any one of them is kept
+        let query = payouts::table
+            .inner_join(payout_attempt::table.on(dsl1::payout_id.eq(dsl::payout_id)))
+            .filter(dsl::merchant_id.eq(merchant_id.to_owned()))
+            .filter(dsl1::merchant_id.eq(merchant_id.to_owned()))
+            .filter(dsl1::status.eq_any(vec![PayoutStatus::Failed, PayoutStatus::Ineligible]))
+            .filter(dsl::created_at.ge(start_time))
+            .filter(dsl::created_at.le(end_time))
+            .group_by((dsl1::connector, dsl1::error_code))
+            .select((
+                dsl1::connector,
+                dsl1::error_code,
+                sql::<Nullable<diesel::sql_types::Text>>("MAX(payout_attempt.error_message)"),
+                count_star(),
+            ))
+            .order((count_star().desc(), dsl1::connector));
+
+        crate::logger::debug!(query = %debug_query::<Pg, _>(&query).to_string());
+        query
+            .get_results_async(conn)
+            .await
+            .into_report()
+            .change_context(errors::DatabaseError::Others)
+            .attach_printable("Error aggregating failed payouts by error")
+    }
+}
@@ -1,6 +1,11 @@
 use async_bb8_diesel::AsyncRunQueryDsl;
 use common_utils::errors::CustomResult;
-use diesel::{associations::HasTable, ExpressionMethods, QueryDsl};
+use diesel::{
+    associations::HasTable,
+    dsl::{count_star, sql},
+    sql_types::BigInt,
+    ExpressionMethods, QueryDsl,
+};
 pub use diesel_models::refund::{
     Refund, RefundCoreWorkflow, RefundNew, RefundUpdate, RefundUpdateInternal,
 };
@@ -11,6 +16,7 @@ use diesel_models::{
 };
 use error_stack::{IntoReport, ResultExt};
 
+use super::analytics::{self, CurrencyVolume};
 use crate::{connection::PgPooledConn, logger};
 
 #[cfg(feature = "kv_store")]
@@ -31,6 +37,13 @@ pub trait RefundDbExt: Sized {
         merchant_id: &str,
         refund_list_details: &api_models::refunds::TimeRange,
     ) -> CustomResult<api_models::refunds::RefundListMetaData, errors::DatabaseError>;
+
+    async fn get_refunded_volume_by_currency(
+        conn: &PgPooledConn,
+        merchant_id: &str,
+        start_time: time::PrimitiveDateTime,
+        end_time: time::PrimitiveDateTime,
+    ) -> CustomResult<Vec<CurrencyVolume>, errors::DatabaseError>;
 }
 
 #[async_trait::async_trait]
@@ -143,4 +156,28 @@ impl RefundDbExt for Refund {
 
         Ok(meta)
     }
+
+    async fn get_refunded_volume_by_currency(
+        conn: &PgPooledConn,
+        merchant_id: &str,
+        start_time: time::PrimitiveDateTime,
+        end_time: time::PrimitiveDateTime,
+    ) -> CustomResult<Vec<CurrencyVolume>, errors::DatabaseError> {
+        <Self as HasTable>::table()
+            .filter(dsl::merchant_id.eq(merchant_id.to_owned()))
+            .filter(dsl::refund_status.eq(RefundStatus::Success))
+            .filter(dsl::modified_at.ge(start_time))
+            .filter(dsl::modified_at.le(end_time))
+            .group_by(dsl::currency)
+            .select((
+                dsl::currency,
+                sql::<BigInt>(&analytics::sum_amount("refund_amount")),
+                count_star(),
+            ))
+            .get_results_async(conn)
+            .await
+            .into_report()
+            .change_context(errors::DatabaseError::Others)
+            .attach_printable("Error aggregating successful refunds by currency")
+    }
 }
@@ -0,0 +1,4 @@
+pub use diesel_models::report_schedule::{
+    ReportGenerationWorkflow, ReportSchedule, ReportScheduleNew, ReportScheduleUpdate,
+    ReportScheduleUpdateInternal,
+};
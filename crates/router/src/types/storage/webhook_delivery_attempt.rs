@@ -0,0 +1,3 @@
+pub use diesel_models::webhook_delivery_attempt::{
+    OutgoingWebhookRetryWorkflow, WebhookDeliveryAttempt, WebhookDeliveryAttemptNew,
+};
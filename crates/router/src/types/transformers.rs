@@ -2,11 +2,11 @@ use api_models::enums as api_enums;
 use common_utils::{crypto::Encryptable, ext_traits::ValueExt, pii};
 use diesel_models::enums as storage_enums;
 use error_stack::ResultExt;
-use masking::{ExposeInterface, PeekInterface};
+use masking::PeekInterface;
 
 use super::domain;
 use crate::{
-    core::errors,
+    core::{admin::webhook_secrets, errors, payments::apple_pay},
     types::{api as api_types, storage},
 };
 
@@ -59,6 +59,17 @@ impl ForeignFrom<api_models::refunds::RefundType> for storage_enums::RefundType
     }
 }
 
+impl ForeignFrom<storage_enums::RefundType> for api_models::refunds::RefundType {
+    fn foreign_from(item: storage_enums::RefundType) -> Self {
+        match item {
+            storage_enums::RefundType::InstantRefund => Self::Instant,
+            storage_enums::RefundType::RegularRefund | storage_enums::RefundType::RetryRefund => {
+                Self::Scheduled
+            }
+        }
+    }
+}
+
 impl ForeignFrom<storage_enums::AttemptStatus> for storage_enums::IntentStatus {
     fn foreign_from(s: storage_enums::AttemptStatus) -> Self {
         match s {
@@ -327,6 +338,8 @@ impl ForeignTryFrom<storage_enums::RefundStatus> for storage_enums::EventType {
         match value {
             storage_enums::RefundStatus::Success => Ok(Self::RefundSucceeded),
             storage_enums::RefundStatus::Failure => Ok(Self::RefundFailed),
+            storage_enums::RefundStatus::AmountMismatch => Ok(Self::RefundAmountMismatch),
+            storage_enums::RefundStatus::ManualReview => Ok(Self::RefundManualReview),
             _ => Err(errors::ValidationError::IncorrectValueProvided {
                 field_name: "refund_status",
             }),
@@ -366,6 +379,59 @@ impl ForeignTryFrom<api_models::webhooks::IncomingWebhookEvent> for storage_enum
     }
 }
 
+#[cfg(feature = "payouts")]
+impl ForeignFrom<storage_enums::PayoutStatus> for storage_enums::RefundStatus {
+    fn foreign_from(status: storage_enums::PayoutStatus) -> Self {
+        match status {
+            storage_enums::PayoutStatus::Success => Self::Success,
+            storage_enums::PayoutStatus::Failed
+            | storage_enums::PayoutStatus::Cancelled
+            | storage_enums::PayoutStatus::Ineligible
+            | storage_enums::PayoutStatus::Reversed => Self::Failure,
+            storage_enums::PayoutStatus::Pending
+            | storage_enums::PayoutStatus::RequiresCreation
+            | storage_enums::PayoutStatus::RequiresPayoutMethodData
+            | storage_enums::PayoutStatus::RequiresFulfillment => Self::Pending,
+        }
+    }
+}
+
+#[cfg(feature = "payouts")]
+impl ForeignTryFrom<api_models::webhooks::IncomingWebhookEvent> for storage_enums::PayoutStatus {
+    type Error = errors::ValidationError;
+
+    fn foreign_try_from(
+        value: api_models::webhooks::IncomingWebhookEvent,
+    ) -> Result<Self, Self::Error> {
+        match value {
+            api_models::webhooks::IncomingWebhookEvent::PayoutInitiated => Ok(Self::Pending),
+            api_models::webhooks::IncomingWebhookEvent::PayoutSuccess => Ok(Self::Success),
+            api_models::webhooks::IncomingWebhookEvent::PayoutFailure => Ok(Self::Failed),
+            api_models::webhooks::IncomingWebhookEvent::PayoutReversed => Ok(Self::Reversed),
+            _ => Err(errors::ValidationError::IncorrectValueProvided {
+                field_name: "incoming_webhook_event_type",
+            }),
+        }
+    }
+}
+
+#[cfg(feature = "payouts")]
+impl ForeignTryFrom<storage_enums::PayoutStatus> for storage_enums::EventType {
+    type Error = errors::ValidationError;
+
+    fn foreign_try_from(value: storage_enums::PayoutStatus) -> Result<Self, Self::Error> {
+        match value {
+            storage_enums::PayoutStatus::Pending => Ok(Self::PayoutProcessing),
+            storage_enums::PayoutStatus::Success => Ok(Self::PayoutSucceeded),
+            storage_enums::PayoutStatus::Failed => Ok(Self::PayoutFailed),
+            storage_enums::PayoutStatus::Reversed => Ok(Self::PayoutReversed),
+            _ => Err(errors::ValidationError::IncorrectValueProvided {
+                field_name: "payout_status",
+            }),
+        }
+    }
+}
+
 impl ForeignFrom<storage::Config> for api_types::Config {
     fn foreign_from(config: storage::Config) -> Self {
         let config = config;
@@ -462,6 +528,38 @@ impl ForeignFrom<api_models::api_keys::UpdateApiKeyRequest>
     }
 }
 
+impl ForeignFrom<diesel_models::terminal::Terminal> for api_models::terminals::TerminalResponse {
+    fn foreign_from(terminal: diesel_models::terminal::Terminal) -> Self {
+        Self {
+            terminal_id: terminal.terminal_id,
+            merchant_id: terminal.merchant_id,
+            merchant_connector_id: terminal.merchant_connector_id,
+            connector_terminal_id: terminal.connector_terminal_id,
+            label: terminal.label,
+            location: terminal.location,
+            serial_number: terminal.serial_number,
+            is_active: terminal.is_active,
+            metadata: terminal.metadata,
+            created: terminal.created_at,
+        }
+    }
+}
+
+impl ForeignFrom<api_models::terminals::TerminalUpdateRequest>
+    for diesel_models::terminal::TerminalUpdate
+{
+    fn foreign_from(terminal: api_models::terminals::TerminalUpdateRequest) -> Self {
+        Self::Update {
+            merchant_connector_id: terminal.merchant_connector_id,
+            connector_terminal_id: terminal.connector_terminal_id,
+            label: terminal.label,
+            location: terminal.location,
+            is_active: terminal.is_active,
+            metadata: terminal.metadata,
+        }
+    }
+}
+
 impl ForeignTryFrom<api_models::webhooks::IncomingWebhookEvent> for storage_enums::DisputeStatus {
     type Error = errors::ValidationError;
 
@@ -508,6 +606,10 @@ impl ForeignFrom<storage::Dispute> for api_models::disputes::DisputeResponse {
             connector_created_at: dispute.connector_created_at,
             connector_updated_at: dispute.connector_updated_at,
             created_at: dispute.created_at,
+            payment_summary: None,
+            customer_id: None,
+            reason_code_description: None,
+            reason_code_details: None,
         }
     }
 }
@@ -558,6 +660,11 @@ impl ForeignFrom<diesel_models::cards_info::CardInfo> for api_models::cards_info
 impl TryFrom<domain::MerchantConnectorAccount> for api_models::admin::MerchantConnectorResponse {
     type Error = error_stack::Report<errors::ApiErrorResponse>;
     fn try_from(item: domain::MerchantConnectorAccount) -> Result<Self, Self::Error> {
+        let connector_webhook_details = item
+            .get_webhook_details()
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Unable to deserialize connector_webhook_details")?
+            .map(webhook_secrets::mask_webhook_details);
         let payment_methods_enabled = match item.payment_methods_enabled {
             Some(val) => serde_json::Value::Array(val)
                 .parse_value("PaymentMethods")
@@ -582,31 +689,30 @@ impl TryFrom<domain::MerchantConnectorAccount> for api_models::admin::MerchantCo
             }
             None => None,
         };
+        // The Apple Pay payment processing key is never returned, including the keys kept in the
+        // metadata of the accounts configured before it was stored apart from the metadata
+        let mut metadata = item.metadata;
+        apple_pay::take_payment_processing_certificate_key(&mut metadata);
         Ok(Self {
             connector_type: item.connector_type,
             connector_name: item.connector_name,
             connector_label: item.connector_label,
             merchant_connector_id: item.merchant_connector_id,
             connector_account_details: item.connector_account_details.into_inner(),
+            test_connector_account_details: item
+                .test_connector_account_details
+                .map(Encryptable::into_inner),
             test_mode: item.test_mode,
             disabled: item.disabled,
             payment_methods_enabled,
-            metadata: item.metadata,
+            metadata,
             business_country: item.business_country,
             business_label: item.business_label,
             business_sub_label: item.business_sub_label,
             frm_configs,
-            connector_webhook_details: item
-                .connector_webhook_details
-                .map(|webhook_details| {
-                    serde_json::Value::parse_value(
-                        webhook_details.expose(),
-                        "MerchantConnectorWebhookDetails",
-                    )
-                    .attach_printable("Unable to deserialize connector_webhook_details")
-                    .change_context(errors::ApiErrorResponse::InternalServerError)
-                })
-                .transpose()?,
+            connector_webhook_details,
+            client_certificate_expires_at: item.client_certificate_expires_at,
+            verification_status: None,
         })
     }
 }
@@ -632,6 +738,25 @@ impl ForeignFrom<storage::PaymentAttempt> for api_models::payments::PaymentAttem
             payment_experience: payment_attempt.payment_experience,
             payment_method_type: payment_attempt.payment_method_type,
             reference_id: payment_attempt.connector_response_reference_id,
+            previous_attempt_id: payment_attempt.previous_attempt_id,
+        }
+    }
+}
+
+impl ForeignFrom<storage::Capture> for api_models::payments::CaptureResponse {
+    fn foreign_from(capture: storage::Capture) -> Self {
+        Self {
+            capture_id: capture.capture_id,
+            status: capture.status,
+            amount: capture.amount,
+            currency: capture.currency,
+            connector: capture.connector,
+            authorized_attempt_id: capture.authorized_attempt_id,
+            connector_capture_id: capture.connector_transaction_id,
+            capture_sequence: capture.capture_sequence,
+            error_message: capture.error_message,
+            error_code: capture.error_code,
+            error_reason: capture.error_reason,
         }
     }
 }
@@ -1,6 +1,7 @@
 pub mod custom_serde;
 pub mod db_utils;
 pub mod ext_traits;
+pub mod schema_validation;
 
 #[cfg(feature = "kv_store")]
 pub mod storage_partitioning;
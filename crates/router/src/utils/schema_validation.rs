@@ -0,0 +1,706 @@
+//! Validation of the bodies of API requests and responses against the OpenAPI specification of
+//! the API.
+//!
+//! Fields which do not match the schema of a body are reported with the JSON pointer to the field,
+//! such as `/billing/address/zip`. Fields which are not described by the schema are reported as
+//! unknown fields, since they would otherwise be silently ignored when the body is deserialized,
+//! which hides typos in the names of optional fields. The values of the fields are never included
+//! in the diagnostics, as they may contain sensitive data.
+
+use once_cell::sync::Lazy;
+use serde_json::Value;
+
+/// The number of references followed when resolving a schema, beyond which the schema is assumed
+/// to be a cycle of references and is left unresolved.
+const MAX_REFERENCE_DEPTH: usize = 16;
+
+/// The number of allowed values of an enum which are listed when a value is not one of them.
+const MAX_LISTED_ENUM_VALUES: usize = 10;
+
+/// A violation of the schema of the API by a field of a body.
+#[derive(Clone, Debug, serde::Serialize)]
+pub struct SchemaDiagnostic {
+    /// The JSON pointer to the field, empty for the whole body
+    pub pointer: String,
+    pub message: String,
+}
+
+/// The result of the validation of a body against a schema.
+#[derive(Debug, Default)]
+pub struct ValidationReport {
+    pub errors: Vec<SchemaDiagnostic>,
+    /// The JSON pointers to the fields which are not described by the schema
+    pub unknown_fields: Vec<String>,
+}
+
+impl ValidationReport {
+    fn push_error(&mut self, pointer: &str, message: String) {
+        self.errors.push(SchemaDiagnostic {
+            pointer: pointer.to_owned(),
+            message,
+        });
+    }
+
+    fn extend(&mut self, other: Self) {
+        self.errors.extend(other.errors);
+        self.unknown_fields.extend(other.unknown_fields);
+    }
+
+    /// Returns the diagnostics of the report, reporting the unknown fields as errors as well.
+    pub fn into_diagnostics(self) -> Vec<SchemaDiagnostic> {
+        let unknown_fields = self
+            .unknown_fields
+            .into_iter()
+            .map(|pointer| SchemaDiagnostic {
+                pointer,
+                message: "unknown field".to_string(),
+            });
+        self.errors.into_iter().chain(unknown_fields).collect()
+    }
+}
+
+/// A segment of the template of a path of the specification.
+enum PathSegment {
+    Literal(String),
+    Parameter,
+}
+
+/// The OpenAPI specification of the API, along with the templates of its paths.
+struct Specification {
+    document: Value,
+    paths: Vec<(String, Vec<PathSegment>)>,
+}
+
+impl Specification {
+    fn new(document: Value) -> Self {
+        let paths = document
+            .get("paths")
+            .and_then(Value::as_object)
+            .map(|paths| {
+                paths
+                    .keys()
+                    .map(|path| {
+                        let segments = path
+                            .split('/')
+                            .filter(|segment| !segment.is_empty())
+                            .map(|segment| {
+                                if segment.starts_with('{') && segment.ends_with('}') {
+                                    PathSegment::Parameter
+                                } else {
+                                    PathSegment::Literal(segment.to_owned())
+                                }
+                            })
+                            .collect();
+                        (path.clone(), segments)
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        Self { document, paths }
+    }
+
+    /// Finds the template of the specification matching the path, preferring the templates with
+    /// the most literal segments, so that `/payments/list` is not matched by
+    /// `/payments/{payment_id}`.
+    fn find_path(&self, path: &str) -> Option<&str> {
+        let segments: Vec<_> = path
+            .split('/')
+            .filter(|segment| !segment.is_empty())
+            .collect();
+
+        self.paths
+            .iter()
+            .filter(|(_, template)| {
+                template.len() == segments.len()
+                    && template
+                        .iter()
+                        .zip(&segments)
+                        .all(|(template_segment, segment)| match template_segment {
+                            PathSegment::Literal(literal) => literal == segment,
+                            PathSegment::Parameter => true,
+                        })
+            })
+            .max_by_key(|(_, template)| {
+                template
+                    .iter()
+                    .filter(|segment| matches!(segment, PathSegment::Literal(_)))
+                    .count()
+            })
+            .map(|(path, _)| path.as_str())
+    }
+}
+
+/// The OpenAPI specification of the API, generated from the `openapi` feature of the application
+/// and embedded in every build, so that bodies are validated regardless of the enabled features.
+const SPECIFICATION_DOCUMENT: &str = include_str!("../../../../openapi/openapi_spec.json");
+
+fn load_specification() -> Option<Specification> {
+    serde_json::from_str(SPECIFICATION_DOCUMENT)
+        .map_err(|error| {
+            router_env::logger::error!(
+                ?error,
+                "Failed to parse the OpenAPI specification, bodies will not be validated"
+            )
+        })
+        .ok()
+        .map(Specification::new)
+}
+
+static SPECIFICATION: Lazy<Option<Specification>> = Lazy::new(load_specification);
+
+/// Returns whether the specification of the API could be loaded, without which no body can be
+/// validated.
+pub fn is_specification_available() -> bool {
+    SPECIFICATION.is_some()
+}
+
+/// An operation of the specification, identified by its path and method.
+pub struct Operation {
+    operation: &'static Value,
+}
+
+impl Operation {
+    /// Finds the operation of the specification serving requests with the method to the path.
+    pub fn find(method: &http::Method, path: &str) -> Option<Self> {
+        let specification = SPECIFICATION.as_ref()?;
+        let template = specification.find_path(path)?;
+
+        specification
+            .document
+            .get("paths")
+            .and_then(|paths| paths.get(template))
+            .and_then(|path_item| path_item.get(method.as_str().to_lowercase()))
+            .map(|operation| Self { operation })
+    }
+
+    /// Returns the schema of the JSON body of the requests of the operation, if it has one.
+    pub fn get_request_schema(&self) -> Option<&'static Value> {
+        self.operation
+            .pointer("/requestBody/content/application~1json/schema")
+    }
+
+    /// Returns the schema of the JSON body of the responses of the operation with the status code,
+    /// falling back to the schema of the default response.
+    pub fn get_response_schema(&self, status_code: http::StatusCode) -> Option<&'static Value> {
+        let responses = self.operation.get("responses")?;
+        responses
+            .get(status_code.as_str())
+            .or_else(|| responses.get("default"))
+            .and_then(|response| response.pointer("/content/application~1json/schema"))
+    }
+}
+
+/// Validates the body against a schema of the specification.
+pub fn validate_body(schema: &'static Value, body: &Value) -> ValidationReport {
+    let mut report = ValidationReport::default();
+    if let Some(specification) = SPECIFICATION.as_ref() {
+        Validator {
+            document: &specification.document,
+        }
+        .validate(schema, body, "", &mut report);
+    }
+    report
+}
+
+struct Validator<'a> {
+    document: &'a Value,
+}
+
+impl<'a> Validator<'a> {
+    /// Follows the references of the schema to the schema they point to.
+    fn resolve(&self, mut schema: &'a Value) -> &'a Value {
+        for _ in 0..MAX_REFERENCE_DEPTH {
+            match schema
+                .get("$ref")
+                .and_then(Value::as_str)
+                .and_then(|reference| reference.strip_prefix('#'))
+                .and_then(|pointer| self.document.pointer(pointer))
+            {
+                Some(target) => schema = target,
+                None => break,
+            }
+        }
+        schema
+    }
+
+    /// Returns the schema along with the schemas it is composed of through `allOf`, all of which
+    /// a value has to match.
+    fn collect_members(&self, schema: &'a Value, members: &mut Vec<&'a Value>) {
+        let schema = self.resolve(schema);
+        members.push(schema);
+        if let Some(all_of) = schema.get("allOf").and_then(Value::as_array) {
+            for member in all_of {
+                self.collect_members(member, members);
+            }
+        }
+    }
+
+    fn validate(
+        &self,
+        schema: &'a Value,
+        value: &Value,
+        pointer: &str,
+        report: &mut ValidationReport,
+    ) {
+        let mut members = Vec::new();
+        self.collect_members(schema, &mut members);
+
+        if value.is_null() {
+            if members
+                .iter()
+                .any(|member| member.get("nullable").and_then(Value::as_bool) == Some(true))
+            {
+                return;
+            }
+            if let Some(expected) = members
+                .iter()
+                .find_map(|member| member.get("type").and_then(Value::as_str))
+            {
+                report.push_error(pointer, format!("expected {expected}, found null"));
+            }
+            return;
+        }
+
+        for member in &members {
+            if let Some(expected) = member.get("type").and_then(Value::as_str) {
+                if !matches_type(expected, value) {
+                    report.push_error(
+                        pointer,
+                        format!("expected {expected}, found {}", get_type_name(value)),
+                    );
+                    return;
+                }
+            }
+            if let Some(variants) = member
+                .get("oneOf")
+                .or_else(|| member.get("anyOf"))
+                .and_then(Value::as_array)
+            {
+                self.validate_variants(variants, value, pointer, report);
+            }
+            validate_constraints(member, value, pointer, report);
+        }
+
+        match value {
+            Value::Object(object) => self.validate_object(&members, object, pointer, report),
+            Value::Array(items) => {
+                if let Some(items_schema) = members.iter().find_map(|member| member.get("items")) {
+                    for (index, item) in items.iter().enumerate() {
+                        self.validate(items_schema, item, &format!("{pointer}/{index}"), report);
+                    }
+                }
+            }
+            _ => (),
+        }
+    }
+
+    /// Validates the value against the variant of `oneOf` or `anyOf` which it matches best,
+    /// reporting the violations of that variant.
+    fn validate_variants(
+        &self,
+        variants: &'a [Value],
+        value: &Value,
+        pointer: &str,
+        report: &mut ValidationReport,
+    ) {
+        let best_report = variants
+            .iter()
+            .map(|variant| {
+                let mut variant_report = ValidationReport::default();
+                self.validate(variant, value, pointer, &mut variant_report);
+                variant_report
+            })
+            .min_by_key(|variant_report| {
+                (
+                    variant_report.errors.len(),
+                    variant_report.unknown_fields.len(),
+                )
+            });
+
+        if let Some(best_report) = best_report {
+            report.extend(best_report);
+        }
+    }
+
+    fn validate_object(
+        &self,
+        members: &[&'a Value],
+        object: &serde_json::Map<String, Value>,
+        pointer: &str,
+        report: &mut ValidationReport,
+    ) {
+        let required: Vec<&str> = members
+            .iter()
+            .filter_map(|member| member.get("required").and_then(Value::as_array))
+            .flatten()
+            .filter_map(Value::as_str)
+            .collect();
+        let has_properties = members
+            .iter()
+            .any(|member| member.get("properties").is_some());
+        let additional_properties = members.iter().find_map(|member| {
+            member
+                .get("additionalProperties")
+                .filter(|additional| additional.as_bool() != Some(false))
+        });
+
+        for field in &required {
+            if !object.contains_key(*field) {
+                report.push_error(pointer, format!("missing required field `{field}`"));
+            }
+        }
+
+        for (key, field_value) in object {
+            let field_pointer = format!("{pointer}/{}", escape_pointer_token(key));
+            let property = members.iter().find_map(|member| {
+                member
+                    .get("properties")
+                    .and_then(|properties| properties.get(key))
+            });
+
+            match (property, additional_properties) {
+                // Optional fields set to null are deserialized as missing fields
+                (Some(_), _) if field_value.is_null() && !required.contains(&key.as_str()) => (),
+                (Some(property), _) => self.validate(property, field_value, &field_pointer, report),
+                (None, Some(additional)) if additional.is_object() => {
+                    self.validate(additional, field_value, &field_pointer, report)
+                }
+                (None, None) if has_properties => report.unknown_fields.push(field_pointer),
+                (None, _) => (),
+            }
+        }
+    }
+}
+
+fn validate_constraints(
+    schema: &Value,
+    value: &Value,
+    pointer: &str,
+    report: &mut ValidationReport,
+) {
+    if let Some(allowed_values) = schema.get("enum").and_then(Value::as_array) {
+        if !allowed_values.contains(value) {
+            let message = if allowed_values.len() <= MAX_LISTED_ENUM_VALUES {
+                let allowed_values = allowed_values
+                    .iter()
+                    .map(Value::to_string)
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                format!("expected one of {allowed_values}")
+            } else {
+                "expected one of the values allowed by the specification".to_string()
+            };
+            report.push_error(pointer, message);
+        }
+    }
+
+    let bound = |keyword: &str| schema.get(keyword).and_then(Value::as_f64);
+
+    if let Some(number) = value.as_f64() {
+        if let Some(minimum) = bound("minimum").filter(|minimum| number < *minimum) {
+            report.push_error(
+                pointer,
+                format!("expected a number not less than {minimum}"),
+            );
+        }
+        if let Some(maximum) = bound("maximum").filter(|maximum| number > *maximum) {
+            report.push_error(
+                pointer,
+                format!("expected a number not greater than {maximum}"),
+            );
+        }
+    }
+
+    if let Some(string) = value.as_str() {
+        #[allow(clippy::as_conversions)]
+        let length = string.chars().count() as f64;
+        if let Some(min_length) = bound("minLength").filter(|min_length| length < *min_length) {
+            report.push_error(
+                pointer,
+                format!("expected at least {min_length} characters"),
+            );
+        }
+        if let Some(max_length) = bound("maxLength").filter(|max_length| length > *max_length) {
+            report.push_error(pointer, format!("expected at most {max_length} characters"));
+        }
+    }
+
+    if let Some(items) = value.as_array() {
+        #[allow(clippy::as_conversions)]
+        let length = items.len() as f64;
+        if let Some(min_items) = bound("minItems").filter(|min_items| length < *min_items) {
+            report.push_error(pointer, format!("expected at least {min_items} items"));
+        }
+        if let Some(max_items) = bound("maxItems").filter(|max_items| length > *max_items) {
+            report.push_error(pointer, format!("expected at most {max_items} items"));
+        }
+    }
+}
+
+fn matches_type(expected: &str, value: &Value) -> bool {
+    match expected {
+        "object" => value.is_object(),
+        "array" => value.is_array(),
+        "string" => value.is_string(),
+        "integer" => value.is_i64() || value.is_u64(),
+        "number" => value.is_number(),
+        "boolean" => value.is_boolean(),
+        _ => true,
+    }
+}
+
+fn get_type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(number) if number.is_f64() => "number",
+        Value::Number(_) => "integer",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+/// Escapes a key of an object to be used as a token of a JSON pointer.
+fn escape_pointer_token(key: &str) -> String {
+    key.replace('~', "~0").replace('/', "~1")
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use serde_json::json;
+
+    use super::*;
+
+    fn get_document() -> Value {
+        json!({
+            "paths": {
+                "/payments/{payment_id}": {},
+                "/payments/list": {},
+                "/payments/{payment_id}/capture": {},
+            },
+            "components": {
+                "schemas": {
+                    "Address": {
+                        "type": "object",
+                        "required": ["country"],
+                        "properties": {
+                            "country": {"type": "string", "minLength": 2, "maxLength": 2},
+                            "zip": {"type": "string", "nullable": true},
+                        },
+                    },
+                    "Request": {
+                        "type": "object",
+                        "required": ["amount"],
+                        "properties": {
+                            "amount": {"type": "integer", "minimum": 1, "maximum": 100},
+                            "capture_method": {"type": "string", "enum": ["automatic", "manual"]},
+                            "billing": {"$ref": "#/components/schemas/Address"},
+                            "metadata": {"type": "object", "additionalProperties": true},
+                            "payment_method_data": {
+                                "oneOf": [
+                                    {
+                                        "type": "object",
+                                        "required": ["card"],
+                                        "properties": {"card": {"type": "object"}},
+                                    },
+                                    {
+                                        "type": "object",
+                                        "required": ["wallet"],
+                                        "properties": {"wallet": {"type": "string"}},
+                                    },
+                                ],
+                            },
+                        },
+                    },
+                },
+            },
+        })
+    }
+
+    fn validate(document: &Value, body: Value) -> ValidationReport {
+        let mut report = ValidationReport::default();
+        Validator { document }.validate(
+            &json!({"$ref": "#/components/schemas/Request"}),
+            &body,
+            "",
+            &mut report,
+        );
+        report
+    }
+
+    fn get_error_pointers(report: &ValidationReport) -> Vec<&str> {
+        let mut pointers: Vec<_> = report
+            .errors
+            .iter()
+            .map(|error| error.pointer.as_str())
+            .collect();
+        pointers.sort_unstable();
+        pointers
+    }
+
+    fn get_error_message<'a>(report: &'a ValidationReport, pointer: &str) -> &'a str {
+        report
+            .errors
+            .iter()
+            .find(|error| error.pointer == pointer)
+            .map(|error| error.message.as_str())
+            .unwrap()
+    }
+
+    #[test]
+    fn test_embedded_specification_is_loaded() {
+        assert!(is_specification_available());
+    }
+
+    #[test]
+    fn test_find_path_prefers_literal_segments() {
+        let specification = Specification::new(get_document());
+
+        assert_eq!(
+            specification.find_path("/payments/list"),
+            Some("/payments/list")
+        );
+        assert_eq!(
+            specification.find_path("/payments/pay_123"),
+            Some("/payments/{payment_id}")
+        );
+        assert_eq!(
+            specification.find_path("/payments/pay_123/capture/"),
+            Some("/payments/{payment_id}/capture")
+        );
+        assert_eq!(specification.find_path("/refunds/ref_123"), None);
+    }
+
+    #[test]
+    fn test_valid_body_has_no_diagnostics() {
+        let document = get_document();
+        let report = validate(
+            &document,
+            json!({
+                "amount": 10,
+                "capture_method": "manual",
+                "billing": {"country": "US", "zip": null},
+                "metadata": {"order_id": "123"},
+                "payment_method_data": {"wallet": "apple_pay"},
+            }),
+        );
+
+        assert!(report.errors.is_empty());
+        assert!(report.unknown_fields.is_empty());
+    }
+
+    #[test]
+    fn test_type_and_required_field_errors() {
+        let document = get_document();
+        let report = validate(
+            &document,
+            json!({"capture_method": 1, "billing": {"zip": 12345}}),
+        );
+
+        assert_eq!(
+            get_error_pointers(&report),
+            vec!["", "/billing", "/billing/zip", "/capture_method"]
+        );
+        assert_eq!(
+            get_error_message(&report, ""),
+            "missing required field `amount`"
+        );
+        assert_eq!(
+            get_error_message(&report, "/billing/zip"),
+            "expected string, found integer"
+        );
+    }
+
+    #[test]
+    fn test_null_values() {
+        let document = get_document();
+
+        // Optional fields set to null are treated as missing
+        let report = validate(&document, json!({"amount": 10, "capture_method": null}));
+        assert!(report.errors.is_empty());
+
+        let report = validate(&document, json!({"amount": null}));
+        assert_eq!(get_error_pointers(&report), vec!["/amount"]);
+        assert_eq!(
+            get_error_message(&report, "/amount"),
+            "expected integer, found null"
+        );
+    }
+
+    #[test]
+    fn test_constraint_errors() {
+        let document = get_document();
+        let report = validate(
+            &document,
+            json!({
+                "amount": 0,
+                "capture_method": "scheduled",
+                "billing": {"country": "USA"},
+            }),
+        );
+
+        assert_eq!(
+            get_error_pointers(&report),
+            vec!["/amount", "/billing/country", "/capture_method"]
+        );
+        assert_eq!(
+            get_error_message(&report, "/amount"),
+            "expected a number not less than 1"
+        );
+        assert_eq!(
+            get_error_message(&report, "/billing/country"),
+            "expected at most 2 characters"
+        );
+        assert_eq!(
+            get_error_message(&report, "/capture_method"),
+            r#"expected one of "automatic", "manual""#
+        );
+    }
+
+    #[test]
+    fn test_unknown_fields_are_reported_separately() {
+        let document = get_document();
+        let report = validate(
+            &document,
+            json!({
+                "amount": 10,
+                "amout": 10,
+                "billing": {"country": "US", "zip_code": "94122"},
+                "metadata": {"anything": "goes"},
+            }),
+        );
+
+        assert!(report.errors.is_empty());
+        let mut unknown_fields = report.unknown_fields.clone();
+        unknown_fields.sort_unstable();
+        assert_eq!(unknown_fields, vec!["/amout", "/billing/zip_code"]);
+        assert_eq!(report.into_diagnostics().len(), 2);
+    }
+
+    #[test]
+    fn test_one_of_reports_best_matching_variant() {
+        let document = get_document();
+        let report = validate(
+            &document,
+            json!({"amount": 10, "payment_method_data": {"wallet": 1}}),
+        );
+
+        assert_eq!(
+            get_error_pointers(&report),
+            vec!["/payment_method_data/wallet"]
+        );
+        assert_eq!(
+            get_error_message(&report, "/payment_method_data/wallet"),
+            "expected string, found integer"
+        );
+    }
+
+    #[test]
+    fn test_escape_pointer_token() {
+        assert_eq!(escape_pointer_token("a/b~c"), "a~1b~0c");
+        assert_eq!(escape_pointer_token("plain"), "plain");
+    }
+}
@@ -58,6 +58,7 @@ fn construct_payment_router_data() -> types::PaymentsAuthorizeRouterData {
             browser_info: None,
             order_details: None,
             order_category: None,
+            l2_l3_data: None,
             email: None,
             session_token: None,
             enrolled_for_3ds: false,
@@ -68,6 +69,8 @@ fn construct_payment_router_data() -> types::PaymentsAuthorizeRouterData {
             webhook_url: None,
             complete_authorize_url: None,
             customer_id: None,
+            network_token_data: None,
+            currency_conversion: None,
         },
         response: Err(types::ErrorResponse::default()),
         payment_method_id: None,
@@ -88,6 +91,8 @@ fn construct_payment_router_data() -> types::PaymentsAuthorizeRouterData {
         quote_id: None,
         test_mode: None,
         payment_method_balance: None,
+        idempotency_key: None,
+        connector_client_certificate: None,
     }
 }
 
@@ -120,6 +125,7 @@ fn construct_refund_router_data<F>() -> types::RefundsRouterData<F> {
             connector_metadata: None,
             reason: None,
             connector_refund_id: None,
+            currency_conversion: None,
         },
         payment_method_id: None,
         response: Err(types::ErrorResponse::default()),
@@ -140,6 +146,8 @@ fn construct_refund_router_data<F>() -> types::RefundsRouterData<F> {
         quote_id: None,
         test_mode: None,
         payment_method_balance: None,
+        idempotency_key: None,
+        connector_client_certificate: None,
     }
 }
 
@@ -143,6 +143,7 @@ impl AdyenTest {
             browser_info: None,
             order_details: None,
             order_category: None,
+            l2_l3_data: None,
             email: None,
             payment_experience: None,
             payment_method_type: None,
@@ -153,6 +154,8 @@ impl AdyenTest {
             webhook_url: None,
             complete_authorize_url: None,
             customer_id: None,
+            network_token_data: None,
+            currency_conversion: None,
         })
     }
 }
@@ -55,6 +55,7 @@ impl CashtocodeTest {
             browser_info: None,
             order_details: None,
             order_category: None,
+            l2_l3_data: None,
             email: None,
             payment_experience: None,
             payment_method_type,
@@ -65,6 +66,8 @@ impl CashtocodeTest {
             webhook_url: None,
             complete_authorize_url: None,
             customer_id: Some("John Doe".to_owned()),
+            network_token_data: None,
+            currency_conversion: None,
         })
     }
 
@@ -81,6 +81,7 @@ fn payment_method_details() -> Option<types::PaymentsAuthorizeData> {
         browser_info: None,
         order_details: None,
         order_category: None,
+        l2_l3_data: None,
         email: None,
         payment_experience: None,
         payment_method_type: None,
@@ -92,6 +93,8 @@ fn payment_method_details() -> Option<types::PaymentsAuthorizeData> {
         complete_authorize_url: None,
         capture_method: None,
         customer_id: None,
+        network_token_data: None,
+        currency_conversion: None,
     })
 }
 
@@ -384,6 +384,7 @@ pub trait ConnectorActions: Connector {
                 connector_metadata: None,
                 reason: None,
                 connector_refund_id: Some(refund_id),
+                currency_conversion: None,
             }),
             payment_info,
         );
@@ -493,7 +494,9 @@ pub trait ConnectorActions: Connector {
             access_token: info.clone().and_then(|a| a.access_token),
             session_token: None,
             reference_id: None,
-            payment_method_token: info.clone().and_then(|a| a.payment_method_token),
+            payment_method_token: info
+                .clone()
+                .and_then(|a| a.payment_method_token.map(types::PaymentMethodToken::Token)),
             connector_customer: info.clone().and_then(|a| a.connector_customer),
             recurring_mandate_payment_data: None,
             preprocessing_id: None,
@@ -504,6 +507,8 @@ pub trait ConnectorActions: Connector {
             quote_id: None,
             test_mode: None,
             payment_method_balance: None,
+            idempotency_key: None,
+            connector_client_certificate: None,
         }
     }
 
@@ -522,6 +527,7 @@ pub trait ConnectorActions: Connector {
             Ok(types::PaymentsResponseData::ConnectorCustomerResponse { .. }) => None,
             Ok(types::PaymentsResponseData::PreProcessingResponse { .. }) => None,
             Ok(types::PaymentsResponseData::ThreeDSEnrollmentResponse { .. }) => None,
+            Ok(types::PaymentsResponseData::IncrementalAuthorizationResponse { .. }) => None,
             Err(_) => None,
         }
     }
@@ -853,6 +859,7 @@ impl Default for PaymentAuthorizeType {
             browser_info: Some(BrowserInfoType::default().0),
             order_details: None,
             order_category: None,
+            l2_l3_data: None,
             email: None,
             session_token: None,
             enrolled_for_3ds: false,
@@ -863,6 +870,8 @@ impl Default for PaymentAuthorizeType {
             complete_authorize_url: None,
             webhook_url: None,
             customer_id: None,
+            network_token_data: None,
+            currency_conversion: None,
         };
         Self(data)
     }
@@ -935,6 +944,7 @@ impl Default for PaymentRefundType {
             connector_metadata: None,
             reason: Some("Customer returned product".to_string()),
             connector_refund_id: None,
+            currency_conversion: None,
         };
         Self(data)
     }
@@ -977,6 +987,7 @@ pub fn get_connector_transaction_id(
         Ok(types::PaymentsResponseData::PreProcessingResponse { .. }) => None,
         Ok(types::PaymentsResponseData::ConnectorCustomerResponse { .. }) => None,
         Ok(types::PaymentsResponseData::ThreeDSEnrollmentResponse { .. }) => None,
+        Ok(types::PaymentsResponseData::IncrementalAuthorizationResponse { .. }) => None,
         Err(_) => None,
     }
 }
@@ -90,6 +90,7 @@ impl WorldlineTest {
             browser_info: None,
             order_details: None,
             order_category: None,
+            l2_l3_data: None,
             email: None,
             session_token: None,
             enrolled_for_3ds: false,
@@ -100,6 +101,8 @@ impl WorldlineTest {
             webhook_url: None,
             complete_authorize_url: None,
             customer_id: None,
+            network_token_data: None,
+            currency_conversion: None,
         })
     }
 }
@@ -72,6 +72,10 @@ pub enum Flow {
     MerchantConnectorsDelete,
     /// Merchant Connectors list flow.
     MerchantConnectorsList,
+    /// Merchant Connectors verify flow.
+    MerchantConnectorsVerify,
+    /// Merchant Connectors webhook secret rotate flow.
+    MerchantConnectorsWebhookSecretRotate,
     /// ConfigKey create flow.
     ConfigKeyCreate,
     /// ConfigKey fetch flow.
@@ -88,6 +92,24 @@ pub enum Flow {
     CustomersDelete,
     /// Customers get mandates flow.
     CustomersGetMandates,
+    /// Customers import flow.
+    CustomersImport,
+    /// Customers import retrieve flow.
+    CustomersImportRetrieve,
+    /// Customers import resume flow.
+    CustomersImportResume,
+    /// Customer addresses create flow.
+    CustomerAddressesCreate,
+    /// Customer addresses list flow.
+    CustomerAddressesList,
+    /// Customer addresses update flow.
+    CustomerAddressesUpdate,
+    /// Customer addresses delete flow.
+    CustomerAddressesDelete,
+    /// Customer addresses set default flow.
+    CustomerAddressesSetDefault,
+    /// Customer payment methods set default flow.
+    CustomerPaymentMethodsSetDefault,
     /// Create an Ephemeral Key.
     EphemeralKeyCreate,
     /// Delete an Ephemeral Key.
@@ -110,6 +132,14 @@ pub enum Flow {
     PaymentMethodsUpdate,
     /// Payment methods delete flow.
     PaymentMethodsDelete,
+    /// Payment method shares create flow.
+    PaymentMethodSharesCreate,
+    /// Payment method shares list flow.
+    PaymentMethodSharesList,
+    /// Payment method shares retrieve flow.
+    PaymentMethodSharesRetrieve,
+    /// Payment method shares revoke flow.
+    PaymentMethodSharesRevoke,
     /// Payments create flow.
     PaymentsCreate,
     /// Payments Retrieve flow.
@@ -118,16 +148,38 @@ pub enum Flow {
     PaymentsUpdate,
     /// Payments confirm flow.
     PaymentsConfirm,
+    /// Payments confirm simulation flow.
+    PaymentsConfirmSimulate,
     /// Payments capture flow.
     PaymentsCapture,
+    /// Payments incremental authorization flow.
+    PaymentsIncrementalAuthorization,
     /// Payments cancel flow.
     PaymentsCancel,
     /// Payments Session Token flow
     PaymentsSessionToken,
+    /// Payments session tokens refresh flow.
+    PaymentsSessionTokenRefresh,
     /// Payments start flow.
     PaymentsStart,
     /// Payments list flow.
     PaymentsList,
+    /// Payments retry flow.
+    PaymentsRetry,
+    /// Payments metadata update flow.
+    PaymentsMetadataUpdate,
+    /// Payments metadata audit list flow.
+    PaymentsMetadataAuditList,
+    /// Payments receipt retrieve flow.
+    PaymentsReceiptRetrieve,
+    /// Payments hosted checkout flow.
+    PaymentsHostedCheckout,
+    /// Payments hosted checkout submit flow.
+    PaymentsHostedCheckoutSubmit,
+    /// Payments velocity limits retrieve flow.
+    PaymentsVelocityLimitsRetrieve,
+    /// Payments velocity limits update flow.
+    PaymentsVelocityLimitsUpdate,
     #[cfg(feature = "payouts")]
     /// Payouts create flow
     PayoutsCreate,
@@ -143,6 +195,15 @@ pub enum Flow {
     #[cfg(feature = "payouts")]
     /// Payouts fulfill flow.
     PayoutsFulfill,
+    #[cfg(feature = "payouts")]
+    /// Payouts sync flow.
+    PayoutsSync,
+    #[cfg(feature = "payouts")]
+    /// Payouts batch create flow.
+    PayoutsBatchCreate,
+    #[cfg(feature = "payouts")]
+    /// Payouts batch retrieve flow.
+    PayoutsBatchRetrieve,
     /// Payouts accounts flow.
     PayoutsAccounts,
     /// Payments Redirect flow.
@@ -153,6 +214,12 @@ pub enum Flow {
     RefundsRetrieve,
     /// Refunds update flow.
     RefundsUpdate,
+    /// Refunds cancel flow.
+    RefundsCancel,
+    /// Refunds approve flow.
+    RefundsApprove,
+    /// Refunds reject flow.
+    RefundsReject,
     /// Refunds list flow.
     RefundsList,
     /// Incoming Webhook Receive
@@ -173,6 +240,12 @@ pub enum Flow {
     DisputesRetrieve,
     /// Dispute List flow
     DisputesList,
+    /// Dispute reason codes list flow
+    DisputesReasonCodesList,
+    /// Dispute auto-defend rules retrieve flow
+    DisputesAutoDefendRulesRetrieve,
+    /// Dispute auto-defend rules update flow
+    DisputesAutoDefendRulesUpdate,
     /// Cards Info flow
     CardsInfo,
     /// Create File flow
@@ -191,6 +264,150 @@ pub enum Flow {
     RetrieveDisputeEvidence,
     /// Invalidate cache flow
     CacheInvalidate,
+    /// Runtime config reload flow
+    RuntimeConfigReload,
+    /// Feature flags list flow
+    FeatureFlagsList,
+    /// Feature flag retrieve flow
+    FeatureFlagRetrieve,
+    /// Feature flag update flow
+    FeatureFlagUpdate,
+    /// Merchant feature flag override update flow
+    MerchantFeatureFlagOverrideUpdate,
+    /// Merchant feature flag override delete flow
+    MerchantFeatureFlagOverrideDelete,
+    /// Merchant key store retrieve flow
+    MerchantKeyStoreRetrieve,
+    /// Merchant key store rotate flow
+    MerchantKeyStoreRotate,
+    /// Merchant allowed domains retrieve flow
+    MerchantAllowedDomainsRetrieve,
+    /// Merchant allowed domains update flow
+    MerchantAllowedDomainsUpdate,
+    /// Connector exclusion rules retrieve flow
+    ConnectorExclusionRulesRetrieve,
+    /// Connector exclusion rules update flow
+    ConnectorExclusionRulesUpdate,
+    /// Merchant commission statement retrieve flow
+    MerchantCommissionStatementRetrieve,
+    /// Webhook endpoint health retrieve flow
+    WebhookEndpointHealthRetrieve,
+    /// Webhook endpoint verify flow
+    WebhookEndpointVerify,
+    /// Webhook signing keys retrieve flow
+    WebhookSigningKeysRetrieve,
+    /// Webhook signing key rotate flow
+    WebhookSigningKeyRotate,
+    /// Webhook signing keys delete flow
+    WebhookSigningKeysDelete,
+    /// Webhook JWKS retrieve flow
+    WebhookJwksRetrieve,
+    /// Webhook egress IP ranges retrieve flow
+    WebhookEgressIpRangesRetrieve,
+    /// Webhook egress IP ranges update flow
+    WebhookEgressIpRangesUpdate,
+    /// Webhook delivery attempts list flow
+    WebhookDeliveryAttemptsList,
+    /// Terminals create flow
+    TerminalsCreate,
+    /// Terminals retrieve flow
+    TerminalsRetrieve,
+    /// Terminals update flow
+    TerminalsUpdate,
+    /// Terminals delete flow
+    TerminalsDelete,
+    /// Terminals list flow
+    TerminalsList,
+    /// Accounting journal export flow
+    AccountingJournalExport,
+    /// Ledger entries list flow
+    LedgerEntriesList,
+    /// Ledger balances retrieve flow
+    LedgerBalancesRetrieve,
+    /// Ledger reconcile flow
+    LedgerReconcile,
+    /// Audit events list flow
+    AuditEventsList,
+    /// Currency exposure retrieve flow
+    CurrencyExposureRetrieve,
+    /// Payments funnel retrieve flow
+    PaymentsFunnelRetrieve,
+    /// Payout analytics retrieve flow
+    PayoutAnalyticsRetrieve,
+    /// Lookup by connector reference flow
+    ConnectorReferenceLookup,
+    /// Invoices create flow
+    InvoicesCreate,
+    /// Invoices retrieve flow
+    InvoicesRetrieve,
+    /// Invoices list flow
+    InvoicesList,
+    /// Invoices void flow
+    InvoicesVoid,
+    /// Invoices payment link retrieve flow
+    InvoicesPaymentLinkRetrieve,
+    /// Invoices pay flow
+    InvoicesPay,
+    /// Payment links create flow
+    PaymentLinksCreate,
+    /// Payment links retrieve flow
+    PaymentLinksRetrieve,
+    /// Payment links render flow
+    PaymentLinksRender,
+    /// Gift cards create flow
+    GiftCardsCreate,
+    /// Gift cards retrieve flow
+    GiftCardsRetrieve,
+    /// Gift cards redeem flow
+    GiftCardsRedeem,
+    /// Gift cards reload flow
+    GiftCardsReload,
+    /// Gift cards void flow
+    GiftCardsVoid,
+    /// Gift cards transactions list flow
+    GiftCardsTransactionsList,
+    /// Report schedules create flow
+    ReportSchedulesCreate,
+    /// Report schedules retrieve flow
+    ReportSchedulesRetrieve,
+    /// Report schedules update flow
+    ReportSchedulesUpdate,
+    /// Report schedules delete flow
+    ReportSchedulesDelete,
+    /// Report schedules list flow
+    ReportSchedulesList,
+    /// Routing fee schedules retrieve flow
+    RoutingFeeSchedulesRetrieve,
+    /// Routing fee schedules update flow
+    RoutingFeeSchedulesUpdate,
+    /// Routing simulate flow
+    RoutingSimulate,
+    /// Refunds resolve flow
+    RefundsResolve,
+    /// Dispute financials retrieve flow
+    DisputeFinancialsRetrieve,
+    /// Subscriptions create flow
+    SubscriptionsCreate,
+    /// Subscriptions retrieve flow
+    SubscriptionsRetrieve,
+    /// Subscriptions list flow
+    SubscriptionsList,
+    /// Subscriptions update flow
+    SubscriptionsUpdate,
+    /// Subscriptions cancel flow
+    SubscriptionsCancel,
+    /// Subscriptions pause flow
+    SubscriptionsPause,
+    /// Subscriptions resume flow
+    SubscriptionsResume,
+    /// Subscriptions dunning config retrieve flow
+    SubscriptionsDunningConfigRetrieve,
+    /// Subscriptions dunning config update flow
+    SubscriptionsDunningConfigUpdate,
+    /// Payments cross-border fee config retrieve flow
+    PaymentsCrossBorderFeeConfigRetrieve,
+    /// Payments cross-border fee config update flow
+    PaymentsCrossBorderFeeConfigUpdate,
 }
 
 ///
@@ -0,0 +1,577 @@
+//! Scaffolding of a new connector from the templates in the `connector-template` directory.
+
+use std::{fs, ops::Range, path::Path};
+
+use crate::Result;
+
+const TEMPLATE_DIR: &str = "connector-template";
+const CONNECTOR_MODULE: &str = "crates/router/src/connector.rs";
+const CONNECTOR_DIR: &str = "crates/router/src/connector";
+const CONNECTOR_TESTS_DIR: &str = "crates/router/tests/connectors";
+const SAMPLE_AUTH: &str = "crates/router/tests/connectors/sample_auth.toml";
+
+/// Modules of the connector module which either are not connectors or are feature gated
+const NON_CONNECTOR_MODULES: [&str; 2] = ["dummyconnector", "utils"];
+
+/// Maximum width of a line, as configured for `rustfmt`
+const MAX_WIDTH: usize = 100;
+
+type Registration = fn(&str, &Connector<'_>) -> Option<String>;
+
+/// Files in which a connector is registered, along with the way it is registered in them
+const REGISTRATIONS: [(&str, Registration); 12] = [
+    (CONNECTOR_MODULE, register_connector_module),
+    ("crates/router/src/types/api.rs", register_connector_data),
+    (
+        "crates/router/src/configs/settings.rs",
+        register_connector_params,
+    ),
+    (
+        "crates/router/src/core/payments/flows.rs",
+        register_default_implementations,
+    ),
+    ("crates/api_models/src/enums.rs", register_connector_enums),
+    ("config/development.toml", register_config),
+    ("config/docker_compose.toml", register_config),
+    ("config/config.example.toml", register_config),
+    ("loadtest/config/development.toml", register_config),
+    (
+        "crates/router/tests/connectors/main.rs",
+        register_test_module,
+    ),
+    (
+        "crates/test_utils/src/connector_auth.rs",
+        register_connector_auth,
+    ),
+    (SAMPLE_AUTH, register_sample_auth),
+];
+
+struct Connector<'a> {
+    name: &'a str,
+    pascal_name: String,
+    base_url: &'a str,
+    /// Existing connectors sorted before the new connector, the closest one first
+    previous: Vec<String>,
+    /// Existing connectors sorted after the new connector, the closest one first
+    next: Vec<String>,
+}
+
+/// Generate the connector module, its transformers and its tests from the connector templates,
+/// and register the connector wherever the existing connectors are registered.
+pub fn add_connector(root: &Path, name: &str, base_url: &str) -> Result<()> {
+    validate_name(name)?;
+
+    let mut existing = fs::read_to_string(root.join(CONNECTOR_MODULE))?
+        .lines()
+        .filter_map(|line| line.strip_prefix("pub mod ")?.strip_suffix(';'))
+        .filter(|module| !NON_CONNECTOR_MODULES.contains(module))
+        .map(ToOwned::to_owned)
+        .collect::<Vec<_>>();
+    if existing.iter().any(|connector| connector == name) {
+        return Err(format!("Connector `{name}` already exists").into());
+    }
+    existing.sort();
+    let (mut previous, next): (Vec<_>, Vec<_>) = existing
+        .into_iter()
+        .partition(|connector| connector.as_str() < name);
+    previous.reverse();
+
+    let connector = Connector {
+        name,
+        pascal_name: pascal_case(name),
+        base_url,
+        previous,
+        next,
+    };
+
+    generate_files(root, &connector)?;
+
+    let mut manual_registrations = Vec::new();
+    for (path, register) in REGISTRATIONS {
+        let content = fs::read_to_string(root.join(path))?;
+        match register(&content, &connector) {
+            Some(updated) => fs::write(root.join(path), updated)?,
+            None => manual_registrations.push(path),
+        }
+    }
+
+    println!("Generated the `{name}` connector");
+    if !manual_registrations.is_empty() {
+        println!("Register the connector manually in the following files:");
+        for path in manual_registrations {
+            println!("  - {path}");
+        }
+    }
+    println!("Next steps:");
+    println!("  - Format the generated code: `cargo +nightly fmt --all`");
+    println!(
+        "  - Implement the request and response types in `{CONNECTOR_DIR}/{name}/transformers.rs`"
+    );
+    println!("  - Update the credentials of the connector in `{SAMPLE_AUTH}`");
+    println!("  - Run the tests of the connector: `cargo test --package router --test connectors -- {name}`");
+
+    Ok(())
+}
+
+fn validate_name(name: &str) -> Result<()> {
+    let is_valid = name.starts_with(|c: char| c.is_ascii_lowercase())
+        && name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_');
+
+    if is_valid {
+        Ok(())
+    } else {
+        Err(format!(
+            "Invalid connector name `{name}`, the name must be a lowercase identifier such as `adyen`"
+        )
+        .into())
+    }
+}
+
+fn pascal_case(name: &str) -> String {
+    name.split(['_', '-'])
+        .map(|word| {
+            let mut chars = word.chars();
+            chars
+                .next()
+                .map(|first| first.to_ascii_uppercase().to_string() + chars.as_str())
+                .unwrap_or_default()
+        })
+        .collect()
+}
+
+fn generate_files(root: &Path, connector: &Connector<'_>) -> Result<()> {
+    let name = connector.name;
+    let files = [
+        ("mod.rs", format!("{CONNECTOR_DIR}/{name}.rs")),
+        (
+            "transformers.rs",
+            format!("{CONNECTOR_DIR}/{name}/transformers.rs"),
+        ),
+        ("test.rs", format!("{CONNECTOR_TESTS_DIR}/{name}.rs")),
+    ];
+
+    if let Some((_, path)) = files.iter().find(|(_, path)| root.join(path).exists()) {
+        return Err(format!("`{path}` already exists").into());
+    }
+
+    for (template, path) in files {
+        let template = fs::read_to_string(root.join(TEMPLATE_DIR).join(template))?;
+        let path = root.join(path);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, render(&template, name)?)?;
+    }
+
+    Ok(())
+}
+
+/// Render the `{{project-name | <filter> | ...}}` placeholders of a connector template, which
+/// follow the syntax of `cargo-generate`.
+fn render(template: &str, name: &str) -> Result<String> {
+    let mut rendered = String::with_capacity(template.len());
+    let mut rest = template;
+
+    while let Some(start) = rest.find("{{") {
+        let end = rest[start..]
+            .find("}}")
+            .map(|end| start + end)
+            .ok_or("Unterminated placeholder in connector template")?;
+        let mut filters = rest[start + 2..end].split('|').map(str::trim);
+        if filters.next() != Some("project-name") {
+            return Err(format!(
+                "Unknown placeholder `{}` in connector template",
+                &rest[start..end + 2]
+            )
+            .into());
+        }
+
+        let value = filters.try_fold(name.to_owned(), |value, filter| match filter {
+            "downcase" => Ok(value.to_lowercase()),
+            "upcase" => Ok(value.to_uppercase()),
+            "pascal_case" => Ok(pascal_case(&value)),
+            _ => Err(format!("Unknown filter `{filter}` in connector template")),
+        })?;
+
+        rendered.push_str(&rest[..start]);
+        rendered.push_str(&value);
+        rest = &rest[end + 2..];
+    }
+    rendered.push_str(rest);
+
+    Ok(rendered)
+}
+
+fn register_connector_module(content: &str, connector: &Connector<'_>) -> Option<String> {
+    let mut lines = to_lines(content);
+    let range = 0..lines.len();
+    insert_line(
+        &mut lines,
+        range,
+        connector,
+        |line, other| line == format!("pub mod {other};"),
+        format!("pub mod {};", connector.name),
+    )?;
+    let content = from_lines(lines, content);
+
+    // The connectors are re-exported as a single list, which is wrapped the way `rustfmt` does
+    const REEXPORT: &str = "pub use self::{\n";
+    let start = content.find(REEXPORT)? + REEXPORT.len();
+    let end = start + content[start..].find("};")?;
+    let new_item = format!("{}::{}", connector.name, connector.pascal_name);
+    let mut items = content[start..end]
+        .split(',')
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+        .collect::<Vec<_>>();
+    let position = items
+        .iter()
+        .position(|item| *item > new_item.as_str())
+        .unwrap_or(items.len());
+    items.insert(position, &new_item);
+
+    Some(format!(
+        "{}{}\n{}",
+        &content[..start],
+        wrap_items(&items, "    "),
+        &content[end..]
+    ))
+}
+
+fn register_connector_data(content: &str, connector: &Connector<'_>) -> Option<String> {
+    let mut lines = to_lines(content);
+    let range = 0..lines.len();
+    let connector_data = |pascal_name: &str| {
+        format!("enums::Connector::{pascal_name} => Ok(Box::new(&connector::{pascal_name})),")
+    };
+    insert_line(
+        &mut lines,
+        range,
+        connector,
+        |line, other| line == connector_data(&pascal_case(other)),
+        connector_data(&connector.pascal_name),
+    )?;
+    Some(from_lines(lines, content))
+}
+
+fn register_connector_params(content: &str, connector: &Connector<'_>) -> Option<String> {
+    let mut lines = to_lines(content);
+    let range = block(&lines, "pub struct Connectors {")?;
+    insert_line(
+        &mut lines,
+        range,
+        connector,
+        |line, other| line.starts_with(&format!("pub {other}: ")),
+        format!("pub {}: ConnectorParams,", connector.name),
+    )?;
+    Some(from_lines(lines, content))
+}
+
+/// Add the connector to every `default_imp_for_*` invocation, so that it gets the default
+/// implementation of the flows it doesn't support.
+fn register_default_implementations(content: &str, connector: &Connector<'_>) -> Option<String> {
+    let mut lines = to_lines(content);
+    let new_item = format!("connector::{}", connector.pascal_name);
+    let mut registered = false;
+    let mut index = 0;
+
+    while index < lines.len() {
+        let header = lines[index].trim();
+        if !(header.starts_with("default_imp_for_") && header.ends_with("!(")) {
+            index += 1;
+            continue;
+        }
+
+        let start = index + 1;
+        let end = start + lines[start..].iter().position(|line| line.trim() == ");")?;
+        let next_item = (start..end).find(|&i| {
+            let item = lines[i].trim().trim_end_matches(',');
+            item.strip_prefix("connector::")
+                .map_or(false, |item| item.to_lowercase().as_str() > connector.name)
+        });
+
+        match next_item {
+            Some(mut position) => {
+                while position > start && is_attribute(&lines[position - 1]) {
+                    position -= 1;
+                }
+                lines.insert(position, format!("    {new_item},"));
+            }
+            None => {
+                if let Some(last_item) = lines[start..end]
+                    .iter_mut()
+                    .rev()
+                    .find(|line| !line.trim().is_empty() && !is_attribute(line))
+                {
+                    if !last_item.ends_with(',') {
+                        last_item.push(',');
+                    }
+                }
+                lines.insert(end, format!("    {new_item}"));
+            }
+        }
+
+        registered = true;
+        index = end + 2;
+    }
+
+    registered.then(|| from_lines(lines, content))
+}
+
+fn register_connector_enums(content: &str, connector: &Connector<'_>) -> Option<String> {
+    let mut lines = to_lines(content);
+    for enum_header in ["pub enum Connector {", "pub enum RoutableConnectors {"] {
+        let range = block(&lines, enum_header)?;
+        insert_line(
+            &mut lines,
+            range,
+            connector,
+            |line, other| line == format!("{},", pascal_case(other)),
+            format!("{},", connector.pascal_name),
+        )?;
+    }
+    Some(from_lines(lines, content))
+}
+
+fn register_config(content: &str, connector: &Connector<'_>) -> Option<String> {
+    let mut lines = to_lines(content);
+    let range = 0..lines.len();
+    insert_line(
+        &mut lines,
+        range,
+        connector,
+        |line, other| line.starts_with(&format!("{other}.base_url = ")),
+        format!("{}.base_url = \"{}\"", connector.name, connector.base_url),
+    )?;
+    let range = 0..lines.len();
+    insert_line(
+        &mut lines,
+        range,
+        connector,
+        |line, other| line == format!("\"{other}\","),
+        format!("\"{}\",", connector.name),
+    )?;
+    Some(from_lines(lines, content))
+}
+
+fn register_test_module(content: &str, connector: &Connector<'_>) -> Option<String> {
+    let mut lines = to_lines(content);
+    let range = 0..lines.len();
+    insert_line(
+        &mut lines,
+        range,
+        connector,
+        |line, other| line == format!("mod {other};"),
+        format!("mod {};", connector.name),
+    )?;
+    Some(from_lines(lines, content))
+}
+
+fn register_connector_auth(content: &str, connector: &Connector<'_>) -> Option<String> {
+    let mut lines = to_lines(content);
+    let range = block(&lines, "pub struct ConnectorAuthentication {")?;
+    insert_line(
+        &mut lines,
+        range,
+        connector,
+        |line, other| line.starts_with(&format!("pub {other}: ")),
+        format!("pub {}: Option<HeaderKey>,", connector.name),
+    )?;
+    Some(from_lines(lines, content))
+}
+
+fn register_sample_auth(content: &str, connector: &Connector<'_>) -> Option<String> {
+    Some(format!(
+        "{}\n\n[{}]\napi_key=\"API Key\"\n",
+        content.trim_end(),
+        connector.name
+    ))
+}
+
+/// Insert `new_line` right after the line of the closest existing connector sorted before the new
+/// connector, or right before the line of the closest one sorted after it, with the indentation
+/// of that line. Returns `None` when none of the existing connectors has a line in `range`.
+fn insert_line(
+    lines: &mut Vec<String>,
+    range: Range<usize>,
+    connector: &Connector<'_>,
+    is_connector_line: impl Fn(&str, &str) -> bool,
+    new_line: String,
+) -> Option<()> {
+    let find = |other: &str, mut indices: Box<dyn Iterator<Item = usize>>| {
+        indices.find(|&index| is_connector_line(lines[index].trim(), other))
+    };
+
+    let position = connector
+        .previous
+        .iter()
+        .find_map(|other| find(other, Box::new(range.clone().rev())))
+        .map(|index| (index, index + 1))
+        .or_else(|| {
+            connector.next.iter().find_map(|other| {
+                let mut index = find(other, Box::new(range.clone()))?;
+                let anchor = index;
+                // Attributes of the next connector's line must stay attached to it
+                while index > range.start && is_attribute(&lines[index - 1]) {
+                    index -= 1;
+                }
+                Some((anchor, index))
+            })
+        });
+
+    let (anchor, position) = position?;
+    let indentation = lines[anchor]
+        .chars()
+        .take_while(|c| c.is_whitespace())
+        .collect::<String>();
+    lines.insert(position, indentation + &new_line);
+
+    Some(())
+}
+
+/// The range of lines within the block opened by the line `header`
+fn block(lines: &[String], header: &str) -> Option<Range<usize>> {
+    let start = lines.iter().position(|line| line.trim() == header)? + 1;
+    let end = start + lines[start..].iter().position(|line| line == "}")?;
+    Some(start..end)
+}
+
+fn is_attribute(line: &str) -> bool {
+    line.trim_start().starts_with("#[")
+}
+
+/// Wrap comma separated items into lines of at most `MAX_WIDTH` characters
+fn wrap_items(items: &[&str], indentation: &str) -> String {
+    let mut lines = Vec::new();
+    let mut line = String::new();
+
+    for item in items {
+        if line.is_empty() {
+            line = format!("{indentation}{item},");
+        } else if line.len() + item.len() + 2 <= MAX_WIDTH {
+            line = format!("{line} {item},");
+        } else {
+            lines.push(line);
+            line = format!("{indentation}{item},");
+        }
+    }
+    lines.push(line);
+
+    lines.join("\n")
+}
+
+fn to_lines(content: &str) -> Vec<String> {
+    content.lines().map(ToOwned::to_owned).collect()
+}
+
+fn from_lines(lines: Vec<String>, original: &str) -> String {
+    let mut content = lines.join("\n");
+    if original.ends_with('\n') {
+        content.push('\n');
+    }
+    content
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+
+    fn connector(name: &str, previous: &[&str], next: &[&str]) -> Connector<'static> {
+        Connector {
+            name: Box::leak(name.to_owned().into_boxed_str()),
+            pascal_name: pascal_case(name),
+            base_url: "https://sandbox.example.com/",
+            previous: previous.iter().map(|c| c.to_string()).collect(),
+            next: next.iter().map(|c| c.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn test_render_template_placeholders() {
+        let template = "use transformers as {{project-name | downcase}};\n\
+            pub struct {{project-name | downcase | pascal_case}};\n\
+            const NAME: &str = \"{{project-name}}\";";
+
+        assert_eq!(
+            render(template, "new_pay").unwrap(),
+            "use transformers as new_pay;\npub struct NewPay;\nconst NAME: &str = \"new_pay\";"
+        );
+        assert!(render("{{project-name | snake_case}}", "newpay").is_err());
+        assert!(render("{{crate_name}}", "newpay").is_err());
+    }
+
+    #[test]
+    fn test_validate_connector_name() {
+        assert!(validate_name("newpay").is_ok());
+        assert!(validate_name("new_pay2").is_ok());
+        assert!(validate_name("NewPay").is_err());
+        assert!(validate_name("2pay").is_err());
+        assert!(validate_name("").is_err());
+    }
+
+    #[test]
+    fn test_insert_line_keeps_attributes_attached() {
+        let content = "pub struct ConnectorAuthentication {\n    \
+            #[cfg(feature = \"payouts\")]\n    \
+            pub adyen: Option<SignatureKey>,\n    \
+            pub bambora: Option<BodyKey>,\n}\n";
+
+        assert_eq!(
+            register_connector_auth(content, &connector("aaa", &[], &["adyen", "bambora"]))
+                .unwrap(),
+            "pub struct ConnectorAuthentication {\n    \
+            pub aaa: Option<HeaderKey>,\n    \
+            #[cfg(feature = \"payouts\")]\n    \
+            pub adyen: Option<SignatureKey>,\n    \
+            pub bambora: Option<BodyKey>,\n}\n"
+        );
+        assert_eq!(
+            register_connector_auth(content, &connector("adyenb", &["adyen"], &["bambora"]))
+                .unwrap(),
+            "pub struct ConnectorAuthentication {\n    \
+            #[cfg(feature = \"payouts\")]\n    \
+            pub adyen: Option<SignatureKey>,\n    \
+            pub adyenb: Option<HeaderKey>,\n    \
+            pub bambora: Option<BodyKey>,\n}\n"
+        );
+    }
+
+    #[test]
+    fn test_register_default_implementations() {
+        let content = "default_imp_for_payouts!(\n    connector::Aci,\n    connector::Zen\n);\n";
+
+        assert_eq!(
+            register_default_implementations(content, &connector("newpay", &["aci"], &["zen"]))
+                .unwrap(),
+            "default_imp_for_payouts!(\n    connector::Aci,\n    connector::Newpay,\n    connector::Zen\n);\n"
+        );
+        assert_eq!(
+            register_default_implementations(content, &connector("zzz", &["aci", "zen"], &[]))
+                .unwrap(),
+            "default_imp_for_payouts!(\n    connector::Aci,\n    connector::Zen,\n    connector::Zzz\n);\n"
+        );
+    }
+
+    #[test]
+    fn test_wrap_items_like_rustfmt() {
+        let content = fs::read_to_string(
+            Path::new(env!("CARGO_MANIFEST_DIR"))
+                .join("../..")
+                .join(CONNECTOR_MODULE),
+        )
+        .unwrap();
+        let start = content.find("pub use self::{\n").unwrap() + "pub use self::{\n".len();
+        let end = start + content[start..].find("\n};").unwrap();
+        let items = content[start..end]
+            .split(',')
+            .map(str::trim)
+            .filter(|item| !item.is_empty())
+            .collect::<Vec<_>>();
+
+        assert_eq!(wrap_items(&items, "    "), &content[start..end]);
+    }
+}
@@ -0,0 +1,34 @@
+//! Development tasks for the repository, run through the `cargo xtask` alias.
+
+mod add_connector;
+
+use std::{env, path::PathBuf, process::ExitCode};
+
+pub(crate) type Result<T> = std::result::Result<T, Box<dyn std::error::Error>>;
+
+const USAGE: &str = "Usage: cargo xtask add-connector <connector_name> <connector_base_url>";
+
+fn main() -> ExitCode {
+    let args = env::args().skip(1).collect::<Vec<_>>();
+    let args = args.iter().map(String::as_str).collect::<Vec<_>>();
+
+    let result = match args.as_slice() {
+        ["add-connector", name, base_url] => {
+            add_connector::add_connector(&workspace_root(), name, base_url)
+        }
+        _ => Err(USAGE.into()),
+    };
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("{error}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn workspace_root() -> PathBuf {
+    // This crate lives at `crates/xtask` within the workspace
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../..")
+}
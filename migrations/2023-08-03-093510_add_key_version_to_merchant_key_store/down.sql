@@ -0,0 +1,4 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE merchant_key_store
+DROP COLUMN key_version,
+DROP COLUMN rotated_at;
@@ -0,0 +1,4 @@
+-- Your SQL goes here
+ALTER TABLE merchant_key_store
+ADD COLUMN key_version INTEGER NOT NULL DEFAULT 1,
+ADD COLUMN rotated_at TIMESTAMP;
@@ -0,0 +1,5 @@
+DELETE FROM pg_enum
+WHERE enumlabel = 'automatic_delayed'
+AND enumtypid = (
+  SELECT oid FROM pg_type WHERE typname = 'CaptureMethod'
+);
@@ -0,0 +1,2 @@
+-- Your SQL goes here
+ALTER TYPE "CaptureMethod" ADD VALUE IF NOT EXISTS 'automatic_delayed';
@@ -0,0 +1,18 @@
+-- Your SQL goes here
+CREATE TABLE terminal (
+    id SERIAL,
+    terminal_id VARCHAR(64) NOT NULL,
+    merchant_id VARCHAR(64) NOT NULL,
+    merchant_connector_id VARCHAR(128),
+    connector_terminal_id VARCHAR(255),
+    label VARCHAR(255),
+    location VARCHAR(255),
+    serial_number VARCHAR(255),
+    is_active BOOLEAN NOT NULL DEFAULT TRUE,
+    metadata JSONB,
+    created_at TIMESTAMP NOT NULL DEFAULT now()::TIMESTAMP,
+    modified_at TIMESTAMP NOT NULL DEFAULT now()::TIMESTAMP,
+    PRIMARY KEY (terminal_id, merchant_id)
+);
+
+CREATE INDEX terminal_merchant_id_index ON terminal (merchant_id);
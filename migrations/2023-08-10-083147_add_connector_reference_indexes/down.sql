@@ -0,0 +1,6 @@
+-- This file should undo anything in `up.sql`
+DROP INDEX refund_merchant_id_connector_refund_id_index;
+
+DROP INDEX dispute_merchant_id_connector_dispute_id_index;
+
+DROP INDEX payout_attempt_merchant_id_connector_payout_id_index;
@@ -0,0 +1,6 @@
+-- Your SQL goes here
+CREATE INDEX refund_merchant_id_connector_refund_id_index ON refund (merchant_id, connector_refund_id);
+
+CREATE INDEX dispute_merchant_id_connector_dispute_id_index ON dispute (merchant_id, connector_dispute_id);
+
+CREATE INDEX payout_attempt_merchant_id_connector_payout_id_index ON payout_attempt (merchant_id, connector_payout_id);
@@ -0,0 +1,24 @@
+-- This file should undo anything in `up.sql`
+DELETE FROM pg_enum
+WHERE enumlabel IN ('invoice_partially_paid', 'invoice_paid', 'invoice_voided')
+AND enumtypid = (
+  SELECT oid FROM pg_type WHERE typname = 'EventType'
+);
+
+DELETE FROM pg_enum
+WHERE enumlabel = 'invoice_details'
+AND enumtypid = (
+  SELECT oid FROM pg_type WHERE typname = 'EventObjectType'
+);
+
+DELETE FROM pg_enum
+WHERE enumlabel = 'invoices'
+AND enumtypid = (
+  SELECT oid FROM pg_type WHERE typname = 'EventClass'
+);
+
+ALTER TABLE payment_intent DROP COLUMN invoice_id;
+
+DROP TABLE invoice;
+
+DROP TYPE "InvoiceStatus";
@@ -0,0 +1,33 @@
+-- Your SQL goes here
+CREATE TYPE "InvoiceStatus" AS ENUM ('open', 'partially_paid', 'paid', 'void');
+
+CREATE TABLE invoice (
+    id SERIAL,
+    invoice_id VARCHAR(64) NOT NULL,
+    merchant_id VARCHAR(64) NOT NULL,
+    customer_id VARCHAR(64),
+    status "InvoiceStatus" NOT NULL DEFAULT 'open',
+    currency "Currency" NOT NULL,
+    amount BIGINT NOT NULL,
+    amount_paid BIGINT NOT NULL DEFAULT 0,
+    line_items JSONB NOT NULL,
+    description VARCHAR(255),
+    due_date TIMESTAMP,
+    client_secret VARCHAR(128) NOT NULL,
+    metadata JSONB,
+    created_at TIMESTAMP NOT NULL DEFAULT now()::TIMESTAMP,
+    modified_at TIMESTAMP NOT NULL DEFAULT now()::TIMESTAMP,
+    PRIMARY KEY (invoice_id, merchant_id)
+);
+
+CREATE INDEX invoice_merchant_id_index ON invoice (merchant_id);
+
+ALTER TABLE payment_intent ADD COLUMN invoice_id VARCHAR(64);
+
+ALTER TYPE "EventClass" ADD VALUE IF NOT EXISTS 'invoices';
+
+ALTER TYPE "EventObjectType" ADD VALUE IF NOT EXISTS 'invoice_details';
+
+ALTER TYPE "EventType" ADD VALUE IF NOT EXISTS 'invoice_partially_paid';
+ALTER TYPE "EventType" ADD VALUE IF NOT EXISTS 'invoice_paid';
+ALTER TYPE "EventType" ADD VALUE IF NOT EXISTS 'invoice_voided';
@@ -0,0 +1,4 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE payment_intent DROP COLUMN IF EXISTS convenience_fee;
+
+ALTER TABLE refund DROP COLUMN IF EXISTS convenience_fee_amount;
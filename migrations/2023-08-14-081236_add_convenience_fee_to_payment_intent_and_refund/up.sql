@@ -0,0 +1,4 @@
+-- Your SQL goes here
+ALTER TABLE payment_intent ADD COLUMN IF NOT EXISTS convenience_fee JSONB;
+
+ALTER TABLE refund ADD COLUMN IF NOT EXISTS convenience_fee_amount BIGINT;
@@ -0,0 +1,17 @@
+-- Your SQL goes here
+CREATE TABLE commission (
+    id SERIAL,
+    commission_id VARCHAR(64) NOT NULL PRIMARY KEY,
+    merchant_id VARCHAR(64) NOT NULL,
+    parent_merchant_id VARCHAR(64),
+    payment_id VARCHAR(64) NOT NULL,
+    pricing_plan VARCHAR(64) NOT NULL,
+    currency "Currency" NOT NULL,
+    payment_amount BIGINT NOT NULL,
+    commission_amount BIGINT NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT now()::TIMESTAMP
+);
+
+CREATE UNIQUE INDEX commission_merchant_id_payment_id_index ON commission (merchant_id, payment_id);
+
+CREATE INDEX commission_merchant_id_created_at_index ON commission (merchant_id, created_at);
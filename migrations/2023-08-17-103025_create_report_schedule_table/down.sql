@@ -0,0 +1,20 @@
+-- This file should undo anything in `up.sql`
+DELETE FROM pg_enum
+WHERE enumlabel = 'report_generated'
+AND enumtypid = (
+  SELECT oid FROM pg_type WHERE typname = 'EventType'
+);
+
+DELETE FROM pg_enum
+WHERE enumlabel = 'report_details'
+AND enumtypid = (
+  SELECT oid FROM pg_type WHERE typname = 'EventObjectType'
+);
+
+DELETE FROM pg_enum
+WHERE enumlabel = 'reports'
+AND enumtypid = (
+  SELECT oid FROM pg_type WHERE typname = 'EventClass'
+);
+
+DROP TABLE report_schedule;
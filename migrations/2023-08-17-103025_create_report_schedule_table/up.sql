@@ -0,0 +1,27 @@
+-- Your SQL goes here
+CREATE TABLE report_schedule (
+    id SERIAL,
+    report_schedule_id VARCHAR(64) NOT NULL,
+    merchant_id VARCHAR(64) NOT NULL,
+    report_type VARCHAR(64) NOT NULL,
+    frequency VARCHAR(64) NOT NULL,
+    template JSONB NOT NULL,
+    filters JSONB,
+    delivery_method VARCHAR(64) NOT NULL,
+    recipient_email VARCHAR(255),
+    is_active BOOLEAN NOT NULL DEFAULT TRUE,
+    next_run_at TIMESTAMP NOT NULL,
+    last_generated_at TIMESTAMP,
+    last_file_id VARCHAR(64),
+    created_at TIMESTAMP NOT NULL DEFAULT now()::TIMESTAMP,
+    modified_at TIMESTAMP NOT NULL DEFAULT now()::TIMESTAMP,
+    PRIMARY KEY (report_schedule_id, merchant_id)
+);
+
+CREATE INDEX report_schedule_merchant_id_index ON report_schedule (merchant_id);
+
+ALTER TYPE "EventClass" ADD VALUE IF NOT EXISTS 'reports';
+
+ALTER TYPE "EventObjectType" ADD VALUE IF NOT EXISTS 'report_details';
+
+ALTER TYPE "EventType" ADD VALUE IF NOT EXISTS 'report_generated';
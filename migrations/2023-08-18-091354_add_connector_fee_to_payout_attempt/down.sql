@@ -0,0 +1,4 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE payout_attempt
+DROP COLUMN IF EXISTS connector_fee,
+DROP COLUMN IF EXISTS fx_margin;
@@ -0,0 +1,4 @@
+-- Your SQL goes here
+ALTER TABLE payout_attempt
+ADD COLUMN IF NOT EXISTS connector_fee BIGINT,
+ADD COLUMN IF NOT EXISTS fx_margin BIGINT;
@@ -0,0 +1,4 @@
+-- This file should undo anything in `up.sql`
+DROP TABLE IF EXISTS queued_webhook;
+
+DROP TABLE IF EXISTS webhook_endpoint_health;
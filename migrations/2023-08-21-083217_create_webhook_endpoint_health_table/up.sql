@@ -0,0 +1,22 @@
+-- Your SQL goes here
+CREATE TABLE webhook_endpoint_health (
+    merchant_id VARCHAR(64) NOT NULL PRIMARY KEY,
+    status VARCHAR(64) NOT NULL,
+    delivery_count INTEGER NOT NULL DEFAULT 0,
+    failure_count INTEGER NOT NULL DEFAULT 0,
+    degraded_at TIMESTAMP,
+    last_verified_at TIMESTAMP,
+    created_at TIMESTAMP NOT NULL DEFAULT now()::TIMESTAMP,
+    modified_at TIMESTAMP NOT NULL DEFAULT now()::TIMESTAMP
+);
+
+CREATE TABLE queued_webhook (
+    id SERIAL PRIMARY KEY,
+    event_id VARCHAR(64) NOT NULL,
+    merchant_id VARCHAR(64) NOT NULL,
+    request_body BYTEA NOT NULL,
+    request_headers JSONB NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT now()::TIMESTAMP
+);
+
+CREATE INDEX queued_webhook_merchant_id_index ON queued_webhook (merchant_id, created_at);
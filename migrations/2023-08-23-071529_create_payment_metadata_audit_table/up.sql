@@ -0,0 +1,11 @@
+-- Your SQL goes here
+CREATE TABLE payment_metadata_audit (
+    id SERIAL PRIMARY KEY,
+    payment_id VARCHAR(64) NOT NULL,
+    merchant_id VARCHAR(64) NOT NULL,
+    previous_metadata JSONB,
+    updated_metadata JSONB NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT now()::TIMESTAMP
+);
+
+CREATE INDEX payment_metadata_audit_merchant_id_payment_id_index ON payment_metadata_audit (merchant_id, payment_id);
@@ -0,0 +1,8 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE customers
+DROP COLUMN IF EXISTS default_shipping_address_id,
+DROP COLUMN IF EXISTS default_billing_address_id;
+
+DROP INDEX IF EXISTS address_merchant_id_customer_id_index;
+
+ALTER TABLE address DROP COLUMN IF EXISTS is_address_book_entry;
@@ -0,0 +1,9 @@
+-- Your SQL goes here
+ALTER TABLE address
+ADD COLUMN IF NOT EXISTS is_address_book_entry BOOLEAN NOT NULL DEFAULT FALSE;
+
+CREATE INDEX IF NOT EXISTS address_merchant_id_customer_id_index ON address (merchant_id, customer_id);
+
+ALTER TABLE customers
+ADD COLUMN IF NOT EXISTS default_shipping_address_id VARCHAR(64),
+ADD COLUMN IF NOT EXISTS default_billing_address_id VARCHAR(64);
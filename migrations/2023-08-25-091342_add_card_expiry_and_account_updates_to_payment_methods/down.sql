@@ -0,0 +1,27 @@
+-- This file should undo anything in `up.sql`
+DELETE FROM pg_enum
+WHERE enumlabel IN ('payment_method_expiring', 'payment_method_updated')
+AND enumtypid = (
+  SELECT oid FROM pg_type WHERE typname = 'EventType'
+);
+
+DELETE FROM pg_enum
+WHERE enumlabel = 'payment_method_details'
+AND enumtypid = (
+  SELECT oid FROM pg_type WHERE typname = 'EventObjectType'
+);
+
+DELETE FROM pg_enum
+WHERE enumlabel = 'payment_methods'
+AND enumtypid = (
+  SELECT oid FROM pg_type WHERE typname = 'EventClass'
+);
+
+ALTER TABLE payment_methods
+DROP COLUMN card_exp_month,
+DROP COLUMN card_exp_year,
+DROP COLUMN expiry_notified_at,
+DROP COLUMN locker_id,
+DROP COLUMN account_update_type,
+DROP COLUMN account_update_source,
+DROP COLUMN account_updated_at;
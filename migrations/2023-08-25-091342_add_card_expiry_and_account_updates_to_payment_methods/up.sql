@@ -0,0 +1,17 @@
+-- Your SQL goes here
+ALTER TABLE payment_methods
+ADD COLUMN card_exp_month VARCHAR(2),
+ADD COLUMN card_exp_year VARCHAR(4),
+ADD COLUMN expiry_notified_at TIMESTAMP,
+ADD COLUMN locker_id VARCHAR(64),
+ADD COLUMN account_update_type VARCHAR(64),
+ADD COLUMN account_update_source VARCHAR(64),
+ADD COLUMN account_updated_at TIMESTAMP;
+
+ALTER TYPE "EventClass" ADD VALUE IF NOT EXISTS 'payment_methods';
+
+ALTER TYPE "EventObjectType" ADD VALUE IF NOT EXISTS 'payment_method_details';
+
+ALTER TYPE "EventType" ADD VALUE IF NOT EXISTS 'payment_method_expiring';
+
+ALTER TYPE "EventType" ADD VALUE IF NOT EXISTS 'payment_method_updated';
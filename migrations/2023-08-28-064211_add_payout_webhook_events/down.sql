@@ -0,0 +1,24 @@
+-- This file should undo anything in `up.sql`
+DELETE FROM pg_enum
+WHERE enumlabel IN ('payout_processing', 'payout_succeeded', 'payout_failed', 'payout_reversed')
+AND enumtypid = (
+  SELECT oid FROM pg_type WHERE typname = 'EventType'
+);
+
+DELETE FROM pg_enum
+WHERE enumlabel = 'payout_details'
+AND enumtypid = (
+  SELECT oid FROM pg_type WHERE typname = 'EventObjectType'
+);
+
+DELETE FROM pg_enum
+WHERE enumlabel = 'payouts'
+AND enumtypid = (
+  SELECT oid FROM pg_type WHERE typname = 'EventClass'
+);
+
+DELETE FROM pg_enum
+WHERE enumlabel = 'reversed'
+AND enumtypid = (
+  SELECT oid FROM pg_type WHERE typname = 'PayoutStatus'
+);
@@ -0,0 +1,14 @@
+-- Your SQL goes here
+ALTER TYPE "PayoutStatus" ADD VALUE IF NOT EXISTS 'reversed';
+
+ALTER TYPE "EventClass" ADD VALUE IF NOT EXISTS 'payouts';
+
+ALTER TYPE "EventObjectType" ADD VALUE IF NOT EXISTS 'payout_details';
+
+ALTER TYPE "EventType" ADD VALUE IF NOT EXISTS 'payout_processing';
+
+ALTER TYPE "EventType" ADD VALUE IF NOT EXISTS 'payout_succeeded';
+
+ALTER TYPE "EventType" ADD VALUE IF NOT EXISTS 'payout_failed';
+
+ALTER TYPE "EventType" ADD VALUE IF NOT EXISTS 'payout_reversed';
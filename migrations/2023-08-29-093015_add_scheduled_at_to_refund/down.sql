@@ -0,0 +1,8 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE refund DROP COLUMN scheduled_at;
+
+DELETE FROM pg_enum
+WHERE enumlabel = 'cancelled'
+AND enumtypid = (
+  SELECT oid FROM pg_type WHERE typname = 'RefundStatus'
+);
@@ -0,0 +1,4 @@
+-- Your SQL goes here
+ALTER TABLE refund ADD COLUMN scheduled_at TIMESTAMP;
+
+ALTER TYPE "RefundStatus" ADD VALUE IF NOT EXISTS 'cancelled';
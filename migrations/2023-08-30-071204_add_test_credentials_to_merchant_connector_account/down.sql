@@ -0,0 +1,4 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE merchant_connector_account DROP COLUMN IF EXISTS test_connector_account_details;
+
+ALTER TABLE payment_intent DROP COLUMN IF EXISTS livemode;
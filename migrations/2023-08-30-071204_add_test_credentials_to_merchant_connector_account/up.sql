@@ -0,0 +1,4 @@
+-- Your SQL goes here
+ALTER TABLE merchant_connector_account ADD COLUMN IF NOT EXISTS test_connector_account_details BYTEA;
+
+ALTER TABLE payment_intent ADD COLUMN IF NOT EXISTS livemode BOOLEAN;
@@ -0,0 +1,18 @@
+-- Your SQL goes here
+CREATE TABLE ledger_entry (
+    id SERIAL,
+    entry_id VARCHAR(64) NOT NULL PRIMARY KEY,
+    merchant_id VARCHAR(64) NOT NULL,
+    entry_type VARCHAR(32) NOT NULL,
+    reference_id VARCHAR(64) NOT NULL,
+    debit_account VARCHAR(32) NOT NULL,
+    credit_account VARCHAR(32) NOT NULL,
+    amount BIGINT NOT NULL,
+    currency "Currency" NOT NULL,
+    connector VARCHAR(64),
+    created_at TIMESTAMP NOT NULL DEFAULT now()::TIMESTAMP
+);
+
+CREATE UNIQUE INDEX ledger_entry_merchant_id_reference_id_entry_type_index ON ledger_entry (merchant_id, reference_id, entry_type);
+
+CREATE INDEX ledger_entry_merchant_id_created_at_index ON ledger_entry (merchant_id, created_at);
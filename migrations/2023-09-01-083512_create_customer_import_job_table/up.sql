@@ -0,0 +1,17 @@
+-- Your SQL goes here
+CREATE TABLE customer_import_job (
+    id SERIAL,
+    job_id VARCHAR(64) NOT NULL,
+    merchant_id VARCHAR(64) NOT NULL,
+    status VARCHAR(64) NOT NULL,
+    records BYTEA NOT NULL,
+    total_count INTEGER NOT NULL,
+    processed_count INTEGER NOT NULL DEFAULT 0,
+    succeeded_count INTEGER NOT NULL DEFAULT 0,
+    failed_count INTEGER NOT NULL DEFAULT 0,
+    errors JSONB,
+    created_at TIMESTAMP NOT NULL DEFAULT now()::TIMESTAMP,
+    modified_at TIMESTAMP NOT NULL DEFAULT now()::TIMESTAMP,
+    completed_at TIMESTAMP,
+    PRIMARY KEY (job_id, merchant_id)
+);
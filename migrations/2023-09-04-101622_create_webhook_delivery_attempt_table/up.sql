@@ -0,0 +1,16 @@
+-- Your SQL goes here
+CREATE TABLE webhook_delivery_attempt (
+    id SERIAL PRIMARY KEY,
+    event_id VARCHAR(64) NOT NULL,
+    merchant_id VARCHAR(64) NOT NULL,
+    attempt_number INTEGER NOT NULL,
+    is_delivered BOOLEAN NOT NULL,
+    response_status_code INTEGER,
+    error_message TEXT,
+    request_body BYTEA,
+    request_headers JSONB,
+    next_retry_at TIMESTAMP,
+    created_at TIMESTAMP NOT NULL DEFAULT now()::TIMESTAMP
+);
+
+CREATE INDEX webhook_delivery_attempt_merchant_id_event_id_index ON webhook_delivery_attempt (merchant_id, event_id);
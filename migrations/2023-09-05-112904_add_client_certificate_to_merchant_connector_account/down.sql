@@ -0,0 +1,5 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE merchant_connector_account
+DROP COLUMN IF EXISTS client_certificate,
+DROP COLUMN IF EXISTS client_certificate_key,
+DROP COLUMN IF EXISTS client_certificate_expires_at;
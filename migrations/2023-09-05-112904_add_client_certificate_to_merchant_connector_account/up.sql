@@ -0,0 +1,5 @@
+-- Your SQL goes here
+ALTER TABLE merchant_connector_account
+ADD COLUMN IF NOT EXISTS client_certificate BYTEA DEFAULT NULL,
+ADD COLUMN IF NOT EXISTS client_certificate_key BYTEA DEFAULT NULL,
+ADD COLUMN IF NOT EXISTS client_certificate_expires_at TIMESTAMP DEFAULT NULL;
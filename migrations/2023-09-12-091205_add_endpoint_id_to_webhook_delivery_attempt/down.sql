@@ -0,0 +1,2 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE webhook_delivery_attempt DROP COLUMN IF EXISTS endpoint_id;
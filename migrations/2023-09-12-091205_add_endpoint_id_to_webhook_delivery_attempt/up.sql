@@ -0,0 +1,2 @@
+-- Your SQL goes here
+ALTER TABLE webhook_delivery_attempt ADD COLUMN IF NOT EXISTS endpoint_id VARCHAR(64);
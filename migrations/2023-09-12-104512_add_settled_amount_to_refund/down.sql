@@ -0,0 +1,14 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE refund DROP COLUMN IF EXISTS settled_amount;
+
+DELETE FROM pg_enum
+WHERE enumlabel = 'amount_mismatch'
+AND enumtypid = (
+  SELECT oid FROM pg_type WHERE typname = 'RefundStatus'
+);
+
+DELETE FROM pg_enum
+WHERE enumlabel = 'refund_amount_mismatch'
+AND enumtypid = (
+  SELECT oid FROM pg_type WHERE typname = 'EventType'
+);
@@ -0,0 +1,6 @@
+-- Your SQL goes here
+ALTER TABLE refund ADD COLUMN IF NOT EXISTS settled_amount BIGINT;
+
+ALTER TYPE "RefundStatus" ADD VALUE IF NOT EXISTS 'amount_mismatch';
+
+ALTER TYPE "EventType" ADD VALUE IF NOT EXISTS 'refund_amount_mismatch';
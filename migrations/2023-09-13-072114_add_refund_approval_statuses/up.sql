@@ -0,0 +1,4 @@
+-- Your SQL goes here
+ALTER TYPE "RefundStatus" ADD VALUE IF NOT EXISTS 'pending_approval';
+
+ALTER TYPE "RefundStatus" ADD VALUE IF NOT EXISTS 'rejected';
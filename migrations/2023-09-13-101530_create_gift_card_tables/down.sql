@@ -0,0 +1,8 @@
+-- This file should undo anything in `up.sql`
+DROP TABLE gift_card_transaction;
+
+DROP TABLE gift_card;
+
+DROP TYPE "GiftCardTransactionType";
+
+DROP TYPE "GiftCardStatus";
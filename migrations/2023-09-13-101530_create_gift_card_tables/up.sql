@@ -0,0 +1,37 @@
+-- Your SQL goes here
+CREATE TYPE "GiftCardStatus" AS ENUM ('active', 'void');
+
+CREATE TYPE "GiftCardTransactionType" AS ENUM ('issue', 'redeem', 'reload', 'void');
+
+CREATE TABLE gift_card (
+    id SERIAL,
+    gift_card_id VARCHAR(64) NOT NULL,
+    merchant_id VARCHAR(64) NOT NULL,
+    customer_id VARCHAR(64),
+    status "GiftCardStatus" NOT NULL DEFAULT 'active',
+    currency "Currency" NOT NULL,
+    initial_balance BIGINT NOT NULL,
+    balance BIGINT NOT NULL,
+    expires_at TIMESTAMP,
+    metadata JSONB,
+    created_at TIMESTAMP NOT NULL DEFAULT now()::TIMESTAMP,
+    modified_at TIMESTAMP NOT NULL DEFAULT now()::TIMESTAMP,
+    PRIMARY KEY (gift_card_id, merchant_id)
+);
+
+CREATE INDEX gift_card_merchant_id_index ON gift_card (merchant_id);
+
+CREATE TABLE gift_card_transaction (
+    id SERIAL,
+    transaction_id VARCHAR(64) NOT NULL PRIMARY KEY,
+    gift_card_id VARCHAR(64) NOT NULL,
+    merchant_id VARCHAR(64) NOT NULL,
+    transaction_type "GiftCardTransactionType" NOT NULL,
+    amount BIGINT NOT NULL,
+    balance_after BIGINT NOT NULL,
+    currency "Currency" NOT NULL,
+    payment_id VARCHAR(64),
+    created_at TIMESTAMP NOT NULL DEFAULT now()::TIMESTAMP
+);
+
+CREATE INDEX gift_card_transaction_merchant_id_gift_card_id_index ON gift_card_transaction (merchant_id, gift_card_id);
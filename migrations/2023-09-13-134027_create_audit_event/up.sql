@@ -0,0 +1,25 @@
+-- Your SQL goes here
+CREATE TYPE "AuditEntityType" AS ENUM (
+    'merchant_account',
+    'merchant_connector_account',
+    'routing_config',
+    'api_key',
+    'webhook_settings'
+);
+
+CREATE TYPE "AuditAction" AS ENUM ('create', 'update', 'delete');
+
+CREATE TABLE audit_event (
+    id SERIAL,
+    event_id VARCHAR(64) NOT NULL PRIMARY KEY,
+    merchant_id VARCHAR(64) NOT NULL,
+    entity_type "AuditEntityType" NOT NULL,
+    entity_id VARCHAR(64) NOT NULL,
+    action "AuditAction" NOT NULL,
+    actor VARCHAR(64) NOT NULL,
+    previous_values JSONB,
+    updated_values JSONB,
+    created_at TIMESTAMP NOT NULL DEFAULT now()::TIMESTAMP
+);
+
+CREATE INDEX audit_event_merchant_id_created_at_index ON audit_event (merchant_id, created_at);
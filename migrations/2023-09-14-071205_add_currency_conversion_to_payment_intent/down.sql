@@ -0,0 +1,7 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE payment_intent
+DROP COLUMN IF EXISTS presentment_amount,
+DROP COLUMN IF EXISTS presentment_currency,
+DROP COLUMN IF EXISTS settlement_amount,
+DROP COLUMN IF EXISTS settlement_currency,
+DROP COLUMN IF EXISTS exchange_rate;
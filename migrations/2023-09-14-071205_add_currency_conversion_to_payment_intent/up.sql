@@ -0,0 +1,7 @@
+-- Your SQL goes here
+ALTER TABLE payment_intent
+ADD COLUMN IF NOT EXISTS presentment_amount BIGINT,
+ADD COLUMN IF NOT EXISTS presentment_currency "Currency",
+ADD COLUMN IF NOT EXISTS settlement_amount BIGINT,
+ADD COLUMN IF NOT EXISTS settlement_currency "Currency",
+ADD COLUMN IF NOT EXISTS exchange_rate VARCHAR(32);
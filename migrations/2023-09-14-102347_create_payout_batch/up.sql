@@ -0,0 +1,9 @@
+-- Your SQL goes here
+CREATE TABLE payout_batch (
+    id SERIAL,
+    batch_id VARCHAR(64) NOT NULL,
+    merchant_id VARCHAR(64) NOT NULL,
+    payout_ids TEXT[] NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT now()::TIMESTAMP,
+    PRIMARY KEY (batch_id, merchant_id)
+);
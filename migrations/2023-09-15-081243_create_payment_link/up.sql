@@ -0,0 +1,18 @@
+-- Your SQL goes here
+CREATE TYPE "PaymentLinkStatus" AS ENUM ('active', 'expired');
+
+CREATE TABLE payment_link (
+    id SERIAL,
+    payment_link_id VARCHAR(64) NOT NULL,
+    merchant_id VARCHAR(64) NOT NULL,
+    payment_id VARCHAR(64) NOT NULL,
+    status "PaymentLinkStatus" NOT NULL DEFAULT 'active',
+    theme JSONB,
+    allowed_payment_methods JSONB,
+    expires_at TIMESTAMP NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT now()::TIMESTAMP,
+    modified_at TIMESTAMP NOT NULL DEFAULT now()::TIMESTAMP,
+    PRIMARY KEY (payment_link_id, merchant_id)
+);
+
+CREATE INDEX payment_link_merchant_id_payment_id_index ON payment_link (merchant_id, payment_id);
@@ -0,0 +1,4 @@
+-- This file should undo anything in `up.sql`
+DROP TABLE payment_method_share;
+
+DROP TYPE "PaymentMethodShareStatus";
@@ -0,0 +1,25 @@
+-- Your SQL goes here
+CREATE TYPE "PaymentMethodShareStatus" AS ENUM ('active', 'revoked');
+
+CREATE TABLE payment_method_share (
+    id SERIAL,
+    share_id VARCHAR(64) NOT NULL PRIMARY KEY,
+    payment_method_id VARCHAR(64) NOT NULL,
+    merchant_id VARCHAR(64) NOT NULL,
+    shared_with_merchant_id VARCHAR(64) NOT NULL,
+    platform_merchant_id VARCHAR(64) NOT NULL,
+    status "PaymentMethodShareStatus" NOT NULL DEFAULT 'active',
+    consent_reference VARCHAR(255) NOT NULL,
+    consented_at TIMESTAMP NOT NULL,
+    consent_ip_address VARCHAR(64),
+    max_uses INTEGER,
+    use_count INTEGER NOT NULL DEFAULT 0,
+    max_amount BIGINT,
+    currency "Currency",
+    expires_at TIMESTAMP,
+    revoked_at TIMESTAMP,
+    created_at TIMESTAMP NOT NULL DEFAULT now()::TIMESTAMP,
+    modified_at TIMESTAMP NOT NULL DEFAULT now()::TIMESTAMP
+);
+
+CREATE INDEX payment_method_share_merchant_id_payment_method_id_index ON payment_method_share (merchant_id, payment_method_id);
@@ -0,0 +1,9 @@
+-- This file should undo anything in `up.sql`
+DROP INDEX IF EXISTS payment_intent_merchant_id_customer_id_index;
+
+DROP INDEX IF EXISTS payment_intent_merchant_id_created_at_id_index;
+
+DROP INDEX IF EXISTS customers_merchant_id_email_hash_index;
+
+ALTER TABLE customers
+DROP COLUMN IF EXISTS email_hash;
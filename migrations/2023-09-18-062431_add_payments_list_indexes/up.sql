@@ -0,0 +1,9 @@
+-- Your SQL goes here
+ALTER TABLE customers
+ADD COLUMN IF NOT EXISTS email_hash VARCHAR(64);
+
+CREATE INDEX IF NOT EXISTS customers_merchant_id_email_hash_index ON customers (merchant_id, email_hash);
+
+CREATE INDEX IF NOT EXISTS payment_intent_merchant_id_created_at_id_index ON payment_intent (merchant_id, created_at DESC, id DESC);
+
+CREATE INDEX IF NOT EXISTS payment_intent_merchant_id_customer_id_index ON payment_intent (merchant_id, customer_id);
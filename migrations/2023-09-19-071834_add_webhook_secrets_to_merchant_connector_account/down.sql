@@ -0,0 +1,4 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE merchant_connector_account
+DROP COLUMN IF EXISTS webhook_secret,
+DROP COLUMN IF EXISTS additional_webhook_secret;
@@ -0,0 +1,4 @@
+-- Your SQL goes here
+ALTER TABLE merchant_connector_account
+ADD COLUMN IF NOT EXISTS webhook_secret BYTEA DEFAULT NULL,
+ADD COLUMN IF NOT EXISTS additional_webhook_secret BYTEA DEFAULT NULL;
@@ -0,0 +1,12 @@
+-- This file should undo anything in `up.sql`
+DELETE FROM pg_enum
+WHERE enumlabel = 'refund_manual_review'
+AND enumtypid = (
+  SELECT oid FROM pg_type WHERE typname = 'EventType'
+);
+
+DELETE FROM pg_enum
+WHERE enumlabel = 'refund'
+AND enumtypid = (
+  SELECT oid FROM pg_type WHERE typname = 'AuditEntityType'
+);
@@ -0,0 +1,4 @@
+-- Your SQL goes here
+ALTER TYPE "EventType" ADD VALUE IF NOT EXISTS 'refund_manual_review';
+
+ALTER TYPE "AuditEntityType" ADD VALUE IF NOT EXISTS 'refund';
@@ -0,0 +1,25 @@
+-- Your SQL goes here
+CREATE TABLE subscription (
+    id SERIAL,
+    subscription_id VARCHAR(64) NOT NULL,
+    merchant_id VARCHAR(64) NOT NULL,
+    customer_id VARCHAR(64) NOT NULL,
+    mandate_id VARCHAR(64) NOT NULL,
+    status VARCHAR(64) NOT NULL,
+    amount BIGINT NOT NULL,
+    currency "Currency" NOT NULL,
+    billing_interval VARCHAR(64) NOT NULL,
+    billing_interval_count INTEGER NOT NULL,
+    description VARCHAR(255),
+    billing_anchor TIMESTAMP NOT NULL,
+    billing_cycle INTEGER NOT NULL DEFAULT 0,
+    next_billing_at TIMESTAMP NOT NULL,
+    failed_attempt_count INTEGER NOT NULL DEFAULT 0,
+    last_payment_id VARCHAR(64),
+    cancelled_at TIMESTAMP,
+    created_at TIMESTAMP NOT NULL DEFAULT now()::TIMESTAMP,
+    modified_at TIMESTAMP NOT NULL DEFAULT now()::TIMESTAMP,
+    PRIMARY KEY (subscription_id, merchant_id)
+);
+
+CREATE INDEX subscription_merchant_id_customer_id_index ON subscription (merchant_id, customer_id);
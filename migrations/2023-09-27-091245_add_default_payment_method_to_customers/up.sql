@@ -0,0 +1,6 @@
+-- Your SQL goes here
+ALTER TABLE customers
+ADD COLUMN IF NOT EXISTS default_payment_method_id VARCHAR(64);
+
+ALTER TABLE payment_methods
+ADD COLUMN IF NOT EXISTS last_used_at TIMESTAMP;
@@ -0,0 +1,8 @@
+-- This file should undo anything in `up.sql`
+DROP INDEX IF EXISTS ledger_entry_merchant_id_reference_id_index;
+
+DROP INDEX IF EXISTS ledger_entry_merchant_id_entry_type_idempotency_key_index;
+
+CREATE UNIQUE INDEX ledger_entry_merchant_id_reference_id_entry_type_index ON ledger_entry (merchant_id, reference_id, entry_type);
+
+ALTER TABLE ledger_entry DROP COLUMN IF EXISTS idempotency_key;
@@ -0,0 +1,12 @@
+-- Your SQL goes here
+ALTER TABLE ledger_entry ADD COLUMN IF NOT EXISTS idempotency_key VARCHAR(128);
+
+UPDATE ledger_entry SET idempotency_key = reference_id WHERE idempotency_key IS NULL;
+
+ALTER TABLE ledger_entry ALTER COLUMN idempotency_key SET NOT NULL;
+
+DROP INDEX IF EXISTS ledger_entry_merchant_id_reference_id_entry_type_index;
+
+CREATE UNIQUE INDEX ledger_entry_merchant_id_entry_type_idempotency_key_index ON ledger_entry (merchant_id, entry_type, idempotency_key);
+
+CREATE INDEX ledger_entry_merchant_id_reference_id_index ON ledger_entry (merchant_id, reference_id);
@@ -0,0 +1,11 @@
+-- This file should undo anything in `up.sql`
+DELETE FROM process_tracker
+WHERE id = 'PAYMENT_METHOD_EXPIRY_BACKFILL_WORKFLOW_PAYMENT_METHOD_EXPIRY_BACKFILL';
+
+ALTER TABLE payment_methods
+DROP COLUMN card_exp_month,
+DROP COLUMN card_exp_year;
+
+ALTER TABLE payment_methods
+ADD COLUMN card_exp_month VARCHAR(2),
+ADD COLUMN card_exp_year VARCHAR(4);
@@ -0,0 +1,50 @@
+-- Your SQL goes here
+-- The expiry of saved cards is stored encrypted with the key of the merchant. The expiry of the
+-- cards saved before is fetched again from the locker by the PAYMENT_METHOD_EXPIRY_BACKFILL task.
+ALTER TABLE payment_methods
+DROP COLUMN card_exp_month,
+DROP COLUMN card_exp_year;
+
+ALTER TABLE payment_methods
+ADD COLUMN card_exp_month BYTEA,
+ADD COLUMN card_exp_year BYTEA;
+
+-- The pending expiry tasks are scheduled again by the backfill
+UPDATE process_tracker
+SET status = 'finish',
+    business_status = 'RESCHEDULED_BY_EXPIRY_BACKFILL',
+    updated_at = now()
+WHERE name = 'PAYMENT_METHOD_EXPIRY'
+AND status IN ('new', 'pending');
+
+INSERT INTO process_tracker (
+    id,
+    name,
+    tag,
+    runner,
+    retry_count,
+    schedule_time,
+    rule,
+    tracking_data,
+    business_status,
+    status,
+    event,
+    created_at,
+    updated_at
+)
+VALUES (
+    'PAYMENT_METHOD_EXPIRY_BACKFILL_WORKFLOW_PAYMENT_METHOD_EXPIRY_BACKFILL',
+    'PAYMENT_METHOD_EXPIRY_BACKFILL',
+    '{SYNC,PAYMENT}',
+    'PAYMENT_METHOD_EXPIRY_BACKFILL_WORKFLOW',
+    0,
+    now(),
+    '',
+    '{"last_id": 0}',
+    'Pending',
+    'new',
+    '{}',
+    now(),
+    now()
+)
+ON CONFLICT (id) DO NOTHING;
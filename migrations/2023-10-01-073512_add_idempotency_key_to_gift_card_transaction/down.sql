@@ -0,0 +1,10 @@
+-- This file should undo anything in `up.sql`
+DROP INDEX IF EXISTS gift_card_transaction_merchant_id_gift_card_id_idempotency_key_index;
+
+ALTER TABLE gift_card_transaction DROP COLUMN IF EXISTS idempotency_key;
+
+DELETE FROM pg_enum
+WHERE enumlabel = 'redeem_reversal'
+AND enumtypid = (
+  SELECT oid FROM pg_type WHERE typname = 'GiftCardTransactionType'
+);
@@ -0,0 +1,6 @@
+-- Your SQL goes here
+ALTER TABLE gift_card_transaction ADD COLUMN IF NOT EXISTS idempotency_key VARCHAR(128);
+
+CREATE UNIQUE INDEX IF NOT EXISTS gift_card_transaction_merchant_id_gift_card_id_idempotency_key_index ON gift_card_transaction (merchant_id, gift_card_id, idempotency_key);
+
+ALTER TYPE "GiftCardTransactionType" ADD VALUE IF NOT EXISTS 'redeem_reversal';
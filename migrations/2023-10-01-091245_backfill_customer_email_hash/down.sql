@@ -0,0 +1,3 @@
+-- This file should undo anything in `up.sql`
+DELETE FROM process_tracker
+WHERE id = 'CUSTOMER_EMAIL_HASH_BACKFILL_WORKFLOW_CUSTOMER_EMAIL_HASH_BACKFILL';
@@ -0,0 +1,38 @@
+-- Your SQL goes here
+-- The emails of customers are hashed with a key derived from the key of the merchant. The hashes
+-- stored before are cleared and computed again by the CUSTOMER_EMAIL_HASH_BACKFILL task.
+UPDATE customers
+SET email_hash = NULL
+WHERE email_hash IS NOT NULL;
+
+INSERT INTO process_tracker (
+    id,
+    name,
+    tag,
+    runner,
+    retry_count,
+    schedule_time,
+    rule,
+    tracking_data,
+    business_status,
+    status,
+    event,
+    created_at,
+    updated_at
+)
+VALUES (
+    'CUSTOMER_EMAIL_HASH_BACKFILL_WORKFLOW_CUSTOMER_EMAIL_HASH_BACKFILL',
+    'CUSTOMER_EMAIL_HASH_BACKFILL',
+    '{SYNC,PAYMENT}',
+    'CUSTOMER_EMAIL_HASH_BACKFILL_WORKFLOW',
+    0,
+    now(),
+    '',
+    '{"last_id": 0}',
+    'Pending',
+    'new',
+    '{}',
+    now(),
+    now()
+)
+ON CONFLICT (id) DO NOTHING;
@@ -0,0 +1,3 @@
+-- This file should undo anything in `up.sql`
+DELETE FROM process_tracker
+WHERE id = 'CONNECTOR_WEBHOOK_DETAILS_BACKFILL_WORKFLOW_CONNECTOR_WEBHOOK_DETAILS_BACKFILL';
@@ -0,0 +1,35 @@
+-- Your SQL goes here
+-- The webhook secrets of merchant connector accounts are stored encrypted. The plaintext webhook
+-- details of the accounts configured before are encrypted and cleared by the
+-- CONNECTOR_WEBHOOK_DETAILS_BACKFILL task.
+INSERT INTO process_tracker (
+    id,
+    name,
+    tag,
+    runner,
+    retry_count,
+    schedule_time,
+    rule,
+    tracking_data,
+    business_status,
+    status,
+    event,
+    created_at,
+    updated_at
+)
+VALUES (
+    'CONNECTOR_WEBHOOK_DETAILS_BACKFILL_WORKFLOW_CONNECTOR_WEBHOOK_DETAILS_BACKFILL',
+    'CONNECTOR_WEBHOOK_DETAILS_BACKFILL',
+    '{SYNC,PAYMENT}',
+    'CONNECTOR_WEBHOOK_DETAILS_BACKFILL_WORKFLOW',
+    0,
+    now(),
+    '',
+    '{"last_id": 0}',
+    'Pending',
+    'new',
+    '{}',
+    now(),
+    now()
+)
+ON CONFLICT (id) DO NOTHING;
@@ -0,0 +1,6 @@
+-- This file should undo anything in `up.sql`
+DROP INDEX IF EXISTS payout_batch_merchant_id_idempotency_key_index;
+
+ALTER TABLE payout_batch DROP COLUMN IF EXISTS errors;
+
+ALTER TABLE payout_batch DROP COLUMN IF EXISTS idempotency_key;
@@ -0,0 +1,6 @@
+-- Your SQL goes here
+ALTER TABLE payout_batch ADD COLUMN IF NOT EXISTS idempotency_key VARCHAR(128);
+
+ALTER TABLE payout_batch ADD COLUMN IF NOT EXISTS errors JSONB;
+
+CREATE UNIQUE INDEX IF NOT EXISTS payout_batch_merchant_id_idempotency_key_index ON payout_batch (merchant_id, idempotency_key);
@@ -0,0 +1,2 @@
+-- Your SQL goes here
+ALTER TYPE "GiftCardTransactionType" ADD VALUE IF NOT EXISTS 'redeem_forfeit';